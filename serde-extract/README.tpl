@@ -0,0 +1,23 @@
+# {{crate}}
+
+[![serde-extract on crates.io](https://img.shields.io/crates/v/serde-extract)](https://crates.io/crates/serde-extract)
+[![Documentation (latest release)](https://docs.rs/serde-extract/badge.svg)](https://docs.rs/serde-extract/)
+[![License](https://img.shields.io/badge/license-Apache-green.svg)](../LICENSE-APACHE)
+[![License](https://img.shields.io/badge/license-MIT-green.svg)](../LICENSE-MIT)
+
+{{readme}}
+
+## Contributing
+
+See the [CONTRIBUTING](../CONTRIBUTING.md) file for how to help out.
+
+## License
+
+This project is available under the terms of either the [Apache 2.0 license](../LICENSE-APACHE) or the [MIT license](../LICENSE-MIT).
+
+<!--
+README.md is generated from README.tpl by cargo readme. To regenerate:
+
+cargo install cargo-readme
+cargo readme > README.md
+-->
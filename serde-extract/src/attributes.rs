@@ -0,0 +1,156 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Parsing of `#[serde(...)]` attributes.
+
+use crate::case::RenameRule;
+use syn::{Attribute, LitStr};
+
+/// The Serde attributes of a container, a variant, or a field.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SerdeAttributes {
+    pub(crate) rename: Option<String>,
+    pub(crate) rename_all: Option<RenameRule>,
+    pub(crate) rename_all_fields: Option<RenameRule>,
+    pub(crate) skip: bool,
+    pub(crate) skip_serializing: bool,
+    pub(crate) skip_deserializing: bool,
+    pub(crate) with: Option<String>,
+    pub(crate) transparent: bool,
+    pub(crate) tag: Option<String>,
+    pub(crate) content: Option<String>,
+    pub(crate) untagged: bool,
+    /// Attributes that cannot be interpreted statically.
+    pub(crate) unsupported: Vec<String>,
+    /// Parsing errors. The attributes parsed successfully are kept.
+    pub(crate) errors: Vec<syn::Error>,
+}
+
+/// Attributes that do not change the serialization format.
+const IGNORED: &[&str] = &[
+    "alias",
+    "borrow",
+    "bound",
+    "crate",
+    "default",
+    "deny_unknown_fields",
+    "expecting",
+    "field_identifier",
+    "other",
+    "variant_identifier",
+];
+
+impl SerdeAttributes {
+    pub(crate) fn parse(attrs: &[Attribute]) -> Self {
+        let mut result = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("serde") {
+                continue;
+            }
+            let parsed = attr.parse_nested_meta(|meta| {
+                let key = match meta.path.get_ident() {
+                    Some(ident) => ident.to_string(),
+                    None => {
+                        result.unsupported.push(path_to_string(&meta.path));
+                        return skip_value(&meta);
+                    }
+                };
+                match key.as_str() {
+                    "rename" | "rename_all" | "rename_all_fields" | "with" | "tag" | "content"
+                        if meta.input.peek(syn::Token![=]) =>
+                    {
+                        let value = meta.value()?.parse::<LitStr>()?.value();
+                        match key.as_str() {
+                            "rename" => result.rename = Some(value),
+                            "with" => result.with = Some(value),
+                            "tag" => result.tag = Some(value),
+                            "content" => result.content = Some(value),
+                            _ => match RenameRule::parse(&value) {
+                                Some(rule) if key == "rename_all" => result.rename_all = Some(rule),
+                                Some(rule) => result.rename_all_fields = Some(rule),
+                                None => result
+                                    .errors
+                                    .push(meta.error(format!("unknown rename rule {}", value))),
+                            },
+                        }
+                        Ok(())
+                    }
+                    "skip" => {
+                        result.skip = true;
+                        Ok(())
+                    }
+                    "skip_serializing" => {
+                        result.skip_serializing = true;
+                        Ok(())
+                    }
+                    "skip_deserializing" => {
+                        result.skip_deserializing = true;
+                        Ok(())
+                    }
+                    "transparent" => {
+                        result.transparent = true;
+                        Ok(())
+                    }
+                    "untagged" => {
+                        result.untagged = true;
+                        Ok(())
+                    }
+                    key if IGNORED.contains(&key) => skip_value(&meta),
+                    _ => {
+                        result.unsupported.push(key);
+                        skip_value(&meta)
+                    }
+                }
+            });
+            if let Err(error) = parsed {
+                result.errors.push(error);
+            }
+        }
+        result
+    }
+
+    /// Whether the attributes request `serde_bytes` for the annotated field.
+    pub(crate) fn is_serde_bytes(&self) -> bool {
+        self.with.as_deref() == Some("serde_bytes")
+    }
+}
+
+/// Consume the value of an attribute, if any, e.g. `= "..."` or `(...)`.
+fn skip_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        content.parse::<proc_macro2::TokenStream>()?;
+    }
+    Ok(())
+}
+
+fn path_to_string(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Whether the item derives `Serialize` or `Deserialize`.
+pub(crate) fn derives_serde(attrs: &[Attribute]) -> bool {
+    let mut found = false;
+    for attr in attrs {
+        if !attr.path().is_ident("derive") {
+            continue;
+        }
+        // Ignore malformed derives.
+        let _ = attr.parse_nested_meta(|meta| {
+            if let Some(segment) = meta.path.segments.last() {
+                if segment.ident == "Serialize" || segment.ident == "Deserialize" {
+                    found = true;
+                }
+            }
+            Ok(())
+        });
+    }
+    found
+}
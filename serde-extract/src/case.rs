@@ -0,0 +1,89 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Renaming rules of `#[serde(rename_all = "...")]`, following the conventions of `serde_derive`.
+
+/// A renaming rule applied to all the fields or variants of a container.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub(crate) fn parse(rule: &str) -> Option<Self> {
+        use RenameRule::*;
+        let rule = match rule {
+            "lowercase" => Lower,
+            "UPPERCASE" => Upper,
+            "PascalCase" => Pascal,
+            "camelCase" => Camel,
+            "snake_case" => Snake,
+            "SCREAMING_SNAKE_CASE" => ScreamingSnake,
+            "kebab-case" => Kebab,
+            "SCREAMING-KEBAB-CASE" => ScreamingKebab,
+            _ => return None,
+        };
+        Some(rule)
+    }
+
+    /// Apply the rule to a variant name, assumed to be in Pascal.
+    pub(crate) fn apply_to_variant(self, variant: &str) -> String {
+        use RenameRule::*;
+        match self {
+            Pascal => variant.to_string(),
+            Lower => variant.to_ascii_lowercase(),
+            Upper => variant.to_ascii_uppercase(),
+            Camel => variant[..1].to_ascii_lowercase() + &variant[1..],
+            Snake => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            ScreamingSnake => Snake.apply_to_variant(variant).to_ascii_uppercase(),
+            Kebab => Snake.apply_to_variant(variant).replace('_', "-"),
+            ScreamingKebab => ScreamingSnake.apply_to_variant(variant).replace('_', "-"),
+        }
+    }
+
+    /// Apply the rule to a field name, assumed to be in snake_case.
+    pub(crate) fn apply_to_field(self, field: &str) -> String {
+        use RenameRule::*;
+        match self {
+            Lower | Snake => field.to_string(),
+            Upper | ScreamingSnake => field.to_ascii_uppercase(),
+            Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            Camel => {
+                let pascal = Pascal.apply_to_field(field);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            }
+            Kebab => field.replace('_', "-"),
+            ScreamingKebab => ScreamingSnake.apply_to_field(field).replace('_', "-"),
+        }
+    }
+}
@@ -0,0 +1,552 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{
+    attributes::{derives_serde, SerdeAttributes},
+    case::RenameRule,
+};
+use quote::ToTokens;
+use serde_reflection::{
+    ContainerFormat, EnumRepresentation, Format, Named, Registry, VariantFormat,
+};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Path, PathBuf},
+};
+use syn::{Fields, GenericArgument, Item, PathArguments, Type};
+use thiserror::Error;
+
+/// Error while reading Rust sources.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Failed to read {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("Failed to parse Rust source: {0}")]
+    Parse(#[from] syn::Error),
+}
+
+/// Result type used in this crate.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Something that could not be resolved statically.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// Location of the problem, e.g. `Foo.field` or `Foo::Variant`.
+    pub path: String,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// The result of an extraction.
+#[derive(Clone, Debug, Default)]
+pub struct Extraction {
+    /// Formats of the containers found in the sources.
+    pub registry: Registry,
+    /// Problems found during the extraction. Unresolved formats are represented by the
+    /// name of the corresponding Rust type, so that the registry remains usable.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Collect Serde containers from Rust sources, then extract their formats.
+#[derive(Default)]
+pub struct Extractor {
+    /// Items deriving `Serialize` or `Deserialize`, in the order of the sources.
+    definitions: Vec<Definition>,
+    /// Type aliases, indexed by name.
+    aliases: BTreeMap<String, Type>,
+}
+
+struct Definition {
+    /// Enclosing modules (for diagnostics).
+    module: Vec<String>,
+    item: Item,
+}
+
+impl Definition {
+    fn ident(&self) -> String {
+        match &self.item {
+            Item::Struct(item) => item.ident.to_string(),
+            Item::Enum(item) => item.ident.to_string(),
+            _ => unreachable!("only structs and enums are collected"),
+        }
+    }
+
+    fn attrs(&self) -> &[syn::Attribute] {
+        match &self.item {
+            Item::Struct(item) => &item.attrs,
+            Item::Enum(item) => &item.attrs,
+            _ => unreachable!("only structs and enums are collected"),
+        }
+    }
+
+    fn generics(&self) -> &syn::Generics {
+        match &self.item {
+            Item::Struct(item) => &item.generics,
+            Item::Enum(item) => &item.generics,
+            _ => unreachable!("only structs and enums are collected"),
+        }
+    }
+}
+
+impl Extractor {
+    /// Create a new extractor.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the items of a Rust source file. Inline modules are explored recursively.
+    pub fn add_source(&mut self, source: &str) -> Result<()> {
+        let file = syn::parse_file(source)?;
+        self.add_items(&[], file.items);
+        Ok(())
+    }
+
+    /// Read and add the items of the Rust source file at `path`.
+    pub fn add_file(&mut self, path: &Path) -> Result<()> {
+        let source = std::fs::read_to_string(path).map_err(|e| Error::Io(path.to_path_buf(), e))?;
+        self.add_source(&source)
+    }
+
+    fn add_items(&mut self, module: &[String], items: Vec<Item>) {
+        for item in items {
+            match item {
+                Item::Struct(ref x) if derives_serde(&x.attrs) => {
+                    self.definitions.push(Definition {
+                        module: module.to_vec(),
+                        item,
+                    });
+                }
+                Item::Enum(ref x) if derives_serde(&x.attrs) => {
+                    self.definitions.push(Definition {
+                        module: module.to_vec(),
+                        item,
+                    });
+                }
+                Item::Type(x) if x.generics.params.is_empty() => {
+                    self.aliases.insert(x.ident.to_string(), *x.ty);
+                }
+                Item::Mod(x) => {
+                    if let Some((_, items)) = x.content {
+                        let mut module = module.to_vec();
+                        module.push(x.ident.to_string());
+                        self.add_items(&module, items);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Compute the formats of all the containers collected so far.
+    pub fn extract(&self) -> Extraction {
+        let mut context = Context {
+            extractor: self,
+            definitions: BTreeMap::new(),
+            diagnostics: Vec::new(),
+            generic_params: HashSet::new(),
+            transparent_structs: HashSet::new(),
+        };
+        for definition in &self.definitions {
+            let ident = definition.ident();
+            if let Some(previous) = context.definitions.insert(ident.clone(), definition) {
+                context.diagnostics.push(Diagnostic {
+                    path: ident,
+                    message: format!(
+                        "type defined several times (in modules `{}` and `{}`)",
+                        previous.module.join("::"),
+                        definition.module.join("::")
+                    ),
+                });
+            }
+        }
+        let mut registry = Registry::new();
+        for definition in &self.definitions {
+            context.extract_container(&mut registry, definition);
+        }
+        Extraction {
+            registry,
+            diagnostics: context.diagnostics,
+        }
+    }
+}
+
+struct Context<'a> {
+    extractor: &'a Extractor,
+    /// Definitions indexed by Rust name.
+    definitions: BTreeMap<String, &'a Definition>,
+    diagnostics: Vec<Diagnostic>,
+    /// Type parameters of the current container.
+    generic_params: HashSet<String>,
+    /// Transparent structs being resolved, to detect recursive definitions.
+    transparent_structs: HashSet<String>,
+}
+
+impl<'a> Context<'a> {
+    fn report(&mut self, path: &str, message: String) {
+        self.diagnostics.push(Diagnostic {
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    fn parse_attributes(&mut self, path: &str, attrs: &[syn::Attribute]) -> SerdeAttributes {
+        let attributes = SerdeAttributes::parse(attrs);
+        for error in &attributes.errors {
+            self.report(path, format!("invalid Serde attribute: {}", error));
+        }
+        for key in &attributes.unsupported {
+            self.report(path, format!("unsupported attribute `{}`", key));
+        }
+        attributes
+    }
+
+    /// Serde name of the container defined by `definition`.
+    fn container_name(definition: &Definition) -> String {
+        SerdeAttributes::parse(definition.attrs())
+            .rename
+            .unwrap_or_else(|| definition.ident())
+    }
+
+    fn extract_container(&mut self, registry: &mut Registry, definition: &'a Definition) {
+        let name = Self::container_name(definition);
+        let attributes = self.parse_attributes(&name, definition.attrs());
+        if attributes.transparent {
+            // Transparent containers are resolved where they are used.
+            return;
+        }
+        self.generic_params = definition
+            .generics()
+            .type_params()
+            .map(|param| param.ident.to_string())
+            .collect();
+        if !self.generic_params.is_empty() {
+            self.report(
+                &name,
+                "generic types must be traced for each instantiation".to_string(),
+            );
+        }
+        let format = match &definition.item {
+            Item::Struct(item) => match &item.fields {
+                Fields::Unit => ContainerFormat::UnitStruct,
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    let formats = self.extract_unnamed_fields(&name, &item.fields);
+                    match formats.into_iter().next() {
+                        Some(format) => ContainerFormat::NewTypeStruct(Box::new(format)),
+                        None => {
+                            self.report(&name, "skipped field in newtype struct".to_string());
+                            ContainerFormat::UnitStruct
+                        }
+                    }
+                }
+                Fields::Unnamed(_) => {
+                    ContainerFormat::TupleStruct(self.extract_unnamed_fields(&name, &item.fields))
+                }
+                Fields::Named(_) => ContainerFormat::Struct(self.extract_named_fields(
+                    &name,
+                    &item.fields,
+                    attributes.rename_all,
+                )),
+            },
+            Item::Enum(item) => {
+                let mut variants = BTreeMap::new();
+                for (index, variant) in item.variants.iter().enumerate() {
+                    let variant_ident = variant.ident.to_string();
+                    let path = format!("{}::{}", name, variant_ident);
+                    let variant_attributes = self.parse_attributes(&path, &variant.attrs);
+                    if variant_attributes.skip || variant_attributes.skip_serializing {
+                        continue;
+                    }
+                    let variant_name = match (&variant_attributes.rename, attributes.rename_all) {
+                        (Some(rename), _) => rename.clone(),
+                        (None, Some(rule)) => rule.apply_to_variant(&variant_ident),
+                        (None, None) => variant_ident,
+                    };
+                    let path = format!("{}::{}", name, variant_name);
+                    let rename_all = variant_attributes
+                        .rename_all
+                        .or(attributes.rename_all_fields);
+                    let format = match &variant.fields {
+                        Fields::Unit => VariantFormat::Unit,
+                        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                            let formats = self.extract_unnamed_fields(&path, &variant.fields);
+                            match formats.into_iter().next() {
+                                Some(format) => VariantFormat::NewType(Box::new(format)),
+                                None => VariantFormat::Unit,
+                            }
+                        }
+                        Fields::Unnamed(_) => VariantFormat::Tuple(
+                            self.extract_unnamed_fields(&path, &variant.fields),
+                        ),
+                        Fields::Named(_) => VariantFormat::Struct(self.extract_named_fields(
+                            &path,
+                            &variant.fields,
+                            rename_all,
+                        )),
+                    };
+                    variants.insert(
+                        index as u32,
+                        Named {
+                            name: variant_name,
+                            value: format,
+                        },
+                    );
+                }
+                match Self::enum_representation(&attributes) {
+                    Some(representation) => ContainerFormat::TaggedEnum {
+                        representation,
+                        variants,
+                    },
+                    None => ContainerFormat::Enum(variants),
+                }
+            }
+            _ => unreachable!("only structs and enums are collected"),
+        };
+        if registry.insert(name.clone(), format).is_some() {
+            self.report(&name, "container name used several times".to_string());
+        }
+        self.generic_params.clear();
+    }
+
+    fn enum_representation(attributes: &SerdeAttributes) -> Option<EnumRepresentation> {
+        match (&attributes.tag, &attributes.content, attributes.untagged) {
+            (_, _, true) => Some(EnumRepresentation::Untagged),
+            (Some(tag), Some(content), false) => Some(EnumRepresentation::Adjacent {
+                tag: tag.clone(),
+                content: content.clone(),
+            }),
+            (Some(tag), None, false) => Some(EnumRepresentation::Internal { tag: tag.clone() }),
+            _ => None,
+        }
+    }
+
+    fn extract_unnamed_fields(&mut self, path: &str, fields: &Fields) -> Vec<Format> {
+        let mut formats = Vec::new();
+        for (i, field) in fields.iter().enumerate() {
+            let path = format!("{}.{}", path, i);
+            if let Some(format) = self.extract_field(&path, field) {
+                formats.push(format);
+            }
+        }
+        formats
+    }
+
+    fn extract_named_fields(
+        &mut self,
+        path: &str,
+        fields: &Fields,
+        rename_all: Option<RenameRule>,
+    ) -> Vec<Named<Format>> {
+        let mut formats = Vec::new();
+        for field in fields {
+            let ident = field
+                .ident
+                .as_ref()
+                .expect("named fields have identifiers")
+                .to_string();
+            // Raw identifiers are serialized without their prefix.
+            let ident = ident.trim_start_matches("r#").to_string();
+            let attributes = SerdeAttributes::parse(&field.attrs);
+            let name = match (attributes.rename, rename_all) {
+                (Some(rename), _) => rename,
+                (None, Some(rule)) => rule.apply_to_field(&ident),
+                (None, None) => ident,
+            };
+            let path = format!("{}.{}", path, name);
+            if let Some(format) = self.extract_field(&path, field) {
+                formats.push(Named {
+                    name,
+                    value: format,
+                });
+            }
+        }
+        formats
+    }
+
+    /// Compute the format of a field, unless the field is skipped.
+    fn extract_field(&mut self, path: &str, field: &syn::Field) -> Option<Format> {
+        let attributes = self.parse_attributes(path, &field.attrs);
+        if attributes.skip || attributes.skip_serializing {
+            return None;
+        }
+        if attributes.skip_deserializing {
+            self.report(
+                path,
+                "fields skipped only during deserialization are not supported".to_string(),
+            );
+        }
+        let format = self.resolve_type(path, &field.ty);
+        match &attributes.with {
+            None => Some(format),
+            Some(_) if attributes.is_serde_bytes() => match to_bytes(&format) {
+                Some(format) => Some(format),
+                None => {
+                    self.report(path, "unexpected type for `serde_bytes`".to_string());
+                    Some(format)
+                }
+            },
+            Some(with) => {
+                self.report(path, format!("custom (de)serialization with `{}`", with));
+                Some(format)
+            }
+        }
+    }
+
+    fn unresolved(&mut self, path: &str, ty: &Type, message: String) -> Format {
+        self.report(path, message);
+        Format::TypeName(
+            ty.to_token_stream()
+                .to_string()
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect(),
+        )
+    }
+
+    fn resolve_type(&mut self, path: &str, ty: &Type) -> Format {
+        match ty {
+            Type::Reference(reference) => match reference.elem.as_ref() {
+                Type::Path(type_path) if type_path.path.is_ident("str") => Format::Str,
+                elem => self.resolve_type(path, elem),
+            },
+            Type::Slice(slice) => Format::Seq(Box::new(self.resolve_type(path, &slice.elem))),
+            Type::Array(array) => match &array.len {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(size),
+                    ..
+                }) => match size.base10_parse::<usize>() {
+                    Ok(size) => Format::TupleArray {
+                        content: Box::new(self.resolve_type(path, &array.elem)),
+                        size,
+                    },
+                    Err(_) => self.unresolved(path, ty, "invalid array size".to_string()),
+                },
+                _ => self.unresolved(path, ty, "array size is not a literal".to_string()),
+            },
+            Type::Tuple(tuple) if tuple.elems.is_empty() => Format::Unit,
+            Type::Tuple(tuple) => Format::Tuple(
+                tuple
+                    .elems
+                    .iter()
+                    .map(|elem| self.resolve_type(path, elem))
+                    .collect(),
+            ),
+            Type::Paren(paren) => self.resolve_type(path, &paren.elem),
+            Type::Group(group) => self.resolve_type(path, &group.elem),
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                self.resolve_path(path, ty, &type_path.path)
+            }
+            _ => self.unresolved(path, ty, "unsupported type".to_string()),
+        }
+    }
+
+    fn resolve_path(&mut self, path: &str, ty: &Type, type_path: &syn::Path) -> Format {
+        let segment = type_path.segments.last().expect("paths are not empty");
+        let ident = segment.ident.to_string();
+        let args = match &segment.arguments {
+            PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        if type_path.segments.len() == 1 && self.generic_params.contains(&ident) {
+            return self.unresolved(path, ty, format!("generic parameter `{}`", ident));
+        }
+        match (ident.as_str(), args.as_slice()) {
+            ("bool", []) => Format::Bool,
+            ("i8", []) => Format::I8,
+            ("i16", []) => Format::I16,
+            ("i32", []) => Format::I32,
+            ("i64" | "isize", []) => Format::I64,
+            ("i128", []) => Format::I128,
+            ("u8", []) => Format::U8,
+            ("u16", []) => Format::U16,
+            ("u32", []) => Format::U32,
+            ("u64" | "usize", []) => Format::U64,
+            ("u128", []) => Format::U128,
+            ("f32", []) => Format::F32,
+            ("f64", []) => Format::F64,
+            ("char", []) => Format::Char,
+            ("String" | "str", []) => Format::Str,
+            ("ByteBuf" | "Bytes", []) => Format::Bytes,
+            ("Option", [content]) => Format::Option(Box::new(self.resolve_type(path, content))),
+            (
+                "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" | "BinaryHeap"
+                | "IndexSet",
+                [content],
+            ) => Format::Seq(Box::new(self.resolve_type(path, content))),
+            ("HashMap" | "BTreeMap" | "IndexMap", [key, value, ..]) => Format::Map {
+                key: Box::new(self.resolve_type(path, key)),
+                value: Box::new(self.resolve_type(path, value)),
+            },
+            ("Box" | "Rc" | "Arc" | "Cow" | "Cell" | "RefCell", [content]) => {
+                self.resolve_type(path, content)
+            }
+            (_, []) => match self.extractor.aliases.get(&ident) {
+                Some(alias) => self.resolve_type(path, alias),
+                None => self.resolve_definition(path, ty, &ident),
+            },
+            _ => self.resolve_definition(path, ty, &ident),
+        }
+    }
+
+    fn resolve_definition(&mut self, path: &str, ty: &Type, ident: &str) -> Format {
+        let definition = match self.definitions.get(ident) {
+            Some(definition) => *definition,
+            None => {
+                return self.unresolved(
+                    path,
+                    ty,
+                    format!("type `{}` not found in the sources", ident),
+                )
+            }
+        };
+        if !SerdeAttributes::parse(definition.attrs()).transparent {
+            return Format::TypeName(Self::container_name(definition));
+        }
+        let fields = match &definition.item {
+            Item::Struct(item) => &item.fields,
+            _ => return self.unresolved(path, ty, "transparent enum".to_string()),
+        };
+        if !self.transparent_structs.insert(ident.to_string()) {
+            return self.unresolved(
+                path,
+                ty,
+                format!("recursive transparent struct `{}`", ident),
+            );
+        }
+        // The format of a transparent struct is the format of its only non-skipped field.
+        let field = fields.iter().find(|field| {
+            let attributes = SerdeAttributes::parse(&field.attrs);
+            !attributes.skip && !attributes.skip_serializing
+        });
+        let format = match field {
+            Some(field) => self.resolve_type(path, &field.ty),
+            None => self.unresolved(path, ty, "transparent struct without fields".to_string()),
+        };
+        self.transparent_structs.remove(ident);
+        format
+    }
+}
+
+/// The format of a field annotated with `#[serde(with = "serde_bytes")]`.
+fn to_bytes(format: &Format) -> Option<Format> {
+    match format {
+        Format::Bytes => Some(Format::Bytes),
+        Format::Seq(content) | Format::TupleArray { content, .. } if **content == Format::U8 => {
+            Some(Format::Bytes)
+        }
+        Format::Option(content) => Some(Format::Option(Box::new(to_bytes(content)?))),
+        _ => None,
+    }
+}
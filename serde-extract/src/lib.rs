@@ -0,0 +1,72 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! This crate extracts Serde formats from Rust source code, without compiling or running it.
+//!
+//! Tracing with [`serde-reflection`](https://crates.io/crates/serde-reflection) requires a binary
+//! that links every traced type. In large workspaces, or for types behind heavy dependencies, it
+//! may be more convenient to parse the definitions of `#[derive(Serialize, Deserialize)]` items
+//! directly:
+//!
+//! ```rust
+//! # fn main() -> Result<(), serde_extract::Error> {
+//! use serde_extract::Extractor;
+//! use serde_reflection::{ContainerFormat, Format, Named};
+//!
+//! let mut extractor = Extractor::new();
+//! extractor.add_source(r#"
+//!     #[derive(Serialize, Deserialize)]
+//!     struct Test {
+//!         #[serde(rename = "A")]
+//!         a: Vec<u64>,
+//!         #[serde(with = "serde_bytes")]
+//!         b: Vec<u8>,
+//!         #[serde(skip)]
+//!         c: u32,
+//!     }
+//! "#)?;
+//! let extraction = extractor.extract();
+//! assert!(extraction.diagnostics.is_empty());
+//! assert_eq!(
+//!     extraction.registry.get("Test").unwrap(),
+//!     &ContainerFormat::Struct(vec![
+//!         Named { name: "A".into(), value: Format::Seq(Box::new(Format::U64)) },
+//!         Named { name: "b".into(), value: Format::Bytes },
+//!     ])
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Supported idioms
+//!
+//! * Structs and enums deriving `Serialize` or `Deserialize`, possibly nested in inline modules.
+//!
+//! * Standard types (integers, strings, `Option`, `Vec`, maps, sets, tuples, arrays, `Box`, etc),
+//!   `serde_bytes::ByteBuf`, and non-generic type aliases.
+//!
+//! * The attributes `rename`, `rename_all`, `rename_all_fields`, `skip`, `skip_serializing`,
+//!   `transparent`, `tag`, `content`, `untagged`, and `with = "serde_bytes"`.
+//!
+//! ## Diagnostics
+//!
+//! Anything that cannot be resolved statically is reported in `extraction.diagnostics`, e.g.
+//! types defined outside of the parsed sources, generic types, custom `Serialize`
+//! implementations, or attributes such as `flatten` and `serialize_with`. Unresolved formats
+//! are represented by the name of the Rust type, so that the registry remains usable.
+//!
+//! Note that types implementing `Serialize` manually are not detected at all. The registry
+//! should therefore be cross-checked against the output of the tracer (when available) using
+//! `serde_reflection::diff_registries`, or with the binary tool:
+//!
+//! ```bash
+//! cargo run -p serde-extract -- src/lib.rs --check traced.yaml
+//! ```
+
+mod attributes;
+mod case;
+mod extract;
+
+pub use extract::{Diagnostic, Error, Extraction, Extractor, Result};
@@ -0,0 +1,67 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! # Serde format extractor
+//!
+//! '''bash
+//! cargo run -- --help
+//! '''
+
+use serde_extract::Extractor;
+use serde_reflection::Registry;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "Serde format extractor",
+    about = "Extract Serde formats from Rust source files"
+)]
+struct Options {
+    /// Rust source files to parse.
+    #[structopt(parse(from_os_str), required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Optional YAML-encoded Serde formats (e.g. traced with serde-reflection) to compare
+    /// the extracted formats with.
+    #[structopt(long, parse(from_os_str))]
+    check: Option<PathBuf>,
+
+    /// Fail if some formats could not be resolved.
+    #[structopt(long)]
+    strict: bool,
+}
+
+fn main() {
+    let options = Options::from_args();
+
+    let mut extractor = Extractor::new();
+    for input in &options.inputs {
+        extractor.add_file(input).unwrap();
+    }
+    let extraction = extractor.extract();
+    for diagnostic in &extraction.diagnostics {
+        eprintln!("warning: {}", diagnostic);
+    }
+
+    match options.check {
+        None => {
+            let content = serde_yaml::to_string(&extraction.registry).unwrap();
+            println!("{}", content);
+        }
+        Some(path) => {
+            let content = std::fs::read_to_string(path).expect("input file must be readable");
+            let traced = serde_yaml::from_str::<Registry>(content.as_str()).unwrap();
+            let differences = serde_reflection::diff_registries(&extraction.registry, &traced);
+            for difference in &differences {
+                eprintln!("difference: {}", difference);
+            }
+            if !differences.is_empty() {
+                std::process::exit(1);
+            }
+        }
+    }
+    if options.strict && !extraction.diagnostics.is_empty() {
+        std::process::exit(1);
+    }
+}
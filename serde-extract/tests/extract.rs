@@ -0,0 +1,232 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use serde_extract::{Diagnostic, Extractor};
+use serde_reflection::{
+    diff_registries, ContainerFormat, EnumRepresentation, Format, Named, Samples, Tracer,
+    TracerConfig,
+};
+use std::collections::BTreeMap;
+
+// Define the items and keep their source code.
+macro_rules! with_source {
+    ($($item:item)*) => {
+        $($item)*
+
+        const SOURCE: &str = stringify!($($item)*);
+    };
+}
+
+with_source! {
+    type Amount = u64;
+
+    #[derive(Serialize, Deserialize)]
+    struct Unit;
+
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper(#[serde(with = "serde_bytes")] Vec<u8>);
+
+    #[derive(Serialize, Deserialize)]
+    struct Pair(u32, Option<String>);
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Id(u128);
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Account", rename_all = "camelCase")]
+    struct AccountState {
+        owner_id: Id,
+        balance: Amount,
+        #[serde(skip)]
+        cache: Vec<u8>,
+        history: BTreeMap<u64, (i8, [u16; 2])>,
+        wrapped: Box<Wrapper>,
+        #[serde(rename = "Pair")]
+        pair: Option<Pair>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Limits {
+        max_amount: Amount,
+        daily_count: u8,
+    }
+
+    mod inner {
+        use super::*;
+
+        #[derive(Serialize, Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        pub enum Choice {
+            FirstOption(super::Unit),
+            SecondOption { #[serde(rename = "X")] x: bool },
+            #[serde(rename = "third")]
+            ThirdOption(char, f64),
+            Fourth,
+        }
+    }
+}
+
+#[test]
+fn test_extraction_matches_tracing() {
+    let mut extractor = Extractor::new();
+    extractor.add_source(SOURCE).unwrap();
+    let extraction = extractor.extract();
+    assert_eq!(extraction.diagnostics, Vec::new());
+    assert!(!extraction.registry.contains_key("Id"));
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let samples = Samples::new();
+    tracer.trace_type::<AccountState>(&samples).unwrap();
+    tracer.trace_type::<inner::Choice>(&samples).unwrap();
+    tracer.trace_type::<Limits>(&samples).unwrap();
+    let registry = tracer.registry().unwrap();
+
+    assert_eq!(diff_registries(&extraction.registry, &registry), Vec::new());
+    assert_eq!(extraction.registry, registry);
+}
+
+#[test]
+fn test_diagnostics() {
+    let mut extractor = Extractor::new();
+    extractor
+        .add_source(
+            r#"
+            #[derive(Serialize)]
+            struct Foo<T> {
+                a: T,
+                b: std::time::Duration,
+                #[serde(flatten)]
+                c: Bar,
+                #[serde(serialize_with = "custom")]
+                d: u8,
+            }
+
+            #[derive(Deserialize)]
+            #[serde(tag = "type")]
+            enum Bar {
+                A { x: u8 },
+            }
+            "#,
+        )
+        .unwrap();
+    let extraction = extractor.extract();
+    let diagnostic = |path: &str, message: &str| Diagnostic {
+        path: path.into(),
+        message: message.into(),
+    };
+    assert_eq!(
+        extraction.diagnostics,
+        vec![
+            diagnostic("Foo", "generic types must be traced for each instantiation"),
+            diagnostic("Foo.a", "generic parameter `T`"),
+            diagnostic("Foo.b", "type `Duration` not found in the sources"),
+            diagnostic("Foo.c", "unsupported attribute `flatten`"),
+            diagnostic("Foo.d", "unsupported attribute `serialize_with`"),
+        ]
+    );
+    assert_eq!(
+        extraction.registry.get("Foo").unwrap(),
+        &ContainerFormat::Struct(vec![
+            Named {
+                name: "a".into(),
+                value: Format::TypeName("T".into()),
+            },
+            Named {
+                name: "b".into(),
+                value: Format::TypeName("std::time::Duration".into()),
+            },
+            Named {
+                name: "c".into(),
+                value: Format::TypeName("Bar".into()),
+            },
+            Named {
+                name: "d".into(),
+                value: Format::U8,
+            },
+        ])
+    );
+    assert!(matches!(
+        extraction.registry.get("Bar").unwrap(),
+        ContainerFormat::TaggedEnum {
+            representation: EnumRepresentation::Internal { .. },
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_parse_error() {
+    let mut extractor = Extractor::new();
+    assert!(extractor.add_source("struct {").is_err());
+}
+
+#[test]
+fn test_invalid_attributes_are_partially_kept() {
+    let mut extractor = Extractor::new();
+    extractor
+        .add_source(
+            r#"
+            #[derive(Serialize)]
+            #[serde(rename = "Bar", rename_all = "Unknown")]
+            struct Foo {
+                #[serde(skip)]
+                a: u8,
+                #[serde(rename = 1)]
+                b: u8,
+            }
+            "#,
+        )
+        .unwrap();
+    let extraction = extractor.extract();
+    assert_eq!(extraction.diagnostics.len(), 2);
+    assert!(extraction.diagnostics[0]
+        .message
+        .contains("unknown rename rule Unknown"));
+    assert_eq!(extraction.diagnostics[1].path, "Bar.b");
+    assert_eq!(
+        extraction.registry.get("Bar").unwrap(),
+        &ContainerFormat::Struct(vec![Named {
+            name: "b".into(),
+            value: Format::U8,
+        }])
+    );
+}
+
+#[test]
+fn test_recursive_transparent_struct() {
+    let mut extractor = Extractor::new();
+    extractor
+        .add_source(
+            r#"
+            #[derive(Serialize)]
+            #[serde(transparent)]
+            struct A(Vec<A>);
+
+            #[derive(Serialize)]
+            struct B {
+                a: A,
+            }
+            "#,
+        )
+        .unwrap();
+    let extraction = extractor.extract();
+    assert_eq!(
+        extraction.diagnostics,
+        vec![Diagnostic {
+            path: "B.a".into(),
+            message: "recursive transparent struct `A`".into(),
+        }]
+    );
+    assert_eq!(
+        extraction.registry.get("B").unwrap(),
+        &ContainerFormat::Struct(vec![Named {
+            name: "a".into(),
+            value: Format::Seq(Box::new(Format::TypeName("A".into()))),
+        }])
+    );
+}
@@ -9,21 +9,27 @@
 //! '''
 
 use serde_generate::{
-    cpp, csharp, dart, golang, java, ocaml, python3, rust, solidity, swift, typescript,
-    CodeGeneratorConfig, Encoding, SourceInstaller,
+    c, cpp, csharp, dart, elixir, golang, haskell, java, kotlin, ocaml, python3, ruby, rust,
+    solidity, swift, typescript, BincodeOptions, Case, CodeGeneratorConfig, Encoding, Endianness,
+    IntEncoding, NamingConventions, SourceInstaller, TrailingBytes,
 };
-use serde_reflection::Registry;
-use std::path::PathBuf;
+use serde_reflection::{Dual, Registry};
+use std::{io::Write, path::PathBuf};
 use structopt::{clap::arg_enum, StructOpt};
 
 arg_enum! {
 #[derive(Debug, StructOpt)]
 enum Language {
     Python3,
+    Ruby,
+    Haskell,
+    Elixir,
+    C,
     Cpp,
     Solidity,
     Rust,
     Java,
+    Kotlin,
     Go,
     Dart,
     TypeScript,
@@ -39,9 +45,38 @@ enum Runtime {
     Serde,
     Bincode,
     Bcs,
+    Postcard,
+    Borsh,
+    Scale,
+    Json,
 }
 }
 
+arg_enum! {
+#[derive(Debug, StructOpt, Clone, Copy)]
+enum NameCase {
+    Preserve,
+    UpperCamel,
+    LowerCamel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+}
+}
+
+impl From<NameCase> for Case {
+    fn from(case: NameCase) -> Self {
+        match case {
+            NameCase::Preserve => Case::Preserve,
+            NameCase::UpperCamel => Case::UpperCamel,
+            NameCase::LowerCamel => Case::LowerCamel,
+            NameCase::Snake => Case::Snake,
+            NameCase::ScreamingSnake => Case::ScreamingSnake,
+            NameCase::Kebab => Case::Kebab,
+        }
+    }
+}
+
 #[derive(Debug, StructOpt)]
 #[structopt(
     name = "Serde code generator",
@@ -52,6 +87,13 @@ struct Options {
     #[structopt(parse(from_os_str))]
     input: Option<PathBuf>,
 
+    /// Path to the YAML-encoded Serde formats traced in the human-readable mode of Serde
+    /// (e.g. by `serde_reflection::DualTracer`). The formats of `input` are then the ones of
+    /// the binary mode, and the JSON encoding uses the human-readable ones (in a separate module
+    /// with the suffix `_json` if both registries differ).
+    #[structopt(long, parse(from_os_str))]
+    human_readable_input: Option<PathBuf>,
+
     /// Language for code generation.
     #[structopt(long, possible_values = &Language::variants(), case_insensitive = true, default_value = "Python3")]
     language: Language,
@@ -84,13 +126,88 @@ struct Options {
     /// Takes effect only for languages that have a package manifest format.
     #[structopt(long)]
     skip_package_manifest: bool,
+
+    /// Generate structural equality, hashing and ordering consistent with Rust (if supported by
+    /// the target language).
+    #[structopt(long)]
+    with_comparisons: bool,
+
+    /// Generate methods validating values against the constraints of the registry.
+    #[structopt(long)]
+    with_validation: bool,
+
+    /// Validate values before serializing them (implies `--with-validation`).
+    #[structopt(long)]
+    validate_on_serialize: bool,
+
+    /// Naming convention for the names of generated types.
+    #[structopt(long, possible_values = &NameCase::variants(), case_insensitive = true, default_value = "Preserve")]
+    type_case: NameCase,
+
+    /// Naming convention for the names of struct fields.
+    #[structopt(long, possible_values = &NameCase::variants(), case_insensitive = true, default_value = "Preserve")]
+    field_case: NameCase,
+
+    /// Naming convention for the names of enum variants.
+    #[structopt(long, possible_values = &NameCase::variants(), case_insensitive = true, default_value = "Preserve")]
+    variant_case: NameCase,
+
+    /// Naming convention for the names of the source files generated for each type (if applicable).
+    #[structopt(long, possible_values = &NameCase::variants(), case_insensitive = true, default_value = "Preserve")]
+    file_case: NameCase,
+
+    /// Encode Bincode integers, lengths and variant indices with a variable number of bytes.
+    #[structopt(long)]
+    bincode_varint_encoding: bool,
+
+    /// Encode Bincode numbers in big-endian byte order.
+    #[structopt(long)]
+    bincode_big_endian: bool,
+
+    /// Maximum number of bytes of a Bincode-encoded value.
+    #[structopt(long)]
+    bincode_limit: Option<u64>,
+
+    /// Accept Bincode inputs with bytes left after the decoded value.
+    #[structopt(long)]
+    bincode_allow_trailing_bytes: bool,
+}
+
+impl Options {
+    fn naming_conventions(&self) -> NamingConventions {
+        NamingConventions::default()
+            .with_types(self.type_case.into())
+            .with_fields(self.field_case.into())
+            .with_variants(self.variant_case.into())
+            .with_files(self.file_case.into())
+    }
+
+    fn bincode_options(&self) -> BincodeOptions {
+        let mut options = BincodeOptions::default().with_limit(self.bincode_limit);
+        if self.bincode_varint_encoding {
+            options = options.with_int_encoding(IntEncoding::Varint);
+        }
+        if self.bincode_big_endian {
+            options = options.with_endianness(Endianness::Big);
+        }
+        if self.bincode_allow_trailing_bytes {
+            options = options.with_trailing_bytes(TrailingBytes::Allow);
+        }
+        options
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn get_codegen_config<'a, I>(
     name: String,
     runtimes: I,
     c_style_enums: bool,
     package_manifest: bool,
+    naming: NamingConventions,
+    comparisons: bool,
+    validation: bool,
+    validate_on_serialize: bool,
+    bincode: BincodeOptions,
 ) -> CodeGeneratorConfig
 where
     I: IntoIterator<Item = &'a Runtime>,
@@ -104,6 +221,18 @@ where
             Runtime::Bcs => {
                 encodings.push(Encoding::Bcs);
             }
+            Runtime::Postcard => {
+                encodings.push(Encoding::Postcard);
+            }
+            Runtime::Borsh => {
+                encodings.push(Encoding::Borsh);
+            }
+            Runtime::Scale => {
+                encodings.push(Encoding::Scale);
+            }
+            Runtime::Json => {
+                encodings.push(Encoding::Json);
+            }
             Runtime::Serde => (),
         }
     }
@@ -111,11 +240,33 @@ where
         .with_encodings(encodings)
         .with_c_style_enums(c_style_enums)
         .with_package_manifest(package_manifest)
+        .with_naming_conventions(naming)
+        .with_comparisons(comparisons)
+        .with_validation(validation)
+        .with_validate_on_serialize(validate_on_serialize)
+        .with_bincode_options(bincode)
 }
 
 fn main() {
     let options = Options::from_args();
+    if let Err(error) = run(options) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn read_registry(path: &std::path::Path) -> Result<Registry, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| format!("cannot read {}: {}", path.display(), error))?;
+    let registry = serde_yaml::from_str::<Registry>(content.as_str())
+        .map_err(|error| format!("invalid formats in {}: {}", path.display(), error))?;
+    Ok(registry)
+}
+
+fn run(options: Options) -> Result<(), Box<dyn std::error::Error>> {
     let serde_package_name_opt = options.serde_package_name.clone();
+    let naming = options.naming_conventions();
+    let bincode = options.bincode_options();
     let named_registry_opt = match &options.input {
         None => None,
         Some(input) => {
@@ -126,74 +277,88 @@ fn main() {
                     .to_string_lossy()
                     .into_owned()
             });
-            let content = std::fs::read_to_string(input).expect("input file must be readable");
-            let registry = serde_yaml::from_str::<Registry>(content.as_str()).unwrap();
-            Some((registry, name))
+            let binary = read_registry(input)?;
+            let human_readable = match &options.human_readable_input {
+                Some(path) => read_registry(path)?,
+                None => binary.clone(),
+            };
+            let registries = Dual {
+                human_readable,
+                binary,
+            };
+            Some((registries, name))
         }
     };
     let runtimes: std::collections::BTreeSet<_> = options.with_runtimes.into_iter().collect();
 
     match options.target_source_dir {
         None => {
-            if let Some((registry, name)) = named_registry_opt {
+            if let Some((registries, name)) = named_registry_opt {
                 let config = get_codegen_config(
                     name,
                     &runtimes,
                     options.use_c_style_enums,
                     !options.skip_package_manifest,
+                    naming,
+                    options.with_comparisons,
+                    options.with_validation,
+                    options.validate_on_serialize,
+                    bincode,
                 );
 
+                let files = config.generate_dual(&registries, |config| match options.language {
+                    Language::Python3 => Box::new(
+                        python3::CodeGenerator::new(config)
+                            .with_serde_package_name(serde_package_name_opt.clone()),
+                    ),
+                    Language::Ruby => Box::new(ruby::CodeGenerator::new(config)),
+                    Language::Haskell => Box::new(haskell::CodeGenerator::new(config)),
+                    Language::Elixir => Box::new(elixir::CodeGenerator::new(config)),
+                    Language::Rust => Box::new(rust::CodeGenerator::new(config)),
+                    Language::C => Box::new(c::CodeGenerator::new(config)),
+                    Language::Cpp => Box::new(cpp::CodeGenerator::new(config)),
+                    Language::Solidity => Box::new(solidity::CodeGenerator::new(config)),
+                    Language::Go => Box::new(golang::CodeGenerator::new(config)),
+                    Language::Java => Box::new(java::CodeGenerator::new(config)),
+                    Language::Kotlin => Box::new(kotlin::CodeGenerator::new(config)),
+                    Language::Dart => Box::new(dart::CodeGenerator::new(config)),
+                    Language::TypeScript => Box::new(typescript::CodeGenerator::new(config)),
+                    Language::CSharp => Box::new(csharp::CodeGenerator::new(config)),
+                    Language::Swift => Box::new(swift::CodeGenerator::new(config)),
+                    Language::OCaml => Box::new(ocaml::CodeGenerator::new(config)),
+                })?;
+
                 let stdout = std::io::stdout();
                 let mut out = stdout.lock();
-                match options.language {
-                    Language::Python3 => python3::CodeGenerator::new(&config)
-                        .with_serde_package_name(serde_package_name_opt)
-                        .output(&mut out, &registry)
-                        .unwrap(),
-                    Language::Rust => rust::CodeGenerator::new(&config)
-                        .output(&mut out, &registry)
-                        .unwrap(),
-                    Language::Cpp => cpp::CodeGenerator::new(&config)
-                        .output(&mut out, &registry)
-                        .unwrap(),
-                    Language::Solidity => solidity::CodeGenerator::new(&config)
-                        .output(&mut out, &registry)
-                        .unwrap(),
-                    Language::Go => golang::CodeGenerator::new(&config)
-                        .output(&mut out, &registry)
-                        .unwrap(),
-                    Language::Java => {
-                        panic!("Code generation in Java requires `--target-source-dir`")
-                    }
-                    Language::Dart => {
-                        panic!("Code generation in Dart requires `--target-source-dir`")
+                if files.len() == 1 {
+                    for (_, content) in files.iter() {
+                        write!(out, "{}", content)?;
                     }
-                    Language::TypeScript => typescript::CodeGenerator::new(&config)
-                        .output(&mut out, &registry)
-                        .unwrap(),
-                    Language::CSharp => {
-                        panic!("Code generation in C# requires `--target-source-dir`")
+                } else {
+                    // Print each file after a header, in the manner of `head`.
+                    for (path, content) in files.iter() {
+                        writeln!(out, "==> {} <==", path.display())?;
+                        writeln!(out, "{}", content)?;
                     }
-                    Language::Swift => swift::CodeGenerator::new(&config)
-                        .output(&mut out, &registry)
-                        .unwrap(),
-                    Language::OCaml => ocaml::CodeGenerator::new(&config)
-                        .output(&mut out, &registry)
-                        .unwrap(),
                 }
             }
         }
 
         Some(install_dir) => {
-            let installer: Box<dyn SourceInstaller<Error = Box<dyn std::error::Error>>> =
+            let installer: Box<dyn SourceInstaller<Error = serde_generate::Error>> =
                 match options.language {
                     Language::Python3 => {
                         Box::new(python3::Installer::new(install_dir, serde_package_name_opt))
                     }
+                    Language::Ruby => Box::new(ruby::Installer::new(install_dir)),
+                    Language::Haskell => Box::new(haskell::Installer::new(install_dir)),
+                    Language::Elixir => Box::new(elixir::Installer::new(install_dir)),
                     Language::Rust => Box::new(rust::Installer::new(install_dir)),
+                    Language::C => Box::new(c::Installer::new(install_dir)),
                     Language::Cpp => Box::new(cpp::Installer::new(install_dir)),
                     Language::Solidity => Box::new(solidity::Installer::new(install_dir)),
                     Language::Java => Box::new(java::Installer::new(install_dir)),
+                    Language::Kotlin => Box::new(kotlin::Installer::new(install_dir)),
                     Language::Go => {
                         Box::new(golang::Installer::new(install_dir, serde_package_name_opt))
                     }
@@ -204,23 +369,35 @@ fn main() {
                     Language::OCaml => Box::new(ocaml::Installer::new(install_dir)),
                 };
 
-            if let Some((registry, name)) = named_registry_opt {
+            if let Some((registries, name)) = named_registry_opt {
                 let config = get_codegen_config(
                     name,
                     &runtimes,
                     options.use_c_style_enums,
                     !options.skip_package_manifest,
+                    naming,
+                    options.with_comparisons,
+                    options.with_validation,
+                    options.validate_on_serialize,
+                    bincode,
                 );
-                installer.install_module(&config, &registry).unwrap();
+                for (config, registry) in config.split_modes(&registries) {
+                    installer.install_module(&config, registry)?;
+                }
             }
 
             for runtime in runtimes {
                 match runtime {
-                    Runtime::Serde => installer.install_serde_runtime().unwrap(),
-                    Runtime::Bincode => installer.install_bincode_runtime().unwrap(),
-                    Runtime::Bcs => installer.install_bcs_runtime().unwrap(),
+                    Runtime::Serde => installer.install_serde_runtime()?,
+                    Runtime::Bincode => installer.install_bincode_runtime()?,
+                    Runtime::Bcs => installer.install_bcs_runtime()?,
+                    Runtime::Postcard => installer.install_postcard_runtime()?,
+                    Runtime::Borsh => installer.install_borsh_runtime()?,
+                    Runtime::Scale => installer.install_scale_runtime()?,
+                    Runtime::Json => installer.install_json_runtime()?,
                 }
             }
         }
     }
+    Ok(())
 }
@@ -294,6 +294,63 @@ fn test_that_installed_cpp_code_compiles() {
     assert!(status.success());
 }
 
+#[test]
+fn test_that_java_code_is_printed_without_target_dir() {
+    let registry = test_utils::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let yaml_path = dir.path().join("test.yaml");
+    std::fs::write(yaml_path.clone(), serde_yaml::to_string(&registry).unwrap()).unwrap();
+
+    let output = Command::new("cargo")
+        .arg("run")
+        .arg("-p")
+        .arg("serde-generate-bin")
+        .arg("--")
+        .arg("--language")
+        .arg("java")
+        .arg("--module-name")
+        .arg("test.types")
+        .arg("--")
+        .arg(yaml_path)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("==> test/types/SerdeData.java <==\npackage test.types;"));
+    assert!(stdout.contains("==> test/types/TraitHelpers.java <=="));
+}
+
+#[test]
+fn test_that_invalid_human_readable_input_is_reported() {
+    let registry = test_utils::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let yaml_path = dir.path().join("test.yaml");
+    std::fs::write(yaml_path.clone(), serde_yaml::to_string(&registry).unwrap()).unwrap();
+    let invalid_path = dir.path().join("invalid.yaml");
+    std::fs::write(invalid_path.clone(), "- not a registry").unwrap();
+
+    let output = Command::new("cargo")
+        .arg("run")
+        .arg("-p")
+        .arg("serde-generate-bin")
+        .arg("--")
+        .arg("--language")
+        .arg("java")
+        .arg("--human-readable-input")
+        .arg(&invalid_path)
+        .arg("--")
+        .arg(yaml_path)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(&format!(
+        "error: invalid formats in {}: ",
+        invalid_path.display()
+    )));
+    assert!(!stderr.contains("panicked"));
+}
+
 #[test]
 fn test_that_installed_java_code_compiles() {
     let registry = test_utils::get_registry().unwrap();
@@ -0,0 +1,98 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#pragma once
+
+#include "serde.h"
+
+// Maximum length supported for BCS sequences and maps.
+#define BCS_MAX_LENGTH ((size_t)INT32_MAX)
+#define BCS_MAX_CONTAINER_DEPTH 500
+
+static inline serde_error_t bcs_serialize_uleb128(serde_serializer_t *serializer,
+                                                  uint32_t value) {
+    while (value >= 0x80) {
+        SERDE_TRY(serde_serialize_u8(serializer, (uint8_t)((value & 0x7f) | 0x80)));
+        value >>= 7;
+    }
+    return serde_serialize_u8(serializer, (uint8_t)value);
+}
+
+static inline serde_error_t bcs_deserialize_uleb128(serde_deserializer_t *deserializer,
+                                                    uint32_t *value) {
+    uint64_t result = 0;
+    for (unsigned shift = 0; shift < 32; shift += 7) {
+        uint8_t byte;
+        uint8_t digit;
+        SERDE_TRY(serde_deserialize_u8(deserializer, &byte));
+        digit = byte & 0x7f;
+        result |= (uint64_t)digit << shift;
+        if (result > UINT32_MAX) {
+            // Overflow while parsing uleb128-encoded uint32 value.
+            return SERDE_ERROR_INVALID_INPUT;
+        }
+        if (digit == byte) {
+            if (shift > 0 && digit == 0) {
+                // Invalid uleb128 number (unexpected zero digit).
+                return SERDE_ERROR_INVALID_INPUT;
+            }
+            *value = (uint32_t)result;
+            return SERDE_OK;
+        }
+    }
+    return SERDE_ERROR_INVALID_INPUT;
+}
+
+static inline serde_error_t bcs_serialize_len(serde_serializer_t *serializer, size_t value) {
+    if (value > BCS_MAX_LENGTH) {
+        return SERDE_ERROR_LENGTH_TOO_LARGE;
+    }
+    return bcs_serialize_uleb128(serializer, (uint32_t)value);
+}
+
+static inline serde_error_t bcs_serialize_variant_index(serde_serializer_t *serializer,
+                                                        uint32_t value) {
+    return bcs_serialize_uleb128(serializer, value);
+}
+
+static inline serde_error_t bcs_deserialize_len(serde_deserializer_t *deserializer,
+                                                size_t *value) {
+    uint32_t len;
+    SERDE_TRY(bcs_deserialize_uleb128(deserializer, &len));
+    if (len > BCS_MAX_LENGTH) {
+        return SERDE_ERROR_LENGTH_TOO_LARGE;
+    }
+    *value = len;
+    return SERDE_OK;
+}
+
+static inline serde_error_t bcs_deserialize_variant_index(serde_deserializer_t *deserializer,
+                                                          uint32_t *value) {
+    return bcs_deserialize_uleb128(deserializer, value);
+}
+
+static const serde_format_t bcs_format = {
+    bcs_serialize_len,
+    bcs_serialize_variant_index,
+    bcs_deserialize_len,
+    bcs_deserialize_variant_index,
+    false,
+    true,
+    BCS_MAX_CONTAINER_DEPTH,
+    false,
+    false,
+    SIZE_MAX,
+};
+
+static inline void bcs_serializer_init(serde_serializer_t *serializer,
+                                       uint8_t *buffer,
+                                       size_t capacity) {
+    serde_serializer_init(serializer, &bcs_format, buffer, capacity);
+}
+
+static inline void bcs_deserializer_init(serde_deserializer_t *deserializer,
+                                         const uint8_t *input,
+                                         size_t length,
+                                         const serde_allocator_t *allocator) {
+    serde_deserializer_init(deserializer, &bcs_format, input, length, allocator);
+}
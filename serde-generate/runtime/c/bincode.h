@@ -0,0 +1,61 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#pragma once
+
+#include "serde.h"
+
+// Maximum length supported in practice (e.g. in Java).
+#define BINCODE_MAX_LENGTH ((size_t)INT32_MAX)
+
+static inline serde_error_t bincode_serialize_len(serde_serializer_t *serializer, size_t value) {
+    if (value > BINCODE_MAX_LENGTH) {
+        return SERDE_ERROR_LENGTH_TOO_LARGE;
+    }
+    return serde_serialize_u64(serializer, (uint64_t)value);
+}
+
+static inline serde_error_t bincode_serialize_variant_index(serde_serializer_t *serializer,
+                                                            uint32_t value) {
+    return serde_serialize_u32(serializer, value);
+}
+
+static inline serde_error_t bincode_deserialize_len(serde_deserializer_t *deserializer,
+                                                    size_t *value) {
+    uint64_t len;
+    SERDE_TRY(serde_deserialize_u64(deserializer, &len));
+    if (len > BINCODE_MAX_LENGTH) {
+        return SERDE_ERROR_LENGTH_TOO_LARGE;
+    }
+    *value = (size_t)len;
+    return SERDE_OK;
+}
+
+static inline serde_error_t bincode_deserialize_variant_index(serde_deserializer_t *deserializer,
+                                                              uint32_t *value) {
+    return serde_deserialize_u32(deserializer, value);
+}
+
+// Initializer of a Bincode format with the given options, mirroring the configuration of the
+// Rust crate `bincode`. Lengths and variant indices follow the encoding of integers.
+#define BINCODE_FORMAT(varint_integers, big_endian, size_limit)                                \
+    {                                                                                          \
+        bincode_serialize_len, bincode_serialize_variant_index, bincode_deserialize_len,       \
+            bincode_deserialize_variant_index, true, false, SIZE_MAX, varint_integers,         \
+            big_endian, size_limit,                                                            \
+    }
+
+static const serde_format_t bincode_format = BINCODE_FORMAT(false, false, SIZE_MAX);
+
+static inline void bincode_serializer_init(serde_serializer_t *serializer,
+                                           uint8_t *buffer,
+                                           size_t capacity) {
+    serde_serializer_init(serializer, &bincode_format, buffer, capacity);
+}
+
+static inline void bincode_deserializer_init(serde_deserializer_t *deserializer,
+                                             const uint8_t *input,
+                                             size_t length,
+                                             const serde_allocator_t *allocator) {
+    serde_deserializer_init(deserializer, &bincode_format, input, length, allocator);
+}
@@ -0,0 +1,930 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#ifndef SERDE_NO_MALLOC
+#include <stdlib.h>
+#endif
+
+// Error codes returned by serialization and deserialization functions.
+typedef enum serde_error {
+    SERDE_OK = 0,
+    // The output buffer is too small.
+    SERDE_ERROR_BUFFER_TOO_SMALL,
+    // The input ended unexpectedly.
+    SERDE_ERROR_UNEXPECTED_END,
+    // The input is not a valid encoding (e.g. invalid booleans, UTF-8, variant indices or
+    // ordering of map keys).
+    SERDE_ERROR_INVALID_INPUT,
+    // Some input bytes were not read.
+    SERDE_ERROR_TRAILING_BYTES,
+    // A sequence or a map exceeds the maximum length of the encoding.
+    SERDE_ERROR_LENGTH_TOO_LARGE,
+    // Containers are nested deeper than the encoding allows.
+    SERDE_ERROR_CONTAINER_DEPTH,
+    // The allocator returned NULL.
+    SERDE_ERROR_ALLOCATION_FAILED,
+    // The value cannot be represented by the encoding (e.g. floats in BCS).
+    SERDE_ERROR_UNSUPPORTED,
+    // The value to serialize is invalid (e.g. invalid UTF-8, tags or duplicate map keys).
+    SERDE_ERROR_INVALID_VALUE,
+    // More bytes would be written or read than the size limit of the format allows.
+    SERDE_ERROR_SIZE_LIMIT,
+} serde_error_t;
+
+// Propagate errors to the caller.
+#define SERDE_TRY(expr)                                                                        \
+    do {                                                                                       \
+        serde_error_t serde_error_ = (expr);                                                   \
+        if (serde_error_ != SERDE_OK) {                                                        \
+            return serde_error_;                                                               \
+        }                                                                                      \
+    } while (0)
+
+static inline const char *serde_error_message(serde_error_t error) {
+    switch (error) {
+    case SERDE_OK:
+        return "No error";
+    case SERDE_ERROR_BUFFER_TOO_SMALL:
+        return "Output buffer is not large enough";
+    case SERDE_ERROR_UNEXPECTED_END:
+        return "Input is not large enough";
+    case SERDE_ERROR_INVALID_INPUT:
+        return "Invalid input";
+    case SERDE_ERROR_TRAILING_BYTES:
+        return "Some input bytes were not read";
+    case SERDE_ERROR_LENGTH_TOO_LARGE:
+        return "Length is too large";
+    case SERDE_ERROR_CONTAINER_DEPTH:
+        return "Exceeded the maximum container depth";
+    case SERDE_ERROR_ALLOCATION_FAILED:
+        return "Allocation failed";
+    case SERDE_ERROR_UNSUPPORTED:
+        return "Not supported by the encoding";
+    case SERDE_ERROR_INVALID_VALUE:
+        return "Invalid value";
+    case SERDE_ERROR_SIZE_LIMIT:
+        return "Exceeded the size limit of the encoding";
+    }
+    return "Unknown error";
+}
+
+// Memory allocator supplied by the caller of deserialization functions.
+// Allocated memory is always zero-initialized by the runtime.
+typedef struct serde_allocator {
+    void *(*alloc)(void *context, size_t size);
+    void (*free)(void *context, void *ptr);
+    void *context;
+} serde_allocator_t;
+
+#ifndef SERDE_NO_MALLOC
+static inline void *serde_malloc_alloc(void *context, size_t size) {
+    (void)context;
+    return malloc(size);
+}
+
+static inline void serde_malloc_free(void *context, void *ptr) {
+    (void)context;
+    free(ptr);
+}
+
+// Allocator using `malloc` and `free`. Define `SERDE_NO_MALLOC` to remove it.
+static const serde_allocator_t serde_malloc_allocator = {
+    serde_malloc_alloc,
+    serde_malloc_free,
+    NULL,
+};
+#endif
+
+static inline void serde_free(const serde_allocator_t *allocator, void *ptr) {
+    if (ptr != NULL) {
+        allocator->free(allocator->context, ptr);
+    }
+}
+
+// Unit values take no space in encodings. (C99 does not allow empty structs.)
+typedef struct serde_unit {
+    uint8_t unused;
+} serde_unit_t;
+
+typedef struct serde_uint128 {
+    uint64_t high;
+    uint64_t low;
+} serde_uint128_t;
+
+typedef struct serde_int128 {
+    int64_t high;
+    uint64_t low;
+} serde_int128_t;
+
+// UTF-8 string of `len` bytes. Deserialized strings are also NUL-terminated.
+typedef struct serde_str {
+    char *data;
+    size_t len;
+} serde_str_t;
+
+typedef struct serde_bytes {
+    uint8_t *data;
+    size_t len;
+} serde_bytes_t;
+
+typedef struct serde_serializer serde_serializer_t;
+typedef struct serde_deserializer serde_deserializer_t;
+
+// Differences between the binary encodings.
+typedef struct serde_format {
+    serde_error_t (*serialize_len)(serde_serializer_t *serializer, size_t value);
+    serde_error_t (*serialize_variant_index)(serde_serializer_t *serializer, uint32_t value);
+    serde_error_t (*deserialize_len)(serde_deserializer_t *deserializer, size_t *value);
+    serde_error_t (*deserialize_variant_index)(serde_deserializer_t *deserializer,
+                                               uint32_t *value);
+    // Whether floats are supported.
+    bool floats;
+    // Whether map entries must be ordered by the lexicographic order of encoded keys.
+    bool canonical_maps;
+    size_t max_container_depth;
+    // Whether integers of at least 16 bits use the variable-length encoding of Bincode.
+    bool varint_integers;
+    // Whether fixed-size integers and floats are big-endian.
+    bool big_endian;
+    // Maximum number of bytes written by serialization or read by deserialization.
+    size_t size_limit;
+} serde_format_t;
+
+// Serializer writing into a caller-supplied buffer.
+struct serde_serializer {
+    const serde_format_t *format;
+    uint8_t *buffer;
+    size_t capacity;
+    size_t offset;
+    size_t depth_budget;
+};
+
+// Deserializer reading from a caller-supplied input. Deserialized sequences, maps, strings
+// and recursive values are allocated with the given allocator.
+struct serde_deserializer {
+    const serde_format_t *format;
+    const uint8_t *input;
+    size_t length;
+    size_t offset;
+    size_t depth_budget;
+    const serde_allocator_t *allocator;
+};
+
+static inline void serde_serializer_init(serde_serializer_t *serializer,
+                                         const serde_format_t *format,
+                                         uint8_t *buffer,
+                                         size_t capacity) {
+    serializer->format = format;
+    serializer->buffer = buffer;
+    serializer->capacity = capacity;
+    serializer->offset = 0;
+    serializer->depth_budget = format->max_container_depth;
+}
+
+static inline void serde_deserializer_init(serde_deserializer_t *deserializer,
+                                           const serde_format_t *format,
+                                           const uint8_t *input,
+                                           size_t length,
+                                           const serde_allocator_t *allocator) {
+    deserializer->format = format;
+    deserializer->input = input;
+    deserializer->length = length;
+    deserializer->offset = 0;
+    deserializer->depth_budget = format->max_container_depth;
+    deserializer->allocator = allocator;
+}
+
+// Helpers for generated code.
+
+static inline bool serde_uint128_equal(serde_uint128_t lhs, serde_uint128_t rhs) {
+    return lhs.high == rhs.high && lhs.low == rhs.low;
+}
+
+static inline bool serde_int128_equal(serde_int128_t lhs, serde_int128_t rhs) {
+    return lhs.high == rhs.high && lhs.low == rhs.low;
+}
+
+static inline bool serde_str_equal(serde_str_t lhs, serde_str_t rhs) {
+    return lhs.len == rhs.len && (lhs.len == 0 || memcmp(lhs.data, rhs.data, lhs.len) == 0);
+}
+
+static inline bool serde_bytes_equal(serde_bytes_t lhs, serde_bytes_t rhs) {
+    return lhs.len == rhs.len && (lhs.len == 0 || memcmp(lhs.data, rhs.data, lhs.len) == 0);
+}
+
+// Three-way comparison of numbers and booleans: -1, 0 or 1.
+#define SERDE_COMPARE(lhs, rhs) (((lhs) > (rhs)) - ((lhs) < (rhs)))
+
+// Return the result of a comparison to the caller unless the values are equal.
+#define SERDE_TRY_COMPARE(expr)                                                                \
+    do {                                                                                       \
+        int serde_result_ = (expr);                                                            \
+        if (serde_result_ != 0) {                                                              \
+            return serde_result_;                                                              \
+        }                                                                                      \
+    } while (0)
+
+static inline int serde_uint128_compare(serde_uint128_t lhs, serde_uint128_t rhs) {
+    return lhs.high != rhs.high ? SERDE_COMPARE(lhs.high, rhs.high) : SERDE_COMPARE(lhs.low, rhs.low);
+}
+
+static inline int serde_int128_compare(serde_int128_t lhs, serde_int128_t rhs) {
+    return lhs.high != rhs.high ? SERDE_COMPARE(lhs.high, rhs.high) : SERDE_COMPARE(lhs.low, rhs.low);
+}
+
+// Lexicographic order of unsigned bytes, which orders UTF-8 strings by code points.
+static inline int serde_memory_compare(const void *lhs, size_t lhs_len, const void *rhs, size_t rhs_len) {
+    size_t len = lhs_len < rhs_len ? lhs_len : rhs_len;
+    int result = len == 0 ? 0 : memcmp(lhs, rhs, len);
+    if (result != 0) {
+        return result < 0 ? -1 : 1;
+    }
+    return SERDE_COMPARE(lhs_len, rhs_len);
+}
+
+static inline int serde_str_compare(serde_str_t lhs, serde_str_t rhs) {
+    return serde_memory_compare(lhs.data, lhs.len, rhs.data, rhs.len);
+}
+
+static inline int serde_bytes_compare(serde_bytes_t lhs, serde_bytes_t rhs) {
+    return serde_memory_compare(lhs.data, lhs.len, rhs.data, rhs.len);
+}
+
+static inline uint64_t serde_hash_combine(uint64_t seed, uint64_t value) {
+    return seed ^ (value + 0x9e3779b97f4a7c15ULL + (seed << 6) + (seed >> 2));
+}
+
+// FNV-1a hash of a memory region.
+static inline uint64_t serde_memory_hash(const void *data, size_t len) {
+    const uint8_t *bytes = data;
+    uint64_t hash = 0xcbf29ce484222325ULL;
+    for (size_t i = 0; i < len; i++) {
+        hash = (hash ^ bytes[i]) * 0x100000001b3ULL;
+    }
+    return hash;
+}
+
+static inline uint64_t serde_uint128_hash(serde_uint128_t value) {
+    return serde_hash_combine(value.high, value.low);
+}
+
+static inline uint64_t serde_int128_hash(serde_int128_t value) {
+    return serde_hash_combine((uint64_t)value.high, value.low);
+}
+
+static inline uint64_t serde_str_hash(serde_str_t value) {
+    return serde_memory_hash(value.data, value.len);
+}
+
+static inline uint64_t serde_bytes_hash(serde_bytes_t value) {
+    return serde_memory_hash(value.data, value.len);
+}
+
+static inline void serde_str_free(serde_str_t *value, const serde_allocator_t *allocator) {
+    serde_free(allocator, value->data);
+}
+
+static inline void serde_bytes_free(serde_bytes_t *value, const serde_allocator_t *allocator) {
+    serde_free(allocator, value->data);
+}
+
+// Compare the byte slices `data[start1..end1]` and `data[start2..end2]` lexicographically.
+static inline int serde_compare_slices(const uint8_t *data,
+                                       size_t start1,
+                                       size_t end1,
+                                       size_t start2,
+                                       size_t end2) {
+    size_t len1 = end1 - start1;
+    size_t len2 = end2 - start2;
+    int result = memcmp(data + start1, data + start2, len1 < len2 ? len1 : len2);
+    if (result != 0) {
+        return result;
+    }
+    return len1 < len2 ? -1 : (len1 > len2 ? 1 : 0);
+}
+
+// Check that `data[0..len]` is valid UTF-8 (without overlong encodings or surrogates).
+static inline bool serde_is_valid_utf8(const uint8_t *data, size_t len) {
+    size_t i = 0;
+    while (i < len) {
+        uint8_t byte = data[i];
+        size_t n;
+        uint32_t code_point;
+        uint32_t min;
+        if (byte < 0x80) {
+            i++;
+            continue;
+        } else if ((byte & 0xe0) == 0xc0) {
+            n = 1;
+            code_point = byte & 0x1f;
+            min = 0x80;
+        } else if ((byte & 0xf0) == 0xe0) {
+            n = 2;
+            code_point = byte & 0x0f;
+            min = 0x800;
+        } else if ((byte & 0xf8) == 0xf0) {
+            n = 3;
+            code_point = byte & 0x07;
+            min = 0x10000;
+        } else {
+            return false;
+        }
+        if (len - i - 1 < n) {
+            return false;
+        }
+        for (size_t j = 1; j <= n; j++) {
+            if ((data[i + j] & 0xc0) != 0x80) {
+                return false;
+            }
+            code_point = (code_point << 6) | (data[i + j] & 0x3f);
+        }
+        if (code_point < min || code_point > 0x10ffff ||
+            (code_point >= 0xd800 && code_point <= 0xdfff)) {
+            return false;
+        }
+        i += n + 1;
+    }
+    return true;
+}
+
+// Serialization.
+
+static inline serde_error_t serde_serializer_write(serde_serializer_t *serializer,
+                                                   const uint8_t *bytes,
+                                                   size_t len) {
+    if (serializer->format->size_limit - serializer->offset < len) {
+        return SERDE_ERROR_SIZE_LIMIT;
+    }
+    if (serializer->capacity - serializer->offset < len) {
+        return SERDE_ERROR_BUFFER_TOO_SMALL;
+    }
+    if (len > 0) {
+        memcpy(serializer->buffer + serializer->offset, bytes, len);
+    }
+    serializer->offset += len;
+    return SERDE_OK;
+}
+
+// Write the `size` lowest bytes of `value` in the byte order of the format.
+static inline serde_error_t serde_serializer_write_uint(serde_serializer_t *serializer,
+                                                        uint64_t value,
+                                                        size_t size) {
+    uint8_t bytes[8];
+    for (size_t i = 0; i < size; i++) {
+        size_t shift = serializer->format->big_endian ? size - 1 - i : i;
+        bytes[i] = (uint8_t)(value >> (8 * shift));
+    }
+    return serde_serializer_write(serializer, bytes, size);
+}
+
+// Write an unsigned integer with the variable-length encoding of Bincode: values below 251
+// take one byte, other values a tag followed by a fixed-size integer.
+static inline serde_error_t serde_serializer_write_varint(serde_serializer_t *serializer,
+                                                          uint64_t value) {
+    if (value < 251) {
+        return serde_serializer_write_uint(serializer, value, 1);
+    }
+    if (value <= UINT16_MAX) {
+        SERDE_TRY(serde_serializer_write_uint(serializer, 251, 1));
+        return serde_serializer_write_uint(serializer, value, 2);
+    }
+    if (value <= UINT32_MAX) {
+        SERDE_TRY(serde_serializer_write_uint(serializer, 252, 1));
+        return serde_serializer_write_uint(serializer, value, 4);
+    }
+    SERDE_TRY(serde_serializer_write_uint(serializer, 253, 1));
+    return serde_serializer_write_uint(serializer, value, 8);
+}
+
+// Write an integer of `size` bytes, given by its two's complement representation.
+static inline serde_error_t serde_serializer_write_int(serde_serializer_t *serializer,
+                                                       uint64_t value,
+                                                       size_t size,
+                                                       bool is_signed) {
+    if (size == 1 || !serializer->format->varint_integers) {
+        return serde_serializer_write_uint(serializer, value, size);
+    }
+    if (is_signed) {
+        // Sign-extend the value, then apply the "zigzag" encoding.
+        uint64_t sign = (value >> (8 * size - 1)) & 1;
+        value = (value << 1) ^ (sign ? UINT64_MAX : 0);
+        if (size < 8) {
+            value &= (UINT64_C(1) << (8 * size)) - 1;
+        }
+    }
+    return serde_serializer_write_varint(serializer, value);
+}
+
+// Write a 128-bit integer given by the two halves of its two's complement representation.
+static inline serde_error_t serde_serializer_write_int128(serde_serializer_t *serializer,
+                                                          uint64_t high,
+                                                          uint64_t low,
+                                                          bool is_signed) {
+    if (serializer->format->varint_integers) {
+        if (is_signed) {
+            // "Zigzag" encoding.
+            uint64_t sign = high >> 63;
+            high = ((high << 1) | (low >> 63)) ^ (sign ? UINT64_MAX : 0);
+            low = (low << 1) ^ (sign ? UINT64_MAX : 0);
+        }
+        if (high == 0) {
+            return serde_serializer_write_varint(serializer, low);
+        }
+        SERDE_TRY(serde_serializer_write_uint(serializer, 254, 1));
+    }
+    if (serializer->format->big_endian) {
+        SERDE_TRY(serde_serializer_write_uint(serializer, high, 8));
+        return serde_serializer_write_uint(serializer, low, 8);
+    }
+    SERDE_TRY(serde_serializer_write_uint(serializer, low, 8));
+    return serde_serializer_write_uint(serializer, high, 8);
+}
+
+static inline serde_error_t serde_serializer_increase_container_depth(
+    serde_serializer_t *serializer) {
+    if (serializer->depth_budget == 0) {
+        return SERDE_ERROR_CONTAINER_DEPTH;
+    }
+    serializer->depth_budget--;
+    return SERDE_OK;
+}
+
+static inline void serde_serializer_decrease_container_depth(serde_serializer_t *serializer) {
+    serializer->depth_budget++;
+}
+
+static inline serde_error_t serde_serialize_unit(serde_serializer_t *serializer,
+                                                 serde_unit_t value) {
+    (void)serializer;
+    (void)value;
+    return SERDE_OK;
+}
+
+static inline serde_error_t serde_serialize_bool(serde_serializer_t *serializer, bool value) {
+    return serde_serializer_write_uint(serializer, value ? 1 : 0, 1);
+}
+
+#define SERDE_DEFINE_SERIALIZE_INT(bits)                                                       \
+    static inline serde_error_t serde_serialize_u##bits(serde_serializer_t *serializer,       \
+                                                        uint##bits##_t value) {                \
+        return serde_serializer_write_int(serializer, value, bits / 8, false);                 \
+    }                                                                                          \
+    static inline serde_error_t serde_serialize_i##bits(serde_serializer_t *serializer,       \
+                                                        int##bits##_t value) {                 \
+        return serde_serializer_write_int(serializer, (uint##bits##_t)value, bits / 8, true);  \
+    }
+
+SERDE_DEFINE_SERIALIZE_INT(8)
+SERDE_DEFINE_SERIALIZE_INT(16)
+SERDE_DEFINE_SERIALIZE_INT(32)
+SERDE_DEFINE_SERIALIZE_INT(64)
+
+static inline serde_error_t serde_serialize_u128(serde_serializer_t *serializer,
+                                                 serde_uint128_t value) {
+    return serde_serializer_write_int128(serializer, value.high, value.low, false);
+}
+
+static inline serde_error_t serde_serialize_i128(serde_serializer_t *serializer,
+                                                 serde_int128_t value) {
+    return serde_serializer_write_int128(serializer, (uint64_t)value.high, value.low, true);
+}
+
+static inline serde_error_t serde_serialize_f32(serde_serializer_t *serializer, float value) {
+    uint32_t bits;
+    if (!serializer->format->floats) {
+        return SERDE_ERROR_UNSUPPORTED;
+    }
+    memcpy(&bits, &value, sizeof bits);
+    return serde_serializer_write_uint(serializer, bits, 4);
+}
+
+static inline serde_error_t serde_serialize_f64(serde_serializer_t *serializer, double value) {
+    uint64_t bits;
+    if (!serializer->format->floats) {
+        return SERDE_ERROR_UNSUPPORTED;
+    }
+    memcpy(&bits, &value, sizeof bits);
+    return serde_serializer_write_uint(serializer, bits, 8);
+}
+
+static inline serde_error_t serde_serialize_char(serde_serializer_t *serializer,
+                                                 uint32_t value) {
+    (void)serializer;
+    (void)value;
+    return SERDE_ERROR_UNSUPPORTED;
+}
+
+static inline serde_error_t serde_serialize_len(serde_serializer_t *serializer, size_t value) {
+    return serializer->format->serialize_len(serializer, value);
+}
+
+static inline serde_error_t serde_serialize_variant_index(serde_serializer_t *serializer,
+                                                          uint32_t value) {
+    return serializer->format->serialize_variant_index(serializer, value);
+}
+
+static inline serde_error_t serde_serialize_option_tag(serde_serializer_t *serializer,
+                                                       bool value) {
+    return serde_serialize_bool(serializer, value);
+}
+
+static inline serde_error_t serde_serialize_str(serde_serializer_t *serializer,
+                                                serde_str_t value) {
+    if (!serde_is_valid_utf8((const uint8_t *)value.data, value.len)) {
+        return SERDE_ERROR_INVALID_VALUE;
+    }
+    SERDE_TRY(serde_serialize_len(serializer, value.len));
+    return serde_serializer_write(serializer, (const uint8_t *)value.data, value.len);
+}
+
+static inline serde_error_t serde_serialize_bytes(serde_serializer_t *serializer,
+                                                  serde_bytes_t value) {
+    SERDE_TRY(serde_serialize_len(serializer, value.len));
+    return serde_serializer_write(serializer, value.data, value.len);
+}
+
+// Serialize the entry at `index` of a map.
+typedef serde_error_t (*serde_serialize_entry_fn)(const void *map,
+                                                  size_t index,
+                                                  serde_serializer_t *serializer);
+
+// Serialize the `len` entries of a map (without the length). When the encoding requires
+// canonical maps, entries are selected by increasing key encodings. This requires no
+// allocation but uses the unused part of the output buffer to compare keys.
+static inline serde_error_t serde_serialize_map_entries(serde_serializer_t *serializer,
+                                                        const void *map,
+                                                        size_t len,
+                                                        serde_serialize_entry_fn serialize_key,
+                                                        serde_serialize_entry_fn serialize_value) {
+    size_t previous_start = 0;
+    size_t previous_end = 0;
+    if (!serializer->format->canonical_maps) {
+        for (size_t i = 0; i < len; i++) {
+            SERDE_TRY(serialize_key(map, i, serializer));
+            SERDE_TRY(serialize_value(map, i, serializer));
+        }
+        return SERDE_OK;
+    }
+    for (size_t i = 0; i < len; i++) {
+        size_t start = serializer->offset;
+        size_t best = len;
+        for (size_t j = 0; j < len; j++) {
+            size_t key_start = serializer->offset;
+            size_t key_end;
+            SERDE_TRY(serialize_key(map, j, serializer));
+            key_end = serializer->offset;
+            if ((i == 0 || serde_compare_slices(serializer->buffer, previous_start, previous_end,
+                                                key_start, key_end) < 0) &&
+                (best == len || serde_compare_slices(serializer->buffer, key_start, key_end,
+                                                     start, key_start) < 0)) {
+                // New best candidate: keep its encoding at `start`.
+                memmove(serializer->buffer + start, serializer->buffer + key_start,
+                        key_end - key_start);
+                serializer->offset = start + (key_end - key_start);
+                best = j;
+            } else {
+                serializer->offset = key_start;
+            }
+        }
+        if (best == len) {
+            // Two keys have the same encoding.
+            serializer->offset = start;
+            return SERDE_ERROR_INVALID_VALUE;
+        }
+        previous_start = start;
+        previous_end = serializer->offset;
+        SERDE_TRY(serialize_value(map, best, serializer));
+    }
+    return SERDE_OK;
+}
+
+// Deserialization.
+
+// Check that `len` more bytes may be read.
+static inline serde_error_t serde_deserializer_check_remaining(
+    serde_deserializer_t *deserializer,
+    size_t len) {
+    if (deserializer->format->size_limit - deserializer->offset < len) {
+        return SERDE_ERROR_SIZE_LIMIT;
+    }
+    if (deserializer->length - deserializer->offset < len) {
+        return SERDE_ERROR_UNEXPECTED_END;
+    }
+    return SERDE_OK;
+}
+
+static inline serde_error_t serde_deserializer_read(serde_deserializer_t *deserializer,
+                                                    uint8_t *bytes,
+                                                    size_t len) {
+    SERDE_TRY(serde_deserializer_check_remaining(deserializer, len));
+    if (len > 0) {
+        memcpy(bytes, deserializer->input + deserializer->offset, len);
+    }
+    deserializer->offset += len;
+    return SERDE_OK;
+}
+
+// Read `size` bytes in the byte order of the format.
+static inline serde_error_t serde_deserializer_read_uint(serde_deserializer_t *deserializer,
+                                                         size_t size,
+                                                         uint64_t *value) {
+    uint8_t bytes[8];
+    uint64_t result = 0;
+    SERDE_TRY(serde_deserializer_read(deserializer, bytes, size));
+    for (size_t i = 0; i < size; i++) {
+        size_t shift = deserializer->format->big_endian ? size - 1 - i : i;
+        result |= (uint64_t)bytes[i] << (8 * shift);
+    }
+    *value = result;
+    return SERDE_OK;
+}
+
+// Read the value of a varint tag (see `serde_serializer_write_varint`). The tag 254 of
+// 128-bit integers is left to the caller.
+static inline serde_error_t serde_deserializer_read_varint_value(
+    serde_deserializer_t *deserializer,
+    uint64_t tag,
+    uint64_t *value) {
+    switch (tag) {
+    case 251:
+        return serde_deserializer_read_uint(deserializer, 2, value);
+    case 252:
+        return serde_deserializer_read_uint(deserializer, 4, value);
+    case 253:
+        return serde_deserializer_read_uint(deserializer, 8, value);
+    default:
+        if (tag > 250) {
+            return SERDE_ERROR_INVALID_INPUT;
+        }
+        *value = tag;
+        return SERDE_OK;
+    }
+}
+
+// Read an integer of `size` bytes and return its two's complement representation.
+static inline serde_error_t serde_deserializer_read_int(serde_deserializer_t *deserializer,
+                                                        size_t size,
+                                                        bool is_signed,
+                                                        uint64_t *value) {
+    uint64_t tag;
+    uint64_t result;
+    if (size == 1 || !deserializer->format->varint_integers) {
+        return serde_deserializer_read_uint(deserializer, size, value);
+    }
+    SERDE_TRY(serde_deserializer_read_uint(deserializer, 1, &tag));
+    SERDE_TRY(serde_deserializer_read_varint_value(deserializer, tag, &result));
+    if (size < 8 && result >> (8 * size) != 0) {
+        // Overflow while parsing varint-encoded value.
+        return SERDE_ERROR_INVALID_INPUT;
+    }
+    if (is_signed) {
+        // Undo the "zigzag" encoding.
+        result = (result >> 1) ^ ((result & 1) ? UINT64_MAX : 0);
+    }
+    *value = result;
+    return SERDE_OK;
+}
+
+// Read a 128-bit integer and return the two halves of its two's complement representation.
+static inline serde_error_t serde_deserializer_read_int128(serde_deserializer_t *deserializer,
+                                                           bool is_signed,
+                                                           uint64_t *high,
+                                                           uint64_t *low) {
+    if (deserializer->format->varint_integers) {
+        uint64_t tag;
+        uint64_t sign;
+        SERDE_TRY(serde_deserializer_read_uint(deserializer, 1, &tag));
+        if (tag != 254) {
+            *high = 0;
+            SERDE_TRY(serde_deserializer_read_varint_value(deserializer, tag, low));
+        } else if (deserializer->format->big_endian) {
+            SERDE_TRY(serde_deserializer_read_uint(deserializer, 8, high));
+            SERDE_TRY(serde_deserializer_read_uint(deserializer, 8, low));
+        } else {
+            SERDE_TRY(serde_deserializer_read_uint(deserializer, 8, low));
+            SERDE_TRY(serde_deserializer_read_uint(deserializer, 8, high));
+        }
+        if (is_signed) {
+            // Undo the "zigzag" encoding.
+            sign = *low & 1;
+            *low = ((*low >> 1) | (*high << 63)) ^ (sign ? UINT64_MAX : 0);
+            *high = (*high >> 1) ^ (sign ? UINT64_MAX : 0);
+        }
+        return SERDE_OK;
+    }
+    if (deserializer->format->big_endian) {
+        SERDE_TRY(serde_deserializer_read_uint(deserializer, 8, high));
+        return serde_deserializer_read_uint(deserializer, 8, low);
+    }
+    SERDE_TRY(serde_deserializer_read_uint(deserializer, 8, low));
+    return serde_deserializer_read_uint(deserializer, 8, high);
+}
+
+// Convert the two's complement representation of a 64-bit integer without relying on
+// implementation-defined conversions of out-of-range values.
+static inline int64_t serde_int64_from_bits(uint64_t bits) {
+    return bits <= INT64_MAX ? (int64_t)bits : -(int64_t)(UINT64_MAX - bits) - 1;
+}
+
+// Allocate `count` zero-initialized values of `size` bytes. Unless values may be encoded
+// with no bytes, `min_encoded_size` is positive and guards the allocation against lengths
+// that exceed the remaining input.
+static inline serde_error_t serde_deserializer_alloc(serde_deserializer_t *deserializer,
+                                                     size_t count,
+                                                     size_t size,
+                                                     size_t min_encoded_size,
+                                                     void **result) {
+    void *ptr;
+    *result = NULL;
+    if (count == 0) {
+        return SERDE_OK;
+    }
+    if (min_encoded_size > 0 &&
+        count > (deserializer->length - deserializer->offset) / min_encoded_size) {
+        return SERDE_ERROR_UNEXPECTED_END;
+    }
+    if (deserializer->allocator == NULL || count > SIZE_MAX / size) {
+        return SERDE_ERROR_ALLOCATION_FAILED;
+    }
+    ptr = deserializer->allocator->alloc(deserializer->allocator->context, count * size);
+    if (ptr == NULL) {
+        return SERDE_ERROR_ALLOCATION_FAILED;
+    }
+    memset(ptr, 0, count * size);
+    *result = ptr;
+    return SERDE_OK;
+}
+
+static inline serde_error_t serde_deserializer_increase_container_depth(
+    serde_deserializer_t *deserializer) {
+    if (deserializer->depth_budget == 0) {
+        return SERDE_ERROR_CONTAINER_DEPTH;
+    }
+    deserializer->depth_budget--;
+    return SERDE_OK;
+}
+
+static inline void serde_deserializer_decrease_container_depth(
+    serde_deserializer_t *deserializer) {
+    deserializer->depth_budget++;
+}
+
+// Check that the input was entirely read.
+static inline serde_error_t serde_deserializer_check_end(serde_deserializer_t *deserializer) {
+    if (deserializer->offset < deserializer->length) {
+        return SERDE_ERROR_TRAILING_BYTES;
+    }
+    return SERDE_OK;
+}
+
+// Check that map keys, given by their positions in the input, are ordered as required by the
+// encoding.
+static inline serde_error_t serde_deserializer_check_key_order(
+    serde_deserializer_t *deserializer,
+    size_t previous_start,
+    size_t previous_end,
+    size_t key_start,
+    size_t key_end) {
+    if (deserializer->format->canonical_maps &&
+        serde_compare_slices(deserializer->input, previous_start, previous_end, key_start,
+                             key_end) >= 0) {
+        return SERDE_ERROR_INVALID_INPUT;
+    }
+    return SERDE_OK;
+}
+
+static inline serde_error_t serde_deserialize_unit(serde_deserializer_t *deserializer,
+                                                   serde_unit_t *value) {
+    (void)deserializer;
+    value->unused = 0;
+    return SERDE_OK;
+}
+
+static inline serde_error_t serde_deserialize_bool(serde_deserializer_t *deserializer,
+                                                   bool *value) {
+    uint64_t byte;
+    SERDE_TRY(serde_deserializer_read_uint(deserializer, 1, &byte));
+    if (byte > 1) {
+        return SERDE_ERROR_INVALID_INPUT;
+    }
+    *value = byte == 1;
+    return SERDE_OK;
+}
+
+#define SERDE_DEFINE_DESERIALIZE_INT(bits)                                                     \
+    static inline serde_error_t serde_deserialize_u##bits(serde_deserializer_t *deserializer, \
+                                                          uint##bits##_t *value) {             \
+        uint64_t result;                                                                       \
+        SERDE_TRY(serde_deserializer_read_int(deserializer, bits / 8, false, &result));        \
+        *value = (uint##bits##_t)result;                                                       \
+        return SERDE_OK;                                                                       \
+    }                                                                                          \
+    static inline serde_error_t serde_deserialize_i##bits(serde_deserializer_t *deserializer, \
+                                                          int##bits##_t *value) {              \
+        uint64_t result;                                                                       \
+        SERDE_TRY(serde_deserializer_read_int(deserializer, bits / 8, true, &result));         \
+        result &= UINT##bits##_MAX;                                                            \
+        /* Avoid implementation-defined conversions of out-of-range values. */                \
+        *value = result <= INT##bits##_MAX                                                     \
+                     ? (int##bits##_t)result                                                   \
+                     : (int##bits##_t)(-(int64_t)(UINT##bits##_MAX - result) - 1);             \
+        return SERDE_OK;                                                                       \
+    }
+
+SERDE_DEFINE_DESERIALIZE_INT(8)
+SERDE_DEFINE_DESERIALIZE_INT(16)
+SERDE_DEFINE_DESERIALIZE_INT(32)
+SERDE_DEFINE_DESERIALIZE_INT(64)
+
+static inline serde_error_t serde_deserialize_u128(serde_deserializer_t *deserializer,
+                                                   serde_uint128_t *value) {
+    return serde_deserializer_read_int128(deserializer, false, &value->high, &value->low);
+}
+
+static inline serde_error_t serde_deserialize_i128(serde_deserializer_t *deserializer,
+                                                   serde_int128_t *value) {
+    uint64_t high;
+    SERDE_TRY(serde_deserializer_read_int128(deserializer, true, &high, &value->low));
+    value->high = serde_int64_from_bits(high);
+    return SERDE_OK;
+}
+
+static inline serde_error_t serde_deserialize_f32(serde_deserializer_t *deserializer,
+                                                  float *value) {
+    uint64_t result;
+    uint32_t bits;
+    if (!deserializer->format->floats) {
+        return SERDE_ERROR_UNSUPPORTED;
+    }
+    SERDE_TRY(serde_deserializer_read_uint(deserializer, 4, &result));
+    bits = (uint32_t)result;
+    memcpy(value, &bits, sizeof bits);
+    return SERDE_OK;
+}
+
+static inline serde_error_t serde_deserialize_f64(serde_deserializer_t *deserializer,
+                                                  double *value) {
+    uint64_t bits;
+    if (!deserializer->format->floats) {
+        return SERDE_ERROR_UNSUPPORTED;
+    }
+    SERDE_TRY(serde_deserializer_read_uint(deserializer, 8, &bits));
+    memcpy(value, &bits, sizeof bits);
+    return SERDE_OK;
+}
+
+static inline serde_error_t serde_deserialize_char(serde_deserializer_t *deserializer,
+                                                   uint32_t *value) {
+    (void)deserializer;
+    (void)value;
+    return SERDE_ERROR_UNSUPPORTED;
+}
+
+static inline serde_error_t serde_deserialize_len(serde_deserializer_t *deserializer,
+                                                  size_t *value) {
+    return deserializer->format->deserialize_len(deserializer, value);
+}
+
+static inline serde_error_t serde_deserialize_variant_index(serde_deserializer_t *deserializer,
+                                                            uint32_t *value) {
+    return deserializer->format->deserialize_variant_index(deserializer, value);
+}
+
+static inline serde_error_t serde_deserialize_option_tag(serde_deserializer_t *deserializer,
+                                                         bool *value) {
+    return serde_deserialize_bool(deserializer, value);
+}
+
+static inline serde_error_t serde_deserialize_str(serde_deserializer_t *deserializer,
+                                                  serde_str_t *value) {
+    size_t len;
+    void *data;
+    SERDE_TRY(serde_deserialize_len(deserializer, &len));
+    SERDE_TRY(serde_deserializer_check_remaining(deserializer, len));
+    if (!serde_is_valid_utf8(deserializer->input + deserializer->offset, len)) {
+        return SERDE_ERROR_INVALID_INPUT;
+    }
+    // Add a NUL terminator for convenience.
+    SERDE_TRY(serde_deserializer_alloc(deserializer, len + 1, 1, 0, &data));
+    value->data = data;
+    value->len = len;
+    return serde_deserializer_read(deserializer, (uint8_t *)value->data, len);
+}
+
+static inline serde_error_t serde_deserialize_bytes(serde_deserializer_t *deserializer,
+                                                    serde_bytes_t *value) {
+    size_t len;
+    void *data;
+    SERDE_TRY(serde_deserialize_len(deserializer, &len));
+    SERDE_TRY(serde_deserializer_alloc(deserializer, len, 1, 1, &data));
+    value->data = data;
+    value->len = len;
+    return serde_deserializer_read(deserializer, value->data, len);
+}
@@ -14,14 +14,39 @@ constexpr size_t BINCODE_MAX_LENGTH = (1ull << 31) - 1;
 
 namespace serde {
 
+// By default, integers use their full size in little-endian order. The options
+// `varint_encoding` and `big_endian` correspond to `with_varint_encoding` and
+// `with_big_endian` in the Rust crate.
 class BincodeSerializer : public BinarySerializer<BincodeSerializer> {
     using Parent = BinarySerializer<BincodeSerializer>;
 
+    bool varint_encoding_;
+    bool big_endian_;
+
+    void write_fixint(uint64_t value, size_t size);
+    void write_fixint128(uint64_t high, uint64_t low);
+    void serialize_varint(uint64_t value);
+    void serialize_unsigned(uint64_t value, size_t size);
+    void serialize_signed(int64_t value, size_t size);
+
   public:
-    BincodeSerializer() : Parent(SIZE_MAX) {}
+    BincodeSerializer(bool varint_encoding = false, bool big_endian = false)
+        : Parent(SIZE_MAX), varint_encoding_(varint_encoding),
+          big_endian_(big_endian) {}
 
     void serialize_f32(float value);
     void serialize_f64(double value);
+
+    void serialize_u16(uint16_t value);
+    void serialize_u32(uint32_t value);
+    void serialize_u64(uint64_t value);
+    void serialize_u128(const uint128_t &value);
+
+    void serialize_i16(int16_t value);
+    void serialize_i32(int32_t value);
+    void serialize_i64(int64_t value);
+    void serialize_i128(const int128_t &value);
+
     void serialize_len(size_t value);
     void serialize_variant_index(uint32_t value);
 
@@ -31,12 +56,36 @@ class BincodeSerializer : public BinarySerializer<BincodeSerializer> {
 class BincodeDeserializer : public BinaryDeserializer<BincodeDeserializer> {
     using Parent = BinaryDeserializer<BincodeDeserializer>;
 
+    bool varint_encoding_;
+    bool big_endian_;
+
+    uint64_t read_fixint(size_t size);
+    uint128_t read_fixint128();
+    uint64_t read_varint_value(uint8_t tag);
+    uint64_t deserialize_varint(unsigned bits);
+    uint128_t deserialize_varint128();
+    uint64_t deserialize_unsigned(size_t size);
+    int64_t deserialize_signed(size_t size);
+
   public:
-    BincodeDeserializer(std::vector<uint8_t> bytes)
-        : Parent(std::move(bytes), SIZE_MAX) {}
+    BincodeDeserializer(std::vector<uint8_t> bytes,
+                        bool varint_encoding = false, bool big_endian = false)
+        : Parent(std::move(bytes), SIZE_MAX),
+          varint_encoding_(varint_encoding), big_endian_(big_endian) {}
 
     float deserialize_f32();
     double deserialize_f64();
+
+    uint16_t deserialize_u16();
+    uint32_t deserialize_u32();
+    uint64_t deserialize_u64();
+    uint128_t deserialize_u128();
+
+    int16_t deserialize_i16();
+    int32_t deserialize_i32();
+    int64_t deserialize_i64();
+    int128_t deserialize_i128();
+
     size_t deserialize_len();
     uint32_t deserialize_variant_index();
 
@@ -49,37 +98,253 @@ static_assert(std::numeric_limits<double>::is_iec559);
 static_assert(sizeof(float) == sizeof(uint32_t));
 static_assert(sizeof(double) == sizeof(uint64_t));
 
+// Tags of varint-encoded integers that do not fit in a single byte.
+constexpr uint8_t BINCODE_SINGLE_BYTE_MAX = 250;
+constexpr uint8_t BINCODE_U16_TAG = 251;
+constexpr uint8_t BINCODE_U32_TAG = 252;
+constexpr uint8_t BINCODE_U64_TAG = 253;
+constexpr uint8_t BINCODE_U128_TAG = 254;
+
+inline void BincodeSerializer::write_fixint(uint64_t value, size_t size) {
+    for (size_t i = 0; i < size; i++) {
+        size_t shift = big_endian_ ? 8 * (size - 1 - i) : 8 * i;
+        bytes_.push_back((uint8_t)(value >> shift));
+    }
+}
+
+inline void BincodeSerializer::write_fixint128(uint64_t high, uint64_t low) {
+    if (big_endian_) {
+        write_fixint(high, 8);
+        write_fixint(low, 8);
+    } else {
+        write_fixint(low, 8);
+        write_fixint(high, 8);
+    }
+}
+
+inline void BincodeSerializer::serialize_varint(uint64_t value) {
+    if (value <= BINCODE_SINGLE_BYTE_MAX) {
+        bytes_.push_back((uint8_t)value);
+    } else if (value <= UINT16_MAX) {
+        bytes_.push_back(BINCODE_U16_TAG);
+        write_fixint(value, 2);
+    } else if (value <= UINT32_MAX) {
+        bytes_.push_back(BINCODE_U32_TAG);
+        write_fixint(value, 4);
+    } else {
+        bytes_.push_back(BINCODE_U64_TAG);
+        write_fixint(value, 8);
+    }
+}
+
+inline void BincodeSerializer::serialize_unsigned(uint64_t value, size_t size) {
+    if (varint_encoding_) {
+        serialize_varint(value);
+    } else {
+        write_fixint(value, size);
+    }
+}
+
+// Signed integers use a "zigzag" encoding before the varint encoding.
+inline void BincodeSerializer::serialize_signed(int64_t value, size_t size) {
+    if (varint_encoding_) {
+        serialize_varint(((uint64_t)value << 1) ^ (uint64_t)(value >> 63));
+    } else {
+        write_fixint((uint64_t)value, size);
+    }
+}
+
 inline void BincodeSerializer::serialize_f32(float value) {
-    Parent::serialize_u32(*reinterpret_cast<uint32_t *>(&value));
+    write_fixint(*reinterpret_cast<uint32_t *>(&value), 4);
 }
 
 inline void BincodeSerializer::serialize_f64(double value) {
-    Parent::serialize_u64(*reinterpret_cast<uint64_t *>(&value));
+    write_fixint(*reinterpret_cast<uint64_t *>(&value), 8);
+}
+
+inline void BincodeSerializer::serialize_u16(uint16_t value) {
+    serialize_unsigned(value, 2);
+}
+
+inline void BincodeSerializer::serialize_u32(uint32_t value) {
+    serialize_unsigned(value, 4);
+}
+
+inline void BincodeSerializer::serialize_u64(uint64_t value) {
+    serialize_unsigned(value, 8);
+}
+
+inline void BincodeSerializer::serialize_u128(const uint128_t &value) {
+    if (!varint_encoding_) {
+        write_fixint128(value.high, value.low);
+    } else if (value.high == 0) {
+        serialize_varint(value.low);
+    } else {
+        bytes_.push_back(BINCODE_U128_TAG);
+        write_fixint128(value.high, value.low);
+    }
+}
+
+inline void BincodeSerializer::serialize_i16(int16_t value) {
+    serialize_signed(value, 2);
+}
+
+inline void BincodeSerializer::serialize_i32(int32_t value) {
+    serialize_signed(value, 4);
+}
+
+inline void BincodeSerializer::serialize_i64(int64_t value) {
+    serialize_signed(value, 8);
+}
+
+inline void BincodeSerializer::serialize_i128(const int128_t &value) {
+    if (!varint_encoding_) {
+        write_fixint128((uint64_t)value.high, value.low);
+        return;
+    }
+    uint64_t sign = (uint64_t)(value.high >> 63);
+    uint64_t high = ((uint64_t)value.high << 1) | (value.low >> 63);
+    uint64_t low = value.low << 1;
+    serialize_u128({high ^ sign, low ^ sign});
 }
 
 inline void BincodeSerializer::serialize_len(size_t value) {
     if (value > BINCODE_MAX_LENGTH) {
         throw serde::serialization_error("Length is too large");
     }
-    Parent::serialize_u64((uint64_t)value);
+    serialize_unsigned((uint64_t)value, 8);
 }
 
 inline void BincodeSerializer::serialize_variant_index(uint32_t value) {
-    Parent::serialize_u32((uint32_t)value);
+    serialize_unsigned(value, 4);
+}
+
+inline uint64_t BincodeDeserializer::read_fixint(size_t size) {
+    uint64_t value = 0;
+    for (size_t i = 0; i < size; i++) {
+        size_t shift = big_endian_ ? 8 * (size - 1 - i) : 8 * i;
+        value |= (uint64_t)read_byte() << shift;
+    }
+    return value;
+}
+
+inline uint128_t BincodeDeserializer::read_fixint128() {
+    uint128_t value;
+    if (big_endian_) {
+        value.high = read_fixint(8);
+        value.low = read_fixint(8);
+    } else {
+        value.low = read_fixint(8);
+        value.high = read_fixint(8);
+    }
+    return value;
+}
+
+// Read the value following a varint tag, for values of at most 64 bits.
+inline uint64_t BincodeDeserializer::read_varint_value(uint8_t tag) {
+    switch (tag) {
+    case BINCODE_U16_TAG:
+        return read_fixint(2);
+    case BINCODE_U32_TAG:
+        return read_fixint(4);
+    case BINCODE_U64_TAG:
+        return read_fixint(8);
+    default:
+        if (tag <= BINCODE_SINGLE_BYTE_MAX) {
+            return tag;
+        }
+        throw serde::deserialization_error(
+            "Invalid tag for varint-encoded value");
+    }
+}
+
+inline uint64_t BincodeDeserializer::deserialize_varint(unsigned bits) {
+    uint64_t value = read_varint_value(read_byte());
+    if (bits < 64 && (value >> bits) != 0) {
+        throw serde::deserialization_error(
+            "Overflow while parsing varint-encoded value");
+    }
+    return value;
+}
+
+inline uint128_t BincodeDeserializer::deserialize_varint128() {
+    uint8_t tag = read_byte();
+    if (tag == BINCODE_U128_TAG) {
+        return read_fixint128();
+    }
+    return {0, read_varint_value(tag)};
+}
+
+inline uint64_t BincodeDeserializer::deserialize_unsigned(size_t size) {
+    if (varint_encoding_) {
+        return deserialize_varint(8 * size);
+    }
+    return read_fixint(size);
+}
+
+// The result is sign-extended by the callers in the fixint encoding.
+inline int64_t BincodeDeserializer::deserialize_signed(size_t size) {
+    if (varint_encoding_) {
+        uint64_t value = deserialize_varint(8 * size);
+        return (int64_t)((value >> 1) ^ -(value & 1));
+    }
+    return (int64_t)read_fixint(size);
 }
 
 inline float BincodeDeserializer::deserialize_f32() {
-    auto value = Parent::deserialize_u32();
+    auto value = (uint32_t)read_fixint(4);
     return *reinterpret_cast<float *>(&value);
 }
 
 inline double BincodeDeserializer::deserialize_f64() {
-    auto value = Parent::deserialize_u64();
+    auto value = read_fixint(8);
     return *reinterpret_cast<double *>(&value);
 }
 
+inline uint16_t BincodeDeserializer::deserialize_u16() {
+    return (uint16_t)deserialize_unsigned(2);
+}
+
+inline uint32_t BincodeDeserializer::deserialize_u32() {
+    return (uint32_t)deserialize_unsigned(4);
+}
+
+inline uint64_t BincodeDeserializer::deserialize_u64() {
+    return deserialize_unsigned(8);
+}
+
+inline uint128_t BincodeDeserializer::deserialize_u128() {
+    if (varint_encoding_) {
+        return deserialize_varint128();
+    }
+    return read_fixint128();
+}
+
+inline int16_t BincodeDeserializer::deserialize_i16() {
+    return (int16_t)deserialize_signed(2);
+}
+
+inline int32_t BincodeDeserializer::deserialize_i32() {
+    return (int32_t)deserialize_signed(4);
+}
+
+inline int64_t BincodeDeserializer::deserialize_i64() {
+    return deserialize_signed(8);
+}
+
+inline int128_t BincodeDeserializer::deserialize_i128() {
+    uint128_t value = deserialize_u128();
+    if (!varint_encoding_) {
+        return {(int64_t)value.high, value.low};
+    }
+    uint64_t sign = -(value.low & 1);
+    uint64_t high = value.high >> 1;
+    uint64_t low = (value.low >> 1) | (value.high << 63);
+    return {(int64_t)(high ^ sign), low ^ sign};
+}
+
 inline size_t BincodeDeserializer::deserialize_len() {
-    auto value = (size_t)Parent::deserialize_u64();
+    auto value = deserialize_unsigned(8);
     if (value > BINCODE_MAX_LENGTH) {
         throw serde::deserialization_error("Length is too large");
     }
@@ -87,7 +352,7 @@ inline size_t BincodeDeserializer::deserialize_len() {
 }
 
 inline uint32_t BincodeDeserializer::deserialize_variant_index() {
-    return Parent::deserialize_u32();
+    return (uint32_t)deserialize_unsigned(4);
 }
 
 } // end of namespace serde
@@ -0,0 +1,384 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#pragma once
+
+#include <algorithm>
+#include <cassert>
+#include <cmath>
+#include <limits>
+#include <map>
+
+#include "binary.hpp"
+#include "serde.hpp"
+
+namespace serde {
+
+// Maximum length supported in practice (e.g. Java).
+constexpr size_t BORSH_MAX_LENGTH = (1ull << 31) - 1;
+
+// Order-preserving encodings of the integers and strings written or read so
+// far, indexed by their positions in the buffer.
+//
+// Borsh sorts map entries by keys as in Rust. Substituting these encodings in
+// the encoded keys gives byte strings that compare in the same way.
+class BorshSortKeys {
+    // Start position -> (end position, encoding).
+    std::map<size_t, std::pair<size_t, std::vector<uint8_t>>> records_;
+
+    void record(size_t start, size_t end, std::vector<uint8_t> key) {
+        // Values written or read inside the current one are superseded.
+        truncate(start);
+        records_.emplace(start, std::make_pair(end, std::move(key)));
+    }
+
+  public:
+    // Little-endian integers compare as in Rust in big-endian order, with the
+    // sign bit flipped.
+    void record_integer(const std::vector<uint8_t> &bytes, size_t start,
+                        size_t end, bool is_signed) {
+        std::vector<uint8_t> key(bytes.rbegin() + (bytes.size() - end),
+                                 bytes.rbegin() + (bytes.size() - start));
+        if (is_signed) {
+            key[0] ^= 0x80;
+        }
+        record(start, end, std::move(key));
+    }
+
+    // Strings have their zeros escaped and a terminator added so that prefixes
+    // come first.
+    void record_str(const std::vector<uint8_t> &bytes, size_t start,
+                    size_t end) {
+        std::vector<uint8_t> key;
+        for (auto i = start + 4; i < end; i++) {
+            key.push_back(bytes[i]);
+            if (bytes[i] == 0) {
+                key.push_back(0xff);
+            }
+        }
+        key.push_back(0);
+        key.push_back(0);
+        record(start, end, std::move(key));
+    }
+
+    void truncate(size_t start) {
+        records_.erase(records_.lower_bound(start), records_.end());
+    }
+
+    std::vector<uint8_t> sort_key(const std::vector<uint8_t> &bytes,
+                                  size_t start, size_t end) const {
+        std::vector<uint8_t> result;
+        auto position = start;
+        for (auto it = records_.lower_bound(start);
+             it != records_.end() && it->first < end; it++) {
+            result.insert(result.end(), bytes.begin() + position,
+                          bytes.begin() + it->first);
+            result.insert(result.end(), it->second.second.begin(),
+                          it->second.second.end());
+            position = it->second.first;
+        }
+        result.insert(result.end(), bytes.begin() + position,
+                      bytes.begin() + end);
+        return result;
+    }
+};
+
+class BorshSerializer : public BinarySerializer<BorshSerializer> {
+    using Parent = BinarySerializer<BorshSerializer>;
+
+    BorshSortKeys sort_keys_;
+
+  public:
+    BorshSerializer() : Parent(SIZE_MAX) {}
+
+    void serialize_str(const std::string &value);
+
+    void serialize_u16(uint16_t value);
+    void serialize_u32(uint32_t value);
+    void serialize_u64(uint64_t value);
+    void serialize_u128(const uint128_t &value);
+
+    void serialize_i8(int8_t value);
+    void serialize_i16(int16_t value);
+    void serialize_i32(int32_t value);
+    void serialize_i64(int64_t value);
+    void serialize_i128(const int128_t &value);
+
+    void serialize_f32(float value);
+    void serialize_f64(double value);
+
+    void serialize_len(size_t value);
+    void serialize_variant_index(uint32_t value);
+
+    static constexpr bool enforce_strict_map_ordering = true;
+    void sort_last_entries(std::vector<size_t> offsets);
+};
+
+class BorshDeserializer : public BinaryDeserializer<BorshDeserializer> {
+    using Parent = BinaryDeserializer<BorshDeserializer>;
+
+    BorshSortKeys sort_keys_;
+
+  public:
+    BorshDeserializer(std::vector<uint8_t> bytes)
+        : Parent(std::move(bytes), SIZE_MAX) {}
+
+    std::string deserialize_str();
+
+    uint16_t deserialize_u16();
+    uint32_t deserialize_u32();
+    uint64_t deserialize_u64();
+    uint128_t deserialize_u128();
+
+    int8_t deserialize_i8();
+    int16_t deserialize_i16();
+    int32_t deserialize_i32();
+    int64_t deserialize_i64();
+    int128_t deserialize_i128();
+
+    float deserialize_f32();
+    double deserialize_f64();
+
+    size_t deserialize_len();
+    uint32_t deserialize_variant_index();
+
+    static constexpr bool enforce_strict_map_ordering = true;
+    void check_that_key_slices_are_increasing(std::tuple<size_t, size_t> key1,
+                                              std::tuple<size_t, size_t> key2);
+};
+
+inline void BorshSerializer::serialize_str(const std::string &value) {
+    auto start = bytes_.size();
+    Parent::serialize_str(value);
+    sort_keys_.record_str(bytes_, start, bytes_.size());
+}
+
+inline void BorshSerializer::serialize_u16(uint16_t value) {
+    auto start = bytes_.size();
+    Parent::serialize_u16(value);
+    sort_keys_.record_integer(bytes_, start, bytes_.size(), false);
+}
+
+inline void BorshSerializer::serialize_u32(uint32_t value) {
+    auto start = bytes_.size();
+    Parent::serialize_u32(value);
+    sort_keys_.record_integer(bytes_, start, bytes_.size(), false);
+}
+
+inline void BorshSerializer::serialize_u64(uint64_t value) {
+    auto start = bytes_.size();
+    Parent::serialize_u64(value);
+    sort_keys_.record_integer(bytes_, start, bytes_.size(), false);
+}
+
+inline void BorshSerializer::serialize_u128(const uint128_t &value) {
+    auto start = bytes_.size();
+    Parent::serialize_u128(value);
+    sort_keys_.record_integer(bytes_, start, bytes_.size(), false);
+}
+
+inline void BorshSerializer::serialize_i8(int8_t value) {
+    auto start = bytes_.size();
+    Parent::serialize_i8(value);
+    sort_keys_.record_integer(bytes_, start, bytes_.size(), true);
+}
+
+inline void BorshSerializer::serialize_i16(int16_t value) {
+    auto start = bytes_.size();
+    Parent::serialize_i16(value);
+    sort_keys_.record_integer(bytes_, start, bytes_.size(), true);
+}
+
+inline void BorshSerializer::serialize_i32(int32_t value) {
+    auto start = bytes_.size();
+    Parent::serialize_i32(value);
+    sort_keys_.record_integer(bytes_, start, bytes_.size(), true);
+}
+
+inline void BorshSerializer::serialize_i64(int64_t value) {
+    auto start = bytes_.size();
+    Parent::serialize_i64(value);
+    sort_keys_.record_integer(bytes_, start, bytes_.size(), true);
+}
+
+inline void BorshSerializer::serialize_i128(const int128_t &value) {
+    auto start = bytes_.size();
+    Parent::serialize_i128(value);
+    sort_keys_.record_integer(bytes_, start, bytes_.size(), true);
+}
+
+inline void BorshSerializer::serialize_f32(float value) {
+    if (std::isnan(value)) {
+        throw serde::serialization_error("NaN values are not supported by Borsh");
+    }
+    Parent::serialize_u32(*reinterpret_cast<uint32_t *>(&value));
+}
+
+inline void BorshSerializer::serialize_f64(double value) {
+    if (std::isnan(value)) {
+        throw serde::serialization_error("NaN values are not supported by Borsh");
+    }
+    Parent::serialize_u64(*reinterpret_cast<uint64_t *>(&value));
+}
+
+inline void BorshSerializer::serialize_len(size_t value) {
+    if (value > BORSH_MAX_LENGTH) {
+        throw serde::serialization_error("Length is too large");
+    }
+    Parent::serialize_u32((uint32_t)value);
+}
+
+inline void BorshSerializer::serialize_variant_index(uint32_t value) {
+    if (value > std::numeric_limits<uint8_t>::max()) {
+        throw serde::serialization_error("Variant index is too large");
+    }
+    Parent::serialize_u8((uint8_t)value);
+}
+
+inline void BorshSerializer::sort_last_entries(std::vector<size_t> offsets) {
+    if (offsets.size() <= 1) {
+        return;
+    }
+    offsets.push_back(bytes_.size());
+
+    // Pairs of sort keys and entries.
+    std::vector<std::pair<std::vector<uint8_t>, std::vector<uint8_t>>> slices;
+    for (auto i = 1; i < offsets.size(); i++) {
+        auto start = bytes_.cbegin() + offsets[i - 1];
+        auto end = bytes_.cbegin() + offsets[i];
+        slices.emplace_back(
+            sort_keys_.sort_key(bytes_, offsets[i - 1], offsets[i]),
+            std::vector<uint8_t>(start, end));
+    }
+
+    std::sort(slices.begin(), slices.end(), [](auto &s1, auto &s2) {
+        return std::lexicographical_compare(s1.first.begin(), s1.first.end(),
+                                            s2.first.begin(), s2.first.end());
+    });
+
+    // Positions in the map are no longer valid. Map keys never contain maps.
+    sort_keys_.truncate(offsets[0]);
+    bytes_.resize(offsets[0]);
+    for (auto slice : slices) {
+        bytes_.insert(bytes_.end(), slice.second.begin(), slice.second.end());
+    }
+    assert(offsets.back() == bytes_.size());
+}
+
+inline std::string BorshDeserializer::deserialize_str() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_str();
+    sort_keys_.record_str(bytes_, start, get_buffer_offset());
+    return value;
+}
+
+inline uint16_t BorshDeserializer::deserialize_u16() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_u16();
+    sort_keys_.record_integer(bytes_, start, get_buffer_offset(), false);
+    return value;
+}
+
+inline uint32_t BorshDeserializer::deserialize_u32() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_u32();
+    sort_keys_.record_integer(bytes_, start, get_buffer_offset(), false);
+    return value;
+}
+
+inline uint64_t BorshDeserializer::deserialize_u64() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_u64();
+    sort_keys_.record_integer(bytes_, start, get_buffer_offset(), false);
+    return value;
+}
+
+inline uint128_t BorshDeserializer::deserialize_u128() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_u128();
+    sort_keys_.record_integer(bytes_, start, get_buffer_offset(), false);
+    return value;
+}
+
+inline int8_t BorshDeserializer::deserialize_i8() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_i8();
+    sort_keys_.record_integer(bytes_, start, get_buffer_offset(), true);
+    return value;
+}
+
+inline int16_t BorshDeserializer::deserialize_i16() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_i16();
+    sort_keys_.record_integer(bytes_, start, get_buffer_offset(), true);
+    return value;
+}
+
+inline int32_t BorshDeserializer::deserialize_i32() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_i32();
+    sort_keys_.record_integer(bytes_, start, get_buffer_offset(), true);
+    return value;
+}
+
+inline int64_t BorshDeserializer::deserialize_i64() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_i64();
+    sort_keys_.record_integer(bytes_, start, get_buffer_offset(), true);
+    return value;
+}
+
+inline int128_t BorshDeserializer::deserialize_i128() {
+    auto start = get_buffer_offset();
+    auto value = Parent::deserialize_i128();
+    sort_keys_.record_integer(bytes_, start, get_buffer_offset(), true);
+    return value;
+}
+
+inline float BorshDeserializer::deserialize_f32() {
+    auto bits = Parent::deserialize_u32();
+    auto value = *reinterpret_cast<float *>(&bits);
+    if (std::isnan(value)) {
+        throw serde::deserialization_error(
+            "NaN values are not supported by Borsh");
+    }
+    return value;
+}
+
+inline double BorshDeserializer::deserialize_f64() {
+    auto bits = Parent::deserialize_u64();
+    auto value = *reinterpret_cast<double *>(&bits);
+    if (std::isnan(value)) {
+        throw serde::deserialization_error(
+            "NaN values are not supported by Borsh");
+    }
+    return value;
+}
+
+inline size_t BorshDeserializer::deserialize_len() {
+    auto value = Parent::deserialize_u32();
+    if (value > BORSH_MAX_LENGTH) {
+        throw serde::deserialization_error("Length is too large");
+    }
+    return (size_t)value;
+}
+
+inline uint32_t BorshDeserializer::deserialize_variant_index() {
+    return Parent::deserialize_u8();
+}
+
+inline void BorshDeserializer::check_that_key_slices_are_increasing(
+    std::tuple<size_t, size_t> key1, std::tuple<size_t, size_t> key2) {
+    auto sort_key1 =
+        sort_keys_.sort_key(bytes_, std::get<0>(key1), std::get<1>(key1));
+    auto sort_key2 =
+        sort_keys_.sort_key(bytes_, std::get<0>(key2), std::get<1>(key2));
+    if (!std::lexicographical_compare(sort_key1.begin(), sort_key1.end(),
+                                      sort_key2.begin(), sort_key2.end())) {
+        throw serde::deserialization_error(
+            "Error while decoding map: keys are not serialized in the "
+            "expected order");
+    }
+}
+
+} // end of namespace serde
@@ -0,0 +1,1298 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#pragma once
+
+#include <algorithm>
+#include <charconv>
+#include <cmath>
+#include <cstdlib>
+#include <limits>
+
+#include "serde.hpp"
+
+// The JSON format of the Rust crate `serde_json`.
+//
+// Values are converted from and to trees of `serde::json::Value` by the traits
+// `JsonSerializable` and `JsonDeserializable`, then `serde::json::parse` and
+// `serde::json::stringify` deal with the JSON text.
+
+namespace serde {
+
+namespace json {
+
+// Maximal number of nested arrays and objects accepted by `serde_json` (whose
+// recursion limit is 128).
+constexpr size_t MAX_CONTAINER_DEPTH = 127;
+
+struct Variant;
+
+// A JSON value. Numbers are kept as written so that integers of any size are
+// read exactly. Keys of objects read as maps have their own kind because they
+// may stand for strings, numbers, booleans, or unit variants of enums.
+struct Value {
+    enum class Kind { Null, Bool, Number, String, Key, Array, Object };
+
+    Kind kind = Kind::Null;
+    bool boolean = false;
+    // Literal of a number, content of a string or a key.
+    std::string text;
+    // Items of an array, or values of the entries of an object.
+    std::vector<Value> items;
+    // Keys of the entries of an object, in order and possibly with duplicates.
+    std::vector<std::string> keys;
+
+    static Value null() { return Value(); }
+
+    static Value from_bool(bool value) {
+        Value result;
+        result.kind = Kind::Bool;
+        result.boolean = value;
+        return result;
+    }
+
+    static Value number(std::string literal) {
+        Value result;
+        result.kind = Kind::Number;
+        result.text = std::move(literal);
+        return result;
+    }
+
+    static Value string(std::string content) {
+        Value result;
+        result.kind = Kind::String;
+        result.text = std::move(content);
+        return result;
+    }
+
+    static Value key(std::string content) {
+        Value result;
+        result.kind = Kind::Key;
+        result.text = std::move(content);
+        return result;
+    }
+
+    static Value array() {
+        Value result;
+        result.kind = Kind::Array;
+        return result;
+    }
+
+    static Value object() {
+        Value result;
+        result.kind = Kind::Object;
+        return result;
+    }
+
+    // A variant of an enum that is not a unit variant (unit variants are
+    // written as strings).
+    static Value variant(std::string name, Value content) {
+        Value result = object();
+        result.insert(std::move(name), std::move(content));
+        return result;
+    }
+
+    bool is_null() const { return kind == Kind::Null; }
+
+    void push(Value item) { items.push_back(std::move(item)); }
+
+    void insert(std::string key, Value value) {
+        keys.push_back(std::move(key));
+        items.push_back(std::move(value));
+    }
+
+    // The items of an array.
+    const std::vector<Value> &as_array() const {
+        if (kind != Kind::Array) {
+            throw deserialization_error("Invalid type: expected an array");
+        }
+        return items;
+    }
+
+    // The items of an array of the given length, i.e. a tuple or a fixed-size
+    // array.
+    const std::vector<Value> &as_array(size_t length) const {
+        const auto &result = as_array();
+        if (result.size() != length) {
+            throw deserialization_error("Invalid length for tuple or array");
+        }
+        return result;
+    }
+
+    const Value &as_object() const {
+        if (kind != Kind::Object) {
+            throw deserialization_error("Invalid type: expected an object");
+        }
+        return *this;
+    }
+
+    // Value of the field `name` of a struct.
+    const Value &field(const std::string &name) const {
+        const Value *result = find(name);
+        if (result == nullptr) {
+            throw deserialization_error("Missing field: " + name);
+        }
+        return *result;
+    }
+
+    // Value of the optional field `name` of a struct, or `null` if the field
+    // is missing.
+    const Value &optional_field(const std::string &name) const {
+        static const Value missing;
+        const Value *result = find(name);
+        return result == nullptr ? missing : *result;
+    }
+
+    Variant as_variant() const;
+
+  private:
+    const Value *find(const std::string &name) const {
+        as_object();
+        const Value *result = nullptr;
+        for (size_t i = 0; i < keys.size(); i++) {
+            if (keys[i] == name) {
+                if (result != nullptr) {
+                    throw deserialization_error("Duplicate field: " + name);
+                }
+                result = &items[i];
+            }
+        }
+        return result;
+    }
+};
+
+// A variant of an enum, read either from a string (unit variants) or from an
+// object with a single entry.
+struct Variant {
+    std::string name;
+    const Value *content;
+
+    // Check that the variant has no content, i.e. that it was written as a
+    // string or with `null`.
+    void unit() const {
+        if (content != nullptr && !content->is_null()) {
+            throw deserialization_error("Unexpected content for unit variant: " +
+                                        name);
+        }
+    }
+
+    // Content of a variant that is not a unit variant.
+    const Value &value() const {
+        if (content == nullptr) {
+            throw deserialization_error("Missing content for variant: " + name);
+        }
+        return *content;
+    }
+};
+
+inline Variant Value::as_variant() const {
+    if (kind == Kind::String || kind == Kind::Key) {
+        return {text, nullptr};
+    }
+    if (kind == Kind::Object && keys.size() == 1) {
+        return {keys[0], &items[0]};
+    }
+    throw deserialization_error(
+        "Invalid type: expected a string or an object with a single entry");
+}
+
+namespace detail {
+
+inline bool is_digit(char c) { return c >= '0' && c <= '9'; }
+
+// Whether `text` is a JSON number, optionally restricted to integers.
+inline bool is_number(const std::string &text, bool integer) {
+    size_t i = 0;
+    size_t n = text.size();
+    if (i < n && text[i] == '-') {
+        i++;
+    }
+    if (i < n && text[i] == '0') {
+        i++;
+    } else if (i < n && is_digit(text[i])) {
+        while (i < n && is_digit(text[i])) {
+            i++;
+        }
+    } else {
+        return false;
+    }
+    if (integer) {
+        return i == n;
+    }
+    if (i < n && text[i] == '.') {
+        i++;
+        if (!(i < n && is_digit(text[i]))) {
+            return false;
+        }
+        while (i < n && is_digit(text[i])) {
+            i++;
+        }
+    }
+    if (i < n && (text[i] == 'e' || text[i] == 'E')) {
+        i++;
+        if (i < n && (text[i] == '+' || text[i] == '-')) {
+            i++;
+        }
+        if (!(i < n && is_digit(text[i]))) {
+            return false;
+        }
+        while (i < n && is_digit(text[i])) {
+            i++;
+        }
+    }
+    return i == n;
+}
+
+// Length of the UTF-8 sequence starting at `text[i]`, or 0 if it is invalid.
+inline size_t utf8_sequence_length(const std::string &text, size_t i) {
+    auto byte = [&](size_t j) -> uint8_t {
+        return j < text.size() ? (uint8_t)text[j] : 0;
+    };
+    uint8_t c = byte(i);
+    auto continuation = [&](size_t j) { return (byte(j) & 0xC0) == 0x80; };
+    if (c < 0x80) {
+        return 1;
+    }
+    if (c >= 0xC2 && c <= 0xDF) {
+        return continuation(i + 1) ? 2 : 0;
+    }
+    if (c >= 0xE0 && c <= 0xEF) {
+        uint8_t d = byte(i + 1);
+        if ((c == 0xE0 && d < 0xA0) || (c == 0xED && d > 0x9F)) {
+            return 0;
+        }
+        return continuation(i + 1) && continuation(i + 2) ? 3 : 0;
+    }
+    if (c >= 0xF0 && c <= 0xF4) {
+        uint8_t d = byte(i + 1);
+        if ((c == 0xF0 && d < 0x90) || (c == 0xF4 && d > 0x8F)) {
+            return 0;
+        }
+        return continuation(i + 1) && continuation(i + 2) &&
+                       continuation(i + 3)
+                   ? 4
+                   : 0;
+    }
+    return 0;
+}
+
+inline bool is_utf8(const std::string &text) {
+    for (size_t i = 0; i < text.size();) {
+        size_t length = utf8_sequence_length(text, i);
+        if (length == 0) {
+            return false;
+        }
+        i += length;
+    }
+    return true;
+}
+
+inline void append_utf8(std::string &output, uint32_t code) {
+    if (code < 0x80) {
+        output.push_back((char)code);
+    } else if (code < 0x800) {
+        output.push_back((char)(0xC0 | (code >> 6)));
+        output.push_back((char)(0x80 | (code & 0x3F)));
+    } else if (code < 0x10000) {
+        output.push_back((char)(0xE0 | (code >> 12)));
+        output.push_back((char)(0x80 | ((code >> 6) & 0x3F)));
+        output.push_back((char)(0x80 | (code & 0x3F)));
+    } else {
+        output.push_back((char)(0xF0 | (code >> 18)));
+        output.push_back((char)(0x80 | ((code >> 12) & 0x3F)));
+        output.push_back((char)(0x80 | ((code >> 6) & 0x3F)));
+        output.push_back((char)(0x80 | (code & 0x3F)));
+    }
+}
+
+class Parser {
+  public:
+    explicit Parser(const std::string &input) : input_(input) {}
+
+    Value parse() {
+        Value value = parse_value(MAX_CONTAINER_DEPTH);
+        skip_whitespace();
+        if (position_ < input_.size()) {
+            error("Trailing characters in JSON input");
+        }
+        return value;
+    }
+
+  private:
+    const std::string &input_;
+    size_t position_ = 0;
+
+    [[noreturn]] void error(const std::string &message) {
+        throw deserialization_error(message + " at position " +
+                                    std::to_string(position_));
+    }
+
+    void skip_whitespace() {
+        while (position_ < input_.size()) {
+            char c = input_[position_];
+            if (c != ' ' && c != '\t' && c != '\n' && c != '\r') {
+                return;
+            }
+            position_++;
+        }
+    }
+
+    bool peek(char c) {
+        return position_ < input_.size() && input_[position_] == c;
+    }
+
+    char next() {
+        if (position_ >= input_.size()) {
+            error("Unexpected end of JSON input");
+        }
+        return input_[position_++];
+    }
+
+    void expect(const char *word) {
+        size_t length = std::char_traits<char>::length(word);
+        if (input_.compare(position_, length, word) != 0) {
+            error("Invalid JSON value");
+        }
+        position_ += length;
+    }
+
+    Value parse_value(size_t depth_budget) {
+        skip_whitespace();
+        if (position_ >= input_.size()) {
+            error("Unexpected end of JSON input");
+        }
+        char c = input_[position_];
+        switch (c) {
+        case 'n':
+            expect("null");
+            return Value::null();
+        case 't':
+            expect("true");
+            return Value::from_bool(true);
+        case 'f':
+            expect("false");
+            return Value::from_bool(false);
+        case '"':
+            return Value::string(parse_string());
+        case '[': {
+            if (depth_budget == 0) {
+                error("Exceeded maximum container depth");
+            }
+            position_++;
+            Value result = Value::array();
+            skip_whitespace();
+            if (peek(']')) {
+                position_++;
+                return result;
+            }
+            while (true) {
+                result.push(parse_value(depth_budget - 1));
+                skip_whitespace();
+                char d = next();
+                if (d == ']') {
+                    return result;
+                }
+                if (d != ',') {
+                    error("Expected ',' or ']'");
+                }
+            }
+        }
+        case '{': {
+            if (depth_budget == 0) {
+                error("Exceeded maximum container depth");
+            }
+            position_++;
+            Value result = Value::object();
+            skip_whitespace();
+            if (peek('}')) {
+                position_++;
+                return result;
+            }
+            while (true) {
+                skip_whitespace();
+                if (!peek('"')) {
+                    error("Expected a string key");
+                }
+                std::string key = parse_string();
+                skip_whitespace();
+                if (next() != ':') {
+                    error("Expected ':'");
+                }
+                result.insert(std::move(key), parse_value(depth_budget - 1));
+                skip_whitespace();
+                char d = next();
+                if (d == '}') {
+                    return result;
+                }
+                if (d != ',') {
+                    error("Expected ',' or '}'");
+                }
+            }
+        }
+        default:
+            if (c == '-' || is_digit(c)) {
+                return parse_number();
+            }
+            error("Invalid JSON value");
+        }
+    }
+
+    Value parse_number() {
+        size_t start = position_;
+        while (position_ < input_.size()) {
+            char c = input_[position_];
+            if (!(c == '-' || c == '+' || c == '.' || c == 'e' || c == 'E' ||
+                  is_digit(c))) {
+                break;
+            }
+            position_++;
+        }
+        std::string literal = input_.substr(start, position_ - start);
+        if (!is_number(literal, false)) {
+            error("Invalid number");
+        }
+        return Value::number(std::move(literal));
+    }
+
+    uint32_t parse_hex() {
+        uint32_t value = 0;
+        for (int i = 0; i < 4; i++) {
+            char c = next();
+            value *= 16;
+            if (is_digit(c)) {
+                value += c - '0';
+            } else if (c >= 'a' && c <= 'f') {
+                value += c - 'a' + 10;
+            } else if (c >= 'A' && c <= 'F') {
+                value += c - 'A' + 10;
+            } else {
+                error("Invalid escape in string");
+            }
+        }
+        return value;
+    }
+
+    std::string parse_string() {
+        position_++;
+        std::string result;
+        while (true) {
+            char c = next();
+            if (c == '"') {
+                return result;
+            }
+            if ((uint8_t)c < 0x20) {
+                error("Control character in string");
+            }
+            if (c != '\\') {
+                size_t length = utf8_sequence_length(input_, position_ - 1);
+                if (length == 0) {
+                    error("Invalid UTF-8 in string");
+                }
+                result.append(input_, position_ - 1, length);
+                position_ += length - 1;
+                continue;
+            }
+            c = next();
+            switch (c) {
+            case '"':
+                result.push_back('"');
+                break;
+            case '\\':
+                result.push_back('\\');
+                break;
+            case '/':
+                result.push_back('/');
+                break;
+            case 'b':
+                result.push_back('\b');
+                break;
+            case 'f':
+                result.push_back('\f');
+                break;
+            case 'n':
+                result.push_back('\n');
+                break;
+            case 'r':
+                result.push_back('\r');
+                break;
+            case 't':
+                result.push_back('\t');
+                break;
+            case 'u': {
+                uint32_t code = parse_hex();
+                if (code >= 0xDC00 && code <= 0xDFFF) {
+                    error("Lone surrogate in string");
+                }
+                if (code >= 0xD800 && code <= 0xDBFF) {
+                    if (!(next() == '\\' && next() == 'u')) {
+                        error("Lone surrogate in string");
+                    }
+                    uint32_t low = parse_hex();
+                    if (!(low >= 0xDC00 && low <= 0xDFFF)) {
+                        error("Lone surrogate in string");
+                    }
+                    code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                }
+                append_utf8(result, code);
+                break;
+            }
+            default:
+                error("Invalid escape in string");
+            }
+        }
+    }
+};
+
+inline void write_string(std::string &output, const std::string &value) {
+    static const char *hex = "0123456789abcdef";
+    if (!is_utf8(value)) {
+        throw serialization_error("Invalid UTF-8 string");
+    }
+    output.push_back('"');
+    for (char c : value) {
+        switch (c) {
+        case '"':
+            output.append("\\\"");
+            break;
+        case '\\':
+            output.append("\\\\");
+            break;
+        case '\b':
+            output.append("\\b");
+            break;
+        case '\f':
+            output.append("\\f");
+            break;
+        case '\n':
+            output.append("\\n");
+            break;
+        case '\r':
+            output.append("\\r");
+            break;
+        case '\t':
+            output.append("\\t");
+            break;
+        default:
+            if ((uint8_t)c < 0x20) {
+                output.append("\\u00");
+                output.push_back(hex[(uint8_t)c >> 4]);
+                output.push_back(hex[(uint8_t)c & 0xF]);
+            } else {
+                output.push_back(c);
+            }
+        }
+    }
+    output.push_back('"');
+}
+
+inline void write(std::string &output, const Value &value) {
+    switch (value.kind) {
+    case Value::Kind::Null:
+        output.append("null");
+        break;
+    case Value::Kind::Bool:
+        output.append(value.boolean ? "true" : "false");
+        break;
+    case Value::Kind::Number:
+        output.append(value.text);
+        break;
+    case Value::Kind::String:
+    case Value::Kind::Key:
+        write_string(output, value.text);
+        break;
+    case Value::Kind::Array:
+        output.push_back('[');
+        for (size_t i = 0; i < value.items.size(); i++) {
+            if (i > 0) {
+                output.push_back(',');
+            }
+            write(output, value.items[i]);
+        }
+        output.push_back(']');
+        break;
+    case Value::Kind::Object:
+        output.push_back('{');
+        for (size_t i = 0; i < value.keys.size(); i++) {
+            if (i > 0) {
+                output.push_back(',');
+            }
+            write_string(output, value.keys[i]);
+            output.push_back(':');
+            write(output, value.items[i]);
+        }
+        output.push_back('}');
+        break;
+    }
+}
+
+// Format a finite float in the manner of the Rust crate `ryu`, as `serde_json`
+// does: with the shortest digits that read back as the same value.
+template <typename T>
+std::string format_float(T value) {
+    if (value == 0) {
+        return std::signbit(value) ? "-0.0" : "0.0";
+    }
+    char buffer[64];
+    auto result = std::to_chars(buffer, buffer + sizeof(buffer), value,
+                                std::chars_format::scientific);
+    std::string scientific(buffer, result.ptr);
+    std::string sign;
+    if (scientific[0] == '-') {
+        sign = "-";
+        scientific.erase(0, 1);
+    }
+    size_t e = scientific.find('e');
+    std::string digits = scientific.substr(0, e);
+    digits.erase(std::remove(digits.begin(), digits.end(), '.'), digits.end());
+    while (digits.size() > 1 && digits.back() == '0') {
+        digits.pop_back();
+    }
+    int point = std::stoi(scientific.substr(e + 1)) + 1;
+    int length = (int)digits.size();
+    bool is_f32 = std::is_same<T, float>::value;
+    int max_point = is_f32 ? 13 : 16;
+    int min_point = is_f32 ? -6 : -5;
+    if (length <= point && point <= max_point) {
+        return sign + digits + std::string(point - length, '0') + ".0";
+    }
+    if (0 < point && point <= max_point) {
+        return sign + digits.substr(0, point) + "." + digits.substr(point);
+    }
+    if (min_point < point && point <= 0) {
+        return sign + "0." + std::string(-point, '0') + digits;
+    }
+    std::string mantissa =
+        length == 1 ? digits : digits.substr(0, 1) + "." + digits.substr(1);
+    return sign + mantissa + "e" + std::to_string(point - 1);
+}
+
+// Decimal representation of the 128-bit unsigned integer `high * 2^64 + low`.
+inline std::string to_decimal(uint64_t high, uint64_t low) {
+    std::string digits;
+    do {
+        // Long division by 10, 32 bits at a time.
+        uint32_t limbs[4] = {(uint32_t)(high >> 32), (uint32_t)high,
+                             (uint32_t)(low >> 32), (uint32_t)low};
+        uint64_t remainder = 0;
+        for (auto &limb : limbs) {
+            uint64_t current = (remainder << 32) | limb;
+            limb = (uint32_t)(current / 10);
+            remainder = current % 10;
+        }
+        high = ((uint64_t)limbs[0] << 32) | limbs[1];
+        low = ((uint64_t)limbs[2] << 32) | limbs[3];
+        digits.push_back((char)('0' + remainder));
+    } while (high != 0 || low != 0);
+    std::reverse(digits.begin(), digits.end());
+    return digits;
+}
+
+// An integer read as a sign and a 128-bit magnitude.
+struct Integer {
+    bool negative;
+    uint64_t high;
+    uint64_t low;
+
+    bool is_zero() const { return high == 0 && low == 0; }
+
+    // Whether the magnitude is at most `high * 2^64 + low`.
+    bool at_most(uint64_t max_high, uint64_t max_low) const {
+        return high < max_high || (high == max_high && low <= max_low);
+    }
+};
+
+inline Integer read_integer(const Value &value, bool accepts_negative_zero) {
+    if (!(value.kind == Value::Kind::Number || value.kind == Value::Kind::Key) ||
+        !is_number(value.text, true)) {
+        throw deserialization_error("Invalid type: expected an integer");
+    }
+    // Like `serde_json`, read `-0` as a float, except for 128-bit integers.
+    if (value.text == "-0" && !accepts_negative_zero) {
+        throw deserialization_error("Invalid type: expected an integer");
+    }
+    Integer result = {value.text[0] == '-', 0, 0};
+    for (char c : value.text) {
+        if (c == '-') {
+            continue;
+        }
+        // Multiplication by 10, 32 bits at a time.
+        uint32_t limbs[4] = {(uint32_t)(result.high >> 32),
+                             (uint32_t)result.high,
+                             (uint32_t)(result.low >> 32),
+                             (uint32_t)result.low};
+        uint64_t carry = c - '0';
+        for (int i = 3; i >= 0; i--) {
+            uint64_t current = (uint64_t)limbs[i] * 10 + carry;
+            limbs[i] = (uint32_t)current;
+            carry = current >> 32;
+        }
+        if (carry != 0) {
+            throw deserialization_error("Integer out of range: " + value.text);
+        }
+        result.high = ((uint64_t)limbs[0] << 32) | limbs[1];
+        result.low = ((uint64_t)limbs[2] << 32) | limbs[3];
+    }
+    return result;
+}
+
+template <typename T>
+Value serialize_integer(T value) {
+    return Value::number(std::to_string(value));
+}
+
+template <typename T>
+T deserialize_integer(const Value &value) {
+    Integer integer = read_integer(value, false);
+    uint64_t max = std::numeric_limits<T>::max();
+    // Magnitude of the minimal value.
+    uint64_t min = -(uint64_t)std::numeric_limits<T>::min();
+    if (integer.high != 0 ||
+        integer.low > (integer.negative ? min : max)) {
+        throw deserialization_error("Integer out of range: " + value.text);
+    }
+    return integer.negative ? (T)(0 - integer.low) : (T)integer.low;
+}
+
+template <typename T>
+Value serialize_float(T value) {
+    if (!std::isfinite(value)) {
+        return Value::null();
+    }
+    return Value::number(format_float(value));
+}
+
+inline double deserialize_double(const Value &value) {
+    if (!(value.kind == Value::Kind::Number ||
+          (value.kind == Value::Kind::Key && is_number(value.text, false)))) {
+        throw deserialization_error("Invalid type: expected a number");
+    }
+    double result = std::strtod(value.text.c_str(), nullptr);
+    if (std::isinf(result)) {
+        throw deserialization_error("Number out of range: " + value.text);
+    }
+    return result;
+}
+
+inline const std::string &deserialize_text(const Value &value) {
+    if (value.kind != Value::Kind::String && value.kind != Value::Kind::Key) {
+        throw deserialization_error("Invalid type: expected a string");
+    }
+    return value.text;
+}
+
+} // end of namespace detail
+
+inline Value parse(const std::string &input) {
+    return detail::Parser(input).parse();
+}
+
+inline std::string stringify(const Value &value) {
+    std::string output;
+    detail::write(output, value);
+    return output;
+}
+
+// The key of a map entry, given the JSON value of the key.
+inline std::string key(const Value &value) {
+    switch (value.kind) {
+    case Value::Kind::String:
+    case Value::Kind::Key:
+    case Value::Kind::Number:
+        return value.text;
+    case Value::Kind::Bool:
+        return value.boolean ? "true" : "false";
+    default:
+        throw serialization_error("Map keys must be strings or numbers");
+    }
+}
+
+} // end of namespace json
+
+// Trait to convert values of type T into JSON values.
+template <typename T>
+struct JsonSerializable {
+    static json::Value serialize(const T &value);
+};
+
+// Trait to convert JSON values into values of type T.
+template <typename T>
+struct JsonDeserializable {
+    static T deserialize(const json::Value &value);
+};
+
+// --- Implementation of JsonSerializable for base types ---
+
+// string
+template <>
+struct JsonSerializable<std::string> {
+    static json::Value serialize(const std::string &value) {
+        return json::Value::string(value);
+    }
+};
+
+// unit
+template <>
+struct JsonSerializable<std::monostate> {
+    static json::Value serialize(const std::monostate &) {
+        return json::Value::null();
+    }
+};
+
+// bool
+template <>
+struct JsonSerializable<bool> {
+    static json::Value serialize(const bool &value) {
+        return json::Value::from_bool(value);
+    }
+};
+
+// char
+template <>
+struct JsonSerializable<char32_t> {
+    static json::Value serialize(const char32_t &value) {
+        if (value > 0x10FFFF || (value >= 0xD800 && value <= 0xDFFF)) {
+            throw serialization_error("Invalid unicode character");
+        }
+        std::string result;
+        json::detail::append_utf8(result, value);
+        return json::Value::string(result);
+    }
+};
+
+// f32
+template <>
+struct JsonSerializable<float> {
+    static json::Value serialize(const float &value) {
+        return json::detail::serialize_float(value);
+    }
+};
+
+// f64
+template <>
+struct JsonSerializable<double> {
+    static json::Value serialize(const double &value) {
+        return json::detail::serialize_float(value);
+    }
+};
+
+// u8
+template <>
+struct JsonSerializable<uint8_t> {
+    static json::Value serialize(const uint8_t &value) {
+        return json::detail::serialize_integer((unsigned)value);
+    }
+};
+
+// u16
+template <>
+struct JsonSerializable<uint16_t> {
+    static json::Value serialize(const uint16_t &value) {
+        return json::detail::serialize_integer(value);
+    }
+};
+
+// u32
+template <>
+struct JsonSerializable<uint32_t> {
+    static json::Value serialize(const uint32_t &value) {
+        return json::detail::serialize_integer(value);
+    }
+};
+
+// u64
+template <>
+struct JsonSerializable<uint64_t> {
+    static json::Value serialize(const uint64_t &value) {
+        return json::detail::serialize_integer(value);
+    }
+};
+
+// u128
+template <>
+struct JsonSerializable<uint128_t> {
+    static json::Value serialize(const uint128_t &value) {
+        return json::Value::number(
+            json::detail::to_decimal(value.high, value.low));
+    }
+};
+
+// i8
+template <>
+struct JsonSerializable<int8_t> {
+    static json::Value serialize(const int8_t &value) {
+        return json::detail::serialize_integer((int)value);
+    }
+};
+
+// i16
+template <>
+struct JsonSerializable<int16_t> {
+    static json::Value serialize(const int16_t &value) {
+        return json::detail::serialize_integer(value);
+    }
+};
+
+// i32
+template <>
+struct JsonSerializable<int32_t> {
+    static json::Value serialize(const int32_t &value) {
+        return json::detail::serialize_integer(value);
+    }
+};
+
+// i64
+template <>
+struct JsonSerializable<int64_t> {
+    static json::Value serialize(const int64_t &value) {
+        return json::detail::serialize_integer(value);
+    }
+};
+
+// i128
+template <>
+struct JsonSerializable<int128_t> {
+    static json::Value serialize(const int128_t &value) {
+        if (value.high >= 0) {
+            return json::Value::number(
+                json::detail::to_decimal(value.high, value.low));
+        }
+        // Two's complement of the magnitude.
+        uint64_t high = ~(uint64_t)value.high;
+        uint64_t low = ~value.low + 1;
+        if (low == 0) {
+            high += 1;
+        }
+        return json::Value::number("-" + json::detail::to_decimal(high, low));
+    }
+};
+
+// --- Derivation of JsonSerializable for composite types ---
+
+// Value pointers (non-nullable)
+template <typename T>
+struct JsonSerializable<value_ptr<T>> {
+    static json::Value serialize(const value_ptr<T> &value) {
+        return JsonSerializable<T>::serialize(*value);
+    }
+};
+
+// Options
+template <typename T>
+struct JsonSerializable<std::optional<T>> {
+    static json::Value serialize(const std::optional<T> &option) {
+        if (option.has_value()) {
+            return JsonSerializable<T>::serialize(option.value());
+        }
+        return json::Value::null();
+    }
+};
+
+// Vectors (sequences)
+template <typename T, typename Allocator>
+struct JsonSerializable<std::vector<T, Allocator>> {
+    static json::Value serialize(const std::vector<T, Allocator> &value) {
+        json::Value result = json::Value::array();
+        for (const T &item : value) {
+            result.push(JsonSerializable<T>::serialize(item));
+        }
+        return result;
+    }
+};
+
+// Fixed-size arrays
+template <typename T, std::size_t N>
+struct JsonSerializable<std::array<T, N>> {
+    static json::Value serialize(const std::array<T, N> &value) {
+        json::Value result = json::Value::array();
+        for (const T &item : value) {
+            result.push(JsonSerializable<T>::serialize(item));
+        }
+        return result;
+    }
+};
+
+// Maps
+template <typename K, typename V, typename Allocator>
+struct JsonSerializable<std::map<K, V, Allocator>> {
+    static json::Value serialize(const std::map<K, V, Allocator> &value) {
+        json::Value result = json::Value::object();
+        for (const auto &item : value) {
+            result.insert(json::key(JsonSerializable<K>::serialize(item.first)),
+                          JsonSerializable<V>::serialize(item.second));
+        }
+        return result;
+    }
+};
+
+// Tuples
+template <class... Types>
+struct JsonSerializable<std::tuple<Types...>> {
+    static json::Value serialize(const std::tuple<Types...> &value) {
+        json::Value result = json::Value::array();
+        std::apply(
+            [&result](Types const &... args) {
+                (result.push(JsonSerializable<Types>::serialize(args)), ...);
+            },
+            value);
+        return result;
+    }
+};
+
+// --- Implementation of JsonDeserializable for base types ---
+
+// string
+template <>
+struct JsonDeserializable<std::string> {
+    static std::string deserialize(const json::Value &value) {
+        return json::detail::deserialize_text(value);
+    }
+};
+
+// unit
+template <>
+struct JsonDeserializable<std::monostate> {
+    static std::monostate deserialize(const json::Value &value) {
+        if (!value.is_null()) {
+            throw deserialization_error("Invalid type: expected null");
+        }
+        return {};
+    }
+};
+
+// bool
+template <>
+struct JsonDeserializable<bool> {
+    static bool deserialize(const json::Value &value) {
+        if (value.kind == json::Value::Kind::Bool) {
+            return value.boolean;
+        }
+        if (value.kind == json::Value::Kind::Key &&
+            (value.text == "true" || value.text == "false")) {
+            return value.text == "true";
+        }
+        throw deserialization_error("Invalid type: expected a boolean");
+    }
+};
+
+// char
+template <>
+struct JsonDeserializable<char32_t> {
+    static char32_t deserialize(const json::Value &value) {
+        const std::string &text = json::detail::deserialize_text(value);
+        size_t length = json::detail::utf8_sequence_length(text, 0);
+        if (text.empty() || length != text.size()) {
+            throw deserialization_error("Invalid type: expected a character");
+        }
+        uint32_t code = (uint8_t)text[0];
+        if (length > 1) {
+            code &= 0x7F >> length;
+            for (size_t i = 1; i < length; i++) {
+                code = (code << 6) | ((uint8_t)text[i] & 0x3F);
+            }
+        }
+        return code;
+    }
+};
+
+// f32
+template <>
+struct JsonDeserializable<float> {
+    static float deserialize(const json::Value &value) {
+        // Like `serde_json`, read the closest f64 value then convert it to f32.
+        return (float)json::detail::deserialize_double(value);
+    }
+};
+
+// f64
+template <>
+struct JsonDeserializable<double> {
+    static double deserialize(const json::Value &value) {
+        return json::detail::deserialize_double(value);
+    }
+};
+
+// u8
+template <>
+struct JsonDeserializable<uint8_t> {
+    static uint8_t deserialize(const json::Value &value) {
+        return json::detail::deserialize_integer<uint8_t>(value);
+    }
+};
+
+// u16
+template <>
+struct JsonDeserializable<uint16_t> {
+    static uint16_t deserialize(const json::Value &value) {
+        return json::detail::deserialize_integer<uint16_t>(value);
+    }
+};
+
+// u32
+template <>
+struct JsonDeserializable<uint32_t> {
+    static uint32_t deserialize(const json::Value &value) {
+        return json::detail::deserialize_integer<uint32_t>(value);
+    }
+};
+
+// u64
+template <>
+struct JsonDeserializable<uint64_t> {
+    static uint64_t deserialize(const json::Value &value) {
+        return json::detail::deserialize_integer<uint64_t>(value);
+    }
+};
+
+// u128
+template <>
+struct JsonDeserializable<uint128_t> {
+    static uint128_t deserialize(const json::Value &value) {
+        auto integer = json::detail::read_integer(value, false);
+        if (integer.negative) {
+            throw deserialization_error("Integer out of range: " + value.text);
+        }
+        return {integer.high, integer.low};
+    }
+};
+
+// i8
+template <>
+struct JsonDeserializable<int8_t> {
+    static int8_t deserialize(const json::Value &value) {
+        return json::detail::deserialize_integer<int8_t>(value);
+    }
+};
+
+// i16
+template <>
+struct JsonDeserializable<int16_t> {
+    static int16_t deserialize(const json::Value &value) {
+        return json::detail::deserialize_integer<int16_t>(value);
+    }
+};
+
+// i32
+template <>
+struct JsonDeserializable<int32_t> {
+    static int32_t deserialize(const json::Value &value) {
+        return json::detail::deserialize_integer<int32_t>(value);
+    }
+};
+
+// i64
+template <>
+struct JsonDeserializable<int64_t> {
+    static int64_t deserialize(const json::Value &value) {
+        return json::detail::deserialize_integer<int64_t>(value);
+    }
+};
+
+// i128
+template <>
+struct JsonDeserializable<int128_t> {
+    static int128_t deserialize(const json::Value &value) {
+        auto integer = json::detail::read_integer(value, true);
+        uint64_t max_high = (uint64_t)1 << 63;
+        if (!integer.negative) {
+            if (!integer.at_most(max_high - 1, UINT64_MAX)) {
+                throw deserialization_error("Integer out of range: " +
+                                            value.text);
+            }
+            return {(int64_t)integer.high, integer.low};
+        }
+        if (!integer.at_most(max_high, 0)) {
+            throw deserialization_error("Integer out of range: " + value.text);
+        }
+        // Two's complement of the magnitude.
+        uint64_t high = ~integer.high;
+        uint64_t low = ~integer.low + 1;
+        if (low == 0) {
+            high += 1;
+        }
+        return {(int64_t)high, low};
+    }
+};
+
+// --- Derivation of JsonDeserializable for composite types ---
+
+// Value pointers
+template <typename T>
+struct JsonDeserializable<value_ptr<T>> {
+    static value_ptr<T> deserialize(const json::Value &value) {
+        return value_ptr<T>(JsonDeserializable<T>::deserialize(value));
+    }
+};
+
+// Options
+template <typename T>
+struct JsonDeserializable<std::optional<T>> {
+    static std::optional<T> deserialize(const json::Value &value) {
+        if (value.is_null()) {
+            return {};
+        }
+        return JsonDeserializable<T>::deserialize(value);
+    }
+};
+
+// Vectors
+template <typename T, typename Allocator>
+struct JsonDeserializable<std::vector<T, Allocator>> {
+    static std::vector<T> deserialize(const json::Value &value) {
+        std::vector<T> result;
+        for (const auto &item : value.as_array()) {
+            result.push_back(JsonDeserializable<T>::deserialize(item));
+        }
+        return result;
+    }
+};
+
+// Fixed-size arrays
+template <typename T, std::size_t N>
+struct JsonDeserializable<std::array<T, N>> {
+    static std::array<T, N> deserialize(const json::Value &value) {
+        const auto &items = value.as_array(N);
+        std::array<T, N> result;
+        for (size_t i = 0; i < N; i++) {
+            result[i] = JsonDeserializable<T>::deserialize(items[i]);
+        }
+        return result;
+    }
+};
+
+// Maps
+template <typename K, typename V>
+struct JsonDeserializable<std::map<K, V>> {
+    static std::map<K, V> deserialize(const json::Value &value) {
+        const auto &object = value.as_object();
+        std::map<K, V> result;
+        for (size_t i = 0; i < object.keys.size(); i++) {
+            auto key =
+                JsonDeserializable<K>::deserialize(json::Value::key(object.keys[i]));
+            // Like `serde_json`, later entries replace earlier ones with the
+            // same key.
+            result.insert_or_assign(
+                std::move(key), JsonDeserializable<V>::deserialize(object.items[i]));
+        }
+        return result;
+    }
+};
+
+// Tuples
+template <class... Types>
+struct JsonDeserializable<std::tuple<Types...>> {
+    static std::tuple<Types...> deserialize(const json::Value &value) {
+        const auto &items = value.as_array(sizeof...(Types));
+        return deserialize_items(items, std::index_sequence_for<Types...>{});
+    }
+
+  private:
+    template <std::size_t... Is>
+    static std::tuple<Types...>
+    deserialize_items(const std::vector<json::Value> &items,
+                      std::index_sequence<Is...>) {
+        return std::tuple<Types...>{
+            JsonDeserializable<Types>::deserialize(items[Is])...};
+    }
+};
+
+} // end of namespace serde
@@ -0,0 +1,300 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#pragma once
+
+#include <cstdint>
+#include <limits>
+
+#include "binary.hpp"
+#include "serde.hpp"
+
+// Maximum length supported in practice (e.g. Java).
+constexpr size_t POSTCARD_MAX_LENGTH = (1ull << 31) - 1;
+
+namespace serde {
+
+class PostcardSerializer : public BinarySerializer<PostcardSerializer> {
+    using Parent = BinarySerializer<PostcardSerializer>;
+
+    void serialize_varint(uint64_t value);
+    void serialize_varint(uint64_t high, uint64_t low);
+
+  public:
+    PostcardSerializer() : Parent(SIZE_MAX) {}
+
+    void serialize_char(char32_t value);
+    void serialize_f32(float value);
+    void serialize_f64(double value);
+
+    void serialize_u16(uint16_t value);
+    void serialize_u32(uint32_t value);
+    void serialize_u64(uint64_t value);
+    void serialize_u128(const uint128_t &value);
+
+    void serialize_i16(int16_t value);
+    void serialize_i32(int32_t value);
+    void serialize_i64(int64_t value);
+    void serialize_i128(const int128_t &value);
+
+    void serialize_len(size_t value);
+    void serialize_variant_index(uint32_t value);
+
+    static constexpr bool enforce_strict_map_ordering = false;
+};
+
+class PostcardDeserializer : public BinaryDeserializer<PostcardDeserializer> {
+    using Parent = BinaryDeserializer<PostcardDeserializer>;
+
+    uint64_t deserialize_varint(unsigned bits);
+    uint128_t deserialize_varint128();
+
+  public:
+    PostcardDeserializer(std::vector<uint8_t> bytes)
+        : Parent(std::move(bytes), SIZE_MAX) {}
+
+    char32_t deserialize_char();
+    float deserialize_f32();
+    double deserialize_f64();
+
+    uint16_t deserialize_u16();
+    uint32_t deserialize_u32();
+    uint64_t deserialize_u64();
+    uint128_t deserialize_u128();
+
+    int16_t deserialize_i16();
+    int32_t deserialize_i32();
+    int64_t deserialize_i64();
+    int128_t deserialize_i128();
+
+    size_t deserialize_len();
+    uint32_t deserialize_variant_index();
+
+    static constexpr bool enforce_strict_map_ordering = false;
+};
+
+// Native floats and doubles must be IEEE-754 values of the expected size.
+static_assert(std::numeric_limits<float>::is_iec559);
+static_assert(std::numeric_limits<double>::is_iec559);
+static_assert(sizeof(float) == sizeof(uint32_t));
+static_assert(sizeof(double) == sizeof(uint64_t));
+
+inline void PostcardSerializer::serialize_varint(uint64_t value) {
+    while (value >= 0x80) {
+        bytes_.push_back((uint8_t)((value & 0x7f) | 0x80));
+        value >>= 7;
+    }
+    bytes_.push_back((uint8_t)value);
+}
+
+inline void PostcardSerializer::serialize_varint(uint64_t high, uint64_t low) {
+    while (high != 0 || low >= 0x80) {
+        bytes_.push_back((uint8_t)((low & 0x7f) | 0x80));
+        low = (low >> 7) | (high << 57);
+        high >>= 7;
+    }
+    bytes_.push_back((uint8_t)low);
+}
+
+inline void PostcardSerializer::serialize_char(char32_t value) {
+    std::string result;
+    if (value < 0x80) {
+        result.push_back((char)value);
+    } else if (value < 0x800) {
+        result.push_back((char)(0xc0 | (value >> 6)));
+        result.push_back((char)(0x80 | (value & 0x3f)));
+    } else if (value < 0x10000) {
+        result.push_back((char)(0xe0 | (value >> 12)));
+        result.push_back((char)(0x80 | ((value >> 6) & 0x3f)));
+        result.push_back((char)(0x80 | (value & 0x3f)));
+    } else if (value < 0x110000) {
+        result.push_back((char)(0xf0 | (value >> 18)));
+        result.push_back((char)(0x80 | ((value >> 12) & 0x3f)));
+        result.push_back((char)(0x80 | ((value >> 6) & 0x3f)));
+        result.push_back((char)(0x80 | (value & 0x3f)));
+    } else {
+        throw serde::serialization_error("Invalid unicode character");
+    }
+    Parent::serialize_str(result);
+}
+
+inline void PostcardSerializer::serialize_f32(float value) {
+    Parent::serialize_u32(*reinterpret_cast<uint32_t *>(&value));
+}
+
+inline void PostcardSerializer::serialize_f64(double value) {
+    Parent::serialize_u64(*reinterpret_cast<uint64_t *>(&value));
+}
+
+inline void PostcardSerializer::serialize_u16(uint16_t value) {
+    serialize_varint(value);
+}
+
+inline void PostcardSerializer::serialize_u32(uint32_t value) {
+    serialize_varint(value);
+}
+
+inline void PostcardSerializer::serialize_u64(uint64_t value) {
+    serialize_varint(value);
+}
+
+inline void PostcardSerializer::serialize_u128(const uint128_t &value) {
+    serialize_varint(value.high, value.low);
+}
+
+// Signed integers use a "zigzag" encoding before the varint encoding.
+inline void PostcardSerializer::serialize_i16(int16_t value) {
+    serialize_varint((uint16_t)(((uint16_t)value << 1) ^ (value >> 15)));
+}
+
+inline void PostcardSerializer::serialize_i32(int32_t value) {
+    serialize_varint((uint32_t)(((uint32_t)value << 1) ^ (value >> 31)));
+}
+
+inline void PostcardSerializer::serialize_i64(int64_t value) {
+    serialize_varint(((uint64_t)value << 1) ^ (uint64_t)(value >> 63));
+}
+
+inline void PostcardSerializer::serialize_i128(const int128_t &value) {
+    uint64_t sign = (uint64_t)(value.high >> 63);
+    uint64_t high = ((uint64_t)value.high << 1) | (value.low >> 63);
+    uint64_t low = value.low << 1;
+    serialize_varint(high ^ sign, low ^ sign);
+}
+
+inline void PostcardSerializer::serialize_len(size_t value) {
+    if (value > POSTCARD_MAX_LENGTH) {
+        throw serde::serialization_error("Length is too large");
+    }
+    serialize_varint((uint64_t)value);
+}
+
+inline void PostcardSerializer::serialize_variant_index(uint32_t value) {
+    serialize_varint(value);
+}
+
+inline uint64_t PostcardDeserializer::deserialize_varint(unsigned bits) {
+    uint64_t value = 0;
+    for (unsigned shift = 0; shift < bits; shift += 7) {
+        uint8_t byte = read_byte();
+        uint64_t digit = byte & 0x7f;
+        if (bits - shift < 7 && (digit >> (bits - shift)) != 0) {
+            throw serde::deserialization_error(
+                "Overflow while parsing varint-encoded value");
+        }
+        value |= digit << shift;
+        if ((byte & 0x80) == 0) {
+            return value;
+        }
+    }
+    throw serde::deserialization_error("Varint-encoded value is too long");
+}
+
+inline uint128_t PostcardDeserializer::deserialize_varint128() {
+    uint128_t value = {0, 0};
+    for (unsigned shift = 0; shift < 128; shift += 7) {
+        uint8_t byte = read_byte();
+        uint64_t digit = byte & 0x7f;
+        if (128 - shift < 7 && (digit >> (128 - shift)) != 0) {
+            throw serde::deserialization_error(
+                "Overflow while parsing varint-encoded value");
+        }
+        if (shift < 64) {
+            value.low |= digit << shift;
+            if (shift > 57) {
+                value.high |= digit >> (64 - shift);
+            }
+        } else {
+            value.high |= digit << (shift - 64);
+        }
+        if ((byte & 0x80) == 0) {
+            return value;
+        }
+    }
+    throw serde::deserialization_error("Varint-encoded value is too long");
+}
+
+inline char32_t PostcardDeserializer::deserialize_char() {
+    auto value = Parent::deserialize_str();
+    size_t len = value.size();
+    auto byte = [&](size_t i) { return (char32_t)(uint8_t)value[i]; };
+    if (len == 1 && byte(0) < 0x80) {
+        return byte(0);
+    }
+    if (len == 2 && byte(0) >> 5 == 0b110) {
+        return ((byte(0) & 0x1f) << 6) | (byte(1) & 0x3f);
+    }
+    if (len == 3 && byte(0) >> 4 == 0b1110) {
+        return ((byte(0) & 0x0f) << 12) | ((byte(1) & 0x3f) << 6) |
+               (byte(2) & 0x3f);
+    }
+    if (len == 4 && byte(0) >> 3 == 0b11110) {
+        return ((byte(0) & 0x07) << 18) | ((byte(1) & 0x3f) << 12) |
+               ((byte(2) & 0x3f) << 6) | (byte(3) & 0x3f);
+    }
+    throw serde::deserialization_error("Expecting a single character");
+}
+
+inline float PostcardDeserializer::deserialize_f32() {
+    auto value = Parent::deserialize_u32();
+    return *reinterpret_cast<float *>(&value);
+}
+
+inline double PostcardDeserializer::deserialize_f64() {
+    auto value = Parent::deserialize_u64();
+    return *reinterpret_cast<double *>(&value);
+}
+
+inline uint16_t PostcardDeserializer::deserialize_u16() {
+    return (uint16_t)deserialize_varint(16);
+}
+
+inline uint32_t PostcardDeserializer::deserialize_u32() {
+    return (uint32_t)deserialize_varint(32);
+}
+
+inline uint64_t PostcardDeserializer::deserialize_u64() {
+    return deserialize_varint(64);
+}
+
+inline uint128_t PostcardDeserializer::deserialize_u128() {
+    return deserialize_varint128();
+}
+
+inline int16_t PostcardDeserializer::deserialize_i16() {
+    auto value = (uint16_t)deserialize_varint(16);
+    return (int16_t)((value >> 1) ^ -(value & 1));
+}
+
+inline int32_t PostcardDeserializer::deserialize_i32() {
+    auto value = (uint32_t)deserialize_varint(32);
+    return (int32_t)((value >> 1) ^ -(value & 1));
+}
+
+inline int64_t PostcardDeserializer::deserialize_i64() {
+    auto value = deserialize_varint(64);
+    return (int64_t)((value >> 1) ^ -(value & 1));
+}
+
+inline int128_t PostcardDeserializer::deserialize_i128() {
+    auto value = deserialize_varint128();
+    uint64_t sign = -(value.low & 1);
+    int128_t result;
+    result.low = ((value.low >> 1) | (value.high << 63)) ^ sign;
+    result.high = (int64_t)((value.high >> 1) ^ sign);
+    return result;
+}
+
+inline size_t PostcardDeserializer::deserialize_len() {
+    auto value = deserialize_varint(64);
+    if (value > POSTCARD_MAX_LENGTH) {
+        throw serde::deserialization_error("Length is too large");
+    }
+    return (size_t)value;
+}
+
+inline uint32_t PostcardDeserializer::deserialize_variant_index() {
+    return (uint32_t)deserialize_varint(32);
+}
+
+} // end of namespace serde
@@ -0,0 +1,143 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#pragma once
+
+#include <cstdint>
+#include <limits>
+
+#include "binary.hpp"
+#include "serde.hpp"
+
+namespace serde {
+
+// Maximum length supported in practice (e.g. Java).
+constexpr size_t SCALE_MAX_LENGTH = (1ull << 31) - 1;
+
+class ScaleSerializer : public BinarySerializer<ScaleSerializer> {
+    using Parent = BinarySerializer<ScaleSerializer>;
+
+    void serialize_compact_u32(uint32_t value);
+
+  public:
+    ScaleSerializer() : Parent(SIZE_MAX) {}
+
+    void serialize_f32(float value);
+    void serialize_f64(double value);
+
+    void serialize_len(size_t value);
+    void serialize_variant_index(uint32_t value);
+
+    static constexpr bool enforce_strict_map_ordering = false;
+};
+
+class ScaleDeserializer : public BinaryDeserializer<ScaleDeserializer> {
+    using Parent = BinaryDeserializer<ScaleDeserializer>;
+
+    uint32_t deserialize_compact_u32();
+
+  public:
+    ScaleDeserializer(std::vector<uint8_t> bytes)
+        : Parent(std::move(bytes), SIZE_MAX) {}
+
+    float deserialize_f32();
+    double deserialize_f64();
+
+    size_t deserialize_len();
+    uint32_t deserialize_variant_index();
+
+    static constexpr bool enforce_strict_map_ordering = false;
+};
+
+inline void ScaleSerializer::serialize_compact_u32(uint32_t value) {
+    if (value < (1u << 6)) {
+        Parent::serialize_u8((uint8_t)(value << 2));
+    } else if (value < (1u << 14)) {
+        Parent::serialize_u16((uint16_t)((value << 2) | 1));
+    } else if (value < (1u << 30)) {
+        Parent::serialize_u32((value << 2) | 2);
+    } else {
+        Parent::serialize_u8(3);
+        Parent::serialize_u32(value);
+    }
+}
+
+inline void ScaleSerializer::serialize_f32(float value) {
+    Parent::serialize_u32(*reinterpret_cast<uint32_t *>(&value));
+}
+
+inline void ScaleSerializer::serialize_f64(double value) {
+    Parent::serialize_u64(*reinterpret_cast<uint64_t *>(&value));
+}
+
+inline void ScaleSerializer::serialize_len(size_t value) {
+    if (value > SCALE_MAX_LENGTH) {
+        throw serde::serialization_error("Length is too large");
+    }
+    serialize_compact_u32((uint32_t)value);
+}
+
+inline void ScaleSerializer::serialize_variant_index(uint32_t value) {
+    if (value > std::numeric_limits<uint8_t>::max()) {
+        throw serde::serialization_error("Variant index is too large");
+    }
+    Parent::serialize_u8((uint8_t)value);
+}
+
+inline uint32_t ScaleDeserializer::deserialize_compact_u32() {
+    uint32_t prefix = Parent::deserialize_u8();
+    uint32_t value;
+    uint32_t minimum;
+    switch (prefix & 3) {
+    case 0:
+        return prefix >> 2;
+    case 1:
+        value = (prefix | ((uint32_t)Parent::deserialize_u8() << 8)) >> 2;
+        minimum = 1u << 6;
+        break;
+    case 2:
+        value = prefix;
+        for (auto shift = 8; shift < 32; shift += 8) {
+            value |= (uint32_t)Parent::deserialize_u8() << shift;
+        }
+        value >>= 2;
+        minimum = 1u << 14;
+        break;
+    default:
+        if (prefix != 3) {
+            throw serde::deserialization_error(
+                "Compact integer does not fit in 32 bits");
+        }
+        value = Parent::deserialize_u32();
+        minimum = 1u << 30;
+    }
+    if (value < minimum) {
+        throw serde::deserialization_error(
+            "Compact integer is not canonically encoded");
+    }
+    return value;
+}
+
+inline float ScaleDeserializer::deserialize_f32() {
+    auto bits = Parent::deserialize_u32();
+    return *reinterpret_cast<float *>(&bits);
+}
+
+inline double ScaleDeserializer::deserialize_f64() {
+    auto bits = Parent::deserialize_u64();
+    return *reinterpret_cast<double *>(&bits);
+}
+
+inline size_t ScaleDeserializer::deserialize_len() {
+    auto value = deserialize_compact_u32();
+    if (value > SCALE_MAX_LENGTH) {
+        throw serde::deserialization_error("Length is too large");
+    }
+    return (size_t)value;
+}
+
+inline uint32_t ScaleDeserializer::deserialize_variant_index() {
+    return Parent::deserialize_u8();
+}
+
+} // end of namespace serde
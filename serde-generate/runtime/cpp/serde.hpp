@@ -40,24 +40,34 @@ struct uint128_t {
     uint64_t low;
 
     friend bool operator==(const uint128_t &, const uint128_t &);
+    friend bool operator<(const uint128_t &, const uint128_t &);
 };
 
 inline bool operator==(const uint128_t &lhs, const uint128_t &rhs) {
     return lhs.high == rhs.high && lhs.low == rhs.low;
 }
 
+inline bool operator<(const uint128_t &lhs, const uint128_t &rhs) {
+    return std::tie(lhs.high, lhs.low) < std::tie(rhs.high, rhs.low);
+}
+
 // 128-bit signed integers.
 struct int128_t {
     int64_t high;
     uint64_t low;
 
     friend bool operator==(const int128_t &, const int128_t &);
+    friend bool operator<(const int128_t &, const int128_t &);
 };
 
 inline bool operator==(const int128_t &lhs, const int128_t &rhs) {
     return lhs.high == rhs.high && lhs.low == rhs.low;
 }
 
+inline bool operator<(const int128_t &lhs, const int128_t &rhs) {
+    return std::tie(lhs.high, lhs.low) < std::tie(rhs.high, rhs.low);
+}
+
 // A copyable unique_ptr with value semantics.
 // Freely inspired by the following discussion:
 // https://codereview.stackexchange.com/questions/103744/deepptr-a-deep-copying-unique-ptr-wrapper-in-c
@@ -99,6 +109,9 @@ class value_ptr {
     template <typename U>
     friend bool operator==(const value_ptr<U> &, const value_ptr<U> &);
 
+    template <typename U>
+    friend bool operator<(const value_ptr<U> &, const value_ptr<U> &);
+
   private:
     std::unique_ptr<T> ptr_;
 };
@@ -108,6 +121,118 @@ bool operator==(const value_ptr<T> &lhs, const value_ptr<T> &rhs) {
     return *lhs == *rhs;
 }
 
+template <typename T>
+bool operator<(const value_ptr<T> &lhs, const value_ptr<T> &rhs) {
+    return *lhs < *rhs;
+}
+
+// Trait to compute hashes consistent with `operator==` on values of type T.
+// Defaults to `std::hash`, which generated containers specialize.
+template <typename T>
+struct Hashable {
+    static size_t hash(const T &value) { return std::hash<T>{}(value); }
+};
+
+inline void hash_combine(size_t &seed, size_t value) {
+    seed ^= value + 0x9e3779b9 + (seed << 6) + (seed >> 2);
+}
+
+template <typename T>
+struct Hashable<value_ptr<T>> {
+    static size_t hash(const value_ptr<T> &value) {
+        return Hashable<T>::hash(*value);
+    }
+};
+
+template <>
+struct Hashable<uint128_t> {
+    static size_t hash(const uint128_t &value) {
+        size_t seed = std::hash<uint64_t>{}(value.high);
+        hash_combine(seed, std::hash<uint64_t>{}(value.low));
+        return seed;
+    }
+};
+
+template <>
+struct Hashable<int128_t> {
+    static size_t hash(const int128_t &value) {
+        size_t seed = std::hash<int64_t>{}(value.high);
+        hash_combine(seed, std::hash<uint64_t>{}(value.low));
+        return seed;
+    }
+};
+
+template <typename T>
+struct Hashable<std::optional<T>> {
+    static size_t hash(const std::optional<T> &value) {
+        size_t seed = value.has_value();
+        if (value.has_value()) {
+            hash_combine(seed, Hashable<T>::hash(*value));
+        }
+        return seed;
+    }
+};
+
+template <typename T, typename Allocator>
+struct Hashable<std::vector<T, Allocator>> {
+    static size_t hash(const std::vector<T, Allocator> &value) {
+        size_t seed = value.size();
+        for (const auto &item : value) {
+            hash_combine(seed, Hashable<T>::hash(item));
+        }
+        return seed;
+    }
+};
+
+template <typename T, std::size_t N>
+struct Hashable<std::array<T, N>> {
+    static size_t hash(const std::array<T, N> &value) {
+        size_t seed = N;
+        for (const auto &item : value) {
+            hash_combine(seed, Hashable<T>::hash(item));
+        }
+        return seed;
+    }
+};
+
+template <typename K, typename V, typename Allocator>
+struct Hashable<std::map<K, V, Allocator>> {
+    static size_t hash(const std::map<K, V, Allocator> &value) {
+        size_t seed = value.size();
+        for (const auto &item : value) {
+            hash_combine(seed, Hashable<K>::hash(item.first));
+            hash_combine(seed, Hashable<V>::hash(item.second));
+        }
+        return seed;
+    }
+};
+
+template <class... Types>
+struct Hashable<std::tuple<Types...>> {
+    static size_t hash(const std::tuple<Types...> &value) {
+        size_t seed = 0;
+        std::apply(
+            [&seed](const auto &...items) {
+                (hash_combine(seed, Hashable<std::decay_t<decltype(items)>>::hash(items)), ...);
+            },
+            value);
+        return seed;
+    }
+};
+
+template <class... Types>
+struct Hashable<std::variant<Types...>> {
+    static size_t hash(const std::variant<Types...> &value) {
+        size_t seed = value.index();
+        std::visit(
+            [&seed](const auto &item) {
+                hash_combine(seed, Hashable<std::decay_t<decltype(item)>>::hash(item));
+            },
+            value);
+        return seed;
+    }
+};
+
 // Trait to enable serialization of values of type T.
 // This is similar to the `serde::Serialize` trait in Rust.
 template <typename T>
@@ -643,7 +768,7 @@ struct Deserializable<std::variant<Types...>> {
 
         // Read the variant index and execute the corresponding case.
         auto index = deserializer.deserialize_variant_index();
-        if (index > cases.size()) {
+        if (index >= cases.size()) {
             throw deserialization_error("Unknown variant index for enum");
         }
         return cases.at(index)(deserializer);
@@ -3,25 +3,138 @@
 
 using Serde;
 using System;
+using System.Numerics;
 
 namespace Bincode
 {
     public class BincodeDeserializer : BinaryDeserializer
     {
+        private readonly bool varintEncoding;
+        private readonly bool bigEndian;
+        private readonly long limit = long.MaxValue;
+
         public BincodeDeserializer(byte[] input) : base(input, long.MaxValue) { }
         public BincodeDeserializer(ArraySegment<byte> input) : base(input, long.MaxValue) { }
 
+        // See `BincodeSerializer` for the meaning of the options.
+        public BincodeDeserializer(ArraySegment<byte> input, bool varintEncoding, bool bigEndian) : base(input, long.MaxValue)
+        {
+            this.varintEncoding = varintEncoding;
+            this.bigEndian = bigEndian;
+        }
+
+        // Reading more than `limit` bytes fails, as with the option `with_limit` of the Rust crate.
+        public BincodeDeserializer(ArraySegment<byte> input, bool varintEncoding, bool bigEndian, long limit) : this(input, varintEncoding, bigEndian)
+        {
+            this.limit = limit;
+        }
+
+        // Check that `size` more bytes may be read within the limit.
+        private void check_limit(long size)
+        {
+            if (size > limit - get_buffer_offset())
+                throw new DeserializationException("Exceeded the size limit of the encoding");
+        }
+
+        protected override byte read_byte()
+        {
+            check_limit(1);
+            return base.read_byte();
+        }
+
+        protected override byte[] read_bytes(int length)
+        {
+            // Check the length before allocating the content.
+            check_limit(length);
+            return base.read_bytes(length);
+        }
+
+        // Read a value between 0 and 2^(8 * size) - 1 in the configured byte order.
+        private BigInteger read_fixint(int size)
+        {
+            byte[] content = read_bytes(size);
+            if (content.Length < size)
+                throw new DeserializationException("Need more bytes to deserialize integer");
+            BigInteger value = 0;
+            for (int i = 0; i < size; i++)
+            {
+                int shift = bigEndian ? 8 * (size - 1 - i) : 8 * i;
+                value |= new BigInteger(content[i]) << shift;
+            }
+            return value;
+        }
+
+        private BigInteger deserialize_varint(int bits)
+        {
+            byte tag = read_byte();
+            BigInteger value;
+            if (tag <= 250)
+                value = tag;
+            else if (tag == 251)
+                value = read_fixint(2);
+            else if (tag == 252)
+                value = read_fixint(4);
+            else if (tag == 253)
+                value = read_fixint(8);
+            else if (tag == 254 && bits == 128)
+                value = read_fixint(16);
+            else
+                throw new DeserializationException("Invalid tag for varint-encoded value");
+            if (value >> bits != 0)
+            {
+                throw new DeserializationException("Overflow while parsing varint-encoded value");
+            }
+            return value;
+        }
+
+        private BigInteger deserialize_unsigned(int size) =>
+            varintEncoding ? deserialize_varint(8 * size) : read_fixint(size);
+
+        private BigInteger deserialize_signed(int size)
+        {
+            if (varintEncoding)
+            {
+                BigInteger value = deserialize_varint(8 * size);
+                return value.IsEven ? value >> 1 : -(value >> 1) - 1;
+            }
+            else
+            {
+                BigInteger value = read_fixint(size);
+                return value >> (8 * size - 1) == 0 ? value : value - (BigInteger.One << (8 * size));
+            }
+        }
+
+        public override float deserialize_f32() => BitConverter.Int32BitsToSingle((int)(uint)read_fixint(4));
+
+        public override double deserialize_f64() => BitConverter.Int64BitsToDouble((long)(ulong)read_fixint(8));
+
         public override long deserialize_len()
         {
-            long value = reader.ReadInt64();
-            if (value < 0 || value > int.MaxValue)
+            BigInteger value = deserialize_unsigned(8);
+            if (value > int.MaxValue)
             {
                 throw new DeserializationException("Incorrect length value");
             }
-            return value;
+            return (long)value;
         }
 
-        public override int deserialize_variant_index() => reader.ReadInt32();
+        public override int deserialize_variant_index() => (int)(uint)deserialize_unsigned(4);
+
+        public override ushort deserialize_u16() => (ushort)deserialize_unsigned(2);
+
+        public override uint deserialize_u32() => (uint)deserialize_unsigned(4);
+
+        public override ulong deserialize_u64() => (ulong)deserialize_unsigned(8);
+
+        public override BigInteger deserialize_u128() => deserialize_unsigned(16);
+
+        public override short deserialize_i16() => (short)deserialize_signed(2);
+
+        public override int deserialize_i32() => (int)deserialize_signed(4);
+
+        public override long deserialize_i64() => (long)deserialize_signed(8);
+
+        public override BigInteger deserialize_i128() => deserialize_signed(16);
 
         public override void check_that_key_slices_are_increasing(Range key1, Range key2)
         {
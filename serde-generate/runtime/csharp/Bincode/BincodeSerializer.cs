@@ -3,18 +3,124 @@
 
 using Serde;
 using System;
+using System.Numerics;
 
 namespace Bincode
 {
     public class BincodeSerializer : BinarySerializer
     {
+        private readonly bool varintEncoding;
+        private readonly bool bigEndian;
+
         public BincodeSerializer() : base(long.MaxValue) { }
         public BincodeSerializer(byte[] buffer) : base(buffer, long.MaxValue) { }
         public BincodeSerializer(ArraySegment<byte> buffer) : base(buffer, long.MaxValue) { }
 
-        public override void serialize_len(long value) => output.Write(value);
+        // `varintEncoding` and `bigEndian` correspond to the options `with_varint_encoding`
+        // and `with_big_endian` of the Rust crate.
+        public BincodeSerializer(bool varintEncoding, bool bigEndian) : base(long.MaxValue)
+        {
+            this.varintEncoding = varintEncoding;
+            this.bigEndian = bigEndian;
+        }
+        public BincodeSerializer(ArraySegment<byte> buffer, bool varintEncoding, bool bigEndian) : base(buffer, long.MaxValue)
+        {
+            this.varintEncoding = varintEncoding;
+            this.bigEndian = bigEndian;
+        }
+
+        // Write a value between 0 and 2^(8 * size) - 1 in the configured byte order.
+        private void write_fixint(BigInteger value, int size)
+        {
+            for (int i = 0; i < size; i++)
+            {
+                int shift = bigEndian ? 8 * (size - 1 - i) : 8 * i;
+                output.Write((byte)((value >> shift) & 0xFF));
+            }
+        }
+
+        private void serialize_varint(BigInteger value)
+        {
+            if (value <= 250)
+            {
+                output.Write((byte)value);
+            }
+            else if (value <= ushort.MaxValue)
+            {
+                output.Write((byte)251);
+                write_fixint(value, 2);
+            }
+            else if (value <= uint.MaxValue)
+            {
+                output.Write((byte)252);
+                write_fixint(value, 4);
+            }
+            else if (value <= ulong.MaxValue)
+            {
+                output.Write((byte)253);
+                write_fixint(value, 8);
+            }
+            else
+            {
+                output.Write((byte)254);
+                write_fixint(value, 16);
+            }
+        }
+
+        private void serialize_unsigned(BigInteger value, int size)
+        {
+            if (varintEncoding)
+                serialize_varint(value);
+            else
+                write_fixint(value, size);
+        }
+
+        // Signed integers use a "zigzag" encoding before the varint encoding.
+        private void serialize_signed(BigInteger value, int size)
+        {
+            if (varintEncoding)
+                serialize_varint(value >= 0 ? value << 1 : -(value << 1) - 1);
+            else
+                write_fixint(value >= 0 ? value : value + (BigInteger.One << (8 * size)), size);
+        }
+
+        public override void serialize_f32(float value) => write_fixint((uint)BitConverter.SingleToInt32Bits(value), 4);
+
+        public override void serialize_f64(double value) => write_fixint((ulong)BitConverter.DoubleToInt64Bits(value), 8);
+
+        public override void serialize_len(long value) => serialize_unsigned((ulong)value, 8);
+
+        public override void serialize_variant_index(int value) => serialize_unsigned((uint)value, 4);
+
+        public override void serialize_u16(ushort value) => serialize_unsigned(value, 2);
+
+        public override void serialize_u32(uint value) => serialize_unsigned(value, 4);
+
+        public override void serialize_u64(ulong value) => serialize_unsigned(value, 8);
 
-        public override void serialize_variant_index(int value) => output.Write(value);
+        public override void serialize_u128(BigInteger value)
+        {
+            if (value < 0 || value >> 128 != 0)
+            {
+                throw new SerializationException("Invalid value for an unsigned int128");
+            }
+            serialize_unsigned(value, 16);
+        }
+
+        public override void serialize_i16(short value) => serialize_signed(value, 2);
+
+        public override void serialize_i32(int value) => serialize_signed(value, 4);
+
+        public override void serialize_i64(long value) => serialize_signed(value, 8);
+
+        public override void serialize_i128(BigInteger value)
+        {
+            if (value >= 0 ? value >> 127 != 0 : (-(value + 1)) >> 127 != 0)
+            {
+                throw new SerializationException("Invalid value for a signed int128");
+            }
+            serialize_signed(value, 16);
+        }
 
         public override void sort_map_entries(int[] offsets)
         {
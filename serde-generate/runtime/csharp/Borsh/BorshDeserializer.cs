@@ -0,0 +1,143 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using Serde;
+using System;
+using System.Numerics;
+
+namespace Borsh
+{
+    public class BorshDeserializer : BinaryDeserializer
+    {
+        public BorshDeserializer(byte[] input) : base(input, long.MaxValue) { }
+        public BorshDeserializer(ArraySegment<byte> input) : base(input, long.MaxValue) { }
+
+        private readonly SortKeys sortKeys = new SortKeys();
+
+        private ReadOnlySpan<byte> BytesSince(int start) => input.Slice(new Range(start, get_buffer_offset()));
+
+        private void RecordInteger(int start, bool signed) => sortKeys.RecordInteger(start, BytesSince(start), signed);
+
+        public override ushort deserialize_u16()
+        {
+            int start = get_buffer_offset();
+            ushort value = base.deserialize_u16();
+            RecordInteger(start, false);
+            return value;
+        }
+
+        public override uint deserialize_u32()
+        {
+            int start = get_buffer_offset();
+            uint value = base.deserialize_u32();
+            RecordInteger(start, false);
+            return value;
+        }
+
+        public override ulong deserialize_u64()
+        {
+            int start = get_buffer_offset();
+            ulong value = base.deserialize_u64();
+            RecordInteger(start, false);
+            return value;
+        }
+
+        public override BigInteger deserialize_u128()
+        {
+            int start = get_buffer_offset();
+            BigInteger value = base.deserialize_u128();
+            RecordInteger(start, false);
+            return value;
+        }
+
+        public override sbyte deserialize_i8()
+        {
+            int start = get_buffer_offset();
+            sbyte value = base.deserialize_i8();
+            RecordInteger(start, true);
+            return value;
+        }
+
+        public override short deserialize_i16()
+        {
+            int start = get_buffer_offset();
+            short value = base.deserialize_i16();
+            RecordInteger(start, true);
+            return value;
+        }
+
+        public override int deserialize_i32()
+        {
+            int start = get_buffer_offset();
+            int value = base.deserialize_i32();
+            RecordInteger(start, true);
+            return value;
+        }
+
+        public override long deserialize_i64()
+        {
+            int start = get_buffer_offset();
+            long value = base.deserialize_i64();
+            RecordInteger(start, true);
+            return value;
+        }
+
+        public override BigInteger deserialize_i128()
+        {
+            int start = get_buffer_offset();
+            BigInteger value = base.deserialize_i128();
+            RecordInteger(start, true);
+            return value;
+        }
+
+        public override string deserialize_str()
+        {
+            int start = get_buffer_offset();
+            string value = base.deserialize_str();
+            sortKeys.RecordStr(start, BytesSince(start));
+            return value;
+        }
+
+        public override float deserialize_f32()
+        {
+            float value = reader.ReadSingle();
+            if (float.IsNaN(value))
+            {
+                throw new DeserializationException("NaN values are not supported by Borsh");
+            }
+            return value;
+        }
+
+        public override double deserialize_f64()
+        {
+            double value = reader.ReadDouble();
+            if (double.IsNaN(value))
+            {
+                throw new DeserializationException("NaN values are not supported by Borsh");
+            }
+            return value;
+        }
+
+        public override long deserialize_len()
+        {
+            long value = reader.ReadUInt32();
+            if (value > BorshSerializer.MAX_LENGTH)
+            {
+                throw new DeserializationException("Incorrect length value");
+            }
+            return value;
+        }
+
+        public override int deserialize_variant_index() => reader.ReadByte();
+
+        public override void check_that_key_slices_are_increasing(Range key1, Range key2)
+        {
+            byte[] sortKey1 = sortKeys.SortKey(input.Slice(key1), key1.Start);
+            byte[] sortKey2 = sortKeys.SortKey(input.Slice(key2), key2.Start);
+            if (Verification.CompareLexicographic(sortKey1, sortKey2) >= 0)
+            {
+                throw new DeserializationException("Error while decoding map: keys are not serialized in the expected order");
+            }
+        }
+    }
+}
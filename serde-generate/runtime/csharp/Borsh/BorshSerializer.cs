@@ -0,0 +1,175 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using Serde;
+using System;
+using System.Collections.Generic;
+using System.IO;
+using System.Numerics;
+
+namespace Borsh
+{
+    public class BorshSerializer : BinarySerializer
+    {
+        public const long MAX_LENGTH = int.MaxValue;
+
+        public BorshSerializer() : base(long.MaxValue) { }
+        public BorshSerializer(byte[] buffer) : base(buffer, long.MaxValue) { }
+        public BorshSerializer(ArraySegment<byte> buffer) : base(buffer, long.MaxValue) { }
+
+        private readonly SortKeys sortKeys = new SortKeys();
+
+        private byte[] BytesSince(int start)
+        {
+            byte[] bytes = new byte[get_buffer_offset() - start];
+            buffer.Seek(start, SeekOrigin.Begin);
+            buffer.Read(bytes, 0, bytes.Length);
+            return bytes;
+        }
+
+        private void RecordInteger(int start, bool signed) => sortKeys.RecordInteger(start, BytesSince(start), signed);
+
+        public override void serialize_u16(ushort value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_u16(value);
+            RecordInteger(start, false);
+        }
+
+        public override void serialize_u32(uint value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_u32(value);
+            RecordInteger(start, false);
+        }
+
+        public override void serialize_u64(ulong value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_u64(value);
+            RecordInteger(start, false);
+        }
+
+        public override void serialize_u128(BigInteger value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_u128(value);
+            RecordInteger(start, false);
+        }
+
+        public override void serialize_i8(sbyte value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_i8(value);
+            RecordInteger(start, true);
+        }
+
+        public override void serialize_i16(short value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_i16(value);
+            RecordInteger(start, true);
+        }
+
+        public override void serialize_i32(int value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_i32(value);
+            RecordInteger(start, true);
+        }
+
+        public override void serialize_i64(long value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_i64(value);
+            RecordInteger(start, true);
+        }
+
+        public override void serialize_i128(BigInteger value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_i128(value);
+            RecordInteger(start, true);
+        }
+
+        public override void serialize_str(string value)
+        {
+            int start = get_buffer_offset();
+            base.serialize_str(value);
+            sortKeys.RecordStr(start, BytesSince(start));
+        }
+
+        public override void serialize_f32(float value)
+        {
+            if (float.IsNaN(value))
+            {
+                throw new SerializationException("NaN values are not supported by Borsh");
+            }
+            output.Write(value);
+        }
+
+        public override void serialize_f64(double value)
+        {
+            if (double.IsNaN(value))
+            {
+                throw new SerializationException("NaN values are not supported by Borsh");
+            }
+            output.Write(value);
+        }
+
+        public override void serialize_len(long value)
+        {
+            if ((value < 0) || (value > MAX_LENGTH))
+            {
+                throw new SerializationException("length value doesn't fit in uint32");
+            }
+            output.Write((uint)value);
+        }
+
+        public override void serialize_variant_index(int value)
+        {
+            if ((value < 0) || (value > byte.MaxValue))
+            {
+                throw new SerializationException("variant index doesn't fit in uint8");
+            }
+            output.Write((byte)value);
+        }
+
+        static ReadOnlySpan<byte> Slice(byte[] array, (int start, int end) tup) =>
+            new ReadOnlySpan<byte>(array, tup.start, tup.end - tup.start);
+
+        public override void sort_map_entries(int[] offsets)
+        {
+            if (offsets.Length <= 1)
+            {
+                return;
+            }
+            int offset0 = offsets[0];
+            var ranges = new (int start, int end)[offsets.Length];
+            for (int i = 0; i < offsets.Length - 1; i++)
+            {
+                ranges[i] = (offsets[i] - offset0, offsets[i + 1] - offset0);
+            }
+            ranges[ranges.Length - 1] = (offsets[offsets.Length - 1] - offset0, (int)buffer.Length - offset0);
+
+            byte[] data = new byte[buffer.Length - offset0];
+            buffer.Seek(offset0, SeekOrigin.Begin);
+            buffer.Read(data, 0, data.Length);
+
+            var keys = new byte[ranges.Length][];
+            for (int i = 0; i < ranges.Length; i++)
+            {
+                keys[i] = sortKeys.SortKey(Slice(data, ranges[i]), offset0 + ranges[i].start);
+            }
+            Array.Sort(keys, ranges, Comparer<byte[]>.Create((l, r) => Verification.CompareLexicographic(l, r)));
+            // Positions in the map are no longer valid. Map keys never contain maps.
+            sortKeys.Truncate(offset0);
+
+            buffer.Seek(offset0, SeekOrigin.Begin);
+            foreach (var range in ranges)
+            {
+                buffer.Write(data, range.start, range.end - range.start);
+            }
+        }
+    }
+}
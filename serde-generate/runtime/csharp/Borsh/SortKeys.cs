@@ -0,0 +1,109 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using System;
+using System.Collections.Generic;
+
+namespace Borsh
+{
+    /// <summary>
+    /// Order-preserving encodings of the integers and strings written or read so far, indexed by
+    /// their positions in the buffer.
+    ///
+    /// Borsh sorts map entries by keys as in Rust. Substituting these encodings in the encoded
+    /// keys gives byte strings that compare in the same way.
+    /// </summary>
+    internal class SortKeys
+    {
+        // Ordered by start positions.
+        private readonly List<(int start, int end, byte[] key)> records = new List<(int start, int end, byte[] key)>();
+
+        // Little-endian integers compare as in Rust in big-endian order, with the sign bit flipped.
+        public void RecordInteger(int start, ReadOnlySpan<byte> bytes, bool signed)
+        {
+            byte[] key = new byte[bytes.Length];
+            for (int i = 0; i < key.Length; i++)
+            {
+                key[i] = bytes[bytes.Length - 1 - i];
+            }
+            if (signed)
+            {
+                key[0] ^= 0x80;
+            }
+            Record(start, start + bytes.Length, key);
+        }
+
+        // Strings have their zeros escaped and a terminator added so that prefixes come first.
+        public void RecordStr(int start, ReadOnlySpan<byte> bytes)
+        {
+            var key = new List<byte>();
+            foreach (byte b in bytes.Slice(4))
+            {
+                key.Add(b);
+                if (b == 0)
+                {
+                    key.Add(0xFF);
+                }
+            }
+            key.Add(0);
+            key.Add(0);
+            Record(start, start + bytes.Length, key.ToArray());
+        }
+
+        private void Record(int start, int end, byte[] key)
+        {
+            // Values written or read inside the current one are superseded.
+            Truncate(start);
+            records.Add((start, end, key));
+        }
+
+        private int LowerBound(int start)
+        {
+            int low = 0;
+            int high = records.Count;
+            while (low < high)
+            {
+                int middle = low + (high - low) / 2;
+                if (records[middle].start < start)
+                {
+                    low = middle + 1;
+                }
+                else
+                {
+                    high = middle;
+                }
+            }
+            return low;
+        }
+
+        public void Truncate(int start)
+        {
+            int index = LowerBound(start);
+            records.RemoveRange(index, records.Count - index);
+        }
+
+        /// <summary>
+        /// Returns the sort key of the given bytes, found at the given position in the buffer.
+        /// </summary>
+        public byte[] SortKey(ReadOnlySpan<byte> bytes, int start)
+        {
+            var result = new List<byte>();
+            int end = start + bytes.Length;
+            int position = start;
+            for (int i = LowerBound(start); i < records.Count && records[i].start < end; i++)
+            {
+                foreach (byte b in bytes.Slice(position - start, records[i].start - position))
+                {
+                    result.Add(b);
+                }
+                result.AddRange(records[i].key);
+                position = records[i].end;
+            }
+            foreach (byte b in bytes.Slice(position - start))
+            {
+                result.Add(b);
+            }
+            return result.ToArray();
+        }
+    }
+}
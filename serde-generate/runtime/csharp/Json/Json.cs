@@ -0,0 +1,580 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using Serde;
+using System;
+using System.Collections.Generic;
+using System.Globalization;
+using System.Linq;
+using System.Numerics;
+using System.Text;
+using System.Text.RegularExpressions;
+
+namespace Json
+{
+    /// <summary>
+    /// The JSON format of the Rust crate `serde_json`. Generated classes convert their values from
+    /// and to trees of JSON values with these helpers, then `parse` and `stringify` deal with the
+    /// JSON text.
+    /// </summary>
+    public static class Json
+    {
+        /// <summary>Maximal number of nested arrays and objects accepted by `serde_json` (whose recursion limit is 128).</summary>
+        public const int MAX_CONTAINER_DEPTH = 127;
+
+        private static readonly Regex INTEGER = new Regex(@"\A-?(0|[1-9][0-9]*)\z");
+        private static readonly Regex NUMBER = new Regex(@"\A-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?\z");
+
+        private static readonly BigInteger MIN_I8 = sbyte.MinValue, MAX_I8 = sbyte.MaxValue;
+        private static readonly BigInteger MIN_I16 = short.MinValue, MAX_I16 = short.MaxValue;
+        private static readonly BigInteger MIN_I32 = int.MinValue, MAX_I32 = int.MaxValue;
+        private static readonly BigInteger MIN_I64 = long.MinValue, MAX_I64 = long.MaxValue;
+        private static readonly BigInteger MIN_I128 = -(BigInteger.One << 127), MAX_I128 = (BigInteger.One << 127) - 1;
+        private static readonly BigInteger MAX_U8 = byte.MaxValue;
+        private static readonly BigInteger MAX_U16 = ushort.MaxValue;
+        private static readonly BigInteger MAX_U32 = uint.MaxValue;
+        private static readonly BigInteger MAX_U64 = ulong.MaxValue;
+        private static readonly BigInteger MAX_U128 = (BigInteger.One << 128) - 1;
+
+        public static object parse(string input)
+        {
+            if (input == null)
+                throw new DeserializationException("Cannot deserialize null string");
+            var parser = new Parser(input);
+            object value = parser.ParseValue(MAX_CONTAINER_DEPTH);
+            parser.SkipWhitespace();
+            if (parser.position < input.Length)
+                throw new DeserializationException("Trailing characters in JSON input");
+            return value;
+        }
+
+        public static string stringify(object value)
+        {
+            var output = new StringBuilder();
+            Write(output, value);
+            return output.ToString();
+        }
+
+        private static void Write(StringBuilder output, object value)
+        {
+            switch (value)
+            {
+                case null:
+                    output.Append("null");
+                    break;
+                case bool b:
+                    output.Append(b ? "true" : "false");
+                    break;
+                case JsonNumber n:
+                    output.Append(n.Literal);
+                    break;
+                case string s:
+                    WriteString(output, s);
+                    break;
+                case JsonKey k:
+                    WriteString(output, k.Value);
+                    break;
+                case List<object> items:
+                    output.Append('[');
+                    for (int i = 0; i < items.Count; i++)
+                    {
+                        if (i > 0) output.Append(',');
+                        Write(output, items[i]);
+                    }
+                    output.Append(']');
+                    break;
+                case JsonObject obj:
+                    output.Append('{');
+                    for (int i = 0; i < obj.Count; i++)
+                    {
+                        if (i > 0) output.Append(',');
+                        WriteString(output, obj.Keys[i]);
+                        output.Append(':');
+                        Write(output, obj.Values[i]);
+                    }
+                    output.Append('}');
+                    break;
+                default:
+                    throw new SerializationException("Invalid JSON value: " + value);
+            }
+        }
+
+        private static void WriteString(StringBuilder output, string value)
+        {
+            output.Append('"');
+            for (int i = 0; i < value.Length; i++)
+            {
+                char c = value[i];
+                switch (c)
+                {
+                    case '"': output.Append("\\\""); break;
+                    case '\\': output.Append("\\\\"); break;
+                    case '\b': output.Append("\\b"); break;
+                    case '\f': output.Append("\\f"); break;
+                    case '\n': output.Append("\\n"); break;
+                    case '\r': output.Append("\\r"); break;
+                    case '\t': output.Append("\\t"); break;
+                    default:
+                        if (c < 0x20)
+                        {
+                            output.Append("\\u00").Append(((int)c).ToString("x2"));
+                        }
+                        else if (char.IsHighSurrogate(c) && i + 1 < value.Length && char.IsLowSurrogate(value[i + 1]))
+                        {
+                            output.Append(c).Append(value[i + 1]);
+                            i++;
+                        }
+                        else if (char.IsSurrogate(c))
+                        {
+                            throw new SerializationException("Invalid unicode string");
+                        }
+                        else
+                        {
+                            output.Append(c);
+                        }
+                        break;
+                }
+            }
+            output.Append('"');
+        }
+
+        private class Parser
+        {
+            private readonly string input;
+            public int position = 0;
+
+            public Parser(string input) { this.input = input; }
+
+            public void SkipWhitespace()
+            {
+                while (position < input.Length)
+                {
+                    char c = input[position];
+                    if (c != ' ' && c != '\t' && c != '\n' && c != '\r') return;
+                    position++;
+                }
+            }
+
+            private DeserializationException Error(string message) =>
+                new DeserializationException(message + " at position " + position);
+
+            private bool Peek(char c) => position < input.Length && input[position] == c;
+
+            private char Next()
+            {
+                if (position >= input.Length)
+                    throw Error("Unexpected end of JSON input");
+                return input[position++];
+            }
+
+            private void Expect(string word)
+            {
+                if (string.CompareOrdinal(input, position, word, 0, word.Length) != 0)
+                    throw Error("Invalid JSON value");
+                position += word.Length;
+            }
+
+            public object ParseValue(int depthBudget)
+            {
+                SkipWhitespace();
+                if (position >= input.Length)
+                    throw Error("Unexpected end of JSON input");
+                char c = input[position];
+                switch (c)
+                {
+                    case 'n':
+                        Expect("null");
+                        return null;
+                    case 't':
+                        Expect("true");
+                        return true;
+                    case 'f':
+                        Expect("false");
+                        return false;
+                    case '"':
+                        return ParseString();
+                    case '[':
+                        {
+                            if (depthBudget == 0)
+                                throw Error("Exceeded maximum container depth");
+                            position++;
+                            var items = new List<object>();
+                            SkipWhitespace();
+                            if (Peek(']'))
+                            {
+                                position++;
+                                return items;
+                            }
+                            for (;;)
+                            {
+                                items.Add(ParseValue(depthBudget - 1));
+                                SkipWhitespace();
+                                char d = Next();
+                                if (d == ']') return items;
+                                if (d != ',') throw Error("Expected ',' or ']'");
+                            }
+                        }
+                    case '{':
+                        {
+                            if (depthBudget == 0)
+                                throw Error("Exceeded maximum container depth");
+                            position++;
+                            var obj = new JsonObject();
+                            SkipWhitespace();
+                            if (Peek('}'))
+                            {
+                                position++;
+                                return obj;
+                            }
+                            for (;;)
+                            {
+                                SkipWhitespace();
+                                if (!Peek('"')) throw Error("Expected a string key");
+                                string key = ParseString();
+                                SkipWhitespace();
+                                if (Next() != ':') throw Error("Expected ':'");
+                                obj.Put(key, ParseValue(depthBudget - 1));
+                                SkipWhitespace();
+                                char d = Next();
+                                if (d == '}') return obj;
+                                if (d != ',') throw Error("Expected ',' or '}'");
+                            }
+                        }
+                    default:
+                        if (c == '-' || (c >= '0' && c <= '9'))
+                            return ParseNumber();
+                        throw Error("Invalid JSON value");
+                }
+            }
+
+            private JsonNumber ParseNumber()
+            {
+                int start = position;
+                while (position < input.Length && "-+.eE0123456789".IndexOf(input[position]) >= 0)
+                    position++;
+                string literal = input.Substring(start, position - start);
+                if (!NUMBER.IsMatch(literal))
+                    throw Error("Invalid number");
+                return new JsonNumber(literal);
+            }
+
+            private int ParseHex()
+            {
+                if (position + 4 > input.Length)
+                    throw Error("Invalid escape in string");
+                string digits = input.Substring(position, 4);
+                if (!digits.All(Uri.IsHexDigit))
+                    throw Error("Invalid escape in string");
+                position += 4;
+                return int.Parse(digits, NumberStyles.AllowHexSpecifier, CultureInfo.InvariantCulture);
+            }
+
+            private string ParseString()
+            {
+                position++;
+                var result = new StringBuilder();
+                for (;;)
+                {
+                    char c = Next();
+                    if (c == '"') return result.ToString();
+                    if (c < 0x20) throw Error("Control character in string");
+                    if (c != '\\')
+                    {
+                        if (char.IsHighSurrogate(c))
+                        {
+                            if (!(position < input.Length && char.IsLowSurrogate(input[position])))
+                                throw Error("Invalid unicode string");
+                            result.Append(c).Append(Next());
+                        }
+                        else if (char.IsLowSurrogate(c))
+                        {
+                            throw Error("Invalid unicode string");
+                        }
+                        else
+                        {
+                            result.Append(c);
+                        }
+                        continue;
+                    }
+                    char escape = Next();
+                    switch (escape)
+                    {
+                        case '"':
+                        case '\\':
+                        case '/':
+                            result.Append(escape);
+                            break;
+                        case 'b': result.Append('\b'); break;
+                        case 'f': result.Append('\f'); break;
+                        case 'n': result.Append('\n'); break;
+                        case 'r': result.Append('\r'); break;
+                        case 't': result.Append('\t'); break;
+                        case 'u':
+                            {
+                                char high = (char)ParseHex();
+                                if (char.IsLowSurrogate(high))
+                                    throw Error("Lone surrogate in string");
+                                if (char.IsHighSurrogate(high))
+                                {
+                                    if (string.CompareOrdinal(input, position, "\\u", 0, 2) != 0)
+                                        throw Error("Lone surrogate in string");
+                                    position += 2;
+                                    char low = (char)ParseHex();
+                                    if (!char.IsLowSurrogate(low))
+                                        throw Error("Lone surrogate in string");
+                                    result.Append(high).Append(low);
+                                }
+                                else
+                                {
+                                    result.Append(high);
+                                }
+                                break;
+                            }
+                        default:
+                            throw Error("Invalid escape in string");
+                    }
+                }
+            }
+        }
+
+        /// <summary>
+        /// Format a finite float in the manner of the Rust crate `ryu`, as `serde_json` does: with the
+        /// shortest digits that read back as the same value. `shortest` is the round-trip
+        /// representation of the absolute value given by .NET.
+        /// </summary>
+        private static string FormatFloat(bool negative, string shortest, bool isF32)
+        {
+            string sign = negative ? "-" : "";
+            int exponent = 0;
+            int e = shortest.IndexOfAny(new[] { 'e', 'E' });
+            if (e >= 0)
+            {
+                exponent = int.Parse(shortest.Substring(e + 1), NumberStyles.AllowLeadingSign, CultureInfo.InvariantCulture);
+                shortest = shortest.Substring(0, e);
+            }
+            int dot = shortest.IndexOf('.');
+            string digits = dot < 0 ? shortest : shortest.Substring(0, dot) + shortest.Substring(dot + 1);
+            int point = (dot < 0 ? shortest.Length : dot) + exponent;
+            int leadingZeros = digits.Length - digits.TrimStart('0').Length;
+            digits = digits.Substring(leadingZeros).TrimEnd('0');
+            point -= leadingZeros;
+            if (digits.Length == 0)
+                return sign + "0.0";
+
+            int maxPoint = isF32 ? 13 : 16;
+            int minPoint = isF32 ? -6 : -5;
+            if (digits.Length <= point && point <= maxPoint)
+                return sign + digits + new string('0', point - digits.Length) + ".0";
+            if (0 < point && point <= maxPoint)
+                return sign + digits.Substring(0, point) + "." + digits.Substring(point);
+            if (minPoint < point && point <= 0)
+                return sign + "0." + new string('0', -point) + digits;
+            string mantissa = digits.Length == 1 ? digits : digits[0] + "." + digits.Substring(1);
+            return sign + mantissa + "e" + (point - 1).ToString(CultureInfo.InvariantCulture);
+        }
+
+        private static DeserializationException TypeError(object value, string expected) =>
+            new DeserializationException("Invalid type: expected " + expected + ", found " + (value ?? "null"));
+
+        private static BigInteger ReadInteger(object value, BigInteger min, BigInteger max, bool acceptsNegativeZero)
+        {
+            string literal;
+            if (value is JsonNumber n)
+                literal = n.Literal;
+            else if (value is JsonKey k)
+                literal = k.Value;
+            else
+                throw TypeError(value, "an integer");
+            // Like `serde_json`, read `-0` as a float, except for 128-bit integers.
+            if (!INTEGER.IsMatch(literal) || (literal == "-0" && !acceptsNegativeZero))
+                throw TypeError(value, "an integer");
+            var result = BigInteger.Parse(literal, NumberStyles.AllowLeadingSign, CultureInfo.InvariantCulture);
+            if (result < min || result > max)
+                throw new DeserializationException("Integer out of range: " + literal);
+            return result;
+        }
+
+        private static double ReadNumber(object value)
+        {
+            string literal;
+            if (value is JsonNumber n)
+                literal = n.Literal;
+            else if (value is JsonKey k && NUMBER.IsMatch(k.Value))
+                literal = k.Value;
+            else
+                throw TypeError(value, "a number");
+            double result = double.Parse(literal, NumberStyles.Float, CultureInfo.InvariantCulture);
+            if (double.IsInfinity(result))
+                throw new DeserializationException("Number out of range: " + literal);
+            return result;
+        }
+
+        // Serialization helpers
+
+        public static object serialize_unit(Unit value) => null;
+
+        public static object serialize_bool(bool value) => value;
+
+        public static object serialize_i8(sbyte value) => Number(value);
+
+        public static object serialize_i16(short value) => Number(value);
+
+        public static object serialize_i32(int value) => Number(value);
+
+        public static object serialize_i64(long value) => Number(value);
+
+        public static object serialize_i128(BigInteger value)
+        {
+            if (value < MIN_I128 || value > MAX_I128)
+                throw new SerializationException("Invalid value for i128: " + value);
+            return Number(value);
+        }
+
+        public static object serialize_u8(byte value) => Number(value);
+
+        public static object serialize_u16(ushort value) => Number(value);
+
+        public static object serialize_u32(uint value) => Number(value);
+
+        public static object serialize_u64(ulong value) => Number(value);
+
+        public static object serialize_u128(BigInteger value)
+        {
+            if (value.Sign < 0 || value > MAX_U128)
+                throw new SerializationException("Invalid value for u128: " + value);
+            return Number(value);
+        }
+
+        public static object serialize_f32(float value)
+        {
+            if (float.IsNaN(value) || float.IsInfinity(value)) return null;
+            bool negative = BitConverter.DoubleToInt64Bits(value) < 0;
+            return new JsonNumber(FormatFloat(negative, Math.Abs(value).ToString("R", CultureInfo.InvariantCulture), true));
+        }
+
+        public static object serialize_f64(double value)
+        {
+            if (double.IsNaN(value) || double.IsInfinity(value)) return null;
+            bool negative = BitConverter.DoubleToInt64Bits(value) < 0;
+            return new JsonNumber(FormatFloat(negative, Math.Abs(value).ToString("R", CultureInfo.InvariantCulture), false));
+        }
+
+        public static object serialize_char(char value)
+        {
+            if (char.IsSurrogate(value))
+                throw new SerializationException("Invalid unicode character");
+            return value.ToString();
+        }
+
+        public static object serialize_str(string value) => value;
+
+        public static object serialize_bytes(ValueArray<byte> value) =>
+            value.Select(b => (object)Number(b)).ToList();
+
+        /// <summary>A variant of an enum that is not a unit variant (unit variants are written as strings).</summary>
+        public static object serialize_variant(string name, object content)
+        {
+            var result = new JsonObject();
+            result.Put(name, content);
+            return result;
+        }
+
+        /// <summary>The key of a map entry, given the JSON value of the key.</summary>
+        public static string key(object value)
+        {
+            switch (value)
+            {
+                case string s: return s;
+                case JsonNumber n: return n.Literal;
+                case bool b: return b ? "true" : "false";
+                default: throw new SerializationException("Map keys must be strings or numbers");
+            }
+        }
+
+        private static JsonNumber Number(IFormattable value) =>
+            new JsonNumber(value.ToString(null, CultureInfo.InvariantCulture));
+
+        // Deserialization helpers
+
+        public static Unit deserialize_unit(object value)
+        {
+            if (value != null) throw TypeError(value, "null");
+            return new Unit();
+        }
+
+        public static bool deserialize_bool(object value)
+        {
+            if (value is bool b) return b;
+            if (value is JsonKey k && (k.Value == "true" || k.Value == "false")) return k.Value == "true";
+            throw TypeError(value, "a boolean");
+        }
+
+        public static sbyte deserialize_i8(object value) => (sbyte)ReadInteger(value, MIN_I8, MAX_I8, false);
+
+        public static short deserialize_i16(object value) => (short)ReadInteger(value, MIN_I16, MAX_I16, false);
+
+        public static int deserialize_i32(object value) => (int)ReadInteger(value, MIN_I32, MAX_I32, false);
+
+        public static long deserialize_i64(object value) => (long)ReadInteger(value, MIN_I64, MAX_I64, false);
+
+        public static BigInteger deserialize_i128(object value) => ReadInteger(value, MIN_I128, MAX_I128, true);
+
+        public static byte deserialize_u8(object value) => (byte)ReadInteger(value, BigInteger.Zero, MAX_U8, false);
+
+        public static ushort deserialize_u16(object value) => (ushort)ReadInteger(value, BigInteger.Zero, MAX_U16, false);
+
+        public static uint deserialize_u32(object value) => (uint)ReadInteger(value, BigInteger.Zero, MAX_U32, false);
+
+        public static ulong deserialize_u64(object value) => (ulong)ReadInteger(value, BigInteger.Zero, MAX_U64, false);
+
+        public static BigInteger deserialize_u128(object value) => ReadInteger(value, BigInteger.Zero, MAX_U128, false);
+
+        // Like `serde_json`, read the closest f64 value then convert it to f32.
+        public static float deserialize_f32(object value) => (float)ReadNumber(value);
+
+        public static double deserialize_f64(object value) => ReadNumber(value);
+
+        public static char deserialize_char(object value)
+        {
+            string result = deserialize_str(value);
+            // Characters outside of the Basic Multilingual Plane do not fit in a C# `char`.
+            if (result.Length != 1 || char.IsSurrogate(result[0]))
+                throw TypeError(value, "a character");
+            return result[0];
+        }
+
+        public static string deserialize_str(object value)
+        {
+            if (value is string s) return s;
+            if (value is JsonKey k) return k.Value;
+            throw TypeError(value, "a string");
+        }
+
+        public static ValueArray<byte> deserialize_bytes(object value) =>
+            new ValueArray<byte>(deserialize_array(value).Select(deserialize_u8).ToArray());
+
+        /// <summary>The items of an array, of the given length if any (e.g. a tuple or a fixed-size array).</summary>
+        public static List<object> deserialize_array(object value, int? length = null)
+        {
+            if (!(value is List<object> items)) throw TypeError(value, "an array");
+            if (length.HasValue && items.Count != length.Value)
+                throw new DeserializationException("Invalid length: " + items.Count + " instead of " + length.Value);
+            return items;
+        }
+
+        public static JsonObject deserialize_object(object value)
+        {
+            if (!(value is JsonObject obj)) throw TypeError(value, "an object");
+            return obj;
+        }
+
+        public static JsonVariant deserialize_variant(object value)
+        {
+            if (value is string || value is JsonKey)
+                return new JsonVariant(deserialize_str(value), null, false);
+            if (value is JsonObject obj && obj.Count == 1)
+                return new JsonVariant(obj.Keys[0], obj.Values[0], true);
+            throw TypeError(value, "a string or an object with a single entry");
+        }
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using Serde;
+using System;
+using System.Collections.Generic;
+using System.Globalization;
+using System.Linq;
+using System.Numerics;
+using System.Text;
+
+namespace Json
+{
+    // JSON values are represented by `object`: `null`, `bool`, `JsonNumber`, `string`, `JsonKey`,
+    // `List<object>`, or `JsonObject`.
+
+    /// <summary>A JSON number, kept as written so that integers of any size are read exactly.</summary>
+    public sealed class JsonNumber
+    {
+        public readonly string Literal;
+
+        public JsonNumber(string literal) { Literal = literal; }
+
+        public override string ToString() => Literal;
+    }
+
+    /// <summary>
+    /// The key of an entry of a JSON object read as a map. Like `serde_json`, keys may stand for
+    /// strings, numbers, booleans, or unit variants of enums.
+    /// </summary>
+    public sealed class JsonKey
+    {
+        public readonly string Value;
+
+        public JsonKey(string value) { Value = value; }
+
+        public override string ToString() => Value;
+    }
+
+    /// <summary>The entries of a JSON object, in order and possibly with duplicate keys.</summary>
+    public sealed class JsonObject
+    {
+        public readonly List<string> Keys = new List<string>();
+        public readonly List<object> Values = new List<object>();
+
+        public int Count => Keys.Count;
+
+        public void Put(string key, object value)
+        {
+            Keys.Add(key);
+            Values.Add(value);
+        }
+
+        /// <summary>Value of the field `name` of a struct.</summary>
+        public object Field(string name)
+        {
+            int index = Find(name);
+            if (index < 0)
+                throw new DeserializationException("Missing field: " + name);
+            return Values[index];
+        }
+
+        /// <summary>Value of the optional field `name` of a struct, or `null` if the field is missing.</summary>
+        public object OptionalField(string name)
+        {
+            int index = Find(name);
+            return index < 0 ? null : Values[index];
+        }
+
+        private int Find(string name)
+        {
+            int index = Keys.IndexOf(name);
+            if (index >= 0 && Keys.LastIndexOf(name) != index)
+                throw new DeserializationException("Duplicate field: " + name);
+            return index;
+        }
+
+        /// <summary>
+        /// Sort the entries of an object written from a map, so that keys come in the order of a
+        /// `BTreeMap` in Rust.
+        /// </summary>
+        public void SortKeys(Comparison<string> order)
+        {
+            var entries = Keys.Zip(Values, (k, v) => new KeyValuePair<string, object>(k, v)).ToList();
+            entries.Sort((a, b) => order(a.Key, b.Key));
+            Keys.Clear();
+            Values.Clear();
+            foreach (var entry in entries)
+                Put(entry.Key, entry.Value);
+        }
+
+        /// <summary>Order of strings and chars (by UTF-8 bytes) and of booleans (`false` first).</summary>
+        public static int StringOrder(string a, string b)
+        {
+            byte[] x = Encoding.UTF8.GetBytes(a);
+            byte[] y = Encoding.UTF8.GetBytes(b);
+            for (int i = 0; i < x.Length && i < y.Length; i++)
+            {
+                if (x[i] != y[i]) return x[i].CompareTo(y[i]);
+            }
+            return x.Length.CompareTo(y.Length);
+        }
+
+        /// <summary>Order of numbers.</summary>
+        public static int NumberOrder(string a, string b)
+        {
+            if (BigInteger.TryParse(a, NumberStyles.AllowLeadingSign, CultureInfo.InvariantCulture, out var x)
+                && BigInteger.TryParse(b, NumberStyles.AllowLeadingSign, CultureInfo.InvariantCulture, out var y))
+                return x.CompareTo(y);
+            return double.Parse(a, NumberStyles.Float, CultureInfo.InvariantCulture)
+                .CompareTo(double.Parse(b, NumberStyles.Float, CultureInfo.InvariantCulture));
+        }
+
+        /// <summary>Order of the unit variants of an enum, given the names of all its variants.</summary>
+        public static Comparison<string> VariantOrder(params string[] names)
+        {
+            int Index(string name)
+            {
+                int index = Array.IndexOf(names, name);
+                return index < 0 ? names.Length : index;
+            }
+            return (a, b) => Index(a).CompareTo(Index(b));
+        }
+    }
+
+    /// <summary>
+    /// A variant of an enum, read either from a string (unit variants) or from an object with a
+    /// single entry.
+    /// </summary>
+    public sealed class JsonVariant
+    {
+        public readonly string Name;
+        private readonly object content;
+        private readonly bool hasContent;
+
+        public JsonVariant(string name, object content, bool hasContent)
+        {
+            Name = name;
+            this.content = content;
+            this.hasContent = hasContent;
+        }
+
+        /// <summary>Check that the variant has no content, i.e. that it was written as a string or with `null`.</summary>
+        public void Unit()
+        {
+            if (content != null)
+                throw new DeserializationException("Unexpected content for unit variant: " + Name);
+        }
+
+        /// <summary>Content of a variant that is not a unit variant.</summary>
+        public object Value()
+        {
+            if (!hasContent)
+                throw new DeserializationException("Missing content for variant: " + Name);
+            return content;
+        }
+    }
+}
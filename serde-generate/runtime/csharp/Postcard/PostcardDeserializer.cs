@@ -0,0 +1,84 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using Serde;
+using System;
+using System.Numerics;
+
+namespace Postcard
+{
+    public class PostcardDeserializer : BinaryDeserializer
+    {
+        public PostcardDeserializer(byte[] input) : base(input, long.MaxValue) { }
+        public PostcardDeserializer(ArraySegment<byte> input) : base(input, long.MaxValue) { }
+
+        private BigInteger deserialize_varint(int bits)
+        {
+            BigInteger value = 0;
+            for (int shift = 0; shift < bits; shift += 7)
+            {
+                byte x = reader.ReadByte();
+                int digit = x & 0x7F;
+                if (bits - shift < 7 && (digit >> (bits - shift)) != 0)
+                {
+                    throw new DeserializationException("Overflow while parsing varint-encoded value");
+                }
+                value |= new BigInteger(digit) << shift;
+                if ((x & 0x80) == 0)
+                {
+                    return value;
+                }
+            }
+            throw new DeserializationException("Varint-encoded value is too long");
+        }
+
+        private BigInteger deserialize_zigzag(int bits)
+        {
+            BigInteger value = deserialize_varint(bits);
+            return value.IsEven ? value >> 1 : -(value >> 1) - 1;
+        }
+
+        public override long deserialize_len()
+        {
+            BigInteger value = deserialize_varint(64);
+            if (value > int.MaxValue)
+            {
+                throw new DeserializationException("Incorrect length value");
+            }
+            return (long)value;
+        }
+
+        public override int deserialize_variant_index() => (int)(uint)deserialize_varint(32);
+
+        public override char deserialize_char()
+        {
+            string value = deserialize_str();
+            if (value.Length != 1)
+            {
+                throw new DeserializationException("Incorrect value for C# char");
+            }
+            return value[0];
+        }
+
+        public override ushort deserialize_u16() => (ushort)deserialize_varint(16);
+
+        public override uint deserialize_u32() => (uint)deserialize_varint(32);
+
+        public override ulong deserialize_u64() => (ulong)deserialize_varint(64);
+
+        public override BigInteger deserialize_u128() => deserialize_varint(128);
+
+        public override short deserialize_i16() => (short)deserialize_zigzag(16);
+
+        public override int deserialize_i32() => (int)deserialize_zigzag(32);
+
+        public override long deserialize_i64() => (long)deserialize_zigzag(64);
+
+        public override BigInteger deserialize_i128() => deserialize_zigzag(128);
+
+        public override void check_that_key_slices_are_increasing(Range key1, Range key2)
+        {
+            // Not required by the format.
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using Serde;
+using System;
+using System.Numerics;
+
+namespace Postcard
+{
+    public class PostcardSerializer : BinarySerializer
+    {
+        public PostcardSerializer() : base(long.MaxValue) { }
+        public PostcardSerializer(byte[] buffer) : base(buffer, long.MaxValue) { }
+        public PostcardSerializer(ArraySegment<byte> buffer) : base(buffer, long.MaxValue) { }
+
+        private void serialize_varint(ulong value)
+        {
+            while (value >= 0x80)
+            {
+                output.Write((byte)((value & 0x7F) | 0x80));
+                value >>= 7;
+            }
+            output.Write((byte)value);
+        }
+
+        private void serialize_varint(BigInteger value)
+        {
+            while (value >= 0x80)
+            {
+                output.Write((byte)((value & 0x7F) | 0x80));
+                value >>= 7;
+            }
+            output.Write((byte)value);
+        }
+
+        public override void serialize_len(long value) => serialize_varint((ulong)value);
+
+        public override void serialize_variant_index(int value) => serialize_varint((uint)value);
+
+        public override void serialize_char(char value) => serialize_str(value.ToString());
+
+        public override void serialize_u16(ushort value) => serialize_varint(value);
+
+        public override void serialize_u32(uint value) => serialize_varint(value);
+
+        public override void serialize_u64(ulong value) => serialize_varint(value);
+
+        public override void serialize_u128(BigInteger value)
+        {
+            if (value < 0 || value >> 128 != 0)
+            {
+                throw new SerializationException("Invalid value for an unsigned int128");
+            }
+            serialize_varint(value);
+        }
+
+        // Signed integers use a "zigzag" encoding before the varint encoding.
+        public override void serialize_i16(short value) => serialize_varint((ushort)((value << 1) ^ (value >> 15)));
+
+        public override void serialize_i32(int value) => serialize_varint((uint)((value << 1) ^ (value >> 31)));
+
+        public override void serialize_i64(long value) => serialize_varint((ulong)((value << 1) ^ (value >> 63)));
+
+        public override void serialize_i128(BigInteger value)
+        {
+            if (value >= 0)
+            {
+                if (value >> 127 != 0)
+                {
+                    throw new SerializationException("Invalid value for a signed int128");
+                }
+                serialize_varint(value << 1);
+            }
+            else
+            {
+                if ((-(value + 1)) >> 127 != 0)
+                {
+                    throw new SerializationException("Invalid value for a signed int128");
+                }
+                serialize_varint(-(value << 1) - 1);
+            }
+        }
+
+        public override void sort_map_entries(int[] offsets)
+        {
+            // Not required by the format.
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using Serde;
+using System;
+
+namespace Scale
+{
+    public class ScaleDeserializer : BinaryDeserializer
+    {
+        public ScaleDeserializer(byte[] input) : base(input, long.MaxValue) { }
+        public ScaleDeserializer(ArraySegment<byte> input) : base(input, long.MaxValue) { }
+
+        public override float deserialize_f32() => reader.ReadSingle();
+
+        public override double deserialize_f64() => reader.ReadDouble();
+
+        private uint deserialize_compact_u32()
+        {
+            uint prefix = reader.ReadByte();
+            uint value;
+            uint minimum;
+            switch (prefix & 3)
+            {
+                case 0:
+                    return prefix >> 2;
+                case 1:
+                    value = (prefix | ((uint)reader.ReadByte() << 8)) >> 2;
+                    minimum = 1u << 6;
+                    break;
+                case 2:
+                    value = (prefix | ((uint)reader.ReadByte() << 8) | ((uint)reader.ReadByte() << 16) | ((uint)reader.ReadByte() << 24)) >> 2;
+                    minimum = 1u << 14;
+                    break;
+                default:
+                    if (prefix != 3)
+                    {
+                        throw new DeserializationException("Compact integer does not fit in 32 bits");
+                    }
+                    value = reader.ReadUInt32();
+                    minimum = 1u << 30;
+                    break;
+            }
+            if (value < minimum)
+            {
+                throw new DeserializationException("Compact integer is not canonically encoded");
+            }
+            return value;
+        }
+
+        public override long deserialize_len()
+        {
+            long value = deserialize_compact_u32();
+            if (value > ScaleSerializer.MAX_LENGTH)
+            {
+                throw new DeserializationException("Incorrect length value");
+            }
+            return value;
+        }
+
+        public override int deserialize_variant_index() => reader.ReadByte();
+
+        public override void check_that_key_slices_are_increasing(Range key1, Range key2)
+        {
+            // Not required by the format.
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using Serde;
+using System;
+
+namespace Scale
+{
+    public class ScaleSerializer : BinarySerializer
+    {
+        public const long MAX_LENGTH = int.MaxValue;
+
+        public ScaleSerializer() : base(long.MaxValue) { }
+        public ScaleSerializer(byte[] buffer) : base(buffer, long.MaxValue) { }
+        public ScaleSerializer(ArraySegment<byte> buffer) : base(buffer, long.MaxValue) { }
+
+        public override void serialize_f32(float value) => output.Write(value);
+
+        public override void serialize_f64(double value) => output.Write(value);
+
+        private void serialize_compact_u32(uint value)
+        {
+            if (value < (1u << 6))
+            {
+                output.Write((byte)(value << 2));
+            }
+            else if (value < (1u << 14))
+            {
+                output.Write((ushort)((value << 2) | 1));
+            }
+            else if (value < (1u << 30))
+            {
+                output.Write((value << 2) | 2);
+            }
+            else
+            {
+                output.Write((byte)3);
+                output.Write(value);
+            }
+        }
+
+        public override void serialize_len(long value)
+        {
+            if ((value < 0) || (value > MAX_LENGTH))
+            {
+                throw new SerializationException("length value doesn't fit in uint32");
+            }
+            serialize_compact_u32((uint)value);
+        }
+
+        public override void serialize_variant_index(int value)
+        {
+            if ((value < 0) || (value > byte.MaxValue))
+            {
+                throw new SerializationException("variant index doesn't fit in uint8");
+            }
+            output.Write((byte)value);
+        }
+
+        public override void sort_map_entries(int[] offsets)
+        {
+            // Not required by the format.
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using System;
+using System.Numerics;
+using NUnit.Framework;
+using Json;
+
+namespace Serde.Tests
+{
+    [TestFixture]
+    public class TestJson
+    {
+        [Test]
+        public void TestParseStringify()
+        {
+            object value = Json.Json.parse(" {\"a\" : [1, -2.5e3, true, null], \"b\": \"é😀\\n\"} ");
+            Assert.AreEqual("{\"a\":[1,-2.5e3,true,null],\"b\":\"é😀\\n\"}", Json.Json.stringify(value));
+            Assert.AreEqual("\"\\u0001/\"", Json.Json.stringify(Json.Json.parse("\"\\u0001\\/\"")));
+            Assert.AreEqual("[]", Json.Json.stringify(Json.Json.parse("[]")));
+        }
+
+        [Test]
+        public void TestParseErrors()
+        {
+            foreach (string input in new[] { "", "01", "[1,]", "{\"a\":1,}", "\"\\ud83d\"", "\"\\x\"", "nul", "1 2", "\"\t\"" })
+            {
+                Assert.Throws<DeserializationException>(() => Json.Json.parse(input), input);
+            }
+            Json.Json.parse(new string('[', 127) + new string(']', 127));
+            Assert.Throws<DeserializationException>(() => Json.Json.parse(new string('[', 128) + new string(']', 128)));
+        }
+
+        [Test]
+        public void TestFloats()
+        {
+            Func<object, string> literal = value => ((JsonNumber)value).Literal;
+            Assert.AreEqual("0.0", literal(Json.Json.serialize_f64(0.0)));
+            Assert.AreEqual("-0.0", literal(Json.Json.serialize_f64(-0.0)));
+            Assert.AreEqual("1e16", literal(Json.Json.serialize_f64(1e16)));
+            Assert.AreEqual("1234567890123456.0", literal(Json.Json.serialize_f64(1234567890123456.0)));
+            Assert.AreEqual("0.0001", literal(Json.Json.serialize_f64(1e-4)));
+            Assert.AreEqual("1e-6", literal(Json.Json.serialize_f64(1e-6)));
+            Assert.AreEqual("0.1", literal(Json.Json.serialize_f32(0.1f)));
+            Assert.AreEqual("1e13", literal(Json.Json.serialize_f32(1e13f)));
+            Assert.AreEqual("0.000001", literal(Json.Json.serialize_f32(1e-6f)));
+            Assert.AreEqual("1e-7", literal(Json.Json.serialize_f32(1e-7f)));
+            Assert.IsNull(Json.Json.serialize_f64(double.PositiveInfinity));
+
+            Assert.IsTrue(float.IsPositiveInfinity(Json.Json.deserialize_f32(new JsonNumber("1e39"))));
+            Assert.Throws<DeserializationException>(() => Json.Json.deserialize_f64(new JsonNumber("1e309")));
+        }
+
+        [Test]
+        public void TestIntegers()
+        {
+            BigInteger min = Json.Json.deserialize_i128(new JsonNumber("-170141183460469231731687303715884105728"));
+            Assert.AreEqual(-(BigInteger.One << 127), min);
+            Assert.AreEqual("-170141183460469231731687303715884105728", ((JsonNumber)Json.Json.serialize_i128(min)).Literal);
+
+            Assert.Throws<DeserializationException>(() => Json.Json.deserialize_u8(new JsonNumber("256")));
+            Assert.Throws<DeserializationException>(() => Json.Json.deserialize_i64(new JsonNumber("-0")));
+            Assert.Throws<DeserializationException>(() => Json.Json.deserialize_i64(new JsonNumber("1.0")));
+            Assert.AreEqual(ulong.MaxValue, Json.Json.deserialize_u64(new JsonKey("18446744073709551615")));
+        }
+
+        [Test]
+        public void TestSortKeys()
+        {
+            var obj = new JsonObject();
+            foreach (string key in new[] { "10", "-3", "2" })
+            {
+                obj.Put(key, null);
+            }
+            obj.SortKeys(JsonObject.NumberOrder);
+            CollectionAssert.AreEqual(new[] { "-3", "2", "10" }, obj.Keys);
+            obj.SortKeys(JsonObject.StringOrder);
+            CollectionAssert.AreEqual(new[] { "-3", "10", "2" }, obj.Keys);
+            obj.SortKeys(JsonObject.VariantOrder("2", "10", "-3"));
+            CollectionAssert.AreEqual(new[] { "2", "10", "-3" }, obj.Keys);
+        }
+    }
+}
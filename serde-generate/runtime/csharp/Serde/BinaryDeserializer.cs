@@ -28,15 +28,19 @@ namespace Serde
 
         public int get_buffer_offset() => (int)reader.BaseStream.Position;
 
+        protected virtual byte read_byte() => reader.ReadByte();
+
+        protected virtual byte[] read_bytes(int length) => reader.ReadBytes(length);
+
         public abstract long deserialize_len();
         public abstract int deserialize_variant_index();
         public abstract void check_that_key_slices_are_increasing(Range key1, Range key2);
 
-        public char deserialize_char() => throw new DeserializationException("Not implemented: char deserialization");
+        public virtual char deserialize_char() => throw new DeserializationException("Not implemented: char deserialization");
 
-        public float deserialize_f32() => reader.ReadSingle();
+        public virtual float deserialize_f32() => reader.ReadSingle();
 
-        public double deserialize_f64() => reader.ReadDouble();
+        public virtual double deserialize_f64() => reader.ReadDouble();
 
         public void increase_container_depth()
         {
@@ -52,14 +56,14 @@ namespace Serde
             containerDepthBudget += 1;
         }
 
-        public string deserialize_str()
+        public virtual string deserialize_str()
         {
             long len = deserialize_len();
             if (len < 0 || len > int.MaxValue)
             {
                 throw new DeserializationException("Incorrect length value for C# string");
             }
-            byte[] content = reader.ReadBytes((int)len);
+            byte[] content = read_bytes((int)len);
             if (content.Length < len)
                 throw new DeserializationException($"Need {len - content.Length} more bytes for string");
             return utf8.GetString(content);
@@ -72,7 +76,7 @@ namespace Serde
             {
                 throw new DeserializationException("Incorrect length value for C# array");
             }
-            byte[] content = reader.ReadBytes((int)len);
+            byte[] content = read_bytes((int)len);
             if (content.Length < len)
                 throw new DeserializationException($"Need {len - content.Length} more bytes for byte array");
             return new ValueArray<byte>(content);
@@ -80,7 +84,7 @@ namespace Serde
 
         public bool deserialize_bool()
         {
-            byte value = reader.ReadByte();
+            byte value = read_byte();
             switch (value)
             {
                 case 0: return false;
@@ -91,15 +95,15 @@ namespace Serde
 
         public Unit deserialize_unit() => new Unit();
 
-        public byte deserialize_u8() => reader.ReadByte();
+        public byte deserialize_u8() => read_byte();
 
-        public ushort deserialize_u16() => reader.ReadUInt16();
+        public virtual ushort deserialize_u16() => reader.ReadUInt16();
 
-        public uint deserialize_u32() => reader.ReadUInt32();
+        public virtual uint deserialize_u32() => reader.ReadUInt32();
 
-        public ulong deserialize_u64() => reader.ReadUInt64();
+        public virtual ulong deserialize_u64() => reader.ReadUInt64();
 
-        public BigInteger deserialize_u128()
+        public virtual BigInteger deserialize_u128()
         {
             BigInteger signed = deserialize_i128();
             if (signed >= 0)
@@ -112,17 +116,17 @@ namespace Serde
             }
         }
 
-        public sbyte deserialize_i8() => reader.ReadSByte();
+        public virtual sbyte deserialize_i8() => (sbyte)read_byte();
 
-        public short deserialize_i16() => reader.ReadInt16();
+        public virtual short deserialize_i16() => reader.ReadInt16();
 
-        public int deserialize_i32() => reader.ReadInt32();
+        public virtual int deserialize_i32() => reader.ReadInt32();
 
-        public long deserialize_i64() => reader.ReadInt64();
+        public virtual long deserialize_i64() => reader.ReadInt64();
 
-        public BigInteger deserialize_i128()
+        public virtual BigInteger deserialize_i128()
         {
-            byte[] content = reader.ReadBytes(16);
+            byte[] content = read_bytes(16);
             if (content.Length < 16)
                 throw new DeserializationException("Need more bytes to deserialize 128-bit integer");
             return new BigInteger(content);
@@ -130,7 +134,7 @@ namespace Serde
 
         public bool deserialize_option_tag()
         {
-            byte value = reader.ReadByte();
+            byte value = read_byte();
             switch (value)
             {
                 case 0: return false;
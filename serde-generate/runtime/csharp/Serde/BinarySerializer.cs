@@ -54,15 +54,15 @@ namespace Serde
 
         public abstract void sort_map_entries(int[] offsets);
 
-        public void serialize_char(char value) => throw new SerializationException("Not implemented: char serialization");
+        public virtual void serialize_char(char value) => throw new SerializationException("Not implemented: char serialization");
 
-        public void serialize_f32(float value) => output.Write(value);
+        public virtual void serialize_f32(float value) => output.Write(value);
 
-        public void serialize_f64(double value) => output.Write(value);
+        public virtual void serialize_f64(double value) => output.Write(value);
 
         public byte[] get_bytes() => buffer.ToArray();
 
-        public void serialize_str(string value) => serialize_bytes(new ValueArray<byte>(utf8.GetBytes(value)));
+        public virtual void serialize_str(string value) => serialize_bytes(new ValueArray<byte>(utf8.GetBytes(value)));
 
         public void serialize_bytes(ValueArray<byte> value)
         {
@@ -77,13 +77,13 @@ namespace Serde
 
         public void serialize_u8(byte value) => output.Write(value);
 
-        public void serialize_u16(ushort value) => output.Write(value);
+        public virtual void serialize_u16(ushort value) => output.Write(value);
 
-        public void serialize_u32(uint value) => output.Write(value);
+        public virtual void serialize_u32(uint value) => output.Write(value);
 
-        public void serialize_u64(ulong value) => output.Write(value);
+        public virtual void serialize_u64(ulong value) => output.Write(value);
 
-        public void serialize_u128(BigInteger value)
+        public virtual void serialize_u128(BigInteger value)
         {
             if (value >> 128 != 0)
             {
@@ -103,15 +103,15 @@ namespace Serde
             }
         }
 
-        public void serialize_i8(sbyte value) => output.Write(value);
+        public virtual void serialize_i8(sbyte value) => output.Write(value);
 
-        public void serialize_i16(short value) => output.Write(value);
+        public virtual void serialize_i16(short value) => output.Write(value);
 
-        public void serialize_i32(int value) => output.Write(value);
+        public virtual void serialize_i32(int value) => output.Write(value);
 
-        public void serialize_i64(long value) => output.Write(value);
+        public virtual void serialize_i64(long value) => output.Write(value);
 
-        public void serialize_i128(BigInteger value)
+        public virtual void serialize_i128(BigInteger value)
         {
             if (value >= 0)
             {
@@ -0,0 +1,37 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+using System;
+
+namespace Serde
+{
+    /// <summary>
+    /// Comparisons consistent with the ordering of the corresponding Rust values.
+    /// </summary>
+    public static class Comparisons
+    {
+        /// <summary>Compare strings by Unicode code points, that is, in the order of their UTF-8 encodings.</summary>
+        public static int CompareStrings(string left, string right)
+        {
+            int i = 0, j = 0;
+            while (i < left.Length && j < right.Length)
+            {
+                int x = CodePointAt(left, i);
+                int y = CodePointAt(right, j);
+                if (x != y) return x.CompareTo(y);
+                i += x > 0xFFFF ? 2 : 1;
+                j += y > 0xFFFF ? 2 : 1;
+            }
+            return (i < left.Length).CompareTo(j < right.Length);
+        }
+
+        /// <summary>Compare byte arrays lexicographically.</summary>
+        public static int CompareBytes(ValueArray<byte> left, ValueArray<byte> right)
+        {
+            return left.AsReadOnlySpan().SequenceCompareTo(right.AsReadOnlySpan());
+        }
+
+        static int CodePointAt(string s, int index) =>
+            char.IsSurrogatePair(s, index) ? char.ConvertToUtf32(s, index) : s[index];
+    }
+}
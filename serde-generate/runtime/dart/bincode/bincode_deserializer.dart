@@ -7,9 +7,159 @@ part of 'bincode.dart';
 const maxContainerDepth = (1 << 31) - 1;
 
 class BincodeDeserializer extends BinaryDeserializer {
-  BincodeDeserializer(Uint8List input)
+  BincodeDeserializer(Uint8List input,
+      {this.varintEncoding = false, this.bigEndian = false, this.limit})
       : super(input: input, containerDepthBudget: maxContainerDepth);
 
+  final bool varintEncoding;
+  final bool bigEndian;
+  // Reading more than `limit` bytes fails, as with the option `with_limit` of the Rust crate.
+  final int? limit;
+
+  // Check that `size` more bytes may be read within the limit.
+  void checkLimit(int size) {
+    final limit = this.limit;
+    if (limit != null && size > limit - offset) {
+      throw Exception('Exceeded the size limit of the encoding');
+    }
+  }
+
+  @override
+  bool deserializeBool() {
+    checkLimit(1);
+    return super.deserializeBool();
+  }
+
+  @override
+  int deserializeUint8() {
+    checkLimit(1);
+    return super.deserializeUint8();
+  }
+
+  @override
+  int deserializeInt8() {
+    checkLimit(1);
+    return super.deserializeInt8();
+  }
+
+  @override
+  Uint8List deserializeUint8List() {
+    final len = deserializeLength();
+    if (len < 0 || len > maxInt) {
+      throw Exception('The length of an array cannot exceed MAXINT');
+    }
+    // Check the length before allocating the content.
+    checkLimit(len);
+    final content = Uint8List(len);
+    for (var i = 0; i < len; i++) {
+      content[i] = super.deserializeUint8();
+    }
+    return content;
+  }
+
+  BigInt readFixint(int size) {
+    var value = BigInt.zero;
+    for (var i = 0; i < size; i++) {
+      final shift = 8 * (bigEndian ? size - 1 - i : i);
+      value = value | (BigInt.from(deserializeUint8()) << shift);
+    }
+    return value;
+  }
+
+  BigInt deserializeVarint(int size) {
+    final tag = deserializeUint8();
+    final BigInt value;
+    if (tag <= singleByteMax) {
+      value = BigInt.from(tag);
+    } else if (tag == u16Tag) {
+      value = readFixint(2);
+    } else if (tag == u32Tag) {
+      value = readFixint(4);
+    } else if (tag == u64Tag) {
+      value = readFixint(8);
+    } else if (tag == u128Tag && size == 16) {
+      value = readFixint(16);
+    } else {
+      throw Exception('Invalid tag for varint-encoded value: $tag');
+    }
+    if (value.bitLength > 8 * size) {
+      throw Exception('Overflow while parsing varint-encoded value');
+    }
+    return value;
+  }
+
+  BigInt deserializeUnsigned(int size) {
+    if (varintEncoding) {
+      return deserializeVarint(size);
+    }
+    return readFixint(size);
+  }
+
+  BigInt deserializeSigned(int size) {
+    if (!varintEncoding) {
+      return readFixint(size).toSigned(8 * size);
+    }
+    final value = deserializeVarint(size);
+    if (value.isEven) {
+      return value >> 1;
+    } else {
+      return -(value >> 1) - BigInt.one;
+    }
+  }
+
+  @override
+  int deserializeUint16() {
+    return deserializeUnsigned(2).toInt();
+  }
+
+  @override
+  int deserializeUint32() {
+    return deserializeUnsigned(4).toInt();
+  }
+
+  @override
+  Uint64 deserializeUint64() {
+    return Uint64(deserializeUnsigned(8));
+  }
+
+  @override
+  Uint128 deserializeUint128() {
+    return Uint128.fromBigInt(deserializeUnsigned(16));
+  }
+
+  @override
+  int deserializeInt16() {
+    return deserializeSigned(2).toInt();
+  }
+
+  @override
+  int deserializeInt32() {
+    return deserializeSigned(4).toInt();
+  }
+
+  @override
+  int deserializeInt64() {
+    return deserializeSigned(8).toInt();
+  }
+
+  @override
+  Int128 deserializeInt128() {
+    return Int128.fromBigInt(deserializeSigned(16));
+  }
+
+  // Floating-point numbers are never varint-encoded.
+  @override
+  double deserializeFloat32() {
+    final bdata = ByteData(4)..setUint32(0, readFixint(4).toInt());
+    return bdata.getFloat32(0);
+  }
+
+  @override
+  double deserializeFloat64() {
+    final bdata = ByteData(8)..setInt64(0, readFixint(8).toSigned(64).toInt());
+    return bdata.getFloat64(0);
+  }
+
   @override
   int deserializeLength() {
     return deserializeUint64().toInt();
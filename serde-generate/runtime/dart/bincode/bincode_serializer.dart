@@ -3,12 +3,141 @@
 
 part of 'bincode.dart';
 
+// Tags used by the varint encoding of integers.
+const singleByteMax = 250;
+const u16Tag = 251;
+const u32Tag = 252;
+const u64Tag = 253;
+const u128Tag = 254;
+
 class BincodeSerializer extends BinarySerializer {
-  BincodeSerializer()
+  BincodeSerializer({this.varintEncoding = false, this.bigEndian = false})
       : super(
           containerDepthBudget: maxContainerDepth,
         );
 
+  final bool varintEncoding;
+  final bool bigEndian;
+
+  void writeFixint(BigInt value, int size) {
+    final byteMask = BigInt.from(0xFF);
+    var number = value.toUnsigned(8 * size);
+    final bdata = Uint8List(size);
+    for (var i = 0; i < size; i++) {
+      bdata[bigEndian ? size - 1 - i : i] = (number & byteMask).toInt();
+      number = number >> 8;
+    }
+    output.addAll(bdata);
+  }
+
+  void serializeVarint(BigInt value) {
+    if (value <= BigInt.from(singleByteMax)) {
+      output.add(value.toInt());
+    } else if (value.bitLength <= 16) {
+      output.add(u16Tag);
+      writeFixint(value, 2);
+    } else if (value.bitLength <= 32) {
+      output.add(u32Tag);
+      writeFixint(value, 4);
+    } else if (value.bitLength <= 64) {
+      output.add(u64Tag);
+      writeFixint(value, 8);
+    } else {
+      output.add(u128Tag);
+      writeFixint(value, 16);
+    }
+  }
+
+  void serializeUnsigned(BigInt value, int size) {
+    if (varintEncoding) {
+      serializeVarint(value);
+    } else {
+      writeFixint(value, size);
+    }
+  }
+
+  // Signed integers use a "zigzag" encoding before the varint encoding.
+  void serializeSigned(BigInt value, int size) {
+    if (!varintEncoding) {
+      writeFixint(value, size);
+    } else if (value.isNegative) {
+      serializeVarint(-(value << 1) - BigInt.one);
+    } else {
+      serializeVarint(value << 1);
+    }
+  }
+
+  @override
+  void serializeUint16(int val) {
+    if (val.bitLength > 16) {
+      throw Exception(
+          'The integer literal $val can\'t be represented in 16 bits.');
+    }
+    serializeUnsigned(BigInt.from(val), 2);
+  }
+
+  @override
+  void serializeUint32(int val) {
+    if (val.bitLength > 32) {
+      throw Exception(
+          'The integer literal $val can\'t be represented in 32 bits.');
+    }
+    serializeUnsigned(BigInt.from(val), 4);
+  }
+
+  @override
+  void serializeUint64(Uint64 val) {
+    serializeUnsigned(val.toBigInt(), 8);
+  }
+
+  @override
+  void serializeUint128(Uint128 value) {
+    serializeUnsigned(value.toBigInt(), 16);
+  }
+
+  @override
+  void serializeInt16(int value) {
+    if (value.bitLength > 15) {
+      throw Exception(
+          'The integer literal $value can\'t be represented in 16 bits.');
+    }
+    serializeSigned(BigInt.from(value), 2);
+  }
+
+  @override
+  void serializeInt32(int value) {
+    if (value.bitLength > 31) {
+      throw Exception(
+          'The integer literal $value can\'t be represented in 32 bits.');
+    }
+    serializeSigned(BigInt.from(value), 4);
+  }
+
+  @override
+  void serializeInt64(int value) {
+    serializeSigned(BigInt.from(value), 8);
+  }
+
+  @override
+  void serializeInt128(Int128 value) {
+    serializeSigned(value.toBigInt(), 16);
+  }
+
+  // Floating-point numbers are never varint-encoded.
+  @override
+  void serializeFloat32(double value) {
+    final bdata = ByteData(4)
+      ..setFloat32(0, value, bigEndian ? Endian.big : Endian.little);
+    output.addAll(bdata.buffer.asUint8List());
+  }
+
+  @override
+  void serializeFloat64(double value) {
+    final bdata = ByteData(8)
+      ..setFloat64(0, value, bigEndian ? Endian.big : Endian.little);
+    output.addAll(bdata.buffer.asUint8List());
+  }
+
   @override
   void serializeLength(int value) {
     serializeUint64(Uint64(BigInt.from(value)));
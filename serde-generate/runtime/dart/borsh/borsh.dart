@@ -0,0 +1,11 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+library borsh;
+
+import 'dart:typed_data';
+import '../serde/serde.dart';
+
+part 'borsh_deserializer.dart';
+part 'borsh_serializer.dart';
+part 'borsh_sort_keys.dart';
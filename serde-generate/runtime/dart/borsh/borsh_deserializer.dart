@@ -0,0 +1,148 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'borsh.dart';
+
+// Maximum length allowed for sequences (vectors, bytes, strings) and maps.
+const maxSequenceLength = (1 << 31) - 1;
+
+// Maximum number of nested structs and enum variants.
+const maxContainerDepth = (1 << 31) - 1;
+
+class BorshDeserializer extends BinaryDeserializer {
+  BorshDeserializer(Uint8List input)
+      : super(
+          input: input,
+          containerDepthBudget: maxContainerDepth,
+        );
+
+  final _sortKeys = _BorshSortKeys();
+
+  @override
+  String deserializeString() {
+    final start = offset;
+    final value = super.deserializeString();
+    _sortKeys.recordStr(input.buffer.asUint8List(), start, offset);
+    return value;
+  }
+
+  @override
+  int deserializeUint16() {
+    final start = offset;
+    final value = super.deserializeUint16();
+    _sortKeys.recordInteger(input.buffer.asUint8List(), start, offset, false);
+    return value;
+  }
+
+  @override
+  int deserializeUint32() {
+    final start = offset;
+    final value = super.deserializeUint32();
+    _sortKeys.recordInteger(input.buffer.asUint8List(), start, offset, false);
+    return value;
+  }
+
+  @override
+  Uint64 deserializeUint64() {
+    final start = offset;
+    final value = super.deserializeUint64();
+    _sortKeys.recordInteger(input.buffer.asUint8List(), start, offset, false);
+    return value;
+  }
+
+  @override
+  Uint128 deserializeUint128() {
+    final start = offset;
+    final value = super.deserializeUint128();
+    _sortKeys.recordInteger(input.buffer.asUint8List(), start, offset, false);
+    return value;
+  }
+
+  @override
+  int deserializeInt8() {
+    final start = offset;
+    final value = super.deserializeInt8();
+    _sortKeys.recordInteger(input.buffer.asUint8List(), start, offset, true);
+    return value;
+  }
+
+  @override
+  int deserializeInt16() {
+    final start = offset;
+    final value = super.deserializeInt16();
+    _sortKeys.recordInteger(input.buffer.asUint8List(), start, offset, true);
+    return value;
+  }
+
+  @override
+  int deserializeInt32() {
+    final start = offset;
+    final value = super.deserializeInt32();
+    _sortKeys.recordInteger(input.buffer.asUint8List(), start, offset, true);
+    return value;
+  }
+
+  @override
+  int deserializeInt64() {
+    final start = offset;
+    final value = super.deserializeInt64();
+    _sortKeys.recordInteger(input.buffer.asUint8List(), start, offset, true);
+    return value;
+  }
+
+  @override
+  Int128 deserializeInt128() {
+    final start = offset;
+    final value = super.deserializeInt128();
+    _sortKeys.recordInteger(input.buffer.asUint8List(), start, offset, true);
+    return value;
+  }
+
+  @override
+  double deserializeFloat32() {
+    final value = super.deserializeFloat32();
+    if (value.isNaN) {
+      throw Exception('NaN values are not supported by Borsh');
+    }
+    return value;
+  }
+
+  @override
+  double deserializeFloat64() {
+    final value = super.deserializeFloat64();
+    if (value.isNaN) {
+      throw Exception('NaN values are not supported by Borsh');
+    }
+    return value;
+  }
+
+  @override
+  int deserializeChar() {
+    throw Exception('Characters are not supported by Borsh');
+  }
+
+  @override
+  int deserializeLength() {
+    final length = deserializeUint32();
+    if (length > maxSequenceLength) {
+      throw Exception("length is too large");
+    }
+    return length;
+  }
+
+  @override
+  int deserializeVariantIndex() {
+    return deserializeUint8();
+  }
+
+  @override
+  void checkThatKeySlicesAreIncreasing(Slice key1, Slice key2) {
+    final content = input.buffer.asUint8List();
+    final sortKey1 = _sortKeys.sortKey(content, key1.start, key1.end);
+    final sortKey2 = _sortKeys.sortKey(content, key2.start, key2.end);
+    if (_BorshSortKeys.compare(sortKey1, sortKey2) >= 0) {
+      throw Exception(
+          "Error while decoding map: keys are not serialized in the expected order");
+    }
+  }
+}
@@ -0,0 +1,162 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'borsh.dart';
+
+class BorshSerializer extends BinarySerializer {
+  BorshSerializer()
+      : super(
+          containerDepthBudget: maxContainerDepth,
+        );
+
+  final _sortKeys = _BorshSortKeys();
+
+  @override
+  void serializeString(String str) {
+    final start = offset;
+    super.serializeString(str);
+    _sortKeys.recordStr(output, start, offset);
+  }
+
+  @override
+  void serializeUint16(int value) {
+    final start = offset;
+    super.serializeUint16(value);
+    _sortKeys.recordInteger(output, start, offset, false);
+  }
+
+  @override
+  void serializeUint32(int value) {
+    final start = offset;
+    super.serializeUint32(value);
+    _sortKeys.recordInteger(output, start, offset, false);
+  }
+
+  @override
+  void serializeUint64(Uint64 value) {
+    final start = offset;
+    super.serializeUint64(value);
+    _sortKeys.recordInteger(output, start, offset, false);
+  }
+
+  @override
+  void serializeUint128(Uint128 value) {
+    final start = offset;
+    super.serializeUint128(value);
+    _sortKeys.recordInteger(output, start, offset, false);
+  }
+
+  @override
+  void serializeInt8(int value) {
+    final start = offset;
+    super.serializeInt8(value);
+    _sortKeys.recordInteger(output, start, offset, true);
+  }
+
+  @override
+  void serializeInt16(int value) {
+    final start = offset;
+    super.serializeInt16(value);
+    _sortKeys.recordInteger(output, start, offset, true);
+  }
+
+  @override
+  void serializeInt32(int value) {
+    final start = offset;
+    super.serializeInt32(value);
+    _sortKeys.recordInteger(output, start, offset, true);
+  }
+
+  @override
+  void serializeInt64(int value) {
+    final start = offset;
+    super.serializeInt64(value);
+    _sortKeys.recordInteger(output, start, offset, true);
+  }
+
+  @override
+  void serializeInt128(Int128 value) {
+    final start = offset;
+    super.serializeInt128(value);
+    _sortKeys.recordInteger(output, start, offset, true);
+  }
+
+  @override
+  void serializeFloat32(double value) {
+    if (value.isNaN) {
+      throw Exception('NaN values are not supported by Borsh');
+    }
+    super.serializeFloat32(value);
+  }
+
+  @override
+  void serializeFloat64(double value) {
+    if (value.isNaN) {
+      throw Exception('NaN values are not supported by Borsh');
+    }
+    super.serializeFloat64(value);
+  }
+
+  @override
+  void serializeChar(int value) {
+    throw Exception('Characters are not supported by Borsh');
+  }
+
+  @override
+  void serializeLength(int value) {
+    if (value < 0 || value > maxSequenceLength) {
+      throw Exception('length is too large');
+    }
+    serializeUint32(value);
+  }
+
+  @override
+  void serializeVariantIndex(int value) {
+    if (value < 0 || value > 0xFF) {
+      throw Exception('variant index is too large');
+    }
+    serializeUint8(value);
+  }
+
+  @override
+  void sortMapEntries(List<int> offsets) {
+    if (offsets.isEmpty) {
+      return;
+    }
+
+    // Prepare a list of slices with their sort keys
+    final data = Uint8List.fromList(output);
+    List<MapEntry<List<int>, Uint8List>> slices = [];
+
+    // Collect slices
+    for (int i = 0; i < offsets.length; i++) {
+      final int startOffset = offsets[i];
+      final int cutOffset;
+      if (i + 1 < offsets.length) {
+        cutOffset = offsets[i + 1];
+      } else {
+        cutOffset = data.length;
+      }
+      slices.add(MapEntry(
+        _sortKeys.sortKey(data, startOffset, cutOffset),
+        data.sublist(startOffset, cutOffset),
+      ));
+    }
+
+    // Sort slices by comparing their sort keys lexicographically
+    slices.sort((a, b) => _BorshSortKeys.compare(a.key, b.key));
+    // Positions in the map are no longer valid. Map keys never contain maps.
+    _sortKeys.truncate(offsets[0]);
+
+    // Write sorted slices back to output
+    int writePosition = offsets[0];
+    for (final slice in slices) {
+      output.setRange(
+          writePosition, writePosition + slice.value.length, slice.value);
+      writePosition += slice.value.length;
+    }
+
+    // Ensure the final length is correct
+    assert(offsets.last == output.length);
+  }
+}
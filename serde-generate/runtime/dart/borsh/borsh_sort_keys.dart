@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'borsh.dart';
+
+class _SortKeyRecord {
+  _SortKeyRecord(this.start, this.end, this.key);
+
+  final int start;
+  final int end;
+  final List<int> key;
+}
+
+// Order-preserving encodings of the integers and strings written or read so far,
+// indexed by their positions in the buffer.
+//
+// Borsh sorts map entries by keys as in Rust. Substituting these encodings in the
+// encoded keys gives byte strings that compare in the same way.
+class _BorshSortKeys {
+  // Ordered by start positions.
+  final List<_SortKeyRecord> _records = [];
+
+  // Little-endian integers compare as in Rust in big-endian order, with the sign bit
+  // flipped.
+  void recordInteger(List<int> bytes, int start, int end, bool signed) {
+    final key = bytes.sublist(start, end).reversed.toList();
+    if (signed) {
+      key[0] ^= 0x80;
+    }
+    _record(start, end, key);
+  }
+
+  // Strings have their zeros escaped and a terminator added so that prefixes come
+  // first.
+  void recordStr(List<int> bytes, int start, int end) {
+    final List<int> key = [];
+    for (final byte in bytes.sublist(start + 4, end)) {
+      key.add(byte);
+      if (byte == 0) {
+        key.add(0xff);
+      }
+    }
+    key.addAll([0, 0]);
+    _record(start, end, key);
+  }
+
+  void _record(int start, int end, List<int> key) {
+    // Values written or read inside the current one are superseded.
+    truncate(start);
+    _records.add(_SortKeyRecord(start, end, key));
+  }
+
+  int _lowerBound(int start) {
+    int low = 0;
+    int high = _records.length;
+    while (low < high) {
+      final middle = low + (high - low) ~/ 2;
+      if (_records[middle].start < start) {
+        low = middle + 1;
+      } else {
+        high = middle;
+      }
+    }
+    return low;
+  }
+
+  void truncate(int start) {
+    _records.removeRange(_lowerBound(start), _records.length);
+  }
+
+  List<int> sortKey(List<int> bytes, int start, int end) {
+    final List<int> result = [];
+    int position = start;
+    for (int i = _lowerBound(start);
+        i < _records.length && _records[i].start < end;
+        i++) {
+      result.addAll(bytes.sublist(position, _records[i].start));
+      result.addAll(_records[i].key);
+      position = _records[i].end;
+    }
+    result.addAll(bytes.sublist(position, end));
+    return result;
+  }
+
+  static int compare(List<int> key1, List<int> key2) {
+    for (int i = 0; i < key1.length && i < key2.length; i++) {
+      if (key1[i] != key2[i]) {
+        return key1[i].compareTo(key2[i]);
+      }
+    }
+    return key1.length.compareTo(key2.length);
+  }
+}
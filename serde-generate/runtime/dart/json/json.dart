@@ -0,0 +1,18 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+/// The JSON format of the Rust crate `serde_json`. Generated classes convert their values from
+/// and to trees of `JsonValue` with the helpers of `Json`, then `Json.parse` and `Json.stringify`
+/// deal with the JSON text.
+library json;
+
+import 'dart:convert' show utf8;
+import 'dart:typed_data';
+
+import 'package:meta/meta.dart';
+
+import '../serde/serde.dart';
+
+part 'json_helpers.dart';
+part 'json_parser.dart';
+part 'json_value.dart';
@@ -0,0 +1,243 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'json.dart';
+
+Exception _typeError(JsonValue value, String expected) {
+  return Exception('Invalid type: expected $expected, found $value');
+}
+
+BigInt _bits(int n) => BigInt.one << n;
+
+BigInt _readInteger(
+  JsonValue value,
+  int bits,
+  bool signed, {
+  bool acceptsNegativeZero = false,
+}) {
+  String literal;
+  if (value is JsonNumber) {
+    literal = value.literal;
+  } else if (value is JsonKey) {
+    literal = value.value;
+  } else {
+    throw _typeError(value, 'an integer');
+  }
+  // Like `serde_json`, read `-0` as a float, except for 128-bit integers.
+  if (!_integer.hasMatch(literal) ||
+      (literal == '-0' && !acceptsNegativeZero)) {
+    throw _typeError(value, 'an integer');
+  }
+  final result = BigInt.parse(literal);
+  final min = signed ? -_bits(bits - 1) : BigInt.zero;
+  final max = (signed ? _bits(bits - 1) : _bits(bits)) - BigInt.one;
+  if (result < min || result > max) {
+    throw Exception('Integer out of range: $literal');
+  }
+  return result;
+}
+
+double _readNumber(JsonValue value) {
+  String literal;
+  if (value is JsonNumber) {
+    literal = value.literal;
+  } else if (value is JsonKey && _number.hasMatch(value.value)) {
+    literal = value.value;
+  } else {
+    throw _typeError(value, 'a number');
+  }
+  final result = double.parse(literal);
+  if (!result.isFinite) {
+    throw Exception('Number out of range: $literal');
+  }
+  return result;
+}
+
+class Json {
+  static JsonValue parse(String input) {
+    final parser = _JsonParser(input);
+    final value = parser.parseValue(maxContainerDepth);
+    parser.skipWhitespace();
+    if (parser.position < input.length) {
+      throw Exception('Trailing characters in JSON input');
+    }
+    return value;
+  }
+
+  static String stringify(JsonValue value) {
+    final output = StringBuffer();
+    _writeJson(output, value);
+    return output.toString();
+  }
+
+  // Serialization helpers
+
+  static JsonValue serializeUnit(Unit value) => null;
+
+  static JsonValue serializeBool(bool value) => value;
+
+  static JsonValue serializeInt8(int value) => JsonNumber('$value');
+
+  static JsonValue serializeInt16(int value) => JsonNumber('$value');
+
+  static JsonValue serializeInt32(int value) => JsonNumber('$value');
+
+  static JsonValue serializeInt64(int value) => JsonNumber('$value');
+
+  static JsonValue serializeInt128(Int128 value) => JsonNumber('$value');
+
+  static JsonValue serializeUint8(int value) => JsonNumber('$value');
+
+  static JsonValue serializeUint16(int value) => JsonNumber('$value');
+
+  static JsonValue serializeUint32(int value) => JsonNumber('$value');
+
+  static JsonValue serializeUint64(Uint64 value) => JsonNumber('$value');
+
+  static JsonValue serializeUint128(Uint128 value) => JsonNumber('$value');
+
+  static JsonValue serializeFloat32(double value) =>
+      value.isFinite ? JsonNumber(_formatFloat(value, true)) : null;
+
+  static JsonValue serializeFloat64(double value) =>
+      value.isFinite ? JsonNumber(_formatFloat(value, false)) : null;
+
+  static JsonValue serializeChar(int value) {
+    if (value < 0 ||
+        value > 0x10ffff ||
+        (_isHighSurrogate(value) || _isLowSurrogate(value))) {
+      throw Exception('Invalid character: $value');
+    }
+    return String.fromCharCode(value);
+  }
+
+  static JsonValue serializeString(String value) => value;
+
+  static JsonValue serializeBytes(Bytes value) =>
+      value.content.map((b) => JsonNumber('$b')).toList();
+
+  /// A variant of an enum that is not a unit variant (unit variants are written as strings).
+  static JsonValue serializeVariant(String name, JsonValue content) {
+    final result = JsonObject();
+    result.put(name, content);
+    return result;
+  }
+
+  /// The key of a map entry, given the JSON value of the key.
+  static String key(JsonValue value) {
+    if (value is String) {
+      return value;
+    }
+    if (value is JsonNumber) {
+      return value.literal;
+    }
+    if (value is bool) {
+      return value ? 'true' : 'false';
+    }
+    throw Exception('Map keys must be strings or numbers');
+  }
+
+  // Deserialization helpers
+
+  static Unit deserializeUnit(JsonValue value) {
+    if (value != null) {
+      throw _typeError(value, 'null');
+    }
+    return const Unit();
+  }
+
+  static bool deserializeBool(JsonValue value) {
+    if (value is bool) {
+      return value;
+    }
+    if (value is JsonKey && (value.value == 'true' || value.value == 'false')) {
+      return value.value == 'true';
+    }
+    throw _typeError(value, 'a boolean');
+  }
+
+  static int deserializeInt8(JsonValue value) =>
+      _readInteger(value, 8, true).toInt();
+
+  static int deserializeInt16(JsonValue value) =>
+      _readInteger(value, 16, true).toInt();
+
+  static int deserializeInt32(JsonValue value) =>
+      _readInteger(value, 32, true).toInt();
+
+  static int deserializeInt64(JsonValue value) =>
+      _readInteger(value, 64, true).toInt();
+
+  static Int128 deserializeInt128(JsonValue value) => Int128.fromBigInt(
+      _readInteger(value, 128, true, acceptsNegativeZero: true));
+
+  static int deserializeUint8(JsonValue value) =>
+      _readInteger(value, 8, false).toInt();
+
+  static int deserializeUint16(JsonValue value) =>
+      _readInteger(value, 16, false).toInt();
+
+  static int deserializeUint32(JsonValue value) =>
+      _readInteger(value, 32, false).toInt();
+
+  static Uint64 deserializeUint64(JsonValue value) =>
+      Uint64.fromBigInt(_readInteger(value, 64, false));
+
+  static Uint128 deserializeUint128(JsonValue value) =>
+      Uint128.fromBigInt(_readInteger(value, 128, false));
+
+  /// Like `serde_json`, read the closest f64 value then convert it to f32.
+  static double deserializeFloat32(JsonValue value) =>
+      _fround(_readNumber(value));
+
+  static double deserializeFloat64(JsonValue value) => _readNumber(value);
+
+  static int deserializeChar(JsonValue value) {
+    final runes = deserializeString(value).runes;
+    if (runes.length != 1) {
+      throw _typeError(value, 'a character');
+    }
+    return runes.first;
+  }
+
+  static String deserializeString(JsonValue value) {
+    if (value is String) {
+      return value;
+    }
+    if (value is JsonKey) {
+      return value.value;
+    }
+    throw _typeError(value, 'a string');
+  }
+
+  static Bytes deserializeBytes(JsonValue value) => Bytes(Uint8List.fromList(
+      deserializeArray(value).map(deserializeUint8).toList()));
+
+  /// The items of an array, of the given length if any (e.g. a tuple or a fixed-size array).
+  static List<JsonValue> deserializeArray(JsonValue value, [int? length]) {
+    if (value is! List<JsonValue>) {
+      throw _typeError(value, 'an array');
+    }
+    if (length != null && value.length != length) {
+      throw Exception('Invalid length: ${value.length} instead of $length');
+    }
+    return value;
+  }
+
+  static JsonObject deserializeObject(JsonValue value) {
+    if (value is! JsonObject) {
+      throw _typeError(value, 'an object');
+    }
+    return value;
+  }
+
+  static JsonVariant deserializeVariant(JsonValue value) {
+    if (value is String || value is JsonKey) {
+      return JsonVariant(deserializeString(value), null, false);
+    }
+    if (value is JsonObject && value.length == 1) {
+      return JsonVariant(value.keys[0], value.values[0], true);
+    }
+    throw _typeError(value, 'a string or an object with a single entry');
+  }
+}
@@ -0,0 +1,348 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'json.dart';
+
+/// Maximal number of nested arrays and objects accepted by `serde_json` (whose recursion limit
+/// is 128).
+const int maxContainerDepth = 127;
+
+final _integer = RegExp(r'^-?(0|[1-9][0-9]*)$');
+final _number = RegExp(r'^-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?$');
+final _hex = RegExp(r'^[0-9a-fA-F]{4}$');
+
+bool _isHighSurrogate(int c) => c >= 0xd800 && c <= 0xdbff;
+
+bool _isLowSurrogate(int c) => c >= 0xdc00 && c <= 0xdfff;
+
+void _writeJson(StringBuffer output, JsonValue value) {
+  if (value == null) {
+    output.write('null');
+  } else if (value is bool) {
+    output.write(value ? 'true' : 'false');
+  } else if (value is JsonNumber) {
+    output.write(value.literal);
+  } else if (value is String) {
+    _writeString(output, value);
+  } else if (value is JsonKey) {
+    _writeString(output, value.value);
+  } else if (value is List<JsonValue>) {
+    output.write('[');
+    for (var i = 0; i < value.length; i++) {
+      if (i > 0) output.write(',');
+      _writeJson(output, value[i]);
+    }
+    output.write(']');
+  } else if (value is JsonObject) {
+    output.write('{');
+    for (var i = 0; i < value.length; i++) {
+      if (i > 0) output.write(',');
+      _writeString(output, value.keys[i]);
+      output.write(':');
+      _writeJson(output, value.values[i]);
+    }
+    output.write('}');
+  } else {
+    throw Exception('Invalid JSON value: $value');
+  }
+}
+
+void _writeString(StringBuffer output, String value) {
+  output.write('"');
+  for (var i = 0; i < value.length; i++) {
+    final c = value.codeUnitAt(i);
+    switch (c) {
+      case 0x22:
+        output.write(r'\"');
+        break;
+      case 0x5c:
+        output.write(r'\\');
+        break;
+      case 0x08:
+        output.write(r'\b');
+        break;
+      case 0x0c:
+        output.write(r'\f');
+        break;
+      case 0x0a:
+        output.write(r'\n');
+        break;
+      case 0x0d:
+        output.write(r'\r');
+        break;
+      case 0x09:
+        output.write(r'\t');
+        break;
+      default:
+        if (c < 0x20) {
+          output.write(r'\u00' + c.toRadixString(16).padLeft(2, '0'));
+        } else if (_isHighSurrogate(c) &&
+            i + 1 < value.length &&
+            _isLowSurrogate(value.codeUnitAt(i + 1))) {
+          output.writeCharCode(c);
+          output.writeCharCode(value.codeUnitAt(i + 1));
+          i++;
+        } else if (_isHighSurrogate(c) || _isLowSurrogate(c)) {
+          throw Exception('Invalid unicode string');
+        } else {
+          output.writeCharCode(c);
+        }
+    }
+  }
+  output.write('"');
+}
+
+class _JsonParser {
+  _JsonParser(this.input);
+
+  final String input;
+  int position = 0;
+
+  void skipWhitespace() {
+    while (position < input.length) {
+      final c = input.codeUnitAt(position);
+      if (c != 0x20 && c != 0x09 && c != 0x0a && c != 0x0d) {
+        return;
+      }
+      position++;
+    }
+  }
+
+  Exception error(String message) => Exception('$message at position $position');
+
+  bool peek(String c) => position < input.length && input[position] == c;
+
+  String next() {
+    if (position >= input.length) {
+      throw error('Unexpected end of JSON input');
+    }
+    return input[position++];
+  }
+
+  void expect(String word) {
+    if (!input.startsWith(word, position)) {
+      throw error('Invalid JSON value');
+    }
+    position += word.length;
+  }
+
+  JsonValue parseValue(int depthBudget) {
+    skipWhitespace();
+    if (position >= input.length) {
+      throw error('Unexpected end of JSON input');
+    }
+    final c = input[position];
+    switch (c) {
+      case 'n':
+        expect('null');
+        return null;
+      case 't':
+        expect('true');
+        return true;
+      case 'f':
+        expect('false');
+        return false;
+      case '"':
+        return parseString();
+      case '[':
+        if (depthBudget == 0) {
+          throw error('Exceeded maximum container depth');
+        }
+        position++;
+        final items = <JsonValue>[];
+        skipWhitespace();
+        if (peek(']')) {
+          position++;
+          return items;
+        }
+        for (;;) {
+          items.add(parseValue(depthBudget - 1));
+          skipWhitespace();
+          final d = next();
+          if (d == ']') {
+            return items;
+          }
+          if (d != ',') {
+            throw error("Expected ',' or ']'");
+          }
+        }
+      case '{':
+        if (depthBudget == 0) {
+          throw error('Exceeded maximum container depth');
+        }
+        position++;
+        final object = JsonObject();
+        skipWhitespace();
+        if (peek('}')) {
+          position++;
+          return object;
+        }
+        for (;;) {
+          skipWhitespace();
+          if (!peek('"')) {
+            throw error('Expected a string key');
+          }
+          final key = parseString();
+          skipWhitespace();
+          if (next() != ':') {
+            throw error("Expected ':'");
+          }
+          object.put(key, parseValue(depthBudget - 1));
+          skipWhitespace();
+          final e = next();
+          if (e == '}') {
+            return object;
+          }
+          if (e != ',') {
+            throw error("Expected ',' or '}'");
+          }
+        }
+      default:
+        if (c == '-' || (c.compareTo('0') >= 0 && c.compareTo('9') <= 0)) {
+          return parseNumber();
+        }
+        throw error('Invalid JSON value');
+    }
+  }
+
+  JsonNumber parseNumber() {
+    final start = position;
+    while (position < input.length && '-+.eE0123456789'.contains(input[position])) {
+      position++;
+    }
+    final literal = input.substring(start, position);
+    if (!_number.hasMatch(literal)) {
+      throw error('Invalid number');
+    }
+    return JsonNumber(literal);
+  }
+
+  int parseHex() {
+    final end = position + 4 < input.length ? position + 4 : input.length;
+    final digits = input.substring(position, end);
+    if (!_hex.hasMatch(digits)) {
+      throw error('Invalid escape in string');
+    }
+    position += 4;
+    return int.parse(digits, radix: 16);
+  }
+
+  String parseString() {
+    position++;
+    final result = StringBuffer();
+    for (;;) {
+      final c = next();
+      final code = c.codeUnitAt(0);
+      if (c == '"') {
+        return result.toString();
+      }
+      if (code < 0x20) {
+        throw error('Control character in string');
+      }
+      if (c != r'\') {
+        if (_isHighSurrogate(code)) {
+          if (!(position < input.length && _isLowSurrogate(input.codeUnitAt(position)))) {
+            throw error('Invalid unicode string');
+          }
+          result.write(c);
+          result.write(next());
+        } else if (_isLowSurrogate(code)) {
+          throw error('Invalid unicode string');
+        } else {
+          result.write(c);
+        }
+        continue;
+      }
+      final escape = next();
+      switch (escape) {
+        case '"':
+        case r'\':
+        case '/':
+          result.write(escape);
+          break;
+        case 'b':
+          result.write('\b');
+          break;
+        case 'f':
+          result.write('\f');
+          break;
+        case 'n':
+          result.write('\n');
+          break;
+        case 'r':
+          result.write('\r');
+          break;
+        case 't':
+          result.write('\t');
+          break;
+        case 'u':
+          final high = parseHex();
+          if (_isLowSurrogate(high)) {
+            throw error('Lone surrogate in string');
+          }
+          if (_isHighSurrogate(high)) {
+            if (!input.startsWith(r'\u', position)) {
+              throw error('Lone surrogate in string');
+            }
+            position += 2;
+            final low = parseHex();
+            if (!_isLowSurrogate(low)) {
+              throw error('Lone surrogate in string');
+            }
+            result.writeCharCode(high);
+            result.writeCharCode(low);
+          } else {
+            result.writeCharCode(high);
+          }
+          break;
+        default:
+          throw error('Invalid escape in string');
+      }
+    }
+  }
+}
+
+/// Round a double to the closest f32 value.
+double _fround(double value) {
+  final buffer = Float32List(1);
+  buffer[0] = value;
+  return buffer[0];
+}
+
+/// Format a finite float in the manner of the Rust crate `ryu`, as `serde_json` does: with the
+/// shortest digits that read back as the same value.
+String _formatFloat(double value, bool isF32) {
+  if (value == 0) {
+    return value.isNegative ? '-0.0' : '0.0';
+  }
+  final sign = value < 0 ? '-' : '';
+  final magnitude = value.abs();
+  var scientific = magnitude.toStringAsExponential();
+  if (isF32) {
+    for (var precision = 1; precision <= 9; precision++) {
+      scientific = magnitude.toStringAsExponential(precision - 1);
+      if (_fround(double.parse(scientific)) == magnitude) {
+        break;
+      }
+    }
+  }
+  final parts = scientific.split('e');
+  final digits = parts[0]
+      .replaceFirst('.', '')
+      .replaceFirstMapped(RegExp(r'(.)0+$'), (match) => match[1]!);
+  final point = int.parse(parts[1]) + 1;
+  final maxPoint = isF32 ? 13 : 16;
+  final minPoint = isF32 ? -6 : -5;
+  if (digits.length <= point && point <= maxPoint) {
+    return '$sign$digits${'0' * (point - digits.length)}.0';
+  }
+  if (0 < point && point <= maxPoint) {
+    return '$sign${digits.substring(0, point)}.${digits.substring(point)}';
+  }
+  if (minPoint < point && point <= 0) {
+    return '${sign}0.${'0' * -point}$digits';
+  }
+  final shortMantissa =
+      digits.length == 1 ? digits : '${digits[0]}.${digits.substring(1)}';
+  return '$sign${shortMantissa}e${point - 1}';
+}
@@ -0,0 +1,134 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'json.dart';
+
+/// A JSON value: `null`, a `bool`, a `JsonNumber`, a `String`, a `JsonKey`, a
+/// `List<JsonValue>`, or a `JsonObject`. Numbers are kept as written so that integers of any
+/// size are read exactly.
+typedef JsonValue = Object?;
+
+@immutable
+class JsonNumber {
+  const JsonNumber(this.literal);
+
+  final String literal;
+
+  @override
+  String toString() => literal;
+}
+
+/// The key of an entry of a JSON object read as a map. Like `serde_json`, keys may stand for
+/// strings, numbers, booleans, or unit variants of enums.
+@immutable
+class JsonKey {
+  const JsonKey(this.value);
+
+  final String value;
+
+  @override
+  String toString() => Json.stringify(value);
+}
+
+/// The entries of a JSON object, in order and possibly with duplicate keys.
+class JsonObject {
+  List<String> keys = [];
+  List<JsonValue> values = [];
+
+  int get length => keys.length;
+
+  void put(String key, JsonValue value) {
+    keys.add(key);
+    values.add(value);
+  }
+
+  /// Value of the field `name` of a struct.
+  JsonValue field(String name) {
+    final index = _find(name);
+    if (index < 0) {
+      throw Exception('Missing field: $name');
+    }
+    return values[index];
+  }
+
+  /// Value of the optional field `name` of a struct, or `null` if the field is missing.
+  JsonValue optionalField(String name) {
+    final index = _find(name);
+    return index < 0 ? null : values[index];
+  }
+
+  int _find(String name) {
+    final index = keys.indexOf(name);
+    if (index >= 0 && keys.lastIndexOf(name) != index) {
+      throw Exception('Duplicate field: $name');
+    }
+    return index;
+  }
+
+  /// Sort the entries of an object written from a map, so that keys come in the order of a
+  /// `BTreeMap` in Rust.
+  void sortKeys(int Function(String, String) compare) {
+    final indices = List<int>.generate(keys.length, (i) => i);
+    indices.sort((i, j) => compare(keys[i], keys[j]));
+    keys = indices.map((i) => keys[i]).toList();
+    values = indices.map((i) => values[i]).toList();
+  }
+
+  /// Order of strings and chars (by UTF-8 bytes) and of booleans (`false` first).
+  static int stringOrder(String a, String b) {
+    final x = utf8.encode(a);
+    final y = utf8.encode(b);
+    final length = x.length < y.length ? x.length : y.length;
+    for (var i = 0; i < length; i++) {
+      final result = x[i].compareTo(y[i]);
+      if (result != 0) return result;
+    }
+    return x.length.compareTo(y.length);
+  }
+
+  /// Order of numbers.
+  static int numberOrder(String a, String b) {
+    if (_integer.hasMatch(a) && _integer.hasMatch(b)) {
+      return BigInt.parse(a).compareTo(BigInt.parse(b));
+    }
+    return double.parse(a).compareTo(double.parse(b));
+  }
+
+  /// Order of the unit variants of an enum, given the names of all its variants.
+  static int Function(String, String) variantOrder(List<String> names) {
+    int index(String name) {
+      final result = names.indexOf(name);
+      return result < 0 ? names.length : result;
+    }
+
+    return (a, b) => index(a).compareTo(index(b));
+  }
+
+  @override
+  String toString() => Json.stringify(this);
+}
+
+/// A variant of an enum, read either from a string (unit variants) or from an object with a
+/// single entry.
+class JsonVariant {
+  const JsonVariant(this.name, this._content, this._hasContent);
+
+  final String name;
+  final JsonValue _content;
+  final bool _hasContent;
+
+  /// Check that the variant has no content, i.e. that it was written as a string or with `null`.
+  void unit() {
+    if (_content != null) {
+      throw Exception('Unexpected content for unit variant: $name');
+    }
+  }
+
+  /// Content of a variant that is not a unit variant.
+  JsonValue value() {
+    if (!_hasContent) {
+      throw Exception('Missing content for variant: $name');
+    }
+    return _content;
+  }
+}
@@ -0,0 +1,11 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+library postcard;
+
+import 'dart:convert';
+import 'dart:typed_data';
+import '../serde/serde.dart';
+
+part 'postcard_deserializer.dart';
+part 'postcard_serializer.dart';
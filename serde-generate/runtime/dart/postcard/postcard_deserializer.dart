@@ -0,0 +1,105 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'postcard.dart';
+
+// Maximum number of nested structs and enum variants.
+const maxContainerDepth = (1 << 31) - 1;
+
+class PostcardDeserializer extends BinaryDeserializer {
+  PostcardDeserializer(Uint8List input)
+      : super(input: input, containerDepthBudget: maxContainerDepth);
+
+  BigInt deserializeVarint(int bits) {
+    var value = BigInt.zero;
+    for (var shift = 0; shift < bits; shift += 7) {
+      final x = deserializeUint8();
+      final digit = x & 0x7f;
+      if (bits - shift < 7 && (digit >> (bits - shift)) != 0) {
+        throw Exception('Overflow while parsing varint-encoded value');
+      }
+      value = value | (BigInt.from(digit) << shift);
+      if ((x & 0x80) == 0) {
+        return value;
+      }
+    }
+    throw Exception('Varint-encoded value is too long');
+  }
+
+  BigInt deserializeZigzag(int bits) {
+    final value = deserializeVarint(bits);
+    if (value.isEven) {
+      return value >> 1;
+    } else {
+      return -(value >> 1) - BigInt.one;
+    }
+  }
+
+  @override
+  int deserializeUint16() {
+    return deserializeVarint(16).toInt();
+  }
+
+  @override
+  int deserializeUint32() {
+    return deserializeVarint(32).toInt();
+  }
+
+  @override
+  Uint64 deserializeUint64() {
+    return Uint64(deserializeVarint(64));
+  }
+
+  @override
+  Uint128 deserializeUint128() {
+    return Uint128.fromBigInt(deserializeVarint(128));
+  }
+
+  @override
+  int deserializeInt16() {
+    return deserializeZigzag(16).toInt();
+  }
+
+  @override
+  int deserializeInt32() {
+    return deserializeZigzag(32).toInt();
+  }
+
+  @override
+  int deserializeInt64() {
+    return deserializeZigzag(64).toInt();
+  }
+
+  @override
+  Int128 deserializeInt128() {
+    return Int128.fromBigInt(deserializeZigzag(128));
+  }
+
+  @override
+  int deserializeChar() {
+    final runes = deserializeString().runes;
+    if (runes.length != 1) {
+      throw Exception('Expecting a single character');
+    }
+    return runes.first;
+  }
+
+  @override
+  int deserializeLength() {
+    final value = deserializeVarint(64);
+    if (value > BigInt.from(maxInt)) {
+      throw Exception('The length of an array cannot exceed MAXINT');
+    }
+    return value.toInt();
+  }
+
+  @override
+  int deserializeVariantIndex() {
+    return deserializeVarint(32).toInt();
+  }
+
+  @override
+  void checkThatKeySlicesAreIncreasing(Slice key1, Slice key2) {
+    // Not required by the format.
+  }
+}
@@ -0,0 +1,105 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'postcard.dart';
+
+class PostcardSerializer extends BinarySerializer {
+  PostcardSerializer()
+      : super(
+          containerDepthBudget: maxContainerDepth,
+        );
+
+  void serializeVarint(BigInt value) {
+    final mask = BigInt.from(0x7f);
+    while (value > mask) {
+      output.add((value & mask).toInt() | 0x80);
+      value = value >> 7;
+    }
+    output.add(value.toInt());
+  }
+
+  // Signed integers use a "zigzag" encoding before the varint encoding.
+  void serializeZigzag(BigInt value) {
+    if (value.isNegative) {
+      serializeVarint(-(value << 1) - BigInt.one);
+    } else {
+      serializeVarint(value << 1);
+    }
+  }
+
+  @override
+  void serializeUint16(int val) {
+    if (val.bitLength > 16) {
+      throw Exception(
+          'The integer literal $val can\'t be represented in 16 bits.');
+    }
+    serializeVarint(BigInt.from(val));
+  }
+
+  @override
+  void serializeUint32(int val) {
+    if (val.bitLength > 32) {
+      throw Exception(
+          'The integer literal $val can\'t be represented in 32 bits.');
+    }
+    serializeVarint(BigInt.from(val));
+  }
+
+  @override
+  void serializeUint64(Uint64 val) {
+    serializeVarint(val.toBigInt());
+  }
+
+  @override
+  void serializeUint128(Uint128 value) {
+    serializeVarint(value.toBigInt());
+  }
+
+  @override
+  void serializeInt16(int value) {
+    if (value.bitLength > 15) {
+      throw Exception(
+          'The integer literal $value can\'t be represented in 16 bits.');
+    }
+    serializeZigzag(BigInt.from(value));
+  }
+
+  @override
+  void serializeInt32(int value) {
+    if (value.bitLength > 31) {
+      throw Exception(
+          'The integer literal $value can\'t be represented in 32 bits.');
+    }
+    serializeZigzag(BigInt.from(value));
+  }
+
+  @override
+  void serializeInt64(int value) {
+    serializeZigzag(BigInt.from(value));
+  }
+
+  @override
+  void serializeInt128(Int128 value) {
+    serializeZigzag(value.toBigInt());
+  }
+
+  @override
+  void serializeChar(int value) {
+    serializeString(String.fromCharCode(value));
+  }
+
+  @override
+  void serializeLength(int value) {
+    serializeVarint(BigInt.from(value));
+  }
+
+  @override
+  void serializeVariantIndex(int value) {
+    serializeVarint(BigInt.from(value));
+  }
+
+  @override
+  void sortMapEntries(List<int> offsets) {
+    // Not required by the format.
+  }
+}
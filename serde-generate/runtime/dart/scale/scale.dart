@@ -0,0 +1,10 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+library scale;
+
+import 'dart:typed_data';
+import '../serde/serde.dart';
+
+part 'scale_deserializer.dart';
+part 'scale_serializer.dart';
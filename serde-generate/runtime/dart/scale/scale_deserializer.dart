@@ -0,0 +1,74 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'scale.dart';
+
+// Maximum length allowed for sequences (vectors, bytes, strings) and maps.
+const maxSequenceLength = (1 << 31) - 1;
+
+// Maximum number of nested structs and enum variants.
+const maxContainerDepth = (1 << 31) - 1;
+
+class ScaleDeserializer extends BinaryDeserializer {
+  ScaleDeserializer(Uint8List input)
+      : super(
+          input: input,
+          containerDepthBudget: maxContainerDepth,
+        );
+
+  @override
+  int deserializeChar() {
+    throw Exception('Characters are not supported by SCALE');
+  }
+
+  int deserializeCompactUint32() {
+    final prefix = deserializeUint8();
+    final int value;
+    final int minimum;
+    switch (prefix & 3) {
+      case 0:
+        return prefix >> 2;
+      case 1:
+        value = (prefix | (deserializeUint8() << 8)) >> 2;
+        minimum = 1 << 6;
+        break;
+      case 2:
+        value = (prefix |
+                (deserializeUint8() << 8) |
+                (deserializeUint8() << 16) |
+                (deserializeUint8() << 24)) >>
+            2;
+        minimum = 1 << 14;
+        break;
+      default:
+        if (prefix != 3) {
+          throw Exception('compact integer does not fit in 32 bits');
+        }
+        value = deserializeUint32();
+        minimum = 1 << 30;
+    }
+    if (value < minimum) {
+      throw Exception('compact integer is not canonically encoded');
+    }
+    return value;
+  }
+
+  @override
+  int deserializeLength() {
+    final length = deserializeCompactUint32();
+    if (length > maxSequenceLength) {
+      throw Exception("length is too large");
+    }
+    return length;
+  }
+
+  @override
+  int deserializeVariantIndex() {
+    return deserializeUint8();
+  }
+
+  @override
+  void checkThatKeySlicesAreIncreasing(Slice key1, Slice key2) {
+    // Not required by the format.
+  }
+}
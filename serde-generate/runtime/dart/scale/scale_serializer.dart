@@ -0,0 +1,50 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+part of 'scale.dart';
+
+class ScaleSerializer extends BinarySerializer {
+  ScaleSerializer()
+      : super(
+          containerDepthBudget: maxContainerDepth,
+        );
+
+  @override
+  void serializeChar(int value) {
+    throw Exception('Characters are not supported by SCALE');
+  }
+
+  void serializeCompactUint32(int value) {
+    if (value < (1 << 6)) {
+      serializeUint8(value << 2);
+    } else if (value < (1 << 14)) {
+      serializeUint16((value << 2) | 1);
+    } else if (value < (1 << 30)) {
+      serializeUint32((value << 2) | 2);
+    } else {
+      serializeUint8(3);
+      serializeUint32(value);
+    }
+  }
+
+  @override
+  void serializeLength(int value) {
+    if (value < 0 || value > maxSequenceLength) {
+      throw Exception('length is too large');
+    }
+    serializeCompactUint32(value);
+  }
+
+  @override
+  void serializeVariantIndex(int value) {
+    if (value < 0 || value > 0xFF) {
+      throw Exception('variant index is too large');
+    }
+    serializeUint8(value);
+  }
+
+  @override
+  void sortMapEntries(List<int> offsets) {
+    // Not required by the format.
+  }
+}
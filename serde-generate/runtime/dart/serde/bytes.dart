@@ -4,9 +4,9 @@
 part of 'serde.dart';
 
 /// Immutable wrapper class around byte[].
-/// Enforces value-semantice for `equals` and `hashCode`.
+/// Enforces value-semantice for `equals` and `hashCode`. Bytes are ordered lexicographically.
 @immutable
-class Bytes {
+class Bytes implements Comparable<Bytes> {
   const Bytes(this.content);
 
   final Uint8List content;
@@ -21,4 +21,16 @@ class Bytes {
 
   @override
   int get hashCode => Object.hashAll(content);
+
+  @override
+  int compareTo(Bytes other) {
+    final length = content.length < other.content.length
+        ? content.length
+        : other.content.length;
+    for (var i = 0; i < length; i++) {
+      final result = content[i].compareTo(other.content[i]);
+      if (result != 0) return result;
+    }
+    return content.length.compareTo(other.content.length);
+  }
 }
@@ -27,6 +27,22 @@ bool mapEquals<T, U>(Map<T, U>? a, Map<T, U>? b) {
   return true;
 }
 
+/// Compare booleans the way Rust does (`false < true`).
+int compareBools(bool a, bool b) => a == b ? 0 : (a ? 1 : -1);
+
+/// Compare strings by Unicode code points, i.e. by their UTF-8 bytes like Rust does.
+int compareStrings(String a, String b) {
+  final x = a.runes.iterator;
+  final y = b.runes.iterator;
+  while (true) {
+    final hasX = x.moveNext();
+    final hasY = y.moveNext();
+    if (!hasX || !hasY) return hasX == hasY ? 0 : (hasX ? 1 : -1);
+    final result = x.current.compareTo(y.current);
+    if (result != 0) return result;
+  }
+}
+
 bool _elementEquals<T>(T? a, T? b) {
   if (a is List && b is List) {
     return listEquals(a, b);
@@ -4,7 +4,7 @@
 part of 'serde.dart';
 
 @immutable
-class Int128 {
+class Int128 implements Comparable<Int128> {
   const Int128(this.high, this.low);
 
   factory Int128.parse(String num, {int? radix}) {
@@ -37,6 +37,9 @@ class Int128 {
         low,
       );
 
+  @override
+  int compareTo(Int128 other) => toBigInt().compareTo(other.toBigInt());
+
   @override
   String toString() {
     return toBigInt().toString();
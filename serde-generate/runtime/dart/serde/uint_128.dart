@@ -5,7 +5,7 @@ part of 'serde.dart';
 
 /// A Dart type to represent the Rust u128 type.
 @immutable
-class Uint128 {
+class Uint128 implements Comparable<Uint128> {
   const Uint128(this.high, this.low);
 
   factory Uint128.parse(String num, {int? radix}) {
@@ -36,6 +36,9 @@ class Uint128 {
         low,
       );
 
+  @override
+  int compareTo(Uint128 other) => toBigInt().compareTo(other.toBigInt());
+
   @override
   String toString() {
     return toBigInt().toString();
@@ -5,7 +5,7 @@ part of 'serde.dart';
 
 /// A Dart type to represent the Rust u64 type.
 @immutable
-class Uint64 {
+class Uint64 implements Comparable<Uint64> {
   const Uint64(this._high);
 
   factory Uint64.parse(String num, {int? radix}) {
@@ -29,6 +29,9 @@ class Uint64 {
   @override
   int get hashCode => _high.hashCode;
 
+  @override
+  int compareTo(Uint64 other) => _high.compareTo(other._high);
+
   @override
   String toString() {
     return toBigInt().toString();
@@ -0,0 +1,61 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+import 'dart:typed_data';
+import 'package:test/test.dart';
+import '../borsh/borsh.dart';
+import '../serde/serde.dart';
+
+void main() {
+  test('serializeLength', () {
+    BorshSerializer serializer = BorshSerializer();
+    serializer.serializeLength(258);
+    expect(serializer.bytes, Uint8List.fromList([0x02, 0x01, 0x00, 0x00]));
+  });
+
+  test('deserializeLength', () {
+    BorshDeserializer deserializer =
+        BorshDeserializer(Uint8List.fromList([0x02, 0x01, 0x00, 0x00]));
+    expect(deserializer.deserializeLength(), 258);
+  });
+
+  test('serializeVariantIndex', () {
+    BorshSerializer serializer = BorshSerializer();
+    serializer.serializeVariantIndex(255);
+    expect(serializer.bytes, Uint8List.fromList([0xff]));
+    expect(() => serializer.serializeVariantIndex(256), throwsException);
+  });
+
+  test('rejectNaN', () {
+    BorshSerializer serializer = BorshSerializer();
+    expect(() => serializer.serializeFloat64(double.nan), throwsException);
+    BorshDeserializer deserializer =
+        BorshDeserializer(Uint8List.fromList([0x00, 0x00, 0xc0, 0x7f]));
+    expect(() => deserializer.deserializeFloat32(), throwsException);
+  })
+
+  test('sortMapEntriesInRustKeyOrder', () {
+    BorshSerializer serializer = BorshSerializer();
+    final offsets = [serializer.offset];
+    serializer.serializeString('b');
+    serializer.serializeInt16(-1);
+    offsets.add(serializer.offset);
+    serializer.serializeString('ab');
+    serializer.serializeInt16(256);
+    serializer.sortMapEntries(offsets);
+    expect(
+        serializer.bytes,
+        Uint8List.fromList(
+            [2, 0, 0, 0, 97, 98, 0, 1, 1, 0, 0, 0, 98, 0xff, 0xff]));
+
+    BorshDeserializer deserializer = BorshDeserializer(
+        Uint8List.fromList([1, 0, 0, 0, 98, 2, 0, 0, 0, 97, 98]));
+    deserializer.deserializeString();
+    deserializer.deserializeString();
+    expect(
+        () => deserializer.checkThatKeySlicesAreIncreasing(
+            Slice(0, 5), Slice(5, 11)),
+        throwsException);
+    deserializer.checkThatKeySlicesAreIncreasing(Slice(5, 11), Slice(0, 5));
+  });
+}
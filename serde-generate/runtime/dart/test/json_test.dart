@@ -0,0 +1,76 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+import 'package:test/test.dart';
+import '../json/json.dart';
+import '../serde/serde.dart';
+
+void main() {
+  test('parse and stringify', () {
+    final value =
+        Json.parse(' {"a" : [1, -2.5e3, true, null], "b": "é😀\\n"} ');
+    expect(Json.stringify(value), '{"a":[1,-2.5e3,true,null],"b":"é😀\\n"}');
+  });
+
+  test('parse errors', () {
+    for (final input in [
+      '',
+      '01',
+      '[1,]',
+      '{"a":1,}',
+      '"\\ud83d"',
+      '"\\x"',
+      'nul',
+      '1 2',
+      '"\t"',
+    ]) {
+      expect(() => Json.parse(input), throwsException, reason: input);
+    }
+    Json.parse('[' * 127 + ']' * 127);
+    expect(() => Json.parse('[' * 128 + ']' * 128), throwsException);
+  });
+
+  test('floats', () {
+    String f64(double value) => Json.stringify(Json.serializeFloat64(value));
+    String f32(double value) => Json.stringify(Json.serializeFloat32(value));
+    expect(f64(0.0), '0.0');
+    expect(f64(-0.0), '-0.0');
+    expect(f64(1e16), '1e16');
+    expect(f64(1234567890123456.0), '1234567890123456.0');
+    expect(f64(1e-4), '0.0001');
+    expect(f64(1e-6), '1e-6');
+    expect(f32(Json.deserializeFloat32(JsonNumber('0.1'))), '0.1');
+    expect(f32(1e13), '1e13');
+    expect(f32(Json.deserializeFloat32(JsonNumber('1e-6'))), '0.000001');
+    expect(f32(Json.deserializeFloat32(JsonNumber('1e-7'))), '1e-7');
+    expect(f64(double.infinity), 'null');
+    expect(Json.deserializeFloat32(JsonNumber('1e39')), double.infinity);
+    expect(() => Json.deserializeFloat64(JsonNumber('1e309')), throwsException);
+  });
+
+  test('integers', () {
+    final min = '-170141183460469231731687303715884105728';
+    expect(Json.stringify(Json.serializeInt128(Json.deserializeInt128(JsonNumber(min)))),
+        min);
+    expect(() => Json.deserializeUint8(JsonNumber('256')), throwsException);
+    expect(() => Json.deserializeInt64(JsonNumber('-0')), throwsException);
+    expect(() => Json.deserializeInt32(JsonNumber('1.0')), throwsException);
+    expect(Json.deserializeUint64(JsonKey('18446744073709551615')),
+        Uint64.parse('18446744073709551615'));
+  });
+
+  test('sortKeys', () {
+    List<String> sorted(int Function(String, String) order) {
+      final object = JsonObject();
+      for (final key in ['10', '-3', '2']) {
+        object.put(key, null);
+      }
+      object.sortKeys(order);
+      return object.keys;
+    }
+
+    expect(sorted(JsonObject.numberOrder), ['-3', '2', '10']);
+    expect(sorted(JsonObject.stringOrder), ['-3', '10', '2']);
+    expect(sorted(JsonObject.variantOrder(['2', '10', '-3'])), ['2', '10', '-3']);
+  });
+}
@@ -0,0 +1,41 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+import 'dart:typed_data';
+import 'package:test/test.dart';
+import '../postcard/postcard.dart';
+
+void main() {
+  test('serializeUint32', () {
+    PostcardSerializer serializer = PostcardSerializer();
+    serializer.serializeUint32(300);
+    expect(serializer.bytes, Uint8List.fromList([0xac, 0x02]));
+  });
+
+  test('deserializeUint32', () {
+    PostcardDeserializer deserializer =
+        PostcardDeserializer(Uint8List.fromList([0xac, 0x02]));
+    int result = deserializer.deserializeUint32();
+    expect(result, 300);
+  });
+
+  test('serializeInt64', () {
+    PostcardSerializer serializer = PostcardSerializer();
+    serializer.serializeInt64(-1);
+    serializer.serializeInt64(64);
+    expect(serializer.bytes, Uint8List.fromList([0x01, 0x80, 0x01]));
+  });
+
+  test('deserializeInt64', () {
+    PostcardDeserializer deserializer =
+        PostcardDeserializer(Uint8List.fromList([0x01, 0x80, 0x01]));
+    expect(deserializer.deserializeInt64(), -1);
+    expect(deserializer.deserializeInt64(), 64);
+  });
+
+  test('overflow', () {
+    PostcardDeserializer deserializer =
+        PostcardDeserializer(Uint8List.fromList([0xff, 0xff, 0x04]));
+    expect(() => deserializer.deserializeUint16(), throwsException);
+  });
+}
@@ -0,0 +1,46 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+import 'dart:typed_data';
+import 'package:test/test.dart';
+import '../scale/scale.dart';
+
+void main() {
+  test('serializeLength', () {
+    for (final (length, bytes) in [
+      (63, [0xfc]),
+      (64, [0x01, 0x01]),
+      (16384, [0x02, 0x00, 0x01, 0x00]),
+      (1 << 30, [0x03, 0x00, 0x00, 0x00, 0x40]),
+    ]) {
+      ScaleSerializer serializer = ScaleSerializer();
+      serializer.serializeLength(length);
+      expect(serializer.bytes, Uint8List.fromList(bytes));
+      ScaleDeserializer deserializer =
+          ScaleDeserializer(Uint8List.fromList(bytes));
+      expect(deserializer.deserializeLength(), length);
+    }
+  });
+
+  test('rejectNonCanonicalLength', () {
+    ScaleDeserializer deserializer =
+        ScaleDeserializer(Uint8List.fromList([0x01, 0x00]));
+    expect(() => deserializer.deserializeLength(), throwsException);
+    deserializer = ScaleDeserializer(
+        Uint8List.fromList([0x07, 0x00, 0x00, 0x00, 0x00, 0x01]));
+    expect(() => deserializer.deserializeLength(), throwsException);
+  });
+
+  test('serializeVariantIndex', () {
+    ScaleSerializer serializer = ScaleSerializer();
+    serializer.serializeVariantIndex(255);
+    expect(serializer.bytes, Uint8List.fromList([0xff]));
+    expect(() => serializer.serializeVariantIndex(256), throwsException);
+  });
+
+  test('acceptNaN', () {
+    ScaleDeserializer deserializer =
+        ScaleDeserializer(Uint8List.fromList([0x00, 0x00, 0xc0, 0x7f]));
+    expect(deserializer.deserializeFloat32().isNaN, true);
+  });
+}
@@ -0,0 +1,128 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+defmodule Bcs do
+  @moduledoc """
+  Runtime of the BCS encoding.
+  """
+
+  @behaviour Serde.Encoding
+
+  import Bitwise
+
+  alias Serde.{DeserializationError, SerializationError}
+
+  @max_length (1 <<< 31) - 1
+  @max_container_depth 500
+
+  def max_length, do: @max_length
+
+  @impl true
+  def max_container_depth, do: @max_container_depth
+
+  @impl true
+  def canonical_maps?, do: true
+
+  @doc """
+  Serialize `value` with the function `serialize` of a generated module.
+  """
+  def serialize(value, serialize), do: Serde.serialize(value, __MODULE__, serialize)
+
+  @doc """
+  Deserialize `input` with the function `deserialize` of a generated module.
+  """
+  def deserialize(input, deserialize), do: Serde.deserialize(input, __MODULE__, deserialize)
+
+  @impl true
+  def serialize_integer(value, bits, :unsigned, _options),
+    do: <<value::little-unsigned-size(bits)>>
+
+  def serialize_integer(value, bits, :signed, _options), do: <<value::little-signed-size(bits)>>
+
+  @impl true
+  def serialize_len(value, _options)
+      when is_integer(value) and value >= 0 and value <= @max_length,
+      do: serialize_u32_as_uleb128(value)
+
+  def serialize_len(_value, _options), do: raise(SerializationError, "Incorrect length value")
+
+  @impl true
+  def serialize_variant_index(value, _options), do: serialize_u32_as_uleb128(value)
+
+  @impl true
+  def serialize_f32(_value, _options),
+    do: raise(SerializationError, "Not implemented: serialize_f32")
+
+  @impl true
+  def serialize_f64(_value, _options),
+    do: raise(SerializationError, "Not implemented: serialize_f64")
+
+  @impl true
+  def deserialize_integer(input, bits, :unsigned, _options) do
+    case input do
+      <<value::little-unsigned-size(bits), rest::binary>> -> {value, rest}
+      _ -> Serde.Deserializer.input_not_large_enough()
+    end
+  end
+
+  def deserialize_integer(input, bits, :signed, _options) do
+    case input do
+      <<value::little-signed-size(bits), rest::binary>> -> {value, rest}
+      _ -> Serde.Deserializer.input_not_large_enough()
+    end
+  end
+
+  @impl true
+  def deserialize_len(input, _options) do
+    case deserialize_uleb128_as_u32(input) do
+      {value, _rest} when value > @max_length ->
+        raise DeserializationError, "Incorrect length value"
+
+      result ->
+        result
+    end
+  end
+
+  @impl true
+  def deserialize_variant_index(input, _options), do: deserialize_uleb128_as_u32(input)
+
+  @impl true
+  def deserialize_f32(_input, _options),
+    do: raise(DeserializationError, "Not implemented: deserialize_f32")
+
+  @impl true
+  def deserialize_f64(_input, _options),
+    do: raise(DeserializationError, "Not implemented: deserialize_f64")
+
+  defp serialize_u32_as_uleb128(value) when value >= 0x80,
+    do: [((value &&& 0x7F) ||| 0x80) | serialize_u32_as_uleb128(value >>> 7)]
+
+  defp serialize_u32_as_uleb128(value), do: [value]
+
+  defp deserialize_uleb128_as_u32(input), do: read_uleb128_digits(input, 0, 0)
+
+  defp read_uleb128_digits(_input, shift, _value) when shift > 28,
+    do: raise(DeserializationError, "Overflow while parsing uleb128-encoded uint32 value")
+
+  defp read_uleb128_digits(<<byte, rest::binary>>, shift, value) do
+    digit = byte &&& 0x7F
+    value = value ||| (digit <<< shift)
+
+    cond do
+      value > 0xFFFFFFFF ->
+        raise DeserializationError, "Overflow while parsing uleb128-encoded uint32 value"
+
+      digit != byte ->
+        read_uleb128_digits(rest, shift + 7, value)
+
+      shift > 0 and digit == 0 ->
+        raise DeserializationError, "Invalid uleb128 number (unexpected zero digit)"
+
+      true ->
+        {value, rest}
+    end
+  end
+
+  defp read_uleb128_digits(_input, _shift, _value),
+    do: Serde.Deserializer.input_not_large_enough()
+end
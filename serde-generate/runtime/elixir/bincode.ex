@@ -0,0 +1,191 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+defmodule Bincode do
+  @moduledoc """
+  Runtime of the Bincode encoding. The default options are those of `bincode::serialize`.
+
+  The options `varint_encoding: true` and `big_endian: true` correspond to the options
+  `with_varint_encoding` and `with_big_endian` of the Rust crate. The options `:limit` and
+  `allow_trailing_bytes: true` are handled by `Serde.serialize/4` and `Serde.deserialize/4`.
+  """
+
+  @behaviour Serde.Encoding
+
+  import Bitwise
+
+  alias Serde.{DeserializationError, SerializationError}
+
+  @max_length (1 <<< 31) - 1
+
+  def max_length, do: @max_length
+
+  @impl true
+  def max_container_depth, do: :infinity
+
+  @impl true
+  def canonical_maps?, do: false
+
+  @doc """
+  Serialize `value` with the function `serialize` of a generated module.
+  """
+  def serialize(value, serialize, options \\ []),
+    do: Serde.serialize(value, __MODULE__, serialize, options)
+
+  @doc """
+  Deserialize `input` with the function `deserialize` of a generated module.
+  """
+  def deserialize(input, deserialize, options \\ []),
+    do: Serde.deserialize(input, __MODULE__, deserialize, options)
+
+  @impl true
+  def serialize_integer(value, bits, signedness, options) do
+    if bits > 8 and varint_encoding?(options) do
+      # Signed integers use the "zigzag" encoding.
+      value =
+        cond do
+          signedness == :unsigned -> value
+          value >= 0 -> value <<< 1
+          true -> (-value <<< 1) - 1
+        end
+
+      write_varint(value, options)
+    else
+      write_fixint(value, bits, signedness, options)
+    end
+  end
+
+  @impl true
+  def serialize_len(value, options)
+      when is_integer(value) and value >= 0 and value <= @max_length,
+      do: serialize_integer(value, 64, :unsigned, options)
+
+  def serialize_len(_value, _options), do: raise(SerializationError, "Incorrect length value")
+
+  @impl true
+  def serialize_variant_index(value, options),
+    do: serialize_integer(value, 32, :unsigned, options)
+
+  @impl true
+  def serialize_f32(value, options) do
+    if big_endian?(options), do: <<value::big-float-32>>, else: <<value::little-float-32>>
+  end
+
+  @impl true
+  def serialize_f64(value, options) do
+    if big_endian?(options), do: <<value::big-float-64>>, else: <<value::little-float-64>>
+  end
+
+  @impl true
+  def deserialize_integer(input, bits, signedness, options) do
+    if bits > 8 and varint_encoding?(options) do
+      {value, rest} = read_varint(input, bits, options)
+      # Undo the "zigzag" encoding.
+      value = if signedness == :signed, do: bxor(value >>> 1, -(value &&& 1)), else: value
+      {value, rest}
+    else
+      read_fixint(input, bits, signedness, options)
+    end
+  end
+
+  @impl true
+  def deserialize_len(input, options) do
+    case deserialize_integer(input, 64, :unsigned, options) do
+      {value, _rest} when value > @max_length ->
+        raise DeserializationError, "Incorrect length value"
+
+      result ->
+        result
+    end
+  end
+
+  @impl true
+  def deserialize_variant_index(input, options),
+    do: deserialize_integer(input, 32, :unsigned, options)
+
+  # Floats that are not finite do not match float segments and are rejected.
+  @impl true
+  def deserialize_f32(input, options) do
+    case {big_endian?(options), input} do
+      {false, <<value::little-float-32, rest::binary>>} -> {value, rest}
+      {true, <<value::big-float-32, rest::binary>>} -> {value, rest}
+      {_, <<_::binary-size(4), _::binary>>} -> non_finite_float()
+      _ -> Serde.Deserializer.input_not_large_enough()
+    end
+  end
+
+  @impl true
+  def deserialize_f64(input, options) do
+    case {big_endian?(options), input} do
+      {false, <<value::little-float-64, rest::binary>>} -> {value, rest}
+      {true, <<value::big-float-64, rest::binary>>} -> {value, rest}
+      {_, <<_::binary-size(8), _::binary>>} -> non_finite_float()
+      _ -> Serde.Deserializer.input_not_large_enough()
+    end
+  end
+
+  defp varint_encoding?(options), do: Keyword.get(options, :varint_encoding, false)
+
+  defp big_endian?(options), do: Keyword.get(options, :big_endian, false)
+
+  # The two's complement of `value` on `bits` bits in the configured order.
+  defp write_fixint(value, bits, :unsigned, options) do
+    if big_endian?(options),
+      do: <<value::big-unsigned-size(bits)>>,
+      else: <<value::little-unsigned-size(bits)>>
+  end
+
+  defp write_fixint(value, bits, :signed, options) do
+    if big_endian?(options),
+      do: <<value::big-signed-size(bits)>>,
+      else: <<value::little-signed-size(bits)>>
+  end
+
+  defp write_varint(value, _options) when value <= 250, do: <<value>>
+
+  defp write_varint(value, options) do
+    {tag, bits} =
+      cond do
+        value < 1 <<< 16 -> {251, 16}
+        value < 1 <<< 32 -> {252, 32}
+        value < 1 <<< 64 -> {253, 64}
+        true -> {254, 128}
+      end
+
+    [tag | write_fixint(value, bits, :unsigned, options)]
+  end
+
+  defp read_fixint(input, bits, signedness, options) do
+    case {big_endian?(options), signedness, input} do
+      {false, :unsigned, <<value::little-unsigned-size(bits), rest::binary>>} -> {value, rest}
+      {false, :signed, <<value::little-signed-size(bits), rest::binary>>} -> {value, rest}
+      {true, :unsigned, <<value::big-unsigned-size(bits), rest::binary>>} -> {value, rest}
+      {true, :signed, <<value::big-signed-size(bits), rest::binary>>} -> {value, rest}
+      _ -> Serde.Deserializer.input_not_large_enough()
+    end
+  end
+
+  # Read a varint-encoded value of at most `bits` bits.
+  defp read_varint(<<tag, rest::binary>>, bits, options) do
+    {value, rest} =
+      case tag do
+        tag when tag <= 250 -> {tag, rest}
+        251 -> read_fixint(rest, 16, :unsigned, options)
+        252 -> read_fixint(rest, 32, :unsigned, options)
+        253 -> read_fixint(rest, 64, :unsigned, options)
+        254 when bits >= 128 -> read_fixint(rest, 128, :unsigned, options)
+        _ -> raise DeserializationError, "Invalid tag for varint-encoded value"
+      end
+
+    if value >= 1 <<< bits do
+      raise DeserializationError, "Overflow while parsing varint-encoded value"
+    end
+
+    {value, rest}
+  end
+
+  defp read_varint(_input, _bits, _options), do: Serde.Deserializer.input_not_large_enough()
+
+  defp non_finite_float,
+    do: raise(DeserializationError, "Not implemented: non-finite float values")
+end
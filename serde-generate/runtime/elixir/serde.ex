@@ -0,0 +1,488 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+defmodule Serde do
+  @moduledoc """
+  Common definitions of the Bincode and BCS runtimes.
+
+  Values are mapped to Elixir as follows: unit values are `nil`, integers of all sizes are
+  integers, floats are floats, strings are UTF-8 binaries, bytes are binaries, options are
+  `nil` or their content, sequences and fixed-size arrays are lists, tuples are tuples, and
+  maps are maps.
+
+  Serializers return iodata and deserializers return the decoded value together with the
+  rest of the input. Errors are raised as `Serde.SerializationError` and
+  `Serde.DeserializationError`, then returned as `{:error, error}` by `serialize/4` and
+  `deserialize/4`.
+
+  Encodings may take options as a keyword list: `:limit` bounds the size of the serialized
+  data and `allow_trailing_bytes: true` accepts unread input after a value.
+
+  The comparison functions `compare/2`, `compare_seq/3` and `compare_map/4` order values
+  as in Rust and return `:lt`, `:eq` or `:gt`.
+  """
+
+  defmodule SerializationError do
+    defexception [:message]
+  end
+
+  defmodule DeserializationError do
+    defexception [:message]
+  end
+
+  defmodule Encoding do
+    @moduledoc """
+    Behaviour of the modules defining an encoding, e.g. `Bcs` and `Bincode`.
+    """
+
+    @callback max_container_depth() :: non_neg_integer() | :infinity
+    @callback canonical_maps?() :: boolean()
+    @callback serialize_integer(integer(), pos_integer(), :signed | :unsigned, keyword()) ::
+                iodata()
+    @callback serialize_len(non_neg_integer(), keyword()) :: iodata()
+    @callback serialize_variant_index(non_neg_integer(), keyword()) :: iodata()
+    @callback serialize_f32(float(), keyword()) :: iodata()
+    @callback serialize_f64(float(), keyword()) :: iodata()
+    @callback deserialize_integer(binary(), pos_integer(), :signed | :unsigned, keyword()) ::
+                {integer(), binary()}
+    @callback deserialize_len(binary(), keyword()) :: {non_neg_integer(), binary()}
+    @callback deserialize_variant_index(binary(), keyword()) :: {non_neg_integer(), binary()}
+    @callback deserialize_f32(binary(), keyword()) :: {float(), binary()}
+    @callback deserialize_f64(binary(), keyword()) :: {float(), binary()}
+  end
+
+  defmodule Serializer do
+    @moduledoc """
+    Serialization of the values of the data model into iodata.
+    """
+
+    @enforce_keys [:encoding, :container_depth_budget]
+    defstruct [:encoding, :container_depth_budget, options: []]
+
+    @type t :: %__MODULE__{
+            encoding: module(),
+            container_depth_budget: non_neg_integer() | :infinity,
+            options: keyword()
+          }
+
+    @spec new(module(), keyword()) :: t()
+    def new(encoding, options \\ []) do
+      %__MODULE__{
+        encoding: encoding,
+        container_depth_budget: encoding.max_container_depth(),
+        options: options
+      }
+    end
+
+    @doc """
+    The serializer to use for the content of a container, after checking the container depth.
+    """
+    @spec increase_container_depth(t()) :: t()
+    def increase_container_depth(%__MODULE__{container_depth_budget: :infinity} = serializer),
+      do: serializer
+
+    def increase_container_depth(%__MODULE__{container_depth_budget: 0}),
+      do: raise(SerializationError, "Exceeded maximum container depth")
+
+    def increase_container_depth(%__MODULE__{container_depth_budget: budget} = serializer),
+      do: %{serializer | container_depth_budget: budget - 1}
+
+    def serialize_unit(nil, _serializer), do: []
+
+    def serialize_unit(_value, _serializer),
+      do: raise(SerializationError, "Invalid value for a unit")
+
+    def serialize_bool(false, _serializer), do: <<0>>
+    def serialize_bool(true, _serializer), do: <<1>>
+
+    def serialize_bool(_value, _serializer),
+      do: raise(SerializationError, "Invalid value for a boolean")
+
+    def serialize_u8(value, serializer), do: write_unsigned(value, 8, serializer)
+    def serialize_u16(value, serializer), do: write_unsigned(value, 16, serializer)
+    def serialize_u32(value, serializer), do: write_unsigned(value, 32, serializer)
+    def serialize_u64(value, serializer), do: write_unsigned(value, 64, serializer)
+    def serialize_u128(value, serializer), do: write_unsigned(value, 128, serializer)
+
+    def serialize_i8(value, serializer), do: write_signed(value, 8, serializer)
+    def serialize_i16(value, serializer), do: write_signed(value, 16, serializer)
+    def serialize_i32(value, serializer), do: write_signed(value, 32, serializer)
+    def serialize_i64(value, serializer), do: write_signed(value, 64, serializer)
+    def serialize_i128(value, serializer), do: write_signed(value, 128, serializer)
+
+    def serialize_f32(value, serializer) when is_float(value),
+      do: serializer.encoding.serialize_f32(value, serializer.options)
+
+    def serialize_f32(_value, _serializer),
+      do: raise(SerializationError, "Invalid value for a float")
+
+    def serialize_f64(value, serializer) when is_float(value),
+      do: serializer.encoding.serialize_f64(value, serializer.options)
+
+    def serialize_f64(_value, _serializer),
+      do: raise(SerializationError, "Invalid value for a float")
+
+    def serialize_char(_value, _serializer),
+      do: raise(SerializationError, "Not implemented: serialize_char")
+
+    def serialize_str(value, serializer) when is_binary(value) do
+      if String.valid?(value) do
+        serialize_bytes(value, serializer)
+      else
+        raise SerializationError, "Incorrect UTF8 string"
+      end
+    end
+
+    def serialize_str(_value, _serializer),
+      do: raise(SerializationError, "Invalid value for a string")
+
+    def serialize_bytes(value, serializer) when is_binary(value),
+      do: [serialize_len(byte_size(value), serializer), value]
+
+    def serialize_bytes(_value, _serializer),
+      do: raise(SerializationError, "Invalid value for bytes")
+
+    def serialize_len(value, serializer),
+      do: serializer.encoding.serialize_len(value, serializer.options)
+
+    def serialize_variant_index(value, serializer),
+      do: serializer.encoding.serialize_variant_index(value, serializer.options)
+
+    @doc """
+    Serialize `nil` or a value serialized by `serialize_content`.
+    """
+    def serialize_option(nil, _serializer, _serialize_content), do: <<0>>
+
+    def serialize_option(value, serializer, serialize_content),
+      do: [<<1>>, serialize_content.(value, serializer)]
+
+    def serialize_seq(values, serializer, serialize_item) when is_list(values) do
+      [
+        serialize_len(length(values), serializer)
+        | Enum.map(values, &serialize_item.(&1, serializer))
+      ]
+    end
+
+    def serialize_seq(_values, _serializer, _serialize_item),
+      do: raise(SerializationError, "Invalid value for a sequence")
+
+    @doc """
+    Serialize the entries of a map, sorted by their serialized bytes if the encoding requires
+    canonical maps.
+    """
+    def serialize_map(values, serializer, serialize_key, serialize_value) when is_map(values) do
+      entries =
+        Enum.map(values, fn {key, value} ->
+          IO.iodata_to_binary([
+            serialize_key.(key, serializer),
+            serialize_value.(value, serializer)
+          ])
+        end)
+
+      entries = if serializer.encoding.canonical_maps?(), do: Enum.sort(entries), else: entries
+      [serialize_len(map_size(values), serializer) | entries]
+    end
+
+    def serialize_map(_values, _serializer, _serialize_key, _serialize_value),
+      do: raise(SerializationError, "Invalid value for a map")
+
+    def serialize_array(values, serializer, size, serialize_item)
+        when is_list(values) and length(values) == size,
+        do: Enum.map(values, &serialize_item.(&1, serializer))
+
+    def serialize_array(_values, _serializer, size, _serialize_item),
+      do: raise(SerializationError, "Invalid value for a fixed-size array of length #{size}")
+
+    # Check that `value` fits in an unsigned integer of `bits` bits before encoding it.
+    defp write_unsigned(value, bits, serializer) do
+      if is_integer(value) and value >= 0 and value < Bitwise.bsl(1, bits) do
+        serializer.encoding.serialize_integer(value, bits, :unsigned, serializer.options)
+      else
+        raise SerializationError, "Invalid value for an unsigned #{bits}-bit integer"
+      end
+    end
+
+    # Check that `value` fits in a signed integer of `bits` bits before encoding it.
+    defp write_signed(value, bits, serializer) do
+      bound = Bitwise.bsl(1, bits - 1)
+
+      if is_integer(value) and value >= -bound and value < bound do
+        serializer.encoding.serialize_integer(value, bits, :signed, serializer.options)
+      else
+        raise SerializationError, "Invalid value for a signed #{bits}-bit integer"
+      end
+    end
+  end
+
+  defmodule Deserializer do
+    @moduledoc """
+    Deserialization of the values of the data model by binary pattern matching.
+
+    Each function takes the remaining input and returns `{value, rest}`.
+    """
+
+    @enforce_keys [:encoding, :container_depth_budget]
+    defstruct [:encoding, :container_depth_budget, options: []]
+
+    @type t :: %__MODULE__{
+            encoding: module(),
+            container_depth_budget: non_neg_integer() | :infinity,
+            options: keyword()
+          }
+
+    @spec new(module(), keyword()) :: t()
+    def new(encoding, options \\ []) do
+      %__MODULE__{
+        encoding: encoding,
+        container_depth_budget: encoding.max_container_depth(),
+        options: options
+      }
+    end
+
+    @doc """
+    The deserializer to use for the content of a container, after checking the container depth.
+    """
+    @spec increase_container_depth(t()) :: t()
+    def increase_container_depth(%__MODULE__{container_depth_budget: :infinity} = deserializer),
+      do: deserializer
+
+    def increase_container_depth(%__MODULE__{container_depth_budget: 0}),
+      do: raise(DeserializationError, "Exceeded maximum container depth")
+
+    def increase_container_depth(%__MODULE__{container_depth_budget: budget} = deserializer),
+      do: %{deserializer | container_depth_budget: budget - 1}
+
+    def deserialize_unit(input, _deserializer), do: {nil, input}
+
+    def deserialize_bool(<<0, rest::binary>>, _deserializer), do: {false, rest}
+    def deserialize_bool(<<1, rest::binary>>, _deserializer), do: {true, rest}
+
+    def deserialize_bool(<<_, _::binary>>, _deserializer),
+      do: raise(DeserializationError, "Incorrect boolean value")
+
+    def deserialize_bool(_input, _deserializer), do: input_not_large_enough()
+
+    def deserialize_u8(input, deserializer), do: read_unsigned(input, 8, deserializer)
+    def deserialize_u16(input, deserializer), do: read_unsigned(input, 16, deserializer)
+    def deserialize_u32(input, deserializer), do: read_unsigned(input, 32, deserializer)
+    def deserialize_u64(input, deserializer), do: read_unsigned(input, 64, deserializer)
+    def deserialize_u128(input, deserializer), do: read_unsigned(input, 128, deserializer)
+
+    def deserialize_i8(input, deserializer), do: read_signed(input, 8, deserializer)
+    def deserialize_i16(input, deserializer), do: read_signed(input, 16, deserializer)
+    def deserialize_i32(input, deserializer), do: read_signed(input, 32, deserializer)
+    def deserialize_i64(input, deserializer), do: read_signed(input, 64, deserializer)
+    def deserialize_i128(input, deserializer), do: read_signed(input, 128, deserializer)
+
+    def deserialize_f32(input, deserializer),
+      do: deserializer.encoding.deserialize_f32(input, deserializer.options)
+
+    def deserialize_f64(input, deserializer),
+      do: deserializer.encoding.deserialize_f64(input, deserializer.options)
+
+    def deserialize_char(_input, _deserializer),
+      do: raise(DeserializationError, "Not implemented: deserialize_char")
+
+    def deserialize_str(input, deserializer) do
+      {content, rest} = deserialize_bytes(input, deserializer)
+
+      if String.valid?(content) do
+        {content, rest}
+      else
+        raise DeserializationError, "Incorrect UTF8 string"
+      end
+    end
+
+    def deserialize_bytes(input, deserializer) do
+      {len, rest} = deserialize_len(input, deserializer)
+      read_bytes(rest, len)
+    end
+
+    def deserialize_len(input, deserializer),
+      do: deserializer.encoding.deserialize_len(input, deserializer.options)
+
+    def deserialize_variant_index(input, deserializer),
+      do: deserializer.encoding.deserialize_variant_index(input, deserializer.options)
+
+    def deserialize_option(input, deserializer, deserialize_content) do
+      case deserialize_bool(input, deserializer) do
+        {false, rest} -> {nil, rest}
+        {true, rest} -> deserialize_content.(rest, deserializer)
+      end
+    end
+
+    def deserialize_seq(input, deserializer, deserialize_item) do
+      {len, rest} = deserialize_len(input, deserializer)
+      read_items(rest, deserializer, len, deserialize_item, [])
+    end
+
+    @doc """
+    Deserialize the entries of a map, checking that keys are serialized in strictly
+    increasing order if the encoding requires canonical maps.
+    """
+    def deserialize_map(input, deserializer, deserialize_key, deserialize_value) do
+      {len, rest} = deserialize_len(input, deserializer)
+      canonical? = deserializer.encoding.canonical_maps?()
+      deserializers = {deserialize_key, deserialize_value}
+      read_entries(rest, deserializer, len, deserializers, canonical?, nil, %{})
+    end
+
+    def deserialize_array(input, deserializer, size, deserialize_item),
+      do: read_items(input, deserializer, size, deserialize_item, [])
+
+    @doc """
+    Read `len` bytes, failing before any allocation if the input is too short.
+    """
+    def read_bytes(input, len) do
+      case input do
+        <<content::binary-size(len), rest::binary>> -> {content, rest}
+        _ -> input_not_large_enough()
+      end
+    end
+
+    @spec input_not_large_enough() :: no_return()
+    def input_not_large_enough, do: raise(DeserializationError, "Input is not large enough")
+
+    defp read_unsigned(input, bits, deserializer),
+      do: deserializer.encoding.deserialize_integer(input, bits, :unsigned, deserializer.options)
+
+    defp read_signed(input, bits, deserializer),
+      do: deserializer.encoding.deserialize_integer(input, bits, :signed, deserializer.options)
+
+    defp read_items(input, _deserializer, 0, _deserialize_item, items),
+      do: {Enum.reverse(items), input}
+
+    defp read_items(input, deserializer, count, deserialize_item, items) do
+      {item, rest} = deserialize_item.(input, deserializer)
+      read_items(rest, deserializer, count - 1, deserialize_item, [item | items])
+    end
+
+    defp read_entries(input, _deserializer, 0, _deserializers, _canonical?, _previous, entries),
+      do: {entries, input}
+
+    defp read_entries(input, deserializer, count, deserializers, canonical?, previous, entries) do
+      {deserialize_key, deserialize_value} = deserializers
+      {key, rest} = deserialize_key.(input, deserializer)
+      key_bytes = binary_part(input, 0, byte_size(input) - byte_size(rest))
+
+      if canonical? and previous != nil and previous >= key_bytes do
+        raise DeserializationError,
+              "Error while decoding map: keys are not serialized in the expected order"
+      end
+
+      {value, rest} = deserialize_value.(rest, deserializer)
+      entries = Map.put(entries, key, value)
+      read_entries(rest, deserializer, count - 1, deserializers, canonical?, key_bytes, entries)
+    end
+  end
+
+  @doc """
+  Serialize `value` with the function `serialize` of a generated module and the given
+  encoding.
+  """
+  @spec serialize(term(), module(), (term(), Serializer.t() -> iodata()), keyword()) ::
+          {:ok, binary()} | {:error, Exception.t()}
+  def serialize(value, encoding, serialize, options \\ []) do
+    output = IO.iodata_to_binary(serialize.(value, Serializer.new(encoding, options)))
+
+    case Keyword.get(options, :limit) do
+      limit when is_integer(limit) and byte_size(output) > limit ->
+        raise SerializationError, "Exceeded the size limit of the encoding"
+
+      _ ->
+        {:ok, output}
+    end
+  rescue
+    error in SerializationError -> {:error, error}
+  end
+
+  @doc """
+  Deserialize `input` with the function `deserialize` of a generated module and the given
+  encoding. All the input must be consumed unless the option `allow_trailing_bytes` is set.
+  """
+  @spec deserialize(
+          binary(),
+          module(),
+          (binary(), Deserializer.t() -> {term(), binary()}),
+          keyword()
+        ) :: {:ok, term()} | {:error, Exception.t()}
+  def deserialize(input, encoding, deserialize, options \\ []) when is_binary(input) do
+    case Keyword.get(options, :limit) do
+      # Reading beyond the size limit fails as if the input was truncated.
+      limit when is_integer(limit) and byte_size(input) > limit ->
+        case deserialize_all(binary_part(input, 0, limit), encoding, deserialize, options) do
+          {:error, %DeserializationError{message: "Input is not large enough"}} ->
+            {:error, DeserializationError.exception("Exceeded the size limit of the encoding")}
+
+          result ->
+            result
+        end
+
+      _ ->
+        deserialize_all(input, encoding, deserialize, options)
+    end
+  end
+
+  defp deserialize_all(input, encoding, deserialize, options) do
+    case deserialize.(input, Deserializer.new(encoding, options)) do
+      {value, <<>>} ->
+        {:ok, value}
+
+      {value, _rest} ->
+        if Keyword.get(options, :allow_trailing_bytes, false) do
+          {:ok, value}
+        else
+          {:error, DeserializationError.exception("Some input bytes were not read")}
+        end
+    end
+  rescue
+    error in DeserializationError -> {:error, error}
+  end
+
+  @doc """
+  Compare unit values, booleans, integers, strings or bytes. Binaries are compared byte by
+  byte, which orders UTF-8 strings by code points.
+  """
+  @spec compare(term(), term()) :: :lt | :eq | :gt
+  def compare(lhs, rhs) when lhs < rhs, do: :lt
+  def compare(lhs, rhs) when lhs > rhs, do: :gt
+  def compare(_lhs, _rhs), do: :eq
+
+  @doc """
+  Compare two lists lexicographically with the given comparison of items.
+  """
+  @spec compare_seq(list(), list(), (term(), term() -> :lt | :eq | :gt)) :: :lt | :eq | :gt
+  def compare_seq([], [], _compare_item), do: :eq
+  def compare_seq([], _rhs, _compare_item), do: :lt
+  def compare_seq(_lhs, [], _compare_item), do: :gt
+
+  def compare_seq([lhs | lhs_rest], [rhs | rhs_rest], compare_item) do
+    case compare_item.(lhs, rhs) do
+      :eq -> compare_seq(lhs_rest, rhs_rest, compare_item)
+      result -> result
+    end
+  end
+
+  @doc """
+  Compare two maps as the lists of their entries sorted by key.
+  """
+  @spec compare_map(
+          map(),
+          map(),
+          (term(), term() -> :lt | :eq | :gt),
+          (term(), term() -> :lt | :eq | :gt)
+        ) :: :lt | :eq | :gt
+  def compare_map(lhs, rhs, compare_key, compare_value) do
+    compare_seq(sorted_entries(lhs, compare_key), sorted_entries(rhs, compare_key), fn
+      {lhs_key, lhs_value}, {rhs_key, rhs_value} ->
+        case compare_key.(lhs_key, rhs_key) do
+          :eq -> compare_value.(lhs_value, rhs_value)
+          result -> result
+        end
+    end)
+  end
+
+  defp sorted_entries(map, compare_key) do
+    Enum.sort(Map.to_list(map), fn {lhs, _}, {rhs, _} -> compare_key.(lhs, rhs) != :gt end)
+  end
+end
@@ -0,0 +1,119 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+# Run with `elixir -r serde.ex -r bcs.ex test_bcs.exs`.
+
+ExUnit.start()
+
+defmodule BcsTest do
+  use ExUnit.Case, async: true
+
+  alias Serde.{DeserializationError, Deserializer, SerializationError, Serializer}
+
+  import Bitwise
+
+  defp serialize(fun), do: IO.iodata_to_binary(fun.(Serializer.new(Bcs)))
+
+  defp deserialize(fun, input), do: fun.(input, Deserializer.new(Bcs))
+
+  test "serialize u128" do
+    assert serialize(&Serializer.serialize_u128((1 <<< 128) - 1, &1)) ==
+             :binary.copy(<<0xFF>>, 16)
+
+    assert serialize(&Serializer.serialize_u128(1, &1)) == <<1>> <> :binary.copy(<<0>>, 15)
+
+    assert_raise SerializationError, fn -> serialize(&Serializer.serialize_u128(-1, &1)) end
+
+    assert_raise SerializationError, fn ->
+      serialize(&Serializer.serialize_u128(1 <<< 128, &1))
+    end
+  end
+
+  test "serialize i128" do
+    assert serialize(&Serializer.serialize_i128(-1, &1)) == :binary.copy(<<0xFF>>, 16)
+
+    assert serialize(&Serializer.serialize_i128(-(1 <<< 127), &1)) ==
+             :binary.copy(<<0>>, 15) <> <<0x80>>
+
+    assert_raise SerializationError, fn ->
+      serialize(&Serializer.serialize_i128(1 <<< 127, &1))
+    end
+
+    assert_raise SerializationError, fn ->
+      serialize(&Serializer.serialize_i128(-(1 <<< 127) - 1, &1))
+    end
+  end
+
+  test "deserialize i128" do
+    input = :binary.copy(<<0xFF>>, 32)
+    {value, rest} = deserialize(&Deserializer.deserialize_i128/2, input)
+    assert value == -1
+    {value, rest} = deserialize(&Deserializer.deserialize_u128/2, rest)
+    assert value == (1 <<< 128) - 1
+
+    assert_raise DeserializationError, fn ->
+      deserialize(&Deserializer.deserialize_u8/2, rest)
+    end
+  end
+
+  test "uleb128" do
+    assert serialize(&Serializer.serialize_len(300, &1)) == <<0xAC, 0x02>>
+    assert deserialize(&Deserializer.deserialize_len/2, <<0xAC, 0x02>>) == {300, <<>>}
+
+    # Non-canonical encodings are rejected.
+    assert_raise DeserializationError, fn ->
+      deserialize(&Deserializer.deserialize_len/2, <<0x80, 0x00>>)
+    end
+
+    # So are lengths larger than `Bcs.max_length()`.
+    assert_raise DeserializationError, fn ->
+      deserialize(&Deserializer.deserialize_len/2, <<0xFF, 0xFF, 0xFF, 0xFF, 0x08>>)
+    end
+  end
+
+  test "strings" do
+    assert serialize(&Serializer.serialize_str("é", &1)) == <<2, 0xC3, 0xA9>>
+    assert deserialize(&Deserializer.deserialize_str/2, <<2, 0xC3, 0xA9>>) == {"é", <<>>}
+
+    assert_raise SerializationError, fn ->
+      serialize(&Serializer.serialize_str(<<0xFF>>, &1))
+    end
+
+    assert_raise DeserializationError, fn ->
+      deserialize(&Deserializer.deserialize_str/2, <<1, 0xFF>>)
+    end
+  end
+
+  test "map entries are sorted by their serialized bytes" do
+    values = %{256 => nil, 1 => nil}
+    serialize_key = &Serializer.serialize_u32/2
+    serialize_value = &Serializer.serialize_unit/2
+    deserialize_key = &Deserializer.deserialize_u32/2
+    deserialize_value = &Deserializer.deserialize_unit/2
+
+    serialize_values = fn serializer ->
+      Serializer.serialize_map(values, serializer, serialize_key, serialize_value)
+    end
+
+    deserialize_values = fn input, deserializer ->
+      Deserializer.deserialize_map(input, deserializer, deserialize_key, deserialize_value)
+    end
+
+    input = <<2, 0, 1, 0, 0, 1, 0, 0, 0>>
+    assert serialize(serialize_values) == input
+    assert deserialize(deserialize_values, input) == {values, <<>>}
+
+    assert_raise DeserializationError, fn ->
+      deserialize(deserialize_values, <<2, 1, 0, 0, 0, 0, 1, 0, 0>>)
+    end
+  end
+
+  test "container depth" do
+    serializer =
+      Enum.reduce(1..Bcs.max_container_depth(), Serializer.new(Bcs), fn _, serializer ->
+        Serializer.increase_container_depth(serializer)
+      end)
+
+    assert_raise SerializationError, fn -> Serializer.increase_container_depth(serializer) end
+  end
+end
@@ -5,6 +5,7 @@ package bincode
 
 import (
 	"errors"
+	"io"
 	"math"
 
 	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
@@ -16,28 +17,216 @@ const MaxSequenceLength = (1 << 31) - 1
 // `deserializer` extends `serde.BinaryDeserializer` to implement `serde.Deserializer`.
 type deserializer struct {
 	serde.BinaryDeserializer
+	varintEncoding bool
+	bigEndian      bool
+	limit          uint64
 }
 
 func NewDeserializer(input []byte) serde.Deserializer {
-	return &deserializer{*serde.NewBinaryDeserializer(input, math.MaxUint64)}
+	return NewDeserializerWithOptions(input, false, false)
 }
 
+// NewDeserializerWithOptions creates a deserializer using the given integer encoding and byte order.
+func NewDeserializerWithOptions(input []byte, varintEncoding bool, bigEndian bool) serde.Deserializer {
+	return NewDeserializerWithLimit(input, varintEncoding, bigEndian, math.MaxUint64)
+}
+
+// NewDeserializerWithLimit creates a deserializer that fails when reading more than `limit` bytes,
+// as with the option `with_limit` of the Rust crate.
+func NewDeserializerWithLimit(input []byte, varintEncoding bool, bigEndian bool, limit uint64) serde.Deserializer {
+	return &deserializer{*serde.NewBinaryDeserializer(input, math.MaxUint64), varintEncoding, bigEndian, limit}
+}
+
+// Check that `size` more bytes may be read within the limit.
+func (d *deserializer) checkLimit(size uint64) error {
+	if size > d.limit-d.GetBufferOffset() {
+		return errors.New("exceeded the size limit of the encoding")
+	}
+	return nil
+}
+
+func (d *deserializer) readFixint(size uint) (uint64, error) {
+	if err := d.checkLimit(uint64(size)); err != nil {
+		return 0, err
+	}
+	buf := make([]byte, size)
+	if _, err := io.ReadFull(d.Buffer, buf); err != nil {
+		return 0, err
+	}
+	var ret uint64
+	for i := uint(0); i < size; i++ {
+		shift := 8 * i
+		if d.bigEndian {
+			shift = 8 * (size - 1 - i)
+		}
+		ret |= uint64(buf[i]) << shift
+	}
+	return ret, nil
+}
+
+func (d *deserializer) readFixint128() (high uint64, low uint64, err error) {
+	first, err := d.readFixint(8)
+	if err != nil {
+		return 0, 0, err
+	}
+	second, err := d.readFixint(8)
+	if err != nil {
+		return 0, 0, err
+	}
+	if d.bigEndian {
+		return first, second, nil
+	}
+	return second, first, nil
+}
+
+// Read a varint-encoded value of at most `bits` bits.
+func (d *deserializer) deserializeVarint(bits uint) (high uint64, low uint64, err error) {
+	tag, err := d.DeserializeU8()
+	if err != nil {
+		return 0, 0, err
+	}
+	switch {
+	case tag <= singleByteMax:
+		low = uint64(tag)
+	case tag == u16Tag:
+		low, err = d.readFixint(2)
+	case tag == u32Tag:
+		low, err = d.readFixint(4)
+	case tag == u64Tag:
+		low, err = d.readFixint(8)
+	case tag == u128Tag && bits == 128:
+		high, low, err = d.readFixint128()
+	default:
+		return 0, 0, errors.New("invalid tag for varint-encoded value")
+	}
+	if err != nil {
+		return 0, 0, err
+	}
+	if bits < 64 && low>>bits != 0 {
+		return 0, 0, errors.New("overflow while parsing varint-encoded value")
+	}
+	return high, low, nil
+}
+
+func (d *deserializer) DeserializeBool() (bool, error) {
+	if err := d.checkLimit(1); err != nil {
+		return false, err
+	}
+	return d.BinaryDeserializer.DeserializeBool()
+}
+
+func (d *deserializer) DeserializeU8() (uint8, error) {
+	if err := d.checkLimit(1); err != nil {
+		return 0, err
+	}
+	return d.BinaryDeserializer.DeserializeU8()
+}
+
+func (d *deserializer) DeserializeI8() (int8, error) {
+	ret, err := d.DeserializeU8()
+	return int8(ret), err
+}
+
+func (d *deserializer) DeserializeOptionTag() (bool, error) {
+	return d.DeserializeBool()
+}
+
+func (d *deserializer) deserializeUnsigned(size uint) (uint64, error) {
+	if d.varintEncoding {
+		_, ret, err := d.deserializeVarint(8 * size)
+		return ret, err
+	}
+	return d.readFixint(size)
+}
+
+func (d *deserializer) DeserializeU16() (uint16, error) {
+	ret, err := d.deserializeUnsigned(2)
+	return uint16(ret), err
+}
+
+func (d *deserializer) DeserializeU32() (uint32, error) {
+	ret, err := d.deserializeUnsigned(4)
+	return uint32(ret), err
+}
+
+func (d *deserializer) DeserializeU64() (uint64, error) {
+	return d.deserializeUnsigned(8)
+}
+
+func (d *deserializer) DeserializeU128() (serde.Uint128, error) {
+	var high, low uint64
+	var err error
+	if d.varintEncoding {
+		high, low, err = d.deserializeVarint(128)
+	} else {
+		high, low, err = d.readFixint128()
+	}
+	return serde.Uint128{High: high, Low: low}, err
+}
+
+func (d *deserializer) DeserializeI16() (int16, error) {
+	ret, err := d.deserializeUnsigned(2)
+	if d.varintEncoding {
+		return int16(ret>>1) ^ -int16(ret&1), err
+	}
+	return int16(ret), err
+}
+
+func (d *deserializer) DeserializeI32() (int32, error) {
+	ret, err := d.deserializeUnsigned(4)
+	if d.varintEncoding {
+		return int32(ret>>1) ^ -int32(ret&1), err
+	}
+	return int32(ret), err
+}
+
+func (d *deserializer) DeserializeI64() (int64, error) {
+	ret, err := d.deserializeUnsigned(8)
+	if d.varintEncoding {
+		return int64(ret>>1) ^ -int64(ret&1), err
+	}
+	return int64(ret), err
+}
+
+func (d *deserializer) DeserializeI128() (serde.Int128, error) {
+	if !d.varintEncoding {
+		high, low, err := d.readFixint128()
+		return serde.Int128{High: int64(high), Low: low}, err
+	}
+	high, low, err := d.deserializeVarint(128)
+	sign := -(low & 1)
+	return serde.Int128{
+		High: int64((high >> 1) ^ sign),
+		Low:  ((low >> 1) | (high << 63)) ^ sign,
+	}, err
+}
+
+// Floating-point numbers are never varint-encoded.
 func (d *deserializer) DeserializeF32() (float32, error) {
-	ret, err := d.DeserializeU32()
-	return math.Float32frombits(ret), err
+	ret, err := d.readFixint(4)
+	return math.Float32frombits(uint32(ret)), err
 }
 
 func (d *deserializer) DeserializeF64() (float64, error) {
-	ret, err := d.DeserializeU64()
+	ret, err := d.readFixint(8)
 	return math.Float64frombits(ret), err
 }
 
 func (d *deserializer) DeserializeBytes() ([]byte, error) {
-	return d.BinaryDeserializer.DeserializeBytes(d.DeserializeLen)
+	return d.BinaryDeserializer.DeserializeBytes(d.deserializeContentLen)
 }
 
 func (d *deserializer) DeserializeStr() (string, error) {
-	return d.BinaryDeserializer.DeserializeStr(d.DeserializeLen)
+	return d.BinaryDeserializer.DeserializeStr(d.deserializeContentLen)
+}
+
+// Read the length of some bytes and check it before allocating the content.
+func (d *deserializer) deserializeContentLen() (uint64, error) {
+	len, err := d.DeserializeLen()
+	if err != nil {
+		return 0, err
+	}
+	return len, d.checkLimit(len)
 }
 
 func (d *deserializer) DeserializeLen() (uint64, error) {
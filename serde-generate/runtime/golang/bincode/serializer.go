@@ -9,21 +9,143 @@ import (
 	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
 )
 
+// Tags used by the varint encoding of integers.
+const (
+	singleByteMax = 250
+	u16Tag        = 251
+	u32Tag        = 252
+	u64Tag        = 253
+	u128Tag       = 254
+)
+
 // `serializer` extends `serde.BinarySerializer` to implement `serde.Serializer`.
 type serializer struct {
 	serde.BinarySerializer
+	varintEncoding bool
+	bigEndian      bool
 }
 
 func NewSerializer() serde.Serializer {
-	return &serializer{*serde.NewBinarySerializer(math.MaxUint64)}
+	return NewSerializerWithOptions(false, false)
+}
+
+// NewSerializerWithOptions creates a serializer using the given integer encoding and byte order.
+func NewSerializerWithOptions(varintEncoding bool, bigEndian bool) serde.Serializer {
+	return &serializer{*serde.NewBinarySerializer(math.MaxUint64), varintEncoding, bigEndian}
+}
+
+func (s *serializer) writeFixint(value uint64, size uint) {
+	for i := uint(0); i < size; i++ {
+		shift := 8 * i
+		if s.bigEndian {
+			shift = 8 * (size - 1 - i)
+		}
+		s.Buffer.WriteByte(byte(value >> shift))
+	}
+}
+
+func (s *serializer) writeFixint128(high uint64, low uint64) {
+	if s.bigEndian {
+		s.writeFixint(high, 8)
+		s.writeFixint(low, 8)
+	} else {
+		s.writeFixint(low, 8)
+		s.writeFixint(high, 8)
+	}
+}
+
+func (s *serializer) serializeVarint(high uint64, low uint64) {
+	switch {
+	case high == 0 && low <= singleByteMax:
+		s.Buffer.WriteByte(byte(low))
+	case high == 0 && low <= math.MaxUint16:
+		s.Buffer.WriteByte(u16Tag)
+		s.writeFixint(low, 2)
+	case high == 0 && low <= math.MaxUint32:
+		s.Buffer.WriteByte(u32Tag)
+		s.writeFixint(low, 4)
+	case high == 0:
+		s.Buffer.WriteByte(u64Tag)
+		s.writeFixint(low, 8)
+	default:
+		s.Buffer.WriteByte(u128Tag)
+		s.writeFixint128(high, low)
+	}
+}
+
+func (s *serializer) serializeUnsigned(value uint64, size uint) error {
+	if s.varintEncoding {
+		s.serializeVarint(0, value)
+	} else {
+		s.writeFixint(value, size)
+	}
+	return nil
+}
+
+func (s *serializer) SerializeU16(value uint16) error {
+	return s.serializeUnsigned(uint64(value), 2)
+}
+
+func (s *serializer) SerializeU32(value uint32) error {
+	return s.serializeUnsigned(uint64(value), 4)
+}
+
+func (s *serializer) SerializeU64(value uint64) error {
+	return s.serializeUnsigned(value, 8)
+}
+
+func (s *serializer) SerializeU128(value serde.Uint128) error {
+	if s.varintEncoding {
+		s.serializeVarint(value.High, value.Low)
+	} else {
+		s.writeFixint128(value.High, value.Low)
+	}
+	return nil
+}
+
+// Signed integers use a "zigzag" encoding before the varint encoding.
+func (s *serializer) SerializeI16(value int16) error {
+	if s.varintEncoding {
+		return s.serializeUnsigned(uint64(uint16((value<<1)^(value>>15))), 2)
+	}
+	return s.serializeUnsigned(uint64(uint16(value)), 2)
+}
+
+func (s *serializer) SerializeI32(value int32) error {
+	if s.varintEncoding {
+		return s.serializeUnsigned(uint64(uint32((value<<1)^(value>>31))), 4)
+	}
+	return s.serializeUnsigned(uint64(uint32(value)), 4)
+}
+
+func (s *serializer) SerializeI64(value int64) error {
+	if s.varintEncoding {
+		return s.serializeUnsigned(uint64((value<<1)^(value>>63)), 8)
+	}
+	return s.serializeUnsigned(uint64(value), 8)
+}
+
+func (s *serializer) SerializeI128(value serde.Int128) error {
+	if s.varintEncoding {
+		sign := uint64(value.High >> 63)
+		high := (uint64(value.High) << 1) | (value.Low >> 63)
+		low := value.Low << 1
+		s.serializeVarint(high^sign, low^sign)
+	} else {
+		s.writeFixint128(uint64(value.High), value.Low)
+	}
+	return nil
 }
 
+// Floating-point numbers are never varint-encoded.
 func (s *serializer) SerializeF32(value float32) error {
-	return s.SerializeU32(math.Float32bits(value))
+	s.writeFixint(uint64(math.Float32bits(value)), 4)
+	return nil
 }
 
 func (s *serializer) SerializeF64(value float64) error {
-	return s.SerializeU64(math.Float64bits(value))
+	s.writeFixint(math.Float64bits(value), 8)
+	return nil
 }
 
 func (s *serializer) SerializeStr(value string) error {
@@ -0,0 +1,72 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package borsh_test
+
+import (
+	"math"
+	"testing"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/borsh"
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestSerializeDeserializeLen(t *testing.T) {
+	s := borsh.NewSerializer()
+	require.NoError(t, s.SerializeLen(258))
+	assert.Equal(t, []byte{2, 1, 0, 0}, s.GetBytes())
+
+	d := borsh.NewDeserializer([]byte{2, 1, 0, 0})
+	deserialized, err := d.DeserializeLen()
+	require.NoError(t, err)
+	assert.Equal(t, uint64(258), deserialized)
+
+	d = borsh.NewDeserializer([]byte{0xff, 0xff, 0xff, 0xff})
+	_, err = d.DeserializeLen()
+	assert.Error(t, err)
+}
+
+func TestSerializeDeserializeVariantIndex(t *testing.T) {
+	s := borsh.NewSerializer()
+	require.NoError(t, s.SerializeVariantIndex(255))
+	assert.Equal(t, []byte{255}, s.GetBytes())
+	assert.Error(t, s.SerializeVariantIndex(256))
+
+	d := borsh.NewDeserializer([]byte{255})
+	deserialized, err := d.DeserializeVariantIndex()
+	require.NoError(t, err)
+	assert.Equal(t, uint32(255), deserialized)
+}
+
+func TestRejectNaN(t *testing.T) {
+	s := borsh.NewSerializer()
+	assert.Error(t, s.SerializeF32(float32(math.NaN())))
+	assert.Error(t, s.SerializeF64(math.NaN()))
+
+	d := borsh.NewDeserializer([]byte{0, 0, 0xc0, 0x7f})
+	_, err := d.DeserializeF32()
+	assert.Error(t, err)
+}
+
+func TestSortMapEntriesInRustKeyOrder(t *testing.T) {
+	s := borsh.NewSerializer()
+	offsets := []uint64{s.GetBufferOffset()}
+	require.NoError(t, s.SerializeStr("b"))
+	require.NoError(t, s.SerializeI16(-1))
+	offsets = append(offsets, s.GetBufferOffset())
+	require.NoError(t, s.SerializeStr("ab"))
+	require.NoError(t, s.SerializeI16(256))
+	s.SortMapEntries(offsets)
+	expected := []byte{2, 0, 0, 0, 'a', 'b', 0, 1, 1, 0, 0, 0, 'b', 0xff, 0xff}
+	assert.Equal(t, expected, s.GetBytes())
+
+	d := borsh.NewDeserializer([]byte{1, 0, 0, 0, 'b', 2, 0, 0, 0, 'a', 'b'})
+	_, err := d.DeserializeStr()
+	require.NoError(t, err)
+	_, err = d.DeserializeStr()
+	require.NoError(t, err)
+	assert.Error(t, d.CheckThatKeySlicesAreIncreasing(serde.Slice{Start: 0, End: 5}, serde.Slice{Start: 5, End: 11}))
+	assert.NoError(t, d.CheckThatKeySlicesAreIncreasing(serde.Slice{Start: 5, End: 11}, serde.Slice{Start: 0, End: 5}))
+}
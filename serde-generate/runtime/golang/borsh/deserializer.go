@@ -0,0 +1,182 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package borsh
+
+import (
+	"bytes"
+	"errors"
+	"math"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+)
+
+// Maximum length allowed for sequences (vectors, bytes, strings) and maps.
+const MaxSequenceLength = (1 << 31) - 1
+
+// `deserializer` extends `serde.BinaryDeserializer` to implement `serde.Deserializer`.
+type deserializer struct {
+	serde.BinaryDeserializer
+	sortKeys sortKeys
+}
+
+func NewDeserializer(input []byte) serde.Deserializer {
+	return &deserializer{BinaryDeserializer: *serde.NewBinaryDeserializer(input, math.MaxUint64)}
+}
+
+func (d *deserializer) recordInteger(start uint64, signed bool) {
+	d.sortKeys.recordInteger(start, d.Input[start:d.GetBufferOffset()], signed)
+}
+
+func (d *deserializer) DeserializeU16() (uint16, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeU16()
+	if err != nil {
+		return ret, err
+	}
+	d.recordInteger(start, false)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeU32() (uint32, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeU32()
+	if err != nil {
+		return ret, err
+	}
+	d.recordInteger(start, false)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeU64() (uint64, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeU64()
+	if err != nil {
+		return ret, err
+	}
+	d.recordInteger(start, false)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeU128() (serde.Uint128, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeU128()
+	if err != nil {
+		return ret, err
+	}
+	d.recordInteger(start, false)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeI8() (int8, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeI8()
+	if err != nil {
+		return ret, err
+	}
+	d.recordInteger(start, true)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeI16() (int16, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeI16()
+	if err != nil {
+		return ret, err
+	}
+	d.recordInteger(start, true)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeI32() (int32, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeI32()
+	if err != nil {
+		return ret, err
+	}
+	d.recordInteger(start, true)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeI64() (int64, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeI64()
+	if err != nil {
+		return ret, err
+	}
+	d.recordInteger(start, true)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeI128() (serde.Int128, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeI128()
+	if err != nil {
+		return ret, err
+	}
+	d.recordInteger(start, true)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeF32() (float32, error) {
+	ret, err := d.BinaryDeserializer.DeserializeU32()
+	if err != nil {
+		return 0, err
+	}
+	value := math.Float32frombits(ret)
+	if math.IsNaN(float64(value)) {
+		return 0, errors.New("NaN values are not supported by Borsh")
+	}
+	return value, nil
+}
+
+func (d *deserializer) DeserializeF64() (float64, error) {
+	ret, err := d.BinaryDeserializer.DeserializeU64()
+	if err != nil {
+		return 0, err
+	}
+	value := math.Float64frombits(ret)
+	if math.IsNaN(value) {
+		return 0, errors.New("NaN values are not supported by Borsh")
+	}
+	return value, nil
+}
+
+func (d *deserializer) DeserializeBytes() ([]byte, error) {
+	return d.BinaryDeserializer.DeserializeBytes(d.DeserializeLen)
+}
+
+func (d *deserializer) DeserializeStr() (string, error) {
+	start := d.GetBufferOffset()
+	ret, err := d.BinaryDeserializer.DeserializeStr(d.DeserializeLen)
+	if err != nil {
+		return ret, err
+	}
+	d.sortKeys.recordStr(start, d.Input[start:d.GetBufferOffset()])
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeLen() (uint64, error) {
+	ret, err := d.BinaryDeserializer.DeserializeU32()
+	if err != nil {
+		return 0, err
+	}
+	if ret > MaxSequenceLength {
+		return 0, errors.New("length is too large")
+	}
+	return uint64(ret), nil
+}
+
+func (d *deserializer) DeserializeVariantIndex() (uint32, error) {
+	ret, err := d.BinaryDeserializer.DeserializeU8()
+	return uint32(ret), err
+}
+
+func (d *deserializer) CheckThatKeySlicesAreIncreasing(key1, key2 serde.Slice) error {
+	sortKey1 := d.sortKeys.sortKey(d.Input[key1.Start:key1.End], key1.Start)
+	sortKey2 := d.sortKeys.sortKey(d.Input[key2.Start:key2.End], key2.Start)
+	if bytes.Compare(sortKey1, sortKey2) >= 0 {
+		return errors.New("Error while decoding map: keys are not serialized in the expected order")
+	}
+	return nil
+}
@@ -0,0 +1,194 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package borsh
+
+import (
+	"bytes"
+	"errors"
+	"math"
+	"sort"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+)
+
+// `serializer` extends `serde.BinarySerializer` to implement `serde.Serializer`.
+type serializer struct {
+	serde.BinarySerializer
+	sortKeys sortKeys
+}
+
+func NewSerializer() serde.Serializer {
+	return &serializer{BinarySerializer: *serde.NewBinarySerializer(math.MaxUint64)}
+}
+
+func (s *serializer) recordInteger(start uint64, signed bool) {
+	s.sortKeys.recordInteger(start, s.Buffer.Bytes()[start:], signed)
+}
+
+func (s *serializer) SerializeU16(value uint16) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeU16(value); err != nil {
+		return err
+	}
+	s.recordInteger(start, false)
+	return nil
+}
+
+func (s *serializer) SerializeU32(value uint32) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeU32(value); err != nil {
+		return err
+	}
+	s.recordInteger(start, false)
+	return nil
+}
+
+func (s *serializer) SerializeU64(value uint64) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeU64(value); err != nil {
+		return err
+	}
+	s.recordInteger(start, false)
+	return nil
+}
+
+func (s *serializer) SerializeU128(value serde.Uint128) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeU128(value); err != nil {
+		return err
+	}
+	s.recordInteger(start, false)
+	return nil
+}
+
+func (s *serializer) SerializeI8(value int8) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeI8(value); err != nil {
+		return err
+	}
+	s.recordInteger(start, true)
+	return nil
+}
+
+func (s *serializer) SerializeI16(value int16) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeI16(value); err != nil {
+		return err
+	}
+	s.recordInteger(start, true)
+	return nil
+}
+
+func (s *serializer) SerializeI32(value int32) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeI32(value); err != nil {
+		return err
+	}
+	s.recordInteger(start, true)
+	return nil
+}
+
+func (s *serializer) SerializeI64(value int64) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeI64(value); err != nil {
+		return err
+	}
+	s.recordInteger(start, true)
+	return nil
+}
+
+func (s *serializer) SerializeI128(value serde.Int128) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeI128(value); err != nil {
+		return err
+	}
+	s.recordInteger(start, true)
+	return nil
+}
+
+func (s *serializer) SerializeF32(value float32) error {
+	if math.IsNaN(float64(value)) {
+		return errors.New("NaN values are not supported by Borsh")
+	}
+	return s.BinarySerializer.SerializeU32(math.Float32bits(value))
+}
+
+func (s *serializer) SerializeF64(value float64) error {
+	if math.IsNaN(value) {
+		return errors.New("NaN values are not supported by Borsh")
+	}
+	return s.BinarySerializer.SerializeU64(math.Float64bits(value))
+}
+
+func (s *serializer) SerializeStr(value string) error {
+	start := s.GetBufferOffset()
+	if err := s.BinarySerializer.SerializeStr(value, s.SerializeLen); err != nil {
+		return err
+	}
+	s.sortKeys.recordStr(start, s.Buffer.Bytes()[start:])
+	return nil
+}
+
+func (s *serializer) SerializeBytes(value []byte) error {
+	return s.BinarySerializer.SerializeBytes(value, s.SerializeLen)
+}
+
+func (s *serializer) SerializeLen(value uint64) error {
+	if value > MaxSequenceLength {
+		return errors.New("length is too large")
+	}
+	return s.BinarySerializer.SerializeU32(uint32(value))
+}
+
+func (s *serializer) SerializeVariantIndex(value uint32) error {
+	if value > math.MaxUint8 {
+		return errors.New("variant index is too large")
+	}
+	return s.BinarySerializer.SerializeU8(uint8(value))
+}
+
+func (s *serializer) SortMapEntries(offsets []uint64) {
+	if len(offsets) <= 1 {
+		return
+	}
+	data := s.Buffer.Bytes()
+	slices := make([]serde.Slice, len(offsets))
+	for i, v := range offsets {
+		var w uint64
+		if i+1 < len(offsets) {
+			w = offsets[i+1]
+		} else {
+			w = uint64(len(data))
+		}
+		slices[i] = serde.Slice{Start: v, End: w}
+	}
+	keys := make([][]byte, len(slices))
+	for i, slice := range slices {
+		keys[i] = s.sortKeys.sortKey(data[slice.Start:slice.End], slice.Start)
+	}
+	entries := map_entries{keys, slices}
+	sort.Sort(entries)
+	// Positions in the map are no longer valid. Map keys never contain maps.
+	s.sortKeys.truncate(offsets[0])
+	buffer := make([]byte, len(data)-int(offsets[0]))
+	current := buffer[0:0]
+	for _, slice := range entries.slices {
+		current = append(current, data[slice.Start:slice.End]...)
+	}
+	copy(data[offsets[0]:], current)
+}
+
+type map_entries struct {
+	keys   [][]byte
+	slices []serde.Slice
+}
+
+func (a map_entries) Len() int { return len(a.slices) }
+
+func (a map_entries) Less(i, j int) bool { return bytes.Compare(a.keys[i], a.keys[j]) < 0 }
+
+func (a map_entries) Swap(i, j int) {
+	a.keys[i], a.keys[j] = a.keys[j], a.keys[i]
+	a.slices[i], a.slices[j] = a.slices[j], a.slices[i]
+}
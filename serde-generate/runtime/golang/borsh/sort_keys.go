@@ -0,0 +1,76 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package borsh
+
+import (
+	"sort"
+)
+
+// `sortKeys` holds order-preserving encodings of the integers and strings written or
+// read so far, indexed by their positions in the buffer.
+//
+// Borsh sorts map entries by keys as in Rust. Substituting these encodings in the
+// encoded keys gives byte strings that compare in the same way.
+type sortKeys struct {
+	// Ordered by start positions.
+	records []sortKeyRecord
+}
+
+type sortKeyRecord struct {
+	start uint64
+	end   uint64
+	key   []byte
+}
+
+// Little-endian integers compare as in Rust in big-endian order, with the sign bit flipped.
+func (k *sortKeys) recordInteger(start uint64, data []byte, signed bool) {
+	key := make([]byte, len(data))
+	for i := range key {
+		key[i] = data[len(data)-1-i]
+	}
+	if signed {
+		key[0] ^= 0x80
+	}
+	k.record(start, start+uint64(len(data)), key)
+}
+
+// Strings have their zeros escaped and a terminator added so that prefixes come first.
+func (k *sortKeys) recordStr(start uint64, data []byte) {
+	key := make([]byte, 0, len(data))
+	for _, b := range data[4:] {
+		key = append(key, b)
+		if b == 0 {
+			key = append(key, 0xff)
+		}
+	}
+	key = append(key, 0, 0)
+	k.record(start, start+uint64(len(data)), key)
+}
+
+func (k *sortKeys) record(start, end uint64, key []byte) {
+	// Values written or read inside the current one are superseded.
+	k.truncate(start)
+	k.records = append(k.records, sortKeyRecord{start, end, key})
+}
+
+func (k *sortKeys) lowerBound(start uint64) int {
+	return sort.Search(len(k.records), func(i int) bool { return k.records[i].start >= start })
+}
+
+func (k *sortKeys) truncate(start uint64) {
+	k.records = k.records[:k.lowerBound(start)]
+}
+
+// Returns the sort key of `data`, found at position `start` in the buffer.
+func (k *sortKeys) sortKey(data []byte, start uint64) []byte {
+	var result []byte
+	end := start + uint64(len(data))
+	position := start
+	for i := k.lowerBound(start); i < len(k.records) && k.records[i].start < end; i++ {
+		result = append(result, data[position-start:k.records[i].start-start]...)
+		result = append(result, k.records[i].key...)
+		position = k.records[i].end
+	}
+	return append(result, data[position-start:]...)
+}
@@ -0,0 +1,758 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+// The JSON format of the Rust crate `serde_json`. Generated types convert their values from
+// and to trees of `Value` with the helpers of this package, then `Parse` and `Stringify`
+// deal with the JSON text.
+package json
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"math"
+	"math/big"
+	"regexp"
+	"strconv"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+)
+
+// Maximal number of nested arrays and objects accepted by `serde_json` (whose recursion limit is 128).
+const MaxContainerDepth = 127
+
+var integerRegexp = regexp.MustCompile(`^-?(0|[1-9][0-9]*)$`)
+var numberRegexp = regexp.MustCompile(`^-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?$`)
+
+func Parse(input []byte) (Value, error) {
+	if !utf8.Valid(input) {
+		return nil, errors.New("Invalid UTF-8 in JSON input")
+	}
+	parser := parser{input: input}
+	value, err := parser.parseValue(MaxContainerDepth)
+	if err != nil {
+		return nil, err
+	}
+	parser.skipWhitespace()
+	if parser.position < len(input) {
+		return nil, errors.New("Trailing characters in JSON input")
+	}
+	return value, nil
+}
+
+func Stringify(value Value) ([]byte, error) {
+	var output strings.Builder
+	if err := writeJson(&output, value); err != nil {
+		return nil, err
+	}
+	return []byte(output.String()), nil
+}
+
+func writeJson(output *strings.Builder, value Value) error {
+	switch value := value.(type) {
+	case nil:
+		output.WriteString("null")
+	case bool:
+		if value {
+			output.WriteString("true")
+		} else {
+			output.WriteString("false")
+		}
+	case Number:
+		output.WriteString(string(value))
+	case string:
+		return writeString(output, value)
+	case Key:
+		return writeString(output, string(value))
+	case []Value:
+		output.WriteByte('[')
+		for i, item := range value {
+			if i > 0 {
+				output.WriteByte(',')
+			}
+			if err := writeJson(output, item); err != nil {
+				return err
+			}
+		}
+		output.WriteByte(']')
+	case *Object:
+		output.WriteByte('{')
+		for i := range value.Keys {
+			if i > 0 {
+				output.WriteByte(',')
+			}
+			if err := writeString(output, value.Keys[i]); err != nil {
+				return err
+			}
+			output.WriteByte(':')
+			if err := writeJson(output, value.Values[i]); err != nil {
+				return err
+			}
+		}
+		output.WriteByte('}')
+	default:
+		return fmt.Errorf("Invalid JSON value: %v", value)
+	}
+	return nil
+}
+
+func writeString(output *strings.Builder, value string) error {
+	if !utf8.ValidString(value) {
+		return errors.New("Invalid UTF-8 string")
+	}
+	output.WriteByte('"')
+	for _, c := range value {
+		switch c {
+		case '"':
+			output.WriteString(`\"`)
+		case '\\':
+			output.WriteString(`\\`)
+		case '\b':
+			output.WriteString(`\b`)
+		case '\f':
+			output.WriteString(`\f`)
+		case '\n':
+			output.WriteString(`\n`)
+		case '\r':
+			output.WriteString(`\r`)
+		case '\t':
+			output.WriteString(`\t`)
+		default:
+			if c < 0x20 {
+				fmt.Fprintf(output, `\u%04x`, c)
+			} else {
+				output.WriteRune(c)
+			}
+		}
+	}
+	output.WriteByte('"')
+	return nil
+}
+
+type parser struct {
+	input    []byte
+	position int
+}
+
+func (p *parser) skipWhitespace() {
+	for p.position < len(p.input) {
+		switch p.input[p.position] {
+		case ' ', '\t', '\n', '\r':
+			p.position++
+		default:
+			return
+		}
+	}
+}
+
+func (p *parser) error(message string) error {
+	return fmt.Errorf("%s at position %d", message, p.position)
+}
+
+func (p *parser) peek(c byte) bool {
+	return p.position < len(p.input) && p.input[p.position] == c
+}
+
+func (p *parser) next() (byte, error) {
+	if p.position >= len(p.input) {
+		return 0, p.error("Unexpected end of JSON input")
+	}
+	p.position++
+	return p.input[p.position-1], nil
+}
+
+func (p *parser) expect(word string) error {
+	if !bytes.HasPrefix(p.input[p.position:], []byte(word)) {
+		return p.error("Invalid JSON value")
+	}
+	p.position += len(word)
+	return nil
+}
+
+func (p *parser) parseValue(depthBudget int) (Value, error) {
+	p.skipWhitespace()
+	if p.position >= len(p.input) {
+		return nil, p.error("Unexpected end of JSON input")
+	}
+	c := p.input[p.position]
+	switch {
+	case c == 'n':
+		return nil, p.expect("null")
+	case c == 't':
+		return true, p.expect("true")
+	case c == 'f':
+		return false, p.expect("false")
+	case c == '"':
+		return p.parseString()
+	case c == '[':
+		if depthBudget == 0 {
+			return nil, p.error("Exceeded maximum container depth")
+		}
+		p.position++
+		items := []Value{}
+		p.skipWhitespace()
+		if p.peek(']') {
+			p.position++
+			return items, nil
+		}
+		for {
+			item, err := p.parseValue(depthBudget - 1)
+			if err != nil {
+				return nil, err
+			}
+			items = append(items, item)
+			p.skipWhitespace()
+			d, err := p.next()
+			if err != nil {
+				return nil, err
+			}
+			if d == ']' {
+				return items, nil
+			}
+			if d != ',' {
+				return nil, p.error("Expected ',' or ']'")
+			}
+		}
+	case c == '{':
+		if depthBudget == 0 {
+			return nil, p.error("Exceeded maximum container depth")
+		}
+		p.position++
+		object := &Object{}
+		p.skipWhitespace()
+		if p.peek('}') {
+			p.position++
+			return object, nil
+		}
+		for {
+			p.skipWhitespace()
+			if !p.peek('"') {
+				return nil, p.error("Expected a string key")
+			}
+			key, err := p.parseString()
+			if err != nil {
+				return nil, err
+			}
+			p.skipWhitespace()
+			if d, err := p.next(); err != nil {
+				return nil, err
+			} else if d != ':' {
+				return nil, p.error("Expected ':'")
+			}
+			value, err := p.parseValue(depthBudget - 1)
+			if err != nil {
+				return nil, err
+			}
+			object.Put(key, value)
+			p.skipWhitespace()
+			d, err := p.next()
+			if err != nil {
+				return nil, err
+			}
+			if d == '}' {
+				return object, nil
+			}
+			if d != ',' {
+				return nil, p.error("Expected ',' or '}'")
+			}
+		}
+	case c == '-' || (c >= '0' && c <= '9'):
+		return p.parseNumber()
+	default:
+		return nil, p.error("Invalid JSON value")
+	}
+}
+
+func (p *parser) parseNumber() (Value, error) {
+	start := p.position
+	for p.position < len(p.input) && strings.IndexByte("-+.eE0123456789", p.input[p.position]) >= 0 {
+		p.position++
+	}
+	literal := string(p.input[start:p.position])
+	if !numberRegexp.MatchString(literal) {
+		return nil, p.error("Invalid number")
+	}
+	return Number(literal), nil
+}
+
+func (p *parser) parseHex() (rune, error) {
+	if p.position+4 > len(p.input) {
+		return 0, p.error("Invalid escape in string")
+	}
+	result, err := strconv.ParseUint(string(p.input[p.position:p.position+4]), 16, 16)
+	if err != nil {
+		return 0, p.error("Invalid escape in string")
+	}
+	p.position += 4
+	return rune(result), nil
+}
+
+func (p *parser) parseString() (string, error) {
+	p.position++
+	var result strings.Builder
+	for {
+		c, err := p.next()
+		if err != nil {
+			return "", err
+		}
+		if c == '"' {
+			return result.String(), nil
+		}
+		if c < 0x20 {
+			return "", p.error("Control character in string")
+		}
+		if c != '\\' {
+			result.WriteByte(c)
+			continue
+		}
+		escape, err := p.next()
+		if err != nil {
+			return "", err
+		}
+		switch escape {
+		case '"', '\\', '/':
+			result.WriteByte(escape)
+		case 'b':
+			result.WriteByte('\b')
+		case 'f':
+			result.WriteByte('\f')
+		case 'n':
+			result.WriteByte('\n')
+		case 'r':
+			result.WriteByte('\r')
+		case 't':
+			result.WriteByte('\t')
+		case 'u':
+			high, err := p.parseHex()
+			if err != nil {
+				return "", err
+			}
+			if high >= 0xdc00 && high <= 0xdfff {
+				return "", p.error("Lone surrogate in string")
+			}
+			if high >= 0xd800 && high <= 0xdbff {
+				if err := p.expect(`\u`); err != nil {
+					return "", p.error("Lone surrogate in string")
+				}
+				low, err := p.parseHex()
+				if err != nil {
+					return "", err
+				}
+				if !(low >= 0xdc00 && low <= 0xdfff) {
+					return "", p.error("Lone surrogate in string")
+				}
+				result.WriteRune(0x10000 + (high-0xd800)<<10 + (low - 0xdc00))
+			} else {
+				result.WriteRune(high)
+			}
+		default:
+			return "", p.error("Invalid escape in string")
+		}
+	}
+}
+
+// Format a finite float in the manner of the Rust crate `ryu`, as `serde_json` does: with the
+// shortest digits that read back as the same value.
+func formatFloat(value float64, bitSize int) string {
+	if value == 0 {
+		if math.Signbit(value) {
+			return "-0.0"
+		}
+		return "0.0"
+	}
+	sign := ""
+	if value < 0 {
+		sign = "-"
+	}
+	scientific := strconv.FormatFloat(math.Abs(value), 'e', -1, bitSize)
+	parts := strings.Split(scientific, "e")
+	digits := strings.Replace(parts[0], ".", "", 1)
+	exponent, _ := strconv.Atoi(parts[1])
+	point := exponent + 1
+	maxPoint, minPoint := 16, -5
+	if bitSize == 32 {
+		maxPoint, minPoint = 13, -6
+	}
+	switch {
+	case len(digits) <= point && point <= maxPoint:
+		return sign + digits + strings.Repeat("0", point-len(digits)) + ".0"
+	case 0 < point && point <= maxPoint:
+		return sign + digits[:point] + "." + digits[point:]
+	case minPoint < point && point <= 0:
+		return sign + "0." + strings.Repeat("0", -point) + digits
+	}
+	mantissa := digits
+	if len(digits) > 1 {
+		mantissa = digits[:1] + "." + digits[1:]
+	}
+	return sign + mantissa + "e" + strconv.Itoa(point-1)
+}
+
+func typeError(value Value, expected string) error {
+	return fmt.Errorf("Invalid type: expected %s, found %v", expected, value)
+}
+
+func readInteger(value Value, min, max *big.Int, acceptsNegativeZero bool) (*big.Int, error) {
+	var literal string
+	switch value := value.(type) {
+	case Number:
+		literal = string(value)
+	case Key:
+		literal = string(value)
+	default:
+		return nil, typeError(value, "an integer")
+	}
+	// Like `serde_json`, read `-0` as a float, except for 128-bit integers.
+	if !integerRegexp.MatchString(literal) || (literal == "-0" && !acceptsNegativeZero) {
+		return nil, typeError(value, "an integer")
+	}
+	result, _ := new(big.Int).SetString(literal, 10)
+	if result.Cmp(min) < 0 || result.Cmp(max) > 0 {
+		return nil, fmt.Errorf("Integer out of range: %s", literal)
+	}
+	return result, nil
+}
+
+func readNumber(value Value) (float64, error) {
+	var literal string
+	switch value := value.(type) {
+	case Number:
+		literal = string(value)
+	case Key:
+		if !numberRegexp.MatchString(string(value)) {
+			return 0, typeError(value, "a number")
+		}
+		literal = string(value)
+	default:
+		return 0, typeError(value, "a number")
+	}
+	result, _ := strconv.ParseFloat(literal, 64)
+	if math.IsInf(result, 0) {
+		return 0, fmt.Errorf("Number out of range: %s", literal)
+	}
+	return result, nil
+}
+
+func signedRange(n uint) (*big.Int, *big.Int) {
+	max := new(big.Int).Lsh(big.NewInt(1), n-1)
+	min := new(big.Int).Neg(max)
+	return min, max.Sub(max, big.NewInt(1))
+}
+
+func unsignedRange(n uint) (*big.Int, *big.Int) {
+	max := new(big.Int).Lsh(big.NewInt(1), n)
+	return big.NewInt(0), max.Sub(max, big.NewInt(1))
+}
+
+var (
+	minI8, maxI8     = signedRange(8)
+	minI16, maxI16   = signedRange(16)
+	minI32, maxI32   = signedRange(32)
+	minI64, maxI64   = signedRange(64)
+	minI128, maxI128 = signedRange(128)
+	minU8, maxU8     = unsignedRange(8)
+	minU16, maxU16   = unsignedRange(16)
+	minU32, maxU32   = unsignedRange(32)
+	minU64, maxU64   = unsignedRange(64)
+	minU128, maxU128 = unsignedRange(128)
+)
+
+func int128ToBig(value serde.Int128) *big.Int {
+	result := new(big.Int).Lsh(big.NewInt(value.High), 64)
+	return result.Add(result, new(big.Int).SetUint64(value.Low))
+}
+
+func uint128ToBig(value serde.Uint128) *big.Int {
+	result := new(big.Int).Lsh(new(big.Int).SetUint64(value.High), 64)
+	return result.Add(result, new(big.Int).SetUint64(value.Low))
+}
+
+func lowBits(value *big.Int) uint64 {
+	return new(big.Int).And(value, maxU64).Uint64()
+}
+
+// Serialization helpers
+
+func SerializeUnit(value struct{}) Value {
+	return nil
+}
+
+func SerializeBool(value bool) Value {
+	return value
+}
+
+func SerializeI8(value int8) Value {
+	return Number(strconv.FormatInt(int64(value), 10))
+}
+
+func SerializeI16(value int16) Value {
+	return Number(strconv.FormatInt(int64(value), 10))
+}
+
+func SerializeI32(value int32) Value {
+	return Number(strconv.FormatInt(int64(value), 10))
+}
+
+func SerializeI64(value int64) Value {
+	return Number(strconv.FormatInt(value, 10))
+}
+
+func SerializeI128(value serde.Int128) Value {
+	return Number(int128ToBig(value).String())
+}
+
+func SerializeU8(value uint8) Value {
+	return Number(strconv.FormatUint(uint64(value), 10))
+}
+
+func SerializeU16(value uint16) Value {
+	return Number(strconv.FormatUint(uint64(value), 10))
+}
+
+func SerializeU32(value uint32) Value {
+	return Number(strconv.FormatUint(uint64(value), 10))
+}
+
+func SerializeU64(value uint64) Value {
+	return Number(strconv.FormatUint(value, 10))
+}
+
+func SerializeU128(value serde.Uint128) Value {
+	return Number(uint128ToBig(value).String())
+}
+
+func SerializeF32(value float32) Value {
+	if math.IsInf(float64(value), 0) || math.IsNaN(float64(value)) {
+		return nil
+	}
+	return Number(formatFloat(float64(value), 32))
+}
+
+func SerializeF64(value float64) Value {
+	if math.IsInf(value, 0) || math.IsNaN(value) {
+		return nil
+	}
+	return Number(formatFloat(value, 64))
+}
+
+func SerializeChar(value rune) Value {
+	return string(value)
+}
+
+func SerializeStr(value string) Value {
+	return value
+}
+
+func SerializeBytes(value []byte) Value {
+	result := make([]Value, len(value))
+	for i, b := range value {
+		result[i] = SerializeU8(b)
+	}
+	return result
+}
+
+// A variant of an enum that is not a unit variant (unit variants are written as strings).
+func SerializeVariant(name string, content Value) Value {
+	result := &Object{}
+	result.Put(name, content)
+	return result
+}
+
+// The key of a map entry, given the JSON value of the key.
+func KeyOf(value Value) string {
+	switch value := value.(type) {
+	case string:
+		return value
+	case Number:
+		return string(value)
+	case bool:
+		return strconv.FormatBool(value)
+	}
+	// Generated code only uses strings, numbers, booleans, and unit variants as keys.
+	panic("Map keys must be strings or numbers")
+}
+
+// Deserialization helpers
+
+func DeserializeUnit(value Value) (struct{}, error) {
+	if value != nil {
+		return struct{}{}, typeError(value, "null")
+	}
+	return struct{}{}, nil
+}
+
+func DeserializeBool(value Value) (bool, error) {
+	switch value := value.(type) {
+	case bool:
+		return value, nil
+	case Key:
+		if value == "true" || value == "false" {
+			return value == "true", nil
+		}
+	}
+	return false, typeError(value, "a boolean")
+}
+
+func DeserializeI8(value Value) (int8, error) {
+	result, err := readInteger(value, minI8, maxI8, false)
+	if err != nil {
+		return 0, err
+	}
+	return int8(result.Int64()), nil
+}
+
+func DeserializeI16(value Value) (int16, error) {
+	result, err := readInteger(value, minI16, maxI16, false)
+	if err != nil {
+		return 0, err
+	}
+	return int16(result.Int64()), nil
+}
+
+func DeserializeI32(value Value) (int32, error) {
+	result, err := readInteger(value, minI32, maxI32, false)
+	if err != nil {
+		return 0, err
+	}
+	return int32(result.Int64()), nil
+}
+
+func DeserializeI64(value Value) (int64, error) {
+	result, err := readInteger(value, minI64, maxI64, false)
+	if err != nil {
+		return 0, err
+	}
+	return result.Int64(), nil
+}
+
+func DeserializeI128(value Value) (serde.Int128, error) {
+	result, err := readInteger(value, minI128, maxI128, true)
+	if err != nil {
+		return serde.Int128{}, err
+	}
+	high := new(big.Int).Rsh(result, 64).Int64()
+	return serde.Int128{High: high, Low: lowBits(result)}, nil
+}
+
+func DeserializeU8(value Value) (uint8, error) {
+	result, err := readInteger(value, minU8, maxU8, false)
+	if err != nil {
+		return 0, err
+	}
+	return uint8(result.Uint64()), nil
+}
+
+func DeserializeU16(value Value) (uint16, error) {
+	result, err := readInteger(value, minU16, maxU16, false)
+	if err != nil {
+		return 0, err
+	}
+	return uint16(result.Uint64()), nil
+}
+
+func DeserializeU32(value Value) (uint32, error) {
+	result, err := readInteger(value, minU32, maxU32, false)
+	if err != nil {
+		return 0, err
+	}
+	return uint32(result.Uint64()), nil
+}
+
+func DeserializeU64(value Value) (uint64, error) {
+	result, err := readInteger(value, minU64, maxU64, false)
+	if err != nil {
+		return 0, err
+	}
+	return result.Uint64(), nil
+}
+
+func DeserializeU128(value Value) (serde.Uint128, error) {
+	result, err := readInteger(value, minU128, maxU128, false)
+	if err != nil {
+		return serde.Uint128{}, err
+	}
+	high := new(big.Int).Rsh(result, 64).Uint64()
+	return serde.Uint128{High: high, Low: lowBits(result)}, nil
+}
+
+func DeserializeF32(value Value) (float32, error) {
+	// Like `serde_json`, read the closest f64 value then convert it to f32.
+	result, err := readNumber(value)
+	return float32(result), err
+}
+
+func DeserializeF64(value Value) (float64, error) {
+	return readNumber(value)
+}
+
+func DeserializeChar(value Value) (rune, error) {
+	result, err := DeserializeStr(value)
+	if err != nil {
+		return 0, err
+	}
+	if utf8.RuneCountInString(result) != 1 {
+		return 0, typeError(value, "a character")
+	}
+	c, _ := utf8.DecodeRuneInString(result)
+	return c, nil
+}
+
+func DeserializeStr(value Value) (string, error) {
+	switch value := value.(type) {
+	case string:
+		return value, nil
+	case Key:
+		return string(value), nil
+	}
+	return "", typeError(value, "a string")
+}
+
+func DeserializeBytes(value Value) ([]byte, error) {
+	items, err := DeserializeArray(value, -1)
+	if err != nil {
+		return nil, err
+	}
+	result := make([]byte, len(items))
+	for i, item := range items {
+		if result[i], err = DeserializeU8(item); err != nil {
+			return nil, err
+		}
+	}
+	return result, nil
+}
+
+// The items of an array, of the given length if not negative (e.g. a tuple or a fixed-size array).
+func DeserializeArray(value Value, length int) ([]Value, error) {
+	items, ok := value.([]Value)
+	if !ok {
+		return nil, typeError(value, "an array")
+	}
+	if length >= 0 && len(items) != length {
+		return nil, fmt.Errorf("Invalid length: %d instead of %d", len(items), length)
+	}
+	return items, nil
+}
+
+func DeserializeObject(value Value) (*Object, error) {
+	object, ok := value.(*Object)
+	if !ok {
+		return nil, typeError(value, "an object")
+	}
+	return object, nil
+}
+
+func DeserializeVariant(value Value) (*Variant, error) {
+	switch value := value.(type) {
+	case string:
+		return &Variant{Name: value}, nil
+	case Key:
+		return &Variant{Name: string(value)}, nil
+	case *Object:
+		if len(value.Keys) == 1 {
+			return &Variant{Name: value.Keys[0], content: value.Values[0], hasContent: true}, nil
+		}
+	}
+	return nil, typeError(value, "a string or an object with a single entry")
+}
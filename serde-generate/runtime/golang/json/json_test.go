@@ -0,0 +1,101 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package json_test
+
+import (
+	"math"
+	"strings"
+	"testing"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/json"
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestParseStringify(t *testing.T) {
+	cases := []struct {
+		input    string
+		expected string
+	}{
+		{input: ` {"a" : [1, -2.5e3, true, null], "b": "é😀\n"} `, expected: "{\"a\":[1,-2.5e3,true,null],\"b\":\"é😀\\n\"}"},
+		{input: `"\u0001\/"`, expected: `"\u0001/"`},
+		{input: `[]`, expected: `[]`},
+	}
+
+	for _, tc := range cases {
+		t.Run(tc.input, func(t *testing.T) {
+			value, err := json.Parse([]byte(tc.input))
+			require.NoError(t, err)
+			output, err := json.Stringify(value)
+			require.NoError(t, err)
+			assert.Equal(t, tc.expected, string(output))
+		})
+	}
+}
+
+func TestParseErrors(t *testing.T) {
+	for _, input := range []string{``, `01`, `[1,]`, `{"a":1,}`, `"\ud83d"`, `"\x"`, `nul`, `1 2`, "\"\t\""} {
+		t.Run(input, func(t *testing.T) {
+			_, err := json.Parse([]byte(input))
+			require.Error(t, err)
+		})
+	}
+	t.Run("maximum depth", func(t *testing.T) {
+		_, err := json.Parse([]byte(strings.Repeat("[", 127) + strings.Repeat("]", 127)))
+		require.NoError(t, err)
+		_, err = json.Parse([]byte(strings.Repeat("[", 128) + strings.Repeat("]", 128)))
+		require.Error(t, err)
+	})
+}
+
+func TestFloats(t *testing.T) {
+	assert.Equal(t, json.Number("0.0"), json.SerializeF64(0))
+	assert.Equal(t, json.Number("-0.0"), json.SerializeF64(math.Copysign(0, -1)))
+	assert.Equal(t, json.Number("1e16"), json.SerializeF64(1e16))
+	assert.Equal(t, json.Number("1234567890123456.0"), json.SerializeF64(1234567890123456))
+	assert.Equal(t, json.Number("0.0001"), json.SerializeF64(1e-4))
+	assert.Equal(t, json.Number("1e-6"), json.SerializeF64(1e-6))
+	assert.Equal(t, json.Number("0.1"), json.SerializeF32(0.1))
+	assert.Equal(t, json.Number("1e13"), json.SerializeF32(1e13))
+	assert.Equal(t, json.Number("0.000001"), json.SerializeF32(1e-6))
+	assert.Equal(t, json.Number("1e-7"), json.SerializeF32(1e-7))
+	assert.Equal(t, nil, json.SerializeF64(math.Inf(1)))
+
+	value, err := json.DeserializeF32(json.Number("1e39"))
+	require.NoError(t, err)
+	assert.True(t, math.IsInf(float64(value), 1))
+	_, err = json.DeserializeF64(json.Number("1e309"))
+	require.Error(t, err)
+}
+
+func TestIntegers(t *testing.T) {
+	value, err := json.DeserializeI128(json.Number("-170141183460469231731687303715884105728"))
+	require.NoError(t, err)
+	assert.Equal(t, serde.Int128{High: math.MinInt64, Low: 0}, value)
+	assert.Equal(t, json.Number("-170141183460469231731687303715884105728"), json.SerializeI128(value))
+
+	_, err = json.DeserializeU8(json.Number("256"))
+	require.Error(t, err)
+	_, err = json.DeserializeI64(json.Number("-0"))
+	require.Error(t, err)
+	_, err = json.DeserializeI64(json.Number("1.0"))
+	require.Error(t, err)
+	key, err := json.DeserializeU64(json.Key("18446744073709551615"))
+	require.NoError(t, err)
+	assert.Equal(t, uint64(math.MaxUint64), key)
+}
+
+func TestSortKeys(t *testing.T) {
+	object := &json.Object{}
+	for _, key := range []string{"10", "-3", "2"} {
+		object.Put(key, nil)
+	}
+	object.SortKeys(json.NumberOrder)
+	assert.Equal(t, []string{"-3", "2", "10"}, object.Keys)
+	object.SortKeys(json.StringOrder)
+	assert.Equal(t, []string{"-3", "10", "2"}, object.Keys)
+	object.SortKeys(json.VariantOrder("2", "10", "-3"))
+	assert.Equal(t, []string{"2", "10", "-3"}, object.Keys)
+}
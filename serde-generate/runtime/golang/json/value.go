@@ -0,0 +1,143 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package json
+
+import (
+	"fmt"
+	"math/big"
+	"sort"
+)
+
+// A JSON value: `nil`, `bool`, `Number`, `string`, `Key`, `[]Value`, or `*Object`.
+type Value interface{}
+
+// A JSON number, kept as written so that integers of any size are read exactly.
+type Number string
+
+// The key of an entry of a JSON object read as a map. Like `serde_json`, keys may stand for
+// strings, numbers, booleans, or unit variants of enums.
+type Key string
+
+// The entries of a JSON object, in order and possibly with duplicate keys.
+type Object struct {
+	Keys   []string
+	Values []Value
+}
+
+func (obj *Object) Put(key string, value Value) {
+	obj.Keys = append(obj.Keys, key)
+	obj.Values = append(obj.Values, value)
+}
+
+// Value of the field `name` of a struct.
+func (obj *Object) Field(name string) (Value, error) {
+	index, err := obj.find(name)
+	if err != nil {
+		return nil, err
+	}
+	if index < 0 {
+		return nil, fmt.Errorf("Missing field: %s", name)
+	}
+	return obj.Values[index], nil
+}
+
+// Value of the optional field `name` of a struct, or `nil` if the field is missing.
+func (obj *Object) OptionalField(name string) (Value, error) {
+	index, err := obj.find(name)
+	if err != nil || index < 0 {
+		return nil, err
+	}
+	return obj.Values[index], nil
+}
+
+func (obj *Object) find(name string) (int, error) {
+	index := -1
+	for i, key := range obj.Keys {
+		if key != name {
+			continue
+		}
+		if index >= 0 {
+			return -1, fmt.Errorf("Duplicate field: %s", name)
+		}
+		index = i
+	}
+	return index, nil
+}
+
+// Sort the entries of an object written from a Go map, so that keys come in the order of a
+// `BTreeMap` in Rust.
+func (obj *Object) SortKeys(order KeyOrder) {
+	sort.Sort(sortedObject{obj, order})
+}
+
+type sortedObject struct {
+	obj   *Object
+	order KeyOrder
+}
+
+func (s sortedObject) Len() int { return len(s.obj.Keys) }
+
+func (s sortedObject) Less(i, j int) bool { return s.order(s.obj.Keys[i], s.obj.Keys[j]) }
+
+func (s sortedObject) Swap(i, j int) {
+	s.obj.Keys[i], s.obj.Keys[j] = s.obj.Keys[j], s.obj.Keys[i]
+	s.obj.Values[i], s.obj.Values[j] = s.obj.Values[j], s.obj.Values[i]
+}
+
+// Strict order on the keys of a map.
+type KeyOrder func(a, b string) bool
+
+// Order of strings and chars (by UTF-8 bytes) and of booleans (`false` first).
+func StringOrder(a, b string) bool {
+	return a < b
+}
+
+// Order of numbers.
+func NumberOrder(a, b string) bool {
+	x, okA := new(big.Float).SetString(a)
+	y, okB := new(big.Float).SetString(b)
+	if !okA || !okB {
+		return a < b
+	}
+	return x.Cmp(y) < 0
+}
+
+// Order of the unit variants of an enum, given the names of all its variants.
+func VariantOrder(names ...string) KeyOrder {
+	index := func(name string) int {
+		for i, n := range names {
+			if n == name {
+				return i
+			}
+		}
+		return len(names)
+	}
+	return func(a, b string) bool {
+		return index(a) < index(b)
+	}
+}
+
+// A variant of an enum, read either from a string (unit variants) or from an object with a
+// single entry.
+type Variant struct {
+	Name       string
+	content    Value
+	hasContent bool
+}
+
+// Check that the variant has no content, i.e. that it was written as a string or with `null`.
+func (v *Variant) Unit() error {
+	if v.content != nil {
+		return fmt.Errorf("Unexpected content for unit variant: %s", v.Name)
+	}
+	return nil
+}
+
+// Content of a variant that is not a unit variant.
+func (v *Variant) Value() (Value, error) {
+	if !v.hasContent {
+		return nil, fmt.Errorf("Missing content for variant: %s", v.Name)
+	}
+	return v.content, nil
+}
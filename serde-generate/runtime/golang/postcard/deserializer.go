@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package postcard
+
+import (
+	"errors"
+	"math"
+	"unicode/utf8"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+)
+
+// MaxSequenceLength is max length supported in practice (e.g. in Java).
+const MaxSequenceLength = (1 << 31) - 1
+
+// `deserializer` extends `serde.BinaryDeserializer` to implement `serde.Deserializer`.
+type deserializer struct {
+	serde.BinaryDeserializer
+}
+
+func NewDeserializer(input []byte) serde.Deserializer {
+	return &deserializer{*serde.NewBinaryDeserializer(input, math.MaxUint64)}
+}
+
+// Read a varint-encoded value of at most 128 bits.
+func (d *deserializer) deserializeVarint(bits uint) (high uint64, low uint64, err error) {
+	for shift := uint(0); shift < bits; shift += 7 {
+		x, err := d.Buffer.ReadByte()
+		if err != nil {
+			return 0, 0, err
+		}
+		digit := uint64(x & 0x7f)
+		if bits-shift < 7 && (digit>>(bits-shift)) != 0 {
+			return 0, 0, errors.New("overflow while parsing varint-encoded value")
+		}
+		if shift < 64 {
+			low |= digit << shift
+			if shift > 57 {
+				high |= digit >> (64 - shift)
+			}
+		} else {
+			high |= digit << (shift - 64)
+		}
+		if x&0x80 == 0 {
+			return high, low, nil
+		}
+	}
+	return 0, 0, errors.New("varint-encoded value is too long")
+}
+
+func (d *deserializer) DeserializeChar() (rune, error) {
+	value, err := d.DeserializeStr()
+	if err != nil {
+		return 0, err
+	}
+	if utf8.RuneCountInString(value) != 1 {
+		return 0, errors.New("expecting a single character")
+	}
+	ret, _ := utf8.DecodeRuneInString(value)
+	return ret, nil
+}
+
+func (d *deserializer) DeserializeU16() (uint16, error) {
+	_, ret, err := d.deserializeVarint(16)
+	return uint16(ret), err
+}
+
+func (d *deserializer) DeserializeU32() (uint32, error) {
+	_, ret, err := d.deserializeVarint(32)
+	return uint32(ret), err
+}
+
+func (d *deserializer) DeserializeU64() (uint64, error) {
+	_, ret, err := d.deserializeVarint(64)
+	return ret, err
+}
+
+func (d *deserializer) DeserializeU128() (serde.Uint128, error) {
+	high, low, err := d.deserializeVarint(128)
+	return serde.Uint128{High: high, Low: low}, err
+}
+
+func (d *deserializer) DeserializeI16() (int16, error) {
+	_, ret, err := d.deserializeVarint(16)
+	return int16(ret>>1) ^ -int16(ret&1), err
+}
+
+func (d *deserializer) DeserializeI32() (int32, error) {
+	_, ret, err := d.deserializeVarint(32)
+	return int32(ret>>1) ^ -int32(ret&1), err
+}
+
+func (d *deserializer) DeserializeI64() (int64, error) {
+	_, ret, err := d.deserializeVarint(64)
+	return int64(ret>>1) ^ -int64(ret&1), err
+}
+
+func (d *deserializer) DeserializeI128() (serde.Int128, error) {
+	high, low, err := d.deserializeVarint(128)
+	sign := -(low & 1)
+	return serde.Int128{
+		High: int64((high >> 1) ^ sign),
+		Low:  ((low >> 1) | (high << 63)) ^ sign,
+	}, err
+}
+
+func (d *deserializer) DeserializeF32() (float32, error) {
+	ret, err := d.BinaryDeserializer.DeserializeU32()
+	return math.Float32frombits(ret), err
+}
+
+func (d *deserializer) DeserializeF64() (float64, error) {
+	ret, err := d.BinaryDeserializer.DeserializeU64()
+	return math.Float64frombits(ret), err
+}
+
+func (d *deserializer) DeserializeBytes() ([]byte, error) {
+	return d.BinaryDeserializer.DeserializeBytes(d.DeserializeLen)
+}
+
+func (d *deserializer) DeserializeStr() (string, error) {
+	return d.BinaryDeserializer.DeserializeStr(d.DeserializeLen)
+}
+
+func (d *deserializer) DeserializeLen() (uint64, error) {
+	_, ret, err := d.deserializeVarint(64)
+	if ret > MaxSequenceLength {
+		return 0, errors.New("length is too large")
+	}
+	return ret, err
+}
+
+func (d *deserializer) DeserializeVariantIndex() (uint32, error) {
+	_, ret, err := d.deserializeVarint(32)
+	return uint32(ret), err
+}
+
+func (d *deserializer) CheckThatKeySlicesAreIncreasing(key1, key2 serde.Slice) error {
+	// No need to check key ordering in Postcard.
+	return nil
+}
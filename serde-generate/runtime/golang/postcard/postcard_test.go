@@ -0,0 +1,63 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package postcard_test
+
+import (
+	"fmt"
+	"testing"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/postcard"
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestSerializeDeserializeI64(t *testing.T) {
+	cases := []struct {
+		target   int64
+		expected []byte
+	}{
+		{target: 0, expected: []byte{0}},
+		{target: -1, expected: []byte{1}},
+		{target: 64, expected: []byte{0x80, 1}},
+		{target: -9223372036854775808, expected: []byte{0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 1}},
+	}
+
+	for _, tc := range cases {
+		t.Run(fmt.Sprintf("%d", tc.target), func(t *testing.T) {
+			s := postcard.NewSerializer()
+			require.NoError(t, s.SerializeI64(tc.target))
+			assert.Equal(t, tc.expected, s.GetBytes())
+
+			d := postcard.NewDeserializer(tc.expected)
+			deserialized, err := d.DeserializeI64()
+			require.NoError(t, err)
+			assert.Equal(t, tc.target, deserialized)
+		})
+	}
+}
+
+func TestSerializeDeserializeU128(t *testing.T) {
+	value := serde.Uint128{High: 1, Low: 0}
+	expected := []byte{0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 2}
+
+	s := postcard.NewSerializer()
+	require.NoError(t, s.SerializeU128(value))
+	assert.Equal(t, expected, s.GetBytes())
+
+	d := postcard.NewDeserializer(expected)
+	deserialized, err := d.DeserializeU128()
+	require.NoError(t, err)
+	assert.Equal(t, value, deserialized)
+}
+
+func TestDeserializeVarintOverflow(t *testing.T) {
+	d := postcard.NewDeserializer([]byte{0xff, 0xff, 0xff, 0xff, 0x10})
+	_, err := d.DeserializeU32()
+	assert.Error(t, err)
+
+	d = postcard.NewDeserializer([]byte{0x80, 0x80, 0x80, 0x80, 0x80, 0})
+	_, err = d.DeserializeVariantIndex()
+	assert.Error(t, err)
+}
@@ -0,0 +1,119 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package postcard
+
+import (
+	"errors"
+	"math"
+	"unicode/utf8"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+)
+
+// `serializer` extends `serde.BinarySerializer` to implement `serde.Serializer`.
+type serializer struct {
+	serde.BinarySerializer
+}
+
+func NewSerializer() serde.Serializer {
+	return &serializer{*serde.NewBinarySerializer(math.MaxUint64)}
+}
+
+func (s *serializer) serializeVarint(value uint64) {
+	for value >= 0x80 {
+		s.Buffer.WriteByte(byte(value&0x7f) | 0x80)
+		value >>= 7
+	}
+	s.Buffer.WriteByte(byte(value))
+}
+
+func (s *serializer) serializeVarint128(high uint64, low uint64) {
+	for high != 0 || low >= 0x80 {
+		s.Buffer.WriteByte(byte(low&0x7f) | 0x80)
+		low = (low >> 7) | (high << 57)
+		high >>= 7
+	}
+	s.Buffer.WriteByte(byte(low))
+}
+
+func (s *serializer) SerializeChar(value rune) error {
+	if !utf8.ValidRune(value) {
+		return errors.New("invalid unicode character")
+	}
+	return s.SerializeStr(string(value))
+}
+
+func (s *serializer) SerializeU16(value uint16) error {
+	s.serializeVarint(uint64(value))
+	return nil
+}
+
+func (s *serializer) SerializeU32(value uint32) error {
+	s.serializeVarint(uint64(value))
+	return nil
+}
+
+func (s *serializer) SerializeU64(value uint64) error {
+	s.serializeVarint(value)
+	return nil
+}
+
+func (s *serializer) SerializeU128(value serde.Uint128) error {
+	s.serializeVarint128(value.High, value.Low)
+	return nil
+}
+
+// Signed integers use a "zigzag" encoding before the varint encoding.
+func (s *serializer) SerializeI16(value int16) error {
+	s.serializeVarint(uint64(uint16((value << 1) ^ (value >> 15))))
+	return nil
+}
+
+func (s *serializer) SerializeI32(value int32) error {
+	s.serializeVarint(uint64(uint32((value << 1) ^ (value >> 31))))
+	return nil
+}
+
+func (s *serializer) SerializeI64(value int64) error {
+	s.serializeVarint(uint64((value << 1) ^ (value >> 63)))
+	return nil
+}
+
+func (s *serializer) SerializeI128(value serde.Int128) error {
+	sign := uint64(value.High >> 63)
+	high := (uint64(value.High) << 1) | (value.Low >> 63)
+	low := value.Low << 1
+	s.serializeVarint128(high^sign, low^sign)
+	return nil
+}
+
+func (s *serializer) SerializeF32(value float32) error {
+	return s.BinarySerializer.SerializeU32(math.Float32bits(value))
+}
+
+func (s *serializer) SerializeF64(value float64) error {
+	return s.BinarySerializer.SerializeU64(math.Float64bits(value))
+}
+
+func (s *serializer) SerializeStr(value string) error {
+	return s.BinarySerializer.SerializeStr(value, s.SerializeLen)
+}
+
+func (s *serializer) SerializeBytes(value []byte) error {
+	return s.BinarySerializer.SerializeBytes(value, s.SerializeLen)
+}
+
+func (s *serializer) SerializeLen(value uint64) error {
+	s.serializeVarint(value)
+	return nil
+}
+
+func (s *serializer) SerializeVariantIndex(value uint32) error {
+	s.serializeVarint(uint64(value))
+	return nil
+}
+
+func (s *serializer) SortMapEntries(offsets []uint64) {
+	// No need to sort map entries in Postcard.
+}
@@ -0,0 +1,105 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package scale
+
+import (
+	"errors"
+	"math"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+)
+
+// Maximum length allowed for sequences (vectors, bytes, strings) and maps.
+const MaxSequenceLength = (1 << 31) - 1
+
+// `deserializer` extends `serde.BinaryDeserializer` to implement `serde.Deserializer`.
+type deserializer struct {
+	serde.BinaryDeserializer
+}
+
+func NewDeserializer(input []byte) serde.Deserializer {
+	return &deserializer{*serde.NewBinaryDeserializer(input, math.MaxUint64)}
+}
+
+func (d *deserializer) DeserializeF32() (float32, error) {
+	ret, err := d.BinaryDeserializer.DeserializeU32()
+	return math.Float32frombits(ret), err
+}
+
+func (d *deserializer) DeserializeF64() (float64, error) {
+	ret, err := d.BinaryDeserializer.DeserializeU64()
+	return math.Float64frombits(ret), err
+}
+
+func (d *deserializer) DeserializeBytes() ([]byte, error) {
+	return d.BinaryDeserializer.DeserializeBytes(d.DeserializeLen)
+}
+
+func (d *deserializer) DeserializeStr() (string, error) {
+	return d.BinaryDeserializer.DeserializeStr(d.DeserializeLen)
+}
+
+func (d *deserializer) deserializeCompactU32() (uint32, error) {
+	prefix, err := d.BinaryDeserializer.DeserializeU8()
+	if err != nil {
+		return 0, err
+	}
+	var value, minimum uint32
+	switch prefix & 3 {
+	case 0:
+		return uint32(prefix >> 2), nil
+	case 1:
+		high, err := d.BinaryDeserializer.DeserializeU8()
+		if err != nil {
+			return 0, err
+		}
+		value = (uint32(prefix) | uint32(high)<<8) >> 2
+		minimum = 1 << 6
+	case 2:
+		value = uint32(prefix)
+		for shift := 8; shift < 32; shift += 8 {
+			b, err := d.BinaryDeserializer.DeserializeU8()
+			if err != nil {
+				return 0, err
+			}
+			value |= uint32(b) << shift
+		}
+		value >>= 2
+		minimum = 1 << 14
+	default:
+		if prefix != 3 {
+			return 0, errors.New("compact integer does not fit in 32 bits")
+		}
+		value, err = d.BinaryDeserializer.DeserializeU32()
+		if err != nil {
+			return 0, err
+		}
+		minimum = 1 << 30
+	}
+	if value < minimum {
+		return 0, errors.New("compact integer is not canonically encoded")
+	}
+	return value, nil
+}
+
+func (d *deserializer) DeserializeLen() (uint64, error) {
+	ret, err := d.deserializeCompactU32()
+	if err != nil {
+		return 0, err
+	}
+	if ret > MaxSequenceLength {
+		return 0, errors.New("length is too large")
+	}
+	return uint64(ret), nil
+}
+
+func (d *deserializer) DeserializeVariantIndex() (uint32, error) {
+	ret, err := d.BinaryDeserializer.DeserializeU8()
+	return uint32(ret), err
+}
+
+func (d *deserializer) CheckThatKeySlicesAreIncreasing(key1, key2 serde.Slice) error {
+	// No need to check key ordering in SCALE.
+	return nil
+}
@@ -0,0 +1,62 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package scale_test
+
+import (
+	"math"
+	"testing"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/scale"
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestSerializeDeserializeLen(t *testing.T) {
+	cases := []struct {
+		length uint64
+		bytes  []byte
+	}{
+		{63, []byte{0xfc}},
+		{64, []byte{0x01, 0x01}},
+		{16384, []byte{0x02, 0x00, 0x01, 0x00}},
+		{1 << 30, []byte{0x03, 0x00, 0x00, 0x00, 0x40}},
+	}
+	for _, c := range cases {
+		s := scale.NewSerializer()
+		require.NoError(t, s.SerializeLen(c.length))
+		assert.Equal(t, c.bytes, s.GetBytes())
+
+		d := scale.NewDeserializer(c.bytes)
+		deserialized, err := d.DeserializeLen()
+		require.NoError(t, err)
+		assert.Equal(t, c.length, deserialized)
+	}
+
+	d := scale.NewDeserializer([]byte{0x01, 0x00})
+	_, err := d.DeserializeLen()
+	assert.Error(t, err)
+
+	d = scale.NewDeserializer([]byte{0x07, 0, 0, 0, 0, 1})
+	_, err = d.DeserializeLen()
+	assert.Error(t, err)
+}
+
+func TestSerializeDeserializeVariantIndex(t *testing.T) {
+	s := scale.NewSerializer()
+	require.NoError(t, s.SerializeVariantIndex(255))
+	assert.Equal(t, []byte{255}, s.GetBytes())
+	assert.Error(t, s.SerializeVariantIndex(256))
+
+	d := scale.NewDeserializer([]byte{255})
+	deserialized, err := d.DeserializeVariantIndex()
+	require.NoError(t, err)
+	assert.Equal(t, uint32(255), deserialized)
+}
+
+func TestAcceptNaN(t *testing.T) {
+	d := scale.NewDeserializer([]byte{0, 0, 0xc0, 0x7f})
+	value, err := d.DeserializeF32()
+	require.NoError(t, err)
+	assert.True(t, math.IsNaN(float64(value)))
+}
@@ -0,0 +1,70 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package scale
+
+import (
+	"errors"
+	"math"
+
+	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
+)
+
+// `serializer` extends `serde.BinarySerializer` to implement `serde.Serializer`.
+type serializer struct {
+	serde.BinarySerializer
+}
+
+func NewSerializer() serde.Serializer {
+	return &serializer{*serde.NewBinarySerializer(math.MaxUint64)}
+}
+
+func (s *serializer) SerializeF32(value float32) error {
+	return s.BinarySerializer.SerializeU32(math.Float32bits(value))
+}
+
+func (s *serializer) SerializeF64(value float64) error {
+	return s.BinarySerializer.SerializeU64(math.Float64bits(value))
+}
+
+func (s *serializer) SerializeStr(value string) error {
+	return s.BinarySerializer.SerializeStr(value, s.SerializeLen)
+}
+
+func (s *serializer) SerializeBytes(value []byte) error {
+	return s.BinarySerializer.SerializeBytes(value, s.SerializeLen)
+}
+
+func (s *serializer) serializeCompactU32(value uint32) error {
+	switch {
+	case value < 1<<6:
+		return s.BinarySerializer.SerializeU8(uint8(value << 2))
+	case value < 1<<14:
+		return s.BinarySerializer.SerializeU16(uint16(value<<2 | 1))
+	case value < 1<<30:
+		return s.BinarySerializer.SerializeU32(value<<2 | 2)
+	default:
+		if err := s.BinarySerializer.SerializeU8(3); err != nil {
+			return err
+		}
+		return s.BinarySerializer.SerializeU32(value)
+	}
+}
+
+func (s *serializer) SerializeLen(value uint64) error {
+	if value > MaxSequenceLength {
+		return errors.New("length is too large")
+	}
+	return s.serializeCompactU32(uint32(value))
+}
+
+func (s *serializer) SerializeVariantIndex(value uint32) error {
+	if value > math.MaxUint8 {
+		return errors.New("variant index is too large")
+	}
+	return s.BinarySerializer.SerializeU8(uint8(value))
+}
+
+func (s *serializer) SortMapEntries(offsets []uint64) {
+	// No need to sort map entries in SCALE.
+}
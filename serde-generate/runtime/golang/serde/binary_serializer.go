@@ -9,7 +9,7 @@ import (
 )
 
 // `BinarySerializer` is a partial implementation of the `Serializer` interface.
-// It is used as an embedded struct by the Bincode and BCS serializers.
+// It is used as an embedded struct by the Bincode, BCS and Postcard serializers.
 type BinarySerializer struct {
 	Buffer               bytes.Buffer
 	containerDepthBudget uint64
@@ -0,0 +1,109 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package serde
+
+import "bytes"
+
+// Three-way comparisons following the order of Rust's derived `Ord` instances.
+
+func CompareBool(a, b bool) int {
+	if a == b {
+		return 0
+	}
+	if a {
+		return 1
+	}
+	return -1
+}
+
+func CompareInt64(a, b int64) int {
+	if a < b {
+		return -1
+	}
+	if a > b {
+		return 1
+	}
+	return 0
+}
+
+func CompareUint64(a, b uint64) int {
+	if a < b {
+		return -1
+	}
+	if a > b {
+		return 1
+	}
+	return 0
+}
+
+// Go strings are compared bytewise, that is, by UTF-8 encoding as in Rust.
+func CompareStr(a, b string) int {
+	if a < b {
+		return -1
+	}
+	if a > b {
+		return 1
+	}
+	return 0
+}
+
+func CompareBytes(a, b []byte) int {
+	return bytes.Compare(a, b)
+}
+
+func (obj Int128) Compare(other Int128) int {
+	if result := CompareInt64(obj.High, other.High); result != 0 {
+		return result
+	}
+	return CompareUint64(obj.Low, other.Low)
+}
+
+func (obj Uint128) Compare(other Uint128) int {
+	if result := CompareUint64(obj.High, other.High); result != 0 {
+		return result
+	}
+	return CompareUint64(obj.Low, other.Low)
+}
+
+// Hashes are combined in the style of FNV-1a, eight bytes at a time.
+
+const fnvOffset uint64 = 14695981039346656037
+const fnvPrime uint64 = 1099511628211
+
+func HashCombine(seed uint64, value uint64) uint64 {
+	hash := seed ^ fnvOffset
+	for i := 0; i < 8; i++ {
+		hash ^= (value >> (8 * i)) & 0xff
+		hash *= fnvPrime
+	}
+	return hash
+}
+
+func HashBool(value bool) uint64 {
+	if value {
+		return 1
+	}
+	return 0
+}
+
+func HashStr(value string) uint64 {
+	hash := fnvOffset
+	for i := 0; i < len(value); i++ {
+		hash ^= uint64(value[i])
+		hash *= fnvPrime
+	}
+	return hash
+}
+
+func HashBytes(value []byte) uint64 {
+	return HashStr(string(value))
+}
+
+func (obj Int128) Hash() uint64 {
+	return HashCombine(uint64(obj.High), obj.Low)
+}
+
+func (obj Uint128) Hash() uint64 {
+	return HashCombine(obj.High, obj.Low)
+}
@@ -0,0 +1,87 @@
+-- Copyright (c) Facebook, Inc. and its affiliates
+-- SPDX-License-Identifier: MIT OR Apache-2.0
+
+-- | Runtime of the BCS encoding.
+module Bcs
+  ( maxLength,
+    maxContainerDepth,
+    encoding,
+    serialize,
+    deserialize,
+  )
+where
+
+import Data.Bits (shiftL, shiftR, (.&.), (.|.))
+import qualified Data.ByteString as ByteString
+import qualified Data.ByteString.Builder as Builder
+import Data.Word (Word32)
+import qualified Serde
+
+maxLength :: Int
+maxLength = 2 ^ (31 :: Int) - 1
+
+maxContainerDepth :: Int
+maxContainerDepth = 500
+
+encoding :: Serde.Encoding
+encoding =
+  Serde.Encoding
+    { Serde.maxContainerDepth = Just maxContainerDepth,
+      Serde.hasCanonicalMaps = True,
+      Serde.putLen = putLen,
+      Serde.putVariantIndex = putUleb128AsU32,
+      Serde.putInteger = Serde.putLittleEndianInteger,
+      Serde.putF32 = \_ -> Serde.serializationError "Not implemented: f32 serialization",
+      Serde.putF64 = \_ -> Serde.serializationError "Not implemented: f64 serialization",
+      Serde.getLen = getLen,
+      Serde.getVariantIndex = getUleb128AsU32,
+      Serde.getInteger = Serde.getLittleEndianInteger,
+      Serde.getF32 = Serde.deserializationError "Not implemented: f32 deserialization",
+      Serde.getF64 = Serde.deserializationError "Not implemented: f64 deserialization"
+    }
+
+putLen :: Int -> Serde.Serializer ()
+putLen len
+  | len > maxLength = Serde.serializationError "Incorrect length value"
+  | otherwise = putUleb128AsU32 (fromIntegral len)
+
+putUleb128AsU32 :: Word32 -> Serde.Serializer ()
+putUleb128AsU32 value
+  | value >= 0x80 = do
+      Serde.putBuilder (Builder.word8 (fromIntegral (value .&. 0x7F) .|. 0x80))
+      putUleb128AsU32 (value `shiftR` 7)
+  | otherwise = Serde.putBuilder (Builder.word8 (fromIntegral value))
+
+getLen :: Serde.Deserializer Int
+getLen = do
+  len <- getUleb128AsU32
+  if fromIntegral len > maxLength
+    then Serde.deserializationError "Incorrect length value"
+    else pure (fromIntegral len)
+
+getUleb128AsU32 :: Serde.Deserializer Word32
+getUleb128AsU32 = loop 0 0
+  where
+    loop :: Integer -> Int -> Serde.Deserializer Word32
+    loop value shift
+      | shift > 28 = overflow
+      | otherwise = do
+          byte <- Serde.getWord8
+          let digit = byte .&. 0x7F
+              value' = value .|. (fromIntegral digit `shiftL` shift)
+          if value' > fromIntegral (maxBound :: Word32)
+            then overflow
+            else
+              if digit == byte
+                then
+                  if shift > 0 && digit == 0
+                    then Serde.deserializationError "Invalid uleb128 number (unexpected zero digit)"
+                    else pure (fromIntegral value')
+                else loop value' (shift + 7)
+    overflow = Serde.deserializationError "Overflow while parsing uleb128-encoded uint32 value"
+
+serialize :: (Serde.Serialize a) => a -> Either Serde.SerializationError ByteString.ByteString
+serialize = Serde.runSerializer encoding . Serde.serialize
+
+deserialize :: (Serde.Deserialize a) => ByteString.ByteString -> Either Serde.DeserializationError a
+deserialize = Serde.runDeserializer encoding Serde.deserialize
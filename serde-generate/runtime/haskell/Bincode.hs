@@ -0,0 +1,172 @@
+-- Copyright (c) Facebook, Inc. and its affiliates
+-- SPDX-License-Identifier: MIT OR Apache-2.0
+
+-- | Runtime of the Bincode encoding. The default options are those of @bincode::serialize@.
+module Bincode
+  ( maxLength,
+    Options (..),
+    defaultOptions,
+    encoding,
+    encodingWith,
+    serialize,
+    deserialize,
+    serializeWith,
+    deserializeWith,
+  )
+where
+
+import qualified Data.ByteString as ByteString
+import qualified Data.ByteString.Builder as Builder
+import GHC.Float (castWord32ToFloat, castWord64ToDouble)
+import qualified Serde
+
+maxLength :: Int
+maxLength = 2 ^ (31 :: Int) - 1
+
+-- | Options of the encoding, mirroring the configuration of the Rust crate @bincode@.
+data Options = Options
+  { -- | Integers of at least 16 bits use a variable-length encoding (@with_varint_encoding@).
+    varintEncoding :: Bool,
+    -- | Fixed-size integers and floats are big-endian (@with_big_endian@).
+    bigEndian :: Bool,
+    -- | Maximum number of bytes written or read (@with_limit@).
+    limit :: Maybe Int,
+    -- | Whether deserialization accepts inputs that are not entirely read.
+    allowTrailingBytes :: Bool
+  }
+  deriving (Eq, Show)
+
+defaultOptions :: Options
+defaultOptions =
+  Options
+    { varintEncoding = False,
+      bigEndian = False,
+      limit = Nothing,
+      allowTrailingBytes = False
+    }
+
+encoding :: Serde.Encoding
+encoding = encodingWith defaultOptions
+
+-- | The encoding of the given options. Size limits are enforced by 'serializeWith' and
+-- 'deserializeWith'.
+encodingWith :: Options -> Serde.Encoding
+encodingWith options =
+  Serde.Encoding
+    { Serde.maxContainerDepth = Nothing,
+      Serde.hasCanonicalMaps = False,
+      Serde.putLen = putLen options,
+      Serde.putVariantIndex = putInteger options 4 False . toInteger,
+      Serde.putInteger = putInteger options,
+      Serde.putF32 = Serde.putBuilder . (if bigEndian options then Builder.floatBE else Builder.floatLE),
+      Serde.putF64 = Serde.putBuilder . (if bigEndian options then Builder.doubleBE else Builder.doubleLE),
+      Serde.getLen = getLen options,
+      Serde.getVariantIndex = fromInteger <$> getInteger options 4 False,
+      Serde.getInteger = getInteger options,
+      Serde.getF32 = castWord32ToFloat . fromInteger <$> getFixint options 4,
+      Serde.getF64 = castWord64ToDouble . fromInteger <$> getFixint options 8
+    }
+
+-- | Write a non-negative integer on `size` bytes in the configured byte order.
+putFixint :: Options -> Int -> Integer -> Serde.Serializer ()
+putFixint options size value =
+  Serde.putBuilder (mconcat [Builder.word8 (fromInteger (value `div` (256 ^ i))) | i <- order])
+  where
+    order = if bigEndian options then [size - 1, size - 2 .. 0] else [0 .. size - 1]
+
+putInteger :: Options -> Int -> Bool -> Integer -> Serde.Serializer ()
+putInteger options size signed value
+  | varintEncoding options = putVarint options (if signed then zigzag else value)
+  | otherwise = putFixint options size (value `mod` (256 ^ size))
+  where
+    zigzag = if value >= 0 then 2 * value else -2 * value - 1
+
+-- | Values below 251 take one byte, larger values a tag followed by a fixed-size integer.
+putVarint :: Options -> Integer -> Serde.Serializer ()
+putVarint options value
+  | value < 251 = Serde.putBuilder (Builder.word8 (fromInteger value))
+  | value < 2 ^ (16 :: Int) = tagged 251 2
+  | value < 2 ^ (32 :: Int) = tagged 252 4
+  | value < 2 ^ (64 :: Int) = tagged 253 8
+  | otherwise = tagged 254 16
+  where
+    tagged tag size = do
+      Serde.putBuilder (Builder.word8 tag)
+      putFixint options size value
+
+putLen :: Options -> Int -> Serde.Serializer ()
+putLen options len
+  | len > maxLength = Serde.serializationError "Incorrect length value"
+  | otherwise = putInteger options 8 False (toInteger len)
+
+-- | Read a non-negative integer on `size` bytes in the configured byte order.
+getFixint :: Options -> Int -> Serde.Deserializer Integer
+getFixint options size = do
+  bytes <- Serde.getBytes size
+  let ordered = if bigEndian options then ByteString.reverse bytes else bytes
+  pure (ByteString.foldr (\byte value -> value * 256 + toInteger byte) 0 ordered)
+
+getInteger :: Options -> Int -> Bool -> Serde.Deserializer Integer
+getInteger options size signed
+  | varintEncoding options = do
+      value <- getVarint options size
+      -- Undo the "zigzag" encoding.
+      pure (if signed then (if even value then value `div` 2 else -(value `div` 2) - 1) else value)
+  | otherwise = do
+      value <- getFixint options size
+      pure (if signed && value >= 2 ^ (8 * size - 1) then value - 2 ^ (8 * size) else value)
+
+-- | Read a varint-encoded value of at most `size` bytes.
+getVarint :: Options -> Int -> Serde.Deserializer Integer
+getVarint options size = do
+  tag <- Serde.getWord8
+  value <- case tag of
+    251 -> getFixint options 2
+    252 -> getFixint options 4
+    253 -> getFixint options 8
+    254 | size == 16 -> getFixint options 16
+    _
+      | tag <= 250 -> pure (toInteger tag)
+      | otherwise -> Serde.deserializationError "Invalid tag for varint-encoded value"
+  if value >= 256 ^ size
+    then Serde.deserializationError "Overflow while parsing varint-encoded value"
+    else pure value
+
+getLen :: Options -> Serde.Deserializer Int
+getLen options = do
+  len <- getInteger options 8 False
+  if len > toInteger maxLength
+    then Serde.deserializationError "Incorrect length value"
+    else pure (fromInteger len)
+
+limitExceeded :: String
+limitExceeded = "Exceeded the size limit of the encoding"
+
+serialize :: (Serde.Serialize a) => a -> Either Serde.SerializationError ByteString.ByteString
+serialize = serializeWith defaultOptions
+
+deserialize :: (Serde.Deserialize a) => ByteString.ByteString -> Either Serde.DeserializationError a
+deserialize = deserializeWith defaultOptions
+
+serializeWith :: (Serde.Serialize a) => Options -> a -> Either Serde.SerializationError ByteString.ByteString
+serializeWith options value = do
+  bytes <- Serde.runSerializer (encodingWith options) (Serde.serialize value)
+  case limit options of
+    Just size | ByteString.length bytes > size -> Left (Serde.SerializationError limitExceeded)
+    _ -> Right bytes
+
+-- | Deserialize a value with the given options. Reading beyond the size limit means reading
+-- beyond the end of the input truncated to the limit.
+deserializeWith :: (Serde.Deserialize a) => Options -> ByteString.ByteString -> Either Serde.DeserializationError a
+deserializeWith options input =
+  case Serde.runDeserializerWithRest (encodingWith options) Serde.deserialize truncated of
+    Left (Serde.DeserializationError "Input is not large enough")
+      | ByteString.length truncated < ByteString.length input ->
+          Left (Serde.DeserializationError limitExceeded)
+    Left error' -> Left error'
+    Right (value, rest)
+      | ByteString.null rest && ByteString.length truncated == ByteString.length input -> Right value
+      | allowTrailingBytes options -> Right value
+      | otherwise -> Left (Serde.DeserializationError "Some input bytes were not read")
+  where
+    truncated = maybe input (`ByteString.take` input) (limit options)
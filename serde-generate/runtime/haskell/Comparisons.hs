@@ -0,0 +1,17 @@
+-- Copyright (c) Facebook, Inc. and its affiliates
+-- SPDX-License-Identifier: MIT OR Apache-2.0
+
+{-# OPTIONS_GHC -Wno-orphans #-}
+
+-- | Hashing of the types of the runtime, imported by the generated code when comparisons
+-- are enabled. Requires the @hashable@ package, unlike the rest of the runtime.
+module Comparisons () where
+
+import Data.Hashable (Hashable (..))
+import Serde (Int128 (..), Word128 (..))
+
+instance Hashable Int128 where
+  hashWithSalt salt (Int128 value) = hashWithSalt salt value
+
+instance Hashable Word128 where
+  hashWithSalt salt (Word128 value) = hashWithSalt salt value
@@ -0,0 +1,471 @@
+-- Copyright (c) Facebook, Inc. and its affiliates
+-- SPDX-License-Identifier: MIT OR Apache-2.0
+
+-- | Common runtime of the binary encodings used by the generated code.
+--
+-- Values are written by the 'Serializer' monad into a 'Builder.Builder' and read back by
+-- the 'Deserializer' monad from a strict 'ByteString.ByteString'. The parts that differ
+-- between encodings (lengths, variant indices, integers, floats, map ordering, container
+-- depth) are provided by an 'Encoding', such as @Bcs.encoding@ or @Bincode.encoding@.
+module Serde
+  ( -- * Errors
+    SerializationError (..),
+    DeserializationError (..),
+
+    -- * Encodings
+    Encoding (..),
+    ContainerDepth (..),
+
+    -- * Serialization
+    Serializer,
+    Serialize (..),
+    runSerializer,
+    serializationError,
+    putBuilder,
+    putLittleEndianInteger,
+    serializeLen,
+    serializeVariantIndex,
+    serializeOption,
+    serializeSeq,
+    serializeMap,
+    serializeArray,
+
+    -- * Deserialization
+    Deserializer,
+    Deserialize (..),
+    runDeserializer,
+    runDeserializerWithRest,
+    deserializationError,
+    getBytes,
+    getWord8,
+    getLittleEndianInteger,
+    deserializeLen,
+    deserializeVariantIndex,
+    unknownVariantIndex,
+    deserializeOption,
+    deserializeSeq,
+    deserializeMap,
+    deserializeArray,
+
+    -- * 128-bit integers
+    Int128 (..),
+    Word128 (..),
+  )
+where
+
+import Control.Monad (replicateM, when)
+import Data.Bits (shiftL, (.|.))
+import qualified Data.ByteString as ByteString
+import qualified Data.ByteString.Builder as Builder
+import qualified Data.ByteString.Lazy as Lazy
+import Data.Int (Int16, Int32, Int64, Int8)
+import qualified Data.List as List
+import qualified Data.Map.Strict as Map
+import Data.Text (Text)
+import qualified Data.Text.Encoding as Text
+import Data.Word (Word16, Word32, Word64, Word8)
+
+newtype SerializationError = SerializationError String
+  deriving (Eq, Show)
+
+newtype DeserializationError = DeserializationError String
+  deriving (Eq, Show)
+
+-- | Signed 128-bit integers, which are not provided by @base@.
+newtype Int128 = Int128 Integer
+  deriving (Eq, Ord, Show)
+
+-- | Unsigned 128-bit integers, which are not provided by @base@.
+newtype Word128 = Word128 Integer
+  deriving (Eq, Ord, Show)
+
+-- | The encoding-specific parts of serialization and deserialization.
+data Encoding = Encoding
+  { -- | Maximum number of nested containers (structs and enums), if any.
+    maxContainerDepth :: Maybe Int,
+    -- | Whether map entries are sorted by their serialized keys, and checked to be so
+    -- during deserialization.
+    hasCanonicalMaps :: Bool,
+    putLen :: Int -> Serializer (),
+    putVariantIndex :: Word32 -> Serializer (),
+    -- | Integers of at least 16 bits, given their size in bytes and whether they are signed.
+    putInteger :: Int -> Bool -> Integer -> Serializer (),
+    putF32 :: Float -> Serializer (),
+    putF64 :: Double -> Serializer (),
+    getLen :: Deserializer Int,
+    getVariantIndex :: Deserializer Word32,
+    getInteger :: Int -> Bool -> Deserializer Integer,
+    getF32 :: Deserializer Float,
+    getF64 :: Deserializer Double
+  }
+
+-- | Monads that keep track of the number of nested containers.
+class ContainerDepth m where
+  -- | Run the given computation inside one more container, failing if the maximum
+  -- container depth of the encoding is exceeded.
+  withContainerDepth :: m a -> m a
+
+-- * Serialization
+
+newtype Serializer a = Serializer
+  {unSerializer :: Encoding -> Int -> Either SerializationError (a, Builder.Builder)}
+
+instance Functor Serializer where
+  fmap f (Serializer m) = Serializer $ \encoding depth ->
+    fmap (\(a, output) -> (f a, output)) (m encoding depth)
+
+instance Applicative Serializer where
+  pure a = Serializer $ \_ _ -> Right (a, mempty)
+  mf <*> ma = do
+    f <- mf
+    f <$> ma
+
+instance Monad Serializer where
+  Serializer m >>= k = Serializer $ \encoding depth -> do
+    (a, output1) <- m encoding depth
+    (b, output2) <- unSerializer (k a) encoding depth
+    Right (b, output1 <> output2)
+
+instance ContainerDepth Serializer where
+  withContainerDepth (Serializer m) = Serializer $ \encoding depth ->
+    case maxContainerDepth encoding of
+      Just limit
+        | depth >= limit ->
+            Left (SerializationError "Exceeded maximum container depth")
+      _ -> m encoding (depth + 1)
+
+-- | Values that can be serialized in any encoding.
+class Serialize a where
+  serialize :: a -> Serializer ()
+
+-- | Serialize a value with the given encoding.
+runSerializer :: Encoding -> Serializer () -> Either SerializationError ByteString.ByteString
+runSerializer encoding (Serializer m) = do
+  ((), output) <- m encoding 0
+  Right (Lazy.toStrict (Builder.toLazyByteString output))
+
+serializationError :: String -> Serializer a
+serializationError message = Serializer $ \_ _ -> Left (SerializationError message)
+
+putBuilder :: Builder.Builder -> Serializer ()
+putBuilder output = Serializer $ \_ _ -> Right ((), output)
+
+askSerializerEncoding :: Serializer Encoding
+askSerializerEncoding = Serializer $ \encoding _ -> Right (encoding, mempty)
+
+-- | Run the given serializer and return its output instead of writing it.
+captureOutput :: Serializer () -> Serializer Lazy.ByteString
+captureOutput (Serializer m) = Serializer $ \encoding depth -> do
+  ((), output) <- m encoding depth
+  Right (Builder.toLazyByteString output, mempty)
+
+-- | Write the little-endian representation of a non-negative integer on `size` bytes.
+putLittleEndian :: Int -> Integer -> Serializer ()
+putLittleEndian size value =
+  putBuilder (mconcat [Builder.word8 (fromInteger (value `div` (256 ^ i))) | i <- [0 .. size - 1]])
+
+-- | Write the little-endian two's complement of an integer on `size` bytes.
+putLittleEndianInteger :: Int -> Bool -> Integer -> Serializer ()
+putLittleEndianInteger size _ value = putLittleEndian size (value `mod` (256 ^ size))
+
+serializeInteger :: Int -> Bool -> Integer -> Serializer ()
+serializeInteger size signed value = do
+  encoding <- askSerializerEncoding
+  putInteger encoding size signed value
+
+serializeLen :: Int -> Serializer ()
+serializeLen len = do
+  encoding <- askSerializerEncoding
+  putLen encoding len
+
+serializeVariantIndex :: Word32 -> Serializer ()
+serializeVariantIndex index = do
+  encoding <- askSerializerEncoding
+  putVariantIndex encoding index
+
+serializeOption :: (a -> Serializer ()) -> Maybe a -> Serializer ()
+serializeOption _ Nothing = putBuilder (Builder.word8 0)
+serializeOption f (Just value) = do
+  putBuilder (Builder.word8 1)
+  f value
+
+serializeSeq :: (a -> Serializer ()) -> [a] -> Serializer ()
+serializeSeq f values = do
+  serializeLen (length values)
+  mapM_ f values
+
+serializeMap :: (k -> Serializer ()) -> (v -> Serializer ()) -> Map.Map k v -> Serializer ()
+serializeMap fk fv values = do
+  serializeLen (Map.size values)
+  entries <- mapM (\(key, value) -> captureOutput (fk key >> fv value)) (Map.toList values)
+  encoding <- askSerializerEncoding
+  let sortedEntries = if hasCanonicalMaps encoding then List.sort entries else entries
+  putBuilder (foldMap Builder.lazyByteString sortedEntries)
+
+-- | Serialize a fixed-size array, represented by a list of the given length.
+serializeArray :: Int -> (a -> Serializer ()) -> [a] -> Serializer ()
+serializeArray size f values = do
+  when (length values /= size) $
+    serializationError
+      ( "Invalid length for fixed-size array: "
+          ++ show (length values)
+          ++ " instead of "
+          ++ show size
+      )
+  mapM_ f values
+
+instance Serialize () where
+  serialize () = pure ()
+
+instance Serialize Bool where
+  serialize value = putBuilder (Builder.word8 (if value then 1 else 0))
+
+instance Serialize Word8 where
+  serialize = putBuilder . Builder.word8
+
+instance Serialize Word16 where
+  serialize = serializeInteger 2 False . toInteger
+
+instance Serialize Word32 where
+  serialize = serializeInteger 4 False . toInteger
+
+instance Serialize Word64 where
+  serialize = serializeInteger 8 False . toInteger
+
+instance Serialize Word128 where
+  serialize (Word128 value)
+    | value < 0 || value >= 2 ^ (128 :: Int) =
+        serializationError "Invalid value for u128"
+    | otherwise = serializeInteger 16 False value
+
+instance Serialize Int8 where
+  serialize = putBuilder . Builder.int8
+
+instance Serialize Int16 where
+  serialize = serializeInteger 2 True . toInteger
+
+instance Serialize Int32 where
+  serialize = serializeInteger 4 True . toInteger
+
+instance Serialize Int64 where
+  serialize = serializeInteger 8 True . toInteger
+
+instance Serialize Int128 where
+  serialize (Int128 value)
+    | value < -(2 ^ (127 :: Int)) || value >= 2 ^ (127 :: Int) =
+        serializationError "Invalid value for i128"
+    | otherwise = serializeInteger 16 True value
+
+instance Serialize Float where
+  serialize value = do
+    encoding <- askSerializerEncoding
+    putF32 encoding value
+
+instance Serialize Double where
+  serialize value = do
+    encoding <- askSerializerEncoding
+    putF64 encoding value
+
+instance Serialize Char where
+  serialize _ = serializationError "Not implemented: char serialization"
+
+instance Serialize Text where
+  serialize value = serialize (Text.encodeUtf8 value)
+
+instance Serialize ByteString.ByteString where
+  serialize value = do
+    serializeLen (ByteString.length value)
+    putBuilder (Builder.byteString value)
+
+-- * Deserialization
+
+newtype Deserializer a = Deserializer
+  { unDeserializer ::
+      Encoding ->
+      Int ->
+      ByteString.ByteString ->
+      Either DeserializationError (a, ByteString.ByteString)
+  }
+
+instance Functor Deserializer where
+  fmap f (Deserializer m) = Deserializer $ \encoding depth input ->
+    fmap (\(a, rest) -> (f a, rest)) (m encoding depth input)
+
+instance Applicative Deserializer where
+  pure a = Deserializer $ \_ _ input -> Right (a, input)
+  mf <*> ma = do
+    f <- mf
+    f <$> ma
+
+instance Monad Deserializer where
+  Deserializer m >>= k = Deserializer $ \encoding depth input -> do
+    (a, rest) <- m encoding depth input
+    unDeserializer (k a) encoding depth rest
+
+instance ContainerDepth Deserializer where
+  withContainerDepth (Deserializer m) = Deserializer $ \encoding depth input ->
+    case maxContainerDepth encoding of
+      Just limit
+        | depth >= limit ->
+            Left (DeserializationError "Exceeded maximum container depth")
+      _ -> m encoding (depth + 1) input
+
+-- | Values that can be deserialized in any encoding.
+class Deserialize a where
+  deserialize :: Deserializer a
+
+-- | Deserialize a value with the given encoding, making sure that all the input is read.
+runDeserializer :: Encoding -> Deserializer a -> ByteString.ByteString -> Either DeserializationError a
+runDeserializer encoding d input = do
+  (value, rest) <- runDeserializerWithRest encoding d input
+  if ByteString.null rest
+    then Right value
+    else Left (DeserializationError "Some input bytes were not read")
+
+-- | Deserialize a value with the given encoding and also return the bytes that were not read.
+runDeserializerWithRest ::
+  Encoding -> Deserializer a -> ByteString.ByteString -> Either DeserializationError (a, ByteString.ByteString)
+runDeserializerWithRest encoding (Deserializer m) = m encoding 0
+
+deserializationError :: String -> Deserializer a
+deserializationError message = Deserializer $ \_ _ _ -> Left (DeserializationError message)
+
+askDeserializerEncoding :: Deserializer Encoding
+askDeserializerEncoding = Deserializer $ \encoding _ input -> Right (encoding, input)
+
+getBytes :: Int -> Deserializer ByteString.ByteString
+getBytes size = Deserializer $ \_ _ input ->
+  if ByteString.length input < size
+    then Left (DeserializationError "Input is not large enough")
+    else Right (ByteString.splitAt size input)
+
+getWord8 :: Deserializer Word8
+getWord8 = ByteString.head <$> getBytes 1
+
+-- | Run the given deserializer and also return the bytes that it read.
+captureInput :: Deserializer a -> Deserializer (a, ByteString.ByteString)
+captureInput (Deserializer m) = Deserializer $ \encoding depth input -> do
+  (value, rest) <- m encoding depth input
+  let bytes = ByteString.take (ByteString.length input - ByteString.length rest) input
+  Right ((value, bytes), rest)
+
+-- | Read the little-endian representation of a non-negative integer on `size` bytes.
+getLittleEndian :: Int -> Deserializer Integer
+getLittleEndian size = do
+  bytes <- getBytes size
+  pure (ByteString.foldr (\byte value -> value `shiftL` 8 .|. fromIntegral byte) 0 bytes)
+
+-- | Read the little-endian two's complement of an integer on `size` bytes.
+getLittleEndianInteger :: Int -> Bool -> Deserializer Integer
+getLittleEndianInteger size signed = do
+  value <- getLittleEndian size
+  pure (if signed && value >= 2 ^ (8 * size - 1) then value - 2 ^ (8 * size) else value)
+
+deserializeInteger :: Int -> Bool -> Deserializer Integer
+deserializeInteger size signed = do
+  encoding <- askDeserializerEncoding
+  getInteger encoding size signed
+
+deserializeLen :: Deserializer Int
+deserializeLen = askDeserializerEncoding >>= getLen
+
+deserializeVariantIndex :: Deserializer Word32
+deserializeVariantIndex = askDeserializerEncoding >>= getVariantIndex
+
+unknownVariantIndex :: String -> Word32 -> Deserializer a
+unknownVariantIndex name index =
+  deserializationError ("Unknown variant index for " ++ name ++ ": " ++ show index)
+
+deserializeOption :: Deserializer a -> Deserializer (Maybe a)
+deserializeOption d = do
+  tag <- getWord8
+  case tag of
+    0 -> pure Nothing
+    1 -> Just <$> d
+    _ -> deserializationError "Incorrect option value"
+
+deserializeSeq :: Deserializer a -> Deserializer [a]
+deserializeSeq d = do
+  len <- deserializeLen
+  replicateM len d
+
+deserializeMap :: (Ord k) => Deserializer k -> Deserializer v -> Deserializer (Map.Map k v)
+deserializeMap dk dv = do
+  len <- deserializeLen
+  encoding <- askDeserializerEncoding
+  let loop 0 _ values = pure values
+      loop n previousKey values = do
+        (key, keyBytes) <- captureInput dk
+        case previousKey of
+          Just previous
+            | hasCanonicalMaps encoding && previous >= keyBytes ->
+                deserializationError
+                  "Error while decoding map: keys are not serialized in the expected order"
+          _ -> pure ()
+        value <- dv
+        loop (n - 1) (Just keyBytes) (Map.insert key value values)
+  loop len Nothing Map.empty
+
+-- | Deserialize a fixed-size array into a list of the given length.
+deserializeArray :: Int -> Deserializer a -> Deserializer [a]
+deserializeArray = replicateM
+
+instance Deserialize () where
+  deserialize = pure ()
+
+instance Deserialize Bool where
+  deserialize = do
+    byte <- getWord8
+    case byte of
+      0 -> pure False
+      1 -> pure True
+      _ -> deserializationError "Incorrect boolean value"
+
+instance Deserialize Word8 where
+  deserialize = getWord8
+
+instance Deserialize Word16 where
+  deserialize = fromInteger <$> deserializeInteger 2 False
+
+instance Deserialize Word32 where
+  deserialize = fromInteger <$> deserializeInteger 4 False
+
+instance Deserialize Word64 where
+  deserialize = fromInteger <$> deserializeInteger 8 False
+
+instance Deserialize Word128 where
+  deserialize = Word128 <$> deserializeInteger 16 False
+
+instance Deserialize Int8 where
+  deserialize = fromIntegral <$> (deserialize :: Deserializer Word8)
+
+instance Deserialize Int16 where
+  deserialize = fromInteger <$> deserializeInteger 2 True
+
+instance Deserialize Int32 where
+  deserialize = fromInteger <$> deserializeInteger 4 True
+
+instance Deserialize Int64 where
+  deserialize = fromInteger <$> deserializeInteger 8 True
+
+instance Deserialize Int128 where
+  deserialize = Int128 <$> deserializeInteger 16 True
+
+instance Deserialize Float where
+  deserialize = askDeserializerEncoding >>= getF32
+
+instance Deserialize Double where
+  deserialize = askDeserializerEncoding >>= getF64
+
+instance Deserialize Char where
+  deserialize = deserializationError "Not implemented: char deserialization"
+
+instance Deserialize Text where
+  deserialize = do
+    bytes <- deserialize
+    case Text.decodeUtf8' bytes of
+      Left _ -> deserializationError "Incorrect UTF8 string"
+      Right value -> pure value
+
+instance Deserialize ByteString.ByteString where
+  deserialize = deserializeLen >>= getBytes
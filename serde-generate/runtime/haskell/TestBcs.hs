@@ -0,0 +1,86 @@
+-- Copyright (c) Facebook, Inc. and its affiliates
+-- SPDX-License-Identifier: MIT OR Apache-2.0
+
+module Main (main) where
+
+import qualified Bcs
+import Control.Monad (unless)
+import qualified Data.ByteString as ByteString
+import Data.Either (isLeft)
+import qualified Data.Map.Strict as Map
+import Data.Text (Text)
+import qualified Data.Text as Text
+import Data.Word (Word32, Word8)
+import qualified Serde
+import System.Exit (exitFailure)
+
+check :: String -> Bool -> IO ()
+check name condition = unless condition $ do
+  putStrLn ("Test failed: " ++ name)
+  exitFailure
+
+bytes :: [Word8] -> ByteString.ByteString
+bytes = ByteString.pack
+
+serializeWith :: Serde.Serializer () -> Either Serde.SerializationError ByteString.ByteString
+serializeWith = Serde.runSerializer Bcs.encoding
+
+deserializeWith :: Serde.Deserializer a -> ByteString.ByteString -> Either Serde.DeserializationError a
+deserializeWith = Serde.runDeserializer Bcs.encoding
+
+-- | A value serialized as the given number of nested containers.
+newtype Nested = Nested Int
+
+instance Serde.Serialize Nested where
+  serialize (Nested 0) = pure ()
+  serialize (Nested depth) = Serde.withContainerDepth (Serde.serialize (Nested (depth - 1)))
+
+main :: IO ()
+main = do
+  check "serialize u128" $
+    Bcs.serialize (Serde.Word128 (2 ^ (128 :: Int) - 1)) == Right (bytes (replicate 16 0xFF))
+  check "serialize u128 (one)" $
+    Bcs.serialize (Serde.Word128 1) == Right (bytes (1 : replicate 15 0))
+  check "serialize u128 (out of range)" $
+    isLeft (Bcs.serialize (Serde.Word128 (2 ^ (128 :: Int))))
+
+  check "serialize i128" $
+    Bcs.serialize (Serde.Int128 (-1)) == Right (bytes (replicate 16 0xFF))
+  check "serialize i128 (minimum)" $
+    Bcs.serialize (Serde.Int128 (-(2 ^ (127 :: Int)))) == Right (bytes (replicate 15 0 ++ [0x80]))
+  check "serialize i128 (out of range)" $
+    isLeft (Bcs.serialize (Serde.Int128 (2 ^ (127 :: Int))))
+  check "deserialize i128" $
+    Bcs.deserialize (bytes (replicate 16 0xFF)) == Right (Serde.Int128 (-1))
+
+  check "serialize uleb128" $
+    serializeWith (Serde.serializeLen 300) == Right (bytes [0xAC, 0x02])
+  check "deserialize uleb128" $
+    deserializeWith Serde.deserializeLen (bytes [0xAC, 0x02]) == Right 300
+  check "deserialize non-canonical uleb128" $
+    isLeft (deserializeWith Serde.deserializeLen (bytes [0x80, 0x00]))
+  check "deserialize uleb128 larger than the maximum length" $
+    isLeft (deserializeWith Serde.deserializeLen (bytes [0xFF, 0xFF, 0xFF, 0xFF, 0x08]))
+
+  check "serialize string" $
+    Bcs.serialize (Text.pack "\233") == Right (bytes [2, 0xC3, 0xA9])
+  check "deserialize string" $
+    Bcs.deserialize (bytes [2, 0xC3, 0xA9]) == Right (Text.pack "\233")
+  check "deserialize invalid string" $
+    isLeft (Bcs.deserialize (bytes [1, 0xFF]) :: Either Serde.DeserializationError Text)
+
+  -- Entries are sorted by their serialized keys rather than by keys.
+  let values = Map.fromList [(256 :: Word32, ()), (1, ())]
+      serializeValues = Serde.serializeMap Serde.serialize Serde.serialize
+      deserializeValues = Serde.deserializeMap Serde.deserialize Serde.deserialize
+  check "serialize map" $
+    serializeWith (serializeValues values) == Right (bytes [2, 0, 1, 0, 0, 1, 0, 0, 0])
+  check "deserialize map" $
+    deserializeWith deserializeValues (bytes [2, 0, 1, 0, 0, 1, 0, 0, 0]) == Right values
+  check "deserialize unordered map" $
+    isLeft (deserializeWith deserializeValues (bytes [2, 1, 0, 0, 0, 0, 1, 0, 0]))
+
+  check "maximum container depth" $
+    Bcs.serialize (Nested Bcs.maxContainerDepth) == Right ByteString.empty
+  check "exceeded container depth" $
+    isLeft (Bcs.serialize (Nested (Bcs.maxContainerDepth + 1)))
@@ -4,12 +4,169 @@
 package com.novi.bincode;
 
 import com.novi.serde.DeserializationError;
+import com.novi.serde.Int128;
 import com.novi.serde.Slice;
 import com.novi.serde.BinaryDeserializer;
+import com.novi.serde.Unsigned;
+import java.math.BigInteger;
+import java.nio.ByteOrder;
 
 public class BincodeDeserializer extends BinaryDeserializer {
+    private final boolean varint_encoding;
+    private final long limit;
+
     public BincodeDeserializer(byte[] input) {
+        this(input, false, false);
+    }
+
+    // See `BincodeSerializer` for the meaning of the options.
+    public BincodeDeserializer(byte[] input, boolean varint_encoding, boolean big_endian) {
+        this(input, varint_encoding, big_endian, Long.MAX_VALUE);
+    }
+
+    // Reading more than `limit` bytes fails, as with the option `with_limit` of the Rust crate.
+    public BincodeDeserializer(byte[] input, boolean varint_encoding, boolean big_endian, long limit) {
         super(input, Long.MAX_VALUE);
+        this.varint_encoding = varint_encoding;
+        this.limit = limit;
+        if (big_endian) {
+            this.input.order(ByteOrder.BIG_ENDIAN);
+        }
+    }
+
+    // Check that `size` more bytes may be read within the limit.
+    private void check_limit(long size) throws DeserializationError {
+        if (size > limit - input.position()) {
+            throw new DeserializationError("Exceeded the size limit of the encoding");
+        }
+    }
+
+    protected byte getByte() throws DeserializationError {
+        check_limit(1);
+        return super.getByte();
+    }
+
+    protected short getShort() throws DeserializationError {
+        check_limit(2);
+        return super.getShort();
+    }
+
+    protected int getInt() throws DeserializationError {
+        check_limit(4);
+        return super.getInt();
+    }
+
+    protected long getLong() throws DeserializationError {
+        check_limit(8);
+        return super.getLong();
+    }
+
+    protected float getFloat() throws DeserializationError {
+        check_limit(4);
+        return super.getFloat();
+    }
+
+    protected double getDouble() throws DeserializationError {
+        check_limit(8);
+        return super.getDouble();
+    }
+
+    protected void read(byte[] content) throws DeserializationError {
+        check_limit(content.length);
+        super.read(content);
+    }
+
+    protected byte[] getBytes(int length) throws DeserializationError {
+        // Check the length before allocating the content.
+        check_limit(length);
+        return super.getBytes(length);
+    }
+
+    // Read a value between 0 and 2^128 - 1 on 16 bytes.
+    private BigInteger read_fixint128() throws DeserializationError {
+        byte[] content = new byte[16];
+        read(content);
+        // Unsigned big-endian representation.
+        byte[] value = new byte[17];
+        for (int i = 0; i < 16; i++) {
+            value[i + 1] = input.order() == ByteOrder.BIG_ENDIAN ? content[i] : content[15 - i];
+        }
+        return new BigInteger(value);
+    }
+
+    // Read the value following a varint tag, for values of at most 64 bits.
+    private long read_varint_value(int tag) throws DeserializationError {
+        if (tag <= 250) {
+            return tag;
+        } else if (tag == 251) {
+            return getShort() & 0xFFFFL;
+        } else if (tag == 252) {
+            return getInt() & 0xFFFFFFFFL;
+        } else if (tag == 253) {
+            return getLong();
+        }
+        throw new DeserializationError("Invalid tag for varint-encoded value");
+    }
+
+    // Read a varint-encoded value of at most 64 bits.
+    private long deserialize_varint(int bits) throws DeserializationError {
+        long value = read_varint_value(getByte() & 0xFF);
+        if (bits < 64 && (value >>> bits) != 0) {
+            throw new DeserializationError("Overflow while parsing varint-encoded value");
+        }
+        return value;
+    }
+
+    private BigInteger deserialize_varint128() throws DeserializationError {
+        int tag = getByte() & 0xFF;
+        if (tag == 254) {
+            return read_fixint128();
+        }
+        long value = read_varint_value(tag);
+        BigInteger result = BigInteger.valueOf(value);
+        return value >= 0 ? result : result.add(BigInteger.ONE.shiftLeft(64));
+    }
+
+    private static long zigzag_decode(long value) {
+        return (value >>> 1) ^ -(value & 1);
+    }
+
+    public @Unsigned Short deserialize_u16() throws DeserializationError {
+        return varint_encoding ? Short.valueOf((short) deserialize_varint(16)) : super.deserialize_u16();
+    }
+
+    public @Unsigned Integer deserialize_u32() throws DeserializationError {
+        return varint_encoding ? Integer.valueOf((int) deserialize_varint(32)) : super.deserialize_u32();
+    }
+
+    public @Unsigned Long deserialize_u64() throws DeserializationError {
+        return varint_encoding ? Long.valueOf(deserialize_varint(64)) : super.deserialize_u64();
+    }
+
+    public @Unsigned @Int128 BigInteger deserialize_u128() throws DeserializationError {
+        return varint_encoding ? deserialize_varint128() : read_fixint128();
+    }
+
+    public Short deserialize_i16() throws DeserializationError {
+        return varint_encoding ? Short.valueOf((short) zigzag_decode(deserialize_varint(16))) : super.deserialize_i16();
+    }
+
+    public Integer deserialize_i32() throws DeserializationError {
+        return varint_encoding ? Integer.valueOf((int) zigzag_decode(deserialize_varint(32))) : super.deserialize_i32();
+    }
+
+    public Long deserialize_i64() throws DeserializationError {
+        return varint_encoding ? Long.valueOf(zigzag_decode(deserialize_varint(64))) : super.deserialize_i64();
+    }
+
+    public @Int128 BigInteger deserialize_i128() throws DeserializationError {
+        if (varint_encoding) {
+            BigInteger value = deserialize_varint128();
+            BigInteger result = value.shiftRight(1);
+            return value.testBit(0) ? result.negate().subtract(BigInteger.ONE) : result;
+        }
+        BigInteger value = read_fixint128();
+        return value.testBit(127) ? value.subtract(BigInteger.ONE.shiftLeft(128)) : value;
     }
 
     public Float deserialize_f32() throws DeserializationError {
@@ -21,7 +178,7 @@ public class BincodeDeserializer extends BinaryDeserializer {
     }
 
     public long deserialize_len() throws DeserializationError {
-        long value = getLong();
+        long value = varint_encoding ? deserialize_varint(64) : getLong();
         if (value < 0 || value > Integer.MAX_VALUE) {
             throw new DeserializationError("Incorrect length value");
         }
@@ -29,7 +186,7 @@ public class BincodeDeserializer extends BinaryDeserializer {
     }
 
     public int deserialize_variant_index() throws DeserializationError {
-        return getInt();
+        return varint_encoding ? (int) deserialize_varint(32) : getInt();
     }
 
     public void check_that_key_slices_are_increasing(Slice key1, Slice key2) throws DeserializationError {
@@ -3,28 +3,164 @@
 
 package com.novi.bincode;
 
+import com.novi.serde.Int128;
 import com.novi.serde.SerializationError;
 import com.novi.serde.BinarySerializer;
+import com.novi.serde.Unsigned;
+import java.math.BigInteger;
 
 public class BincodeSerializer extends BinarySerializer {
+    private final boolean varint_encoding;
+    private final boolean big_endian;
+
     public BincodeSerializer() {
+        this(false, false);
+    }
+
+    // `varint_encoding` and `big_endian` correspond to the options `with_varint_encoding`
+    // and `with_big_endian` of the Rust crate.
+    public BincodeSerializer(boolean varint_encoding, boolean big_endian) {
         super(Long.MAX_VALUE);
+        this.varint_encoding = varint_encoding;
+        this.big_endian = big_endian;
+    }
+
+    // Write the `size` lowest bytes of `value` in the configured byte order.
+    private void write_fixint(long value, int size) {
+        for (int i = 0; i < size; i++) {
+            int shift = big_endian ? 8 * (size - 1 - i) : 8 * i;
+            output.write((byte) (value >>> shift));
+        }
+    }
+
+    // Write a value between 0 and 2^128 - 1 on 16 bytes.
+    private void write_fixint(BigInteger value) {
+        for (int i = 0; i < 16; i++) {
+            int shift = big_endian ? 8 * (15 - i) : 8 * i;
+            output.write(value.shiftRight(shift).byteValue());
+        }
+    }
+
+    // Write an unsigned 64-bit value using the varint encoding.
+    private void serialize_varint(long value) {
+        if (Long.compareUnsigned(value, 250) <= 0) {
+            output.write((byte) value);
+        } else if (Long.compareUnsigned(value, 0xFFFFL) <= 0) {
+            output.write((byte) 251);
+            write_fixint(value, 2);
+        } else if (Long.compareUnsigned(value, 0xFFFFFFFFL) <= 0) {
+            output.write((byte) 252);
+            write_fixint(value, 4);
+        } else {
+            output.write((byte) 253);
+            write_fixint(value, 8);
+        }
+    }
+
+    private void serialize_varint(BigInteger value) {
+        if (value.bitLength() <= 64) {
+            serialize_varint(value.longValue());
+        } else {
+            output.write((byte) 254);
+            write_fixint(value);
+        }
+    }
+
+    private void serialize_unsigned(long value, int size) {
+        if (varint_encoding) {
+            serialize_varint(value);
+        } else {
+            write_fixint(value, size);
+        }
+    }
+
+    public void serialize_u16(@Unsigned Short value) throws SerializationError {
+        serialize_unsigned(value.shortValue() & 0xFFFFL, 2);
+    }
+
+    public void serialize_u32(@Unsigned Integer value) throws SerializationError {
+        serialize_unsigned(value.intValue() & 0xFFFFFFFFL, 4);
+    }
+
+    public void serialize_u64(@Unsigned Long value) throws SerializationError {
+        serialize_unsigned(value.longValue(), 8);
+    }
+
+    public void serialize_u128(@Unsigned @Int128 BigInteger value) throws SerializationError {
+        if (value.compareTo(BigInteger.ZERO) < 0 || !value.shiftRight(128).equals(BigInteger.ZERO)) {
+            throw new java.lang.IllegalArgumentException("Invalid value for an unsigned int128");
+        }
+        if (varint_encoding) {
+            serialize_varint(value);
+        } else {
+            write_fixint(value);
+        }
+    }
+
+    public void serialize_i16(Short value) throws SerializationError {
+        short val = value.shortValue();
+        if (varint_encoding) {
+            serialize_varint(((val << 1) ^ (val >> 15)) & 0xFFFFL);
+        } else {
+            write_fixint(val, 2);
+        }
+    }
+
+    public void serialize_i32(Integer value) throws SerializationError {
+        int val = value.intValue();
+        if (varint_encoding) {
+            serialize_varint(((val << 1) ^ (val >> 31)) & 0xFFFFFFFFL);
+        } else {
+            write_fixint(val, 4);
+        }
+    }
+
+    public void serialize_i64(Long value) throws SerializationError {
+        long val = value.longValue();
+        if (varint_encoding) {
+            serialize_varint((val << 1) ^ (val >> 63));
+        } else {
+            write_fixint(val, 8);
+        }
+    }
+
+    public void serialize_i128(@Int128 BigInteger value) throws SerializationError {
+        if (value.compareTo(BigInteger.ZERO) >= 0) {
+            if (!value.shiftRight(127).equals(BigInteger.ZERO)) {
+                throw new java.lang.IllegalArgumentException("Invalid value for a signed int128");
+            }
+        } else {
+            if (!value.add(BigInteger.ONE).negate().shiftRight(127).equals(BigInteger.ZERO)) {
+                throw new java.lang.IllegalArgumentException("Invalid value for a signed int128");
+            }
+        }
+        if (varint_encoding) {
+            if (value.compareTo(BigInteger.ZERO) >= 0) {
+                serialize_varint(value.shiftLeft(1));
+            } else {
+                serialize_varint(value.shiftLeft(1).negate().subtract(BigInteger.ONE));
+            }
+        } else if (value.compareTo(BigInteger.ZERO) >= 0) {
+            write_fixint(value);
+        } else {
+            write_fixint(value.add(BigInteger.ONE.shiftLeft(128)));
+        }
     }
 
     public void serialize_f32(Float value) throws SerializationError {
-        serialize_i32(Integer.valueOf(Float.floatToRawIntBits(value.floatValue())));
+        write_fixint(Float.floatToRawIntBits(value.floatValue()), 4);
     }
 
     public void serialize_f64(Double value) throws SerializationError {
-        serialize_i64(Long.valueOf(Double.doubleToRawLongBits(value.doubleValue())));
+        write_fixint(Double.doubleToRawLongBits(value.doubleValue()), 8);
     }
 
     public void serialize_len(long value) throws SerializationError {
-        serialize_u64(value);
+        serialize_unsigned(value, 8);
     }
 
     public void serialize_variant_index(int value) throws SerializationError {
-        serialize_u32(value);
+        serialize_unsigned(value & 0xFFFFFFFFL, 4);
     }
 
     public void sort_map_entries(int[] offsets) {
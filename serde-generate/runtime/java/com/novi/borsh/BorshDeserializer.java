@@ -0,0 +1,126 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.borsh;
+
+import java.math.BigInteger;
+import com.novi.serde.DeserializationError;
+import com.novi.serde.Int128;
+import com.novi.serde.Slice;
+import com.novi.serde.BinaryDeserializer;
+import com.novi.serde.Unsigned;
+
+public class BorshDeserializer extends BinaryDeserializer {
+    private final SortKeys sort_keys = new SortKeys();
+
+    public BorshDeserializer(byte[] input) {
+        super(input, Long.MAX_VALUE);
+    }
+
+    public @Unsigned Short deserialize_u16() throws DeserializationError {
+        int start = input.position();
+        Short value = super.deserialize_u16();
+        sort_keys.record_integer(input.array(), start, input.position(), false);
+        return value;
+    }
+
+    public @Unsigned Integer deserialize_u32() throws DeserializationError {
+        int start = input.position();
+        Integer value = super.deserialize_u32();
+        sort_keys.record_integer(input.array(), start, input.position(), false);
+        return value;
+    }
+
+    public @Unsigned Long deserialize_u64() throws DeserializationError {
+        int start = input.position();
+        Long value = super.deserialize_u64();
+        sort_keys.record_integer(input.array(), start, input.position(), false);
+        return value;
+    }
+
+    public @Unsigned @Int128 BigInteger deserialize_u128() throws DeserializationError {
+        int start = input.position();
+        BigInteger value = super.deserialize_u128();
+        sort_keys.record_integer(input.array(), start, input.position(), false);
+        return value;
+    }
+
+    public Byte deserialize_i8() throws DeserializationError {
+        int start = input.position();
+        Byte value = super.deserialize_i8();
+        sort_keys.record_integer(input.array(), start, input.position(), true);
+        return value;
+    }
+
+    public Short deserialize_i16() throws DeserializationError {
+        int start = input.position();
+        Short value = super.deserialize_i16();
+        sort_keys.record_integer(input.array(), start, input.position(), true);
+        return value;
+    }
+
+    public Integer deserialize_i32() throws DeserializationError {
+        int start = input.position();
+        Integer value = super.deserialize_i32();
+        sort_keys.record_integer(input.array(), start, input.position(), true);
+        return value;
+    }
+
+    public Long deserialize_i64() throws DeserializationError {
+        int start = input.position();
+        Long value = super.deserialize_i64();
+        sort_keys.record_integer(input.array(), start, input.position(), true);
+        return value;
+    }
+
+    public @Int128 BigInteger deserialize_i128() throws DeserializationError {
+        int start = input.position();
+        BigInteger value = super.deserialize_i128();
+        sort_keys.record_integer(input.array(), start, input.position(), true);
+        return value;
+    }
+
+    public String deserialize_str() throws DeserializationError {
+        int start = input.position();
+        String value = super.deserialize_str();
+        sort_keys.record_str(input.array(), start, input.position());
+        return value;
+    }
+
+    public Float deserialize_f32() throws DeserializationError {
+        float value = getFloat();
+        if (Float.isNaN(value)) {
+            throw new DeserializationError("NaN values are not supported by Borsh");
+        }
+        return Float.valueOf(value);
+    }
+
+    public Double deserialize_f64() throws DeserializationError {
+        double value = getDouble();
+        if (Double.isNaN(value)) {
+            throw new DeserializationError("NaN values are not supported by Borsh");
+        }
+        return Double.valueOf(value);
+    }
+
+    public long deserialize_len() throws DeserializationError {
+        long value = getInt() & 0xFFFFFFFFL;
+        if (value > BorshSerializer.MAX_LENGTH) {
+            throw new DeserializationError("Incorrect length value");
+        }
+        return value;
+    }
+
+    public int deserialize_variant_index() throws DeserializationError {
+        return getByte() & 0xFF;
+    }
+
+    public void check_that_key_slices_are_increasing(Slice key1, Slice key2) throws DeserializationError {
+        byte[] content = input.array();
+        byte[] sort_key1 = sort_keys.sort_key(content, key1.start, key1.end);
+        byte[] sort_key2 = sort_keys.sort_key(content, key2.start, key2.end);
+        if (SortKeys.compare(sort_key1, sort_key2) >= 0) {
+            throw new DeserializationError("Error while decoding map: keys are not serialized in the expected order");
+        }
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.borsh;
+
+import java.math.BigInteger;
+import com.novi.serde.Int128;
+import com.novi.serde.SerializationError;
+import com.novi.serde.Slice;
+import com.novi.serde.BinarySerializer;
+import com.novi.serde.Unsigned;
+
+public class BorshSerializer extends BinarySerializer {
+    public static final long MAX_LENGTH = Integer.MAX_VALUE;
+    public static final int MAX_VARIANT_INDEX = 255;
+
+    private final SortKeys sort_keys = new SortKeys();
+
+    public BorshSerializer() {
+        super(Long.MAX_VALUE);
+    }
+
+    private void write_little_endian(long value, int size) {
+        for (int i = 0; i < size; i++) {
+            output.write((byte) (value >>> (8 * i)));
+        }
+    }
+
+    public void serialize_u16(@Unsigned Short value) throws SerializationError {
+        int start = output.size();
+        super.serialize_u16(value);
+        sort_keys.record_integer(output.getBuffer(), start, output.size(), false);
+    }
+
+    public void serialize_u32(@Unsigned Integer value) throws SerializationError {
+        int start = output.size();
+        super.serialize_u32(value);
+        sort_keys.record_integer(output.getBuffer(), start, output.size(), false);
+    }
+
+    public void serialize_u64(@Unsigned Long value) throws SerializationError {
+        int start = output.size();
+        super.serialize_u64(value);
+        sort_keys.record_integer(output.getBuffer(), start, output.size(), false);
+    }
+
+    public void serialize_u128(@Unsigned @Int128 BigInteger value) throws SerializationError {
+        int start = output.size();
+        super.serialize_u128(value);
+        sort_keys.record_integer(output.getBuffer(), start, output.size(), false);
+    }
+
+    public void serialize_i8(Byte value) throws SerializationError {
+        int start = output.size();
+        super.serialize_i8(value);
+        sort_keys.record_integer(output.getBuffer(), start, output.size(), true);
+    }
+
+    public void serialize_i16(Short value) throws SerializationError {
+        int start = output.size();
+        super.serialize_i16(value);
+        sort_keys.record_integer(output.getBuffer(), start, output.size(), true);
+    }
+
+    public void serialize_i32(Integer value) throws SerializationError {
+        int start = output.size();
+        super.serialize_i32(value);
+        sort_keys.record_integer(output.getBuffer(), start, output.size(), true);
+    }
+
+    public void serialize_i64(Long value) throws SerializationError {
+        int start = output.size();
+        super.serialize_i64(value);
+        sort_keys.record_integer(output.getBuffer(), start, output.size(), true);
+    }
+
+    public void serialize_i128(@Int128 BigInteger value) throws SerializationError {
+        int start = output.size();
+        super.serialize_i128(value);
+        sort_keys.record_integer(output.getBuffer(), start, output.size(), true);
+    }
+
+    public void serialize_str(String value) throws SerializationError {
+        int start = output.size();
+        super.serialize_str(value);
+        sort_keys.record_str(output.getBuffer(), start, output.size());
+    }
+
+    public void serialize_f32(Float value) throws SerializationError {
+        if (value.isNaN()) {
+            throw new SerializationError("NaN values are not supported by Borsh");
+        }
+        write_little_endian(Float.floatToRawIntBits(value.floatValue()), 4);
+    }
+
+    public void serialize_f64(Double value) throws SerializationError {
+        if (value.isNaN()) {
+            throw new SerializationError("NaN values are not supported by Borsh");
+        }
+        write_little_endian(Double.doubleToRawLongBits(value.doubleValue()), 8);
+    }
+
+    public void serialize_len(long value) throws SerializationError {
+        if ((value < 0) || (value > MAX_LENGTH)) {
+            throw new SerializationError("Incorrect length value");
+        }
+        write_little_endian(value, 4);
+    }
+
+    public void serialize_variant_index(int value) throws SerializationError {
+        if ((value < 0) || (value > MAX_VARIANT_INDEX)) {
+            throw new SerializationError("Incorrect variant index");
+        }
+        output.write((byte) value);
+    }
+
+    public void sort_map_entries(int[] offsets) {
+        if (offsets.length <= 1) {
+            return;
+        }
+        int offset0 = offsets[0];
+        byte[] content = output.getBuffer();
+        Slice[] slices = new Slice[offsets.length];
+        for (int i = 0; i < offsets.length - 1; i++) {
+            slices[i] = new Slice(offsets[i], offsets[i + 1]);
+        }
+        slices[offsets.length - 1] = new Slice(offsets[offsets.length - 1], output.size());
+
+        java.util.Map<Slice, byte[]> keys = new java.util.IdentityHashMap<Slice, byte[]>();
+        for (Slice slice : slices) {
+            keys.put(slice, sort_keys.sort_key(content, slice.start, slice.end));
+        }
+        java.util.Arrays.sort(slices, new java.util.Comparator<Slice>() {
+            @Override
+            public int compare(Slice slice1, Slice slice2) {
+                return SortKeys.compare(keys.get(slice1), keys.get(slice2));
+            }
+        });
+        // Positions in the map are no longer valid. Map keys never contain maps.
+        sort_keys.truncate(offset0);
+
+        byte[] old_content = new byte[output.size() - offset0];
+        System.arraycopy(content, offset0, old_content, 0, output.size() - offset0);
+
+        int position = offset0;
+        for (int i = 0; i < offsets.length; i++) {
+            int start = slices[i].start;
+            int end = slices[i].end;
+            System.arraycopy(old_content, start - offset0, content, position, end - start);
+            position += end - start;
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.borsh;
+
+import java.util.Map;
+import java.util.TreeMap;
+
+/**
+ * Order-preserving encodings of the integers and strings written or read so far, indexed by
+ * their positions in the buffer.
+ *
+ * Borsh sorts map entries by keys as in Rust. Substituting these encodings in the encoded keys
+ * gives byte arrays that compare in the same way.
+ */
+final class SortKeys {
+    private static final class Record {
+        final int end;
+        final byte[] key;
+
+        Record(int end, byte[] key) {
+            this.end = end;
+            this.key = key;
+        }
+    }
+
+    private final TreeMap<Integer, Record> records = new TreeMap<Integer, Record>();
+
+    // Little-endian integers compare as in Rust in big-endian order, with the sign bit flipped.
+    void record_integer(byte[] buffer, int start, int end, boolean signed) {
+        byte[] key = new byte[end - start];
+        for (int i = 0; i < key.length; i++) {
+            key[i] = buffer[end - 1 - i];
+        }
+        if (signed) {
+            key[0] ^= (byte) 0x80;
+        }
+        record(start, end, key);
+    }
+
+    // Strings have their zeros escaped and a terminator added so that prefixes come first.
+    void record_str(byte[] buffer, int start, int end) {
+        java.io.ByteArrayOutputStream key = new java.io.ByteArrayOutputStream();
+        for (int i = start + 4; i < end; i++) {
+            key.write(buffer[i]);
+            if (buffer[i] == 0) {
+                key.write(0xFF);
+            }
+        }
+        key.write(0);
+        key.write(0);
+        record(start, end, key.toByteArray());
+    }
+
+    private void record(int start, int end, byte[] key) {
+        // Values written or read inside the current one are superseded.
+        truncate(start);
+        records.put(start, new Record(end, key));
+    }
+
+    void truncate(int start) {
+        records.tailMap(start, true).clear();
+    }
+
+    byte[] sort_key(byte[] buffer, int start, int end) {
+        java.io.ByteArrayOutputStream result = new java.io.ByteArrayOutputStream();
+        int position = start;
+        for (Map.Entry<Integer, Record> entry : records.subMap(start, end).entrySet()) {
+            result.write(buffer, position, entry.getKey() - position);
+            result.write(entry.getValue().key, 0, entry.getValue().key.length);
+            position = entry.getValue().end;
+        }
+        result.write(buffer, position, end - position);
+        return result.toByteArray();
+    }
+
+    // Lexicographic comparison between (unsigned!) bytes.
+    static int compare(byte[] key1, byte[] key2) {
+        for (int i = 0; i < key1.length && i < key2.length; i++) {
+            int byte1 = key1[i] & 0xFF;
+            int byte2 = key2[i] & 0xFF;
+            if (byte1 != byte2) {
+                return byte1 < byte2 ? -1 : 1;
+            }
+        }
+        return Integer.compare(key1.length, key2.length);
+    }
+}
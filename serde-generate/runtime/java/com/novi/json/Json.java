@@ -0,0 +1,669 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.json;
+
+import com.novi.serde.Bytes;
+import com.novi.serde.DeserializationError;
+import com.novi.serde.Int128;
+import com.novi.serde.SerializationError;
+import com.novi.serde.Unit;
+import com.novi.serde.Unsigned;
+import java.math.BigDecimal;
+import java.math.BigInteger;
+import java.math.MathContext;
+import java.math.RoundingMode;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.regex.Pattern;
+
+/**
+ * The JSON format of the Rust crate `serde_json`.
+ *
+ * JSON values are represented by `null`, `Boolean`, `JsonNumber`, `String`, `List<Object>`,
+ * and `JsonObject`. Generated classes convert their values from and to such trees with the
+ * helper functions below, then `parse` and `stringify` deal with the JSON text.
+ */
+public final class Json {
+    /** Maximal number of nested arrays and objects accepted by `serde_json` (whose recursion limit is 128). */
+    public static final long MAX_CONTAINER_DEPTH = 127;
+
+    private static final Pattern INTEGER = Pattern.compile("-?(0|[1-9][0-9]*)");
+    private static final Pattern NUMBER = Pattern.compile("-?(0|[1-9][0-9]*)(\\.[0-9]+)?([eE][+-]?[0-9]+)?");
+
+    private static final BigInteger MIN_I8 = BigInteger.valueOf(Byte.MIN_VALUE);
+    private static final BigInteger MAX_I8 = BigInteger.valueOf(Byte.MAX_VALUE);
+    private static final BigInteger MIN_I16 = BigInteger.valueOf(Short.MIN_VALUE);
+    private static final BigInteger MAX_I16 = BigInteger.valueOf(Short.MAX_VALUE);
+    private static final BigInteger MIN_I32 = BigInteger.valueOf(Integer.MIN_VALUE);
+    private static final BigInteger MAX_I32 = BigInteger.valueOf(Integer.MAX_VALUE);
+    private static final BigInteger MIN_I64 = BigInteger.valueOf(Long.MIN_VALUE);
+    private static final BigInteger MAX_I64 = BigInteger.valueOf(Long.MAX_VALUE);
+    private static final BigInteger MIN_I128 = BigInteger.ONE.shiftLeft(127).negate();
+    private static final BigInteger MAX_I128 = BigInteger.ONE.shiftLeft(127).subtract(BigInteger.ONE);
+    private static final BigInteger MAX_U8 = BigInteger.ONE.shiftLeft(8).subtract(BigInteger.ONE);
+    private static final BigInteger MAX_U16 = BigInteger.ONE.shiftLeft(16).subtract(BigInteger.ONE);
+    private static final BigInteger MAX_U32 = BigInteger.ONE.shiftLeft(32).subtract(BigInteger.ONE);
+    private static final BigInteger MAX_U64 = BigInteger.ONE.shiftLeft(64).subtract(BigInteger.ONE);
+    private static final BigInteger MAX_U128 = BigInteger.ONE.shiftLeft(128).subtract(BigInteger.ONE);
+
+    private Json() {}
+
+    // Text
+
+    public static Object parse(String input) throws DeserializationError {
+        if (input == null) {
+            throw new DeserializationError("Cannot deserialize null string");
+        }
+        Parser parser = new Parser(input);
+        Object value = parser.parseValue(MAX_CONTAINER_DEPTH);
+        parser.skipWhitespace();
+        if (parser.position < input.length()) {
+            throw new DeserializationError("Trailing characters in JSON input");
+        }
+        return value;
+    }
+
+    public static String stringify(Object value) throws SerializationError {
+        StringBuilder output = new StringBuilder();
+        write(output, value);
+        return output.toString();
+    }
+
+    private static void write(StringBuilder output, Object value) throws SerializationError {
+        if (value == null) {
+            output.append("null");
+        } else if (value instanceof Boolean) {
+            output.append(((Boolean) value).booleanValue() ? "true" : "false");
+        } else if (value instanceof JsonNumber) {
+            output.append(((JsonNumber) value).literal);
+        } else if (value instanceof String) {
+            writeString(output, (String) value);
+        } else if (value instanceof List) {
+            output.append('[');
+            boolean first = true;
+            for (Object item : (List<?>) value) {
+                if (!first) {
+                    output.append(',');
+                }
+                first = false;
+                write(output, item);
+            }
+            output.append(']');
+        } else if (value instanceof JsonObject) {
+            JsonObject object = (JsonObject) value;
+            output.append('{');
+            for (int i = 0; i < object.size(); i++) {
+                if (i > 0) {
+                    output.append(',');
+                }
+                writeString(output, object.key(i));
+                output.append(':');
+                write(output, object.value(i));
+            }
+            output.append('}');
+        } else {
+            throw new SerializationError("Unexpected JSON value: " + value.getClass().getName());
+        }
+    }
+
+    private static void writeString(StringBuilder output, String value) throws SerializationError {
+        output.append('"');
+        for (int i = 0; i < value.length(); i++) {
+            char c = value.charAt(i);
+            switch (c) {
+                case '"': output.append("\\\""); break;
+                case '\\': output.append("\\\\"); break;
+                case '\b': output.append("\\b"); break;
+                case '\f': output.append("\\f"); break;
+                case '\n': output.append("\\n"); break;
+                case '\r': output.append("\\r"); break;
+                case '\t': output.append("\\t"); break;
+                default:
+                    if (c < 0x20) {
+                        output.append(String.format("\\u%04x", (int) c));
+                    } else if (Character.isHighSurrogate(c) && i + 1 < value.length() && Character.isLowSurrogate(value.charAt(i + 1))) {
+                        output.append(c);
+                        output.append(value.charAt(++i));
+                    } else if (Character.isSurrogate(c)) {
+                        throw new SerializationError("Invalid unicode string");
+                    } else {
+                        output.append(c);
+                    }
+            }
+        }
+        output.append('"');
+    }
+
+    private static final class Parser {
+        final String input;
+        int position = 0;
+
+        Parser(String input) {
+            this.input = input;
+        }
+
+        void skipWhitespace() {
+            while (position < input.length()) {
+                char c = input.charAt(position);
+                if (c != ' ' && c != '\t' && c != '\n' && c != '\r') {
+                    return;
+                }
+                position++;
+            }
+        }
+
+        DeserializationError error(String message) {
+            return new DeserializationError(message + " at position " + position);
+        }
+
+        char next() throws DeserializationError {
+            if (position >= input.length()) {
+                throw error("Unexpected end of JSON input");
+            }
+            return input.charAt(position++);
+        }
+
+        void expect(String word) throws DeserializationError {
+            if (!input.startsWith(word, position)) {
+                throw error("Invalid JSON value");
+            }
+            position += word.length();
+        }
+
+        Object parseValue(long depthBudget) throws DeserializationError {
+            skipWhitespace();
+            if (position >= input.length()) {
+                throw error("Unexpected end of JSON input");
+            }
+            char c = input.charAt(position);
+            switch (c) {
+                case 'n': expect("null"); return null;
+                case 't': expect("true"); return Boolean.TRUE;
+                case 'f': expect("false"); return Boolean.FALSE;
+                case '"': return parseString();
+                case '[': {
+                    if (depthBudget == 0) {
+                        throw error("Exceeded maximum container depth");
+                    }
+                    position++;
+                    List<Object> items = new ArrayList<Object>();
+                    skipWhitespace();
+                    if (position < input.length() && input.charAt(position) == ']') {
+                        position++;
+                        return items;
+                    }
+                    while (true) {
+                        items.add(parseValue(depthBudget - 1));
+                        skipWhitespace();
+                        char d = next();
+                        if (d == ']') {
+                            return items;
+                        }
+                        if (d != ',') {
+                            throw error("Expected ',' or ']'");
+                        }
+                    }
+                }
+                case '{': {
+                    if (depthBudget == 0) {
+                        throw error("Exceeded maximum container depth");
+                    }
+                    position++;
+                    JsonObject object = new JsonObject();
+                    skipWhitespace();
+                    if (position < input.length() && input.charAt(position) == '}') {
+                        position++;
+                        return object;
+                    }
+                    while (true) {
+                        skipWhitespace();
+                        if (position >= input.length() || input.charAt(position) != '"') {
+                            throw error("Expected a string key");
+                        }
+                        String key = parseString();
+                        skipWhitespace();
+                        if (next() != ':') {
+                            throw error("Expected ':'");
+                        }
+                        object.put(key, parseValue(depthBudget - 1));
+                        skipWhitespace();
+                        char d = next();
+                        if (d == '}') {
+                            return object;
+                        }
+                        if (d != ',') {
+                            throw error("Expected ',' or '}'");
+                        }
+                    }
+                }
+                default:
+                    if (c == '-' || (c >= '0' && c <= '9')) {
+                        return parseNumber();
+                    }
+                    throw error("Invalid JSON value");
+            }
+        }
+
+        JsonNumber parseNumber() throws DeserializationError {
+            int start = position;
+            while (position < input.length()) {
+                char c = input.charAt(position);
+                if (!(c == '-' || c == '+' || c == '.' || c == 'e' || c == 'E' || (c >= '0' && c <= '9'))) {
+                    break;
+                }
+                position++;
+            }
+            String literal = input.substring(start, position);
+            if (!NUMBER.matcher(literal).matches()) {
+                throw error("Invalid number");
+            }
+            return new JsonNumber(literal);
+        }
+
+        String parseString() throws DeserializationError {
+            position++;
+            StringBuilder result = new StringBuilder();
+            while (true) {
+                char c = next();
+                if (c == '"') {
+                    return result.toString();
+                }
+                if (c < 0x20) {
+                    throw error("Control character in string");
+                }
+                if (c == '\\') {
+                    c = next();
+                    switch (c) {
+                        case '"': result.append('"'); break;
+                        case '\\': result.append('\\'); break;
+                        case '/': result.append('/'); break;
+                        case 'b': result.append('\b'); break;
+                        case 'f': result.append('\f'); break;
+                        case 'n': result.append('\n'); break;
+                        case 'r': result.append('\r'); break;
+                        case 't': result.append('\t'); break;
+                        case 'u': {
+                            char u = parseHex();
+                            if (Character.isHighSurrogate(u)) {
+                                if (!input.startsWith("\\u", position)) {
+                                    throw error("Lone surrogate in string");
+                                }
+                                position += 2;
+                                char v = parseHex();
+                                if (!Character.isLowSurrogate(v)) {
+                                    throw error("Lone surrogate in string");
+                                }
+                                result.append(u);
+                                result.append(v);
+                            } else if (Character.isLowSurrogate(u)) {
+                                throw error("Lone surrogate in string");
+                            } else {
+                                result.append(u);
+                            }
+                            break;
+                        }
+                        default:
+                            throw error("Invalid escape in string");
+                    }
+                } else if (Character.isHighSurrogate(c) && position < input.length() && Character.isLowSurrogate(input.charAt(position))) {
+                    result.append(c);
+                    result.append(input.charAt(position++));
+                } else if (Character.isSurrogate(c)) {
+                    throw error("Invalid unicode string");
+                } else {
+                    result.append(c);
+                }
+            }
+        }
+
+        char parseHex() throws DeserializationError {
+            if (position + 4 > input.length()) {
+                throw error("Unexpected end of JSON input");
+            }
+            int value = 0;
+            for (int i = 0; i < 4; i++) {
+                int digit = Character.digit(input.charAt(position++), 16);
+                if (digit < 0) {
+                    throw error("Invalid escape in string");
+                }
+                value = value * 16 + digit;
+            }
+            return (char) value;
+        }
+    }
+
+    // Serialization helpers
+
+    public static Object serialize_unit(Unit value) {
+        return null;
+    }
+
+    public static Object serialize_bool(Boolean value) {
+        return value;
+    }
+
+    public static Object serialize_i8(Byte value) {
+        return new JsonNumber(value.toString());
+    }
+
+    public static Object serialize_i16(Short value) {
+        return new JsonNumber(value.toString());
+    }
+
+    public static Object serialize_i32(Integer value) {
+        return new JsonNumber(value.toString());
+    }
+
+    public static Object serialize_i64(Long value) {
+        return new JsonNumber(value.toString());
+    }
+
+    public static Object serialize_i128(@Int128 BigInteger value) throws SerializationError {
+        if (value.compareTo(MIN_I128) < 0 || value.compareTo(MAX_I128) > 0) {
+            throw new SerializationError("Invalid value for a signed int128");
+        }
+        return new JsonNumber(value.toString());
+    }
+
+    public static Object serialize_u8(@Unsigned Byte value) {
+        return new JsonNumber(Integer.toString(Byte.toUnsignedInt(value)));
+    }
+
+    public static Object serialize_u16(@Unsigned Short value) {
+        return new JsonNumber(Integer.toString(Short.toUnsignedInt(value)));
+    }
+
+    public static Object serialize_u32(@Unsigned Integer value) {
+        return new JsonNumber(Integer.toUnsignedString(value));
+    }
+
+    public static Object serialize_u64(@Unsigned Long value) {
+        return new JsonNumber(Long.toUnsignedString(value));
+    }
+
+    public static Object serialize_u128(@Unsigned @Int128 BigInteger value) throws SerializationError {
+        if (value.signum() < 0 || value.compareTo(MAX_U128) > 0) {
+            throw new SerializationError("Invalid value for an unsigned int128");
+        }
+        return new JsonNumber(value.toString());
+    }
+
+    public static Object serialize_f32(Float value) {
+        if (value.isNaN() || value.isInfinite()) {
+            return null;
+        }
+        return new JsonNumber(formatFloat(value.doubleValue(), true));
+    }
+
+    public static Object serialize_f64(Double value) {
+        if (value.isNaN() || value.isInfinite()) {
+            return null;
+        }
+        return new JsonNumber(formatFloat(value.doubleValue(), false));
+    }
+
+    public static Object serialize_char(Character value) {
+        return value.toString();
+    }
+
+    public static Object serialize_str(String value) {
+        return value;
+    }
+
+    public static Object serialize_bytes(Bytes value) {
+        byte[] content = value.content();
+        List<Object> result = new ArrayList<Object>(content.length);
+        for (byte b : content) {
+            result.add(new JsonNumber(Integer.toString(Byte.toUnsignedInt(b))));
+        }
+        return result;
+    }
+
+    /** A variant of an enum that is not a unit variant (unit variants are written as strings). */
+    public static Object serialize_variant(String name, Object content) {
+        JsonObject result = new JsonObject();
+        result.put(name, content);
+        return result;
+    }
+
+    /** The key of a map entry, given the JSON value of the key. */
+    public static String key(Object value) throws SerializationError {
+        if (value instanceof String) {
+            return (String) value;
+        }
+        if (value instanceof JsonNumber) {
+            return ((JsonNumber) value).literal;
+        }
+        if (value instanceof Boolean) {
+            return value.toString();
+        }
+        throw new SerializationError("Map keys must be strings or numbers");
+    }
+
+    /**
+     * Format a finite float in the manner of the Rust crate `ryu`, as `serde_json` does: with
+     * the shortest digits that read back as the same value.
+     */
+    static String formatFloat(double value, boolean isFloat) {
+        if (value == 0) {
+            return (Double.doubleToRawLongBits(value) < 0) ? "-0.0" : "0.0";
+        }
+        String sign = value < 0 ? "-" : "";
+        double magnitude = Math.abs(value);
+        BigDecimal exact = new BigDecimal(magnitude);
+        BigDecimal rounded = exact;
+        for (int precision = 1; precision <= 17; precision++) {
+            rounded = exact.round(new MathContext(precision, RoundingMode.HALF_EVEN));
+            String literal = rounded.toString();
+            if (isFloat ? Float.parseFloat(literal) == (float) magnitude : Double.parseDouble(literal) == magnitude) {
+                break;
+            }
+        }
+        rounded = rounded.stripTrailingZeros();
+        String digits = rounded.unscaledValue().toString();
+        int point = digits.length() - rounded.scale();
+        int maxPoint = isFloat ? 13 : 16;
+        int minPoint = isFloat ? -6 : -5;
+        StringBuilder result = new StringBuilder(sign);
+        if (digits.length() <= point && point <= maxPoint) {
+            result.append(digits);
+            for (int i = digits.length(); i < point; i++) {
+                result.append('0');
+            }
+            result.append(".0");
+        } else if (0 < point && point <= maxPoint) {
+            result.append(digits, 0, point).append('.').append(digits, point, digits.length());
+        } else if (minPoint < point && point <= 0) {
+            result.append("0.");
+            for (int i = point; i < 0; i++) {
+                result.append('0');
+            }
+            result.append(digits);
+        } else {
+            result.append(digits.charAt(0));
+            if (digits.length() > 1) {
+                result.append('.').append(digits, 1, digits.length());
+            }
+            result.append('e').append(point - 1);
+        }
+        return result.toString();
+    }
+
+    // Deserialization helpers
+
+    private static DeserializationError typeError(Object value, String expected) {
+        String found = value == null ? "null" : value.toString();
+        return new DeserializationError("Invalid type: expected " + expected + ", found " + found);
+    }
+
+    private static BigInteger integer(Object value, BigInteger min, BigInteger max, boolean acceptsNegativeZero) throws DeserializationError {
+        String literal;
+        if (value instanceof JsonNumber) {
+            literal = ((JsonNumber) value).literal;
+        } else if (value instanceof JsonKey) {
+            literal = ((JsonKey) value).value;
+        } else {
+            throw typeError(value, "an integer");
+        }
+        // Like `serde_json`, read `-0` as a float, except for 128-bit integers.
+        if (!INTEGER.matcher(literal).matches() || (literal.equals("-0") && !acceptsNegativeZero)) {
+            throw typeError(value, "an integer");
+        }
+        BigInteger result = new BigInteger(literal);
+        if (result.compareTo(min) < 0 || result.compareTo(max) > 0) {
+            throw new DeserializationError("Integer out of range: " + literal);
+        }
+        return result;
+    }
+
+    private static double number(Object value) throws DeserializationError {
+        String literal;
+        if (value instanceof JsonNumber) {
+            literal = ((JsonNumber) value).literal;
+        } else if (value instanceof JsonKey && NUMBER.matcher(((JsonKey) value).value).matches()) {
+            literal = ((JsonKey) value).value;
+        } else {
+            throw typeError(value, "a number");
+        }
+        double result = Double.parseDouble(literal);
+        if (Double.isInfinite(result)) {
+            throw new DeserializationError("Number out of range: " + literal);
+        }
+        return result;
+    }
+
+    public static Unit deserialize_unit(Object value) throws DeserializationError {
+        if (value != null) {
+            throw typeError(value, "null");
+        }
+        return new Unit();
+    }
+
+    public static Boolean deserialize_bool(Object value) throws DeserializationError {
+        if (value instanceof Boolean) {
+            return (Boolean) value;
+        }
+        if (value instanceof JsonKey) {
+            String key = ((JsonKey) value).value;
+            if (key.equals("true") || key.equals("false")) {
+                return Boolean.valueOf(key.equals("true"));
+            }
+        }
+        throw typeError(value, "a boolean");
+    }
+
+    public static Byte deserialize_i8(Object value) throws DeserializationError {
+        return Byte.valueOf(integer(value, MIN_I8, MAX_I8, false).byteValue());
+    }
+
+    public static Short deserialize_i16(Object value) throws DeserializationError {
+        return Short.valueOf(integer(value, MIN_I16, MAX_I16, false).shortValue());
+    }
+
+    public static Integer deserialize_i32(Object value) throws DeserializationError {
+        return Integer.valueOf(integer(value, MIN_I32, MAX_I32, false).intValue());
+    }
+
+    public static Long deserialize_i64(Object value) throws DeserializationError {
+        return Long.valueOf(integer(value, MIN_I64, MAX_I64, false).longValue());
+    }
+
+    public static @Int128 BigInteger deserialize_i128(Object value) throws DeserializationError {
+        return integer(value, MIN_I128, MAX_I128, true);
+    }
+
+    public static @Unsigned Byte deserialize_u8(Object value) throws DeserializationError {
+        return Byte.valueOf(integer(value, BigInteger.ZERO, MAX_U8, false).byteValue());
+    }
+
+    public static @Unsigned Short deserialize_u16(Object value) throws DeserializationError {
+        return Short.valueOf(integer(value, BigInteger.ZERO, MAX_U16, false).shortValue());
+    }
+
+    public static @Unsigned Integer deserialize_u32(Object value) throws DeserializationError {
+        return Integer.valueOf(integer(value, BigInteger.ZERO, MAX_U32, false).intValue());
+    }
+
+    public static @Unsigned Long deserialize_u64(Object value) throws DeserializationError {
+        return Long.valueOf(integer(value, BigInteger.ZERO, MAX_U64, false).longValue());
+    }
+
+    public static @Unsigned @Int128 BigInteger deserialize_u128(Object value) throws DeserializationError {
+        return integer(value, BigInteger.ZERO, MAX_U128, false);
+    }
+
+    public static Float deserialize_f32(Object value) throws DeserializationError {
+        // Like `serde_json`, read the closest f64 value then convert it to f32.
+        return Float.valueOf((float) number(value));
+    }
+
+    public static Double deserialize_f64(Object value) throws DeserializationError {
+        return Double.valueOf(number(value));
+    }
+
+    public static Character deserialize_char(Object value) throws DeserializationError {
+        String content = deserialize_str(value);
+        if (content.codePointCount(0, content.length()) != 1) {
+            throw typeError(value, "a character");
+        }
+        if (content.length() != 1) {
+            throw new DeserializationError("Character out of range for Java: " + content);
+        }
+        return Character.valueOf(content.charAt(0));
+    }
+
+    public static String deserialize_str(Object value) throws DeserializationError {
+        if (value instanceof String) {
+            return (String) value;
+        }
+        if (value instanceof JsonKey) {
+            return ((JsonKey) value).value;
+        }
+        throw typeError(value, "a string");
+    }
+
+    public static Bytes deserialize_bytes(Object value) throws DeserializationError {
+        List<Object> items = deserialize_array(value);
+        byte[] content = new byte[items.size()];
+        for (int i = 0; i < content.length; i++) {
+            content[i] = deserialize_u8(items.get(i)).byteValue();
+        }
+        return new Bytes(content);
+    }
+
+    @SuppressWarnings("unchecked")
+    public static List<Object> deserialize_array(Object value) throws DeserializationError {
+        if (!(value instanceof List)) {
+            throw typeError(value, "an array");
+        }
+        return (List<Object>) value;
+    }
+
+    /** The items of an array of the given length, i.e. a tuple or a fixed-size array. */
+    public static List<Object> deserialize_array(Object value, int length) throws DeserializationError {
+        List<Object> items = deserialize_array(value);
+        if (items.size() != length) {
+            throw new DeserializationError("Invalid length: " + items.size() + " instead of " + length);
+        }
+        return items;
+    }
+
+    public static JsonObject deserialize_object(Object value) throws DeserializationError {
+        if (!(value instanceof JsonObject)) {
+            throw typeError(value, "an object");
+        }
+        return (JsonObject) value;
+    }
+
+    public static JsonVariant deserialize_variant(Object value) throws DeserializationError {
+        if (value instanceof String || value instanceof JsonKey) {
+            return new JsonVariant(deserialize_str(value), null, false);
+        }
+        if (value instanceof JsonObject && ((JsonObject) value).size() == 1) {
+            JsonObject object = (JsonObject) value;
+            return new JsonVariant(object.key(0), object.value(0), true);
+        }
+        throw typeError(value, "a string or an object with a single entry");
+    }
+}
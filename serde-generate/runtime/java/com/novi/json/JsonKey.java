@@ -0,0 +1,20 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.json;
+
+/**
+ * The key of an entry of a JSON object read as a map. Like `serde_json`, keys may stand for
+ * strings, numbers, booleans, or unit variants of enums.
+ */
+public final class JsonKey {
+    public final String value;
+
+    public JsonKey(String value) {
+        this.value = value;
+    }
+
+    public String toString() {
+        return value;
+    }
+}
@@ -0,0 +1,19 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.json;
+
+/**
+ * A JSON number, kept as written so that integers of any size are read exactly.
+ */
+public final class JsonNumber {
+    public final String literal;
+
+    public JsonNumber(String literal) {
+        this.literal = literal;
+    }
+
+    public String toString() {
+        return literal;
+    }
+}
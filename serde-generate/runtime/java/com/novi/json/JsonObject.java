@@ -0,0 +1,59 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.json;
+
+import com.novi.serde.DeserializationError;
+import java.util.ArrayList;
+import java.util.List;
+
+/**
+ * The entries of a JSON object, in order and possibly with duplicate keys.
+ */
+public final class JsonObject {
+    private final List<String> keys = new ArrayList<String>();
+    private final List<Object> values = new ArrayList<Object>();
+
+    public void put(String key, Object value) {
+        keys.add(key);
+        values.add(value);
+    }
+
+    public int size() {
+        return keys.size();
+    }
+
+    public String key(int index) {
+        return keys.get(index);
+    }
+
+    public Object value(int index) {
+        return values.get(index);
+    }
+
+    /** Value of the field `name` of a struct. */
+    public Object field(String name) throws DeserializationError {
+        int index = find(name);
+        if (index < 0) {
+            throw new DeserializationError("Missing field: " + name);
+        }
+        return values.get(index);
+    }
+
+    /** Value of the optional field `name` of a struct, or `null` if the field is missing. */
+    public Object optional_field(String name) throws DeserializationError {
+        int index = find(name);
+        if (index < 0) {
+            return null;
+        }
+        return values.get(index);
+    }
+
+    private int find(String name) throws DeserializationError {
+        int index = keys.indexOf(name);
+        if (index >= 0 && keys.lastIndexOf(name) != index) {
+            throw new DeserializationError("Duplicate field: " + name);
+        }
+        return index;
+    }
+}
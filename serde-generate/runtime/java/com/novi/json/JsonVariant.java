@@ -0,0 +1,37 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.json;
+
+import com.novi.serde.DeserializationError;
+
+/**
+ * A variant of an enum, read either from a string (unit variants) or from an object with a
+ * single entry.
+ */
+public final class JsonVariant {
+    public final String name;
+    private final Object content;
+    private final boolean hasContent;
+
+    public JsonVariant(String name, Object content, boolean hasContent) {
+        this.name = name;
+        this.content = content;
+        this.hasContent = hasContent;
+    }
+
+    /** Check that the variant has no content, i.e. that it was written as a string or with `null`. */
+    public void unit() throws DeserializationError {
+        if (content != null) {
+            throw new DeserializationError("Unexpected content for unit variant: " + name);
+        }
+    }
+
+    /** Content of a variant that is not a unit variant. */
+    public Object content() throws DeserializationError {
+        if (!hasContent) {
+            throw new DeserializationError("Missing content for variant: " + name);
+        }
+        return content;
+    }
+}
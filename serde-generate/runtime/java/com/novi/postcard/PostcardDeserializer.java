@@ -0,0 +1,123 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.postcard;
+
+import com.novi.serde.DeserializationError;
+import com.novi.serde.Int128;
+import com.novi.serde.Slice;
+import com.novi.serde.BinaryDeserializer;
+import com.novi.serde.Unsigned;
+import java.math.BigInteger;
+
+public class PostcardDeserializer extends BinaryDeserializer {
+    public PostcardDeserializer(byte[] input) {
+        super(input, Long.MAX_VALUE);
+    }
+
+    // Read a varint-encoded value of at most 64 bits.
+    private long deserialize_varint(int bits) throws DeserializationError {
+        long value = 0;
+        for (int shift = 0; shift < bits; shift += 7) {
+            byte x = getByte();
+            long digit = x & 0x7F;
+            if (bits - shift < 7 && (digit >>> (bits - shift)) != 0) {
+                throw new DeserializationError("Overflow while parsing varint-encoded value");
+            }
+            value = value | (digit << shift);
+            if ((x & 0x80) == 0) {
+                return value;
+            }
+        }
+        throw new DeserializationError("Varint-encoded value is too long");
+    }
+
+    private BigInteger deserialize_varint128() throws DeserializationError {
+        BigInteger value = BigInteger.ZERO;
+        for (int shift = 0; shift < 128; shift += 7) {
+            byte x = getByte();
+            int digit = x & 0x7F;
+            if (128 - shift < 7 && (digit >>> (128 - shift)) != 0) {
+                throw new DeserializationError("Overflow while parsing varint-encoded value");
+            }
+            value = value.or(BigInteger.valueOf(digit).shiftLeft(shift));
+            if ((x & 0x80) == 0) {
+                return value;
+            }
+        }
+        throw new DeserializationError("Varint-encoded value is too long");
+    }
+
+    private static long zigzag_decode(long value) {
+        return (value >>> 1) ^ -(value & 1);
+    }
+
+    public Character deserialize_char() throws DeserializationError {
+        String value = deserialize_str();
+        if (value.length() != 1) {
+            throw new DeserializationError("Incorrect value for Java char");
+        }
+        return Character.valueOf(value.charAt(0));
+    }
+
+    public @Unsigned Short deserialize_u16() throws DeserializationError {
+        return Short.valueOf((short) deserialize_varint(16));
+    }
+
+    public @Unsigned Integer deserialize_u32() throws DeserializationError {
+        return Integer.valueOf((int) deserialize_varint(32));
+    }
+
+    public @Unsigned Long deserialize_u64() throws DeserializationError {
+        return Long.valueOf(deserialize_varint(64));
+    }
+
+    public @Unsigned @Int128 BigInteger deserialize_u128() throws DeserializationError {
+        return deserialize_varint128();
+    }
+
+    public Short deserialize_i16() throws DeserializationError {
+        return Short.valueOf((short) zigzag_decode(deserialize_varint(16)));
+    }
+
+    public Integer deserialize_i32() throws DeserializationError {
+        return Integer.valueOf((int) zigzag_decode(deserialize_varint(32)));
+    }
+
+    public Long deserialize_i64() throws DeserializationError {
+        return Long.valueOf(zigzag_decode(deserialize_varint(64)));
+    }
+
+    public @Int128 BigInteger deserialize_i128() throws DeserializationError {
+        BigInteger value = deserialize_varint128();
+        if (value.testBit(0)) {
+            return value.shiftRight(1).add(BigInteger.ONE).negate();
+        } else {
+            return value.shiftRight(1);
+        }
+    }
+
+    public Float deserialize_f32() throws DeserializationError {
+        return Float.valueOf(getFloat());
+    }
+
+    public Double deserialize_f64() throws DeserializationError {
+        return Double.valueOf(getDouble());
+    }
+
+    public long deserialize_len() throws DeserializationError {
+        long value = deserialize_varint(64);
+        if (value < 0 || value > Integer.MAX_VALUE) {
+            throw new DeserializationError("Incorrect length value");
+        }
+        return value;
+    }
+
+    public int deserialize_variant_index() throws DeserializationError {
+        return (int) deserialize_varint(32);
+    }
+
+    public void check_that_key_slices_are_increasing(Slice key1, Slice key2) throws DeserializationError {
+        // Not required by the format.
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.postcard;
+
+import com.novi.serde.Int128;
+import com.novi.serde.SerializationError;
+import com.novi.serde.BinarySerializer;
+import com.novi.serde.Unsigned;
+import java.math.BigInteger;
+
+public class PostcardSerializer extends BinarySerializer {
+    public PostcardSerializer() {
+        super(Long.MAX_VALUE);
+    }
+
+    private void serialize_varint(long value) {
+        while ((value & ~0x7FL) != 0) {
+            output.write((byte) ((value & 0x7F) | 0x80));
+            value >>>= 7;
+        }
+        output.write((byte) value);
+    }
+
+    private void serialize_varint(BigInteger value) {
+        BigInteger mask = BigInteger.valueOf(0x7F);
+        while (value.compareTo(mask) > 0) {
+            output.write((byte) (value.and(mask).intValue() | 0x80));
+            value = value.shiftRight(7);
+        }
+        output.write((byte) value.intValue());
+    }
+
+    private void write_little_endian(long value, int size) {
+        for (int i = 0; i < size; i++) {
+            output.write((byte) (value >>> (8 * i)));
+        }
+    }
+
+    public void serialize_char(Character value) throws SerializationError {
+        serialize_str(value.toString());
+    }
+
+    public void serialize_u16(@Unsigned Short value) throws SerializationError {
+        serialize_varint(value.shortValue() & 0xFFFFL);
+    }
+
+    public void serialize_u32(@Unsigned Integer value) throws SerializationError {
+        serialize_varint(value.intValue() & 0xFFFFFFFFL);
+    }
+
+    public void serialize_u64(@Unsigned Long value) throws SerializationError {
+        serialize_varint(value.longValue());
+    }
+
+    public void serialize_u128(@Unsigned @Int128 BigInteger value) throws SerializationError {
+        if (value.compareTo(BigInteger.ZERO) < 0 || !value.shiftRight(128).equals(BigInteger.ZERO)) {
+            throw new java.lang.IllegalArgumentException("Invalid value for an unsigned int128");
+        }
+        serialize_varint(value);
+    }
+
+    public void serialize_i16(Short value) throws SerializationError {
+        short val = value.shortValue();
+        serialize_varint(((val << 1) ^ (val >> 15)) & 0xFFFFL);
+    }
+
+    public void serialize_i32(Integer value) throws SerializationError {
+        int val = value.intValue();
+        serialize_varint(((val << 1) ^ (val >> 31)) & 0xFFFFFFFFL);
+    }
+
+    public void serialize_i64(Long value) throws SerializationError {
+        long val = value.longValue();
+        serialize_varint((val << 1) ^ (val >> 63));
+    }
+
+    public void serialize_i128(@Int128 BigInteger value) throws SerializationError {
+        if (value.compareTo(BigInteger.ZERO) >= 0) {
+            if (!value.shiftRight(127).equals(BigInteger.ZERO)) {
+                throw new java.lang.IllegalArgumentException("Invalid value for a signed int128");
+            }
+            serialize_varint(value.shiftLeft(1));
+        } else {
+            if (!value.add(BigInteger.ONE).negate().shiftRight(127).equals(BigInteger.ZERO)) {
+                throw new java.lang.IllegalArgumentException("Invalid value for a signed int128");
+            }
+            serialize_varint(value.shiftLeft(1).negate().subtract(BigInteger.ONE));
+        }
+    }
+
+    public void serialize_f32(Float value) throws SerializationError {
+        write_little_endian(Float.floatToRawIntBits(value.floatValue()), 4);
+    }
+
+    public void serialize_f64(Double value) throws SerializationError {
+        write_little_endian(Double.doubleToRawLongBits(value.doubleValue()), 8);
+    }
+
+    public void serialize_len(long value) throws SerializationError {
+        serialize_varint(value);
+    }
+
+    public void serialize_variant_index(int value) throws SerializationError {
+        serialize_varint(value & 0xFFFFFFFFL);
+    }
+
+    public void sort_map_entries(int[] offsets) {
+        // Not required by the format.
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.scale;
+
+import com.novi.serde.DeserializationError;
+import com.novi.serde.Slice;
+import com.novi.serde.BinaryDeserializer;
+
+public class ScaleDeserializer extends BinaryDeserializer {
+    public ScaleDeserializer(byte[] input) {
+        super(input, Long.MAX_VALUE);
+    }
+
+    public Float deserialize_f32() throws DeserializationError {
+        return Float.valueOf(getFloat());
+    }
+
+    public Double deserialize_f64() throws DeserializationError {
+        return Double.valueOf(getDouble());
+    }
+
+    private long deserialize_compact_u32() throws DeserializationError {
+        long prefix = getByte() & 0xFFL;
+        long value;
+        long minimum;
+        switch ((int) (prefix & 3)) {
+            case 0:
+                return prefix >> 2;
+            case 1:
+                value = (prefix | ((getByte() & 0xFFL) << 8)) >> 2;
+                minimum = 1L << 6;
+                break;
+            case 2:
+                value = prefix;
+                for (int shift = 8; shift < 32; shift += 8) {
+                    value |= (getByte() & 0xFFL) << shift;
+                }
+                value >>= 2;
+                minimum = 1L << 14;
+                break;
+            default:
+                if (prefix != 3) {
+                    throw new DeserializationError("Compact integer does not fit in 32 bits");
+                }
+                value = getInt() & 0xFFFFFFFFL;
+                minimum = 1L << 30;
+        }
+        if (value < minimum) {
+            throw new DeserializationError("Compact integer is not canonically encoded");
+        }
+        return value;
+    }
+
+    public long deserialize_len() throws DeserializationError {
+        long value = deserialize_compact_u32();
+        if (value > ScaleSerializer.MAX_LENGTH) {
+            throw new DeserializationError("Incorrect length value");
+        }
+        return value;
+    }
+
+    public int deserialize_variant_index() throws DeserializationError {
+        return getByte() & 0xFF;
+    }
+
+    public void check_that_key_slices_are_increasing(Slice key1, Slice key2) throws DeserializationError {
+        // Not required by the format.
+    }
+}
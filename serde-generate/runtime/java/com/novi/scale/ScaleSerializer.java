@@ -0,0 +1,61 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.scale;
+
+import com.novi.serde.SerializationError;
+import com.novi.serde.BinarySerializer;
+
+public class ScaleSerializer extends BinarySerializer {
+    public static final long MAX_LENGTH = Integer.MAX_VALUE;
+    public static final int MAX_VARIANT_INDEX = 255;
+
+    public ScaleSerializer() {
+        super(Long.MAX_VALUE);
+    }
+
+    private void write_little_endian(long value, int size) {
+        for (int i = 0; i < size; i++) {
+            output.write((byte) (value >>> (8 * i)));
+        }
+    }
+
+    public void serialize_f32(Float value) throws SerializationError {
+        write_little_endian(Float.floatToRawIntBits(value.floatValue()), 4);
+    }
+
+    public void serialize_f64(Double value) throws SerializationError {
+        write_little_endian(Double.doubleToRawLongBits(value.doubleValue()), 8);
+    }
+
+    private void serialize_compact_u32(long value) {
+        if (value < (1L << 6)) {
+            write_little_endian(value << 2, 1);
+        } else if (value < (1L << 14)) {
+            write_little_endian((value << 2) | 1, 2);
+        } else if (value < (1L << 30)) {
+            write_little_endian((value << 2) | 2, 4);
+        } else {
+            output.write((byte) 3);
+            write_little_endian(value, 4);
+        }
+    }
+
+    public void serialize_len(long value) throws SerializationError {
+        if ((value < 0) || (value > MAX_LENGTH)) {
+            throw new SerializationError("Incorrect length value");
+        }
+        serialize_compact_u32(value);
+    }
+
+    public void serialize_variant_index(int value) throws SerializationError {
+        if ((value < 0) || (value > MAX_VARIANT_INDEX)) {
+            throw new SerializationError("Incorrect variant index");
+        }
+        output.write((byte) value);
+    }
+
+    public void sort_map_entries(int[] offsets) {
+        // Not required by the format.
+    }
+}
@@ -36,8 +36,7 @@ public abstract class BinaryDeserializer implements Deserializer {
         if (len < 0 || len > Integer.MAX_VALUE) {
             throw new DeserializationError("Incorrect length value for Java string");
         }
-        byte[] content = new byte[(int) len];
-        read(content);
+        byte[] content = getBytes((int) len);
         CharsetDecoder decoder = StandardCharsets.UTF_8.newDecoder();
         try {
             decoder.decode(ByteBuffer.wrap(content));
@@ -52,8 +51,7 @@ public abstract class BinaryDeserializer implements Deserializer {
         if (len < 0 || len > Integer.MAX_VALUE) {
             throw new DeserializationError("Incorrect length value for Java array");
         }
-        byte[] content = new byte[(int) len];
-        read(content);
+        byte[] content = getBytes((int) len);
         return new Bytes(content);
     }
 
@@ -192,4 +190,10 @@ public abstract class BinaryDeserializer implements Deserializer {
             throw new DeserializationError(INPUT_NOT_LARGE_ENOUGH);
         }
     }
+
+    protected byte[] getBytes(int length)  throws DeserializationError {
+        byte[] content = new byte[length];
+        read(content);
+        return content;
+    }
 }
@@ -9,9 +9,10 @@ import java.util.Objects;
 /**
  * Immutable wrapper class around byte[].
  *
- * Enforces value-semantice for `equals` and `hashCode`.
+ * Enforces value-semantice for `equals` and `hashCode`. Bytes are ordered lexicographically
+ * as unsigned values.
  */
-public final class Bytes {
+public final class Bytes implements Comparable<Bytes> {
     private final byte[] content;
 
     private static final Bytes EMPTY = new Bytes(new byte[0]);
@@ -51,4 +52,13 @@ public final class Bytes {
         return Arrays.hashCode(content);
     }
 
+    public int compareTo(Bytes other) {
+        int length = Math.min(this.content.length, other.content.length);
+        for (int i = 0; i < length; i++) {
+            int result = Integer.compare(Byte.toUnsignedInt(this.content[i]), Byte.toUnsignedInt(other.content[i]));
+            if (result != 0) return result;
+        }
+        return Integer.compare(this.content.length, other.content.length);
+    }
+
 }
@@ -0,0 +1,25 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.serde;
+
+/**
+ * Comparisons consistent with the ordering of the corresponding Rust values.
+ */
+public final class Comparisons {
+    private Comparisons() {}
+
+    /// Compare strings by Unicode code points, that is, in the order of their UTF-8 encodings.
+    public static int compareStrings(String left, String right) {
+        int i = 0;
+        int j = 0;
+        while (i < left.length() && j < right.length()) {
+            int x = left.codePointAt(i);
+            int y = right.codePointAt(j);
+            if (x != y) return Integer.compare(x, y);
+            i += Character.charCount(x);
+            j += Character.charCount(y);
+        }
+        return Boolean.compare(i < left.length(), j < right.length());
+    }
+}
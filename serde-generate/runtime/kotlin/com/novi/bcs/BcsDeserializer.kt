@@ -0,0 +1,47 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.bcs
+
+import com.novi.serde.BinaryDeserializer
+import com.novi.serde.DeserializationError
+import com.novi.serde.Slice
+
+class BcsDeserializer(input: ByteArray) : BinaryDeserializer(input, BcsSerializer.MAX_CONTAINER_DEPTH) {
+    override fun deserializeF32(): Float {
+        throw DeserializationError("Not implemented: deserializeF32")
+    }
+
+    override fun deserializeF64(): Double {
+        throw DeserializationError("Not implemented: deserializeF64")
+    }
+
+    private fun deserializeUleb128AsU32(): Int {
+        var value = 0L
+        for (shift in 0 until 32 step 7) {
+            val x = getByte().toInt()
+            val digit = x and 0x7F
+            value = value or (digit.toLong() shl shift)
+            if (value > Int.MAX_VALUE) {
+                throw DeserializationError("Overflow while parsing uleb128-encoded uint32 value")
+            }
+            if (digit == x) {
+                if (shift > 0 && digit == 0) {
+                    throw DeserializationError("Invalid uleb128 number (unexpected zero digit)")
+                }
+                return value.toInt()
+            }
+        }
+        throw DeserializationError("Overflow while parsing uleb128-encoded uint32 value")
+    }
+
+    override fun deserializeLen(): Long = deserializeUleb128AsU32().toLong()
+
+    override fun deserializeVariantIndex(): Int = deserializeUleb128AsU32()
+
+    override fun checkThatKeySlicesAreIncreasing(key1: Slice, key2: Slice) {
+        if (Slice.compareBytes(input.array(), key1, key2) >= 0) {
+            throw DeserializationError("Error while decoding map: keys are not serialized in the expected order")
+        }
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.bcs
+
+import com.novi.serde.BinarySerializer
+import com.novi.serde.SerializationError
+import com.novi.serde.Slice
+
+class BcsSerializer : BinarySerializer(MAX_CONTAINER_DEPTH) {
+    override fun serializeF32(value: Float) {
+        throw SerializationError("Not implemented: serializeF32")
+    }
+
+    override fun serializeF64(value: Double) {
+        throw SerializationError("Not implemented: serializeF64")
+    }
+
+    private fun serializeU32AsUleb128(value: Int) {
+        var rest = value
+        while ((rest ushr 7) != 0) {
+            output.write((rest and 0x7F) or 0x80)
+            rest = rest ushr 7
+        }
+        output.write(rest)
+    }
+
+    override fun serializeLen(value: Long) {
+        if (value < 0 || value > MAX_LENGTH) {
+            throw SerializationError("Incorrect length value")
+        }
+        serializeU32AsUleb128(value.toInt())
+    }
+
+    override fun serializeVariantIndex(value: Int) {
+        serializeU32AsUleb128(value)
+    }
+
+    override fun sortMapEntries(offsets: IntArray) {
+        if (offsets.size <= 1) {
+            return
+        }
+        val offset0 = offsets[0]
+        val total = output.size()
+        val content = output.buffer
+        val slices = offsets.indices
+            .map { i -> Slice(offsets[i], if (i + 1 < offsets.size) offsets[i + 1] else total) }
+            .sortedWith { slice1, slice2 -> Slice.compareBytes(content, slice1, slice2) }
+
+        val oldContent = content.copyOfRange(offset0, total)
+        var position = offset0
+        for (slice in slices) {
+            val length = slice.end - slice.start
+            System.arraycopy(oldContent, slice.start - offset0, content, position, length)
+            position += length
+        }
+    }
+
+    companion object {
+        const val MAX_LENGTH: Long = 2147483647 // Int.MAX_VALUE
+        const val MAX_CONTAINER_DEPTH: Long = 500
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.bcs
+
+import java.math.BigInteger
+
+private fun bytes(vararg values: Int): ByteArray = ByteArray(values.size) { values[it].toByte() }
+
+private fun assertFails(block: () -> Unit) {
+    try {
+        block()
+    } catch (e: IllegalArgumentException) {
+        return
+    }
+    throw AssertionError("Expected an exception")
+}
+
+fun testSerializeU128() {
+    var serializer = BcsSerializer()
+    serializer.serializeU128(BigInteger.ONE.shiftLeft(128).subtract(BigInteger.ONE))
+    check(serializer.getBytes().contentEquals(ByteArray(16) { -1 }))
+
+    serializer = BcsSerializer()
+    serializer.serializeU128(BigInteger.ONE)
+    check(serializer.getBytes().contentEquals(bytes(1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0)))
+
+    assertFails { serializer.serializeU128(BigInteger.ONE.negate()) }
+    assertFails { serializer.serializeU128(BigInteger.ONE.shiftLeft(128)) }
+}
+
+fun testSerializeI128() {
+    var serializer = BcsSerializer()
+    serializer.serializeI128(BigInteger.ONE.negate())
+    check(serializer.getBytes().contentEquals(ByteArray(16) { -1 }))
+
+    serializer = BcsSerializer()
+    serializer.serializeI128(BigInteger.ONE.shiftLeft(127).negate())
+    check(serializer.getBytes().contentEquals(bytes(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80)))
+
+    assertFails { serializer.serializeI128(BigInteger.ONE.shiftLeft(127)) }
+    assertFails { serializer.serializeI128(BigInteger.ONE.shiftLeft(127).add(BigInteger.ONE).negate()) }
+}
+
+fun testDeserializeI128() {
+    val deserializer = BcsDeserializer(ByteArray(16) { -1 } + ByteArray(16) { -1 })
+    check(deserializer.deserializeI128() == BigInteger.ONE.negate())
+    check(deserializer.deserializeU128() == BigInteger.ONE.shiftLeft(128).subtract(BigInteger.ONE))
+}
+
+fun testUleb128() {
+    val serializer = BcsSerializer()
+    serializer.serializeLen(300)
+    check(serializer.getBytes().contentEquals(bytes(0xAC, 0x02)))
+    check(BcsDeserializer(bytes(0xAC, 0x02)).deserializeLen() == 300L)
+    // Non-canonical encodings are rejected.
+    try {
+        BcsDeserializer(bytes(0x80, 0x00)).deserializeLen()
+        throw AssertionError("Expected an exception")
+    } catch (e: com.novi.serde.DeserializationError) {
+        // All good
+    }
+}
+
+fun testSerializerSliceOrdering() {
+    val serializer = BcsSerializer()
+    serializer.serializeU8(0xFFu)
+    serializer.serializeU32(1u)
+    serializer.serializeU32(1u)
+    serializer.serializeU32(2u)
+    check(serializer.getBytes().contentEquals(bytes(0xFF, 1, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0)))
+
+    serializer.sortMapEntries(intArrayOf(1, 2, 4, 7, 8, 9))
+    check(serializer.getBytes().contentEquals(bytes(0xFF, 0, 0, 0, 0, 0, 1, 0, 1, 2, 0, 0, 0)))
+}
+
+fun main() {
+    testSerializeU128()
+    testSerializeI128()
+    testDeserializeI128()
+    testUleb128()
+    testSerializerSliceOrdering()
+}
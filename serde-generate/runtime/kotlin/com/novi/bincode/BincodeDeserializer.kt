@@ -0,0 +1,156 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.bincode
+
+import com.novi.serde.BinaryDeserializer
+import com.novi.serde.DeserializationError
+import com.novi.serde.Slice
+import java.math.BigInteger
+import java.nio.ByteOrder
+
+// See `BincodeSerializer` for the meaning of the options. Reading more than `limit` bytes
+// fails, as with the option `with_limit` of the Rust crate.
+class BincodeDeserializer(
+    input: ByteArray,
+    private val varintEncoding: Boolean = false,
+    bigEndian: Boolean = false,
+    private val limit: Long = Long.MAX_VALUE
+) : BinaryDeserializer(input, Long.MAX_VALUE) {
+    init {
+        if (bigEndian) {
+            this.input.order(ByteOrder.BIG_ENDIAN)
+        }
+    }
+
+    // Check that `size` more bytes may be read within the limit.
+    private fun checkLimit(size: Long) {
+        if (size > limit - input.position()) {
+            throw DeserializationError("Exceeded the size limit of the encoding")
+        }
+    }
+
+    override fun getByte(): Byte {
+        checkLimit(1)
+        return super.getByte()
+    }
+
+    override fun getShort(): Short {
+        checkLimit(2)
+        return super.getShort()
+    }
+
+    override fun getInt(): Int {
+        checkLimit(4)
+        return super.getInt()
+    }
+
+    override fun getLong(): Long {
+        checkLimit(8)
+        return super.getLong()
+    }
+
+    override fun getFloat(): Float {
+        checkLimit(4)
+        return super.getFloat()
+    }
+
+    override fun getDouble(): Double {
+        checkLimit(8)
+        return super.getDouble()
+    }
+
+    override fun read(length: Long): ByteArray {
+        // Check the length before allocating the content.
+        checkLimit(length)
+        return super.read(length)
+    }
+
+    // Read a value between 0 and 2^128 - 1 on 16 bytes.
+    private fun readFixint128(): BigInteger {
+        val content = read(16)
+        if (input.order() == ByteOrder.LITTLE_ENDIAN) {
+            content.reverse()
+        }
+        return BigInteger(1, content)
+    }
+
+    // Read the value following a varint tag, for values of at most 64 bits.
+    private fun readVarintValue(tag: Int): Long =
+        when (tag) {
+            in 0..250 -> tag.toLong()
+            251 -> getShort().toLong() and 0xFFFFL
+            252 -> getInt().toLong() and 0xFFFFFFFFL
+            253 -> getLong()
+            else -> throw DeserializationError("Invalid tag for varint-encoded value")
+        }
+
+    // Read a varint-encoded value of at most 64 bits.
+    private fun deserializeVarint(bits: Int): Long {
+        val value = readVarintValue(getByte().toInt() and 0xFF)
+        if (bits < 64 && (value ushr bits) != 0L) {
+            throw DeserializationError("Overflow while parsing varint-encoded value")
+        }
+        return value
+    }
+
+    private fun deserializeVarint128(): BigInteger {
+        val tag = getByte().toInt() and 0xFF
+        if (tag == 254) {
+            return readFixint128()
+        }
+        return BigInteger(java.lang.Long.toUnsignedString(readVarintValue(tag)))
+    }
+
+    private fun zigzagDecode(value: Long): Long = (value ushr 1) xor -(value and 1)
+
+    override fun deserializeU16(): UShort =
+        if (varintEncoding) deserializeVarint(16).toUShort() else super.deserializeU16()
+
+    override fun deserializeU32(): UInt =
+        if (varintEncoding) deserializeVarint(32).toUInt() else super.deserializeU32()
+
+    override fun deserializeU64(): ULong =
+        if (varintEncoding) deserializeVarint(64).toULong() else super.deserializeU64()
+
+    override fun deserializeU128(): BigInteger =
+        if (varintEncoding) deserializeVarint128() else readFixint128()
+
+    override fun deserializeI16(): Short =
+        if (varintEncoding) zigzagDecode(deserializeVarint(16)).toShort() else super.deserializeI16()
+
+    override fun deserializeI32(): Int =
+        if (varintEncoding) zigzagDecode(deserializeVarint(32)).toInt() else super.deserializeI32()
+
+    override fun deserializeI64(): Long =
+        if (varintEncoding) zigzagDecode(deserializeVarint(64)) else super.deserializeI64()
+
+    override fun deserializeI128(): BigInteger {
+        if (varintEncoding) {
+            val value = deserializeVarint128()
+            val result = value.shiftRight(1)
+            return if (value.testBit(0)) result.negate().subtract(BigInteger.ONE) else result
+        }
+        val value = readFixint128()
+        return if (value.testBit(127)) value.subtract(BigInteger.ONE.shiftLeft(128)) else value
+    }
+
+    override fun deserializeF32(): Float = getFloat()
+
+    override fun deserializeF64(): Double = getDouble()
+
+    override fun deserializeLen(): Long {
+        val value = if (varintEncoding) deserializeVarint(64) else getLong()
+        if (value < 0 || value > Int.MAX_VALUE) {
+            throw DeserializationError("Incorrect length value")
+        }
+        return value
+    }
+
+    override fun deserializeVariantIndex(): Int =
+        if (varintEncoding) deserializeVarint(32).toInt() else getInt()
+
+    override fun checkThatKeySlicesAreIncreasing(key1: Slice, key2: Slice) {
+        // Not required by the format.
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.bincode
+
+import com.novi.serde.BinarySerializer
+import java.math.BigInteger
+
+// `varintEncoding` and `bigEndian` correspond to the options `with_varint_encoding` and
+// `with_big_endian` of the Rust crate.
+class BincodeSerializer(
+    private val varintEncoding: Boolean = false,
+    private val bigEndian: Boolean = false
+) : BinarySerializer(Long.MAX_VALUE) {
+    // Write the `size` lowest bytes of `value` in the configured byte order.
+    private fun writeFixint(value: Long, size: Int) {
+        for (i in 0 until size) {
+            val shift = if (bigEndian) 8 * (size - 1 - i) else 8 * i
+            output.write((value ushr shift).toInt() and 0xFF)
+        }
+    }
+
+    // Write the 16 lowest bytes of the two's complement of `value` in the configured byte order.
+    private fun writeFixint(value: BigInteger) {
+        for (i in 0 until 16) {
+            val shift = if (bigEndian) 8 * (15 - i) else 8 * i
+            output.write(value.shiftRight(shift).toInt() and 0xFF)
+        }
+    }
+
+    // Write an unsigned 64-bit value using the varint encoding.
+    private fun serializeVarint(value: Long) {
+        when {
+            value.toULong() <= 250UL -> output.write(value.toInt())
+            value.toULong() <= 0xFFFFUL -> {
+                output.write(251)
+                writeFixint(value, 2)
+            }
+            value.toULong() <= 0xFFFFFFFFUL -> {
+                output.write(252)
+                writeFixint(value, 4)
+            }
+            else -> {
+                output.write(253)
+                writeFixint(value, 8)
+            }
+        }
+    }
+
+    private fun serializeVarint(value: BigInteger) {
+        if (value.bitLength() <= 64) {
+            serializeVarint(value.toLong())
+        } else {
+            output.write(254)
+            writeFixint(value)
+        }
+    }
+
+    private fun serializeUnsigned(value: Long, size: Int) {
+        if (varintEncoding) {
+            serializeVarint(value)
+        } else {
+            writeFixint(value, size)
+        }
+    }
+
+    override fun serializeU16(value: UShort) {
+        serializeUnsigned(value.toLong(), 2)
+    }
+
+    override fun serializeU32(value: UInt) {
+        serializeUnsigned(value.toLong(), 4)
+    }
+
+    override fun serializeU64(value: ULong) {
+        serializeUnsigned(value.toLong(), 8)
+    }
+
+    override fun serializeU128(value: BigInteger) {
+        require(value.signum() >= 0 && value.bitLength() <= 128) {
+            "Invalid value for an unsigned int128"
+        }
+        if (varintEncoding) {
+            serializeVarint(value)
+        } else {
+            writeFixint(value)
+        }
+    }
+
+    override fun serializeI16(value: Short) {
+        val v = value.toInt()
+        if (varintEncoding) {
+            serializeVarint((((v shl 1) xor (v shr 15)) and 0xFFFF).toLong())
+        } else {
+            writeFixint(v.toLong(), 2)
+        }
+    }
+
+    override fun serializeI32(value: Int) {
+        if (varintEncoding) {
+            serializeVarint(((value shl 1) xor (value shr 31)).toLong() and 0xFFFFFFFFL)
+        } else {
+            writeFixint(value.toLong(), 4)
+        }
+    }
+
+    override fun serializeI64(value: Long) {
+        if (varintEncoding) {
+            serializeVarint((value shl 1) xor (value shr 63))
+        } else {
+            writeFixint(value, 8)
+        }
+    }
+
+    override fun serializeI128(value: BigInteger) {
+        require(value.bitLength() <= 127) { "Invalid value for a signed int128" }
+        if (varintEncoding) {
+            // "Zigzag" encoding.
+            val zigzag = value.shiftLeft(1)
+            serializeVarint(if (value.signum() >= 0) zigzag else zigzag.negate().subtract(BigInteger.ONE))
+        } else {
+            writeFixint(value)
+        }
+    }
+
+    override fun serializeF32(value: Float) {
+        writeFixint(value.toRawBits().toLong(), 4)
+    }
+
+    override fun serializeF64(value: Double) {
+        writeFixint(value.toRawBits(), 8)
+    }
+
+    override fun serializeLen(value: Long) {
+        serializeUnsigned(value, 8)
+    }
+
+    override fun serializeVariantIndex(value: Int) {
+        serializeUnsigned(value.toLong() and 0xFFFFFFFFL, 4)
+    }
+
+    override fun sortMapEntries(offsets: IntArray) {
+        // Not required by the format.
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.serde
+
+import java.math.BigInteger
+import java.nio.BufferUnderflowException
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+import java.nio.charset.CharacterCodingException
+import java.nio.charset.StandardCharsets
+
+abstract class BinaryDeserializer(input: ByteArray, maxContainerDepth: Long) : Deserializer {
+    protected val input: ByteBuffer = ByteBuffer.wrap(input).order(ByteOrder.LITTLE_ENDIAN)
+    private var containerDepthBudget = maxContainerDepth
+
+    override fun increaseContainerDepth() {
+        if (containerDepthBudget == 0L) {
+            throw DeserializationError("Exceeded maximum container depth")
+        }
+        containerDepthBudget -= 1
+    }
+
+    override fun decreaseContainerDepth() {
+        containerDepthBudget += 1
+    }
+
+    override fun deserializeStr(): String {
+        val content = read(deserializeLen())
+        try {
+            return StandardCharsets.UTF_8.newDecoder().decode(ByteBuffer.wrap(content)).toString()
+        } catch (e: CharacterCodingException) {
+            throw DeserializationError("Incorrect UTF8 string")
+        }
+    }
+
+    override fun deserializeBytes(): Bytes = Bytes(read(deserializeLen()))
+
+    override fun deserializeBool(): Boolean =
+        when (getByte().toInt()) {
+            0 -> false
+            1 -> true
+            else -> throw DeserializationError("Incorrect boolean value")
+        }
+
+    override fun deserializeUnit() {}
+
+    override fun deserializeChar(): Char {
+        throw DeserializationError("Not implemented: deserializeChar")
+    }
+
+    override fun deserializeU8(): UByte = getByte().toUByte()
+
+    override fun deserializeU16(): UShort = getShort().toUShort()
+
+    override fun deserializeU32(): UInt = getInt().toUInt()
+
+    override fun deserializeU64(): ULong = getLong().toULong()
+
+    override fun deserializeU128(): BigInteger {
+        val signed = deserializeI128()
+        return if (signed.signum() >= 0) signed else signed.add(BigInteger.ONE.shiftLeft(128))
+    }
+
+    override fun deserializeI8(): Byte = getByte()
+
+    override fun deserializeI16(): Short = getShort()
+
+    override fun deserializeI32(): Int = getInt()
+
+    override fun deserializeI64(): Long = getLong()
+
+    override fun deserializeI128(): BigInteger {
+        val content = read(16)
+        content.reverse()
+        return BigInteger(content)
+    }
+
+    override fun deserializeOptionTag(): Boolean = deserializeBool()
+
+    override fun getBufferOffset(): Int = input.position()
+
+    protected open fun getByte(): Byte = underflowAsError { input.get() }
+
+    protected open fun getShort(): Short = underflowAsError { input.getShort() }
+
+    protected open fun getInt(): Int = underflowAsError { input.getInt() }
+
+    protected open fun getLong(): Long = underflowAsError { input.getLong() }
+
+    protected open fun getFloat(): Float = underflowAsError { input.getFloat() }
+
+    protected open fun getDouble(): Double = underflowAsError { input.getDouble() }
+
+    // Read `length` bytes, failing before any allocation if the input is too short.
+    protected open fun read(length: Long): ByteArray {
+        if (length < 0 || length > input.remaining()) {
+            throw DeserializationError(INPUT_NOT_LARGE_ENOUGH)
+        }
+        val content = ByteArray(length.toInt())
+        input.get(content)
+        return content
+    }
+
+    private inline fun <T> underflowAsError(read: () -> T): T {
+        try {
+            return read()
+        } catch (e: BufferUnderflowException) {
+            throw DeserializationError(INPUT_NOT_LARGE_ENOUGH)
+        }
+    }
+
+    companion object {
+        const val INPUT_NOT_LARGE_ENOUGH = "Input is not large enough"
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.serde
+
+import java.io.ByteArrayOutputStream
+import java.math.BigInteger
+
+abstract class BinarySerializer(maxContainerDepth: Long) : Serializer {
+    protected val output = Output()
+    private var containerDepthBudget = maxContainerDepth
+
+    override fun increaseContainerDepth() {
+        if (containerDepthBudget == 0L) {
+            throw SerializationError("Exceeded maximum container depth")
+        }
+        containerDepthBudget -= 1
+    }
+
+    override fun decreaseContainerDepth() {
+        containerDepthBudget += 1
+    }
+
+    override fun serializeStr(value: String) {
+        serializeBytes(Bytes(value.toByteArray(Charsets.UTF_8)))
+    }
+
+    override fun serializeBytes(value: Bytes) {
+        val content = value.content()
+        serializeLen(content.size.toLong())
+        output.write(content, 0, content.size)
+    }
+
+    override fun serializeBool(value: Boolean) {
+        output.write(if (value) 1 else 0)
+    }
+
+    override fun serializeUnit(value: Unit) {}
+
+    override fun serializeChar(value: Char) {
+        throw SerializationError("Not implemented: serializeChar")
+    }
+
+    override fun serializeU8(value: UByte) {
+        output.write(value.toInt())
+    }
+
+    override fun serializeU16(value: UShort) {
+        writeLittleEndian(value.toLong(), 2)
+    }
+
+    override fun serializeU32(value: UInt) {
+        writeLittleEndian(value.toLong(), 4)
+    }
+
+    override fun serializeU64(value: ULong) {
+        writeLittleEndian(value.toLong(), 8)
+    }
+
+    override fun serializeU128(value: BigInteger) {
+        require(value.signum() >= 0 && value.bitLength() <= 128) {
+            "Invalid value for an unsigned int128"
+        }
+        writeLittleEndian(value)
+    }
+
+    override fun serializeI8(value: Byte) {
+        output.write(value.toInt())
+    }
+
+    override fun serializeI16(value: Short) {
+        writeLittleEndian(value.toLong(), 2)
+    }
+
+    override fun serializeI32(value: Int) {
+        writeLittleEndian(value.toLong(), 4)
+    }
+
+    override fun serializeI64(value: Long) {
+        writeLittleEndian(value, 8)
+    }
+
+    override fun serializeI128(value: BigInteger) {
+        require(value.bitLength() <= 127) { "Invalid value for a signed int128" }
+        writeLittleEndian(value)
+    }
+
+    override fun serializeOptionTag(value: Boolean) {
+        output.write(if (value) 1 else 0)
+    }
+
+    override fun getBufferOffset(): Int = output.size()
+
+    override fun getBytes(): ByteArray = output.toByteArray()
+
+    // Write the `size` lowest bytes of `value` in little-endian order.
+    protected fun writeLittleEndian(value: Long, size: Int) {
+        for (i in 0 until size) {
+            output.write((value ushr (8 * i)).toInt() and 0xFF)
+        }
+    }
+
+    // Write the 16 lowest bytes of the two's complement of `value` in little-endian order.
+    protected fun writeLittleEndian(value: BigInteger) {
+        for (i in 0 until 16) {
+            output.write(value.shiftRight(8 * i).toInt() and 0xFF)
+        }
+    }
+
+    // Local extension to provide access to the underlying buffer.
+    class Output : ByteArrayOutputStream() {
+        val buffer: ByteArray
+            get() = buf
+    }
+}
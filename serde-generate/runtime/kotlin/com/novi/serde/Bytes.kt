@@ -0,0 +1,44 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.serde
+
+/**
+ * Immutable wrapper class around `ByteArray`.
+ *
+ * Enforces value-semantics for `equals` and `hashCode`, so that generated data classes
+ * compare byte strings by content. Bytes are ordered lexicographically as unsigned values.
+ */
+class Bytes(content: ByteArray) : Comparable<Bytes> {
+    private val content: ByteArray = content.copyOf()
+
+    fun content(): ByteArray = content.copyOf()
+
+    val size: Int
+        get() = content.size
+
+    override fun equals(other: Any?): Boolean {
+        if (this === other) return true
+        if (other !is Bytes) return false
+        return content.contentEquals(other.content)
+    }
+
+    override fun hashCode(): Int = content.contentHashCode()
+
+    override fun compareTo(other: Bytes): Int {
+        for (i in 0 until minOf(content.size, other.content.size)) {
+            val result = content[i].toUByte().compareTo(other.content[i].toUByte())
+            if (result != 0) return result
+        }
+        return content.size.compareTo(other.content.size)
+    }
+
+    override fun toString(): String =
+        content.joinToString(separator = "", prefix = "Bytes(", postfix = ")") { "%02x".format(it) }
+
+    companion object {
+        private val EMPTY = Bytes(ByteArray(0))
+
+        fun empty(): Bytes = EMPTY
+    }
+}
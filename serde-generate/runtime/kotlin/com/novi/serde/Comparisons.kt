@@ -0,0 +1,23 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.serde
+
+/**
+ * Comparisons consistent with the ordering of the corresponding Rust values.
+ */
+object Comparisons {
+    /** Compare strings by Unicode code points, that is, in the order of their UTF-8 encodings. */
+    fun compareStrings(left: String, right: String): Int {
+        var i = 0
+        var j = 0
+        while (i < left.length && j < right.length) {
+            val x = left.codePointAt(i)
+            val y = right.codePointAt(j)
+            if (x != y) return x.compareTo(y)
+            i += Character.charCount(x)
+            j += Character.charCount(y)
+        }
+        return (i < left.length).compareTo(j < right.length)
+    }
+}
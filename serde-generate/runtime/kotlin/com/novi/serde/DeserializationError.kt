@@ -0,0 +1,6 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.serde
+
+class DeserializationError(message: String) : Exception(message)
@@ -0,0 +1,56 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.serde
+
+import java.math.BigInteger
+
+interface Deserializer {
+    fun deserializeStr(): String
+
+    fun deserializeBytes(): Bytes
+
+    fun deserializeBool(): Boolean
+
+    fun deserializeUnit(): Unit
+
+    fun deserializeChar(): Char
+
+    fun deserializeF32(): Float
+
+    fun deserializeF64(): Double
+
+    fun deserializeU8(): UByte
+
+    fun deserializeU16(): UShort
+
+    fun deserializeU32(): UInt
+
+    fun deserializeU64(): ULong
+
+    fun deserializeU128(): BigInteger
+
+    fun deserializeI8(): Byte
+
+    fun deserializeI16(): Short
+
+    fun deserializeI32(): Int
+
+    fun deserializeI64(): Long
+
+    fun deserializeI128(): BigInteger
+
+    fun deserializeLen(): Long
+
+    fun deserializeVariantIndex(): Int
+
+    fun deserializeOptionTag(): Boolean
+
+    fun increaseContainerDepth()
+
+    fun decreaseContainerDepth()
+
+    fun getBufferOffset(): Int
+
+    fun checkThatKeySlicesAreIncreasing(key1: Slice, key2: Slice)
+}
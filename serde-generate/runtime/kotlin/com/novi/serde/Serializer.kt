@@ -0,0 +1,58 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.serde
+
+import java.math.BigInteger
+
+interface Serializer {
+    fun serializeStr(value: String)
+
+    fun serializeBytes(value: Bytes)
+
+    fun serializeBool(value: Boolean)
+
+    fun serializeUnit(value: Unit)
+
+    fun serializeChar(value: Char)
+
+    fun serializeF32(value: Float)
+
+    fun serializeF64(value: Double)
+
+    fun serializeU8(value: UByte)
+
+    fun serializeU16(value: UShort)
+
+    fun serializeU32(value: UInt)
+
+    fun serializeU64(value: ULong)
+
+    fun serializeU128(value: BigInteger)
+
+    fun serializeI8(value: Byte)
+
+    fun serializeI16(value: Short)
+
+    fun serializeI32(value: Int)
+
+    fun serializeI64(value: Long)
+
+    fun serializeI128(value: BigInteger)
+
+    fun serializeLen(value: Long)
+
+    fun serializeVariantIndex(value: Int)
+
+    fun serializeOptionTag(value: Boolean)
+
+    fun increaseContainerDepth()
+
+    fun decreaseContainerDepth()
+
+    fun getBufferOffset(): Int
+
+    fun sortMapEntries(offsets: IntArray)
+
+    fun getBytes(): ByteArray
+}
@@ -0,0 +1,23 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package com.novi.serde
+
+data class Slice(val start: Int, val end: Int) {
+    companion object {
+        // Lexicographic comparison between the (unsigned!) bytes referenced by `slice1` and `slice2`
+        // into `content`.
+        fun compareBytes(content: ByteArray, slice1: Slice, slice2: Slice): Int {
+            val length1 = slice1.end - slice1.start
+            val length2 = slice2.end - slice2.start
+            for (i in 0 until minOf(length1, length2)) {
+                val byte1 = content[slice1.start + i].toInt() and 0xFF
+                val byte2 = content[slice2.start + i].toInt() and 0xFF
+                if (byte1 != byte2) {
+                    return byte1.compareTo(byte2)
+                }
+            }
+            return length1.compareTo(length2)
+        }
+    }
+}
@@ -1,32 +1,107 @@
 (* Copyright (c) Zefchain Labs, Inc.
  * SPDX-License-Identifier: MIT OR Apache-2.0 *)
 
+open Stdint
 open Common.Misc
 include Common.Deserialize
 
+module Options = Common.Bincode_options
+
 let max_length = 1 lsl 31 - 1
 
+(* Check that `size` more bytes may be read within the limit. *)
+let check_limit b size =
+  match !Options.limit with
+  | Some limit when size > limit - b.offset -> failwith "exceeded the size limit of the encoding"
+  | _ -> ()
+
+(* Read a fixed-size integer in the configured byte order. *)
+let fixint of_le of_be size b =
+  check_limit b size;
+  let r = if !Options.big_endian then of_be b.buffer b.offset else of_le b.buffer b.offset in
+  b.offset <- b.offset + size;
+  {r; depth=0}
+
+let bool b = check_limit b 1; bool b
+let uint8 b = check_limit b 1; uint8 b
+let int8 b = check_limit b 1; int8 b
+
+(* Read a varint-encoded value of at most `bits` bits. *)
+let varint bits b =
+  let tag = Uint8.to_int (uint8 b).r in
+  let v =
+    if tag <= 250 then Uint128.of_int tag
+    else if tag = 251 then
+      Uint128.of_uint16 (fixint Uint16.of_bytes_little_endian Uint16.of_bytes_big_endian 2 b).r
+    else if tag = 252 then
+      Uint128.of_uint32 (fixint Uint32.of_bytes_little_endian Uint32.of_bytes_big_endian 4 b).r
+    else if tag = 253 then
+      Uint128.of_uint64 (fixint Uint64.of_bytes_little_endian Uint64.of_bytes_big_endian 8 b).r
+    else if tag = 254 && bits = 128 then
+      (fixint Uint128.of_bytes_little_endian Uint128.of_bytes_big_endian 16 b).r
+    else failwith "invalid tag for varint-encoded value" in
+  if bits < 128 && Uint128.compare (Uint128.shift_right v bits) Uint128.zero <> 0 then
+    failwith "overflow while parsing varint-encoded value";
+  v
+
+let unsigned of_le of_be of_uint128 size b =
+  if !Options.varint_encoding then {r = of_uint128 (varint (8 * size) b); depth=0}
+  else fixint of_le of_be size b
+
+(* Signed integers use a "zigzag" encoding before the varint encoding. *)
+let signed of_le of_be of_int128 size b =
+  if !Options.varint_encoding then
+    let v = varint (8 * size) b in
+    let h = Int128.of_uint128 (Uint128.shift_right v 1) in
+    let odd = Uint128.compare (Uint128.logand v Uint128.one) Uint128.zero <> 0 in
+    {r = of_int128 (if odd then Int128.lognot h else h); depth=0}
+  else fixint of_le of_be size b
+
+let uint16 b = unsigned Uint16.of_bytes_little_endian Uint16.of_bytes_big_endian Uint16.of_uint128 2 b
+let uint32 b = unsigned Uint32.of_bytes_little_endian Uint32.of_bytes_big_endian Uint32.of_uint128 4 b
+let uint64 b = unsigned Uint64.of_bytes_little_endian Uint64.of_bytes_big_endian Uint64.of_uint128 8 b
+let uint128 b = unsigned Uint128.of_bytes_little_endian Uint128.of_bytes_big_endian (fun v -> v) 16 b
+let int16 b = signed Int16.of_bytes_little_endian Int16.of_bytes_big_endian Int16.of_int128 2 b
+let int32 b = signed Int32.of_bytes_little_endian Int32.of_bytes_big_endian Int32.of_int128 4 b
+let int64 b = signed Int64.of_bytes_little_endian Int64.of_bytes_big_endian Int64.of_int128 8 b
+let int128 b = signed Int128.of_bytes_little_endian Int128.of_bytes_big_endian (fun v -> v) 16 b
+
+let option f b =
+  let bo = bool b in
+  if not bo.r then {r=None; depth=0}
+  else
+    let r = f b in
+    {r with r=Some r.r}
+
 let char b =
+  check_limit b 1;
   let c = Bytes.get b.buffer b.offset in
   b.offset <- b.offset + 1;
   {Common.Misc.r=c; depth=0}
 
 let length b =
-  let i = Stdint.Uint64.to_int @@ (uint64 b).r in
+  let i = Uint64.to_int @@ (uint64 b).r in
   if i < 0 || i > max_length then failwith "integer above max length"
   else i
 
-let variant_index b = Stdint.Uint32.to_int @@ (uint32 b).r
+(* Read the length of some bytes and check it before allocating the content. *)
+let content_length b =
+  let n = length b in
+  check_limit b n;
+  n
+
+let variant_index b = Uint32.to_int @@ (uint32 b).r
 
+(* Floating-point numbers are never varint-encoded. *)
 let float32 b =
-  let i = int32 b in
+  let i = fixint Int32.of_bytes_little_endian Int32.of_bytes_big_endian 4 b in
   { i with r = Stdlib.Int32.float_of_bits i.r }
 
 let float64 b =
-  let i = int64 b in
+  let i = fixint Int64.of_bytes_little_endian Int64.of_bytes_big_endian 8 b in
   { i with r = Stdlib.Int64.float_of_bits i.r }
 
 let variable f b = variable length f b
-let string b = string length b
-let bytes b = bytes length b
+let string b = string content_length b
+let bytes b = bytes content_length b
 let map ser_k de_k de_v b = map length ser_k de_k de_v b
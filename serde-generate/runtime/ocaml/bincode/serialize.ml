@@ -1,23 +1,71 @@
 (* Copyright (c) Zefchain Labs, Inc.
  * SPDX-License-Identifier: MIT OR Apache-2.0 *)
 
+open Stdint
 open Common.Misc
 include Common.Serialize
 
+module Options = Common.Bincode_options
+
 let max_depth : int option = None
 let max_length = 1 lsl 31 - 1
 
+(* Write a fixed-size integer in the configured byte order. *)
+let fixint to_le to_be size i =
+  let b = Bytes.create size in
+  if !Options.big_endian then to_be i b 0 else to_le i b 0;
+  {r=b; depth=0}
+
+(* Integers up to 250 use a single byte. Larger values use a tag byte followed by the
+   smallest sufficient fixed-size integer. *)
+let varint (v : uint128) =
+  let fits bits = Uint128.compare (Uint128.shift_right v bits) Uint128.zero = 0 in
+  let tagged tag r = {r=Bytes.cat (Bytes.make 1 (Char.chr tag)) r.r; depth=0} in
+  if Uint128.compare v (Uint128.of_int 250) <= 0 then
+    {r=Bytes.make 1 (Char.chr (Uint128.to_int v)); depth=0}
+  else if fits 16 then
+    tagged 251 (fixint Uint16.to_bytes_little_endian Uint16.to_bytes_big_endian 2 (Uint16.of_uint128 v))
+  else if fits 32 then
+    tagged 252 (fixint Uint32.to_bytes_little_endian Uint32.to_bytes_big_endian 4 (Uint32.of_uint128 v))
+  else if fits 64 then
+    tagged 253 (fixint Uint64.to_bytes_little_endian Uint64.to_bytes_big_endian 8 (Uint64.of_uint128 v))
+  else
+    tagged 254 (fixint Uint128.to_bytes_little_endian Uint128.to_bytes_big_endian 16 v)
+
+let unsigned to_le to_be to_uint128 size i =
+  if !Options.varint_encoding then varint (to_uint128 i)
+  else fixint to_le to_be size i
+
+(* Signed integers use a "zigzag" encoding before the varint encoding. *)
+let signed to_le to_be to_int128 size i =
+  if !Options.varint_encoding then
+    let n = to_int128 i in
+    if Int128.compare n Int128.zero >= 0 then
+      varint (Uint128.shift_left (Uint128.of_int128 n) 1)
+    else
+      varint (Uint128.add (Uint128.shift_left (Uint128.of_int128 (Int128.lognot n)) 1) Uint128.one)
+  else fixint to_le to_be size i
+
+let uint16 i = unsigned Uint16.to_bytes_little_endian Uint16.to_bytes_big_endian Uint128.of_uint16 2 i
+let uint32 i = unsigned Uint32.to_bytes_little_endian Uint32.to_bytes_big_endian Uint128.of_uint32 4 i
+let uint64 i = unsigned Uint64.to_bytes_little_endian Uint64.to_bytes_big_endian Uint128.of_uint64 8 i
+let uint128 i = unsigned Uint128.to_bytes_little_endian Uint128.to_bytes_big_endian (fun v -> v) 16 i
+let int16 i = signed Int16.to_bytes_little_endian Int16.to_bytes_big_endian Int128.of_int16 2 i
+let int32 i = signed Int32.to_bytes_little_endian Int32.to_bytes_big_endian Int128.of_int32 4 i
+let int64 i = signed Int64.to_bytes_little_endian Int64.to_bytes_big_endian Int128.of_int64 8 i
+let int128 i = signed Int128.to_bytes_little_endian Int128.to_bytes_big_endian (fun v -> v) 16 i
+
 let char (c : char) = {Common.Misc.r=Bytes.make 1 c; depth=0}
 let length i =
   if i > max_length then failwith "integer above max length"
-  else (uint64 @@ Stdint.Uint64.of_int i).r
-let variant_index i = uint32 @@ Stdint.Uint32.of_int i
+  else (uint64 @@ Uint64.of_int i).r
+let variant_index i = uint32 @@ Uint32.of_int i
+
+(* Floating-point numbers are never varint-encoded. *)
 let float32 f =
-  let i = Stdlib.Int32.bits_of_float f in
-  int32 i
+  fixint Int32.to_bytes_little_endian Int32.to_bytes_big_endian 4 (Stdlib.Int32.bits_of_float f)
 let float64 f =
-  let i = Stdlib.Int64.bits_of_float f in
-  int64 i
+  fixint Int64.to_bytes_little_endian Int64.to_bytes_big_endian 8 (Stdlib.Int64.bits_of_float f)
 
 let variable f l = variable length f l
 let string s = string length s
@@ -0,0 +1,31 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+open Stdint
+include Common.Deserialize
+
+let char _ = failwith "char deserialization not supported by borsh"
+
+let length b =
+  let i = Uint32.to_int (uint32 b).r in
+  if i > max_length then failwith "integer above max length"
+  else i
+
+let variant_index b = Uint8.to_int (uint8 b).r
+
+let float32 b =
+  let i = Common.Deserialize.int32 b in
+  let f = Stdlib.Int32.float_of_bits i.r in
+  if Float.is_nan f then failwith "NaN deserialization not supported by borsh"
+  else { i with r = f }
+
+let float64 b =
+  let i = Common.Deserialize.int64 b in
+  let f = Stdlib.Int64.float_of_bits i.r in
+  if Float.is_nan f then failwith "NaN deserialization not supported by borsh"
+  else { i with r = f }
+
+let variable f b = variable length f b
+let string b = string length b
+let bytes b = bytes length b
+let map ser_k de_k de_v b = map length (Sort_keys.key ser_k) de_k de_v b
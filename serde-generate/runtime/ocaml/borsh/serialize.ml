@@ -0,0 +1,45 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+open Stdint
+include Common.Serialize
+
+let max_depth : int option = None
+let max_length = 1 lsl 31 - 1
+
+let char _ = failwith "char serialization not supported by borsh"
+
+let length i =
+  if i < 0 || i > max_length then failwith "integer above max length"
+  else (uint32 (Uint32.of_int i)).r
+
+let variant_index i =
+  if i < 0 || i > 0xff then failwith "variant index above max u8"
+  else uint8 (Uint8.of_int i)
+
+let float32 f =
+  if Float.is_nan f then failwith "NaN serialization not supported by borsh"
+  else Common.Serialize.int32 (Stdlib.Int32.bits_of_float f)
+
+let float64 f =
+  if Float.is_nan f then failwith "NaN serialization not supported by borsh"
+  else Common.Serialize.int64 (Stdlib.Int64.bits_of_float f)
+
+let uint16 i = Sort_keys.integer ~signed:false (uint16 i)
+let uint32 i = Sort_keys.integer ~signed:false (uint32 i)
+let uint64 i = Sort_keys.integer ~signed:false (uint64 i)
+let uint128 i = Sort_keys.integer ~signed:false (uint128 i)
+let int8 i = Sort_keys.integer ~signed:true (int8 i)
+let int16 i = Sort_keys.integer ~signed:true (int16 i)
+let int32 i = Sort_keys.integer ~signed:true (int32 i)
+let int64 i = Sort_keys.integer ~signed:true (int64 i)
+let int128 i = Sort_keys.integer ~signed:true (int128 i)
+
+let variable f l = variable length f l
+let string s = if !Sort_keys.enabled then Sort_keys.string s else string length s
+let bytes b = bytes length b
+
+let map ser_k ser_v m =
+  let compare (k1, _) (k2, _) = Sort_keys.compare ser_k k1 k2 in
+  variable (fun (k, v) -> concat [ ser_k k; ser_v v ])
+    (List.sort compare (Common.Map.bindings m))
@@ -0,0 +1,34 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+(* Borsh sorts map entries by keys as in Rust. While [enabled] is set, integers
+   and strings are serialized into order-preserving encodings instead, so that
+   serialized keys compare in the same way. *)
+let enabled = ref false
+
+let key ser_k k =
+  enabled := true;
+  Fun.protect ~finally:(fun () -> enabled := false) (fun () -> ser_k k)
+
+let compare ser_k k1 k2 =
+  Bytes.compare (key ser_k k1).Common.Misc.r (key ser_k k2).Common.Misc.r
+
+(* Little-endian integers compare as in Rust in big-endian order, with the sign
+   bit flipped. *)
+let integer ~signed (x : bytes Common.Misc.r) =
+  if not !enabled then x
+  else
+    let n = Bytes.length x.r in
+    let r = Bytes.init n (fun i -> Bytes.get x.r (n - 1 - i)) in
+    if signed then Bytes.set_uint8 r 0 (Bytes.get_uint8 r 0 lxor 0x80);
+    { x with r }
+
+(* Strings have their zeros escaped and a terminator added so that prefixes
+   come first. *)
+let string s =
+  let b = Buffer.create (String.length s + 2) in
+  String.iter (fun c ->
+      Buffer.add_char b c;
+      if c = '\000' then Buffer.add_char b '\255') s;
+  Buffer.add_string b "\000\000";
+  { Common.Misc.r = Buffer.to_bytes b; depth = 0 }
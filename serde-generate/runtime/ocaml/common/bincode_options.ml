@@ -0,0 +1,17 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+(* Options of the Bincode runtime, mirroring the configuration of the Rust crate `bincode`.
+   Generated modules set them when they are initialized. *)
+
+(* Integers of at least 16 bits use a variable-length encoding (`with_varint_encoding`). *)
+let varint_encoding = ref false
+
+(* Fixed-size integers and floats are big-endian (`with_big_endian`). *)
+let big_endian = ref false
+
+(* Maximal number of bytes written by serialization and read by deserialization. *)
+let limit : int option ref = ref None
+
+(* Whether deserialization accepts inputs that are not entirely read. *)
+let allow_trailing_bytes = ref false
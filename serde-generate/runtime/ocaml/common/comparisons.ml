@@ -0,0 +1,46 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+(* Orderings follow the Rust derive: `None` first, sequences lexicographically,
+   maps by their bindings sorted by keys. *)
+
+let compare_option cmp a b = match a, b with
+  | None, None -> 0
+  | None, Some _ -> -1
+  | Some _, None -> 1
+  | Some a, Some b -> cmp a b
+
+let rec compare_list cmp a b = match a, b with
+  | [], [] -> 0
+  | [], _ -> -1
+  | _, [] -> 1
+  | x :: a, y :: b ->
+    let c = cmp x y in
+    if c <> 0 then c else compare_list cmp a b
+
+let compare_array cmp a b = compare_list cmp (Array.to_list a) (Array.to_list b)
+
+(* Maps are ordered by serialized keys at deserialization time, which may differ
+   from the order of the keys themselves. *)
+let sorted_bindings cmp m =
+  List.stable_sort (fun (k1, _) (k2, _) -> cmp k1 k2) (Map.bindings m)
+
+let compare_map cmp_k cmp_v a b =
+  let cmp (k1, v1) (k2, v2) =
+    let c = cmp_k k1 k2 in
+    if c <> 0 then c else cmp_v v1 v2 in
+  compare_list cmp (sorted_bindings cmp_k a) (sorted_bindings cmp_k b)
+
+let hash_combine seed h = seed * 31 + h
+
+let hash_option hash = function
+  | None -> 0
+  | Some x -> hash_combine 1 (hash x)
+
+let hash_list hash l = List.fold_left (fun acc x -> hash_combine acc (hash x)) 1 l
+
+let hash_array hash a = Array.fold_left (fun acc x -> hash_combine acc (hash x)) 1 a
+
+(* Commutative, hence independent of the shape of the tree. *)
+let hash_map hash_k hash_v m =
+  Map.fold (fun k v acc -> acc + hash_combine (hash_k k) (hash_v v)) m 0
@@ -2,6 +2,9 @@
  * SPDX-License-Identifier: MIT OR Apache-2.0 *)
 
 module Map = Common.Map
+module Comparisons = Common.Comparisons
+module Validation = Common.Validation
+module Bincode_options = Common.Bincode_options
 type ('k, 'v) map = ('k, 'v) Map.t
 
 include Common.Misc
@@ -14,7 +17,11 @@ let check_depth r = match max_depth with
 
 module Serialize = struct
   include Runtime.Serialize
-  let apply f x = (f x).r
+  let apply f x =
+    let r = (f x).r in
+    match !Bincode_options.limit with
+    | Some limit when Bytes.length r > limit -> failwith "exceeded the size limit of the encoding"
+    | _ -> r
 end
 
 module Deserialize = struct
@@ -25,6 +32,6 @@ module Deserialize = struct
   let apply f buffer =
     let b = {buffer; offset=0} in
     let r = f b in
-    let () = check_length b in
+    let () = if not !Bincode_options.allow_trailing_bytes then check_length b in
     r.r
 end
@@ -0,0 +1,14 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+(* Checks of the constraints that OCaml types do not enforce, e.g. the length of
+   fixed-size arrays. Failures are reported with `failwith` as in serializers. *)
+
+let validate_array size validate a =
+  if Array.length a <> size then
+    failwith (Format.sprintf "invalid length for fixed-size array: %d instead of %d"
+      (Array.length a) size);
+  Array.iter validate a
+
+let validate_map validate_k validate_v m =
+  Map.iter (fun k v -> validate_k k; validate_v v) m
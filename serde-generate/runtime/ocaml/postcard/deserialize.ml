@@ -0,0 +1,62 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+open Stdint
+open Common.Misc
+include Common.Deserialize
+
+let max_length = 1 lsl 31 - 1
+
+let varint bits b =
+  let rec f acc shift =
+    if shift >= bits then failwith "varint-encoded value is too long"
+    else
+      let v = Uint8.to_int (uint8 b).r in
+      let digit = v land 0x7f in
+      if bits - shift < 7 && digit lsr (bits - shift) <> 0 then
+        failwith "integer overflow in varint-encoded value"
+      else
+        let acc = Uint128.logor acc (Uint128.shift_left (Uint128.of_int digit) shift) in
+        if v land 0x80 <> 0 then f acc (shift + 7) else acc in
+  f Uint128.zero 0
+
+let zigzag bits b =
+  let u = varint bits b in
+  Int128.logxor
+    (Int128.of_uint128 @@ Uint128.shift_right u 1)
+    (Int128.neg @@ Int128.of_uint128 @@ Uint128.logand u Uint128.one)
+
+let uint16 b = {Common.Misc.r=Uint16.of_uint128 (varint 16 b); depth=0}
+let uint32 b = {Common.Misc.r=Uint32.of_uint128 (varint 32 b); depth=0}
+let uint64 b = {Common.Misc.r=Uint64.of_uint128 (varint 64 b); depth=0}
+let uint128 b = {Common.Misc.r=varint 128 b; depth=0}
+let int16 b = {Common.Misc.r=Int16.of_int128 (zigzag 16 b); depth=0}
+let int32 b = {Common.Misc.r=Int32.of_int128 (zigzag 32 b); depth=0}
+let int64 b = {Common.Misc.r=Int64.of_int128 (zigzag 64 b); depth=0}
+let int128 b = {Common.Misc.r=zigzag 128 b; depth=0}
+
+let length b =
+  let i = varint 64 b in
+  if Uint128.compare i (Uint128.of_int max_length) > 0 then
+    failwith "integer above max length"
+  else Uint128.to_int i
+
+let variant_index b = Uint128.to_int (varint 32 b)
+
+let float32 b =
+  let i = Common.Deserialize.int32 b in
+  { i with r = Stdlib.Int32.float_of_bits i.r }
+
+let float64 b =
+  let i = Common.Deserialize.int64 b in
+  { i with r = Stdlib.Int64.float_of_bits i.r }
+
+let variable f b = variable length f b
+let string b = string length b
+let bytes b = bytes length b
+let map ser_k de_k de_v b = map length ser_k de_k de_v b
+
+let char b =
+  let s = string b in
+  if String.length s.r <> 1 then failwith "expecting a single character"
+  else {s with r=String.get s.r 0}
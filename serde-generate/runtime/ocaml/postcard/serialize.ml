@@ -0,0 +1,53 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+open Stdint
+open Common.Misc
+include Common.Serialize
+
+let max_depth : int option = None
+let max_length = 1 lsl 31 - 1
+
+let varint (i : uint128) =
+  let buf = Buffer.create 19 in
+  let rec f x =
+    if Uint128.compare x (Uint128.of_int 0x80) < 0 then
+      Buffer.add_uint8 buf (Uint128.to_int x)
+    else begin
+      Buffer.add_uint8 buf
+        ((Uint128.to_int @@ Uint128.logand x (Uint128.of_int 0x7f)) lor 0x80);
+      f (Uint128.shift_right x 7)
+    end in
+  f i;
+  {Common.Misc.r=Buffer.to_bytes buf; depth=0}
+
+(* Signed integers use a "zigzag" encoding before the varint encoding. *)
+let zigzag (i : int128) =
+  varint @@ Uint128.of_int128 @@
+  Int128.logxor (Int128.shift_left i 1) (Int128.shift_right i 127)
+
+let uint16 i = varint (Uint128.of_uint16 i)
+let uint32 i = varint (Uint128.of_uint32 i)
+let uint64 i = varint (Uint128.of_uint64 i)
+let uint128 i = varint i
+let int16 i = zigzag (Int128.of_int16 i)
+let int32 i = zigzag (Int128.of_int32 i)
+let int64 i = zigzag (Int128.of_int64 i)
+let int128 i = zigzag i
+
+let length i =
+  if i > max_length then failwith "integer above max length"
+  else (varint @@ Uint128.of_int i).r
+let variant_index i = varint @@ Uint128.of_int i
+let float32 f =
+  let i = Stdlib.Int32.bits_of_float f in
+  Common.Serialize.int32 i
+let float64 f =
+  let i = Stdlib.Int64.bits_of_float f in
+  Common.Serialize.int64 i
+
+let variable f l = variable length f l
+let string s = string length s
+let bytes b = bytes length b
+let map ser_k ser_v m = map length ser_k ser_v m
+let char (c : char) = string (String.make 1 c)
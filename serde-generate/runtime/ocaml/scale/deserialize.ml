@@ -0,0 +1,54 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+open Stdint
+include Common.Deserialize
+
+let char _ = failwith "char deserialization not supported by scale"
+
+let compact_uint32 b =
+  let byte () = Uint8.to_int (uint8 b).r in
+  let prefix = byte () in
+  let value, minimum =
+    match prefix land 3 with
+    | 0 -> prefix lsr 2, 0
+    | 1 -> (prefix lor (byte () lsl 8)) lsr 2, 1 lsl 6
+    | 2 ->
+      let b1 = byte () in
+      let b2 = byte () in
+      let b3 = byte () in
+      (prefix lor (b1 lsl 8) lor (b2 lsl 16) lor (b3 lsl 24)) lsr 2, 1 lsl 14
+    | _ ->
+      if prefix <> 3 then failwith "compact integer above max u32"
+      else Uint32.to_int (uint32 b).r, 1 lsl 30 in
+  if value < minimum then failwith "non-canonical compact integer"
+  else value
+
+let length b =
+  let i = compact_uint32 b in
+  if i > max_length then failwith "integer above max length"
+  else i
+
+let variant_index b = Uint8.to_int (uint8 b).r
+
+let float32 b =
+  let i = Common.Deserialize.int32 b in
+  { i with r = Stdlib.Int32.float_of_bits i.r }
+
+let float64 b =
+  let i = Common.Deserialize.int64 b in
+  { i with r = Stdlib.Int64.float_of_bits i.r }
+
+let variable f b = variable length f b
+let string b = string length b
+let bytes b = bytes length b
+
+(* Map entries may come in any order and later entries replace earlier ones. *)
+let map ser_k de_k de_v b =
+  let compare k1 k2 = Bytes.compare (ser_k k1).r (ser_k k2).r in
+  let r = variable (fun b ->
+      let k = de_k b in
+      let v = de_v b in
+      {Common.Misc.depth = max k.Common.Misc.depth v.Common.Misc.depth; r = (k.r, v.r)}) b in
+  { r with
+    r = List.fold_left (fun acc (k, v) -> Common.Map.add ~compare k v acc) Common.Map.empty r.r }
@@ -0,0 +1,5 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+module Serialize = Serialize
+module Deserialize = Deserialize
@@ -0,0 +1,32 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+open Stdint
+include Common.Serialize
+
+let max_depth : int option = None
+let max_length = 1 lsl 31 - 1
+
+let char _ = failwith "char serialization not supported by scale"
+
+let compact_uint32 i =
+  if i < 1 lsl 6 then (uint8 (Uint8.of_int (i lsl 2))).r
+  else if i < 1 lsl 14 then (uint16 (Uint16.of_int ((i lsl 2) lor 1))).r
+  else if i < 1 lsl 30 then (uint32 (Uint32.of_int ((i lsl 2) lor 2))).r
+  else Bytes.cat (Bytes.make 1 '\003') (uint32 (Uint32.of_int i)).r
+
+let length i =
+  if i < 0 || i > max_length then failwith "integer above max length"
+  else compact_uint32 i
+
+let variant_index i =
+  if i < 0 || i > 0xff then failwith "variant index above max u8"
+  else uint8 (Uint8.of_int i)
+
+let float32 f = Common.Serialize.int32 (Stdlib.Int32.bits_of_float f)
+let float64 f = Common.Serialize.int64 (Stdlib.Int64.bits_of_float f)
+
+let variable f l = variable length f l
+let string s = string length s
+let bytes b = bytes length b
+let map ser_k ser_v m = map length ser_k ser_v m
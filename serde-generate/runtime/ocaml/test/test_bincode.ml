@@ -130,6 +130,27 @@ let test_variant_ser () =
 let test_variant_de () =
   (check bool) "same" true (B {x = Uint8.of_int 2; y = Uint16.one} = (bar_de @@ mk @@ vec [|1; 0; 0; 0; 2; 1; 0|]).r)
 
+let with_options ?(varint_encoding=false) ?(big_endian=false) ?limit f () =
+  let open Bincode_options in
+  let reset () = varint_encoding := false; big_endian := false; limit := None in
+  Bincode_options.varint_encoding := varint_encoding;
+  Bincode_options.big_endian := big_endian;
+  Bincode_options.limit := limit;
+  Fun.protect ~finally:reset f
+
+let test_varint_ser () =
+  (check bytes) "same bytes" (vec [|251; 1; 44|]) (Ser.uint16 (Uint16.of_int 300)).r;
+  (check bytes) "same bytes" (vec [|3|]) (Ser.int32 (Int32.of_int (-2))).r
+let test_varint_de () =
+  (check int) "same int" 300 (Uint16.to_int (De.uint16 @@ mk @@ vec [|251; 1; 44|]).r);
+  (check int) "same int" (-2) (Int32.to_int (De.int32 @@ mk @@ vec [|3|]).r)
+let test_varint_fail_overflow () = check_fail (fun () -> De.uint16 @@ mk @@ vec [|252; 0; 1; 0; 0|])
+
+let test_limit () =
+  (check int) "same int" 3 (Uint16.to_int (De.uint16 @@ mk @@ vec [|3; 0|]).r);
+  check_fail (fun () -> De.uint32 @@ mk @@ vec [|0; 0; 0; 0|]);
+  check_fail (fun () -> De.bytes @@ mk @@ vec [|1; 0; 0; 0; 0; 0; 0; 0; 0|])
+
 let () =
   run "bincode" [
     "bool", [
@@ -227,5 +248,14 @@ let () =
       test_case "serialize" `Quick test_variant_ser;
       test_case "deserialize" `Quick test_variant_de;
     ];
+    "options", [
+      test_case "serialize varint big endian" `Quick
+        (with_options ~varint_encoding:true ~big_endian:true test_varint_ser);
+      test_case "deserialize varint big endian" `Quick
+        (with_options ~varint_encoding:true ~big_endian:true test_varint_de);
+      test_case "deserialize varint fail overflow" `Quick
+        (with_options ~varint_encoding:true test_varint_fail_overflow);
+      test_case "deserialize with limit" `Quick (with_options ~limit:2 test_limit);
+    ];
 
   ]
@@ -0,0 +1,109 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+open Alcotest
+open Stdint
+open Serde
+module Ser = Serialize
+module De = Deserialize
+
+let vec a =
+  Bytes.init (Array.length a) (fun i -> Char.chr a.(i))
+
+let mk buffer = { De.buffer; offset = 0 }
+
+let check_fail f =
+  (check bool) "fail" true (try let _ = f () in false with _ -> true)
+
+let test_bool_ser () = (check bytes) "same bytes" (vec [|1|]) (Ser.bool true).r
+let test_bool_fail_2 () = check_fail (fun () -> De.bool @@ mk @@ vec [|2|])
+
+let test_u16_ser () = (check bytes) "same bytes" (vec [|0; 1|]) (Ser.uint16 (Uint16.of_int 256)).r
+let test_u32_de () = (check int) "same int" 4294967295 (Uint32.to_int (De.uint32 @@ mk @@ vec [|0xff; 0xff; 0xff; 0xff|]).r)
+
+let test_f64_ser () = (check bytes) "same bytes" (vec [|0x1a; 0xdf; 0xc4; 0x41; 0x66; 0x63; 0x8a; 0x3d|]) (Ser.float64 0.000000000003).r
+let test_f64_ser_nan () = check_fail (fun () -> Ser.float64 Float.nan)
+let test_f32_de_nan () = check_fail (fun () -> De.float32 @@ mk @@ vec [|0; 0; 0xc0; 0x7f|])
+
+let test_seq_ser () = (check bytes) "same bytes"
+    (vec [|2; 0; 0; 0; 0; 1; 1; 0|])
+    (Ser.variable Ser.uint16 [Uint16.of_int 256; Uint16.of_int 1]).r
+let test_seq_de_fail_length () =
+  check_fail (fun () -> De.variable De.uint16 @@ mk @@ vec [|0xff; 0xff; 0xff; 0xff|])
+
+let test_string_ser () =
+  (check bytes) "same bytes" (vec [|5; 0; 0; 0; 65; 66; 67; 0xce; 0x94|]) (Ser.string "ABC\u{0394}").r
+
+let test_map () =
+  let m = Map.empty in
+  let m = Map.add (Uint16.of_int 256) (Uint8.of_int 3) m in
+  let m = Map.add (Uint16.of_int 1) (Uint8.of_int 5) m in
+  let b = Ser.(map uint16 uint8) m in
+  (check bytes) "same bytes" (vec [|2; 0; 0; 0; 1; 0; 5; 0; 1; 3|]) b.r;
+  (check bool) "same" true ((Map.bindings (De.map Ser.uint16 De.uint16 De.uint8 @@ mk b.r).r) = Map.bindings m);
+  check_fail (fun () -> De.map Ser.uint16 De.uint16 De.uint8 @@ mk @@ vec [|2; 0; 0; 0; 0; 1; 3; 1; 0; 5|])
+
+let test_map_string_keys () =
+  let m = Map.empty in
+  let m = Map.add "b" (Int16.of_int (-1)) m in
+  let m = Map.add "ab" (Int16.of_int 256) m in
+  (check bytes) "same bytes"
+    (vec [|2; 0; 0; 0; 2; 0; 0; 0; 97; 98; 0; 1; 1; 0; 0; 0; 98; 0xff; 0xff|])
+    (Ser.(map string int16) m).r
+
+type foo = {
+  x: uint8;
+  y: uint16;
+} [@@deriving serde]
+
+let test_struct_ser () =
+  (check bytes) "same bytes" (vec [|0; 1; 0|]) (foo_ser {x = Uint8.zero; y = Uint16.one}).r
+
+type bar =
+  | A
+  | B of foo
+  | C
+[@@deriving serde]
+
+let test_variant_ser () =
+  (check bytes) "same bytes" (vec [|1; 0; 1; 0|]) (bar_ser (B {x = Uint8.zero; y = Uint16.one})).r
+
+let test_variant_de () =
+  (check bool) "same" true (B {x = Uint8.of_int 2; y = Uint16.one} = (bar_de @@ mk @@ vec [|1; 2; 1; 0|]).r)
+
+let () =
+  run "borsh" [
+    "bool", [
+      test_case "serialize true" `Quick test_bool_ser;
+      test_case "deserialize fail 2" `Quick test_bool_fail_2;
+    ];
+    "uint16", [
+      test_case "serialize 256u16" `Quick test_u16_ser;
+    ];
+    "uint32", [
+      test_case "deserialize ffffffff" `Quick test_u32_de;
+    ];
+    "float", [
+      test_case "serialize" `Quick test_f64_ser;
+      test_case "serialize fail nan" `Quick test_f64_ser_nan;
+      test_case "deserialize fail nan" `Quick test_f32_de_nan;
+    ];
+    "sequence", [
+      test_case "serialize" `Quick test_seq_ser;
+      test_case "deserialize fail length" `Quick test_seq_de_fail_length;
+    ];
+    "string", [
+      test_case "serialize" `Quick test_string_ser;
+    ];
+    "map", [
+      test_case "serialize and deserialize" `Quick test_map;
+      test_case "serialize string keys" `Quick test_map_string_keys;
+    ];
+    "struct", [
+      test_case "serialize" `Quick test_struct_ser;
+    ];
+    "variant", [
+      test_case "serialize" `Quick test_variant_ser;
+      test_case "deserialize" `Quick test_variant_de;
+    ];
+  ]
@@ -0,0 +1,126 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+open Alcotest
+open Stdint
+open Serde
+module Ser = Serialize
+module De = Deserialize
+
+let vec a =
+  Bytes.init (Array.length a) (fun i -> Char.chr a.(i))
+
+let mk buffer = { De.buffer; offset = 0 }
+
+let check_fail f =
+  (check bool) "fail" true (try let _ = f () in false with _ -> true)
+
+let test_bool_ser () = (check bytes) "same bytes" (vec [|1|]) (Ser.bool true).r
+let test_bool_fail_2 () = check_fail (fun () -> De.bool @@ mk @@ vec [|2|])
+
+let test_u16_ser () = (check bytes) "same bytes" (vec [|0x80; 2|]) (Ser.uint16 (Uint16.of_int 256)).r
+let test_u16_de () = (check int) "same int" 65535 (Uint16.to_int (De.uint16 @@ mk @@ vec [|0xff; 0xff; 3|]).r)
+let test_u16_fail_overflow () = check_fail (fun () -> De.uint16 @@ mk @@ vec [|0xff; 0xff; 4|])
+let test_u16_fail_too_long () = check_fail (fun () -> De.uint16 @@ mk @@ vec [|0x80; 0x80; 0x80; 0|])
+
+let test_u32_ser () = (check bytes) "same bytes" (vec [|0x84; 0x86; 0x88; 8|]) (Ser.uint32 (Uint32.of_int 16909060)).r
+let test_u32_de () = (check int) "same int" 4294967295 (Uint32.to_int (De.uint32 @@ mk @@ vec [|0xff; 0xff; 0xff; 0xff; 0x0f|]).r)
+
+let test_u64_de () = (check bool) "same int" true Uint128.(((shift_left (of_int 1) 64) - (of_int 1)) = Uint128.of_uint64 (De.uint64 @@ mk @@ vec @@ Array.append (Array.make 9 0xff) [|1|]).r)
+
+let test_u128_ser () = (check bytes) "same bytes" (vec @@ Array.append (Array.make 18 0xff) [|3|]) (Ser.uint128 (Uint128.of_string "0xffffffffffffffffffffffffffffffff")).r
+
+let test_i16_ser_pos () = (check bytes) "same bytes" (vec [|0x80; 1|]) (Ser.int16 (Int16.of_int 64)).r
+let test_i16_ser_neg () = (check bytes) "same bytes" (vec [|0x7f|]) (Ser.int16 (Int16.of_int (-64))).r
+let test_i16_de () = (check int) "same int" (-32768) (Int16.to_int @@ (De.int16 @@ mk @@ vec [|0xff; 0xff; 3|]).r)
+
+let test_i32_de () = (check int) "same int" (-2) (Int32.to_int @@ (De.int32 @@ mk @@ vec [|3|]).r)
+
+let test_f64_ser () = (check bytes) "same bytes" (vec [|0x1a; 0xdf; 0xc4; 0x41; 0x66; 0x63; 0x8a; 0x3d|]) (Ser.float64 0.000000000003).r
+
+let test_option_ser_some () = (check bytes) "same bytes" (vec [|1; 6|]) (Ser.option Ser.uint16 (Some (Uint16.of_int 6))).r
+let test_option_de_fail () = check_fail (fun () -> De.option De.uint16 (mk @@ vec [|2; 6|]))
+
+let test_seq_ser_big () = (check bytes) "same bytes"
+    (vec (Array.concat ([|0x80; 2|] :: List.init 256 (fun _ -> [|0x80; 2|]))))
+    (Ser.variable Ser.uint16 @@ List.init 256 (fun _ -> Uint16.of_int 256)).r
+let test_seq_de () = (check bool) "same" true ([Uint16.of_int 3] = (De.variable De.uint16 @@ mk @@ vec [|1; 3|]).r)
+
+let test_string_ser () =
+  (check bytes) "same bytes" (vec [|5; 65; 66; 67; 0xce; 0x94|]) (Ser.string "ABC\u{0394}").r
+let test_string_fail_length () =
+  check_fail (fun () -> De.string @@ mk @@ vec [|3; 65; 66|])
+
+type foo = {
+  x: uint8;
+  y: uint16;
+} [@@deriving serde]
+
+let test_struct_ser () =
+  (check bytes) "same bytes" (vec [|0; 1|]) (foo_ser {x = Uint8.zero; y = Uint16.one}).r
+
+type bar =
+  | A
+  | B of foo
+  | C
+[@@deriving serde]
+
+let test_variant_ser () =
+  (check bytes) "same bytes" (vec [|1; 0; 1|]) (bar_ser (B {x = Uint8.zero; y = Uint16.one})).r
+
+let test_variant_de () =
+  (check bool) "same" true (B {x = Uint8.of_int 2; y = Uint16.one} = (bar_de @@ mk @@ vec [|1; 2; 1|]).r)
+
+let () =
+  run "postcard" [
+    "bool", [
+      test_case "serialize true" `Quick test_bool_ser;
+      test_case "deserialize fail 2" `Quick test_bool_fail_2;
+    ];
+    "uint16", [
+      test_case "serialize 256u16" `Quick test_u16_ser;
+      test_case "deserialize ffff03" `Quick test_u16_de;
+      test_case "deserialize fail overflow" `Quick test_u16_fail_overflow;
+      test_case "deserialize fail too long" `Quick test_u16_fail_too_long;
+    ];
+    "uint32", [
+      test_case "serialize 16909060u32" `Quick test_u32_ser;
+      test_case "deserialize ffffffff0f" `Quick test_u32_de;
+    ];
+    "uint64", [
+      test_case "deserialize max" `Quick test_u64_de;
+    ];
+    "uint128", [
+      test_case "serialize max" `Quick test_u128_ser;
+    ];
+    "int16", [
+      test_case "serialize 64i16" `Quick test_i16_ser_pos;
+      test_case "serialize -64i16" `Quick test_i16_ser_neg;
+      test_case "deserialize ffff03" `Quick test_i16_de;
+    ];
+    "int32", [
+      test_case "deserialize 03" `Quick test_i32_de;
+    ];
+    "float64", [
+      test_case "serialize" `Quick test_f64_ser;
+    ];
+    "option", [
+      test_case "serialize some" `Quick test_option_ser_some;
+      test_case "deserialize fail" `Quick test_option_de_fail;
+    ];
+    "sequence", [
+      test_case "serialize big" `Quick test_seq_ser_big;
+      test_case "deserialize" `Quick test_seq_de;
+    ];
+    "string", [
+      test_case "serialize" `Quick test_string_ser;
+      test_case "deserialize fail length" `Quick test_string_fail_length;
+    ];
+    "struct", [
+      test_case "serialize" `Quick test_struct_ser;
+    ];
+    "variant", [
+      test_case "serialize" `Quick test_variant_ser;
+      test_case "deserialize" `Quick test_variant_de;
+    ];
+  ]
@@ -0,0 +1,96 @@
+(* Copyright (c) Zefchain Labs, Inc.
+ * SPDX-License-Identifier: MIT OR Apache-2.0 *)
+
+open Alcotest
+open Stdint
+open Serde
+module Ser = Serialize
+module De = Deserialize
+
+let vec a =
+  Bytes.init (Array.length a) (fun i -> Char.chr a.(i))
+
+let mk buffer = { De.buffer; offset = 0 }
+
+let check_fail f =
+  (check bool) "fail" true (try let _ = f () in false with _ -> true)
+
+let test_bool_ser () = (check bytes) "same bytes" (vec [|1|]) (Ser.bool true).r
+let test_bool_fail_2 () = check_fail (fun () -> De.bool @@ mk @@ vec [|2|])
+
+let test_u16_ser () = (check bytes) "same bytes" (vec [|0; 1|]) (Ser.uint16 (Uint16.of_int 256)).r
+
+let test_f32_de_nan () =
+  (check bool) "nan" true (Float.is_nan (De.float32 @@ mk @@ vec [|0; 0; 0xc0; 0x7f|]).r)
+
+let test_length_ser () =
+  (check bytes) "same bytes" (vec [|0xfc|]) (Ser.length 63);
+  (check bytes) "same bytes" (vec [|0x01; 0x01|]) (Ser.length 64);
+  (check bytes) "same bytes" (vec [|0x02; 0x00; 0x01; 0x00|]) (Ser.length 16384);
+  (check bytes) "same bytes" (vec [|0x03; 0x00; 0x00; 0x00; 0x40|]) (Ser.length (1 lsl 30))
+
+let test_length_de () =
+  (check int) "same int" 64 (De.length @@ mk @@ vec [|0x01; 0x01|]);
+  (check int) "same int" (1 lsl 30) (De.length @@ mk @@ vec [|0x03; 0x00; 0x00; 0x00; 0x40|])
+
+let test_length_de_fail_non_canonical () =
+  check_fail (fun () -> De.length @@ mk @@ vec [|0x01; 0x00|])
+
+let test_seq_ser () = (check bytes) "same bytes"
+    (vec [|8; 0; 1; 1; 0|])
+    (Ser.variable Ser.uint16 [Uint16.of_int 256; Uint16.of_int 1]).r
+
+let test_string_ser () =
+  (check bytes) "same bytes" (vec [|20; 65; 66; 67; 0xce; 0x94|]) (Ser.string "ABC\u{0394}").r
+
+type foo = {
+  x: uint8;
+  y: uint16;
+} [@@deriving serde]
+
+let test_struct_ser () =
+  (check bytes) "same bytes" (vec [|0; 1; 0|]) (foo_ser {x = Uint8.zero; y = Uint16.one}).r
+
+type bar =
+  | A
+  | B of foo
+  | C
+[@@deriving serde]
+
+let test_variant_ser () =
+  (check bytes) "same bytes" (vec [|1; 0; 1; 0|]) (bar_ser (B {x = Uint8.zero; y = Uint16.one})).r
+
+let test_variant_de () =
+  (check bool) "same" true (B {x = Uint8.of_int 2; y = Uint16.one} = (bar_de @@ mk @@ vec [|1; 2; 1; 0|]).r)
+
+let () =
+  run "scale" [
+    "bool", [
+      test_case "serialize true" `Quick test_bool_ser;
+      test_case "deserialize fail 2" `Quick test_bool_fail_2;
+    ];
+    "uint16", [
+      test_case "serialize 256u16" `Quick test_u16_ser;
+    ];
+    "float", [
+      test_case "deserialize nan" `Quick test_f32_de_nan;
+    ];
+    "length", [
+      test_case "serialize" `Quick test_length_ser;
+      test_case "deserialize" `Quick test_length_de;
+      test_case "deserialize fail non canonical" `Quick test_length_de_fail_non_canonical;
+    ];
+    "sequence", [
+      test_case "serialize" `Quick test_seq_ser;
+    ];
+    "string", [
+      test_case "serialize" `Quick test_string_ser;
+    ];
+    "struct", [
+      test_case "serialize" `Quick test_struct_ser;
+    ];
+    "variant", [
+      test_case "serialize" `Quick test_variant_ser;
+      test_case "deserialize" `Quick test_variant_de;
+    ];
+  ]
@@ -16,48 +16,202 @@ import serde_binary as sb
 MAX_LENGTH = (1 << 31) - 1
 
 
+# Tags of varint-encoded integers that do not fit in a single byte.
+SINGLE_BYTE_MAX = 250
+U16_TAG = 251
+U32_TAG = 252
+U64_TAG = 253
+U128_TAG = 254
+
+
 class BincodeSerializer(sb.BinarySerializer):
-    def __init__(self):
+    """Bincode serializer, by default using the fixed-size little-endian encoding.
+
+    `varint_encoding` and `big_endian` correspond to the options `with_varint_encoding`
+    and `with_big_endian` of the Rust crate.
+    """
+
+    def __init__(self, varint_encoding: bool = False, big_endian: bool = False):
         super().__init__(output=io.BytesIO(), container_depth_budget=None)
+        self.varint_encoding = varint_encoding
+        self.byteorder = "big" if big_endian else "little"
+        self.float_prefix = ">" if big_endian else "<"
+
+    def serialize_fixint(self, value: int, size: int, signed: bool):
+        self.output.write(int(value).to_bytes(size, self.byteorder, signed=signed))
+
+    def serialize_varint(self, value: int):
+        if value <= SINGLE_BYTE_MAX:
+            self.output.write(value.to_bytes(1, "little", signed=False))
+        elif value < (1 << 16):
+            self.serialize_u8(st.uint8(U16_TAG))
+            self.serialize_fixint(value, 2, False)
+        elif value < (1 << 32):
+            self.serialize_u8(st.uint8(U32_TAG))
+            self.serialize_fixint(value, 4, False)
+        elif value < (1 << 64):
+            self.serialize_u8(st.uint8(U64_TAG))
+            self.serialize_fixint(value, 8, False)
+        else:
+            self.serialize_u8(st.uint8(U128_TAG))
+            self.serialize_fixint(value, 16, False)
+
+    def serialize_unsigned(self, value: int, size: int):
+        if self.varint_encoding:
+            self.serialize_varint(int(value))
+        else:
+            self.serialize_fixint(value, size, False)
+
+    def serialize_signed(self, value: int, size: int):
+        if self.varint_encoding:
+            value = int(value)
+            self.serialize_varint((value << 1) ^ (value >> (8 * size - 1)))
+        else:
+            self.serialize_fixint(value, size, True)
+
+    def serialize_u16(self, value: st.uint16):
+        self.serialize_unsigned(value, 2)
+
+    def serialize_u32(self, value: st.uint32):
+        self.serialize_unsigned(value, 4)
+
+    def serialize_u64(self, value: st.uint64):
+        self.serialize_unsigned(value, 8)
+
+    def serialize_u128(self, value: st.uint128):
+        self.serialize_unsigned(value, 16)
+
+    def serialize_i16(self, value: st.int16):
+        self.serialize_signed(value, 2)
+
+    def serialize_i32(self, value: st.int32):
+        self.serialize_signed(value, 4)
+
+    def serialize_i64(self, value: st.int64):
+        self.serialize_signed(value, 8)
+
+    def serialize_i128(self, value: st.int128):
+        self.serialize_signed(value, 16)
 
     def serialize_f32(self, value: st.float32):
-        self.output.write(struct.pack("<f", value))
+        self.output.write(struct.pack(self.float_prefix + "f", value))
 
     def serialize_f64(self, value: st.float64):
-        self.output.write(struct.pack("<d", value))
+        self.output.write(struct.pack(self.float_prefix + "d", value))
 
     def serialize_len(self, value: int):
         if value > MAX_LENGTH:
             raise st.SerializationError("Length exceeds the maximum supported value.")
-        self.output.write(int(value).to_bytes(8, "little", signed=False))
+        self.serialize_unsigned(value, 8)
 
     def serialize_variant_index(self, value: int):
-        self.output.write(int(value).to_bytes(4, "little", signed=False))
+        self.serialize_unsigned(value, 4)
 
     def sort_map_entries(self, offsets: typing.List[int]):
         pass
 
 
 class BincodeDeserializer(sb.BinaryDeserializer):
-    def __init__(self, content):
+    """Bincode deserializer, by default using the fixed-size little-endian encoding.
+
+    See `BincodeSerializer` for the meaning of the options. If `limit` is set, reading more
+    than `limit` bytes fails, as with the option `with_limit` of the Rust crate.
+    """
+
+    def __init__(
+        self,
+        content,
+        varint_encoding: bool = False,
+        big_endian: bool = False,
+        limit: typing.Optional[int] = None,
+    ):
         super().__init__(input=io.BytesIO(content), container_depth_budget=None)
+        self.varint_encoding = varint_encoding
+        self.byteorder = "big" if big_endian else "little"
+        self.float_prefix = ">" if big_endian else "<"
+        self.limit = limit
+
+    def read(self, length: int) -> bytes:
+        if self.limit is not None and self.get_buffer_offset() + length > self.limit:
+            raise st.DeserializationError("Exceeded the size limit of the encoding")
+        return super().read(length)
+
+    def deserialize_fixint(self, size: int, signed: bool) -> int:
+        return int.from_bytes(self.read(size), byteorder=self.byteorder, signed=signed)
+
+    def deserialize_varint(self, size: int) -> int:
+        tag = int.from_bytes(self.read(1), byteorder="little", signed=False)
+        if tag <= SINGLE_BYTE_MAX:
+            value = tag
+        elif tag == U16_TAG:
+            value = self.deserialize_fixint(2, False)
+        elif tag == U32_TAG:
+            value = self.deserialize_fixint(4, False)
+        elif tag == U64_TAG:
+            value = self.deserialize_fixint(8, False)
+        elif tag == U128_TAG and size == 16:
+            value = self.deserialize_fixint(16, False)
+        else:
+            raise st.DeserializationError(
+                "Invalid tag for varint-encoded value:", tag
+            )
+        if value >> (8 * size) != 0:
+            raise st.DeserializationError(
+                "Overflow while parsing varint-encoded value"
+            )
+        return value
+
+    def deserialize_unsigned(self, size: int) -> int:
+        if self.varint_encoding:
+            return self.deserialize_varint(size)
+        return self.deserialize_fixint(size, False)
+
+    def deserialize_signed(self, size: int) -> int:
+        if self.varint_encoding:
+            value = self.deserialize_varint(size)
+            return (value >> 1) ^ -(value & 1)
+        return self.deserialize_fixint(size, True)
+
+    def deserialize_u16(self) -> st.uint16:
+        return st.uint16(self.deserialize_unsigned(2))
+
+    def deserialize_u32(self) -> st.uint32:
+        return st.uint32(self.deserialize_unsigned(4))
+
+    def deserialize_u64(self) -> st.uint64:
+        return st.uint64(self.deserialize_unsigned(8))
+
+    def deserialize_u128(self) -> st.uint128:
+        return st.uint128(self.deserialize_unsigned(16))
+
+    def deserialize_i16(self) -> st.int16:
+        return st.int16(self.deserialize_signed(2))
+
+    def deserialize_i32(self) -> st.int32:
+        return st.int32(self.deserialize_signed(4))
+
+    def deserialize_i64(self) -> st.int64:
+        return st.int64(self.deserialize_signed(8))
+
+    def deserialize_i128(self) -> st.int128:
+        return st.int128(self.deserialize_signed(16))
 
     def deserialize_f32(self) -> st.float32:
-        (value,) = struct.unpack("<f", self.read(4))
+        (value,) = struct.unpack(self.float_prefix + "f", self.read(4))
         return st.float32(value)
 
     def deserialize_f64(self) -> st.float64:
-        (value,) = struct.unpack("<d", self.read(8))
+        (value,) = struct.unpack(self.float_prefix + "d", self.read(8))
         return st.float64(value)
 
     def deserialize_len(self) -> int:
-        value = int.from_bytes(self.read(8), byteorder="little", signed=False)
+        value = self.deserialize_unsigned(8)
         if value > MAX_LENGTH:
             raise st.DeserializationError("Length exceeds the maximum supported value.")
         return value
 
     def deserialize_variant_index(self) -> int:
-        return int.from_bytes(self.read(4), byteorder="little", signed=False)
+        return self.deserialize_unsigned(4)
 
     def check_that_key_slices_are_increasing(
         self, slice1: typing.Tuple[int, int], slice2: typing.Tuple[int, int]
@@ -65,13 +219,21 @@ class BincodeDeserializer(sb.BinaryDeserializer):
         pass
 
 
-def serialize(obj: typing.Any, obj_type) -> bytes:
-    serializer = BincodeSerializer()
+def serialize(
+    obj: typing.Any, obj_type, varint_encoding: bool = False, big_endian: bool = False
+) -> bytes:
+    serializer = BincodeSerializer(varint_encoding, big_endian)
     serializer.serialize_any(obj, obj_type)
     return serializer.get_buffer()
 
 
-def deserialize(content: bytes, obj_type) -> typing.Tuple[typing.Any, bytes]:
-    deserializer = BincodeDeserializer(content)
+def deserialize(
+    content: bytes,
+    obj_type,
+    varint_encoding: bool = False,
+    big_endian: bool = False,
+    limit: typing.Optional[int] = None,
+) -> typing.Tuple[typing.Any, bytes]:
+    deserializer = BincodeDeserializer(content, varint_encoding, big_endian, limit)
     value = deserializer.deserialize_any(obj_type)
     return value, deserializer.get_remaining_buffer()
@@ -236,3 +236,65 @@ class BincodeTestCase(unittest.TestCase):
             bincode.deserialize(b"\x01\x00\x00\x00\x02\x01\x00", BincodeTestCase.Bar),
             (BincodeTestCase.Bar1(x=2, y=1), b""),
         )
+
+    def test_bincode_varint_encoding(self):
+        def serialize(value, value_type):
+            return bincode.serialize(value, value_type, varint_encoding=True)
+
+        def deserialize(content, value_type):
+            return bincode.deserialize(content, value_type, varint_encoding=True)
+
+        self.assertEqual(serialize(250, st.uint16), b"\xfa")
+        self.assertEqual(serialize(251, st.uint16), b"\xfb\xfb\x00")
+        self.assertEqual(serialize(1 << 16, st.uint32), b"\xfc\x00\x00\x01\x00")
+        self.assertEqual(
+            serialize(1 << 32, st.uint64), b"\xfd\x00\x00\x00\x00\x01\x00\x00\x00"
+        )
+        self.assertEqual(
+            serialize(st.uint128(1 << 64), st.uint128),
+            b"\xfe" + bytes(8) + b"\x01" + bytes(7),
+        )
+        self.assertEqual(serialize(-1, st.int32), b"\x01")
+        self.assertEqual(serialize(-200, st.int16), b"\xfb\x8f\x01")
+        self.assertEqual(serialize(1, st.uint8), b"\x01")
+        self.assertEqual(serialize([1, 2], typing.Sequence[st.uint8]), b"\x02\x01\x02")
+        self.assertEqual(
+            serialize(BincodeTestCase.Bar1(x=0, y=1), BincodeTestCase.Bar),
+            b"\x01\x00\x01",
+        )
+
+        self.assertEqual(deserialize(b"\xfb\x8f\x01", st.int16), (-200, b""))
+        self.assertEqual(deserialize(b"\xfb\x05\x00", st.uint16), (5, b""))
+        with self.assertRaises(st.DeserializationError):
+            deserialize(b"\xff", st.uint16)
+        with self.assertRaises(st.DeserializationError):
+            deserialize(b"\xfc\x00\x00\x01\x00", st.uint16)
+        with self.assertRaises(st.DeserializationError):
+            deserialize(b"\xfe" + bytes(16), st.uint64)
+
+    def test_bincode_big_endian(self):
+        self.assertEqual(
+            bincode.serialize(0x01020304, st.uint32, big_endian=True),
+            b"\x01\x02\x03\x04",
+        )
+        self.assertEqual(bincode.serialize(-2, st.int16, big_endian=True), b"\xff\xfe")
+        self.assertEqual(
+            bincode.serialize(300, st.uint16, varint_encoding=True, big_endian=True),
+            b"\xfb\x01\x2c",
+        )
+        self.assertEqual(
+            bincode.deserialize(bytes(7) + b"\x01", st.uint64, big_endian=True),
+            (1, b""),
+        )
+
+    def test_bincode_limit(self):
+        content = bincode.serialize(b"abc", bytes)
+        self.assertEqual(bincode.deserialize(content, bytes, limit=11), (b"abc", b""))
+        with self.assertRaises(st.DeserializationError):
+            bincode.deserialize(content, bytes, limit=10)
+        with self.assertRaises(st.DeserializationError):
+            bincode.deserialize(b"\xc8" + bytes(7 + 200), bytes, limit=100)
+        # Bytes after the limit do not matter until they are read.
+        self.assertEqual(
+            bincode.deserialize(b"\x01\x02", st.uint8, limit=1), (1, b"\x02")
+        )
@@ -0,0 +1,289 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+import bisect
+import dataclasses
+import collections
+import io
+import math
+import struct
+import typing
+from copy import copy
+from typing import get_type_hints
+
+import serde_types as st
+import serde_binary as sb
+
+# Maximum length in practice for sequences (e.g. in Java).
+MAX_LENGTH = (1 << 31) - 1
+MAX_U8 = (1 << 8) - 1
+
+
+def unsigned_sort_key(value: int, size: int) -> bytes:
+    return int(value).to_bytes(size, "big", signed=False)
+
+
+def signed_sort_key(value: int, size: int) -> bytes:
+    return (int(value) + (1 << (8 * size - 1))).to_bytes(size, "big", signed=False)
+
+
+def str_sort_key(value: str) -> bytes:
+    # Escape zeros and add a terminator so that prefixes come first.
+    return value.encode().replace(b"\x00", b"\x00\xff") + b"\x00\x00"
+
+
+class SortKeys:
+    """Order-preserving encodings of the integers and strings written or read so far, indexed
+    by their positions in the buffer.
+
+    Borsh sorts map entries by keys as in Rust. Substituting these encodings in the encoded keys
+    gives byte strings that compare in the same way."""
+
+    def __init__(self):
+        self.starts = []  # type: typing.List[int]
+        self.records = []  # type: typing.List[typing.Tuple[int, bytes]]
+
+    def record(self, start: int, end: int, key: bytes):
+        # Values read or written inside the current one are superseded.
+        while self.starts and self.starts[-1] >= start:
+            self.starts.pop()
+            self.records.pop()
+        self.starts.append(start)
+        self.records.append((end, key))
+
+    def truncate(self, start: int):
+        index = bisect.bisect_left(self.starts, start)
+        del self.starts[index:]
+        del self.records[index:]
+
+    def sort_key(self, buf, start: int, end: int) -> bytes:
+        result = bytearray()
+        position = start
+        index = bisect.bisect_left(self.starts, start)
+        while index < len(self.starts) and self.starts[index] < end:
+            result += buf[position : self.starts[index]]
+            position, key = self.records[index]
+            result += key
+            index += 1
+        result += buf[position:end]
+        return bytes(result)
+
+
+class BorshSerializer(sb.BinarySerializer):
+    def __init__(self):
+        super().__init__(output=io.BytesIO(), container_depth_budget=None)
+        self.sort_keys = SortKeys()
+
+    def record_sort_key(self, start: int, key: bytes):
+        self.sort_keys.record(start, self.get_buffer_offset(), key)
+
+    def serialize_u16(self, value: st.uint16):
+        start = self.get_buffer_offset()
+        super().serialize_u16(value)
+        self.record_sort_key(start, unsigned_sort_key(value, 2))
+
+    def serialize_u32(self, value: st.uint32):
+        start = self.get_buffer_offset()
+        super().serialize_u32(value)
+        self.record_sort_key(start, unsigned_sort_key(value, 4))
+
+    def serialize_u64(self, value: st.uint64):
+        start = self.get_buffer_offset()
+        super().serialize_u64(value)
+        self.record_sort_key(start, unsigned_sort_key(value, 8))
+
+    def serialize_u128(self, value: st.uint128):
+        start = self.get_buffer_offset()
+        super().serialize_u128(value)
+        self.record_sort_key(start, unsigned_sort_key(value, 16))
+
+    def serialize_i8(self, value: st.int8):
+        start = self.get_buffer_offset()
+        super().serialize_i8(value)
+        self.record_sort_key(start, signed_sort_key(value, 1))
+
+    def serialize_i16(self, value: st.int16):
+        start = self.get_buffer_offset()
+        super().serialize_i16(value)
+        self.record_sort_key(start, signed_sort_key(value, 2))
+
+    def serialize_i32(self, value: st.int32):
+        start = self.get_buffer_offset()
+        super().serialize_i32(value)
+        self.record_sort_key(start, signed_sort_key(value, 4))
+
+    def serialize_i64(self, value: st.int64):
+        start = self.get_buffer_offset()
+        super().serialize_i64(value)
+        self.record_sort_key(start, signed_sort_key(value, 8))
+
+    def serialize_i128(self, value: st.int128):
+        start = self.get_buffer_offset()
+        super().serialize_i128(value)
+        self.record_sort_key(start, signed_sort_key(value, 16))
+
+    def serialize_str(self, value: str):
+        start = self.get_buffer_offset()
+        super().serialize_str(value)
+        self.record_sort_key(start, str_sort_key(value))
+
+    def serialize_f32(self, value: st.float32):
+        if math.isnan(value):
+            raise st.SerializationError("NaN values are not supported by Borsh.")
+        self.output.write(struct.pack("<f", value))
+
+    def serialize_f64(self, value: st.float64):
+        if math.isnan(value):
+            raise st.SerializationError("NaN values are not supported by Borsh.")
+        self.output.write(struct.pack("<d", value))
+
+    def serialize_char(self, value: st.char):
+        raise st.SerializationError("Characters are not supported by Borsh.")
+
+    def serialize_len(self, value: int):
+        if value > MAX_LENGTH:
+            raise st.SerializationError("Length exceeds the maximum supported value.")
+        self.output.write(value.to_bytes(4, "little", signed=False))
+
+    def serialize_variant_index(self, value: int):
+        if value > MAX_U8:
+            raise st.SerializationError(
+                "Variant index exceeds the maximum supported value."
+            )
+        self.output.write(value.to_bytes(1, "little", signed=False))
+
+    def sort_map_entries(self, offsets: typing.List[int]):
+        if len(offsets) < 1:
+            return
+        buf = self.output.getbuffer()
+        offsets.append(len(buf))
+        slices = []
+        for i in range(1, len(offsets)):
+            start, end = offsets[i - 1], offsets[i]
+            key = self.sort_keys.sort_key(buf, start, end)
+            slices.append((key, bytes(buf[start:end])))
+        buf.release()
+        slices.sort()
+        # Positions in the map are no longer valid. Map keys never contain maps.
+        self.sort_keys.truncate(offsets[0])
+        self.output.seek(offsets[0])
+        for _, s in slices:
+            self.output.write(s)
+        assert offsets[-1] == len(self.output.getbuffer())
+
+
+class BorshDeserializer(sb.BinaryDeserializer):
+    def __init__(self, content):
+        super().__init__(input=io.BytesIO(content), container_depth_budget=None)
+        self.sort_keys = SortKeys()
+
+    def record_sort_key(self, start: int, key: bytes):
+        self.sort_keys.record(start, self.get_buffer_offset(), key)
+
+    def deserialize_u16(self) -> st.uint16:
+        start = self.get_buffer_offset()
+        value = super().deserialize_u16()
+        self.record_sort_key(start, unsigned_sort_key(value, 2))
+        return value
+
+    def deserialize_u32(self) -> st.uint32:
+        start = self.get_buffer_offset()
+        value = super().deserialize_u32()
+        self.record_sort_key(start, unsigned_sort_key(value, 4))
+        return value
+
+    def deserialize_u64(self) -> st.uint64:
+        start = self.get_buffer_offset()
+        value = super().deserialize_u64()
+        self.record_sort_key(start, unsigned_sort_key(value, 8))
+        return value
+
+    def deserialize_u128(self) -> st.uint128:
+        start = self.get_buffer_offset()
+        value = super().deserialize_u128()
+        self.record_sort_key(start, unsigned_sort_key(value, 16))
+        return value
+
+    def deserialize_i8(self) -> st.int8:
+        start = self.get_buffer_offset()
+        value = super().deserialize_i8()
+        self.record_sort_key(start, signed_sort_key(value, 1))
+        return value
+
+    def deserialize_i16(self) -> st.int16:
+        start = self.get_buffer_offset()
+        value = super().deserialize_i16()
+        self.record_sort_key(start, signed_sort_key(value, 2))
+        return value
+
+    def deserialize_i32(self) -> st.int32:
+        start = self.get_buffer_offset()
+        value = super().deserialize_i32()
+        self.record_sort_key(start, signed_sort_key(value, 4))
+        return value
+
+    def deserialize_i64(self) -> st.int64:
+        start = self.get_buffer_offset()
+        value = super().deserialize_i64()
+        self.record_sort_key(start, signed_sort_key(value, 8))
+        return value
+
+    def deserialize_i128(self) -> st.int128:
+        start = self.get_buffer_offset()
+        value = super().deserialize_i128()
+        self.record_sort_key(start, signed_sort_key(value, 16))
+        return value
+
+    def deserialize_str(self) -> str:
+        start = self.get_buffer_offset()
+        value = super().deserialize_str()
+        self.record_sort_key(start, str_sort_key(value))
+        return value
+
+    def deserialize_f32(self) -> st.float32:
+        (value,) = struct.unpack("<f", self.read(4))
+        if math.isnan(value):
+            raise st.DeserializationError("NaN values are not supported by Borsh.")
+        return st.float32(value)
+
+    def deserialize_f64(self) -> st.float64:
+        (value,) = struct.unpack("<d", self.read(8))
+        if math.isnan(value):
+            raise st.DeserializationError("NaN values are not supported by Borsh.")
+        return st.float64(value)
+
+    def deserialize_char(self) -> st.char:
+        raise st.DeserializationError("Characters are not supported by Borsh.")
+
+    def deserialize_len(self) -> int:
+        value = int.from_bytes(self.read(4), "little", signed=False)
+        if value > MAX_LENGTH:
+            raise st.DeserializationError("Length exceeds the maximum supported value.")
+        return value
+
+    def deserialize_variant_index(self) -> int:
+        return int.from_bytes(self.read(1), "little", signed=False)
+
+    def check_that_key_slices_are_increasing(
+        self, slice1: typing.Tuple[int, int], slice2: typing.Tuple[int, int]
+    ):
+        buf = self.input.getbuffer()
+        key1 = self.sort_keys.sort_key(buf, slice1[0], slice1[1])
+        key2 = self.sort_keys.sort_key(buf, slice2[0], slice2[1])
+        buf.release()
+        if key1 >= key2:
+            raise st.DeserializationError(
+                "Serialized keys in a map must be ordered by increasing keys"
+            )
+
+
+def serialize(obj: typing.Any, obj_type) -> bytes:
+    serializer = BorshSerializer()
+    serializer.serialize_any(obj, obj_type)
+    return serializer.get_buffer()
+
+
+def deserialize(content: bytes, obj_type) -> typing.Tuple[typing.Any, bytes]:
+    deserializer = BorshDeserializer(content)
+    value = deserializer.deserialize_any(obj_type)
+    return value, deserializer.get_remaining_buffer()
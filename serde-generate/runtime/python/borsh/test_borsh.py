@@ -0,0 +1,178 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+from collections import OrderedDict
+from dataclasses import dataclass
+import unittest
+import serde_types as st
+import borsh
+import typing
+
+
+class BorshTestCase(unittest.TestCase):
+    def test_borsh_bool(self):
+        self.assertEqual(borsh.serialize(False, bool), b"\x00")
+        self.assertEqual(borsh.serialize(True, bool), b"\x01")
+        self.assertEqual(borsh.deserialize(b"\x00", bool), (False, b""))
+        self.assertEqual(borsh.deserialize(b"\x01", bool), (True, b""))
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(b"\x02", bool)
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(b"", bool)
+
+    def test_borsh_u16(self):
+        self.assertEqual(borsh.serialize(0x0102, st.uint16), b"\x02\x01")
+        self.assertEqual(borsh.deserialize(b"\xff\xff", st.uint16), (65535, b""))
+
+    def test_borsh_u128(self):
+        self.assertEqual(
+            borsh.serialize(st.uint128(1), st.uint128), b"\x01" + b"\x00" * 15
+        )
+        self.assertEqual(
+            borsh.deserialize(b"\xff" * 16, st.uint128),
+            (st.uint128((1 << 128) - 1), b""),
+        )
+
+    def test_borsh_i32(self):
+        self.assertEqual(borsh.serialize(-2, st.int32), b"\xfe\xff\xff\xff")
+        self.assertEqual(
+            borsh.deserialize(b"\xff\xff\xff\x7f", st.int32), (2147483647, b"")
+        )
+
+    def test_borsh_f32(self):
+        self.assertEqual(borsh.serialize(0.3, st.float32), b"\x9a\x99\x99\x3e")
+        value, reminder = borsh.deserialize(b"\x9a\x99\x99\x3e", st.float32)
+        self.assertEqual(reminder, b"")
+        self.assertAlmostEqual(value, 0.3)
+        with self.assertRaises(st.SerializationError):
+            borsh.serialize(float("nan"), st.float32)
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(b"\x00\x00\xc0\x7f", st.float32)
+
+    def test_borsh_f64(self):
+        self.assertEqual(
+            borsh.serialize(0.000000000003, st.float64),
+            b"\x1a\xdf\xc4\x41\x66\x63\x8a\x3d",
+        )
+        with self.assertRaises(st.SerializationError):
+            borsh.serialize(float("nan"), st.float64)
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(b"\x00\x00\x00\x00\x00\x00\xf8\x7f", st.float64)
+
+    def test_borsh_char(self):
+        with self.assertRaises(st.SerializationError):
+            borsh.serialize(st.char("A"), st.char)
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(b"\x01\x00\x00\x00A", st.char)
+
+    def test_serialize_bytes(self):
+        self.assertEqual(borsh.serialize(b"", bytes), b"\x00\x00\x00\x00")
+        self.assertEqual(
+            borsh.serialize(b"\x00\x00", bytes), b"\x02\x00\x00\x00\x00\x00"
+        )
+        self.assertEqual(borsh.deserialize(b"\x00\x00\x00\x00", bytes), (b"", b""))
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(b"\xff\xff\xff\xff", bytes)
+
+    def test_serialize_tuple(self):
+        T = typing.Tuple[st.uint8, st.uint16]
+        self.assertEqual(borsh.serialize((0, 1), T), b"\x00\x01\x00")
+        self.assertEqual(borsh.deserialize(b"\x02\x80\x01", T), ((2, 384), b""))
+
+    def test_serialize_option(self):
+        T = typing.Optional[st.uint16]
+        self.assertEqual(borsh.serialize(None, T), b"\x00")
+        self.assertEqual(borsh.serialize(6, T), b"\x01\x06\x00")
+        self.assertEqual(borsh.deserialize(b"\x00", T), (None, b""))
+        self.assertEqual(borsh.deserialize(b"\x01\x02\x00", T), (2, b""))
+        with self.assertRaisesRegex(st.DeserializationError, "Wrong tag.*"):
+            borsh.deserialize(b"\x02\x06\x00", T)
+
+    def test_serialize_sequence(self):
+        Seq = typing.Sequence[st.uint16]
+        self.assertEqual(borsh.serialize([], Seq), b"\x00\x00\x00\x00")
+        self.assertEqual(
+            borsh.serialize([0, 1], Seq), b"\x02\x00\x00\x00\x00\x00\x01\x00"
+        )
+        self.assertEqual(
+            borsh.deserialize(b"\x01\x00\x00\x00\x03\x00", Seq), ([3], b"")
+        )
+
+    def test_serialize_str(self):
+        self.assertEqual(borsh.serialize("ABCΔ", str), b"\x05\x00\x00\x00ABC\xce\x94")
+        self.assertEqual(
+            borsh.deserialize(b"\x05\x00\x00\x00ABC\xce\x94A", str), ("ABCΔ", b"A")
+        )
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(b"\x03\x00\x00\x00AB", str)
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(b"\x03\x00\x00\x00\x80ab", str)
+
+    def test_serialize_map(self):
+        Map = typing.Dict[st.uint8, st.uint16]
+        m = OrderedDict([(3, 256), (1, 5)])
+        e = borsh.serialize(m, Map)
+        self.assertEqual(e, b"\x02\x00\x00\x00\x01\x05\x00\x03\x00\x01")
+        self.assertEqual(
+            (OrderedDict([(1, 5), (3, 256)]), b""),
+            borsh.deserialize(b"\x02\x00\x00\x00\x01\x05\x00\x03\x00\x01", Map),
+        )
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(b"\x02\x00\x00\x00\x03\x00\x01\x01\x05\x00", Map)
+
+    def test_serialize_map_in_rust_key_order(self):
+        Map = typing.Dict[str, st.int16]
+        m = OrderedDict([("b", -1), ("ab", 256)])
+        e = borsh.serialize(m, Map)
+        self.assertEqual(
+            e, b"\x02\x00\x00\x00\x02\x00\x00\x00ab\x00\x01\x01\x00\x00\x00b\xff\xff"
+        )
+        self.assertEqual(
+            (OrderedDict([("ab", 256), ("b", -1)]), b""), borsh.deserialize(e, Map)
+        )
+        with self.assertRaises(st.DeserializationError):
+            borsh.deserialize(
+                b"\x02\x00\x00\x00\x01\x00\x00\x00b\xff\xff\x02\x00\x00\x00ab\x00\x01",
+                Map,
+            )
+
+        Set = typing.Dict[typing.Tuple[st.int8, st.uint64], st.unit]
+        s = OrderedDict([((0, 1), None), ((0, 256), None), ((-1, 2), None)])
+        e = borsh.serialize(s, Set)
+        self.assertEqual(list(borsh.deserialize(e, Set)[0]), [(-1, 2), (0, 1), (0, 256)])
+
+    @dataclass
+    class Foo:
+        x: st.uint8
+        y: st.uint16
+
+    def test_struct(self):
+        self.assertEqual(
+            borsh.serialize(BorshTestCase.Foo(x=0, y=1), BorshTestCase.Foo),
+            b"\x00\x01\x00",
+        )
+        self.assertEqual(
+            borsh.deserialize(b"\x02\x01\x00", BorshTestCase.Foo),
+            (BorshTestCase.Foo(x=2, y=1), b""),
+        )
+
+    class Bar:
+        VARIANTS = []  # type: typing.Sequence[typing.Type['Bar']]
+
+    @dataclass
+    class Bar1(Bar):
+        INDEX = 1
+        x: st.uint8
+        y: st.uint16
+
+    Bar.VARIANTS = [None, Bar1, None]
+
+    def test_enum(self):
+        self.assertEqual(
+            borsh.serialize(BorshTestCase.Bar1(x=0, y=1), BorshTestCase.Bar),
+            b"\x01\x00\x01\x00",
+        )
+        self.assertEqual(
+            borsh.deserialize(b"\x01\x02\x01\x00", BorshTestCase.Bar),
+            (BorshTestCase.Bar1(x=2, y=1), b""),
+        )
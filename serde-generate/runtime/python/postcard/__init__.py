@@ -0,0 +1,166 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+import dataclasses
+import collections
+import io
+import struct
+import typing
+from copy import copy
+from typing import get_type_hints
+
+import serde_types as st
+import serde_binary as sb
+
+# Maximum length in practice for sequences (e.g. in Java).
+MAX_LENGTH = (1 << 31) - 1
+MAX_U32 = (1 << 32) - 1
+
+
+class PostcardSerializer(sb.BinarySerializer):
+    def __init__(self):
+        super().__init__(output=io.BytesIO(), container_depth_budget=None)
+
+    def serialize_varint(self, value: int):
+        while value >= 0x80:
+            b = (value & 0x7F) | 0x80
+            self.output.write(b.to_bytes(1, "little", signed=False))
+            value >>= 7
+        self.output.write(value.to_bytes(1, "little", signed=False))
+
+    def serialize_zigzag(self, value: int, bits: int):
+        self.serialize_varint((value << 1) ^ (value >> (bits - 1)))
+
+    def serialize_u16(self, value: st.uint16):
+        self.serialize_varint(int(value))
+
+    def serialize_u32(self, value: st.uint32):
+        self.serialize_varint(int(value))
+
+    def serialize_u64(self, value: st.uint64):
+        self.serialize_varint(int(value))
+
+    def serialize_u128(self, value: st.uint128):
+        self.serialize_varint(int(value))
+
+    def serialize_i16(self, value: st.int16):
+        self.serialize_zigzag(int(value), 16)
+
+    def serialize_i32(self, value: st.int32):
+        self.serialize_zigzag(int(value), 32)
+
+    def serialize_i64(self, value: st.int64):
+        self.serialize_zigzag(int(value), 64)
+
+    def serialize_i128(self, value: st.int128):
+        self.serialize_zigzag(int(value), 128)
+
+    def serialize_f32(self, value: st.float32):
+        self.output.write(struct.pack("<f", value))
+
+    def serialize_f64(self, value: st.float64):
+        self.output.write(struct.pack("<d", value))
+
+    def serialize_char(self, value: st.char):
+        self.serialize_str(str(value))
+
+    def serialize_len(self, value: int):
+        if value > MAX_LENGTH:
+            raise st.SerializationError("Length exceeds the maximum supported value.")
+        self.serialize_varint(value)
+
+    def serialize_variant_index(self, value: int):
+        if value > MAX_U32:
+            raise st.SerializationError(
+                "Variant index exceeds the maximum supported value."
+            )
+        self.serialize_varint(value)
+
+    def sort_map_entries(self, offsets: typing.List[int]):
+        pass
+
+
+class PostcardDeserializer(sb.BinaryDeserializer):
+    def __init__(self, content):
+        super().__init__(input=io.BytesIO(content), container_depth_budget=None)
+
+    def deserialize_varint(self, bits: int) -> int:
+        value = 0
+        for shift in range(0, bits, 7):
+            byte = int.from_bytes(self.read(1), "little", signed=False)
+            value |= (byte & 0x7F) << shift
+            if byte & 0x80 == 0:
+                if value >> bits != 0:
+                    raise st.DeserializationError(
+                        "Overflow while parsing varint-encoded value"
+                    )
+                return value
+        raise st.DeserializationError("Varint-encoded value is too long")
+
+    def deserialize_zigzag(self, bits: int) -> int:
+        value = self.deserialize_varint(bits)
+        return (value >> 1) ^ -(value & 1)
+
+    def deserialize_u16(self) -> st.uint16:
+        return st.uint16(self.deserialize_varint(16))
+
+    def deserialize_u32(self) -> st.uint32:
+        return st.uint32(self.deserialize_varint(32))
+
+    def deserialize_u64(self) -> st.uint64:
+        return st.uint64(self.deserialize_varint(64))
+
+    def deserialize_u128(self) -> st.uint128:
+        return st.uint128(self.deserialize_varint(128))
+
+    def deserialize_i16(self) -> st.int16:
+        return st.int16(self.deserialize_zigzag(16))
+
+    def deserialize_i32(self) -> st.int32:
+        return st.int32(self.deserialize_zigzag(32))
+
+    def deserialize_i64(self) -> st.int64:
+        return st.int64(self.deserialize_zigzag(64))
+
+    def deserialize_i128(self) -> st.int128:
+        return st.int128(self.deserialize_zigzag(128))
+
+    def deserialize_f32(self) -> st.float32:
+        (value,) = struct.unpack("<f", self.read(4))
+        return st.float32(value)
+
+    def deserialize_f64(self) -> st.float64:
+        (value,) = struct.unpack("<d", self.read(8))
+        return st.float64(value)
+
+    def deserialize_char(self) -> st.char:
+        value = self.deserialize_str()
+        if len(value) != 1:
+            raise st.DeserializationError("Expecting a single character:", value)
+        return st.char(value)
+
+    def deserialize_len(self) -> int:
+        value = self.deserialize_varint(64)
+        if value > MAX_LENGTH:
+            raise st.DeserializationError("Length exceeds the maximum supported value.")
+        return value
+
+    def deserialize_variant_index(self) -> int:
+        return self.deserialize_varint(32)
+
+    def check_that_key_slices_are_increasing(
+        self, slice1: typing.Tuple[int, int], slice2: typing.Tuple[int, int]
+    ):
+        pass
+
+
+def serialize(obj: typing.Any, obj_type) -> bytes:
+    serializer = PostcardSerializer()
+    serializer.serialize_any(obj, obj_type)
+    return serializer.get_buffer()
+
+
+def deserialize(content: bytes, obj_type) -> typing.Tuple[typing.Any, bytes]:
+    deserializer = PostcardDeserializer(content)
+    value = deserializer.deserialize_any(obj_type)
+    return value, deserializer.get_remaining_buffer()
@@ -0,0 +1,119 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+import dataclasses
+import collections
+import io
+import struct
+import typing
+from copy import copy
+from typing import get_type_hints
+
+import serde_types as st
+import serde_binary as sb
+
+# Maximum length in practice for sequences (e.g. in Java).
+MAX_LENGTH = (1 << 31) - 1
+MAX_U8 = (1 << 8) - 1
+
+
+class ScaleSerializer(sb.BinarySerializer):
+    def __init__(self):
+        super().__init__(output=io.BytesIO(), container_depth_budget=None)
+
+    def serialize_f32(self, value: st.float32):
+        self.output.write(struct.pack("<f", value))
+
+    def serialize_f64(self, value: st.float64):
+        self.output.write(struct.pack("<d", value))
+
+    def serialize_char(self, value: st.char):
+        raise st.SerializationError("Characters are not supported by SCALE.")
+
+    def serialize_compact_u32(self, value: int):
+        if value < (1 << 6):
+            self.output.write((value << 2).to_bytes(1, "little", signed=False))
+        elif value < (1 << 14):
+            self.output.write(((value << 2) | 1).to_bytes(2, "little", signed=False))
+        elif value < (1 << 30):
+            self.output.write(((value << 2) | 2).to_bytes(4, "little", signed=False))
+        else:
+            self.output.write(b"\x03")
+            self.output.write(value.to_bytes(4, "little", signed=False))
+
+    def serialize_len(self, value: int):
+        if value > MAX_LENGTH:
+            raise st.SerializationError("Length exceeds the maximum supported value.")
+        self.serialize_compact_u32(value)
+
+    def serialize_variant_index(self, value: int):
+        if value > MAX_U8:
+            raise st.SerializationError(
+                "Variant index exceeds the maximum supported value."
+            )
+        self.output.write(value.to_bytes(1, "little", signed=False))
+
+    def sort_map_entries(self, offsets: typing.List[int]):
+        pass
+
+
+class ScaleDeserializer(sb.BinaryDeserializer):
+    def __init__(self, content):
+        super().__init__(input=io.BytesIO(content), container_depth_budget=None)
+
+    def deserialize_f32(self) -> st.float32:
+        (value,) = struct.unpack("<f", self.read(4))
+        return st.float32(value)
+
+    def deserialize_f64(self) -> st.float64:
+        (value,) = struct.unpack("<d", self.read(8))
+        return st.float64(value)
+
+    def deserialize_char(self) -> st.char:
+        raise st.DeserializationError("Characters are not supported by SCALE.")
+
+    def deserialize_compact_u32(self) -> int:
+        prefix = int.from_bytes(self.read(1), "little", signed=False)
+        mode = prefix & 3
+        if mode == 0:
+            return prefix >> 2
+        if mode == 1:
+            value = (prefix | (int.from_bytes(self.read(1), "little") << 8)) >> 2
+            minimum = 1 << 6
+        elif mode == 2:
+            value = (prefix | (int.from_bytes(self.read(3), "little") << 8)) >> 2
+            minimum = 1 << 14
+        else:
+            if prefix != 3:
+                raise st.DeserializationError("Compact integer exceeds 32 bits.")
+            value = int.from_bytes(self.read(4), "little", signed=False)
+            minimum = 1 << 30
+        if value < minimum:
+            raise st.DeserializationError("Compact integer is not canonically encoded.")
+        return value
+
+    def deserialize_len(self) -> int:
+        value = self.deserialize_compact_u32()
+        if value > MAX_LENGTH:
+            raise st.DeserializationError("Length exceeds the maximum supported value.")
+        return value
+
+    def deserialize_variant_index(self) -> int:
+        return int.from_bytes(self.read(1), "little", signed=False)
+
+    def check_that_key_slices_are_increasing(
+        self, slice1: typing.Tuple[int, int], slice2: typing.Tuple[int, int]
+    ):
+        pass
+
+
+def serialize(obj: typing.Any, obj_type) -> bytes:
+    serializer = ScaleSerializer()
+    serializer.serialize_any(obj, obj_type)
+    return serializer.get_buffer()
+
+
+def deserialize(content: bytes, obj_type) -> typing.Tuple[typing.Any, bytes]:
+    deserializer = ScaleDeserializer(content)
+    value = deserializer.deserialize_any(obj_type)
+    return value, deserializer.get_remaining_buffer()
@@ -0,0 +1,170 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+from collections import OrderedDict
+from dataclasses import dataclass
+import math
+import unittest
+import serde_types as st
+import scale
+import typing
+
+
+class ScaleTestCase(unittest.TestCase):
+    def test_scale_bool(self):
+        self.assertEqual(scale.serialize(False, bool), b"\x00")
+        self.assertEqual(scale.serialize(True, bool), b"\x01")
+        self.assertEqual(scale.deserialize(b"\x00", bool), (False, b""))
+        self.assertEqual(scale.deserialize(b"\x01", bool), (True, b""))
+        with self.assertRaises(st.DeserializationError):
+            scale.deserialize(b"\x02", bool)
+        with self.assertRaises(st.DeserializationError):
+            scale.deserialize(b"", bool)
+
+    def test_scale_u16(self):
+        self.assertEqual(scale.serialize(0x0102, st.uint16), b"\x02\x01")
+        self.assertEqual(scale.deserialize(b"\xff\xff", st.uint16), (65535, b""))
+
+    def test_scale_u128(self):
+        self.assertEqual(
+            scale.serialize(st.uint128(1), st.uint128), b"\x01" + b"\x00" * 15
+        )
+        self.assertEqual(
+            scale.deserialize(b"\xff" * 16, st.uint128),
+            (st.uint128((1 << 128) - 1), b""),
+        )
+
+    def test_scale_i32(self):
+        self.assertEqual(scale.serialize(-2, st.int32), b"\xfe\xff\xff\xff")
+        self.assertEqual(
+            scale.deserialize(b"\xff\xff\xff\x7f", st.int32), (2147483647, b"")
+        )
+
+    def test_scale_floats(self):
+        self.assertEqual(scale.serialize(0.3, st.float32), b"\x9a\x99\x99\x3e")
+        value, reminder = scale.deserialize(b"\x9a\x99\x99\x3e", st.float32)
+        self.assertEqual(reminder, b"")
+        self.assertAlmostEqual(value, 0.3)
+        value, reminder = scale.deserialize(
+            b"\x00\x00\x00\x00\x00\x00\xf8\x7f", st.float64
+        )
+        self.assertTrue(math.isnan(value))
+
+    def test_scale_char(self):
+        with self.assertRaises(st.SerializationError):
+            scale.serialize(st.char("A"), st.char)
+        with self.assertRaises(st.DeserializationError):
+            scale.deserialize(b"\x04A", st.char)
+
+    def test_scale_compact_lengths(self):
+        for length, encoding in [
+            (0, b"\x00"),
+            (63, b"\xfc"),
+            (64, b"\x01\x01"),
+            (16383, b"\xfd\xff"),
+            (16384, b"\x02\x00\x01\x00"),
+            ((1 << 30) - 1, b"\xfe\xff\xff\xff"),
+            (1 << 30, b"\x03\x00\x00\x00\x40"),
+        ]:
+            serializer = scale.ScaleSerializer()
+            serializer.serialize_len(length)
+            self.assertEqual(serializer.get_buffer(), encoding)
+            deserializer = scale.ScaleDeserializer(encoding)
+            self.assertEqual(deserializer.deserialize_len(), length)
+        # Non-canonical encodings and values beyond 32 bits.
+        for encoding in [
+            b"\x01\x00",
+            b"\x02\x01\x00\x00",
+            b"\x03\xff\xff\xff\x3f",
+            b"\x07\x00\x00\x00\x00\x01",
+        ]:
+            with self.assertRaises(st.DeserializationError):
+                scale.ScaleDeserializer(encoding).deserialize_len()
+
+    def test_serialize_bytes(self):
+        self.assertEqual(scale.serialize(b"", bytes), b"\x00")
+        self.assertEqual(scale.serialize(b"\x00\x00", bytes), b"\x08\x00\x00")
+        self.assertEqual(scale.deserialize(b"\x00", bytes), (b"", b""))
+        with self.assertRaises(st.DeserializationError):
+            scale.deserialize(b"\x08\x00", bytes)
+
+    def test_serialize_tuple(self):
+        T = typing.Tuple[st.uint8, st.uint16]
+        self.assertEqual(scale.serialize((0, 1), T), b"\x00\x01\x00")
+        self.assertEqual(scale.deserialize(b"\x02\x80\x01", T), ((2, 384), b""))
+
+    def test_serialize_option(self):
+        T = typing.Optional[st.uint16]
+        self.assertEqual(scale.serialize(None, T), b"\x00")
+        self.assertEqual(scale.serialize(6, T), b"\x01\x06\x00")
+        self.assertEqual(scale.deserialize(b"\x00", T), (None, b""))
+        self.assertEqual(scale.deserialize(b"\x01\x02\x00", T), (2, b""))
+        with self.assertRaisesRegex(st.DeserializationError, "Wrong tag.*"):
+            scale.deserialize(b"\x02\x06\x00", T)
+
+    def test_serialize_sequence(self):
+        Seq = typing.Sequence[st.uint16]
+        self.assertEqual(scale.serialize([], Seq), b"\x00")
+        self.assertEqual(scale.serialize([0, 1], Seq), b"\x08\x00\x00\x01\x00")
+        self.assertEqual(scale.deserialize(b"\x04\x03\x00", Seq), ([3], b""))
+        self.assertEqual(
+            scale.serialize([0] * 64, Seq), b"\x01\x01" + b"\x00" * 128
+        )
+
+    def test_serialize_str(self):
+        self.assertEqual(scale.serialize("ABCΔ", str), b"\x14ABC\xce\x94")
+        self.assertEqual(scale.deserialize(b"\x14ABC\xce\x94A", str), ("ABCΔ", b"A"))
+        with self.assertRaises(st.DeserializationError):
+            scale.deserialize(b"\x0cAB", str)
+        with self.assertRaises(st.DeserializationError):
+            scale.deserialize(b"\x0c\x80ab", str)
+
+    def test_serialize_map(self):
+        Map = typing.Dict[st.uint8, st.uint16]
+        m = OrderedDict([(3, 256), (1, 5)])
+        e = scale.serialize(m, Map)
+        self.assertEqual(e, b"\x08\x03\x00\x01\x01\x05\x00")
+        self.assertEqual(
+            (OrderedDict([(3, 256), (1, 5)]), b""),
+            scale.deserialize(b"\x08\x03\x00\x01\x01\x05\x00", Map),
+        )
+        # Duplicate keys are accepted and the last value wins.
+        self.assertEqual(
+            scale.deserialize(b"\x08\x01\x05\x00\x01\x06\x00", Map), ({1: 6}, b"")
+        )
+
+    @dataclass
+    class Foo:
+        x: st.uint8
+        y: st.uint16
+
+    def test_struct(self):
+        self.assertEqual(
+            scale.serialize(ScaleTestCase.Foo(x=0, y=1), ScaleTestCase.Foo),
+            b"\x00\x01\x00",
+        )
+        self.assertEqual(
+            scale.deserialize(b"\x02\x01\x00", ScaleTestCase.Foo),
+            (ScaleTestCase.Foo(x=2, y=1), b""),
+        )
+
+    class Bar:
+        VARIANTS = []  # type: typing.Sequence[typing.Type['Bar']]
+
+    @dataclass
+    class Bar1(Bar):
+        INDEX = 1
+        x: st.uint8
+        y: st.uint16
+
+    Bar.VARIANTS = [None, Bar1, None]
+
+    def test_enum(self):
+        self.assertEqual(
+            scale.serialize(ScaleTestCase.Bar1(x=0, y=1), ScaleTestCase.Bar),
+            b"\x01\x00\x01\x00",
+        )
+        self.assertEqual(
+            scale.deserialize(b"\x01\x02\x01\x00", ScaleTestCase.Bar),
+            (ScaleTestCase.Bar1(x=2, y=1), b""),
+        )
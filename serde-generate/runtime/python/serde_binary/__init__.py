@@ -4,7 +4,7 @@
 """
 Module describing the "binary" serialization formats.
 
-Note: This internal module is currently only meant to share code between the BCS and bincode formats. Internal APIs could change in the future.
+Note: This internal module is currently only meant to share code between the BCS, bincode and postcard formats. Internal APIs could change in the future.
 """
 
 import dataclasses
@@ -0,0 +1,481 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+"""
+Module implementing the JSON format of the Rust crate `serde_json`.
+
+Values are written compactly, structs as objects with fields in declaration order, newtype
+structs as their content, unit values as `null`, and enums in the externally tagged
+representation: unit variants as strings and other variants as objects with a single entry.
+Map keys are written as JSON strings.
+"""
+
+import collections
+import dataclasses
+import decimal
+import json
+import re
+import struct
+import typing
+from typing import get_type_hints
+
+import serde_types as st
+
+# Maximal number of nested arrays and objects accepted by `serde_json` (whose recursion limit
+# is 128).
+MAX_CONTAINER_DEPTH = 127
+
+_INTEGER = re.compile(r"-?(0|[1-9][0-9]*)\Z")
+_NUMBER = re.compile(r"-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?\Z")
+
+_INTEGER_RANGES = {
+    st.uint8: (0, 2**8 - 1),
+    st.uint16: (0, 2**16 - 1),
+    st.uint32: (0, 2**32 - 1),
+    st.uint64: (0, 2**64 - 1),
+    st.uint128: (0, 2**128 - 1),
+    st.int8: (-(2**7), 2**7 - 1),
+    st.int16: (-(2**15), 2**15 - 1),
+    st.int32: (-(2**31), 2**31 - 1),
+    st.int64: (-(2**63), 2**63 - 1),
+    st.int128: (-(2**127), 2**127 - 1),
+}
+
+
+class _Float:
+    """A JSON number that is not an integer (or is `-0`), as read from the input."""
+
+    def __init__(self, literal: str):
+        self.literal = literal
+
+
+class _Object(list):
+    """The entries of a JSON object, as read from the input (in order, with duplicates)."""
+
+
+class _Key:
+    """A map key, i.e. a JSON string that may stand for a number or a boolean."""
+
+    def __init__(self, value: str):
+        self.value = value
+
+
+def _parse_int(literal: str) -> typing.Any:
+    # Like `serde_json`, read `-0` as a float, except for 128-bit integers.
+    return _Float(literal) if literal == "-0" else int(literal)
+
+
+def _reject_constant(name: str) -> typing.Any:
+    raise st.DeserializationError("Invalid JSON value", name)
+
+
+def serialize(obj: typing.Any, obj_type) -> str:
+    """Serialize a value of the given type into a JSON string."""
+    output = []
+    _serialize_any(output, obj, obj_type)
+    return "".join(output)
+
+
+def deserialize(content: str, obj_type) -> typing.Any:
+    """Deserialize a value of the given type from a JSON string."""
+    try:
+        value = json.loads(
+            content,
+            parse_float=_Float,
+            parse_int=_parse_int,
+            parse_constant=_reject_constant,
+            object_pairs_hook=_Object,
+        )
+    except (ValueError, RecursionError) as e:
+        raise st.DeserializationError("Invalid JSON input", e)
+    _check_depth(value, MAX_CONTAINER_DEPTH)
+    return _deserialize_any(value, obj_type)
+
+
+def _check_depth(value: typing.Any, budget: int):
+    if isinstance(value, list):
+        if budget == 0:
+            raise st.DeserializationError("Exceeded maximum container depth")
+        for item in value:
+            if isinstance(value, _Object):
+                item = item[1]
+            _check_depth(item, budget - 1)
+
+
+def _to_f32(value: float) -> float:
+    """Closest f32 value, or an infinity if `value` is too large."""
+    try:
+        return struct.unpack("<f", struct.pack("<f", value))[0]
+    except OverflowError:
+        return float("inf") if value > 0 else float("-inf")
+
+
+def _shortest_digits(value: float, is_f32: bool) -> typing.Tuple[str, int]:
+    """Shortest decimal digits `d` and exponent `e` such that `0.d * 10**e` reads back as `value`."""
+    if is_f32:
+        for precision in range(1, 10):
+            literal = "%.*e" % (precision - 1, value)
+            if _to_f32(float(literal)) == value:
+                break
+    else:
+        literal = repr(value)
+    digits = decimal.Decimal(literal).normalize().as_tuple()
+    text = "".join(str(d) for d in digits.digits)
+    return text, len(text) + digits.exponent
+
+
+def _format_float(value: float, is_f32: bool) -> str:
+    """Format a finite float in the manner of the Rust crate `ryu`, as `serde_json` does."""
+    if value == 0:
+        return "-0.0" if str(value).startswith("-") else "0.0"
+    sign = "-" if value < 0 else ""
+    digits, point = _shortest_digits(abs(value), is_f32)
+    max_point = 13 if is_f32 else 16
+    min_point = -6 if is_f32 else -5
+    if len(digits) <= point <= max_point:
+        return sign + digits + "0" * (point - len(digits)) + ".0"
+    if 0 < point <= max_point:
+        return sign + digits[:point] + "." + digits[point:]
+    if min_point < point <= 0:
+        return sign + "0." + "0" * -point + digits
+    mantissa = digits if len(digits) == 1 else digits[0] + "." + digits[1:]
+    return sign + mantissa + "e" + str(point - 1)
+
+
+def _serialize_float(value: typing.Any, is_f32: bool) -> str:
+    value = float(value)
+    if value != value or value in (float("inf"), float("-inf")):
+        return "null"
+    return _format_float(value, is_f32)
+
+
+def _serialize_str(value: str) -> str:
+    try:
+        value.encode()
+    except UnicodeEncodeError:
+        raise st.SerializationError("Invalid unicode string", value)
+    return json.dumps(value, ensure_ascii=False)
+
+
+def _serialize_key(obj: typing.Any, obj_type) -> str:
+    output = []
+    _serialize_any(output, obj, obj_type)
+    key = output[0] if len(output) == 1 else "".join(output)
+    if key.startswith('"'):
+        return key
+    if key == "null" or key.startswith("[") or key.startswith("{"):
+        raise st.SerializationError("Map keys must be strings or numbers", obj)
+    return '"' + key + '"'
+
+
+# noqa: C901
+def _serialize_any(output: typing.List[str], obj: typing.Any, obj_type):
+    if obj_type == st.unit:
+        output.append("null")
+
+    elif obj_type == bool:
+        output.append("true" if obj else "false")
+
+    elif obj_type in _INTEGER_RANGES:
+        output.append(str(int(obj)))
+
+    elif obj_type in (st.float32, st.float64):
+        output.append(_serialize_float(obj, obj_type == st.float32))
+
+    elif obj_type == st.char:
+        output.append(_serialize_str(str(obj)))
+
+    elif obj_type == str:
+        output.append(_serialize_str(obj))
+
+    elif obj_type == bytes:
+        output.append("[" + ",".join(str(b) for b in obj) + "]")
+
+    elif hasattr(obj_type, "__origin__"):  # Generic type
+        types = getattr(obj_type, "__args__")
+
+        if getattr(obj_type, "__origin__") == collections.abc.Sequence:  # Sequence
+            assert len(types) == 1
+            _serialize_items(output, obj, [types[0]] * len(obj))
+
+        elif getattr(obj_type, "__origin__") == tuple:  # Tuple
+            if len(types) == 1 and types[0] == ():
+                types = ()
+            _serialize_items(output, obj, types)
+
+        elif getattr(obj_type, "__origin__") == typing.Union:  # Option
+            assert len(types) == 2 and types[1] == type(None)
+            if obj is None:
+                output.append("null")
+            else:
+                _serialize_any(output, obj, types[0])
+
+        elif getattr(obj_type, "__origin__") == dict:  # Map
+            assert len(types) == 2
+            output.append("{")
+            for i, (key, value) in enumerate(obj.items()):
+                if i > 0:
+                    output.append(",")
+                output.append(_serialize_key(key, types[0]))
+                output.append(":")
+                _serialize_any(output, value, types[1])
+            output.append("}")
+
+        else:
+            raise st.SerializationError("Unexpected type", obj_type)
+
+    else:
+        variant_name = None
+        if not dataclasses.is_dataclass(obj_type):  # Enum
+            if not hasattr(obj_type, "VARIANTS"):
+                raise st.SerializationError("Unexpected type", obj_type)
+            if not hasattr(obj, "INDEX"):
+                raise st.SerializationError("Wrong Value for the type", obj, obj_type)
+            # Proceed to variant
+            variant_type = obj_type.VARIANTS[obj.__class__.INDEX]
+            variant_name = _variant_name(obj_type, variant_type)
+            obj_type = variant_type
+            if not dataclasses.is_dataclass(obj_type):
+                raise st.SerializationError("Unexpected type", obj_type)
+
+        # pyre-ignore
+        if not isinstance(obj, obj_type):
+            raise st.SerializationError("Wrong Value for the type", obj, obj_type)
+
+        kind = obj_type.JSON_KIND
+        if variant_name is not None:
+            if kind == "unit":
+                output.append(_serialize_str(variant_name))
+                return
+            output.append("{" + _serialize_str(variant_name) + ":")
+        _serialize_content(output, obj, obj_type, kind)
+        if variant_name is not None:
+            output.append("}")
+
+
+def _serialize_items(output: typing.List[str], obj: typing.Any, types):
+    if len(obj) != len(types):
+        raise st.SerializationError(
+            "Invalid length for tuple or fixed-size array", obj, types
+        )
+    output.append("[")
+    for i, (item, item_type) in enumerate(zip(obj, types)):
+        if i > 0:
+            output.append(",")
+        _serialize_any(output, item, item_type)
+    output.append("]")
+
+
+def _serialize_content(output: typing.List[str], obj: typing.Any, obj_type, kind: str):
+    """Serialize the fields of a struct or a variant, according to the kind of container."""
+    types = get_type_hints(obj_type)
+    if kind == "unit":
+        output.append("null")
+    elif kind in ("newtype", "tuple"):
+        _serialize_any(output, obj.value, types["value"])
+    else:
+        output.append("{")
+        for i, field in enumerate(dataclasses.fields(obj_type)):
+            if i > 0:
+                output.append(",")
+            output.append(_serialize_str(field.name) + ":")
+            _serialize_any(output, obj.__dict__[field.name], types[field.name])
+        output.append("}")
+
+
+def _variant_name(obj_type, variant_type) -> str:
+    # Variant classes are named after the enum and the variant, e.g. `Choice__A`.
+    return variant_type.__name__[len(obj_type.__name__) + 2 :]
+
+
+def _type_error(value: typing.Any, expected: str) -> st.DeserializationError:
+    return st.DeserializationError("Invalid type: expected " + expected, value)
+
+
+def _deserialize_integer(value: typing.Any, obj_type) -> int:
+    if isinstance(value, _Key):
+        if not _INTEGER.match(value.value):
+            raise _type_error(value.value, "an integer key")
+        value = _parse_int(value.value)
+    if isinstance(value, _Float) and value.literal == "-0" and obj_type == st.int128:
+        value = 0
+    elif isinstance(value, bool) or not isinstance(value, int):
+        raise _type_error(value, "an integer")
+    low, high = _INTEGER_RANGES[obj_type]
+    if not low <= value <= high:
+        raise st.DeserializationError("Integer out of range", value, obj_type)
+    return obj_type(value)
+
+
+def _deserialize_float(value: typing.Any, obj_type) -> float:
+    if isinstance(value, _Key):
+        if not _NUMBER.match(value.value):
+            raise _type_error(value.value, "a number key")
+        result = float(value.value)
+    elif isinstance(value, _Float):
+        result = float(value.literal)
+    elif isinstance(value, int) and not isinstance(value, bool):
+        try:
+            result = float(value)
+        except OverflowError:
+            raise st.DeserializationError("Number out of range", value)
+    else:
+        raise _type_error(value, "a number")
+    if result in (float("inf"), float("-inf")):
+        raise st.DeserializationError("Number out of range", value)
+    if obj_type == st.float32:
+        # Like `serde_json`, read the closest f64 value then convert it to f32.
+        result = _to_f32(result)
+    return obj_type(result)
+
+
+def _deserialize_str(value: typing.Any) -> str:
+    if isinstance(value, _Key):
+        value = value.value
+    if not isinstance(value, str):
+        raise _type_error(value, "a string")
+    try:
+        value.encode()
+    except UnicodeEncodeError:
+        raise st.DeserializationError("Invalid unicode string", value)
+    return value
+
+
+def _deserialize_object(value: typing.Any, fields, types) -> typing.List[typing.Any]:
+    """Read the values of the given fields from the entries of a JSON object."""
+    if not isinstance(value, _Object):
+        raise _type_error(value, "an object")
+    entries = {}
+    for key, item in value:
+        if key in entries:
+            raise st.DeserializationError("Duplicate field", key)
+        entries[key] = item
+    result = []
+    for field in fields:
+        field_type = types[field.name]
+        if field.name in entries:
+            result.append(_deserialize_any(entries[field.name], field_type))
+        elif getattr(field_type, "__origin__", None) == typing.Union:
+            # Like Serde, missing optional fields are `None`.
+            result.append(None)
+        else:
+            raise st.DeserializationError("Missing field", field.name)
+    return result
+
+
+def _deserialize_items(value: typing.Any, types) -> typing.List[typing.Any]:
+    if type(value) is not list:
+        raise _type_error(value, "an array")
+    if len(value) != len(types):
+        raise st.DeserializationError("Invalid length", value)
+    return [_deserialize_any(item, item_type) for item, item_type in zip(value, types)]
+
+
+def _deserialize_content(value: typing.Any, obj_type, kind: str) -> typing.Any:
+    """Deserialize the fields of a struct or a variant, according to the kind of container."""
+    types = get_type_hints(obj_type)
+    if kind == "unit":
+        if value is not None:
+            raise _type_error(value, "null")
+        return obj_type()
+    if kind in ("newtype", "tuple"):
+        return obj_type(_deserialize_any(value, types["value"]))
+    return obj_type(*_deserialize_object(value, dataclasses.fields(obj_type), types))
+
+
+# noqa
+def _deserialize_any(value: typing.Any, obj_type) -> typing.Any:
+    if obj_type == st.unit:
+        if value is not None:
+            raise _type_error(value, "null")
+        return None
+
+    elif obj_type == bool:
+        if isinstance(value, _Key) and value.value in ("true", "false"):
+            return value.value == "true"
+        if not isinstance(value, bool):
+            raise _type_error(value, "a boolean")
+        return value
+
+    elif obj_type in _INTEGER_RANGES:
+        return _deserialize_integer(value, obj_type)
+
+    elif obj_type in (st.float32, st.float64):
+        return _deserialize_float(value, obj_type)
+
+    elif obj_type == st.char:
+        value = _deserialize_str(value)
+        if len(value) != 1:
+            raise _type_error(value, "a character")
+        return st.char(value)
+
+    elif obj_type == str:
+        return _deserialize_str(value)
+
+    elif obj_type == bytes:
+        if type(value) is not list:
+            raise _type_error(value, "an array")
+        items = _deserialize_items(value, [st.uint8] * len(value))
+        return bytes(int(b) for b in items)
+
+    elif hasattr(obj_type, "__origin__"):  # Generic type
+        types = getattr(obj_type, "__args__")
+        if getattr(obj_type, "__origin__") == collections.abc.Sequence:  # Sequence
+            assert len(types) == 1
+            if type(value) is not list:
+                raise _type_error(value, "an array")
+            return _deserialize_items(value, [types[0]] * len(value))
+
+        elif getattr(obj_type, "__origin__") == tuple:  # Tuple
+            if len(types) == 1 and types[0] == ():
+                types = ()
+            return tuple(_deserialize_items(value, types))
+
+        elif getattr(obj_type, "__origin__") == typing.Union:  # Option
+            assert len(types) == 2 and types[1] == type(None)
+            if value is None:
+                return None
+            return _deserialize_any(value, types[0])
+
+        elif getattr(obj_type, "__origin__") == dict:  # Map
+            assert len(types) == 2
+            if not isinstance(value, _Object):
+                raise _type_error(value, "an object")
+            result = dict()
+            for key, item in value:
+                # Like `serde_json`, later entries replace earlier ones with the same key.
+                result[_deserialize_any(_Key(key), types[0])] = _deserialize_any(
+                    item, types[1]
+                )
+            return result
+
+        else:
+            raise st.DeserializationError("Unexpected type", obj_type)
+
+    elif dataclasses.is_dataclass(obj_type):  # Struct
+        if isinstance(value, _Key):
+            if obj_type.JSON_KIND != "newtype":
+                raise _type_error(value.value, "a struct")
+        return _deserialize_content(value, obj_type, obj_type.JSON_KIND)
+
+    elif hasattr(obj_type, "VARIANTS"):  # Enum
+        if isinstance(value, (str, _Key)):
+            name, content, has_content = _deserialize_str(value), None, False
+        elif isinstance(value, _Object) and len(value) == 1:
+            (name, content), has_content = value[0], True
+        else:
+            raise _type_error(value, "a string or an object with a single entry")
+        for variant_type in obj_type.VARIANTS:
+            if _variant_name(obj_type, variant_type) == name:
+                kind = variant_type.JSON_KIND
+                if kind == "unit":
+                    if content is not None:
+                        raise _type_error(content, "null")
+                    return variant_type()
+                if not has_content:
+                    raise _type_error(name, "a variant with content")
+                return _deserialize_content(content, variant_type, kind)
+        raise st.DeserializationError("Unknown variant", name)
+
+    else:
+        raise st.DeserializationError("Unexpected type", obj_type)
@@ -0,0 +1,107 @@
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+from dataclasses import dataclass
+import unittest
+import serde_types as st
+import serde_json
+import typing
+
+
+class SerdeJsonTestCase(unittest.TestCase):
+    def test_serde_json_bool(self):
+        self.assertEqual(serde_json.serialize(False, bool), "false")
+        self.assertEqual(serde_json.deserialize(" true ", bool), True)
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize("1", bool)
+
+    def test_serde_json_integers(self):
+        self.assertEqual(serde_json.serialize(-3, st.int64), "-3")
+        self.assertEqual(serde_json.deserialize("255", st.uint8), 255)
+        self.assertEqual(
+            serde_json.deserialize(str((1 << 128) - 1), st.uint128),
+            st.uint128((1 << 128) - 1),
+        )
+        self.assertEqual(serde_json.deserialize("-0", st.int128), st.int128(0))
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize("256", st.uint8)
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize("-0", st.int32)
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize("1.0", st.int32)
+
+    def test_serde_json_floats(self):
+        self.assertEqual(serde_json.serialize(st.float32(0.3), st.float32), "0.3")
+        self.assertEqual(serde_json.serialize(st.float64(0.3), st.float64), "0.3")
+        self.assertEqual(serde_json.serialize(st.float64(-0.0), st.float64), "-0.0")
+        self.assertEqual(serde_json.serialize(st.float64(1e16), st.float64), "1e16")
+        self.assertEqual(serde_json.serialize(st.float64(1e-5), st.float64), "0.00001")
+        self.assertEqual(serde_json.serialize(st.float32(1e13), st.float32), "1e13")
+        self.assertEqual(
+            serde_json.serialize(st.float64(float("nan")), st.float64), "null"
+        )
+        self.assertEqual(serde_json.deserialize("2", st.float64), 2.0)
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize("1e400", st.float64)
+
+    def test_serde_json_str(self):
+        self.assertEqual(
+            serde_json.serialize('A"\\\n\x01\x7fΔ', str), '"A\\"\\\\\\n\\u0001\x7fΔ"'
+        )
+        self.assertEqual(serde_json.deserialize('"\\u0394\\/"', str), "Δ/")
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize('"\\ud800"', str)
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize('"\t"', str)
+
+    def test_serde_json_bytes(self):
+        self.assertEqual(serde_json.serialize(b"\x00\xff", bytes), "[0,255]")
+        self.assertEqual(serde_json.deserialize("[1,2]", bytes), b"\x01\x02")
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize("[256]", bytes)
+
+    def test_serde_json_tuple(self):
+        T = typing.Tuple[st.uint8, st.uint16]
+        self.assertEqual(serde_json.serialize((0, 1), T), "[0,1]")
+        self.assertEqual(serde_json.deserialize("[2,384]", T), (2, 384))
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize("[2]", T)
+
+    def test_serde_json_option(self):
+        T = typing.Optional[st.uint16]
+        self.assertEqual(serde_json.serialize(None, T), "null")
+        self.assertEqual(serde_json.serialize(6, T), "6")
+        self.assertEqual(serde_json.deserialize("null", T), None)
+        self.assertEqual(serde_json.deserialize("2", T), 2)
+
+    def test_serde_json_map(self):
+        Map = typing.Dict[st.uint8, st.uint16]
+        self.assertEqual(serde_json.serialize({3: 256, 1: 5}, Map), '{"3":256,"1":5}')
+        self.assertEqual(serde_json.deserialize('{"1":5,"1":6}', Map), {1: 6})
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize('{"01":5}', Map)
+
+    def test_serde_json_depth(self):
+        T = typing.Sequence[typing.Any]
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize("[" * 128 + "]" * 128, T)
+
+    @dataclass
+    class Foo:
+        JSON_KIND = "struct"  # type: str
+        x: st.uint8
+        y: typing.Optional[st.uint16]
+
+    def test_struct(self):
+        self.assertEqual(
+            serde_json.serialize(SerdeJsonTestCase.Foo(x=0, y=1), SerdeJsonTestCase.Foo),
+            '{"x":0,"y":1}',
+        )
+        self.assertEqual(
+            serde_json.deserialize('{"z":[],"x":2}', SerdeJsonTestCase.Foo),
+            SerdeJsonTestCase.Foo(x=2, y=None),
+        )
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize('{"x":2,"x":3}', SerdeJsonTestCase.Foo)
+        with self.assertRaises(st.DeserializationError):
+            serde_json.deserialize('{"y":2}', SerdeJsonTestCase.Foo)
@@ -2,6 +2,8 @@
 # SPDX-License-Identifier: MIT OR Apache-2.0
 
 import numpy as np
+import collections.abc
+import dataclasses
 from dataclasses import dataclass
 import typing
 
@@ -30,6 +32,9 @@ class uint128:
     def __int__(self):
         return (int(self.high) << 64) | int(self.low)
 
+    def __hash__(self):
+        return hash(int(self))
+
 
 @dataclass(init=False)
 class int128:
@@ -43,6 +48,9 @@ class int128:
     def __int__(self):
         return (int(self.high) << 64) | int(self.low)
 
+    def __hash__(self):
+        return hash(int(self))
+
 
 @dataclass(init=False)
 class char:
@@ -56,6 +64,9 @@ class char:
     def __str__(self):
         return self.value
 
+    def __hash__(self):
+        return hash(self.value)
+
 
 unit = typing.Type[None]
 
@@ -72,3 +83,73 @@ uint64 = np.uint64
 
 float32 = np.float32
 float64 = np.float64
+
+
+def sort_key(value: typing.Any) -> typing.Any:
+    """Map a value to a hashable key whose ordering matches the Rust derive order."""
+    if value is None:
+        return (0,)
+    if isinstance(value, Ordered):
+        return (1, value._sort_key())
+    if isinstance(value, (list, tuple)):
+        return (1, tuple(sort_key(x) for x in value))
+    if isinstance(value, dict):
+        return (1, tuple(sorted((sort_key(k), sort_key(v)) for k, v in value.items())))
+    if isinstance(value, (uint128, int128)):
+        return (1, int(value))
+    if isinstance(value, char):
+        return (1, value.value)
+    return (1, value)
+
+
+class Ordered:
+    """Total ordering for generated classes, based on `_sort_key`."""
+
+    def _sort_key(self) -> typing.Any:
+        raise NotImplementedError
+
+    def __lt__(self, other: typing.Any) -> bool:
+        return self._sort_key() < other._sort_key()
+
+    def __le__(self, other: typing.Any) -> bool:
+        return self._sort_key() <= other._sort_key()
+
+    def __gt__(self, other: typing.Any) -> bool:
+        return self._sort_key() > other._sort_key()
+
+    def __ge__(self, other: typing.Any) -> bool:
+        return self._sort_key() >= other._sort_key()
+
+
+def validate(value: typing.Any, value_type: typing.Any) -> None:
+    """Check the constraints of `value_type` that Python annotations do not enforce, such as
+    the length of tuples and fixed-size arrays. Raise `SerializationError` otherwise."""
+    origin = getattr(value_type, "__origin__", None)
+    if origin is not None:  # Generic type
+        types = [t for t in value_type.__args__ if t != ()]
+        if origin == collections.abc.Sequence:
+            for item in value:
+                validate(item, types[0])
+        elif origin == tuple:
+            if len(value) != len(types):
+                raise SerializationError(
+                    "Invalid length for tuple or fixed-size array", value, value_type
+                )
+            for item, item_type in zip(value, types):
+                validate(item, item_type)
+        elif origin == typing.Union:  # Option
+            if value is not None:
+                validate(value, types[0])
+        elif origin == dict:
+            for key, item in value.items():
+                validate(key, types[0])
+                validate(item, types[1])
+
+    elif dataclasses.is_dataclass(value) and isinstance(value, value_type):
+        # Struct, or variant of the enum `value_type`.
+        types = typing.get_type_hints(type(value))
+        for field in dataclasses.fields(value):
+            validate(getattr(value, field.name), types[field.name])
+
+    elif dataclasses.is_dataclass(value_type):
+        raise SerializationError("Wrong Value for the type", value, value_type)
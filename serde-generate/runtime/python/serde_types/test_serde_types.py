@@ -1,6 +1,7 @@
 # Copyright (c) Facebook, Inc. and its affiliates
 # SPDX-License-Identifier: MIT OR Apache-2.0
 
+import typing
 import unittest
 import serde_types as st
 
@@ -31,3 +32,19 @@ class SerdeTypesTestCase(unittest.TestCase):
         self.assertEqual(str(st.char("a")), "a")
         with self.assertRaises(ValueError):
             st.char("ab")
+
+    def test_sort_key(self):
+        self.assertLess(st.sort_key(None), st.sort_key(0))
+        self.assertLess(st.sort_key([1, 2]), st.sort_key([1, 2, 0]))
+        self.assertLess(st.sort_key({1: "b", 3: "a"}), st.sort_key({2: "a"}))
+        self.assertLess(st.sort_key(st.uint128(1)), st.sort_key(st.uint128(1 << 64)))
+        self.assertEqual(hash(st.sort_key({1: [2]})), hash(st.sort_key({1: [2]})))
+
+    def test_validate(self):
+        pair = typing.Tuple[st.uint8, typing.Tuple[st.uint8, st.uint8]]
+        st.validate((1, (2, 3)), pair)
+        st.validate([None, (1, 2)], typing.Sequence[typing.Optional[typing.Tuple[int, int]]])
+        with self.assertRaises(st.SerializationError):
+            st.validate((1, (2,)), pair)
+        with self.assertRaises(st.SerializationError):
+            st.validate({"a": (1, 2, 3)}, typing.Dict[str, typing.Tuple[int, int]])
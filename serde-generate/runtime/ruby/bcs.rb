@@ -0,0 +1,108 @@
+# frozen_string_literal: true
+
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+require_relative 'serde'
+
+# Runtime of the BCS encoding.
+module Bcs
+  MAX_LENGTH = (1 << 31) - 1
+  MAX_CONTAINER_DEPTH = 500
+
+  class Serializer < Serde::BinarySerializer
+    def initialize
+      super(MAX_CONTAINER_DEPTH)
+    end
+
+    def serialize_f32(_value)
+      raise Serde::SerializationError, 'Not implemented: serialize_f32'
+    end
+
+    def serialize_f64(_value)
+      raise Serde::SerializationError, 'Not implemented: serialize_f64'
+    end
+
+    def serialize_len(value)
+      raise Serde::SerializationError, 'Incorrect length value' if value > MAX_LENGTH
+
+      serialize_u32_as_uleb128(value)
+    end
+
+    def serialize_variant_index(value)
+      serialize_u32_as_uleb128(value)
+    end
+
+    # Sort the serialized map entries starting at the given offsets by their bytes.
+    def sort_map_entries(offsets)
+      return if offsets.size <= 1
+
+      total = @output.bytesize
+      entries = offsets.each_with_index.map do |start, i|
+        @output.byteslice(start...(offsets[i + 1] || total))
+      end
+      @output = @output.byteslice(0, offsets[0])
+      entries.sort.each { |entry| @output << entry }
+    end
+
+    private
+
+    def serialize_u32_as_uleb128(value)
+      while value >= 0x80
+        @output << ((value & 0x7F) | 0x80)
+        value >>= 7
+      end
+      @output << value
+    end
+  end
+
+  class Deserializer < Serde::BinaryDeserializer
+    def initialize(input)
+      super(input, MAX_CONTAINER_DEPTH)
+    end
+
+    def deserialize_f32
+      raise Serde::DeserializationError, 'Not implemented: deserialize_f32'
+    end
+
+    def deserialize_f64
+      raise Serde::DeserializationError, 'Not implemented: deserialize_f64'
+    end
+
+    def deserialize_len
+      deserialize_uleb128_as_u32
+    end
+
+    def deserialize_variant_index
+      deserialize_uleb128_as_u32
+    end
+
+    # Check that the bytes of two consecutive keys of a map, given as ranges of offsets,
+    # are in strictly increasing order.
+    def check_that_key_slices_are_increasing(key1, key2)
+      return if (input_slice(key1) <=> input_slice(key2)).negative?
+
+      raise Serde::DeserializationError,
+            'Error while decoding map: keys are not serialized in the expected order'
+    end
+
+    private
+
+    def deserialize_uleb128_as_u32
+      value = 0
+      0.step(28, 7) do |shift|
+        byte = read_byte
+        digit = byte & 0x7F
+        value |= digit << shift
+        raise Serde::DeserializationError, 'Overflow while parsing uleb128-encoded uint32 value' if value > MAX_LENGTH
+
+        if digit == byte
+          raise Serde::DeserializationError, 'Invalid uleb128 number (unexpected zero digit)' if shift.positive? && digit.zero?
+
+          return value
+        end
+      end
+      raise Serde::DeserializationError, 'Overflow while parsing uleb128-encoded uint32 value'
+    end
+  end
+end
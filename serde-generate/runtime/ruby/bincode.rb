@@ -0,0 +1,152 @@
+# frozen_string_literal: true
+
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+require_relative 'serde'
+
+# Runtime of the Bincode encoding. The default options are those of `bincode::serialize`.
+module Bincode
+  MAX_LENGTH = (1 << 31) - 1
+
+  # `varint_encoding` and `big_endian` correspond to the options `with_varint_encoding` and
+  # `with_big_endian` of the Rust crate.
+  class Serializer < Serde::BinarySerializer
+    def initialize(varint_encoding: false, big_endian: false)
+      super(Float::INFINITY)
+      @varint_encoding = varint_encoding
+      @big_endian = big_endian
+    end
+
+    def serialize_f32(value)
+      check_float(value)
+      @output << [value].pack(@big_endian ? 'g' : 'e')
+    end
+
+    def serialize_f64(value)
+      check_float(value)
+      @output << [value].pack(@big_endian ? 'G' : 'E')
+    end
+
+    def serialize_len(value)
+      write_integer(value, 8, false)
+    end
+
+    def serialize_variant_index(value)
+      write_integer(value, 4, false)
+    end
+
+    def sort_map_entries(_offsets)
+      # Not required by the format.
+    end
+
+    protected
+
+    def write_integer(value, size, signed)
+      check_integer(value, size, signed)
+      if @varint_encoding && size > 1
+        # Signed integers use the "zigzag" encoding.
+        value = value >= 0 ? value << 1 : (-value << 1) - 1 if signed
+        write_varint(value)
+      else
+        write_fixint(value, size)
+      end
+    end
+
+    # Write the `size` lowest bytes of the two's complement of `value` in the configured order.
+    def write_fixint(value, size)
+      bytes = Array.new(size) { |i| (value >> (8 * i)) & 0xFF }
+      bytes.reverse! if @big_endian
+      bytes.each { |byte| @output << byte }
+    end
+
+    def write_varint(value)
+      if value <= 250
+        @output << value
+      else
+        size = [2, 4, 8, 16].find { |n| value < 1 << (8 * n) }
+        @output << { 2 => 251, 4 => 252, 8 => 253, 16 => 254 }[size]
+        write_fixint(value, size)
+      end
+    end
+  end
+
+  # See `Serializer` for the meaning of the options. Reading more than `limit` bytes fails,
+  # as with the option `with_limit` of the Rust crate.
+  class Deserializer < Serde::BinaryDeserializer
+    def initialize(input, varint_encoding: false, big_endian: false, limit: Float::INFINITY)
+      super(input, Float::INFINITY)
+      @varint_encoding = varint_encoding
+      @big_endian = big_endian
+      @limit = limit
+    end
+
+    def deserialize_f32
+      read(4).unpack1(@big_endian ? 'g' : 'e')
+    end
+
+    def deserialize_f64
+      read(8).unpack1(@big_endian ? 'G' : 'E')
+    end
+
+    def deserialize_len
+      value = read_integer(8, false)
+      raise Serde::DeserializationError, 'Incorrect length value' if value > MAX_LENGTH
+
+      value
+    end
+
+    def deserialize_variant_index
+      read_integer(4, false)
+    end
+
+    def check_that_key_slices_are_increasing(_key1, _key2)
+      # Not required by the format.
+    end
+
+    protected
+
+    def read(length)
+      raise Serde::DeserializationError, 'Exceeded the size limit of the encoding' if length > @limit - @offset
+
+      super
+    end
+
+    def read_integer(size, signed)
+      return read_fixint(size, signed) unless @varint_encoding && size > 1
+
+      value = read_varint(size)
+      # Undo the "zigzag" encoding.
+      signed ? (value >> 1) ^ -(value & 1) : value
+    end
+
+    def read_fixint(size, signed)
+      bytes = read(size).bytes
+      bytes.reverse! if @big_endian
+      value = 0
+      bytes.each_with_index { |byte, i| value |= byte << (8 * i) }
+      value -= 1 << (8 * size) if signed && value >= 1 << (8 * size - 1)
+      value
+    end
+
+    # Read a varint-encoded value of at most `size` bytes.
+    def read_varint(size)
+      tag = read_byte
+      value =
+        case tag
+        when 0..250 then tag
+        when 251 then read_fixint(2, false)
+        when 252 then read_fixint(4, false)
+        when 253 then read_fixint(8, false)
+        when 254
+          raise Serde::DeserializationError, 'Invalid tag for varint-encoded value' if size < 16
+
+          read_fixint(16, false)
+        else raise Serde::DeserializationError, 'Invalid tag for varint-encoded value'
+        end
+      raise Serde::DeserializationError, 'Overflow while parsing varint-encoded value' if value >= 1 << (8 * size)
+
+      value
+    end
+  end
+end
@@ -0,0 +1,268 @@
+# frozen_string_literal: true
+
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+# Common definitions of the Bincode and BCS runtimes.
+#
+# Values are mapped to Ruby as follows: unit values are `nil`, integers of all sizes are
+# `Integer`, floats are `Float`, strings are UTF-8 `String`s, bytes are binary `String`s,
+# options are `nil` or their content, sequences, tuples and fixed-size arrays are `Array`s,
+# and maps are `Hash`es.
+module Serde
+  class SerializationError < StandardError; end
+
+  class DeserializationError < StandardError; end
+
+  # Serializer writing little-endian integers into a binary string.
+  # Subclasses define `serialize_len`, `serialize_variant_index`, `serialize_f32`,
+  # `serialize_f64`, and `sort_map_entries`.
+  class BinarySerializer
+    def initialize(max_container_depth)
+      @output = String.new(encoding: Encoding::BINARY)
+      @container_depth_budget = max_container_depth
+    end
+
+    def increase_container_depth
+      raise SerializationError, 'Exceeded maximum container depth' if @container_depth_budget.zero?
+
+      @container_depth_budget -= 1
+    end
+
+    def decrease_container_depth
+      @container_depth_budget += 1
+    end
+
+    def serialize_str(value)
+      raise SerializationError, 'Invalid value for a string' unless value.is_a?(String)
+
+      content = value.encode(Encoding::UTF_8)
+      raise SerializationError, 'Incorrect UTF8 string' unless content.valid_encoding?
+
+      serialize_bytes(content)
+    rescue EncodingError
+      raise SerializationError, 'Incorrect UTF8 string'
+    end
+
+    def serialize_bytes(value)
+      raise SerializationError, 'Invalid value for bytes' unless value.is_a?(String)
+
+      serialize_len(value.bytesize)
+      @output << value.b
+    end
+
+    def serialize_bool(value)
+      raise SerializationError, 'Invalid value for a boolean' unless [true, false].include?(value)
+
+      @output << (value ? 1 : 0)
+    end
+
+    def serialize_unit(_value); end
+
+    def serialize_char(_value)
+      raise SerializationError, 'Not implemented: serialize_char'
+    end
+
+    def serialize_u8(value)
+      write_integer(value, 1, false)
+    end
+
+    def serialize_u16(value)
+      write_integer(value, 2, false)
+    end
+
+    def serialize_u32(value)
+      write_integer(value, 4, false)
+    end
+
+    def serialize_u64(value)
+      write_integer(value, 8, false)
+    end
+
+    def serialize_u128(value)
+      write_integer(value, 16, false)
+    end
+
+    def serialize_i8(value)
+      write_integer(value, 1, true)
+    end
+
+    def serialize_i16(value)
+      write_integer(value, 2, true)
+    end
+
+    def serialize_i32(value)
+      write_integer(value, 4, true)
+    end
+
+    def serialize_i64(value)
+      write_integer(value, 8, true)
+    end
+
+    def serialize_i128(value)
+      write_integer(value, 16, true)
+    end
+
+    def serialize_option_tag(value)
+      @output << (value ? 1 : 0)
+    end
+
+    def buffer_offset
+      @output.bytesize
+    end
+
+    # The serialized bytes, as a binary string.
+    def bytes
+      @output.dup
+    end
+
+    protected
+
+    # Write the `size` lowest bytes of the two's complement of `value` in little-endian order,
+    # after checking that `value` fits in an integer of that size.
+    def write_integer(value, size, signed)
+      check_integer(value, size, signed)
+      size.times { |i| @output << ((value >> (8 * i)) & 0xFF) }
+    end
+
+    def check_integer(value, size, signed)
+      bits = 8 * size
+      min, max = signed ? [-(1 << (bits - 1)), (1 << (bits - 1)) - 1] : [0, (1 << bits) - 1]
+      return if value.is_a?(Integer) && value >= min && value <= max
+
+      raise SerializationError, "Invalid value for a #{signed ? 'signed' : 'unsigned'} #{bits}-bit integer"
+    end
+
+    def check_float(value)
+      raise SerializationError, 'Invalid value for a float' unless value.is_a?(Float)
+    end
+  end
+
+  # Deserializer reading little-endian integers from a binary string.
+  # Subclasses define `deserialize_len`, `deserialize_variant_index`, `deserialize_f32`,
+  # `deserialize_f64`, and `check_that_key_slices_are_increasing`.
+  class BinaryDeserializer
+    INPUT_NOT_LARGE_ENOUGH = 'Input is not large enough'
+
+    def initialize(input, max_container_depth)
+      raise ArgumentError, 'Input must be a string of bytes' unless input.is_a?(String)
+
+      @input = input.b
+      @offset = 0
+      @container_depth_budget = max_container_depth
+    end
+
+    def increase_container_depth
+      raise DeserializationError, 'Exceeded maximum container depth' if @container_depth_budget.zero?
+
+      @container_depth_budget -= 1
+    end
+
+    def decrease_container_depth
+      @container_depth_budget += 1
+    end
+
+    def deserialize_str
+      content = read(deserialize_len).force_encoding(Encoding::UTF_8)
+      raise DeserializationError, 'Incorrect UTF8 string' unless content.valid_encoding?
+
+      content
+    end
+
+    def deserialize_bytes
+      read(deserialize_len)
+    end
+
+    def deserialize_bool
+      case read_byte
+      when 0 then false
+      when 1 then true
+      else raise DeserializationError, 'Incorrect boolean value'
+      end
+    end
+
+    def deserialize_unit
+      nil
+    end
+
+    def deserialize_char
+      raise DeserializationError, 'Not implemented: deserialize_char'
+    end
+
+    def deserialize_u8
+      read_integer(1, false)
+    end
+
+    def deserialize_u16
+      read_integer(2, false)
+    end
+
+    def deserialize_u32
+      read_integer(4, false)
+    end
+
+    def deserialize_u64
+      read_integer(8, false)
+    end
+
+    def deserialize_u128
+      read_integer(16, false)
+    end
+
+    def deserialize_i8
+      read_integer(1, true)
+    end
+
+    def deserialize_i16
+      read_integer(2, true)
+    end
+
+    def deserialize_i32
+      read_integer(4, true)
+    end
+
+    def deserialize_i64
+      read_integer(8, true)
+    end
+
+    def deserialize_i128
+      read_integer(16, true)
+    end
+
+    def deserialize_option_tag
+      deserialize_bool
+    end
+
+    def buffer_offset
+      @offset
+    end
+
+    protected
+
+    # Read `length` bytes, failing before any allocation if the input is too short.
+    def read(length)
+      raise DeserializationError, INPUT_NOT_LARGE_ENOUGH if length > @input.bytesize - @offset
+
+      content = @input.byteslice(@offset, length)
+      @offset += length
+      content
+    end
+
+    def read_byte
+      read(1).getbyte(0)
+    end
+
+    # Read a little-endian integer of `size` bytes, in two's complement if `signed`.
+    def read_integer(size, signed)
+      value = 0
+      read(size).each_byte.with_index { |byte, i| value |= byte << (8 * i) }
+      value -= 1 << (8 * size) if signed && value >= 1 << (8 * size - 1)
+      value
+    end
+
+    # The bytes of the input within the given range of offsets.
+    def input_slice(range)
+      @input.byteslice(range)
+    end
+  end
+end
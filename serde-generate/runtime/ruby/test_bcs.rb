@@ -0,0 +1,90 @@
+# frozen_string_literal: true
+
+# Copyright (c) Facebook, Inc. and its affiliates
+# SPDX-License-Identifier: MIT OR Apache-2.0
+
+require 'minitest/autorun'
+require_relative 'bcs'
+
+class BcsTest < Minitest::Test
+  def bytes(*values)
+    values.pack('C*')
+  end
+
+  def repeat(value, count)
+    ([value] * count).pack('C*')
+  end
+
+  def test_serialize_u128
+    serializer = Bcs::Serializer.new
+    serializer.serialize_u128((1 << 128) - 1)
+    assert_equal repeat(0xFF, 16), serializer.bytes
+
+    serializer = Bcs::Serializer.new
+    serializer.serialize_u128(1)
+    assert_equal bytes(1) + repeat(0, 15), serializer.bytes
+
+    assert_raises(Serde::SerializationError) { serializer.serialize_u128(-1) }
+    assert_raises(Serde::SerializationError) { serializer.serialize_u128(1 << 128) }
+  end
+
+  def test_serialize_i128
+    serializer = Bcs::Serializer.new
+    serializer.serialize_i128(-1)
+    assert_equal repeat(0xFF, 16), serializer.bytes
+
+    serializer = Bcs::Serializer.new
+    serializer.serialize_i128(-(1 << 127))
+    assert_equal repeat(0, 15) + bytes(0x80), serializer.bytes
+
+    assert_raises(Serde::SerializationError) { serializer.serialize_i128(1 << 127) }
+    assert_raises(Serde::SerializationError) { serializer.serialize_i128(-(1 << 127) - 1) }
+  end
+
+  def test_deserialize_i128
+    deserializer = Bcs::Deserializer.new(repeat(0xFF, 32))
+    assert_equal(-1, deserializer.deserialize_i128)
+    assert_equal((1 << 128) - 1, deserializer.deserialize_u128)
+    assert_raises(Serde::DeserializationError) { deserializer.deserialize_u8 }
+  end
+
+  def test_uleb128
+    serializer = Bcs::Serializer.new
+    serializer.serialize_len(300)
+    assert_equal bytes(0xAC, 0x02), serializer.bytes
+    assert_equal 300, Bcs::Deserializer.new(bytes(0xAC, 0x02)).deserialize_len
+    # Non-canonical encodings are rejected.
+    assert_raises(Serde::DeserializationError) { Bcs::Deserializer.new(bytes(0x80, 0x00)).deserialize_len }
+    # So are lengths larger than `MAX_LENGTH`.
+    assert_raises(Serde::DeserializationError) do
+      Bcs::Deserializer.new(bytes(0xFF, 0xFF, 0xFF, 0xFF, 0x08)).deserialize_len
+    end
+  end
+
+  def test_strings
+    serializer = Bcs::Serializer.new
+    serializer.serialize_str('é')
+    assert_equal bytes(2, 0xC3, 0xA9), serializer.bytes
+    assert_equal 'é', Bcs::Deserializer.new(serializer.bytes).deserialize_str
+    assert_raises(Serde::SerializationError) { serializer.serialize_str(bytes(0xFF)) }
+    assert_raises(Serde::DeserializationError) { Bcs::Deserializer.new(bytes(1, 0xFF)).deserialize_str }
+  end
+
+  def test_serializer_slice_ordering
+    serializer = Bcs::Serializer.new
+    serializer.serialize_u8(0xFF)
+    serializer.serialize_u32(1)
+    serializer.serialize_u32(1)
+    serializer.serialize_u32(2)
+    assert_equal bytes(0xFF, 1, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0), serializer.bytes
+
+    serializer.sort_map_entries([1, 2, 4, 7, 8, 9])
+    assert_equal bytes(0xFF, 0, 0, 0, 0, 0, 1, 0, 1, 2, 0, 0, 0), serializer.bytes
+  end
+
+  def test_container_depth
+    serializer = Bcs::Serializer.new
+    Bcs::MAX_CONTAINER_DEPTH.times { serializer.increase_container_depth }
+    assert_raises(Serde::SerializationError) { serializer.increase_container_depth }
+  end
+end
@@ -13,7 +13,7 @@ public class BinaryDeserializer: Deserializer {
         containerDepthBudget = maxContainerDepth
     }
 
-    private func readBytes(count: Int) throws -> [UInt8] {
+    func readBytes(count: Int) throws -> [UInt8] {
         let newLocation = location + count
         if newLocation > input.count {
             throw DeserializationError.invalidInput(issue: "Input is too small")
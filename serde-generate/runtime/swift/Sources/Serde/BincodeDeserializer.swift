@@ -4,29 +4,144 @@ import Foundation
 
 public class BincodeDeserializer: BinaryDeserializer {
     public let MAX_LENGTH: Int = 1 << 31 - 1
+    let varintEncoding: Bool
+    let bigEndian: Bool
+    let limit: Int
 
-    public init(input: [UInt8]) {
+    // Reading more than `limit` bytes fails, as with the option `with_limit` of the Rust crate.
+    public init(input: [UInt8], varintEncoding: Bool = false, bigEndian: Bool = false, limit: Int = Int.max) {
+        self.varintEncoding = varintEncoding
+        self.bigEndian = bigEndian
+        self.limit = limit
         super.init(input: input, maxContainerDepth: Int.max)
     }
 
+    override func readBytes(count: Int) throws -> [UInt8] {
+        if count > limit - get_buffer_offset() {
+            throw DeserializationError.invalidInput(issue: "Exceeded the size limit of the encoding")
+        }
+        return try super.readBytes(count: count)
+    }
+
+    private func read_fixint(size: Int) throws -> UInt64 {
+        var value: UInt64 = 0
+        for i in 0 ..< size {
+            let byte = try deserialize_u8()
+            let shift = bigEndian ? 8 * (size - 1 - i) : 8 * i
+            value |= UInt64(byte) << shift
+        }
+        return value
+    }
+
+    private func read_fixint128() throws -> (UInt64, UInt64) {
+        let first = try read_fixint(size: 8)
+        let second = try read_fixint(size: 8)
+        return bigEndian ? (first, second) : (second, first)
+    }
+
+    // Read a varint-encoded value of at most `bits` bits.
+    private func deserialize_varint(bits: Int) throws -> (UInt64, UInt64) {
+        let tag = try deserialize_u8()
+        var high: UInt64 = 0
+        var low: UInt64
+        switch tag {
+        case 0 ... BINCODE_SINGLE_BYTE_MAX:
+            low = UInt64(tag)
+        case BINCODE_U16_TAG:
+            low = try read_fixint(size: 2)
+        case BINCODE_U32_TAG:
+            low = try read_fixint(size: 4)
+        case BINCODE_U64_TAG:
+            low = try read_fixint(size: 8)
+        case BINCODE_U128_TAG where bits == 128:
+            (high, low) = try read_fixint128()
+        default:
+            throw DeserializationError.invalidInput(issue: "Invalid tag for varint-encoded value")
+        }
+        if bits < 64, (low >> bits) != 0 {
+            throw DeserializationError.invalidInput(issue: "Overflow while parsing varint-encoded value")
+        }
+        return (high, low)
+    }
+
+    private func deserialize_unsigned(size: Int) throws -> UInt64 {
+        if varintEncoding {
+            let (_, value) = try deserialize_varint(bits: 8 * size)
+            return value
+        }
+        return try read_fixint(size: size)
+    }
+
     override public func deserialize_len() throws -> Int {
-        let value = try deserialize_i64()
-        if value < 0 || value > MAX_LENGTH {
+        let value = try deserialize_u64()
+        if value > MAX_LENGTH {
             throw DeserializationError.invalidInput(issue: "Incorrect length value")
         }
         return Int(value)
     }
 
+    // Floating-point numbers are never varint-encoded.
     override public func deserialize_f32() throws -> Float {
-        let num = try deserialize_u32()
-        return Float(bitPattern: num)
+        let num = try read_fixint(size: 4)
+        return Float(bitPattern: UInt32(num))
     }
 
     override public func deserialize_f64() throws -> Double {
-        let num = try deserialize_u64()
+        let num = try read_fixint(size: 8)
         return Double(bitPattern: num)
     }
 
+    override public func deserialize_u16() throws -> UInt16 {
+        return UInt16(try deserialize_unsigned(size: 2))
+    }
+
+    override public func deserialize_u32() throws -> UInt32 {
+        return UInt32(try deserialize_unsigned(size: 4))
+    }
+
+    override public func deserialize_u64() throws -> UInt64 {
+        return try deserialize_unsigned(size: 8)
+    }
+
+    override public func deserialize_u128() throws -> UInt128 {
+        let (high, low) = try varintEncoding ? deserialize_varint(bits: 128) : read_fixint128()
+        return UInt128(high: high, low: low)
+    }
+
+    override public func deserialize_i16() throws -> Int16 {
+        let value = try deserialize_unsigned(size: 2)
+        if varintEncoding {
+            return Int16(value >> 1) ^ -Int16(value & 1)
+        }
+        return Int16(bitPattern: UInt16(value))
+    }
+
+    override public func deserialize_i32() throws -> Int32 {
+        let value = try deserialize_unsigned(size: 4)
+        if varintEncoding {
+            return Int32(value >> 1) ^ -Int32(value & 1)
+        }
+        return Int32(bitPattern: UInt32(value))
+    }
+
+    override public func deserialize_i64() throws -> Int64 {
+        let value = try deserialize_unsigned(size: 8)
+        if varintEncoding {
+            return Int64(value >> 1) ^ -Int64(value & 1)
+        }
+        return Int64(bitPattern: value)
+    }
+
+    override public func deserialize_i128() throws -> Int128 {
+        if !varintEncoding {
+            let (high, low) = try read_fixint128()
+            return Int128(high: Int64(bitPattern: high), low: low)
+        }
+        let (high, low) = try deserialize_varint(bits: 128)
+        let sign = 0 &- (low & 1)
+        return Int128(high: Int64(bitPattern: (high >> 1) ^ sign), low: ((low >> 1) | (high << 63)) ^ sign)
+    }
+
     override public func deserialize_variant_index() throws -> UInt32 {
         return try deserialize_u32()
     }
@@ -2,13 +2,67 @@
 
 import Foundation
 
+// Tags used by the varint encoding of integers.
+let BINCODE_SINGLE_BYTE_MAX: UInt8 = 250
+let BINCODE_U16_TAG: UInt8 = 251
+let BINCODE_U32_TAG: UInt8 = 252
+let BINCODE_U64_TAG: UInt8 = 253
+let BINCODE_U128_TAG: UInt8 = 254
+
 public class BincodeSerializer: BinarySerializer {
     public let MAX_LENGTH: Int = 1 << 31 - 1
+    let varintEncoding: Bool
+    let bigEndian: Bool
 
-    public init() {
+    public init(varintEncoding: Bool = false, bigEndian: Bool = false) {
+        self.varintEncoding = varintEncoding
+        self.bigEndian = bigEndian
         super.init(maxContainerDepth: Int.max)
     }
 
+    private func write_fixint(value: UInt64, size: Int) {
+        for i in 0 ..< size {
+            let shift = bigEndian ? 8 * (size - 1 - i) : 8 * i
+            writeByte(UInt8(truncatingIfNeeded: value >> shift))
+        }
+    }
+
+    private func write_fixint(high: UInt64, low: UInt64) {
+        if bigEndian {
+            write_fixint(value: high, size: 8)
+            write_fixint(value: low, size: 8)
+        } else {
+            write_fixint(value: low, size: 8)
+            write_fixint(value: high, size: 8)
+        }
+    }
+
+    private func serialize_varint(high: UInt64, low: UInt64) {
+        if high != 0 {
+            writeByte(BINCODE_U128_TAG)
+            write_fixint(high: high, low: low)
+        } else if low <= UInt64(BINCODE_SINGLE_BYTE_MAX) {
+            writeByte(UInt8(low))
+        } else if low <= UInt64(UInt16.max) {
+            writeByte(BINCODE_U16_TAG)
+            write_fixint(value: low, size: 2)
+        } else if low <= UInt64(UInt32.max) {
+            writeByte(BINCODE_U32_TAG)
+            write_fixint(value: low, size: 4)
+        } else {
+            writeByte(BINCODE_U64_TAG)
+            write_fixint(value: low, size: 8)
+        }
+    }
+
+    private func serialize_unsigned(value: UInt64, size: Int) {
+        if varintEncoding {
+            serialize_varint(high: 0, low: value)
+        } else {
+            write_fixint(value: value, size: size)
+        }
+    }
+
     override public func serialize_len(value: Int) throws {
         if value < 0 || value > MAX_LENGTH {
             throw SerializationError.invalidValue(issue: "Invalid length value")
@@ -16,12 +70,60 @@ public class BincodeSerializer: BinarySerializer {
         try serialize_u64(value: UInt64(value))
     }
 
+    // Floating-point numbers are never varint-encoded.
     override public func serialize_f32(value: Float) throws {
-        try serialize_u32(value: value.bitPattern)
+        write_fixint(value: UInt64(value.bitPattern), size: 4)
     }
 
     override public func serialize_f64(value: Double) throws {
-        try serialize_u64(value: value.bitPattern)
+        write_fixint(value: value.bitPattern, size: 8)
+    }
+
+    override public func serialize_u16(value: UInt16) throws {
+        serialize_unsigned(value: UInt64(value), size: 2)
+    }
+
+    override public func serialize_u32(value: UInt32) throws {
+        serialize_unsigned(value: UInt64(value), size: 4)
+    }
+
+    override public func serialize_u64(value: UInt64) throws {
+        serialize_unsigned(value: value, size: 8)
+    }
+
+    override public func serialize_u128(value: UInt128) throws {
+        if varintEncoding {
+            serialize_varint(high: value.high, low: value.low)
+        } else {
+            write_fixint(high: value.high, low: value.low)
+        }
+    }
+
+    // Signed integers use a "zigzag" encoding before the varint encoding.
+    override public func serialize_i16(value: Int16) throws {
+        let bits = varintEncoding ? (value << 1) ^ (value >> 15) : value
+        serialize_unsigned(value: UInt64(UInt16(bitPattern: bits)), size: 2)
+    }
+
+    override public func serialize_i32(value: Int32) throws {
+        let bits = varintEncoding ? (value << 1) ^ (value >> 31) : value
+        serialize_unsigned(value: UInt64(UInt32(bitPattern: bits)), size: 4)
+    }
+
+    override public func serialize_i64(value: Int64) throws {
+        let bits = varintEncoding ? (value << 1) ^ (value >> 63) : value
+        serialize_unsigned(value: UInt64(bitPattern: bits), size: 8)
+    }
+
+    override public func serialize_i128(value: Int128) throws {
+        if varintEncoding {
+            let sign = UInt64(bitPattern: value.high >> 63)
+            let high = (UInt64(bitPattern: value.high) << 1) | (value.low >> 63)
+            let low = value.low << 1
+            serialize_varint(high: high ^ sign, low: low ^ sign)
+        } else {
+            write_fixint(high: UInt64(bitPattern: value.high), low: value.low)
+        }
     }
 
     override public func serialize_variant_index(value: UInt32) throws {
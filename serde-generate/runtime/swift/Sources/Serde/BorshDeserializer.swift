@@ -0,0 +1,119 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+public class BorshDeserializer: BinaryDeserializer {
+    public let MAX_LENGTH: Int = 1 << 31 - 1
+
+    var sortKeys = BorshSortKeys()
+
+    public init(input: [UInt8]) {
+        super.init(input: input, maxContainerDepth: Int.max)
+    }
+
+    override public func deserialize_str() throws -> String {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_str()
+        sortKeys.recordStr(bytes: input, start: start, end: get_buffer_offset())
+        return value
+    }
+
+    override public func deserialize_u16() throws -> UInt16 {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_u16()
+        sortKeys.recordInteger(bytes: input, start: start, end: get_buffer_offset(), signed: false)
+        return value
+    }
+
+    override public func deserialize_u32() throws -> UInt32 {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_u32()
+        sortKeys.recordInteger(bytes: input, start: start, end: get_buffer_offset(), signed: false)
+        return value
+    }
+
+    override public func deserialize_u64() throws -> UInt64 {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_u64()
+        sortKeys.recordInteger(bytes: input, start: start, end: get_buffer_offset(), signed: false)
+        return value
+    }
+
+    override public func deserialize_u128() throws -> UInt128 {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_u128()
+        sortKeys.recordInteger(bytes: input, start: start, end: get_buffer_offset(), signed: false)
+        return value
+    }
+
+    override public func deserialize_i8() throws -> Int8 {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_i8()
+        sortKeys.recordInteger(bytes: input, start: start, end: get_buffer_offset(), signed: true)
+        return value
+    }
+
+    override public func deserialize_i16() throws -> Int16 {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_i16()
+        sortKeys.recordInteger(bytes: input, start: start, end: get_buffer_offset(), signed: true)
+        return value
+    }
+
+    override public func deserialize_i32() throws -> Int32 {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_i32()
+        sortKeys.recordInteger(bytes: input, start: start, end: get_buffer_offset(), signed: true)
+        return value
+    }
+
+    override public func deserialize_i64() throws -> Int64 {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_i64()
+        sortKeys.recordInteger(bytes: input, start: start, end: get_buffer_offset(), signed: true)
+        return value
+    }
+
+    override public func deserialize_i128() throws -> Int128 {
+        let start = get_buffer_offset()
+        let value = try super.deserialize_i128()
+        sortKeys.recordInteger(bytes: input, start: start, end: get_buffer_offset(), signed: true)
+        return value
+    }
+
+    override public func deserialize_f32() throws -> Float {
+        let value = try Float(bitPattern: deserialize_u32())
+        if value.isNaN {
+            throw DeserializationError.invalidInput(issue: "NaN values are not supported by Borsh")
+        }
+        return value
+    }
+
+    override public func deserialize_f64() throws -> Double {
+        let value = try Double(bitPattern: deserialize_u64())
+        if value.isNaN {
+            throw DeserializationError.invalidInput(issue: "NaN values are not supported by Borsh")
+        }
+        return value
+    }
+
+    override public func deserialize_len() throws -> Int {
+        let value = try deserialize_u32()
+        if value > MAX_LENGTH {
+            throw DeserializationError.invalidInput(issue: "Overflow while parsing length value")
+        }
+        return Int(value)
+    }
+
+    override public func deserialize_variant_index() throws -> UInt32 {
+        return try UInt32(deserialize_u8())
+    }
+
+    override public func check_that_key_slices_are_increasing(key1: Slice, key2: Slice) throws {
+        let sortKey1 = sortKeys.sortKey(bytes: input, slice: key1)
+        let sortKey2 = sortKeys.sortKey(bytes: input, slice: key2)
+        guard sortKey1.lexicographicallyPrecedes(sortKey2) else {
+            throw DeserializationError.invalidInput(issue: "Invalid ordering of keys")
+        }
+    }
+}
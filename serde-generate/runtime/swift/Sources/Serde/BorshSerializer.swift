@@ -0,0 +1,127 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+public class BorshSerializer: BinarySerializer {
+    public let MAX_LENGTH: Int = 1 << 31 - 1
+
+    var sortKeys = BorshSortKeys()
+
+    public init() {
+        super.init(maxContainerDepth: Int.max)
+    }
+
+    override public func serialize_str(value: String) throws {
+        let start = output.count
+        try super.serialize_str(value: value)
+        sortKeys.recordStr(bytes: output, start: start, end: output.count)
+    }
+
+    override public func serialize_u16(value: UInt16) throws {
+        let start = output.count
+        try super.serialize_u16(value: value)
+        sortKeys.recordInteger(bytes: output, start: start, end: output.count, signed: false)
+    }
+
+    override public func serialize_u32(value: UInt32) throws {
+        let start = output.count
+        try super.serialize_u32(value: value)
+        sortKeys.recordInteger(bytes: output, start: start, end: output.count, signed: false)
+    }
+
+    override public func serialize_u64(value: UInt64) throws {
+        let start = output.count
+        try super.serialize_u64(value: value)
+        sortKeys.recordInteger(bytes: output, start: start, end: output.count, signed: false)
+    }
+
+    override public func serialize_u128(value: UInt128) throws {
+        let start = output.count
+        try super.serialize_u128(value: value)
+        sortKeys.recordInteger(bytes: output, start: start, end: output.count, signed: false)
+    }
+
+    override public func serialize_i8(value: Int8) throws {
+        let start = output.count
+        try super.serialize_i8(value: value)
+        sortKeys.recordInteger(bytes: output, start: start, end: output.count, signed: true)
+    }
+
+    override public func serialize_i16(value: Int16) throws {
+        let start = output.count
+        try super.serialize_i16(value: value)
+        sortKeys.recordInteger(bytes: output, start: start, end: output.count, signed: true)
+    }
+
+    override public func serialize_i32(value: Int32) throws {
+        let start = output.count
+        try super.serialize_i32(value: value)
+        sortKeys.recordInteger(bytes: output, start: start, end: output.count, signed: true)
+    }
+
+    override public func serialize_i64(value: Int64) throws {
+        let start = output.count
+        try super.serialize_i64(value: value)
+        sortKeys.recordInteger(bytes: output, start: start, end: output.count, signed: true)
+    }
+
+    override public func serialize_i128(value: Int128) throws {
+        let start = output.count
+        try super.serialize_i128(value: value)
+        sortKeys.recordInteger(bytes: output, start: start, end: output.count, signed: true)
+    }
+
+    override public func serialize_f32(value: Float) throws {
+        if value.isNaN {
+            throw SerializationError.invalidValue(issue: "NaN values are not supported by Borsh")
+        }
+        try serialize_u32(value: value.bitPattern)
+    }
+
+    override public func serialize_f64(value: Double) throws {
+        if value.isNaN {
+            throw SerializationError.invalidValue(issue: "NaN values are not supported by Borsh")
+        }
+        try serialize_u64(value: value.bitPattern)
+    }
+
+    override public func serialize_len(value: Int) throws {
+        if value < 0 || value > MAX_LENGTH {
+            throw SerializationError.invalidValue(issue: "Invalid length value")
+        }
+        try serialize_u32(value: UInt32(value))
+    }
+
+    override public func serialize_variant_index(value: UInt32) throws {
+        if value > UInt8.max {
+            throw SerializationError.invalidValue(issue: "Invalid variant index")
+        }
+        try serialize_u8(value: UInt8(value))
+    }
+
+    override public func sort_map_entries(offsets: [Int]) {
+        if offsets.count <= 1 {
+            return
+        }
+        let offset0 = offsets[0]
+        var slices: [Slice] = []
+        slices.reserveCapacity(offsets.count)
+        for i in 0 ..< (offsets.count - 1) {
+            slices.append(Slice(start: offsets[i], end: offsets[i + 1]))
+        }
+        slices.append(Slice(start: offsets[offsets.count - 1], end: output.count))
+        let keys = slices.map { (slice: $0, key: sortKeys.sortKey(bytes: output, slice: $0)) }
+        slices = keys.sorted(by: { $0.key.lexicographicallyPrecedes($1.key) }).map { $0.slice }
+        // Positions in the map are no longer valid. Map keys never contain maps.
+        sortKeys.truncate(start: offset0)
+
+        let content = output
+        var position = offset0
+        for slice in slices {
+            for i in slice.start ..< slice.end {
+                output[position] = content[i]
+                position += 1
+            }
+        }
+    }
+}
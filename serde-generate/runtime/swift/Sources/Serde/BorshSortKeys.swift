@@ -0,0 +1,73 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+// Order-preserving encodings of the integers and strings written or read so far, indexed by
+// their positions in the buffer.
+//
+// Borsh sorts map entries by keys as in Rust. Substituting these encodings in the encoded keys
+// gives byte strings that compare in the same way.
+struct BorshSortKeys {
+    // Ordered by start positions.
+    private var records: [(start: Int, end: Int, key: [UInt8])] = []
+
+    // Little-endian integers compare as in Rust in big-endian order, with the sign bit flipped.
+    mutating func recordInteger(bytes: [UInt8], start: Int, end: Int, signed: Bool) {
+        var key = Array(bytes[start ..< end].reversed())
+        if signed {
+            key[0] ^= 0x80
+        }
+        record(start: start, end: end, key: key)
+    }
+
+    // Strings have their zeros escaped and a terminator added so that prefixes come first.
+    mutating func recordStr(bytes: [UInt8], start: Int, end: Int) {
+        var key: [UInt8] = []
+        for byte in bytes[(start + 4) ..< end] {
+            key.append(byte)
+            if byte == 0 {
+                key.append(0xFF)
+            }
+        }
+        key.append(contentsOf: [0, 0])
+        record(start: start, end: end, key: key)
+    }
+
+    private mutating func record(start: Int, end: Int, key: [UInt8]) {
+        // Values written or read inside the current one are superseded.
+        truncate(start: start)
+        records.append((start: start, end: end, key: key))
+    }
+
+    private func lowerBound(start: Int) -> Int {
+        var low = 0
+        var high = records.count
+        while low < high {
+            let middle = low + (high - low) / 2
+            if records[middle].start < start {
+                low = middle + 1
+            } else {
+                high = middle
+            }
+        }
+        return low
+    }
+
+    mutating func truncate(start: Int) {
+        records.removeSubrange(lowerBound(start: start)...)
+    }
+
+    func sortKey(bytes: [UInt8], slice: Slice) -> [UInt8] {
+        var result: [UInt8] = []
+        var position = slice.start
+        var index = lowerBound(start: slice.start)
+        while index < records.count, records[index].start < slice.end {
+            result.append(contentsOf: bytes[position ..< records[index].start])
+            result.append(contentsOf: records[index].key)
+            position = records[index].end
+            index += 1
+        }
+        result.append(contentsOf: bytes[position ..< slice.end])
+        return result
+    }
+}
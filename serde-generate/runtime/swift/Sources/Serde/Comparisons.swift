@@ -0,0 +1,34 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+// Three-way comparisons following the order of Rust's derived `Ord` instances.
+
+public func compareValues<T: Comparable>(_ a: T, _ b: T) -> Int {
+    return a < b ? -1 : (a == b ? 0 : 1)
+}
+
+public func compareBools(_ a: Bool, _ b: Bool) -> Int {
+    return a == b ? 0 : (a ? 1 : -1)
+}
+
+// Rust compares strings by Unicode scalar values (equivalently, by UTF-8 bytes).
+public func compareStrings(_ a: String, _ b: String) -> Int {
+    return compareSequences(a.unicodeScalars.map { $0.value }, b.unicodeScalars.map { $0.value })
+}
+
+public func compareCharacters(_ a: Character, _ b: Character) -> Int {
+    return compareStrings(String(a), String(b))
+}
+
+public func compareBytes(_ a: [UInt8], _ b: [UInt8]) -> Int {
+    return compareSequences(a, b)
+}
+
+func compareSequences<T: Comparable>(_ a: [T], _ b: [T]) -> Int {
+    for (x, y) in zip(a, b) {
+        let result = compareValues(x, y)
+        if result != 0 { return result }
+    }
+    return compareValues(a.count, b.count)
+}
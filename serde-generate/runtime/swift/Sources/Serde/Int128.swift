@@ -2,7 +2,7 @@
 
 import Foundation
 
-public struct Int128: Hashable {
+public struct Int128: Hashable, Comparable {
     public var high: Int64
     public var low: UInt64
 
@@ -10,4 +10,8 @@ public struct Int128: Hashable {
         self.high = high
         self.low = low
     }
+
+    public static func < (lhs: Int128, rhs: Int128) -> Bool {
+        return (lhs.high, lhs.low) < (rhs.high, rhs.low)
+    }
 }
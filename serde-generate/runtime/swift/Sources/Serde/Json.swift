@@ -0,0 +1,664 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+// The JSON format of the Rust crate `serde_json`. Generated types convert their values from and
+// to trees of JSON values with these helpers, then `parse` and `stringify` deal with the JSON text.
+public enum Json {
+    // Maximal number of nested arrays and objects accepted by `serde_json` (whose recursion limit is 128).
+    public static let MAX_CONTAINER_DEPTH = 127
+
+    public static func parse(input: String) throws -> JsonValue {
+        var parser = Parser(input: Array(input.unicodeScalars))
+        let value = try parser.parseValue(depthBudget: MAX_CONTAINER_DEPTH)
+        parser.skipWhitespace()
+        if parser.position < parser.input.count {
+            throw DeserializationError.invalidInput(issue: "Trailing characters in JSON input")
+        }
+        return value
+    }
+
+    public static func stringify(value: JsonValue) -> String {
+        var output = ""
+        write(output: &output, value: value)
+        return output
+    }
+
+    private static func write(output: inout String, value: JsonValue) {
+        switch value {
+        case .null:
+            output += "null"
+        case let .bool(b):
+            output += b ? "true" : "false"
+        case let .number(literal):
+            output += literal
+        case let .string(s), let .key(s):
+            writeString(output: &output, value: s)
+        case let .array(items):
+            output += "["
+            for (i, item) in items.enumerated() {
+                if i > 0 { output += "," }
+                write(output: &output, value: item)
+            }
+            output += "]"
+        case let .object(obj):
+            output += "{"
+            for i in 0 ..< obj.count {
+                if i > 0 { output += "," }
+                writeString(output: &output, value: obj.keys[i])
+                output += ":"
+                write(output: &output, value: obj.values[i])
+            }
+            output += "}"
+        }
+    }
+
+    private static func writeString(output: inout String, value: String) {
+        output += "\""
+        for c in value.unicodeScalars {
+            switch c {
+            case "\"": output += "\\\""
+            case "\\": output += "\\\\"
+            case "\u{08}": output += "\\b"
+            case "\u{0C}": output += "\\f"
+            case "\n": output += "\\n"
+            case "\r": output += "\\r"
+            case "\t": output += "\\t"
+            default:
+                if c.value < 0x20 {
+                    output += "\\u00" + (c.value < 0x10 ? "0" : "") + String(c.value, radix: 16)
+                } else {
+                    output.unicodeScalars.append(c)
+                }
+            }
+        }
+        output += "\""
+    }
+
+    private struct Parser {
+        let input: [Unicode.Scalar]
+        var position = 0
+
+        init(input: [Unicode.Scalar]) {
+            self.input = input
+        }
+
+        mutating func skipWhitespace() {
+            while position < input.count {
+                let c = input[position]
+                if c != " ", c != "\t", c != "\n", c != "\r" { return }
+                position += 1
+            }
+        }
+
+        func error(_ message: String) -> DeserializationError {
+            return DeserializationError.invalidInput(issue: "\(message) at position \(position)")
+        }
+
+        func peek(_ c: Unicode.Scalar) -> Bool {
+            return position < input.count && input[position] == c
+        }
+
+        mutating func next() throws -> Unicode.Scalar {
+            if position >= input.count {
+                throw error("Unexpected end of JSON input")
+            }
+            position += 1
+            return input[position - 1]
+        }
+
+        mutating func expect(_ word: String) throws {
+            for c in word.unicodeScalars {
+                if !peek(c) {
+                    throw error("Invalid JSON value")
+                }
+                position += 1
+            }
+        }
+
+        mutating func parseValue(depthBudget: Int) throws -> JsonValue {
+            skipWhitespace()
+            if position >= input.count {
+                throw error("Unexpected end of JSON input")
+            }
+            switch input[position] {
+            case "n":
+                try expect("null")
+                return .null
+            case "t":
+                try expect("true")
+                return .bool(true)
+            case "f":
+                try expect("false")
+                return .bool(false)
+            case "\"":
+                return .string(try parseString())
+            case "[":
+                if depthBudget == 0 {
+                    throw error("Exceeded maximum container depth")
+                }
+                position += 1
+                var items: [JsonValue] = []
+                skipWhitespace()
+                if peek("]") {
+                    position += 1
+                    return .array(items)
+                }
+                while true {
+                    items.append(try parseValue(depthBudget: depthBudget - 1))
+                    skipWhitespace()
+                    let d = try next()
+                    if d == "]" { return .array(items) }
+                    if d != "," { throw error("Expected ',' or ']'") }
+                }
+            case "{":
+                if depthBudget == 0 {
+                    throw error("Exceeded maximum container depth")
+                }
+                position += 1
+                var obj = JsonObject()
+                skipWhitespace()
+                if peek("}") {
+                    position += 1
+                    return .object(obj)
+                }
+                while true {
+                    skipWhitespace()
+                    if !peek("\"") { throw error("Expected a string key") }
+                    let key = try parseString()
+                    skipWhitespace()
+                    if try next() != ":" { throw error("Expected ':'") }
+                    obj.put(key: key, value: try parseValue(depthBudget: depthBudget - 1))
+                    skipWhitespace()
+                    let d = try next()
+                    if d == "}" { return .object(obj) }
+                    if d != "," { throw error("Expected ',' or '}'") }
+                }
+            case "-", "0" ... "9":
+                return try parseNumber()
+            default:
+                throw error("Invalid JSON value")
+            }
+        }
+
+        mutating func parseNumber() throws -> JsonValue {
+            let start = position
+            while position < input.count, "-+.eE0123456789".unicodeScalars.contains(input[position]) {
+                position += 1
+            }
+            var literal = ""
+            literal.unicodeScalars.append(contentsOf: input[start ..< position])
+            if !Json.isNumber(literal) {
+                throw error("Invalid number")
+            }
+            return .number(literal)
+        }
+
+        mutating func parseHex() throws -> UInt32 {
+            if position + 4 > input.count {
+                throw error("Invalid escape in string")
+            }
+            var digits = ""
+            digits.unicodeScalars.append(contentsOf: input[position ..< position + 4])
+            guard digits.unicodeScalars.allSatisfy({ $0.properties.isASCIIHexDigit }),
+                  let result = UInt32(digits, radix: 16)
+            else {
+                throw error("Invalid escape in string")
+            }
+            position += 4
+            return result
+        }
+
+        mutating func parseString() throws -> String {
+            position += 1
+            var result = ""
+            while true {
+                let c = try next()
+                if c == "\"" { return result }
+                if c.value < 0x20 { throw error("Control character in string") }
+                if c != "\\" {
+                    result.unicodeScalars.append(c)
+                    continue
+                }
+                let escape = try next()
+                switch escape {
+                case "\"", "\\", "/":
+                    result.unicodeScalars.append(escape)
+                case "b": result += "\u{08}"
+                case "f": result += "\u{0C}"
+                case "n": result += "\n"
+                case "r": result += "\r"
+                case "t": result += "\t"
+                case "u":
+                    let high = try parseHex()
+                    var code = high
+                    if 0xDC00 ... 0xDFFF ~= high {
+                        throw error("Lone surrogate in string")
+                    }
+                    if 0xD800 ... 0xDBFF ~= high {
+                        if !(peek("\\") && position + 1 < input.count && input[position + 1] == "u") {
+                            throw error("Lone surrogate in string")
+                        }
+                        position += 2
+                        let low = try parseHex()
+                        if !(0xDC00 ... 0xDFFF ~= low) {
+                            throw error("Lone surrogate in string")
+                        }
+                        code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                    }
+                    result.unicodeScalars.append(Unicode.Scalar(code)!)
+                default:
+                    throw error("Invalid escape in string")
+                }
+            }
+        }
+    }
+
+    // Whether `literal` is a JSON number.
+    static func isNumber(_ literal: String) -> Bool {
+        var scalars = literal.unicodeScalars[...]
+        func digits() -> Int {
+            var count = 0
+            while let c = scalars.first, "0" ... "9" ~= c {
+                scalars.removeFirst()
+                count += 1
+            }
+            return count
+        }
+        func accept(_ options: String) -> Bool {
+            if let c = scalars.first, options.unicodeScalars.contains(c) {
+                scalars.removeFirst()
+                return true
+            }
+            return false
+        }
+        _ = accept("-")
+        if accept("0") {
+            if let c = scalars.first, "0" ... "9" ~= c { return false }
+        } else if digits() == 0 {
+            return false
+        }
+        if accept("."), digits() == 0 {
+            return false
+        }
+        if accept("eE") {
+            _ = accept("+-")
+            if digits() == 0 { return false }
+        }
+        return scalars.isEmpty
+    }
+
+    // The sign and the magnitude of an integer literal, or nil if `literal` is not an integer or
+    // does not fit in 128 bits.
+    static func parseInteger(_ literal: String) -> (negative: Bool, magnitude: UInt128)? {
+        var digits = literal.utf8[...]
+        let negative = digits.first == UInt8(ascii: "-")
+        if negative {
+            digits.removeFirst()
+        }
+        if digits.isEmpty || (digits.first == UInt8(ascii: "0") && digits.count > 1) {
+            return nil
+        }
+        var high: UInt64 = 0
+        var low: UInt64 = 0
+        for c in digits {
+            guard UInt8(ascii: "0") ... UInt8(ascii: "9") ~= c else {
+                return nil
+            }
+            let product = low.multipliedFullWidth(by: 10)
+            let (high1, overflow1) = high.multipliedReportingOverflow(by: 10)
+            let (high2, overflow2) = high1.addingReportingOverflow(product.high)
+            let (low1, carry) = product.low.addingReportingOverflow(UInt64(c - UInt8(ascii: "0")))
+            let (high3, overflow3) = high2.addingReportingOverflow(carry ? 1 : 0)
+            if overflow1 || overflow2 || overflow3 {
+                return nil
+            }
+            high = high3
+            low = low1
+        }
+        return (negative, UInt128(high: high, low: low))
+    }
+
+    private static func formatInteger(negative: Bool, magnitude: UInt128) -> String {
+        var high = magnitude.high
+        var low = magnitude.low
+        var digits = ""
+        repeat {
+            let (quotient, remainder) = high.quotientAndRemainder(dividingBy: 10)
+            let division = UInt64(10).dividingFullWidth((high: remainder, low: low))
+            high = quotient
+            low = division.quotient
+            digits.insert(Character(String(division.remainder)), at: digits.startIndex)
+        } while high != 0 || low != 0
+        return negative ? "-" + digits : digits
+    }
+
+    // Format a finite float in the manner of the Rust crate `ryu`, as `serde_json` does: with the
+    // shortest digits that read back as the same value. `shortest` is the description of the
+    // absolute value given by Swift.
+    private static func formatFloat(negative: Bool, shortest: String, isF32: Bool) -> String {
+        let sign = negative ? "-" : ""
+        var mantissa = Substring(shortest)
+        var exponent = 0
+        if let e = mantissa.firstIndex(where: { $0 == "e" || $0 == "E" }) {
+            exponent = Int(mantissa[mantissa.index(after: e)...]) ?? 0
+            mantissa = mantissa[..<e]
+        }
+        let dot = mantissa.firstIndex(of: ".")
+        var digits = String(mantissa.filter { $0 != "." })
+        var point = (dot.map { mantissa.distance(from: mantissa.startIndex, to: $0) } ?? mantissa.count) + exponent
+        let leadingZeros = digits.prefix { $0 == "0" }.count
+        digits = String(digits.dropFirst(leadingZeros))
+        while digits.last == "0" {
+            digits.removeLast()
+        }
+        point -= leadingZeros
+        if digits.isEmpty {
+            return sign + "0.0"
+        }
+
+        let maxPoint = isF32 ? 13 : 16
+        let minPoint = isF32 ? -6 : -5
+        if digits.count <= point, point <= maxPoint {
+            return sign + digits + String(repeating: "0", count: point - digits.count) + ".0"
+        }
+        if 0 < point, point <= maxPoint {
+            return sign + String(digits.prefix(point)) + "." + String(digits.dropFirst(point))
+        }
+        if minPoint < point, point <= 0 {
+            return sign + "0." + String(repeating: "0", count: -point) + digits
+        }
+        let significand = digits.count == 1 ? digits : String(digits.prefix(1)) + "." + String(digits.dropFirst())
+        return sign + significand + "e" + String(point - 1)
+    }
+
+    private static func typeError(_ value: JsonValue, _ expected: String) -> DeserializationError {
+        return DeserializationError.invalidInput(issue: "Invalid type: expected \(expected), found \(value)")
+    }
+
+    private static func readIntegerLiteral(_ value: JsonValue, acceptsNegativeZero: Bool) throws -> String {
+        let literal: String
+        switch value {
+        case let .number(s), let .key(s):
+            literal = s
+        default:
+            throw typeError(value, "an integer")
+        }
+        // Like `serde_json`, read `-0` as a float, except for 128-bit integers.
+        let isInteger = isNumber(literal) && !literal.contains { "." == $0 || "e" == $0 || "E" == $0 }
+        if !isInteger || (literal == "-0" && !acceptsNegativeZero) {
+            throw typeError(value, "an integer")
+        }
+        return literal
+    }
+
+    private static func readInteger<T: FixedWidthInteger>(_ value: JsonValue) throws -> T {
+        let literal = try readIntegerLiteral(value, acceptsNegativeZero: false)
+        guard let result = T(literal) else {
+            throw DeserializationError.invalidInput(issue: "Integer out of range: \(literal)")
+        }
+        return result
+    }
+
+    private static func readNumber(_ value: JsonValue) throws -> Double {
+        let literal: String
+        switch value {
+        case let .number(s):
+            literal = s
+        case let .key(s) where isNumber(s):
+            literal = s
+        default:
+            throw typeError(value, "a number")
+        }
+        guard let result = Double(literal), !result.isInfinite else {
+            throw DeserializationError.invalidInput(issue: "Number out of range: \(literal)")
+        }
+        return result
+    }
+
+    // Serialization helpers
+
+    public static func serialize_unit(value _: Unit) -> JsonValue {
+        return .null
+    }
+
+    public static func serialize_bool(value: Bool) -> JsonValue {
+        return .bool(value)
+    }
+
+    public static func serialize_i8(value: Int8) -> JsonValue {
+        return .number(String(value))
+    }
+
+    public static func serialize_i16(value: Int16) -> JsonValue {
+        return .number(String(value))
+    }
+
+    public static func serialize_i32(value: Int32) -> JsonValue {
+        return .number(String(value))
+    }
+
+    public static func serialize_i64(value: Int64) -> JsonValue {
+        return .number(String(value))
+    }
+
+    public static func serialize_i128(value: Int128) -> JsonValue {
+        let negative = value.high < 0
+        var high = UInt64(bitPattern: value.high)
+        var low = value.low
+        if negative {
+            // Two's complement.
+            low = ~low &+ 1
+            high = ~high &+ (low == 0 ? 1 : 0)
+        }
+        return .number(formatInteger(negative: negative, magnitude: UInt128(high: high, low: low)))
+    }
+
+    public static func serialize_u8(value: UInt8) -> JsonValue {
+        return .number(String(value))
+    }
+
+    public static func serialize_u16(value: UInt16) -> JsonValue {
+        return .number(String(value))
+    }
+
+    public static func serialize_u32(value: UInt32) -> JsonValue {
+        return .number(String(value))
+    }
+
+    public static func serialize_u64(value: UInt64) -> JsonValue {
+        return .number(String(value))
+    }
+
+    public static func serialize_u128(value: UInt128) -> JsonValue {
+        return .number(formatInteger(negative: false, magnitude: value))
+    }
+
+    public static func serialize_f32(value: Float) -> JsonValue {
+        if !value.isFinite {
+            return .null
+        }
+        return .number(formatFloat(negative: value.sign == .minus, shortest: value.magnitude.description, isF32: true))
+    }
+
+    public static func serialize_f64(value: Double) -> JsonValue {
+        if !value.isFinite {
+            return .null
+        }
+        return .number(formatFloat(negative: value.sign == .minus, shortest: value.magnitude.description, isF32: false))
+    }
+
+    public static func serialize_char(value: Character) throws -> JsonValue {
+        // A Rust `char` is a single Unicode scalar, whereas a Swift `Character` may hold several.
+        if value.unicodeScalars.count != 1 {
+            throw SerializationError.invalidValue(issue: "Invalid unicode character: \(value)")
+        }
+        return .string(String(value))
+    }
+
+    public static func serialize_str(value: String) -> JsonValue {
+        return .string(value)
+    }
+
+    public static func serialize_bytes(value: [UInt8]) -> JsonValue {
+        return .array(value.map { serialize_u8(value: $0) })
+    }
+
+    // A variant of an enum that is not a unit variant (unit variants are written as strings).
+    public static func serialize_variant(name: String, value: JsonValue) -> JsonValue {
+        var result = JsonObject()
+        result.put(key: name, value: value)
+        return .object(result)
+    }
+
+    // The key of a map entry, given the JSON value of the key.
+    public static func key(value: JsonValue) throws -> String {
+        switch value {
+        case let .string(s), let .number(s), let .key(s):
+            return s
+        case let .bool(b):
+            return b ? "true" : "false"
+        default:
+            throw SerializationError.invalidValue(issue: "Map keys must be strings or numbers")
+        }
+    }
+
+    // Deserialization helpers
+
+    public static func deserialize_unit(value: JsonValue) throws -> Unit {
+        guard case .null = value else {
+            throw typeError(value, "null")
+        }
+        return Unit()
+    }
+
+    public static func deserialize_bool(value: JsonValue) throws -> Bool {
+        switch value {
+        case let .bool(b):
+            return b
+        case .key("true"):
+            return true
+        case .key("false"):
+            return false
+        default:
+            throw typeError(value, "a boolean")
+        }
+    }
+
+    public static func deserialize_i8(value: JsonValue) throws -> Int8 {
+        return try readInteger(value)
+    }
+
+    public static func deserialize_i16(value: JsonValue) throws -> Int16 {
+        return try readInteger(value)
+    }
+
+    public static func deserialize_i32(value: JsonValue) throws -> Int32 {
+        return try readInteger(value)
+    }
+
+    public static func deserialize_i64(value: JsonValue) throws -> Int64 {
+        return try readInteger(value)
+    }
+
+    public static func deserialize_i128(value: JsonValue) throws -> Int128 {
+        let literal = try readIntegerLiteral(value, acceptsNegativeZero: true)
+        let limit = UInt128(high: 1 << 63, low: 0)
+        guard let integer = parseInteger(literal),
+              integer.magnitude < limit || (integer.negative && integer.magnitude == limit)
+        else {
+            throw DeserializationError.invalidInput(issue: "Integer out of range: \(literal)")
+        }
+        var high = integer.magnitude.high
+        var low = integer.magnitude.low
+        if integer.negative {
+            // Two's complement.
+            low = ~low &+ 1
+            high = ~high &+ (low == 0 ? 1 : 0)
+        }
+        return Int128(high: Int64(bitPattern: high), low: low)
+    }
+
+    public static func deserialize_u8(value: JsonValue) throws -> UInt8 {
+        return try readInteger(value)
+    }
+
+    public static func deserialize_u16(value: JsonValue) throws -> UInt16 {
+        return try readInteger(value)
+    }
+
+    public static func deserialize_u32(value: JsonValue) throws -> UInt32 {
+        return try readInteger(value)
+    }
+
+    public static func deserialize_u64(value: JsonValue) throws -> UInt64 {
+        return try readInteger(value)
+    }
+
+    public static func deserialize_u128(value: JsonValue) throws -> UInt128 {
+        let literal = try readIntegerLiteral(value, acceptsNegativeZero: false)
+        guard let integer = parseInteger(literal), !integer.negative else {
+            throw DeserializationError.invalidInput(issue: "Integer out of range: \(literal)")
+        }
+        return integer.magnitude
+    }
+
+    // Like `serde_json`, read the closest f64 value then convert it to f32.
+    public static func deserialize_f32(value: JsonValue) throws -> Float {
+        return Float(try readNumber(value))
+    }
+
+    public static func deserialize_f64(value: JsonValue) throws -> Double {
+        return try readNumber(value)
+    }
+
+    public static func deserialize_char(value: JsonValue) throws -> Character {
+        let result = try deserialize_str(value: value)
+        guard result.unicodeScalars.count == 1, let scalar = result.unicodeScalars.first else {
+            throw typeError(value, "a character")
+        }
+        return Character(scalar)
+    }
+
+    public static func deserialize_str(value: JsonValue) throws -> String {
+        switch value {
+        case let .string(s), let .key(s):
+            return s
+        default:
+            throw typeError(value, "a string")
+        }
+    }
+
+    public static func deserialize_bytes(value: JsonValue) throws -> [UInt8] {
+        return try deserialize_array(value: value).map { try deserialize_u8(value: $0) }
+    }
+
+    // The items of an array, of the given length if any (e.g. a tuple or a fixed-size array).
+    public static func deserialize_array(value: JsonValue, length: Int? = nil) throws -> [JsonValue] {
+        guard case let .array(items) = value else {
+            throw typeError(value, "an array")
+        }
+        if let length = length, items.count != length {
+            throw DeserializationError.invalidInput(issue: "Invalid length: \(items.count) instead of \(length)")
+        }
+        return items
+    }
+
+    public static func deserialize_object(value: JsonValue) throws -> JsonObject {
+        guard case let .object(obj) = value else {
+            throw typeError(value, "an object")
+        }
+        return obj
+    }
+
+    public static func deserialize_variant(value: JsonValue) throws -> JsonVariant {
+        switch value {
+        case let .string(name), let .key(name):
+            return JsonVariant(name: name, content: nil)
+        case let .object(obj) where obj.count == 1:
+            return JsonVariant(name: obj.keys[0], content: obj.values[0])
+        default:
+            throw typeError(value, "a string or an object with a single entry")
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+// A JSON value. Numbers are kept as written so that integers of any size are read exactly.
+public indirect enum JsonValue: CustomStringConvertible {
+    case null
+    case bool(Bool)
+    case number(String)
+    case string(String)
+    // The key of an entry of a JSON object read as a map. Like `serde_json`, keys may stand for
+    // strings, numbers, booleans, or unit variants of enums.
+    case key(String)
+    case array([JsonValue])
+    case object(JsonObject)
+
+    public var description: String {
+        return Json.stringify(value: self)
+    }
+}
+
+// The entries of a JSON object, in order and possibly with duplicate keys.
+public struct JsonObject {
+    public private(set) var keys: [String] = []
+    public private(set) var values: [JsonValue] = []
+
+    public init() {}
+
+    public var count: Int {
+        return keys.count
+    }
+
+    public mutating func put(key: String, value: JsonValue) {
+        keys.append(key)
+        values.append(value)
+    }
+
+    // Value of the field `name` of a struct.
+    public func field(name: String) throws -> JsonValue {
+        guard let index = try find(name: name) else {
+            throw DeserializationError.invalidInput(issue: "Missing field: \(name)")
+        }
+        return values[index]
+    }
+
+    // Value of the optional field `name` of a struct, or `null` if the field is missing.
+    public func optionalField(name: String) throws -> JsonValue {
+        guard let index = try find(name: name) else {
+            return .null
+        }
+        return values[index]
+    }
+
+    private func find(name: String) throws -> Int? {
+        let index = keys.firstIndex(of: name)
+        if index != nil, keys.lastIndex(of: name) != index {
+            throw DeserializationError.invalidInput(issue: "Duplicate field: \(name)")
+        }
+        return index
+    }
+
+    // Sort the entries of an object written from a map, so that keys come in the order of a
+    // `BTreeMap` in Rust.
+    public mutating func sortKeys(by areInIncreasingOrder: (String, String) -> Bool) {
+        let entries = zip(keys, values).sorted { areInIncreasingOrder($0.0, $1.0) }
+        keys = entries.map { $0.0 }
+        values = entries.map { $0.1 }
+    }
+
+    // Order of strings and chars (by UTF-8 bytes) and of booleans (`false` first).
+    public static func stringOrder(_ a: String, _ b: String) -> Bool {
+        return a.utf8.lexicographicallyPrecedes(b.utf8)
+    }
+
+    // Order of numbers.
+    public static func numberOrder(_ a: String, _ b: String) -> Bool {
+        if let x = Json.parseInteger(a), let y = Json.parseInteger(b) {
+            if x.negative != y.negative {
+                return x.negative
+            }
+            let (high, low) = x.negative ? (y.magnitude, x.magnitude) : (x.magnitude, y.magnitude)
+            return high < low
+        }
+        return (Double(a) ?? 0) < (Double(b) ?? 0)
+    }
+
+    // Order of the unit variants of an enum, given the names of all its variants.
+    public static func variantOrder(names: [String]) -> (String, String) -> Bool {
+        func index(_ name: String) -> Int {
+            return names.firstIndex(of: name) ?? names.count
+        }
+        return { index($0) < index($1) }
+    }
+}
+
+// A variant of an enum, read either from a string (unit variants) or from an object with a
+// single entry.
+public struct JsonVariant {
+    public let name: String
+    let content: JsonValue?
+
+    public init(name: String, content: JsonValue?) {
+        self.name = name
+        self.content = content
+    }
+
+    // Check that the variant has no content, i.e. that it was written as a string or with `null`.
+    public func unit() throws {
+        switch content {
+        case nil, .some(.null):
+            return
+        default:
+            throw DeserializationError.invalidInput(issue: "Unexpected content for unit variant: \(name)")
+        }
+    }
+
+    // Content of a variant that is not a unit variant.
+    public func value() throws -> JsonValue {
+        guard let content = content else {
+            throw DeserializationError.invalidInput(issue: "Missing content for variant: \(name)")
+        }
+        return content
+    }
+}
@@ -0,0 +1,112 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+public class PostcardDeserializer: BinaryDeserializer {
+    public let MAX_LENGTH: Int = 1 << 31 - 1
+
+    public init(input: [UInt8]) {
+        super.init(input: input, maxContainerDepth: Int.max)
+    }
+
+    // Read a varint-encoded value of at most 128 bits.
+    private func deserialize_varint(bits: Int) throws -> (UInt64, UInt64) {
+        var high: UInt64 = 0
+        var low: UInt64 = 0
+        for shift in stride(from: 0, to: bits, by: 7) {
+            let byte = try deserialize_u8()
+            let digit = UInt64(byte & 0x7F)
+            if bits - shift < 7, (digit >> (bits - shift)) != 0 {
+                throw DeserializationError.invalidInput(issue: "Overflow while parsing varint-encoded value")
+            }
+            if shift < 64 {
+                low |= digit << shift
+                if shift > 57 {
+                    high |= digit >> (64 - shift)
+                }
+            } else {
+                high |= digit << (shift - 64)
+            }
+            if byte & 0x80 == 0 {
+                return (high, low)
+            }
+        }
+        throw DeserializationError.invalidInput(issue: "Varint-encoded value is too long")
+    }
+
+    override public func deserialize_len() throws -> Int {
+        let (_, value) = try deserialize_varint(bits: 64)
+        if value > MAX_LENGTH {
+            throw DeserializationError.invalidInput(issue: "Incorrect length value")
+        }
+        return Int(value)
+    }
+
+    override public func deserialize_char() throws -> Character {
+        let value = try deserialize_str()
+        if value.count != 1 {
+            throw DeserializationError.invalidInput(issue: "Expecting a single character")
+        }
+        return value[value.startIndex]
+    }
+
+    override public func deserialize_f32() throws -> Float {
+        let num = try super.deserialize_u32()
+        return Float(bitPattern: num)
+    }
+
+    override public func deserialize_f64() throws -> Double {
+        let num = try super.deserialize_u64()
+        return Double(bitPattern: num)
+    }
+
+    override public func deserialize_u16() throws -> UInt16 {
+        let (_, value) = try deserialize_varint(bits: 16)
+        return UInt16(value)
+    }
+
+    override public func deserialize_u32() throws -> UInt32 {
+        let (_, value) = try deserialize_varint(bits: 32)
+        return UInt32(value)
+    }
+
+    override public func deserialize_u64() throws -> UInt64 {
+        let (_, value) = try deserialize_varint(bits: 64)
+        return value
+    }
+
+    override public func deserialize_u128() throws -> UInt128 {
+        let (high, low) = try deserialize_varint(bits: 128)
+        return UInt128(high: high, low: low)
+    }
+
+    override public func deserialize_i16() throws -> Int16 {
+        let (_, value) = try deserialize_varint(bits: 16)
+        return Int16(value >> 1) ^ -Int16(value & 1)
+    }
+
+    override public func deserialize_i32() throws -> Int32 {
+        let (_, value) = try deserialize_varint(bits: 32)
+        return Int32(value >> 1) ^ -Int32(value & 1)
+    }
+
+    override public func deserialize_i64() throws -> Int64 {
+        let (_, value) = try deserialize_varint(bits: 64)
+        return Int64(value >> 1) ^ -Int64(value & 1)
+    }
+
+    override public func deserialize_i128() throws -> Int128 {
+        let (high, low) = try deserialize_varint(bits: 128)
+        let sign = 0 &- (low & 1)
+        return Int128(high: Int64(bitPattern: (high >> 1) ^ sign), low: ((low >> 1) | (high << 63)) ^ sign)
+    }
+
+    override public func deserialize_variant_index() throws -> UInt32 {
+        let (_, value) = try deserialize_varint(bits: 32)
+        return UInt32(value)
+    }
+
+    override public func check_that_key_slices_are_increasing(key1 _: Slice, key2 _: Slice) throws {
+        // Nothing to do
+    }
+}
@@ -0,0 +1,94 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+public class PostcardSerializer: BinarySerializer {
+    public let MAX_LENGTH: Int = 1 << 31 - 1
+
+    public init() {
+        super.init(maxContainerDepth: Int.max)
+    }
+
+    private func serialize_varint(value: UInt64) {
+        var input = value
+        while input >= 0x80 {
+            writeByte(UInt8(input & 0x7F) | 0x80)
+            input >>= 7
+        }
+        writeByte(UInt8(input))
+    }
+
+    private func serialize_varint(high: UInt64, low: UInt64) {
+        var high = high
+        var low = low
+        while high != 0 || low >= 0x80 {
+            writeByte(UInt8(low & 0x7F) | 0x80)
+            low = (low >> 7) | (high << 57)
+            high >>= 7
+        }
+        writeByte(UInt8(low))
+    }
+
+    override public func serialize_len(value: Int) throws {
+        if value < 0 || value > MAX_LENGTH {
+            throw SerializationError.invalidValue(issue: "Invalid length value")
+        }
+        serialize_varint(value: UInt64(value))
+    }
+
+    override public func serialize_char(value: Character) throws {
+        try serialize_str(value: String(value))
+    }
+
+    override public func serialize_f32(value: Float) throws {
+        try super.serialize_u32(value: value.bitPattern)
+    }
+
+    override public func serialize_f64(value: Double) throws {
+        try super.serialize_u64(value: value.bitPattern)
+    }
+
+    override public func serialize_u16(value: UInt16) throws {
+        serialize_varint(value: UInt64(value))
+    }
+
+    override public func serialize_u32(value: UInt32) throws {
+        serialize_varint(value: UInt64(value))
+    }
+
+    override public func serialize_u64(value: UInt64) throws {
+        serialize_varint(value: value)
+    }
+
+    override public func serialize_u128(value: UInt128) throws {
+        serialize_varint(high: value.high, low: value.low)
+    }
+
+    // Signed integers use a "zigzag" encoding before the varint encoding.
+    override public func serialize_i16(value: Int16) throws {
+        serialize_varint(value: UInt64(UInt16(bitPattern: (value << 1) ^ (value >> 15))))
+    }
+
+    override public func serialize_i32(value: Int32) throws {
+        serialize_varint(value: UInt64(UInt32(bitPattern: (value << 1) ^ (value >> 31))))
+    }
+
+    override public func serialize_i64(value: Int64) throws {
+        serialize_varint(value: UInt64(bitPattern: (value << 1) ^ (value >> 63)))
+    }
+
+    override public func serialize_i128(value: Int128) throws {
+        let sign = UInt64(bitPattern: value.high >> 63)
+        let high = (UInt64(bitPattern: value.high) << 1) | (value.low >> 63)
+        let low = value.low << 1
+        serialize_varint(high: high ^ sign, low: low ^ sign)
+    }
+
+    override public func serialize_variant_index(value: UInt32) throws {
+        serialize_varint(value: UInt64(value))
+    }
+
+    override public func sort_map_entries(offsets _: [Int]) {
+        // Not required by the format.
+    }
+}
@@ -0,0 +1,65 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+public class ScaleDeserializer: BinaryDeserializer {
+    public let MAX_LENGTH: Int = (1 << 31) - 1
+
+    public init(input: [UInt8]) {
+        super.init(input: input, maxContainerDepth: Int.max)
+    }
+
+    override public func deserialize_f32() throws -> Float {
+        return try Float(bitPattern: deserialize_u32())
+    }
+
+    override public func deserialize_f64() throws -> Double {
+        return try Double(bitPattern: deserialize_u64())
+    }
+
+    func deserialize_compact_u32() throws -> UInt32 {
+        let prefix = try UInt32(deserialize_u8())
+        let value: UInt32
+        let minimum: UInt32
+        switch prefix & 3 {
+        case 0:
+            return prefix >> 2
+        case 1:
+            value = try (prefix | (UInt32(deserialize_u8()) << 8)) >> 2
+            minimum = 1 << 6
+        case 2:
+            var bits = prefix
+            for shift in [8, 16, 24] {
+                bits |= try UInt32(deserialize_u8()) << shift
+            }
+            value = bits >> 2
+            minimum = 1 << 14
+        default:
+            if prefix != 3 {
+                throw DeserializationError.invalidInput(issue: "Compact integer does not fit in 32 bits")
+            }
+            value = try deserialize_u32()
+            minimum = 1 << 30
+        }
+        if value < minimum {
+            throw DeserializationError.invalidInput(issue: "Compact integer is not canonically encoded")
+        }
+        return value
+    }
+
+    override public func deserialize_len() throws -> Int {
+        let value = try deserialize_compact_u32()
+        if value > MAX_LENGTH {
+            throw DeserializationError.invalidInput(issue: "Overflow while parsing length value")
+        }
+        return Int(value)
+    }
+
+    override public func deserialize_variant_index() throws -> UInt32 {
+        return try UInt32(deserialize_u8())
+    }
+
+    override public func check_that_key_slices_are_increasing(key1 _: Slice, key2 _: Slice) throws {
+        // Nothing to do
+    }
+}
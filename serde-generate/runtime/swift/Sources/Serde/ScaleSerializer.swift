@@ -0,0 +1,50 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Foundation
+
+public class ScaleSerializer: BinarySerializer {
+    public let MAX_LENGTH: Int = (1 << 31) - 1
+
+    public init() {
+        super.init(maxContainerDepth: Int.max)
+    }
+
+    override public func serialize_f32(value: Float) throws {
+        try serialize_u32(value: value.bitPattern)
+    }
+
+    override public func serialize_f64(value: Double) throws {
+        try serialize_u64(value: value.bitPattern)
+    }
+
+    func serialize_compact_u32(value: UInt32) throws {
+        if value < 1 << 6 {
+            try serialize_u8(value: UInt8(value << 2))
+        } else if value < 1 << 14 {
+            try serialize_u16(value: UInt16((value << 2) | 1))
+        } else if value < 1 << 30 {
+            try serialize_u32(value: (value << 2) | 2)
+        } else {
+            try serialize_u8(value: 3)
+            try serialize_u32(value: value)
+        }
+    }
+
+    override public func serialize_len(value: Int) throws {
+        if value < 0 || value > MAX_LENGTH {
+            throw SerializationError.invalidValue(issue: "Invalid length value")
+        }
+        try serialize_compact_u32(value: UInt32(value))
+    }
+
+    override public func serialize_variant_index(value: UInt32) throws {
+        if value > UInt8.max {
+            throw SerializationError.invalidValue(issue: "Invalid variant index")
+        }
+        try serialize_u8(value: UInt8(value))
+    }
+
+    override public func sort_map_entries(offsets _: [Int]) {
+        // Not required by the format.
+    }
+}
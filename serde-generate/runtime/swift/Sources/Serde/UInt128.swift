@@ -2,7 +2,7 @@
 
 import Foundation
 
-public struct UInt128: Hashable {
+public struct UInt128: Hashable, Comparable {
     public var high: UInt64
     public var low: UInt64
 
@@ -10,4 +10,8 @@ public struct UInt128: Hashable {
         self.high = high
         self.low = low
     }
+
+    public static func < (lhs: UInt128, rhs: UInt128) -> Bool {
+        return (lhs.high, lhs.low) < (rhs.high, rhs.low)
+    }
 }
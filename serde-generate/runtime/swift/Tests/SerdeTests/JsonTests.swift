@@ -0,0 +1,66 @@
+//  Copyright (c) Facebook, Inc. and its affiliates.
+
+import Serde
+import XCTest
+
+class JsonTests: XCTestCase {
+    func testParseStringify() throws {
+        let value = try Json.parse(input: " {\"a\" : [1, -2.5e3, true, null], \"b\": \"é😀\\n\"} ")
+        XCTAssertEqual(Json.stringify(value: value), "{\"a\":[1,-2.5e3,true,null],\"b\":\"é😀\\n\"}")
+        XCTAssertEqual(Json.stringify(value: try Json.parse(input: "\"\\u0001\\/\\ud83d\\ude00\"")), "\"\\u0001/😀\"")
+        XCTAssertEqual(Json.stringify(value: try Json.parse(input: "[]")), "[]")
+    }
+
+    func testParseErrors() throws {
+        for input in ["", "01", "[1,]", "{\"a\":1,}", "\"\\ud83d\"", "\"\\x\"", "nul", "1 2", "\"\t\""] {
+            XCTAssertThrowsError(try Json.parse(input: input), input)
+        }
+        _ = try Json.parse(input: String(repeating: "[", count: 127) + String(repeating: "]", count: 127))
+        XCTAssertThrowsError(try Json.parse(input: String(repeating: "[", count: 128) + String(repeating: "]", count: 128)))
+    }
+
+    func testFloats() throws {
+        XCTAssertEqual(Json.serialize_f64(value: 0.0).description, "0.0")
+        XCTAssertEqual(Json.serialize_f64(value: -0.0).description, "-0.0")
+        XCTAssertEqual(Json.serialize_f64(value: 1e16).description, "1e16")
+        XCTAssertEqual(Json.serialize_f64(value: 1_234_567_890_123_456.0).description, "1234567890123456.0")
+        XCTAssertEqual(Json.serialize_f64(value: 1e-4).description, "0.0001")
+        XCTAssertEqual(Json.serialize_f64(value: 1e-6).description, "1e-6")
+        XCTAssertEqual(Json.serialize_f32(value: 0.1).description, "0.1")
+        XCTAssertEqual(Json.serialize_f32(value: 1e13).description, "1e13")
+        XCTAssertEqual(Json.serialize_f32(value: 1e-6).description, "0.000001")
+        XCTAssertEqual(Json.serialize_f32(value: 1e-7).description, "1e-7")
+        XCTAssertEqual(Json.serialize_f64(value: Double.infinity).description, "null")
+
+        XCTAssertEqual(try Json.deserialize_f32(value: .number("1e39")), Float.infinity)
+        XCTAssertThrowsError(try Json.deserialize_f64(value: .number("1e309")))
+    }
+
+    func testIntegers() throws {
+        let min = try Json.deserialize_i128(value: .number("-170141183460469231731687303715884105728"))
+        XCTAssertEqual(min, Int128(high: Int64.min, low: 0))
+        XCTAssertEqual(Json.serialize_i128(value: min).description, "-170141183460469231731687303715884105728")
+        XCTAssertEqual(Json.serialize_i128(value: Int128(high: -1, low: UInt64.max)).description, "-1")
+        let max = try Json.deserialize_u128(value: .number("340282366920938463463374607431768211455"))
+        XCTAssertEqual(max, UInt128(high: UInt64.max, low: UInt64.max))
+        XCTAssertThrowsError(try Json.deserialize_u128(value: .number("340282366920938463463374607431768211456")))
+
+        XCTAssertThrowsError(try Json.deserialize_u8(value: .number("256")))
+        XCTAssertThrowsError(try Json.deserialize_i64(value: .number("-0")))
+        XCTAssertThrowsError(try Json.deserialize_i64(value: .number("1.0")))
+        XCTAssertEqual(try Json.deserialize_u64(value: .key("18446744073709551615")), UInt64.max)
+    }
+
+    func testSortKeys() throws {
+        var obj = JsonObject()
+        for key in ["10", "-3", "2"] {
+            obj.put(key: key, value: .null)
+        }
+        obj.sortKeys(by: JsonObject.numberOrder)
+        XCTAssertEqual(obj.keys, ["-3", "2", "10"])
+        obj.sortKeys(by: JsonObject.stringOrder)
+        XCTAssertEqual(obj.keys, ["-3", "10", "2"])
+        obj.sortKeys(by: JsonObject.variantOrder(names: ["2", "10", "-3"]))
+        XCTAssertEqual(obj.keys, ["2", "10", "-3"])
+    }
+}
@@ -3,6 +3,19 @@
 #if !canImport(ObjectiveC)
     import XCTest
 
+    extension JsonTests {
+        // DO NOT MODIFY: This is autogenerated, use:
+        //   `swift test --generate-linuxmain`
+        // to regenerate.
+        static let __allTests__JsonTests = [
+            ("testFloats", testFloats),
+            ("testIntegers", testIntegers),
+            ("testParseErrors", testParseErrors),
+            ("testParseStringify", testParseStringify),
+            ("testSortKeys", testSortKeys),
+        ]
+    }
+
     extension SerdeTests {
         // DO NOT MODIFY: This is autogenerated, use:
         //   `swift test --generate-linuxmain`
@@ -27,6 +40,7 @@
 
     public func __allTests() -> [XCTestCaseEntry] {
         return [
+            testCase(JsonTests.__allTests__JsonTests),
             testCase(SerdeTests.__allTests__SerdeTests),
         ]
     }
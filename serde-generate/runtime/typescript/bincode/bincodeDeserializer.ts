@@ -4,8 +4,81 @@
  */
 
 import { BinaryDeserializer } from "../serde/binaryDeserializer.ts";
+import {
+  SINGLE_BYTE_MAX,
+  U128_TAG,
+  U16_TAG,
+  U32_TAG,
+  U64_TAG,
+} from "./bincodeSerializer.ts";
 
 export class BincodeDeserializer extends BinaryDeserializer {
+  private static readonly BIG_8: bigint = BigInt(8);
+
+  constructor(
+    data: Uint8Array,
+    private readonly varintEncoding = false,
+    private readonly bigEndian = false,
+    // Reading more than `limit` bytes fails, as with the option `with_limit` of the Rust crate.
+    private readonly limit = Number.MAX_SAFE_INTEGER,
+  ) {
+    super(data);
+  }
+
+  protected read(length: number): ArrayBuffer {
+    if (length > this.limit - this.offset) {
+      throw new Error("Exceeded the size limit of the encoding");
+    }
+    return super.read(length);
+  }
+
+  private readFixint(size: number): bigint {
+    let value = BigInt(0);
+    for (let i = 0; i < size; i++) {
+      const shift = BigInt(this.bigEndian ? size - 1 - i : i) *
+        BincodeDeserializer.BIG_8;
+      value = value | (BigInt(this.deserializeU8()) << shift);
+    }
+    return value;
+  }
+
+  public deserializeVarint(size: number): bigint {
+    const tag = this.deserializeU8();
+    let value: bigint;
+    if (tag <= SINGLE_BYTE_MAX) {
+      value = BigInt(tag);
+    } else if (tag == U16_TAG) {
+      value = this.readFixint(2);
+    } else if (tag == U32_TAG) {
+      value = this.readFixint(4);
+    } else if (tag == U64_TAG) {
+      value = this.readFixint(8);
+    } else if (tag == U128_TAG && size == 16) {
+      value = this.readFixint(16);
+    } else {
+      throw new Error("Invalid tag for varint-encoded value: " + tag);
+    }
+    if (value >> BigInt(8 * size) != BigInt(0)) {
+      throw new Error("Overflow while parsing varint-encoded value");
+    }
+    return value;
+  }
+
+  private deserializeUnsigned(size: number): bigint {
+    if (this.varintEncoding) {
+      return this.deserializeVarint(size);
+    }
+    return this.readFixint(size);
+  }
+
+  private deserializeSigned(size: number): bigint {
+    if (!this.varintEncoding) {
+      return BigInt.asIntN(8 * size, this.readFixint(size));
+    }
+    const value = this.deserializeVarint(size);
+    return (value >> BigInt(1)) ^ -(value & BigInt(1));
+  }
+
   deserializeLen(): number {
     return Number(this.deserializeU64());
   }
@@ -14,6 +87,51 @@ export class BincodeDeserializer extends BinaryDeserializer {
     return this.deserializeU32();
   }
 
+  public deserializeU16(): number {
+    return Number(this.deserializeUnsigned(2));
+  }
+
+  public deserializeU32(): number {
+    return Number(this.deserializeUnsigned(4));
+  }
+
+  public deserializeU64(): bigint {
+    return this.deserializeUnsigned(8);
+  }
+
+  public deserializeU128(): bigint {
+    return this.deserializeUnsigned(16);
+  }
+
+  public deserializeI16(): number {
+    return Number(this.deserializeSigned(2));
+  }
+
+  public deserializeI32(): number {
+    return Number(this.deserializeSigned(4));
+  }
+
+  public deserializeI64(): bigint {
+    return this.deserializeSigned(8);
+  }
+
+  public deserializeI128(): bigint {
+    return this.deserializeSigned(16);
+  }
+
+  // Floating-point numbers are never varint-encoded.
+  public deserializeF32(): number {
+    const bytes = new Uint8Array(4);
+    new DataView(bytes.buffer).setUint32(0, Number(this.readFixint(4)));
+    return new DataView(bytes.buffer).getFloat32(0);
+  }
+
+  public deserializeF64(): number {
+    const bytes = new Uint8Array(8);
+    new DataView(bytes.buffer).setBigUint64(0, this.readFixint(8));
+    return new DataView(bytes.buffer).getFloat64(0);
+  }
+
   checkThatKeySlicesAreIncreasing(
     key1: [number, number],
     key2: [number, number],
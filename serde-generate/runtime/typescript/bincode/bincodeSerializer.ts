@@ -5,7 +5,75 @@
 
 import { BinarySerializer } from "../serde/binarySerializer.ts";
 
+// Tags used by the varint encoding of integers.
+export const SINGLE_BYTE_MAX = 250;
+export const U16_TAG = 251;
+export const U32_TAG = 252;
+export const U64_TAG = 253;
+export const U128_TAG = 254;
+
 export class BincodeSerializer extends BinarySerializer {
+  private static readonly BIG_8: bigint = BigInt(8);
+  private static readonly BIG_FF: bigint = BigInt(0xff);
+
+  constructor(
+    private readonly varintEncoding = false,
+    private readonly bigEndian = false,
+  ) {
+    super();
+  }
+
+  private writeFixint(value: bigint | number, size: number): void {
+    let big = BigInt.asUintN(8 * size, BigInt(value));
+    const bytes = new Uint8Array(size);
+    for (let i = 0; i < size; i++) {
+      bytes[this.bigEndian ? size - 1 - i : i] = Number(
+        big & BincodeSerializer.BIG_FF,
+      );
+      big = big >> BincodeSerializer.BIG_8;
+    }
+    this.serialize(bytes);
+  }
+
+  public serializeVarint(value: bigint | number): void {
+    const big = BigInt(value);
+    if (big <= BigInt(SINGLE_BYTE_MAX)) {
+      this.serialize(new Uint8Array([Number(big)]));
+    } else if (big < BigInt(1) << BigInt(16)) {
+      this.serialize(new Uint8Array([U16_TAG]));
+      this.writeFixint(big, 2);
+    } else if (big < BigInt(1) << BigInt(32)) {
+      this.serialize(new Uint8Array([U32_TAG]));
+      this.writeFixint(big, 4);
+    } else if (big < BigInt(1) << BigInt(64)) {
+      this.serialize(new Uint8Array([U64_TAG]));
+      this.writeFixint(big, 8);
+    } else {
+      this.serialize(new Uint8Array([U128_TAG]));
+      this.writeFixint(big, 16);
+    }
+  }
+
+  private serializeUnsigned(value: bigint | number, size: number): void {
+    if (this.varintEncoding) {
+      this.serializeVarint(value);
+    } else {
+      this.writeFixint(value, size);
+    }
+  }
+
+  // Signed integers use a "zigzag" encoding before the varint encoding.
+  private serializeSigned(value: bigint | number, size: number): void {
+    const big = BigInt(value);
+    if (this.varintEncoding) {
+      this.serializeVarint(
+        (big << BigInt(1)) ^ (big >> BigInt(8 * size - 1)),
+      );
+    } else {
+      this.writeFixint(big, size);
+    }
+  }
+
   serializeLen(value: number): void {
     this.serializeU64(value);
   }
@@ -14,6 +82,51 @@ export class BincodeSerializer extends BinarySerializer {
     this.serializeU32(value);
   }
 
+  public serializeU16(value: number): void {
+    this.serializeUnsigned(value, 2);
+  }
+
+  public serializeU32(value: number): void {
+    this.serializeUnsigned(value, 4);
+  }
+
+  public serializeU64(value: BigInt | number): void {
+    this.serializeUnsigned(BigInt(value.toString()), 8);
+  }
+
+  public serializeU128(value: BigInt | number): void {
+    this.serializeUnsigned(BigInt(value.toString()), 16);
+  }
+
+  public serializeI16(value: number): void {
+    this.serializeSigned(value, 2);
+  }
+
+  public serializeI32(value: number): void {
+    this.serializeSigned(value, 4);
+  }
+
+  public serializeI64(value: bigint | number): void {
+    this.serializeSigned(value, 8);
+  }
+
+  public serializeI128(value: bigint | number): void {
+    this.serializeSigned(value, 16);
+  }
+
+  // Floating-point numbers are never varint-encoded.
+  public serializeF32(value: number): void {
+    const bytes = new Uint8Array(4);
+    new DataView(bytes.buffer).setFloat32(0, value, !this.bigEndian);
+    this.serialize(bytes);
+  }
+
+  public serializeF64(value: number): void {
+    const bytes = new Uint8Array(8);
+    new DataView(bytes.buffer).setFloat64(0, value, !this.bigEndian);
+    this.serialize(bytes);
+  }
+
   public sortMapEntries(offsets: number[]): void {
     return;
   }
@@ -0,0 +1,133 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+import { BinaryDeserializer } from "../serde/binaryDeserializer.ts";
+import { compareBytes, MAX_LENGTH } from "./borshSerializer.ts";
+import { SortKeys } from "./sortKeys.ts";
+
+export class BorshDeserializer extends BinaryDeserializer {
+  private sortKeys = new SortKeys();
+
+  public deserializeStr(): string {
+    const start = this.getBufferOffset();
+    const value = super.deserializeStr();
+    const content = new Uint8Array(this.buffer);
+    this.sortKeys.recordStr(content, start, this.getBufferOffset());
+    return value;
+  }
+
+  private recordInteger(start: number, signed: boolean): void {
+    const content = new Uint8Array(this.buffer);
+    this.sortKeys.recordInteger(content, start, this.getBufferOffset(), signed);
+  }
+
+  public deserializeU16(): number {
+    const start = this.getBufferOffset();
+    const value = super.deserializeU16();
+    this.recordInteger(start, false);
+    return value;
+  }
+
+  public deserializeU32(): number {
+    const start = this.getBufferOffset();
+    const value = super.deserializeU32();
+    this.recordInteger(start, false);
+    return value;
+  }
+
+  public deserializeU64(): bigint {
+    const start = this.getBufferOffset();
+    const value = super.deserializeU64();
+    this.recordInteger(start, false);
+    return value;
+  }
+
+  public deserializeU128(): bigint {
+    const start = this.getBufferOffset();
+    const value = super.deserializeU128();
+    this.recordInteger(start, false);
+    return value;
+  }
+
+  public deserializeI8(): number {
+    const start = this.getBufferOffset();
+    const value = super.deserializeI8();
+    this.recordInteger(start, true);
+    return value;
+  }
+
+  public deserializeI16(): number {
+    const start = this.getBufferOffset();
+    const value = super.deserializeI16();
+    this.recordInteger(start, true);
+    return value;
+  }
+
+  public deserializeI32(): number {
+    const start = this.getBufferOffset();
+    const value = super.deserializeI32();
+    this.recordInteger(start, true);
+    return value;
+  }
+
+  public deserializeI64(): bigint {
+    const start = this.getBufferOffset();
+    const value = super.deserializeI64();
+    this.recordInteger(start, true);
+    return value;
+  }
+
+  public deserializeI128(): bigint {
+    const start = this.getBufferOffset();
+    const value = super.deserializeI128();
+    this.recordInteger(start, true);
+    return value;
+  }
+
+  deserializeLen(): number {
+    const value = this.deserializeU32();
+    if (value > MAX_LENGTH) {
+      throw new Error("Length exceeds the maximum supported value.");
+    }
+    return value;
+  }
+
+  public deserializeVariantIndex(): number {
+    return this.deserializeU8();
+  }
+
+  public deserializeF32(): number {
+    const value = super.deserializeF32();
+    if (Number.isNaN(value)) {
+      throw new Error("NaN values are not supported by Borsh.");
+    }
+    return value;
+  }
+
+  public deserializeF64(): number {
+    const value = super.deserializeF64();
+    if (Number.isNaN(value)) {
+      throw new Error("NaN values are not supported by Borsh.");
+    }
+    return value;
+  }
+
+  public checkThatKeySlicesAreIncreasing(
+    key1: [number, number],
+    key2: [number, number],
+  ): void {
+    const content = new Uint8Array(this.buffer);
+    if (
+      compareBytes(
+        this.sortKeys.sortKey(content, key1[0], key1[1]),
+        this.sortKeys.sortKey(content, key2[0], key2[1]),
+      ) >= 0
+    ) {
+      throw new Error(
+        "Error while decoding map: keys are not serialized in the expected order",
+      );
+    }
+  }
+}
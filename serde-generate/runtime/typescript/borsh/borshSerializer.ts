@@ -0,0 +1,140 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+import { BinarySerializer } from "../serde/binarySerializer.ts";
+import { SortKeys } from "./sortKeys.ts";
+
+// Maximum length in practice for sequences (e.g. in Java).
+export const MAX_LENGTH = 2 ** 31 - 1;
+
+export function compareBytes(key1: Uint8Array, key2: Uint8Array): number {
+  for (let i = 0; i < key1.length && i < key2.length; i++) {
+    if (key1[i] != key2[i]) {
+      return key1[i] - key2[i];
+    }
+  }
+  return key1.length - key2.length;
+}
+
+export class BorshSerializer extends BinarySerializer {
+  private sortKeys = new SortKeys();
+
+  public serializeStr(value: string): void {
+    const start = this.getBufferOffset();
+    super.serializeStr(value);
+    const content = new Uint8Array(this.buffer);
+    this.sortKeys.recordStr(content, start, this.getBufferOffset());
+  }
+
+  private recordInteger(start: number, signed: boolean): void {
+    const content = new Uint8Array(this.buffer);
+    this.sortKeys.recordInteger(content, start, this.getBufferOffset(), signed);
+  }
+
+  public serializeU16(value: number): void {
+    const start = this.getBufferOffset();
+    super.serializeU16(value);
+    this.recordInteger(start, false);
+  }
+
+  public serializeU32(value: number): void {
+    const start = this.getBufferOffset();
+    super.serializeU32(value);
+    this.recordInteger(start, false);
+  }
+
+  public serializeU64(value: BigInt | number): void {
+    const start = this.getBufferOffset();
+    super.serializeU64(value);
+    this.recordInteger(start, false);
+  }
+
+  public serializeU128(value: BigInt | number): void {
+    const start = this.getBufferOffset();
+    super.serializeU128(value);
+    this.recordInteger(start, false);
+  }
+
+  public serializeI8(value: number): void {
+    const start = this.getBufferOffset();
+    super.serializeI8(value);
+    this.recordInteger(start, true);
+  }
+
+  public serializeI16(value: number): void {
+    const start = this.getBufferOffset();
+    super.serializeI16(value);
+    this.recordInteger(start, true);
+  }
+
+  public serializeI32(value: number): void {
+    const start = this.getBufferOffset();
+    super.serializeI32(value);
+    this.recordInteger(start, true);
+  }
+
+  public serializeI64(value: bigint | number): void {
+    const start = this.getBufferOffset();
+    super.serializeI64(value);
+    this.recordInteger(start, true);
+  }
+
+  public serializeI128(value: bigint | number): void {
+    const start = this.getBufferOffset();
+    super.serializeI128(value);
+    this.recordInteger(start, true);
+  }
+
+  serializeLen(value: number): void {
+    if (value < 0 || value > MAX_LENGTH) {
+      throw new Error("Length exceeds the maximum supported value.");
+    }
+    this.serializeU32(value);
+  }
+
+  public serializeVariantIndex(value: number): void {
+    if (value < 0 || value > 0xff) {
+      throw new Error("Variant index exceeds the maximum supported value.");
+    }
+    this.serializeU8(value);
+  }
+
+  public serializeF32(value: number): void {
+    if (Number.isNaN(value)) {
+      throw new Error("NaN values are not supported by Borsh.");
+    }
+    super.serializeF32(value);
+  }
+
+  public serializeF64(value: number): void {
+    if (Number.isNaN(value)) {
+      throw new Error("NaN values are not supported by Borsh.");
+    }
+    super.serializeF64(value);
+  }
+
+  public sortMapEntries(offsets: number[]): void {
+    if (offsets.length <= 1) {
+      return;
+    }
+    const end = this.getBufferOffset();
+    const content = new Uint8Array(this.buffer);
+    const slices = offsets.map((start, i) => {
+      const sliceEnd = i + 1 < offsets.length ? offsets[i + 1] : end;
+      return {
+        key: this.sortKeys.sortKey(content, start, sliceEnd),
+        bytes: content.slice(start, sliceEnd),
+      };
+    });
+    slices.sort((slice1, slice2) => compareBytes(slice1.key, slice2.key));
+    // Positions in the map are no longer valid. Map keys never contain maps.
+    this.sortKeys.truncate(offsets[0]);
+    let position = offsets[0];
+    for (const slice of slices) {
+      content.set(slice.bytes, position);
+      position += slice.bytes.length;
+    }
+  }
+}
@@ -0,0 +1,91 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+interface SortKeyRecord {
+  start: number;
+  end: number;
+  key: Uint8Array;
+}
+
+/**
+ * Order-preserving encodings of the integers and strings written or read so far,
+ * indexed by their positions in the buffer.
+ *
+ * Borsh sorts map entries by keys as in Rust. Substituting these encodings in the
+ * encoded keys gives byte strings that compare in the same way.
+ */
+export class SortKeys {
+  // Ordered by start positions.
+  private records: SortKeyRecord[] = [];
+
+  // Little-endian integers compare as in Rust in big-endian order, with the sign bit
+  // flipped.
+  public recordInteger(
+    content: Uint8Array,
+    start: number,
+    end: number,
+    signed: boolean,
+  ): void {
+    const key = content.slice(start, end).reverse();
+    if (signed) {
+      key[0] ^= 0x80;
+    }
+    this.record(start, end, key);
+  }
+
+  // Strings have their zeros escaped and a terminator added so that prefixes come
+  // first.
+  public recordStr(content: Uint8Array, start: number, end: number): void {
+    const key: number[] = [];
+    for (const byte of content.subarray(start + 4, end)) {
+      key.push(byte);
+      if (byte == 0) {
+        key.push(0xff);
+      }
+    }
+    key.push(0, 0);
+    this.record(start, end, new Uint8Array(key));
+  }
+
+  private record(start: number, end: number, key: Uint8Array): void {
+    // Values written or read inside the current one are superseded.
+    this.truncate(start);
+    this.records.push({ start, end, key });
+  }
+
+  private lowerBound(start: number): number {
+    let low = 0;
+    let high = this.records.length;
+    while (low < high) {
+      const middle = (low + high) >>> 1;
+      if (this.records[middle].start < start) {
+        low = middle + 1;
+      } else {
+        high = middle;
+      }
+    }
+    return low;
+  }
+
+  public truncate(start: number): void {
+    this.records.length = this.lowerBound(start);
+  }
+
+  public sortKey(content: Uint8Array, start: number, end: number): Uint8Array {
+    const result: number[] = [];
+    let position = start;
+    for (
+      let i = this.lowerBound(start);
+      i < this.records.length && this.records[i].start < end;
+      i++
+    ) {
+      result.push(...content.subarray(position, this.records[i].start));
+      result.push(...this.records[i].key);
+      position = this.records[i].end;
+    }
+    result.push(...content.subarray(position, end));
+    return new Uint8Array(result);
+  }
+}
@@ -0,0 +1,657 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+/**
+ * The JSON format of the Rust crate `serde_json`. Generated classes convert their values from
+ * and to trees of `JsonValue` with the helpers of `Json`, then `parseJson` and `stringifyJson`
+ * deal with the JSON text.
+ */
+
+import {
+  JsonKey,
+  JsonNumber,
+  JsonObject,
+  JsonValue,
+  JsonVariant,
+} from "./jsonValue.ts";
+
+/** Maximal number of nested arrays and objects accepted by `serde_json` (whose recursion limit is 128). */
+export const MAX_CONTAINER_DEPTH = 127;
+
+const INTEGER = /^-?(0|[1-9][0-9]*)$/;
+const NUMBER = /^-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?$/;
+
+export function parseJson(input: string): JsonValue {
+  const parser = new JsonParser(input);
+  const value = parser.parseValue(MAX_CONTAINER_DEPTH);
+  parser.skipWhitespace();
+  if (parser.position < input.length) {
+    throw new Error("Trailing characters in JSON input");
+  }
+  return value;
+}
+
+export function stringifyJson(value: JsonValue): string {
+  const output: string[] = [];
+  writeJson(output, value);
+  return output.join("");
+}
+
+function writeJson(output: string[], value: JsonValue): void {
+  if (value === null) {
+    output.push("null");
+  } else if (typeof value === "boolean") {
+    output.push(value ? "true" : "false");
+  } else if (value instanceof JsonNumber) {
+    output.push(value.literal);
+  } else if (typeof value === "string") {
+    writeString(output, value);
+  } else if (value instanceof JsonKey) {
+    writeString(output, value.value);
+  } else if (Array.isArray(value)) {
+    output.push("[");
+    value.forEach((item, i) => {
+      if (i > 0) {
+        output.push(",");
+      }
+      writeJson(output, item);
+    });
+    output.push("]");
+  } else {
+    output.push("{");
+    for (let i = 0; i < value.size(); i++) {
+      if (i > 0) {
+        output.push(",");
+      }
+      writeString(output, value.keys[i]);
+      output.push(":");
+      writeJson(output, value.values[i]);
+    }
+    output.push("}");
+  }
+}
+
+function writeString(output: string[], value: string): void {
+  let result = '"';
+  for (let i = 0; i < value.length; i++) {
+    const c = value.charCodeAt(i);
+    switch (value[i]) {
+      case '"':
+        result += '\\"';
+        break;
+      case "\\":
+        result += "\\\\";
+        break;
+      case "\b":
+        result += "\\b";
+        break;
+      case "\f":
+        result += "\\f";
+        break;
+      case "\n":
+        result += "\\n";
+        break;
+      case "\r":
+        result += "\\r";
+        break;
+      case "\t":
+        result += "\\t";
+        break;
+      default:
+        if (c < 0x20) {
+          result += "\\u00" + c.toString(16).padStart(2, "0");
+        } else if (
+          c >= 0xd800 && c <= 0xdbff && i + 1 < value.length &&
+          value.charCodeAt(i + 1) >= 0xdc00 && value.charCodeAt(i + 1) <= 0xdfff
+        ) {
+          result += value[i] + value[i + 1];
+          i++;
+        } else if (c >= 0xd800 && c <= 0xdfff) {
+          throw new Error("Invalid unicode string");
+        } else {
+          result += value[i];
+        }
+    }
+  }
+  output.push(result + '"');
+}
+
+class JsonParser {
+  public position = 0;
+
+  constructor(private input: string) {}
+
+  public skipWhitespace(): void {
+    while (this.position < this.input.length) {
+      const c = this.input[this.position];
+      if (c != " " && c != "\t" && c != "\n" && c != "\r") {
+        return;
+      }
+      this.position++;
+    }
+  }
+
+  private error(message: string): Error {
+    return new Error(message + " at position " + this.position);
+  }
+
+  private peek(c: string): boolean {
+    return this.input[this.position] === c;
+  }
+
+  private next(): string {
+    if (this.position >= this.input.length) {
+      throw this.error("Unexpected end of JSON input");
+    }
+    return this.input[this.position++];
+  }
+
+  private expect(word: string): void {
+    if (!this.input.startsWith(word, this.position)) {
+      throw this.error("Invalid JSON value");
+    }
+    this.position += word.length;
+  }
+
+  public parseValue(depthBudget: number): JsonValue {
+    this.skipWhitespace();
+    if (this.position >= this.input.length) {
+      throw this.error("Unexpected end of JSON input");
+    }
+    const c = this.input[this.position];
+    switch (c) {
+      case "n":
+        this.expect("null");
+        return null;
+      case "t":
+        this.expect("true");
+        return true;
+      case "f":
+        this.expect("false");
+        return false;
+      case '"':
+        return this.parseString();
+      case "[": {
+        if (depthBudget == 0) {
+          throw this.error("Exceeded maximum container depth");
+        }
+        this.position++;
+        const items: JsonValue[] = [];
+        this.skipWhitespace();
+        if (this.peek("]")) {
+          this.position++;
+          return items;
+        }
+        for (;;) {
+          items.push(this.parseValue(depthBudget - 1));
+          this.skipWhitespace();
+          const d = this.next();
+          if (d == "]") {
+            return items;
+          }
+          if (d != ",") {
+            throw this.error("Expected ',' or ']'");
+          }
+        }
+      }
+      case "{": {
+        if (depthBudget == 0) {
+          throw this.error("Exceeded maximum container depth");
+        }
+        this.position++;
+        const object = new JsonObject();
+        this.skipWhitespace();
+        if (this.peek("}")) {
+          this.position++;
+          return object;
+        }
+        for (;;) {
+          this.skipWhitespace();
+          if (!this.peek('"')) {
+            throw this.error("Expected a string key");
+          }
+          const key = this.parseString();
+          this.skipWhitespace();
+          if (this.next() != ":") {
+            throw this.error("Expected ':'");
+          }
+          object.put(key, this.parseValue(depthBudget - 1));
+          this.skipWhitespace();
+          const d = this.next();
+          if (d == "}") {
+            return object;
+          }
+          if (d != ",") {
+            throw this.error("Expected ',' or '}'");
+          }
+        }
+      }
+      default:
+        if (c == "-" || (c >= "0" && c <= "9")) {
+          return this.parseNumber();
+        }
+        throw this.error("Invalid JSON value");
+    }
+  }
+
+  private parseNumber(): JsonNumber {
+    const start = this.position;
+    while (/[-+.eE0-9]/.test(this.input[this.position] ?? "")) {
+      this.position++;
+    }
+    const literal = this.input.substring(start, this.position);
+    if (!NUMBER.test(literal)) {
+      throw this.error("Invalid number");
+    }
+    return new JsonNumber(literal);
+  }
+
+  private parseHex(): number {
+    const digits = this.input.substring(this.position, this.position + 4);
+    if (!/^[0-9a-fA-F]{4}$/.test(digits)) {
+      throw this.error("Invalid escape in string");
+    }
+    this.position += 4;
+    return parseInt(digits, 16);
+  }
+
+  private parseString(): string {
+    this.position++;
+    let result = "";
+    for (;;) {
+      const c = this.next();
+      const code = c.charCodeAt(0);
+      if (c == '"') {
+        return result;
+      }
+      if (code < 0x20) {
+        throw this.error("Control character in string");
+      }
+      if (c != "\\") {
+        if (code >= 0xd800 && code <= 0xdbff) {
+          const low = this.input.charCodeAt(this.position);
+          if (!(low >= 0xdc00 && low <= 0xdfff)) {
+            throw this.error("Invalid unicode string");
+          }
+          result += c + this.next();
+        } else if (code >= 0xdc00 && code <= 0xdfff) {
+          throw this.error("Invalid unicode string");
+        } else {
+          result += c;
+        }
+        continue;
+      }
+      const escape = this.next();
+      switch (escape) {
+        case '"':
+        case "\\":
+        case "/":
+          result += escape;
+          break;
+        case "b":
+          result += "\b";
+          break;
+        case "f":
+          result += "\f";
+          break;
+        case "n":
+          result += "\n";
+          break;
+        case "r":
+          result += "\r";
+          break;
+        case "t":
+          result += "\t";
+          break;
+        case "u": {
+          const high = this.parseHex();
+          if (high >= 0xdc00 && high <= 0xdfff) {
+            throw this.error("Lone surrogate in string");
+          }
+          if (high >= 0xd800 && high <= 0xdbff) {
+            if (!this.input.startsWith("\\u", this.position)) {
+              throw this.error("Lone surrogate in string");
+            }
+            this.position += 2;
+            const low = this.parseHex();
+            if (!(low >= 0xdc00 && low <= 0xdfff)) {
+              throw this.error("Lone surrogate in string");
+            }
+            result += String.fromCharCode(high, low);
+          } else {
+            result += String.fromCharCode(high);
+          }
+          break;
+        }
+        default:
+          throw this.error("Invalid escape in string");
+      }
+    }
+  }
+}
+
+/**
+ * Format a finite float in the manner of the Rust crate `ryu`, as `serde_json` does: with the
+ * shortest digits that read back as the same value.
+ */
+function formatFloat(value: number, isF32: boolean): string {
+  if (value == 0) {
+    return Object.is(value, -0) ? "-0.0" : "0.0";
+  }
+  const sign = value < 0 ? "-" : "";
+  const magnitude = Math.abs(value);
+  let scientific = magnitude.toExponential();
+  if (isF32) {
+    for (let precision = 1; precision <= 9; precision++) {
+      scientific = magnitude.toExponential(precision - 1);
+      if (Math.fround(Number(scientific)) == magnitude) {
+        break;
+      }
+    }
+  }
+  const [mantissa, exponent] = scientific.split("e");
+  const digits = mantissa.replace(".", "").replace(/(.)0+$/, "$1");
+  const point = Number(exponent) + 1;
+  const maxPoint = isF32 ? 13 : 16;
+  const minPoint = isF32 ? -6 : -5;
+  if (digits.length <= point && point <= maxPoint) {
+    return sign + digits + "0".repeat(point - digits.length) + ".0";
+  }
+  if (0 < point && point <= maxPoint) {
+    return sign + digits.substring(0, point) + "." + digits.substring(point);
+  }
+  if (minPoint < point && point <= 0) {
+    return sign + "0." + "0".repeat(-point) + digits;
+  }
+  const shortMantissa = digits.length == 1
+    ? digits
+    : digits[0] + "." + digits.substring(1);
+  return sign + shortMantissa + "e" + (point - 1);
+}
+
+function typeError(value: JsonValue, expected: string): Error {
+  return new Error(
+    "Invalid type: expected " + expected + ", found " + String(value),
+  );
+}
+
+function readInteger(
+  value: JsonValue,
+  min: bigint,
+  max: bigint,
+  acceptsNegativeZero: boolean,
+): bigint {
+  let literal: string;
+  if (value instanceof JsonNumber) {
+    literal = value.literal;
+  } else if (value instanceof JsonKey) {
+    literal = value.value;
+  } else {
+    throw typeError(value, "an integer");
+  }
+  // Like `serde_json`, read `-0` as a float, except for 128-bit integers.
+  if (!INTEGER.test(literal) || (literal == "-0" && !acceptsNegativeZero)) {
+    throw typeError(value, "an integer");
+  }
+  const result = BigInt(literal);
+  if (result < min || result > max) {
+    throw new Error("Integer out of range: " + literal);
+  }
+  return result;
+}
+
+function readNumber(value: JsonValue): number {
+  let literal: string;
+  if (value instanceof JsonNumber) {
+    literal = value.literal;
+  } else if (value instanceof JsonKey && NUMBER.test(value.value)) {
+    literal = value.value;
+  } else {
+    throw typeError(value, "a number");
+  }
+  const result = Number(literal);
+  if (!isFinite(result)) {
+    throw new Error("Number out of range: " + literal);
+  }
+  return result;
+}
+
+function bits(n: number): bigint {
+  return BigInt(1) << BigInt(n);
+}
+
+function signedRange(n: number): [bigint, bigint] {
+  return [-bits(n - 1), bits(n - 1) - BigInt(1)];
+}
+
+function unsignedRange(n: number): [bigint, bigint] {
+  return [BigInt(0), bits(n) - BigInt(1)];
+}
+
+export class Json {
+  private static readonly I8 = signedRange(8);
+  private static readonly I16 = signedRange(16);
+  private static readonly I32 = signedRange(32);
+  private static readonly I64 = signedRange(64);
+  private static readonly I128 = signedRange(128);
+  private static readonly U8 = unsignedRange(8);
+  private static readonly U16 = unsignedRange(16);
+  private static readonly U32 = unsignedRange(32);
+  private static readonly U64 = unsignedRange(64);
+  private static readonly U128 = unsignedRange(128);
+
+  // Serialization helpers
+
+  static serializeUnit(_value: null): JsonValue {
+    return null;
+  }
+
+  static serializeBool(value: boolean): JsonValue {
+    return value;
+  }
+
+  static serializeI8(value: number): JsonValue {
+    return new JsonNumber(String(value));
+  }
+
+  static serializeI16(value: number): JsonValue {
+    return new JsonNumber(String(value));
+  }
+
+  static serializeI32(value: number): JsonValue {
+    return new JsonNumber(String(value));
+  }
+
+  static serializeI64(value: bigint): JsonValue {
+    return new JsonNumber(value.toString());
+  }
+
+  static serializeI128(value: bigint): JsonValue {
+    return new JsonNumber(value.toString());
+  }
+
+  static serializeU8(value: number): JsonValue {
+    return new JsonNumber(String(value));
+  }
+
+  static serializeU16(value: number): JsonValue {
+    return new JsonNumber(String(value));
+  }
+
+  static serializeU32(value: number): JsonValue {
+    return new JsonNumber(String(value));
+  }
+
+  static serializeU64(value: bigint): JsonValue {
+    return new JsonNumber(value.toString());
+  }
+
+  static serializeU128(value: bigint): JsonValue {
+    return new JsonNumber(value.toString());
+  }
+
+  static serializeF32(value: number): JsonValue {
+    return isFinite(value) ? new JsonNumber(formatFloat(value, true)) : null;
+  }
+
+  static serializeF64(value: number): JsonValue {
+    return isFinite(value) ? new JsonNumber(formatFloat(value, false)) : null;
+  }
+
+  static serializeChar(value: string): JsonValue {
+    return value;
+  }
+
+  static serializeStr(value: string): JsonValue {
+    return value;
+  }
+
+  static serializeBytes(value: Uint8Array): JsonValue {
+    return Array.from(value, (b) => new JsonNumber(String(b)));
+  }
+
+  /** A variant of an enum that is not a unit variant (unit variants are written as strings). */
+  static serializeVariant(name: string, content: JsonValue): JsonValue {
+    const result = new JsonObject();
+    result.put(name, content);
+    return result;
+  }
+
+  /** The key of a map entry, given the JSON value of the key. */
+  static key(value: JsonValue): string {
+    if (typeof value === "string") {
+      return value;
+    }
+    if (value instanceof JsonNumber) {
+      return value.literal;
+    }
+    if (typeof value === "boolean") {
+      return value ? "true" : "false";
+    }
+    throw new Error("Map keys must be strings or numbers");
+  }
+
+  // Deserialization helpers
+
+  static deserializeUnit(value: JsonValue): null {
+    if (value !== null) {
+      throw typeError(value, "null");
+    }
+    return null;
+  }
+
+  static deserializeBool(value: JsonValue): boolean {
+    if (typeof value === "boolean") {
+      return value;
+    }
+    if (
+      value instanceof JsonKey && (value.value == "true" || value.value == "false")
+    ) {
+      return value.value == "true";
+    }
+    throw typeError(value, "a boolean");
+  }
+
+  static deserializeI8(value: JsonValue): number {
+    return Number(readInteger(value, ...Json.I8, false));
+  }
+
+  static deserializeI16(value: JsonValue): number {
+    return Number(readInteger(value, ...Json.I16, false));
+  }
+
+  static deserializeI32(value: JsonValue): number {
+    return Number(readInteger(value, ...Json.I32, false));
+  }
+
+  static deserializeI64(value: JsonValue): bigint {
+    return readInteger(value, ...Json.I64, false);
+  }
+
+  static deserializeI128(value: JsonValue): bigint {
+    return readInteger(value, ...Json.I128, true);
+  }
+
+  static deserializeU8(value: JsonValue): number {
+    return Number(readInteger(value, ...Json.U8, false));
+  }
+
+  static deserializeU16(value: JsonValue): number {
+    return Number(readInteger(value, ...Json.U16, false));
+  }
+
+  static deserializeU32(value: JsonValue): number {
+    return Number(readInteger(value, ...Json.U32, false));
+  }
+
+  static deserializeU64(value: JsonValue): bigint {
+    return readInteger(value, ...Json.U64, false);
+  }
+
+  static deserializeU128(value: JsonValue): bigint {
+    return readInteger(value, ...Json.U128, false);
+  }
+
+  static deserializeF32(value: JsonValue): number {
+    // Like `serde_json`, read the closest f64 value then convert it to f32.
+    return Math.fround(readNumber(value));
+  }
+
+  static deserializeF64(value: JsonValue): number {
+    return readNumber(value);
+  }
+
+  static deserializeChar(value: JsonValue): string {
+    const result = Json.deserializeStr(value);
+    if ([...result].length != 1) {
+      throw typeError(value, "a character");
+    }
+    return result;
+  }
+
+  static deserializeStr(value: JsonValue): string {
+    if (typeof value === "string") {
+      return value;
+    }
+    if (value instanceof JsonKey) {
+      return value.value;
+    }
+    throw typeError(value, "a string");
+  }
+
+  static deserializeBytes(value: JsonValue): Uint8Array {
+    return Uint8Array.from(Json.deserializeArray(value), Json.deserializeU8);
+  }
+
+  /** The items of an array, of the given length if any (e.g. a tuple or a fixed-size array). */
+  static deserializeArray(value: JsonValue, length?: number): JsonValue[] {
+    if (!Array.isArray(value)) {
+      throw typeError(value, "an array");
+    }
+    if (length !== undefined && value.length != length) {
+      throw new Error(
+        "Invalid length: " + value.length + " instead of " + length,
+      );
+    }
+    return value;
+  }
+
+  static deserializeObject(value: JsonValue): JsonObject {
+    if (!(value instanceof JsonObject)) {
+      throw typeError(value, "an object");
+    }
+    return value;
+  }
+
+  static deserializeVariant(value: JsonValue): JsonVariant {
+    if (typeof value === "string" || value instanceof JsonKey) {
+      return new JsonVariant(Json.deserializeStr(value), null, false);
+    }
+    if (value instanceof JsonObject && value.size() == 1) {
+      return new JsonVariant(value.keys[0], value.values[0], true);
+    }
+    throw typeError(value, "a string or an object with a single entry");
+  }
+}
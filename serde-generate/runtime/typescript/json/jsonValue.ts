@@ -0,0 +1,95 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+/**
+ * A JSON value. Numbers are kept as written so that integers of any size are read exactly.
+ */
+export type JsonValue =
+  | null
+  | boolean
+  | JsonNumber
+  | string
+  | JsonValue[]
+  | JsonObject
+  | JsonKey;
+
+export class JsonNumber {
+  constructor(public literal: string) {}
+}
+
+/**
+ * The key of an entry of a JSON object read as a map. Like `serde_json`, keys may stand for
+ * strings, numbers, booleans, or unit variants of enums.
+ */
+export class JsonKey {
+  constructor(public value: string) {}
+}
+
+/**
+ * The entries of a JSON object, in order and possibly with duplicate keys.
+ */
+export class JsonObject {
+  public keys: string[] = [];
+  public values: JsonValue[] = [];
+
+  public put(key: string, value: JsonValue): void {
+    this.keys.push(key);
+    this.values.push(value);
+  }
+
+  public size(): number {
+    return this.keys.length;
+  }
+
+  /** Value of the field `name` of a struct. */
+  public field(name: string): JsonValue {
+    const index = this.find(name);
+    if (index < 0) {
+      throw new Error("Missing field: " + name);
+    }
+    return this.values[index];
+  }
+
+  /** Value of the optional field `name` of a struct, or `null` if the field is missing. */
+  public optionalField(name: string): JsonValue {
+    const index = this.find(name);
+    return index < 0 ? null : this.values[index];
+  }
+
+  private find(name: string): number {
+    const index = this.keys.indexOf(name);
+    if (index >= 0 && this.keys.lastIndexOf(name) != index) {
+      throw new Error("Duplicate field: " + name);
+    }
+    return index;
+  }
+}
+
+/**
+ * A variant of an enum, read either from a string (unit variants) or from an object with a
+ * single entry.
+ */
+export class JsonVariant {
+  constructor(
+    public name: string,
+    private content: JsonValue,
+    private hasContent: boolean,
+  ) {}
+
+  /** Check that the variant has no content, i.e. that it was written as a string or with `null`. */
+  public unit(): void {
+    if (this.content !== null) {
+      throw new Error("Unexpected content for unit variant: " + this.name);
+    }
+  }
+
+  /** Content of a variant that is not a unit variant. */
+  public value(): JsonValue {
+    if (!this.hasContent) {
+      throw new Error("Missing content for variant: " + this.name);
+    }
+    return this.content;
+  }
+}
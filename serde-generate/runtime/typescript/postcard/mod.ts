@@ -0,0 +1,7 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+export { PostcardSerializer } from "./postcardSerializer.ts";
+export { PostcardDeserializer } from "./postcardDeserializer.ts";
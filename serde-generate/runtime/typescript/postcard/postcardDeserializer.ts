@@ -0,0 +1,90 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+import { BinaryDeserializer } from "../serde/binaryDeserializer.ts";
+
+export class PostcardDeserializer extends BinaryDeserializer {
+  private static readonly BIG_1: bigint = BigInt(1);
+
+  public deserializeVarint(bits: number): bigint {
+    let value = BigInt(0);
+    for (let shift = 0; shift < bits; shift += 7) {
+      if (this.offset >= this.buffer.byteLength) {
+        throw new Error("Input is not large enough");
+      }
+      const x = this.deserializeU8();
+      const digit = x & 0x7f;
+      if (bits - shift < 7 && digit >> (bits - shift) != 0) {
+        throw new Error("Overflow while parsing varint-encoded value");
+      }
+      value = value | (BigInt(digit) << BigInt(shift));
+      if ((x & 0x80) == 0) {
+        return value;
+      }
+    }
+    throw new Error("Varint-encoded value is too long");
+  }
+
+  private deserializeZigzag(bits: number): bigint {
+    const value = this.deserializeVarint(bits);
+    return (value >> PostcardDeserializer.BIG_1) ^
+      -(value & PostcardDeserializer.BIG_1);
+  }
+
+  deserializeLen(): number {
+    return Number(this.deserializeVarint(64));
+  }
+
+  public deserializeVariantIndex(): number {
+    return Number(this.deserializeVarint(32));
+  }
+
+  public deserializeChar(): string {
+    const value = this.deserializeStr();
+    if ([...value].length != 1) {
+      throw new Error("Expecting a single character");
+    }
+    return value;
+  }
+
+  public deserializeU16(): number {
+    return Number(this.deserializeVarint(16));
+  }
+
+  public deserializeU32(): number {
+    return Number(this.deserializeVarint(32));
+  }
+
+  public deserializeU64(): bigint {
+    return this.deserializeVarint(64);
+  }
+
+  public deserializeU128(): bigint {
+    return this.deserializeVarint(128);
+  }
+
+  public deserializeI16(): number {
+    return Number(this.deserializeZigzag(16));
+  }
+
+  public deserializeI32(): number {
+    return Number(this.deserializeZigzag(32));
+  }
+
+  public deserializeI64(): bigint {
+    return this.deserializeZigzag(64);
+  }
+
+  public deserializeI128(): bigint {
+    return this.deserializeZigzag(128);
+  }
+
+  checkThatKeySlicesAreIncreasing(
+    key1: [number, number],
+    key2: [number, number],
+  ): void {
+    return;
+  }
+}
@@ -0,0 +1,77 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+import { BinarySerializer } from "../serde/binarySerializer.ts";
+
+export class PostcardSerializer extends BinarySerializer {
+  private static readonly BIG_7: bigint = BigInt(7);
+  private static readonly BIG_7F: bigint = BigInt(0x7f);
+  private static readonly BIG_80: bigint = BigInt(0x80);
+
+  public serializeVarint(value: bigint | number): void {
+    let big = BigInt(value);
+    const bytes: number[] = [];
+    while (big >= PostcardSerializer.BIG_80) {
+      bytes.push(Number(big & PostcardSerializer.BIG_7F) | 0x80);
+      big = big >> PostcardSerializer.BIG_7;
+    }
+    bytes.push(Number(big));
+    this.serialize(new Uint8Array(bytes));
+  }
+
+  // Signed integers use a "zigzag" encoding before the varint encoding.
+  private serializeZigzag(value: bigint | number, bits: number): void {
+    const big = BigInt(value);
+    this.serializeVarint((big << BigInt(1)) ^ (big >> BigInt(bits - 1)));
+  }
+
+  serializeLen(value: number): void {
+    this.serializeVarint(value);
+  }
+
+  public serializeVariantIndex(value: number): void {
+    this.serializeVarint(value);
+  }
+
+  public serializeChar(value: string): void {
+    this.serializeStr(value);
+  }
+
+  public serializeU16(value: number): void {
+    this.serializeVarint(value);
+  }
+
+  public serializeU32(value: number): void {
+    this.serializeVarint(value);
+  }
+
+  public serializeU64(value: BigInt | number): void {
+    this.serializeVarint(BigInt(value.toString()));
+  }
+
+  public serializeU128(value: BigInt | number): void {
+    this.serializeVarint(BigInt(value.toString()));
+  }
+
+  public serializeI16(value: number): void {
+    this.serializeZigzag(value, 16);
+  }
+
+  public serializeI32(value: number): void {
+    this.serializeZigzag(value, 32);
+  }
+
+  public serializeI64(value: bigint | number): void {
+    this.serializeZigzag(value, 64);
+  }
+
+  public serializeI128(value: bigint | number): void {
+    this.serializeZigzag(value, 128);
+  }
+
+  public sortMapEntries(offsets: number[]): void {
+    return;
+  }
+}
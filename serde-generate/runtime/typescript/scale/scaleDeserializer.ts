@@ -0,0 +1,61 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+import { BinaryDeserializer } from "../serde/binaryDeserializer.ts";
+import { MAX_LENGTH } from "./scaleSerializer.ts";
+
+export class ScaleDeserializer extends BinaryDeserializer {
+  deserializeCompactU32(): number {
+    const prefix = this.deserializeU8();
+    let value: number;
+    let minimum: number;
+    switch (prefix % 4) {
+      case 0:
+        return prefix / 4;
+      case 1:
+        value = Math.floor((prefix + this.deserializeU8() * 2 ** 8) / 4);
+        minimum = 2 ** 6;
+        break;
+      case 2: {
+        let bits = prefix;
+        for (const factor of [2 ** 8, 2 ** 16, 2 ** 24]) {
+          bits += this.deserializeU8() * factor;
+        }
+        value = Math.floor(bits / 4);
+        minimum = 2 ** 14;
+        break;
+      }
+      default:
+        if (prefix != 3) {
+          throw new Error("Compact integer does not fit in 32 bits.");
+        }
+        value = this.deserializeU32();
+        minimum = 2 ** 30;
+    }
+    if (value < minimum) {
+      throw new Error("Compact integer is not canonically encoded.");
+    }
+    return value;
+  }
+
+  deserializeLen(): number {
+    const value = this.deserializeCompactU32();
+    if (value > MAX_LENGTH) {
+      throw new Error("Length exceeds the maximum supported value.");
+    }
+    return value;
+  }
+
+  public deserializeVariantIndex(): number {
+    return this.deserializeU8();
+  }
+
+  public checkThatKeySlicesAreIncreasing(
+    key1: [number, number],
+    key2: [number, number],
+  ): void {
+    return;
+  }
+}
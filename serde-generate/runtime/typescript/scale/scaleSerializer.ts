@@ -0,0 +1,43 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+import { BinarySerializer } from "../serde/binarySerializer.ts";
+
+// Maximum length in practice for sequences (e.g. in Java).
+export const MAX_LENGTH = 2 ** 31 - 1;
+
+export class ScaleSerializer extends BinarySerializer {
+  // Arithmetic is used instead of bitwise operators, which are limited to signed 32-bit values.
+  serializeCompactU32(value: number): void {
+    if (value < 2 ** 6) {
+      this.serializeU8(value * 4);
+    } else if (value < 2 ** 14) {
+      this.serializeU16(value * 4 + 1);
+    } else if (value < 2 ** 30) {
+      this.serializeU32(value * 4 + 2);
+    } else {
+      this.serializeU8(3);
+      this.serializeU32(value);
+    }
+  }
+
+  serializeLen(value: number): void {
+    if (value < 0 || value > MAX_LENGTH) {
+      throw new Error("Length exceeds the maximum supported value.");
+    }
+    this.serializeCompactU32(value);
+  }
+
+  public serializeVariantIndex(value: number): void {
+    if (value < 0 || value > 0xff) {
+      throw new Error("Variant index exceeds the maximum supported value.");
+    }
+    this.serializeU8(value);
+  }
+
+  public sortMapEntries(offsets: number[]): void {
+    return;
+  }
+}
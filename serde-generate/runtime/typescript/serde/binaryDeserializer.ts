@@ -22,7 +22,7 @@ export abstract class BinaryDeserializer implements Deserializer {
     this.offset = 0;
   }
 
-  private read(length: number): ArrayBuffer {
+  protected read(length: number): ArrayBuffer {
     const bytes = this.buffer.slice(this.offset, this.offset + length);
     this.offset += length;
     return bytes;
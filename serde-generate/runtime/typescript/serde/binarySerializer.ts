@@ -17,7 +17,7 @@ export abstract class BinarySerializer implements Serializer {
     ? new util.TextEncoder()
     : new TextEncoder();
 
-  private buffer: ArrayBuffer;
+  protected buffer: ArrayBuffer;
   private offset: number;
 
   constructor() {
@@ -0,0 +1,67 @@
+/**
+ * Copyright (c) Facebook, Inc. and its affiliates
+ * SPDX-License-Identifier: MIT OR Apache-2.0
+ */
+
+// Three-way comparisons following the order of Rust's derived `Ord` instances.
+
+export function compareBools(a: boolean, b: boolean): number {
+  return a === b ? 0 : a ? 1 : -1;
+}
+
+export function compareNumbers<T extends number | bigint>(a: T, b: T): number {
+  return a < b ? -1 : a > b ? 1 : 0;
+}
+
+// Rust compares strings by Unicode code points (equivalently, by UTF-8 bytes).
+export function compareStrings(a: string, b: string): number {
+  const x = a[Symbol.iterator]();
+  const y = b[Symbol.iterator]();
+  for (;;) {
+    const i = x.next();
+    const j = y.next();
+    if (i.done || j.done) {
+      return compareBools(!i.done, !j.done);
+    }
+    const result = compareNumbers(i.value.codePointAt(0)!, j.value.codePointAt(0)!);
+    if (result !== 0) {
+      return result;
+    }
+  }
+}
+
+export function compareBytes(a: Uint8Array, b: Uint8Array): number {
+  for (let i = 0; i < a.length && i < b.length; i++) {
+    const result = compareNumbers(a[i], b[i]);
+    if (result !== 0) {
+      return result;
+    }
+  }
+  return compareNumbers(a.length, b.length);
+}
+
+// 32-bit hashes consistent with the comparisons above.
+
+export function hashCombine(seed: number, value: number): number {
+  return (Math.imul(seed ^ 0x9e3779b9, 31) + value) | 0;
+}
+
+export function hashNumber(value: number): number {
+  return value | 0;
+}
+
+export function hashBigInt(value: bigint): number {
+  return hashCombine(Number(BigInt.asIntN(32, value >> 32n)), Number(BigInt.asIntN(32, value)));
+}
+
+export function hashString(value: string): number {
+  let hash = value.length;
+  for (let i = 0; i < value.length; i++) {
+    hash = hashCombine(hash, value.charCodeAt(i));
+  }
+  return hash;
+}
+
+export function hashBytes(value: Uint8Array): number {
+  return value.reduce((hash: number, byte: number) => hashCombine(hash, byte), value.length);
+}
@@ -4,6 +4,7 @@
  */
 
 export * from "./types.ts";
+export * from "./comparisons.ts";
 export * from "./serializer.ts";
 export * from "./deserializer.ts";
 export * from "./binarySerializer.ts";
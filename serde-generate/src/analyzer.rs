@@ -89,3 +89,26 @@ where
     }
     result
 }
+
+/// Find a node that belongs to a cycle of the graph, if any. Nodes are tried by increasing key.
+pub fn find_node_in_cycle<T>(children: &BTreeMap<T, BTreeSet<T>>) -> Option<T>
+where
+    T: Clone + std::cmp::Ord + std::cmp::Eq + std::hash::Hash,
+{
+    children.keys().find_map(|start| {
+        // Depth-first search for a path going back to `start`.
+        let mut seen = HashSet::new();
+        let mut stack: Vec<_> = children[start].iter().collect();
+        while let Some(node) = stack.pop() {
+            if node == start {
+                return Some(start.clone());
+            }
+            if seen.insert(node) {
+                if let Some(next) = children.get(node) {
+                    stack.extend(next.iter());
+                }
+            }
+        }
+        None
+    })
+}
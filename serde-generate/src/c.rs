@@ -0,0 +1,1749 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{
+    analyzer, common,
+    indent::{IndentConfig, IndentedWriter},
+    naming, BincodeOptions, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error,
+    IntEncoding, Result, SourceGenerator, TrailingBytes,
+};
+use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    path::PathBuf,
+};
+
+/// Features supported by the C code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "C",
+    c_style_enums: true,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: &[Encoding::Bincode, Encoding::Bcs],
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: false,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: false,
+    bincode_options: true,
+};
+
+/// Reserved words of C99 (including the macros of `stdbool.h`), which names obtained from
+/// naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "_Bool",
+    "_Complex",
+    "_Imaginary",
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "true",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// Main configuration object for code-generation in C.
+///
+/// The generated header defines a struct (or a tagged union) for each container, prefixed
+/// by the module name (e.g. `my_module_MyStruct`), together with the functions
+/// `my_module_MyStruct_equal` and `my_module_MyStruct_free`. When serialization is enabled,
+/// `my_module_MyStruct_bcs_serialize` writes into a caller-supplied buffer and
+/// `my_module_MyStruct_bcs_deserialize` allocates sequences, maps, strings and recursive
+/// values with a caller-supplied allocator. All these functions return error codes.
+/// With comparisons, `my_module_MyStruct_compare` returns -1, 0 or 1 and
+/// `my_module_MyStruct_hash` returns a hash consistent with `my_module_MyStruct_equal`.
+///
+/// Options, sequences, maps, tuples and fixed-size arrays are mapped to auxiliary structs
+/// with explicit lengths (e.g. `my_module_vector_u8`). Containers used before their
+/// definition (i.e. recursive types) are stored behind a pointer, which must not be `NULL`.
+#[derive(Clone)]
+pub struct CodeGenerator<'a> {
+    /// Language-independent configuration.
+    config: &'a CodeGeneratorConfig,
+    /// Mapping from external type names to suitably prefixed names (e.g. "MyStruct" -> "other_MyStruct").
+    /// Derived from `config.external_definitions`.
+    external_qualified_names: HashMap<String, String>,
+}
+
+/// A type of the generated code with its own functions: either a container of the registry
+/// or an auxiliary struct for an option, a sequence, a map, a tuple or a fixed-size array.
+enum Item<'a> {
+    Container(&'a str, &'a ContainerFormat),
+    Composite(Format),
+}
+
+/// Shared state for the code generation of a C header.
+struct CEmitter<'a, T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// Generator.
+    generator: &'a CodeGenerator<'a>,
+    /// Registry of the generated containers.
+    registry: &'a Registry,
+    /// Prefix of generated names (e.g. "my_module").
+    prefix: String,
+    /// Containers used before their definition. References to them are pointers.
+    boxed: HashSet<String>,
+}
+
+impl<'a> CodeGenerator<'a> {
+    /// Create a C code generator for the given config.
+    pub fn new(config: &'a CodeGeneratorConfig) -> Self {
+        let mut external_qualified_names = HashMap::new();
+        for (namespace, names) in &config.external_definitions {
+            for name in names {
+                external_qualified_names.insert(
+                    name.to_string(),
+                    format!("{}_{}", quote_prefix(namespace), name),
+                );
+            }
+        }
+        Self {
+            config,
+            external_qualified_names,
+        }
+    }
+
+    pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).output(out, &registry);
+        }
+        for (name, format) in registry {
+            Self::check_container(name, format)?;
+        }
+        let mut emitter = CEmitter {
+            out: IndentedWriter::new(out, IndentConfig::Space(4)),
+            generator: self,
+            registry,
+            prefix: quote_prefix(&self.config.module_name),
+            boxed: HashSet::new(),
+        };
+
+        let dependencies = analyzer::get_dependency_map(registry)?;
+        let entries = analyzer::best_effort_topological_sort(&dependencies);
+        let items = emitter.plan_items(&entries);
+
+        emitter.output_preamble()?;
+        for item in &items {
+            if let Item::Container(name, format) = item {
+                if emitter.is_c_style_enum(format) {
+                    emitter.output_c_style_enum(name, format)?;
+                }
+            }
+        }
+        writeln!(emitter.out)?;
+        for item in &items {
+            if !emitter.is_c_style_item(item) {
+                let name = emitter.quote_item_name(item);
+                writeln!(emitter.out, "typedef struct {0} {0};", name)?;
+            }
+        }
+        for item in &items {
+            match item {
+                Item::Container(name, format) => {
+                    if !emitter.is_c_style_enum(format) {
+                        emitter.output_container(name, format)?;
+                    }
+                }
+                Item::Composite(format) => emitter.output_composite(format)?,
+            }
+        }
+        for item in &items {
+            emitter.output_prototypes(item)?;
+        }
+        for item in &items {
+            emitter.output_functions(item)?;
+        }
+        if self.config.serialization {
+            emitter.output_bincode_format()?;
+            for encoding in &self.config.encodings {
+                for name in registry.keys() {
+                    emitter.output_encoding_functions(name, *encoding)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Empty enums and empty arrays cannot be defined in C99.
+    fn check_container(name: &str, format: &ContainerFormat) -> Result<()> {
+        if let ContainerFormat::Enum(variants) = format {
+            if variants.is_empty() {
+                return Err(Error::unsupported(CAPABILITIES.language, "empty enums").at(name));
+            }
+        }
+        let mut result = Ok(());
+        format
+            .visit(&mut |format| {
+                if result.is_ok() {
+                    if let Format::TupleArray { size: 0, .. } = format {
+                        result = Err(Error::unsupported(
+                            CAPABILITIES.language,
+                            "fixed-size arrays of length 0",
+                        ));
+                    }
+                }
+                Ok(())
+            })
+            .unwrap();
+        result.map_err(|error| error.at(name))
+    }
+}
+
+/// Prefix of the C names generated for a module or a namespace (e.g. "a::b" -> "a_b").
+fn quote_prefix(module_name: &str) -> String {
+    module_name.replace("::", "_").replace('.', "_")
+}
+
+/// Fields of a struct container, as in the C++ code generator.
+fn container_fields(name: &str, format: &ContainerFormat) -> Result<Vec<Named<Format>>> {
+    use ContainerFormat::*;
+    Ok(match format {
+        UnitStruct => Vec::new(),
+        NewTypeStruct(format) => vec![Named {
+            name: "value".to_string(),
+            value: format.as_ref().clone(),
+        }],
+        TupleStruct(formats) => vec![Named {
+            name: "value".to_string(),
+            value: Format::Tuple(formats.clone()),
+        }],
+        Struct(fields) => fields.clone(),
+        OpenEnum(_) => common::open_enum_fields(),
+        TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("C", name)),
+        FlattenedStruct(_) => return Err(common::flattened_struct_not_supported("C", name)),
+        Enum(_) => panic!("unexpected value"),
+    })
+}
+
+/// Fields of the member of a tagged union for the given variant. Variants without fields
+/// have no member.
+fn variant_fields(format: &VariantFormat) -> Vec<Named<Format>> {
+    use VariantFormat::*;
+    match format {
+        Unit => Vec::new(),
+        NewType(format) => vec![Named {
+            name: "value".to_string(),
+            value: format.as_ref().clone(),
+        }],
+        Tuple(formats) if formats.is_empty() => Vec::new(),
+        Tuple(formats) => vec![Named {
+            name: "value".to_string(),
+            value: Format::Tuple(formats.clone()),
+        }],
+        Struct(fields) => fields.clone(),
+        Variable(_) => panic!("incorrect value"),
+    }
+}
+
+fn is_composite(format: &Format) -> bool {
+    use Format::*;
+    matches!(
+        format,
+        Option(_) | Seq(_) | Map { .. } | Tuple(_) | TupleArray { .. }
+    )
+}
+
+impl<'a, T> CEmitter<'a, T>
+where
+    T: std::io::Write,
+{
+    /// Compute the order of type definitions and the containers that must be boxed.
+    fn plan_items(&mut self, entries: &[&'a str]) -> Vec<Item<'a>> {
+        let mut known = HashSet::new();
+        let mut items = Vec::new();
+        // C-style enums have no dependencies and are defined first.
+        for name in entries {
+            if self.is_c_style_enum(&self.registry[*name]) {
+                known.insert(name.to_string());
+            }
+        }
+        for name in entries {
+            let format = &self.registry[*name];
+            for field in self.container_formats(format) {
+                self.plan_by_value(&field, &mut known, &mut items);
+            }
+            known.insert(name.to_string());
+            items.push(Item::Container(name, format));
+        }
+        items
+    }
+
+    /// Formats used by the fields of a container, including the fields of variants.
+    fn container_formats(&self, format: &ContainerFormat) -> Vec<Format> {
+        match format {
+            ContainerFormat::Enum(variants) => variants
+                .values()
+                .flat_map(|variant| variant_fields(&variant.value))
+                .map(|field| field.value)
+                .collect(),
+            _ => container_fields("", format)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|field| field.value)
+                .collect(),
+        }
+    }
+
+    fn plan_by_value(
+        &mut self,
+        format: &Format,
+        known: &mut HashSet<String>,
+        items: &mut Vec<Item<'a>>,
+    ) {
+        match format {
+            Format::TypeName(name) => {
+                if self.local_container(name).is_some() && !known.contains(name) {
+                    self.boxed.insert(name.clone());
+                }
+            }
+            _ => self.plan_composite(format, known, items),
+        }
+    }
+
+    fn plan_composite(
+        &mut self,
+        format: &Format,
+        known: &mut HashSet<String>,
+        items: &mut Vec<Item<'a>>,
+    ) {
+        use Format::*;
+        let key = common::mangle_type(format);
+        if !is_composite(format) || known.contains(&key) {
+            return;
+        }
+        match format {
+            Option(format)
+            | TupleArray {
+                content: format, ..
+            } => self.plan_by_value(format, known, items),
+            Tuple(formats) => {
+                for format in formats {
+                    self.plan_by_value(format, known, items);
+                }
+            }
+            // Elements of sequences and maps are allocated separately.
+            Seq(format) => self.plan_composite(format, known, items),
+            Map { key, value } => {
+                self.plan_composite(key, known, items);
+                self.plan_composite(value, known, items);
+            }
+            _ => unreachable!(),
+        }
+        known.insert(key);
+        items.push(Item::Composite(format.clone()));
+    }
+
+    fn is_c_style_enum(&self, format: &ContainerFormat) -> bool {
+        match format {
+            ContainerFormat::Enum(variants) => {
+                self.generator.config.c_style_enums
+                    && variants.values().all(|f| f.value == VariantFormat::Unit)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_c_style_item(&self, item: &Item) -> bool {
+        matches!(item, Item::Container(_, format) if self.is_c_style_enum(format))
+    }
+
+    /// The definition of a container of the registry, unless it is declared external.
+    fn local_container(&self, name: &str) -> Option<&'a ContainerFormat> {
+        if self.generator.external_qualified_names.contains_key(name) {
+            return None;
+        }
+        self.registry.get(name)
+    }
+
+    fn is_boxed(&self, format: &Format) -> bool {
+        matches!(format, Format::TypeName(name) if self.boxed.contains(name))
+    }
+
+    /// Whether values of the given format may be encoded with no bytes at all.
+    fn may_be_empty(&self, format: &Format, visited: &mut HashSet<String>) -> bool {
+        use Format::*;
+        match format {
+            Unit => true,
+            TypeName(name) => {
+                let container = match self.local_container(name) {
+                    Some(container) => container,
+                    // External definitions are unknown.
+                    None => return true,
+                };
+                if !visited.insert(name.clone()) {
+                    return false;
+                }
+                let result = !matches!(
+                    container,
+                    ContainerFormat::Enum(_) | ContainerFormat::OpenEnum(_)
+                ) && self
+                    .container_formats(container)
+                    .iter()
+                    .all(|format| self.may_be_empty(format, visited));
+                visited.remove(name);
+                result
+            }
+            Tuple(formats) => formats
+                .iter()
+                .all(|format| self.may_be_empty(format, visited)),
+            TupleArray { content, .. } => self.may_be_empty(content, visited),
+            _ => false,
+        }
+    }
+
+    /// Whether values of the given format may own allocated memory.
+    fn needs_free(&self, format: &Format, visited: &mut HashSet<String>) -> bool {
+        use Format::*;
+        match format {
+            Str | Bytes | Seq(_) | Map { .. } => true,
+            Option(format)
+            | TupleArray {
+                content: format, ..
+            } => self.needs_free(format, visited),
+            Tuple(formats) => formats
+                .iter()
+                .any(|format| self.needs_free(format, visited)),
+            TypeName(name) => {
+                if self.boxed.contains(name) {
+                    return true;
+                }
+                let container = match self.local_container(name) {
+                    Some(container) => container,
+                    None => return true,
+                };
+                if !visited.insert(name.clone()) {
+                    return false;
+                }
+                let result = self
+                    .container_formats(container)
+                    .iter()
+                    .any(|format| self.needs_free(format, visited));
+                visited.remove(name);
+                result
+            }
+            _ => false,
+        }
+    }
+
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"#pragma once
+
+#include "serde.h""#
+        )?;
+        if self.generator.config.serialization {
+            for encoding in &self.generator.config.encodings {
+                writeln!(self.out, "#include \"{}.h\"", encoding.name())?;
+            }
+        }
+        // External definitions are expected in headers named after their namespace,
+        // as generated for other modules.
+        for namespace in self.generator.config.external_definitions.keys() {
+            if !namespace.is_empty() {
+                writeln!(self.out, "#include \"{}.h\"", namespace)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn output_comment(&mut self, path: &[&str]) -> std::io::Result<()> {
+        let mut path: Vec<_> = path.iter().map(|s| s.to_string()).collect();
+        path.insert(0, self.generator.config.module_name.clone());
+        if let Some(doc) = self.generator.config.comments.get(&path) {
+            let text = textwrap::indent(doc, "/// ").replace("\n\n", "\n///\n");
+            write!(self.out, "{}", text)?;
+        }
+        Ok(())
+    }
+
+    fn output_custom_code(&mut self, name: &str) -> std::io::Result<()> {
+        let path = vec![self.generator.config.module_name.clone(), name.to_string()];
+        if let Some(code) = self.generator.config.custom_code.get(&path) {
+            writeln!(self.out, "\n{}", code)?;
+        }
+        Ok(())
+    }
+
+    /// Compute the C name of a container, an external definition or an auxiliary struct.
+    fn quote_name(&self, format: &Format) -> String {
+        match format {
+            Format::TypeName(name) => self
+                .generator
+                .external_qualified_names
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| format!("{}_{}", self.prefix, name)),
+            _ => format!("{}_{}", self.prefix, common::mangle_type(format)),
+        }
+    }
+
+    fn quote_item_name(&self, item: &Item) -> String {
+        match item {
+            Item::Container(name, _) => format!("{}_{}", self.prefix, name),
+            Item::Composite(format) => self.quote_name(format),
+        }
+    }
+
+    fn quote_type(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            Unit => "serde_unit_t".into(),
+            Bool => "bool".into(),
+            I8 => "int8_t".into(),
+            I16 => "int16_t".into(),
+            I32 => "int32_t".into(),
+            I64 => "int64_t".into(),
+            I128 => "serde_int128_t".into(),
+            U8 => "uint8_t".into(),
+            U16 => "uint16_t".into(),
+            U32 => "uint32_t".into(),
+            U64 => "uint64_t".into(),
+            U128 => "serde_uint128_t".into(),
+            F32 => "float".into(),
+            F64 => "double".into(),
+            Char => "uint32_t".into(),
+            Str => "serde_str_t".into(),
+            Bytes => "serde_bytes_t".into(),
+            Variable(_) => panic!("unexpected value"),
+            _ => self.quote_name(format),
+        }
+    }
+
+    /// Declaration of a field or an array. Boxed values are declared as pointers.
+    fn quote_declaration(&self, format: &Format, indirect: bool, name: &str) -> String {
+        if indirect {
+            format!("{} *{}", self.quote_type(format), name)
+        } else {
+            format!("{} {}", self.quote_type(format), name)
+        }
+    }
+
+    /// Boolean expression testing the equality of two values, unless trivially true.
+    fn quote_equal(&self, format: &Format, indirect: bool, lhs: &str, rhs: &str) -> Option<String> {
+        use Format::*;
+        Some(match format {
+            Unit => return None,
+            Bool | I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64 | F32 | F64 | Char => {
+                format!("{} == {}", lhs, rhs)
+            }
+            I128 => format!("serde_int128_equal({}, {})", lhs, rhs),
+            U128 => format!("serde_uint128_equal({}, {})", lhs, rhs),
+            Str => format!("serde_str_equal({}, {})", lhs, rhs),
+            Bytes => format!("serde_bytes_equal({}, {})", lhs, rhs),
+            _ if indirect => format!("{}_equal({}, {})", self.quote_name(format), lhs, rhs),
+            _ => format!("{}_equal(&{}, &{})", self.quote_name(format), lhs, rhs),
+        })
+    }
+
+    /// Expression comparing two values as -1, 0 or 1, unless trivially 0.
+    fn quote_compare(
+        &self,
+        format: &Format,
+        indirect: bool,
+        lhs: &str,
+        rhs: &str,
+    ) -> Option<String> {
+        use Format::*;
+        Some(match format {
+            Unit => return None,
+            Bool | I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64 | Char => {
+                format!("SERDE_COMPARE({}, {})", lhs, rhs)
+            }
+            F32 | F64 => panic!("unexpected value"),
+            I128 => format!("serde_int128_compare({}, {})", lhs, rhs),
+            U128 => format!("serde_uint128_compare({}, {})", lhs, rhs),
+            Str => format!("serde_str_compare({}, {})", lhs, rhs),
+            Bytes => format!("serde_bytes_compare({}, {})", lhs, rhs),
+            _ if indirect => format!("{}_compare({}, {})", self.quote_name(format), lhs, rhs),
+            _ => format!("{}_compare(&{}, &{})", self.quote_name(format), lhs, rhs),
+        })
+    }
+
+    /// Expression computing the hash of a value, unless trivially constant.
+    fn quote_hash(&self, format: &Format, indirect: bool, place: &str) -> Option<String> {
+        use Format::*;
+        Some(match format {
+            Unit => return None,
+            Bool | I8 | I16 | I32 | I64 | U8 | U16 | U32 | U64 | Char => {
+                format!("(uint64_t){}", place)
+            }
+            F32 | F64 => panic!("unexpected value"),
+            I128 => format!("serde_int128_hash({})", place),
+            U128 => format!("serde_uint128_hash({})", place),
+            Str => format!("serde_str_hash({})", place),
+            Bytes => format!("serde_bytes_hash({})", place),
+            _ if indirect => format!("{}_hash({})", self.quote_name(format), place),
+            _ => format!("{}_hash(&{})", self.quote_name(format), place),
+        })
+    }
+
+    /// Statement releasing the memory owned by a value, if any.
+    fn quote_free(&self, format: &Format, indirect: bool, place: &str) -> Option<String> {
+        use Format::*;
+        if !self.needs_free(format, &mut HashSet::new()) {
+            return None;
+        }
+        Some(match format {
+            Str => format!("serde_str_free(&{}, allocator);", place),
+            Bytes => format!("serde_bytes_free(&{}, allocator);", place),
+            _ if indirect => format!(
+                "{}_free_boxed({}, allocator);",
+                self.quote_name(format),
+                place
+            ),
+            _ => format!("{}_free(&{}, allocator);", self.quote_name(format), place),
+        })
+    }
+
+    fn quote_serialize(&self, format: &Format, indirect: bool, place: &str) -> String {
+        if is_composite(format) || matches!(format, Format::TypeName(_)) {
+            let reference = if indirect {
+                place.to_string()
+            } else {
+                format!("&{}", place)
+            };
+            format!(
+                "SERDE_TRY({}_serialize({}, serializer));",
+                self.quote_name(format),
+                reference
+            )
+        } else {
+            format!(
+                "SERDE_TRY(serde_serialize_{}(serializer, {}));",
+                common::mangle_type(format),
+                place
+            )
+        }
+    }
+
+    fn quote_deserialize(&self, format: &Format, indirect: bool, place: &str) -> String {
+        if indirect {
+            format!(
+                "SERDE_TRY({}_deserialize_boxed(&{}, deserializer));",
+                self.quote_name(format),
+                place
+            )
+        } else if is_composite(format) || matches!(format, Format::TypeName(_)) {
+            format!(
+                "SERDE_TRY({}_deserialize(&{}, deserializer));",
+                self.quote_name(format),
+                place
+            )
+        } else {
+            format!(
+                "SERDE_TRY(serde_deserialize_{}(deserializer, &{}));",
+                common::mangle_type(format),
+                place
+            )
+        }
+    }
+
+    /// Minimal size of encoded elements used to guard allocations.
+    fn quote_min_encoded_size(&self, formats: &[&Format]) -> usize {
+        if formats
+            .iter()
+            .all(|format| self.may_be_empty(format, &mut HashSet::new()))
+        {
+            0
+        } else {
+            1
+        }
+    }
+
+    fn output_fields(&mut self, path: &[&str], fields: &[Named<Format>]) -> Result<()> {
+        if fields.is_empty() {
+            // C99 does not allow empty structs.
+            writeln!(self.out, "uint8_t unused;")?;
+        }
+        for field in fields {
+            let mut path = path.to_vec();
+            path.push(&field.name);
+            self.output_comment(&path)?;
+            writeln!(
+                self.out,
+                "{};",
+                self.quote_declaration(&field.value, self.is_boxed(&field.value), &field.name)
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_c_style_enum(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
+        let variants = match format {
+            ContainerFormat::Enum(variants) => variants,
+            _ => unreachable!(),
+        };
+        let qname = format!("{}_{}", self.prefix, name);
+        writeln!(self.out)?;
+        self.output_comment(&[name])?;
+        writeln!(self.out, "typedef enum {} {{", qname)?;
+        self.out.indent();
+        for (expected_index, (index, variant)) in variants.iter().enumerate() {
+            assert_eq!(*index, expected_index as u32);
+            self.output_comment(&[name, &variant.name])?;
+            writeln!(self.out, "{}_{} = {},", qname, variant.name, index)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}} {};", qname)?;
+        Ok(())
+    }
+
+    fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
+        let qname = format!("{}_{}", self.prefix, name);
+        writeln!(self.out)?;
+        if let ContainerFormat::Enum(variants) = format {
+            writeln!(self.out, "typedef enum {}_tag {{", qname)?;
+            self.out.indent();
+            for (expected_index, (index, variant)) in variants.iter().enumerate() {
+                assert_eq!(*index, expected_index as u32);
+                self.output_comment(&[name, &variant.name])?;
+                writeln!(self.out, "{}_{} = {},", qname, variant.name, index)?;
+            }
+            self.out.unindent();
+            writeln!(self.out, "}} {}_tag;\n", qname)?;
+            self.output_comment(&[name])?;
+            writeln!(self.out, "struct {} {{", qname)?;
+            self.out.indent();
+            writeln!(self.out, "{}_tag tag;", qname)?;
+            if variants
+                .values()
+                .any(|variant| !variant_fields(&variant.value).is_empty())
+            {
+                writeln!(self.out, "union {{")?;
+                self.out.indent();
+                for variant in variants.values() {
+                    let fields = variant_fields(&variant.value);
+                    if fields.is_empty() {
+                        continue;
+                    }
+                    writeln!(self.out, "struct {{")?;
+                    self.out.indent();
+                    self.output_fields(&[name, &variant.name], &fields)?;
+                    self.out.unindent();
+                    writeln!(self.out, "}} {};", variant.name)?;
+                }
+                self.out.unindent();
+                writeln!(self.out, "}} value;")?;
+            }
+        } else {
+            let fields = container_fields(name, format)?;
+            self.output_comment(&[name])?;
+            writeln!(self.out, "struct {} {{", qname)?;
+            self.out.indent();
+            self.output_fields(&[name], &fields)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}};")?;
+        self.output_custom_code(name)?;
+        Ok(())
+    }
+
+    fn output_composite(&mut self, format: &Format) -> Result<()> {
+        use Format::*;
+        writeln!(self.out, "\nstruct {} {{", self.quote_name(format))?;
+        self.out.indent();
+        match format {
+            Option(format) => {
+                writeln!(self.out, "bool has_value;")?;
+                writeln!(
+                    self.out,
+                    "{};",
+                    self.quote_declaration(format, self.is_boxed(format), "value")
+                )?;
+            }
+            Seq(format) => {
+                writeln!(
+                    self.out,
+                    "{};",
+                    self.quote_declaration(format, true, "data")
+                )?;
+                writeln!(self.out, "size_t len;")?;
+            }
+            Map { key, value } => {
+                writeln!(self.out, "{};", self.quote_declaration(key, true, "keys"))?;
+                writeln!(
+                    self.out,
+                    "{};",
+                    self.quote_declaration(value, true, "values")
+                )?;
+                writeln!(self.out, "size_t len;")?;
+            }
+            Tuple(formats) => {
+                let fields = formats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, format)| Named {
+                        name: format!("field{}", i),
+                        value: format.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                self.output_fields(&[], &fields)?;
+            }
+            TupleArray { content, size } => {
+                writeln!(
+                    self.out,
+                    "{};",
+                    self.quote_declaration(
+                        content,
+                        self.is_boxed(content),
+                        &format!("data[{}]", size)
+                    )
+                )?;
+            }
+            _ => unreachable!(),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}};")?;
+        Ok(())
+    }
+
+    fn item_signatures(&self, item: &Item) -> Vec<String> {
+        let name = self.quote_item_name(item);
+        let mut signatures = vec![
+            format!("bool {0}_equal(const {0} *lhs, const {0} *rhs)", name),
+            format!(
+                "void {0}_free({0} *value, const serde_allocator_t *allocator)",
+                name
+            ),
+        ];
+        let boxed = matches!(item, Item::Container(name, _) if self.boxed.contains(*name));
+        if boxed {
+            signatures.push(format!(
+                "void {0}_free_boxed({0} *value, const serde_allocator_t *allocator)",
+                name
+            ));
+        }
+        if self.generator.config.comparisons {
+            signatures.push(format!(
+                "int {0}_compare(const {0} *lhs, const {0} *rhs)",
+                name
+            ));
+            signatures.push(format!("uint64_t {0}_hash(const {0} *value)", name));
+        }
+        if self.generator.config.serialization {
+            signatures.push(format!(
+                "serde_error_t {0}_serialize(const {0} *value, serde_serializer_t *serializer)",
+                name
+            ));
+            signatures.push(format!(
+                "serde_error_t {0}_deserialize({0} *value, serde_deserializer_t *deserializer)",
+                name
+            ));
+            if boxed {
+                signatures.push(format!(
+                    "serde_error_t {0}_deserialize_boxed({0} **value, serde_deserializer_t *deserializer)",
+                    name
+                ));
+            }
+        }
+        signatures
+    }
+
+    fn output_prototypes(&mut self, item: &Item) -> Result<()> {
+        writeln!(self.out)?;
+        for signature in self.item_signatures(item) {
+            writeln!(self.out, "static inline {};", signature)?;
+        }
+        Ok(())
+    }
+
+    /// Output a function with the given body. Nested blocks are indented in the body.
+    fn output_function(&mut self, signature: &str, body: &[String]) -> Result<()> {
+        writeln!(self.out, "\nstatic inline {} {{", signature)?;
+        self.out.indent();
+        for line in body {
+            writeln!(self.out, "{}", line)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_functions(&mut self, item: &Item) -> Result<()> {
+        let signatures = self.item_signatures(item);
+        let mut signatures = signatures.iter();
+        let (equal, free, serialize, deserialize) = match item {
+            Item::Container(name, format) => self.container_bodies(name, format)?,
+            Item::Composite(format) => self.composite_bodies(format),
+        };
+        self.output_function(signatures.next().unwrap(), &equal)?;
+        self.output_function(signatures.next().unwrap(), &free)?;
+        let name = self.quote_item_name(item);
+        if let Item::Container(container, _) = item {
+            if self.boxed.contains(*container) {
+                self.output_function(
+                    signatures.next().unwrap(),
+                    &[
+                        "if (value != NULL) {".to_string(),
+                        format!("    {}_free(value, allocator);", name),
+                        "    serde_free(allocator, value);".to_string(),
+                        "}".to_string(),
+                    ],
+                )?;
+            }
+        }
+        if self.generator.config.comparisons {
+            if let Item::Composite(Format::Map { key, .. }) = item {
+                let test = |other: &str| {
+                    self.quote_compare(key, false, "map->keys[i]", other)
+                        .unwrap_or_else(|| "0".to_string())
+                };
+                self.output_function(
+                    &format!(
+                        "size_t {0}_next_key(const {0} *map, size_t previous)",
+                        name
+                    ),
+                    &[
+                        "size_t next = map->len;".to_string(),
+                        for_loop(
+                            "map->len",
+                            &format!(
+                                "if ((previous == map->len || {} > 0) && (next == map->len || {} < 0)) {{\n    next = i;\n}}",
+                                test("map->keys[previous]"),
+                                test("map->keys[next]"),
+                            ),
+                        ),
+                        "return next;".to_string(),
+                    ],
+                )?;
+            }
+            let (compare, hash) = self.comparison_bodies(item)?;
+            self.output_function(signatures.next().unwrap(), &compare)?;
+            self.output_function(signatures.next().unwrap(), &hash)?;
+        }
+        if !self.generator.config.serialization {
+            return Ok(());
+        }
+        if let Item::Composite(Format::Map { key, value }) = item {
+            for (suffix, format, place) in [("key", key, "keys"), ("value", value, "values")] {
+                self.output_function(
+                    &format!(
+                        "serde_error_t {0}_serialize_{1}(const void *map, size_t index, serde_serializer_t *serializer)",
+                        name, suffix
+                    ),
+                    &[
+                        format!("const {} *value = map;", name),
+                        self.quote_serialize(format, false, &format!("value->{}[index]", place)),
+                        "return SERDE_OK;".to_string(),
+                    ],
+                )?;
+            }
+        }
+        self.output_function(signatures.next().unwrap(), &serialize)?;
+        self.output_function(signatures.next().unwrap(), &deserialize)?;
+        if let Item::Container(container, _) = item {
+            if self.boxed.contains(*container) {
+                self.output_function(
+                    signatures.next().unwrap(),
+                    &[
+                        "void *box;".to_string(),
+                        format!(
+                            "SERDE_TRY(serde_deserializer_alloc(deserializer, 1, sizeof({}), 0, &box));",
+                            name
+                        ),
+                        "*value = box;".to_string(),
+                        format!("return {}_deserialize(*value, deserializer);", name),
+                    ],
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Bodies of the functions `equal`, `free`, `serialize`, and `deserialize` of a container.
+    #[allow(clippy::type_complexity)]
+    fn container_bodies(
+        &self,
+        name: &str,
+        format: &ContainerFormat,
+    ) -> Result<(Vec<String>, Vec<String>, Vec<String>, Vec<String>)> {
+        let qname = format!("{}_{}", self.prefix, name);
+        let mut equal = Vec::new();
+        let mut free = Vec::new();
+        let mut serialize =
+            vec!["SERDE_TRY(serde_serializer_increase_container_depth(serializer));".to_string()];
+        let mut deserialize = Vec::new();
+        let mut deserialize_fields = Vec::new();
+        match format {
+            ContainerFormat::Enum(variants) if self.is_c_style_enum(format) => {
+                equal.push("return *lhs == *rhs;".to_string());
+                serialize.insert(
+                    0,
+                    format!(
+                        "if ((uint32_t)*value >= {}) {{\n    return SERDE_ERROR_INVALID_VALUE;\n}}",
+                        variants.len()
+                    ),
+                );
+                serialize.push(
+                    "SERDE_TRY(serde_serialize_variant_index(serializer, (uint32_t)*value));"
+                        .to_string(),
+                );
+                deserialize.push("uint32_t index;".to_string());
+                deserialize_fields.push(
+                    "SERDE_TRY(serde_deserialize_variant_index(deserializer, &index));".to_string(),
+                );
+                deserialize_fields.push(format!(
+                    "if (index >= {}) {{\n    return SERDE_ERROR_INVALID_INPUT;\n}}",
+                    variants.len()
+                ));
+                deserialize_fields.push(format!("*value = ({})index;", qname));
+            }
+            ContainerFormat::Enum(variants) => {
+                equal.push("if (lhs->tag != rhs->tag) {\n    return false;\n}".to_string());
+                serialize.insert(
+                    0,
+                    format!(
+                        "if ((uint32_t)value->tag >= {}) {{\n    return SERDE_ERROR_INVALID_VALUE;\n}}",
+                        variants.len()
+                    ),
+                );
+                serialize.push(
+                    "SERDE_TRY(serde_serialize_variant_index(serializer, (uint32_t)value->tag));"
+                        .to_string(),
+                );
+                deserialize.push("uint32_t index;".to_string());
+                deserialize_fields.push(
+                    "SERDE_TRY(serde_deserialize_variant_index(deserializer, &index));".to_string(),
+                );
+                deserialize_fields.push(format!(
+                    "if (index >= {}) {{\n    return SERDE_ERROR_INVALID_INPUT;\n}}",
+                    variants.len()
+                ));
+                deserialize_fields.push(format!("value->tag = ({}_tag)index;", qname));
+                let mut equal_cases = Vec::new();
+                let mut free_cases = Vec::new();
+                let mut serialize_cases = Vec::new();
+                let mut deserialize_cases = Vec::new();
+                for variant in variants.values() {
+                    let fields = variant_fields(&variant.value);
+                    if fields.is_empty() {
+                        continue;
+                    }
+                    let case = format!("case {}_{}:", qname, variant.name);
+                    let (mut e, mut f, mut s, mut d) = (vec![], vec![], vec![], vec![]);
+                    for field in &fields {
+                        let place = format!("value.{}.{}", variant.name, field.name);
+                        self.push_field_statements(
+                            &field.value,
+                            &place,
+                            (&mut e, &mut f, &mut s, &mut d),
+                        );
+                    }
+                    for (cases, statements) in [
+                        (&mut equal_cases, e),
+                        (&mut free_cases, f),
+                        (&mut serialize_cases, s),
+                        (&mut deserialize_cases, d),
+                    ] {
+                        if !statements.is_empty() {
+                            cases.push(case.clone());
+                            cases.extend(statements.iter().map(|s| indent_lines(s)));
+                            cases.push("    break;".to_string());
+                        }
+                    }
+                }
+                equal.extend(switch_statement("lhs->tag", equal_cases));
+                free.extend(switch_statement("value->tag", free_cases));
+                serialize.extend(switch_statement("value->tag", serialize_cases));
+                deserialize_fields.extend(switch_statement("value->tag", deserialize_cases));
+            }
+            _ => {
+                for field in container_fields(name, format)? {
+                    self.push_field_statements(
+                        &field.value,
+                        &field.name,
+                        (
+                            &mut equal,
+                            &mut free,
+                            &mut serialize,
+                            &mut deserialize_fields,
+                        ),
+                    );
+                }
+            }
+        }
+        if !equal.iter().any(|s| s.starts_with("return")) {
+            if equal.is_empty() {
+                equal.push("(void)lhs;\n(void)rhs;".to_string());
+            }
+            equal.push("return true;".to_string());
+        }
+        if free.is_empty() {
+            free.push("(void)value;\n(void)allocator;".to_string());
+        }
+        if serialize.len() == 1 {
+            serialize.insert(0, "(void)value;".to_string());
+        }
+        serialize.push("serde_serializer_decrease_container_depth(serializer);".to_string());
+        serialize.push("return SERDE_OK;".to_string());
+        if deserialize_fields.is_empty() {
+            deserialize.push("(void)value;".to_string());
+        }
+        deserialize.push(
+            "SERDE_TRY(serde_deserializer_increase_container_depth(deserializer));".to_string(),
+        );
+        deserialize.extend(deserialize_fields);
+        deserialize.push("serde_deserializer_decrease_container_depth(deserializer);".to_string());
+        deserialize.push("return SERDE_OK;".to_string());
+        Ok((equal, free, serialize, deserialize))
+    }
+
+    /// Add the statements for the field `place` (relative to `value`, `lhs`, or `rhs`) to the
+    /// bodies of the functions `equal`, `free`, `serialize`, and `deserialize`.
+    fn push_field_statements(
+        &self,
+        format: &Format,
+        place: &str,
+        (equal, free, serialize, deserialize): (
+            &mut Vec<String>,
+            &mut Vec<String>,
+            &mut Vec<String>,
+            &mut Vec<String>,
+        ),
+    ) {
+        let indirect = self.is_boxed(format);
+        if let Some(test) = self.quote_equal(
+            format,
+            indirect,
+            &format!("lhs->{}", place),
+            &format!("rhs->{}", place),
+        ) {
+            equal.push(format!("if (!({})) {{\n    return false;\n}}", test));
+        }
+        if let Some(statement) = self.quote_free(format, indirect, &format!("value->{}", place)) {
+            free.push(statement);
+        }
+        serialize.push(self.quote_serialize(format, indirect, &format!("value->{}", place)));
+        deserialize.push(self.quote_deserialize(format, indirect, &format!("value->{}", place)));
+    }
+
+    /// Bodies of the functions `compare` and `hash` of an item. Variants are ordered by index
+    /// and the entries of maps by key, as in Rust. Hashes of maps do not depend on the order
+    /// of entries.
+    fn comparison_bodies(&self, item: &Item) -> Result<(Vec<String>, Vec<String>)> {
+        use Format::*;
+        let mut compare = Vec::new();
+        let mut hash = vec!["uint64_t hash = 0;".to_string()];
+        match item {
+            Item::Container(_, format) if self.is_c_style_enum(format) => {
+                compare.push("return SERDE_COMPARE(*lhs, *rhs);".to_string());
+                hash = vec!["return (uint64_t)*value;".to_string()];
+            }
+            Item::Container(name, ContainerFormat::Enum(variants)) => {
+                let qname = format!("{}_{}", self.prefix, name);
+                compare.push("SERDE_TRY_COMPARE(SERDE_COMPARE(lhs->tag, rhs->tag));".to_string());
+                hash = vec!["uint64_t hash = (uint64_t)value->tag;".to_string()];
+                let mut compare_cases = Vec::new();
+                let mut hash_cases = Vec::new();
+                for variant in variants.values() {
+                    let case = format!("case {}_{}:", qname, variant.name);
+                    let (mut c, mut h) = (vec![], vec![]);
+                    for field in variant_fields(&variant.value) {
+                        let place = format!("value.{}.{}", variant.name, field.name);
+                        self.push_comparison_statements(&field.value, &place, (&mut c, &mut h));
+                    }
+                    for (cases, statements) in [(&mut compare_cases, c), (&mut hash_cases, h)] {
+                        if !statements.is_empty() {
+                            cases.push(case.clone());
+                            cases.extend(statements.iter().map(|s| indent_lines(s)));
+                            cases.push("    break;".to_string());
+                        }
+                    }
+                }
+                compare.extend(switch_statement("lhs->tag", compare_cases));
+                hash.extend(switch_statement("value->tag", hash_cases));
+            }
+            Item::Container(name, format) => {
+                for field in container_fields(name, format)? {
+                    self.push_comparison_statements(
+                        &field.value,
+                        &field.name,
+                        (&mut compare, &mut hash),
+                    );
+                }
+            }
+            Item::Composite(Option(content)) => {
+                let indirect = self.is_boxed(content);
+                compare.push("if (!lhs->has_value || !rhs->has_value) {\n    return SERDE_COMPARE(lhs->has_value, rhs->has_value);\n}".to_string());
+                if let Some(test) =
+                    self.quote_compare(content, indirect, "lhs->value", "rhs->value")
+                {
+                    compare.push(format!("return {};", test));
+                }
+                hash = vec!["if (!value->has_value) {\n    return 0;\n}".to_string()];
+                hash.push(match self.quote_hash(content, indirect, "value->value") {
+                    Some(expr) => format!("return serde_hash_combine(1, {});", expr),
+                    None => "return 1;".to_string(),
+                });
+            }
+            Item::Composite(Seq(content)) => {
+                if let Some(test) =
+                    self.quote_compare(content, false, "lhs->data[i]", "rhs->data[i]")
+                {
+                    compare.push(format!(
+                        "for (size_t i = 0; i < lhs->len && i < rhs->len; i++) {{\n    SERDE_TRY_COMPARE({});\n}}",
+                        test
+                    ));
+                }
+                compare.push("return SERDE_COMPARE(lhs->len, rhs->len);".to_string());
+                hash = vec!["uint64_t hash = (uint64_t)value->len;".to_string()];
+                if let Some(expr) = self.quote_hash(content, false, "value->data[i]") {
+                    hash.push(for_loop(
+                        "value->len",
+                        &format!("hash = serde_hash_combine(hash, {});", expr),
+                    ));
+                }
+            }
+            Item::Composite(format @ Map { key, value }) => {
+                let name = self.quote_name(format);
+                let tests = [
+                    self.quote_compare(key, false, "lhs->keys[i]", "rhs->keys[j]"),
+                    self.quote_compare(value, false, "lhs->values[i]", "rhs->values[j]"),
+                ];
+                let mut body = vec![
+                    format!("i = {}_next_key(lhs, i);", name),
+                    format!("j = {}_next_key(rhs, j);", name),
+                ];
+                body.extend(
+                    tests
+                        .iter()
+                        .flatten()
+                        .map(|test| format!("SERDE_TRY_COMPARE({});", test)),
+                );
+                compare.push("size_t i = lhs->len;\nsize_t j = rhs->len;".to_string());
+                compare.push(format!(
+                    "for (size_t k = 0; k < lhs->len && k < rhs->len; k++) {{\n{}\n}}",
+                    indent_lines(&body.join("\n"))
+                ));
+                compare.push("return SERDE_COMPARE(lhs->len, rhs->len);".to_string());
+                let hashes = [
+                    self.quote_hash(key, false, "value->keys[i]"),
+                    self.quote_hash(value, false, "value->values[i]"),
+                ]
+                .map(|expr| expr.unwrap_or_else(|| "0".to_string()));
+                hash = vec!["uint64_t hash = (uint64_t)value->len;".to_string()];
+                hash.push(for_loop(
+                    "value->len",
+                    &format!("hash += serde_hash_combine({}, {});", hashes[0], hashes[1]),
+                ));
+            }
+            Item::Composite(Tuple(formats)) => {
+                for (i, format) in formats.iter().enumerate() {
+                    self.push_comparison_statements(
+                        format,
+                        &format!("field{}", i),
+                        (&mut compare, &mut hash),
+                    );
+                }
+            }
+            Item::Composite(TupleArray { content, size }) => {
+                let indirect = self.is_boxed(content);
+                let size = size.to_string();
+                if let Some(test) =
+                    self.quote_compare(content, indirect, "lhs->data[i]", "rhs->data[i]")
+                {
+                    compare.push(for_loop(&size, &format!("SERDE_TRY_COMPARE({});", test)));
+                }
+                if let Some(expr) = self.quote_hash(content, indirect, "value->data[i]") {
+                    hash.push(for_loop(
+                        &size,
+                        &format!("hash = serde_hash_combine(hash, {});", expr),
+                    ));
+                }
+            }
+            Item::Composite(_) => unreachable!(),
+        }
+        if !compare.iter().any(|s| s.starts_with("return")) {
+            if compare.is_empty() {
+                compare.push("(void)lhs;\n(void)rhs;".to_string());
+            }
+            compare.push("return 0;".to_string());
+        }
+        if !hash.iter().any(|s| s.starts_with("return")) {
+            if hash.len() == 1 && hash[0] == "uint64_t hash = 0;" {
+                hash = vec!["(void)value;".to_string(), "return 0;".to_string()];
+            } else {
+                hash.push("return hash;".to_string());
+            }
+        }
+        Ok((compare, hash))
+    }
+
+    /// Add the statements for the field `place` (relative to `value`, `lhs`, or `rhs`) to the
+    /// bodies of the functions `compare` and `hash`.
+    fn push_comparison_statements(
+        &self,
+        format: &Format,
+        place: &str,
+        (compare, hash): (&mut Vec<String>, &mut Vec<String>),
+    ) {
+        let indirect = self.is_boxed(format);
+        if let Some(test) = self.quote_compare(
+            format,
+            indirect,
+            &format!("lhs->{}", place),
+            &format!("rhs->{}", place),
+        ) {
+            compare.push(format!("SERDE_TRY_COMPARE({});", test));
+        }
+        if let Some(expr) = self.quote_hash(format, indirect, &format!("value->{}", place)) {
+            hash.push(format!("hash = serde_hash_combine(hash, {});", expr));
+        }
+    }
+
+    /// Bodies of the functions `equal`, `free`, `serialize`, and `deserialize` of an auxiliary
+    /// struct.
+    fn composite_bodies(
+        &self,
+        format: &Format,
+    ) -> (Vec<String>, Vec<String>, Vec<String>, Vec<String>) {
+        use Format::*;
+        let mut equal = Vec::new();
+        let mut free = Vec::new();
+        let mut serialize = Vec::new();
+        let mut deserialize = Vec::new();
+        match format {
+            Option(content) => {
+                let indirect = self.is_boxed(content);
+                let test = self.quote_equal(content, indirect, "lhs->value", "rhs->value");
+                equal.push(match test {
+                    Some(test) => format!(
+                        "return lhs->has_value == rhs->has_value && (!lhs->has_value || {});",
+                        test
+                    ),
+                    None => "return lhs->has_value == rhs->has_value;".to_string(),
+                });
+                if let Some(statement) = self.quote_free(content, indirect, "value->value") {
+                    free.push(format!("if (value->has_value) {{\n    {}\n}}", statement));
+                }
+                serialize.push(
+                    "SERDE_TRY(serde_serialize_option_tag(serializer, value->has_value));"
+                        .to_string(),
+                );
+                serialize.push(format!(
+                    "if (value->has_value) {{\n    {}\n}}",
+                    self.quote_serialize(content, indirect, "value->value")
+                ));
+                deserialize.push(
+                    "SERDE_TRY(serde_deserialize_option_tag(deserializer, &value->has_value));"
+                        .to_string(),
+                );
+                deserialize.push(format!(
+                    "if (value->has_value) {{\n    {}\n}}",
+                    self.quote_deserialize(content, indirect, "value->value")
+                ));
+            }
+            Seq(content) => {
+                equal.push("if (lhs->len != rhs->len) {\n    return false;\n}".to_string());
+                if let Some(test) = self.quote_equal(content, false, "lhs->data[i]", "rhs->data[i]")
+                {
+                    equal.push(for_loop(
+                        "lhs->len",
+                        &format!("if (!({})) {{\n    return false;\n}}", test),
+                    ));
+                }
+                if let Some(statement) = self.quote_free(content, false, "value->data[i]") {
+                    free.push(for_loop("value->len", &statement));
+                }
+                free.push("serde_free(allocator, value->data);".to_string());
+                serialize
+                    .push("SERDE_TRY(serde_serialize_len(serializer, value->len));".to_string());
+                serialize.push(for_loop(
+                    "value->len",
+                    &self.quote_serialize(content, false, "value->data[i]"),
+                ));
+                deserialize.push("size_t len;\nvoid *data;".to_string());
+                deserialize
+                    .push("SERDE_TRY(serde_deserialize_len(deserializer, &len));".to_string());
+                deserialize.push(format!(
+                    "SERDE_TRY(serde_deserializer_alloc(deserializer, len, sizeof({}), {}, &data));",
+                    self.quote_type(content),
+                    self.quote_min_encoded_size(&[content.as_ref()]),
+                ));
+                deserialize.push("value->data = data;\nvalue->len = len;".to_string());
+                deserialize.push(for_loop(
+                    "len",
+                    &self.quote_deserialize(content, false, "value->data[i]"),
+                ));
+            }
+            Map { key, value } => {
+                let name = self.quote_name(format);
+                equal.push("if (lhs->len != rhs->len) {\n    return false;\n}".to_string());
+                let tests = [
+                    self.quote_equal(key, false, "lhs->keys[i]", "rhs->keys[i]"),
+                    self.quote_equal(value, false, "lhs->values[i]", "rhs->values[i]"),
+                ];
+                let tests = tests.iter().flatten().cloned().collect::<Vec<_>>();
+                if !tests.is_empty() {
+                    equal.push(for_loop(
+                        "lhs->len",
+                        &format!("if (!({})) {{\n    return false;\n}}", tests.join(" && ")),
+                    ));
+                }
+                let statements = [
+                    self.quote_free(key, false, "value->keys[i]"),
+                    self.quote_free(value, false, "value->values[i]"),
+                ];
+                let statements = statements.iter().flatten().cloned().collect::<Vec<_>>();
+                if !statements.is_empty() {
+                    free.push(for_loop("value->len", &statements.join("\n")));
+                }
+                free.push("serde_free(allocator, value->keys);".to_string());
+                free.push("serde_free(allocator, value->values);".to_string());
+                serialize
+                    .push("SERDE_TRY(serde_serialize_len(serializer, value->len));".to_string());
+                serialize.push(format!(
+                    "return serde_serialize_map_entries(serializer, value, value->len, {0}_serialize_key, {0}_serialize_value);",
+                    name
+                ));
+                deserialize.push(
+                    "size_t len;\nvoid *keys;\nvoid *values;\nsize_t previous_key_start = 0;\nsize_t previous_key_end = 0;"
+                        .to_string(),
+                );
+                deserialize
+                    .push("SERDE_TRY(serde_deserialize_len(deserializer, &len));".to_string());
+                deserialize.push(format!(
+                    "SERDE_TRY(serde_deserializer_alloc(deserializer, len, sizeof({}), {}, &keys));",
+                    self.quote_type(key),
+                    self.quote_min_encoded_size(&[key.as_ref(), value.as_ref()]),
+                ));
+                deserialize.push("value->keys = keys;".to_string());
+                deserialize.push(format!(
+                    "SERDE_TRY(serde_deserializer_alloc(deserializer, len, sizeof({}), 0, &values));",
+                    self.quote_type(value),
+                ));
+                deserialize.push("value->values = values;\nvalue->len = len;".to_string());
+                deserialize.push(for_loop(
+                    "len",
+                    &[
+                        "size_t key_start = deserializer->offset;".to_string(),
+                        self.quote_deserialize(key, false, "value->keys[i]"),
+                        "if (i > 0) {\n    SERDE_TRY(serde_deserializer_check_key_order(deserializer, previous_key_start, previous_key_end, key_start, deserializer->offset));\n}".to_string(),
+                        "previous_key_start = key_start;".to_string(),
+                        "previous_key_end = deserializer->offset;".to_string(),
+                        self.quote_deserialize(value, false, "value->values[i]"),
+                    ]
+                    .join("\n"),
+                ));
+            }
+            Tuple(formats) => {
+                for (i, format) in formats.iter().enumerate() {
+                    self.push_field_statements(
+                        format,
+                        &format!("field{}", i),
+                        (&mut equal, &mut free, &mut serialize, &mut deserialize),
+                    );
+                }
+                if formats.is_empty() {
+                    serialize.push("(void)value;\n(void)serializer;".to_string());
+                    deserialize.push("(void)value;\n(void)deserializer;".to_string());
+                }
+                if equal.is_empty() {
+                    equal.push("(void)lhs;\n(void)rhs;".to_string());
+                }
+                equal.push("return true;".to_string());
+            }
+            TupleArray { content, size } => {
+                let indirect = self.is_boxed(content);
+                let size = size.to_string();
+                match self.quote_equal(content, indirect, "lhs->data[i]", "rhs->data[i]") {
+                    Some(test) => equal.push(for_loop(
+                        &size,
+                        &format!("if (!({})) {{\n    return false;\n}}", test),
+                    )),
+                    None => equal.push("(void)lhs;\n(void)rhs;".to_string()),
+                }
+                equal.push("return true;".to_string());
+                if let Some(statement) = self.quote_free(content, indirect, "value->data[i]") {
+                    free.push(for_loop(&size, &statement));
+                }
+                serialize.push(for_loop(
+                    &size,
+                    &self.quote_serialize(content, indirect, "value->data[i]"),
+                ));
+                deserialize.push(for_loop(
+                    &size,
+                    &self.quote_deserialize(content, indirect, "value->data[i]"),
+                ));
+            }
+            _ => unreachable!(),
+        }
+        if !equal.iter().any(|s| s.starts_with("return")) {
+            equal.push("return true;".to_string());
+        }
+        if free.is_empty() {
+            free.push("(void)value;\n(void)allocator;".to_string());
+        }
+        if !serialize.iter().any(|s| s.starts_with("return")) {
+            serialize.push("return SERDE_OK;".to_string());
+        }
+        deserialize.push("return SERDE_OK;".to_string());
+        (equal, free, serialize, deserialize)
+    }
+
+    /// Whether Bincode is used with non-default options, hence a format defined by the module.
+    fn has_custom_bincode_format(&self) -> bool {
+        self.generator.config.encodings.contains(&Encoding::Bincode)
+            && self.generator.config.bincode_options != BincodeOptions::default()
+    }
+
+    fn output_bincode_format(&mut self) -> Result<()> {
+        if !self.has_custom_bincode_format() {
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options;
+        let limit = match options.limit {
+            Some(limit) => format!("{}", limit),
+            None => "SIZE_MAX".to_string(),
+        };
+        writeln!(
+            self.out,
+            "\nstatic const serde_format_t {}_bincode_format = BINCODE_FORMAT({}, {}, {});",
+            self.prefix,
+            options.int_encoding == IntEncoding::Varint,
+            options.endianness == Endianness::Big,
+            limit,
+        )?;
+        Ok(())
+    }
+
+    fn output_encoding_functions(&mut self, name: &str, encoding: Encoding) -> Result<()> {
+        let (serializer_init, deserializer_init) = if encoding == Encoding::Bincode
+            && self.has_custom_bincode_format()
+        {
+            (
+                    format!(
+                        "serde_serializer_init(&serializer, &{}_bincode_format, buffer, capacity);",
+                        self.prefix
+                    ),
+                    format!(
+                        "serde_deserializer_init(&deserializer, &{}_bincode_format, input, length, allocator);",
+                        self.prefix
+                    ),
+                )
+        } else {
+            (
+                format!(
+                    "{}_serializer_init(&serializer, buffer, capacity);",
+                    encoding.name()
+                ),
+                format!(
+                    "{}_deserializer_init(&deserializer, input, length, allocator);",
+                    encoding.name()
+                ),
+            )
+        };
+        let check_end = match self
+            .generator
+            .config
+            .bincode_options_for(encoding)
+            .trailing_bytes
+        {
+            TrailingBytes::Reject => {
+                "\n    if (error == SERDE_OK) {\n        error = serde_deserializer_check_end(&deserializer);\n    }"
+            }
+            TrailingBytes::Allow => "",
+        };
+        writeln!(
+            self.out,
+            r#"
+static inline serde_error_t {0}_{1}_serialize(const {0} *value, uint8_t *buffer, size_t capacity, size_t *length) {{
+    serde_serializer_t serializer;
+    {2}
+    SERDE_TRY({0}_serialize(value, &serializer));
+    *length = serializer.offset;
+    return SERDE_OK;
+}}
+
+static inline serde_error_t {0}_{1}_deserialize({0} *value, const uint8_t *input, size_t length, const serde_allocator_t *allocator) {{
+    serde_deserializer_t deserializer;
+    serde_error_t error;
+    {3}
+    memset(value, 0, sizeof *value);
+    error = {0}_deserialize(value, &deserializer);{4}
+    if (error != SERDE_OK) {{
+        {0}_free(value, allocator);
+        memset(value, 0, sizeof *value);
+    }}
+    return error;
+}}"#,
+            format!("{}_{}", self.prefix, name),
+            encoding.name(),
+            serializer_init,
+            deserializer_init,
+            check_end,
+        )?;
+        Ok(())
+    }
+}
+
+/// Indent each line of a (possibly multi-line) statement.
+fn indent_lines(statement: &str) -> String {
+    statement
+        .lines()
+        .map(|line| format!("    {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn for_loop(len: &str, body: &str) -> String {
+    format!(
+        "for (size_t i = 0; i < {}; i++) {{\n{}\n}}",
+        len,
+        indent_lines(body)
+    )
+}
+
+fn switch_statement(tag: &str, cases: Vec<String>) -> Vec<String> {
+    if cases.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![format!("switch ({}) {{", tag)];
+    lines.extend(cases);
+    lines.push("default:".to_string());
+    lines.push("    break;".to_string());
+    lines.push("}".to_string());
+    lines
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+    ) -> std::result::Result<crate::SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let mut files = crate::SourceFiles::new();
+        files.insert_bytes(format!("{}.h", self.config.module_name), content);
+        Ok(files)
+    }
+}
+
+/// Installer for generated source files in C.
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+
+    fn create_header_file(&self, name: &str) -> std::io::Result<std::fs::File> {
+        let dir_path = &self.install_dir;
+        std::fs::create_dir_all(dir_path)?;
+        std::fs::File::create(dir_path.join(name.to_string() + ".h"))
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Error;
+
+    fn install_module(
+        &self,
+        config: &crate::CodeGeneratorConfig,
+        registry: &Registry,
+    ) -> std::result::Result<(), Self::Error> {
+        let generator = CodeGenerator::new(config);
+        generator.generate(registry)?.write_to(&self.install_dir)?;
+        Ok(())
+    }
+
+    fn install_serde_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_header_file("serde")?;
+        write!(file, "{}", include_str!("../runtime/c/serde.h"))?;
+        Ok(())
+    }
+
+    fn install_bincode_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_header_file("bincode")?;
+        write!(file, "{}", include_str!("../runtime/c/bincode.h"))?;
+        Ok(())
+    }
+
+    fn install_bcs_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_header_file("bcs")?;
+        write!(file, "{}", include_str!("../runtime/c/bcs.h"))?;
+        Ok(())
+    }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the postcard encoding",
+        ))
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the borsh encoding",
+        ))
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the scale encoding",
+        ))
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the json encoding",
+        ))
+    }
+}
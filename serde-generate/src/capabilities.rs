@@ -0,0 +1,419 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{analyzer, common, BincodeOptions, Case, CodeGeneratorConfig, Encoding, Error, Result};
+use serde_reflection::{ContainerFormat, Format, FormatHolder, Registry, VariantFormat};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The features of Serde formats and of `CodeGeneratorConfig` supported by a target language.
+/// Each language module exposes its capabilities as a constant `CAPABILITIES`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Name of the target language, as used in error messages.
+    pub language: &'static str,
+    /// Whether enums with only unit variants may be generated as native enums
+    /// (see `CodeGeneratorConfig::with_c_style_enums`).
+    pub c_style_enums: bool,
+    /// Whether `f32` and `f64` values are supported.
+    pub floats: bool,
+    /// Whether containers of the form `UnitStruct` are supported.
+    pub unit_structs: bool,
+    /// Whether `i128` and `u128` values are supported.
+    pub u128: bool,
+    /// Whether containers may depend on themselves, directly or indirectly.
+    pub recursive_types: bool,
+    /// Whether tagged and untagged Serde enum representations are supported. Only Rust does, by
+    /// deriving the matching Serde attributes.
+    pub tagged_enums: bool,
+    /// The encodings for which a runtime and serialization helpers are available.
+    pub encodings: &'static [Encoding],
+    /// Whether a package manifest is generated. Otherwise, `CodeGeneratorConfig::package_manifest`
+    /// is ignored.
+    pub package_manifest: bool,
+    /// Whether names of types, fields and variants may follow custom naming conventions
+    /// (see `CodeGeneratorConfig::with_naming_conventions`).
+    pub naming_conventions: bool,
+    /// Whether the names of the source files generated for each type may follow a custom
+    /// naming convention.
+    pub file_naming_conventions: bool,
+    /// Whether containers may be split into several modules that refer to each other
+    /// (see `ModuleMap`).
+    pub modules: bool,
+    /// Whether modules may depend on each other cyclically.
+    pub module_cycles: bool,
+    /// Whether containers may be replaced by native types of the target language
+    /// (see `CodeGeneratorConfig::with_native_types`).
+    pub native_types: bool,
+    /// Whether equality, hashing and ordering may be generated for all containers
+    /// (see `CodeGeneratorConfig::with_comparisons`).
+    pub comparisons: bool,
+    /// Whether `validate` methods may be generated, or are not needed because the types of the
+    /// target language enforce all the constraints of a registry
+    /// (see `CodeGeneratorConfig::with_validation`).
+    pub validation: bool,
+    /// Whether the Bincode encoding may use options other than the default ones
+    /// (see `CodeGeneratorConfig::with_bincode_options`).
+    pub bincode_options: bool,
+}
+
+impl Capabilities {
+    /// Check that the given registry and configuration only use supported features.
+    /// Otherwise, return an `Error::Unsupported` value for the first feature found.
+    pub fn check(&self, registry: &Registry, config: &CodeGeneratorConfig) -> Result<()> {
+        self.check_config(config)?;
+        for (name, format) in registry {
+            if !config.native_types.contains_key(name) {
+                self.check_container(name, format)?;
+                if config.comparisons {
+                    self.check_comparable(name, format)?;
+                }
+                if config.encodings.contains(&Encoding::Borsh) {
+                    self.check_borsh(registry, name, format)?;
+                }
+                if config.encodings.contains(&Encoding::Scale) {
+                    self.check_scale(name, format)?;
+                }
+                if config.encodings.contains(&Encoding::Json) {
+                    self.check_json(registry, name, format)?;
+                }
+            }
+        }
+        if !self.recursive_types {
+            let registry = common::remove_native_types(config, registry);
+            let external_names = config
+                .external_definitions
+                .values()
+                .flatten()
+                .chain(config.native_types.keys())
+                .cloned()
+                .collect();
+            let dependencies = analyzer::get_dependency_map_with_external_dependencies(
+                &registry,
+                &external_names,
+            )?;
+            if let Some(name) = analyzer::find_node_in_cycle(&dependencies) {
+                return Err(self.unsupported("recursive types").at(name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the given configuration only uses supported features.
+    pub fn check_config(&self, config: &CodeGeneratorConfig) -> Result<()> {
+        if config.c_style_enums && !self.c_style_enums {
+            return Err(self.unsupported("c-style enums"));
+        }
+        if !config.native_types.is_empty() && !self.native_types {
+            return Err(self.unsupported("native types"));
+        }
+        if config.comparisons && !self.comparisons {
+            return Err(self.unsupported("comparisons"));
+        }
+        if config.generates_validation() && !self.validation {
+            return Err(self.unsupported("validation"));
+        }
+        if config.naming.renames_identifiers() && !self.naming_conventions {
+            return Err(self.unsupported("naming conventions"));
+        }
+        if config.naming.files != Case::Preserve && !self.file_naming_conventions {
+            return Err(self.unsupported("file naming conventions"));
+        }
+        if let Some(encoding) = config
+            .encodings
+            .iter()
+            .find(|encoding| !self.encodings.contains(encoding))
+        {
+            return Err(self.unsupported(format!("the {} encoding", encoding.name())));
+        }
+        if config.encodings.contains(&Encoding::Bincode)
+            && config.bincode_options != BincodeOptions::default()
+            && !self.bincode_options
+        {
+            return Err(self.unsupported("bincode options"));
+        }
+        if config.encodings.contains(&Encoding::Json) {
+            // Field and variant names are part of the JSON encoding.
+            if config.naming.fields != Case::Preserve || config.naming.variants != Case::Preserve {
+                return Err(self.unsupported(
+                    "naming conventions for fields and variants in the json encoding",
+                ));
+            }
+            if !config.native_types.is_empty() {
+                return Err(self.unsupported("native types in the json encoding"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that code may be generated in the given modules, where `dependencies` maps each
+    /// module to the modules that it refers to.
+    pub fn check_modules(&self, dependencies: &BTreeMap<String, BTreeSet<String>>) -> Result<()> {
+        if dependencies.len() > 1 && !self.modules {
+            return Err(self.unsupported("multiple modules"));
+        }
+        if !self.module_cycles {
+            if let Some(module) = analyzer::find_node_in_cycle(dependencies) {
+                return Err(self
+                    .unsupported("cyclic dependencies between modules")
+                    .at(&module));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_container(&self, name: &str, format: &ContainerFormat) -> Result<()> {
+        use ContainerFormat::*;
+        match format {
+            UnitStruct if !self.unit_structs => Err(self.unsupported("unit structs").at(name)),
+            UnitStruct => Ok(()),
+            NewTypeStruct(format) => self.check_formats(format.as_ref(), name),
+            TupleStruct(formats) => formats
+                .iter()
+                .try_for_each(|format| self.check_formats(format, name)),
+            Struct(fields) => fields.iter().try_for_each(|field| {
+                self.check_formats(&field.value, &format!("{}.{}", name, field.name))
+            }),
+            OpenEnum(formats) => formats.iter().try_for_each(|(tag, format)| {
+                self.check_formats(format, &format!("{}.{}", name, tag))
+            }),
+            // The fields of flattened containers cannot be recovered from the registry.
+            FlattenedStruct(_) => Err(self.unsupported("flattened structs").at(name)),
+            TaggedEnum { .. } if !self.tagged_enums => Err(self
+                .unsupported("tagged or untagged enum representations")
+                .at(name)),
+            TaggedEnum { variants, .. } | Enum(variants) => {
+                variants.values().try_for_each(|variant| {
+                    let path = format!("{}.{}", name, variant.name);
+                    match &variant.value {
+                        VariantFormat::Struct(fields) => fields.iter().try_for_each(|field| {
+                            self.check_formats(&field.value, &format!("{}.{}", path, field.name))
+                        }),
+                        value => self.check_formats(value, &path),
+                    }
+                })
+            }
+        }
+    }
+
+    fn check_formats<F: FormatHolder>(&self, formats: &F, path: &str) -> Result<()> {
+        let mut result = Ok(());
+        formats.visit(&mut |format| {
+            if result.is_ok() {
+                result = self.check_primitive(format, path);
+            }
+            Ok(())
+        })?;
+        result
+    }
+
+    fn check_primitive(&self, format: &Format, path: &str) -> Result<()> {
+        match format {
+            Format::F32 | Format::F64 if !self.floats => {
+                Err(self.unsupported("floating point numbers").at(path))
+            }
+            Format::I128 | Format::U128 if !self.u128 => {
+                Err(self.unsupported("128-bit integers").at(path))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Floating point numbers have no total order, hence no comparisons in any language.
+    fn check_comparable(&self, name: &str, format: &ContainerFormat) -> Result<()> {
+        let mut has_floats = false;
+        format.visit(&mut |format| {
+            has_floats |= matches!(format, Format::F32 | Format::F64);
+            Ok(())
+        })?;
+        if has_floats {
+            return Err(self
+                .unsupported("comparisons of floating point numbers")
+                .at(name));
+        }
+        Ok(())
+    }
+
+    /// Borsh has no characters and encodes variant indices as `u8` values. Besides, map entries
+    /// are sorted by keys. Runtimes sort them as in Rust by comparing the integers and strings
+    /// within encoded keys, which excludes keys containing floats, bytes, sequences or maps.
+    fn check_borsh(&self, registry: &Registry, name: &str, format: &ContainerFormat) -> Result<()> {
+        if let ContainerFormat::Enum(variants) = format {
+            if variants.keys().any(|index| *index > u8::MAX as u32) {
+                return Err(self
+                    .unsupported("more than 256 variants in the borsh encoding")
+                    .at(name));
+            }
+        }
+        let mut result = Ok(());
+        format.visit(&mut |format| {
+            if result.is_ok() {
+                result = match format {
+                    Format::Char => Err(self.unsupported("characters in the borsh encoding")),
+                    Format::Map { key, .. }
+                        if !is_borsh_map_key(registry, key, &mut BTreeSet::new()) =>
+                    {
+                        Err(self.unsupported(
+                            "map keys containing floats, bytes, sequences or maps in the borsh encoding",
+                        ))
+                    }
+                    _ => Ok(()),
+                };
+            }
+            Ok(())
+        })?;
+        result.map_err(|error| error.at(name))
+    }
+
+    /// SCALE has no characters and encodes variant indices as `u8` values. Unlike Borsh, map
+    /// entries are decoded in any order, so map keys are not restricted.
+    fn check_scale(&self, name: &str, format: &ContainerFormat) -> Result<()> {
+        if let ContainerFormat::Enum(variants) = format {
+            if variants.keys().any(|index| *index > u8::MAX as u32) {
+                return Err(self
+                    .unsupported("more than 256 variants in the scale encoding")
+                    .at(name));
+            }
+        }
+        let mut result = Ok(());
+        format.visit(&mut |format| {
+            if result.is_ok() {
+                if let Format::Char = format {
+                    result = Err(self.unsupported("characters in the scale encoding"));
+                }
+            }
+            Ok(())
+        })?;
+        result.map_err(|error| error.at(name))
+    }
+
+    /// `serde_json` writes map keys as JSON strings, which only works for strings, characters,
+    /// booleans, numbers, and the newtype structs and unit variants made of them. Besides, the
+    /// content of open enums is encoded as bytes, which has no JSON counterpart.
+    fn check_json(&self, registry: &Registry, name: &str, format: &ContainerFormat) -> Result<()> {
+        if let ContainerFormat::OpenEnum(_) = format {
+            return Err(self.unsupported("open enums in the json encoding").at(name));
+        }
+        let mut result = Ok(());
+        format.visit(&mut |format| {
+            if result.is_ok() {
+                if let Format::Map { key, .. } = format {
+                    if !is_json_map_key(registry, key, &mut BTreeSet::new()) {
+                        result = Err(self.unsupported(
+                            "map keys other than strings and numbers in the json encoding",
+                        ));
+                    }
+                }
+            }
+            Ok(())
+        })?;
+        result.map_err(|error| error.at(name))
+    }
+
+    fn unsupported(&self, feature: impl Into<String>) -> Error {
+        Error::unsupported(self.language, feature)
+    }
+}
+
+/// Whether Borsh runtimes order the values of the given format as in Rust when used as map
+/// keys. This holds for unit values, booleans, integers and strings, and for the tuples,
+/// arrays, options, structs and enums made of them. Containers being visited are
+/// assumed to qualify.
+fn is_borsh_map_key(registry: &Registry, format: &Format, visiting: &mut BTreeSet<String>) -> bool {
+    use Format::*;
+    match format {
+        Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | Str => true,
+        Option(format)
+        | TupleArray {
+            content: format, ..
+        } => is_borsh_map_key(registry, format, visiting),
+        Tuple(formats) => are_borsh_map_keys(registry, formats, visiting),
+        TypeName(name) => {
+            if !visiting.insert(name.clone()) {
+                return true;
+            }
+            match registry.get(name) {
+                Some(ContainerFormat::UnitStruct) => true,
+                Some(ContainerFormat::NewTypeStruct(format)) => {
+                    is_borsh_map_key(registry, format, visiting)
+                }
+                Some(ContainerFormat::TupleStruct(formats)) => {
+                    are_borsh_map_keys(registry, formats, visiting)
+                }
+                Some(ContainerFormat::Struct(fields)) => fields
+                    .iter()
+                    .all(|field| is_borsh_map_key(registry, &field.value, visiting)),
+                Some(ContainerFormat::Enum(variants)) => {
+                    variants.values().all(|variant| match &variant.value {
+                        VariantFormat::Unit => true,
+                        VariantFormat::NewType(format) => {
+                            is_borsh_map_key(registry, format, visiting)
+                        }
+                        VariantFormat::Tuple(formats) => {
+                            are_borsh_map_keys(registry, formats, visiting)
+                        }
+                        VariantFormat::Struct(fields) => fields
+                            .iter()
+                            .all(|field| is_borsh_map_key(registry, &field.value, visiting)),
+                        VariantFormat::Variable(_) => false,
+                    })
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn are_borsh_map_keys(
+    registry: &Registry,
+    formats: &[Format],
+    visiting: &mut BTreeSet<String>,
+) -> bool {
+    formats
+        .iter()
+        .all(|format| is_borsh_map_key(registry, format, visiting))
+}
+
+/// Whether `serde_json` accepts the values of the given format as map keys. Containers being
+/// visited are assumed to qualify.
+fn is_json_map_key(registry: &Registry, format: &Format, visiting: &mut BTreeSet<String>) -> bool {
+    use Format::*;
+    match format {
+        Str | Char | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32
+        | F64 => true,
+        TypeName(name) => {
+            if !visiting.insert(name.clone()) {
+                return true;
+            }
+            match registry.get(name) {
+                Some(ContainerFormat::NewTypeStruct(format)) => {
+                    is_json_map_key(registry, format, visiting)
+                }
+                Some(ContainerFormat::Enum(variants)) => variants
+                    .values()
+                    .all(|variant| matches!(variant.value, VariantFormat::Unit)),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// The encodings for which all runtimes are available.
+pub(crate) const ALL_ENCODINGS: &[Encoding] = &[
+    Encoding::Bincode,
+    Encoding::Bcs,
+    Encoding::Postcard,
+    Encoding::Borsh,
+    Encoding::Scale,
+];
+
+/// The encodings of `ALL_ENCODINGS` and JSON.
+pub(crate) const ALL_ENCODINGS_AND_JSON: &[Encoding] = &[
+    Encoding::Bincode,
+    Encoding::Bcs,
+    Encoding::Postcard,
+    Encoding::Borsh,
+    Encoding::Scale,
+    Encoding::Json,
+];
@@ -1,7 +1,8 @@
 // Copyright (c) Facebook, Inc. and its affiliates
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use serde_reflection::Format;
+use crate::{BincodeOptions, CodeGeneratorConfig, Endianness, Error, IntEncoding};
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
 
 pub(crate) fn mangle_type(format: &Format) -> String {
     use Format::*;
@@ -57,3 +58,155 @@ pub(crate) fn lowercase_first_letter(s: &str) -> String {
         Some(f) => f.to_lowercase().collect::<String>() + c.as_str(),
     }
 }
+
+/// The boolean arguments `varint_encoding, big_endian` passed to the constructors of the
+/// Bincode runtime in languages without named arguments, or an empty string when the options
+/// use the default encoding.
+pub(crate) fn bincode_runtime_arguments(options: &BincodeOptions) -> String {
+    if options.is_custom_encoding() {
+        format!(
+            "{}, {}",
+            options.int_encoding == IntEncoding::Varint,
+            options.endianness == Endianness::Big
+        )
+    } else {
+        String::new()
+    }
+}
+
+/// Error returned by generators whose (binary) encodings cannot represent the enum `name`
+/// because it uses a tagged or untagged Serde representation.
+pub(crate) fn tagged_enum_not_supported(language: &'static str, name: &str) -> Error {
+    Error::unsupported(language, "tagged or untagged enum representations").at(name)
+}
+
+/// Error returned by generators whose encodings cannot represent the struct `name` because
+/// some of its fields are flattened.
+pub(crate) fn flattened_struct_not_supported(language: &'static str, name: &str) -> Error {
+    Error::unsupported(language, "flattened structs").at(name)
+}
+
+/// The registry without the containers replaced by native types of the target language,
+/// for which no code is generated. References to these containers are kept.
+pub(crate) fn remove_native_types(config: &CodeGeneratorConfig, registry: &Registry) -> Registry {
+    registry
+        .iter()
+        .filter(|(name, _)| !config.native_types.contains_key(*name))
+        .map(|(name, format)| (name.clone(), format.clone()))
+        .collect()
+}
+
+/// Fields of the struct used to encode the values of an open enum: the tag of the variant
+/// and its content encoded as bytes, so that decoders may skip unknown variants.
+pub(crate) fn open_enum_fields() -> Vec<Named<Format>> {
+    vec![
+        Named {
+            name: "tag".to_string(),
+            value: Format::Str,
+        },
+        Named {
+            name: "content".to_string(),
+            value: Format::Bytes,
+        },
+    ]
+}
+
+/// Whether generated `validate` methods must look into values of the given format: either
+/// the format contains values of user-defined types, which have their own `validate`
+/// method, or it contains fixed-size arrays and `fixed_arrays` says that their lengths are
+/// not enforced by the target language.
+pub(crate) fn needs_validation(
+    config: &CodeGeneratorConfig,
+    format: &Format,
+    fixed_arrays: bool,
+) -> bool {
+    use Format::*;
+    match format {
+        TypeName(name) => !config.native_types.contains_key(name),
+        Option(format) | Seq(format) => needs_validation(config, format, fixed_arrays),
+        Map { key, value } => {
+            needs_validation(config, key, fixed_arrays)
+                || needs_validation(config, value, fixed_arrays)
+        }
+        Tuple(formats) => formats
+            .iter()
+            .any(|format| needs_validation(config, format, fixed_arrays)),
+        TupleArray { content, .. } => {
+            fixed_arrays || needs_validation(config, content, fixed_arrays)
+        }
+        _ => false,
+    }
+}
+
+/// How the JSON encoding writes the values of a container or of an enum variant, besides the
+/// name of the variant: `null`, the content of a newtype, an array of the components of a
+/// tuple, or an object with the fields of a struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum JsonShape {
+    Unit,
+    NewType,
+    Tuple,
+    Struct,
+}
+
+impl JsonShape {
+    pub(crate) fn of_container(format: &ContainerFormat) -> Self {
+        match format {
+            ContainerFormat::UnitStruct => JsonShape::Unit,
+            ContainerFormat::NewTypeStruct(_) => JsonShape::NewType,
+            ContainerFormat::TupleStruct(_) => JsonShape::Tuple,
+            _ => JsonShape::Struct,
+        }
+    }
+
+    pub(crate) fn of_variant(format: &VariantFormat) -> Self {
+        match format {
+            VariantFormat::Unit => JsonShape::Unit,
+            VariantFormat::NewType(_) => JsonShape::NewType,
+            VariantFormat::Tuple(_) => JsonShape::Tuple,
+            VariantFormat::Struct(_) | VariantFormat::Variable(_) => JsonShape::Struct,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            JsonShape::Unit => "unit",
+            JsonShape::NewType => "newtype",
+            JsonShape::Tuple => "tuple",
+            JsonShape::Struct => "struct",
+        }
+    }
+}
+
+/// How `serde_json` orders the entries of a `BTreeMap`, given the JSON keys of the entries.
+/// Languages whose maps are unordered must sort entries this way when writing JSON.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum JsonKeyOrder {
+    /// Strings and chars, by UTF-8 bytes, and booleans.
+    Text,
+    /// Integers and floats, by value.
+    Number,
+    /// Unit variants of an enum, given the names of all the variants by index.
+    Variants(Vec<String>),
+}
+
+impl JsonKeyOrder {
+    /// The order of map keys of the given format (see `Capabilities::check` for the
+    /// formats accepted as keys in the JSON encoding).
+    pub(crate) fn of_key(registry: &Registry, format: &Format) -> Self {
+        match format {
+            Format::TypeName(name) => match registry.get(name) {
+                Some(ContainerFormat::NewTypeStruct(format)) => Self::of_key(registry, format),
+                Some(ContainerFormat::Enum(variants)) => JsonKeyOrder::Variants(
+                    variants
+                        .values()
+                        .map(|variant| variant.name.clone())
+                        .collect(),
+                ),
+                _ => JsonKeyOrder::Text,
+            },
+            Format::Str | Format::Char | Format::Bool => JsonKeyOrder::Text,
+            _ => JsonKeyOrder::Number,
+        }
+    }
+}
@@ -1,7 +1,11 @@
 // Copyright (c) Facebook, Inc. and its affiliates
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use std::collections::{BTreeMap, BTreeSet};
+use serde_reflection::{Dual, Registry};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
 
 /// Code generation options meant to be supported by all languages.
 #[derive(Clone, Debug)]
@@ -9,23 +13,149 @@ pub struct CodeGeneratorConfig {
     pub module_name: String,
     pub serialization: bool,
     pub encodings: BTreeSet<Encoding>,
+    pub bincode_options: BincodeOptions,
     pub external_definitions: ExternalDefinitions,
+    pub generated_modules: BTreeSet<String>,
     pub comments: DocComments,
     pub custom_code: CustomCode,
     pub c_style_enums: bool,
     pub package_manifest: bool,
+    pub naming: NamingConventions,
+    pub native_types: NativeTypes,
+    pub comparisons: bool,
+    pub validation: bool,
+    pub validate_on_serialize: bool,
 }
 
 #[derive(Clone, Copy, Debug, PartialOrd, Ord, PartialEq, Eq)]
 pub enum Encoding {
     Bincode,
     Bcs,
+    Postcard,
+    Borsh,
+    /// The SCALE codec of Substrate, as implemented by the Rust crate `parity-scale-codec`.
+    Scale,
+    /// The JSON format of `serde_json`, with its default (externally tagged) representation
+    /// of enums.
+    Json,
+}
+
+/// Options of the Bincode encoding, mirroring the configuration of the Rust crate `bincode`.
+/// The default value corresponds to `bincode::serialize` and `bincode::deserialize`, except
+/// that trailing bytes are rejected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BincodeOptions {
+    /// How integers are encoded, including sequence lengths and variant indices.
+    pub int_encoding: IntEncoding,
+    /// Byte order of fixed-size integers and floats.
+    pub endianness: Endianness,
+    /// Maximal number of bytes written by serialization and read by deserialization.
+    pub limit: Option<u64>,
+    /// Whether deserialization helpers accept inputs that are not entirely read.
+    pub trailing_bytes: TrailingBytes,
+}
+
+/// Encoding of integers in Bincode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntEncoding {
+    /// Integers use their full size, e.g. 8 bytes for `u64` (`with_fixint_encoding`).
+    Fixint,
+    /// Integers of at least 16 bits use 1 byte when smaller than 251, otherwise a tag byte
+    /// followed by the smallest sufficient fixed-size integer. Signed integers are first
+    /// zigzag-encoded (`with_varint_encoding`).
+    Varint,
+}
+
+impl Default for IntEncoding {
+    fn default() -> Self {
+        Self::Fixint
+    }
+}
+
+/// Byte order of a binary encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Default for Endianness {
+    fn default() -> Self {
+        Self::Little
+    }
+}
+
+/// What to do with input bytes left after deserializing a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrailingBytes {
+    Reject,
+    Allow,
+}
+
+impl Default for TrailingBytes {
+    fn default() -> Self {
+        Self::Reject
+    }
+}
+
+/// Naming conventions applied to the identifiers of the generated code.
+/// Names only change in the generated code: the wire format of values is unaffected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NamingConventions {
+    /// Names of the generated types (containers).
+    pub types: Case,
+    /// Names of struct fields, including fields of struct variants.
+    pub fields: Case,
+    /// Names of enum variants.
+    pub variants: Case,
+    /// Names of the source files generated for each type, in languages that allow it.
+    pub files: Case,
+}
+
+/// A naming convention, e.g. `Case::Snake` for `foo_bar`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Case {
+    /// Keep the names of the registry, subject to the usual adjustments of each language.
+    Preserve,
+    /// `FooBar`
+    UpperCamel,
+    /// `fooBar`
+    LowerCamel,
+    /// `foo_bar`
+    Snake,
+    /// `FOO_BAR`
+    ScreamingSnake,
+    /// `foo-bar`, only for file names since identifiers cannot contain `-`.
+    Kebab,
+}
+
+impl Default for Case {
+    fn default() -> Self {
+        Self::Preserve
+    }
 }
 
 /// Track types definitions provided by external modules.
 pub type ExternalDefinitions =
     std::collections::BTreeMap</* module */ String, /* type names */ Vec<String>>;
 
+/// Track containers replaced by native types of the target language.
+pub type NativeTypes = BTreeMap</* container name */ String, NativeType>;
+
+/// A type of the target language provided by the user in place of a container of the registry,
+/// e.g. `java.util.UUID` for a container `Uuid`. No code is generated for the container:
+/// the generated code refers to the native type and calls the given functions to serialize and
+/// deserialize its values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NativeType {
+    /// The (qualified) name of the type in the target language.
+    pub name: String,
+    /// A function taking a value of the type and a serializer, in that order.
+    pub serializer: String,
+    /// A function taking a deserializer and returning a value of the type.
+    pub deserializer: String,
+}
+
 /// Track documentation to be attached to particular definitions.
 pub type DocComments =
     std::collections::BTreeMap</* qualified name */ Vec<String>, /* comment */ String>;
@@ -55,6 +185,95 @@ pub trait SourceInstaller {
 
     /// Install the Libra Canonical Serialization (BCS) runtime.
     fn install_bcs_runtime(&self) -> std::result::Result<(), Self::Error>;
+
+    /// Install the postcard runtime.
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error>;
+
+    /// Install the Borsh runtime.
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error>;
+
+    /// Install the SCALE runtime.
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error>;
+
+    /// Install the JSON runtime.
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error>;
+}
+
+/// How to generate the source code of a module for a given language, in memory.
+pub trait SourceGenerator {
+    type Error;
+
+    /// The configuration of the generator.
+    fn config(&self) -> &CodeGeneratorConfig;
+
+    /// Generate the source files defining the container types contained in the registry.
+    /// Paths are relative to the installation directory of the corresponding `SourceInstaller`.
+    fn generate(
+        &self,
+        registry: &serde_reflection::Registry,
+    ) -> std::result::Result<SourceFiles, Self::Error>;
+}
+
+/// A virtual file tree of generated source files, indexed by relative paths.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceFiles(BTreeMap<PathBuf, String>);
+
+impl SourceFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a file.
+    pub fn insert<P: Into<PathBuf>>(&mut self, path: P, content: String) {
+        self.0.insert(path.into(), content);
+    }
+
+    /// Add or replace a file produced by a code emitter.
+    pub(crate) fn insert_bytes<P: Into<PathBuf>>(&mut self, path: P, content: Vec<u8>) {
+        let content = String::from_utf8(content).expect("generated code should be valid UTF-8");
+        self.insert(path, content);
+    }
+
+    /// The content of the file at the given path, if any.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&str> {
+        self.0.get(path.as_ref()).map(String::as_str)
+    }
+
+    /// Iterate over the files in the order of their paths.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &str)> {
+        self.0
+            .iter()
+            .map(|(path, content)| (path.as_path(), content.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Write the files under the given directory, creating sub-directories as needed.
+    pub fn write_to(&self, install_dir: &Path) -> std::io::Result<()> {
+        for (path, content) in &self.0 {
+            let path = install_dir.join(path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for SourceFiles {
+    type Item = (PathBuf, String);
+    type IntoIter = std::collections::btree_map::IntoIter<PathBuf, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
 }
 
 impl CodeGeneratorConfig {
@@ -64,11 +283,18 @@ impl CodeGeneratorConfig {
             module_name,
             serialization: true,
             encodings: BTreeSet::new(),
+            bincode_options: BincodeOptions::default(),
             external_definitions: BTreeMap::new(),
+            generated_modules: BTreeSet::new(),
             comments: BTreeMap::new(),
             custom_code: BTreeMap::new(),
             c_style_enums: false,
             package_manifest: true,
+            naming: NamingConventions::default(),
+            native_types: BTreeMap::new(),
+            comparisons: false,
+            validation: false,
+            validate_on_serialize: false,
         }
     }
 
@@ -91,12 +317,34 @@ impl CodeGeneratorConfig {
         self
     }
 
+    /// Options of the Bincode encoding, if enabled.
+    pub fn with_bincode_options(mut self, bincode_options: BincodeOptions) -> Self {
+        self.bincode_options = bincode_options;
+        self
+    }
+
+    /// The options to apply in the helpers of the given encoding: the Bincode options for
+    /// `Encoding::Bincode`, and the default options (i.e. no limit and no trailing bytes) otherwise.
+    pub(crate) fn bincode_options_for(&self, encoding: Encoding) -> BincodeOptions {
+        match encoding {
+            Encoding::Bincode => self.bincode_options,
+            _ => BincodeOptions::default(),
+        }
+    }
+
     /// Container names provided by external modules.
     pub fn with_external_definitions(mut self, external_definitions: ExternalDefinitions) -> Self {
         self.external_definitions = external_definitions;
         self
     }
 
+    /// Modules of `external_definitions` generated together with this module, e.g. by
+    /// `ModuleMap::split`. Languages such as C++ import the files generated for these modules.
+    pub fn with_generated_modules(mut self, generated_modules: BTreeSet<String>) -> Self {
+        self.generated_modules = generated_modules;
+        self
+    }
+
     /// Comments attached to particular entity.
     pub fn with_comments(mut self, mut comments: DocComments) -> Self {
         // Make sure comments end with a (single) newline.
@@ -125,6 +373,227 @@ impl CodeGeneratorConfig {
         self.package_manifest = package_manifest;
         self
     }
+
+    /// Naming conventions for types, fields, variants and files.
+    pub fn with_naming_conventions(mut self, naming: NamingConventions) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Native types of the target language replacing particular containers.
+    pub fn with_native_types(mut self, native_types: NativeTypes) -> Self {
+        self.native_types = native_types;
+        self
+    }
+
+    /// Generate structural equality, a hash function consistent with equality, and a total
+    /// ordering matching the `Ord` trait derived by Rust for the same definitions: fields
+    /// compare in order, enum values by variant index first, `None` before `Some`, sequences
+    /// and maps lexicographically (maps by increasing keys), strings and bytes byte-wise, and
+    /// integers by value (including unsigned integers).
+    pub fn with_comparisons(mut self, comparisons: bool) -> Self {
+        self.comparisons = comparisons;
+        self
+    }
+
+    /// Generate `validate` methods checking that a value built in memory satisfies the
+    /// constraints of the registry that the types of the target language do not enforce,
+    /// e.g. the length of fixed-size arrays.
+    pub fn with_validation(mut self, validation: bool) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Call `validate` at the beginning of the serialization methods of each encoding
+    /// (e.g. `bcsSerialize`). The `validate` methods are then generated even if
+    /// `with_validation` is disabled.
+    pub fn with_validate_on_serialize(mut self, validate_on_serialize: bool) -> Self {
+        self.validate_on_serialize = validate_on_serialize;
+        self
+    }
+
+    /// Whether to generate `validate` methods, as requested by either `with_validation` or
+    /// `with_validate_on_serialize`.
+    pub(crate) fn generates_validation(&self) -> bool {
+        self.validation || self.validate_on_serialize
+    }
+
+    /// Assign the requested encodings to the registries of both modes of Serde (see
+    /// `serde_reflection::DualTracer`): human-readable encodings (e.g. JSON) use the
+    /// human-readable registry, and binary encodings the binary one. Each registry comes with
+    /// a copy of the configuration restricted to its encodings.
+    ///
+    /// When encodings of both modes are requested and the two registries differ, the generated
+    /// types cannot be shared: the human-readable encodings are then generated in a separate
+    /// module named after `module_name` with the suffix `_json`. Otherwise, a single module is
+    /// generated with all the encodings.
+    pub fn split_modes<'a>(
+        &self,
+        registries: &'a Dual<Registry>,
+    ) -> Vec<(CodeGeneratorConfig, &'a Registry)> {
+        if !self.serialization {
+            return vec![(self.clone(), &registries.binary)];
+        }
+        let (human_readable, binary): (BTreeSet<_>, BTreeSet<_>) = self
+            .encodings
+            .iter()
+            .partition(|encoding| encoding.is_human_readable());
+        if human_readable.is_empty() || binary.is_empty() || registries.differences().is_empty() {
+            let registry = registries.get(!human_readable.is_empty() && binary.is_empty());
+            return vec![(self.clone(), registry)];
+        }
+        let mut binary_config = self.clone();
+        binary_config.encodings = binary;
+        let mut human_readable_config = self.clone();
+        human_readable_config.module_name = format!("{}_json", self.module_name);
+        human_readable_config.encodings = human_readable;
+        vec![
+            (binary_config, &registries.binary),
+            (human_readable_config, &registries.human_readable),
+        ]
+    }
+
+    /// Generate the source files for the registries of both modes of Serde with the code
+    /// generator of a language, following `split_modes`.
+    ///
+    /// ```
+    /// # use serde_reflection::{ContainerFormat, Dual, Format, Registry};
+    /// use serde_generate::{java, CodeGeneratorConfig, Encoding};
+    ///
+    /// // Addresses are written as hex strings in human-readable encodings.
+    /// let registry = |format| {
+    ///     let mut registry = Registry::new();
+    ///     registry.insert("Address".to_string(), ContainerFormat::NewTypeStruct(Box::new(format)));
+    ///     registry
+    /// };
+    /// let registries = Dual {
+    ///     human_readable: registry(Format::Str),
+    ///     binary: registry(Format::Bytes),
+    /// };
+    ///
+    /// let config = CodeGeneratorConfig::new("com.example".to_string())
+    ///     .with_encodings(vec![Encoding::Bcs, Encoding::Json]);
+    /// let files = config
+    ///     .generate_dual(&registries, |config| Box::new(java::CodeGenerator::new(config)))
+    ///     .unwrap();
+    /// assert!(files.get("com/example/Address.java").unwrap().contains("Bytes value"));
+    /// assert!(files.get("com/example_json/Address.java").unwrap().contains("String value"));
+    /// ```
+    pub fn generate_dual<F>(
+        &self,
+        registries: &Dual<Registry>,
+        generator: F,
+    ) -> crate::Result<SourceFiles>
+    where
+        F: for<'a> Fn(
+            &'a CodeGeneratorConfig,
+        ) -> Box<dyn SourceGenerator<Error = crate::Error> + 'a>,
+    {
+        let mut files = SourceFiles::new();
+        for (config, registry) in self.split_modes(registries) {
+            for (path, content) in generator(&config).generate(registry)? {
+                files.insert(path, content);
+            }
+        }
+        Ok(files)
+    }
+}
+
+impl NativeType {
+    pub fn new(name: String, serializer: String, deserializer: String) -> Self {
+        Self {
+            name,
+            serializer,
+            deserializer,
+        }
+    }
+}
+
+impl BincodeOptions {
+    /// The standard configuration of `bincode` 2, i.e. `bincode::config::standard()`.
+    pub fn standard() -> Self {
+        Self::default().with_int_encoding(IntEncoding::Varint)
+    }
+
+    pub fn with_int_encoding(mut self, int_encoding: IntEncoding) -> Self {
+        self.int_encoding = int_encoding;
+        self
+    }
+
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    pub fn with_limit(mut self, limit: Option<u64>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn with_trailing_bytes(mut self, trailing_bytes: TrailingBytes) -> Self {
+        self.trailing_bytes = trailing_bytes;
+        self
+    }
+
+    /// Whether the runtime serializers must be configured, i.e. whether integers or floats are
+    /// encoded differently from the default configuration.
+    pub fn is_custom_encoding(&self) -> bool {
+        self.int_encoding != IntEncoding::Fixint || self.endianness != Endianness::Little
+    }
+}
+
+impl NamingConventions {
+    /// The same convention for types, fields, variants and files.
+    pub fn new(case: Case) -> Self {
+        Self {
+            types: case,
+            fields: case,
+            variants: case,
+            files: case,
+        }
+    }
+
+    pub fn with_types(mut self, case: Case) -> Self {
+        self.types = case;
+        self
+    }
+
+    pub fn with_fields(mut self, case: Case) -> Self {
+        self.fields = case;
+        self
+    }
+
+    pub fn with_variants(mut self, case: Case) -> Self {
+        self.variants = case;
+        self
+    }
+
+    pub fn with_files(mut self, case: Case) -> Self {
+        self.files = case;
+        self
+    }
+
+    /// Whether some names of types, fields or variants may change.
+    pub fn renames_identifiers(&self) -> bool {
+        self.types != Case::Preserve
+            || self.fields != Case::Preserve
+            || self.variants != Case::Preserve
+    }
+}
+
+impl Case {
+    /// Convert a name to this convention.
+    pub fn apply(self, name: &str) -> String {
+        use heck::{CamelCase, KebabCase, MixedCase, ShoutySnakeCase, SnakeCase};
+        match self {
+            Case::Preserve => name.to_string(),
+            Case::UpperCamel => name.to_camel_case(),
+            Case::LowerCamel => name.to_mixed_case(),
+            Case::Snake => name.to_snake_case(),
+            Case::ScreamingSnake => name.to_shouty_snake_case(),
+            Case::Kebab => name.to_kebab_case(),
+        }
+    }
 }
 
 impl Encoding {
@@ -132,6 +601,24 @@ impl Encoding {
         match self {
             Encoding::Bincode => "bincode",
             Encoding::Bcs => "bcs",
+            Encoding::Postcard => "postcard",
+            Encoding::Borsh => "borsh",
+            Encoding::Scale => "scale",
+            Encoding::Json => "json",
+        }
+    }
+
+    /// Whether the encoding uses the human-readable mode of Serde. When formats were traced
+    /// with `serde_reflection::DualTracer`, this selects the registry to generate code from
+    /// (see `CodeGeneratorConfig::split_modes`).
+    pub fn is_human_readable(self) -> bool {
+        match self {
+            Encoding::Bincode
+            | Encoding::Bcs
+            | Encoding::Postcard
+            | Encoding::Borsh
+            | Encoding::Scale => false,
+            Encoding::Json => true,
         }
     }
 }
@@ -2,19 +2,130 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::{
-    analyzer,
+    analyzer, common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig, Encoding,
+    naming, Capabilities, CodeGeneratorConfig, Encoding, Error, Result, SourceGenerator,
+    TrailingBytes,
 };
 use heck::CamelCase;
 use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
-    io::{Result, Write},
+    io::Write,
     path::PathBuf,
 };
 
+/// Features supported by the C++ code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "C++",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: crate::capabilities::ALL_ENCODINGS_AND_JSON,
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: true,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Reserved words of C++, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char16_t",
+    "char32_t",
+    "class",
+    "compl",
+    "const",
+    "const_cast",
+    "constexpr",
+    "continue",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+];
+
 /// Main configuration object for code-generation in C++.
+#[derive(Clone)]
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
     config: &'a CodeGeneratorConfig,
@@ -40,9 +151,6 @@ struct CppEmitter<'a, T> {
 impl<'a> CodeGenerator<'a> {
     /// Create a C++ code generator for the given config.
     pub fn new(config: &'a CodeGeneratorConfig) -> Self {
-        if config.c_style_enums {
-            panic!("C++ does not support generating c-style enums");
-        }
         let mut external_qualified_names = HashMap::new();
         for (namespace, names) in &config.external_definitions {
             for name in names {
@@ -56,11 +164,16 @@ impl<'a> CodeGenerator<'a> {
         }
     }
 
-    pub fn output(
-        &self,
-        out: &mut dyn Write,
-        registry: &Registry,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).output(out, &registry);
+        }
         let current_namespace = self
             .config
             .module_name
@@ -78,7 +191,15 @@ impl<'a> CodeGenerator<'a> {
         emitter.output_preamble()?;
         emitter.output_open_namespace()?;
 
-        let dependencies = analyzer::get_dependency_map(registry)?;
+        let external_names = self
+            .config
+            .external_definitions
+            .values()
+            .flatten()
+            .cloned()
+            .collect();
+        let dependencies =
+            analyzer::get_dependency_map_with_external_dependencies(registry, &external_names)?;
         let entries = analyzer::best_effort_topological_sort(&dependencies);
 
         for name in entries {
@@ -96,8 +217,17 @@ impl<'a> CodeGenerator<'a> {
 
         emitter.output_close_namespace()?;
         writeln!(emitter.out)?;
+        if self.config.comparisons {
+            emitter.output_hash_declarations(registry)?;
+        }
+        if emitter.has_json() {
+            emitter.output_json_declarations(registry)?;
+        }
         for (name, format) in registry {
             emitter.output_container_traits(name, format)?;
+            if emitter.has_json() {
+                emitter.output_container_json_traits(name, format)?;
+            }
         }
         Ok(())
     }
@@ -119,6 +249,10 @@ where
                 writeln!(self.out, "#include \"{}.hpp\"", encoding.name())?;
             }
         }
+        // Headers of other modules generated together with this one.
+        for namespace in &self.generator.config.generated_modules {
+            writeln!(self.out, "#include \"{}\"", header_name(namespace))?;
+        }
         Ok(())
     }
 
@@ -270,7 +404,8 @@ where
         self.output_class_method_declarations(name)?;
         self.output_custom_code()?;
         self.leave_class();
-        writeln!(self.out, "}};")
+        writeln!(self.out, "}};")?;
+        Ok(())
     }
 
     fn output_variant(&mut self, name: &str, variant: &VariantFormat) -> Result<()> {
@@ -292,7 +427,8 @@ where
     }
 
     fn output_container_forward_definition(&mut self, name: &str) -> Result<()> {
-        writeln!(self.out, "\nstruct {};", name)
+        writeln!(self.out, "\nstruct {};", name)?;
+        Ok(())
     }
 
     fn output_enum_container(
@@ -321,7 +457,8 @@ where
         self.output_class_method_declarations(name)?;
         self.output_custom_code()?;
         self.leave_class();
-        writeln!(self.out, "}};")
+        writeln!(self.out, "}};")?;
+        Ok(())
     }
 
     fn output_class_method_declarations(&mut self, name: &str) -> Result<()> {
@@ -330,18 +467,31 @@ where
             "friend bool operator==(const {}&, const {}&);",
             name, name
         )?;
+        if self.generator.config.comparisons {
+            writeln!(
+                self.out,
+                "friend bool operator<(const {}&, const {}&);",
+                name, name
+            )?;
+        }
         if self.generator.config.serialization {
             for encoding in &self.generator.config.encodings {
+                let data_type = match encoding {
+                    Encoding::Json => "std::string",
+                    _ => "std::vector<uint8_t>",
+                };
                 writeln!(
                     self.out,
-                    "std::vector<uint8_t> {}Serialize() const;",
+                    "{} {}Serialize() const;",
+                    data_type,
                     encoding.name()
                 )?;
                 writeln!(
                     self.out,
-                    "static {} {}Deserialize(std::vector<uint8_t>);",
+                    "static {} {}Deserialize({});",
                     name,
-                    encoding.name()
+                    encoding.name(),
+                    data_type
                 )?;
             }
         }
@@ -361,6 +511,9 @@ where
                 value: Format::Tuple(formats.clone()),
             }],
             Struct(fields) => fields.clone(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("C++", name)),
+            FlattenedStruct(_) => return Err(common::flattened_struct_not_supported("C++", name)),
             Enum(variants) => {
                 self.output_enum_container(name, variants)?;
                 return Ok(());
@@ -385,7 +538,298 @@ where
         }
         writeln!(self.out, "return true;")?;
         self.out.unindent();
-        writeln!(self.out, "}}")
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// Fields are compared lexicographically, like Rust's derived `Ord`.
+    fn output_struct_comparison(&mut self, name: &str, fields: &[&str]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\ninline bool operator<(const {0} &lhs, const {0} &rhs) {{",
+            name,
+        )?;
+        self.out.indent();
+        if fields.is_empty() {
+            writeln!(self.out, "return false;")?;
+        } else {
+            let quote_tie = |side: &str| {
+                fields
+                    .iter()
+                    .map(|field| format!("{}.{}", side, field))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            writeln!(
+                self.out,
+                "return std::tie({}) < std::tie({});",
+                quote_tie("lhs"),
+                quote_tie("rhs")
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// Hashes must be specialized before their first use, which may happen in other containers.
+    fn output_hash_declarations(&mut self, registry: &Registry) -> Result<()> {
+        writeln!(self.out, "namespace std {{")?;
+        for (name, format) in registry {
+            let mut names = vec![name.clone()];
+            if let ContainerFormat::Enum(variants) = format {
+                names.extend(
+                    variants
+                        .values()
+                        .map(|variant| format!("{}::{}", name, variant.name)),
+                );
+            }
+            for name in names {
+                writeln!(
+                    self.out,
+                    "\ntemplate <>\nstruct hash<{0}> {{\n    size_t operator()(const {0} &) const;\n}};",
+                    self.quote_qualified_name(&name)
+                )?;
+            }
+        }
+        writeln!(self.out, "\n}} // end of namespace std\n")?;
+        Ok(())
+    }
+
+    fn has_json(&self) -> bool {
+        self.generator.config.serialization
+            && self.generator.config.encodings.contains(&Encoding::Json)
+    }
+
+    /// Like hashes, JSON conversions must be specialized before their first use.
+    fn output_json_declarations(&mut self, registry: &Registry) -> Result<()> {
+        for (name, format) in registry {
+            let mut names = vec![name.clone()];
+            if let ContainerFormat::Enum(variants) = format {
+                names.extend(
+                    variants
+                        .values()
+                        .map(|variant| format!("{}::{}", name, variant.name)),
+                );
+            }
+            for name in names {
+                writeln!(
+                    self.out,
+                    r#"
+template <>
+inline serde::json::Value serde::JsonSerializable<{0}>::serialize(const {0} &);
+
+template <>
+inline {0} serde::JsonDeserializable<{0}>::deserialize(const serde::json::Value &);"#,
+                    self.quote_qualified_name(&name)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert a struct or a variant (without its name) from and to JSON values. Newtypes and
+    /// tuples have a single field `value`.
+    fn output_struct_json_traits(
+        &mut self,
+        name: &str,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        use common::JsonShape;
+        writeln!(
+            self.out,
+            r#"
+template <>
+inline serde::json::Value serde::JsonSerializable<{0}>::serialize(const {0} &obj) {{"#,
+            name,
+        )?;
+        self.out.indent();
+        match shape {
+            JsonShape::Unit => writeln!(self.out, "return serde::json::Value::null();")?,
+            JsonShape::NewType | JsonShape::Tuple => writeln!(
+                self.out,
+                "return serde::JsonSerializable<decltype(obj.value)>::serialize(obj.value);"
+            )?,
+            JsonShape::Struct => {
+                writeln!(self.out, "auto json = serde::json::Value::object();")?;
+                for field in fields {
+                    writeln!(
+                        self.out,
+                        "json.insert(\"{0}\", serde::JsonSerializable<decltype(obj.{0})>::serialize(obj.{0}));",
+                        field.name,
+                    )?;
+                }
+                writeln!(self.out, "return json;")?;
+            }
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        writeln!(
+            self.out,
+            r#"
+template <>
+inline {0} serde::JsonDeserializable<{0}>::deserialize(const serde::json::Value &json) {{"#,
+            name,
+        )?;
+        self.out.indent();
+        writeln!(self.out, "{} obj;", name)?;
+        match shape {
+            JsonShape::Unit => writeln!(
+                self.out,
+                "serde::JsonDeserializable<std::monostate>::deserialize(json);"
+            )?,
+            JsonShape::NewType | JsonShape::Tuple => writeln!(
+                self.out,
+                "obj.value = serde::JsonDeserializable<decltype(obj.value)>::deserialize(json);"
+            )?,
+            JsonShape::Struct => {
+                for field in fields {
+                    // Like Serde, missing optional fields are read as `None`.
+                    let getter = match field.value {
+                        Format::Option(_) => "optional_field",
+                        _ => "field",
+                    };
+                    writeln!(
+                        self.out,
+                        "obj.{0} = serde::JsonDeserializable<decltype(obj.{0})>::deserialize(json.{1}(\"{0}\"));",
+                        field.name, getter,
+                    )?;
+                }
+            }
+        }
+        writeln!(self.out, "return obj;")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_enum_json_traits(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+template <>
+inline serde::json::Value serde::JsonSerializable<{0}>::serialize(const {0} &obj) {{"#,
+            name,
+        )?;
+        self.out.indent();
+        writeln!(self.out, "switch (obj.value.index()) {{")?;
+        for (index, variant) in variants {
+            writeln!(self.out, "case {}:", index)?;
+            self.out.indent();
+            if let VariantFormat::Unit = variant.value {
+                writeln!(
+                    self.out,
+                    "return serde::json::Value::string(\"{}\");",
+                    variant.name
+                )?;
+            } else {
+                writeln!(
+                    self.out,
+                    "return serde::json::Value::variant(\"{1}\", serde::JsonSerializable<{0}::{1}>::serialize(std::get<{2}>(obj.value)));",
+                    name, variant.name, index
+                )?;
+            }
+            self.out.unindent();
+        }
+        writeln!(self.out, "default:")?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "throw serde::serialization_error(\"Invalid variant index\");"
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        writeln!(
+            self.out,
+            r#"
+template <>
+inline {0} serde::JsonDeserializable<{0}>::deserialize(const serde::json::Value &json) {{"#,
+            name,
+        )?;
+        self.out.indent();
+        writeln!(self.out, "auto variant = json.as_variant();")?;
+        for variant in variants.values() {
+            writeln!(self.out, "if (variant.name == \"{}\") {{", variant.name)?;
+            self.out.indent();
+            if let VariantFormat::Unit = variant.value {
+                writeln!(self.out, "variant.unit();")?;
+                writeln!(self.out, "return {0}{{{0}::{1}{{}}}};", name, variant.name)?;
+            } else {
+                writeln!(
+                    self.out,
+                    "return {0}{{serde::JsonDeserializable<{0}::{1}>::deserialize(variant.value())}};",
+                    name, variant.name
+                )?;
+            }
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+        }
+        writeln!(
+            self.out,
+            "throw serde::deserialization_error(\"Unknown variant for {}: \" + variant.name);",
+            name
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_container_json_traits(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
+        use ContainerFormat::*;
+        let name = self.quote_qualified_name(name);
+        let fields = match format {
+            UnitStruct | NewTypeStruct(_) | TupleStruct(_) => Vec::new(),
+            Struct(fields) => fields.clone(),
+            OpenEnum(_) | TaggedEnum { .. } | FlattenedStruct(_) => {
+                unreachable!("rejected by the code generator")
+            }
+            Enum(variants) => {
+                self.output_enum_json_traits(&name, variants)?;
+                for variant in variants.values() {
+                    let fields = match &variant.value {
+                        VariantFormat::Struct(fields) => fields.clone(),
+                        _ => Vec::new(),
+                    };
+                    self.output_struct_json_traits(
+                        &format!("{}::{}", name, variant.name),
+                        common::JsonShape::of_variant(&variant.value),
+                        &fields,
+                    )?;
+                }
+                return Ok(());
+            }
+        };
+        self.output_struct_json_traits(&name, common::JsonShape::of_container(format), &fields)
+    }
+
+    fn output_struct_hash(&mut self, name: &str, fields: &[&str]) -> Result<()> {
+        writeln!(
+            self.out,
+            "\ninline size_t std::hash<{0}>::operator()(const {0} &obj) const {{",
+            name,
+        )?;
+        self.out.indent();
+        writeln!(self.out, "size_t seed = 0;")?;
+        for field in fields {
+            writeln!(
+                self.out,
+                "serde::hash_combine(seed, serde::Hashable<decltype(obj.{0})>::hash(obj.{0}));",
+                field,
+            )?;
+        }
+        writeln!(self.out, "return seed;")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
     }
 
     fn output_struct_serialize_for_encoding(
@@ -393,19 +837,43 @@ where
         name: &str,
         encoding: Encoding,
     ) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+inline std::string {0}::jsonSerialize() const {{
+    return serde::json::stringify(serde::JsonSerializable<{0}>::serialize(*this));
+}}"#,
+                name,
+            )?;
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(encoding);
         writeln!(
             self.out,
             r#"
 inline std::vector<uint8_t> {}::{}Serialize() const {{
-    auto serializer = serde::{}Serializer();
-    serde::Serializable<{}>::serialize(*this, serializer);
+    auto serializer = serde::{}Serializer({});
+    serde::Serializable<{}>::serialize(*this, serializer);{}
     return std::move(serializer).bytes();
 }}"#,
             name,
             encoding.name(),
             encoding.name().to_camel_case(),
-            name
-        )
+            common::bincode_runtime_arguments(&options),
+            name,
+            match options.limit {
+                Some(limit) => format!(
+                    r#"
+    if (serializer.get_buffer_offset() > {}ull) {{
+        throw serde::serialization_error("Exceeded the size limit of the encoding");
+    }}"#,
+                    limit
+                ),
+                None => String::new(),
+            }
+        )?;
+        Ok(())
     }
 
     fn output_struct_deserialize_for_encoding(
@@ -413,23 +881,56 @@ inline std::vector<uint8_t> {}::{}Serialize() const {{
         name: &str,
         encoding: Encoding,
     ) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+inline {0} {0}::jsonDeserialize(std::string input) {{
+    return serde::JsonDeserializable<{0}>::deserialize(serde::json::parse(input));
+}}"#,
+                name,
+            )?;
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(encoding);
+        let arguments = common::bincode_runtime_arguments(&options);
+        let mut checks = String::new();
+        if let Some(limit) = options.limit {
+            checks += &format!(
+                r#"
+    if (deserializer.get_buffer_offset() > {}ull) {{
+        throw serde::deserialization_error("Exceeded the size limit of the encoding");
+    }}"#,
+                limit
+            );
+        }
+        if options.trailing_bytes == TrailingBytes::Reject {
+            checks += r#"
+    if (deserializer.get_buffer_offset() < input.size()) {
+        throw serde::deserialization_error("Some input bytes were not read");
+    }"#;
+        }
         writeln!(
             self.out,
             r#"
 inline {} {}::{}Deserialize(std::vector<uint8_t> input) {{
-    auto deserializer = serde::{}Deserializer(input);
-    auto value = serde::Deserializable<{}>::deserialize(deserializer);
-    if (deserializer.get_buffer_offset() < input.size()) {{
-        throw serde::deserialization_error("Some input bytes were not read");
-    }}
+    auto deserializer = serde::{}Deserializer(input{});
+    auto value = serde::Deserializable<{}>::deserialize(deserializer);{}
     return value;
 }}"#,
             name,
             name,
             encoding.name(),
             encoding.name().to_camel_case(),
+            if arguments.is_empty() {
+                arguments
+            } else {
+                format!(", {}", arguments)
+            },
             name,
-        )
+            checks,
+        )?;
+        Ok(())
     }
 
     fn output_struct_serializable(
@@ -461,7 +962,8 @@ void serde::Serializable<{0}>::serialize(const {0} &obj, Serializer &serializer)
             writeln!(self.out, "serializer.decrease_container_depth();")?;
         }
         self.out.unindent();
-        writeln!(self.out, "}}")
+        writeln!(self.out, "}}")?;
+        Ok(())
     }
 
     fn output_struct_deserializable(
@@ -495,7 +997,8 @@ template <typename Deserializer>
         }
         writeln!(self.out, "return obj;")?;
         self.out.unindent();
-        writeln!(self.out, "}}")
+        writeln!(self.out, "}}")?;
+        Ok(())
     }
 
     fn output_struct_traits(
@@ -506,6 +1009,9 @@ template <typename Deserializer>
     ) -> Result<()> {
         self.output_open_namespace()?;
         self.output_struct_equality_test(name, fields)?;
+        if self.generator.config.comparisons {
+            self.output_struct_comparison(name, fields)?;
+        }
         if self.generator.config.serialization {
             for encoding in &self.generator.config.encodings {
                 self.output_struct_serialize_for_encoding(name, *encoding)?;
@@ -514,6 +1020,9 @@ template <typename Deserializer>
         }
         self.output_close_namespace()?;
         let namespaced_name = self.quote_qualified_name(name);
+        if self.generator.config.comparisons {
+            self.output_struct_hash(&namespaced_name, fields)?;
+        }
         if self.generator.config.serialization {
             self.output_struct_serializable(&namespaced_name, fields, is_container)?;
             self.output_struct_deserializable(&namespaced_name, fields, is_container)?;
@@ -549,6 +1058,9 @@ template <typename Deserializer>
                     .collect::<Vec<_>>(),
                 true,
             ),
+            OpenEnum(_) => self.output_struct_traits(name, &["tag", "content"], true),
+            TaggedEnum { .. } => Err(common::tagged_enum_not_supported("C++", name)),
+            FlattenedStruct(_) => Err(common::flattened_struct_not_supported("C++", name)),
             Enum(variants) => {
                 self.output_struct_traits(name, &["value"], true)?;
                 for variant in variants.values() {
@@ -564,6 +1076,30 @@ template <typename Deserializer>
     }
 }
 
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+    ) -> std::result::Result<crate::SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let mut files = crate::SourceFiles::new();
+        files.insert_bytes(header_name(&self.config.module_name), content);
+        Ok(files)
+    }
+}
+
+/// The name of the header generated for a namespace, e.g. `a_b.hpp` for `a::b`.
+fn header_name(namespace: &str) -> String {
+    format!("{}.hpp", namespace.replace("::", "_"))
+}
+
 /// Installer for generated source files in C++.
 pub struct Installer {
     install_dir: PathBuf,
@@ -574,7 +1110,7 @@ impl Installer {
         Installer { install_dir }
     }
 
-    fn create_header_file(&self, name: &str) -> Result<std::fs::File> {
+    fn create_header_file(&self, name: &str) -> std::io::Result<std::fs::File> {
         let dir_path = &self.install_dir;
         std::fs::create_dir_all(dir_path)?;
         std::fs::File::create(dir_path.join(name.to_string() + ".hpp"))
@@ -582,16 +1118,16 @@ impl Installer {
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
         config: &crate::CodeGeneratorConfig,
         registry: &Registry,
     ) -> std::result::Result<(), Self::Error> {
-        let mut file = self.create_header_file(&config.module_name)?;
         let generator = CodeGenerator::new(config);
-        generator.output(&mut file, registry)
+        generator.generate(registry)?.write_to(&self.install_dir)?;
+        Ok(())
     }
 
     fn install_serde_runtime(&self) -> std::result::Result<(), Self::Error> {
@@ -613,4 +1149,28 @@ impl crate::SourceInstaller for Installer {
         write!(file, "{}", include_str!("../runtime/cpp/bcs.hpp"))?;
         Ok(())
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_header_file("postcard")?;
+        write!(file, "{}", include_str!("../runtime/cpp/postcard.hpp"))?;
+        Ok(())
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_header_file("borsh")?;
+        write!(file, "{}", include_str!("../runtime/cpp/borsh.hpp"))?;
+        Ok(())
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_header_file("scale")?;
+        write!(file, "{}", include_str!("../runtime/cpp/scale.hpp"))?;
+        Ok(())
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_header_file("json")?;
+        write!(file, "{}", include_str!("../runtime/cpp/json.hpp"))?;
+        Ok(())
+    }
 }
@@ -4,7 +4,8 @@
 use crate::{
     common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig, Encoding,
+    naming, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error, IntEncoding, Result,
+    SourceFiles, TrailingBytes,
 };
 use heck::CamelCase;
 use include_dir::include_dir as include_directory;
@@ -12,11 +13,114 @@ use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, V
 use std::{
     collections::{BTreeMap, HashMap},
     fmt::Write as _,
-    io::{Result, Write},
+    io::Write,
     path::PathBuf,
 };
 
+/// Features supported by the C# code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "C#",
+    c_style_enums: true,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: crate::capabilities::ALL_ENCODINGS_AND_JSON,
+    package_manifest: true,
+    naming_conventions: true,
+    file_naming_conventions: true,
+    modules: true,
+    module_cycles: true,
+    native_types: true,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Reserved words of C#, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
 /// Main configuration object for code-generation in C#.
+#[derive(Clone)]
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
     config: &'a CodeGeneratorConfig,
@@ -67,18 +171,34 @@ impl<'a> CodeGenerator<'a> {
         install_dir: std::path::PathBuf,
         registry: &Registry,
     ) -> Result<std::path::PathBuf> {
+        self.source_files(registry)?.write_to(&install_dir)?;
+        Ok(install_dir.join(self.namespace_path()))
+    }
+
+    /// The subdirectory corresponding to the package name.
+    fn namespace_path(&self) -> std::path::PathBuf {
+        self.config.module_name.split('.').collect()
+    }
+
+    /// Generate class definitions in memory, in a subdirectory corresponding to the package name.
+    fn source_files(&self, registry: &Registry) -> Result<SourceFiles> {
+        CAPABILITIES.check(registry, self.config)?;
+        let registry = &common::remove_native_types(self.config, registry);
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).source_files(&registry);
+        }
         let current_namespace = self
             .config
             .module_name
             .split('.')
             .map(String::from)
             .collect::<Vec<_>>();
-
-        let mut dir_path = install_dir;
-        for part in &current_namespace {
-            dir_path = dir_path.join(part);
-        }
-        std::fs::create_dir_all(&dir_path)?;
+        let dir_path = self.namespace_path();
 
         // When we find an enum with all Unit variants, we ser/de as a regular C# enum.
         // We keep track of this so we can use the enum's extension class for ser/de since enums can't have methods.
@@ -93,32 +213,38 @@ impl<'a> CodeGenerator<'a> {
             }
         }
 
+        let mut files = SourceFiles::new();
         for (name, format) in registry {
-            self.write_container_class(
-                &dir_path,
+            let content = self.write_container_class(
                 current_namespace.clone(),
                 cstyle_enum_names.clone(),
                 name,
                 format,
             )?;
+            let file_name = self.config.naming.files.apply(name);
+            files.insert_bytes(dir_path.join(file_name + ".cs"), content);
         }
-        if self.config.serialization {
-            self.write_helper_class(&dir_path, current_namespace, cstyle_enum_names, registry)?;
+        if self.config.serialization
+            || self.config.comparisons
+            || self.config.generates_validation()
+        {
+            let content =
+                self.write_helper_class(current_namespace, cstyle_enum_names, registry)?;
+            files.insert_bytes(dir_path.join("TraitHelpers.cs"), content);
         }
-        Ok(dir_path)
+        Ok(files)
     }
 
     fn write_container_class(
         &self,
-        dir_path: &std::path::Path,
         current_namespace: Vec<String>,
         cstyle_enum_names: Vec<String>,
         name: &str,
         format: &ContainerFormat,
-    ) -> Result<()> {
-        let mut file = std::fs::File::create(dir_path.join(name.to_string() + ".cs"))?;
+    ) -> Result<Vec<u8>> {
+        let mut content = Vec::new();
         let mut emitter = CSharpEmitter {
-            out: IndentedWriter::new(&mut file, IndentConfig::Space(4)),
+            out: IndentedWriter::new(&mut content, IndentConfig::Space(4)),
             generator: self,
             current_namespace,
             current_reserved_names: HashMap::new(),
@@ -130,19 +256,18 @@ impl<'a> CodeGenerator<'a> {
         emitter.output_container(name, format)?;
         emitter.output_close_namespace()?;
 
-        Ok(())
+        Ok(content)
     }
 
     fn write_helper_class(
         &self,
-        dir_path: &std::path::Path,
         current_namespace: Vec<String>,
         cstyle_enum_names: Vec<String>,
         registry: &Registry,
-    ) -> Result<()> {
-        let mut file = std::fs::File::create(dir_path.join("TraitHelpers.cs"))?;
+    ) -> Result<Vec<u8>> {
+        let mut content = Vec::new();
         let mut emitter = CSharpEmitter {
-            out: IndentedWriter::new(&mut file, IndentConfig::Space(4)),
+            out: IndentedWriter::new(&mut content, IndentConfig::Space(4)),
             generator: self,
             current_namespace,
             current_reserved_names: HashMap::new(),
@@ -154,7 +279,19 @@ impl<'a> CodeGenerator<'a> {
         emitter.output_trait_helpers(registry)?;
         emitter.output_close_namespace()?;
 
-        Ok(())
+        Ok(content)
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(&self, registry: &Registry) -> std::result::Result<SourceFiles, Self::Error> {
+        self.source_files(registry)
     }
 }
 
@@ -250,7 +387,11 @@ using System.Numerics;"
     fn is_nullable(&self, format: &Format) -> bool {
         use Format::*;
         match format {
-            TypeName(name) => !self.cstyle_enum_names.contains(name),
+            // Native types may be value types.
+            TypeName(name) => {
+                !self.cstyle_enum_names.contains(name)
+                    && !self.generator.config.native_types.contains_key(name)
+            }
             Str | Seq(_) | Map { .. } | TupleArray { .. } => true,
             Variable(_) => panic!("unexpected value"),
             _ => false,
@@ -260,7 +401,10 @@ using System.Numerics;"
     fn quote_type(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(x) => self.quote_qualified_name(x),
+            TypeName(x) => match self.generator.config.native_types.get(x) {
+                Some(native) => native.name.clone(),
+                None => self.quote_qualified_name(x),
+            },
             Unit => "Serde.Unit".into(),
             Bool => "bool".into(),
             I8 => "sbyte".into(),
@@ -343,11 +487,26 @@ using System.Numerics;"
         let reserved_names = &[];
         self.enter_class("TraitHelpers", reserved_names);
         for (mangled_name, subtype) in &subtypes {
-            self.output_serialization_helper(mangled_name, subtype)?;
-            self.output_deserialization_helper(mangled_name, subtype)?;
+            if self.generator.config.serialization {
+                self.output_serialization_helper(mangled_name, subtype)?;
+                self.output_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.has_json() {
+                self.output_json_serialization_helper(registry, mangled_name, subtype)?;
+                self.output_json_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.comparisons {
+                self.output_comparison_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.generates_validation()
+                && common::needs_validation(self.generator.config, subtype, true)
+            {
+                self.output_validation_helper(mangled_name, subtype)?;
+            }
         }
         self.leave_class(reserved_names);
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
     fn needs_helper(format: &Format) -> bool {
@@ -361,7 +520,10 @@ using System.Numerics;"
     fn quote_serialize_value(&self, value: &str, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(_) => format!("{}.Serialize(serializer);", value),
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!("{}({}, serializer);", native.serializer, value),
+                None => format!("{}.Serialize(serializer);", value),
+            },
             Unit => format!("serializer.serialize_unit({});", value),
             Bool => format!("serializer.serialize_bool({});", value),
             I8 => format!("serializer.serialize_i8({});", value),
@@ -392,7 +554,9 @@ using System.Numerics;"
         use Format::*;
         match format {
             TypeName(name) => {
-                if self.cstyle_enum_names.contains(name) {
+                if let Some(native) = self.generator.config.native_types.get(name) {
+                    format!("{}(deserializer)", native.deserializer)
+                } else if self.cstyle_enum_names.contains(name) {
                     let extensions_name = format!("{}Extensions", name.to_camel_case());
                     format!(
                         "{}.Deserialize(deserializer)",
@@ -517,7 +681,8 @@ foreach (var item in value) {{
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
     fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
@@ -628,208 +793,696 @@ return new Serde.ValueArray<{0}>(obj);
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
-    }
-
-    fn output_variant(
-        &mut self,
-        base: &str,
-        index: u32,
-        name: &str,
-        variant: &VariantFormat,
-    ) -> Result<()> {
-        use VariantFormat::*;
-        let fields = match variant {
-            Unit => Vec::new(),
-            NewType(format) => vec![Named {
-                name: "value".to_string(),
-                value: format.as_ref().clone(),
-            }],
-            Tuple(formats) => formats
-                .iter()
-                .enumerate()
-                .map(|(i, f)| Named {
-                    name: format!("field{}", i),
-                    value: f.clone(),
-                })
-                .collect(),
-            Struct(fields) => fields.clone(),
-            Variable(_) => panic!("incorrect value"),
-        };
-        self.output_struct_or_variant_container(Some(base), Some(index), name, &fields)
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
-    fn output_variants(
-        &mut self,
-        base: &str,
-        variants: &BTreeMap<u32, Named<VariantFormat>>,
-    ) -> Result<()> {
-        for (index, variant) in variants {
-            self.output_variant(base, *index, &variant.name, &variant.value)?;
+    /// An `int` expression comparing the values `a` and `b` in the order of Rust values.
+    fn quote_compare(&self, a: &str, b: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            Unit => "0".to_string(),
+            Str => format!("Serde.Comparisons.CompareStrings({}, {})", a, b),
+            Bytes => format!("Serde.Comparisons.CompareBytes({}, {})", a, b),
+            Option(_) | Seq(_) | Map { .. } | Tuple(_) | TupleArray { .. } => format!(
+                "{}.compare_{}({}, {})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                a,
+                b
+            ),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            _ => format!("{}.CompareTo({})", a, b),
         }
-        Ok(())
     }
 
-    fn output_struct_or_variant_container(
-        &mut self,
-        variant_base: Option<&str>,
-        variant_index: Option<u32>,
-        name: &str,
-        fields: &[Named<Format>],
-    ) -> Result<()> {
-        // Beginning of class
-        writeln!(self.out)?;
-        let fn_mods = if let Some(base) = variant_base {
-            self.output_comment(name)?;
-            writeln!(
-                self.out,
-                "public sealed class {0}: {1}, IEquatable<{0}>, ICloneable {{",
-                name, base
-            )?;
-            "override "
-        } else {
-            self.output_comment(name)?;
-            writeln!(
-                self.out,
-                "public sealed class {0}: IEquatable<{0}>, ICloneable {{",
-                name
-            )?;
-            ""
-        };
-        let reserved_names = &[];
-        self.enter_class(name, reserved_names);
-
-        // Fields
-        for field in fields {
-            self.output_comment(&field.name)?;
-            writeln!(
-                self.out,
-                "public {} {};",
-                self.quote_type(&field.value),
-                field.name
-            )?;
-        }
-        if !fields.is_empty() {
-            writeln!(self.out)?;
-        }
+    fn output_comparison_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
 
-        // Constructor.
-        writeln!(
+        write!(
             self.out,
-            "public {}({}) {{",
+            "public static int compare_{0}({1} a, {1} b) {{",
             name,
-            fields
-                .iter()
-                .map(|f| format!("{} _{}", self.quote_type(&f.value), &f.name))
-                .collect::<Vec<_>>()
-                .join(", ")
+            self.quote_type(format0)
         )?;
         self.out.indent();
-        for field in fields {
-            if self.is_nullable(&field.value) {
-                writeln!(
+        match format0 {
+            Option(format) => {
+                write!(
                     self.out,
-                    "if (_{0} == null) throw new ArgumentNullException(nameof(_{0}));",
-                    &field.name
+                    r#"
+bool hasX = a.IsSome(out var x);
+bool hasY = b.IsSome(out var y);
+if (!hasX || !hasY) return hasX.CompareTo(hasY);
+return {};
+"#,
+                    self.quote_compare("x", "y", format)
                 )?;
             }
-            writeln!(self.out, "{0} = _{0};", &field.name)?;
-        }
-        self.out.unindent();
-        writeln!(self.out, "}}")?;
 
-        // Serialize
-        if self.generator.config.serialization {
-            writeln!(
-                self.out,
-                "\npublic {}void Serialize(Serde.ISerializer serializer) {{",
-                fn_mods
-            )?;
-            self.out.indent();
-            writeln!(self.out, "serializer.increase_container_depth();")?;
-            if let Some(index) = variant_index {
-                writeln!(self.out, "serializer.serialize_variant_index({});", index)?;
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                write!(
+                    self.out,
+                    r#"
+for (int i = 0; i < a.Count && i < b.Count; i++) {{
+    int result = {};
+    if (result != 0) return result;
+}}
+return a.Count.CompareTo(b.Count);
+"#,
+                    self.quote_compare("a[i]", "b[i]", format)
+                )?;
             }
-            for field in fields {
-                writeln!(
+
+            Map { key, value } => {
+                write!(
                     self.out,
-                    "{}",
-                    self.quote_serialize_value(&field.name, &field.value)
+                    r#"
+var x = new List<{0}>(a.Keys);
+var y = new List<{0}>(b.Keys);
+x.Sort((k, l) => {1});
+y.Sort((k, l) => {1});
+for (int i = 0; i < x.Count && i < y.Count; i++) {{
+    int result = {2};
+    if (result != 0) return result;
+    result = {3};
+    if (result != 0) return result;
+}}
+return x.Count.CompareTo(y.Count);
+"#,
+                    self.quote_type(key),
+                    self.quote_compare("k", "l", key),
+                    self.quote_compare("x[i]", "y[i]", key),
+                    self.quote_compare("a[x[i]]", "b[y[i]]", value),
                 )?;
             }
-            writeln!(self.out, "serializer.decrease_container_depth();")?;
-            self.out.unindent();
-            writeln!(self.out, "}}")?;
 
-            if variant_index.is_none() {
-                for encoding in &self.generator.config.encodings {
-                    self.output_class_serialize_for_encoding(*encoding)?;
+            Tuple(formats) => {
+                writeln!(self.out, "\nint result;")?;
+                for (index, format) in formats.iter().enumerate() {
+                    let a = format!("a.Item{}", index + 1);
+                    let b = format!("b.Item{}", index + 1);
+                    writeln!(
+                        self.out,
+                        "if ((result = {}) != 0) return result;",
+                        self.quote_compare(&a, &b, format)
+                    )?;
                 }
+                writeln!(self.out, "return 0;")?;
             }
+
+            _ => panic!("unexpected case"),
         }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
 
-        // Deserialize (struct) or Load (variant)
-        if self.generator.config.serialization {
-            if variant_index.is_none() {
+    /// A statement validating `value`, assuming that `common::needs_validation` holds.
+    fn quote_validate(&self, value: &str, format: &Format) -> String {
+        match format {
+            Format::TypeName(_) => format!("{}.Validate();", value),
+            _ => format!(
+                "{}.validate_{}({});",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn output_validation_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(
+            self.out,
+            "public static void validate_{}({} value) {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        let needs_validation =
+            |format| common::needs_validation(self.generator.config, format, true);
+        match format0 {
+            Option(format) => {
                 writeln!(
                     self.out,
-                    "\npublic static {}{} Deserialize(Serde.IDeserializer deserializer) {{",
-                    fn_mods, name,
+                    "if (value.IsSome(out var inner)) {{\n    {}\n}}",
+                    self.quote_validate("inner", format)
                 )?;
-            } else {
+            }
+
+            Seq(format) => {
                 writeln!(
                     self.out,
-                    "\ninternal static {} Load(Serde.IDeserializer deserializer) {{",
-                    name,
+                    "foreach (var item in value) {{\n    {}\n}}",
+                    self.quote_validate("item", format)
                 )?;
             }
-            self.out.indent();
-            writeln!(self.out, "deserializer.increase_container_depth();")?;
-            writeln!(
-                self.out,
-                "{0} obj = new {0}(\n\t{1});",
-                name,
-                fields
-                    .iter()
-                    .map(|f| self.quote_deserialize(&f.value))
-                    .collect::<Vec<_>>()
-                    .join(",\n\t")
-            )?;
-            writeln!(self.out, "deserializer.decrease_container_depth();")?;
-            writeln!(self.out, "return obj;")?;
-            self.out.unindent();
-            writeln!(self.out, "}}")?;
 
-            if variant_index.is_none() {
-                for encoding in &self.generator.config.encodings {
-                    self.output_class_deserialize_for_encoding(name, *encoding)?;
+            Map { key, value } => {
+                writeln!(self.out, "foreach (var entry in value) {{")?;
+                if needs_validation(key) {
+                    writeln!(self.out, "    {}", self.quote_validate("entry.Key", key))?;
+                }
+                if needs_validation(value) {
+                    writeln!(
+                        self.out,
+                        "    {}",
+                        self.quote_validate("entry.Value", value)
+                    )?;
                 }
+                writeln!(self.out, "}}")?;
             }
-        }
-        // Equality
-        writeln!(
-            self.out,
-            "public override bool Equals(object obj) => obj is {} other && Equals(other);\n",
-            name
-        )?;
-        writeln!(
-            self.out,
-            "public static bool operator ==({0} left, {0} right) => Equals(left, right);\n",
-            name
-        )?;
-        writeln!(
-            self.out,
-            "public static bool operator !=({0} left, {0} right) => !Equals(left, right);\n",
-            name
-        )?;
 
-        writeln!(self.out, "public bool Equals({} other) {{", name)?;
-        self.out.indent();
-        writeln!(self.out, "if (other == null) return false;")?;
-        writeln!(self.out, "if (ReferenceEquals(this, other)) return true;")?;
-        for field in fields {
-            writeln!(
-                self.out,
+            Tuple(formats) => {
+                for (index, format) in formats.iter().enumerate() {
+                    if needs_validation(format) {
+                        let expr = format!("value.Item{}", index + 1);
+                        writeln!(self.out, "{}", self.quote_validate(&expr, format))?;
+                    }
+                }
+            }
+
+            TupleArray { content, size } => {
+                writeln!(
+                    self.out,
+                    r#"if (value.Count != {0}) {{
+    throw new Serde.SerializationException("Invalid length for fixed-size array: " + value.Count + " instead of " + {0});
+}}"#,
+                    size
+                )?;
+                if needs_validation(content) {
+                    writeln!(
+                        self.out,
+                        "foreach (var item in value) {{\n    {}\n}}",
+                        self.quote_validate("item", content)
+                    )?;
+                }
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// Whether to generate the JSON encoding, which reads and writes values as trees of
+    /// JSON values rather than through a `Serializer` or a `Deserializer`.
+    fn has_json(&self) -> bool {
+        self.generator.config.serialization
+            && self.generator.config.encodings.contains(&Encoding::Json)
+    }
+
+    fn quote_to_json(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) => format!("{}.ToJson()", value),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "Json.Json.serialize_{}({})",
+                common::mangle_type(format),
+                value
+            ),
+            _ => format!(
+                "{}.to_json_{}({})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn quote_from_json(&self, json: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => {
+                if self.cstyle_enum_names.contains(name) {
+                    let extensions_name = format!("{}Extensions", name.to_camel_case());
+                    format!(
+                        "{}.FromJson({})",
+                        self.quote_qualified_name(&extensions_name),
+                        json
+                    )
+                } else {
+                    format!("{}.FromJson({})", self.quote_qualified_name(name), json)
+                }
+            }
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "Json.Json.deserialize_{}({})",
+                common::mangle_type(format),
+                json
+            ),
+            _ => format!(
+                "{}.from_json_{}({})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                json
+            ),
+        }
+    }
+
+    /// The comparison used to sort the entries of maps with keys of the given format.
+    fn quote_json_key_order(registry: &Registry, key: &Format) -> String {
+        match common::JsonKeyOrder::of_key(registry, key) {
+            common::JsonKeyOrder::Text => "Json.JsonObject.StringOrder".to_string(),
+            common::JsonKeyOrder::Number => "Json.JsonObject.NumberOrder".to_string(),
+            common::JsonKeyOrder::Variants(names) => format!(
+                "Json.JsonObject.VariantOrder({})",
+                names
+                    .iter()
+                    .map(|name| format!("\"{}\"", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn output_json_serialization_helper(
+        &mut self,
+        registry: &Registry,
+        name: &str,
+        format0: &Format,
+    ) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "public static object to_json_{}({} value) {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (value.IsSome(out var val)) {{
+    return {};
+}} else {{
+    return null;
+}}
+"#,
+                    self.quote_to_json("val", format)
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+var json = new List<object>(value.Count);
+foreach (var item in value) {{
+    json.Add({});
+}}
+return json;
+"#,
+                    self.quote_to_json("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                // Entries are sorted as in a `BTreeMap`, whatever the order of the dictionary.
+                write!(
+                    self.out,
+                    r#"
+var json = new Json.JsonObject();
+foreach (KeyValuePair<{}, {}> entry in value) {{
+    json.Put(Json.Json.key({}), {});
+}}
+json.SortKeys({});
+return json;
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_to_json("entry.Key", key),
+                    self.quote_to_json("entry.Value", value),
+                    Self::quote_json_key_order(registry, key),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    "\nreturn new List<object> {{{}\n}};\n",
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(index, format)| format!(
+                            "\n    {}",
+                            self.quote_to_json(&format!("value.Item{}", index + 1), format)
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+if (value.Count != {0}) {{
+    throw new Serde.SerializationException("Invalid length for fixed-size array: " + value.Count + " instead of " + {0});
+}}
+var json = new List<object>({0});
+foreach (var item in value) {{
+    json.Add({1});
+}}
+return json;
+"#,
+                    size,
+                    self.quote_to_json("item", content),
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_json_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "public static {} from_json_{}(object json) {{",
+            self.quote_type(format0),
+            name,
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (json == null) {{
+    return Serde.Option<{0}>.None;
+}} else {{
+    return Serde.Option<{0}>.Some({1});
+}}
+"#,
+                    self.quote_type(format),
+                    self.quote_from_json("json", format),
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+List<object> items = Json.Json.deserialize_array(json);
+{0}[] obj = new {0}[items.Count];
+for (int i = 0; i < items.Count; i++) {{
+    obj[i] = {1};
+}}
+return new Serde.ValueArray<{0}>(obj);
+"#,
+                    self.quote_type(format),
+                    self.quote_from_json("items[i]", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+Json.JsonObject entries = Json.Json.deserialize_object(json);
+var obj = new Dictionary<{0}, {1}>();
+for (int i = 0; i < entries.Count; i++) {{
+    var key = {2};
+    var value = {3};
+    obj[key] = value;
+}}
+return new Serde.ValueDictionary<{0}, {1}>(obj);
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_from_json("new Json.JsonKey(entries.Keys[i])", key),
+                    self.quote_from_json("entries.Values[i]", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    r#"
+List<object> items = Json.Json.deserialize_array(json, {});
+return ({}
+);
+"#,
+                    formats.len(),
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| format!(
+                            "\n    {}",
+                            self.quote_from_json(&format!("items[{}]", i), f)
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+List<object> items = Json.Json.deserialize_array(json, {1});
+{0}[] obj = new {0}[{1}];
+for (int i = 0; i < {1}; i++) {{
+    obj[i] = {2};
+}}
+return new Serde.ValueArray<{0}>(obj);
+"#,
+                    self.quote_type(content),
+                    size,
+                    self.quote_from_json("items[i]", content)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_variant(
+        &mut self,
+        base: &str,
+        index: u32,
+        name: &str,
+        variant: &VariantFormat,
+    ) -> Result<()> {
+        use VariantFormat::*;
+        let fields = match variant {
+            Unit => Vec::new(),
+            NewType(format) => vec![Named {
+                name: "value".to_string(),
+                value: format.as_ref().clone(),
+            }],
+            Tuple(formats) => formats
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Named {
+                    name: format!("field{}", i),
+                    value: f.clone(),
+                })
+                .collect(),
+            Struct(fields) => fields.clone(),
+            Variable(_) => panic!("incorrect value"),
+        };
+        let shape = common::JsonShape::of_variant(variant);
+        self.output_struct_or_variant_container(Some(base), Some(index), name, shape, &fields)
+    }
+
+    fn output_variants(
+        &mut self,
+        base: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        for (index, variant) in variants {
+            self.output_variant(base, *index, &variant.name, &variant.value)?;
+        }
+        Ok(())
+    }
+
+    fn output_struct_or_variant_container(
+        &mut self,
+        variant_base: Option<&str>,
+        variant_index: Option<u32>,
+        name: &str,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        // Beginning of class
+        writeln!(self.out)?;
+        let fn_mods = if let Some(base) = variant_base {
+            self.output_comment(name)?;
+            writeln!(
+                self.out,
+                "public sealed class {0}: {1}, IEquatable<{0}>, {2}ICloneable {{",
+                name,
+                base,
+                self.comparable_interface(name),
+            )?;
+            "override "
+        } else {
+            self.output_comment(name)?;
+            writeln!(
+                self.out,
+                "public sealed class {0}: IEquatable<{0}>, {1}ICloneable {{",
+                name,
+                self.comparable_interface(name),
+            )?;
+            ""
+        };
+        let reserved_names = &[];
+        self.enter_class(name, reserved_names);
+
+        // Fields
+        for field in fields {
+            self.output_comment(&field.name)?;
+            writeln!(
+                self.out,
+                "public {} {};",
+                self.quote_type(&field.value),
+                field.name
+            )?;
+        }
+        if !fields.is_empty() {
+            writeln!(self.out)?;
+        }
+
+        // Constructor.
+        writeln!(
+            self.out,
+            "public {}({}) {{",
+            name,
+            fields
+                .iter()
+                .map(|f| format!("{} _{}", self.quote_type(&f.value), &f.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        self.out.indent();
+        for field in fields {
+            if self.is_nullable(&field.value) {
+                writeln!(
+                    self.out,
+                    "if (_{0} == null) throw new ArgumentNullException(nameof(_{0}));",
+                    &field.name
+                )?;
+            }
+            writeln!(self.out, "{0} = _{0};", &field.name)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        // Serialize
+        if self.generator.config.serialization {
+            writeln!(
+                self.out,
+                "\npublic {}void Serialize(Serde.ISerializer serializer) {{",
+                fn_mods
+            )?;
+            self.out.indent();
+            writeln!(self.out, "serializer.increase_container_depth();")?;
+            if let Some(index) = variant_index {
+                writeln!(self.out, "serializer.serialize_variant_index({});", index)?;
+            }
+            for field in fields {
+                writeln!(
+                    self.out,
+                    "{}",
+                    self.quote_serialize_value(&field.name, &field.value)
+                )?;
+            }
+            writeln!(self.out, "serializer.decrease_container_depth();")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            if variant_index.is_none() {
+                for encoding in &self.generator.config.encodings {
+                    self.output_class_serialize_for_encoding(*encoding)?;
+                }
+            }
+        }
+
+        // Deserialize (struct) or Load (variant)
+        if self.generator.config.serialization {
+            if variant_index.is_none() {
+                writeln!(
+                    self.out,
+                    "\npublic static {}{} Deserialize(Serde.IDeserializer deserializer) {{",
+                    fn_mods, name,
+                )?;
+            } else {
+                writeln!(
+                    self.out,
+                    "\ninternal static {} Load(Serde.IDeserializer deserializer) {{",
+                    name,
+                )?;
+            }
+            self.out.indent();
+            writeln!(self.out, "deserializer.increase_container_depth();")?;
+            writeln!(
+                self.out,
+                "{0} obj = new {0}(\n\t{1});",
+                name,
+                fields
+                    .iter()
+                    .map(|f| self.quote_deserialize(&f.value))
+                    .collect::<Vec<_>>()
+                    .join(",\n\t")
+            )?;
+            writeln!(self.out, "deserializer.decrease_container_depth();")?;
+            writeln!(self.out, "return obj;")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            if variant_index.is_none() {
+                for encoding in &self.generator.config.encodings {
+                    self.output_class_deserialize_for_encoding(name, *encoding)?;
+                }
+            }
+        }
+        // JSON conversions
+        if self.has_json() {
+            self.output_json_conversions(variant_base, name, shape, fields)?;
+        }
+        // Equality
+        writeln!(
+            self.out,
+            "public override bool Equals(object obj) => obj is {} other && Equals(other);\n",
+            name
+        )?;
+        writeln!(
+            self.out,
+            "public static bool operator ==({0} left, {0} right) => Equals(left, right);\n",
+            name
+        )?;
+        writeln!(
+            self.out,
+            "public static bool operator !=({0} left, {0} right) => !Equals(left, right);\n",
+            name
+        )?;
+
+        writeln!(self.out, "public bool Equals({} other) {{", name)?;
+        self.out.indent();
+        writeln!(self.out, "if (other == null) return false;")?;
+        writeln!(self.out, "if (ReferenceEquals(this, other)) return true;")?;
+        for field in fields {
+            writeln!(
+                self.out,
                 "if (!{0}.Equals(other.{0})) return false;",
                 &field.name,
             )?;
@@ -857,6 +1510,52 @@ return new Serde.ValueArray<{0}>(obj);
         self.out.unindent();
         writeln!(self.out, "}}\n")?;
 
+        // Ordering
+        if self.generator.config.comparisons {
+            if let Some(index) = variant_index {
+                writeln!(
+                    self.out,
+                    "internal override int VariantIndex() => {};\n",
+                    index
+                )?;
+            }
+            writeln!(self.out, "public int CompareTo({} other) {{", name)?;
+            self.out.indent();
+            writeln!(self.out, "if (other == null) return 1;")?;
+            if !fields.is_empty() {
+                writeln!(self.out, "int result;")?;
+            }
+            for field in fields {
+                let a = format!("this.{}", field.name);
+                let b = format!("other.{}", field.name);
+                writeln!(
+                    self.out,
+                    "if ((result = {}) != 0) return result;",
+                    self.quote_compare(&a, &b, &field.value)
+                )?;
+            }
+            writeln!(self.out, "return 0;")?;
+            self.out.unindent();
+            writeln!(self.out, "}}\n")?;
+        }
+
+        // Validation
+        if self.generator.config.generates_validation() {
+            writeln!(self.out, "public {}void Validate() {{", fn_mods)?;
+            self.out.indent();
+            for field in fields {
+                if common::needs_validation(self.generator.config, &field.value, true) {
+                    writeln!(
+                        self.out,
+                        "{}",
+                        self.quote_validate(&field.name, &field.value)
+                    )?;
+                }
+            }
+            self.out.unindent();
+            writeln!(self.out, "}}\n")?;
+        }
+
         // Clone
         if variant_base.is_none() {
             // Derived classes can use the method inherited from the base class, it works with derived fields.
@@ -877,7 +1576,153 @@ return new Serde.ValueArray<{0}>(obj);
 
         // End of class
         self.leave_class(reserved_names);
-        writeln!(self.out, "}}")
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_json_conversions(
+        &mut self,
+        variant_base: Option<&str>,
+        name: &str,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        use common::JsonShape;
+        // Serialization
+        writeln!(
+            self.out,
+            "\npublic {}object ToJson() {{",
+            if variant_base.is_some() {
+                "override "
+            } else {
+                ""
+            }
+        )?;
+        self.out.indent();
+        let content = match shape {
+            JsonShape::Unit => "null".to_string(),
+            JsonShape::NewType => {
+                self.quote_to_json(&format!("this.{}", fields[0].name), &fields[0].value)
+            }
+            JsonShape::Tuple => {
+                writeln!(self.out, "var json = new List<object>({});", fields.len())?;
+                for field in fields {
+                    writeln!(
+                        self.out,
+                        "json.Add({});",
+                        self.quote_to_json(&format!("this.{}", field.name), &field.value)
+                    )?;
+                }
+                "json".to_string()
+            }
+            JsonShape::Struct => {
+                writeln!(self.out, "var json = new Json.JsonObject();")?;
+                for field in fields {
+                    writeln!(
+                        self.out,
+                        "json.Put(\"{}\", {});",
+                        field.name,
+                        self.quote_to_json(&format!("this.{}", field.name), &field.value)
+                    )?;
+                }
+                "json".to_string()
+            }
+        };
+        match variant_base {
+            Some(_) if shape == JsonShape::Unit => writeln!(self.out, "return \"{}\";", name)?,
+            Some(_) => writeln!(
+                self.out,
+                "return Json.Json.serialize_variant(\"{}\", {});",
+                name, content
+            )?,
+            None => writeln!(self.out, "return {};", content)?,
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        // Deserialization
+        if variant_base.is_none() {
+            writeln!(
+                self.out,
+                "\npublic static {} FromJson(object json) {{",
+                name,
+            )?;
+            self.out.indent();
+            if shape == JsonShape::Unit {
+                writeln!(self.out, "Json.Json.deserialize_unit(json);")?;
+            }
+        } else {
+            writeln!(
+                self.out,
+                "\ninternal static {} LoadJson(Json.JsonVariant variant) {{",
+                name,
+            )?;
+            self.out.indent();
+            if shape == JsonShape::Unit {
+                writeln!(self.out, "variant.Unit();")?;
+            } else {
+                writeln!(self.out, "object json = variant.Value();")?;
+            }
+        }
+        let values = match shape {
+            JsonShape::Unit => Vec::new(),
+            JsonShape::NewType => vec![self.quote_from_json("json", &fields[0].value)],
+            JsonShape::Tuple => {
+                writeln!(
+                    self.out,
+                    "List<object> items = Json.Json.deserialize_array(json, {});",
+                    fields.len()
+                )?;
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        self.quote_from_json(&format!("items[{}]", index), &field.value)
+                    })
+                    .collect()
+            }
+            JsonShape::Struct => {
+                writeln!(
+                    self.out,
+                    "Json.JsonObject obj = Json.Json.deserialize_object(json);"
+                )?;
+                fields
+                    .iter()
+                    .map(|field| {
+                        // Like Serde, missing optional fields are read as `None`.
+                        let getter = match field.value {
+                            Format::Option(_) => "OptionalField",
+                            _ => "Field",
+                        };
+                        self.quote_from_json(
+                            &format!("obj.{}(\"{}\")", getter, field.name),
+                            &field.value,
+                        )
+                    })
+                    .collect()
+            }
+        };
+        writeln!(
+            self.out,
+            "return new {}({});",
+            name,
+            values
+                .iter()
+                .map(|value| format!("\n\t{}", value))
+                .collect::<Vec<_>>()
+                .join(",")
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// The `IComparable` interface implemented by containers, if comparisons are enabled.
+    fn comparable_interface(&self, name: &str) -> String {
+        if self.generator.config.comparisons {
+            format!("IComparable<{}>, ", name)
+        } else {
+            String::new()
+        }
     }
 
     fn output_enum_container(
@@ -889,8 +1734,9 @@ return new Serde.ValueArray<{0}>(obj);
         self.output_comment(name)?;
         writeln!(
             self.out,
-            "public abstract class {0}: IEquatable<{0}>, ICloneable {{",
-            name
+            "public abstract class {0}: IEquatable<{0}>, {1}ICloneable {{",
+            name,
+            self.comparable_interface(name),
         )?;
         let reserved_names = variants
             .values()
@@ -939,6 +1785,36 @@ switch (index) {{"#,
                 self.output_class_deserialize_for_encoding(name, *encoding)?;
             }
         }
+        if self.has_json() {
+            writeln!(self.out, "\npublic abstract object ToJson();")?;
+            write!(
+                self.out,
+                r#"
+public static {} FromJson(object json) {{
+    Json.JsonVariant variant = Json.Json.deserialize_variant(json);
+    switch (variant.Name) {{"#,
+                name
+            )?;
+            self.out.indent();
+            self.out.indent();
+            writeln!(self.out)?;
+            for variant in variants.values() {
+                writeln!(
+                    self.out,
+                    "case \"{0}\": return {0}.LoadJson(variant);",
+                    variant.name,
+                )?;
+            }
+            writeln!(
+                self.out,
+                r#"default: throw new Serde.DeserializationException("Unknown variant for {}: " + variant.Name);"#,
+                name,
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+            self.out.unindent();
+            writeln!(self.out, "}}\n")?;
+        }
 
         // HashCode
         writeln!(self.out, "public override int GetHashCode() {{")?;
@@ -983,6 +1859,38 @@ switch (index) {{"#,
         self.out.unindent();
         writeln!(self.out, "}}\n")?;
 
+        // Ordering
+        if self.generator.config.comparisons {
+            writeln!(self.out, "internal abstract int VariantIndex();\n")?;
+            writeln!(self.out, "public int CompareTo({} other) {{", name)?;
+            self.out.indent();
+            writeln!(self.out, "if (other == null) return 1;")?;
+            writeln!(
+                self.out,
+                "if (GetType() != other.GetType()) return VariantIndex().CompareTo(other.VariantIndex());"
+            )?;
+            writeln!(self.out, "switch (this) {{")?;
+            for variant in variants.values() {
+                writeln!(
+                    self.out,
+                    "case {0} x: return x.CompareTo(({0})other);",
+                    variant.name
+                )?;
+            }
+            writeln!(
+                self.out,
+                r#"default: throw new InvalidOperationException("Unknown variant type");"#
+            )?;
+            writeln!(self.out, "}}")?;
+            self.out.unindent();
+            writeln!(self.out, "}}\n")?;
+        }
+
+        // Validation
+        if self.generator.config.generates_validation() {
+            writeln!(self.out, "public abstract void Validate();\n")?;
+        }
+
         // Clone
         writeln!(
             self.out,
@@ -997,7 +1905,8 @@ switch (index) {{"#,
 
         self.output_variants(name, variants)?;
         self.leave_class(&reserved_names);
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
     fn output_cstyle_enum(
@@ -1015,11 +1924,23 @@ switch (index) {{"#,
         self.out.unindent();
         writeln!(self.out, "}}")?;
 
-        if self.generator.config.serialization {
+        if self.generator.config.serialization || self.generator.config.generates_validation() {
             let ext_name = format!("{}Extensions", name.to_camel_case());
             writeln!(self.out, "public static class {} {{", ext_name)?;
             self.enter_class(&ext_name, &[]);
-
+        }
+        if self.generator.config.generates_validation() {
+            writeln!(
+                self.out,
+                r#"
+public static void Validate(this {0} value) {{
+    if (!Enum.IsDefined(typeof({0}), value))
+        throw new Serde.SerializationException("Unknown variant index for {0}: " + (int)value);
+}}"#,
+                name
+            )?;
+        }
+        if self.generator.config.serialization {
             writeln!(
                 self.out,
                 r#"
@@ -1041,21 +1962,41 @@ public static {0} Deserialize(Serde.IDeserializer deserializer) {{
                 name
             )?;
 
+            if self.has_json() {
+                self.output_cstyle_enum_json_conversions(name, variants)?;
+            }
             for encoding in &self.generator.config.encodings {
+                if *encoding == Encoding::Json {
+                    writeln!(
+                        self.out,
+                        r#"
+public static string JsonSerialize(this {} value) {{{}
+    return Json.Json.stringify(value.ToJson());
+}}"#,
+                        name,
+                        self.quote_validate_on_serialize("value."),
+                    )?;
+                    self.output_class_deserialize_for_encoding(name, *encoding)?;
+                    continue;
+                }
                 writeln!(
                     self.out,
                     r#"
-public static byte[] {0}Serialize(this {1} value)  {{
-    Serde.ISerializer serializer = new {0}.{0}Serializer();
-    Serialize(value, serializer);
+public static byte[] {0}Serialize(this {1} value)  {{{2}
+    Serde.ISerializer serializer = new {0}.{0}Serializer({3});
+    Serialize(value, serializer);{4}
     return serializer.get_bytes();
 }}"#,
                     encoding.name().to_camel_case(),
-                    name
+                    name,
+                    self.quote_validate_on_serialize("value."),
+                    self.quote_runtime_arguments(*encoding, ""),
+                    self.quote_serialization_checks(*encoding),
                 )?;
                 self.output_class_deserialize_for_encoding(name, *encoding)?;
             }
-
+        }
+        if self.generator.config.serialization || self.generator.config.generates_validation() {
             self.leave_class(&[]);
             writeln!(self.out, "}}")?;
         }
@@ -1063,25 +2004,133 @@ public static byte[] {0}Serialize(this {1} value)  {{
         Ok(())
     }
 
+    /// The JSON conversions of a C-style enum, written as the names of its variants.
+    fn output_cstyle_enum_json_conversions(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            "\npublic static object ToJson(this {} value) {{",
+            name
+        )?;
+        self.out.indent();
+        writeln!(self.out, "switch (value) {{")?;
+        for variant in variants.values() {
+            writeln!(
+                self.out,
+                "case {0}.{1}: return \"{1}\";",
+                name, variant.name
+            )?;
+        }
+        writeln!(
+            self.out,
+            r#"default: throw new Serde.SerializationException("Unknown variant index for {}: " + (int)value);"#,
+            name
+        )?;
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        writeln!(
+            self.out,
+            "\npublic static {} FromJson(object json) {{",
+            name
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "Json.JsonVariant variant = Json.Json.deserialize_variant(json);"
+        )?;
+        writeln!(self.out, "switch (variant.Name) {{")?;
+        for variant in variants.values() {
+            writeln!(
+                self.out,
+                "case \"{1}\": variant.Unit(); return {0}.{1};",
+                name, variant.name
+            )?;
+        }
+        writeln!(
+            self.out,
+            r#"default: throw new Serde.DeserializationException("Unknown variant for {}: " + variant.Name);"#,
+            name
+        )?;
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
     fn output_class_serialize_for_encoding(&mut self, encoding: Encoding) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+public string JsonSerialize() {{{}
+    return Json.Json.stringify(ToJson());
+}}"#,
+                self.quote_validate_on_serialize(""),
+            )?;
+            return Ok(());
+        }
         writeln!(
             self.out,
             r#"
 public int {0}Serialize(byte[] outputBuffer) => {0}Serialize(new ArraySegment<byte>(outputBuffer));
 
-public int {0}Serialize(ArraySegment<byte> outputBuffer) {{
-    Serde.ISerializer serializer = new {0}.{0}Serializer(outputBuffer);
-    Serialize(serializer);
+public int {0}Serialize(ArraySegment<byte> outputBuffer) {{{1}
+    Serde.ISerializer serializer = new {0}.{0}Serializer({2});
+    Serialize(serializer);{4}
     return serializer.get_buffer_offset();
 }}
 
-public byte[] {0}Serialize()  {{
-    Serde.ISerializer serializer = new {0}.{0}Serializer();
-    Serialize(serializer);
+public byte[] {0}Serialize()  {{{1}
+    Serde.ISerializer serializer = new {0}.{0}Serializer({3});
+    Serialize(serializer);{4}
     return serializer.get_bytes();
 }}"#,
-            encoding.name().to_camel_case()
-        )
+            encoding.name().to_camel_case(),
+            self.quote_validate_on_serialize(""),
+            self.quote_runtime_arguments(encoding, "outputBuffer"),
+            self.quote_runtime_arguments(encoding, ""),
+            self.quote_serialization_checks(encoding),
+        )?;
+        Ok(())
+    }
+
+    /// The arguments of the constructors of the runtime, starting with `input` if not empty.
+    fn quote_runtime_arguments(&self, encoding: Encoding, input: &str) -> String {
+        let options = self.generator.config.bincode_options_for(encoding);
+        let arguments = common::bincode_runtime_arguments(&options);
+        if input.is_empty() || arguments.is_empty() {
+            format!("{}{}", input, arguments)
+        } else {
+            format!("{}, {}", input, arguments)
+        }
+    }
+
+    /// Checks on the `serializer` after serializing a value.
+    fn quote_serialization_checks(&self, encoding: Encoding) -> String {
+        match self.generator.config.bincode_options_for(encoding).limit {
+            Some(limit) => format!(
+                r#"
+    if ((ulong)serializer.get_buffer_offset() > {}UL) {{
+         throw new Serde.SerializationException("Exceeded the size limit of the encoding");
+    }}"#,
+                limit
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// The call to `Validate` at the beginning of serialization methods, if enabled.
+    fn quote_validate_on_serialize(&self, receiver: &str) -> String {
+        if self.generator.config.validate_on_serialize {
+            format!("\n    {}Validate();", receiver)
+        } else {
+            String::new()
+        }
     }
 
     fn output_class_deserialize_for_encoding(
@@ -1089,6 +2138,35 @@ public byte[] {0}Serialize()  {{
         name: &str,
         encoding: Encoding,
     ) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+public static {} JsonDeserialize(string input) => FromJson(Json.Json.parse(input));"#,
+                name,
+            )?;
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(encoding);
+        let checks = match options.trailing_bytes {
+            TrailingBytes::Reject => {
+                r#"
+    if (deserializer.get_buffer_offset() < input.Count) {
+         throw new Serde.DeserializationException("Some input bytes were not read");
+    }"#
+            }
+            TrailingBytes::Allow => "",
+        };
+        // The runtime enforces the size limit while reading.
+        let arguments = match options.limit {
+            Some(limit) => format!(
+                "input, {}, {}, {}L",
+                options.int_encoding == IntEncoding::Varint,
+                options.endianness == Endianness::Big,
+                limit.min(i64::MAX as u64)
+            ),
+            None => self.quote_runtime_arguments(encoding, "input"),
+        };
         writeln!(
             self.out,
             r#"
@@ -1098,16 +2176,16 @@ public static {0} {1}Deserialize(ArraySegment<byte> input) {{
     if (input == null) {{
          throw new Serde.DeserializationException("Cannot deserialize null array");
     }}
-    Serde.IDeserializer deserializer = new {1}.{1}Deserializer(input);
-    {0} value = Deserialize(deserializer);
-    if (deserializer.get_buffer_offset() < input.Count) {{
-         throw new Serde.DeserializationException("Some input bytes were not read");
-    }}
+    Serde.IDeserializer deserializer = new {1}.{1}Deserializer({2});
+    {0} value = Deserialize(deserializer);{3}
     return value;
 }}"#,
             name,
-            encoding.name().to_camel_case()
-        )
+            encoding.name().to_camel_case(),
+            arguments,
+            checks,
+        )?;
+        Ok(())
     }
 
     fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
@@ -1127,6 +2205,9 @@ public static {0} {1}Deserialize(ArraySegment<byte> input) {{
                 })
                 .collect::<Vec<_>>(),
             Struct(fields) => fields.clone(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("C#", name)),
+            FlattenedStruct(_) => return Err(common::flattened_struct_not_supported("C#", name)),
             Enum(variants) => {
                 if variants
                     .iter()
@@ -1140,7 +2221,8 @@ public static {0} {1}Deserialize(ArraySegment<byte> input) {{
                 return Ok(());
             }
         };
-        self.output_struct_or_variant_container(None, None, name, &fields)
+        let shape = common::JsonShape::of_container(format);
+        self.output_struct_or_variant_container(None, None, name, shape, &fields)
     }
 }
 
@@ -1154,11 +2236,7 @@ impl Installer {
         Installer { install_dir }
     }
 
-    fn install_runtime(
-        &self,
-        source_dir: include_dir::Dir,
-        path: &str,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    fn install_runtime(&self, source_dir: include_dir::Dir, path: &str) -> Result<()> {
         let dir_path = self.install_dir.join(path);
         std::fs::create_dir_all(&dir_path)?;
         for entry in source_dir.files() {
@@ -1170,7 +2248,7 @@ impl Installer {
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
@@ -1187,7 +2265,7 @@ impl crate::SourceInstaller for Installer {
 
         let back_path: String = "..\\"
             .to_string()
-            .repeat(dir_path.strip_prefix(&self.install_dir)?.iter().count());
+            .repeat(config.module_name.split('.').count());
         let mut deps = vec!["Serde".to_string()];
         for encoding in &config.encodings {
             deps.push(encoding.name().to_camel_case());
@@ -1235,4 +2313,20 @@ impl crate::SourceInstaller for Installer {
     fn install_bcs_runtime(&self) -> std::result::Result<(), Self::Error> {
         self.install_runtime(include_directory!("runtime/csharp/Bcs"), "Bcs")
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/csharp/Postcard"), "Postcard")
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/csharp/Borsh"), "Borsh")
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/csharp/Scale"), "Scale")
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/csharp/Json"), "Json")
+    }
 }
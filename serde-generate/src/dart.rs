@@ -4,21 +4,114 @@
 use crate::{
     common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig, Encoding,
+    naming, BincodeOptions, Capabilities, Case, CodeGeneratorConfig, Encoding, Endianness, Error,
+    IntEncoding, Result, SourceFiles, TrailingBytes,
 };
 use heck::{CamelCase, MixedCase, SnakeCase};
 use include_dir::include_dir as include_directory;
 use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
 use std::{
     collections::{BTreeMap, HashMap},
-    io::{Result, Write},
+    io::Write,
     path::{Path, PathBuf},
 };
 
+/// Features supported by the Dart code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Dart",
+    c_style_enums: true,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: crate::capabilities::ALL_ENCODINGS_AND_JSON,
+    package_manifest: true,
+    naming_conventions: true,
+    file_naming_conventions: true,
+    modules: true,
+    module_cycles: true,
+    native_types: true,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Reserved words of Dart, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "Function",
+    "abstract",
+    "as",
+    "assert",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "covariant",
+    "default",
+    "deferred",
+    "do",
+    "dynamic",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "extension",
+    "external",
+    "factory",
+    "false",
+    "final",
+    "finally",
+    "for",
+    "get",
+    "hide",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "interface",
+    "is",
+    "late",
+    "library",
+    "mixin",
+    "new",
+    "null",
+    "on",
+    "operator",
+    "part",
+    "required",
+    "rethrow",
+    "return",
+    "set",
+    "show",
+    "static",
+    "super",
+    "switch",
+    "sync",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
 /// Main configuration object for code-generation in Dart.
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
     config: &'a CodeGeneratorConfig,
+    /// Mapping from external type names to names qualified by the library prefix of their module
+    /// (e.g. "MyClass" -> "my_module.MyClass").
+    /// Derived from `config.external_definitions`, except for the special key "import".
+    external_qualified_names: HashMap<String, String>,
 }
 
 /// Shared state for the code generation of a Dart source file.
@@ -33,21 +126,57 @@ struct DartEmitter<'a, T> {
     registry: &'a Registry,
 }
 
+/// The modules of external definitions, except for the special key "import" that lists
+/// additional files to import.
+fn external_modules(config: &CodeGeneratorConfig) -> impl Iterator<Item = (&String, &Vec<String>)> {
+    config
+        .external_definitions
+        .iter()
+        .filter(|(namespace, _)| namespace.as_str() != "import")
+}
+
 impl<'a> CodeGenerator<'a> {
     /// Create a Dart code generator for the given config.
     pub fn new(config: &'a CodeGeneratorConfig) -> Self {
         let mut external_qualified_names = HashMap::new();
-        for (namespace, names) in &config.external_definitions {
+        for (namespace, names) in external_modules(config) {
             for name in names {
                 external_qualified_names
                     .insert(name.to_string(), format!("{}.{}", namespace, name));
             }
         }
-        Self { config }
+        Self {
+            config,
+            external_qualified_names,
+        }
+    }
+
+    /// Source files are in snake case unless a naming convention is given.
+    fn file_name(&self, name: &str) -> String {
+        match self.config.naming.files {
+            Case::Preserve => name.to_snake_case(),
+            case => case.apply(name),
+        }
     }
 
     /// Output class definitions for `registry`.
     pub fn output(&self, install_dir: std::path::PathBuf, registry: &Registry) -> Result<()> {
+        self.source_files(registry)?.write_to(&install_dir)?;
+        Ok(())
+    }
+
+    /// Generate class definitions in memory, following the layout of a Dart package.
+    fn source_files(&self, registry: &Registry) -> Result<SourceFiles> {
+        CAPABILITIES.check(registry, self.config)?;
+        let registry = &common::remove_native_types(self.config, registry);
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).source_files(&registry);
+        }
         let current_namespace = self
             .config
             .module_name
@@ -55,38 +184,44 @@ impl<'a> CodeGenerator<'a> {
             .map(String::from)
             .collect::<Vec<_>>();
 
-        let mut dir_path = install_dir;
-        std::fs::create_dir_all(&dir_path)?;
-        dir_path = dir_path.join("lib").join("src");
+        let mut dir_path = Path::new("lib").join("src");
         for part in &current_namespace {
             dir_path = dir_path.join(part);
         }
-        std::fs::create_dir_all(&dir_path)?;
 
+        let mut files = SourceFiles::new();
         for (name, format) in registry {
-            self.write_container_class(
-                &dir_path,
-                current_namespace.clone(),
-                name,
-                format,
-                registry,
-            )?;
+            let content =
+                self.write_container_class(current_namespace.clone(), name, format, registry)?;
+            files.insert_bytes(dir_path.join(self.file_name(name) + ".dart"), content);
         }
-        self.write_helper_class(&dir_path, current_namespace.clone(), registry)?;
-        self.write_library(&dir_path, current_namespace, registry)?;
-        Ok(())
+        let content = self.write_helper_class(current_namespace.clone(), registry)?;
+        files.insert_bytes(dir_path.join("trait_helpers.dart"), content);
+        let content = self.write_library(current_namespace, registry)?;
+        files.insert_bytes(
+            dir_path.join(self.config.module_name.clone() + ".dart"),
+            content,
+        );
+
+        // The main module file exports the public API.
+        files.insert(
+            Path::new("lib").join(format!("{}.dart", &self.config.module_name)),
+            format!(
+                "export 'src/{name}/{name}.dart';",
+                name = &self.config.module_name
+            ),
+        );
+        Ok(files)
     }
 
     fn write_library(
         &self,
-        install_dir: &Path,
         current_namespace: Vec<String>,
         registry: &Registry,
-    ) -> Result<()> {
-        let mut file =
-            std::fs::File::create(install_dir.join(self.config.module_name.clone() + ".dart"))?;
+    ) -> Result<Vec<u8>> {
+        let mut content = Vec::new();
         let mut emitter = DartEmitter {
-            out: IndentedWriter::new(&mut file, IndentConfig::Space(2)),
+            out: IndentedWriter::new(&mut content, IndentConfig::Space(2)),
             generator: self,
             current_namespace,
             registry,
@@ -117,54 +252,72 @@ import '../serde/serde.dart';"#,
                 writeln!(&mut emitter.out, "import '{0}';", file)?;
             }
         }
+        for (namespace, _) in external_modules(self.config) {
+            writeln!(
+                &mut emitter.out,
+                "import '../{0}/{0}.dart' as {0};",
+                namespace
+            )?;
+        }
 
         writeln!(&mut emitter.out, "\nexport '../serde/serde.dart';")?;
 
         writeln!(&mut emitter.out, "\npart 'trait_helpers.dart';")?;
         for name in registry.keys() {
-            writeln!(&mut emitter.out, "part '{}.dart';", name.to_snake_case())?;
+            writeln!(&mut emitter.out, "part '{}.dart';", self.file_name(name))?;
         }
 
-        Ok(())
+        Ok(content)
     }
 
     fn write_container_class(
         &self,
-        dir_path: &std::path::Path,
         current_namespace: Vec<String>,
         name: &str,
         format: &ContainerFormat,
         registry: &Registry,
-    ) -> Result<()> {
-        let mut file =
-            std::fs::File::create(dir_path.join(name.to_string().to_snake_case() + ".dart"))?;
+    ) -> Result<Vec<u8>> {
+        let mut content = Vec::new();
         let mut emitter = DartEmitter {
-            out: IndentedWriter::new(&mut file, IndentConfig::Space(2)),
+            out: IndentedWriter::new(&mut content, IndentConfig::Space(2)),
             generator: self,
             current_namespace,
             registry,
         };
 
         emitter.output_preamble()?;
-        emitter.output_container(name, format)
+        emitter.output_container(name, format)?;
+        Ok(content)
     }
 
     fn write_helper_class(
         &self,
-        dir_path: &std::path::Path,
         current_namespace: Vec<String>,
         registry: &Registry,
-    ) -> Result<()> {
-        let mut file = std::fs::File::create(dir_path.join("trait_helpers.dart"))?;
+    ) -> Result<Vec<u8>> {
+        let mut content = Vec::new();
         let mut emitter = DartEmitter {
-            out: IndentedWriter::new(&mut file, IndentConfig::Space(2)),
+            out: IndentedWriter::new(&mut content, IndentConfig::Space(2)),
             generator: self,
             current_namespace,
             registry,
         };
 
         emitter.output_preamble()?;
-        emitter.output_trait_helpers(registry)
+        emitter.output_trait_helpers(registry)?;
+        Ok(content)
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(&self, registry: &Registry) -> std::result::Result<SourceFiles, Self::Error> {
+        self.source_files(registry)
     }
 }
 
@@ -210,6 +363,9 @@ where
     }
 
     fn quote_qualified_name(&self, name: &str) -> String {
+        if let Some(qname) = self.generator.external_qualified_names.get(name) {
+            return qname.clone();
+        }
         match name {
             "List" => "List_".to_string(),
             "Map" => "Map_".to_string(),
@@ -225,10 +381,29 @@ where
         }
     }
 
+    /// Fields are in lower camel case unless a naming convention is given.
+    fn field_name(&self, name: &str) -> String {
+        match self.generator.config.naming.fields {
+            Case::Preserve => self.quote_field(&name.to_mixed_case()),
+            _ => self.quote_field(name),
+        }
+    }
+
+    /// Values of native enums are in lower camel case unless a naming convention is given.
+    fn enum_value_name(&self, name: &str) -> String {
+        match self.generator.config.naming.variants {
+            Case::Preserve => self.quote_field(&name.to_mixed_case()),
+            _ => self.quote_field(name),
+        }
+    }
+
     fn quote_type(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(x) => self.quote_qualified_name(x),
+            TypeName(x) => match self.generator.config.native_types.get(x) {
+                Some(native) => native.name.clone(),
+                None => self.quote_qualified_name(x),
+            },
             Unit => "Unit".into(),
             Bool => "bool".into(),
             I8 => "int".into(),
@@ -269,7 +444,10 @@ where
     fn quote_serialize_value(&self, value: &str, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(_) => format!("{}.serialize(serializer);", value),
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!("{}({}, serializer);", native.serializer, value),
+                None => format!("{}.serialize(serializer);", value),
+            },
             Unit => format!("serializer.serializeUnit({});", value),
             Bool => format!("serializer.serializeBool({});", value),
             I8 => format!("serializer.serializeInt8({});", value),
@@ -299,12 +477,13 @@ where
     fn quote_deserialize(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(name) => {
-                format!(
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!("{}(deserializer)", native.deserializer),
+                None => format!(
                     "{}.deserialize(deserializer)",
                     self.quote_qualified_name(&self.get_class(name))
-                )
-            }
+                ),
+            },
             Unit => "deserializer.deserializeUnit()".to_string(),
             Bool => "deserializer.deserializeBool()".to_string(),
             I8 => "deserializer.deserializeInt8()".to_string(),
@@ -330,6 +509,37 @@ where
         }
     }
 
+    fn quote_compare(&self, a: &str, b: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            Unit => "0".to_string(),
+            Bool => format!("compareBools({}, {})", a, b),
+            Str => format!("compareStrings({}, {})", a, b),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            Option(_) | Seq(_) | Map { .. } | Tuple(_) | TupleArray { .. } => format!(
+                "{}.compare{}({}, {})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format).to_camel_case(),
+                a,
+                b
+            ),
+            _ => format!("{}.compareTo({})", a, b),
+        }
+    }
+
+    fn quote_hash(&self, value: &str, format: &Format) -> String {
+        if Self::needs_helper(format) {
+            format!(
+                "{}.hash{}({})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format).to_camel_case(),
+                value
+            )
+        } else {
+            format!("{}.hashCode", value)
+        }
+    }
+
     fn enter_class(&mut self, name: &str) {
         self.out.indent();
         self.current_namespace.push(name.to_string());
@@ -355,11 +565,27 @@ where
         writeln!(self.out, "class TraitHelpers {{")?;
         self.enter_class("TraitHelpers");
         for (mangled_name, subtype) in &subtypes {
-            self.output_serialization_helper(mangled_name, subtype)?;
-            self.output_deserialization_helper(mangled_name, subtype)?;
+            if self.generator.config.serialization {
+                self.output_serialization_helper(mangled_name, subtype)?;
+                self.output_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.has_json() {
+                self.output_json_serialization_helper(mangled_name, subtype)?;
+                self.output_json_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.comparisons {
+                self.output_comparison_helper(mangled_name, subtype)?;
+                self.output_hash_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.generates_validation()
+                && common::needs_validation(self.generator.config, subtype, true)
+            {
+                self.output_validation_helper(mangled_name, subtype)?;
+            }
         }
         self.leave_class();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
     fn needs_helper(format: &Format) -> bool {
@@ -452,7 +678,8 @@ for (final item in value) {{
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
     fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
@@ -559,163 +786,651 @@ return obj;
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
-    fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
-        use ContainerFormat::*;
-        let fields = match format {
-            UnitStruct => Vec::new(),
-            NewTypeStruct(format) => {
-                vec![Named {
-                    name: "value".to_string(),
-                    value: format.as_ref().clone(),
-                }]
-            }
-            TupleStruct(formats) => formats
-                .iter()
-                .enumerate()
-                .map(|(i, f)| Named {
-                    name: format!("field{}", i),
-                    value: f.clone(),
-                })
-                .collect::<Vec<_>>(),
-            Struct(fields) => fields.clone(),
-            Enum(variants) => {
-                // When we find an enum with all Unit variants, we ser/de as a regular Dart enum.
-                if self.generator.config.c_style_enums
-                    && variants.values().all(|f| f.value == VariantFormat::Unit)
-                {
-                    self.output_enum_container(name, variants)?;
-                } else {
-                    self.output_enum_class_container(name, variants)?;
-                }
-                return Ok(());
-            }
-        };
-        self.output_struct_or_variant_container(None, None, name, &fields)
+    /// Whether to generate the JSON encoding, which reads and writes values as trees of
+    /// JSON values rather than through a `BinarySerializer` or a `BinaryDeserializer`.
+    fn has_json(&self) -> bool {
+        self.generator.config.serialization
+            && self.generator.config.encodings.contains(&Encoding::Json)
     }
 
-    fn output_struct_or_variant_container(
-        &mut self,
-        variant_base: Option<&str>,
-        variant_index: Option<u32>,
-        name: &str,
-        fields: &[Named<Format>],
-    ) -> Result<()> {
-        let field_count = fields.len();
+    /// Suffix of the JSON helpers of the runtime for a primitive format, following the
+    /// methods of `BinarySerializer`.
+    fn json_primitive_name(format: &Format) -> Option<&'static str> {
+        use Format::*;
+        Some(match format {
+            Unit => "Unit",
+            Bool => "Bool",
+            I8 => "Int8",
+            I16 => "Int16",
+            I32 => "Int32",
+            I64 => "Int64",
+            I128 => "Int128",
+            U8 => "Uint8",
+            U16 => "Uint16",
+            U32 => "Uint32",
+            U64 => "Uint64",
+            U128 => "Uint128",
+            F32 => "Float32",
+            F64 => "Float64",
+            Char => "Char",
+            Str => "String",
+            Bytes => "Bytes",
+            _ => return None,
+        })
+    }
 
-        // Beginning of class
-        writeln!(self.out)?;
-        self.output_comment(name)?;
-        if let Some(base) = variant_base {
-            writeln!(
-                self.out,
-                "@immutable\nclass {} extends {} {{",
-                self.quote_qualified_name(name),
-                base
-            )?;
-        } else {
-            writeln!(
-                self.out,
-                "@immutable\nclass {} {{",
-                self.quote_qualified_name(name)
-            )?;
+    fn quote_to_json(&self, value: &str, format: &Format) -> String {
+        if let Format::TypeName(_) = format {
+            return format!("{}.toJson()", value);
         }
-        self.enter_class(name);
+        match Self::json_primitive_name(format) {
+            Some(primitive) => format!("Json.serialize{}({})", primitive, value),
+            None => format!(
+                "{}.toJson{}({})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format).to_camel_case(),
+                value
+            ),
+        }
+    }
 
-        // Constructor.
-        writeln!(
+    fn quote_from_json(&self, json: &str, format: &Format) -> String {
+        if let Format::TypeName(name) = format {
+            return format!(
+                "{}.fromJson({})",
+                self.quote_qualified_name(&self.get_class(name)),
+                json
+            );
+        }
+        match Self::json_primitive_name(format) {
+            Some(primitive) => format!("Json.deserialize{}({})", primitive, json),
+            None => format!(
+                "{}.fromJson{}({})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format).to_camel_case(),
+                json
+            ),
+        }
+    }
+
+    /// The order used to sort the entries of maps with keys of the given format.
+    fn quote_json_key_order(&self, key: &Format) -> String {
+        match common::JsonKeyOrder::of_key(self.registry, key) {
+            common::JsonKeyOrder::Text => "JsonObject.stringOrder".to_string(),
+            common::JsonKeyOrder::Number => "JsonObject.numberOrder".to_string(),
+            common::JsonKeyOrder::Variants(names) => format!(
+                "JsonObject.variantOrder([{}])",
+                names
+                    .iter()
+                    .map(|name| format!("'{}'", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn output_json_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
             self.out,
-            "const {}({}",
-            self.quote_qualified_name(name),
-            if !fields.is_empty() { "{" } else { "" }
+            "static JsonValue toJson{}({} value) {{",
+            name.to_camel_case(),
+            self.quote_type(format0)
         )?;
         self.out.indent();
-        for field in fields.iter() {
-            let field_name = self.quote_field(&field.name.to_mixed_case());
-            match &field.value {
-                Format::Option(_) => writeln!(self.out, "this.{},", field_name)?,
-                _ => writeln!(self.out, "required this.{},", field_name)?,
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (value == null) {{
+    return null;
+}} else {{
+    return {};
+}}
+"#,
+                    self.quote_to_json("value", format)
+                )?;
             }
-        }
-        self.out.unindent();
-        if variant_base.is_some() {
-            writeln!(
-                self.out,
-                "{}) : super();",
-                if !fields.is_empty() { "}" } else { "" }
-            )?;
-        } else {
-            writeln!(self.out, "{});", if !fields.is_empty() { "}" } else { "" })?;
-        }
 
-        if self.generator.config.serialization {
-            // Deserialize (struct) or Load (variant)
-            if variant_index.is_none() {
-                writeln!(
+            Seq(format) => {
+                write!(
                     self.out,
-                    "\nstatic {} deserialize(BinaryDeserializer deserializer) {{",
-                    self.quote_qualified_name(name)
+                    r#"
+return value.map<JsonValue>((item) => {}).toList();
+"#,
+                    self.quote_to_json("item", format)
                 )?;
-            } else {
-                writeln!(
+            }
+
+            Map { key, value } => {
+                // Entries are sorted as in a `BTreeMap`, whatever the order of the map.
+                write!(
                     self.out,
-                    "\nstatic {} load(BinaryDeserializer deserializer) {{",
-                    self.quote_qualified_name(name)
+                    r#"
+final json = JsonObject();
+for (final entry in value.entries) {{
+    json.put(Json.key({}), {});
+}}
+json.sortKeys({});
+return json;
+"#,
+                    self.quote_to_json("entry.key", key),
+                    self.quote_to_json("entry.value", value),
+                    self.quote_json_key_order(key),
                 )?;
             }
 
-            self.out.indent();
-            writeln!(self.out, "deserializer.increaseContainerDepth();")?;
-            writeln!(
-                self.out,
-                "final instance = {}(",
-                self.quote_qualified_name(name)
-            )?;
-            self.out.indent();
-            for field in fields {
-                writeln!(
+            Tuple(formats) => {
+                write!(
                     self.out,
-                    "{}: {},",
-                    self.quote_field(&field.name.to_mixed_case()),
-                    self.quote_deserialize(&field.value)
+                    "\nreturn <JsonValue>[{}\n];\n",
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(index, format)| format!(
+                            "\n    {}",
+                            self.quote_to_json(&format!("value.item{}", index + 1), format)
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(",")
                 )?;
             }
-            self.out.unindent();
-            writeln!(self.out, ");")?;
-            writeln!(self.out, "deserializer.decreaseContainerDepth();")?;
-            writeln!(self.out, "return instance;")?;
-            self.out.unindent();
-            writeln!(self.out, "}}")?;
 
-            if variant_index.is_none() {
-                for encoding in &self.generator.config.encodings {
-                    self.output_class_deserialize_for_encoding(name, *encoding)?;
-                }
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+if (value.length != {0}) {{
+    throw Exception('Invalid length for fixed-size array: ${{value.length}} instead of {0}');
+}}
+return value.map<JsonValue>((item) => {1}).toList();
+"#,
+                    size,
+                    self.quote_to_json("item", content),
+                )?;
             }
-        }
 
-        // Fields
-        if !fields.is_empty() {
-            writeln!(self.out)?;
-        }
-        for field in fields {
-            writeln!(
-                self.out,
-                "final {} {};",
-                self.quote_type(&field.value),
-                self.quote_field(&field.name.to_mixed_case())
-            )?;
+            _ => panic!("unexpected case"),
         }
-        if !fields.is_empty() {
-            writeln!(self.out)?;
-
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_json_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "static {} fromJson{}(JsonValue json) {{",
+            self.quote_type(format0),
+            name.to_camel_case(),
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (json == null) {{
+    return null;
+}} else {{
+    return {};
+}}
+"#,
+                    self.quote_from_json("json", format),
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+return Json.deserializeArray(json).map<{}>((item) => {}).toList();
+"#,
+                    self.quote_type(format),
+                    self.quote_from_json("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+final entries = Json.deserializeObject(json);
+final obj = <{0}, {1}>{{}};
+for (var i = 0; i < entries.length; i++) {{
+    obj[{2}] = {3};
+}}
+return obj;
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_from_json("JsonKey(entries.keys[i])", key),
+                    self.quote_from_json("entries.values[i]", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    r#"
+final items = Json.deserializeArray(json, {});
+return {}({}
+);
+"#,
+                    formats.len(),
+                    self.quote_type(format0),
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| format!(
+                            "\n    {}",
+                            self.quote_from_json(&format!("items[{}]", i), f)
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+return Json.deserializeArray(json, {}).map<{}>((item) => {}).toList();
+"#,
+                    size,
+                    self.quote_type(content),
+                    self.quote_from_json("item", content)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_comparison_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "static int compare{0}({1} a, {1} b) {{",
+            name.to_camel_case(),
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (a == null) return b == null ? 0 : -1;
+if (b == null) return 1;
+return {};
+"#,
+                    self.quote_compare("a", "b", format)
+                )?;
+            }
+
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                write!(
+                    self.out,
+                    r#"
+for (var i = 0; i < a.length && i < b.length; i++) {{
+    final result = {};
+    if (result != 0) return result;
+}}
+return a.length.compareTo(b.length);
+"#,
+                    self.quote_compare("a[i]", "b[i]", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+final x = a.keys.toList()..sort((k, l) => {0});
+final y = b.keys.toList()..sort((k, l) => {0});
+for (var i = 0; i < x.length && i < y.length; i++) {{
+    var result = {1};
+    if (result != 0) return result;
+    result = {2};
+    if (result != 0) return result;
+}}
+return x.length.compareTo(y.length);
+"#,
+                    self.quote_compare("k", "l", key),
+                    self.quote_compare("x[i]", "y[i]", key),
+                    self.quote_compare(
+                        &format!("(a[x[i]] as {})", self.quote_type(value)),
+                        &format!("(b[y[i]] as {})", self.quote_type(value)),
+                        value
+                    ),
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out, "\nint result;")?;
+                for (index, format) in formats.iter().enumerate() {
+                    let a = format!("a.item{}", index + 1);
+                    let b = format!("b.item{}", index + 1);
+                    writeln!(
+                        self.out,
+                        "if ((result = {}) != 0) return result;",
+                        self.quote_compare(&a, &b, format)
+                    )?;
+                }
+                writeln!(self.out, "return 0;")?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_hash_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        let body = match format0 {
+            Option(format) => format!(
+                "value == null ? 0 : Object.hash(1, {})",
+                self.quote_hash("value", format)
+            ),
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => format!(
+                "Object.hashAll(value.map((item) => {}))",
+                self.quote_hash("item", format)
+            ),
+            Map { key, value } => format!(
+                "Object.hashAllUnordered(value.entries.map((entry) => Object.hash({}, {})))",
+                self.quote_hash("entry.key", key),
+                self.quote_hash("entry.value", value)
+            ),
+            Tuple(formats) => format!(
+                "Object.hashAll([{}])",
+                formats
+                    .iter()
+                    .enumerate()
+                    .map(|(index, f)| self.quote_hash(&format!("value.item{}", index + 1), f))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => panic!("unexpected case"),
+        };
+        writeln!(
+            self.out,
+            "static int hash{}({} value) =>\n    {};\n",
+            name.to_camel_case(),
+            self.quote_type(format0),
+            body
+        )?;
+        Ok(())
+    }
+
+    /// A statement validating `value`, assuming that `common::needs_validation` holds.
+    fn quote_validate(&self, value: &str, format: &Format) -> String {
+        match format {
+            Format::TypeName(_) => format!("{}.validate();", value),
+            _ => format!(
+                "{}.validate{}({});",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format).to_camel_case(),
+                value
+            ),
+        }
+    }
+
+    fn output_validation_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(
+            self.out,
+            "static void validate{}({} value) {{",
+            name.to_camel_case(),
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        let needs_validation =
+            |format| common::needs_validation(self.generator.config, format, true);
+        match format0 {
+            Option(format) => {
+                writeln!(
+                    self.out,
+                    "if (value != null) {{\n  {}\n}}",
+                    self.quote_validate("value", format)
+                )?;
+            }
+
+            Seq(format) => {
+                writeln!(
+                    self.out,
+                    "for (final item in value) {{\n  {}\n}}",
+                    self.quote_validate("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                writeln!(self.out, "for (final entry in value.entries) {{")?;
+                if needs_validation(key) {
+                    writeln!(self.out, "  {}", self.quote_validate("entry.key", key))?;
+                }
+                if needs_validation(value) {
+                    writeln!(self.out, "  {}", self.quote_validate("entry.value", value))?;
+                }
+                writeln!(self.out, "}}")?;
+            }
+
+            Tuple(formats) => {
+                for (index, format) in formats.iter().enumerate() {
+                    if needs_validation(format) {
+                        let expr = format!("value.item{}", index + 1);
+                        writeln!(self.out, "{}", self.quote_validate(&expr, format))?;
+                    }
+                }
+            }
+
+            TupleArray { content, size } => {
+                writeln!(
+                    self.out,
+                    r#"if (value.length != {0}) {{
+  throw Exception('Invalid length for fixed-size array: ' + value.length.toString() + ' instead of {0}');
+}}"#,
+                    size
+                )?;
+                if needs_validation(content) {
+                    writeln!(
+                        self.out,
+                        "for (final item in value) {{\n  {}\n}}",
+                        self.quote_validate("item", content)
+                    )?;
+                }
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
+        use ContainerFormat::*;
+        let fields = match format {
+            UnitStruct => Vec::new(),
+            NewTypeStruct(format) => {
+                vec![Named {
+                    name: "value".to_string(),
+                    value: format.as_ref().clone(),
+                }]
+            }
+            TupleStruct(formats) => formats
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Named {
+                    name: format!("field{}", i),
+                    value: f.clone(),
+                })
+                .collect::<Vec<_>>(),
+            Struct(fields) => fields.clone(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("Dart", name)),
+            FlattenedStruct(_) => return Err(common::flattened_struct_not_supported("Dart", name)),
+            Enum(variants) => {
+                // When we find an enum with all Unit variants, we ser/de as a regular Dart enum.
+                if self.generator.config.c_style_enums
+                    && variants.values().all(|f| f.value == VariantFormat::Unit)
+                {
+                    self.output_enum_container(name, variants)?;
+                } else {
+                    self.output_enum_class_container(name, variants)?;
+                }
+                return Ok(());
+            }
+        };
+        let shape = common::JsonShape::of_container(format);
+        self.output_struct_or_variant_container(None, None, name, shape, &fields)
+    }
+
+    fn output_struct_or_variant_container(
+        &mut self,
+        variant_base: Option<&str>,
+        variant: Option<(u32, &str)>,
+        name: &str,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        let variant_index = variant.map(|(index, _)| index);
+        let field_count = fields.len();
+
+        // Beginning of class
+        writeln!(self.out)?;
+        self.output_comment(name)?;
+        if let Some(base) = variant_base {
+            writeln!(
+                self.out,
+                "@immutable\nclass {} extends {} {{",
+                self.quote_qualified_name(name),
+                base
+            )?;
+        } else {
+            writeln!(
+                self.out,
+                "@immutable\nclass {0} {1}{{",
+                self.quote_qualified_name(name),
+                self.comparable_interface(name)
+            )?;
+        }
+        self.enter_class(name);
+
+        // Constructor.
+        writeln!(
+            self.out,
+            "const {}({}",
+            self.quote_qualified_name(name),
+            if !fields.is_empty() { "{" } else { "" }
+        )?;
+        self.out.indent();
+        for field in fields.iter() {
+            let field_name = self.field_name(&field.name);
+            match &field.value {
+                Format::Option(_) => writeln!(self.out, "this.{},", field_name)?,
+                _ => writeln!(self.out, "required this.{},", field_name)?,
+            }
+        }
+        self.out.unindent();
+        if variant_base.is_some() {
+            writeln!(
+                self.out,
+                "{}) : super();",
+                if !fields.is_empty() { "}" } else { "" }
+            )?;
+        } else {
+            writeln!(self.out, "{});", if !fields.is_empty() { "}" } else { "" })?;
+        }
+
+        if self.generator.config.serialization {
+            // Deserialize (struct) or Load (variant)
+            if variant_index.is_none() {
+                writeln!(
+                    self.out,
+                    "\nstatic {} deserialize(BinaryDeserializer deserializer) {{",
+                    self.quote_qualified_name(name)
+                )?;
+            } else {
+                writeln!(
+                    self.out,
+                    "\nstatic {} load(BinaryDeserializer deserializer) {{",
+                    self.quote_qualified_name(name)
+                )?;
+            }
+
+            self.out.indent();
+            writeln!(self.out, "deserializer.increaseContainerDepth();")?;
+            writeln!(
+                self.out,
+                "final instance = {}(",
+                self.quote_qualified_name(name)
+            )?;
+            self.out.indent();
+            for field in fields {
+                writeln!(
+                    self.out,
+                    "{}: {},",
+                    self.field_name(&field.name),
+                    self.quote_deserialize(&field.value)
+                )?;
+            }
+            self.out.unindent();
+            writeln!(self.out, ");")?;
+            writeln!(self.out, "deserializer.decreaseContainerDepth();")?;
+            writeln!(self.out, "return instance;")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            if variant_index.is_none() {
+                for encoding in &self.generator.config.encodings {
+                    self.output_class_deserialize_for_encoding(name, *encoding)?;
+                }
+            }
+        }
+
+        // Fields
+        if !fields.is_empty() {
+            writeln!(self.out)?;
+        }
+        for field in fields {
+            writeln!(
+                self.out,
+                "final {} {};",
+                self.quote_type(&field.value),
+                self.field_name(&field.name)
+            )?;
+        }
+        if !fields.is_empty() {
+            writeln!(self.out)?;
+
             let cls_name = self.quote_qualified_name(name);
             writeln!(self.out, "{} copyWith({{", cls_name)?;
             self.out.indent();
             for field in fields {
-                let field_name = self.quote_field(&field.name.to_mixed_case());
+                let field_name = self.field_name(&field.name);
                 let field_type = self.quote_type(&field.value);
 
                 match field.value {
@@ -732,7 +1447,7 @@ return obj;
             self.out.indent();
 
             for field in fields {
-                let field_name = self.quote_field(&field.name.to_mixed_case());
+                let field_name = self.field_name(&field.name);
 
                 match field.value {
                     Format::Option(_) => {
@@ -759,10 +1474,7 @@ return obj;
                 writeln!(
                     self.out,
                     "{}",
-                    self.quote_serialize_value(
-                        &self.quote_field(&field.name.to_mixed_case()),
-                        &field.value
-                    )
+                    self.quote_serialize_value(&self.field_name(&field.name), &field.value)
                 )?;
             }
             writeln!(self.out, "serializer.decreaseContainerDepth();")?;
@@ -776,6 +1488,28 @@ return obj;
             }
         }
 
+        // JSON conversions
+        if self.has_json() {
+            self.output_struct_json_conversions(variant, name, shape, fields)?;
+        }
+
+        // Validation
+        if self.generator.config.generates_validation() {
+            writeln!(self.out, "\nvoid validate() {{")?;
+            self.out.indent();
+            for field in fields {
+                if common::needs_validation(self.generator.config, &field.value, true) {
+                    writeln!(
+                        self.out,
+                        "{}",
+                        self.quote_validate(&self.field_name(&field.name), &field.value)
+                    )?;
+                }
+            }
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+        }
+
         // Equality
         write!(self.out, "\n@override")?;
         write!(self.out, "\nbool operator ==(Object other) {{")?;
@@ -789,7 +1523,10 @@ return obj;
         write!(self.out, "\nreturn other is {}", name)?;
 
         self.out.indent();
-        for field in fields.iter() {
+        if self.generator.config.comparisons {
+            write!(self.out, "\n&& compareTo(other) == 0")?;
+        }
+        for field in fields.iter().filter(|_| !self.generator.config.comparisons) {
             // because the Dart functions of listEquals and mapEquals accept nullable
             // we only care about the data type and can discard the enclosing Format::Option
             let value = if let Format::Option(value) = &field.value {
@@ -800,28 +1537,16 @@ return obj;
 
             let stmt = match value {
                 Format::Seq(_) => {
-                    format!(
-                        "listEquals({0}, other.{0})",
-                        self.quote_field(&field.name.to_mixed_case())
-                    )
+                    format!("listEquals({0}, other.{0})", self.field_name(&field.name))
                 }
                 Format::TupleArray {
                     content: _,
                     size: _,
-                } => format!(
-                    "listEquals({0}, other.{0})",
-                    self.quote_field(&field.name.to_mixed_case())
-                ),
+                } => format!("listEquals({0}, other.{0})", self.field_name(&field.name)),
                 Format::Map { .. } => {
-                    format!(
-                        "mapEquals({0}, other.{0})",
-                        self.quote_field(&field.name.to_mixed_case())
-                    )
+                    format!("mapEquals({0}, other.{0})", self.field_name(&field.name))
                 }
-                _ => format!(
-                    "{0} == other.{0}",
-                    self.quote_field(&field.name.to_mixed_case())
-                ),
+                _ => format!("{0} == other.{0}", self.field_name(&field.name)),
             };
 
             write!(self.out, "\n&& {}", stmt)?;
@@ -837,10 +1562,11 @@ return obj;
         if field_count == 0 {
             writeln!(self.out, "\nint get hashCode => runtimeType.hashCode;")?;
         } else if field_count == 1 {
+            let field = fields.first().unwrap();
             writeln!(
                 self.out,
-                "\nint get hashCode => {}.hashCode;",
-                fields.first().unwrap().name.to_mixed_case()
+                "\nint get hashCode => {};",
+                self.quote_field_hash(&field.name, &field.value)
             )?;
         } else {
             let use_hash_all = field_count > 20;
@@ -859,7 +1585,7 @@ return obj;
                 writeln!(
                     self.out,
                     "{},",
-                    self.quote_field(&field.name.to_mixed_case())
+                    self.quote_field_hash(&field.name, &field.value)
                 )?;
             }
 
@@ -875,6 +1601,10 @@ return obj;
             self.out.unindent();
         }
 
+        if self.generator.config.comparisons {
+            self.output_compare_to(variant_base, variant_index, name, fields)?;
+        }
+
         // Generate a toString implementation in each class
         writeln!(self.out, "\n@override\nString toString() {{")?;
         self.out.indent();
@@ -885,17 +1615,9 @@ return obj;
         self.out.indent();
         for (index, field) in fields.iter().enumerate() {
             if index == field_count - 1 {
-                writeln!(
-                    self.out,
-                    "'{0}: ${0}'",
-                    self.quote_field(&field.name.to_mixed_case())
-                )?;
-            } else {
-                writeln!(
-                    self.out,
-                    "'{0}: ${0}, '",
-                    self.quote_field(&field.name.to_mixed_case())
-                )?;
+                writeln!(self.out, "'{0}: ${0}'", self.field_name(&field.name))?;
+            } else {
+                writeln!(self.out, "'{0}: ${0}, '", self.field_name(&field.name))?;
             }
         }
         writeln!(self.out, "')';")?;
@@ -910,21 +1632,117 @@ return obj;
         self.out.unindent();
         // End of class
         self.leave_class();
-        writeln!(self.out, "}}")
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// Hash of a field. Collections are hashed structurally when comparisons are enabled.
+    fn quote_field_hash(&self, name: &str, format: &Format) -> String {
+        if self.generator.config.comparisons {
+            self.quote_hash(&self.field_name(name), format)
+        } else {
+            self.field_name(name)
+        }
+    }
+
+    /// The `Comparable` interface implemented by containers, if comparisons are enabled.
+    fn comparable_interface(&self, name: &str) -> String {
+        if self.generator.config.comparisons {
+            format!(
+                "implements Comparable<{}> ",
+                self.quote_qualified_name(name)
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    /// Order fields lexicographically. Variants of different indices are ordered by index.
+    fn output_compare_to(
+        &mut self,
+        variant_base: Option<&str>,
+        variant_index: Option<u32>,
+        name: &str,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            "\n@override\nint compareTo({} other) {{",
+            variant_base.unwrap_or(&self.quote_qualified_name(name))
+        )?;
+        self.out.indent();
+        if variant_index.is_some() {
+            writeln!(
+                self.out,
+                "if (other is! {}) return _variantIndex.compareTo(other._variantIndex);",
+                self.quote_qualified_name(name)
+            )?;
+        }
+        if !fields.is_empty() {
+            writeln!(self.out, "int result;")?;
+        }
+        for field in fields {
+            let field_name = self.field_name(&field.name);
+            writeln!(
+                self.out,
+                "if ((result = {}) != 0) return result;",
+                self.quote_compare(&field_name, &format!("other.{}", field_name), &field.value)
+            )?;
+        }
+        writeln!(self.out, "return 0;")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        if let Some(index) = variant_index {
+            writeln!(self.out, "\n@override\nint get _variantIndex => {};", index)?;
+        }
+        Ok(())
     }
 
     fn output_class_serialize_for_encoding(&mut self, encoding: Encoding) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+String jsonSerialize() {{{}
+    return Json.stringify(toJson());
+}}"#,
+                if self.generator.config.validate_on_serialize {
+                    "\n    validate();"
+                } else {
+                    ""
+                },
+            )?;
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(encoding);
         writeln!(
             self.out,
             r#"
-Uint8List {0}Serialize() {{
-    final serializer = {1}Serializer();
-    serialize(serializer);
+Uint8List {0}Serialize() {{{2}
+    final serializer = {1}Serializer({3});
+    serialize(serializer);{4}
     return serializer.bytes;
 }}"#,
             encoding.name(),
             encoding.name().to_camel_case(),
-        )
+            if self.generator.config.validate_on_serialize {
+                "\n    validate();"
+            } else {
+                ""
+            },
+            Self::quote_bincode_arguments(&options),
+            match options.limit {
+                Some(limit) => format!(
+                    r#"
+    if (serializer.offset > {}) {{
+      throw Exception('Exceeded the size limit of the encoding');
+    }}"#,
+                    limit
+                ),
+                None => String::new(),
+            }
+        )?;
+        Ok(())
     }
 
     fn output_class_deserialize_for_encoding(
@@ -932,22 +1750,326 @@ Uint8List {0}Serialize() {{
         name: &str,
         encoding: Encoding,
     ) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+static {} jsonDeserialize(String input) {{
+  return {}.fromJson(Json.parse(input));
+}}"#,
+                self.quote_qualified_name(name),
+                self.quote_qualified_name(&self.get_class(name)),
+            )?;
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(encoding);
+        let checks = match options.trailing_bytes {
+            TrailingBytes::Reject => {
+                r#"
+  if (deserializer.offset < input.length) {
+    throw Exception('Some input bytes were not read');
+  }"#
+            }
+            TrailingBytes::Allow => "",
+        };
+        let mut arguments = Self::quote_bincode_arguments(&options);
+        // The runtime enforces the size limit while reading.
+        if let Some(limit) = options.limit {
+            if !arguments.is_empty() {
+                arguments += ", ";
+            }
+            arguments += &format!("limit: {}", limit.min(i64::MAX as u64));
+        }
         writeln!(
             self.out,
             r#"
 static {klass} {encoding}Deserialize(Uint8List input) {{
-  final deserializer = {encoding_class}Deserializer(input);
-  final value = {static_class}.deserialize(deserializer);
-  if (deserializer.offset < input.length) {{
-    throw Exception('Some input bytes were not read');
-  }}
+  final deserializer = {encoding_class}Deserializer(input{arguments});
+  final value = {static_class}.deserialize(deserializer);{checks}
   return value;
 }}"#,
             klass = self.quote_qualified_name(name),
             static_class = self.quote_qualified_name(&self.get_class(name)),
             encoding = encoding.name(),
-            encoding_class = encoding.name().to_camel_case()
-        )
+            encoding_class = encoding.name().to_camel_case(),
+            arguments = if arguments.is_empty() {
+                arguments
+            } else {
+                format!(", {}", arguments)
+            },
+            checks = checks,
+        )?;
+        Ok(())
+    }
+
+    /// Write the statements building the JSON content of a struct or a variant, given its
+    /// fields, and return the resulting expression. JSON keys are the names of the Rust fields.
+    fn output_json_content(
+        &mut self,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<String> {
+        use common::JsonShape;
+        let values = fields
+            .iter()
+            .map(|f| self.quote_to_json(&format!("this.{}", self.field_name(&f.name)), &f.value))
+            .collect::<Vec<_>>();
+        Ok(match shape {
+            JsonShape::Unit => "null".to_string(),
+            JsonShape::NewType => values[0].clone(),
+            JsonShape::Tuple => format!("<JsonValue>[{}]", values.join(", ")),
+            JsonShape::Struct => {
+                writeln!(self.out, "final json = JsonObject();")?;
+                for (field, value) in fields.iter().zip(values) {
+                    writeln!(self.out, "json.put('{}', {});", field.name, value)?;
+                }
+                "json".to_string()
+            }
+        })
+    }
+
+    /// Write the statements reading the JSON content `json` of a struct or a variant (other
+    /// than a unit), and return the values of its fields.
+    fn output_json_fields(
+        &mut self,
+        json: &str,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<Vec<String>> {
+        use common::JsonShape;
+        Ok(match shape {
+            JsonShape::Unit => Vec::new(),
+            JsonShape::NewType => vec![self.quote_from_json(json, &fields[0].value)],
+            JsonShape::Tuple => {
+                writeln!(
+                    self.out,
+                    "{}Json.deserializeArray({}, {});",
+                    if fields.is_empty() {
+                        ""
+                    } else {
+                        "final items = "
+                    },
+                    json,
+                    fields.len()
+                )?;
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, f)| self.quote_from_json(&format!("items[{}]", index), &f.value))
+                    .collect()
+            }
+            JsonShape::Struct => {
+                writeln!(
+                    self.out,
+                    "{}Json.deserializeObject({});",
+                    if fields.is_empty() {
+                        ""
+                    } else {
+                        "final obj = "
+                    },
+                    json
+                )?;
+                fields
+                    .iter()
+                    .map(|f| {
+                        // Like Serde, missing optional fields are read as `null`.
+                        let getter = match f.value {
+                            Format::Option(_) => "optionalField",
+                            _ => "field",
+                        };
+                        self.quote_from_json(&format!("obj.{}('{}')", getter, f.name), &f.value)
+                    })
+                    .collect()
+            }
+        })
+    }
+
+    /// Conversions of a struct (`toJson` and `fromJson`) or of a variant (`toJson` and
+    /// `loadJson`, called by `fromJson` in the base class).
+    fn output_struct_json_conversions(
+        &mut self,
+        variant: Option<(u32, &str)>,
+        name: &str,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        // Serialization
+        if variant.is_some() {
+            writeln!(self.out, "\n@override")?;
+        } else {
+            writeln!(self.out)?;
+        }
+        writeln!(self.out, "JsonValue toJson() {{")?;
+        self.out.indent();
+        match variant {
+            Some((_, variant_name)) if shape == common::JsonShape::Unit => {
+                writeln!(self.out, "return '{}';", variant_name)?;
+            }
+            Some((_, variant_name)) => {
+                let content = self.output_json_content(shape, fields)?;
+                writeln!(
+                    self.out,
+                    "return Json.serializeVariant('{}', {});",
+                    variant_name, content
+                )?;
+            }
+            None => {
+                let content = self.output_json_content(shape, fields)?;
+                writeln!(self.out, "return {};", content)?;
+            }
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        // Deserialization
+        let json = if variant.is_some() {
+            writeln!(
+                self.out,
+                "\nstatic {} loadJson(JsonVariant variant) {{",
+                self.quote_qualified_name(name)
+            )?;
+            "variant.value()"
+        } else {
+            writeln!(
+                self.out,
+                "\nstatic {} fromJson(JsonValue json) {{",
+                self.quote_qualified_name(name)
+            )?;
+            "json"
+        };
+        self.out.indent();
+        if shape == common::JsonShape::Unit {
+            if variant.is_some() {
+                writeln!(self.out, "variant.unit();")?;
+            } else {
+                writeln!(self.out, "Json.deserializeUnit(json);")?;
+            }
+        }
+        let values = self.output_json_fields(json, shape, fields)?;
+        if fields.is_empty() {
+            writeln!(self.out, "return {}();", self.quote_qualified_name(name))?;
+        } else {
+            writeln!(self.out, "return {}(", self.quote_qualified_name(name))?;
+            self.out.indent();
+            for (field, value) in fields.iter().zip(values) {
+                writeln!(self.out, "{}: {},", self.field_name(&field.name), value)?;
+            }
+            self.out.unindent();
+            writeln!(self.out, ");")?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_enum_class_json_conversions(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        writeln!(self.out, "\nJsonValue toJson();")?;
+        write!(
+            self.out,
+            "\nstatic {} fromJson(JsonValue json) {{",
+            self.quote_qualified_name(name)
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"
+final variant = Json.deserializeVariant(json);
+switch (variant.name) {{"#,
+        )?;
+        self.out.indent();
+        for variant in variants.values() {
+            writeln!(
+                self.out,
+                "case '{}': return {}{}.loadJson(variant);",
+                variant.name,
+                self.quote_qualified_name(name).to_camel_case(),
+                self.quote_field(&variant.name),
+            )?;
+        }
+        writeln!(
+            self.out,
+            "default: throw Exception('Unknown variant for {}: ' + variant.name);",
+            self.quote_qualified_name(name),
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_c_style_enum_json_conversions(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        write!(self.out, "\nJsonValue toJson() {{")?;
+        self.out.indent();
+        writeln!(self.out, "\nswitch (this) {{")?;
+        self.out.indent();
+        for variant in variants.values() {
+            writeln!(
+                self.out,
+                "case {}.{}: return '{}';",
+                self.quote_qualified_name(name),
+                self.enum_value_name(&variant.name),
+                variant.name,
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        write!(
+            self.out,
+            "\nstatic {} fromJson(JsonValue json) {{",
+            self.quote_qualified_name(name)
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"
+final variant = Json.deserializeVariant(json);
+variant.unit();
+switch (variant.name) {{"#,
+        )?;
+        self.out.indent();
+        for variant in variants.values() {
+            writeln!(
+                self.out,
+                "case '{}': return {}.{};",
+                variant.name,
+                self.quote_qualified_name(name),
+                self.enum_value_name(&variant.name),
+            )?;
+        }
+        writeln!(
+            self.out,
+            "default: throw Exception('Unknown variant for {}: ' + variant.name);",
+            self.quote_qualified_name(name),
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn quote_bincode_arguments(options: &BincodeOptions) -> String {
+        if options.is_custom_encoding() {
+            format!(
+                "varintEncoding: {}, bigEndian: {}",
+                options.int_encoding == IntEncoding::Varint,
+                options.endianness == Endianness::Big
+            )
+        } else {
+            String::new()
+        }
     }
 
     fn output_enum_container(
@@ -957,21 +2079,29 @@ static {klass} {encoding}Deserialize(Uint8List input) {{
     ) -> Result<()> {
         writeln!(self.out)?;
         self.output_comment(name)?;
-        writeln!(self.out, "enum {} {{", self.quote_qualified_name(name))?;
+        writeln!(
+            self.out,
+            "enum {0} {1}{{",
+            self.quote_qualified_name(name),
+            self.comparable_interface(name)
+        )?;
         self.enter_class(name);
 
         for variant in variants.values() {
+            writeln!(self.out, "{},", self.enum_value_name(&variant.name))?;
+        }
+        if self.generator.config.comparisons {
             writeln!(
                 self.out,
-                "{},",
-                self.quote_field(&variant.name.to_mixed_case())
+                ";\n\n@override\nint compareTo({} other) => index.compareTo(other.index);",
+                self.quote_qualified_name(name)
             )?;
         }
 
         self.out.unindent();
         writeln!(self.out, "}}\n")?;
 
-        if self.generator.config.serialization {
+        if self.generator.config.serialization || self.generator.config.generates_validation() {
             writeln!(
                 self.out,
                 "extension {name}Extension on {n} {{",
@@ -979,6 +2109,12 @@ static {klass} {encoding}Deserialize(Uint8List input) {{
                 n = self.quote_qualified_name(name)
             )?;
             self.out.indent();
+        }
+        if self.generator.config.generates_validation() {
+            // Dart enums only take valid values.
+            writeln!(self.out, "void validate() {{}}\n")?;
+        }
+        if self.generator.config.serialization {
             write!(
                 self.out,
                 "static {} deserialize(BinaryDeserializer deserializer) {{",
@@ -998,7 +2134,7 @@ switch (index) {{"#,
                     "case {}: return {}.{};",
                     index,
                     self.quote_qualified_name(name),
-                    self.quote_field(&variant.name.to_mixed_case()),
+                    self.enum_value_name(&variant.name),
                 )?;
             }
             writeln!(
@@ -1025,7 +2161,7 @@ switch (this) {{"#,
                     self.out,
                     "case {}.{}: return serializer.serializeVariantIndex({});",
                     self.quote_qualified_name(name),
-                    self.quote_field(&variant.name.to_mixed_case()),
+                    self.enum_value_name(&variant.name),
                     index,
                 )?;
             }
@@ -1034,15 +2170,19 @@ switch (this) {{"#,
             self.out.unindent();
             writeln!(self.out, "}}")?;
 
+            if self.has_json() {
+                self.output_c_style_enum_json_conversions(name, variants)?;
+            }
+
             for encoding in &self.generator.config.encodings {
                 self.output_class_serialize_for_encoding(*encoding)?;
                 self.output_class_deserialize_for_encoding(name, *encoding)?;
             }
         }
-        self.out.unindent();
-        self.out.unindent();
-
-        writeln!(self.out, "}}\n")?;
+        if self.generator.config.serialization || self.generator.config.generates_validation() {
+            self.out.unindent();
+            writeln!(self.out, "}}\n")?;
+        }
 
         self.leave_class();
         Ok(())
@@ -1057,11 +2197,18 @@ switch (this) {{"#,
         self.output_comment(name)?;
         writeln!(
             self.out,
-            "abstract class {} {{",
-            self.quote_qualified_name(name)
+            "abstract class {0} {1}{{",
+            self.quote_qualified_name(name),
+            self.comparable_interface(name)
         )?;
         self.enter_class(name);
         writeln!(self.out, "const {}();", self.quote_qualified_name(name))?;
+        if self.generator.config.comparisons {
+            writeln!(self.out, "\nint get _variantIndex;")?;
+        }
+        if self.generator.config.generates_validation() {
+            writeln!(self.out, "\nvoid validate();")?;
+        }
 
         if self.generator.config.serialization {
             writeln!(self.out, "\nvoid serialize(BinarySerializer serializer);")?;
@@ -1097,6 +2244,10 @@ switch (index) {{"#,
             self.out.unindent();
             writeln!(self.out, "}}")?;
 
+            if self.has_json() {
+                self.output_enum_class_json_conversions(name, variants)?;
+            }
+
             for encoding in &self.generator.config.encodings {
                 self.output_class_serialize_for_encoding(*encoding)?;
                 self.output_class_deserialize_for_encoding(name, *encoding)?;
@@ -1121,6 +2272,7 @@ switch (index) {{"#,
             self.output_variant(
                 base,
                 *index,
+                &variant.name,
                 &format!("{}{}", base, &variant.name),
                 &variant.value,
             )?;
@@ -1132,6 +2284,7 @@ switch (index) {{"#,
         &mut self,
         base: &str,
         index: u32,
+        variant_name: &str,
         name: &str,
         variant: &VariantFormat,
     ) -> Result<()> {
@@ -1155,8 +2308,9 @@ switch (index) {{"#,
         };
         self.output_struct_or_variant_container(
             Some(&self.quote_qualified_name(base)),
-            Some(index),
+            Some((index, variant_name)),
             name,
+            common::JsonShape::of_variant(variant),
             &fields,
         )
     }
@@ -1182,11 +2336,7 @@ impl Installer {
         Installer { install_dir }
     }
 
-    fn install_runtime(
-        &self,
-        source_dir: include_dir::Dir,
-        path: &str,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    fn install_runtime(&self, source_dir: include_dir::Dir, path: &str) -> Result<()> {
         let dir_path = self.install_dir.join(path);
         std::fs::create_dir_all(&dir_path)?;
         for entry in source_dir.files() {
@@ -1217,7 +2367,7 @@ dependencies:
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
@@ -1232,17 +2382,6 @@ impl crate::SourceInstaller for Installer {
             self.write_package(&self.install_dir, &config.module_name)?;
         }
 
-        // Write the main module file to export the public API.
-        std::fs::write(
-            self.install_dir
-                .join("lib")
-                .join(format!("{}.dart", &config.module_name)),
-            format!(
-                "export 'src/{name}/{name}.dart';",
-                name = &config.module_name
-            ),
-        )?;
-
         Ok(())
     }
 
@@ -1260,4 +2399,23 @@ impl crate::SourceInstaller for Installer {
     fn install_bcs_runtime(&self) -> std::result::Result<(), Self::Error> {
         self.install_runtime(include_directory!("runtime/dart/bcs"), "lib/src/bcs")
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(
+            include_directory!("runtime/dart/postcard"),
+            "lib/src/postcard",
+        )
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/dart/borsh"), "lib/src/borsh")
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/dart/scale"), "lib/src/scale")
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/dart/json"), "lib/src/json")
+    }
 }
@@ -0,0 +1,1248 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{
+    common,
+    indent::{IndentConfig, IndentedWriter},
+    naming, BincodeOptions, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error,
+    IntEncoding, Result, SourceFiles, SourceGenerator, TrailingBytes,
+};
+use heck::CamelCase;
+use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Write,
+    path::PathBuf,
+};
+
+/// Features supported by the Elixir code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Elixir",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: &[Encoding::Bincode, Encoding::Bcs],
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: false,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: false,
+    bincode_options: true,
+};
+
+/// Reserved words of Elixir, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "after", "and", "catch", "do", "else", "end", "false", "fn", "in", "nil", "not", "or",
+    "rescue", "true", "when",
+];
+
+/// Variables of the generated functions, which the variables bound to fields must avoid.
+const LOCAL_VARIABLES: &[&str] = &["deserializer", "input", "serializer"];
+
+/// Main configuration object for code-generation in Elixir.
+///
+/// Each container is generated as a module. Structs define an Elixir struct, while the values
+/// of an enum are tagged tuples such as `{:NewTypeVariant, value}`, or bare atoms for unit
+/// variants. Values are encoded by binary pattern matching in the runtime, through the
+/// functions of each module, e.g. `MyStruct.bcs_serialize/1` and `MyStruct.bcs_deserialize/1`.
+/// Values have structural equality and can be used as map keys. With comparisons,
+/// `MyStruct.compare/2` orders them as in Rust, e.g. for `Enum.sort(values, MyStruct)`.
+#[derive(Clone)]
+pub struct CodeGenerator<'a> {
+    /// Language-independent configuration.
+    config: &'a CodeGeneratorConfig,
+    /// Mapping from external type names to module names (e.g. "MyStruct" -> "MyOrg.MyModule.MyStruct").
+    /// Assumes that the corresponding modules are compiled together with the generated code.
+    /// Derived from `config.external_definitions`.
+    external_qualified_names: HashMap<String, String>,
+}
+
+/// Shared state for the code generation of an Elixir source file.
+struct ElixirEmitter<'a, T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// Generator.
+    generator: &'a CodeGenerator<'a>,
+    /// Module name prefixing the generated modules (e.g. "MyOrg.MyModule").
+    module_prefix: String,
+    /// Current namespace (e.g. vec!["my_org", "my_module", "MyStruct"])
+    current_namespace: Vec<String>,
+}
+
+impl<'a> CodeGenerator<'a> {
+    /// Create an Elixir code generator for the given config.
+    pub fn new(config: &'a CodeGeneratorConfig) -> Self {
+        let mut external_qualified_names = HashMap::new();
+        for (module_path, names) in &config.external_definitions {
+            for name in names {
+                external_qualified_names.insert(
+                    name.to_string(),
+                    format!("{}.{}", quote_module_name(module_path), name),
+                );
+            }
+        }
+        Self {
+            config,
+            external_qualified_names,
+        }
+    }
+
+    /// Write container definitions in Elixir.
+    pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).output(out, &registry);
+        }
+        for (name, format) in registry {
+            Self::check_container(name, format)?;
+        }
+        let mut emitter = ElixirEmitter {
+            out: IndentedWriter::new(out, IndentConfig::Space(2)),
+            generator: self,
+            module_prefix: quote_module_name(&self.config.module_name),
+            current_namespace: self
+                .config
+                .module_name
+                .split('.')
+                .map(String::from)
+                .collect(),
+        };
+        for (index, (name, format)) in registry.iter().enumerate() {
+            if index > 0 {
+                writeln!(emitter.out)?;
+            }
+            emitter.output_container(name, format)?;
+        }
+        if self.config.serialization || self.config.comparisons {
+            emitter.output_trait_helpers(registry)?;
+        }
+        Ok(())
+    }
+
+    /// Module names must start with an uppercase letter, and fields with a lowercase letter
+    /// since they are bound to variables. Options are mapped to `nil` or to their content,
+    /// hence nested options and options of unit values cannot be told apart from `None`.
+    fn check_container(name: &str, format: &ContainerFormat) -> Result<()> {
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return Err(Error::unsupported(
+                CAPABILITIES.language,
+                "names of types that do not start with an uppercase letter",
+            )
+            .at(name));
+        }
+        let mut fields = Vec::new();
+        match format {
+            ContainerFormat::Struct(named_formats) => fields.extend(named_formats),
+            ContainerFormat::Enum(variants) => {
+                for variant in variants.values() {
+                    if let VariantFormat::Struct(named_formats) = &variant.value {
+                        fields.extend(named_formats);
+                    }
+                }
+            }
+            _ => (),
+        }
+        if let Some(field) = fields
+            .iter()
+            .find(|field| !field.name.starts_with(|c: char| c.is_ascii_lowercase()))
+        {
+            return Err(Error::unsupported(
+                CAPABILITIES.language,
+                "names of fields that do not start with a lowercase letter",
+            )
+            .at(&format!("{}.{}", name, field.name)));
+        }
+        let mut result = Ok(());
+        format
+            .visit(&mut |format| {
+                if result.is_ok() {
+                    if let Format::Option(content) = format {
+                        if matches!(content.as_ref(), Format::Option(_) | Format::Unit) {
+                            result = Err(Error::unsupported(
+                                CAPABILITIES.language,
+                                "options of options or of unit values",
+                            ));
+                        }
+                    }
+                }
+                Ok(())
+            })
+            .unwrap();
+        result.map_err(|error| error.at(name))
+    }
+}
+
+/// The Elixir module of a module path such as "my_org.my_module", e.g. "MyOrg.MyModule".
+fn quote_module_name(module_path: &str) -> String {
+    module_path
+        .split('.')
+        .map(|name| name.to_camel_case())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// The variable bound to the value of a field, avoiding reserved words and the variables of
+/// the generated functions.
+fn quote_variable(name: &str) -> String {
+    if RESERVED_WORDS.contains(&name) || LOCAL_VARIABLES.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// The text of a comment as the content of a heredoc, without interpolations.
+fn quote_heredoc(doc: &str) -> String {
+    doc.trim_end()
+        .replace('\\', "\\\\")
+        .replace("#{", "\\#{")
+        .replace("\"\"\"", "\\\"\"\"")
+}
+
+/// The fields of a variant, bound to variables in the tagged tuple of the variant.
+fn variant_fields(variant: &VariantFormat) -> Vec<Named<Format>> {
+    use VariantFormat::*;
+    match variant {
+        Unit => Vec::new(),
+        NewType(format) => vec![Named {
+            name: "value".to_string(),
+            value: format.as_ref().clone(),
+        }],
+        Tuple(formats) => formats
+            .iter()
+            .enumerate()
+            .map(|(i, f)| Named {
+                name: format!("field{}", i),
+                value: f.clone(),
+            })
+            .collect(),
+        Struct(fields) => fields.clone(),
+        Variable(_) => panic!("incorrect value"),
+    }
+}
+
+/// The tagged tuple (or atom) of a variant, as a pattern or as an expression, where fields
+/// are bound to the variables given by `variant_fields`.
+fn quote_variant_value(name: &str, variant: &VariantFormat) -> String {
+    quote_variant_pattern(name, variant, quote_variable)
+}
+
+/// The tagged tuple (or atom) of a variant, where fields are bound to the given variables.
+fn quote_variant_pattern(
+    name: &str,
+    variant: &VariantFormat,
+    variable: impl Fn(&str) -> String,
+) -> String {
+    let fields = variant_fields(variant);
+    match variant {
+        VariantFormat::Unit => format!(":{}", name),
+        VariantFormat::Struct(_) => format!(
+            "{{:{}, %{{{}}}}}",
+            name,
+            fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, variable(&field.name)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => {
+            std::iter::once(format!("{{:{}", name))
+                .chain(fields.iter().map(|field| variable(&field.name)))
+                .collect::<Vec<_>>()
+                .join(", ")
+                + "}"
+        }
+    }
+}
+
+impl<'a, T> ElixirEmitter<'a, T>
+where
+    T: Write,
+{
+    fn quote_qualified_name(&self, name: &str) -> String {
+        match self.generator.external_qualified_names.get(name) {
+            Some(qname) => qname.clone(),
+            None => format!("{}.{}", self.module_prefix, name),
+        }
+    }
+
+    fn comment(&self, name: &str) -> Option<&'a String> {
+        let mut path = self.current_namespace.clone();
+        path.push(name.to_string());
+        self.generator.config.comments.get(&path)
+    }
+
+    fn output_moduledoc(&mut self, name: &str) -> Result<()> {
+        if let Some(doc) = self.comment(name) {
+            writeln!(
+                self.out,
+                "@moduledoc \"\"\"\n{}\n\"\"\"\n",
+                quote_heredoc(doc)
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Output the comment of a field or a variant within a type specification.
+    fn output_type_comment(&mut self, name: &str) -> Result<()> {
+        if let Some(doc) = self.comment(name) {
+            let text = textwrap::indent(doc, "        # ").replace("\n\n", "\n        #\n");
+            write!(self.out, "{}", text)?;
+        }
+        Ok(())
+    }
+
+    fn output_custom_code(&mut self) -> Result<()> {
+        if let Some(code) = self
+            .generator
+            .config
+            .custom_code
+            .get(&self.current_namespace)
+        {
+            writeln!(self.out, "\n{}", code)?;
+        }
+        Ok(())
+    }
+
+    fn quote_type(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(x) => format!("{}.t()", self.quote_qualified_name(x)),
+            Unit => "nil".into(),
+            Bool => "boolean()".into(),
+            I8 | I16 | I32 | I64 | I128 => "integer()".into(),
+            U8 | U16 | U32 | U64 | U128 => "non_neg_integer()".into(),
+            F32 | F64 => "float()".into(),
+            Char => "char()".into(),
+            Str => "String.t()".into(),
+            Bytes => "binary()".into(),
+
+            Option(format) => format!("{} | nil", self.quote_type(format)),
+            Seq(format) => format!("[{}]", self.quote_type(format)),
+            Map { key, value } => format!(
+                "%{{optional({}) => {}}}",
+                self.quote_type(key),
+                self.quote_type(value)
+            ),
+            Tuple(formats) => format!("{{{}}}", self.quote_types(formats)),
+            TupleArray { content, size: _ } => format!("[{}]", self.quote_type(content)),
+
+            Variable(_) => panic!("unexpected value"),
+        }
+    }
+
+    fn quote_types(&self, formats: &[Format]) -> String {
+        formats
+            .iter()
+            .map(|f| self.quote_type(f))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_variant_type(&self, name: &str, variant: &VariantFormat) -> String {
+        use VariantFormat::*;
+        match variant {
+            Unit => format!(":{}", name),
+            NewType(format) => format!("{{:{}, {}}}", name, self.quote_type(format)),
+            Tuple(formats) if formats.is_empty() => format!("{{:{}}}", name),
+            Tuple(formats) => format!("{{:{}, {}}}", name, self.quote_types(formats)),
+            Struct(fields) => format!(
+                "{{:{}, %{{{}}}}}",
+                name,
+                fields
+                    .iter()
+                    .map(|field| format!("{}: {}", field.name, self.quote_type(&field.value)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Variable(_) => panic!("incorrect value"),
+        }
+    }
+
+    fn needs_helper(format: &Format) -> bool {
+        use Format::*;
+        matches!(
+            format,
+            Option(_) | Seq(_) | Map { .. } | Tuple(_) | TupleArray { .. }
+        )
+    }
+
+    fn quote_serialize_value(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!(
+                "{}.serialize({}, serializer)",
+                self.quote_qualified_name(name),
+                value
+            ),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "Serde.Serializer.serialize_{}({}, serializer)",
+                common::mangle_type(format),
+                value
+            ),
+            _ => format!(
+                "{}.TraitHelpers.serialize_{}({}, serializer)",
+                self.module_prefix,
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn quote_deserialize(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!(
+                "{}.deserialize(input, deserializer)",
+                self.quote_qualified_name(name)
+            ),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "Serde.Deserializer.deserialize_{}(input, deserializer)",
+                common::mangle_type(format)
+            ),
+            _ => format!(
+                "{}.TraitHelpers.deserialize_{}(input, deserializer)",
+                self.module_prefix,
+                common::mangle_type(format)
+            ),
+        }
+    }
+
+    /// A reference to the serialization function of a format, within the helper module.
+    fn quote_serialize_function(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!("&{}.serialize/2", self.quote_qualified_name(name)),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => {
+                format!(
+                    "&Serde.Serializer.serialize_{}/2",
+                    common::mangle_type(format)
+                )
+            }
+            _ => format!("&serialize_{}/2", common::mangle_type(format)),
+        }
+    }
+
+    /// A reference to the deserialization function of a format, within the helper module.
+    fn quote_deserialize_function(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!("&{}.deserialize/2", self.quote_qualified_name(name)),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "&Serde.Deserializer.deserialize_{}/2",
+                common::mangle_type(format)
+            ),
+            _ => format!("&deserialize_{}/2", common::mangle_type(format)),
+        }
+    }
+
+    fn quote_compare(&self, lhs: &str, rhs: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!(
+                "{}.compare({}, {})",
+                self.quote_qualified_name(name),
+                lhs,
+                rhs
+            ),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | Char
+            | Str | Bytes => format!("Serde.compare({}, {})", lhs, rhs),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            _ => format!(
+                "{}.TraitHelpers.compare_{}({}, {})",
+                self.module_prefix,
+                common::mangle_type(format),
+                lhs,
+                rhs
+            ),
+        }
+    }
+
+    /// A reference to the comparison function of a format, within the helper module.
+    fn quote_compare_function(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!("&{}.compare/2", self.quote_qualified_name(name)),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | Char
+            | Str | Bytes => "&Serde.compare/2".to_string(),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            _ => format!("&compare_{}/2", common::mangle_type(format)),
+        }
+    }
+
+    /// Output the first comparison that is not `:eq`, or `:eq`.
+    fn output_comparisons(&mut self, comparisons: &[String]) -> Result<()> {
+        match comparisons {
+            [] => writeln!(self.out, ":eq")?,
+            [comparison] => writeln!(self.out, "{}", comparison)?,
+            _ => {
+                writeln!(self.out, "with :eq <- {},", comparisons[0])?;
+                for (index, comparison) in comparisons[1..].iter().enumerate() {
+                    writeln!(
+                        self.out,
+                        "     :eq <- {}{}",
+                        comparison,
+                        if index + 2 < comparisons.len() {
+                            ","
+                        } else {
+                            " do"
+                        }
+                    )?;
+                }
+                writeln!(self.out, "  :eq\nend")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Output the given iodata items as a single expression.
+    fn output_iodata(&mut self, items: &[String]) -> Result<()> {
+        match items {
+            [] => writeln!(self.out, "[]")?,
+            [item] => writeln!(self.out, "{}", item)?,
+            _ => {
+                writeln!(self.out, "[")?;
+                writeln!(self.out, "  {}", items.join(",\n  "))?;
+                writeln!(self.out, "]")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Output the statements binding the given fields to variables, while consuming `input`.
+    fn output_field_deserialization(&mut self, fields: &[Named<Format>]) -> Result<()> {
+        for field in fields {
+            writeln!(
+                self.out,
+                "{{{}, input}} = {}",
+                quote_variable(&field.name),
+                self.quote_deserialize(&field.value)
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_trait_helpers(&mut self, registry: &Registry) -> Result<()> {
+        let mut subtypes = BTreeMap::new();
+        for format in registry.values() {
+            format
+                .visit(&mut |f| {
+                    if Self::needs_helper(f) {
+                        subtypes.insert(common::mangle_type(f), f.clone());
+                    }
+                    Ok(())
+                })
+                .unwrap();
+        }
+        if subtypes.is_empty() {
+            return Ok(());
+        }
+        writeln!(
+            self.out,
+            "\ndefmodule {}.TraitHelpers do",
+            self.module_prefix
+        )?;
+        self.out.indent();
+        writeln!(self.out, "@moduledoc false")?;
+        for (mangled_name, subtype) in &subtypes {
+            if self.generator.config.serialization {
+                self.output_serialization_helper(mangled_name, subtype)?;
+                self.output_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.comparisons {
+                self.output_comparison_helper(mangled_name, subtype)?;
+            }
+        }
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    fn output_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        let body = match format0 {
+            Option(format) => format!(
+                "Serde.Serializer.serialize_option(value, serializer, {})",
+                self.quote_serialize_function(format)
+            ),
+            Seq(format) => format!(
+                "Serde.Serializer.serialize_seq(value, serializer, {})",
+                self.quote_serialize_function(format)
+            ),
+            Map { key, value } => format!(
+                "Serde.Serializer.serialize_map(value, serializer, {}, {})",
+                self.quote_serialize_function(key),
+                self.quote_serialize_function(value)
+            ),
+            TupleArray { content, size } => format!(
+                "Serde.Serializer.serialize_array(value, serializer, {}, {})",
+                size,
+                self.quote_serialize_function(content)
+            ),
+            Tuple(formats) => {
+                let variables = (0..formats.len())
+                    .map(|i| format!("field{}", i))
+                    .collect::<Vec<_>>();
+                writeln!(
+                    self.out,
+                    "\ndef serialize_{}({{{}}}, {}) do",
+                    name,
+                    variables.join(", "),
+                    if formats.is_empty() {
+                        "_serializer"
+                    } else {
+                        "serializer"
+                    }
+                )?;
+                self.out.indent();
+                let items = variables
+                    .iter()
+                    .zip(formats)
+                    .map(|(variable, format)| self.quote_serialize_value(variable, format))
+                    .collect::<Vec<_>>();
+                self.output_iodata(&items)?;
+                self.out.unindent();
+                writeln!(
+                    self.out,
+                    r#"end
+
+def serialize_{}(_value, _serializer),
+  do: raise(Serde.SerializationError, "Invalid value for a tuple of length {}")"#,
+                    name,
+                    formats.len()
+                )?;
+                return Ok(());
+            }
+            _ => panic!("unexpected case"),
+        };
+        writeln!(
+            self.out,
+            "\ndef serialize_{}(value, serializer) do\n  {}\nend",
+            name, body
+        )?;
+        Ok(())
+    }
+
+    fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        let body = match format0 {
+            Option(format) => format!(
+                "Serde.Deserializer.deserialize_option(input, deserializer, {})",
+                self.quote_deserialize_function(format)
+            ),
+            Seq(format) => format!(
+                "Serde.Deserializer.deserialize_seq(input, deserializer, {})",
+                self.quote_deserialize_function(format)
+            ),
+            Map { key, value } => format!(
+                "Serde.Deserializer.deserialize_map(input, deserializer, {}, {})",
+                self.quote_deserialize_function(key),
+                self.quote_deserialize_function(value)
+            ),
+            TupleArray { content, size } => format!(
+                "Serde.Deserializer.deserialize_array(input, deserializer, {}, {})",
+                size,
+                self.quote_deserialize_function(content)
+            ),
+            Tuple(formats) => {
+                let fields = formats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| Named {
+                        name: format!("field{}", i),
+                        value: f.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                writeln!(
+                    self.out,
+                    "\ndef deserialize_{}(input, {}) do",
+                    name,
+                    if formats.is_empty() {
+                        "_deserializer"
+                    } else {
+                        "deserializer"
+                    }
+                )?;
+                self.out.indent();
+                self.output_field_deserialization(&fields)?;
+                writeln!(
+                    self.out,
+                    "{{{{{}}}, input}}",
+                    fields
+                        .iter()
+                        .map(|field| field.name.clone())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+                self.out.unindent();
+                writeln!(self.out, "end")?;
+                return Ok(());
+            }
+            _ => panic!("unexpected case"),
+        };
+        writeln!(
+            self.out,
+            "\ndef deserialize_{}(input, deserializer) do\n  {}\nend",
+            name, body
+        )?;
+        Ok(())
+    }
+
+    fn output_comparison_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        let body = match format0 {
+            Option(format) => {
+                writeln!(
+                    self.out,
+                    r#"
+def compare_{0}(nil, nil), do: :eq
+def compare_{0}(nil, _rhs), do: :lt
+def compare_{0}(_lhs, nil), do: :gt
+def compare_{0}(lhs, rhs), do: {1}"#,
+                    name,
+                    self.quote_compare("lhs", "rhs", format)
+                )?;
+                return Ok(());
+            }
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => format!(
+                "Serde.compare_seq(lhs, rhs, {})",
+                self.quote_compare_function(format)
+            ),
+            Map { key, value } => format!(
+                "Serde.compare_map(lhs, rhs, {}, {})",
+                self.quote_compare_function(key),
+                self.quote_compare_function(value)
+            ),
+            Tuple(formats) => {
+                let variables = |prefix: &str| {
+                    (0..formats.len())
+                        .map(|i| format!("{}{}", prefix, i))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                writeln!(
+                    self.out,
+                    "\ndef compare_{}({{{}}}, {{{}}}) do",
+                    name,
+                    variables("lhs"),
+                    variables("rhs")
+                )?;
+                self.out.indent();
+                let comparisons = formats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, format)| {
+                        self.quote_compare(&format!("lhs{}", i), &format!("rhs{}", i), format)
+                    })
+                    .collect::<Vec<_>>();
+                self.output_comparisons(&comparisons)?;
+                self.out.unindent();
+                writeln!(self.out, "end")?;
+                return Ok(());
+            }
+            _ => panic!("unexpected case"),
+        };
+        writeln!(
+            self.out,
+            "\ndef compare_{}(lhs, rhs) do\n  {}\nend",
+            name, body
+        )?;
+        Ok(())
+    }
+
+    /// Output `compare/2`, which orders the values of a struct by fields.
+    fn output_struct_compare(&mut self, fields: &[Named<Format>]) -> Result<()> {
+        writeln!(self.out, "\n@spec compare(t(), t()) :: :lt | :eq | :gt")?;
+        if fields.is_empty() {
+            writeln!(
+                self.out,
+                "def compare(%__MODULE__{{}}, %__MODULE__{{}}), do: :eq"
+            )?;
+            return Ok(());
+        }
+        writeln!(
+            self.out,
+            "def compare(%__MODULE__{{}} = lhs, %__MODULE__{{}} = rhs) do"
+        )?;
+        self.out.indent();
+        let comparisons = fields
+            .iter()
+            .map(|field| {
+                self.quote_compare(
+                    &format!("lhs.{}", field.name),
+                    &format!("rhs.{}", field.name),
+                    &field.value,
+                )
+            })
+            .collect::<Vec<_>>();
+        self.output_comparisons(&comparisons)?;
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    /// Output `compare/2`, which orders the values of an enum by variant index, then by fields.
+    fn output_enum_compare(
+        &mut self,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        writeln!(self.out, "\n@spec compare(t(), t()) :: :lt | :eq | :gt")?;
+        if variants.is_empty() {
+            writeln!(self.out, "def compare(_lhs, _rhs), do: :eq")?;
+            return Ok(());
+        }
+        writeln!(self.out, "def compare(lhs, rhs) do")?;
+        self.out.indent();
+        writeln!(self.out, "case {{lhs, rhs}} do")?;
+        self.out.indent();
+        for variant in variants.values() {
+            writeln!(
+                self.out,
+                "{{{}, {}}} ->",
+                quote_variant_pattern(&variant.name, &variant.value, |name| format!(
+                    "lhs_{}",
+                    name
+                )),
+                quote_variant_pattern(&variant.name, &variant.value, |name| format!(
+                    "rhs_{}",
+                    name
+                )),
+            )?;
+            self.out.indent();
+            let comparisons = variant_fields(&variant.value)
+                .iter()
+                .map(|field| {
+                    self.quote_compare(
+                        &format!("lhs_{}", field.name),
+                        &format!("rhs_{}", field.name),
+                        &field.value,
+                    )
+                })
+                .collect::<Vec<_>>();
+            self.output_comparisons(&comparisons)?;
+            self.out.unindent();
+            writeln!(self.out)?;
+        }
+        if variants.len() > 1 {
+            writeln!(
+                self.out,
+                "_ ->\n  Serde.compare(variant_index(lhs), variant_index(rhs))"
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        if variants.len() > 1 {
+            writeln!(self.out, "\ndefp variant_index(value) do")?;
+            self.out.indent();
+            writeln!(self.out, "case value do")?;
+            self.out.indent();
+            for (index, variant) in variants {
+                writeln!(
+                    self.out,
+                    "{} -> {}",
+                    quote_variant_pattern(&variant.name, &variant.value, |_| "_".to_string()),
+                    index
+                )?;
+            }
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+        }
+        Ok(())
+    }
+
+    /// The options passed to the runtime of an encoding, omitting the default values.
+    fn encoding_options(options: &BincodeOptions) -> String {
+        let mut result = String::new();
+        if options.int_encoding == IntEncoding::Varint {
+            result += ", varint_encoding: true";
+        }
+        if options.endianness == Endianness::Big {
+            result += ", big_endian: true";
+        }
+        if let Some(limit) = options.limit {
+            result += &format!(", limit: {}", limit);
+        }
+        if options.trailing_bytes == TrailingBytes::Allow {
+            result += ", allow_trailing_bytes: true";
+        }
+        result
+    }
+
+    /// Output the functions serializing and deserializing a value in each encoding.
+    fn output_encoding_functions(&mut self) -> Result<()> {
+        for encoding in &self.generator.config.encodings {
+            let options = self.generator.config.bincode_options_for(*encoding);
+            writeln!(
+                self.out,
+                r#"
+@spec {0}_serialize(t()) :: {{:ok, binary()}} | {{:error, Exception.t()}}
+def {0}_serialize(value), do: {1}.serialize(value, &serialize/2{2})
+
+@spec {0}_deserialize(binary()) :: {{:ok, t()}} | {{:error, Exception.t()}}
+def {0}_deserialize(input), do: {1}.deserialize(input, &deserialize/2{2})"#,
+                encoding.name(),
+                encoding.name().to_camel_case(),
+                Self::encoding_options(&options),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_struct_container(&mut self, name: &str, fields: &[Named<Format>]) -> Result<()> {
+        // Beginning of module
+        writeln!(self.out, "defmodule {} do", self.quote_qualified_name(name))?;
+        self.out.indent();
+        self.output_moduledoc(name)?;
+        self.current_namespace.push(name.to_string());
+        // Struct
+        let pattern = fields
+            .iter()
+            .map(|field| format!("{}: {}", field.name, quote_variable(&field.name)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if fields.is_empty() {
+            writeln!(self.out, "@type t :: %__MODULE__{{}}")?;
+            writeln!(self.out, "defstruct []")?;
+        } else {
+            writeln!(self.out, "@type t :: %__MODULE__{{")?;
+            for (index, field) in fields.iter().enumerate() {
+                self.output_type_comment(&field.name)?;
+                writeln!(
+                    self.out,
+                    "        {}: {}{}",
+                    field.name,
+                    self.quote_type(&field.value),
+                    if index + 1 < fields.len() { "," } else { "" }
+                )?;
+            }
+            writeln!(self.out, "      }}")?;
+            let keys = fields
+                .iter()
+                .map(|field| format!(":{}", field.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(self.out, "@enforce_keys [{}]", keys)?;
+            writeln!(self.out, "defstruct [{}]", keys)?;
+        }
+        if self.generator.config.serialization {
+            // Serialize
+            writeln!(
+                self.out,
+                "\n@doc false\ndef serialize(%__MODULE__{{{}}}, serializer) do",
+                pattern
+            )?;
+            self.out.indent();
+            writeln!(
+                self.out,
+                "{} = Serde.Serializer.increase_container_depth(serializer)\n",
+                if fields.is_empty() {
+                    "_serializer"
+                } else {
+                    "serializer"
+                }
+            )?;
+            let items = fields
+                .iter()
+                .map(|field| self.quote_serialize_value(&quote_variable(&field.name), &field.value))
+                .collect::<Vec<_>>();
+            self.output_iodata(&items)?;
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+            // Deserialize
+            writeln!(
+                self.out,
+                "\n@doc false\ndef deserialize(input, deserializer) do"
+            )?;
+            self.out.indent();
+            writeln!(
+                self.out,
+                "{} = Serde.Deserializer.increase_container_depth(deserializer)",
+                if fields.is_empty() {
+                    "_deserializer"
+                } else {
+                    "deserializer"
+                }
+            )?;
+            self.output_field_deserialization(fields)?;
+            writeln!(self.out, "{{%__MODULE__{{{}}}, input}}", pattern)?;
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+            self.output_encoding_functions()?;
+        }
+        if self.generator.config.comparisons {
+            self.output_struct_compare(fields)?;
+        }
+        // Custom code
+        self.output_custom_code()?;
+        // End of module
+        self.current_namespace.pop();
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    fn output_enum_container(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        // Beginning of module
+        writeln!(self.out, "defmodule {} do", self.quote_qualified_name(name))?;
+        self.out.indent();
+        self.output_moduledoc(name)?;
+        self.current_namespace.push(name.to_string());
+        // Tagged tuples
+        if variants.is_empty() {
+            writeln!(self.out, "@type t :: none()")?;
+        } else {
+            writeln!(self.out, "@type t ::")?;
+            for (index, variant) in variants.values().enumerate() {
+                self.output_type_comment(&variant.name)?;
+                writeln!(
+                    self.out,
+                    "        {}{}",
+                    if index > 0 { "| " } else { "" },
+                    self.quote_variant_type(&variant.name, &variant.value)
+                )?;
+            }
+        }
+        if self.generator.config.serialization {
+            // Serialize
+            writeln!(
+                self.out,
+                r#"
+@doc false
+def serialize(value, serializer) do
+  serializer = Serde.Serializer.increase_container_depth(serializer)
+
+  case value do"#
+            )?;
+            self.out.indent();
+            self.out.indent();
+            for (index, variant) in variants {
+                writeln!(
+                    self.out,
+                    "{} ->",
+                    quote_variant_value(&variant.name, &variant.value)
+                )?;
+                self.out.indent();
+                let items = std::iter::once(format!(
+                    "Serde.Serializer.serialize_variant_index({}, serializer)",
+                    index
+                ))
+                .chain(variant_fields(&variant.value).iter().map(|field| {
+                    self.quote_serialize_value(&quote_variable(&field.name), &field.value)
+                }))
+                .collect::<Vec<_>>();
+                self.output_iodata(&items)?;
+                self.out.unindent();
+                writeln!(self.out)?;
+            }
+            writeln!(
+                self.out,
+                r#"_ ->
+  raise Serde.SerializationError, "Invalid value for {}""#,
+                name
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+            // Deserialize
+            writeln!(
+                self.out,
+                r#"
+@doc false
+def deserialize(input, deserializer) do
+  deserializer = Serde.Deserializer.increase_container_depth(deserializer)
+  {{index, input}} = Serde.Deserializer.deserialize_variant_index(input, deserializer)
+
+  case index do"#
+            )?;
+            self.out.indent();
+            self.out.indent();
+            for (index, variant) in variants {
+                writeln!(self.out, "{} ->", index)?;
+                self.out.indent();
+                self.output_field_deserialization(&variant_fields(&variant.value))?;
+                writeln!(
+                    self.out,
+                    "{{{}, input}}",
+                    quote_variant_value(&variant.name, &variant.value)
+                )?;
+                self.out.unindent();
+                writeln!(self.out)?;
+            }
+            writeln!(
+                self.out,
+                r#"_ ->
+  raise Serde.DeserializationError, "Unknown variant index for {}: #{{index}}""#,
+                name
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+            self.output_encoding_functions()?;
+        }
+        if self.generator.config.comparisons {
+            self.output_enum_compare(variants)?;
+        }
+        // Custom code
+        self.output_custom_code()?;
+        // End of module
+        self.current_namespace.pop();
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
+        use ContainerFormat::*;
+        let fields = match format {
+            UnitStruct => Vec::new(),
+            NewTypeStruct(format) => vec![Named {
+                name: "value".to_string(),
+                value: format.as_ref().clone(),
+            }],
+            TupleStruct(formats) => formats
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Named {
+                    name: format!("field{}", i),
+                    value: f.clone(),
+                })
+                .collect::<Vec<_>>(),
+            Struct(fields) => fields.clone(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("Elixir", name)),
+            FlattenedStruct(_) => {
+                return Err(common::flattened_struct_not_supported("Elixir", name))
+            }
+            Enum(variants) => {
+                self.output_enum_container(name, variants)?;
+                return Ok(());
+            }
+        };
+        self.output_struct_container(name, &fields)
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(&self, registry: &Registry) -> std::result::Result<SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let path = self
+            .config
+            .module_name
+            .split('.')
+            .collect::<PathBuf>()
+            .with_extension("ex");
+        let mut files = SourceFiles::new();
+        files.insert_bytes(path, content);
+        Ok(files)
+    }
+}
+
+/// Installer for generated source files in Elixir.
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+
+    fn install_runtime(&self, name: &str, content: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.install_dir)?;
+        let mut file = std::fs::File::create(self.install_dir.join(name))?;
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Error;
+
+    fn install_module(
+        &self,
+        config: &CodeGeneratorConfig,
+        registry: &Registry,
+    ) -> std::result::Result<(), Self::Error> {
+        let generator = CodeGenerator::new(config);
+        generator.generate(registry)?.write_to(&self.install_dir)?;
+        Ok(())
+    }
+
+    fn install_serde_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime("serde.ex", include_str!("../runtime/elixir/serde.ex"))
+    }
+
+    fn install_bincode_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime("bincode.ex", include_str!("../runtime/elixir/bincode.ex"))
+    }
+
+    fn install_bcs_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime("bcs.ex", include_str!("../runtime/elixir/bcs.ex"))
+    }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the postcard encoding",
+        ))
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the borsh encoding",
+        ))
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the scale encoding",
+        ))
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the json encoding",
+        ))
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use thiserror::Error;
+
+/// Result type used in this crate.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Error type returned by code generators and installers.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// A feature used by the registry or requested by the configuration is not supported
+    /// by the target language.
+    #[error("{language} does not support {feature}{}", location(.path))]
+    Unsupported {
+        /// The target language, e.g. "Java".
+        language: &'static str,
+        /// The unsupported feature, e.g. "c-style enums".
+        feature: String,
+        /// Where the feature is used, e.g. "Foo.bar", or `None` for configuration options.
+        path: Option<String>,
+    },
+    /// An identifier obtained by applying naming conventions is reserved in the target language
+    /// or clashes with another identifier.
+    #[error("{language} name `{name}` of {path} {conflict}")]
+    NameConflict {
+        /// The target language, e.g. "Java".
+        language: &'static str,
+        /// The converted name, e.g. "fooBar".
+        name: String,
+        /// The original location of the name, e.g. "Foo.foo_bar".
+        path: String,
+        /// What the name conflicts with, e.g. "clashes with Foo.fooBar".
+        conflict: String,
+    },
+    #[error("Invalid registry: {0}")]
+    Registry(#[from] serde_reflection::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Fmt(#[from] std::fmt::Error),
+}
+
+fn location(path: &Option<String>) -> String {
+    match path {
+        Some(path) => format!(" (in {})", path),
+        None => String::new(),
+    }
+}
+
+impl Error {
+    pub(crate) fn unsupported(language: &'static str, feature: impl Into<String>) -> Self {
+        Error::Unsupported {
+            language,
+            feature: feature.into(),
+            path: None,
+        }
+    }
+
+    /// Set the location of an `Unsupported` error, unless it is already known.
+    pub(crate) fn at(mut self, location: &str) -> Self {
+        if let Error::Unsupported { path, .. } = &mut self {
+            path.get_or_insert_with(|| location.to_string());
+        }
+        self
+    }
+}
@@ -4,17 +4,69 @@
 use crate::{
     common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig, Encoding,
+    naming, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error, IntEncoding, Result,
+    SourceGenerator, TrailingBytes,
 };
 use heck::CamelCase;
 use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
 use std::{
-    collections::{BTreeMap, HashMap},
-    io::{Result, Write},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    io::Write,
     path::PathBuf,
 };
 
+/// Features supported by the Go code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Go",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: crate::capabilities::ALL_ENCODINGS_AND_JSON,
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: true,
+    module_cycles: false,
+    native_types: true,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Reserved words of Go, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
 /// Main configuration object for code-generation in Go.
+#[derive(Clone)]
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
     config: &'a CodeGeneratorConfig,
@@ -34,14 +86,47 @@ struct GoEmitter<'a, T> {
     generator: &'a CodeGenerator<'a>,
     /// Current namespace (e.g. vec!["com", "my_org", "my_package", "MyClass"])
     current_namespace: Vec<String>,
+    /// Names of the enums of the registry. Their values are interfaces and may be `nil`.
+    enum_names: BTreeSet<String>,
+}
+
+/// How a struct or a variant is written in the JSON encoding, with the names of the variant
+/// and of the fields of a struct before their conversion to Go names.
+struct JsonNames {
+    shape: common::JsonShape,
+    name: String,
+    fields: Vec<String>,
+}
+
+impl JsonNames {
+    fn of_container(name: &str, format: &ContainerFormat) -> Self {
+        let fields = match format {
+            ContainerFormat::Struct(fields) => fields.iter().map(|f| f.name.clone()).collect(),
+            _ => Vec::new(),
+        };
+        JsonNames {
+            shape: common::JsonShape::of_container(format),
+            name: name.to_string(),
+            fields,
+        }
+    }
+
+    fn of_variant(name: &str, format: &VariantFormat) -> Self {
+        let fields = match format {
+            VariantFormat::Struct(fields) => fields.iter().map(|f| f.name.clone()).collect(),
+            _ => Vec::new(),
+        };
+        JsonNames {
+            shape: common::JsonShape::of_variant(format),
+            name: name.to_string(),
+            fields,
+        }
+    }
 }
 
 impl<'a> CodeGenerator<'a> {
     /// Create a Go code generator for the given config.
     pub fn new(config: &'a CodeGeneratorConfig) -> Self {
-        if config.c_style_enums {
-            panic!("Go does not support generating c-style enums");
-        }
         let mut external_qualified_names = HashMap::new();
         for (namespace, names) in &config.external_definitions {
             let package_name = {
@@ -74,6 +159,18 @@ impl<'a> CodeGenerator<'a> {
 
     /// Output class definitions for `registry`.
     pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        let registry = &common::remove_native_types(self.config, registry);
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config)
+                .with_serde_module_path(self.serde_module_path.clone())
+                .output(out, &registry);
+        }
         let current_namespace = self
             .config
             .module_name
@@ -86,6 +183,11 @@ impl<'a> CodeGenerator<'a> {
             out: IndentedWriter::new(out, IndentConfig::Tab),
             generator: self,
             current_namespace,
+            enum_names: registry
+                .iter()
+                .filter(|(_, format)| matches!(format, ContainerFormat::Enum(_)))
+                .map(|(name, _)| name.clone())
+                .collect(),
         };
 
         emitter.output_preamble(registry)?;
@@ -94,7 +196,10 @@ impl<'a> CodeGenerator<'a> {
             emitter.output_container(name, format)?;
         }
 
-        if self.config.serialization {
+        if self.config.serialization
+            || self.config.comparisons
+            || self.config.generates_validation()
+        {
             emitter.output_trait_helpers(registry)?;
         }
 
@@ -118,12 +223,19 @@ where
         }
         writeln!(self.out, "import (")?;
         self.out.indent();
-        if self.generator.config.serialization
-            && (Self::has_enum(registry) || !self.generator.config.encodings.is_empty())
+        if (self.generator.config.serialization
+            && (Self::has_enum(registry) || !self.generator.config.encodings.is_empty()))
+            || (self.generator.config.generates_validation() && self.has_enum_reference(registry))
         {
             writeln!(self.out, "\"fmt\"")?;
         }
-        if self.generator.config.serialization || Self::has_int128(registry) {
+        if self.generator.config.comparisons && Self::has_map(registry) {
+            writeln!(self.out, "\"sort\"")?;
+        }
+        if self.generator.config.serialization
+            || self.generator.config.comparisons
+            || Self::has_int128(registry)
+        {
             writeln!(self.out, "\"{}/serde\"", self.generator.serde_module_path)?;
         }
         if self.generator.config.serialization {
@@ -162,6 +274,33 @@ where
         false
     }
 
+    fn has_map(registry: &Registry) -> bool {
+        registry.values().any(|format| {
+            format
+                .visit(&mut |f| match f {
+                    Format::Map { .. } => Err(serde_reflection::Error::Custom(String::new())),
+                    _ => Ok(()),
+                })
+                .is_err()
+        })
+    }
+
+    /// Whether some value of the registry holds an enum of the registry.
+    fn has_enum_reference(&self, registry: &Registry) -> bool {
+        let mut result = false;
+        for format in registry.values() {
+            format
+                .visit(&mut |f| {
+                    if let Format::TypeName(name) = f {
+                        result |= self.enum_names.contains(name);
+                    }
+                    Ok(())
+                })
+                .unwrap();
+        }
+        result
+    }
+
     fn has_enum(registry: &Registry) -> bool {
         for format in registry.values() {
             if let ContainerFormat::Enum(_) = format {
@@ -202,7 +341,10 @@ where
     fn quote_type(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(x) => self.quote_qualified_name(x),
+            TypeName(x) => match self.generator.config.native_types.get(x) {
+                Some(native) => native.name.clone(),
+                None => self.quote_qualified_name(x),
+            },
             Unit => "struct {}".into(),
             Bool => "bool".into(),
             I8 => "int8".into(),
@@ -264,8 +406,23 @@ where
                 .unwrap();
         }
         for (mangled_name, subtype) in &subtypes {
-            self.output_serialization_helper(mangled_name, subtype)?;
-            self.output_deserialization_helper(mangled_name, subtype)?;
+            if self.generator.config.serialization {
+                self.output_serialization_helper(mangled_name, subtype)?;
+                self.output_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.has_json() {
+                self.output_json_serialization_helper(registry, mangled_name, subtype)?;
+                self.output_json_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.comparisons {
+                self.output_comparison_helper(mangled_name, subtype)?;
+                self.output_hash_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.generates_validation()
+                && common::needs_validation(self.generator.config, subtype, false)
+            {
+                self.output_validation_helper(mangled_name, subtype)?;
+            }
         }
         Ok(())
     }
@@ -281,7 +438,10 @@ where
     fn quote_serialize_value(&self, value: &str, format: &Format) -> String {
         use Format::*;
         let expr = match format {
-            TypeName(_) => format!("{}.Serialize(serializer)", value),
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!("{}({}, serializer)", native.serializer, value),
+                None => format!("{}.Serialize(serializer)", value),
+            },
             Unit => format!("serializer.SerializeUnit({})", value),
             Bool => format!("serializer.SerializeBool({})", value),
             I8 => format!("serializer.SerializeI8({})", value),
@@ -311,10 +471,13 @@ where
     fn quote_deserialize(&self, format: &Format, dest: &str, fail: &str) -> String {
         use Format::*;
         let expr = match format {
-            TypeName(name) => format!(
-                "Deserialize{}(deserializer)",
-                self.quote_qualified_name(name)
-            ),
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!("{}(deserializer)", native.deserializer),
+                None => format!(
+                    "Deserialize{}(deserializer)",
+                    self.quote_qualified_name(name)
+                ),
+            },
             Unit => "deserializer.DeserializeUnit()".to_string(),
             Bool => "deserializer.DeserializeBool()".to_string(),
             I8 => "deserializer.DeserializeI8()".to_string(),
@@ -340,6 +503,382 @@ where
         )
     }
 
+    /// Three-way comparison of `a` and `b`. Values of registry types must be addressable.
+    fn quote_compare(&self, a: &str, b: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) | I128 | U128 => format!("{}.Compare({})", a, b),
+            Unit => "0".to_string(),
+            Bool => format!("serde.CompareBool({}, {})", a, b),
+            I8 | I16 | I32 | I64 | Char => {
+                format!("serde.CompareInt64(int64({}), int64({}))", a, b)
+            }
+            U8 | U16 | U32 | U64 => format!("serde.CompareUint64(uint64({}), uint64({}))", a, b),
+            Str => format!("serde.CompareStr({}, {})", a, b),
+            Bytes => format!("serde.CompareBytes({}, {})", a, b),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            _ => format!("compare_{}({}, {})", common::mangle_type(format), a, b),
+        }
+    }
+
+    /// Hash of `value`, consistent with `quote_compare`.
+    fn quote_hash(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) | I128 | U128 => format!("{}.Hash()", value),
+            Unit => "0".to_string(),
+            Bool => format!("serde.HashBool({})", value),
+            I8 | I16 | I32 | I64 | Char | U8 | U16 | U32 | U64 => format!("uint64({})", value),
+            Str => format!("serde.HashStr({})", value),
+            Bytes => format!("serde.HashBytes({})", value),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            _ => format!("hash_{}({})", common::mangle_type(format), value),
+        }
+    }
+
+    fn output_comparison_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "func compare_{0}(a {1}, b {1}) int {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if a == nil || b == nil {{ return serde.CompareBool(a != nil, b != nil) }}
+return {}
+"#,
+                    self.quote_compare("(*a)", "(*b)", format)
+                )?;
+            }
+
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                write!(
+                    self.out,
+                    r#"
+for i := 0; i < len(a) && i < len(b); i++ {{
+	if result := {}; result != 0 {{ return result }}
+}}
+return serde.CompareInt64(int64(len(a)), int64(len(b)))
+"#,
+                    self.quote_compare("a[i]", "b[i]", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+x := make([]{0}, 0, len(a))
+for k := range a {{ x = append(x, k) }}
+sort.Slice(x, func(i, j int) bool {{ return {1} < 0 }})
+y := make([]{0}, 0, len(b))
+for k := range b {{ y = append(y, k) }}
+sort.Slice(y, func(i, j int) bool {{ return {2} < 0 }})
+for i := 0; i < len(x) && i < len(y); i++ {{
+	if result := {3}; result != 0 {{ return result }}
+	u, v := a[x[i]], b[y[i]]
+	if result := {4}; result != 0 {{ return result }}
+}}
+return serde.CompareInt64(int64(len(x)), int64(len(y)))
+"#,
+                    self.quote_type(key),
+                    self.quote_compare("x[i]", "x[j]", key),
+                    self.quote_compare("y[i]", "y[j]", key),
+                    self.quote_compare("x[i]", "y[i]", key),
+                    self.quote_compare("u", "v", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out)?;
+                for (index, format) in formats.iter().enumerate() {
+                    let a = format!("a.Field{}", index);
+                    let b = format!("b.Field{}", index);
+                    writeln!(
+                        self.out,
+                        "if result := {}; result != 0 {{ return result }}",
+                        self.quote_compare(&a, &b, format)
+                    )?;
+                }
+                writeln!(self.out, "return 0")?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_hash_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "func hash_{}(value {}) uint64 {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if value == nil {{ return 0 }}
+return serde.HashCombine(1, {})
+"#,
+                    self.quote_hash("(*value)", format)
+                )?;
+            }
+
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                write!(
+                    self.out,
+                    r#"
+hash := uint64(len(value))
+for i := range value {{
+	hash = serde.HashCombine(hash, {})
+}}
+return hash
+"#,
+                    self.quote_hash("value[i]", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                // Entries are combined with a commutative operation since maps are unordered.
+                write!(
+                    self.out,
+                    r#"
+hash := uint64(len(value))
+for k, v := range value {{
+	hash += serde.HashCombine({}, {})
+}}
+return hash
+"#,
+                    self.quote_hash("k", key),
+                    self.quote_hash("v", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out, "\nhash := uint64(0)")?;
+                for (index, format) in formats.iter().enumerate() {
+                    writeln!(
+                        self.out,
+                        "hash = serde.HashCombine(hash, {})",
+                        self.quote_hash(&format!("value.Field{}", index), format)
+                    )?;
+                }
+                writeln!(self.out, "return hash")?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// Emit `Compare`, `Equal` and `Hash` methods for a struct or a variant.
+    /// Variants are ordered by index first, then by their fields.
+    fn output_comparisons<'b, I>(
+        &mut self,
+        variant_base: Option<&str>,
+        variant_index: Option<u32>,
+        full_name: &str,
+        fields: I,
+    ) -> Result<()>
+    where
+        I: Clone + IntoIterator<Item = (String, String, &'b Format)>,
+    {
+        let other_type = variant_base.unwrap_or(full_name);
+        writeln!(
+            self.out,
+            "\nfunc (obj *{}) Compare(other {}) int {{",
+            full_name, other_type
+        )?;
+        self.out.indent();
+        if let Some(index) = variant_index {
+            let has_fields = fields.clone().into_iter().next().is_some();
+            writeln!(
+                self.out,
+                "{}, ok := other.(*{})\nif !ok {{ return serde.CompareUint64({}, uint64(other.variantIndex())) }}",
+                if has_fields { "o" } else { "_" },
+                full_name,
+                index
+            )?;
+        }
+        for (a, b, format) in fields.clone() {
+            writeln!(
+                self.out,
+                "if result := {}; result != 0 {{ return result }}",
+                self.quote_compare(&a, &b, format)
+            )?;
+        }
+        writeln!(self.out, "return 0")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        writeln!(
+            self.out,
+            "\nfunc (obj *{}) Equal(other {}) bool {{\n\treturn obj.Compare(other) == 0\n}}",
+            full_name, other_type
+        )?;
+
+        writeln!(self.out, "\nfunc (obj *{}) Hash() uint64 {{", full_name)?;
+        self.out.indent();
+        writeln!(self.out, "hash := uint64({})", variant_index.unwrap_or(0))?;
+        for (a, _, format) in fields {
+            writeln!(
+                self.out,
+                "hash = serde.HashCombine(hash, {})",
+                self.quote_hash(&a, format)
+            )?;
+        }
+        writeln!(self.out, "return hash")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        if let Some(index) = variant_index {
+            writeln!(
+                self.out,
+                "\nfunc (*{}) variantIndex() uint32 {{ return {} }}",
+                full_name, index
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Fixed-size arrays are enforced by Go types, but enum values may be `nil`.
+    fn needs_validation(&self, format: &Format) -> bool {
+        common::needs_validation(self.generator.config, format, false)
+    }
+
+    fn quote_validate(&self, value: &str, format: &Format) -> String {
+        match format {
+            Format::TypeName(name) if self.enum_names.contains(name) => format!(
+                "if {0} == nil {{ return fmt.Errorf(\"Missing value for enum {1}\") }}\nif err := {0}.Validate(); err != nil {{ return err }}",
+                value, name
+            ),
+            Format::TypeName(_) => {
+                format!("if err := {}.Validate(); err != nil {{ return err }}", value)
+            }
+            _ => format!(
+                "if err := validate_{}({}); err != nil {{ return err }}",
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn output_validation_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(
+            self.out,
+            "func validate_{}(value {}) error {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                writeln!(
+                    self.out,
+                    "if value != nil {{\n{}\n}}",
+                    textwrap::indent(&self.quote_validate("(*value)", format), "\t")
+                )?;
+            }
+
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                writeln!(
+                    self.out,
+                    "for _, item := range(value) {{\n{}\n}}",
+                    textwrap::indent(&self.quote_validate("item", format), "\t")
+                )?;
+            }
+
+            Map { key, value } => {
+                writeln!(
+                    self.out,
+                    "for {}, {} := range(value) {{",
+                    if self.needs_validation(key) { "k" } else { "_" },
+                    if self.needs_validation(value) {
+                        "v"
+                    } else {
+                        "_"
+                    },
+                )?;
+                if self.needs_validation(key) {
+                    writeln!(
+                        self.out,
+                        "{}",
+                        textwrap::indent(&self.quote_validate("k", key), "\t")
+                    )?;
+                }
+                if self.needs_validation(value) {
+                    writeln!(
+                        self.out,
+                        "{}",
+                        textwrap::indent(&self.quote_validate("v", value), "\t")
+                    )?;
+                }
+                writeln!(self.out, "}}")?;
+            }
+
+            Tuple(formats) => {
+                for (index, format) in formats.iter().enumerate() {
+                    if self.needs_validation(format) {
+                        let expr = format!("value.Field{}", index);
+                        writeln!(self.out, "{}", self.quote_validate(&expr, format))?;
+                    }
+                }
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        writeln!(self.out, "return nil")?;
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// Emit the `Validate` method of a struct or a variant, given the expressions for its fields.
+    fn output_validate<'b, I>(&mut self, full_name: &str, fields: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (String, &'b Format)>,
+    {
+        writeln!(self.out, "\nfunc (obj *{}) Validate() error {{", full_name)?;
+        self.out.indent();
+        for (value, format) in fields {
+            if self.needs_validation(format) {
+                writeln!(self.out, "{}", self.quote_validate(&value, format))?;
+            }
+        }
+        writeln!(self.out, "return nil")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
     fn output_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
         use Format::*;
 
@@ -421,17 +960,284 @@ for _, item := range(value) {{
 
             _ => panic!("unexpected case"),
         }
-        writeln!(self.out, "return nil")?;
+        writeln!(self.out, "return nil")?;
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "func deserialize_{}(deserializer serde.Deserializer) ({}, error) {{",
+            name,
+            self.quote_type(format0),
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+tag, err := deserializer.DeserializeOptionTag()
+if err != nil {{ return nil, err }}
+if tag {{
+	value := new({})
+	{}
+        return value, nil
+}} else {{
+	return nil, nil
+}}
+"#,
+                    self.quote_type(format),
+                    self.quote_deserialize(format, "*value", "nil"),
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+length, err := deserializer.DeserializeLen()
+if err != nil {{ return nil, err }}
+obj := make([]{}, length)
+for i := range(obj) {{
+	{}
+}}
+return obj, nil
+"#,
+                    self.quote_type(format),
+                    self.quote_deserialize(format, "obj[i]", "nil")
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+length, err := deserializer.DeserializeLen()
+if err != nil {{ return nil, err }}
+obj := make(map[{0}]{1})
+previous_slice := serde.Slice {{ 0, 0 }}
+for i := 0; i < int(length); i++ {{
+	var slice serde.Slice
+	slice.Start = deserializer.GetBufferOffset()
+	var key {0}
+	{2}
+	slice.End = deserializer.GetBufferOffset()
+	if i > 0 {{
+		err := deserializer.CheckThatKeySlicesAreIncreasing(previous_slice, slice)
+		if err != nil {{ return nil, err }}
+	}}
+	previous_slice = slice
+	{3}
+}}
+return obj, nil
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_deserialize(key, "key", "nil"),
+                    self.quote_deserialize(value, "obj[key]", "nil"),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    r#"
+var obj {}
+{}
+return obj, nil
+"#,
+                    self.quote_type(format0),
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| self.quote_deserialize(f, &format!("obj.Field{}", i), "obj"))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+var obj [{1}]{0}
+for i := range(obj) {{
+	{2}
+}}
+return obj, nil
+"#,
+                    self.quote_type(content),
+                    size,
+                    self.quote_deserialize(content, "obj[i]", "obj")
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// Whether to generate the JSON encoding, which reads and writes values as trees of
+    /// JSON values rather than through a `Serializer` or a `Deserializer`.
+    fn has_json(&self) -> bool {
+        self.generator.config.serialization
+            && self.generator.config.encodings.contains(&Encoding::Json)
+    }
+
+    /// Binary encodings for which the generated types provide serialization methods.
+    fn binary_encodings(&self) -> Vec<Encoding> {
+        self.generator
+            .config
+            .encodings
+            .iter()
+            .copied()
+            .filter(|encoding| *encoding != Encoding::Json)
+            .collect()
+    }
+
+    /// JSON value of `value`. Values of registry types must be addressable.
+    fn quote_to_json(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) => format!("{}.ToJson()", value),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "json.Serialize{}({})",
+                common::mangle_type(format).to_camel_case(),
+                value
+            ),
+            _ => format!("toJson_{}({})", common::mangle_type(format), value),
+        }
+    }
+
+    fn quote_from_json(&self, value: &str, format: &Format, dest: &str, fail: &str) -> String {
+        use Format::*;
+        let expr = match format {
+            TypeName(name) => format!("FromJson{}({})", self.quote_qualified_name(name), value),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "json.Deserialize{}({})",
+                common::mangle_type(format).to_camel_case(),
+                value
+            ),
+            _ => format!("fromJson_{}({})", common::mangle_type(format), value),
+        };
+        format!(
+            "if val, err := {}; err == nil {{ {} = val }} else {{ return {}, err }}",
+            expr, dest, fail
+        )
+    }
+
+    fn quote_json_key_order(registry: &Registry, key: &Format) -> String {
+        match common::JsonKeyOrder::of_key(registry, key) {
+            common::JsonKeyOrder::Text => "json.StringOrder".to_string(),
+            common::JsonKeyOrder::Number => "json.NumberOrder".to_string(),
+            common::JsonKeyOrder::Variants(names) => format!(
+                "json.VariantOrder({})",
+                names
+                    .iter()
+                    .map(|name| format!("{:?}", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn output_json_serialization_helper(
+        &mut self,
+        registry: &Registry,
+        name: &str,
+        format0: &Format,
+    ) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "func toJson_{}(value {}) json.Value {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if value == nil {{ return nil }}
+return {}
+"#,
+                    self.quote_to_json("(*value)", format)
+                )?;
+            }
+
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                write!(
+                    self.out,
+                    r#"
+result := make([]json.Value, len(value))
+for i := range value {{
+	result[i] = {}
+}}
+return result
+"#,
+                    self.quote_to_json("value[i]", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                // Go maps are unordered: entries are sorted as in a `BTreeMap`.
+                write!(
+                    self.out,
+                    r#"
+result := &json.Object{{}}
+for k, v := range value {{
+	result.Put(json.KeyOf({}), {})
+}}
+result.SortKeys({})
+return result
+"#,
+                    self.quote_to_json("k", key),
+                    self.quote_to_json("v", value),
+                    Self::quote_json_key_order(registry, key),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    "\nreturn []json.Value{{{}}}\n",
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| self.quote_to_json(&format!("value.Field{}", i), f))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
-    fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+    fn output_json_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
         use Format::*;
 
         write!(
             self.out,
-            "func deserialize_{}(deserializer serde.Deserializer) ({}, error) {{",
+            "func fromJson_{}(value json.Value) ({}, error) {{",
             name,
             self.quote_type(format0),
         )?;
@@ -441,18 +1247,13 @@ for _, item := range(value) {{
                 write!(
                     self.out,
                     r#"
-tag, err := deserializer.DeserializeOptionTag()
-if err != nil {{ return nil, err }}
-if tag {{
-	value := new({})
-	{}
-        return value, nil
-}} else {{
-	return nil, nil
-}}
+if value == nil {{ return nil, nil }}
+obj := new({})
+{}
+return obj, nil
 "#,
                     self.quote_type(format),
-                    self.quote_deserialize(format, "*value", "nil"),
+                    self.quote_from_json("value", format, "*obj", "nil"),
                 )?;
             }
 
@@ -460,16 +1261,16 @@ if tag {{
                 write!(
                     self.out,
                     r#"
-length, err := deserializer.DeserializeLen()
+items, err := json.DeserializeArray(value, -1)
 if err != nil {{ return nil, err }}
-obj := make([]{}, length)
-for i := range(obj) {{
+obj := make([]{}, len(items))
+for i := range items {{
 	{}
 }}
 return obj, nil
 "#,
                     self.quote_type(format),
-                    self.quote_deserialize(format, "obj[i]", "nil")
+                    self.quote_from_json("items[i]", format, "obj[i]", "nil")
                 )?;
             }
 
@@ -477,29 +1278,20 @@ return obj, nil
                 write!(
                     self.out,
                     r#"
-length, err := deserializer.DeserializeLen()
+entries, err := json.DeserializeObject(value)
 if err != nil {{ return nil, err }}
 obj := make(map[{0}]{1})
-previous_slice := serde.Slice {{ 0, 0 }}
-for i := 0; i < int(length); i++ {{
-	var slice serde.Slice
-	slice.Start = deserializer.GetBufferOffset()
+for i := range entries.Keys {{
 	var key {0}
 	{2}
-	slice.End = deserializer.GetBufferOffset()
-	if i > 0 {{
-		err := deserializer.CheckThatKeySlicesAreIncreasing(previous_slice, slice)
-		if err != nil {{ return nil, err }}
-	}}
-	previous_slice = slice
 	{3}
 }}
 return obj, nil
 "#,
                     self.quote_type(key),
                     self.quote_type(value),
-                    self.quote_deserialize(key, "key", "nil"),
-                    self.quote_deserialize(value, "obj[key]", "nil"),
+                    self.quote_from_json("json.Key(entries.Keys[i])", key, "key", "nil"),
+                    self.quote_from_json("entries.Values[i]", value, "obj[key]", "nil"),
                 )?;
             }
 
@@ -508,14 +1300,22 @@ return obj, nil
                     self.out,
                     r#"
 var obj {}
+items, err := json.DeserializeArray(value, {})
+if err != nil {{ return obj, err }}
 {}
 return obj, nil
 "#,
                     self.quote_type(format0),
+                    formats.len(),
                     formats
                         .iter()
                         .enumerate()
-                        .map(|(i, f)| self.quote_deserialize(f, &format!("obj.Field{}", i), "obj"))
+                        .map(|(i, f)| self.quote_from_json(
+                            &format!("items[{}]", i),
+                            f,
+                            &format!("obj.Field{}", i),
+                            "obj"
+                        ))
                         .collect::<Vec<_>>()
                         .join("\n")
                 )?;
@@ -526,21 +1326,270 @@ return obj, nil
                     self.out,
                     r#"
 var obj [{1}]{0}
-for i := range(obj) {{
+items, err := json.DeserializeArray(value, {1})
+if err != nil {{ return obj, err }}
+for i := range items {{
 	{2}
 }}
 return obj, nil
 "#,
                     self.quote_type(content),
                     size,
-                    self.quote_deserialize(content, "obj[i]", "obj")
+                    self.quote_from_json("items[i]", content, "obj[i]", "obj")
                 )?;
             }
 
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// Emit the `ToJson` method and the `FromJson` function (struct) or the `loadJson_`
+    /// function (variant) of a struct or a variant. `fields` holds the expressions that read
+    /// the fields from `obj` and the destinations where to write them into a new `obj` of type
+    /// `obj_type`. The new value is then returned as `result`.
+    fn output_json_conversions(
+        &mut self,
+        variant_base: Option<&str>,
+        full_name: &str,
+        json: &JsonNames,
+        (obj_type, result): (&str, &str),
+        fields: &[(String, String, &Format)],
+    ) -> Result<()> {
+        use common::JsonShape;
+        // Serialization
+        writeln!(
+            self.out,
+            "\nfunc (obj *{}) ToJson() json.Value {{",
+            full_name
+        )?;
+        self.out.indent();
+        let content = match json.shape {
+            JsonShape::Unit => "nil".to_string(),
+            JsonShape::NewType => self.quote_to_json(&fields[0].0, fields[0].2),
+            JsonShape::Tuple => format!(
+                "[]json.Value{{{}}}",
+                fields
+                    .iter()
+                    .map(|(value, _, format)| self.quote_to_json(value, format))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            JsonShape::Struct => {
+                writeln!(self.out, "result := &json.Object{{}}")?;
+                for ((value, _, format), name) in fields.iter().zip(&json.fields) {
+                    writeln!(
+                        self.out,
+                        "result.Put({:?}, {})",
+                        name,
+                        self.quote_to_json(value, format)
+                    )?;
+                }
+                "result".to_string()
+            }
+        };
+        match variant_base {
+            Some(_) if json.shape == JsonShape::Unit => {
+                writeln!(self.out, "return {:?}", json.name)?
+            }
+            Some(_) => writeln!(
+                self.out,
+                "return json.SerializeVariant({:?}, {})",
+                json.name, content
+            )?,
+            None => writeln!(self.out, "return {}", content)?,
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        // Deserialization
+        match variant_base {
+            None => {
+                writeln!(
+                    self.out,
+                    "\nfunc FromJson{0}(value json.Value) ({0}, error) {{",
+                    full_name
+                )?;
+                self.out.indent();
+                writeln!(self.out, "var obj {}", obj_type)?;
+                if json.shape == JsonShape::Unit {
+                    writeln!(
+                        self.out,
+                        "if _, err := json.DeserializeUnit(value); err != nil {{ return {}, err }}",
+                        result
+                    )?;
+                }
+            }
+            Some(_) => {
+                writeln!(
+                    self.out,
+                    "\nfunc loadJson_{0}(variant *json.Variant) ({0}, error) {{",
+                    full_name
+                )?;
+                self.out.indent();
+                writeln!(self.out, "var obj {}", obj_type)?;
+                if json.shape == JsonShape::Unit {
+                    writeln!(
+                        self.out,
+                        "if err := variant.Unit(); err != nil {{ return {}, err }}",
+                        result
+                    )?;
+                } else {
+                    writeln!(
+                        self.out,
+                        "value, err := variant.Value()\nif err != nil {{ return {}, err }}",
+                        result
+                    )?;
+                }
+            }
+        }
+        match json.shape {
+            JsonShape::Unit => (),
+            JsonShape::NewType => {
+                let (_, dest, format) = &fields[0];
+                writeln!(
+                    self.out,
+                    "{}",
+                    self.quote_from_json("value", format, dest, result)
+                )?;
+            }
+            JsonShape::Tuple if fields.is_empty() => writeln!(
+                self.out,
+                "if _, err := json.DeserializeArray(value, 0); err != nil {{ return {}, err }}",
+                result
+            )?,
+            JsonShape::Tuple => {
+                writeln!(
+                    self.out,
+                    "items, err := json.DeserializeArray(value, {})\nif err != nil {{ return {}, err }}",
+                    fields.len(),
+                    result
+                )?;
+                for (i, (_, dest, format)) in fields.iter().enumerate() {
+                    writeln!(
+                        self.out,
+                        "{}",
+                        self.quote_from_json(&format!("items[{}]", i), format, dest, result)
+                    )?;
+                }
+            }
+            JsonShape::Struct if fields.is_empty() => writeln!(
+                self.out,
+                "if _, err := json.DeserializeObject(value); err != nil {{ return {}, err }}",
+                result
+            )?,
+            JsonShape::Struct => {
+                writeln!(
+                    self.out,
+                    "object, err := json.DeserializeObject(value)\nif err != nil {{ return {}, err }}",
+                    result
+                )?;
+                for ((_, dest, format), name) in fields.iter().zip(&json.fields) {
+                    // Like Serde, missing optional fields are read as `None`.
+                    let getter = match format {
+                        Format::Option(_) => "OptionalField",
+                        _ => "Field",
+                    };
+                    writeln!(
+                        self.out,
+                        "if field, err := object.{}({:?}); err != nil {{ return {}, err }} else {{ {} }}",
+                        getter,
+                        name,
+                        result,
+                        self.quote_from_json("field", format, dest, result)
+                    )?;
+                }
+            }
+        }
+        writeln!(self.out, "return {}, nil", result)?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_enum_from_json(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        write!(
+            self.out,
+            "\nfunc FromJson{0}(value json.Value) ({0}, error) {{",
+            name
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"
+variant, err := json.DeserializeVariant(value)
+if err != nil {{ return nil, err }}
+
+switch variant.Name {{"#,
+        )?;
+        for variant in variants.values() {
+            writeln!(
+                self.out,
+                r#"case {:?}:
+	if val, err := loadJson_{}__{}(variant); err == nil {{
+		return &val, nil
+	}} else {{
+		return nil, err
+	}}
+"#,
+                variant.name,
+                name,
+                variant.name.to_camel_case()
+            )?;
+        }
+        writeln!(
+            self.out,
+            "default:
+	return nil, fmt.Errorf(\"Unknown variant for {}: %s\", variant.Name)",
+            name,
+        )?;
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_json_serialize(&mut self, name: &str) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+func (obj *{}) JsonSerialize() ([]byte, error) {{
+	if obj == nil {{
+		return nil, fmt.Errorf("Cannot serialize null object")
+	}}{}
+	return json.Stringify(obj.ToJson())
+}}"#,
+            name,
+            if self.generator.config.validate_on_serialize {
+                "\n\tif err := obj.Validate(); err != nil { return nil, err }"
+            } else {
+                ""
+            },
+        )?;
+        Ok(())
+    }
+
+    fn output_json_deserialize(&mut self, name: &str) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+func JsonDeserialize{0}(input []byte) ({0}, error) {{
+	value, err := json.Parse(input)
+	if err != nil {{
+		var obj {0}
+		return obj, err
+	}}
+	return FromJson{0}(value)
+}}"#,
+            name,
+        )?;
+        Ok(())
     }
 
     fn output_variant(
@@ -551,6 +1600,8 @@ return obj, nil
         variant: &VariantFormat,
     ) -> Result<()> {
         use VariantFormat::*;
+        let json = JsonNames::of_variant(name, variant);
+        let name = &name.to_camel_case();
         let fields = match variant {
             Unit => Vec::new(),
             NewType(format) => match format.as_ref() {
@@ -571,6 +1622,7 @@ return obj, nil
                         Some(index),
                         name,
                         format,
+                        &json,
                     )?;
                     return Ok(());
                 }
@@ -592,7 +1644,7 @@ return obj, nil
                 .collect(),
             Variable(_) => panic!("incorrect value"),
         };
-        self.output_struct_or_variant_container(Some(base), Some(index), name, &fields)
+        self.output_struct_or_variant_container(Some(base), Some(index), name, &fields, &json)
     }
 
     fn output_struct_or_variant_container(
@@ -601,6 +1653,7 @@ return obj, nil
         variant_index: Option<u32>,
         name: &str,
         fields: &[Named<Format>],
+        json: &JsonNames,
     ) -> Result<()> {
         let full_name = match variant_base {
             None => name.to_string(),
@@ -650,8 +1703,8 @@ return obj, nil
             self.out.unindent();
             writeln!(self.out, "}}")?;
 
-            for encoding in &self.generator.config.encodings {
-                self.output_struct_serialize_for_encoding(&full_name, *encoding)?;
+            for encoding in self.binary_encodings() {
+                self.output_struct_serialize_for_encoding(&full_name, encoding)?;
             }
         }
         // Deserialize (struct) or Load (variant)
@@ -685,11 +1738,49 @@ return obj, nil
             writeln!(self.out, "}}")?;
 
             if variant_base.is_none() {
-                for encoding in &self.generator.config.encodings {
-                    self.output_struct_deserialize_for_encoding(&full_name, *encoding)?;
+                for encoding in self.binary_encodings() {
+                    self.output_struct_deserialize_for_encoding(&full_name, encoding)?;
                 }
             }
         }
+        // JSON
+        if self.has_json() {
+            let fields = fields
+                .iter()
+                .map(|f| {
+                    let value = format!("obj.{}", f.name);
+                    (value.clone(), value, &f.value)
+                })
+                .collect::<Vec<_>>();
+            self.output_json_conversions(
+                variant_base,
+                &full_name,
+                json,
+                (&full_name, "obj"),
+                &fields,
+            )?;
+            self.output_json_serialize(&full_name)?;
+            if variant_base.is_none() {
+                self.output_json_deserialize(&full_name)?;
+            }
+        }
+        // Validation
+        if self.generator.config.generates_validation() {
+            let fields = fields.iter().map(|f| (format!("obj.{}", f.name), &f.value));
+            self.output_validate(&full_name, fields)?;
+        }
+        // Comparisons
+        if self.generator.config.comparisons {
+            let other = if variant_base.is_some() { "o" } else { "other" };
+            let fields = fields.iter().map(|f| {
+                (
+                    format!("obj.{}", f.name),
+                    format!("{}.{}", other, f.name),
+                    &f.value,
+                )
+            });
+            self.output_comparisons(variant_base, variant_index, &full_name, fields)?;
+        }
         // Custom code
         self.output_custom_code(name)?;
         Ok(())
@@ -703,6 +1794,7 @@ return obj, nil
         variant_index: Option<u32>,
         name: &str,
         format: &Format,
+        json: &JsonNames,
     ) -> Result<()> {
         let full_name = match variant_base {
             None => name.to_string(),
@@ -746,8 +1838,8 @@ return obj, nil
             self.out.unindent();
             writeln!(self.out, "}}")?;
 
-            for encoding in &self.generator.config.encodings {
-                self.output_struct_serialize_for_encoding(&full_name, *encoding)?;
+            for encoding in self.binary_encodings() {
+                self.output_struct_serialize_for_encoding(&full_name, encoding)?;
             }
         }
         // Deserialize (struct) or Load (variant)
@@ -776,11 +1868,49 @@ return obj, nil
             writeln!(self.out, "}}")?;
 
             if variant_base.is_none() {
-                for encoding in &self.generator.config.encodings {
-                    self.output_struct_deserialize_for_encoding(&full_name, *encoding)?;
+                for encoding in self.binary_encodings() {
+                    self.output_struct_deserialize_for_encoding(&full_name, encoding)?;
                 }
             }
         }
+        // JSON
+        if self.has_json() {
+            let field = (
+                format!("(({})(*obj))", self.quote_type(format)),
+                "obj".to_string(),
+                format,
+            );
+            self.output_json_conversions(
+                variant_base,
+                &full_name,
+                json,
+                (&self.quote_type(format), &format!("({})(obj)", full_name)),
+                &[field],
+            )?;
+            self.output_json_serialize(&full_name)?;
+            if variant_base.is_none() {
+                self.output_json_deserialize(&full_name)?;
+            }
+        }
+        // Validation
+        if self.generator.config.generates_validation() {
+            let field = (format!("(({})(*obj))", self.quote_type(format)), format);
+            self.output_validate(&full_name, Some(field))?;
+        }
+        // Comparisons
+        if self.generator.config.comparisons {
+            let other = if variant_base.is_some() {
+                "*o"
+            } else {
+                "other"
+            };
+            let field = (
+                format!("(({})(*obj))", self.quote_type(format)),
+                format!("(({})({}))", self.quote_type(format), other),
+                format,
+            );
+            self.output_comparisons(variant_base, variant_index, &full_name, Some(field))?;
+        }
         // Custom code
         self.output_custom_code(name)?;
         Ok(())
@@ -791,21 +1921,46 @@ return obj, nil
         name: &str,
         encoding: Encoding,
     ) -> Result<()> {
+        let options = self.generator.config.bincode_options_for(encoding);
         writeln!(
             self.out,
             r#"
 func (obj *{0}) {2}Serialize() ([]byte, error) {{
 	if obj == nil {{
 		return nil, fmt.Errorf("Cannot serialize null object")
-	}}
-	serializer := {1}.NewSerializer();
-	if err := obj.Serialize(serializer); err != nil {{ return nil, err }}
+	}}{3}
+	serializer := {1}.{4};
+	if err := obj.Serialize(serializer); err != nil {{ return nil, err }}{5}
 	return serializer.GetBytes(), nil
 }}"#,
             name,
             encoding.name(),
-            encoding.name().to_camel_case()
-        )
+            encoding.name().to_camel_case(),
+            if self.generator.config.validate_on_serialize {
+                "\n\tif err := obj.Validate(); err != nil { return nil, err }"
+            } else {
+                ""
+            },
+            if options.is_custom_encoding() {
+                format!(
+                    "NewSerializerWithOptions({})",
+                    common::bincode_runtime_arguments(&options)
+                )
+            } else {
+                "NewSerializer()".to_string()
+            },
+            match options.limit {
+                Some(limit) => format!(
+                    r#"
+	if serializer.GetBufferOffset() > {} {{
+		return nil, fmt.Errorf("Exceeded the size limit of the encoding")
+	}}"#,
+                    limit
+                ),
+                None => String::new(),
+            }
+        )?;
+        Ok(())
     }
 
     fn output_struct_deserialize_for_encoding(
@@ -813,6 +1968,30 @@ func (obj *{0}) {2}Serialize() ([]byte, error) {{
         name: &str,
         encoding: Encoding,
     ) -> Result<()> {
+        let options = self.generator.config.bincode_options_for(encoding);
+        let checks = match options.trailing_bytes {
+            TrailingBytes::Reject => {
+                r#"
+	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
+		return obj, fmt.Errorf("Some input bytes were not read")
+	}"#
+            }
+            TrailingBytes::Allow => "",
+        };
+        // The runtime enforces the size limit while reading.
+        let constructor = match options.limit {
+            Some(limit) => format!(
+                "NewDeserializerWithLimit(input, {}, {}, {})",
+                options.int_encoding == IntEncoding::Varint,
+                options.endianness == Endianness::Big,
+                limit
+            ),
+            None if options.is_custom_encoding() => format!(
+                "NewDeserializerWithOptions(input, {})",
+                common::bincode_runtime_arguments(&options)
+            ),
+            None => "NewDeserializer(input)".to_string(),
+        };
         writeln!(
             self.out,
             r#"
@@ -821,17 +2000,17 @@ func {2}Deserialize{0}(input []byte) ({0}, error) {{
 		var obj {0}
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}}
-	deserializer := {1}.NewDeserializer(input);
-	obj, err := Deserialize{0}(deserializer)
-	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {{
-		return obj, fmt.Errorf("Some input bytes were not read")
-	}}
+	deserializer := {1}.{3};
+	obj, err := Deserialize{0}(deserializer){4}
 	return obj, err
 }}"#,
             name,
             encoding.name(),
             encoding.name().to_camel_case(),
-        )
+            constructor,
+            checks,
+        )?;
+        Ok(())
     }
 
     fn output_enum_container(
@@ -845,8 +2024,20 @@ func {2}Deserialize{0}(input []byte) ({0}, error) {{
         self.current_namespace.push(name.to_string());
         self.out.indent();
         writeln!(self.out, "is{}()", name)?;
+        if self.generator.config.comparisons {
+            writeln!(self.out, "variantIndex() uint32")?;
+            writeln!(self.out, "Compare(other {}) int", name)?;
+            writeln!(self.out, "Equal(other {}) bool", name)?;
+            writeln!(self.out, "Hash() uint64")?;
+        }
+        if self.generator.config.generates_validation() {
+            writeln!(self.out, "Validate() error")?;
+        }
         if self.generator.config.serialization {
             writeln!(self.out, "Serialize(serializer serde.Serializer) error")?;
+            if self.has_json() {
+                writeln!(self.out, "ToJson() json.Value")?;
+            }
             for encoding in &self.generator.config.encodings {
                 writeln!(
                     self.out,
@@ -883,7 +2074,9 @@ switch index {{"#,
 		return nil, err
 	}}
 "#,
-                    index, name, variant.name
+                    index,
+                    name,
+                    variant.name.to_camel_case()
                 )?;
             }
             writeln!(
@@ -896,11 +2089,16 @@ switch index {{"#,
             self.out.unindent();
             writeln!(self.out, "}}")?;
 
-            for encoding in &self.generator.config.encodings {
-                self.output_struct_deserialize_for_encoding(name, *encoding)?;
+            for encoding in self.binary_encodings() {
+                self.output_struct_deserialize_for_encoding(name, encoding)?;
             }
         }
 
+        if self.has_json() {
+            self.output_enum_from_json(name, variants)?;
+            self.output_json_deserialize(name)?;
+        }
+
         for (index, variant) in variants {
             self.output_variant(name, *index, &variant.name, &variant.value)?;
         }
@@ -912,6 +2110,7 @@ switch index {{"#,
 
     fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
         use ContainerFormat::*;
+        let json = JsonNames::of_container(name, format);
         let fields = match format {
             UnitStruct => Vec::new(),
             NewTypeStruct(format) => match format.as_ref() {
@@ -921,7 +2120,9 @@ switch index {{"#,
                     value: format.as_ref().clone(),
                 }],
                 _ => {
-                    self.output_struct_or_variant_new_type_container(None, None, name, format)?;
+                    self.output_struct_or_variant_new_type_container(
+                        None, None, name, format, &json,
+                    )?;
                     return Ok(());
                 }
             },
@@ -940,24 +2141,89 @@ switch index {{"#,
                     value: f.value.clone(),
                 })
                 .collect(),
+            OpenEnum(_) => common::open_enum_fields()
+                .into_iter()
+                .map(|f| Named {
+                    name: f.name.to_camel_case(),
+                    value: f.value,
+                })
+                .collect(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("Go", name)),
+            FlattenedStruct(_) => return Err(common::flattened_struct_not_supported("Go", name)),
             Enum(variants) => {
-                let variants = variants
-                    .iter()
-                    .map(|(i, f)| {
-                        (
-                            *i,
-                            Named {
-                                name: f.name.to_camel_case(),
-                                value: f.value.clone(),
-                            },
-                        )
-                    })
-                    .collect();
-                self.output_enum_container(name, &variants)?;
+                self.output_enum_container(name, variants)?;
                 return Ok(());
             }
         };
-        self.output_struct_or_variant_container(None, None, name, &fields)
+        self.output_struct_or_variant_container(None, None, name, &fields, &json)?;
+        if let OpenEnum(formats) = format {
+            self.output_open_enum_handlers(name, formats)?;
+        }
+        Ok(())
+    }
+
+    /// Output the tags of the variants of an open enum known at generation time, and a registry
+    /// of handlers so that clients may decode the variants that they know and skip others.
+    fn output_open_enum_handlers(
+        &mut self,
+        name: &str,
+        formats: &BTreeMap<String, Format>,
+    ) -> Result<()> {
+        let tags = formats
+            .keys()
+            .map(|tag| format!("{:?}", tag))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            self.out,
+            r#"
+// {0}Tags lists the tags of the variants of {0} known when the code was generated.
+var {0}Tags = []string{{{1}}}
+
+var {2}Handlers = map[string]func(content []byte) error{{}}
+
+// Register{0}Handler registers the handler of the variants of {0} with the given tag.
+// Handlers receive the encoded content of the variants.
+func Register{0}Handler(tag string, handler func(content []byte) error) {{
+	{2}Handlers[tag] = handler
+}}
+
+// Handle calls the handler registered for the tag of obj, if any. Values with unknown tags
+// are skipped and return false.
+func (obj *{0}) Handle() (bool, error) {{
+	handler, ok := {2}Handlers[obj.Tag]
+	if !ok {{
+		return false, nil
+	}}
+	return true, handler(obj.Content)
+}}"#,
+            name,
+            tags,
+            common::lowercase_first_letter(name),
+        )?;
+        Ok(())
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+    ) -> std::result::Result<crate::SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let mut files = crate::SourceFiles::new();
+        files.insert_bytes(
+            std::path::Path::new(&self.config.module_name).join("lib.go"),
+            content,
+        );
+        Ok(files)
     }
 }
 
@@ -988,23 +2254,18 @@ impl Installer {
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
         config: &CodeGeneratorConfig,
         registry: &Registry,
     ) -> std::result::Result<(), Self::Error> {
-        let dir_path = self.install_dir.join(&config.module_name);
-        std::fs::create_dir_all(&dir_path)?;
-        let source_path = dir_path.join("lib.go");
-        let mut file = std::fs::File::create(source_path)?;
-
         let mut generator = CodeGenerator::new(config);
         if let Some(path) = &self.serde_module_path {
             generator = generator.with_serde_module_path(path.clone());
         }
-        generator.output(&mut file, registry)?;
+        generator.generate(registry)?.write_to(&self.install_dir)?;
         Ok(())
     }
 
@@ -1022,4 +2283,24 @@ impl crate::SourceInstaller for Installer {
         self.runtime_installation_message("bcs");
         Ok(())
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.runtime_installation_message("postcard");
+        Ok(())
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.runtime_installation_message("borsh");
+        Ok(())
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.runtime_installation_message("scale");
+        Ok(())
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.runtime_installation_message("json");
+        Ok(())
+    }
 }
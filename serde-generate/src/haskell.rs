@@ -0,0 +1,1086 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{
+    common,
+    indent::{IndentConfig, IndentedWriter},
+    naming, BincodeOptions, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error,
+    IntEncoding, Result, SourceFiles, SourceGenerator, TrailingBytes,
+};
+use heck::CamelCase;
+use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    io::Write,
+    path::PathBuf,
+};
+
+/// Features supported by the Haskell code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Haskell",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: &[Encoding::Bincode, Encoding::Bcs],
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: false,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: false,
+    bincode_options: true,
+};
+
+/// Keywords of Haskell, which field names must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "case", "class", "data", "default", "deriving", "do", "else", "foreign", "if", "import", "in",
+    "infix", "infixl", "infixr", "instance", "let", "module", "newtype", "of", "then", "type",
+    "where",
+];
+
+/// Main configuration object for code-generation in Haskell.
+///
+/// Containers are generated as algebraic data types with `Serde.Serialize` and
+/// `Serde.Deserialize` instances. The constructor of each variant is prefixed by the name of
+/// its enum (e.g. `Choice_A`), as constructors share a single namespace in a module. Values are
+/// encoded with the functions of the runtime, e.g. `Bcs.serialize` and `Bcs.deserialize`.
+/// Non-default Bincode options are provided by `bincodeOptions`, together with the functions
+/// `bincodeSerialize` and `bincodeDeserialize`. Types always derive `Eq` and `Ord`; with
+/// comparisons, they also have a `Hashable` instance, which requires the `hashable` package.
+#[derive(Clone)]
+pub struct CodeGenerator<'a> {
+    /// Language-independent configuration.
+    config: &'a CodeGeneratorConfig,
+    /// Mapping from external type names to qualified names (e.g. "MyType" -> "MyOrg.MyModule.MyType").
+    /// Assumes that the corresponding modules are imported qualified (e.g. `import qualified MyOrg.MyModule`).
+    /// Derived from `config.external_definitions`.
+    external_qualified_names: HashMap<String, String>,
+}
+
+/// Shared state for the code generation of a Haskell source file.
+struct HaskellEmitter<'a, T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// Generator.
+    generator: &'a CodeGenerator<'a>,
+    /// Current namespace (e.g. vec!["my_org", "my_module", "MyType"])
+    current_namespace: Vec<String>,
+}
+
+impl<'a> CodeGenerator<'a> {
+    /// Create a Haskell code generator for the given config.
+    pub fn new(config: &'a CodeGeneratorConfig) -> Self {
+        let mut external_qualified_names = HashMap::new();
+        for (module_path, names) in &config.external_definitions {
+            for name in names {
+                external_qualified_names.insert(
+                    name.to_string(),
+                    format!("{}.{}", quote_module_name(module_path), name),
+                );
+            }
+        }
+        Self {
+            config,
+            external_qualified_names,
+        }
+    }
+
+    /// Write container definitions in Haskell.
+    pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).output(out, &registry);
+        }
+        Self::check_registry(registry)?;
+        let mut emitter = HaskellEmitter {
+            out: IndentedWriter::new(out, IndentConfig::Space(2)),
+            generator: self,
+            current_namespace: self
+                .config
+                .module_name
+                .split('.')
+                .map(String::from)
+                .collect(),
+        };
+        emitter.output_preamble(registry)?;
+        for (name, format) in registry {
+            emitter.output_container(name, format)?;
+        }
+        if self.config.serialization {
+            emitter.output_helpers(registry)?;
+            if self.has_custom_bincode_options() {
+                emitter.output_bincode_functions()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn has_custom_bincode_options(&self) -> bool {
+        self.config.encodings.contains(&Encoding::Bincode)
+            && self.config.bincode_options != BincodeOptions::default()
+    }
+
+    /// Types and constructors must start with an uppercase letter and fields with a
+    /// lowercase letter. Constructors must be unique in the module, and the fields of the
+    /// struct variants of an enum must agree on their types.
+    fn check_registry(registry: &Registry) -> Result<()> {
+        let mut constructors = BTreeSet::new();
+        for (name, format) in registry {
+            if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+                return Err(Error::unsupported(
+                    CAPABILITIES.language,
+                    "names of types that do not start with an uppercase letter",
+                )
+                .at(name));
+            }
+            let mut names = Vec::new();
+            let mut fields = Vec::new();
+            match format {
+                ContainerFormat::Enum(variants) => {
+                    let mut variant_fields = BTreeMap::new();
+                    for variant in variants.values() {
+                        names.push(format!("{}_{}", name, variant.name));
+                        if let VariantFormat::Struct(named_formats) = &variant.value {
+                            for field in named_formats {
+                                match variant_fields.insert(&field.name, &field.value) {
+                                    Some(previous) if previous != &field.value => {
+                                        return Err(Error::unsupported(
+                                            CAPABILITIES.language,
+                                            "fields of the same name with different types in the variants of an enum",
+                                        )
+                                        .at(&format!("{}.{}", name, field.name)));
+                                    }
+                                    _ => (),
+                                }
+                            }
+                            fields.extend(named_formats);
+                        }
+                    }
+                }
+                ContainerFormat::Struct(named_formats) => {
+                    names.push(name.clone());
+                    fields.extend(named_formats);
+                }
+                _ => names.push(name.clone()),
+            }
+            if names.into_iter().any(|name| !constructors.insert(name)) {
+                return Err(Error::unsupported(
+                    CAPABILITIES.language,
+                    "constructors with the same name",
+                )
+                .at(name));
+            }
+            if let Some(field) = fields.iter().find(|field| {
+                !field
+                    .name
+                    .starts_with(|c: char| c.is_lowercase() || c == '_')
+            }) {
+                return Err(Error::unsupported(
+                    CAPABILITIES.language,
+                    "names of fields that do not start with a lowercase letter",
+                )
+                .at(&format!("{}.{}", name, field.name)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The Haskell module of a module path such as "my_org.my_module", e.g. "MyOrg.MyModule".
+fn quote_module_name(module_path: &str) -> String {
+    module_path
+        .split('.')
+        .map(|name| name.to_camel_case())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn quote_bool(value: bool) -> &'static str {
+    if value {
+        "Prelude.True"
+    } else {
+        "Prelude.False"
+    }
+}
+
+/// The Haskell name of a field, avoiding keywords.
+fn quote_field_name(name: &str) -> String {
+    if RESERVED_WORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// The local variable bound to the value of a field. Primes cannot occur in the names of
+/// the registry, hence local variables never shadow top-level definitions.
+fn quote_local_name(name: &str) -> String {
+    format!("{}'", quote_field_name(name))
+}
+
+impl<'a, T> HaskellEmitter<'a, T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self, registry: &Registry) -> Result<()> {
+        let config = self.generator.config;
+        // Modules imported qualified, with their aliases.
+        let mut imports = BTreeMap::new();
+        imports.insert("Prelude".to_string(), None);
+        if config.serialization {
+            imports.insert("Serde".to_string(), None);
+            if self.generator.has_custom_bincode_options() {
+                imports.insert("Bincode".to_string(), None);
+                imports.insert("Data.ByteString".to_string(), Some("ByteString"));
+            }
+        }
+        if config.comparisons {
+            imports.insert("Data.Hashable".to_string(), Some("Hashable"));
+            imports.insert("GHC.Generics".to_string(), Some("Generics"));
+        }
+        for module in config.external_definitions.keys() {
+            imports.insert(quote_module_name(module), None);
+        }
+        for format in registry.values() {
+            let open_enum;
+            let format = match format {
+                ContainerFormat::OpenEnum(_) => {
+                    // Handlers of open enums are indexed by tag.
+                    imports.insert("Data.Map.Strict".to_string(), Some("Map"));
+                    open_enum = ContainerFormat::Struct(common::open_enum_fields());
+                    &open_enum
+                }
+                _ => format,
+            };
+            format
+                .visit(&mut |format| {
+                    use Format::*;
+                    let import = match format {
+                        Bytes => Some(("Data.ByteString", Some("ByteString"))),
+                        I8 | I16 | I32 | I64 => Some(("Data.Int", Some("Int"))),
+                        Map { .. } => Some(("Data.Map.Strict", Some("Map"))),
+                        Str => Some(("Data.Text", Some("Text"))),
+                        U8 | U16 | U32 | U64 => Some(("Data.Word", Some("Word"))),
+                        I128 | U128 => Some(("Serde", None)),
+                        _ => None,
+                    };
+                    if let Some((module, alias)) = import {
+                        imports.insert(module.to_string(), alias);
+                    }
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        if config.comparisons {
+            writeln!(self.out, "{{-# LANGUAGE DeriveGeneric #-}}")?;
+        }
+        writeln!(self.out, "{{-# LANGUAGE DuplicateRecordFields #-}}")?;
+        writeln!(
+            self.out,
+            "\nmodule {} where\n",
+            quote_module_name(&config.module_name)
+        )?;
+        for (module, alias) in imports {
+            match alias {
+                Some(alias) => writeln!(self.out, "import qualified {} as {}", module, alias)?,
+                None => writeln!(self.out, "import qualified {}", module)?,
+            }
+        }
+        if config.comparisons {
+            // Hashable instances of the 128-bit integers of the runtime.
+            writeln!(self.out, "import Comparisons ()")?;
+        }
+        Ok(())
+    }
+
+    fn quote_qualified_name(&self, name: &str) -> String {
+        match self.generator.external_qualified_names.get(name) {
+            Some(qname) => qname.clone(),
+            None => name.to_string(),
+        }
+    }
+
+    fn quote_type(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(x) => self.quote_qualified_name(x),
+            Unit => "()".into(),
+            Bool => "Prelude.Bool".into(),
+            I8 => "Int.Int8".into(),
+            I16 => "Int.Int16".into(),
+            I32 => "Int.Int32".into(),
+            I64 => "Int.Int64".into(),
+            I128 => "Serde.Int128".into(),
+            U8 => "Word.Word8".into(),
+            U16 => "Word.Word16".into(),
+            U32 => "Word.Word32".into(),
+            U64 => "Word.Word64".into(),
+            U128 => "Serde.Word128".into(),
+            F32 => "Prelude.Float".into(),
+            F64 => "Prelude.Double".into(),
+            Char => "Prelude.Char".into(),
+            Str => "Text.Text".into(),
+            Bytes => "ByteString.ByteString".into(),
+
+            Option(format) => format!("Prelude.Maybe {}", self.quote_type_argument(format)),
+            Seq(format) => format!("[{}]", self.quote_type(format)),
+            Map { key, value } => format!(
+                "Map.Map {} {}",
+                self.quote_type_argument(key),
+                self.quote_type_argument(value)
+            ),
+            // Tuples of size 1 are encoded as their content.
+            Tuple(formats) if formats.len() == 1 => self.quote_type(&formats[0]),
+            Tuple(formats) => format!("({})", self.quote_types(formats, ", ")),
+            TupleArray { content, size: _ } => format!("[{}]", self.quote_type(content)),
+
+            Variable(_) => panic!("unexpected value"),
+        }
+    }
+
+    /// The type of a constructor argument or of a type parameter.
+    fn quote_type_argument(&self, format: &Format) -> String {
+        match format {
+            Format::Option(_) | Format::Map { .. } => format!("({})", self.quote_type(format)),
+            Format::Tuple(formats) if formats.len() == 1 => self.quote_type_argument(&formats[0]),
+            _ => self.quote_type(format),
+        }
+    }
+
+    fn quote_types(&self, formats: &[Format], sep: &str) -> String {
+        formats
+            .iter()
+            .map(|f| self.quote_type(f))
+            .collect::<Vec<_>>()
+            .join(sep)
+    }
+
+    fn comment(&self, name: &str) -> Option<&'a String> {
+        let mut path = self.current_namespace.clone();
+        path.push(name.to_string());
+        self.generator.config.comments.get(&path)
+    }
+
+    /// The lines of the Haddock comment of the given item, if any.
+    fn comment_lines(&self, name: &str) -> Vec<String> {
+        match self.comment(name) {
+            Some(doc) => doc
+                .lines()
+                .enumerate()
+                .map(|(i, line)| match (i, line.is_empty()) {
+                    (0, _) => format!("-- | {}", line).trim_end().to_string(),
+                    (_, true) => "--".to_string(),
+                    (_, false) => format!("-- {}", line),
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn output_comment(&mut self, name: &str) -> std::io::Result<()> {
+        for line in self.comment_lines(name) {
+            writeln!(self.out, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    fn output_custom_code(&mut self) -> std::io::Result<()> {
+        if let Some(code) = self
+            .generator
+            .config
+            .custom_code
+            .get(&self.current_namespace)
+        {
+            writeln!(self.out, "\n{}", code)?;
+        }
+        Ok(())
+    }
+
+    /// Output the fields of a record, each one on its own line, followed by the closing brace.
+    fn output_record_fields(&mut self, fields: &[Named<Format>]) -> Result<()> {
+        for (i, field) in fields.iter().enumerate() {
+            let prefix = if i == 0 { "{ " } else { ", " };
+            let declaration = format!(
+                "{} :: {}",
+                quote_field_name(&field.name),
+                self.quote_type(&field.value)
+            );
+            let comment = self.comment_lines(&field.name);
+            if comment.is_empty() {
+                writeln!(self.out, "{}{}", prefix, declaration)?;
+            } else {
+                for (j, line) in comment.iter().enumerate() {
+                    let prefix = if j == 0 { prefix } else { "  " };
+                    writeln!(self.out, "{}{}", prefix, line)?;
+                }
+                writeln!(self.out, "  {}", declaration)?;
+            }
+        }
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// The constructor of a non-enum container or of an enum variant, with its arguments
+    /// unless they are named fields.
+    fn quote_constructor(&self, constructor: &str, fields: &Fields) -> String {
+        match fields {
+            Fields::Positional(formats) if !formats.is_empty() => format!(
+                "{} {}",
+                constructor,
+                formats
+                    .iter()
+                    .map(|f| self.quote_type_argument(f))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            _ => constructor.to_string(),
+        }
+    }
+
+    fn output_deriving(&mut self) -> Result<()> {
+        if self.generator.config.comparisons {
+            writeln!(
+                self.out,
+                "deriving (Prelude.Eq, Prelude.Ord, Prelude.Show, Generics.Generic)"
+            )?;
+        } else {
+            writeln!(self.out, "deriving (Prelude.Eq, Prelude.Ord, Prelude.Show)")?;
+        }
+        Ok(())
+    }
+
+    /// Hashing is consistent with the derived equality, by default through `Generics.Generic`.
+    fn output_hashable_instance(&mut self, name: &str) -> Result<()> {
+        if self.generator.config.comparisons {
+            writeln!(self.out, "\ninstance Hashable.Hashable {}", name)?;
+        }
+        Ok(())
+    }
+
+    fn output_struct_type(&mut self, name: &str, fields: &Fields) -> Result<()> {
+        let keyword = match fields {
+            Fields::Positional(formats) if formats.len() == 1 => "newtype",
+            _ => "data",
+        };
+        writeln!(
+            self.out,
+            "{} {} = {}",
+            keyword,
+            name,
+            self.quote_constructor(name, fields)
+        )?;
+        self.out.indent();
+        if let Fields::Named(fields) = fields {
+            if !fields.is_empty() {
+                self.current_namespace.push(name.to_string());
+                self.output_record_fields(fields)?;
+                self.current_namespace.pop();
+            }
+        }
+        self.output_deriving()?;
+        self.out.unindent();
+        self.output_hashable_instance(name)
+    }
+
+    fn output_enum_type(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        writeln!(self.out, "data {}", name)?;
+        self.out.indent();
+        self.current_namespace.push(name.to_string());
+        for (i, variant) in variants.values().enumerate() {
+            let prefix = if i == 0 { "= " } else { "| " };
+            let fields = Fields::of_variant(&variant.value);
+            let constructor =
+                self.quote_constructor(&format!("{}_{}", name, variant.name), &fields);
+            let comment = self.comment_lines(&variant.name);
+            if comment.is_empty() {
+                writeln!(self.out, "{}{}", prefix, constructor)?;
+            } else {
+                writeln!(self.out, "{}{}", prefix, comment[0])?;
+                for line in &comment[1..] {
+                    writeln!(self.out, "  {}", line)?;
+                }
+                writeln!(self.out, "  {}", constructor)?;
+            }
+            if let Fields::Named(fields) = &fields {
+                if !fields.is_empty() {
+                    self.out.indent();
+                    self.out.indent();
+                    self.current_namespace.push(variant.name.clone());
+                    self.output_record_fields(fields)?;
+                    self.current_namespace.pop();
+                    self.out.unindent();
+                    self.out.unindent();
+                }
+            }
+        }
+        self.current_namespace.pop();
+        self.output_deriving()?;
+        self.out.unindent();
+        self.output_hashable_instance(name)
+    }
+
+    /// The pattern matching the given constructor and binding its fields to local variables.
+    fn quote_pattern(constructor: &str, fields: &Fields) -> String {
+        let names = fields.local_names();
+        if names.is_empty() {
+            constructor.to_string()
+        } else {
+            format!("({} {})", constructor, names.join(" "))
+        }
+    }
+
+    /// Output the statements of a `do` block serializing the fields bound by `quote_pattern`.
+    fn output_serialize_fields(&mut self, fields: &Fields) -> Result<()> {
+        for (name, format) in fields.local_names().iter().zip(fields.formats()) {
+            writeln!(self.out, "{} {}", Self::quote_serialize(format), name)?;
+        }
+        Ok(())
+    }
+
+    /// Output the statements of a `do` block deserializing the fields of the given
+    /// constructor, then returning the value.
+    fn output_deserialize_fields(&mut self, constructor: &str, fields: &Fields) -> Result<()> {
+        let names = fields.local_names();
+        for (name, format) in names.iter().zip(fields.formats()) {
+            writeln!(self.out, "{} <- {}", name, Self::quote_deserialize(format))?;
+        }
+        writeln!(
+            self.out,
+            "Prelude.pure {}",
+            Self::quote_pattern(constructor, fields)
+        )?;
+        Ok(())
+    }
+
+    fn output_struct_instances(&mut self, name: &str, fields: &Fields) -> Result<()> {
+        let is_empty = fields.formats().is_empty();
+        writeln!(self.out, "\ninstance Serde.Serialize {} where", name)?;
+        self.out.indent();
+        if is_empty {
+            writeln!(
+                self.out,
+                "serialize {} = Serde.withContainerDepth (Prelude.pure ())",
+                name
+            )?;
+        } else {
+            writeln!(
+                self.out,
+                "serialize {} = Serde.withContainerDepth Prelude.$ do",
+                Self::quote_pattern(name, fields)
+            )?;
+            self.out.indent();
+            self.output_serialize_fields(fields)?;
+            self.out.unindent();
+        }
+        self.out.unindent();
+
+        writeln!(self.out, "\ninstance Serde.Deserialize {} where", name)?;
+        self.out.indent();
+        if is_empty {
+            writeln!(
+                self.out,
+                "deserialize = Serde.withContainerDepth (Prelude.pure {})",
+                name
+            )?;
+        } else {
+            writeln!(
+                self.out,
+                "deserialize = Serde.withContainerDepth Prelude.$ do"
+            )?;
+            self.out.indent();
+            self.output_deserialize_fields(name, fields)?;
+            self.out.unindent();
+        }
+        self.out.unindent();
+        Ok(())
+    }
+
+    fn output_enum_instances(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        writeln!(self.out, "\ninstance Serde.Serialize {} where", name)?;
+        self.out.indent();
+        for (index, variant) in variants {
+            let constructor = format!("{}_{}", name, variant.name);
+            let fields = Fields::of_variant(&variant.value);
+            if fields.formats().is_empty() {
+                writeln!(
+                    self.out,
+                    "serialize {} = Serde.withContainerDepth (Serde.serializeVariantIndex {})",
+                    constructor, index
+                )?;
+            } else {
+                writeln!(
+                    self.out,
+                    "serialize {} = Serde.withContainerDepth Prelude.$ do",
+                    Self::quote_pattern(&constructor, &fields)
+                )?;
+                self.out.indent();
+                writeln!(self.out, "Serde.serializeVariantIndex {}", index)?;
+                self.output_serialize_fields(&fields)?;
+                self.out.unindent();
+            }
+        }
+        self.out.unindent();
+
+        writeln!(self.out, "\ninstance Serde.Deserialize {} where", name)?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"deserialize = Serde.withContainerDepth Prelude.$ do
+  index' <- Serde.deserializeVariantIndex
+  case index' of"#
+        )?;
+        self.out.indent();
+        self.out.indent();
+        for (index, variant) in variants {
+            let constructor = format!("{}_{}", name, variant.name);
+            let fields = Fields::of_variant(&variant.value);
+            if fields.formats().is_empty() {
+                writeln!(self.out, "{} -> Prelude.pure {}", index, constructor)?;
+            } else {
+                writeln!(self.out, "{} -> do", index)?;
+                self.out.indent();
+                self.output_deserialize_fields(&constructor, &fields)?;
+                self.out.unindent();
+            }
+        }
+        writeln!(
+            self.out,
+            "_ -> Serde.unknownVariantIndex \"{}\" index'",
+            name
+        )?;
+        self.out.unindent();
+        self.out.unindent();
+        self.out.unindent();
+        Ok(())
+    }
+
+    /// Output the tags of the variants of an open enum known at generation time, and functions
+    /// to register handlers so that clients may decode the variants that they know and skip
+    /// others.
+    fn output_open_enum_handlers(
+        &mut self,
+        name: &str,
+        formats: &BTreeMap<String, Format>,
+    ) -> Result<()> {
+        let tags = formats
+            .keys()
+            .map(|tag| format!("Text.pack {:?}", tag))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            self.out,
+            r#"
+-- | The tags of the variants of '{0}' known when the code was generated.
+{1}Tags :: [Text.Text]
+{1}Tags = [{2}]
+
+-- | Handlers of the variants of '{0}', indexed by tag. Each handler receives the encoded
+-- content of a variant.
+type {0}Handlers a = Map.Map Text.Text (ByteString.ByteString -> a)
+
+-- | Register the handler of the variants with the given tag.
+register{0}Handler :: Text.Text -> (ByteString.ByteString -> a) -> {0}Handlers a -> {0}Handlers a
+register{0}Handler = Map.insert
+
+-- | Call the handler registered for the tag of a value, if any. Values with unknown tags are
+-- skipped and return 'Prelude.Nothing'.
+handle{0} :: {0}Handlers a -> {0} -> Prelude.Maybe a
+handle{0} handlers ({0} tag' content') = Prelude.fmap (Prelude.$ content') (Map.lookup tag' handlers)"#,
+            name,
+            common::lowercase_first_letter(name),
+            tags
+        )?;
+        Ok(())
+    }
+
+    fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
+        use ContainerFormat::*;
+        writeln!(self.out)?;
+        self.output_comment(name)?;
+        let fields = match format {
+            UnitStruct => Fields::Positional(Vec::new()),
+            NewTypeStruct(format) => Fields::Positional(vec![format.as_ref().clone()]),
+            TupleStruct(formats) => Fields::Positional(formats.clone()),
+            Struct(fields) => Fields::Named(fields.clone()),
+            OpenEnum(_) => Fields::Named(common::open_enum_fields()),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("Haskell", name)),
+            FlattenedStruct(_) => {
+                return Err(common::flattened_struct_not_supported("Haskell", name))
+            }
+            Enum(variants) => {
+                self.output_enum_type(name, variants)?;
+                if self.generator.config.serialization {
+                    self.output_enum_instances(name, variants)?;
+                }
+                self.current_namespace.push(name.to_string());
+                self.output_custom_code()?;
+                self.current_namespace.pop();
+                return Ok(());
+            }
+        };
+        self.output_struct_type(name, &fields)?;
+        if self.generator.config.serialization {
+            self.output_struct_instances(name, &fields)?;
+        }
+        if let OpenEnum(formats) = format {
+            self.output_open_enum_handlers(name, formats)?;
+        }
+        self.current_namespace.push(name.to_string());
+        self.output_custom_code()?;
+        self.current_namespace.pop();
+        Ok(())
+    }
+
+    /// Formats that are (de)serialized by generated helper functions rather than by the
+    /// instances of the runtime.
+    fn needs_helper(format: &Format) -> bool {
+        use Format::*;
+        match format {
+            Option(_) | Seq(_) | Map { .. } | TupleArray { .. } => true,
+            Tuple(formats) => formats.len() >= 2,
+            _ => false,
+        }
+    }
+
+    fn quote_serialize(format: &Format) -> String {
+        match format {
+            Format::Tuple(formats) if formats.len() == 1 => Self::quote_serialize(&formats[0]),
+            _ if Self::needs_helper(format) => {
+                format!("serialize_{}", common::mangle_type(format))
+            }
+            _ => "Serde.serialize".into(),
+        }
+    }
+
+    fn quote_deserialize(format: &Format) -> String {
+        match format {
+            Format::Tuple(formats) if formats.len() == 1 => Self::quote_deserialize(&formats[0]),
+            _ if Self::needs_helper(format) => {
+                format!("deserialize_{}", common::mangle_type(format))
+            }
+            _ => "Serde.deserialize".into(),
+        }
+    }
+
+    fn output_bincode_functions(&mut self) -> Result<()> {
+        let options = self.generator.config.bincode_options;
+        let limit = match options.limit {
+            Some(limit) => format!("Prelude.Just {}", limit.min(i64::MAX as u64)),
+            None => "Prelude.Nothing".to_string(),
+        };
+        writeln!(
+            self.out,
+            r#"
+-- | Options of the Bincode encoding of this module.
+bincodeOptions :: Bincode.Options
+bincodeOptions =
+  Bincode.Options
+    {{ Bincode.varintEncoding = {},
+      Bincode.bigEndian = {},
+      Bincode.limit = {},
+      Bincode.allowTrailingBytes = {}
+    }}
+
+bincodeSerialize :: (Serde.Serialize a) => a -> Prelude.Either Serde.SerializationError ByteString.ByteString
+bincodeSerialize = Bincode.serializeWith bincodeOptions
+
+bincodeDeserialize :: (Serde.Deserialize a) => ByteString.ByteString -> Prelude.Either Serde.DeserializationError a
+bincodeDeserialize = Bincode.deserializeWith bincodeOptions"#,
+            quote_bool(options.int_encoding == IntEncoding::Varint),
+            quote_bool(options.endianness == Endianness::Big),
+            limit,
+            quote_bool(options.trailing_bytes == TrailingBytes::Allow),
+        )?;
+        Ok(())
+    }
+
+    fn output_helpers(&mut self, registry: &Registry) -> Result<()> {
+        let mut subtypes = BTreeMap::new();
+        for format in registry.values() {
+            format
+                .visit(&mut |f| {
+                    if Self::needs_helper(f) {
+                        subtypes.insert(common::mangle_type(f), f.clone());
+                    }
+                    Ok(())
+                })
+                .unwrap();
+        }
+        for (mangled_name, subtype) in &subtypes {
+            self.output_serialization_helper(mangled_name, subtype)?;
+            self.output_deserialization_helper(mangled_name, subtype)?;
+        }
+        Ok(())
+    }
+
+    fn output_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(
+            self.out,
+            "\nserialize_{} :: {} -> Serde.Serializer ()",
+            name,
+            self.quote_type(format0)
+        )?;
+        match format0 {
+            Option(format) => writeln!(
+                self.out,
+                "serialize_{} = Serde.serializeOption {}",
+                name,
+                Self::quote_serialize(format)
+            )?,
+
+            Seq(format) => writeln!(
+                self.out,
+                "serialize_{} = Serde.serializeSeq {}",
+                name,
+                Self::quote_serialize(format)
+            )?,
+
+            Map { key, value } => writeln!(
+                self.out,
+                "serialize_{} = Serde.serializeMap {} {}",
+                name,
+                Self::quote_serialize(key),
+                Self::quote_serialize(value)
+            )?,
+
+            Tuple(formats) => {
+                let names = (0..formats.len())
+                    .map(|i| quote_local_name(&format!("field{}", i)))
+                    .collect::<Vec<_>>();
+                writeln!(self.out, "serialize_{} ({}) = do", name, names.join(", "))?;
+                self.out.indent();
+                for (name, format) in names.iter().zip(formats) {
+                    writeln!(self.out, "{} {}", Self::quote_serialize(format), name)?;
+                }
+                self.out.unindent();
+            }
+
+            TupleArray { content, size } => writeln!(
+                self.out,
+                "serialize_{} = Serde.serializeArray {} {}",
+                name,
+                size,
+                Self::quote_serialize(content)
+            )?,
+
+            _ => panic!("unexpected case"),
+        }
+        Ok(())
+    }
+
+    fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(
+            self.out,
+            "\ndeserialize_{} :: Serde.Deserializer {}",
+            name,
+            self.quote_type_argument(format0)
+        )?;
+        match format0 {
+            Option(format) => writeln!(
+                self.out,
+                "deserialize_{} = Serde.deserializeOption {}",
+                name,
+                Self::quote_deserialize(format)
+            )?,
+
+            Seq(format) => writeln!(
+                self.out,
+                "deserialize_{} = Serde.deserializeSeq {}",
+                name,
+                Self::quote_deserialize(format)
+            )?,
+
+            Map { key, value } => writeln!(
+                self.out,
+                "deserialize_{} = Serde.deserializeMap {} {}",
+                name,
+                Self::quote_deserialize(key),
+                Self::quote_deserialize(value)
+            )?,
+
+            Tuple(formats) => {
+                let names = (0..formats.len())
+                    .map(|i| quote_local_name(&format!("field{}", i)))
+                    .collect::<Vec<_>>();
+                writeln!(self.out, "deserialize_{} = do", name)?;
+                self.out.indent();
+                for (name, format) in names.iter().zip(formats) {
+                    writeln!(self.out, "{} <- {}", name, Self::quote_deserialize(format))?;
+                }
+                writeln!(self.out, "Prelude.pure ({})", names.join(", "))?;
+                self.out.unindent();
+            }
+
+            TupleArray { content, size } => writeln!(
+                self.out,
+                "deserialize_{} = Serde.deserializeArray {} {}",
+                name,
+                size,
+                Self::quote_deserialize(content)
+            )?,
+
+            _ => panic!("unexpected case"),
+        }
+        Ok(())
+    }
+}
+
+/// The arguments of a constructor.
+enum Fields {
+    Positional(Vec<Format>),
+    Named(Vec<Named<Format>>),
+}
+
+impl Fields {
+    fn of_variant(variant: &VariantFormat) -> Self {
+        use VariantFormat::*;
+        match variant {
+            Unit => Fields::Positional(Vec::new()),
+            NewType(format) => Fields::Positional(vec![format.as_ref().clone()]),
+            Tuple(formats) => Fields::Positional(formats.clone()),
+            Struct(fields) => Fields::Named(fields.clone()),
+            Variable(_) => panic!("incorrect value"),
+        }
+    }
+
+    fn formats(&self) -> Vec<&Format> {
+        match self {
+            Fields::Positional(formats) => formats.iter().collect(),
+            Fields::Named(fields) => fields.iter().map(|field| &field.value).collect(),
+        }
+    }
+
+    /// Local variables for the values of the fields: `value'` for newtypes, `field0'`,
+    /// `field1'`, .. for tuples, and the names of the fields followed by a prime otherwise.
+    fn local_names(&self) -> Vec<String> {
+        match self {
+            Fields::Positional(formats) if formats.len() == 1 => vec![quote_local_name("value")],
+            Fields::Positional(formats) => (0..formats.len())
+                .map(|i| quote_local_name(&format!("field{}", i)))
+                .collect(),
+            Fields::Named(fields) => fields
+                .iter()
+                .map(|field| quote_local_name(&field.name))
+                .collect(),
+        }
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(&self, registry: &Registry) -> std::result::Result<SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let path = quote_module_name(&self.config.module_name)
+            .split('.')
+            .collect::<PathBuf>()
+            .with_extension("hs");
+        let mut files = SourceFiles::new();
+        files.insert_bytes(path, content);
+        Ok(files)
+    }
+}
+
+/// Installer for generated source files in Haskell.
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+
+    fn install_runtime(&self, name: &str, content: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.install_dir)?;
+        let mut file = std::fs::File::create(self.install_dir.join(name))?;
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Error;
+
+    fn install_module(
+        &self,
+        config: &CodeGeneratorConfig,
+        registry: &Registry,
+    ) -> std::result::Result<(), Self::Error> {
+        let generator = CodeGenerator::new(config);
+        generator.generate(registry)?.write_to(&self.install_dir)?;
+        Ok(())
+    }
+
+    fn install_serde_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime("Serde.hs", include_str!("../runtime/haskell/Serde.hs"))?;
+        self.install_runtime(
+            "Comparisons.hs",
+            include_str!("../runtime/haskell/Comparisons.hs"),
+        )
+    }
+
+    fn install_bincode_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime("Bincode.hs", include_str!("../runtime/haskell/Bincode.hs"))
+    }
+
+    fn install_bcs_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime("Bcs.hs", include_str!("../runtime/haskell/Bcs.hs"))
+    }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the postcard encoding",
+        ))
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the borsh encoding",
+        ))
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the scale encoding",
+        ))
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the json encoding",
+        ))
+    }
+}
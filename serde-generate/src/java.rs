@@ -4,18 +4,101 @@
 use crate::{
     common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig, Encoding,
+    naming, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error, IntEncoding, Result,
+    SourceFiles, TrailingBytes,
 };
 use heck::CamelCase;
 use include_dir::include_dir as include_directory;
 use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
 use std::{
     collections::{BTreeMap, HashMap},
-    io::{Result, Write},
+    io::Write,
     path::PathBuf,
 };
 
+/// Features supported by the Java code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Java",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: crate::capabilities::ALL_ENCODINGS_AND_JSON,
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: true,
+    module_cycles: true,
+    native_types: true,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Reserved words of Java, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "record",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "var",
+    "void",
+    "volatile",
+    "while",
+    "yield",
+];
+
 /// Main configuration object for code-generation in Java.
+#[derive(Clone)]
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
     config: &'a CodeGeneratorConfig,
@@ -42,9 +125,6 @@ struct JavaEmitter<'a, T> {
 impl<'a> CodeGenerator<'a> {
     /// Create a Java code generator for the given config.
     pub fn new(config: &'a CodeGeneratorConfig) -> Self {
-        if config.c_style_enums {
-            panic!("Java does not support generating c-style enums");
-        }
         let mut external_qualified_names = HashMap::new();
         for (namespace, names) in &config.external_definitions {
             for name in names {
@@ -66,63 +146,92 @@ impl<'a> CodeGenerator<'a> {
         install_dir: std::path::PathBuf,
         registry: &Registry,
     ) -> Result<()> {
+        self.source_files(registry)?.write_to(&install_dir)?;
+        Ok(())
+    }
+
+    /// Generate class definitions in memory, in a subdirectory corresponding to the package name.
+    fn source_files(&self, registry: &Registry) -> Result<SourceFiles> {
+        CAPABILITIES.check(registry, self.config)?;
+        let registry = &common::remove_native_types(self.config, registry);
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).source_files(&registry);
+        }
         let current_namespace = self
             .config
             .module_name
             .split('.')
             .map(String::from)
             .collect::<Vec<_>>();
+        let dir_path = current_namespace.iter().collect::<std::path::PathBuf>();
 
-        let mut dir_path = install_dir;
-        for part in &current_namespace {
-            dir_path = dir_path.join(part);
-        }
-        std::fs::create_dir_all(&dir_path)?;
-
+        let mut files = SourceFiles::new();
         for (name, format) in registry {
-            self.write_container_class(&dir_path, current_namespace.clone(), name, format)?;
+            let content = self.write_container_class(current_namespace.clone(), name, format)?;
+            files.insert_bytes(dir_path.join(name.to_string() + ".java"), content);
         }
-        if self.config.serialization {
-            self.write_helper_class(&dir_path, current_namespace, registry)?;
+        if self.config.serialization
+            || self.config.comparisons
+            || self.config.generates_validation()
+        {
+            let content = self.write_helper_class(current_namespace, registry)?;
+            files.insert_bytes(dir_path.join("TraitHelpers.java"), content);
         }
-        Ok(())
+        Ok(files)
     }
 
     fn write_container_class(
         &self,
-        dir_path: &std::path::Path,
         current_namespace: Vec<String>,
         name: &str,
         format: &ContainerFormat,
-    ) -> Result<()> {
-        let mut file = std::fs::File::create(dir_path.join(name.to_string() + ".java"))?;
+    ) -> Result<Vec<u8>> {
+        let mut content = Vec::new();
         let mut emitter = JavaEmitter {
-            out: IndentedWriter::new(&mut file, IndentConfig::Space(4)),
+            out: IndentedWriter::new(&mut content, IndentConfig::Space(4)),
             generator: self,
             current_namespace,
             current_reserved_names: HashMap::new(),
         };
 
         emitter.output_preamble()?;
-        emitter.output_container(name, format)
+        emitter.output_container(name, format)?;
+        Ok(content)
     }
 
     fn write_helper_class(
         &self,
-        dir_path: &std::path::Path,
         current_namespace: Vec<String>,
         registry: &Registry,
-    ) -> Result<()> {
-        let mut file = std::fs::File::create(dir_path.join("TraitHelpers.java"))?;
+    ) -> Result<Vec<u8>> {
+        let mut content = Vec::new();
         let mut emitter = JavaEmitter {
-            out: IndentedWriter::new(&mut file, IndentConfig::Space(4)),
+            out: IndentedWriter::new(&mut content, IndentConfig::Space(4)),
             generator: self,
             current_namespace,
             current_reserved_names: HashMap::new(),
         };
 
         emitter.output_preamble()?;
-        emitter.output_trait_helpers(registry)
+        emitter.output_trait_helpers(registry)?;
+        Ok(content)
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(&self, registry: &Registry) -> std::result::Result<SourceFiles, Self::Error> {
+        self.source_files(registry)
     }
 }
 
@@ -190,7 +299,10 @@ where
     fn quote_type(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(x) => self.quote_qualified_name(x),
+            TypeName(x) => match self.generator.config.native_types.get(x) {
+                Some(native) => native.name.clone(),
+                None => self.quote_qualified_name(x),
+            },
             Unit => "com.novi.serde.Unit".into(),
             Bool => "Boolean".into(),
             I8 => "Byte".into(),
@@ -278,11 +390,26 @@ where
         let reserved_names = &[];
         self.enter_class("TraitHelpers", reserved_names);
         for (mangled_name, subtype) in &subtypes {
-            self.output_serialization_helper(mangled_name, subtype)?;
-            self.output_deserialization_helper(mangled_name, subtype)?;
+            if self.generator.config.serialization {
+                self.output_serialization_helper(mangled_name, subtype)?;
+                self.output_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.has_json() {
+                self.output_json_serialization_helper(mangled_name, subtype)?;
+                self.output_json_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.comparisons {
+                self.output_comparison_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.generates_validation()
+                && common::needs_validation(self.generator.config, subtype, true)
+            {
+                self.output_validation_helper(mangled_name, subtype)?;
+            }
         }
         self.leave_class(reserved_names);
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
     fn needs_helper(format: &Format) -> bool {
@@ -296,7 +423,10 @@ where
     fn quote_serialize_value(&self, value: &str, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(_) => format!("{}.serialize(serializer);", value),
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!("{}({}, serializer);", native.serializer, value),
+                None => format!("{}.serialize(serializer);", value),
+            },
             Unit => format!("serializer.serialize_unit({});", value),
             Bool => format!("serializer.serialize_bool({});", value),
             I8 => format!("serializer.serialize_i8({});", value),
@@ -326,10 +456,13 @@ where
     fn quote_deserialize(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(name) => format!(
-                "{}.deserialize(deserializer)",
-                self.quote_qualified_name(name)
-            ),
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!("{}(deserializer)", native.deserializer),
+                None => format!(
+                    "{}.deserialize(deserializer)",
+                    self.quote_qualified_name(name)
+                ),
+            },
             Unit => "deserializer.deserialize_unit()".to_string(),
             Bool => "deserializer.deserialize_bool()".to_string(),
             I8 => "deserializer.deserialize_i8()".to_string(),
@@ -444,7 +577,8 @@ for ({1} item : value) {{
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
     fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
@@ -555,190 +689,694 @@ return obj;
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
-    fn output_variant(
-        &mut self,
-        base: &str,
-        index: u32,
-        name: &str,
-        variant: &VariantFormat,
-    ) -> Result<()> {
-        use VariantFormat::*;
-        let fields = match variant {
-            Unit => Vec::new(),
-            NewType(format) => vec![Named {
-                name: "value".to_string(),
-                value: format.as_ref().clone(),
-            }],
-            Tuple(formats) => formats
-                .iter()
-                .enumerate()
-                .map(|(i, f)| Named {
-                    name: format!("field{}", i),
-                    value: f.clone(),
-                })
-                .collect(),
-            Struct(fields) => fields.clone(),
-            Variable(_) => panic!("incorrect value"),
-        };
-        self.output_struct_or_variant_container(Some(base), Some(index), name, &fields)
+    /// Whether to generate the JSON encoding, which reads and writes values as trees of
+    /// JSON values rather than through a `Serializer` or a `Deserializer`.
+    fn has_json(&self) -> bool {
+        self.generator.config.serialization
+            && self.generator.config.encodings.contains(&Encoding::Json)
     }
 
-    fn output_variants(
-        &mut self,
-        base: &str,
-        variants: &BTreeMap<u32, Named<VariantFormat>>,
-    ) -> Result<()> {
-        for (index, variant) in variants {
-            self.output_variant(base, *index, &variant.name, &variant.value)?;
+    fn quote_to_json(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) => format!("{}.toJson()", value),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "com.novi.json.Json.serialize_{}({})",
+                common::mangle_type(format),
+                value
+            ),
+            _ => format!(
+                "{}.toJson_{}({})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                value
+            ),
         }
-        Ok(())
     }
 
-    fn output_struct_or_variant_container(
-        &mut self,
-        variant_base: Option<&str>,
-        variant_index: Option<u32>,
-        name: &str,
-        fields: &[Named<Format>],
-    ) -> Result<()> {
-        // Beginning of class
-        writeln!(self.out)?;
-        if let Some(base) = variant_base {
-            self.output_comment(name)?;
-            writeln!(
-                self.out,
-                "public static final class {} extends {} {{",
-                name, base
-            )?;
-        } else {
-            self.output_comment(name)?;
-            writeln!(self.out, "public final class {} {{", name)?;
-        }
-        let reserved_names = &["Builder"];
-        self.enter_class(name, reserved_names);
-        // Fields
-        for field in fields {
-            self.output_comment(&field.name)?;
-            writeln!(
-                self.out,
-                "public final {} {};",
-                self.quote_type(&field.value),
-                field.name
-            )?;
-        }
-        if !fields.is_empty() {
-            writeln!(self.out)?;
+    fn quote_from_json(&self, json: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!("{}.fromJson({})", self.quote_qualified_name(name), json),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "com.novi.json.Json.deserialize_{}({})",
+                common::mangle_type(format),
+                json
+            ),
+            _ => format!(
+                "{}.fromJson_{}({})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                json
+            ),
         }
-        // Constructor.
-        writeln!(
+    }
+
+    fn output_json_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
             self.out,
-            "public {}({}) {{",
+            "static Object toJson_{}({} value) throws com.novi.serde.SerializationError {{",
             name,
-            fields
-                .iter()
-                .map(|f| format!("{} {}", self.quote_type(&f.value), &f.name))
-                .collect::<Vec<_>>()
-                .join(", ")
+            self.quote_type(format0)
         )?;
         self.out.indent();
-        for field in fields {
-            writeln!(
-                self.out,
-                "java.util.Objects.requireNonNull({0}, \"{0} must not be null\");",
-                &field.name
-            )?;
-        }
-        for field in fields {
-            writeln!(self.out, "this.{} = {};", &field.name, &field.name)?;
-        }
-        self.out.unindent();
-        writeln!(self.out, "}}")?;
-        // Serialize
-        if self.generator.config.serialization {
-            writeln!(
-                self.out,
-                "\npublic void serialize(com.novi.serde.Serializer serializer) throws com.novi.serde.SerializationError {{",
-            )?;
-            self.out.indent();
-            writeln!(self.out, "serializer.increase_container_depth();")?;
-            if let Some(index) = variant_index {
-                writeln!(self.out, "serializer.serialize_variant_index({});", index)?;
-            }
-            for field in fields {
-                writeln!(
+        match format0 {
+            Option(format) => {
+                write!(
                     self.out,
-                    "{}",
-                    self.quote_serialize_value(&field.name, &field.value)
+                    r#"
+if (value.isPresent()) {{
+    return {};
+}} else {{
+    return null;
+}}
+"#,
+                    self.quote_to_json("value.get()", format)
                 )?;
             }
-            writeln!(self.out, "serializer.decrease_container_depth();")?;
-            self.out.unindent();
-            writeln!(self.out, "}}")?;
 
-            if variant_index.is_none() {
-                for encoding in &self.generator.config.encodings {
-                    self.output_class_serialize_for_encoding(*encoding)?;
-                }
-            }
-        }
-        // Deserialize (struct) or Load (variant)
-        if self.generator.config.serialization {
-            if variant_index.is_none() {
-                writeln!(
+            Seq(format) => {
+                write!(
                     self.out,
-                    "\npublic static {} deserialize(com.novi.serde.Deserializer deserializer) throws com.novi.serde.DeserializationError {{",
-                    name,
+                    r#"
+java.util.List<Object> json = new java.util.ArrayList<Object>(value.size());
+for ({} item : value) {{
+    json.add({});
+}}
+return json;
+"#,
+                    self.quote_type(format),
+                    self.quote_to_json("item", format)
                 )?;
-            } else {
-                writeln!(
+            }
+
+            Map { key, value } => {
+                write!(
                     self.out,
-                    "\nstatic {} load(com.novi.serde.Deserializer deserializer) throws com.novi.serde.DeserializationError {{",
-                    name,
+                    r#"
+com.novi.json.JsonObject json = new com.novi.json.JsonObject();
+for (java.util.Map.Entry<{}, {}> entry : value.entrySet()) {{
+    json.put(com.novi.json.Json.key({}), {});
+}}
+return json;
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_to_json("entry.getKey()", key),
+                    self.quote_to_json("entry.getValue()", value)
                 )?;
             }
-            self.out.indent();
-            writeln!(self.out, "deserializer.increase_container_depth();")?;
-            writeln!(self.out, "Builder builder = new Builder();")?;
-            for field in fields {
+
+            Tuple(formats) => {
                 writeln!(
                     self.out,
-                    "builder.{} = {};",
-                    field.name,
-                    self.quote_deserialize(&field.value)
+                    "\njava.util.List<Object> json = new java.util.ArrayList<Object>({});",
+                    formats.len()
                 )?;
+                for (index, format) in formats.iter().enumerate() {
+                    let expr = format!("value.field{}", index);
+                    writeln!(self.out, "json.add({});", self.quote_to_json(&expr, format))?;
+                }
+                writeln!(self.out, "return json;")?;
             }
-            writeln!(self.out, "deserializer.decrease_container_depth();")?;
-            writeln!(self.out, "return builder.build();")?;
-            self.out.unindent();
-            writeln!(self.out, "}}")?;
 
-            if variant_index.is_none() {
-                for encoding in &self.generator.config.encodings {
-                    self.output_class_deserialize_for_encoding(name, *encoding)?;
-                }
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+if (value.size() != {0}) {{
+    throw new java.lang.IllegalArgumentException("Invalid length for fixed-size array: " + value.size() + " instead of "+ {0});
+}}
+java.util.List<Object> json = new java.util.ArrayList<Object>({0});
+for ({1} item : value) {{
+    json.add({2});
+}}
+return json;
+"#,
+                    size,
+                    self.quote_type(content),
+                    self.quote_to_json("item", content),
+                )?;
             }
+
+            _ => panic!("unexpected case"),
         }
-        // Equality
-        write!(self.out, "\npublic boolean equals(Object obj) {{")?;
-        self.out.indent();
-        writeln!(
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_json_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
             self.out,
-            r#"
-if (this == obj) return true;
-if (obj == null) return false;
-if (getClass() != obj.getClass()) return false;
-{0} other = ({0}) obj;"#,
+            "static {} fromJson_{}(Object json) throws com.novi.serde.DeserializationError {{",
+            self.quote_type(format0),
             name,
         )?;
-        for field in fields {
-            writeln!(
-                self.out,
-                "if (!java.util.Objects.equals(this.{0}, other.{0})) {{ return false; }}",
-                &field.name,
-            )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (json == null) {{
+    return java.util.Optional.empty();
+}} else {{
+    return java.util.Optional.of({});
+}}
+"#,
+                    self.quote_from_json("json", format),
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+java.util.List<Object> items = com.novi.json.Json.deserialize_array(json);
+java.util.List<{0}> obj = new java.util.ArrayList<{0}>(items.size());
+for (Object item : items) {{
+    obj.add({1});
+}}
+return obj;
+"#,
+                    self.quote_type(format),
+                    self.quote_from_json("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+com.novi.json.JsonObject entries = com.novi.json.Json.deserialize_object(json);
+java.util.Map<{0}, {1}> obj = new java.util.LinkedHashMap<{0}, {1}>();
+for (int i = 0; i < entries.size(); i++) {{
+    {0} key = {2};
+    {1} value = {3};
+    obj.put(key, value);
+}}
+return obj;
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_from_json("new com.novi.json.JsonKey(entries.key(i))", key),
+                    self.quote_from_json("entries.value(i)", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    r#"
+java.util.List<Object> items = com.novi.json.Json.deserialize_array(json, {});
+return new {}({}
+);
+"#,
+                    formats.len(),
+                    self.quote_type(format0),
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| format!(
+                            "\n    {}",
+                            self.quote_from_json(&format!("items.get({})", i), f)
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+java.util.List<Object> items = com.novi.json.Json.deserialize_array(json, {1});
+java.util.List<{0}> obj = new java.util.ArrayList<{0}>({1});
+for (Object item : items) {{
+    obj.add({2});
+}}
+return obj;
+"#,
+                    self.quote_type(content),
+                    size,
+                    self.quote_from_json("item", content)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// A statement validating `value`, assuming that `common::needs_validation` holds.
+    fn quote_validate(&self, value: &str, format: &Format) -> String {
+        match format {
+            Format::TypeName(_) => format!("{}.validate();", value),
+            _ => format!(
+                "{}.validate_{}({});",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn output_validation_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(
+            self.out,
+            "static void validate_{}({} value) {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        let needs_validation =
+            |format| common::needs_validation(self.generator.config, format, true);
+        match format0 {
+            Option(format) => {
+                writeln!(
+                    self.out,
+                    "if (value.isPresent()) {{\n    {}\n}}",
+                    self.quote_validate("value.get()", format)
+                )?;
+            }
+
+            Seq(format) => {
+                writeln!(
+                    self.out,
+                    "for ({} item : value) {{\n    {}\n}}",
+                    self.quote_type(format),
+                    self.quote_validate("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                writeln!(
+                    self.out,
+                    "for (java.util.Map.Entry<{}, {}> entry : value.entrySet()) {{",
+                    self.quote_type(key),
+                    self.quote_type(value)
+                )?;
+                if needs_validation(key) {
+                    writeln!(
+                        self.out,
+                        "    {}",
+                        self.quote_validate("entry.getKey()", key)
+                    )?;
+                }
+                if needs_validation(value) {
+                    writeln!(
+                        self.out,
+                        "    {}",
+                        self.quote_validate("entry.getValue()", value)
+                    )?;
+                }
+                writeln!(self.out, "}}")?;
+            }
+
+            Tuple(formats) => {
+                for (index, format) in formats.iter().enumerate() {
+                    if needs_validation(format) {
+                        let expr = format!("value.field{}", index);
+                        writeln!(self.out, "{}", self.quote_validate(&expr, format))?;
+                    }
+                }
+            }
+
+            TupleArray { content, size } => {
+                writeln!(
+                    self.out,
+                    r#"if (value.size() != {0}) {{
+    throw new java.lang.IllegalArgumentException("Invalid length for fixed-size array: " + value.size() + " instead of " + {0});
+}}"#,
+                    size
+                )?;
+                if needs_validation(content) {
+                    writeln!(
+                        self.out,
+                        "for ({} item : value) {{\n    {}\n}}",
+                        self.quote_type(content),
+                        self.quote_validate("item", content)
+                    )?;
+                }
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// Check the constraints of the registry that Java types do not enforce.
+    fn output_validate(&mut self, fields: &[Named<Format>]) -> Result<()> {
+        writeln!(self.out, "\npublic void validate() {{")?;
+        self.out.indent();
+        for field in fields {
+            if common::needs_validation(self.generator.config, &field.value, true) {
+                writeln!(
+                    self.out,
+                    "{}",
+                    self.quote_validate(&field.name, &field.value)
+                )?;
+            }
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// An `int` expression comparing the values `a` and `b` in the order of Rust values.
+    fn quote_compare(&self, a: &str, b: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) | I128 | U128 | Bytes => format!("{}.compareTo({})", a, b),
+            Unit => "0".to_string(),
+            Bool => format!("Boolean.compare({}, {})", a, b),
+            I8 => format!("Byte.compare({}, {})", a, b),
+            I16 => format!("Short.compare({}, {})", a, b),
+            I32 => format!("Integer.compare({}, {})", a, b),
+            I64 => format!("Long.compare({}, {})", a, b),
+            U8 => format!(
+                "Integer.compare(Byte.toUnsignedInt({}), Byte.toUnsignedInt({}))",
+                a, b
+            ),
+            U16 => format!(
+                "Integer.compare(Short.toUnsignedInt({}), Short.toUnsignedInt({}))",
+                a, b
+            ),
+            U32 => format!("Integer.compareUnsigned({}, {})", a, b),
+            U64 => format!("Long.compareUnsigned({}, {})", a, b),
+            Char => format!("Character.compare({}, {})", a, b),
+            Str => format!("com.novi.serde.Comparisons.compareStrings({}, {})", a, b),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            _ => format!(
+                "{}.compare_{}({}, {})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                a,
+                b
+            ),
+        }
+    }
+
+    fn output_comparison_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "static int compare_{0}({1} a, {1} b) {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (!a.isPresent() || !b.isPresent()) {{
+    return Boolean.compare(a.isPresent(), b.isPresent());
+}}
+return {};
+"#,
+                    self.quote_compare("a.get()", "b.get()", format)
+                )?;
+            }
+
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                write!(
+                    self.out,
+                    r#"
+java.util.Iterator<{0}> i = a.iterator();
+java.util.Iterator<{0}> j = b.iterator();
+while (i.hasNext() && j.hasNext()) {{
+    int result = {1};
+    if (result != 0) return result;
+}}
+return Boolean.compare(i.hasNext(), j.hasNext());
+"#,
+                    self.quote_type(format),
+                    self.quote_compare("i.next()", "j.next()", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+java.util.List<java.util.Map.Entry<{0}, {1}>> x = new java.util.ArrayList<>(a.entrySet());
+java.util.List<java.util.Map.Entry<{0}, {1}>> y = new java.util.ArrayList<>(b.entrySet());
+x.sort((e, f) -> {2});
+y.sort((e, f) -> {2});
+for (int k = 0; k < x.size() && k < y.size(); k++) {{
+    int result = {3};
+    if (result != 0) return result;
+    result = {4};
+    if (result != 0) return result;
+}}
+return Integer.compare(x.size(), y.size());
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_compare("e.getKey()", "f.getKey()", key),
+                    self.quote_compare("x.get(k).getKey()", "y.get(k).getKey()", key),
+                    self.quote_compare("x.get(k).getValue()", "y.get(k).getValue()", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out, "\nint result;")?;
+                for (index, format) in formats.iter().enumerate() {
+                    let a = format!("a.field{}", index);
+                    let b = format!("b.field{}", index);
+                    writeln!(
+                        self.out,
+                        "if ((result = {}) != 0) return result;",
+                        self.quote_compare(&a, &b, format)
+                    )?;
+                }
+                writeln!(self.out, "return 0;")?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_variant(
+        &mut self,
+        base: &str,
+        index: u32,
+        name: &str,
+        variant: &VariantFormat,
+    ) -> Result<()> {
+        use VariantFormat::*;
+        let fields = match variant {
+            Unit => Vec::new(),
+            NewType(format) => vec![Named {
+                name: "value".to_string(),
+                value: format.as_ref().clone(),
+            }],
+            Tuple(formats) => formats
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Named {
+                    name: format!("field{}", i),
+                    value: f.clone(),
+                })
+                .collect(),
+            Struct(fields) => fields.clone(),
+            Variable(_) => panic!("incorrect value"),
+        };
+        let shape = common::JsonShape::of_variant(variant);
+        self.output_struct_or_variant_container(Some(base), Some(index), name, shape, &fields)
+    }
+
+    fn output_variants(
+        &mut self,
+        base: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        for (index, variant) in variants {
+            self.output_variant(base, *index, &variant.name, &variant.value)?;
+        }
+        Ok(())
+    }
+
+    fn output_struct_or_variant_container(
+        &mut self,
+        variant_base: Option<&str>,
+        variant_index: Option<u32>,
+        name: &str,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        // Beginning of class
+        writeln!(self.out)?;
+        if let Some(base) = variant_base {
+            self.output_comment(name)?;
+            writeln!(
+                self.out,
+                "public static final class {} extends {} {{",
+                name, base
+            )?;
+        } else if self.generator.config.comparisons {
+            self.output_comment(name)?;
+            writeln!(
+                self.out,
+                "public final class {0} implements Comparable<{0}> {{",
+                name
+            )?;
+        } else {
+            self.output_comment(name)?;
+            writeln!(self.out, "public final class {} {{", name)?;
+        }
+        let reserved_names = &["Builder"];
+        self.enter_class(name, reserved_names);
+        // Fields
+        for field in fields {
+            self.output_comment(&field.name)?;
+            writeln!(
+                self.out,
+                "public final {} {};",
+                self.quote_type(&field.value),
+                field.name
+            )?;
+        }
+        if !fields.is_empty() {
+            writeln!(self.out)?;
+        }
+        // Constructor.
+        writeln!(
+            self.out,
+            "public {}({}) {{",
+            name,
+            fields
+                .iter()
+                .map(|f| format!("{} {}", self.quote_type(&f.value), &f.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        self.out.indent();
+        for field in fields {
+            writeln!(
+                self.out,
+                "java.util.Objects.requireNonNull({0}, \"{0} must not be null\");",
+                &field.name
+            )?;
+        }
+        for field in fields {
+            writeln!(self.out, "this.{} = {};", &field.name, &field.name)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        // Serialize
+        if self.generator.config.serialization {
+            writeln!(
+                self.out,
+                "\npublic void serialize(com.novi.serde.Serializer serializer) throws com.novi.serde.SerializationError {{",
+            )?;
+            self.out.indent();
+            writeln!(self.out, "serializer.increase_container_depth();")?;
+            if let Some(index) = variant_index {
+                writeln!(self.out, "serializer.serialize_variant_index({});", index)?;
+            }
+            for field in fields {
+                writeln!(
+                    self.out,
+                    "{}",
+                    self.quote_serialize_value(&field.name, &field.value)
+                )?;
+            }
+            writeln!(self.out, "serializer.decrease_container_depth();")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            if variant_index.is_none() {
+                for encoding in &self.generator.config.encodings {
+                    self.output_class_serialize_for_encoding(*encoding)?;
+                }
+            }
+        }
+        // Deserialize (struct) or Load (variant)
+        if self.generator.config.serialization {
+            if variant_index.is_none() {
+                writeln!(
+                    self.out,
+                    "\npublic static {} deserialize(com.novi.serde.Deserializer deserializer) throws com.novi.serde.DeserializationError {{",
+                    name,
+                )?;
+            } else {
+                writeln!(
+                    self.out,
+                    "\nstatic {} load(com.novi.serde.Deserializer deserializer) throws com.novi.serde.DeserializationError {{",
+                    name,
+                )?;
+            }
+            self.out.indent();
+            writeln!(self.out, "deserializer.increase_container_depth();")?;
+            writeln!(self.out, "Builder builder = new Builder();")?;
+            for field in fields {
+                writeln!(
+                    self.out,
+                    "builder.{} = {};",
+                    field.name,
+                    self.quote_deserialize(&field.value)
+                )?;
+            }
+            writeln!(self.out, "deserializer.decrease_container_depth();")?;
+            writeln!(self.out, "return builder.build();")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            if variant_index.is_none() {
+                for encoding in &self.generator.config.encodings {
+                    self.output_class_deserialize_for_encoding(name, *encoding)?;
+                }
+            }
+        }
+        // JSON conversions
+        if self.has_json() {
+            self.output_json_conversions(variant_base, name, shape, fields)?;
+        }
+        // Equality
+        write!(self.out, "\npublic boolean equals(Object obj) {{")?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"
+if (this == obj) return true;
+if (obj == null) return false;
+if (getClass() != obj.getClass()) return false;
+{0} other = ({0}) obj;"#,
+            name,
+        )?;
+        for field in fields {
+            writeln!(
+                self.out,
+                "if (!java.util.Objects.equals(this.{0}, other.{0})) {{ return false; }}",
+                &field.name,
+            )?;
         }
         writeln!(self.out, "return true;")?;
         self.out.unindent();
@@ -757,13 +1395,208 @@ if (getClass() != obj.getClass()) return false;
         writeln!(self.out, "return value;")?;
         self.out.unindent();
         writeln!(self.out, "}}")?;
+        // Ordering
+        if self.generator.config.comparisons {
+            self.output_compare_to(variant_base, variant_index, name, fields)?;
+        }
+        // Validation
+        if self.generator.config.generates_validation() {
+            self.output_validate(fields)?;
+        }
         // Builder
         self.output_struct_or_variant_container_builder(name, fields)?;
         // Custom code
         self.output_custom_code()?;
         // End of class
         self.leave_class(reserved_names);
-        writeln!(self.out, "}}")
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_json_conversions(
+        &mut self,
+        variant_base: Option<&str>,
+        name: &str,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        use common::JsonShape;
+        // Serialization
+        writeln!(
+            self.out,
+            "\npublic Object toJson() throws com.novi.serde.SerializationError {{"
+        )?;
+        self.out.indent();
+        let content = match shape {
+            JsonShape::Unit => "null".to_string(),
+            JsonShape::NewType => {
+                self.quote_to_json(&format!("this.{}", fields[0].name), &fields[0].value)
+            }
+            JsonShape::Tuple => {
+                writeln!(
+                    self.out,
+                    "java.util.List<Object> json = new java.util.ArrayList<Object>({});",
+                    fields.len()
+                )?;
+                for field in fields {
+                    writeln!(
+                        self.out,
+                        "json.add({});",
+                        self.quote_to_json(&format!("this.{}", field.name), &field.value)
+                    )?;
+                }
+                "json".to_string()
+            }
+            JsonShape::Struct => {
+                writeln!(
+                    self.out,
+                    "com.novi.json.JsonObject json = new com.novi.json.JsonObject();"
+                )?;
+                for field in fields {
+                    writeln!(
+                        self.out,
+                        "json.put(\"{}\", {});",
+                        field.name,
+                        self.quote_to_json(&format!("this.{}", field.name), &field.value)
+                    )?;
+                }
+                "json".to_string()
+            }
+        };
+        match variant_base {
+            Some(_) if shape == JsonShape::Unit => writeln!(self.out, "return \"{}\";", name)?,
+            Some(_) => writeln!(
+                self.out,
+                "return com.novi.json.Json.serialize_variant(\"{}\", {});",
+                name, content
+            )?,
+            None => writeln!(self.out, "return {};", content)?,
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        // Deserialization
+        if variant_base.is_none() {
+            writeln!(
+                self.out,
+                "\npublic static {} fromJson(Object json) throws com.novi.serde.DeserializationError {{",
+                name,
+            )?;
+            self.out.indent();
+            if shape == JsonShape::Unit {
+                writeln!(self.out, "com.novi.json.Json.deserialize_unit(json);")?;
+            }
+        } else {
+            writeln!(
+                self.out,
+                "\nstatic {} loadJson(com.novi.json.JsonVariant variant) throws com.novi.serde.DeserializationError {{",
+                name,
+            )?;
+            self.out.indent();
+            if shape == JsonShape::Unit {
+                writeln!(self.out, "variant.unit();")?;
+            } else {
+                writeln!(self.out, "Object json = variant.content();")?;
+            }
+        }
+        writeln!(self.out, "Builder builder = new Builder();")?;
+        match shape {
+            JsonShape::Unit => (),
+            JsonShape::NewType => writeln!(
+                self.out,
+                "builder.{} = {};",
+                fields[0].name,
+                self.quote_from_json("json", &fields[0].value)
+            )?,
+            JsonShape::Tuple => {
+                writeln!(
+                    self.out,
+                    "java.util.List<Object> items = com.novi.json.Json.deserialize_array(json, {});",
+                    fields.len()
+                )?;
+                for (index, field) in fields.iter().enumerate() {
+                    writeln!(
+                        self.out,
+                        "builder.{} = {};",
+                        field.name,
+                        self.quote_from_json(&format!("items.get({})", index), &field.value)
+                    )?;
+                }
+            }
+            JsonShape::Struct => {
+                writeln!(
+                    self.out,
+                    "com.novi.json.JsonObject object = com.novi.json.Json.deserialize_object(json);"
+                )?;
+                for field in fields {
+                    // Like Serde, missing optional fields are read as `None`.
+                    let getter = match field.value {
+                        Format::Option(_) => "optional_field",
+                        _ => "field",
+                    };
+                    writeln!(
+                        self.out,
+                        "builder.{} = {};",
+                        field.name,
+                        self.quote_from_json(
+                            &format!("object.{}(\"{}\")", getter, field.name),
+                            &field.value
+                        )
+                    )?;
+                }
+            }
+        }
+        writeln!(self.out, "return builder.build();")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_compare_to(
+        &mut self,
+        variant_base: Option<&str>,
+        variant_index: Option<u32>,
+        name: &str,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        if let Some(index) = variant_index {
+            writeln!(self.out, "\nint variantIndex() {{ return {}; }}", index)?;
+        }
+        match variant_base {
+            Some(base) => {
+                write!(self.out, "\npublic int compareTo({} obj) {{", base)?;
+                self.out.indent();
+                writeln!(
+                    self.out,
+                    r#"
+if (getClass() != obj.getClass()) {{
+    return Integer.compare(variantIndex(), obj.variantIndex());
+}}"#
+                )?;
+                if !fields.is_empty() {
+                    writeln!(self.out, "{0} other = ({0}) obj;", name)?;
+                }
+            }
+            None => {
+                writeln!(self.out, "\npublic int compareTo({} other) {{", name)?;
+                self.out.indent();
+            }
+        }
+        if !fields.is_empty() {
+            writeln!(self.out, "int result;")?;
+        }
+        for field in fields {
+            let a = format!("this.{}", field.name);
+            let b = format!("other.{}", field.name);
+            writeln!(
+                self.out,
+                "if ((result = {}) != 0) return result;",
+                self.quote_compare(&a, &b, &field.value)
+            )?;
+        }
+        writeln!(self.out, "return 0;")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
     }
 
     fn output_struct_or_variant_container_builder(
@@ -806,7 +1639,8 @@ if (getClass() != obj.getClass()) return false;
         self.output_custom_code()?;
         // End of class
         self.leave_class(reserved_names);
-        writeln!(self.out, "}}")
+        writeln!(self.out, "}}")?;
+        Ok(())
     }
 
     fn output_enum_container(
@@ -816,12 +1650,26 @@ if (getClass() != obj.getClass()) return false;
     ) -> Result<()> {
         writeln!(self.out)?;
         self.output_comment(name)?;
-        writeln!(self.out, "public abstract class {} {{", name)?;
+        if self.generator.config.comparisons {
+            writeln!(
+                self.out,
+                "public abstract class {0} implements Comparable<{0}> {{",
+                name
+            )?;
+        } else {
+            writeln!(self.out, "public abstract class {} {{", name)?;
+        }
         let reserved_names = variants
             .values()
             .map(|v| v.name.as_str())
             .collect::<Vec<_>>();
         self.enter_class(name, &reserved_names);
+        if self.generator.config.comparisons {
+            writeln!(self.out, "\nabstract int variantIndex();")?;
+        }
+        if self.generator.config.generates_validation() {
+            writeln!(self.out, "\nabstract public void validate();")?;
+        }
         if self.generator.config.serialization {
             writeln!(
                 self.out,
@@ -862,24 +1710,91 @@ switch (index) {{"#,
                 self.output_class_deserialize_for_encoding(name, *encoding)?;
             }
         }
+        if self.has_json() {
+            writeln!(
+                self.out,
+                "\nabstract public Object toJson() throws com.novi.serde.SerializationError;"
+            )?;
+            write!(
+                self.out,
+                r#"
+public static {} fromJson(Object json) throws com.novi.serde.DeserializationError {{
+    com.novi.json.JsonVariant variant = com.novi.json.Json.deserialize_variant(json);
+    switch (variant.name) {{"#,
+                name
+            )?;
+            self.out.indent();
+            self.out.indent();
+            writeln!(self.out)?;
+            for variant in variants.values() {
+                writeln!(
+                    self.out,
+                    "case \"{0}\": return {0}.loadJson(variant);",
+                    variant.name,
+                )?;
+            }
+            writeln!(
+                self.out,
+                "default: throw new com.novi.serde.DeserializationError(\"Unknown variant for {}: \" + variant.name);",
+                name,
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+        }
 
         self.output_variants(name, variants)?;
         self.leave_class(&reserved_names);
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
     fn output_class_serialize_for_encoding(&mut self, encoding: Encoding) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+public String jsonSerialize() throws com.novi.serde.SerializationError {{{}
+    return com.novi.json.Json.stringify(toJson());
+}}"#,
+                if self.generator.config.validate_on_serialize {
+                    "\n    validate();"
+                } else {
+                    ""
+                },
+            )?;
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(encoding);
         writeln!(
             self.out,
             r#"
-public byte[] {0}Serialize() throws com.novi.serde.SerializationError {{
-    com.novi.serde.Serializer serializer = new com.novi.{0}.{1}Serializer();
-    serialize(serializer);
+public byte[] {0}Serialize() throws com.novi.serde.SerializationError {{{2}
+    com.novi.serde.Serializer serializer = new com.novi.{0}.{1}Serializer({3});
+    serialize(serializer);{4}
     return serializer.get_bytes();
 }}"#,
             encoding.name(),
-            encoding.name().to_camel_case()
-        )
+            encoding.name().to_camel_case(),
+            if self.generator.config.validate_on_serialize {
+                "\n    validate();"
+            } else {
+                ""
+            },
+            common::bincode_runtime_arguments(&options),
+            match options.limit {
+                Some(limit) => format!(
+                    r#"
+    if (serializer.get_buffer_offset() > {}L) {{
+         throw new com.novi.serde.SerializationError("Exceeded the size limit of the encoding");
+    }}"#,
+                    limit
+                ),
+                None => String::new(),
+            }
+        )?;
+        Ok(())
     }
 
     fn output_class_deserialize_for_encoding(
@@ -887,6 +1802,40 @@ public byte[] {0}Serialize() throws com.novi.serde.SerializationError {{
         name: &str,
         encoding: Encoding,
     ) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+public static {} jsonDeserialize(String input) throws com.novi.serde.DeserializationError {{
+    return fromJson(com.novi.json.Json.parse(input));
+}}"#,
+                name,
+            )?;
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(encoding);
+        let checks = match options.trailing_bytes {
+            TrailingBytes::Reject => {
+                r#"
+    if (deserializer.get_buffer_offset() < input.length) {
+         throw new com.novi.serde.DeserializationError("Some input bytes were not read");
+    }"#
+            }
+            TrailingBytes::Allow => "",
+        };
+        // The runtime enforces the size limit while reading.
+        let arguments = match options.limit {
+            Some(limit) => format!(
+                ", {}, {}, {}L",
+                options.int_encoding == IntEncoding::Varint,
+                options.endianness == Endianness::Big,
+                limit
+            ),
+            None if options.is_custom_encoding() => {
+                format!(", {}", common::bincode_runtime_arguments(&options))
+            }
+            None => String::new(),
+        };
         writeln!(
             self.out,
             r#"
@@ -894,17 +1843,17 @@ public static {0} {1}Deserialize(byte[] input) throws com.novi.serde.Deserializa
     if (input == null) {{
          throw new com.novi.serde.DeserializationError("Cannot deserialize null array");
     }}
-    com.novi.serde.Deserializer deserializer = new com.novi.{1}.{2}Deserializer(input);
-    {0} value = deserialize(deserializer);
-    if (deserializer.get_buffer_offset() < input.length) {{
-         throw new com.novi.serde.DeserializationError("Some input bytes were not read");
-    }}
+    com.novi.serde.Deserializer deserializer = new com.novi.{1}.{2}Deserializer(input{3});
+    {0} value = deserialize(deserializer);{4}
     return value;
 }}"#,
             name,
             encoding.name(),
-            encoding.name().to_camel_case()
-        )
+            encoding.name().to_camel_case(),
+            arguments,
+            checks,
+        )?;
+        Ok(())
     }
 
     fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
@@ -924,12 +1873,16 @@ public static {0} {1}Deserialize(byte[] input) throws com.novi.serde.Deserializa
                 })
                 .collect::<Vec<_>>(),
             Struct(fields) => fields.clone(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("Java", name)),
+            FlattenedStruct(_) => return Err(common::flattened_struct_not_supported("Java", name)),
             Enum(variants) => {
                 self.output_enum_container(name, variants)?;
                 return Ok(());
             }
         };
-        self.output_struct_or_variant_container(None, None, name, &fields)
+        let shape = common::JsonShape::of_container(format);
+        self.output_struct_or_variant_container(None, None, name, shape, &fields)
     }
 }
 
@@ -943,11 +1896,7 @@ impl Installer {
         Installer { install_dir }
     }
 
-    fn install_runtime(
-        &self,
-        source_dir: include_dir::Dir,
-        path: &str,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    fn install_runtime(&self, source_dir: include_dir::Dir, path: &str) -> Result<()> {
         let dir_path = self.install_dir.join(path);
         std::fs::create_dir_all(&dir_path)?;
         for entry in source_dir.files() {
@@ -959,7 +1908,7 @@ impl Installer {
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
@@ -991,4 +1940,32 @@ impl crate::SourceInstaller for Installer {
             "com/novi/bcs",
         )
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(
+            include_directory!("runtime/java/com/novi/postcard"),
+            "com/novi/postcard",
+        )
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(
+            include_directory!("runtime/java/com/novi/borsh"),
+            "com/novi/borsh",
+        )
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(
+            include_directory!("runtime/java/com/novi/scale"),
+            "com/novi/scale",
+        )
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(
+            include_directory!("runtime/java/com/novi/json"),
+            "com/novi/json",
+        )
+    }
 }
@@ -0,0 +1,1515 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{
+    common,
+    indent::{IndentConfig, IndentedWriter},
+    naming, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error, IntEncoding, Result,
+    SourceFiles, TrailingBytes,
+};
+use heck::CamelCase;
+use include_dir::include_dir as include_directory;
+use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    io::Write,
+    path::PathBuf,
+};
+
+/// Features supported by the Kotlin code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Kotlin",
+    c_style_enums: true,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: &[Encoding::Bincode, Encoding::Bcs],
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: false,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Hard keywords of Kotlin, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Names of the components of `Pair`, `Triple`, and the tuples of the runtime.
+const TUPLE_FIELDS: &[&str] = &["first", "second", "third", "fourth", "fifth", "sixth"];
+
+/// Main configuration object for code-generation in Kotlin.
+#[derive(Clone)]
+pub struct CodeGenerator<'a> {
+    /// Language-independent configuration.
+    config: &'a CodeGeneratorConfig,
+    /// Mapping from external type names to fully-qualified class names (e.g. "MyClass" -> "com.my_org.my_package.MyClass").
+    /// Derived from `config.external_definitions`.
+    external_qualified_names: HashMap<String, String>,
+}
+
+/// Shared state for the code generation of a Kotlin source file.
+struct KotlinEmitter<'a, T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// Generator.
+    generator: &'a CodeGenerator<'a>,
+    /// Names of the types of the registry and of the external definitions, which shadow the
+    /// types of the standard library with the same name.
+    type_names: &'a BTreeSet<String>,
+    /// Current namespace (e.g. vec!["com", "my_org", "my_package", "MyClass"])
+    current_namespace: Vec<String>,
+    /// Current (non-qualified) generated class names that could clash with names in the registry
+    /// (e.g. variant classes).
+    /// * We count multiplicities to allow inplace backtracking.
+    /// * Names in the registry are assumed to never clash.
+    current_reserved_names: HashMap<String, usize>,
+}
+
+impl<'a> CodeGenerator<'a> {
+    /// Create a Kotlin code generator for the given config.
+    pub fn new(config: &'a CodeGeneratorConfig) -> Self {
+        let mut external_qualified_names = HashMap::new();
+        for (namespace, names) in &config.external_definitions {
+            for name in names {
+                external_qualified_names
+                    .insert(name.to_string(), format!("{}.{}", namespace, name));
+            }
+        }
+        Self {
+            config,
+            external_qualified_names,
+        }
+    }
+
+    /// Output class definitions for `registry` in separate source files.
+    /// Source files will be created in a subdirectory of `install_dir` corresponding to the given
+    /// package name (if any, otherwise `install_dir` it self).
+    pub fn write_source_files(
+        &self,
+        install_dir: std::path::PathBuf,
+        registry: &Registry,
+    ) -> Result<()> {
+        self.source_files(registry)?.write_to(&install_dir)?;
+        Ok(())
+    }
+
+    /// Generate class definitions in memory, in a subdirectory corresponding to the package name.
+    fn source_files(&self, registry: &Registry) -> Result<SourceFiles> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).source_files(&registry);
+        }
+        for (name, format) in registry {
+            Self::check_formats(name, format)?;
+        }
+        let current_namespace = self
+            .config
+            .module_name
+            .split('.')
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let dir_path = current_namespace.iter().collect::<std::path::PathBuf>();
+        let type_names = registry
+            .keys()
+            .chain(self.external_qualified_names.keys())
+            .cloned()
+            .collect();
+
+        let mut files = SourceFiles::new();
+        for (name, format) in registry {
+            let mut content = Vec::new();
+            let mut emitter = self.emitter(&mut content, &type_names, current_namespace.clone());
+            emitter.output_preamble()?;
+            emitter.output_container(name, format)?;
+            files.insert_bytes(dir_path.join(name.to_string() + ".kt"), content);
+        }
+        if self.config.serialization
+            || self.config.generates_validation()
+            || self.config.comparisons
+        {
+            let mut content = Vec::new();
+            let mut emitter = self.emitter(&mut content, &type_names, current_namespace);
+            emitter.output_preamble()?;
+            emitter.output_trait_helpers(registry)?;
+            files.insert_bytes(dir_path.join("TraitHelpers.kt"), content);
+        }
+        Ok(files)
+    }
+
+    /// Nested options cannot be told apart from simple options once mapped to nullable
+    /// types. Tuples are mapped to `Pair`, `Triple`, and the tuples of the runtime.
+    fn check_formats(name: &str, format: &ContainerFormat) -> Result<()> {
+        let mut result = Ok(());
+        format
+            .visit(&mut |format| {
+                if result.is_ok() {
+                    result = match format {
+                        Format::Option(content)
+                            if matches!(content.as_ref(), Format::Option(_)) =>
+                        {
+                            Err(Error::unsupported(CAPABILITIES.language, "nested options"))
+                        }
+                        Format::Tuple(formats)
+                            if formats.len() < 2 || formats.len() > TUPLE_FIELDS.len() =>
+                        {
+                            Err(Error::unsupported(
+                                CAPABILITIES.language,
+                                "tuples with fewer than 2 or more than 6 elements",
+                            ))
+                        }
+                        _ => Ok(()),
+                    };
+                }
+                Ok(())
+            })
+            .unwrap();
+        result.map_err(|error| error.at(name))
+    }
+
+    fn emitter<'b, T: Write>(
+        &'b self,
+        out: T,
+        type_names: &'b BTreeSet<String>,
+        current_namespace: Vec<String>,
+    ) -> KotlinEmitter<'b, T> {
+        KotlinEmitter {
+            out: IndentedWriter::new(out, IndentConfig::Space(4)),
+            generator: self,
+            type_names,
+            current_namespace,
+            current_reserved_names: HashMap::new(),
+        }
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(&self, registry: &Registry) -> std::result::Result<SourceFiles, Self::Error> {
+        self.source_files(registry)
+    }
+}
+
+impl<'a, T> KotlinEmitter<'a, T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(self.out, "package {}", self.generator.config.module_name)?;
+        Ok(())
+    }
+
+    /// Compute a safe reference to the registry type `name` in the given context.
+    /// If `name` is not marked as "reserved" (e.g. a variant class), we compare the global
+    /// name `self.qualified_names[name]` with the current namespace and try to use the
+    /// short string `name` if possible.
+    fn quote_qualified_name(&self, name: &str) -> String {
+        let qname = self
+            .generator
+            .external_qualified_names
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| format!("{}.{}", self.generator.config.module_name, name));
+        let mut path = qname.split('.').collect::<Vec<_>>();
+        if path.len() <= 1 {
+            return qname;
+        }
+        let name = path.pop().unwrap();
+        if self.current_reserved_names.contains_key(name) {
+            return qname;
+        }
+        for (index, element) in path.iter().enumerate() {
+            match self.current_namespace.get(index) {
+                Some(e) if e == element => (),
+                _ => {
+                    return qname;
+                }
+            }
+        }
+        name.to_string()
+    }
+
+    /// Refer to a type of the standard library, unless its name is shadowed by a type of the
+    /// registry or by a variant class in the current context.
+    fn quote_builtin(&self, package: &str, name: &str) -> String {
+        if self.type_names.contains(name) || self.current_reserved_names.contains_key(name) {
+            format!("{}.{}", package, name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn output_comment(&mut self, name: &str) -> std::io::Result<()> {
+        let mut path = self.current_namespace.clone();
+        path.push(name.to_string());
+        if let Some(doc) = self.generator.config.comments.get(&path) {
+            let text = textwrap::indent(doc, " * ").replace("\n\n", "\n *\n");
+            writeln!(self.out, "/**\n{} */", text)?;
+        }
+        Ok(())
+    }
+
+    fn output_custom_code(&mut self) -> std::io::Result<()> {
+        if let Some(code) = self
+            .generator
+            .config
+            .custom_code
+            .get(&self.current_namespace)
+        {
+            writeln!(self.out, "\n{}", code)?;
+        }
+        Ok(())
+    }
+
+    fn has_custom_code(&self, name: &str) -> bool {
+        let mut path = self.current_namespace.clone();
+        path.push(name.to_string());
+        self.generator.config.custom_code.contains_key(&path)
+    }
+
+    fn quote_type(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(x) => self.quote_qualified_name(x),
+            Unit => self.quote_builtin("kotlin", "Unit"),
+            Bool => self.quote_builtin("kotlin", "Boolean"),
+            I8 => self.quote_builtin("kotlin", "Byte"),
+            I16 => self.quote_builtin("kotlin", "Short"),
+            I32 => self.quote_builtin("kotlin", "Int"),
+            I64 => self.quote_builtin("kotlin", "Long"),
+            I128 | U128 => "java.math.BigInteger".into(),
+            U8 => self.quote_builtin("kotlin", "UByte"),
+            U16 => self.quote_builtin("kotlin", "UShort"),
+            U32 => self.quote_builtin("kotlin", "UInt"),
+            U64 => self.quote_builtin("kotlin", "ULong"),
+            F32 => self.quote_builtin("kotlin", "Float"),
+            F64 => self.quote_builtin("kotlin", "Double"),
+            Char => self.quote_builtin("kotlin", "Char"),
+            Str => self.quote_builtin("kotlin", "String"),
+            Bytes => "com.novi.serde.Bytes".into(),
+
+            Option(format) => format!("{}?", self.quote_type(format)),
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => format!(
+                "{}<{}>",
+                self.quote_builtin("kotlin.collections", "List"),
+                self.quote_type(format)
+            ),
+            Map { key, value } => format!(
+                "{}<{}, {}>",
+                self.quote_builtin("kotlin.collections", "Map"),
+                self.quote_type(key),
+                self.quote_type(value)
+            ),
+            Tuple(formats) => format!(
+                "{}<{}>",
+                self.quote_tuple_class(formats.len()),
+                self.quote_types(formats)
+            ),
+            Variable(_) => panic!("unexpected value"),
+        }
+    }
+
+    fn quote_types(&self, formats: &[Format]) -> String {
+        formats
+            .iter()
+            .map(|f| self.quote_type(f))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_tuple_class(&self, size: usize) -> String {
+        match size {
+            2 => self.quote_builtin("kotlin", "Pair"),
+            3 => self.quote_builtin("kotlin", "Triple"),
+            _ => format!("com.novi.serde.Tuple{}", size),
+        }
+    }
+
+    fn enter_class(&mut self, name: &str, reserved_subclass_names: &[&str]) {
+        self.out.indent();
+        self.current_namespace.push(name.to_string());
+        for name in reserved_subclass_names {
+            let entry = self
+                .current_reserved_names
+                .entry(name.to_string())
+                .or_insert(0);
+            *entry += 1;
+        }
+    }
+
+    fn leave_class(&mut self, reserved_subclass_names: &[&str]) {
+        self.out.unindent();
+        self.current_namespace.pop();
+        for name in reserved_subclass_names {
+            let entry = self.current_reserved_names.get_mut(*name).unwrap();
+            *entry -= 1;
+            if *entry == 0 {
+                self.current_reserved_names.remove(*name);
+            }
+        }
+    }
+
+    fn output_trait_helpers(&mut self, registry: &Registry) -> Result<()> {
+        let mut subtypes = BTreeMap::new();
+        for format in registry.values() {
+            format
+                .visit(&mut |f| {
+                    if Self::needs_helper(f) {
+                        subtypes.insert(common::mangle_type(f), f.clone());
+                    }
+                    Ok(())
+                })
+                .unwrap();
+        }
+        writeln!(self.out, "\ninternal object TraitHelpers {{")?;
+        let reserved_names = &[];
+        self.enter_class("TraitHelpers", reserved_names);
+        for (mangled_name, subtype) in &subtypes {
+            if self.generator.config.serialization {
+                self.output_serialization_helper(mangled_name, subtype)?;
+                self.output_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.generates_validation()
+                && common::needs_validation(self.generator.config, subtype, true)
+            {
+                self.output_validation_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.comparisons {
+                self.output_comparison_helper(mangled_name, subtype)?;
+            }
+        }
+        self.leave_class(reserved_names);
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn needs_helper(format: &Format) -> bool {
+        use Format::*;
+        matches!(
+            format,
+            Option(_) | Seq(_) | Map { .. } | Tuple(_) | TupleArray { .. }
+        )
+    }
+
+    fn quote_serialize_value(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) => format!("{}.serialize(serializer)", value),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "serializer.serialize{}({})",
+                common::uppercase_first_letter(&common::mangle_type(format)),
+                value
+            ),
+            _ => format!(
+                "{}.serialize_{}({}, serializer)",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn quote_deserialize(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!(
+                "{}.deserialize(deserializer)",
+                self.quote_qualified_name(name)
+            ),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "deserializer.deserialize{}()",
+                common::uppercase_first_letter(&common::mangle_type(format))
+            ),
+            _ => format!(
+                "{}.deserialize_{}(deserializer)",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+            ),
+        }
+    }
+
+    fn output_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "\nfun serialize_{}(value: {}, serializer: com.novi.serde.Serializer) {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (value != null) {{
+    serializer.serializeOptionTag(true)
+    {}
+}} else {{
+    serializer.serializeOptionTag(false)
+}}
+"#,
+                    self.quote_serialize_value("value", format)
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+serializer.serializeLen(value.size.toLong())
+for (item in value) {{
+    {}
+}}
+"#,
+                    self.quote_serialize_value("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+serializer.serializeLen(value.size.toLong())
+val offsets = {}(value.size)
+var count = 0
+for ((key, item) in value) {{
+    offsets[count++] = serializer.getBufferOffset()
+    {}
+    {}
+}}
+serializer.sortMapEntries(offsets)
+"#,
+                    self.quote_builtin("kotlin", "IntArray"),
+                    self.quote_serialize_value("key", key),
+                    self.quote_serialize_value("item", value)
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out)?;
+                for (index, format) in formats.iter().enumerate() {
+                    let expr = format!("value.{}", TUPLE_FIELDS[index]);
+                    writeln!(self.out, "{}", self.quote_serialize_value(&expr, format))?;
+                }
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+require(value.size == {0}) {{ "Invalid length for fixed-size array: ${{value.size}} instead of {0}" }}
+for (item in value) {{
+    {1}
+}}
+"#,
+                    size,
+                    self.quote_serialize_value("item", content),
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "\nfun deserialize_{}(deserializer: com.novi.serde.Deserializer): {} {{",
+            name,
+            self.quote_type(format0),
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+val tag = deserializer.deserializeOptionTag()
+return if (tag) {} else null
+"#,
+                    self.quote_deserialize(format),
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+val length = deserializer.deserializeLen()
+val obj = mutableListOf<{}>()
+for (i in 0L until length) {{
+    obj.add({})
+}}
+return obj
+"#,
+                    self.quote_type(format),
+                    self.quote_deserialize(format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+val length = deserializer.deserializeLen()
+val obj = mutableMapOf<{0}, {1}>()
+var previousKeyStart = 0
+var previousKeyEnd = 0
+for (i in 0L until length) {{
+    val keyStart = deserializer.getBufferOffset()
+    val key = {2}
+    val keyEnd = deserializer.getBufferOffset()
+    if (i > 0) {{
+        deserializer.checkThatKeySlicesAreIncreasing(
+            com.novi.serde.Slice(previousKeyStart, previousKeyEnd),
+            com.novi.serde.Slice(keyStart, keyEnd),
+        )
+    }}
+    previousKeyStart = keyStart
+    previousKeyEnd = keyEnd
+    obj[key] = {3}
+}}
+return obj
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_deserialize(key),
+                    self.quote_deserialize(value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    r#"
+return {}({}
+)
+"#,
+                    self.quote_tuple_class(formats.len()),
+                    formats
+                        .iter()
+                        .map(|f| format!("\n    {},", self.quote_deserialize(f)))
+                        .collect::<Vec<_>>()
+                        .join("")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+return {0}({1}) {{ {2} }}
+"#,
+                    self.quote_builtin("kotlin.collections", "List"),
+                    size,
+                    self.quote_deserialize(content)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// A statement validating `value`, assuming that `common::needs_validation` holds.
+    fn quote_validate(&self, value: &str, format: &Format) -> String {
+        match format {
+            Format::TypeName(_) => format!("{}.validate()", value),
+            _ => format!(
+                "{}.validate_{}({})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn output_validation_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(
+            self.out,
+            "\nfun validate_{}(value: {}) {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        let needs_validation =
+            |format| common::needs_validation(self.generator.config, format, true);
+        match format0 {
+            Option(format) => {
+                writeln!(
+                    self.out,
+                    "if (value != null) {{\n    {}\n}}",
+                    self.quote_validate("value", format)
+                )?;
+            }
+
+            Seq(format) => {
+                writeln!(
+                    self.out,
+                    "for (item in value) {{\n    {}\n}}",
+                    self.quote_validate("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                writeln!(
+                    self.out,
+                    "for (({}, {}) in value) {{",
+                    if needs_validation(key) { "key" } else { "_" },
+                    if needs_validation(value) { "item" } else { "_" },
+                )?;
+                if needs_validation(key) {
+                    writeln!(self.out, "    {}", self.quote_validate("key", key))?;
+                }
+                if needs_validation(value) {
+                    writeln!(self.out, "    {}", self.quote_validate("item", value))?;
+                }
+                writeln!(self.out, "}}")?;
+            }
+
+            Tuple(formats) => {
+                for (index, format) in formats.iter().enumerate() {
+                    if needs_validation(format) {
+                        let expr = format!("value.{}", TUPLE_FIELDS[index]);
+                        writeln!(self.out, "{}", self.quote_validate(&expr, format))?;
+                    }
+                }
+            }
+
+            TupleArray { content, size } => {
+                writeln!(
+                    self.out,
+                    r#"require(value.size == {0}) {{ "Invalid length for fixed-size array: ${{value.size}} instead of {0}" }}"#,
+                    size
+                )?;
+                if needs_validation(content) {
+                    writeln!(
+                        self.out,
+                        "for (item in value) {{\n    {}\n}}",
+                        self.quote_validate("item", content)
+                    )?;
+                }
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// Check the constraints of the registry that Kotlin types do not enforce.
+    fn output_validate(&mut self, modifier: &str, fields: &[Named<Format>]) -> Result<()> {
+        let fields = fields
+            .iter()
+            .filter(|field| common::needs_validation(self.generator.config, &field.value, true))
+            .collect::<Vec<_>>();
+        if fields.is_empty() {
+            writeln!(self.out, "\n{}fun validate() {{}}", modifier)?;
+            return Ok(());
+        }
+        writeln!(self.out, "\n{}fun validate() {{", modifier)?;
+        self.out.indent();
+        for field in fields {
+            writeln!(
+                self.out,
+                "{}",
+                self.quote_validate(&format!("this.{}", field.name), &field.value)
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// An `Int` expression comparing the values `a` and `b` in the order of Rust values.
+    /// Equality and hashing are those of data classes, which are structural.
+    fn quote_compare(&self, a: &str, b: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            Unit => "0".to_string(),
+            TypeName(_) | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128
+            | Char | Bytes => format!("{}.compareTo({})", a, b),
+            Str => format!("com.novi.serde.Comparisons.compareStrings({}, {})", a, b),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            _ => format!(
+                "{}.compare_{}({}, {})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                a,
+                b
+            ),
+        }
+    }
+
+    fn output_comparison_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "\nfun compare_{0}(a: {1}, b: {1}): {2} {{",
+            name,
+            self.quote_type(format0),
+            self.quote_builtin("kotlin", "Int")
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (a == null || b == null) return (a != null).compareTo(b != null)
+return {}
+"#,
+                    self.quote_compare("a", "b", format)
+                )?;
+            }
+
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                write!(
+                    self.out,
+                    r#"
+for (i in 0 until minOf(a.size, b.size)) {{
+    val result = {}
+    if (result != 0) return result
+}}
+return a.size.compareTo(b.size)
+"#,
+                    self.quote_compare("a[i]", "b[i]", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+val x = a.entries.sortedWith {{ e, f -> {0} }}
+val y = b.entries.sortedWith {{ e, f -> {0} }}
+for (i in 0 until minOf(x.size, y.size)) {{
+    var result = {1}
+    if (result != 0) return result
+    result = {2}
+    if (result != 0) return result
+}}
+return x.size.compareTo(y.size)
+"#,
+                    self.quote_compare("e.key", "f.key", key),
+                    self.quote_compare("x[i].key", "y[i].key", key),
+                    self.quote_compare("x[i].value", "y[i].value", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(
+                    self.out,
+                    "\nvar result: {}",
+                    self.quote_builtin("kotlin", "Int")
+                )?;
+                for (index, format) in formats.iter().enumerate() {
+                    let a = format!("a.{}", TUPLE_FIELDS[index]);
+                    let b = format!("b.{}", TUPLE_FIELDS[index]);
+                    writeln!(
+                        self.out,
+                        "result = {}\nif (result != 0) return result",
+                        self.quote_compare(&a, &b, format)
+                    )?;
+                }
+                writeln!(self.out, "return 0")?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// Output `compareTo`, which orders variants of an enum by index, then values by fields.
+    fn output_compare_to(
+        &mut self,
+        variant_base: Option<&str>,
+        variant_index: Option<u32>,
+        name: &str,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        let int = self.quote_builtin("kotlin", "Int");
+        if let Some(index) = variant_index {
+            writeln!(
+                self.out,
+                "override fun variantIndex(): {} = {}\n",
+                int, index
+            )?;
+        }
+        match variant_base {
+            Some(base) => {
+                writeln!(
+                    self.out,
+                    "override fun compareTo(other: {}): {} {{",
+                    base, int
+                )?;
+                self.out.indent();
+                writeln!(
+                    self.out,
+                    "if (other !is {}) return variantIndex().compareTo(other.variantIndex())",
+                    name
+                )?;
+            }
+            None => {
+                writeln!(
+                    self.out,
+                    "override fun compareTo(other: {}): {} {{",
+                    name, int
+                )?;
+                self.out.indent();
+            }
+        }
+        if !fields.is_empty() {
+            writeln!(self.out, "var result: {}", int)?;
+        }
+        for field in fields {
+            let a = format!("this.{}", field.name);
+            let b = format!("other.{}", field.name);
+            writeln!(
+                self.out,
+                "result = {}\nif (result != 0) return result",
+                self.quote_compare(&a, &b, &field.value)
+            )?;
+        }
+        writeln!(self.out, "return 0")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_variant(
+        &mut self,
+        base: &str,
+        index: u32,
+        name: &str,
+        variant: &VariantFormat,
+    ) -> Result<()> {
+        use VariantFormat::*;
+        let fields = match variant {
+            Unit => Vec::new(),
+            NewType(format) => vec![Named {
+                name: "value".to_string(),
+                value: format.as_ref().clone(),
+            }],
+            Tuple(formats) => formats
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Named {
+                    name: format!("field{}", i),
+                    value: f.clone(),
+                })
+                .collect(),
+            Struct(fields) => fields.clone(),
+            Variable(_) => panic!("incorrect value"),
+        };
+        self.output_struct_or_variant_container(Some(base), Some(index), name, &fields)
+    }
+
+    fn output_variants(
+        &mut self,
+        base: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        for (index, variant) in variants {
+            self.output_variant(base, *index, &variant.name, &variant.value)?;
+        }
+        Ok(())
+    }
+
+    /// Output a data class, or a data object when there are no fields (Kotlin data classes
+    /// need at least one property).
+    fn output_struct_or_variant_container(
+        &mut self,
+        variant_base: Option<&str>,
+        variant_index: Option<u32>,
+        name: &str,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        let config = self.generator.config;
+        let has_body = config.serialization
+            || config.generates_validation()
+            || config.comparisons
+            || self.has_custom_code(name);
+        let supertype = match variant_base {
+            Some(base) => format!(" : {}()", base),
+            None if config.comparisons => format!(" : Comparable<{}>", name),
+            None => String::new(),
+        };
+        // Beginning of class
+        writeln!(self.out)?;
+        self.output_comment(name)?;
+        if fields.is_empty() {
+            write!(self.out, "data object {}{}", name, supertype)?;
+        } else {
+            writeln!(self.out, "data class {}(", name)?;
+            self.enter_class(name, &[]);
+            for field in fields {
+                self.output_comment(&field.name)?;
+                writeln!(
+                    self.out,
+                    "val {}: {},",
+                    field.name,
+                    self.quote_type(&field.value)
+                )?;
+            }
+            self.leave_class(&[]);
+            write!(self.out, "){}", supertype)?;
+        }
+        if !has_body {
+            writeln!(self.out)?;
+            return Ok(());
+        }
+        writeln!(self.out, " {{")?;
+        self.enter_class(name, &[]);
+        let modifier = if variant_base.is_some() {
+            "override "
+        } else {
+            ""
+        };
+        // Serialize
+        if config.serialization {
+            writeln!(
+                self.out,
+                "{}fun serialize(serializer: com.novi.serde.Serializer) {{",
+                modifier
+            )?;
+            self.out.indent();
+            writeln!(self.out, "serializer.increaseContainerDepth()")?;
+            if let Some(index) = variant_index {
+                writeln!(self.out, "serializer.serializeVariantIndex({})", index)?;
+            }
+            for field in fields {
+                writeln!(
+                    self.out,
+                    "{}",
+                    self.quote_serialize_value(&format!("this.{}", field.name), &field.value)
+                )?;
+            }
+            writeln!(self.out, "serializer.decreaseContainerDepth()")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            if variant_index.is_none() {
+                for encoding in &config.encodings {
+                    self.output_class_serialize_for_encoding(*encoding)?;
+                }
+            }
+        }
+        // Validation
+        if config.generates_validation() {
+            self.output_validate(modifier, fields)?;
+        }
+        // Ordering
+        if config.comparisons {
+            if config.serialization || config.generates_validation() {
+                writeln!(self.out)?;
+            }
+            self.output_compare_to(variant_base, variant_index, name, fields)?;
+        }
+        // Custom code
+        self.output_custom_code()?;
+        // Deserialize (struct) or Load (variant)
+        if config.serialization {
+            if fields.is_empty() {
+                writeln!(self.out)?;
+                self.output_deserialize(variant_index, name, fields)?;
+            } else {
+                writeln!(self.out, "\ncompanion object {{")?;
+                self.out.indent();
+                self.output_deserialize(variant_index, name, fields)?;
+                self.out.unindent();
+                writeln!(self.out, "}}")?;
+            }
+        }
+        // End of class
+        self.leave_class(&[]);
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_deserialize(
+        &mut self,
+        variant_index: Option<u32>,
+        name: &str,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        if variant_index.is_none() {
+            writeln!(
+                self.out,
+                "fun deserialize(deserializer: com.novi.serde.Deserializer): {} {{",
+                name,
+            )?;
+        } else {
+            writeln!(
+                self.out,
+                "internal fun load(deserializer: com.novi.serde.Deserializer): {} {{",
+                name,
+            )?;
+        }
+        self.out.indent();
+        writeln!(self.out, "deserializer.increaseContainerDepth()")?;
+        if fields.is_empty() {
+            writeln!(self.out, "deserializer.decreaseContainerDepth()")?;
+            writeln!(self.out, "return {}", name)?;
+        } else {
+            writeln!(self.out, "val obj = {}(", name)?;
+            for field in fields {
+                writeln!(self.out, "    {},", self.quote_deserialize(&field.value))?;
+            }
+            writeln!(self.out, ")")?;
+            writeln!(self.out, "deserializer.decreaseContainerDepth()")?;
+            writeln!(self.out, "return obj")?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        if variant_index.is_none() {
+            for encoding in &self.generator.config.encodings {
+                self.output_class_deserialize_for_encoding(name, *encoding)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn output_enum_container(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        let config = self.generator.config;
+        writeln!(self.out)?;
+        self.output_comment(name)?;
+        if config.comparisons {
+            writeln!(self.out, "sealed class {0} : Comparable<{0}> {{", name)?;
+        } else {
+            writeln!(self.out, "sealed class {} {{", name)?;
+        }
+        let reserved_names = variants
+            .values()
+            .map(|v| v.name.as_str())
+            .collect::<Vec<_>>();
+        self.enter_class(name, &reserved_names);
+        if config.comparisons {
+            writeln!(
+                self.out,
+                "internal abstract fun variantIndex(): {}",
+                self.quote_builtin("kotlin", "Int")
+            )?;
+        }
+        if config.serialization {
+            if config.comparisons {
+                writeln!(self.out)?;
+            }
+            writeln!(
+                self.out,
+                "abstract fun serialize(serializer: com.novi.serde.Serializer)"
+            )?;
+            for encoding in &config.encodings {
+                self.output_class_serialize_for_encoding(*encoding)?;
+            }
+        }
+        if config.generates_validation() {
+            writeln!(self.out, "\nabstract fun validate()")?;
+        }
+        self.output_custom_code()?;
+        self.output_variants(name, variants)?;
+        if config.serialization {
+            writeln!(self.out, "\ncompanion object {{")?;
+            self.out.indent();
+            write!(
+                self.out,
+                "fun deserialize(deserializer: com.novi.serde.Deserializer): {} {{",
+                name
+            )?;
+            self.out.indent();
+            writeln!(
+                self.out,
+                r#"
+val index = deserializer.deserializeVariantIndex()
+return when (index) {{"#,
+            )?;
+            self.out.indent();
+            for (index, variant) in variants {
+                writeln!(self.out, "{} -> {}.load(deserializer)", index, variant.name,)?;
+            }
+            writeln!(
+                self.out,
+                "else -> throw com.novi.serde.DeserializationError(\"Unknown variant index for {}: $index\")",
+                name,
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            for encoding in &config.encodings {
+                self.output_class_deserialize_for_encoding(name, *encoding)?;
+            }
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+        }
+        self.leave_class(&reserved_names);
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// Output an enum made of unit variants as an `enum class`.
+    fn output_c_style_enum_container(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        let config = self.generator.config;
+        let has_body =
+            config.serialization || config.generates_validation() || self.has_custom_code(name);
+        writeln!(self.out)?;
+        self.output_comment(name)?;
+        writeln!(self.out, "enum class {} {{", name)?;
+        let reserved_names = variants
+            .values()
+            .map(|v| v.name.as_str())
+            .collect::<Vec<_>>();
+        self.enter_class(name, &reserved_names);
+        for (position, variant) in variants.values().enumerate() {
+            self.output_comment(&variant.name)?;
+            let separator = if position + 1 < variants.len() {
+                ","
+            } else if has_body {
+                ";"
+            } else {
+                ""
+            };
+            writeln!(self.out, "{}{}", variant.name, separator)?;
+        }
+        if variants.is_empty() && has_body {
+            writeln!(self.out, ";")?;
+        }
+        if config.serialization {
+            writeln!(
+                self.out,
+                "\nfun serialize(serializer: com.novi.serde.Serializer) {{"
+            )?;
+            self.out.indent();
+            writeln!(self.out, "val index = when (this) {{")?;
+            for (index, variant) in variants {
+                writeln!(self.out, "    {} -> {}", variant.name, index)?;
+            }
+            writeln!(self.out, "}}")?;
+            writeln!(self.out, "serializer.serializeVariantIndex(index)")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+            for encoding in &config.encodings {
+                self.output_class_serialize_for_encoding(*encoding)?;
+            }
+        }
+        if config.generates_validation() {
+            // Kotlin enums only take valid values.
+            writeln!(self.out, "\nfun validate() {{}}")?;
+        }
+        self.output_custom_code()?;
+        if config.serialization {
+            writeln!(self.out, "\ncompanion object {{")?;
+            self.out.indent();
+            write!(
+                self.out,
+                "fun deserialize(deserializer: com.novi.serde.Deserializer): {} {{",
+                name
+            )?;
+            self.out.indent();
+            writeln!(
+                self.out,
+                r#"
+val index = deserializer.deserializeVariantIndex()
+return when (index) {{"#,
+            )?;
+            self.out.indent();
+            for (index, variant) in variants {
+                writeln!(self.out, "{} -> {}", index, variant.name)?;
+            }
+            writeln!(
+                self.out,
+                "else -> throw com.novi.serde.DeserializationError(\"Unknown variant index for {}: $index\")",
+                name,
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            for encoding in &config.encodings {
+                self.output_class_deserialize_for_encoding(name, *encoding)?;
+            }
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+        }
+        self.leave_class(&reserved_names);
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_class_serialize_for_encoding(&mut self, encoding: Encoding) -> Result<()> {
+        let options = self.generator.config.bincode_options_for(encoding);
+        writeln!(
+            self.out,
+            r#"
+fun {0}Serialize(): {1} {{{3}
+    val serializer = com.novi.{0}.{2}Serializer({4})
+    serialize(serializer){5}
+    return serializer.getBytes()
+}}"#,
+            encoding.name(),
+            self.quote_builtin("kotlin", "ByteArray"),
+            encoding.name().to_camel_case(),
+            if self.generator.config.validate_on_serialize {
+                "\n    validate()"
+            } else {
+                ""
+            },
+            common::bincode_runtime_arguments(&options),
+            match options.limit {
+                Some(limit) => format!(
+                    r#"
+    if (serializer.getBufferOffset() > {}L) {{
+        throw com.novi.serde.SerializationError("Exceeded the size limit of the encoding")
+    }}"#,
+                    limit.min(i64::MAX as u64)
+                ),
+                None => String::new(),
+            },
+        )?;
+        Ok(())
+    }
+
+    fn output_class_deserialize_for_encoding(
+        &mut self,
+        name: &str,
+        encoding: Encoding,
+    ) -> Result<()> {
+        let options = self.generator.config.bincode_options_for(encoding);
+        let checks = match options.trailing_bytes {
+            TrailingBytes::Reject => {
+                r#"
+    if (deserializer.getBufferOffset() < input.size) {
+        throw com.novi.serde.DeserializationError("Some input bytes were not read")
+    }"#
+            }
+            TrailingBytes::Allow => "",
+        };
+        // The runtime enforces the size limit while reading.
+        let arguments = match options.limit {
+            Some(limit) => format!(
+                ", {}, {}, {}L",
+                options.int_encoding == IntEncoding::Varint,
+                options.endianness == Endianness::Big,
+                limit.min(i64::MAX as u64)
+            ),
+            None if options.is_custom_encoding() => {
+                format!(", {}", common::bincode_runtime_arguments(&options))
+            }
+            None => String::new(),
+        };
+        writeln!(
+            self.out,
+            r#"
+fun {1}Deserialize(input: {3}): {0} {{
+    val deserializer = com.novi.{1}.{2}Deserializer(input{4})
+    val value = deserialize(deserializer){5}
+    return value
+}}"#,
+            name,
+            encoding.name(),
+            encoding.name().to_camel_case(),
+            self.quote_builtin("kotlin", "ByteArray"),
+            arguments,
+            checks,
+        )?;
+        Ok(())
+    }
+
+    fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
+        use ContainerFormat::*;
+        let fields = match format {
+            UnitStruct => Vec::new(),
+            NewTypeStruct(format) => vec![Named {
+                name: "value".to_string(),
+                value: format.as_ref().clone(),
+            }],
+            TupleStruct(formats) => formats
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Named {
+                    name: format!("field{}", i),
+                    value: f.clone(),
+                })
+                .collect::<Vec<_>>(),
+            Struct(fields) => fields.clone(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("Kotlin", name)),
+            FlattenedStruct(_) => {
+                return Err(common::flattened_struct_not_supported("Kotlin", name))
+            }
+            Enum(variants) => {
+                if self.generator.config.c_style_enums
+                    && variants.values().all(|f| f.value == VariantFormat::Unit)
+                {
+                    self.output_c_style_enum_container(name, variants)?;
+                } else {
+                    self.output_enum_container(name, variants)?;
+                }
+                return Ok(());
+            }
+        };
+        self.output_struct_or_variant_container(None, None, name, &fields)
+    }
+}
+
+/// Installer for generated source files in Kotlin.
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+
+    fn install_runtime(&self, source_dir: include_dir::Dir, path: &str) -> Result<()> {
+        let dir_path = self.install_dir.join(path);
+        std::fs::create_dir_all(&dir_path)?;
+        for entry in source_dir.files() {
+            let mut file = std::fs::File::create(dir_path.join(entry.path()))?;
+            file.write_all(entry.contents())?;
+        }
+        Ok(())
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Error;
+
+    fn install_module(
+        &self,
+        config: &CodeGeneratorConfig,
+        registry: &Registry,
+    ) -> std::result::Result<(), Self::Error> {
+        let generator = CodeGenerator::new(config);
+        generator.write_source_files(self.install_dir.clone(), registry)?;
+        Ok(())
+    }
+
+    fn install_serde_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(
+            include_directory!("runtime/kotlin/com/novi/serde"),
+            "com/novi/serde",
+        )
+    }
+
+    fn install_bincode_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(
+            include_directory!("runtime/kotlin/com/novi/bincode"),
+            "com/novi/bincode",
+        )
+    }
+
+    fn install_bcs_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(
+            include_directory!("runtime/kotlin/com/novi/bcs"),
+            "com/novi/bcs",
+        )
+    }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the postcard encoding",
+        ))
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the borsh encoding",
+        ))
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the scale encoding",
+        ))
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the json encoding",
+        ))
+    }
+}
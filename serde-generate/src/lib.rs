@@ -25,6 +25,11 @@
 //!
 //! * TypeScript 4 (packaged and tested with Deno) [(follow-up issue)](https://github.com/zefchain/serde-reflection/issues/58)
 //! * Solidity (tested with Revm) [(initial PR with discussion)](https://github.com/zefchain/serde-reflection/pull/61)
+//! * Kotlin 1.9 (Bincode and BCS only)
+//! * C99 (Bincode and BCS only)
+//! * Ruby >= 3.2 (Bincode and BCS only)
+//! * Haskell (GHC >= 9.2, Bincode and BCS only)
+//! * Elixir (Bincode and BCS only)
 //!
 //! ## Supported Encodings
 //!
@@ -33,9 +38,28 @@
 //!
 //! This crate provides easy-to-deploy runtime libraries for the following binary formats, in all supported languages:
 //!
-//! * [Bincode](https://docs.rs/bincode/1.3.1/bincode/) (default configuration only),
+//! * [Bincode](https://docs.rs/bincode/1.3.1/bincode/) (with configurable integer encoding, endianness,
+//!   size limit and trailing bytes),
 //! * [BCS](https://github.com/diem/bcs) (short for Binary Canonical Serialization, the main format used
-//!   in the [Diem blockchain](https://github.com/diem/diem)).
+//!   in the [Diem blockchain](https://github.com/diem/diem)),
+//! * [Postcard](https://docs.rs/postcard/1.0.0/postcard/) (compact format based on variable-length integers),
+//! * [Borsh](https://borsh.io/) (little-endian format with `u32` lengths and `u8` variant indices,
+//!   not available for Rust and Solidity; registries are checked for Borsh compatibility during
+//!   code generation, and map keys cannot contain characters, floats, bytes, sequences or maps),
+//! * [SCALE](https://docs.substrate.io/reference/scale-codec/) (the codec of Substrate, with compact
+//!   lengths and `u8` variant indices, not available for Rust and Solidity; characters are not supported).
+//!
+//! Besides, the code generated for C++, C#, Dart, Go, Java, Python, Swift, TypeScript, and Rust may
+//! read and write JSON text in the default representation of
+//! [`serde_json`](https://docs.rs/serde_json) (using the encoding `json`): newtypes are unwrapped,
+//! tuples are written as arrays, unit variants as strings, and other variants as objects with a
+//! single entry. Other representations of enums (see `#[serde(tag = ..)]` and `#[serde(untagged)]`)
+//! are only supported in Rust. Map keys must be strings, numbers, or unit variants, and field and
+//! variant names cannot be renamed with naming conventions. Types that serialize differently in
+//! human-readable formats should be traced with `serde_reflection::DualTracer` and passed to
+//! `CodeGeneratorConfig::generate_dual`, which uses the human-readable registry for `json` and the
+//! binary registry for other encodings. If the two registries differ, the `json` encoding is
+//! generated in a separate module with the suffix `_json` (see `CodeGeneratorConfig::split_modes`).
 //!
 //! ## Quick Start with Python and Bincode
 //!
@@ -51,7 +75,7 @@
 //!     b: (u32, u32),
 //! }
 //!
-//! # fn main() -> Result<(), std::io::Error> {
+//! # fn main() -> serde_generate::Result<()> {
 //! // Obtain the Serde format of `Test`. (In practice, formats are more likely read from a file.)
 //! let mut tracer = Tracer::new(TracerConfig::default());
 //! tracer.trace_simple_type::<Test>().unwrap();
@@ -97,6 +121,208 @@
 //! # }
 //! ```
 //!
+//! ## Generating Source Files in Memory
+//!
+//! Every language implements the trait `SourceGenerator`, which returns the generated source
+//! files as a virtual file tree. Paths are relative to the installation directory used by the
+//! corresponding `SourceInstaller`. Files may then be written with `SourceFiles::write_to`,
+//! compared with existing files, or bundled in other ways.
+//! ```
+//! # use serde::Deserialize;
+//! # use serde_reflection::{Tracer, TracerConfig};
+//! use serde_generate::{java, CodeGeneratorConfig, SourceGenerator};
+//! # #[derive(Deserialize)]
+//! # struct Test(u64);
+//! # let mut tracer = Tracer::new(TracerConfig::default());
+//! # tracer.trace_simple_type::<Test>().unwrap();
+//! # let registry = tracer.registry().unwrap();
+//!
+//! let config = CodeGeneratorConfig::new("com.example".to_string());
+//! let files = java::CodeGenerator::new(&config).generate(&registry).unwrap();
+//! assert!(files.get("com/example/Test.java").is_some());
+//! assert!(files.get("com/example/TraitHelpers.java").is_some());
+//! ```
+//!
+//! Code generators and installers report failures with the type `serde_generate::Error`.
+//! Registries and configurations that a language cannot represent, e.g. floating point
+//! numbers in Solidity or c-style enums in Java, result in an `Error::Unsupported` value
+//! that names the feature and, when possible, the container or field where it occurs.
+//!
+//! The features supported by each language are declared by a constant `CAPABILITIES` in the
+//! corresponding module, e.g. `solidity::CAPABILITIES`. Code generators check registries and
+//! configurations against these declarations before generating any code. The same check is
+//! available as `Capabilities::check`.
+//! ```
+//! # use serde::Deserialize;
+//! # use serde_reflection::{Tracer, TracerConfig};
+//! use serde_generate::{java, CodeGeneratorConfig, Error, SourceGenerator};
+//! # #[derive(Deserialize)]
+//! # struct Test(u64);
+//! # let mut tracer = Tracer::new(TracerConfig::default());
+//! # tracer.trace_simple_type::<Test>().unwrap();
+//! # let registry = tracer.registry().unwrap();
+//!
+//! let config = CodeGeneratorConfig::new("com.example".to_string()).with_c_style_enums(true);
+//! let error = java::CodeGenerator::new(&config).generate(&registry).unwrap_err();
+//! assert!(matches!(error, Error::Unsupported { language: "Java", .. }));
+//! ```
+//!
+//! ## Naming Conventions
+//!
+//! By default, generated code mostly keeps the names of the registry. The option
+//! `CodeGeneratorConfig::with_naming_conventions` converts the names of types, fields, variants,
+//! and per-type source files to a given `Case`, e.g. to follow the style guide of the target
+//! language. Since names are not part of the supported binary encodings, the wire format is
+//! unchanged. Converted names that clash with each other or with a reserved word of the target
+//! language result in an `Error::NameConflict` value. Qualified names used for comments and
+//! custom code refer to the original names.
+//! ```
+//! # use serde::Deserialize;
+//! # use serde_reflection::{Tracer, TracerConfig};
+//! use serde_generate::{java, Case, CodeGeneratorConfig, NamingConventions, SourceGenerator};
+//! # #[derive(Deserialize)]
+//! # struct Test { some_field: u64 }
+//! # let mut tracer = Tracer::new(TracerConfig::default());
+//! # tracer.trace_simple_type::<Test>().unwrap();
+//! # let registry = tracer.registry().unwrap();
+//!
+//! let naming = NamingConventions::default().with_fields(Case::LowerCamel);
+//! let config = CodeGeneratorConfig::new("com.example".to_string()).with_naming_conventions(naming);
+//! let files = java::CodeGenerator::new(&config).generate(&registry).unwrap();
+//! assert!(files.get("com/example/Test.java").unwrap().contains("someField"));
+//! ```
+//!
+//! ## Multiple Modules
+//!
+//! A `ModuleMap` assigns containers to modules, either explicitly or from the module paths
+//! found in qualified container names, e.g. `my_crate::a::Foo`. It then splits a registry
+//! into one registry per module, with configurations listing the containers of other modules
+//! as external definitions, so that code generators emit the corresponding imports. (In C++,
+//! the header of a module `a::b` is named `a_b.hpp`.) Cyclic dependencies between modules
+//! are rejected for languages that do not support them, as declared by
+//! `Capabilities::module_cycles`.
+//! ```
+//! # use serde_reflection::{ContainerFormat, Format, Registry};
+//! use serde_generate::{python3, CodeGeneratorConfig, ModuleMap};
+//!
+//! # let mut registry = Registry::new();
+//! # registry.insert("a::Foo".to_string(), ContainerFormat::NewTypeStruct(Box::new(Format::U64)));
+//! # registry.insert(
+//! #     "b::Bar".to_string(),
+//! #     ContainerFormat::NewTypeStruct(Box::new(Format::TypeName("a::Foo".to_string()))),
+//! # );
+//! let config = CodeGeneratorConfig::new("main".to_string());
+//! let modules = ModuleMap::from_qualified_names(&registry, ".");
+//! let files = modules
+//!     .generate(&python3::CAPABILITIES, &config, &registry, |config| {
+//!         Box::new(python3::CodeGenerator::new(config))
+//!     })
+//!     .unwrap();
+//! assert!(files.get("b/__init__.py").unwrap().contains("import a"));
+//! ```
+//!
+//! ## Native Types
+//!
+//! The option `CodeGeneratorConfig::with_native_types` replaces particular containers, e.g.
+//! `Uuid` or `Timestamp`, by types of the target language provided by the user. No code is
+//! generated for these containers. Instead, the generated code refers to the given native type
+//! and calls the given functions to serialize and deserialize values. Serializers take a value
+//! and a serializer, and deserializers take a deserializer, following the conventions of each
+//! runtime (e.g. `try f(value: v, serializer: s)` in Swift).
+//!
+//! Native types are supported in C#, Dart, Go, Java, Swift and TypeScript, where the generated
+//! code serializes each field with an explicit call. Other languages, where
+//! `Capabilities::native_types` is false, reject this option. In particular, C++, Python,
+//! Rust and OCaml derive (de)serialization from the types themselves, through template
+//! specializations, type annotations, `serde` derives, and `ppx` derivers respectively: there,
+//! use `with_external_definitions` to refer to user types that provide the same serialization
+//! interface as generated types instead.
+//! ```
+//! # use serde::Deserialize;
+//! # use serde_reflection::{Tracer, TracerConfig};
+//! use serde_generate::{java, CodeGeneratorConfig, NativeType, SourceGenerator};
+//! # #[derive(Deserialize)]
+//! # struct Timestamp(u64);
+//! # #[derive(Deserialize)]
+//! # struct Event { at: Timestamp }
+//! # let mut tracer = Tracer::new(TracerConfig::default());
+//! # tracer.trace_simple_type::<Event>().unwrap();
+//! # let registry = tracer.registry().unwrap();
+//!
+//! let mut native_types = std::collections::BTreeMap::new();
+//! native_types.insert(
+//!     "Timestamp".to_string(),
+//!     NativeType::new(
+//!         "java.time.Instant".to_string(),
+//!         "com.example.Codecs.serializeInstant".to_string(),
+//!         "com.example.Codecs.deserializeInstant".to_string(),
+//!     ),
+//! );
+//! let config = CodeGeneratorConfig::new("com.example".to_string()).with_native_types(native_types);
+//! let files = java::CodeGenerator::new(&config).generate(&registry).unwrap();
+//! assert!(files.get("com/example/Timestamp.java").is_none());
+//! assert!(files.get("com/example/Event.java").unwrap().contains("java.time.Instant at"));
+//! ```
+//!
+//! ## Comparisons
+//!
+//! The option `CodeGeneratorConfig::with_comparisons` makes every generated type support
+//! structural equality, a hash consistent with equality, and a total ordering that matches
+//! `#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]` in Rust: fields are compared in order,
+//! enum variants by index, strings by code points, and maps by their entries sorted by key.
+//! Generated values can then be used as keys of maps and sets, e.g. `Comparable` in Java and
+//! Swift, `compareTo` in Dart and TypeScript, `<=>` in Ruby, `compare/2` in Elixir, `operator<`
+//! and `std::hash` in C++, `x_compare`, `x_equal` and `x_hash` functions in C, or `compare_x`,
+//! `equal_x` and `hash_x` functions in OCaml and Solidity. Floating point numbers have no total
+//! order: registries that contain them are rejected.
+//! ```
+//! # use serde::Deserialize;
+//! # use serde_reflection::{Tracer, TracerConfig};
+//! use serde_generate::{java, CodeGeneratorConfig, SourceGenerator};
+//! # #[derive(Deserialize)]
+//! # struct Test { some_field: u64 }
+//! # let mut tracer = Tracer::new(TracerConfig::default());
+//! # tracer.trace_simple_type::<Test>().unwrap();
+//! # let registry = tracer.registry().unwrap();
+//!
+//! let config = CodeGeneratorConfig::new("com.example".to_string()).with_comparisons(true);
+//! let files = java::CodeGenerator::new(&config).generate(&registry).unwrap();
+//! assert!(files.get("com/example/Test.java").unwrap().contains("implements Comparable<Test>"));
+//! ```
+//!
+//! ## Validation
+//!
+//! Some constraints of a registry are only enforced by the types of Rust, C++ and Solidity, e.g.
+//! the length of `[T; N]`. Elsewhere, deserializers check them but a value built in memory may
+//! violate them. The option `CodeGeneratorConfig::with_validation` adds a `validate` method
+//! (`Validate` in C# and Go, `validate_x` functions in OCaml) checking the length of fixed-size
+//! arrays and tuples, the presence of enum values in Go, and the values of C-style enums in C#.
+//! This option is available in C#, Dart, Go, Java, Kotlin, OCaml, Python, Ruby, Swift and
+//! TypeScript. Keys of maps are unique by construction and sorted by serializers, so maps only need
+//! their entries to be validated. With `CodeGeneratorConfig::with_validate_on_serialize`, the
+//! generated serialization entry points (e.g. `bcsSerialize`) call `validate` first. TypeScript has
+//! no such entry points: its serializers check the length of fixed-size arrays instead. OCaml
+//! serializers are derived by a PPX and do not validate values. The Rust, C++ and Solidity
+//! generators ignore these options, while the C, Haskell and Elixir generators reject them.
+//! ```
+//! # use serde::Deserialize;
+//! # use serde_reflection::{Tracer, TracerConfig};
+//! use serde_generate::{java, CodeGeneratorConfig, Encoding, SourceGenerator};
+//! # #[derive(Deserialize)]
+//! # struct Test { some_field: [u64; 2] }
+//! # let mut tracer = Tracer::new(TracerConfig::default());
+//! # tracer.trace_simple_type::<Test>().unwrap();
+//! # let registry = tracer.registry().unwrap();
+//!
+//! let config = CodeGeneratorConfig::new("com.example".to_string())
+//!     .with_encodings(vec![Encoding::Bcs])
+//!     .with_validate_on_serialize(true);
+//! let files = java::CodeGenerator::new(&config).generate(&registry).unwrap();
+//! let content = files.get("com/example/Test.java").unwrap();
+//! assert!(content.contains("public void validate() {"));
+//! assert!(content.contains("public byte[] bcsSerialize() throws com.novi.serde.SerializationError {\n        validate();"));
+//! ```
+//!
 //! ## Binary Tool
 //!
 //! In addition to a Rust library, this crate provides a binary tool `serdegen` to process Serde formats
@@ -127,6 +353,9 @@
 //! cargo run -p serde-generate-bin -- --language python3 test.yaml > test.py
 //! ```
 //!
+//! For languages that generate several files, such as Java, each file is printed after a header
+//! `==> path <==`.
+//!
 //! To create a python module `test` and install the bincode runtime in a directory `$DEST`, you may run:
 //! ```bash
 //! cargo run -p serde-generate-bin -- --language python3 --with-runtimes serde bincode --module-name test --target-source-dir "$DEST" test.yaml
@@ -141,6 +370,9 @@ pub mod analyzer;
 /// Utility function to generate indented text
 pub mod indent;
 
+/// Support for code-generation in C
+#[cfg(feature = "c")]
+pub mod c;
 /// Support for code-generation in C++
 #[cfg(feature = "cpp")]
 pub mod cpp;
@@ -150,18 +382,30 @@ pub mod csharp;
 /// Support for code-generation in Dart
 #[cfg(feature = "dart")]
 pub mod dart;
+/// Support for code-generation in Elixir
+#[cfg(feature = "elixir")]
+pub mod elixir;
 /// Support for code-generation in Go
 #[cfg(feature = "golang")]
 pub mod golang;
+/// Support for code-generation in Haskell
+#[cfg(feature = "haskell")]
+pub mod haskell;
 /// Support for code-generation in Java
 #[cfg(feature = "java")]
 pub mod java;
+/// Support for code-generation in Kotlin
+#[cfg(feature = "kotlin")]
+pub mod kotlin;
 /// Support for code-generation in OCaml
 #[cfg(feature = "ocaml")]
 pub mod ocaml;
 /// Support for code-generation in Python 3
 #[cfg(feature = "python3")]
 pub mod python3;
+/// Support for code-generation in Ruby
+#[cfg(feature = "ruby")]
+pub mod ruby;
 /// Support for code-generation in Rust
 #[cfg(feature = "rust")]
 pub mod rust;
@@ -175,9 +419,20 @@ pub mod swift;
 #[cfg(feature = "typescript")]
 pub mod typescript;
 
+/// Features supported by each target language.
+mod capabilities;
 /// Common logic for codegen.
 mod common;
 /// Common configuration objects and traits used in public APIs.
 mod config;
+/// Error type of code generators and installers.
+mod error;
+/// Partition of registries into modules.
+mod modules;
+/// Application of naming conventions to registries.
+mod naming;
 
+pub use capabilities::*;
 pub use config::*;
+pub use error::{Error, Result};
+pub use modules::ModuleMap;
@@ -0,0 +1,247 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{
+    common, Capabilities, CodeGeneratorConfig, Error, Result, SourceFiles, SourceGenerator,
+};
+use serde_name::{unique_identifiers, TypeName, UnderscoreRenderer};
+use serde_reflection::{Format, FormatHolder, Registry};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Assignment of the containers of a registry to modules, so that code is generated in one
+/// module (or package, namespace, etc) per group of containers.
+///
+/// Module names are used as `CodeGeneratorConfig::module_name` and to refer to other modules
+/// in `CodeGeneratorConfig::external_definitions`, hence they must follow the conventions of
+/// the target language for these options. Containers without a module stay in the module
+/// named by the configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleMap(BTreeMap</* container */ String, Location>);
+
+/// Where a container is defined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Location {
+    /// The name of the module.
+    module: String,
+    /// The name of the container within the module, before sanitization.
+    name: String,
+}
+
+impl ModuleMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assign a container to a module.
+    pub fn insert(&mut self, container: String, module: String) {
+        let location = Location {
+            module,
+            name: container.clone(),
+        };
+        self.0.insert(container, location);
+    }
+
+    /// The module of a container, if any.
+    pub fn module(&self, container: &str) -> Option<&str> {
+        self.0
+            .get(container)
+            .map(|location| location.module.as_str())
+    }
+
+    /// Assign each container named by a qualified Rust path, e.g. `my_crate::a::Foo<u8>`,
+    /// to the module given by the path, here `my_crate.a` if `separator` is `"."`. Within the
+    /// module, the container is named `Foo<u8>` (then `Foo_u8` after sanitization).
+    pub fn from_qualified_names(registry: &Registry, separator: &str) -> Self {
+        let mut map = Self::new();
+        for container in registry.keys() {
+            if let Ok(TypeName::Path { path, ident, args }) = TypeName::parse(container) {
+                if path.is_empty() {
+                    continue;
+                }
+                let name = TypeName::Path {
+                    path: Vec::new(),
+                    ident,
+                    args,
+                };
+                let location = Location {
+                    module: path.join(separator),
+                    name: name.to_string(),
+                };
+                map.0.insert(container.clone(), location);
+            }
+        }
+        map
+    }
+
+    /// Split the registry into one registry per module. Each module comes with a copy of the
+    /// configuration where `module_name` is the name of the module and `external_definitions`
+    /// also lists the containers of other modules that the module refers to.
+    ///
+    /// Since references to other modules are resolved before generating code, the naming
+    /// convention for types applies to them as well.
+    pub fn split(
+        &self,
+        config: &CodeGeneratorConfig,
+        registry: &Registry,
+    ) -> Result<BTreeMap<String, (CodeGeneratorConfig, Registry)>> {
+        // Containers replaced by native types belong to no module.
+        let registry = &common::remove_native_types(config, registry);
+        // Sanitize names within each module.
+        let mut local_names = BTreeMap::<_, Vec<_>>::new();
+        for container in registry.keys() {
+            let location = self.location(config, container);
+            local_names
+                .entry(location.module)
+                .or_default()
+                .push((container, location.name));
+        }
+        let mut locations = BTreeMap::new();
+        for (module, names) in &local_names {
+            let identifiers = unique_identifiers(
+                names.iter().map(|(_, name)| name.as_str()),
+                &UnderscoreRenderer,
+            );
+            for (container, name) in names {
+                let location = Location {
+                    module: module.clone(),
+                    name: identifiers[name].clone(),
+                };
+                locations.insert(container.as_str(), location);
+            }
+        }
+
+        let mut result = BTreeMap::new();
+        for (container, format) in registry {
+            let module = &locations[container.as_str()].module;
+            let (module_config, module_registry) =
+                result.entry(module.clone()).or_insert_with(|| {
+                    let mut module_config = config.clone();
+                    module_config.module_name = module.clone();
+                    (module_config, Registry::new())
+                });
+            let mut format = format.clone();
+            format.visit_mut(&mut |format| {
+                if let Format::TypeName(name) = format {
+                    if let Some(location) = locations.get(name.as_str()) {
+                        if &location.module == module {
+                            *name = location.name.clone();
+                        } else {
+                            *name = config.naming.types.apply(&location.name);
+                            let names = module_config
+                                .external_definitions
+                                .entry(location.module.clone())
+                                .or_default();
+                            if !names.contains(name) {
+                                names.push(name.clone());
+                            }
+                            module_config
+                                .generated_modules
+                                .insert(location.module.clone());
+                        }
+                    }
+                }
+                Ok(())
+            })?;
+            module_registry.insert(locations[container.as_str()].name.clone(), format);
+        }
+        Ok(result)
+    }
+
+    /// Generate the source files of all modules with the code generator of a language.
+    ///
+    /// ```
+    /// # use serde_reflection::{ContainerFormat, Format, Registry};
+    /// use serde_generate::{java, CodeGeneratorConfig, ModuleMap};
+    ///
+    /// let mut registry = Registry::new();
+    /// registry.insert("a::Foo".to_string(), ContainerFormat::NewTypeStruct(Box::new(Format::U64)));
+    /// registry.insert(
+    ///     "b::Bar".to_string(),
+    ///     ContainerFormat::NewTypeStruct(Box::new(Format::TypeName("a::Foo".to_string()))),
+    /// );
+    ///
+    /// let config = CodeGeneratorConfig::new("com.example".to_string());
+    /// let modules = ModuleMap::from_qualified_names(&registry, ".");
+    /// let files = modules
+    ///     .generate(&java::CAPABILITIES, &config, &registry, |config| {
+    ///         Box::new(java::CodeGenerator::new(config))
+    ///     })
+    ///     .unwrap();
+    /// assert!(files.get("b/Bar.java").unwrap().contains("a.Foo value"));
+    /// ```
+    pub fn generate<F>(
+        &self,
+        capabilities: &Capabilities,
+        config: &CodeGeneratorConfig,
+        registry: &Registry,
+        generator: F,
+    ) -> Result<SourceFiles>
+    where
+        F: for<'a> Fn(&'a CodeGeneratorConfig) -> Box<dyn SourceGenerator<Error = Error> + 'a>,
+    {
+        let modules = self.split(config, registry)?;
+        capabilities.check_modules(&dependencies(&modules))?;
+        check_imported_names(capabilities, &modules)?;
+        let mut files = SourceFiles::new();
+        for (module_config, module_registry) in modules.values() {
+            for (path, content) in generator(module_config).generate(module_registry)? {
+                files.insert(path, content);
+            }
+        }
+        Ok(files)
+    }
+
+    fn location(&self, config: &CodeGeneratorConfig, container: &str) -> Location {
+        self.0.get(container).cloned().unwrap_or_else(|| Location {
+            module: config.module_name.clone(),
+            name: container.to_string(),
+        })
+    }
+}
+
+/// The modules that each module refers to, among the modules being generated.
+fn dependencies(
+    modules: &BTreeMap<String, (CodeGeneratorConfig, Registry)>,
+) -> BTreeMap<String, BTreeSet<String>> {
+    modules
+        .iter()
+        .map(|(module, (module_config, _))| {
+            let children = module_config
+                .external_definitions
+                .keys()
+                .filter(|name| modules.contains_key(*name))
+                .cloned()
+                .collect();
+            (module.clone(), children)
+        })
+        .collect()
+}
+
+/// Check that the containers imported from other modules do not clash with local containers.
+/// Imported names are already converted by `ModuleMap::split`, so local names are compared
+/// after the same conversion (e.g. `foo_bar` and `FooBar` clash in upper camel case).
+fn check_imported_names(
+    capabilities: &Capabilities,
+    modules: &BTreeMap<String, (CodeGeneratorConfig, Registry)>,
+) -> Result<()> {
+    for (module, (module_config, module_registry)) in modules {
+        let local_names = module_registry
+            .keys()
+            .map(|name| (module_config.naming.types.apply(name), name))
+            .collect::<BTreeMap<_, _>>();
+        for (other, names) in &module_config.external_definitions {
+            if let Some((name, local_name)) = names
+                .iter()
+                .find_map(|name| Some((name, local_names.get(name)?)))
+            {
+                return Err(Error::NameConflict {
+                    language: capabilities.language,
+                    name: name.clone(),
+                    path: format!("{}.{}", other, name),
+                    conflict: format!("clashes with {}.{} when imported", module, local_name),
+                });
+            }
+        }
+    }
+    Ok(())
+}
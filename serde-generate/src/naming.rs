@@ -0,0 +1,272 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{Case, CodeGeneratorConfig, Error, Result};
+use serde_name::{unique_identifiers, UnderscoreRenderer};
+use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Apply the naming conventions of the configuration to the types, fields and variants of the
+/// registry, as well as to the qualified names used as keys for comments and custom code.
+/// Type names that are not identifiers are sanitized first (see `sanitize_type_names`).
+/// Return `None` if no name changes.
+///
+/// Converted names are checked against the reserved words of the target language and against
+/// each other. Variants of tagged enums and tags of open enums are kept as is since they are
+/// part of the wire format.
+pub(crate) fn convert(
+    language: &'static str,
+    is_reserved: impl Fn(&str) -> bool,
+    config: &CodeGeneratorConfig,
+    registry: &Registry,
+) -> Result<Option<(CodeGeneratorConfig, Registry)>> {
+    if let Some((config, registry)) = sanitize_type_names(config, registry) {
+        let converted = convert(language, is_reserved, &config, &registry)?;
+        return Ok(Some(converted.unwrap_or((config, registry))));
+    }
+    if !config.naming.renames_identifiers() {
+        return Ok(None);
+    }
+    let external_names = config
+        .external_definitions
+        .values()
+        .flatten()
+        .cloned()
+        .collect::<BTreeSet<_>>();
+    let mut renamer = Renamer {
+        language,
+        is_reserved: &is_reserved,
+        changed: false,
+    };
+
+    let mut type_names = HashMap::new();
+    let mut scope = Scope::with_names(external_names.iter());
+    for name in registry.keys() {
+        let new_name = renamer.rename(config.naming.types, name, name, &mut scope)?;
+        type_names.insert(name.clone(), new_name);
+    }
+
+    let mut renamings = Renamings::default();
+    let mut result = Registry::new();
+    for (name, format) in registry {
+        let mut format = format.clone();
+        let names = renamer.rename_container(config, name, &mut format)?;
+        format.visit_mut(&mut |format| {
+            if let Format::TypeName(name) = format {
+                if let Some(new_name) = type_names.get(name) {
+                    *name = new_name.clone();
+                }
+            }
+            Ok(())
+        })?;
+        renamings.insert(name.clone(), (type_names[name].clone(), names));
+        result.insert(type_names[name].clone(), format);
+    }
+    if !renamer.changed {
+        return Ok(None);
+    }
+
+    let mut config = config.clone();
+    config.comments = renamings.rename_keys(&config.comments);
+    config.custom_code = renamings.rename_keys(&config.custom_code);
+    Ok(Some((config, result)))
+}
+
+/// Rename the types whose names are not identifiers, e.g. names such as
+/// `my_crate::Foo<alloc::string::String>` obtained with `std::any::type_name`. Names are
+/// rendered as `Foo_String` and disambiguated with module paths if needed. The same mapping
+/// applies to the type names of external definitions and to the qualified names used as keys
+/// for comments and custom code. Return `None` if all the names are valid identifiers.
+pub(crate) fn sanitize_type_names(
+    config: &CodeGeneratorConfig,
+    registry: &Registry,
+) -> Option<(CodeGeneratorConfig, Registry)> {
+    let names = registry
+        .keys()
+        .chain(config.external_definitions.values().flatten())
+        .map(String::as_str);
+    let identifiers = unique_identifiers(names, &UnderscoreRenderer);
+    if identifiers
+        .iter()
+        .all(|(name, identifier)| name == identifier)
+    {
+        return None;
+    }
+    let rename = |name: &String| identifiers.get(name).unwrap_or(name).clone();
+
+    let mut result = Registry::new();
+    for (name, format) in registry {
+        let mut format = format.clone();
+        format
+            .visit_mut(&mut |format| {
+                if let Format::TypeName(name) = format {
+                    *name = rename(name);
+                }
+                Ok(())
+            })
+            .expect("renaming should not fail");
+        result.insert(rename(name), format);
+    }
+
+    let rename_keys = |entries: &BTreeMap<Vec<String>, String>| {
+        entries
+            .iter()
+            .map(|(path, value)| (path.iter().map(rename).collect(), value.clone()))
+            .collect()
+    };
+    let mut config = config.clone();
+    for names in config.external_definitions.values_mut() {
+        for name in names {
+            *name = rename(name);
+        }
+    }
+    config.comments = rename_keys(&config.comments);
+    config.custom_code = rename_keys(&config.custom_code);
+    Some((config, result))
+}
+
+struct Renamer<'a> {
+    language: &'static str,
+    is_reserved: &'a dyn Fn(&str) -> bool,
+    changed: bool,
+}
+
+/// Names already in use within a type, an enum, or the module, indexed by converted names.
+#[derive(Default)]
+struct Scope(BTreeMap<String, String>);
+
+/// New names of the members (fields or variants) of a container. Variants may have fields.
+type MemberNames = BTreeMap<String, (String, BTreeMap<String, String>)>;
+
+/// New names of the containers of a registry and of their members.
+#[derive(Default)]
+struct Renamings(BTreeMap<String, (String, MemberNames)>);
+
+impl Scope {
+    fn with_names<'a>(names: impl Iterator<Item = &'a String>) -> Self {
+        Self(names.map(|name| (name.clone(), name.clone())).collect())
+    }
+}
+
+impl Renamer<'_> {
+    /// Convert `name`, found at the given `path`, and record the result in `scope`.
+    fn rename(&mut self, case: Case, name: &str, path: &str, scope: &mut Scope) -> Result<String> {
+        let new_name = case.apply(name);
+        if new_name != name {
+            self.changed = true;
+            if !new_name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Err(self.conflict(new_name, path, "is not an identifier".to_string()));
+            }
+            if (self.is_reserved)(&new_name) {
+                return Err(self.conflict(new_name, path, "is a reserved word".to_string()));
+            }
+        }
+        if let Some(other) = scope.0.get(&new_name) {
+            return Err(self.conflict(new_name, path, format!("clashes with {}", other)));
+        }
+        scope.0.insert(new_name.clone(), path.to_string());
+        Ok(new_name)
+    }
+
+    fn rename_fields(
+        &mut self,
+        case: Case,
+        fields: &mut [Named<Format>],
+        path: &str,
+    ) -> Result<BTreeMap<String, String>> {
+        let mut scope = Scope::default();
+        let mut names = BTreeMap::new();
+        for field in fields {
+            let field_path = format!("{}.{}", path, field.name);
+            let new_name = self.rename(case, &field.name, &field_path, &mut scope)?;
+            names.insert(
+                std::mem::replace(&mut field.name, new_name.clone()),
+                new_name,
+            );
+        }
+        Ok(names)
+    }
+
+    fn rename_container(
+        &mut self,
+        config: &CodeGeneratorConfig,
+        name: &str,
+        format: &mut ContainerFormat,
+    ) -> Result<MemberNames> {
+        let naming = &config.naming;
+        let mut names = MemberNames::new();
+        match format {
+            ContainerFormat::Struct(fields) => {
+                for (old, new) in self.rename_fields(naming.fields, fields, name)? {
+                    names.insert(old, (new, BTreeMap::new()));
+                }
+            }
+            ContainerFormat::Enum(variants) => {
+                let mut scope = Scope::default();
+                for variant in variants.values_mut() {
+                    let path = format!("{}.{}", name, variant.name);
+                    let new_name =
+                        self.rename(naming.variants, &variant.name, &path, &mut scope)?;
+                    let fields = match &mut variant.value {
+                        VariantFormat::Struct(fields) => {
+                            self.rename_fields(naming.fields, fields, &path)?
+                        }
+                        _ => BTreeMap::new(),
+                    };
+                    let old_name = std::mem::replace(&mut variant.name, new_name.clone());
+                    names.insert(old_name, (new_name, fields));
+                }
+            }
+            ContainerFormat::UnitStruct
+            | ContainerFormat::NewTypeStruct(_)
+            | ContainerFormat::TupleStruct(_)
+            | ContainerFormat::TaggedEnum { .. }
+            | ContainerFormat::FlattenedStruct(_)
+            | ContainerFormat::OpenEnum(_) => (),
+        }
+        Ok(names)
+    }
+
+    fn conflict(&self, name: String, path: &str, conflict: String) -> Error {
+        Error::NameConflict {
+            language: self.language,
+            name,
+            path: path.to_string(),
+            conflict,
+        }
+    }
+}
+
+impl Renamings {
+    fn insert(&mut self, name: String, names: (String, MemberNames)) {
+        self.0.insert(name, names);
+    }
+
+    /// Rename the qualified names `[.., Type, member, field]` used as keys in the configuration.
+    fn rename_keys<T: Clone>(
+        &self,
+        entries: &BTreeMap<Vec<String>, T>,
+    ) -> BTreeMap<Vec<String>, T> {
+        entries
+            .iter()
+            .map(|(path, value)| (self.rename_path(path), value.clone()))
+            .collect()
+    }
+
+    fn rename_path(&self, path: &[String]) -> Vec<String> {
+        let mut path = path.to_vec();
+        let position = path.iter().position(|name| self.0.contains_key(name));
+        if let Some(index) = position {
+            let (new_name, members) = &self.0[&path[index]];
+            path[index] = new_name.clone();
+            if let Some((new_name, fields)) = path.get(index + 1).and_then(|n| members.get(n)) {
+                let field = path.get(index + 2).and_then(|n| fields.get(n)).cloned();
+                path[index + 1] = new_name.clone();
+                if let Some(field) = field {
+                    path[index + 2] = field;
+                }
+            }
+        }
+        path
+    }
+}
@@ -2,21 +2,40 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::{
-    common::uppercase_first_letter,
+    common::{self, uppercase_first_letter},
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig, Encoding,
+    naming, BincodeOptions, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error,
+    IntEncoding, Result, SourceGenerator, TrailingBytes,
 };
 use heck::CamelCase;
 use heck::SnakeCase;
 use include_dir::include_dir as include_directory;
 use phf::phf_set;
 use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
-use std::{
-    collections::BTreeMap,
-    io::{Result, Write},
-    path::PathBuf,
+use std::{collections::BTreeMap, io::Write, path::PathBuf};
+
+/// Features supported by the OCaml code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "OCaml",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: crate::capabilities::ALL_ENCODINGS,
+    package_manifest: true,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: true,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
 };
 
+#[derive(Clone)]
 pub struct CodeGenerator<'a> {
     config: &'a CodeGeneratorConfig,
     libraries: Vec<String>,
@@ -30,9 +49,6 @@ struct OCamlEmitter<'a, T> {
 
 impl<'a> CodeGenerator<'a> {
     pub fn new(config: &'a CodeGeneratorConfig) -> Self {
-        if config.c_style_enums {
-            panic!("OCaml does not support generating c-style enums");
-        }
         Self {
             config,
             libraries: config
@@ -44,6 +60,15 @@ impl<'a> CodeGenerator<'a> {
     }
 
     pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| KEYWORDS.contains(name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).output(out, &registry);
+        }
         let current_namespace = self
             .config
             .module_name
@@ -62,6 +87,17 @@ impl<'a> CodeGenerator<'a> {
             let last = i == n - 1;
             emitter.output_container(name, format, first, last)?;
         }
+        if self.config.comparisons {
+            emitter.output_comparisons(registry)?;
+        }
+        if self.config.generates_validation() {
+            emitter.output_validation(registry)?;
+        }
+        for (name, format) in registry {
+            if let ContainerFormat::OpenEnum(formats) = format {
+                emitter.output_open_enum_handlers(name, formats)?;
+            }
+        }
         for (name, _) in registry.iter() {
             emitter.output_custom_code(name)?;
         }
@@ -122,6 +158,31 @@ where
                 writeln!(self.out, "open {}", uppercase_first_letter(namespace))?
             }
         }
+        self.output_bincode_options()
+    }
+
+    /// The Bincode runtime is selected at link time, so its options are global settings that
+    /// the generated module sets when it is initialized.
+    fn output_bincode_options(&mut self) -> Result<()> {
+        if !self.generator.config.encodings.contains(&Encoding::Bincode) {
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(Encoding::Bincode);
+        if options == BincodeOptions::default() {
+            return Ok(());
+        }
+        let limit = match options.limit {
+            Some(limit) => format!("Some {}", limit.min(i64::MAX as u64 >> 1)),
+            None => "None".to_string(),
+        };
+        writeln!(
+            self.out,
+            "let () = Serde.Bincode_options.(varint_encoding := {}; big_endian := {}; limit := {}; allow_trailing_bytes := {})",
+            options.int_encoding == IntEncoding::Varint,
+            options.endianness == Endianness::Big,
+            limit,
+            options.trailing_bytes == TrailingBytes::Allow,
+        )?;
         Ok(())
     }
 
@@ -186,7 +247,8 @@ where
         self.output_format(key, false)?;
         write!(self.out, ", ")?;
         self.output_format(value, false)?;
-        write!(self.out, ") Serde.map")
+        write!(self.out, ") Serde.map")?;
+        Ok(())
     }
 
     fn output_tuple(&mut self, formats: &[Format], is_struct: bool) -> Result<()> {
@@ -201,10 +263,9 @@ where
             .map(|(i, f)| {
                 self.output_format(f, false)?;
                 if i != n - 1 {
-                    write!(self.out, " * ")
-                } else {
-                    Ok(())
+                    write!(self.out, " * ")?;
                 }
+                Ok(())
             })
             .collect::<Result<Vec<_>>>()?;
         write!(self.out, ")")?;
@@ -223,11 +284,13 @@ where
                 self.output_comment(&f.name)?;
                 write!(self.out, "{}: ", self.safe_snake_case(&f.name))?;
                 self.output_format(&f.value, false)?;
-                writeln!(self.out, ";")
+                writeln!(self.out, ";")?;
+                Ok(())
             })
             .collect::<Result<Vec<_>>>()?;
         self.out.unindent();
-        write!(self.out, "}}")
+        write!(self.out, "}}")?;
+        Ok(())
     }
 
     fn output_variant(&mut self, format: &VariantFormat) -> Result<()> {
@@ -267,7 +330,8 @@ where
                 self.output_comment(&f.name)?;
                 write!(self.out, "| {}_{}", name, f.name)?;
                 self.output_variant(&f.value)?;
-                writeln!(self.out, "{}", c)
+                writeln!(self.out, "{}", c)?;
+                Ok(())
             })
             .collect::<Result<Vec<_>>>()?;
         self.out.unindent();
@@ -287,6 +351,517 @@ where
         }
     }
 
+    /// Function comparing values of the given format, following the Rust derive order.
+    fn quote_compare(format: &Format) -> String {
+        use Format::*;
+        match format {
+            Variable(_) => panic!("incorrect value"),
+            TypeName(s) => format!("compare_{}", s.to_snake_case()),
+            I8 => "Stdint.Int8.compare".into(),
+            I16 => "Stdint.Int16.compare".into(),
+            I128 => "Stdint.Int128.compare".into(),
+            U8 => "Stdint.Uint8.compare".into(),
+            U16 => "Stdint.Uint16.compare".into(),
+            U32 => "Stdint.Uint32.compare".into(),
+            U64 => "Stdint.Uint64.compare".into(),
+            U128 => "Stdint.Uint128.compare".into(),
+            Unit | Bool | I32 | I64 | Char | Str | Bytes | F32 | F64 => "Stdlib.compare".into(),
+            Option(f) => format!(
+                "(Serde.Comparisons.compare_option {})",
+                Self::quote_compare(f)
+            ),
+            Seq(f) => format!(
+                "(Serde.Comparisons.compare_list {})",
+                Self::quote_compare(f)
+            ),
+            Map { key, value } => format!(
+                "(Serde.Comparisons.compare_map {} {})",
+                Self::quote_compare(key),
+                Self::quote_compare(value)
+            ),
+            Tuple(formats) => {
+                let names = |x: &str| {
+                    (0..formats.len())
+                        .map(|i| format!("{}{}", x, i))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                let comparisons = formats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| format!("{} a{i} b{i}", Self::quote_compare(f), i = i))
+                    .collect();
+                format!(
+                    "(fun ({}) ({}) -> {})",
+                    names("a"),
+                    names("b"),
+                    Self::chain_comparisons(comparisons)
+                )
+            }
+            TupleArray { content, size: _ } => format!(
+                "(Serde.Comparisons.compare_array {})",
+                Self::quote_compare(content)
+            ),
+        }
+    }
+
+    /// Hash function for the given format, consistent with `quote_compare`.
+    fn quote_hash(format: &Format) -> String {
+        use Format::*;
+        match format {
+            Variable(_) => panic!("incorrect value"),
+            TypeName(s) => format!("hash_{}", s.to_snake_case()),
+            Option(f) => format!("(Serde.Comparisons.hash_option {})", Self::quote_hash(f)),
+            Seq(f) => format!("(Serde.Comparisons.hash_list {})", Self::quote_hash(f)),
+            Map { key, value } => format!(
+                "(Serde.Comparisons.hash_map {} {})",
+                Self::quote_hash(key),
+                Self::quote_hash(value)
+            ),
+            Tuple(formats) => {
+                let names = (0..formats.len())
+                    .map(|i| format!("x{}", i))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let hashes = formats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| format!("{} x{}", Self::quote_hash(f), i))
+                    .collect();
+                format!(
+                    "(fun ({}) -> {})",
+                    names,
+                    Self::combine_hashes("1".to_string(), hashes)
+                )
+            }
+            TupleArray { content, size: _ } => format!(
+                "(Serde.Comparisons.hash_array {})",
+                Self::quote_hash(content)
+            ),
+            _ => "Hashtbl.hash".into(),
+        }
+    }
+
+    fn chain_comparisons(comparisons: Vec<String>) -> String {
+        let mut comparisons = comparisons.into_iter().rev();
+        let last = match comparisons.next() {
+            None => return "0".to_string(),
+            Some(c) => c,
+        };
+        comparisons.fold(last, |acc, c| {
+            format!("(let c = {} in if c <> 0 then c else {})", c, acc)
+        })
+    }
+
+    fn combine_hashes(seed: String, hashes: Vec<String>) -> String {
+        hashes.into_iter().fold(seed, |acc, h| {
+            format!("(Serde.Comparisons.hash_combine {} ({}))", acc, h)
+        })
+    }
+
+    /// Whether the variant has a payload, then the comparisons of payloads `a` and `b`
+    /// and the hashes of payload `v`.
+    fn variant_comparison(&self, format: &VariantFormat) -> (bool, Vec<String>, Vec<String>) {
+        use VariantFormat::*;
+        match format {
+            Variable(_) => panic!("incorrect value"),
+            Unit => (false, Vec::new(), Vec::new()),
+            Tuple(fields) if fields.is_empty() => (false, Vec::new(), Vec::new()),
+            Struct(fields) if fields.is_empty() => (false, Vec::new(), Vec::new()),
+            NewType(f) => (
+                true,
+                vec![format!("{} a b", Self::quote_compare(f))],
+                vec![format!("{} v", Self::quote_hash(f))],
+            ),
+            Tuple(fields) => {
+                let format = Format::Tuple(fields.clone());
+                (
+                    true,
+                    vec![format!("{} a b", Self::quote_compare(&format))],
+                    vec![format!("{} v", Self::quote_hash(&format))],
+                )
+            }
+            Struct(fields) => (
+                true,
+                self.fields_comparisons(fields),
+                self.fields_hashes(fields),
+            ),
+        }
+    }
+
+    fn fields_comparisons(&self, fields: &[Named<Format>]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|f| {
+                format!(
+                    "{} a.{name} b.{name}",
+                    Self::quote_compare(&f.value),
+                    name = self.safe_snake_case(&f.name)
+                )
+            })
+            .collect()
+    }
+
+    fn fields_hashes(&self, fields: &[Named<Format>]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|f| {
+                format!(
+                    "{} v.{}",
+                    Self::quote_hash(&f.value),
+                    self.safe_snake_case(&f.name)
+                )
+            })
+            .collect()
+    }
+
+    fn output_enum_comparison(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        let name = name.to_camel_case();
+        writeln!(self.out, "let index = function")?;
+        for (index, variant) in variants {
+            let (has_payload, _, _) = self.variant_comparison(&variant.value);
+            let payload = if has_payload { " _" } else { "" };
+            writeln!(
+                self.out,
+                "  | {}_{}{} -> {}",
+                name, variant.name, payload, index
+            )?;
+        }
+        writeln!(self.out, "in\nmatch a, b with")?;
+        for variant in variants.values() {
+            let (has_payload, comparisons, _) = self.variant_comparison(&variant.value);
+            if has_payload {
+                writeln!(
+                    self.out,
+                    "| {0}_{1} a, {0}_{1} b -> {2}",
+                    name,
+                    variant.name,
+                    Self::chain_comparisons(comparisons)
+                )?;
+            } else {
+                writeln!(self.out, "| {0}_{1}, {0}_{1} -> 0", name, variant.name)?;
+            }
+        }
+        if variants.len() > 1 {
+            writeln!(self.out, "| _ -> Stdlib.compare (index a) (index b)")?;
+        }
+        Ok(())
+    }
+
+    fn output_enum_hash(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        let name = name.to_camel_case();
+        writeln!(self.out, "match v with")?;
+        for (index, variant) in variants {
+            let (has_payload, _, hashes) = self.variant_comparison(&variant.value);
+            let payload = if has_payload { " v" } else { "" };
+            writeln!(
+                self.out,
+                "| {}_{}{} -> {}",
+                name,
+                variant.name,
+                payload,
+                Self::combine_hashes(index.to_string(), hashes)
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Output `compare_x`, `equal_x` and `hash_x` for every type `x` of the registry.
+    fn output_comparisons(&mut self, registry: &Registry) -> Result<()> {
+        use ContainerFormat::*;
+        for (i, (name, format)) in registry.iter().enumerate() {
+            let type_name = self.safe_snake_case(name);
+            writeln!(
+                self.out,
+                "\n{} compare_{} (a : {}) (b : {}) : int =",
+                if i == 0 { "let rec" } else { "and" },
+                name.to_snake_case(),
+                type_name,
+                type_name
+            )?;
+            self.out.indent();
+            match format {
+                UnitStruct => writeln!(self.out, "Stdlib.compare a b")?,
+                NewTypeStruct(format) if Self::is_cyclic(name, format.as_ref()) => {
+                    let variants = Self::cyclic_variants(format);
+                    self.output_enum_comparison(name, &variants)?;
+                }
+                NewTypeStruct(format) => writeln!(self.out, "{} a b", Self::quote_compare(format))?,
+                TupleStruct(formats) => writeln!(
+                    self.out,
+                    "{} a b",
+                    Self::quote_compare(&Format::Tuple(formats.clone()))
+                )?,
+                Struct(fields) => {
+                    let comparisons = self.fields_comparisons(fields);
+                    writeln!(self.out, "{}", Self::chain_comparisons(comparisons))?
+                }
+                OpenEnum(_) => {
+                    let comparisons = self.fields_comparisons(&common::open_enum_fields());
+                    writeln!(self.out, "{}", Self::chain_comparisons(comparisons))?
+                }
+                TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("OCaml", name)),
+                FlattenedStruct(_) => {
+                    return Err(common::flattened_struct_not_supported("OCaml", name))
+                }
+                Enum(variants) => self.output_enum_comparison(name, variants)?,
+            }
+            self.out.unindent();
+        }
+        for name in registry.keys() {
+            writeln!(
+                self.out,
+                "\nlet equal_{0} (a : {1}) (b : {1}) : bool = compare_{0} a b = 0",
+                name.to_snake_case(),
+                self.safe_snake_case(name)
+            )?;
+        }
+        for (i, (name, format)) in registry.iter().enumerate() {
+            writeln!(
+                self.out,
+                "\n{} hash_{} (v : {}) : int =",
+                if i == 0 { "let rec" } else { "and" },
+                name.to_snake_case(),
+                self.safe_snake_case(name)
+            )?;
+            self.out.indent();
+            match format {
+                UnitStruct => writeln!(self.out, "Hashtbl.hash v")?,
+                NewTypeStruct(format) if Self::is_cyclic(name, format.as_ref()) => {
+                    let variants = Self::cyclic_variants(format);
+                    self.output_enum_hash(name, &variants)?;
+                }
+                NewTypeStruct(format) => writeln!(self.out, "{} v", Self::quote_hash(format))?,
+                TupleStruct(formats) => writeln!(
+                    self.out,
+                    "{} v",
+                    Self::quote_hash(&Format::Tuple(formats.clone()))
+                )?,
+                Struct(fields) => {
+                    let hashes = self.fields_hashes(fields);
+                    writeln!(self.out, "{}", Self::combine_hashes("1".into(), hashes))?
+                }
+                OpenEnum(_) => {
+                    let hashes = self.fields_hashes(&common::open_enum_fields());
+                    writeln!(self.out, "{}", Self::combine_hashes("1".into(), hashes))?
+                }
+                TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("OCaml", name)),
+                FlattenedStruct(_) => {
+                    return Err(common::flattened_struct_not_supported("OCaml", name))
+                }
+                Enum(variants) => self.output_enum_hash(name, variants)?,
+            }
+            self.out.unindent();
+        }
+        Ok(())
+    }
+
+    /// Function checking values of the given format, or `ignore` if there is nothing to check.
+    fn quote_validate(&self, format: &Format) -> String {
+        use Format::*;
+        if !common::needs_validation(self.generator.config, format, true) {
+            return "ignore".into();
+        }
+        match format {
+            TypeName(s) => format!("validate_{}", s.to_snake_case()),
+            Option(f) => format!("(Option.iter {})", self.quote_validate(f)),
+            Seq(f) => format!("(List.iter {})", self.quote_validate(f)),
+            Map { key, value } => format!(
+                "(Serde.Validation.validate_map {} {})",
+                self.quote_validate(key),
+                self.quote_validate(value)
+            ),
+            Tuple(formats) => {
+                let mut names = Vec::new();
+                let mut checks = Vec::new();
+                for (i, f) in formats.iter().enumerate() {
+                    if common::needs_validation(self.generator.config, f, true) {
+                        names.push(format!("x{}", i));
+                        checks.push(format!("{} x{}", self.quote_validate(f), i));
+                    } else {
+                        names.push("_".into());
+                    }
+                }
+                format!("(fun ({}) -> {})", names.join(", "), checks.join("; "))
+            }
+            TupleArray { content, size } => format!(
+                "(Serde.Validation.validate_array {} {})",
+                size,
+                self.quote_validate(content)
+            ),
+            _ => panic!("unexpected value"),
+        }
+    }
+
+    /// The checks of the fields of the record `v`, in order.
+    fn fields_validations(&self, fields: &[Named<Format>]) -> Vec<String> {
+        fields
+            .iter()
+            .filter(|f| common::needs_validation(self.generator.config, &f.value, true))
+            .map(|f| {
+                format!(
+                    "{} v.{}",
+                    self.quote_validate(&f.value),
+                    self.safe_snake_case(&f.name)
+                )
+            })
+            .collect()
+    }
+
+    fn chain_validations(validations: Vec<String>) -> String {
+        if validations.is_empty() {
+            "ignore v".into()
+        } else {
+            validations.join("; ")
+        }
+    }
+
+    fn output_enum_validation(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        use VariantFormat::*;
+        let name = name.to_camel_case();
+        let mut cases = Vec::new();
+        for variant in variants.values() {
+            let payload = match &variant.value {
+                NewType(f) => Some(f.as_ref().clone()),
+                Tuple(fields) => Some(Format::Tuple(fields.clone())),
+                Struct(_) | Unit | Variable(_) => None,
+            };
+            let validations = match (&variant.value, payload) {
+                (Struct(fields), _) => self.fields_validations(fields),
+                (_, Some(f)) if common::needs_validation(self.generator.config, &f, true) => {
+                    vec![format!("{} v", self.quote_validate(&f))]
+                }
+                _ => Vec::new(),
+            };
+            if !validations.is_empty() {
+                cases.push((&variant.name, validations));
+            }
+        }
+        if cases.is_empty() {
+            writeln!(self.out, "ignore v")?;
+            return Ok(());
+        }
+        writeln!(self.out, "match v with")?;
+        for (variant, validations) in &cases {
+            writeln!(
+                self.out,
+                "| {}_{} v -> {}",
+                name,
+                variant,
+                validations.join("; ")
+            )?;
+        }
+        if cases.len() < variants.len() {
+            writeln!(self.out, "| _ -> ()")?;
+        }
+        Ok(())
+    }
+
+    /// Output `validate_x` for every type `x` of the registry.
+    fn output_validation(&mut self, registry: &Registry) -> Result<()> {
+        use ContainerFormat::*;
+        for (i, (name, format)) in registry.iter().enumerate() {
+            writeln!(
+                self.out,
+                "\n{} validate_{} (v : {}) : unit =",
+                if i == 0 { "let rec" } else { "and" },
+                name.to_snake_case(),
+                self.safe_snake_case(name)
+            )?;
+            self.out.indent();
+            match format {
+                UnitStruct => writeln!(self.out, "ignore v")?,
+                NewTypeStruct(format) if Self::is_cyclic(name, format.as_ref()) => {
+                    let variants = Self::cyclic_variants(format);
+                    self.output_enum_validation(name, &variants)?;
+                }
+                NewTypeStruct(format) => {
+                    let validation = format!("{} v", self.quote_validate(format));
+                    writeln!(self.out, "{}", validation)?
+                }
+                TupleStruct(formats) => {
+                    let validation =
+                        format!("{} v", self.quote_validate(&Format::Tuple(formats.clone())));
+                    writeln!(self.out, "{}", validation)?
+                }
+                Struct(fields) => {
+                    let validations = self.fields_validations(fields);
+                    writeln!(self.out, "{}", Self::chain_validations(validations))?
+                }
+                OpenEnum(_) => writeln!(self.out, "ignore v")?,
+                TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("OCaml", name)),
+                FlattenedStruct(_) => {
+                    return Err(common::flattened_struct_not_supported("OCaml", name))
+                }
+                Enum(variants) => self.output_enum_validation(name, variants)?,
+            }
+            self.out.unindent();
+        }
+        Ok(())
+    }
+
+    /// Output the tags of the variants of an open enum known at generation time, and a registry
+    /// of handlers so that clients may decode the variants that they know and skip others.
+    fn output_open_enum_handlers(
+        &mut self,
+        name: &str,
+        formats: &BTreeMap<String, Format>,
+    ) -> Result<()> {
+        let tags = formats
+            .keys()
+            .map(|tag| format!("{:?}", tag))
+            .collect::<Vec<_>>()
+            .join("; ");
+        writeln!(
+            self.out,
+            r#"
+let {0}_tags = [{2}]
+
+let {0}_handlers : (string, bytes -> unit) Hashtbl.t = Hashtbl.create 8
+
+(* Register the handler of the variants with the given tag. Handlers receive the encoded
+   content of the variants. *)
+let register_{0}_handler (tag : string) (handler : bytes -> unit) : unit =
+  Hashtbl.replace {0}_handlers tag handler
+
+(* Call the handler registered for the tag of [v], if any. Values with unknown tags are
+   skipped and return [false]. *)
+let handle_{0} (v : {1}) : bool =
+  match Hashtbl.find_opt {0}_handlers v.tag with
+  | None -> false
+  | Some handler -> handler v.content; true"#,
+            name.to_snake_case(),
+            self.safe_snake_case(name),
+            tags
+        )?;
+        Ok(())
+    }
+
+    /// Cyclic newtypes are emitted as an enum with a single unnamed variant.
+    fn cyclic_variants(format: &Format) -> BTreeMap<u32, Named<VariantFormat>> {
+        let mut map = BTreeMap::new();
+        map.insert(
+            0,
+            Named {
+                name: String::new(),
+                value: VariantFormat::NewType(Box::new(format.clone())),
+            },
+        );
+        map
+    }
+
     fn output_container(
         &mut self,
         name: &str,
@@ -308,14 +883,7 @@ where
                 writeln!(self.out)?;
             }
             NewTypeStruct(format) if Self::is_cyclic(name, format.as_ref()) => {
-                let mut map = BTreeMap::new();
-                map.insert(
-                    0,
-                    Named {
-                        name: String::new(),
-                        value: VariantFormat::NewType(format.clone()),
-                    },
-                );
+                let map = Self::cyclic_variants(format);
                 self.output_enum(&name.to_camel_case(), &map, true)?;
             }
             NewTypeStruct(format) => {
@@ -333,6 +901,15 @@ where
                 self.output_record(fields)?;
                 writeln!(self.out)?;
             }
+            OpenEnum(_) => {
+                write!(self.out, " ")?;
+                self.output_record(&common::open_enum_fields())?;
+                writeln!(self.out)?;
+            }
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("OCaml", name)),
+            FlattenedStruct(_) => {
+                return Err(common::flattened_struct_not_supported("OCaml", name))
+            }
             Enum(variants) => {
                 self.output_enum(&name.to_camel_case(), variants, false)?;
             }
@@ -345,6 +922,29 @@ where
     }
 }
 
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+    ) -> std::result::Result<crate::SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let mut files = crate::SourceFiles::new();
+        files.insert_bytes(
+            std::path::Path::new(&self.config.module_name)
+                .join(format!("{}.ml", self.config.module_name.to_snake_case())),
+            content,
+        );
+        Ok(files)
+    }
+}
+
 pub struct Installer {
     install_dir: PathBuf,
 }
@@ -354,11 +954,7 @@ impl Installer {
         Installer { install_dir }
     }
 
-    fn install_runtime(
-        &self,
-        source_dir: include_dir::Dir,
-        path: &str,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    fn install_runtime(&self, source_dir: include_dir::Dir, path: &str) -> Result<()> {
         let dir_path = self.install_dir.join(path);
         std::fs::create_dir_all(&dir_path)?;
         for entry in source_dir.files() {
@@ -370,7 +966,7 @@ impl Installer {
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
@@ -393,6 +989,10 @@ impl crate::SourceInstaller for Installer {
                     match enc {
                         Encoding::Bcs => runtime_str = "\n(libraries bcs_runtime)",
                         Encoding::Bincode => runtime_str = "\n(libraries bincode_runtime)",
+                        Encoding::Postcard => runtime_str = "\n(libraries postcard_runtime)",
+                        Encoding::Borsh => runtime_str = "\n(libraries borsh_runtime)",
+                        Encoding::Scale => runtime_str = "\n(libraries scale_runtime)",
+                        Encoding::Json => (),
                     }
                 }
             }
@@ -404,10 +1004,8 @@ impl crate::SourceInstaller for Installer {
             )?;
         }
 
-        let source_path = dir_path.join(format!("{}.ml", name));
-        let mut file = std::fs::File::create(source_path)?;
         let generator = CodeGenerator::new(config);
-        generator.output(&mut file, registry)?;
+        generator.generate(registry)?.write_to(&self.install_dir)?;
         Ok(())
     }
 
@@ -433,4 +1031,35 @@ impl crate::SourceInstaller for Installer {
         self.install_runtime(include_directory!("runtime/ocaml/serde"), "serde")?;
         self.install_runtime(include_directory!("runtime/ocaml/bcs"), "bcs")
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/ocaml/common"), "common")?;
+        self.install_runtime(include_directory!("runtime/ocaml/virtual"), "virtual")?;
+        self.install_runtime(include_directory!("runtime/ocaml/ppx"), "ppx")?;
+        self.install_runtime(include_directory!("runtime/ocaml/serde"), "serde")?;
+        self.install_runtime(include_directory!("runtime/ocaml/postcard"), "postcard")
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/ocaml/common"), "common")?;
+        self.install_runtime(include_directory!("runtime/ocaml/virtual"), "virtual")?;
+        self.install_runtime(include_directory!("runtime/ocaml/ppx"), "ppx")?;
+        self.install_runtime(include_directory!("runtime/ocaml/serde"), "serde")?;
+        self.install_runtime(include_directory!("runtime/ocaml/borsh"), "borsh")
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/ocaml/common"), "common")?;
+        self.install_runtime(include_directory!("runtime/ocaml/virtual"), "virtual")?;
+        self.install_runtime(include_directory!("runtime/ocaml/ppx"), "ppx")?;
+        self.install_runtime(include_directory!("runtime/ocaml/serde"), "serde")?;
+        self.install_runtime(include_directory!("runtime/ocaml/scale"), "scale")
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the json encoding",
+        ))
+    }
 }
@@ -2,17 +2,49 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::{
+    common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig, Encoding,
+    naming, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error, IntEncoding, Result,
+    SourceGenerator, TrailingBytes,
 };
 use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
 use std::{
     collections::{BTreeMap, HashMap},
-    io::{Result, Write},
+    io::Write,
     path::PathBuf,
 };
 
+/// Features supported by the Python 3 code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Python 3",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: crate::capabilities::ALL_ENCODINGS_AND_JSON,
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: true,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Reserved words of Python, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
 /// Main configuration object for code-generation in Python.
+#[derive(Clone)]
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
     config: &'a CodeGeneratorConfig,
@@ -37,9 +69,6 @@ struct PythonEmitter<'a, T> {
 impl<'a> CodeGenerator<'a> {
     /// Create a Python code generator for the given config.
     pub fn new(config: &'a CodeGeneratorConfig) -> Self {
-        if config.c_style_enums {
-            panic!("Python 3 does not support generating c-style enums");
-        }
         let mut external_qualified_names = HashMap::new();
         for (module_path, names) in &config.external_definitions {
             let module = {
@@ -69,6 +98,17 @@ impl<'a> CodeGenerator<'a> {
 
     /// Write container definitions in Python.
     pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config)
+                .with_serde_package_name(self.serde_package_name.clone())
+                .output(out, &registry);
+        }
         let current_namespace = self
             .config
             .module_name
@@ -84,6 +124,11 @@ impl<'a> CodeGenerator<'a> {
         for (name, format) in registry {
             emitter.output_container(name, format)?;
         }
+        for (name, format) in registry {
+            if let ContainerFormat::OpenEnum(formats) = format {
+                emitter.output_open_enum_variants(name, formats)?;
+            }
+        }
         Ok(())
     }
 }
@@ -116,7 +161,12 @@ import typing
             from_serde_package,
         )?;
         for encoding in &self.generator.config.encodings {
-            writeln!(self.out, "{}import {}", from_serde_package, encoding.name())?;
+            writeln!(
+                self.out,
+                "{}import {}",
+                from_serde_package,
+                runtime_module(*encoding)
+            )?;
         }
         for module in self.generator.config.external_definitions.keys() {
             writeln!(self.out, "{}\n", self.quote_import(module))?;
@@ -230,6 +280,47 @@ import typing
         Ok(())
     }
 
+    fn quote_ordered_base(&self) -> &'static str {
+        if self.generator.config.comparisons {
+            "(st.Ordered)"
+        } else {
+            ""
+        }
+    }
+
+    /// Ordering follows the Rust derive: variant index first, then fields in order.
+    /// Lists and dicts are not hashable, hence the explicit `__hash__` over the sort key.
+    fn output_comparison_methods(
+        &mut self,
+        variant_index: Option<u32>,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        if !self.generator.config.comparisons {
+            return Ok(());
+        }
+        let mut keys = Vec::new();
+        if let Some(index) = variant_index {
+            keys.push(index.to_string());
+        }
+        for field in fields {
+            keys.push(format!("st.sort_key(self.{})", field.name));
+        }
+        writeln!(
+            self.out,
+            r#"
+def _sort_key(self) -> typing.Any:
+    return ({})
+
+def __hash__(self) -> int:
+    return hash(self._sort_key())"#,
+            match keys.len() {
+                1 => format!("{},", keys[0]),
+                _ => keys.join(", "),
+            }
+        )?;
+        Ok(())
+    }
+
     fn output_variant(
         &mut self,
         base: &str,
@@ -260,15 +351,18 @@ import typing
         )?;
         self.out.indent();
         self.output_comment(name)?;
-        if self.generator.config.serialization {
+        if self.generator.config.serialization || self.generator.config.comparisons {
             writeln!(self.out, "INDEX = {}  # type: int", index)?;
         }
+        self.output_json_kind(common::JsonShape::of_variant(variant))?;
         self.current_namespace.push(name.to_string());
         self.output_fields(&fields)?;
+        self.output_comparison_methods(Some(index), &fields)?;
         self.output_custom_code()?;
         self.current_namespace.pop();
         self.out.unindent();
-        writeln!(self.out)
+        writeln!(self.out)?;
+        Ok(())
     }
 
     fn output_enum_container(
@@ -276,7 +370,7 @@ import typing
         name: &str,
         variants: &BTreeMap<u32, Named<VariantFormat>>,
     ) -> Result<()> {
-        writeln!(self.out, "\nclass {}:", name)?;
+        writeln!(self.out, "\nclass {}{}:", name, self.quote_ordered_base())?;
         self.out.indent();
         self.output_comment(name)?;
         self.current_namespace.push(name.to_string());
@@ -291,8 +385,12 @@ import typing
                 self.output_deserialize_method_for_encoding(name, *encoding)?;
             }
         }
+        self.output_validate_method(name)?;
         let wrote_custom_code = self.output_custom_code()?;
-        if !self.generator.config.serialization && !wrote_custom_code {
+        if !self.generator.config.serialization
+            && !self.generator.config.generates_validation()
+            && !wrote_custom_code
+        {
             writeln!(self.out, "pass")?;
         }
         writeln!(self.out)?;
@@ -318,19 +416,73 @@ import typing
         Ok(())
     }
 
+    /// The JSON encoding of dataclasses depends on the kind of container they come from: e.g.
+    /// a struct with a single field `value` is written as an object, unlike a newtype struct.
+    fn output_json_kind(&mut self, shape: common::JsonShape) -> Result<()> {
+        if self.generator.config.serialization
+            && self.generator.config.encodings.contains(&Encoding::Json)
+        {
+            writeln!(self.out, "JSON_KIND = \"{}\"  # type: str", shape.name())?;
+        }
+        Ok(())
+    }
+
+    fn output_validate_method(&mut self, name: &str) -> Result<()> {
+        if !self.generator.config.generates_validation() {
+            return Ok(());
+        }
+        writeln!(
+            self.out,
+            r#"
+def validate(self) -> None:
+    st.validate(self, {})"#,
+            name
+        )?;
+        Ok(())
+    }
+
     fn output_serialize_method_for_encoding(
         &mut self,
         name: &str,
         encoding: Encoding,
     ) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+def json_serialize(self) -> str:{}
+    return serde_json.serialize(self, {})"#,
+                if self.generator.config.validate_on_serialize {
+                    "\n    self.validate()"
+                } else {
+                    ""
+                },
+                name
+            )?;
+            return Ok(());
+        }
         writeln!(
             self.out,
             r#"
-def {0}_serialize(self) -> bytes:
-    return {0}.serialize(self, {1})"#,
+def {0}_serialize(self) -> bytes:{1}{2}"#,
             encoding.name(),
-            name
-        )
+            if self.generator.config.validate_on_serialize {
+                "\n    self.validate()"
+            } else {
+                ""
+            },
+            self.serialization_statements(
+                encoding,
+                &format!(
+                    "{}.serialize(self, {}{})",
+                    encoding.name(),
+                    name,
+                    self.runtime_arguments(encoding)
+                ),
+                |output| output.to_string()
+            ),
+        )?;
+        Ok(())
     }
 
     fn output_deserialize_method_for_encoding(
@@ -338,18 +490,166 @@ def {0}_serialize(self) -> bytes:
         name: &str,
         encoding: Encoding,
     ) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+@staticmethod
+def json_deserialize(input: str) -> '{0}':
+    return serde_json.deserialize(input, {0})"#,
+                name
+            )?;
+            return Ok(());
+        }
         writeln!(
             self.out,
             r#"
 @staticmethod
 def {0}_deserialize(input: bytes) -> '{1}':
-    v, buffer = {0}.deserialize(input, {1})
-    if buffer:
-        raise st.DeserializationError("Some input bytes were not read");
+    v, buffer = {0}.deserialize(input, {1}{2}){3}
     return v"#,
             encoding.name(),
+            name,
+            self.deserialization_arguments(encoding),
+            self.deserialization_checks(encoding),
+        )?;
+        Ok(())
+    }
+
+    /// Additional arguments of the runtime functions `serialize` and `deserialize`.
+    fn runtime_arguments(&self, encoding: Encoding) -> String {
+        let options = self.generator.config.bincode_options_for(encoding);
+        let mut arguments = String::new();
+        if options.int_encoding == IntEncoding::Varint {
+            arguments += ", varint_encoding=True";
+        }
+        if options.endianness == Endianness::Big {
+            arguments += ", big_endian=True";
+        }
+        arguments
+    }
+
+    /// Additional arguments of the runtime function `deserialize`, which enforces the size limit
+    /// of the encoding while reading.
+    fn deserialization_arguments(&self, encoding: Encoding) -> String {
+        let mut arguments = self.runtime_arguments(encoding);
+        if let Some(limit) = self.generator.config.bincode_options_for(encoding).limit {
+            arguments += &format!(", limit={}", limit);
+        }
+        arguments
+    }
+
+    /// Statements returning `result(output)` where `output` is the result of the serialization
+    /// function `call`.
+    fn serialization_statements(
+        &self,
+        encoding: Encoding,
+        call: &str,
+        result: impl Fn(&str) -> String,
+    ) -> String {
+        match self.generator.config.bincode_options_for(encoding).limit {
+            Some(limit) => format!(
+                r#"
+    output = {}
+    if len(output) > {}:
+        raise st.SerializationError("Exceeded the size limit of the encoding")
+    return {}"#,
+                call,
+                limit,
+                result("output")
+            ),
+            None => format!("\n    return {}", result(call)),
+        }
+    }
+
+    /// Checks on the bytes `buffer` left by the runtime after reading the input.
+    fn deserialization_checks(&self, encoding: Encoding) -> &'static str {
+        match self
+            .generator
+            .config
+            .bincode_options_for(encoding)
+            .trailing_bytes
+        {
+            TrailingBytes::Reject => {
+                r#"
+    if buffer:
+        raise st.DeserializationError("Some input bytes were not read");"#
+            }
+            TrailingBytes::Allow => "",
+        }
+    }
+
+    fn output_open_enum_registration(&mut self, name: &str) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+VARIANTS: typing.ClassVar[typing.Dict[str, typing.Any]] = {{}}
+
+@staticmethod
+def register(tag: str, content_type: typing.Any) -> None:
+    """Register the type of the content of the variants with the given tag."""
+    {0}.VARIANTS[tag] = content_type"#,
             name
-        )
+        )?;
+        Ok(())
+    }
+
+    fn output_open_enum_methods_for_encoding(
+        &mut self,
+        name: &str,
+        encoding: Encoding,
+    ) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+@staticmethod
+def {0}_encode(tag: str, value: typing.Any) -> '{1}':{3}
+
+def {0}_decode(self) -> typing.Optional[typing.Any]:
+    """Decode the content of the variant, or return None if the tag is unknown."""
+    content_type = {1}.VARIANTS.get(self.tag)
+    if content_type is None:
+        return None
+    v, buffer = {0}.deserialize(self.content, content_type{2}){4}
+    return v"#,
+            encoding.name(),
+            name,
+            self.deserialization_arguments(encoding),
+            self.serialization_statements(
+                encoding,
+                &format!(
+                    "{}.serialize(value, {}.VARIANTS[tag]{})",
+                    encoding.name(),
+                    name,
+                    self.runtime_arguments(encoding)
+                ),
+                |output| format!("{}(tag=tag, content={})", name, output)
+            ),
+            self.deserialization_checks(encoding),
+        )?;
+        Ok(())
+    }
+
+    /// Register the variants of open enums that are known at generation time.
+    fn output_open_enum_variants(
+        &mut self,
+        name: &str,
+        formats: &BTreeMap<String, Format>,
+    ) -> Result<()> {
+        for (tag, format) in formats {
+            let content_type = match format {
+                // Types are defined at this point: no need for quotes.
+                Format::TypeName(x) => self
+                    .generator
+                    .external_qualified_names
+                    .get(x)
+                    .cloned()
+                    .unwrap_or_else(|| x.to_string()),
+                _ => self.quote_type(format),
+            };
+            writeln!(self.out, "{}.register(\"{}\", {})", name, tag, content_type)?;
+        }
+        Ok(())
     }
 
     fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
@@ -365,6 +665,11 @@ def {0}_deserialize(input: bytes) -> '{1}':
                 value: Format::Tuple(formats.clone()),
             }],
             Struct(fields) => fields.clone(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("Python 3", name)),
+            FlattenedStruct(_) => {
+                return Err(common::flattened_struct_not_supported("Python 3", name))
+            }
             Enum(variants) => {
                 // Enum case.
                 self.output_enum_container(name, variants)?;
@@ -372,19 +677,65 @@ def {0}_deserialize(input: bytes) -> '{1}':
             }
         };
         // Struct case.
-        writeln!(self.out, "\n@dataclass(frozen=True)\nclass {}:", name)?;
+        writeln!(
+            self.out,
+            "\n@dataclass(frozen=True)\nclass {}{}:",
+            name,
+            self.quote_ordered_base()
+        )?;
         self.out.indent();
         self.output_comment(name)?;
         self.current_namespace.push(name.to_string());
         self.output_fields(&fields)?;
+        self.output_json_kind(common::JsonShape::of_container(format))?;
+        self.output_comparison_methods(None, &fields)?;
+        if let OpenEnum(_) = format {
+            self.output_open_enum_registration(name)?;
+        }
+        self.output_validate_method(name)?;
         for encoding in &self.generator.config.encodings {
             self.output_serialize_method_for_encoding(name, *encoding)?;
             self.output_deserialize_method_for_encoding(name, *encoding)?;
+            if let OpenEnum(_) = format {
+                self.output_open_enum_methods_for_encoding(name, *encoding)?;
+            }
         }
         self.output_custom_code()?;
         self.current_namespace.pop();
         self.out.unindent();
-        writeln!(self.out)
+        writeln!(self.out)?;
+        Ok(())
+    }
+}
+
+/// The Python module of the runtime of the given encoding. The JSON runtime is not called
+/// `json` so as not to shadow the standard library.
+fn runtime_module(encoding: Encoding) -> &'static str {
+    match encoding {
+        Encoding::Json => "serde_json",
+        _ => encoding.name(),
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+    ) -> std::result::Result<crate::SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let mut files = crate::SourceFiles::new();
+        files.insert_bytes(
+            std::path::Path::new(&self.config.module_name).join("__init__.py"),
+            content,
+        );
+        Ok(files)
     }
 }
 
@@ -402,7 +753,7 @@ impl Installer {
         }
     }
 
-    fn create_module_init_file(&self, name: &str) -> Result<std::fs::File> {
+    fn create_module_init_file(&self, name: &str) -> std::io::Result<std::fs::File> {
         let dir_path = self.install_dir.join(name);
         std::fs::create_dir_all(&dir_path)?;
         std::fs::File::create(dir_path.join("__init__.py"))
@@ -425,17 +776,16 @@ impl Installer {
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
         config: &crate::CodeGeneratorConfig,
         registry: &Registry,
     ) -> std::result::Result<(), Self::Error> {
-        let mut file = self.create_module_init_file(&config.module_name)?;
         let generator =
             CodeGenerator::new(config).with_serde_package_name(self.serde_package_name.clone());
-        generator.output(&mut file, registry)?;
+        generator.generate(registry)?.write_to(&self.install_dir)?;
         Ok(())
     }
 
@@ -474,4 +824,44 @@ impl crate::SourceInstaller for Installer {
         )?;
         Ok(())
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_module_init_file("postcard")?;
+        write!(
+            file,
+            "{}",
+            self.fix_serde_package(include_str!("../runtime/python/postcard/__init__.py"))
+        )?;
+        Ok(())
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_module_init_file("borsh")?;
+        write!(
+            file,
+            "{}",
+            self.fix_serde_package(include_str!("../runtime/python/borsh/__init__.py"))
+        )?;
+        Ok(())
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_module_init_file("scale")?;
+        write!(
+            file,
+            "{}",
+            self.fix_serde_package(include_str!("../runtime/python/scale/__init__.py"))
+        )?;
+        Ok(())
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        let mut file = self.create_module_init_file("serde_json")?;
+        write!(
+            file,
+            "{}",
+            self.fix_serde_package(include_str!("../runtime/python/serde_json/__init__.py"))
+        )?;
+        Ok(())
+    }
 }
@@ -0,0 +1,1293 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{
+    common,
+    indent::{IndentConfig, IndentedWriter},
+    naming, BincodeOptions, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error,
+    IntEncoding, Result, SourceFiles, SourceGenerator, TrailingBytes,
+};
+use heck::CamelCase;
+use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Write,
+    path::PathBuf,
+};
+
+/// Features supported by the Ruby code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Ruby",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: &[Encoding::Bincode, Encoding::Bcs],
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: false,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Keywords of Ruby, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "BEGIN",
+    "END",
+    "__ENCODING__",
+    "__FILE__",
+    "__LINE__",
+    "alias",
+    "and",
+    "begin",
+    "break",
+    "case",
+    "class",
+    "def",
+    "defined?",
+    "do",
+    "else",
+    "elsif",
+    "end",
+    "ensure",
+    "false",
+    "for",
+    "if",
+    "in",
+    "module",
+    "next",
+    "nil",
+    "not",
+    "or",
+    "redo",
+    "rescue",
+    "retry",
+    "return",
+    "self",
+    "super",
+    "then",
+    "true",
+    "undef",
+    "unless",
+    "until",
+    "when",
+    "while",
+    "yield",
+];
+
+/// Main configuration object for code-generation in Ruby.
+///
+/// Containers are generated as immutable `Data` classes (Ruby >= 3.2). Enums are generated
+/// as modules: each variant is a `Data` class nested in the module of the enum and including
+/// it, so that `value.is_a?(MyEnum)` holds for all variants. `Data` classes define structural
+/// `eql?` and `hash` methods. With comparisons, they also include `Comparable` and define `<=>`.
+#[derive(Clone)]
+pub struct CodeGenerator<'a> {
+    /// Language-independent configuration.
+    config: &'a CodeGeneratorConfig,
+    /// Mapping from external type names to fully-qualified constants (e.g. "MyClass" -> "::MyOrg::MyModule::MyClass").
+    /// Assumes that the corresponding files are required (e.g. `require 'my_org/my_module'`).
+    /// Derived from `config.external_definitions`.
+    external_qualified_names: HashMap<String, String>,
+}
+
+/// Shared state for the code generation of a Ruby source file.
+struct RubyEmitter<'a, T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// Generator.
+    generator: &'a CodeGenerator<'a>,
+    /// Fully-qualified constant of the generated module (e.g. "::MyOrg::MyModule").
+    module_constant: String,
+    /// Current namespace (e.g. vec!["my_org", "my_module", "MyClass"])
+    current_namespace: Vec<String>,
+    /// Current (non-qualified) generated class names that could clash with names in the registry
+    /// (e.g. variant classes).
+    /// * We count multiplicities to allow inplace backtracking.
+    /// * Names in the registry are assumed to never clash.
+    current_reserved_names: HashMap<String, usize>,
+    /// Whether nothing was written yet in the body of the current class or module, so that
+    /// the next definition does not need a blank line before it.
+    at_beginning_of_body: bool,
+}
+
+impl<'a> CodeGenerator<'a> {
+    /// Create a Ruby code generator for the given config.
+    pub fn new(config: &'a CodeGeneratorConfig) -> Self {
+        let mut external_qualified_names = HashMap::new();
+        for (module_path, names) in &config.external_definitions {
+            for name in names {
+                external_qualified_names.insert(
+                    name.to_string(),
+                    format!("{}::{}", quote_module_constant(module_path), name),
+                );
+            }
+        }
+        Self {
+            config,
+            external_qualified_names,
+        }
+    }
+
+    /// Write container definitions in Ruby.
+    pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).output(out, &registry);
+        }
+        for (name, format) in registry {
+            Self::check_container(name, format)?;
+        }
+        let mut emitter = RubyEmitter {
+            out: IndentedWriter::new(out, IndentConfig::Space(2)),
+            generator: self,
+            module_constant: quote_module_constant(&self.config.module_name),
+            current_namespace: self
+                .config
+                .module_name
+                .split('.')
+                .map(String::from)
+                .collect(),
+            current_reserved_names: HashMap::new(),
+            at_beginning_of_body: true,
+        };
+        emitter.output_preamble()?;
+        emitter.output_open_modules()?;
+        for (name, format) in registry {
+            emitter.output_container(name, format)?;
+        }
+        if self.config.serialization
+            || self.config.generates_validation()
+            || self.config.comparisons
+        {
+            emitter.output_trait_helpers(registry)?;
+        }
+        emitter.output_close_modules()?;
+        Ok(())
+    }
+
+    /// Classes and modules are Ruby constants, which must start with an uppercase letter.
+    /// Options are mapped to `nil` or to their content, hence nested options and options of
+    /// unit values cannot be told apart from `None`.
+    fn check_container(name: &str, format: &ContainerFormat) -> Result<()> {
+        let mut names = vec![name];
+        if let ContainerFormat::Enum(variants) = format {
+            names.extend(variants.values().map(|variant| variant.name.as_str()));
+        }
+        if !names
+            .iter()
+            .all(|name| name.starts_with(|c: char| c.is_ascii_uppercase()))
+        {
+            return Err(Error::unsupported(
+                CAPABILITIES.language,
+                "names of types and variants that do not start with an uppercase letter",
+            )
+            .at(name));
+        }
+        let mut result = Ok(());
+        format
+            .visit(&mut |format| {
+                if result.is_ok() {
+                    if let Format::Option(content) = format {
+                        if matches!(content.as_ref(), Format::Option(_) | Format::Unit) {
+                            result = Err(Error::unsupported(
+                                CAPABILITIES.language,
+                                "options of options or of unit values",
+                            ));
+                        }
+                    }
+                }
+                Ok(())
+            })
+            .unwrap();
+        result.map_err(|error| error.at(name))
+    }
+}
+
+/// The Ruby constant of a module path such as "my_org.my_module", e.g. "::MyOrg::MyModule".
+fn quote_module_constant(module_path: &str) -> String {
+    module_path
+        .split('.')
+        .flat_map(|name| ["::".to_string(), name.to_camel_case()])
+        .collect()
+}
+
+/// The path to `require` for a module path such as "my_org.my_module", e.g. "my_org/my_module".
+fn quote_require_path(module_path: &str) -> String {
+    module_path.replace('.', "/")
+}
+
+impl<'a, T> RubyEmitter<'a, T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(self.out, "# frozen_string_literal: true")?;
+        let mut paths = Vec::new();
+        if self.generator.config.serialization {
+            paths.push("serde".to_string());
+            for encoding in &self.generator.config.encodings {
+                paths.push(encoding.name().to_string());
+            }
+        }
+        for module in self.generator.config.external_definitions.keys() {
+            paths.push(quote_require_path(module));
+        }
+        if !paths.is_empty() {
+            writeln!(self.out)?;
+        }
+        for path in paths {
+            writeln!(self.out, "require '{}'", path)?;
+        }
+        Ok(())
+    }
+
+    fn output_open_modules(&mut self) -> Result<()> {
+        writeln!(self.out)?;
+        for name in self.generator.config.module_name.split('.') {
+            writeln!(self.out, "module {}", name.to_camel_case())?;
+            self.out.indent();
+        }
+        self.at_beginning_of_body = true;
+        Ok(())
+    }
+
+    fn output_close_modules(&mut self) -> Result<()> {
+        for _ in self.generator.config.module_name.split('.') {
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+        }
+        Ok(())
+    }
+
+    /// Separate the definition about to be written from the previous one in the same body.
+    fn start_definition(&mut self) -> Result<()> {
+        if !self.at_beginning_of_body {
+            writeln!(self.out)?;
+        }
+        self.at_beginning_of_body = false;
+        Ok(())
+    }
+
+    /// Compute a safe reference to the registry type `name` in the given context.
+    /// Names marked as "reserved" (e.g. by variant classes) are qualified by the generated
+    /// module.
+    fn quote_qualified_name(&self, name: &str) -> String {
+        match self.generator.external_qualified_names.get(name) {
+            Some(qname) => qname.clone(),
+            None if self.current_reserved_names.contains_key(name) => {
+                format!("{}::{}", self.module_constant, name)
+            }
+            None => name.to_string(),
+        }
+    }
+
+    fn comment(&self, name: &str) -> Option<&'a String> {
+        let mut path = self.current_namespace.clone();
+        path.push(name.to_string());
+        self.generator.config.comments.get(&path)
+    }
+
+    fn output_comment(&mut self, name: &str) -> std::io::Result<()> {
+        if let Some(doc) = self.comment(name) {
+            let text = textwrap::indent(doc, "# ").replace("\n\n", "\n#\n");
+            write!(self.out, "{}", text)?;
+        }
+        Ok(())
+    }
+
+    fn output_custom_code(&mut self) -> Result<()> {
+        if let Some(code) = self
+            .generator
+            .config
+            .custom_code
+            .get(&self.current_namespace)
+        {
+            self.start_definition()?;
+            writeln!(self.out, "{}", code)?;
+        }
+        Ok(())
+    }
+
+    fn enter_class(&mut self, name: &str, reserved_subclass_names: &[&str]) {
+        self.out.indent();
+        self.current_namespace.push(name.to_string());
+        for name in reserved_subclass_names {
+            let entry = self
+                .current_reserved_names
+                .entry(name.to_string())
+                .or_insert(0);
+            *entry += 1;
+        }
+        self.at_beginning_of_body = true;
+    }
+
+    fn leave_class(&mut self, reserved_subclass_names: &[&str]) {
+        self.out.unindent();
+        self.current_namespace.pop();
+        for name in reserved_subclass_names {
+            let entry = self.current_reserved_names.get_mut(*name).unwrap();
+            *entry -= 1;
+            if *entry == 0 {
+                self.current_reserved_names.remove(*name);
+            }
+        }
+        self.at_beginning_of_body = false;
+    }
+
+    fn output_trait_helpers(&mut self, registry: &Registry) -> Result<()> {
+        let mut subtypes = BTreeMap::new();
+        for format in registry.values() {
+            format
+                .visit(&mut |f| {
+                    if Self::needs_helper(f) {
+                        subtypes.insert(common::mangle_type(f), f.clone());
+                    }
+                    Ok(())
+                })
+                .unwrap();
+        }
+        if subtypes.is_empty() {
+            return Ok(());
+        }
+        self.start_definition()?;
+        writeln!(self.out, "module TraitHelpers")?;
+        let reserved_names = &[];
+        self.enter_class("TraitHelpers", reserved_names);
+        writeln!(self.out, "module_function")?;
+        for (mangled_name, subtype) in &subtypes {
+            if self.generator.config.serialization {
+                self.output_serialization_helper(mangled_name, subtype)?;
+                self.output_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.generates_validation()
+                && common::needs_validation(self.generator.config, subtype, true)
+            {
+                self.output_validation_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.comparisons {
+                self.output_comparison_helper(mangled_name, subtype)?;
+            }
+        }
+        self.leave_class(reserved_names);
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    fn needs_helper(format: &Format) -> bool {
+        use Format::*;
+        matches!(
+            format,
+            Option(_) | Seq(_) | Map { .. } | Tuple(_) | TupleArray { .. }
+        )
+    }
+
+    fn quote_serialize_value(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) => format!("{}.serialize(serializer)", value),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "serializer.serialize_{}({})",
+                common::mangle_type(format),
+                value
+            ),
+            _ => format!(
+                "{}.serialize_{}({}, serializer)",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn quote_deserialize(&self, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!(
+                "{}.deserialize(deserializer)",
+                self.quote_qualified_name(name)
+            ),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => {
+                format!("deserializer.deserialize_{}", common::mangle_type(format))
+            }
+            _ => format!(
+                "{}.deserialize_{}(deserializer)",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+            ),
+        }
+    }
+
+    fn output_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(self.out, "\ndef serialize_{}(value, serializer)", name)?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if value.nil?
+  serializer.serialize_option_tag(false)
+else
+  serializer.serialize_option_tag(true)
+  {}
+end
+"#,
+                    self.quote_serialize_value("value", format)
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+serializer.serialize_len(value.size)
+value.each do |item|
+  {}
+end
+"#,
+                    self.quote_serialize_value("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+serializer.serialize_len(value.size)
+offsets = []
+value.each do |key, item|
+  offsets << serializer.buffer_offset
+  {}
+  {}
+end
+serializer.sort_map_entries(offsets)
+"#,
+                    self.quote_serialize_value("key", key),
+                    self.quote_serialize_value("item", value)
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    r#"
+unless value.size == {0}
+  raise ::Serde::SerializationError, "Invalid length for tuple: #{{value.size}} instead of {0}"
+end
+"#,
+                    formats.len()
+                )?;
+                for (index, format) in formats.iter().enumerate() {
+                    let expr = format!("value[{}]", index);
+                    writeln!(self.out, "{}", self.quote_serialize_value(&expr, format))?;
+                }
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+unless value.size == {0}
+  raise ::Serde::SerializationError, "Invalid length for fixed-size array: #{{value.size}} instead of {0}"
+end
+value.each do |item|
+  {1}
+end
+"#,
+                    size,
+                    self.quote_serialize_value("item", content),
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(self.out, "\ndef deserialize_{}(deserializer)", name)?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+deserializer.deserialize_option_tag ? {} : nil
+"#,
+                    self.quote_deserialize(format),
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+length = deserializer.deserialize_len
+result = []
+length.times do
+  result << {}
+end
+result
+"#,
+                    self.quote_deserialize(format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+length = deserializer.deserialize_len
+result = {{}}
+previous_key = nil
+length.times do
+  key_start = deserializer.buffer_offset
+  key = {0}
+  key_end = deserializer.buffer_offset
+  current_key = key_start...key_end
+  deserializer.check_that_key_slices_are_increasing(previous_key, current_key) unless previous_key.nil?
+  previous_key = current_key
+  result[key] = {1}
+end
+result
+"#,
+                    self.quote_deserialize(key),
+                    self.quote_deserialize(value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    r#"
+[{}
+]
+"#,
+                    formats
+                        .iter()
+                        .map(|f| format!("\n  {},", self.quote_deserialize(f)))
+                        .collect::<Vec<_>>()
+                        .join("")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+::Array.new({}) {{ {} }}
+"#,
+                    size,
+                    self.quote_deserialize(content)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    /// A statement validating `value`, assuming that `common::needs_validation` holds.
+    fn quote_validate(&self, value: &str, format: &Format) -> String {
+        match format {
+            Format::TypeName(_) => format!("{}.validate", value),
+            _ => format!(
+                "{}.validate_{}({})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn output_validation_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(self.out, "\ndef validate_{}(value)", name)?;
+        self.out.indent();
+        let needs_validation =
+            |format| common::needs_validation(self.generator.config, format, true);
+        match format0 {
+            Option(format) => {
+                writeln!(
+                    self.out,
+                    "{} unless value.nil?",
+                    self.quote_validate("value", format)
+                )?;
+            }
+
+            Seq(format) => {
+                writeln!(
+                    self.out,
+                    "value.each {{ |item| {} }}",
+                    self.quote_validate("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                writeln!(self.out, "value.each do |key, item|")?;
+                if needs_validation(key) {
+                    writeln!(self.out, "  {}", self.quote_validate("key", key))?;
+                }
+                if needs_validation(value) {
+                    writeln!(self.out, "  {}", self.quote_validate("item", value))?;
+                }
+                writeln!(self.out, "end")?;
+            }
+
+            Tuple(formats) => {
+                for (index, format) in formats.iter().enumerate() {
+                    if needs_validation(format) {
+                        let expr = format!("value[{}]", index);
+                        writeln!(self.out, "{}", self.quote_validate(&expr, format))?;
+                    }
+                }
+            }
+
+            TupleArray { content, size } => {
+                writeln!(
+                    self.out,
+                    r#"unless value.size == {0}
+  raise ::ArgumentError, "Invalid length for fixed-size array: #{{value.size}} instead of {0}"
+end"#,
+                    size
+                )?;
+                if needs_validation(content) {
+                    writeln!(
+                        self.out,
+                        "value.each {{ |item| {} }}",
+                        self.quote_validate("item", content)
+                    )?;
+                }
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    /// Check the constraints of the registry that are not enforced by construction.
+    fn output_validate(&mut self, fields: &[Named<Format>]) -> Result<()> {
+        let fields = fields
+            .iter()
+            .filter(|field| common::needs_validation(self.generator.config, &field.value, true))
+            .collect::<Vec<_>>();
+        self.start_definition()?;
+        if fields.is_empty() {
+            writeln!(self.out, "def validate; end")?;
+            return Ok(());
+        }
+        writeln!(self.out, "def validate")?;
+        self.out.indent();
+        for field in fields {
+            writeln!(
+                self.out,
+                "{}",
+                self.quote_validate(&format!("self.{}", field.name), &field.value)
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    /// An expression comparing the values `a` and `b` in the order of Rust values, as an
+    /// integer that may be followed by a method call.
+    fn quote_compare(&self, a: &str, b: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            Unit => "0".to_string(),
+            Bool => format!("(({} ? 1 : 0) <=> ({} ? 1 : 0))", a, b),
+            TypeName(_) | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | Char
+            | Str | Bytes => format!("({} <=> {})", a, b),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            _ => format!(
+                "{}.compare_{}({}, {})",
+                self.quote_qualified_name("TraitHelpers"),
+                common::mangle_type(format),
+                a,
+                b
+            ),
+        }
+    }
+
+    fn output_comparison_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(self.out, "\ndef compare_{}(a, b)", name)?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+return (a.nil? ? 0 : 1) <=> (b.nil? ? 0 : 1) if a.nil? || b.nil?
+
+{}
+"#,
+                    self.quote_compare("a", "b", format)
+                )?;
+            }
+
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                write!(
+                    self.out,
+                    r#"
+[a.size, b.size].min.times do |i|
+  result = {}
+  return result unless result.zero?
+end
+a.size <=> b.size
+"#,
+                    self.quote_compare("a[i]", "b[i]", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+x = a.sort {{ |e, f| {0} }}
+y = b.sort {{ |e, f| {0} }}
+[x.size, y.size].min.times do |i|
+  result = {1}.nonzero? || {2}
+  return result unless result.zero?
+end
+x.size <=> y.size
+"#,
+                    self.quote_compare("e[0]", "f[0]", key),
+                    self.quote_compare("x[i][0]", "y[i][0]", key),
+                    self.quote_compare("x[i][1]", "y[i][1]", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out)?;
+                let comparisons = formats
+                    .iter()
+                    .enumerate()
+                    .map(|(index, format)| {
+                        let a = format!("a[{}]", index);
+                        let b = format!("b[{}]", index);
+                        self.quote_compare(&a, &b, format)
+                    })
+                    .collect::<Vec<_>>();
+                self.output_comparison_chain(&comparisons)?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    /// Output `<=>`, which orders the variants of an enum by index, then values by fields.
+    fn output_compare(
+        &mut self,
+        variant_base: Option<&str>,
+        name: &str,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        self.start_definition()?;
+        writeln!(self.out, "def <=>(other)")?;
+        self.out.indent();
+        match variant_base {
+            Some(base) => {
+                writeln!(
+                    self.out,
+                    "return nil unless other.is_a?({})\nreturn VARIANT_INDEX <=> other.class::VARIANT_INDEX unless other.is_a?({})",
+                    self.quote_qualified_name(base),
+                    name
+                )?;
+            }
+            None => writeln!(self.out, "return nil unless other.is_a?({})", name)?,
+        }
+        writeln!(self.out)?;
+        let comparisons = fields
+            .iter()
+            .map(|field| {
+                let a = format!("self.{}", field.name);
+                let b = format!("other.{}", field.name);
+                self.quote_compare(&a, &b, &field.value)
+            })
+            .collect::<Vec<_>>();
+        self.output_comparison_chain(&comparisons)?;
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    /// Output the first non-zero result of the given comparisons, or `0`.
+    fn output_comparison_chain(&mut self, comparisons: &[String]) -> Result<()> {
+        let mut lines = comparisons
+            .iter()
+            .map(|comparison| format!("{}.nonzero? ||", comparison))
+            .chain(std::iter::once("0".to_string()));
+        writeln!(self.out, "{}", lines.next().unwrap())?;
+        self.out.indent();
+        for line in lines {
+            writeln!(self.out, "{}", line)?;
+        }
+        self.out.unindent();
+        Ok(())
+    }
+
+    fn output_variant(
+        &mut self,
+        base: &str,
+        index: u32,
+        name: &str,
+        variant: &VariantFormat,
+    ) -> Result<()> {
+        use VariantFormat::*;
+        let fields = match variant {
+            Unit => Vec::new(),
+            NewType(format) => vec![Named {
+                name: "value".to_string(),
+                value: format.as_ref().clone(),
+            }],
+            Tuple(formats) => formats
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Named {
+                    name: format!("field{}", i),
+                    value: f.clone(),
+                })
+                .collect(),
+            Struct(fields) => fields.clone(),
+            Variable(_) => panic!("incorrect value"),
+        };
+        self.output_struct_or_variant_container(Some(base), Some(index), name, &fields)
+    }
+
+    /// Output the `Data` class of a struct or of the variant of an enum.
+    fn output_struct_or_variant_container(
+        &mut self,
+        variant_base: Option<&str>,
+        variant_index: Option<u32>,
+        name: &str,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        let config = self.generator.config;
+        // Beginning of class
+        self.start_definition()?;
+        self.output_comment(name)?;
+        self.current_namespace.push(name.to_string());
+        let members = fields
+            .iter()
+            .map(|field| format!(":{}", field.name))
+            .collect::<Vec<_>>();
+        if fields.is_empty() {
+            writeln!(self.out, "class {} < ::Data.define", name)?;
+        } else if fields
+            .iter()
+            .all(|field| self.comment(&field.name).is_none())
+        {
+            writeln!(
+                self.out,
+                "class {} < ::Data.define({})",
+                name,
+                members.join(", ")
+            )?;
+        } else {
+            writeln!(self.out, "class {} < ::Data.define(", name)?;
+            self.out.indent();
+            for (field, member) in fields.iter().zip(&members) {
+                self.output_comment(&field.name)?;
+                writeln!(self.out, "{},", member)?;
+            }
+            self.out.unindent();
+            writeln!(self.out, ")")?;
+        }
+        self.current_namespace.pop();
+        self.enter_class(name, &[]);
+        if let Some(base) = variant_base {
+            self.start_definition()?;
+            writeln!(self.out, "include {}", self.quote_qualified_name(base))?;
+            if config.comparisons {
+                writeln!(self.out, "\nVARIANT_INDEX = {}", variant_index.unwrap())?;
+            }
+        } else if config.comparisons {
+            self.start_definition()?;
+            writeln!(self.out, "include ::Comparable")?;
+        }
+        // Serialize
+        if config.serialization {
+            self.start_definition()?;
+            writeln!(self.out, "def serialize(serializer)")?;
+            self.out.indent();
+            writeln!(self.out, "serializer.increase_container_depth")?;
+            if let Some(index) = variant_index {
+                writeln!(self.out, "serializer.serialize_variant_index({})", index)?;
+            }
+            for field in fields {
+                writeln!(
+                    self.out,
+                    "{}",
+                    self.quote_serialize_value(&format!("self.{}", field.name), &field.value)
+                )?;
+            }
+            writeln!(self.out, "serializer.decrease_container_depth")?;
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+
+            if variant_index.is_none() {
+                for encoding in &config.encodings {
+                    self.output_class_serialize_for_encoding(*encoding)?;
+                }
+            }
+        }
+        // Deserialize (struct) or Load (variant)
+        if config.serialization {
+            self.output_deserialize(variant_index, fields)?;
+        }
+        // Validation
+        if config.generates_validation() {
+            self.output_validate(fields)?;
+        }
+        // Ordering
+        if config.comparisons {
+            self.output_compare(variant_base, name, fields)?;
+        }
+        // Custom code
+        self.output_custom_code()?;
+        // End of class
+        self.leave_class(&[]);
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    fn output_deserialize(
+        &mut self,
+        variant_index: Option<u32>,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        self.start_definition()?;
+        if variant_index.is_none() {
+            writeln!(self.out, "def self.deserialize(deserializer)")?;
+        } else {
+            writeln!(self.out, "def self.load(deserializer)")?;
+        }
+        self.out.indent();
+        writeln!(self.out, "deserializer.increase_container_depth")?;
+        if fields.is_empty() {
+            writeln!(self.out, "value = new")?;
+        } else {
+            writeln!(self.out, "value = new(")?;
+            for field in fields {
+                writeln!(
+                    self.out,
+                    "  {}: {},",
+                    field.name,
+                    self.quote_deserialize(&field.value)
+                )?;
+            }
+            writeln!(self.out, ")")?;
+        }
+        writeln!(self.out, "deserializer.decrease_container_depth")?;
+        writeln!(self.out, "value")?;
+        self.out.unindent();
+        writeln!(self.out, "end")?;
+
+        if variant_index.is_none() {
+            for encoding in &self.generator.config.encodings {
+                self.output_class_deserialize_for_encoding(*encoding)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn output_enum_container(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        let config = self.generator.config;
+        self.start_definition()?;
+        self.output_comment(name)?;
+        writeln!(self.out, "module {}", name)?;
+        let reserved_names = variants
+            .values()
+            .map(|v| v.name.as_str())
+            .collect::<Vec<_>>();
+        self.enter_class(name, &reserved_names);
+        if config.comparisons {
+            self.start_definition()?;
+            writeln!(self.out, "include ::Comparable")?;
+        }
+        if config.serialization {
+            self.start_definition()?;
+            writeln!(
+                self.out,
+                r#"def self.deserialize(deserializer)
+  index = deserializer.deserialize_variant_index
+  case index"#
+            )?;
+            self.out.indent();
+            for (index, variant) in variants {
+                writeln!(
+                    self.out,
+                    "when {} then {}.load(deserializer)",
+                    index, variant.name
+                )?;
+            }
+            writeln!(
+                self.out,
+                r#"else raise ::Serde::DeserializationError, "Unknown variant index for {}: #{{index}}""#,
+                name,
+            )?;
+            writeln!(self.out, "end")?;
+            self.out.unindent();
+            writeln!(self.out, "end")?;
+
+            for encoding in &config.encodings {
+                self.output_class_deserialize_for_encoding(*encoding)?;
+            }
+            for encoding in &config.encodings {
+                self.output_class_serialize_for_encoding(*encoding)?;
+            }
+        }
+        self.output_custom_code()?;
+        for (index, variant) in variants {
+            self.output_variant(name, *index, &variant.name, &variant.value)?;
+        }
+        self.leave_class(&reserved_names);
+        writeln!(self.out, "end")?;
+        Ok(())
+    }
+
+    /// Keyword arguments of the runtime for the integer encoding of the options, if any.
+    fn bincode_encoding_arguments(options: &BincodeOptions) -> Vec<String> {
+        if options.is_custom_encoding() {
+            vec![
+                format!(
+                    "varint_encoding: {}",
+                    options.int_encoding == IntEncoding::Varint
+                ),
+                format!("big_endian: {}", options.endianness == Endianness::Big),
+            ]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn output_class_serialize_for_encoding(&mut self, encoding: Encoding) -> Result<()> {
+        let options = self.generator.config.bincode_options_for(encoding);
+        let arguments = Self::bincode_encoding_arguments(&options);
+        self.start_definition()?;
+        writeln!(
+            self.out,
+            r#"def {0}_serialize{2}
+  serializer = ::{1}::Serializer.new{3}
+  serialize(serializer){4}
+  serializer.bytes
+end"#,
+            encoding.name(),
+            encoding.name().to_camel_case(),
+            if self.generator.config.validate_on_serialize {
+                "\n  validate"
+            } else {
+                ""
+            },
+            if arguments.is_empty() {
+                String::new()
+            } else {
+                format!("({})", arguments.join(", "))
+            },
+            match options.limit {
+                Some(limit) => format!(
+                    r#"
+  if serializer.buffer_offset > {}
+    raise ::Serde::SerializationError, 'Exceeded the size limit of the encoding'
+  end"#,
+                    limit
+                ),
+                None => String::new(),
+            },
+        )?;
+        Ok(())
+    }
+
+    fn output_class_deserialize_for_encoding(&mut self, encoding: Encoding) -> Result<()> {
+        let options = self.generator.config.bincode_options_for(encoding);
+        let mut arguments = vec!["input".to_string()];
+        arguments.extend(Self::bincode_encoding_arguments(&options));
+        // The runtime enforces the size limit while reading.
+        if let Some(limit) = options.limit {
+            arguments.push(format!("limit: {}", limit));
+        }
+        let checks = match options.trailing_bytes {
+            TrailingBytes::Reject => {
+                r#"
+  if deserializer.buffer_offset < input.bytesize
+    raise ::Serde::DeserializationError, 'Some input bytes were not read'
+  end"#
+            }
+            TrailingBytes::Allow => "",
+        };
+        self.start_definition()?;
+        writeln!(
+            self.out,
+            r#"def self.{0}_deserialize(input)
+  deserializer = ::{1}::Deserializer.new({2})
+  value = deserialize(deserializer){3}
+  value
+end"#,
+            encoding.name(),
+            encoding.name().to_camel_case(),
+            arguments.join(", "),
+            checks,
+        )?;
+        Ok(())
+    }
+
+    fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
+        use ContainerFormat::*;
+        let fields = match format {
+            UnitStruct => Vec::new(),
+            NewTypeStruct(format) => vec![Named {
+                name: "value".to_string(),
+                value: format.as_ref().clone(),
+            }],
+            TupleStruct(formats) => formats
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Named {
+                    name: format!("field{}", i),
+                    value: f.clone(),
+                })
+                .collect::<Vec<_>>(),
+            Struct(fields) => fields.clone(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("Ruby", name)),
+            FlattenedStruct(_) => return Err(common::flattened_struct_not_supported("Ruby", name)),
+            Enum(variants) => {
+                self.output_enum_container(name, variants)?;
+                return Ok(());
+            }
+        };
+        self.output_struct_or_variant_container(None, None, name, &fields)
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(&self, registry: &Registry) -> std::result::Result<SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let path = self
+            .config
+            .module_name
+            .split('.')
+            .collect::<PathBuf>()
+            .with_extension("rb");
+        let mut files = SourceFiles::new();
+        files.insert_bytes(path, content);
+        Ok(files)
+    }
+}
+
+/// Installer for generated source files in Ruby.
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+
+    fn install_runtime(&self, name: &str, content: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.install_dir)?;
+        let mut file = std::fs::File::create(self.install_dir.join(name))?;
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Error;
+
+    fn install_module(
+        &self,
+        config: &CodeGeneratorConfig,
+        registry: &Registry,
+    ) -> std::result::Result<(), Self::Error> {
+        let generator = CodeGenerator::new(config);
+        generator.generate(registry)?.write_to(&self.install_dir)?;
+        Ok(())
+    }
+
+    fn install_serde_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime("serde.rb", include_str!("../runtime/ruby/serde.rb"))
+    }
+
+    fn install_bincode_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime("bincode.rb", include_str!("../runtime/ruby/bincode.rb"))
+    }
+
+    fn install_bcs_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime("bcs.rb", include_str!("../runtime/ruby/bcs.rb"))
+    }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the postcard encoding",
+        ))
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the borsh encoding",
+        ))
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the scale encoding",
+        ))
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the json encoding",
+        ))
+    }
+}
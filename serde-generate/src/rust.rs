@@ -2,18 +2,46 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::{
-    analyzer,
+    analyzer, common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig,
+    naming, Capabilities, CodeGeneratorConfig, Encoding, Error, Result, SourceGenerator,
+};
+use serde_reflection::{
+    ContainerFormat, EnumRepresentation, Format, Named, Registry, VariantFormat,
 };
-use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
 use std::{
     borrow::Cow,
     collections::{BTreeMap, HashSet},
-    io::{Result, Write},
+    io::Write,
     path::PathBuf,
 };
 
+/// Features supported by the Rust code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Rust",
+    c_style_enums: true,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: true,
+    encodings: &[
+        Encoding::Bincode,
+        Encoding::Bcs,
+        Encoding::Postcard,
+        Encoding::Json,
+    ],
+    package_manifest: true,
+    naming_conventions: false,
+    file_naming_conventions: false,
+    modules: true,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
 /// Main configuration object for code-generation in Rust.
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
@@ -72,11 +100,15 @@ impl<'a> CodeGenerator<'a> {
     }
 
     /// Write container definitions in Rust.
-    pub fn output(
-        &self,
-        out: &mut dyn Write,
-        registry: &Registry,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::sanitize_type_names(self.config, registry) {
+            return CodeGenerator::new(&config)
+                .with_derive_macros(self.derive_macros.clone())
+                .with_custom_derive_block(self.custom_derive_block.clone())
+                .with_track_visibility(self.track_visibility)
+                .output(out, &registry);
+        }
         let external_names = self
             .config
             .external_definitions
@@ -119,7 +151,7 @@ impl<'a> CodeGenerator<'a> {
     pub fn quote_container_definitions(
         &self,
         registry: &Registry,
-    ) -> std::result::Result<BTreeMap<String, String>, Box<dyn std::error::Error>> {
+    ) -> Result<BTreeMap<String, String>> {
         let dependencies = analyzer::get_dependency_map(registry)?;
         let entries = analyzer::best_effort_topological_sort(&dependencies);
 
@@ -292,19 +324,19 @@ where
         self.output_comment(name)?;
         use VariantFormat::*;
         match variant {
-            Unit => writeln!(self.out, "{},", name),
+            Unit => writeln!(self.out, "{},", name)?,
             NewType(format) => writeln!(
                 self.out,
                 "{}({}),",
                 name,
                 Self::quote_type(format, Some(&self.known_sizes))
-            ),
+            )?,
             Tuple(formats) => writeln!(
                 self.out,
                 "{}({}),",
                 name,
                 Self::quote_types(formats, Some(&self.known_sizes))
-            ),
+            )?,
             Struct(fields) => {
                 writeln!(self.out, "{} {{", name)?;
                 self.current_namespace.push(name.to_string());
@@ -312,10 +344,11 @@ where
                 self.output_fields(&[base, name], fields)?;
                 self.out.unindent();
                 self.current_namespace.pop();
-                writeln!(self.out, "}},")
+                writeln!(self.out, "}},")?;
             }
             Variable(_) => panic!("incorrect value"),
         }
+        Ok(())
     }
 
     fn output_variants(
@@ -330,9 +363,81 @@ where
         Ok(())
     }
 
+    fn quote_enum_representation(representation: &EnumRepresentation) -> String {
+        use EnumRepresentation::*;
+        match representation {
+            Internal { tag } => format!("#[serde(tag = {:?})]", tag),
+            Adjacent { tag, content } => {
+                format!("#[serde(tag = {:?}, content = {:?})]", tag, content)
+            }
+            Untagged => "#[serde(untagged)]".to_string(),
+        }
+    }
+
+    /// Output the tags of the variants of an open enum known at generation time, and a registry
+    /// of handlers so that clients may decode the variants that they know and skip others.
+    fn output_open_enum_handlers(
+        &mut self,
+        name: &str,
+        formats: &BTreeMap<String, Format>,
+    ) -> Result<()> {
+        let visibility = if self.generator.track_visibility {
+            "pub "
+        } else {
+            ""
+        };
+        let tags = formats
+            .keys()
+            .map(|tag| format!("{:?}", tag))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            self.out,
+            r#"impl {0} {{
+    /// The tags of the variants known when the code was generated.
+    pub const TAGS: &'static [&'static str] = &[{2}];
+}}
+
+/// Handlers of the variants of `{0}`, indexed by tag. Each handler receives the encoded
+/// content of a variant.
+{1}struct {0}Handlers<R>(Map<String, Box<dyn Fn(&[u8]) -> R>>);
+
+impl<R> {0}Handlers<R> {{
+    pub fn new() -> Self {{
+        Self(Map::new())
+    }}
+
+    /// Register the handler of the variants with the given tag.
+    pub fn register<F>(&mut self, tag: &str, handler: F)
+    where
+        F: Fn(&[u8]) -> R + 'static,
+    {{
+        self.0.insert(tag.to_string(), Box::new(handler));
+    }}
+
+    /// Call the handler registered for the tag of `value`, if any. Values with unknown tags
+    /// are skipped and return `None`.
+    pub fn handle(&self, value: &{0}) -> Option<R> {{
+        let handler = self.0.get(&value.tag)?;
+        Some(handler(&value.content))
+    }}
+}}
+"#,
+            name, visibility, tags
+        )?;
+        Ok(())
+    }
+
     fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
         self.output_comment(name)?;
         let mut derive_macros = self.generator.derive_macros.clone();
+        if self.generator.config.comparisons {
+            for name in ["PartialEq", "Eq", "Hash", "PartialOrd", "Ord"] {
+                if !derive_macros.iter().any(|x| x == name) {
+                    derive_macros.push(name.to_string());
+                }
+            }
+        }
         if self.generator.config.serialization {
             derive_macros.push("Serialize".to_string());
             derive_macros.push("Deserialize".to_string());
@@ -345,6 +450,12 @@ where
             prefix.push_str(text);
             prefix.push('\n');
         }
+        if let ContainerFormat::TaggedEnum { representation, .. } = format {
+            if self.generator.config.serialization {
+                prefix.push_str(&Self::quote_enum_representation(representation));
+                prefix.push('\n');
+            }
+        }
         if self.generator.track_visibility {
             prefix.push_str("pub ");
         }
@@ -380,7 +491,18 @@ where
                 self.current_namespace.pop();
                 writeln!(self.out, "}}\n")?;
             }
-            Enum(variants) => {
+            FlattenedStruct(_) => return Err(common::flattened_struct_not_supported("Rust", name)),
+            OpenEnum(formats) => {
+                writeln!(self.out, "{}struct {} {{", prefix, name)?;
+                self.current_namespace.push(name.to_string());
+                self.out.indent();
+                self.output_fields(&[name], &common::open_enum_fields())?;
+                self.out.unindent();
+                self.current_namespace.pop();
+                writeln!(self.out, "}}\n")?;
+                self.output_open_enum_handlers(name, formats)?;
+            }
+            Enum(variants) | TaggedEnum { variants, .. } => {
                 writeln!(self.out, "{}enum {} {{", prefix, name)?;
                 self.current_namespace.push(name.to_string());
                 self.out.indent();
@@ -390,7 +512,38 @@ where
                 writeln!(self.out, "}}\n")?;
             }
         }
-        self.output_custom_code(name)
+        self.output_custom_code(name)?;
+        Ok(())
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+    ) -> std::result::Result<crate::SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let mut files = crate::SourceFiles::new();
+        files.insert_bytes(
+            std::path::Path::new(
+                self.config
+                    .module_name
+                    .split(':')
+                    .next()
+                    .unwrap_or_default(),
+            )
+            .join("src")
+            .join("lib.rs"),
+            content,
+        );
+        Ok(files)
     }
 }
 
@@ -410,7 +563,7 @@ impl Installer {
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
@@ -446,10 +599,8 @@ serde_bytes = "0.11"
             )?;
         }
 
-        std::fs::create_dir_all(dir_path.join("src"))?;
-        let source_path = dir_path.join("src/lib.rs");
-        let mut source = std::fs::File::create(source_path)?;
-        generator.output(&mut source, registry)
+        generator.generate(registry)?.write_to(&self.install_dir)?;
+        Ok(())
     }
 
     fn install_serde_runtime(&self) -> std::result::Result<(), Self::Error> {
@@ -466,4 +617,24 @@ serde_bytes = "0.11"
         Self::runtime_installation_message("bcs");
         Ok(())
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Self::runtime_installation_message("postcard");
+        Ok(())
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Self::runtime_installation_message("borsh");
+        Ok(())
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Self::runtime_installation_message("scale");
+        Ok(())
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Self::runtime_installation_message("serde_json");
+        Ok(())
+    }
 }
@@ -2,18 +2,40 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::{
+    common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig,
+    naming, Capabilities, CodeGeneratorConfig, Encoding, Error, Result, SourceGenerator,
 };
 use heck::SnakeCase;
 use phf::phf_set;
 use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
 use std::{
     collections::{BTreeMap, HashSet},
-    io::{Result, Write},
+    io::Write,
     path::PathBuf,
 };
 
+/// Features supported by the Solidity code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Solidity",
+    c_style_enums: false,
+    floats: false,
+    unit_structs: false,
+    u128: true,
+    recursive_types: false,
+    tagged_enums: false,
+    encodings: &[Encoding::Bcs],
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: false,
+    module_cycles: false,
+    native_types: false,
+    comparisons: true,
+    validation: true,
+    bincode_options: false,
+};
+
 /// Main configuration object for code-generation in solidity
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
@@ -60,6 +82,66 @@ function bcs_deserialize_{key_name}(bytes memory input)
     Ok(())
 }
 
+/// Output the functions `compare_{key_name}`, `equal_{key_name}` and `hash_{key_name}`
+/// given the bodies of the comparison (of `a` and `b`) and of the hash (of `value`).
+fn output_generic_comparisons<T: std::io::Write>(
+    out: &mut IndentedWriter<T>,
+    key_name: &str,
+    code_name: &str,
+    need_memory: bool,
+    compare_body: &str,
+    hash_body: &str,
+) -> Result<()> {
+    let data_location = get_data_location(need_memory);
+    writeln!(
+        out,
+        r#"
+function compare_{key_name}({code_name}{data_location} a, {code_name}{data_location} b)
+    internal
+    pure
+    returns (int8)
+{{
+{compare_body}
+}}
+
+function equal_{key_name}({code_name}{data_location} a, {code_name}{data_location} b)
+    internal
+    pure
+    returns (bool)
+{{
+    return compare_{key_name}(a, b) == 0;
+}}
+
+function hash_{key_name}({code_name}{data_location} value)
+    internal
+    pure
+    returns (bytes32)
+{{
+{hash_body}
+}}"#
+    )?;
+    Ok(())
+}
+
+/// Comparison of two values supporting the operators `<` and `>`.
+fn scalar_comparison(a: &str, b: &str) -> String {
+    format!(
+        r#"    if ({a} < {b}) {{
+        return -1;
+    }}
+    if ({a} > {b}) {{
+        return 1;
+    }}
+    return 0;"#
+    )
+}
+
+/// Comparison of the lengths of `a` and `b`, once their common prefix is known to be equal.
+const LENGTH_COMPARISON: &str = r#"    if (a.length != b.length) {
+        return a.length < b.length ? int8(-1) : int8(1);
+    }
+    return 0;"#;
+
 static KEYWORDS: phf::Set<&str> = phf_set! {
     "abstract", "after", "alias", "anonymous",
     "as", "assembly", "break", "catch", "constant",
@@ -144,6 +226,45 @@ impl Primitive {
         matches!(self, Primitive::Unit | Primitive::Bytes | Primitive::Str)
     }
 
+    pub fn output_comparisons<T: std::io::Write>(&self, out: &mut IndentedWriter<T>) -> Result<()> {
+        use Primitive::*;
+        let (compare_body, hash_body) = match self {
+            Unit => (
+                "    return 0;".to_string(),
+                "    return keccak256(abi.encodePacked(uint8(0)));",
+            ),
+            Bool => (
+                r#"    if (a == b) {
+        return 0;
+    }
+    return a ? int8(1) : int8(-1);"#
+                    .to_string(),
+                "    return keccak256(abi.encodePacked(value));",
+            ),
+            I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | Char => (
+                scalar_comparison("a", "b"),
+                "    return keccak256(abi.encodePacked(value));",
+            ),
+            Str => (
+                "    return compare_raw_bytes(bytes(a), bytes(b));".to_string(),
+                "    return keccak256(bytes(value));",
+            ),
+            Bytes => (
+                "    return compare_raw_bytes(a, b);".to_string(),
+                "    return keccak256(value);",
+            ),
+        };
+        let name = self.name();
+        output_generic_comparisons(
+            out,
+            &name,
+            &name,
+            self.need_memory(),
+            &compare_body,
+            hash_body,
+        )
+    }
+
     pub fn output<T: std::io::Write>(&self, out: &mut IndentedWriter<T>) -> Result<()> {
         use Primitive::*;
         match self {
@@ -1132,6 +1253,228 @@ function bcs_deserialize_offset_{name}(uint256 pos, bytes memory input)
         Ok(())
     }
 
+    pub fn output_comparisons<T: std::io::Write>(
+        &self,
+        out: &mut IndentedWriter<T>,
+        sol_registry: &SolRegistry,
+    ) -> Result<()> {
+        use SolFormat::*;
+        let key_name = self.key_name();
+        let code_name = self.code_name();
+        let (compare_body, hash_body) = match self {
+            Primitive(primitive) => return primitive.output_comparisons(out),
+            TypeName(_) => {
+                // by definition for TypeName the code already exists
+                return Ok(());
+            }
+            Option(format) => {
+                let inner_key_name = format.key_name();
+                (
+                    format!(
+                        r#"    if (a.has_value != b.has_value) {{
+        return a.has_value ? int8(1) : int8(-1);
+    }}
+    if (!a.has_value) {{
+        return 0;
+    }}
+    return compare_{inner_key_name}(a.value, b.value);"#
+                    ),
+                    format!(
+                        r#"    if (!value.has_value) {{
+        return keccak256(abi.encodePacked(uint8(0)));
+    }}
+    return keccak256(abi.encodePacked(uint8(1), hash_{inner_key_name}(value.value)));"#
+                    ),
+                )
+            }
+            Seq(format) => {
+                let inner_key_name = format.key_name();
+                match sol_registry.maps.get(&inner_key_name) {
+                    Some(map_key) => {
+                        // Entries of maps are compared in the order of their keys.
+                        let map_key_name = map_key.key_name();
+                        writeln!(
+                            out,
+                            r#"
+function next_{key_name}({code_name} memory entries, uint256 previous, bool has_previous)
+    internal
+    pure
+    returns (uint256)
+{{
+    bool found = false;
+    uint256 index;
+    for (uint256 i=0; i<entries.length; i++) {{
+        if (has_previous && compare_{map_key_name}(entries[i].key, entries[previous].key) <= 0) {{
+            continue;
+        }}
+        if (!found || compare_{map_key_name}(entries[i].key, entries[index].key) < 0) {{
+            found = true;
+            index = i;
+        }}
+    }}
+    return index;
+}}"#
+                        )?;
+                        (
+                            format!(
+                                r#"    uint256 len = a.length < b.length ? a.length : b.length;
+    uint256 index_a;
+    uint256 index_b;
+    for (uint256 i=0; i<len; i++) {{
+        index_a = next_{key_name}(a, index_a, i > 0);
+        index_b = next_{key_name}(b, index_b, i > 0);
+        int8 result = compare_{inner_key_name}(a[index_a], b[index_b]);
+        if (result != 0) {{
+            return result;
+        }}
+    }}
+{LENGTH_COMPARISON}"#
+                            ),
+                            format!(
+                                r#"    uint256 result;
+    for (uint256 i=0; i<value.length; i++) {{
+        unchecked {{
+            result += uint256(hash_{inner_key_name}(value[i]));
+        }}
+    }}
+    return keccak256(abi.encodePacked(value.length, result));"#
+                            ),
+                        )
+                    }
+                    None => (
+                        format!(
+                            r#"    uint256 len = a.length < b.length ? a.length : b.length;
+    for (uint256 i=0; i<len; i++) {{
+        int8 result = compare_{inner_key_name}(a[i], b[i]);
+        if (result != 0) {{
+            return result;
+        }}
+    }}
+{LENGTH_COMPARISON}"#
+                        ),
+                        format!(
+                            r#"    bytes32 result = keccak256(abi.encodePacked(value.length));
+    for (uint256 i=0; i<value.length; i++) {{
+        result = keccak256(abi.encodePacked(result, hash_{inner_key_name}(value[i])));
+    }}
+    return result;"#
+                        ),
+                    ),
+                }
+            }
+            TupleArray { format, size } => {
+                let inner_key_name = format.key_name();
+                (
+                    format!(
+                        r#"    for (uint256 i=0; i<{size}; i++) {{
+        int8 result = compare_{inner_key_name}(a.values[i], b.values[i]);
+        if (result != 0) {{
+            return result;
+        }}
+    }}
+    return 0;"#
+                    ),
+                    format!(
+                        r#"    bytes32 result;
+    for (uint256 i=0; i<{size}; i++) {{
+        result = keccak256(abi.encodePacked(result, hash_{inner_key_name}(value.values[i])));
+    }}
+    return result;"#
+                    ),
+                )
+            }
+            Struct { name: _, formats } => {
+                let mut compare_body = String::new();
+                if formats.len() > 1 {
+                    compare_body.push_str("    int8 result;\n");
+                }
+                let mut hashes = Vec::new();
+                for (index, named_format) in formats.iter().enumerate() {
+                    let field_key_name = named_format.value.key_name();
+                    let safe_name = safe_variable(&named_format.name);
+                    let comparison =
+                        format!("compare_{field_key_name}(a.{safe_name}, b.{safe_name})");
+                    if index < formats.len() - 1 {
+                        compare_body.push_str(&format!(
+                            r#"    result = {comparison};
+    if (result != 0) {{
+        return result;
+    }}
+"#
+                        ));
+                    } else {
+                        compare_body.push_str(&format!("    return {comparison};"));
+                    }
+                    hashes.push(format!("hash_{field_key_name}(value.{safe_name})"));
+                }
+                (
+                    compare_body,
+                    format!(
+                        "    return keccak256(abi.encodePacked({}));",
+                        hashes.join(", ")
+                    ),
+                )
+            }
+            SimpleEnum { name: _, names: _ } => (
+                scalar_comparison("uint8(a)", "uint8(b)"),
+                "    return keccak256(abi.encodePacked(value));".to_string(),
+            ),
+            Enum { name: _, formats } => {
+                let mut compare_body = r#"    if (a.choice != b.choice) {
+        return a.choice < b.choice ? int8(-1) : int8(1);
+    }
+"#
+                .to_string();
+                let mut hash_body = String::new();
+                for (idx, named_format) in formats.iter().enumerate() {
+                    if let Some(format) = &named_format.value {
+                        let inner_key_name = format.key_name();
+                        let snake_name = safe_variable(&named_format.name.to_snake_case());
+                        compare_body.push_str(&format!(
+                            r#"    if (a.choice == {idx}) {{
+        return compare_{inner_key_name}(a.{snake_name}, b.{snake_name});
+    }}
+"#
+                        ));
+                        hash_body.push_str(&format!(
+                            r#"    if (value.choice == {idx}) {{
+        return keccak256(abi.encodePacked(value.choice, hash_{inner_key_name}(value.{snake_name})));
+    }}
+"#
+                        ));
+                    }
+                }
+                compare_body.push_str("    return 0;");
+                hash_body.push_str("    return keccak256(abi.encodePacked(value.choice));");
+                (compare_body, hash_body)
+            }
+            BytesN { size: _ } => (
+                scalar_comparison("a", "b"),
+                "    return keccak256(abi.encodePacked(value));".to_string(),
+            ),
+            OptionBool => (
+                // The variants are declared as `None, True, False`.
+                r#"    if (a == b) {
+        return 0;
+    }
+    if (a == OptionBool.None || b == OptionBool.True) {
+        return -1;
+    }
+    return 1;"#
+                    .to_string(),
+                "    return keccak256(abi.encodePacked(value));".to_string(),
+            ),
+        };
+        output_generic_comparisons(
+            out,
+            &key_name,
+            &code_name,
+            sol_registry.need_memory(self),
+            &compare_body,
+            &hash_body,
+        )
+    }
+
     fn get_dependency(&self) -> Vec<String> {
         use SolFormat::*;
         match self {
@@ -1161,6 +1504,8 @@ function bcs_deserialize_offset_{name}(uint256 pos, bytes memory input)
 #[derive(Default)]
 struct SolRegistry {
     names: BTreeMap<String, SolFormat>,
+    /// The key formats of maps, indexed by the name of the struct of their entries.
+    maps: BTreeMap<String, SolFormat>,
 }
 
 impl SolRegistry {
@@ -1229,7 +1574,7 @@ impl SolRegistry {
         false
     }
 
-    fn parse_format(&mut self, format: Format) -> SolFormat {
+    fn parse_format(&mut self, format: Format) -> Result<SolFormat> {
         use Format::*;
         let sol_format = match format {
             Variable(_) => panic!("variable is not supported in solidity"),
@@ -1246,13 +1591,12 @@ impl SolRegistry {
             U32 => SolFormat::Primitive(Primitive::U32),
             U64 => SolFormat::Primitive(Primitive::U64),
             U128 => SolFormat::Primitive(Primitive::U128),
-            F32 => panic!("floating point is not supported in solidity"),
-            F64 => panic!("floating point is not supported in solidity"),
+            F32 | F64 => return Err(Error::unsupported("Solidity", "floating point numbers")),
             Char => SolFormat::Primitive(Primitive::Char),
             Str => SolFormat::Primitive(Primitive::Str),
             Bytes => SolFormat::Primitive(Primitive::Bytes),
             Option(format) => {
-                let format = self.parse_format(*format);
+                let format = self.parse_format(*format)?;
                 if format == SolFormat::Primitive(Primitive::Bool) {
                     SolFormat::OptionBool
                 } else {
@@ -1260,13 +1604,14 @@ impl SolRegistry {
                 }
             }
             Seq(format) => {
-                let format = self.parse_format(*format);
+                let format = self.parse_format(*format)?;
                 SolFormat::Seq(Box::new(format))
             }
             Map { key, value } => {
-                let key = self.parse_format(*key);
-                let value = self.parse_format(*value);
+                let key = self.parse_format(*key)?;
+                let value = self.parse_format(*value)?;
                 let name = format!("key_values_{}_{}", key.key_name(), value.key_name());
+                self.maps.insert(name.clone(), key.clone());
                 let formats = vec![
                     Named {
                         name: "key".into(),
@@ -1285,7 +1630,7 @@ impl SolRegistry {
                 let formats = formats
                     .into_iter()
                     .map(|format| self.parse_format(format))
-                    .collect::<Vec<_>>();
+                    .collect::<Result<Vec<_>>>()?;
                 let name = format!(
                     "tuple_{}",
                     formats
@@ -1305,7 +1650,7 @@ impl SolRegistry {
                 SolFormat::Struct { name, formats }
             }
             TupleArray { content, size } => {
-                let format = self.parse_format(*content);
+                let format = self.parse_format(*content)?;
                 if (1..=32).contains(&size) && format == SolFormat::Primitive(Primitive::U8) {
                     SolFormat::BytesN { size }
                 } else {
@@ -1317,40 +1662,53 @@ impl SolRegistry {
             }
         };
         self.insert(sol_format.clone());
-        sol_format
+        Ok(sol_format)
     }
 
-    fn parse_struct_format(&mut self, name: String, formats: Vec<Named<Format>>) -> SolFormat {
+    fn parse_struct_format(
+        &mut self,
+        name: String,
+        formats: Vec<Named<Format>>,
+    ) -> Result<SolFormat> {
         let formats = formats
             .into_iter()
-            .map(|named_format| Named {
-                name: named_format.name,
-                value: self.parse_format(named_format.value),
+            .map(|named_format| {
+                let value = self
+                    .parse_format(named_format.value)
+                    .map_err(|e| e.at(&format!("{}.{}", name, named_format.name)))?;
+                Ok(Named {
+                    name: named_format.name,
+                    value,
+                })
             })
-            .collect();
+            .collect::<Result<_>>()?;
         let sol_format = SolFormat::Struct { name, formats };
         self.insert(sol_format.clone());
-        sol_format
+        Ok(sol_format)
     }
 
-    fn parse_container_format(&mut self, container_format: Named<ContainerFormat>) {
+    fn parse_container_format(&mut self, container_format: Named<ContainerFormat>) -> Result<()> {
         use ContainerFormat::*;
         let name = container_format.name;
         let sol_format = match container_format.value {
-            UnitStruct => panic!("UnitStruct is not supported in solidity"),
+            UnitStruct => return Err(Error::unsupported("Solidity", "unit structs").at(&name)),
+            OpenEnum(_) => self.parse_struct_format(name, common::open_enum_fields())?,
+            TaggedEnum { .. } => unreachable!("tagged enums are rejected by the code generator"),
+            FlattenedStruct(_) => {
+                unreachable!("flattened structs are rejected by the code generator")
+            }
             NewTypeStruct(format) => {
                 let format = Named {
                     name: "value".to_string(),
                     value: *format,
                 };
                 let formats = vec![format];
-                self.parse_struct_format(name, formats)
+                self.parse_struct_format(name, formats)?
             }
             TupleStruct(formats) => {
-                assert!(
-                    !formats.is_empty(),
-                    "The TupleStruct should be non-trivial in solidity"
-                );
+                if formats.is_empty() {
+                    return Err(Error::unsupported("Solidity", "empty tuple structs").at(&name));
+                }
                 let formats = formats
                     .into_iter()
                     .enumerate()
@@ -1359,21 +1717,23 @@ impl SolRegistry {
                         value,
                     })
                     .collect();
-                self.parse_struct_format(name, formats)
+                self.parse_struct_format(name, formats)?
             }
             Struct(formats) => {
-                assert!(
-                    !formats.is_empty(),
-                    "The struct should be non-trivial in solidity"
-                );
-                self.parse_struct_format(name, formats)
+                if formats.is_empty() {
+                    return Err(Error::unsupported("Solidity", "empty structs").at(&name));
+                }
+                self.parse_struct_format(name, formats)?
             }
             Enum(map) => {
-                assert!(
-                    !map.is_empty(),
-                    "The enum should be non-trivial in solidity"
-                );
-                assert!(map.len() < 256, "The enum should have at most 256 entries");
+                if map.is_empty() {
+                    return Err(Error::unsupported("Solidity", "empty enums").at(&name));
+                }
+                if map.len() >= 256 {
+                    return Err(
+                        Error::unsupported("Solidity", "enums with 256 variants or more").at(&name),
+                    );
+                }
                 let is_trivial = map
                     .iter()
                     .all(|(_, v)| matches!(v.value, VariantFormat::Unit));
@@ -1393,7 +1753,9 @@ impl SolRegistry {
                         let concat_name = format!("{}_{}", name, name_red);
                         let entry = match value.value {
                             VariantFormat::Unit => None,
-                            NewType(format) => Some(self.parse_format(*format)),
+                            NewType(format) => {
+                                Some(self.parse_format(*format).map_err(|e| e.at(&concat_name))?)
+                            }
                             Tuple(formats) => {
                                 let formats = formats
                                     .into_iter()
@@ -1403,9 +1765,11 @@ impl SolRegistry {
                                         value,
                                     })
                                     .collect::<Vec<_>>();
-                                Some(self.parse_struct_format(concat_name, formats))
+                                Some(self.parse_struct_format(concat_name, formats)?)
+                            }
+                            Struct(formats) => {
+                                Some(self.parse_struct_format(concat_name, formats)?)
                             }
-                            Struct(formats) => Some(self.parse_struct_format(concat_name, formats)),
                             Variable(_) => panic!("Variable is not supported for solidity"),
                         };
                         let format = Named {
@@ -1419,6 +1783,7 @@ impl SolRegistry {
             }
         };
         self.insert(sol_format);
+        Ok(())
     }
 
     fn need_memory(&self, sol_format: &SolFormat) -> bool {
@@ -1455,17 +1820,19 @@ impl SolRegistry {
 impl<'a> CodeGenerator<'a> {
     /// Create a solidity code generator for the given config.
     pub fn new(config: &'a CodeGeneratorConfig) -> Self {
-        if config.c_style_enums {
-            panic!("Solidity does not support generating c-style enums");
-        }
         Self { config }
     }
 
-    pub fn output(
-        &self,
-        out: &mut dyn Write,
-        registry: &Registry,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| KEYWORDS.contains(name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).output(out, &registry);
+        }
         let mut emitter = SolEmitter {
             out: IndentedWriter::new(out, IndentConfig::Space(4)),
             generator: self,
@@ -1474,20 +1841,35 @@ impl<'a> CodeGenerator<'a> {
         emitter.output_license()?;
         emitter.output_open_library()?;
         emitter.output_preamble()?;
+        if self.config.comparisons {
+            emitter.output_comparison_preamble()?;
+        }
 
         let mut sol_registry = SolRegistry::default();
         for (key, container_format) in registry {
+            match container_format {
+                ContainerFormat::TaggedEnum { .. } => {
+                    return Err(common::tagged_enum_not_supported("Solidity", key))
+                }
+                ContainerFormat::FlattenedStruct(_) => {
+                    return Err(common::flattened_struct_not_supported("Solidity", key))
+                }
+                _ => (),
+            }
             let container_format = Named {
                 name: key.to_string(),
                 value: container_format.clone(),
             };
-            sol_registry.parse_container_format(container_format);
+            sol_registry.parse_container_format(container_format)?;
         }
         if sol_registry.has_circular_dependency() {
-            panic!("solidity does not allow for circular dependencies");
+            return Err(Error::unsupported("Solidity", "recursive types"));
         }
         for sol_format in sol_registry.names.values() {
             sol_format.output(&mut emitter.out, &sol_registry)?;
+            if self.config.comparisons {
+                sol_format.output_comparisons(&mut emitter.out, &sol_registry)?;
+            }
         }
 
         emitter.output_close_library()?;
@@ -1563,6 +1945,27 @@ function bcs_deserialize_offset_len(uint256 pos, bytes memory input)
         Ok(())
     }
 
+    fn output_comparison_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"
+function compare_raw_bytes(bytes memory a, bytes memory b)
+    internal
+    pure
+    returns (int8)
+{{
+    uint256 len = a.length < b.length ? a.length : b.length;
+    for (uint256 i=0; i<len; i++) {{
+        if (a[i] != b[i]) {{
+            return a[i] < b[i] ? int8(-1) : int8(1);
+        }}
+    }}
+{LENGTH_COMPARISON}
+}}"#
+        )?;
+        Ok(())
+    }
+
     fn output_open_library(&mut self) -> Result<()> {
         writeln!(
             self.out,
@@ -1584,6 +1987,25 @@ function bcs_deserialize_offset_len(uint256 pos, bytes memory input)
     }
 }
 
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+    ) -> std::result::Result<crate::SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let mut files = crate::SourceFiles::new();
+        files.insert_bytes(format!("{}.sol", self.config.module_name), content);
+        Ok(files)
+    }
+}
+
 /// Installer for generated source files in solidity
 pub struct Installer {
     install_dir: PathBuf,
@@ -1594,28 +2016,22 @@ impl Installer {
         Installer { install_dir }
     }
 
-    fn create_header_file(&self, name: &str) -> Result<std::fs::File> {
-        let dir_path = &self.install_dir;
-        std::fs::create_dir_all(dir_path)?;
-        std::fs::File::create(dir_path.join(name.to_string() + ".sol"))
-    }
-
     fn runtime_installation_message(name: &str) {
         eprintln!("Not installing sources for published crate {}", name);
     }
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
         config: &crate::CodeGeneratorConfig,
         registry: &Registry,
     ) -> std::result::Result<(), Self::Error> {
-        let mut file = self.create_header_file(&config.module_name)?;
         let generator = CodeGenerator::new(config);
-        generator.output(&mut file, registry)
+        generator.generate(registry)?.write_to(&self.install_dir)?;
+        Ok(())
     }
 
     fn install_serde_runtime(&self) -> std::result::Result<(), Self::Error> {
@@ -1632,4 +2048,26 @@ impl crate::SourceInstaller for Installer {
         Self::runtime_installation_message("bcs");
         Ok(())
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Self::runtime_installation_message("postcard");
+        Ok(())
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Self::runtime_installation_message("borsh");
+        Ok(())
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Self::runtime_installation_message("scale");
+        Ok(())
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        Err(Error::unsupported(
+            CAPABILITIES.language,
+            "the json encoding",
+        ))
+    }
 }
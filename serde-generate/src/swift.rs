@@ -6,24 +6,107 @@
 use crate::{
     common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig, Encoding,
+    naming, BincodeOptions, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error,
+    IntEncoding, Result, SourceGenerator, TrailingBytes,
 };
 use heck::{CamelCase, MixedCase};
 use include_dir::include_dir as include_directory;
 use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
 use std::{
-    collections::{BTreeMap, HashMap},
-    io::{Result, Write},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    io::Write,
     path::PathBuf,
 };
 
+/// Features supported by the Swift code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "Swift",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: crate::capabilities::ALL_ENCODINGS_AND_JSON,
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: true,
+    module_cycles: false,
+    native_types: true,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Reserved words of Swift, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "Any",
+    "Self",
+    "as",
+    "associatedtype",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
 /// Main configuration object for code-generation in Swift.
+#[derive(Clone)]
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
     config: &'a CodeGeneratorConfig,
     /// Mapping from external type names to fully-qualified class names (e.g. "MyClass" -> "com.my_org.my_package.MyClass").
     /// Derived from `config.external_definitions`.
     external_qualified_names: HashMap<String, String>,
+    /// Packages to import for external definitions (e.g. "my_package").
+    /// Derived from `config.external_definitions`.
+    external_packages: BTreeSet<String>,
 }
 
 /// Shared state for the code generation of a Swift source file.
@@ -39,10 +122,8 @@ struct SwiftEmitter<'a, T> {
 impl<'a> CodeGenerator<'a> {
     /// Create a Swift code generator for the given config.
     pub fn new(config: &'a CodeGeneratorConfig) -> Self {
-        if config.c_style_enums {
-            panic!("Swift does not support generating c-style enums");
-        }
         let mut external_qualified_names = HashMap::new();
+        let mut external_packages = BTreeSet::new();
         for (namespace, names) in &config.external_definitions {
             let package_name = {
                 let path = namespace.rsplitn(2, '/').collect::<Vec<_>>();
@@ -56,15 +137,29 @@ impl<'a> CodeGenerator<'a> {
                 external_qualified_names
                     .insert(name.to_string(), format!("{}.{}", package_name, name));
             }
+            if !package_name.is_empty() {
+                external_packages.insert(package_name.to_string());
+            }
         }
         Self {
             config,
             external_qualified_names,
+            external_packages,
         }
     }
 
     /// Output class definitions for `registry`.
     pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        let registry = &common::remove_native_types(self.config, registry);
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).output(out, &registry);
+        }
         let current_namespace = self
             .config
             .module_name
@@ -84,7 +179,10 @@ impl<'a> CodeGenerator<'a> {
             emitter.output_container(name, format)?;
         }
 
-        if self.config.serialization {
+        if self.config.serialization
+            || self.config.comparisons
+            || self.config.generates_validation()
+        {
             writeln!(emitter.out)?;
             emitter.output_trait_helpers(registry)?;
         }
@@ -98,7 +196,11 @@ where
     T: Write,
 {
     fn output_preamble(&mut self) -> Result<()> {
-        writeln!(self.out, "import Serde\n")?;
+        writeln!(self.out, "import Serde")?;
+        for package_name in &self.generator.external_packages {
+            writeln!(self.out, "import {}", package_name)?;
+        }
+        writeln!(self.out)?;
         Ok(())
     }
 
@@ -133,7 +235,10 @@ where
     fn quote_type(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(x) => self.quote_qualified_name(x),
+            TypeName(x) => match self.generator.config.native_types.get(x) {
+                Some(native) => native.name.clone(),
+                None => self.quote_qualified_name(x),
+            },
             Unit => "Unit".into(),
             Bool => "Bool".into(),
             I8 => "Int8".into(),
@@ -202,8 +307,22 @@ where
                 .unwrap();
         }
         for (mangled_name, subtype) in &subtypes {
-            self.output_serialization_helper(mangled_name, subtype)?;
-            self.output_deserialization_helper(mangled_name, subtype)?;
+            if self.generator.config.serialization {
+                self.output_serialization_helper(mangled_name, subtype)?;
+                self.output_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.has_json() {
+                self.output_json_serialization_helper(registry, mangled_name, subtype)?;
+                self.output_json_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.comparisons {
+                self.output_comparison_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.generates_validation()
+                && common::needs_validation(self.generator.config, subtype, true)
+            {
+                self.output_validation_helper(mangled_name, subtype)?;
+            }
         }
         Ok(())
     }
@@ -219,7 +338,13 @@ where
     fn quote_serialize_value(&self, value: &str, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(_) => format!("try {}.serialize(serializer: serializer)", value),
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!(
+                    "try {}(value: {}, serializer: serializer)",
+                    native.serializer, value
+                ),
+                None => format!("try {}.serialize(serializer: serializer)", value),
+            },
             Unit => format!("try serializer.serialize_unit(value: {})", value),
             Bool => format!("try serializer.serialize_bool(value: {})", value),
             I8 => format!("try serializer.serialize_i8(value: {})", value),
@@ -248,10 +373,13 @@ where
     fn quote_deserialize(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(name) => format!(
-                "try {}.deserialize(deserializer: deserializer)",
-                self.quote_qualified_name(name)
-            ),
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!("try {}(deserializer: deserializer)", native.deserializer),
+                None => format!(
+                    "try {}.deserialize(deserializer: deserializer)",
+                    self.quote_qualified_name(name)
+                ),
+            },
             Unit => "try deserializer.deserialize_unit()".to_string(),
             Bool => "try deserializer.deserialize_bool()".to_string(),
             I8 => "try deserializer.deserialize_i8()".to_string(),
@@ -276,6 +404,203 @@ where
         }
     }
 
+    fn quote_compare(&self, a: &str, b: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            Unit => "0".to_string(),
+            Bool => format!("compareBools({}, {})", a, b),
+            Char => format!("compareCharacters({}, {})", a, b),
+            Str => format!("compareStrings({}, {})", a, b),
+            Bytes => format!("compareBytes({}, {})", a, b),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            Option(_) | Seq(_) | Map { .. } | Tuple(_) | TupleArray { .. } => {
+                format!("compare_{}({}, {})", common::mangle_type(format), a, b)
+            }
+            _ => format!("compareValues({}, {})", a, b),
+        }
+    }
+
+    fn output_comparison_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "func compare_{0}(_ a: {1}, _ b: {1}) -> Int {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+guard let a = a else {{ return b == nil ? 0 : -1 }}
+guard let b = b else {{ return 1 }}
+return {}
+"#,
+                    self.quote_compare("a", "b", format)
+                )?;
+            }
+
+            Seq(format)
+            | TupleArray {
+                content: format, ..
+            } => {
+                write!(
+                    self.out,
+                    r#"
+for (x, y) in zip(a, b) {{
+    let result = {}
+    if result != 0 {{ return result }}
+}}
+return compareValues(a.count, b.count)
+"#,
+                    self.quote_compare("x", "y", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+let x = a.sorted {{ {0} < 0 }}
+let y = b.sorted {{ {0} < 0 }}
+for (e, f) in zip(x, y) {{
+    var result = {1}
+    if result != 0 {{ return result }}
+    result = {2}
+    if result != 0 {{ return result }}
+}}
+return compareValues(x.count, y.count)
+"#,
+                    self.quote_compare("$0.key", "$1.key", key),
+                    self.quote_compare("e.key", "f.key", key),
+                    self.quote_compare("e.value", "f.value", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out, "\nvar result = 0")?;
+                for (index, format) in formats.iter().enumerate() {
+                    let a = format!("a.field{}", index);
+                    let b = format!("b.field{}", index);
+                    writeln!(self.out, "result = {}", self.quote_compare(&a, &b, format))?;
+                    writeln!(self.out, "if result != 0 {{ return result }}")?;
+                }
+                writeln!(self.out, "return 0")?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn quote_validate(&self, value: &str, format: &Format) -> String {
+        match format {
+            Format::TypeName(_) => format!("try {}.validate()", value),
+            _ => format!(
+                "try validate_{}(value: {})",
+                common::mangle_type(format),
+                value
+            ),
+        }
+    }
+
+    fn output_validation_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(
+            self.out,
+            "func validate_{}(value: {}) throws {{",
+            name,
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        let needs_validation =
+            |format| common::needs_validation(self.generator.config, format, true);
+        match format0 {
+            Option(format) => {
+                writeln!(
+                    self.out,
+                    "if let value = value {{\n    {}\n}}",
+                    self.quote_validate("value", format)
+                )?;
+            }
+
+            Seq(format) => {
+                writeln!(
+                    self.out,
+                    "for item in value {{\n    {}\n}}",
+                    self.quote_validate("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                let key_pattern = if needs_validation(key) { "key" } else { "_" };
+                let value_pattern = if needs_validation(value) {
+                    "value"
+                } else {
+                    "_"
+                };
+                writeln!(
+                    self.out,
+                    "for ({}, {}) in value {{",
+                    key_pattern, value_pattern
+                )?;
+                if needs_validation(key) {
+                    writeln!(self.out, "    {}", self.quote_validate("key", key))?;
+                }
+                if needs_validation(value) {
+                    writeln!(self.out, "    {}", self.quote_validate("value", value))?;
+                }
+                writeln!(self.out, "}}")?;
+            }
+
+            Tuple(formats) => {
+                for (index, format) in formats.iter().enumerate() {
+                    if needs_validation(format) {
+                        let expr = format!("value.field{}", index);
+                        writeln!(self.out, "{}", self.quote_validate(&expr, format))?;
+                    }
+                }
+            }
+
+            TupleArray { content, size } => {
+                writeln!(
+                    self.out,
+                    r#"if value.count != {0} {{
+    throw SerializationError.invalidValue(issue: "Invalid length for fixed-size array: \(value.count) instead of {0}")
+}}"#,
+                    size
+                )?;
+                if needs_validation(content) {
+                    writeln!(
+                        self.out,
+                        "for item in value {{\n    {}\n}}",
+                        self.quote_validate("item", content)
+                    )?;
+                }
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// The protocols adopted by containers: `Comparable` requires comparisons to be enabled.
+    fn quote_protocols(&self) -> &'static str {
+        if self.generator.config.comparisons {
+            "Hashable, Comparable"
+        } else {
+            "Hashable"
+        }
+    }
+
     // TODO: Should this be an extension for Serializer?
     fn output_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
         use Format::*;
@@ -357,7 +682,8 @@ for item in value {{
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
     fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
@@ -463,109 +789,388 @@ return obj
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
-    fn output_variant(&mut self, name: &str, variant: &VariantFormat) -> Result<()> {
-        use VariantFormat::*;
-        self.output_comment(name)?;
-        let name = common::lowercase_first_letter(name).to_mixed_case();
-        match variant {
-            Unit => {
-                writeln!(self.out, "case {}", name)?;
-            }
-            NewType(format) => {
-                writeln!(self.out, "case {}({})", name, self.quote_type(format))?;
-            }
-            Tuple(formats) => {
-                if formats.is_empty() {
-                    writeln!(self.out, "case {}", name)?;
-                } else {
-                    writeln!(self.out, "case {}({})", name, self.quote_types(formats))?;
-                }
-            }
-            Struct(fields) => {
-                if fields.is_empty() {
-                    writeln!(self.out, "case {}", name)?;
-                } else {
-                    writeln!(
-                        self.out,
-                        "case {}({})",
-                        name,
-                        fields
-                            .iter()
-                            .map(|f| format!("{}: {}", f.name, self.quote_type(&f.value)))
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    )?;
-                }
-            }
-            Variable(_) => panic!("incorrect value"),
+    /// Whether to generate the JSON encoding, which reads and writes values as trees of
+    /// JSON values rather than through a `Serializer` or a `Deserializer`.
+    fn has_json(&self) -> bool {
+        self.generator.config.serialization
+            && self.generator.config.encodings.contains(&Encoding::Json)
+    }
+
+    fn quote_to_json(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) => format!("try {}.toJson()", value),
+            Char => format!("try Json.serialize_char(value: {})", value),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Str | Bytes => format!(
+                "Json.serialize_{}(value: {})",
+                common::mangle_type(format),
+                value
+            ),
+            _ => format!(
+                "try to_json_{}(value: {})",
+                common::mangle_type(format),
+                value
+            ),
         }
-        Ok(())
     }
 
-    fn variant_fields(variant: &VariantFormat) -> Vec<Named<Format>> {
-        use VariantFormat::*;
-        match variant {
-            Unit => Vec::new(),
-            NewType(format) => vec![Named {
-                name: "x".to_string(),
-                value: format.as_ref().clone(),
-            }],
-            Tuple(formats) => formats
-                .clone()
-                .into_iter()
-                .enumerate()
-                .map(|(i, f)| Named {
-                    name: format!("x{}", i),
-                    value: f,
-                })
-                .collect(),
-            Struct(fields) => fields.clone(),
-            Variable(_) => panic!("incorrect value"),
+    fn quote_from_json(&self, json: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!(
+                "try {}.fromJson(json: {})",
+                self.quote_qualified_name(name),
+                json
+            ),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "try Json.deserialize_{}(value: {})",
+                common::mangle_type(format),
+                json
+            ),
+            _ => format!(
+                "try from_json_{}(json: {})",
+                common::mangle_type(format),
+                json
+            ),
         }
     }
 
-    fn output_struct_container(&mut self, name: &str, fields: &[Named<Format>]) -> Result<()> {
-        // Struct
-        writeln!(self.out)?;
-        self.output_comment(name)?;
-        writeln!(self.out, "public struct {}: Hashable {{", name)?;
-        self.enter_class(name);
-        for field in fields {
-            self.output_comment(&field.name)?;
-            writeln!(
-                self.out,
-                "@Indirect public var {}: {}",
-                field.name,
-                self.quote_type(&field.value)
-            )?;
+    /// The order used to sort the entries of maps with keys of the given format.
+    fn quote_json_key_order(registry: &Registry, key: &Format) -> String {
+        match common::JsonKeyOrder::of_key(registry, key) {
+            common::JsonKeyOrder::Text => "JsonObject.stringOrder".to_string(),
+            common::JsonKeyOrder::Number => "JsonObject.numberOrder".to_string(),
+            common::JsonKeyOrder::Variants(names) => format!(
+                "JsonObject.variantOrder(names: [{}])",
+                names
+                    .iter()
+                    .map(|name| format!("\"{}\"", name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
-        // Public constructor
-        writeln!(
+    }
+
+    fn output_json_serialization_helper(
+        &mut self,
+        registry: &Registry,
+        name: &str,
+        format0: &Format,
+    ) -> Result<()> {
+        use Format::*;
+
+        write!(
             self.out,
-            "\npublic init({}) {{",
-            fields
-                .iter()
-                .map(|f| format!("{}: {}", &f.name, self.quote_type(&f.value)))
-                .collect::<Vec<_>>()
-                .join(", ")
+            "func to_json_{}(value: {}) throws -> JsonValue {{",
+            name,
+            self.quote_type(format0)
         )?;
         self.out.indent();
-        for field in fields {
-            writeln!(self.out, "self.{0} = {0}", &field.name)?;
-        }
-        self.out.unindent();
-        writeln!(self.out, "}}")?;
-        // Serialize
-        if self.generator.config.serialization {
-            writeln!(
-                self.out,
-                "\npublic func serialize<S: Serializer>(serializer: S) throws {{",
-            )?;
-            self.out.indent();
-            writeln!(self.out, "try serializer.increase_container_depth()")?;
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if let value = value {{
+    return {}
+}} else {{
+    return .null
+}}
+"#,
+                    self.quote_to_json("value", format)
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+var json : [JsonValue] = []
+for item in value {{
+    json.append({})
+}}
+return .array(json)
+"#,
+                    self.quote_to_json("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                // Entries are sorted as in a `BTreeMap`, whatever the order of the dictionary.
+                write!(
+                    self.out,
+                    r#"
+var json = JsonObject()
+for (key, value) in value {{
+    json.put(key: try Json.key(value: {}), value: {})
+}}
+json.sortKeys(by: {})
+return .object(json)
+"#,
+                    self.quote_to_json("key", key),
+                    self.quote_to_json("value", value),
+                    Self::quote_json_key_order(registry, key),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    "\nreturn .array([{}\n])\n",
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(index, format)| format!(
+                            "\n    {}",
+                            self.quote_to_json(&format!("value.field{}", index), format)
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+if value.count != {0} {{
+    throw SerializationError.invalidValue(issue: "Invalid length for fixed-size array: \(value.count) instead of {0}")
+}}
+var json : [JsonValue] = []
+for item in value {{
+    json.append({1})
+}}
+return .array(json)
+"#,
+                    size,
+                    self.quote_to_json("item", content),
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_json_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "func from_json_{}(json: JsonValue) throws -> {} {{",
+            name,
+            self.quote_type(format0),
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if case .null = json {{
+    return nil
+}} else {{
+    return {}
+}}
+"#,
+                    self.quote_from_json("json", format),
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+var obj : [{}] = []
+for item in try Json.deserialize_array(value: json) {{
+    obj.append({})
+}}
+return obj
+"#,
+                    self.quote_type(format),
+                    self.quote_from_json("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+let entries = try Json.deserialize_object(value: json)
+var obj : [{0}: {1}] = [:]
+for (key, value) in zip(entries.keys, entries.values) {{
+    let k = {2}
+    obj[k] = {3}
+}}
+return obj
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_from_json(".key(key)", key),
+                    self.quote_from_json("value", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    r#"
+let items = try Json.deserialize_array(value: json, length: {})
+return Tuple{}.init({})
+"#,
+                    formats.len(),
+                    formats.len(),
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| self.quote_from_json(&format!("items[{}]", i), f))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+var obj : [{}] = []
+for item in try Json.deserialize_array(value: json, length: {}) {{
+    obj.append({})
+}}
+return obj
+"#,
+                    self.quote_type(content),
+                    size,
+                    self.quote_from_json("item", content)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_variant(&mut self, name: &str, variant: &VariantFormat) -> Result<()> {
+        use VariantFormat::*;
+        self.output_comment(name)?;
+        let name = common::lowercase_first_letter(name).to_mixed_case();
+        match variant {
+            Unit => {
+                writeln!(self.out, "case {}", name)?;
+            }
+            NewType(format) => {
+                writeln!(self.out, "case {}({})", name, self.quote_type(format))?;
+            }
+            Tuple(formats) => {
+                if formats.is_empty() {
+                    writeln!(self.out, "case {}", name)?;
+                } else {
+                    writeln!(self.out, "case {}({})", name, self.quote_types(formats))?;
+                }
+            }
+            Struct(fields) => {
+                if fields.is_empty() {
+                    writeln!(self.out, "case {}", name)?;
+                } else {
+                    writeln!(
+                        self.out,
+                        "case {}({})",
+                        name,
+                        fields
+                            .iter()
+                            .map(|f| format!("{}: {}", f.name, self.quote_type(&f.value)))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )?;
+                }
+            }
+            Variable(_) => panic!("incorrect value"),
+        }
+        Ok(())
+    }
+
+    fn variant_fields(variant: &VariantFormat) -> Vec<Named<Format>> {
+        use VariantFormat::*;
+        match variant {
+            Unit => Vec::new(),
+            NewType(format) => vec![Named {
+                name: "x".to_string(),
+                value: format.as_ref().clone(),
+            }],
+            Tuple(formats) => formats
+                .clone()
+                .into_iter()
+                .enumerate()
+                .map(|(i, f)| Named {
+                    name: format!("x{}", i),
+                    value: f,
+                })
+                .collect(),
+            Struct(fields) => fields.clone(),
+            Variable(_) => panic!("incorrect value"),
+        }
+    }
+
+    fn output_struct_container(
+        &mut self,
+        name: &str,
+        shape: common::JsonShape,
+        json_names: &[String],
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        // Struct
+        writeln!(self.out)?;
+        self.output_comment(name)?;
+        writeln!(
+            self.out,
+            "public struct {}: {} {{",
+            name,
+            self.quote_protocols()
+        )?;
+        self.enter_class(name);
+        for field in fields {
+            self.output_comment(&field.name)?;
+            writeln!(
+                self.out,
+                "@Indirect public var {}: {}",
+                field.name,
+                self.quote_type(&field.value)
+            )?;
+        }
+        // Public constructor
+        writeln!(
+            self.out,
+            "\npublic init({}) {{",
+            fields
+                .iter()
+                .map(|f| format!("{}: {}", &f.name, self.quote_type(&f.value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        self.out.indent();
+        for field in fields {
+            writeln!(self.out, "self.{0} = {0}", &field.name)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        // Serialize
+        if self.generator.config.serialization {
+            writeln!(
+                self.out,
+                "\npublic func serialize<S: Serializer>(serializer: S) throws {{",
+            )?;
+            self.out.indent();
+            writeln!(self.out, "try serializer.increase_container_depth()")?;
             for field in fields {
                 writeln!(
                     self.out,
@@ -573,90 +1178,463 @@ return obj
                     self.quote_serialize_value(&format!("self.{}", &field.name), &field.value)
                 )?;
             }
-            writeln!(self.out, "try serializer.decrease_container_depth()")?;
-            self.out.unindent();
-            writeln!(self.out, "}}")?;
+            writeln!(self.out, "try serializer.decrease_container_depth()")?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            for encoding in &self.generator.config.encodings {
+                self.output_struct_serialize_for_encoding(*encoding)?;
+            }
+        }
+        // Deserialize
+        if self.generator.config.serialization {
+            writeln!(
+                self.out,
+                "\npublic static func deserialize<D: Deserializer>(deserializer: D) throws -> {} {{",
+                name,
+            )?;
+            self.out.indent();
+            writeln!(self.out, "try deserializer.increase_container_depth()")?;
+            for field in fields {
+                writeln!(
+                    self.out,
+                    "let {} = {}",
+                    field.name,
+                    self.quote_deserialize(&field.value)
+                )?;
+            }
+            writeln!(self.out, "try deserializer.decrease_container_depth()")?;
+            writeln!(
+                self.out,
+                "return {}.init({})",
+                name,
+                fields
+                    .iter()
+                    .map(|f| format!("{0}: {0}", &f.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+
+            for encoding in &self.generator.config.encodings {
+                self.output_struct_deserialize_for_encoding(name, *encoding)?;
+            }
+        }
+        // JSON conversions
+        if self.has_json() {
+            self.output_struct_json_conversions(name, shape, json_names, fields)?;
+        }
+        // Validation
+        if self.generator.config.generates_validation() {
+            writeln!(self.out, "\npublic func validate() throws {{")?;
+            self.out.indent();
+            for field in fields {
+                if common::needs_validation(self.generator.config, &field.value, true) {
+                    let value = format!("self.{}", field.name);
+                    writeln!(self.out, "{}", self.quote_validate(&value, &field.value))?;
+                }
+            }
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+        }
+        // Comparisons
+        if self.generator.config.comparisons {
+            writeln!(
+                self.out,
+                "\npublic static func < (lhs: {0}, rhs: {0}) -> Bool {{",
+                name
+            )?;
+            self.out.indent();
+            self.output_fields_comparison(fields.iter().map(|f| {
+                (
+                    format!("lhs.{}", f.name),
+                    format!("rhs.{}", f.name),
+                    &f.value,
+                )
+            }))?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+        }
+        // Custom code
+        self.output_custom_code(name)?;
+        self.leave_class();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_struct_serialize_for_encoding(&mut self, encoding: Encoding) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+public func jsonSerialize() throws -> String {{{}
+    return Json.stringify(value: try self.toJson())
+}}"#,
+                if self.generator.config.validate_on_serialize {
+                    "\n    try self.validate()"
+                } else {
+                    ""
+                },
+            )?;
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(encoding);
+        writeln!(
+            self.out,
+            r#"
+public func {0}Serialize() throws -> [UInt8] {{{2}
+    let serializer = {1}Serializer.init({3});
+    try self.serialize(serializer: serializer){4}
+    return serializer.get_bytes()
+}}"#,
+            encoding.name(),
+            encoding.name().to_camel_case(),
+            if self.generator.config.validate_on_serialize {
+                "\n    try self.validate()"
+            } else {
+                ""
+            },
+            Self::quote_bincode_arguments(&options),
+            match options.limit {
+                Some(limit) => format!(
+                    r#"
+    if serializer.get_buffer_offset() > {} {{
+        throw SerializationError.invalidValue(issue: "Exceeded the size limit of the encoding")
+    }}"#,
+                    limit
+                ),
+                None => String::new(),
+            }
+        )?;
+        Ok(())
+    }
+
+    fn output_struct_deserialize_for_encoding(
+        &mut self,
+        name: &str,
+        encoding: Encoding,
+    ) -> Result<()> {
+        if encoding == Encoding::Json {
+            writeln!(
+                self.out,
+                r#"
+public static func jsonDeserialize(input: String) throws -> {} {{
+    return try fromJson(json: try Json.parse(input: input))
+}}"#,
+                name,
+            )?;
+            return Ok(());
+        }
+        let options = self.generator.config.bincode_options_for(encoding);
+        let checks = match options.trailing_bytes {
+            TrailingBytes::Reject => {
+                r#"
+    if deserializer.get_buffer_offset() < input.count {
+        throw DeserializationError.invalidInput(issue: "Some input bytes were not read")
+    }"#
+            }
+            TrailingBytes::Allow => "",
+        };
+        let mut arguments = Self::quote_bincode_arguments(&options);
+        // The runtime enforces the size limit while reading.
+        if let Some(limit) = options.limit {
+            if !arguments.is_empty() {
+                arguments += ", ";
+            }
+            arguments += &format!("limit: {}", limit.min(i64::MAX as u64));
+        }
+        writeln!(
+            self.out,
+            r#"
+public static func {1}Deserialize(input: [UInt8]) throws -> {0} {{
+    let deserializer = {2}Deserializer.init(input: input{3});
+    let obj = try deserialize(deserializer: deserializer){4}
+    return obj
+}}"#,
+            name,
+            encoding.name(),
+            encoding.name().to_camel_case(),
+            if arguments.is_empty() {
+                arguments
+            } else {
+                format!(", {}", arguments)
+            },
+            checks,
+        )?;
+        Ok(())
+    }
+
+    /// Write the statements building the JSON content of a container or a variant, given the
+    /// JSON names, values, and formats of its fields, and return the resulting expression.
+    fn output_json_content(
+        &mut self,
+        shape: common::JsonShape,
+        fields: &[(String, String, &Format)],
+    ) -> Result<String> {
+        use common::JsonShape;
+        Ok(match shape {
+            JsonShape::Unit => ".null".to_string(),
+            JsonShape::NewType => self.quote_to_json(&fields[0].1, fields[0].2),
+            JsonShape::Tuple => format!(
+                ".array([{}])",
+                fields
+                    .iter()
+                    .map(|(_, value, format)| self.quote_to_json(value, format))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            JsonShape::Struct => {
+                writeln!(
+                    self.out,
+                    "{} json = JsonObject()",
+                    if fields.is_empty() { "let" } else { "var" }
+                )?;
+                for (key, value, format) in fields {
+                    writeln!(
+                        self.out,
+                        "json.put(key: \"{}\", value: {})",
+                        key,
+                        self.quote_to_json(value, format)
+                    )?;
+                }
+                ".object(json)".to_string()
+            }
+        })
+    }
 
-            for encoding in &self.generator.config.encodings {
-                self.output_struct_serialize_for_encoding(*encoding)?;
-            }
-        }
-        // Deserialize
-        if self.generator.config.serialization {
-            writeln!(
-                self.out,
-                "\npublic static func deserialize<D: Deserializer>(deserializer: D) throws -> {} {{",
-                name,
-            )?;
-            self.out.indent();
-            writeln!(self.out, "try deserializer.increase_container_depth()")?;
-            for field in fields {
+    /// Write the statements reading the JSON content `json` of a container or a variant (other
+    /// than a unit), given the JSON names and formats of its fields, and return the values of
+    /// the fields.
+    fn output_json_fields(
+        &mut self,
+        json: &str,
+        shape: common::JsonShape,
+        fields: &[(String, &Format)],
+    ) -> Result<Vec<String>> {
+        use common::JsonShape;
+        Ok(match shape {
+            JsonShape::Unit => Vec::new(),
+            JsonShape::NewType => vec![self.quote_from_json(json, fields[0].1)],
+            JsonShape::Tuple => {
                 writeln!(
                     self.out,
-                    "let {} = {}",
-                    field.name,
-                    self.quote_deserialize(&field.value)
+                    "{} = try Json.deserialize_array(value: {}, length: {})",
+                    if fields.is_empty() { "_" } else { "let items" },
+                    json,
+                    fields.len()
                 )?;
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (_, format))| {
+                        self.quote_from_json(&format!("items[{}]", index), format)
+                    })
+                    .collect()
             }
-            writeln!(self.out, "try deserializer.decrease_container_depth()")?;
-            writeln!(
-                self.out,
-                "return {}.init({})",
-                name,
+            JsonShape::Struct => {
+                writeln!(
+                    self.out,
+                    "{} = try Json.deserialize_object(value: {})",
+                    if fields.is_empty() { "_" } else { "let obj" },
+                    json
+                )?;
                 fields
                     .iter()
-                    .map(|f| format!("{0}: {0}", &f.name))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )?;
-            self.out.unindent();
-            writeln!(self.out, "}}")?;
-
-            for encoding in &self.generator.config.encodings {
-                self.output_struct_deserialize_for_encoding(name, *encoding)?;
+                    .map(|(key, format)| {
+                        // Like Serde, missing optional fields are read as `None`.
+                        let getter = match format {
+                            Format::Option(_) => "optionalField",
+                            _ => "field",
+                        };
+                        self.quote_from_json(&format!("obj.{}(name: \"{}\")", getter, key), format)
+                    })
+                    .collect()
             }
-        }
-        // Custom code
-        self.output_custom_code(name)?;
-        self.leave_class();
-        writeln!(self.out, "}}")?;
-        Ok(())
+        })
     }
 
-    fn output_struct_serialize_for_encoding(&mut self, encoding: Encoding) -> Result<()> {
+    fn output_struct_json_conversions(
+        &mut self,
+        name: &str,
+        shape: common::JsonShape,
+        json_names: &[String],
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        let json_name = |index: usize| {
+            json_names
+                .get(index)
+                .cloned()
+                .unwrap_or_else(|| fields[index].name.clone())
+        };
+        // Serialization
+        writeln!(self.out, "\npublic func toJson() throws -> JsonValue {{")?;
+        self.out.indent();
+        let content = self.output_json_content(
+            shape,
+            &fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| (json_name(i), format!("self.{}", f.name), &f.value))
+                .collect::<Vec<_>>(),
+        )?;
+        writeln!(self.out, "return {}", content)?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        // Deserialization
         writeln!(
             self.out,
-            r#"
-public func {0}Serialize() throws -> [UInt8] {{
-    let serializer = {1}Serializer.init();
-    try self.serialize(serializer: serializer)
-    return serializer.get_bytes()
-}}"#,
-            encoding.name(),
-            encoding.name().to_camel_case()
-        )
+            "\npublic static func fromJson(json: JsonValue) throws -> {} {{",
+            name
+        )?;
+        self.out.indent();
+        if shape == common::JsonShape::Unit {
+            writeln!(self.out, "_ = try Json.deserialize_unit(value: json)")?;
+        }
+        let values = self.output_json_fields(
+            "json",
+            shape,
+            &fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| (json_name(i), &f.value))
+                .collect::<Vec<_>>(),
+        )?;
+        writeln!(
+            self.out,
+            "return {}.init({})",
+            name,
+            fields
+                .iter()
+                .zip(values)
+                .map(|(f, value)| format!("{}: {}", f.name, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
     }
 
-    fn output_struct_deserialize_for_encoding(
+    fn output_enum_json_conversions(
         &mut self,
         name: &str,
-        encoding: Encoding,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
     ) -> Result<()> {
+        // Serialization
+        writeln!(self.out, "\npublic func toJson() throws -> JsonValue {{")?;
+        self.out.indent();
+        writeln!(self.out, "switch self {{")?;
+        for variant in variants.values() {
+            let fields = Self::variant_fields(&variant.value);
+            let formatted_variant_name =
+                common::lowercase_first_letter(&variant.name).to_mixed_case();
+            if fields.is_empty() {
+                writeln!(self.out, "case .{}:", formatted_variant_name)?;
+            } else {
+                writeln!(
+                    self.out,
+                    "case .{}({}):",
+                    formatted_variant_name,
+                    fields
+                        .iter()
+                        .map(|f| format!("let {}", f.name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+            }
+            self.out.indent();
+            let shape = common::JsonShape::of_variant(&variant.value);
+            if shape == common::JsonShape::Unit {
+                writeln!(self.out, "return .string(\"{}\")", variant.name)?;
+            } else {
+                let content = self.output_json_content(
+                    shape,
+                    &fields
+                        .iter()
+                        .map(|f| (f.name.clone(), f.name.clone(), &f.value))
+                        .collect::<Vec<_>>(),
+                )?;
+                writeln!(
+                    self.out,
+                    "return Json.serialize_variant(name: \"{}\", value: {})",
+                    variant.name, content
+                )?;
+            }
+            self.out.unindent();
+        }
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        // Deserialization
         writeln!(
             self.out,
             r#"
-public static func {1}Deserialize(input: [UInt8]) throws -> {0} {{
-    let deserializer = {2}Deserializer.init(input: input);
-    let obj = try deserialize(deserializer: deserializer)
-    if deserializer.get_buffer_offset() < input.count {{
-        throw DeserializationError.invalidInput(issue: "Some input bytes were not read")
-    }}
-    return obj
-}}"#,
+public static func fromJson(json: JsonValue) throws -> {} {{
+    let variant = try Json.deserialize_variant(value: json)
+    switch variant.name {{"#,
+            name
+        )?;
+        self.out.indent();
+        for variant in variants.values() {
+            writeln!(self.out, "case \"{}\":", variant.name)?;
+            self.out.indent();
+            let fields = Self::variant_fields(&variant.value);
+            let formatted_variant_name =
+                common::lowercase_first_letter(&variant.name).to_mixed_case();
+            let shape = common::JsonShape::of_variant(&variant.value);
+            if shape == common::JsonShape::Unit {
+                writeln!(self.out, "try variant.unit()")?;
+            }
+            let values = self.output_json_fields(
+                "try variant.value()",
+                shape,
+                &fields
+                    .iter()
+                    .map(|f| (f.name.clone(), &f.value))
+                    .collect::<Vec<_>>(),
+            )?;
+            if values.is_empty() {
+                writeln!(self.out, "return .{}", formatted_variant_name)?;
+            } else {
+                let init_values = match &variant.value {
+                    VariantFormat::Struct(_) => fields
+                        .iter()
+                        .zip(values)
+                        .map(|(f, value)| format!("{}: {}", f.name, value))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    _ => values.join(", "),
+                };
+                writeln!(
+                    self.out,
+                    "return .{}({})",
+                    formatted_variant_name, init_values
+                )?;
+            }
+            self.out.unindent();
+        }
+        writeln!(
+            self.out,
+            "default: throw DeserializationError.invalidInput(issue: \"Unknown variant for {}: \\(variant.name)\")",
             name,
-            encoding.name(),
-            encoding.name().to_camel_case(),
-        )
+        )?;
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn quote_bincode_arguments(options: &BincodeOptions) -> String {
+        if options.is_custom_encoding() {
+            format!(
+                "varintEncoding: {}, bigEndian: {}",
+                options.int_encoding == IntEncoding::Varint,
+                options.endianness == Endianness::Big
+            )
+        } else {
+            String::new()
+        }
     }
 
     fn output_enum_container(
@@ -666,7 +1644,12 @@ public static func {1}Deserialize(input: [UInt8]) throws -> {0} {{
     ) -> Result<()> {
         writeln!(self.out)?;
         self.output_comment(name)?;
-        writeln!(self.out, "indirect public enum {}: Hashable {{", name)?;
+        writeln!(
+            self.out,
+            "indirect public enum {}: {} {{",
+            name,
+            self.quote_protocols()
+        )?;
         self.current_namespace.push(name.to_string());
         self.out.indent();
         for variant in variants.values() {
@@ -793,6 +1776,21 @@ switch index {{"#,
             }
         }
 
+        // JSON conversions
+        if self.has_json() {
+            self.output_enum_json_conversions(name, variants)?;
+        }
+
+        // Validation
+        if self.generator.config.generates_validation() {
+            self.output_enum_validation(variants)?;
+        }
+
+        // Comparisons
+        if self.generator.config.comparisons {
+            self.output_enum_comparison(name, variants)?;
+        }
+
         self.current_namespace.pop();
         // Custom code
         self.output_custom_code(name)?;
@@ -801,8 +1799,155 @@ switch index {{"#,
         Ok(())
     }
 
+    /// Only the variants with fields needing validation are matched explicitly.
+    fn output_enum_validation(
+        &mut self,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        writeln!(self.out, "\npublic func validate() throws {{")?;
+        self.out.indent();
+        let mut cases = Vec::new();
+        for variant in variants.values() {
+            let fields = Self::variant_fields(&variant.value);
+            if fields
+                .iter()
+                .any(|f| common::needs_validation(self.generator.config, &f.value, true))
+            {
+                cases.push((
+                    common::lowercase_first_letter(&variant.name).to_mixed_case(),
+                    fields,
+                ));
+            }
+        }
+        if !cases.is_empty() {
+            writeln!(self.out, "switch self {{")?;
+            for (name, fields) in &cases {
+                let bindings = fields
+                    .iter()
+                    .map(|f| {
+                        if common::needs_validation(self.generator.config, &f.value, true) {
+                            format!("let {}", f.name)
+                        } else {
+                            "_".to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(self.out, "case .{}({}):", name, bindings)?;
+                self.out.indent();
+                for field in fields {
+                    if common::needs_validation(self.generator.config, &field.value, true) {
+                        writeln!(
+                            self.out,
+                            "{}",
+                            self.quote_validate(&field.name, &field.value)
+                        )?;
+                    }
+                }
+                self.out.unindent();
+            }
+            if cases.len() < variants.len() {
+                writeln!(self.out, "default:\n    break")?;
+            }
+            writeln!(self.out, "}}")?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// Emit the body of `<` comparing the given pairs of values lexicographically.
+    fn output_fields_comparison<'b, I>(&mut self, fields: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (String, String, &'b Format)>,
+    {
+        let mut fields = fields.into_iter().peekable();
+        if fields.peek().is_some() {
+            writeln!(self.out, "var result = 0")?;
+        }
+        for (a, b, format) in fields {
+            writeln!(self.out, "result = {}", self.quote_compare(&a, &b, format))?;
+            writeln!(self.out, "if result != 0 {{ return result < 0 }}")?;
+        }
+        writeln!(self.out, "return false")?;
+        Ok(())
+    }
+
+    /// Variants are ordered by index first, then by their fields.
+    fn output_enum_comparison(
+        &mut self,
+        name: &str,
+        variants: &BTreeMap<u32, Named<VariantFormat>>,
+    ) -> Result<()> {
+        writeln!(self.out, "\nvar variantIndex: Int {{")?;
+        self.out.indent();
+        writeln!(self.out, "switch self {{")?;
+        for (index, variant) in variants {
+            writeln!(
+                self.out,
+                "case .{}: return {}",
+                common::lowercase_first_letter(&variant.name).to_mixed_case(),
+                index
+            )?;
+        }
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        writeln!(
+            self.out,
+            "\npublic static func < (lhs: {0}, rhs: {0}) -> Bool {{",
+            name
+        )?;
+        self.out.indent();
+        writeln!(self.out, "switch (lhs, rhs) {{")?;
+        for variant in variants.values() {
+            let fields = Self::variant_fields(&variant.value);
+            let formatted_variant_name =
+                common::lowercase_first_letter(&variant.name).to_mixed_case();
+            if fields.is_empty() {
+                writeln!(self.out, "case (.{0}, .{0}):", formatted_variant_name)?;
+            } else {
+                let bindings = |prefix: &str| {
+                    (0..fields.len())
+                        .map(|i| format!("{}{}", prefix, i))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                writeln!(
+                    self.out,
+                    "case let (.{0}({1}), .{0}({2})):",
+                    formatted_variant_name,
+                    bindings("a"),
+                    bindings("b")
+                )?;
+            }
+            self.out.indent();
+            self.output_fields_comparison(
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| (format!("a{}", i), format!("b{}", i), &f.value)),
+            )?;
+            self.out.unindent();
+        }
+        writeln!(self.out, "default:")?;
+        self.out.indent();
+        writeln!(self.out, "return lhs.variantIndex < rhs.variantIndex")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
     fn output_container(&mut self, name: &str, format: &ContainerFormat) -> Result<()> {
         use ContainerFormat::*;
+        // JSON keys are the names of the Rust fields, before conversion to Swift names.
+        let json_names = match format {
+            Struct(fields) => fields.iter().map(|f| f.name.clone()).collect(),
+            _ => Vec::new(),
+        };
         let fields = match format {
             UnitStruct => Vec::new(),
             NewTypeStruct(format) => vec![Named {
@@ -824,12 +1969,42 @@ switch index {{"#,
                     value: f.value.clone(),
                 })
                 .collect(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("Swift", name)),
+            FlattenedStruct(_) => {
+                return Err(common::flattened_struct_not_supported("Swift", name))
+            }
             Enum(variants) => {
                 self.output_enum_container(name, variants)?;
                 return Ok(());
             }
         };
-        self.output_struct_container(name, &fields)
+        let shape = common::JsonShape::of_container(format);
+        self.output_struct_container(name, shape, &json_names, &fields)
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+    ) -> std::result::Result<crate::SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let mut files = crate::SourceFiles::new();
+        files.insert_bytes(
+            std::path::Path::new("Sources")
+                .join(&self.config.module_name)
+                .join(format!("{}.swift", self.config.module_name.to_camel_case())),
+            content,
+        );
+        Ok(files)
     }
 }
 
@@ -843,11 +2018,7 @@ impl Installer {
         Installer { install_dir }
     }
 
-    fn install_runtime(
-        &self,
-        source_dir: include_dir::Dir,
-        path: &str,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    fn install_runtime(&self, source_dir: include_dir::Dir, path: &str) -> Result<()> {
         let dir_path = self.install_dir.join(path);
         std::fs::create_dir_all(&dir_path)?;
         for entry in source_dir.files() {
@@ -859,19 +2030,15 @@ impl Installer {
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
         config: &CodeGeneratorConfig,
         registry: &Registry,
     ) -> std::result::Result<(), Self::Error> {
-        let dir_path = self.install_dir.join("Sources").join(&config.module_name);
-        std::fs::create_dir_all(&dir_path)?;
-        let source_path = dir_path.join(format!("{}.swift", config.module_name.to_camel_case()));
-        let mut file = std::fs::File::create(source_path)?;
         let generator = CodeGenerator::new(config);
-        generator.output(&mut file, registry)?;
+        generator.generate(registry)?.write_to(&self.install_dir)?;
         Ok(())
     }
 
@@ -891,4 +2058,24 @@ impl crate::SourceInstaller for Installer {
         // Ignored. Currently always installed with Serde.
         Ok(())
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        // Ignored. Currently always installed with Serde.
+        Ok(())
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        // Ignored. Currently always installed with Serde.
+        Ok(())
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        // Ignored. Currently always installed with Serde.
+        Ok(())
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        // Ignored. Currently always installed with Serde.
+        Ok(())
+    }
 }
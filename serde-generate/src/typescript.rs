@@ -4,19 +4,106 @@
 use crate::{
     common,
     indent::{IndentConfig, IndentedWriter},
-    CodeGeneratorConfig,
+    naming, Capabilities, CodeGeneratorConfig, Encoding, Endianness, Error, IntEncoding, Result,
+    SourceGenerator, TrailingBytes,
 };
 use heck::CamelCase;
 use include_dir::include_dir as include_directory;
 use serde_reflection::{ContainerFormat, Format, FormatHolder, Named, Registry, VariantFormat};
 use std::{
     collections::{BTreeMap, HashMap},
-    io::{Result, Write},
+    io::Write,
     path::PathBuf,
 };
 
+/// Features supported by the TypeScript code generator.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    language: "TypeScript",
+    c_style_enums: false,
+    floats: true,
+    unit_structs: true,
+    u128: true,
+    recursive_types: true,
+    tagged_enums: false,
+    encodings: crate::capabilities::ALL_ENCODINGS_AND_JSON,
+    package_manifest: false,
+    naming_conventions: true,
+    file_naming_conventions: false,
+    modules: true,
+    module_cycles: true,
+    native_types: true,
+    comparisons: true,
+    validation: true,
+    bincode_options: true,
+};
+
+/// Reserved words of TypeScript, which names obtained from naming conventions must avoid.
+const RESERVED_WORDS: &[&str] = &[
+    "any",
+    "as",
+    "boolean",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "constructor",
+    "continue",
+    "debugger",
+    "declare",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "get",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "module",
+    "new",
+    "null",
+    "number",
+    "of",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "require",
+    "return",
+    "set",
+    "static",
+    "string",
+    "super",
+    "switch",
+    "symbol",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
 /// Main configuration object for code-generation in TypeScript, powered by
 /// the Deno runtime.
+#[derive(Clone)]
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
     config: &'a CodeGeneratorConfig,
@@ -38,9 +125,6 @@ struct TypeScriptEmitter<'a, T> {
 impl<'a> CodeGenerator<'a> {
     /// Create a TypeScript code generator for the given config.
     pub fn new(config: &'a CodeGeneratorConfig) -> Self {
-        if config.c_style_enums {
-            panic!("TypeScript does not support generating c-style enums");
-        }
         let mut external_qualified_names = HashMap::new();
         for (namespace, names) in &config.external_definitions {
             for name in names {
@@ -63,6 +147,16 @@ impl<'a> CodeGenerator<'a> {
 
     /// Output class definitions for `registry` in a single source file.
     pub fn output(&self, out: &mut dyn Write, registry: &Registry) -> Result<()> {
+        CAPABILITIES.check(registry, self.config)?;
+        let registry = &common::remove_native_types(self.config, registry);
+        if let Some((config, registry)) = naming::convert(
+            CAPABILITIES.language,
+            |name| RESERVED_WORDS.contains(&name),
+            self.config,
+            registry,
+        )? {
+            return CodeGenerator::new(&config).output(out, &registry);
+        }
         let mut emitter = TypeScriptEmitter {
             out: IndentedWriter::new(out, IndentConfig::Space(2)),
             generator: self,
@@ -74,7 +168,10 @@ impl<'a> CodeGenerator<'a> {
             emitter.output_container(name, format)?;
         }
 
-        if self.config.serialization {
+        if self.config.serialization
+            || self.config.comparisons
+            || self.config.generates_validation()
+        {
             emitter.output_helpers(registry)?;
         }
 
@@ -95,6 +192,28 @@ import {{ BcsSerializer, BcsDeserializer }} from '../bcs/mod.ts';
 import {{ Optional, Seq, Tuple, ListTuple, unit, bool, int8, int16, int32, int64, int128, uint8, uint16, uint32, uint64, uint128, float32, float64, char, str, bytes }} from '../serde/mod.ts';
 "#,
         )?;
+        for encoding in self.binary_encodings() {
+            if encoding != Encoding::Bcs {
+                writeln!(
+                    self.out,
+                    "import {{ {0}Serializer, {0}Deserializer }} from '../{1}/mod.ts';\n",
+                    encoding.name().to_camel_case(),
+                    encoding.name()
+                )?;
+            }
+        }
+        if self.has_json() {
+            writeln!(
+                self.out,
+                "import {{ Json, JsonKey, JsonObject, JsonValue, JsonVariant, parseJson, stringifyJson }} from '../json/mod.ts';\n"
+            )?;
+        }
+        if self.generator.config.comparisons {
+            writeln!(
+                self.out,
+                "import {{ compareBools, compareNumbers, compareStrings, compareBytes, hashCombine, hashNumber, hashBigInt, hashString, hashBytes }} from '../serde/mod.ts';\n"
+            )?;
+        }
         for namespace in self.generator.namespaces_to_import.iter() {
             writeln!(
                 self.out,
@@ -127,7 +246,10 @@ import {{ Optional, Seq, Tuple, ListTuple, unit, bool, int8, int16, int32, int64
     fn quote_type(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(x) => self.quote_qualified_name(x),
+            TypeName(x) => match self.generator.config.native_types.get(x) {
+                Some(native) => native.name.clone(),
+                None => self.quote_qualified_name(x),
+            },
             Unit => "unit".into(),
             Bool => "bool".into(),
             I8 => "int8".into(),
@@ -184,12 +306,28 @@ import {{ Optional, Seq, Tuple, ListTuple, unit, bool, int8, int16, int32, int64
         writeln!(self.out, "export class Helpers {{")?;
         self.out.indent();
         for (mangled_name, subtype) in &subtypes {
-            self.output_serialization_helper(mangled_name, subtype)?;
-            self.output_deserialization_helper(mangled_name, subtype)?;
+            if self.generator.config.serialization {
+                self.output_serialization_helper(mangled_name, subtype)?;
+                self.output_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.has_json() {
+                self.output_json_serialization_helper(mangled_name, subtype)?;
+                self.output_json_deserialization_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.comparisons {
+                self.output_comparison_helper(mangled_name, subtype)?;
+                self.output_hash_helper(mangled_name, subtype)?;
+            }
+            if self.generator.config.generates_validation()
+                && common::needs_validation(self.generator.config, subtype, true)
+            {
+                self.output_validation_helper(mangled_name, subtype)?;
+            }
         }
         self.out.unindent();
         writeln!(self.out, "}}")?;
-        writeln!(self.out)
+        writeln!(self.out)?;
+        Ok(())
     }
 
     fn needs_helper(format: &Format) -> bool {
@@ -205,7 +343,12 @@ import {{ Optional, Seq, Tuple, ListTuple, unit, bool, int8, int16, int32, int64
         let this_str = if use_this { "this." } else { "" };
 
         match format {
-            TypeName(_) => format!("{}{}.serialize(serializer);", this_str, value),
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => {
+                    format!("{}({}{}, serializer);", native.serializer, this_str, value)
+                }
+                None => format!("{}{}.serialize(serializer);", this_str, value),
+            },
             Unit => format!("serializer.serializeUnit({}{});", this_str, value),
             Bool => format!("serializer.serializeBool({}{});", this_str, value),
             I8 => format!("serializer.serializeI8({}{});", this_str, value),
@@ -235,40 +378,648 @@ import {{ Optional, Seq, Tuple, ListTuple, unit, bool, int8, int16, int32, int64
     fn quote_deserialize(&self, format: &Format) -> String {
         use Format::*;
         match format {
-            TypeName(name) => format!(
-                "{}.deserialize(deserializer)",
-                self.quote_qualified_name(name)
+            TypeName(name) => match self.generator.config.native_types.get(name) {
+                Some(native) => format!("{}(deserializer)", native.deserializer),
+                None => format!(
+                    "{}.deserialize(deserializer)",
+                    self.quote_qualified_name(name)
+                ),
+            },
+            Unit => "deserializer.deserializeUnit()".to_string(),
+            Bool => "deserializer.deserializeBool()".to_string(),
+            I8 => "deserializer.deserializeI8()".to_string(),
+            I16 => "deserializer.deserializeI16()".to_string(),
+            I32 => "deserializer.deserializeI32()".to_string(),
+            I64 => "deserializer.deserializeI64()".to_string(),
+            I128 => "deserializer.deserializeI128()".to_string(),
+            U8 => "deserializer.deserializeU8()".to_string(),
+            U16 => "deserializer.deserializeU16()".to_string(),
+            U32 => "deserializer.deserializeU32()".to_string(),
+            U64 => "deserializer.deserializeU64()".to_string(),
+            U128 => "deserializer.deserializeU128()".to_string(),
+            F32 => "deserializer.deserializeF32()".to_string(),
+            F64 => "deserializer.deserializeF64()".to_string(),
+            Char => "deserializer.deserializeChar()".to_string(),
+            Str => "deserializer.deserializeStr()".to_string(),
+            Bytes => "deserializer.deserializeBytes()".to_string(),
+            _ => format!(
+                "Helpers.deserialize{}(deserializer)",
+                common::mangle_type(format).to_camel_case(),
+            ),
+        }
+    }
+
+    fn quote_compare(&self, a: &str, b: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) => format!("{}.compareTo({})", a, b),
+            Unit => "0".to_string(),
+            Bool => format!("compareBools({}, {})", a, b),
+            Char | Str => format!("compareStrings({}, {})", a, b),
+            Bytes => format!("compareBytes({}, {})", a, b),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            Option(_) | Seq(_) | Map { .. } | Tuple(_) | TupleArray { .. } => format!(
+                "Helpers.compare{}({}, {})",
+                common::mangle_type(format).to_camel_case(),
+                a,
+                b
+            ),
+            _ => format!("compareNumbers({}, {})", a, b),
+        }
+    }
+
+    fn quote_hash(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) => format!("{}.hashCode()", value),
+            Unit => "0".to_string(),
+            Bool => format!("({} ? 1 : 0)", value),
+            I64 | I128 | U64 | U128 => format!("hashBigInt({})", value),
+            Char | Str => format!("hashString({})", value),
+            Bytes => format!("hashBytes({})", value),
+            F32 | F64 | Variable(_) => panic!("unexpected value"),
+            Option(_) | Seq(_) | Map { .. } | Tuple(_) | TupleArray { .. } => format!(
+                "Helpers.hash{}({})",
+                common::mangle_type(format).to_camel_case(),
+                value
+            ),
+            _ => format!("hashNumber({})", value),
+        }
+    }
+
+    fn output_comparison_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "static compare{0}(a: {1}, b: {1}): number {{",
+            name.to_camel_case(),
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (a === null || b === null) {{
+    return compareBools(a !== null, b !== null);
+}}
+return {};
+"#,
+                    self.quote_compare("a", "b", format)
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+for (let i = 0; i < a.length && i < b.length; i++) {{
+    const result = {};
+    if (result !== 0) return result;
+}}
+return compareNumbers(a.length, b.length);
+"#,
+                    self.quote_compare("a[i]", "b[i]", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+const x = [...a.keys()].sort((k, l) => {0});
+const y = [...b.keys()].sort((k, l) => {0});
+for (let i = 0; i < x.length && i < y.length; i++) {{
+    let result = {1};
+    if (result !== 0) return result;
+    result = {2};
+    if (result !== 0) return result;
+}}
+return compareNumbers(x.length, y.length);
+"#,
+                    self.quote_compare("k", "l", key),
+                    self.quote_compare("x[i]", "y[i]", key),
+                    self.quote_compare("a.get(x[i])!", "b.get(y[i])!", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out, "\nlet result: number;")?;
+                for (index, format) in formats.iter().enumerate() {
+                    let a = format!("a[{}]", index);
+                    let b = format!("b[{}]", index);
+                    writeln!(
+                        self.out,
+                        "if ((result = {}) !== 0) return result;",
+                        self.quote_compare(&a, &b, format)
+                    )?;
+                }
+                writeln!(self.out, "return 0;")?;
+            }
+
+            TupleArray { content, .. } => {
+                write!(
+                    self.out,
+                    r#"
+for (let i = 0; i < a.length && i < b.length; i++) {{
+    const result = {};
+    if (result !== 0) return result;
+}}
+return compareNumbers(a.length, b.length);
+"#,
+                    self.quote_compare("a[i][0]", "b[i][0]", content)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_hash_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "static hash{}(value: {}): number {{",
+            name.to_camel_case(),
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    "\nreturn value === null ? 0 : hashCombine(1, {});\n",
+                    self.quote_hash("value", format)
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    "\nreturn value.reduce((hash: number, item) => hashCombine(hash, {}), value.length);\n",
+                    self.quote_hash("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                // Entries are combined with a commutative operation since maps are unordered.
+                write!(
+                    self.out,
+                    r#"
+let hash = value.size;
+for (const [k, v] of value.entries()) {{
+    hash = (hash + hashCombine({}, {})) | 0;
+}}
+return hash;
+"#,
+                    self.quote_hash("k", key),
+                    self.quote_hash("v", value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out, "\nlet hash = 0;")?;
+                for (index, format) in formats.iter().enumerate() {
+                    writeln!(
+                        self.out,
+                        "hash = hashCombine(hash, {});",
+                        self.quote_hash(&format!("value[{}]", index), format)
+                    )?;
+                }
+                writeln!(self.out, "return hash;")?;
+            }
+
+            TupleArray { content, .. } => {
+                write!(
+                    self.out,
+                    "\nreturn value.reduce((hash: number, item) => hashCombine(hash, {}), value.length);\n",
+                    self.quote_hash("item[0]", content)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn quote_validate(&self, value: &str, format: &Format) -> String {
+        match format {
+            Format::TypeName(_) => format!("{}.validate();", value),
+            _ => format!(
+                "Helpers.validate{}({});",
+                common::mangle_type(format).to_camel_case(),
+                value
+            ),
+        }
+    }
+
+    fn quote_length_check(size: usize) -> String {
+        format!(
+            r#"if (value.length !== {0}) {{
+  throw new Error("Invalid length for fixed-size array: " + value.length + " instead of {0}");
+}}"#,
+            size
+        )
+    }
+
+    fn output_validation_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        writeln!(
+            self.out,
+            "static validate{}(value: {}): void {{",
+            name.to_camel_case(),
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        let needs_validation =
+            |format| common::needs_validation(self.generator.config, format, true);
+        match format0 {
+            Option(format) => {
+                writeln!(
+                    self.out,
+                    "if (value !== null) {{\n  {}\n}}",
+                    self.quote_validate("value", format)
+                )?;
+            }
+
+            Seq(format) => {
+                writeln!(
+                    self.out,
+                    "for (const item of value) {{\n  {}\n}}",
+                    self.quote_validate("item", format)
+                )?;
+            }
+
+            Map { key, value } => {
+                writeln!(self.out, "for (const [k, v] of value.entries()) {{")?;
+                if needs_validation(key) {
+                    writeln!(self.out, "  {}", self.quote_validate("k", key))?;
+                }
+                if needs_validation(value) {
+                    writeln!(self.out, "  {}", self.quote_validate("v", value))?;
+                }
+                writeln!(self.out, "}}")?;
+            }
+
+            Tuple(formats) => {
+                for (index, format) in formats.iter().enumerate() {
+                    if needs_validation(format) {
+                        let expr = format!("value[{}]", index);
+                        writeln!(self.out, "{}", self.quote_validate(&expr, format))?;
+                    }
+                }
+            }
+
+            TupleArray { content, size } => {
+                writeln!(self.out, "{}", Self::quote_length_check(*size))?;
+                if needs_validation(content) {
+                    writeln!(
+                        self.out,
+                        "for (const item of value) {{\n  {}\n}}",
+                        self.quote_validate("item[0]", content)
+                    )?;
+                }
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// Emit `compareTo`, `equals` and `hashCode` for a struct or a variant.
+    /// Variants are ordered by index first, then by their fields.
+    fn output_comparisons(
+        &mut self,
+        variant_base: Option<&str>,
+        variant_index: Option<u32>,
+        name: &str,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        let other_type = variant_base.unwrap_or(name);
+        writeln!(
+            self.out,
+            "public compareTo(other: {}): number {{",
+            other_type
+        )?;
+        self.out.indent();
+        if let Some(index) = variant_index {
+            writeln!(
+                self.out,
+                "if (!(other instanceof {}Variant{})) return compareNumbers({}, other.variantIndex());",
+                other_type, name, index
+            )?;
+        }
+        if !fields.is_empty() {
+            writeln!(self.out, "let result: number;")?;
+        }
+        for field in fields {
+            writeln!(
+                self.out,
+                "if ((result = {}) !== 0) return result;",
+                self.quote_compare(
+                    &format!("this.{}", field.name),
+                    &format!("other.{}", field.name),
+                    &field.value
+                )
+            )?;
+        }
+        writeln!(self.out, "return 0;")?;
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+
+        writeln!(
+            self.out,
+            "public equals(other: {}): boolean {{\n  return this.compareTo(other) === 0;\n}}\n",
+            other_type
+        )?;
+
+        writeln!(self.out, "public hashCode(): number {{")?;
+        self.out.indent();
+        writeln!(self.out, "let hash = {};", variant_index.unwrap_or(0))?;
+        for field in fields {
+            writeln!(
+                self.out,
+                "hash = hashCombine(hash, {});",
+                self.quote_hash(&format!("this.{}", field.name), &field.value)
+            )?;
+        }
+        writeln!(self.out, "return hash;")?;
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+
+        if let Some(index) = variant_index {
+            writeln!(
+                self.out,
+                "public variantIndex(): number {{\n  return {};\n}}\n",
+                index
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "static serialize{}(value: {}, serializer: Serializer): void {{",
+            name.to_camel_case(),
+            self.quote_type(format0)
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+if (value) {{
+    serializer.serializeOptionTag(true);
+    {}
+}} else {{
+    serializer.serializeOptionTag(false);
+}}
+"#,
+                    self.quote_serialize_value("value", format, false)
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+serializer.serializeLen(value.length);
+value.forEach((item: {}) => {{
+    {}
+}});
+"#,
+                    self.quote_type(format),
+                    self.quote_serialize_value("item", format, false)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+serializer.serializeLen(value.size);
+const offsets: number[] = [];
+for (const [k, v] of value.entries()) {{
+  offsets.push(serializer.getBufferOffset());
+  {}
+  {}
+}}
+serializer.sortMapEntries(offsets);
+"#,
+                    self.quote_serialize_value("k", key, false),
+                    self.quote_serialize_value("v", value, false)
+                )?;
+            }
+
+            Tuple(formats) => {
+                writeln!(self.out)?;
+                for (index, format) in formats.iter().enumerate() {
+                    let expr = format!("value[{}]", index);
+                    writeln!(
+                        self.out,
+                        "{}",
+                        self.quote_serialize_value(&expr, format, false)
+                    )?;
+                }
+            }
+
+            TupleArray { content, size } => {
+                if self.generator.config.validate_on_serialize {
+                    write!(self.out, "\n{}", Self::quote_length_check(*size))?;
+                }
+                write!(
+                    self.out,
+                    r#"
+value.forEach((item) =>{{
+    {}
+}});
+"#,
+                    self.quote_serialize_value("item[0]", content, false)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+        use Format::*;
+
+        write!(
+            self.out,
+            "static deserialize{}(deserializer: Deserializer): {} {{",
+            name.to_camel_case(),
+            self.quote_type(format0),
+        )?;
+        self.out.indent();
+        match format0 {
+            Option(format) => {
+                write!(
+                    self.out,
+                    r#"
+const tag = deserializer.deserializeOptionTag();
+if (!tag) {{
+    return null;
+}} else {{
+    return {};
+}}
+"#,
+                    self.quote_deserialize(format),
+                )?;
+            }
+
+            Seq(format) => {
+                write!(
+                    self.out,
+                    r#"
+const length = deserializer.deserializeLen();
+const list: {} = [];
+for (let i = 0; i < length; i++) {{
+    list.push({});
+}}
+return list;
+"#,
+                    self.quote_type(format0),
+                    self.quote_deserialize(format)
+                )?;
+            }
+
+            Map { key, value } => {
+                write!(
+                    self.out,
+                    r#"
+const length = deserializer.deserializeLen();
+const obj = new Map<{0}, {1}>();
+let previousKeyStart = 0;
+let previousKeyEnd = 0;
+for (let i = 0; i < length; i++) {{
+    const keyStart = deserializer.getBufferOffset();
+    const key = {2};
+    const keyEnd = deserializer.getBufferOffset();
+    if (i > 0) {{
+        deserializer.checkThatKeySlicesAreIncreasing(
+            [previousKeyStart, previousKeyEnd],
+            [keyStart, keyEnd]);
+    }}
+    previousKeyStart = keyStart;
+    previousKeyEnd = keyEnd;
+    const value = {3};
+    obj.set(key, value);
+}}
+return obj;
+"#,
+                    self.quote_type(key),
+                    self.quote_type(value),
+                    self.quote_deserialize(key),
+                    self.quote_deserialize(value),
+                )?;
+            }
+
+            Tuple(formats) => {
+                write!(
+                    self.out,
+                    r#"
+return [{}
+];
+"#,
+                    formats
+                        .iter()
+                        .map(|f| format!("\n    {}", self.quote_deserialize(f)))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?;
+            }
+
+            TupleArray { content, size } => {
+                write!(
+                    self.out,
+                    r#"
+const list: {} = [];
+for (let i = 0; i < {}; i++) {{
+    list.push([{}]);
+}}
+return list;
+"#,
+                    self.quote_type(format0),
+                    size,
+                    self.quote_deserialize(content)
+                )?;
+            }
+
+            _ => panic!("unexpected case"),
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    /// Whether to generate the JSON encoding, which reads and writes values as trees of
+    /// JSON values rather than through a `Serializer` or a `Deserializer`.
+    fn has_json(&self) -> bool {
+        self.generator.config.serialization
+            && self.generator.config.encodings.contains(&Encoding::Json)
+    }
+
+    fn quote_to_json(&self, value: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(_) => format!("{}.toJson()", value),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "Json.serialize{}({})",
+                common::mangle_type(format).to_camel_case(),
+                value
+            ),
+            _ => format!(
+                "Helpers.toJson{}({})",
+                common::mangle_type(format).to_camel_case(),
+                value
+            ),
+        }
+    }
+
+    fn quote_from_json(&self, json: &str, format: &Format) -> String {
+        use Format::*;
+        match format {
+            TypeName(name) => format!("{}.fromJson({})", self.quote_qualified_name(name), json),
+            Unit | Bool | I8 | I16 | I32 | I64 | I128 | U8 | U16 | U32 | U64 | U128 | F32 | F64
+            | Char | Str | Bytes => format!(
+                "Json.deserialize{}({})",
+                common::mangle_type(format).to_camel_case(),
+                json
             ),
-            Unit => "deserializer.deserializeUnit()".to_string(),
-            Bool => "deserializer.deserializeBool()".to_string(),
-            I8 => "deserializer.deserializeI8()".to_string(),
-            I16 => "deserializer.deserializeI16()".to_string(),
-            I32 => "deserializer.deserializeI32()".to_string(),
-            I64 => "deserializer.deserializeI64()".to_string(),
-            I128 => "deserializer.deserializeI128()".to_string(),
-            U8 => "deserializer.deserializeU8()".to_string(),
-            U16 => "deserializer.deserializeU16()".to_string(),
-            U32 => "deserializer.deserializeU32()".to_string(),
-            U64 => "deserializer.deserializeU64()".to_string(),
-            U128 => "deserializer.deserializeU128()".to_string(),
-            F32 => "deserializer.deserializeF32()".to_string(),
-            F64 => "deserializer.deserializeF64()".to_string(),
-            Char => "deserializer.deserializeChar()".to_string(),
-            Str => "deserializer.deserializeStr()".to_string(),
-            Bytes => "deserializer.deserializeBytes()".to_string(),
             _ => format!(
-                "Helpers.deserialize{}(deserializer)",
+                "Helpers.fromJson{}({})",
                 common::mangle_type(format).to_camel_case(),
+                json
             ),
         }
     }
 
-    fn output_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+    fn output_json_serialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
         use Format::*;
 
         write!(
             self.out,
-            "static serialize{}(value: {}, serializer: Serializer): void {{",
+            "static toJson{}(value: {}): JsonValue {{",
             name.to_camel_case(),
             self.quote_type(format0)
         )?;
@@ -278,14 +1029,13 @@ import {{ Optional, Seq, Tuple, ListTuple, unit, bool, int8, int16, int32, int64
                 write!(
                     self.out,
                     r#"
-if (value) {{
-    serializer.serializeOptionTag(true);
-    {}
+if (value === null) {{
+    return null;
 }} else {{
-    serializer.serializeOptionTag(false);
+    return {};
 }}
 "#,
-                    self.quote_serialize_value("value", format, false)
+                    self.quote_to_json("value", format)
                 )?;
             }
 
@@ -293,13 +1043,10 @@ if (value) {{
                 write!(
                     self.out,
                     r#"
-serializer.serializeLen(value.length);
-value.forEach((item: {}) => {{
-    {}
-}});
+return value.map((item: {}) => {});
 "#,
                     self.quote_type(format),
-                    self.quote_serialize_value("item", format, false)
+                    self.quote_to_json("item", format)
                 )?;
             }
 
@@ -307,59 +1054,62 @@ value.forEach((item: {}) => {{
                 write!(
                     self.out,
                     r#"
-serializer.serializeLen(value.size);
-const offsets: number[] = [];
+const json = new JsonObject();
 for (const [k, v] of value.entries()) {{
-  offsets.push(serializer.getBufferOffset());
-  {}
-  {}
+  json.put(Json.key({}), {});
 }}
-serializer.sortMapEntries(offsets);
+return json;
 "#,
-                    self.quote_serialize_value("k", key, false),
-                    self.quote_serialize_value("v", value, false)
+                    self.quote_to_json("k", key),
+                    self.quote_to_json("v", value)
                 )?;
             }
 
             Tuple(formats) => {
-                writeln!(self.out)?;
-                for (index, format) in formats.iter().enumerate() {
-                    let expr = format!("value[{}]", index);
-                    writeln!(
-                        self.out,
-                        "{}",
-                        self.quote_serialize_value(&expr, format, false)
-                    )?;
-                }
+                write!(
+                    self.out,
+                    r#"
+return [{}
+];
+"#,
+                    formats
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| format!(
+                            "\n    {}",
+                            self.quote_to_json(&format!("value[{}]", i), f)
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?;
             }
 
-            TupleArray {
-                content,
-                size: _size,
-            } => {
+            TupleArray { content, size } => {
+                if self.generator.config.validate_on_serialize {
+                    write!(self.out, "\n{}", Self::quote_length_check(*size))?;
+                }
                 write!(
                     self.out,
                     r#"
-value.forEach((item) =>{{
-    {}
-}});
+return value.map((item) => {});
 "#,
-                    self.quote_serialize_value("item[0]", content, false)
+                    self.quote_to_json("item[0]", content)
                 )?;
             }
 
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
     }
 
-    fn output_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
+    fn output_json_deserialization_helper(&mut self, name: &str, format0: &Format) -> Result<()> {
         use Format::*;
 
         write!(
             self.out,
-            "static deserialize{}(deserializer: Deserializer): {} {{",
+            "static fromJson{}(json: JsonValue): {} {{",
             name.to_camel_case(),
             self.quote_type(format0),
         )?;
@@ -369,14 +1119,13 @@ value.forEach((item) =>{{
                 write!(
                     self.out,
                     r#"
-const tag = deserializer.deserializeOptionTag();
-if (!tag) {{
+if (json === null) {{
     return null;
 }} else {{
     return {};
 }}
 "#,
-                    self.quote_deserialize(format),
+                    self.quote_from_json("json", format),
                 )?;
             }
 
@@ -384,15 +1133,9 @@ if (!tag) {{
                 write!(
                     self.out,
                     r#"
-const length = deserializer.deserializeLen();
-const list: {} = [];
-for (let i = 0; i < length; i++) {{
-    list.push({});
-}}
-return list;
+return Json.deserializeArray(json).map((item) => {});
 "#,
-                    self.quote_type(format0),
-                    self.quote_deserialize(format)
+                    self.quote_from_json("item", format)
                 )?;
             }
 
@@ -400,21 +1143,10 @@ return list;
                 write!(
                     self.out,
                     r#"
-const length = deserializer.deserializeLen();
+const entries = Json.deserializeObject(json);
 const obj = new Map<{0}, {1}>();
-let previousKeyStart = 0;
-let previousKeyEnd = 0;
-for (let i = 0; i < length; i++) {{
-    const keyStart = deserializer.getBufferOffset();
+for (let i = 0; i < entries.size(); i++) {{
     const key = {2};
-    const keyEnd = deserializer.getBufferOffset();
-    if (i > 0) {{
-        deserializer.checkThatKeySlicesAreIncreasing(
-            [previousKeyStart, previousKeyEnd],
-            [keyStart, keyEnd]);
-    }}
-    previousKeyStart = keyStart;
-    previousKeyEnd = keyEnd;
     const value = {3};
     obj.set(key, value);
 }}
@@ -422,8 +1154,8 @@ return obj;
 "#,
                     self.quote_type(key),
                     self.quote_type(value),
-                    self.quote_deserialize(key),
-                    self.quote_deserialize(value),
+                    self.quote_from_json("new JsonKey(entries.keys[i])", key),
+                    self.quote_from_json("entries.values[i]", value),
                 )?;
             }
 
@@ -431,12 +1163,18 @@ return obj;
                 write!(
                     self.out,
                     r#"
+const items = Json.deserializeArray(json, {});
 return [{}
 ];
 "#,
+                    formats.len(),
                     formats
                         .iter()
-                        .map(|f| format!("\n    {}", self.quote_deserialize(f)))
+                        .enumerate()
+                        .map(|(i, f)| format!(
+                            "\n    {}",
+                            self.quote_from_json(&format!("items[{}]", i), f)
+                        ))
                         .collect::<Vec<_>>()
                         .join(",")
                 )?;
@@ -446,22 +1184,248 @@ return [{}
                 write!(
                     self.out,
                     r#"
-const list: {} = [];
-for (let i = 0; i < {}; i++) {{
-    list.push([{}]);
-}}
-return list;
+return Json.deserializeArray(json, {}).map((item): [{}] => [{}]);
 "#,
-                    self.quote_type(format0),
                     size,
-                    self.quote_deserialize(content)
+                    self.quote_type(content),
+                    self.quote_from_json("item", content)
                 )?;
             }
 
             _ => panic!("unexpected case"),
         }
         self.out.unindent();
-        writeln!(self.out, "}}\n")
+        writeln!(self.out, "}}\n")?;
+        Ok(())
+    }
+
+    fn output_json_conversions(
+        &mut self,
+        variant_base: Option<&str>,
+        name: &str,
+        shape: common::JsonShape,
+        fields: &[Named<Format>],
+    ) -> Result<()> {
+        use common::JsonShape;
+        // Serialization
+        writeln!(self.out, "public toJson(): JsonValue {{")?;
+        self.out.indent();
+        let content = match shape {
+            JsonShape::Unit => "null".to_string(),
+            JsonShape::NewType => {
+                self.quote_to_json(&format!("this.{}", fields[0].name), &fields[0].value)
+            }
+            JsonShape::Tuple => format!(
+                "[{}]",
+                fields
+                    .iter()
+                    .map(|f| self.quote_to_json(&format!("this.{}", f.name), &f.value))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            JsonShape::Struct => {
+                writeln!(self.out, "const json = new JsonObject();")?;
+                for field in fields {
+                    writeln!(
+                        self.out,
+                        "json.put(\"{}\", {});",
+                        field.name,
+                        self.quote_to_json(&format!("this.{}", field.name), &field.value)
+                    )?;
+                }
+                "json".to_string()
+            }
+        };
+        match variant_base {
+            Some(_) if shape == JsonShape::Unit => writeln!(self.out, "return \"{}\";", name)?,
+            Some(_) => writeln!(
+                self.out,
+                "return Json.serializeVariant(\"{}\", {});",
+                name, content
+            )?,
+            None => writeln!(self.out, "return {};", content)?,
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        // Deserialization
+        let class_name = match variant_base {
+            None => {
+                writeln!(self.out, "static fromJson(json: JsonValue): {} {{", name)?;
+                self.out.indent();
+                if shape == JsonShape::Unit {
+                    writeln!(self.out, "Json.deserializeUnit(json);")?;
+                }
+                name.to_string()
+            }
+            Some(base) => {
+                let class_name = format!("{}Variant{}", base, name);
+                writeln!(
+                    self.out,
+                    "static loadJson(variant: JsonVariant): {} {{",
+                    class_name
+                )?;
+                self.out.indent();
+                if shape == JsonShape::Unit {
+                    writeln!(self.out, "variant.unit();")?;
+                } else {
+                    writeln!(self.out, "const json = variant.value();")?;
+                }
+                class_name
+            }
+        };
+        let values = match shape {
+            JsonShape::Unit => Vec::new(),
+            JsonShape::NewType => vec![self.quote_from_json("json", &fields[0].value)],
+            JsonShape::Tuple => {
+                writeln!(
+                    self.out,
+                    "const items = Json.deserializeArray(json, {});",
+                    fields.len()
+                )?;
+                fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| self.quote_from_json(&format!("items[{}]", i), &f.value))
+                    .collect()
+            }
+            JsonShape::Struct => {
+                writeln!(self.out, "const object = Json.deserializeObject(json);")?;
+                fields
+                    .iter()
+                    .map(|f| {
+                        // Like Serde, missing optional fields are read as `None`.
+                        let getter = match f.value {
+                            Format::Option(_) => "optionalField",
+                            _ => "field",
+                        };
+                        self.quote_from_json(
+                            &format!("object.{}(\"{}\")", getter, f.name),
+                            &f.value,
+                        )
+                    })
+                    .collect()
+            }
+        };
+        for (field, value) in fields.iter().zip(values) {
+            writeln!(self.out, "const {} = {};", field.name, value)?;
+        }
+        writeln!(
+            self.out,
+            "return new {}({});",
+            class_name,
+            fields
+                .iter()
+                .map(|f| f.name.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}\n")?;
+        if variant_base.is_none() {
+            self.output_json_text_methods(name)?;
+        }
+        Ok(())
+    }
+
+    /// Binary encodings for which the generated classes provide serialization methods.
+    fn binary_encodings(&self) -> Vec<Encoding> {
+        if !self.generator.config.serialization {
+            return Vec::new();
+        }
+        self.generator
+            .config
+            .encodings
+            .iter()
+            .copied()
+            .filter(|encoding| *encoding != Encoding::Json)
+            .collect()
+    }
+
+    fn output_encoding_methods(&mut self, name: &str) -> Result<()> {
+        for encoding in self.binary_encodings() {
+            let options = self.generator.config.bincode_options_for(encoding);
+            // The runtime enforces the size limit while reading.
+            let deserializer_arguments = match options.limit {
+                Some(limit) => format!(
+                    ", {}, {}, {}",
+                    options.int_encoding == IntEncoding::Varint,
+                    options.endianness == Endianness::Big,
+                    limit
+                ),
+                None if options.is_custom_encoding() => {
+                    format!(", {}", common::bincode_runtime_arguments(&options))
+                }
+                None => String::new(),
+            };
+            writeln!(
+                self.out,
+                r#"public {0}Serialize(): Uint8Array {{{2}
+  const serializer = new {1}Serializer({3});
+  this.serialize(serializer);
+  const bytes = serializer.getBytes();{4}
+  return bytes;
+}}
+
+static {0}Deserialize(input: Uint8Array): {5} {{
+  const deserializer = new {1}Deserializer(input{6});
+  const value = {5}.deserialize(deserializer);{7}
+  return value;
+}}
+"#,
+                encoding.name(),
+                encoding.name().to_camel_case(),
+                if self.generator.config.validate_on_serialize {
+                    "\n  this.validate();"
+                } else {
+                    ""
+                },
+                common::bincode_runtime_arguments(&options),
+                match options.limit {
+                    Some(limit) => format!(
+                        r#"
+  if (bytes.length > {}) {{
+    throw new Error("Exceeded the size limit of the encoding");
+  }}"#,
+                        limit
+                    ),
+                    None => String::new(),
+                },
+                name,
+                deserializer_arguments,
+                match options.trailing_bytes {
+                    TrailingBytes::Reject => {
+                        r#"
+  if (deserializer.offset < input.length) {
+    throw new Error("Some input bytes were not read");
+  }"#
+                    }
+                    TrailingBytes::Allow => "",
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    fn output_json_text_methods(&mut self, name: &str) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"public jsonSerialize(): string {{{}
+  return stringifyJson(this.toJson());
+}}
+
+static jsonDeserialize(input: string): {} {{
+  return {}.fromJson(parseJson(input));
+}}
+"#,
+            if self.generator.config.validate_on_serialize {
+                "\n  this.validate();"
+            } else {
+                ""
+            },
+            name,
+            name
+        )?;
+        Ok(())
     }
 
     fn output_variant(
@@ -489,7 +1453,8 @@ return list;
             Struct(fields) => fields.clone(),
             Variable(_) => panic!("incorrect value"),
         };
-        self.output_struct_or_variant_container(Some(base), Some(index), name, &fields)
+        let shape = common::JsonShape::of_variant(variant);
+        self.output_struct_or_variant_container(Some(base), Some(index), name, shape, &fields)
     }
 
     fn output_variants(
@@ -508,6 +1473,7 @@ return list;
         variant_base: Option<&str>,
         variant_index: Option<u32>,
         name: &str,
+        shape: common::JsonShape,
         fields: &[Named<Format>],
     ) -> Result<()> {
         let mut variant_base_name = String::new();
@@ -602,8 +1568,33 @@ return list;
             )?;
             self.out.unindent();
             writeln!(self.out, "}}\n")?;
+            if variant_index.is_none() {
+                self.output_encoding_methods(name)?;
+            }
+        }
+        // JSON conversions
+        if self.has_json() {
+            self.output_json_conversions(variant_base, name, shape, fields)?;
+        }
+        // Validation
+        if self.generator.config.generates_validation() {
+            writeln!(self.out, "public validate(): void {{")?;
+            self.out.indent();
+            for field in fields {
+                if common::needs_validation(self.generator.config, &field.value, true) {
+                    let value = format!("this.{}", field.name);
+                    writeln!(self.out, "{}", self.quote_validate(&value, &field.value))?;
+                }
+            }
+            self.out.unindent();
+            writeln!(self.out, "}}\n")?;
         }
-        writeln!(self.out, "}}")
+        // Comparisons
+        if self.generator.config.comparisons {
+            self.output_comparisons(variant_base, variant_index, name, fields)?;
+        }
+        writeln!(self.out, "}}")?;
+        Ok(())
     }
 
     fn output_enum_container(
@@ -613,6 +1604,16 @@ return list;
     ) -> Result<()> {
         self.output_comment(name)?;
         writeln!(self.out, "export abstract class {} {{", name)?;
+        if self.generator.config.comparisons {
+            writeln!(
+                self.out,
+                "abstract compareTo(other: {0}): number;\n\nabstract equals(other: {0}): boolean;\n\nabstract hashCode(): number;\n\nabstract variantIndex(): number;\n",
+                name
+            )?;
+        }
+        if self.generator.config.generates_validation() {
+            writeln!(self.out, "abstract validate(): void;\n")?;
+        }
         if self.generator.config.serialization {
             writeln!(
                 self.out,
@@ -647,6 +1648,42 @@ switch (index) {{"#,
             writeln!(self.out, "}}")?;
             self.out.unindent();
             writeln!(self.out, "}}")?;
+            if !self.binary_encodings().is_empty() {
+                writeln!(self.out)?;
+                self.output_encoding_methods(name)?;
+            }
+        }
+        if self.has_json() {
+            write!(
+                self.out,
+                r#"
+abstract toJson(): JsonValue;
+
+static fromJson(json: JsonValue): {} {{
+  const variant = Json.deserializeVariant(json);
+  switch (variant.name) {{"#,
+                name
+            )?;
+            self.out.indent();
+            self.out.indent();
+            writeln!(self.out)?;
+            for variant in variants.values() {
+                writeln!(
+                    self.out,
+                    "case \"{1}\": return {0}Variant{1}.loadJson(variant);",
+                    name, variant.name,
+                )?;
+            }
+            writeln!(
+                self.out,
+                "default: throw new Error(\"Unknown variant for {}: \" + variant.name);",
+                name,
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+            self.out.unindent();
+            writeln!(self.out, "}}\n")?;
+            self.output_json_text_methods(name)?;
         }
         writeln!(self.out, "}}\n")?;
         self.output_variants(name, variants)?;
@@ -670,12 +1707,40 @@ switch (index) {{"#,
                 })
                 .collect::<Vec<_>>(),
             Struct(fields) => fields.clone(),
+            OpenEnum(_) => common::open_enum_fields(),
+            TaggedEnum { .. } => return Err(common::tagged_enum_not_supported("TypeScript", name)),
+            FlattenedStruct(_) => {
+                return Err(common::flattened_struct_not_supported("TypeScript", name))
+            }
             Enum(variants) => {
                 self.output_enum_container(name, variants)?;
                 return Ok(());
             }
         };
-        self.output_struct_or_variant_container(None, None, name, &fields)
+        let shape = common::JsonShape::of_container(format);
+        self.output_struct_or_variant_container(None, None, name, shape, &fields)
+    }
+}
+
+impl crate::SourceGenerator for CodeGenerator<'_> {
+    type Error = Error;
+
+    fn config(&self) -> &CodeGeneratorConfig {
+        self.config
+    }
+
+    fn generate(
+        &self,
+        registry: &Registry,
+    ) -> std::result::Result<crate::SourceFiles, Self::Error> {
+        let mut content = Vec::new();
+        self.output(&mut content, registry)?;
+        let mut files = crate::SourceFiles::new();
+        files.insert_bytes(
+            std::path::Path::new(&self.config.module_name).join("mod.ts"),
+            content,
+        );
+        Ok(files)
     }
 }
 
@@ -689,11 +1754,7 @@ impl Installer {
         Installer { install_dir }
     }
 
-    fn install_runtime(
-        &self,
-        source_dir: include_dir::Dir,
-        path: &str,
-    ) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    fn install_runtime(&self, source_dir: include_dir::Dir, path: &str) -> Result<()> {
         let dir_path = self.install_dir.join(path);
         std::fs::create_dir_all(&dir_path)?;
         for entry in source_dir.files() {
@@ -705,20 +1766,15 @@ impl Installer {
 }
 
 impl crate::SourceInstaller for Installer {
-    type Error = Box<dyn std::error::Error>;
+    type Error = Error;
 
     fn install_module(
         &self,
         config: &CodeGeneratorConfig,
         registry: &Registry,
     ) -> std::result::Result<(), Self::Error> {
-        let dir_path = self.install_dir.join(&config.module_name);
-        std::fs::create_dir_all(&dir_path)?;
-        let source_path = dir_path.join("mod.ts");
-        let mut file = std::fs::File::create(source_path)?;
-
         let generator = CodeGenerator::new(config);
-        generator.output(&mut file, registry)?;
+        generator.generate(registry)?.write_to(&self.install_dir)?;
         Ok(())
     }
 
@@ -733,4 +1789,23 @@ impl crate::SourceInstaller for Installer {
     fn install_bcs_runtime(&self) -> std::result::Result<(), Self::Error> {
         self.install_runtime(include_directory!("runtime/typescript/bcs"), "bcs")
     }
+
+    fn install_postcard_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(
+            include_directory!("runtime/typescript/postcard"),
+            "postcard",
+        )
+    }
+
+    fn install_borsh_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/typescript/borsh"), "borsh")
+    }
+
+    fn install_scale_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/typescript/scale"), "scale")
+    }
+
+    fn install_json_runtime(&self) -> std::result::Result<(), Self::Error> {
+        self.install_runtime(include_directory!("runtime/typescript/json"), "json")
+    }
 }
@@ -58,6 +58,33 @@ fn test_topological_sort() {
     );
 }
 
+#[test]
+fn test_find_node_in_cycle() {
+    use analyzer::find_node_in_cycle;
+    assert_eq!(
+        find_node_in_cycle(&btreemap! {
+            1 => btreeset![2, 3],
+            2 => btreeset![3],
+            3 => btreeset![],
+        }),
+        None
+    );
+    assert_eq!(
+        find_node_in_cycle(&btreemap! {
+            1 => btreeset![2],
+            2 => btreeset![3],
+            3 => btreeset![2],
+        }),
+        Some(2)
+    );
+    assert_eq!(
+        find_node_in_cycle(&btreemap! {
+            1 => btreeset![1],
+        }),
+        Some(1)
+    );
+}
+
 #[test]
 fn test_on_larger_registry() {
     let registry = test_utils::get_registry().unwrap();
@@ -95,4 +122,5 @@ fn test_on_larger_registry() {
             "SerdeData"
         ]
     );
+    assert_eq!(analyzer::find_node_in_cycle(&map), Some("List"));
 }
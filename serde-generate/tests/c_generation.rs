@@ -0,0 +1,339 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils;
+use serde::Deserialize;
+use serde_generate::{
+    c, BincodeOptions, Case, CodeGeneratorConfig, Encoding, NamingConventions, SourceGenerator,
+    SourceInstaller, TrailingBytes,
+};
+use serde_reflection::{Registry, Tracer, TracerConfig};
+use std::{collections::BTreeMap, fs::File, io::Write, process::Command};
+use tempfile::{tempdir, TempDir};
+
+fn test_that_c_code_compiles_with_config(
+    config: &CodeGeneratorConfig,
+) -> (TempDir, std::path::PathBuf) {
+    let registry = test_utils::get_registry().unwrap();
+    test_that_c_code_compiles_with_registry(config, &registry)
+}
+
+fn test_that_c_code_compiles_with_registry(
+    config: &CodeGeneratorConfig,
+    registry: &Registry,
+) -> (TempDir, std::path::PathBuf) {
+    let dir = tempdir().unwrap();
+
+    let header_path = dir.path().join("testing.h");
+    let mut header = File::create(&header_path).unwrap();
+    let generator = c::CodeGenerator::new(config);
+    generator.output(&mut header, registry).unwrap();
+
+    let source_path = dir.path().join("test.c");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(source, "#include \"testing.h\"").unwrap();
+
+    let status = Command::new("cc")
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Wextra")
+        .arg("-pedantic")
+        .arg("-Werror")
+        .arg("-c")
+        .arg("-o")
+        .arg(dir.path().join("test.o"))
+        .arg("-I")
+        .arg("runtime/c")
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    (dir, header_path)
+}
+
+#[test]
+fn test_that_c_code_compiles() {
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    test_that_c_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_c_code_compiles_without_serialization() {
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_serialization(false);
+    let (_dir, header_path) = test_that_c_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(header_path).unwrap();
+    assert!(content.contains("testing_SerdeData_free("));
+    assert!(!content.contains("testing_SerdeData_serialize("));
+}
+
+#[test]
+fn test_that_c_code_compiles_with_bcs() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bcs]);
+    test_that_c_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_c_code_compiles_with_bincode() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bincode]);
+    test_that_c_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_c_code_compiles_with_bincode_options() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bincode])
+        .with_bincode_options(
+            BincodeOptions::standard()
+                .with_limit(Some(100))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let (_dir, header_path) = test_that_c_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(header_path).unwrap();
+    assert!(content.contains(
+        "static const serde_format_t testing_bincode_format = BINCODE_FORMAT(true, false, 100);"
+    ));
+    assert!(content.contains(
+        "serde_deserializer_init(&deserializer, &testing_bincode_format, input, length, allocator);"
+    ));
+    assert!(!content.contains("serde_deserializer_check_end"));
+}
+
+#[test]
+fn test_that_c_code_compiles_with_c_style_enums() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_c_style_enums(true);
+    let (_dir, header_path) = test_that_c_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(header_path).unwrap();
+    assert!(content.contains("typedef enum testing_CStyleEnum {"));
+    assert!(content.contains("} testing_CStyleEnum;"));
+}
+
+#[test]
+fn test_that_c_code_compiles_with_comparisons() {
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comparisons(true);
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let (_dir, header_path) = test_that_c_code_compiles_with_registry(&config, &registry);
+
+    let content = std::fs::read_to_string(header_path).unwrap();
+    assert!(content.contains(
+        "static inline int testing_OrderedData_compare(const testing_OrderedData *lhs, const testing_OrderedData *rhs) {"
+    ));
+    assert!(content.contains("SERDE_TRY_COMPARE(SERDE_COMPARE(lhs->tag, rhs->tag));"));
+    assert!(content.contains("i = testing_map_str_to_u16_next_key(lhs, i);"));
+    assert!(
+        content.contains("static inline uint64_t testing_Label_hash(const testing_Label *value) {")
+    );
+}
+
+#[test]
+fn test_that_c_code_compiles_with_comparisons_of_recursive_types() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    enum Tree {
+        Leaf,
+        Node(Box<Tree>, Box<Tree>),
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Record {
+        color: Color,
+        unit: (),
+        big: i128,
+        unsigned_big: u128,
+        letter: char,
+        next: Option<Box<Record>>,
+        keys: BTreeMap<u8, ()>,
+        colors: [Option<Color>; 2],
+        tree: Tree,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Color>().unwrap();
+    tracer.trace_simple_type::<Tree>().unwrap();
+    tracer.trace_simple_type::<Record>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_c_style_enums(true)
+        .with_comparisons(true);
+    let (_dir, header_path) = test_that_c_code_compiles_with_registry(&config, &registry);
+
+    let content = std::fs::read_to_string(header_path).unwrap();
+    assert!(content.contains("return SERDE_COMPARE(*lhs, *rhs);"));
+    assert!(content.contains("SERDE_TRY_COMPARE(serde_int128_compare(lhs->big, rhs->big));"));
+}
+
+#[test]
+fn test_that_c_code_compiles_with_comments() {
+    let comments = vec![(
+        vec!["testing".to_string(), "SerdeData".to_string()],
+        "Some\ncomments".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comments(comments);
+
+    let (_dir, header_path) = test_that_c_code_compiles_with_config(&config);
+
+    // Comment was correctly generated.
+    let content = std::fs::read_to_string(header_path).unwrap();
+    assert!(content.contains(
+        r#"
+/// Some
+/// comments
+struct testing_SerdeData {
+"#
+    ));
+}
+
+#[test]
+fn test_that_c_code_compiles_with_custom_code() {
+    let custom_code = vec![(
+        vec!["testing".to_string(), "SerdeData".to_string()],
+        "#define TESTING_SERDE_DATA_CUSTOM 1".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_custom_code(custom_code);
+
+    let (_dir, header_path) = test_that_c_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(header_path).unwrap();
+    assert!(content.contains("TESTING_SERDE_DATA_CUSTOM"));
+}
+
+#[test]
+fn test_that_c_code_compiles_with_naming_conventions() {
+    let naming = NamingConventions::default()
+        .with_fields(Case::LowerCamel)
+        .with_variants(Case::ScreamingSnake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+
+    let (_dir, header_path) = test_that_c_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(header_path).unwrap();
+    assert!(content.contains("testing_SerdeData_PRIMITIVE_TYPES = 0,"));
+    assert!(content.contains("serde_str_t fString;"));
+}
+
+#[test]
+fn test_c_code_with_external_definitions() {
+    let registry = test_utils::get_registry().unwrap();
+
+    // (wrongly) Declare Tree as external.
+    let mut definitions = BTreeMap::new();
+    definitions.insert("pkg::foo".to_string(), vec!["Tree".to_string()]);
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_external_definitions(definitions);
+    let generator = c::CodeGenerator::new(&config);
+    let files = generator.generate(&registry).unwrap();
+
+    let content = files.get("testing.h").unwrap();
+    assert!(content.contains("#include \"pkg::foo.h\""));
+    assert!(content.contains("pkg_foo_Tree"));
+    assert!(!content.contains("testing_Tree value;"));
+}
+
+#[test]
+fn test_c_code_boxes_recursive_types() {
+    let registry = test_utils::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs, Encoding::Bincode]);
+    let files = c::CodeGenerator::new(&config).generate(&registry).unwrap();
+    assert_eq!(files.len(), 1);
+
+    let content = files.get("testing.h").unwrap();
+    assert!(content.starts_with("#pragma once\n"));
+    assert!(content.contains("testing_SimpleList *value;"));
+    assert!(content.contains("uint32_t data[3];"));
+    assert!(content.contains("static inline serde_error_t testing_SerdeData_bcs_deserialize("));
+    assert!(content.contains("static inline serde_error_t testing_SerdeData_bincode_serialize("));
+}
+
+#[test]
+fn test_c_code_rejects_json() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Json]);
+    let error = c::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "C does not support the json encoding");
+}
+
+#[test]
+fn test_c_code_rejects_validation() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let configs = [
+        CodeGeneratorConfig::new("testing".to_string()).with_validation(true),
+        CodeGeneratorConfig::new("testing".to_string()).with_validate_on_serialize(true),
+    ];
+    for config in &configs {
+        let error = c::CodeGenerator::new(config)
+            .generate(&registry)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "C does not support validation");
+    }
+}
+
+#[test]
+fn test_c_code_rejects_reserved_words() {
+    #[derive(Deserialize)]
+    #[allow(dead_code, non_snake_case)]
+    struct Foo {
+        Int: u8,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let naming = NamingConventions::default().with_fields(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = c::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "C name `int` of Foo.Int is a reserved word"
+    );
+}
+
+#[test]
+fn test_c_code_rejects_kebab_case_identifiers() {
+    let registry = test_utils::get_registry().unwrap();
+    let naming = NamingConventions::default().with_types(Case::Kebab);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = c::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert!(error.to_string().contains("is not an identifier"));
+}
+
+#[test]
+fn test_c_installer_rejects_unsupported_runtimes() {
+    let dir = tempdir().unwrap();
+    let installer = c::Installer::new(dir.path().to_path_buf());
+    installer.install_serde_runtime().unwrap();
+    installer.install_bcs_runtime().unwrap();
+    assert!(dir.path().join("serde.h").exists());
+    assert!(dir.path().join("bcs.h").exists());
+
+    let error = installer.install_json_runtime().unwrap_err();
+    assert_eq!(error.to_string(), "C does not support the json encoding");
+}
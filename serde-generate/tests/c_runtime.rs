@@ -0,0 +1,402 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils;
+use crate::test_utils::{Choice, Runtime, Test};
+use serde_generate::{c, BincodeOptions, CodeGeneratorConfig, Encoding, TrailingBytes};
+use std::{fs::File, io::Write, path::Path, process::Command};
+use tempfile::tempdir;
+
+/// Compile `test.c` together with the generated header `test.h`, then run it.
+fn compile_and_run_c_test(dir: &Path) {
+    let status = Command::new("cc")
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Wextra")
+        .arg("-pedantic")
+        .arg("-Werror")
+        .arg("-o")
+        .arg(dir.join("test"))
+        .arg("-I")
+        .arg("runtime/c")
+        .arg(dir.join("test.c"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new(dir.join("test")).status().unwrap();
+    assert!(status.success());
+}
+
+/// Helpers for `test.c`, including an allocator that counts live allocations.
+const TEST_PRELUDE: &str = r#"
+#include <stdio.h>
+#include <stdlib.h>
+#include "test.h"
+
+#define CHECK(cond)                                                             \
+    do {                                                                        \
+        if (!(cond)) {                                                          \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond); \
+            return 1;                                                           \
+        }                                                                       \
+    } while (0)
+
+static void *counting_alloc(void *context, size_t size) {
+    ++*(size_t *)context;
+    return malloc(size);
+}
+
+static void counting_free(void *context, void *ptr) {
+    --*(size_t *)context;
+    free(ptr);
+}
+"#;
+
+fn quote_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|x| format!("0x{:02x}", x))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[test]
+fn test_c_bcs_runtime_on_simple_data() {
+    test_c_runtime_on_simple_data(Runtime::Bcs);
+}
+
+#[test]
+fn test_c_bincode_runtime_on_simple_data() {
+    test_c_runtime_on_simple_data(Runtime::Bincode);
+}
+
+#[test]
+fn test_c_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_c_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+fn test_c_runtime_on_simple_data(runtime: Runtime) {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let mut header = File::create(dir.path().join("test.h")).unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let generator = c::CodeGenerator::new(&config);
+    generator.output(&mut header, &registry).unwrap();
+
+    let reference = runtime.serialize(&Test {
+        a: vec![4, 6],
+        b: (-3, 5),
+        c: Choice::C { x: 7 },
+    });
+
+    let mut source = File::create(dir.path().join("test.c")).unwrap();
+    writeln!(
+        source,
+        r#"{0}
+int main(void) {{
+    static const uint8_t input[] = {{{1}}};
+    uint8_t input2[sizeof input + 1];
+    uint8_t output[sizeof input];
+    size_t length;
+    size_t allocations = 0;
+    serde_allocator_t allocator = {{counting_alloc, counting_free, &allocations}};
+    uint32_t a[] = {{4, 6}};
+    testing_Test value;
+    testing_Test value2;
+
+    CHECK(testing_Test_{2}_deserialize(&value, input, sizeof input, &allocator) == SERDE_OK);
+
+    memset(&value2, 0, sizeof value2);
+    value2.a.data = a;
+    value2.a.len = 2;
+    value2.b.field0 = -3;
+    value2.b.field1 = 5;
+    value2.c.tag = testing_Choice_C;
+    value2.c.value.C.x = 7;
+    CHECK(testing_Test_equal(&value, &value2));
+
+    CHECK(testing_Test_{2}_serialize(&value2, output, sizeof output, &length) == SERDE_OK);
+    CHECK(length == sizeof input && memcmp(input, output, length) == 0);
+    CHECK(testing_Test_{2}_serialize(&value2, output, sizeof output - 1, &length) ==
+          SERDE_ERROR_BUFFER_TOO_SMALL);
+
+    testing_Test_free(&value, &allocator);
+    CHECK(allocations == 0);
+
+    memcpy(input2, input, sizeof input);
+    input2[sizeof input] = 1;
+    CHECK(testing_Test_{2}_deserialize(&value, input2, sizeof input2, &allocator) ==
+          SERDE_ERROR_TRAILING_BYTES);
+    CHECK(allocations == 0);
+    CHECK(testing_Test_{2}_deserialize(&value, input, sizeof input - 1, &allocator) ==
+          SERDE_ERROR_UNEXPECTED_END);
+    CHECK(allocations == 0);
+    return 0;
+}}
+"#,
+        TEST_PRELUDE,
+        quote_bytes(&reference),
+        runtime.name(),
+    )
+    .unwrap();
+
+    compile_and_run_c_test(dir.path());
+}
+
+#[test]
+fn test_c_bcs_runtime_on_supported_types() {
+    test_c_runtime_on_supported_types(Runtime::Bcs);
+}
+
+#[test]
+fn test_c_bincode_runtime_on_supported_types() {
+    test_c_runtime_on_supported_types(Runtime::Bincode);
+}
+
+#[test]
+fn test_c_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_c_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+/// C99 does not allow empty initializers, hence the final placeholder.
+fn quote_inputs(name: &str, samples: &[Vec<u8>]) -> String {
+    let mut result = String::new();
+    for (i, bytes) in samples.iter().enumerate() {
+        if !bytes.is_empty() {
+            result += &format!(
+                "static const uint8_t {}_{}[] = {{{}}};\n",
+                name,
+                i,
+                quote_bytes(bytes)
+            );
+        }
+    }
+    result += &format!("static const input_t {}[] = {{\n", name);
+    for (i, bytes) in samples.iter().enumerate() {
+        if bytes.is_empty() {
+            result += "    {NULL, 0},\n";
+        } else {
+            result += &format!("    {{{0}_{1}, sizeof {0}_{1}}},\n", name, i);
+        }
+    }
+    result += &format!(
+        "    {{NULL, 0}},\n}};\nstatic const size_t {}_count = {};\n",
+        name,
+        samples.len()
+    );
+    result
+}
+
+fn test_c_runtime_on_supported_types(runtime: Runtime) {
+    let registry = runtime.get_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let mut header = File::create(dir.path().join("test.h")).unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let generator = c::CodeGenerator::new(&config);
+    generator.output(&mut header, &registry).unwrap();
+
+    let mut source = File::create(dir.path().join("test.c")).unwrap();
+    writeln!(
+        source,
+        r#"{0}
+typedef struct {{
+    const uint8_t *data;
+    size_t len;
+}} input_t;
+
+{1}
+{2}
+static uint8_t output[1 << 16];
+static uint8_t input2[1 << 16];
+
+int main(void) {{
+    size_t allocations = 0;
+    serde_allocator_t allocator = {{counting_alloc, counting_free, &allocations}};
+
+    for (size_t k = 0; k < positive_inputs_count; k++) {{
+        const input_t input = positive_inputs[k];
+        testing_SerdeData value;
+        testing_SerdeData value2;
+        size_t length;
+        serde_error_t error = testing_SerdeData_{3}_deserialize(&value, input.data, input.len, &allocator);
+        if (error != SERDE_OK) {{
+            fprintf(stderr, "Failed to deserialize positive input %zu: %s\n", k, serde_error_message(error));
+            return 1;
+        }}
+        CHECK(input.len <= sizeof output);
+        CHECK(testing_SerdeData_{3}_serialize(&value, output, sizeof output, &length) == SERDE_OK);
+        CHECK(length == input.len && memcmp(input.data, output, length) == 0);
+
+        // Test self-equality for the Serde value.
+        CHECK(testing_SerdeData_{3}_deserialize(&value2, input.data, input.len, &allocator) == SERDE_OK);
+        CHECK(testing_SerdeData_equal(&value, &value2));
+        testing_SerdeData_free(&value2, &allocator);
+
+        // Test simple mutations of the input.
+        for (size_t i = 0; i < input.len && i < 20; i++) {{
+            memcpy(input2, input.data, input.len);
+            input2[i] ^= 0x81;
+            if (testing_SerdeData_{3}_deserialize(&value2, input2, input.len, &allocator) == SERDE_OK) {{
+                CHECK(!testing_SerdeData_equal(&value, &value2));
+                testing_SerdeData_free(&value2, &allocator);
+            }}
+        }}
+
+        testing_SerdeData_free(&value, &allocator);
+        CHECK(allocations == 0);
+    }}
+
+    for (size_t k = 0; k < negative_inputs_count; k++) {{
+        const input_t input = negative_inputs[k];
+        testing_SerdeData value;
+        if (testing_SerdeData_{3}_deserialize(&value, input.data, input.len, &allocator) == SERDE_OK) {{
+            fprintf(stderr, "Input should fail to deserialize:");
+            for (size_t i = 0; i < input.len; i++) {{
+                fprintf(stderr, " %d", input.data[i]);
+            }}
+            fprintf(stderr, "\n");
+            return 1;
+        }}
+        CHECK(allocations == 0);
+    }}
+    return 0;
+}}
+"#,
+        TEST_PRELUDE,
+        quote_inputs("positive_inputs", &runtime.get_positive_samples()),
+        quote_inputs("negative_inputs", &runtime.get_negative_samples()),
+        runtime.name(),
+    )
+    .unwrap();
+
+    compile_and_run_c_test(dir.path());
+}
+
+#[test]
+fn test_c_runtime_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let mut header = File::create(dir.path().join("test.h")).unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_comparisons(true);
+    let generator = c::CodeGenerator::new(&config);
+    generator.output(&mut header, &registry).unwrap();
+
+    // Values are sorted in the order of Rust.
+    let inputs = test_utils::get_ordered_values()
+        .iter()
+        .map(|value| Runtime::Bcs.serialize(value))
+        .collect::<Vec<_>>();
+
+    let mut source = File::create(dir.path().join("test.c")).unwrap();
+    writeln!(
+        source,
+        r#"{0}
+typedef struct {{
+    const uint8_t *data;
+    size_t len;
+}} input_t;
+
+{1}
+int main(void) {{
+    size_t allocations = 0;
+    serde_allocator_t allocator = {{counting_alloc, counting_free, &allocations}};
+    testing_OrderedData values[sizeof inputs / sizeof inputs[0]];
+
+    for (size_t i = 0; i < inputs_count; i++) {{
+        CHECK(testing_OrderedData_bcs_deserialize(&values[i], inputs[i].data, inputs[i].len, &allocator) == SERDE_OK);
+    }}
+    for (size_t i = 0; i < inputs_count; i++) {{
+        testing_OrderedData value;
+        CHECK(testing_OrderedData_bcs_deserialize(&value, inputs[i].data, inputs[i].len, &allocator) == SERDE_OK);
+        CHECK(testing_OrderedData_hash(&value) == testing_OrderedData_hash(&values[i]));
+        testing_OrderedData_free(&value, &allocator);
+        for (size_t j = 0; j < inputs_count; j++) {{
+            CHECK(testing_OrderedData_compare(&values[i], &values[j]) == SERDE_COMPARE(i, j));
+            CHECK(testing_OrderedData_equal(&values[i], &values[j]) == (i == j));
+        }}
+    }}
+    for (size_t i = 0; i < inputs_count; i++) {{
+        testing_OrderedData_free(&values[i], &allocator);
+    }}
+    CHECK(allocations == 0);
+    return 0;
+}}
+"#,
+        TEST_PRELUDE,
+        quote_inputs("inputs", &inputs),
+    )
+    .unwrap();
+
+    compile_and_run_c_test(dir.path());
+}
+
+#[test]
+fn test_c_runtime_with_bincode_limit_and_trailing_bytes() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let mut header = File::create(dir.path().join("test.h")).unwrap();
+
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (-3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (-3, 5),
+        c: Choice::C { x: 7 },
+    });
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bincode])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let generator = c::CodeGenerator::new(&config);
+    generator.output(&mut header, &registry).unwrap();
+
+    let mut source = File::create(dir.path().join("test.c")).unwrap();
+    writeln!(
+        source,
+        r#"{0}
+int main(void) {{
+    static const uint8_t input[] = {{{1}, 0x00}};
+    static const uint8_t too_large[] = {{{2}}};
+    uint8_t output[sizeof too_large];
+    size_t length;
+    size_t allocations = 0;
+    serde_allocator_t allocator = {{counting_alloc, counting_free, &allocations}};
+    testing_Test value;
+
+    CHECK(testing_Test_bincode_deserialize(&value, input, sizeof input, &allocator) == SERDE_OK);
+    CHECK(testing_Test_bincode_serialize(&value, output, sizeof output, &length) == SERDE_OK);
+    CHECK(length == sizeof input - 1 && memcmp(input, output, length) == 0);
+    testing_Test_free(&value, &allocator);
+    CHECK(allocations == 0);
+
+    CHECK(testing_Test_bincode_deserialize(&value, too_large, sizeof too_large, &allocator) ==
+          SERDE_ERROR_SIZE_LIMIT);
+    CHECK(allocations == 0);
+    return 0;
+}}
+"#,
+        TEST_PRELUDE,
+        quote_bytes(&reference),
+        quote_bytes(&too_large),
+    )
+    .unwrap();
+
+    compile_and_run_c_test(dir.path());
+}
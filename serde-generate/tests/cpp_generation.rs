@@ -1,9 +1,10 @@
 // Copyright (c) Facebook, Inc. and its affiliates
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use crate::test_utils;
-use serde_generate::{cpp, CodeGeneratorConfig, Encoding};
-use std::{collections::BTreeMap, fs::File, io::Write, process::Command};
+use crate::test_utils::{self, struct_format, type_name};
+use serde_generate::{cpp, CodeGeneratorConfig, Encoding, ModuleMap};
+use serde_reflection::Registry;
+use std::{collections::BTreeMap, fs::File, io::Write, path::Path, process::Command};
 use tempfile::{tempdir, TempDir};
 
 fn test_that_cpp_code_compiles_with_config(
@@ -123,9 +124,39 @@ fn test_cpp_code_with_external_definitions() {
 
     let content = std::fs::read_to_string(&header_path).unwrap();
     assert!(content.contains("pkg::foo::Tree"));
+    // Headers of external definitions are not generated, hence not included.
+    assert!(!content.contains("#include \"pkg"));
     assert!(!content.contains("testing::Tree"));
 }
 
+#[test]
+fn test_cpp_code_with_modules() {
+    let mut registry = Registry::new();
+    registry.insert(
+        "x::a::Foo".to_string(),
+        struct_format(&[("value", serde_reflection::Format::U64)]),
+    );
+    registry.insert(
+        "x::b::Bar".to_string(),
+        struct_format(&[("foo", type_name("x::a::Foo"))]),
+    );
+    let config = CodeGeneratorConfig::new("main".to_string());
+    let modules = ModuleMap::from_qualified_names(&registry, "::");
+    let files = modules
+        .generate(&cpp::CAPABILITIES, &config, &registry, |config| {
+            Box::new(cpp::CodeGenerator::new(config))
+        })
+        .unwrap();
+    let paths = files.iter().map(|(path, _)| path).collect::<Vec<_>>();
+    assert_eq!(paths, vec![Path::new("x_a.hpp"), Path::new("x_b.hpp")]);
+    assert!(files
+        .get("x_b.hpp")
+        .unwrap()
+        .contains("#include \"x_a.hpp\""));
+    assert!(files.get("x_b.hpp").unwrap().contains("x::a::Foo> foo;"));
+    assert!(!files.get("x_a.hpp").unwrap().contains("#include \"x_"));
+}
+
 #[test]
 fn test_that_cpp_code_compiles_with_custom_code() {
     let custom_code = vec![
@@ -230,3 +261,49 @@ int main() {{
         .unwrap();
     assert!(status.success());
 }
+
+#[test]
+fn test_that_cpp_code_compiles_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let header_path = dir.path().join("test.hpp");
+    let mut header = File::create(&header_path).unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comparisons(true);
+    let generator = cpp::CodeGenerator::new(&config);
+    generator.output(&mut header, &registry).unwrap();
+
+    let source_path = dir.path().join("test.cpp");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+#include <set>
+#include <unordered_set>
+#include "test.hpp"
+
+using namespace testing;
+
+std::set<OrderedData> ordered_set(OrderedData value) {{
+    return std::set<OrderedData>{{value}};
+}}
+
+std::unordered_set<OrderedData> unordered_set(OrderedData value) {{
+    return std::unordered_set<OrderedData>{{value}};
+}}
+"#
+    )
+    .unwrap();
+
+    let status = Command::new("clang++")
+        .arg("--std=c++17")
+        .arg("-c")
+        .arg("-o")
+        .arg(dir.path().join("test.o"))
+        .arg("-I")
+        .arg("runtime/cpp")
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use crate::test_utils::{Choice, Runtime, Test};
-use serde_generate::{cpp, CodeGeneratorConfig};
+use crate::test_utils::{json_data, Choice, Runtime, Test};
+use serde_generate::{cpp, CodeGeneratorConfig, Encoding};
 use std::{fs::File, io::Write, process::Command};
 use tempfile::tempdir;
 
@@ -17,14 +17,35 @@ fn test_cpp_bincode_runtime_on_simple_date() {
     test_cpp_runtime_on_simple_date(Runtime::Bincode);
 }
 
+#[test]
+fn test_cpp_bincode_varint_big_endian_runtime_on_simple_date() {
+    test_cpp_runtime_on_simple_date(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_cpp_postcard_runtime_on_simple_date() {
+    test_cpp_runtime_on_simple_date(Runtime::Postcard);
+}
+
+#[test]
+fn test_cpp_borsh_runtime_on_simple_date() {
+    test_cpp_runtime_on_simple_date(Runtime::Borsh);
+}
+
+#[test]
+fn test_cpp_scale_runtime_on_simple_date() {
+    test_cpp_runtime_on_simple_date(Runtime::Scale);
+}
+
 fn test_cpp_runtime_on_simple_date(runtime: Runtime) {
     let registry = test_utils::get_simple_registry().unwrap();
     let dir = tempdir().unwrap();
     let header_path = dir.path().join("test.hpp");
     let mut header = File::create(header_path).unwrap();
 
-    let config =
-        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
     let generator = cpp::CodeGenerator::new(&config);
     generator.output(&mut header, &registry).unwrap();
 
@@ -102,6 +123,26 @@ fn test_cpp_bincode_runtime_on_supported_types() {
     test_cpp_runtime_on_supported_types(Runtime::Bincode);
 }
 
+#[test]
+fn test_cpp_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_cpp_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_cpp_postcard_runtime_on_supported_types() {
+    test_cpp_runtime_on_supported_types(Runtime::Postcard);
+}
+
+#[test]
+fn test_cpp_borsh_runtime_on_supported_types() {
+    test_cpp_runtime_on_supported_types(Runtime::Borsh);
+}
+
+#[test]
+fn test_cpp_scale_runtime_on_supported_types() {
+    test_cpp_runtime_on_supported_types(Runtime::Scale);
+}
+
 fn quote_bytes(bytes: &[u8]) -> String {
     format!(
         "std::vector<uint8_t>{{{}}}",
@@ -114,13 +155,14 @@ fn quote_bytes(bytes: &[u8]) -> String {
 }
 
 fn test_cpp_runtime_on_supported_types(runtime: Runtime) {
-    let registry = test_utils::get_registry().unwrap();
+    let registry = runtime.get_registry().unwrap();
     let dir = tempdir().unwrap();
     let header_path = dir.path().join("test.hpp");
     let mut header = File::create(header_path).unwrap();
 
-    let config =
-        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
     let generator = cpp::CodeGenerator::new(&config);
     generator.output(&mut header, &registry).unwrap();
 
@@ -223,3 +265,126 @@ int main() {{
     let status = Command::new(dir.path().join("test")).status().unwrap();
     assert!(status.success());
 }
+
+fn quote_string(value: &str) -> String {
+    let mut result = String::from("std::string(\"");
+    for b in value.bytes() {
+        match b {
+            b' '..=b'~' if b != b'"' && b != b'\\' && b != b'?' => result.push(b as char),
+            _ => result.push_str(&format!("\\{:03o}", b)),
+        }
+    }
+    result.push_str("\")");
+    result
+}
+
+#[test]
+fn test_cpp_json_runtime_on_supported_types() {
+    let registry = json_data::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let header_path = dir.path().join("test.hpp");
+    let mut header = File::create(header_path).unwrap();
+
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Json]);
+    let generator = cpp::CodeGenerator::new(&config);
+    generator.output(&mut header, &registry).unwrap();
+
+    let positive_inputs: Vec<_> = json_data::get_positive_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect();
+    let alternate_inputs: Vec<_> = json_data::get_alternate_samples()
+        .iter()
+        .map(|(input, output)| format!("{{{}, {}}}", quote_string(input), quote_string(output)))
+        .collect();
+    let negative_inputs: Vec<_> = json_data::get_negative_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect();
+
+    let source_path = dir.path().join("test.cpp");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+#include <exception>
+#include <iostream>
+#include <cassert>
+#include "test.hpp"
+
+using namespace testing;
+
+// Ordering of map keys, which is only generated together with comparisons.
+namespace testing {{
+bool operator<(const Key &lhs, const Key &rhs) {{
+    return lhs.value < rhs.value;
+}}
+
+bool operator<(const Color &lhs, const Color &rhs) {{
+    return lhs.value.index() < rhs.value.index();
+}}
+}} // end of namespace testing
+
+int main() {{
+    std::vector<std::string> positive_inputs = {{{0}}};
+    std::vector<std::pair<std::string, std::string>> alternate_inputs = {{{1}}};
+    std::vector<std::string> negative_inputs = {{{2}}};
+    try {{
+        for (auto input: positive_inputs) {{
+            auto value = SerdeData::jsonDeserialize(input);
+            auto output = value.jsonSerialize();
+            if (input != output) {{
+                std::cout << "Unexpected output: " << output << '\n';
+                return 1;
+            }}
+
+            // Test self-equality for the Serde value.
+            assert(value == SerdeData::jsonDeserialize(input));
+        }}
+
+        for (auto inputs: alternate_inputs) {{
+            auto output = SerdeData::jsonDeserialize(inputs.first).jsonSerialize();
+            if (inputs.second != output) {{
+                std::cout << "Unexpected output: " << output << '\n';
+                return 1;
+            }}
+        }}
+
+        for (auto input: negative_inputs) {{
+            try {{
+                SerdeData::jsonDeserialize(input);
+                std::cout << "Input should fail to deserialize: " << input << '\n';
+                return 1;
+            }} catch (serde::deserialization_error e) {{
+                // All good
+            }}
+        }}
+        return 0;
+    }} catch (std::exception& e) {{
+        std::cout << "Error: " << e.what() << '\n';
+        return 1;
+    }}
+}}
+"#,
+        positive_inputs.join(", "),
+        alternate_inputs.join(", "),
+        negative_inputs.join(", "),
+    )
+    .unwrap();
+
+    let status = Command::new("clang++")
+        .arg("--std=c++17")
+        .arg("-g")
+        .arg("-o")
+        .arg(dir.path().join("test"))
+        .arg("-I")
+        .arg("runtime/cpp")
+        .arg(source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new(dir.path().join("test")).status().unwrap();
+    assert!(status.success());
+}
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use serde_generate::{csharp, CodeGeneratorConfig, Encoding};
+use serde_generate::{csharp, CodeGeneratorConfig, Encoding, NativeType};
+use serde_reflection::Registry;
 use std::{collections::BTreeMap, process::Command, sync::Mutex};
 use tempfile::{tempdir, TempDir};
 
@@ -13,18 +14,26 @@ lazy_static::lazy_static! {
 
 fn test_that_csharp_code_compiles_with_config(
     config: &CodeGeneratorConfig,
+) -> (TempDir, std::path::PathBuf) {
+    let registry = test_utils::get_registry().unwrap();
+    test_that_csharp_code_compiles_with_config_and_registry(config, &registry)
+}
+
+fn test_that_csharp_code_compiles_with_config_and_registry(
+    config: &CodeGeneratorConfig,
+    registry: &Registry,
 ) -> (TempDir, std::path::PathBuf) {
     use serde_generate::SourceInstaller;
 
-    let registry = test_utils::get_registry().unwrap();
     let dir = tempdir().unwrap();
     let dir_path = dir.path().to_path_buf();
 
     let installer = csharp::Installer::new(dir_path.clone());
-    installer.install_module(config, &registry).unwrap();
+    installer.install_module(config, registry).unwrap();
     installer.install_serde_runtime().unwrap();
     installer.install_bincode_runtime().unwrap();
     installer.install_bcs_runtime().unwrap();
+    installer.install_json_runtime().unwrap();
 
     let proj_path = dir_path.join(config.module_name().replace('.', "/"));
     {
@@ -58,6 +67,15 @@ fn test_that_csharp_code_compiles_with_c_style_enums() {
     test_that_csharp_code_compiles_with_config(&config);
 }
 
+#[test]
+fn test_that_csharp_code_compiles_with_validation() {
+    let config = CodeGeneratorConfig::new("Generated".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_c_style_enums(true)
+        .with_validate_on_serialize(true);
+    test_that_csharp_code_compiles_with_config(&config);
+}
+
 #[test]
 fn test_that_csharp_code_compiles_with_bcs() {
     let config =
@@ -79,6 +97,23 @@ fn test_that_csharp_code_compiles_with_bincode() {
     test_that_csharp_code_compiles_with_config(&config);
 }
 
+#[test]
+fn test_that_csharp_code_compiles_with_json() {
+    let registry = test_utils::json_data::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("Generated".to_string())
+        .with_encodings(vec![Encoding::Bcs, Encoding::Json])
+        .with_c_style_enums(true);
+
+    let (_dir, path) = test_that_csharp_code_compiles_with_config_and_registry(&config, &registry);
+    let content = std::fs::read_to_string(path.join("SerdeData.cs")).unwrap();
+    assert!(content.contains("public static SerdeData JsonDeserialize(string input)"));
+    assert!(content.contains("internal static PrimitiveTypes LoadJson(Json.JsonVariant variant)"));
+    let content = std::fs::read_to_string(path.join("Color.cs")).unwrap();
+    assert!(content.contains("public static object ToJson(this Color value)"));
+    let content = std::fs::read_to_string(path.join("TraitHelpers.cs")).unwrap();
+    assert!(content.contains("json.SortKeys(Json.JsonObject.NumberOrder);"));
+}
+
 #[test]
 fn test_that_csharp_code_compiles_with_comments() {
     let comments = vec![(
@@ -114,3 +149,33 @@ fn test_csharp_code_with_external_definitions() {
     let content = std::fs::read_to_string(dir.path().join("Generated/SerdeData.cs")).unwrap();
     assert!(content.contains("foo.TraitHelpers."));
 }
+
+#[test]
+fn test_csharp_code_with_native_types() {
+    let registry = test_utils::get_registry_with_native_types().unwrap();
+    let dir = tempdir().unwrap();
+
+    let mut native_types = BTreeMap::new();
+    native_types.insert(
+        "Timestamp".to_string(),
+        NativeType::new(
+            "System.DateTimeOffset".to_string(),
+            "Codecs.SerializeTimestamp".to_string(),
+            "Codecs.DeserializeTimestamp".to_string(),
+        ),
+    );
+    let config = CodeGeneratorConfig::new("Generated".to_string()).with_native_types(native_types);
+    let generator = csharp::CodeGenerator::new(&config);
+
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    assert!(!dir.path().join("Generated/Timestamp.cs").exists());
+    let content = std::fs::read_to_string(dir.path().join("Generated/Event.cs")).unwrap();
+    assert!(content.contains("public System.DateTimeOffset at;"));
+    assert!(content.contains("Codecs.SerializeTimestamp(at, serializer);"));
+    assert!(content.contains("Codecs.DeserializeTimestamp(deserializer),"));
+    // Native types may be value types.
+    assert!(!content.contains("if (_at == null)"));
+}
@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use crate::test_utils::{Choice, Runtime, Test};
+use crate::test_utils::{json_data, Choice, Runtime, Test};
 use heck::CamelCase;
-use serde_generate::{csharp, CodeGeneratorConfig, SourceInstaller};
+use serde_generate::{
+    csharp, BincodeOptions, CodeGeneratorConfig, Encoding, SourceInstaller, TrailingBytes,
+};
 use std::{
     fs::File,
     io::Write,
@@ -70,7 +72,7 @@ fn run_nunit(proj_dir: &Path) {
 
 fn make_test_project(
     tmp_dir: &Path,
-    runtime: Runtime,
+    runtime_name: &str,
     test_name: &str,
     library_name: &str,
 ) -> std::io::Result<PathBuf> {
@@ -102,8 +104,7 @@ fn make_test_project(
 
 </Project>
 "#,
-        runtime.name().to_camel_case(),
-        library_name
+        runtime_name, library_name
     )?;
     Ok(test_dir)
 }
@@ -127,17 +128,54 @@ fn test_csharp_bincode_runtime_on_simple_data() {
     test_csharp_runtime_on_simple_data(dir, Runtime::Bincode);
 }
 
+#[test]
+fn test_csharp_bincode_varint_big_endian_runtime_on_simple_data() {
+    let (dir, _tmp) =
+        create_test_dir("test_csharp_bincode_varint_big_endian_runtime_on_simple_data");
+    test_csharp_runtime_on_simple_data(dir, Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_csharp_postcard_runtime_on_simple_data() {
+    let (dir, _tmp) = create_test_dir("test_csharp_postcard_runtime_on_simple_data");
+    test_csharp_runtime_on_simple_data(dir, Runtime::Postcard);
+}
+
+#[test]
+fn test_csharp_borsh_runtime_on_simple_data() {
+    let (dir, _tmp) = create_test_dir("test_csharp_borsh_runtime_on_simple_data");
+    test_csharp_runtime_on_simple_data(dir, Runtime::Borsh);
+}
+
+#[test]
+fn test_csharp_scale_runtime_on_simple_data() {
+    let (dir, _tmp) = create_test_dir("test_csharp_scale_runtime_on_simple_data");
+    test_csharp_runtime_on_simple_data(dir, Runtime::Scale);
+}
+
 fn test_csharp_runtime_on_simple_data(dir: PathBuf, runtime: Runtime) {
     let registry = test_utils::get_simple_registry().unwrap();
-    let test_dir = make_test_project(&dir, runtime, "Testing", "SimpleData").unwrap();
-    let config =
-        CodeGeneratorConfig::new("SimpleData".to_string()).with_encodings(vec![runtime.into()]);
+    let test_dir = make_test_project(
+        &dir,
+        &runtime.name().to_camel_case(),
+        "Testing",
+        "SimpleData",
+    )
+    .unwrap();
+    let config = CodeGeneratorConfig::new("SimpleData".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
 
     let installer = csharp::Installer::new(dir);
     installer.install_serde_runtime().unwrap();
     match runtime {
-        Runtime::Bincode => installer.install_bincode_runtime().unwrap(),
+        Runtime::Bincode | Runtime::BincodeVarintBigEndian => {
+            installer.install_bincode_runtime().unwrap()
+        }
         Runtime::Bcs => installer.install_bcs_runtime().unwrap(),
+        Runtime::Postcard => installer.install_postcard_runtime().unwrap(),
+        Runtime::Borsh => installer.install_borsh_runtime().unwrap(),
+        Runtime::Scale => installer.install_scale_runtime().unwrap(),
     }
     installer.install_module(&config, &registry).unwrap();
 
@@ -198,6 +236,80 @@ namespace Testing {{
     run_nunit(&test_dir);
 }
 
+#[test]
+fn test_csharp_runtime_with_bincode_limit_and_trailing_bytes() {
+    let (dir, _tmp) = create_test_dir("test_csharp_runtime_with_bincode_limit_and_trailing_bytes");
+    let registry = test_utils::get_simple_registry().unwrap();
+    let test_dir = make_test_project(
+        &dir,
+        &Runtime::Bincode.name().to_camel_case(),
+        "Testing",
+        "SimpleData",
+    )
+    .unwrap();
+
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("SimpleData".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+
+    let installer = csharp::Installer::new(dir);
+    installer.install_serde_runtime().unwrap();
+    installer.install_bincode_runtime().unwrap();
+    installer.install_module(&config, &registry).unwrap();
+
+    let to_csharp_bytes = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .map(|x| format!("{}", *x))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut source = File::create(test_dir.join("TestRuntime.cs")).unwrap();
+    writeln!(
+        source,
+        r#"
+using System;
+using NUnit.Framework;
+using Serde;
+using SimpleData;
+
+namespace Testing {{
+    [TestFixture]
+    public class TestBincodeLimit {{
+        [Test]
+        public void TestLimitAndTrailingBytes() {{
+            Test test = Test.BincodeDeserialize(new byte[] {{{0}, 0}});
+            CollectionAssert.AreEqual(test.BincodeSerialize(), new byte[] {{{0}}});
+
+            var e = Assert.Throws<DeserializationException>(() => Test.BincodeDeserialize(new byte[] {{{1}}}));
+            StringAssert.Contains("limit", e.Message);
+        }}
+    }}
+}}
+"#,
+        to_csharp_bytes(&reference),
+        to_csharp_bytes(&too_large),
+    )
+    .unwrap();
+
+    dotnet_build(&test_dir);
+    run_nunit(&test_dir);
+}
+
 #[test]
 fn test_csharp_bcs_runtime_on_supported_types() {
     let (dir, _tmp) = create_test_dir("test_csharp_bcs_runtime_on_supported_types");
@@ -210,6 +322,31 @@ fn test_csharp_bincode_runtime_on_supported_types() {
     test_csharp_runtime_on_supported_types(dir, Runtime::Bincode);
 }
 
+#[test]
+fn test_csharp_bincode_varint_big_endian_runtime_on_supported_types() {
+    let (dir, _tmp) =
+        create_test_dir("test_csharp_bincode_varint_big_endian_runtime_on_supported_types");
+    test_csharp_runtime_on_supported_types(dir, Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_csharp_postcard_runtime_on_supported_types() {
+    let (dir, _tmp) = create_test_dir("test_csharp_postcard_runtime_on_supported_types");
+    test_csharp_runtime_on_supported_types(dir, Runtime::Postcard);
+}
+
+#[test]
+fn test_csharp_borsh_runtime_on_supported_types() {
+    let (dir, _tmp) = create_test_dir("test_csharp_borsh_runtime_on_supported_types");
+    test_csharp_runtime_on_supported_types(dir, Runtime::Borsh);
+}
+
+#[test]
+fn test_csharp_scale_runtime_on_supported_types() {
+    let (dir, _tmp) = create_test_dir("test_csharp_scale_runtime_on_supported_types");
+    test_csharp_runtime_on_supported_types(dir, Runtime::Scale);
+}
+
 fn quote_bytes(bytes: &[u8]) -> String {
     format!(
         "yield return new TestCaseData(new byte[] {{ {} }});",
@@ -222,15 +359,23 @@ fn quote_bytes(bytes: &[u8]) -> String {
 }
 
 fn test_csharp_runtime_on_supported_types(dir: PathBuf, runtime: Runtime) {
-    let registry = test_utils::get_registry().unwrap();
-    let test_dir = make_test_project(&dir, runtime, "Testing", "Data").unwrap();
-    let config = CodeGeneratorConfig::new("Data".to_string()).with_encodings(vec![runtime.into()]);
+    let registry = runtime.get_registry().unwrap();
+    let test_dir =
+        make_test_project(&dir, &runtime.name().to_camel_case(), "Testing", "Data").unwrap();
+    let config = CodeGeneratorConfig::new("Data".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
 
     let installer = csharp::Installer::new(dir);
     installer.install_serde_runtime().unwrap();
     match runtime {
-        Runtime::Bincode => installer.install_bincode_runtime().unwrap(),
+        Runtime::Bincode | Runtime::BincodeVarintBigEndian => {
+            installer.install_bincode_runtime().unwrap()
+        }
         Runtime::Bcs => installer.install_bcs_runtime().unwrap(),
+        Runtime::Postcard => installer.install_postcard_runtime().unwrap(),
+        Runtime::Borsh => installer.install_borsh_runtime().unwrap(),
+        Runtime::Scale => installer.install_scale_runtime().unwrap(),
     }
     installer.install_module(&config, &registry).unwrap();
 
@@ -326,3 +471,91 @@ namespace Testing {{
     dotnet_build(&test_dir);
     run_nunit(&test_dir);
 }
+
+#[test]
+fn test_csharp_json_runtime_on_supported_types() {
+    let (dir, _tmp) = create_test_dir("test_csharp_json_runtime_on_supported_types");
+    let registry = json_data::get_registry().unwrap();
+    let test_dir = make_test_project(&dir, "Json", "Testing", "Data").unwrap();
+    let config = CodeGeneratorConfig::new("Data".to_string())
+        .with_encodings(vec![Encoding::Json])
+        .with_c_style_enums(true);
+
+    let installer = csharp::Installer::new(dir);
+    installer.install_serde_runtime().unwrap();
+    installer.install_json_runtime().unwrap();
+    installer.install_module(&config, &registry).unwrap();
+
+    // JSON strings are valid C# string literals.
+    let quote = |input: &str| serde_json::to_string(input).unwrap();
+    let positive_inputs = json_data::get_positive_samples()
+        .iter()
+        .map(|input| quote(input))
+        .collect::<Vec<_>>()
+        .join(",\n\t\t\t");
+    let alternate_inputs = json_data::get_alternate_samples()
+        .iter()
+        .map(|(input, output)| format!("new string[] {{ {}, {} }}", quote(input), quote(output)))
+        .collect::<Vec<_>>()
+        .join(",\n\t\t\t");
+    let negative_inputs = json_data::get_negative_samples()
+        .iter()
+        .map(|input| quote(input))
+        .collect::<Vec<_>>()
+        .join(",\n\t\t\t");
+
+    let mut source = File::create(test_dir.join("TestRuntime.cs")).unwrap();
+    writeln!(
+        source,
+        r#"
+using System;
+using NUnit.Framework;
+using Serde;
+using Data;
+
+namespace Testing {{
+    [TestFixture]
+    public class TestJsonRuntime {{
+        static readonly string[] PositiveInputs = new string[] {{
+            {0}
+        }};
+
+        static readonly string[][] AlternateInputs = new string[][] {{
+            {1}
+        }};
+
+        static readonly string[] NegativeInputs = new string[] {{
+            {2}
+        }};
+
+        [Test]
+        public void TestRoundTrip() {{
+            foreach (string input in PositiveInputs) {{
+                SerdeData value = SerdeData.JsonDeserialize(input);
+                Assert.AreEqual(input, value.JsonSerialize());
+                Assert.AreEqual(value, SerdeData.JsonDeserialize(input));
+            }}
+        }}
+
+        [Test]
+        public void TestAlternateInputs() {{
+            foreach (string[] inputs in AlternateInputs) {{
+                Assert.AreEqual(inputs[1], SerdeData.JsonDeserialize(inputs[0]).JsonSerialize());
+            }}
+        }}
+
+        [Test]
+        public void TestNegativeInputs() {{
+            foreach (string input in NegativeInputs) {{
+                Assert.Throws<DeserializationException>(() => SerdeData.JsonDeserialize(input), input);
+            }}
+        }}
+    }}
+}}"#,
+        positive_inputs, alternate_inputs, negative_inputs,
+    )
+    .unwrap();
+
+    dotnet_build(&test_dir);
+    run_nunit(&test_dir);
+}
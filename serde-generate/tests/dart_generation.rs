@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use serde_generate::{dart, CodeGeneratorConfig, Encoding, SourceInstaller};
+use serde_generate::{
+    dart, Case, CodeGeneratorConfig, Encoding, ModuleMap, NamingConventions, SourceGenerator,
+    SourceInstaller,
+};
 use std::fs::read_to_string;
 use std::{
     io::Result,
@@ -127,3 +130,116 @@ fn test_dart_code_compiles_class_enums_for_complex_enums() {
     assert!(generated_c_style.contains("enum CStyleEnum {"));
     assert!(generated_class_style.contains("abstract class List_ {"));
 }
+
+#[test]
+fn test_dart_source_files_with_naming_conventions() {
+    let registry = test_utils::get_registry().unwrap();
+    let naming = NamingConventions::default()
+        .with_fields(Case::Snake)
+        .with_files(Case::UpperCamel);
+    let config = CodeGeneratorConfig::new("example".to_string()).with_naming_conventions(naming);
+    let files = dart::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("lib/src/example/OtherTypes.dart").unwrap();
+    assert!(content.contains("final String f_string;"));
+    let content = files.get("lib/src/example/example.dart").unwrap();
+    assert!(content.contains("part 'OtherTypes.dart';"));
+}
+
+#[test]
+fn test_dart_source_files_with_modules() {
+    let registry = test_utils::get_registry_with_modules();
+    let config = CodeGeneratorConfig::new("example".to_string());
+    let modules = ModuleMap::from_qualified_names(&registry, "_");
+    let files = modules
+        .generate(&dart::CAPABILITIES, &config, &registry, |config| {
+            Box::new(dart::CodeGenerator::new(config))
+        })
+        .unwrap();
+
+    let content = files.get("lib/src/a/a.dart").unwrap();
+    assert!(content.contains("import '../b/b.dart' as b;"));
+    let content = files.get("lib/src/a/foo.dart").unwrap();
+    assert!(content.contains("final b.Bar? bar;"));
+    let content = files.get("lib/src/c/c.dart").unwrap();
+    assert!(content.contains("import '../a/a.dart' as a;"));
+    assert!(content.contains("import '../example/example.dart' as example;"));
+}
+
+#[test]
+fn test_dart_source_files_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("example".to_string()).with_comparisons(true);
+    let files = dart::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("lib/src/example/ordered_data.dart").unwrap();
+    assert!(content.contains("abstract class OrderedData implements Comparable<OrderedData> {"));
+    assert!(content.contains(
+        "if (other is! OrderedDataFlag) return _variantIndex.compareTo(other._variantIndex);"
+    ));
+    assert!(content.contains("int get hashCode => TraitHelpers.hashVectorOptionU32(value);"));
+    let content = files.get("lib/src/example/trait_helpers.dart").unwrap();
+    assert!(
+        content.contains("static int compareMapStrToU16(Map<String, int> a, Map<String, int> b) {")
+    );
+}
+
+#[test]
+fn test_dart_source_files_with_validation() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("example".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_validate_on_serialize(true);
+    let files = dart::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("lib/src/example/ordered_data.dart").unwrap();
+    assert!(content.contains("void validate();"));
+    assert!(content.contains("TraitHelpers.validateTuple2LabelArray2U16Array(value);"));
+    assert!(content.contains("Uint8List bcsSerialize() {\n      validate();"));
+    let content = files.get("lib/src/example/trait_helpers.dart").unwrap();
+    assert!(content.contains("if (value.length != 2) {"));
+}
+
+#[test]
+fn test_dart_code_compiles_with_validation() {
+    let source_path = tempdir().unwrap().path().join("dart_validation_project");
+
+    let config = CodeGeneratorConfig::new("example".to_string())
+        .with_encodings(vec![Encoding::Bcs, Encoding::Bincode])
+        .with_c_style_enums(true)
+        .with_validate_on_serialize(true);
+
+    generate_with_config(source_path, &config);
+}
+
+#[test]
+fn test_dart_source_files_with_json() {
+    let registry = test_utils::json_data::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("example".to_string())
+        .with_encodings(vec![Encoding::Bcs, Encoding::Json])
+        .with_c_style_enums(true);
+    let files = dart::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("lib/src/example/example.dart").unwrap();
+    assert!(content.contains("import '../json/json.dart';"));
+    let content = files.get("lib/src/example/other_types.dart").unwrap();
+    assert!(content.contains("static OtherTypes jsonDeserialize(String input) {"));
+    assert!(content.contains("json.put('f_string', Json.serializeString(this.fString));"));
+    assert!(content
+        .contains("fOption: TraitHelpers.fromJsonOptionStruct(obj.optionalField('f_option')),"));
+    let content = files.get("lib/src/example/serde_data.dart").unwrap();
+    assert!(content.contains("case 'UnitVariant': return SerdeDataUnitVariant.loadJson(variant);"));
+    let content = files.get("lib/src/example/color.dart").unwrap();
+    assert!(content.contains("case Color.red: return 'Red';"));
+    let content = files.get("lib/src/example/trait_helpers.dart").unwrap();
+    assert!(content.contains("json.sortKeys(JsonObject.variantOrder(['Red', 'Green', 'Blue']));"));
+    assert!(content.contains("obj[ColorExtension.fromJson(JsonKey(entries.keys[i]))]"));
+}
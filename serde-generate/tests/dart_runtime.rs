@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use crate::test_utils::{Choice, Runtime, Test};
-use serde_generate::{dart, CodeGeneratorConfig, SourceInstaller};
+use crate::test_utils::{json_data, Choice, Runtime, Test};
+use serde_generate::{
+    dart, BincodeOptions, CodeGeneratorConfig, Encoding, SourceInstaller, TrailingBytes,
+};
 use std::{
     fs::{create_dir_all, File},
     io::{Result, Write},
@@ -53,6 +55,26 @@ fn test_dart_bincode_runtime_on_simple_data() {
     test_dart_runtime_on_simple_data(Runtime::Bincode);
 }
 
+#[test]
+fn test_dart_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_dart_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_dart_postcard_runtime_on_simple_data() {
+    test_dart_runtime_on_simple_data(Runtime::Postcard);
+}
+
+#[test]
+fn test_dart_borsh_runtime_on_simple_data() {
+    test_dart_runtime_on_simple_data(Runtime::Borsh);
+}
+
+#[test]
+fn test_dart_scale_runtime_on_simple_data() {
+    test_dart_runtime_on_simple_data(Runtime::Scale);
+}
+
 fn test_dart_runtime_on_simple_data(runtime: Runtime) {
     let tempdir = tempdir().unwrap();
     let source_path = tempdir
@@ -61,6 +83,7 @@ fn test_dart_runtime_on_simple_data(runtime: Runtime) {
     let registry = test_utils::get_simple_registry().unwrap();
     let config = CodeGeneratorConfig::new("example".to_string())
         .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options())
         .with_c_style_enums(false);
 
     let installer = dart::Installer::new(source_path.clone());
@@ -125,6 +148,79 @@ void main() {{
     assert!(output.status.success());
 }
 
+#[test]
+fn test_dart_runtime_with_bincode_limit_and_trailing_bytes() {
+    let tempdir = tempdir().unwrap();
+    let source_path = tempdir.path().join("dart_project_bincode_limit");
+    let registry = test_utils::get_simple_registry().unwrap();
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("example".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        )
+        .with_c_style_enums(false);
+
+    let installer = dart::Installer::new(source_path.clone());
+    installer.install_module(&config, &registry).unwrap();
+    installer.install_serde_runtime().unwrap();
+    installer.install_bincode_runtime().unwrap();
+    install_test_dependency(&source_path).unwrap();
+
+    create_dir_all(source_path.join("test")).unwrap();
+
+    let source = source_path.join("test/runtime_test.dart");
+    let mut source_file = File::create(source).unwrap();
+
+    let mut input = reference.clone();
+    input.push(0);
+    writeln!(
+        source_file,
+        r#"
+import 'dart:typed_data';
+import 'package:example/example.dart';
+import 'package:test/test.dart';
+
+void main() {{
+    test('bincode limit and trailing bytes', () {{
+        Test value = Test.bincodeDeserialize({0});
+        expect(value.bincodeSerialize(), equals({1}));
+        expect(
+            () => Test.bincodeDeserialize({2}),
+            throwsA(predicate((e) => e.toString().contains('limit'))),
+        );
+    }});
+}}"#,
+        quote_bytes(&input),
+        quote_bytes(&reference),
+        quote_bytes(&too_large),
+    )
+    .unwrap();
+
+    let output = Command::new(DART_EXECUTABLE)
+        .current_dir(&source_path)
+        .env("PUB_CACHE", "../.pub-cache")
+        .args(["test", "test/runtime_test.dart"])
+        .output()
+        .unwrap();
+    if !output.status.success() {
+        let error_output = String::from_utf8_lossy(&output.stdout);
+        eprintln!("{}", error_output);
+    }
+    assert!(output.status.success());
+}
+
 #[test]
 fn test_dart_bcs_runtime_on_supported_types() {
     test_dart_runtime_on_supported_types(Runtime::Bcs);
@@ -135,6 +231,26 @@ fn test_dart_bincode_runtime_on_supported_types() {
     test_dart_runtime_on_supported_types(Runtime::Bincode);
 }
 
+#[test]
+fn test_dart_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_dart_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_dart_postcard_runtime_on_supported_types() {
+    test_dart_runtime_on_supported_types(Runtime::Postcard);
+}
+
+#[test]
+fn test_dart_borsh_runtime_on_supported_types() {
+    test_dart_runtime_on_supported_types(Runtime::Borsh);
+}
+
+#[test]
+fn test_dart_scale_runtime_on_supported_types() {
+    test_dart_runtime_on_supported_types(Runtime::Scale);
+}
+
 fn quote_bytes(bytes: &[u8]) -> String {
     format!(
         "Uint8List.fromList([{}])",
@@ -151,9 +267,10 @@ fn test_dart_runtime_on_supported_types(runtime: Runtime) {
     let source_path = tempdir
         .path()
         .join(format!("dart_project_{}", runtime.name().to_lowercase()));
-    let registry = test_utils::get_registry().unwrap();
+    let registry = runtime.get_registry().unwrap();
     let config = CodeGeneratorConfig::new("example".to_string())
         .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options())
         .with_c_style_enums(false);
 
     let installer = dart::Installer::new(source_path.clone());
@@ -259,3 +376,115 @@ void main() {{
     }
     assert!(output.status.success());
 }
+
+/// Quote a string as a Dart string literal.
+fn quote_string(input: &str) -> String {
+    let mut result = String::from("'");
+    for c in input.chars() {
+        match c {
+            '\'' | '\\' | '$' => {
+                result.push('\\');
+                result.push(c);
+            }
+            ' '..='~' => result.push(c),
+            _ => result += &format!("\\u{{{:x}}}", c as u32),
+        }
+    }
+    result.push('\'');
+    result
+}
+
+#[test]
+fn test_dart_json_runtime_on_supported_types() {
+    let tempdir = tempdir().unwrap();
+    let source_path = tempdir.path().join("dart_project_json");
+    let registry = json_data::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("example".to_string())
+        .with_encodings(vec![Encoding::Json])
+        .with_c_style_enums(false);
+
+    let installer = dart::Installer::new(source_path.clone());
+    installer.install_module(&config, &registry).unwrap();
+    installer.install_serde_runtime().unwrap();
+    installer.install_json_runtime().unwrap();
+    install_test_dependency(&source_path).unwrap();
+
+    create_dir_all(source_path.join("test")).unwrap();
+
+    let source = source_path.join("test/runtime_test.dart");
+    let mut source_file = File::create(source).unwrap();
+
+    let positive_inputs = json_data::get_positive_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect::<Vec<_>>()
+        .join(",\n            ");
+    let alternate_inputs = json_data::get_alternate_samples()
+        .iter()
+        .map(|(input, output)| format!("[{}, {}]", quote_string(input), quote_string(output)))
+        .collect::<Vec<_>>()
+        .join(",\n            ");
+    let negative_inputs = json_data::get_negative_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect::<Vec<_>>()
+        .join(",\n            ");
+
+    writeln!(
+        source_file,
+        r#"
+import 'package:example/example.dart';
+import 'package:test/test.dart';
+
+void main() {{
+    test('json supported types', () {{
+        List<String> positiveInputs = [
+            {0}
+        ];
+        List<List<String>> alternateInputs = [
+            {1}
+        ];
+        List<String> negativeInputs = [
+            {2}
+        ];
+
+        for (var input in positiveInputs) {{
+            SerdeData value = SerdeData.jsonDeserialize(input);
+            expect(value.jsonSerialize(), equals(input));
+
+            // Test self-equality for the deserialized value.
+            expect(value, equals(SerdeData.jsonDeserialize(input)));
+        }}
+
+        for (var pair in alternateInputs) {{
+            expect(SerdeData.jsonDeserialize(pair[0]).jsonSerialize(), equals(pair[1]));
+        }}
+
+        // Test negative inputs for deserialization failure.
+        for (var input in negativeInputs) {{
+            try {{
+                SerdeData.jsonDeserialize(input);
+            }} catch (e) {{
+                continue;
+            }}
+            throw Exception('Input should fail to deserialize: ' + input);
+        }}
+    }});
+}}
+"#,
+        positive_inputs, alternate_inputs, negative_inputs,
+    )
+    .unwrap();
+
+    let output = Command::new(DART_EXECUTABLE)
+        .current_dir(&source_path)
+        .env("PUB_CACHE", "../.pub-cache")
+        .args(["test", "test/runtime_test.dart"])
+        .output()
+        .unwrap();
+    if !output.status.success() {
+        let error_output = String::from_utf8_lossy(&output.stdout);
+        eprintln!("{}", error_output);
+    }
+    assert!(output.status.success());
+}
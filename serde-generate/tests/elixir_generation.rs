@@ -0,0 +1,322 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils;
+use serde::Deserialize;
+use serde_generate::{
+    elixir, BincodeOptions, Case, CodeGeneratorConfig, Encoding, NamingConventions,
+    SourceGenerator, SourceInstaller, TrailingBytes,
+};
+use serde_reflection::{Tracer, TracerConfig};
+use std::{collections::BTreeMap, process::Command};
+use tempfile::{tempdir, TempDir};
+
+/// Generate `testing.ex` then compile it together with the runtime.
+fn test_that_elixir_code_compiles_with_config(
+    config: &CodeGeneratorConfig,
+) -> (TempDir, std::path::PathBuf) {
+    let registry = test_utils::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let installer = elixir::Installer::new(dir.path().to_path_buf());
+    installer.install_module(config, &registry).unwrap();
+
+    let path = dir.path().join("testing.ex");
+    let status = Command::new("elixirc")
+        .arg("--warnings-as-errors")
+        .arg("-o")
+        .arg(dir.path())
+        .arg("runtime/elixir/serde.ex")
+        .arg("runtime/elixir/bincode.ex")
+        .arg("runtime/elixir/bcs.ex")
+        .arg(&path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    (dir, path)
+}
+
+#[test]
+fn test_that_elixir_code_compiles() {
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    test_that_elixir_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_elixir_code_compiles_without_serialization() {
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_serialization(false);
+    let (_dir, path) = test_that_elixir_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(!content.contains("def serialize("));
+    assert!(!content.contains("TraitHelpers"));
+}
+
+#[test]
+fn test_that_elixir_code_compiles_with_bcs() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bcs]);
+    let (_dir, path) = test_that_elixir_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content
+        .contains("  def bcs_deserialize(input), do: Bcs.deserialize(input, &deserialize/2)\n"));
+}
+
+#[test]
+fn test_that_elixir_code_compiles_with_bincode() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bincode]);
+    test_that_elixir_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_elixir_code_compiles_with_bincode_options() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bincode])
+        .with_bincode_options(
+            BincodeOptions::standard()
+                .with_limit(Some(100))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let (_dir, path) = test_that_elixir_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains(
+        "Bincode.deserialize(input, &deserialize/2, varint_encoding: true, limit: 100, allow_trailing_bytes: true)"
+    ));
+}
+
+#[test]
+fn test_that_elixir_code_compiles_with_comments() {
+    let comments = vec![
+        (
+            vec!["testing".to_string(), "SerdeData".to_string()],
+            "Some\ncomments".to_string(),
+        ),
+        (
+            vec![
+                "testing".to_string(),
+                "SerdeData".to_string(),
+                "UnitVariant".to_string(),
+            ],
+            "A variant".to_string(),
+        ),
+        (
+            vec!["testing".to_string(), "Struct".to_string(), "x".to_string()],
+            "A field".to_string(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comments(comments);
+
+    let (_dir, path) = test_that_elixir_code_compiles_with_config(&config);
+
+    // Comments were correctly generated.
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains(
+        r#"
+defmodule Testing.SerdeData do
+  @moduledoc """
+  Some
+  comments
+  """
+"#
+    ));
+    assert!(content.contains(
+        r#"
+          # A variant
+          | :UnitVariant
+"#
+    ));
+    assert!(content.contains(
+        r#"
+  @type t :: %__MODULE__{
+          # A field
+          x: non_neg_integer(),
+          y: non_neg_integer()
+        }
+"#
+    ));
+}
+
+#[test]
+fn test_that_elixir_code_compiles_with_custom_code() {
+    let custom_code = vec![(
+        vec!["testing".to_string(), "SerdeData".to_string()],
+        "def custom_function, do: 42".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_custom_code(custom_code);
+
+    let (_dir, path) = test_that_elixir_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains("def custom_function, do: 42"));
+}
+
+#[test]
+fn test_that_elixir_code_compiles_with_naming_conventions() {
+    let naming = NamingConventions::default()
+        .with_fields(Case::LowerCamel)
+        .with_variants(Case::ScreamingSnake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+
+    let (_dir, path) = test_that_elixir_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains("{:PRIMITIVE_TYPES, Testing.PrimitiveTypes.t()}\n"));
+    assert!(content.contains("          fString: String.t(),\n"));
+}
+
+#[test]
+fn test_elixir_code_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comparisons(true);
+    let files = elixir::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("testing.ex").unwrap();
+    assert!(content.contains("@spec compare(t(), t()) :: :lt | :eq | :gt"));
+    assert!(content.contains("{{:Flag, lhs_value}, {:Flag, rhs_value}} ->"));
+    assert!(content.contains("Serde.compare(variant_index(lhs), variant_index(rhs))"));
+    assert!(content.contains("Serde.compare_map(lhs, rhs, &Serde.compare/2, &Serde.compare/2)"));
+}
+
+#[test]
+fn test_elixir_code_with_external_definitions() {
+    let registry = test_utils::get_registry().unwrap();
+
+    // (wrongly) Declare Tree as external.
+    let mut definitions = BTreeMap::new();
+    definitions.insert("pkg.foo".to_string(), vec!["Tree".to_string()]);
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_external_definitions(definitions);
+    let generator = elixir::CodeGenerator::new(&config);
+    let files = generator.generate(&registry).unwrap();
+
+    let content = files.get("testing.ex").unwrap();
+    assert!(!content.contains("defmodule Testing.Tree do"));
+    assert!(content.contains("Pkg.Foo.Tree.deserialize(input, deserializer)"));
+}
+
+#[test]
+fn test_elixir_code_escapes_variables_of_fields() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Foo {
+        input: u8,
+        r#do: u8,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let files = elixir::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("testing.ex").unwrap();
+    assert!(
+        content.contains("  def serialize(%__MODULE__{input: input_, do: do_}, serializer) do\n")
+    );
+    assert!(content.contains(
+        "    {input_, input} = Serde.Deserializer.deserialize_u8(input, deserializer)\n"
+    ));
+}
+
+#[test]
+fn test_elixir_code_rejects_nested_options() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Foo {
+        bar: Option<Option<u8>>,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let error = elixir::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Elixir does not support options of options or of unit values (in Foo)"
+    );
+}
+
+#[test]
+fn test_elixir_code_rejects_lowercase_type_names() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let naming = NamingConventions::default().with_types(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = elixir::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Elixir does not support names of types that do not start with an uppercase letter (in choice)"
+    );
+}
+
+#[test]
+fn test_elixir_code_rejects_validation() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let configs = [
+        CodeGeneratorConfig::new("testing".to_string()).with_validation(true),
+        CodeGeneratorConfig::new("testing".to_string()).with_validate_on_serialize(true),
+    ];
+    for config in &configs {
+        let error = elixir::CodeGenerator::new(config)
+            .generate(&registry)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Elixir does not support validation");
+    }
+}
+
+#[test]
+fn test_elixir_code_rejects_reserved_words() {
+    #[derive(Deserialize)]
+    #[allow(dead_code, non_snake_case)]
+    struct Foo {
+        End: u8,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let naming = NamingConventions::default().with_fields(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = elixir::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Elixir name `end` of Foo.End is a reserved word"
+    );
+}
+
+#[test]
+fn test_elixir_installer_rejects_unsupported_runtimes() {
+    let dir = tempdir().unwrap();
+    let installer = elixir::Installer::new(dir.path().to_path_buf());
+    installer.install_serde_runtime().unwrap();
+    installer.install_bcs_runtime().unwrap();
+    assert!(dir.path().join("serde.ex").exists());
+    assert!(dir.path().join("bcs.ex").exists());
+
+    let error = installer.install_postcard_runtime().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Elixir does not support the postcard encoding"
+    );
+}
@@ -0,0 +1,292 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils;
+use crate::test_utils::{Choice, Runtime, Test};
+use serde_generate::{elixir, BincodeOptions, CodeGeneratorConfig, SourceInstaller, TrailingBytes};
+use std::{fs::File, io::Write, path::Path, process::Command};
+use tempfile::tempdir;
+
+/// Compile the runtime and the generated code, then run `main.exs`.
+fn compile_and_run_elixir_main(dir: &Path) {
+    let status = Command::new("elixirc")
+        .arg("-o")
+        .arg(dir)
+        .arg("runtime/elixir/serde.ex")
+        .arg("runtime/elixir/bincode.ex")
+        .arg("runtime/elixir/bcs.ex")
+        .arg(dir.join("testing.ex"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("elixir")
+        .arg("-pa")
+        .arg(dir)
+        .arg(dir.join("main.exs"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn quote_bytes(bytes: &[u8]) -> String {
+    format!(
+        "<<{}>>",
+        bytes
+            .iter()
+            .map(|x| format!("{}", x))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+#[test]
+fn test_elixir_bcs_runtime_on_simple_data() {
+    test_elixir_runtime_on_simple_data(Runtime::Bcs);
+}
+
+#[test]
+fn test_elixir_bincode_runtime_on_simple_data() {
+    test_elixir_runtime_on_simple_data(Runtime::Bincode);
+}
+
+#[test]
+fn test_elixir_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_elixir_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+fn test_elixir_runtime_on_simple_data(runtime: Runtime) {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let installer = elixir::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    let reference = runtime.serialize(&Test {
+        a: vec![4, 6],
+        b: (-3, 5),
+        c: Choice::C { x: 7 },
+    });
+
+    let mut source = File::create(dir.path().join("main.exs")).unwrap();
+    writeln!(
+        source,
+        r#"input = {0}
+
+{{:ok, value}} = Testing.Test.{1}_deserialize(input)
+
+value2 = %Testing.Test{{a: [4, 6], b: {{-3, 5}}, c: {{:C, %{{x: 7}}}}}}
+unless value == value2, do: raise("unexpected value")
+
+{{:ok, output}} = Testing.Test.{1}_serialize(value2)
+unless output == input, do: raise("unexpected output")
+
+case Testing.Test.{1}_deserialize(input <> <<1>>) do
+  {{:error, %Serde.DeserializationError{{}}}} -> :ok
+  _ -> raise "trailing bytes should be rejected"
+end
+"#,
+        quote_bytes(&reference),
+        runtime.name(),
+    )
+    .unwrap();
+
+    compile_and_run_elixir_main(dir.path());
+}
+
+#[test]
+fn test_elixir_bcs_runtime_on_supported_types() {
+    test_elixir_runtime_on_supported_types(Runtime::Bcs);
+}
+
+#[test]
+fn test_elixir_bincode_runtime_on_supported_types() {
+    test_elixir_runtime_on_supported_types(Runtime::Bincode);
+}
+
+#[test]
+fn test_elixir_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_elixir_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+fn test_elixir_runtime_on_supported_types(runtime: Runtime) {
+    let registry = runtime.get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let installer = elixir::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    let positive_encodings: Vec<_> = runtime
+        .get_positive_samples_quick()
+        .iter()
+        .map(|bytes| quote_bytes(bytes))
+        .collect();
+
+    let negative_encodings: Vec<_> = runtime
+        .get_negative_samples()
+        .iter()
+        .map(|bytes| quote_bytes(bytes))
+        .collect();
+
+    let mut source = File::create(dir.path().join("main.exs")).unwrap();
+    writeln!(
+        source,
+        r#"positive_inputs = [
+  {0}
+]
+
+negative_inputs = [
+  {1}
+]
+
+for input <- positive_inputs do
+  {{:ok, value}} = Testing.SerdeData.{2}_deserialize(input)
+  {{:ok, output}} = Testing.SerdeData.{2}_serialize(value)
+  unless output == input, do: raise("Failed to re-serialize #{{inspect(input)}}")
+
+  # Test simple mutations of the input.
+  for i <- 0..(byte_size(input) - 1) do
+    <<prefix::binary-size(i), byte, suffix::binary>> = input
+    input2 = prefix <> <<Bitwise.bxor(byte, 0x80)>> <> suffix
+
+    case Testing.SerdeData.{2}_deserialize(input2) do
+      {{:ok, ^value}} -> raise "Mutation of #{{inspect(input)}} at #{{i}} gave the same value"
+      _ -> :ok
+    end
+  end
+end
+
+for input <- negative_inputs do
+  case Testing.SerdeData.{2}_deserialize(input) do
+    {{:error, %Serde.DeserializationError{{}}}} -> :ok
+    _ -> raise "Input should fail to deserialize: #{{inspect(input)}}"
+  end
+end
+"#,
+        positive_encodings.join(",\n  "),
+        negative_encodings.join(",\n  "),
+        runtime.name(),
+    )
+    .unwrap();
+
+    compile_and_run_elixir_main(dir.path());
+}
+
+#[test]
+fn test_elixir_runtime_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bcs.into()])
+        .with_comparisons(true);
+    let installer = elixir::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    // Values are sorted in the order of Rust.
+    let inputs = test_utils::get_ordered_values()
+        .iter()
+        .map(|value| quote_bytes(&Runtime::Bcs.serialize(value)))
+        .collect::<Vec<_>>()
+        .join(",\n  ");
+
+    let mut source = File::create(dir.path().join("main.exs")).unwrap();
+    writeln!(
+        source,
+        r#"inputs = [
+  {0}
+]
+
+values =
+  Enum.map(inputs, fn input ->
+    {{:ok, value}} = Testing.OrderedData.bcs_deserialize(input)
+    value
+  end)
+
+for {{x, i}} <- Enum.with_index(values), {{y, j}} <- Enum.with_index(values) do
+  unless Testing.OrderedData.compare(x, y) == Serde.compare(i, j),
+    do: raise("unexpected comparison: #{{i}} vs #{{j}}")
+
+  unless (x == y) == (i == j), do: raise("unexpected equality: #{{i}} vs #{{j}}")
+end
+
+unless Enum.sort(Enum.reverse(values), Testing.OrderedData) == values,
+  do: raise("unexpected sort")
+
+unless MapSet.size(MapSet.new(values)) == length(values), do: raise("unexpected set")
+"#,
+        inputs
+    )
+    .unwrap();
+
+    compile_and_run_elixir_main(dir.path());
+}
+
+#[test]
+fn test_elixir_runtime_with_bincode_limit_and_trailing_bytes() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let installer = elixir::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    let mut source = File::create(dir.path().join("main.exs")).unwrap();
+    writeln!(
+        source,
+        r#"input = {0}
+
+{{:ok, value}} = Testing.Test.bincode_deserialize(input <> <<0>>)
+{{:ok, output}} = Testing.Test.bincode_serialize(value)
+unless output == input, do: raise("unexpected output")
+
+case Testing.Test.bincode_deserialize({1}) do
+  {{:error, %Serde.DeserializationError{{message: message}}}} ->
+    unless message =~ "limit", do: raise("unexpected error")
+
+  _ ->
+    raise "the size limit should be enforced"
+end
+"#,
+        quote_bytes(&reference),
+        quote_bytes(&too_large),
+    )
+    .unwrap();
+
+    compile_and_run_elixir_main(dir.path());
+}
+
+#[test]
+fn test_elixir_bcs_runtime_autotest() {
+    let status = Command::new("elixir")
+        .arg("-r")
+        .arg("runtime/elixir/serde.ex")
+        .arg("-r")
+        .arg("runtime/elixir/bcs.ex")
+        .arg("runtime/elixir/test_bcs.exs")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -187,3 +187,65 @@ fn test_that_golang_code_compiles_with_custom_code() {
     assert!(content.contains("// custom1"));
     assert!(content.contains("// custom2"));
 }
+
+#[test]
+fn test_that_golang_code_compiles_with_comparisons() {
+    let config = CodeGeneratorConfig::new("main".to_string()).with_comparisons(true);
+
+    let (_dir, source_path) = test_that_golang_code_compiles_with_config_and_registry(
+        &config,
+        &test_utils::get_ordered_registry().unwrap(),
+    );
+    let content = std::fs::read_to_string(source_path).unwrap();
+    assert!(content.contains("func (obj *OrderedData__Text) Compare(other OrderedData) int {"));
+    assert!(content.contains("func (obj *Label) Hash() uint64 {"));
+    assert!(content.contains("\"sort\"\n"));
+}
+
+#[test]
+fn test_that_golang_code_compiles_with_validation() {
+    let config = CodeGeneratorConfig::new("main".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_validate_on_serialize(true);
+    let registry = test_utils::get_registry().unwrap();
+    let mut source = Vec::new();
+    golang::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+    let content = String::from_utf8(source).unwrap();
+    assert!(content.contains("func (obj *SerdeData__PrimitiveTypes) Validate() error {"));
+    assert!(content
+        .contains("if obj.Field1 == nil { return fmt.Errorf(\"Missing value for enum List\") }"));
+    assert!(content.contains("if err := obj.Validate(); err != nil { return nil, err }"));
+
+    test_that_golang_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_golang_code_compiles_with_json() {
+    let config = CodeGeneratorConfig::new("main".to_string()).with_encodings(vec![Encoding::Json]);
+    let registry = test_utils::json_data::get_registry().unwrap();
+    let mut source = Vec::new();
+    golang::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+    let content = String::from_utf8(source).unwrap();
+    assert!(content.contains("func JsonDeserializeSerdeData(input []byte) (SerdeData, error) {"));
+    assert!(content.contains("func loadJson_SerdeData__PrimitiveTypes(variant *json.Variant)"));
+    assert!(content.contains("result.SortKeys(json.NumberOrder)"));
+    assert!(!content.contains("json.NewSerializer()"));
+
+    test_that_golang_code_compiles_with_config_and_registry(&config, &registry);
+}
+
+#[test]
+fn test_that_golang_code_with_open_enums_compiles() {
+    let config = CodeGeneratorConfig::new("main".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_comparisons(true)
+        .with_validation(true);
+    test_that_golang_code_compiles_with_config_and_registry(
+        &config,
+        &test_utils::get_registry_with_open_enum(),
+    );
+}
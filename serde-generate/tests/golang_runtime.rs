@@ -2,9 +2,9 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use crate::test_utils::{Choice, Runtime, Test};
+use crate::test_utils::{json_data, Choice, Runtime, Test};
 use heck::CamelCase;
-use serde_generate::{golang, CodeGeneratorConfig};
+use serde_generate::{golang, BincodeOptions, CodeGeneratorConfig, Encoding, TrailingBytes};
 use std::{fs::File, io::Write, process::Command};
 use tempfile::tempdir;
 
@@ -35,6 +35,26 @@ fn test_golang_bincode_runtime_on_simple_data() {
     test_golang_runtime_on_simple_data(Runtime::Bincode);
 }
 
+#[test]
+fn test_golang_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_golang_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_golang_postcard_runtime_on_simple_data() {
+    test_golang_runtime_on_simple_data(Runtime::Postcard);
+}
+
+#[test]
+fn test_golang_borsh_runtime_on_simple_data() {
+    test_golang_runtime_on_simple_data(Runtime::Borsh);
+}
+
+#[test]
+fn test_golang_scale_runtime_on_simple_data() {
+    test_golang_runtime_on_simple_data(Runtime::Scale);
+}
+
 fn test_golang_runtime_on_simple_data(runtime: Runtime) {
     let registry = test_utils::get_simple_registry().unwrap();
     let dir = tempdir().unwrap();
@@ -43,6 +63,7 @@ fn test_golang_runtime_on_simple_data(runtime: Runtime) {
 
     let config = CodeGeneratorConfig::new("main".to_string())
         .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options())
         .with_external_definitions(
             vec![("github.com/google/go-cmp/cmp".to_string(), vec![])]
                 .into_iter()
@@ -137,6 +158,206 @@ fn test_golang_bincode_runtime_on_supported_types() {
     test_golang_runtime_on_supported_types(Runtime::Bincode);
 }
 
+#[test]
+fn test_golang_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_golang_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_golang_postcard_runtime_on_supported_types() {
+    test_golang_runtime_on_supported_types(Runtime::Postcard);
+}
+
+#[test]
+fn test_golang_borsh_runtime_on_supported_types() {
+    test_golang_runtime_on_supported_types(Runtime::Borsh);
+}
+
+#[test]
+fn test_golang_scale_runtime_on_supported_types() {
+    test_golang_runtime_on_supported_types(Runtime::Scale);
+}
+
+#[test]
+fn test_golang_bcs_runtime_on_open_enum() {
+    let registry = test_utils::get_registry_with_open_enum();
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.go");
+    let mut source = File::create(&source_path).unwrap();
+
+    let config = CodeGeneratorConfig::new("main".to_string())
+        .with_encodings(vec![Runtime::Bcs.into()])
+        .with_external_definitions(
+            vec![("github.com/google/go-cmp/cmp".to_string(), vec![])]
+                .into_iter()
+                .collect(),
+        );
+    let generator = golang::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+
+    // The second event has a tag unknown to the generated code.
+    let reference = test_utils::get_open_enum_log(Runtime::Bcs);
+    writeln!(
+        source,
+        r#"
+func main() {{
+	input := []byte{0}
+	log, err := BcsDeserializeLog(input)
+	if err != nil {{ panic("failed to deserialize") }}
+	if !cmp.Equal(EventTags, []string{{ "Click" }}) {{ panic("unexpected tags") }}
+
+	var clicks []Click
+	RegisterEventHandler("Click", func(content []byte) error {{
+		click, err := BcsDeserializeClick(content)
+		clicks = append(clicks, click)
+		return err
+	}})
+	handled, err := log.Events[0].Handle()
+	if !handled || err != nil {{ panic("failed to handle the first event") }}
+	if !cmp.Equal(clicks, []Click{{ {{ X: 7 }} }}) {{ panic("unexpected clicks") }}
+	handled, err = log.Events[1].Handle()
+	if handled || err != nil {{ panic("failed to skip the second event") }}
+
+	output, err := log.BcsSerialize()
+	if err != nil {{ panic("failed to serialize") }}
+	if !cmp.Equal(input, output) {{ panic("input != output") }}
+}}
+"#,
+        quote_bytes(&reference),
+    )
+    .unwrap();
+
+    let status = Command::new("go")
+        .current_dir(dir.path())
+        .arg("mod")
+        .arg("init")
+        .arg("testing")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let runtime_mod_path = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("../../../serde-generate/runtime/golang");
+    let status = Command::new("go")
+        .current_dir(dir.path())
+        .arg("mod")
+        .arg("edit")
+        .arg("-replace")
+        .arg(format!(
+            "github.com/novifinancial/serde-reflection/serde-generate/runtime/golang={}",
+            runtime_mod_path.to_str().unwrap()
+        ))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("go")
+        .current_dir(dir.path())
+        .arg("run")
+        .arg("-mod=mod")
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_golang_runtime_with_bincode_limit_and_trailing_bytes() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.go");
+    let mut source = File::create(&source_path).unwrap();
+
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("main".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        )
+        .with_external_definitions(
+            vec![
+                ("github.com/google/go-cmp/cmp".to_string(), vec![]),
+                ("strings".to_string(), vec![]),
+            ]
+            .into_iter()
+            .collect(),
+        );
+    let generator = golang::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+
+    let mut input = reference.clone();
+    input.push(0);
+    writeln!(
+        source,
+        r#"
+func main() {{
+	value, err := BincodeDeserializeTest([]byte{0})
+	if err != nil {{ panic("failed to deserialize") }}
+	output, err := value.BincodeSerialize()
+	if err != nil {{ panic("failed to serialize") }}
+	if !cmp.Equal(output, []byte{1}) {{ panic("unexpected output") }}
+
+	_, err = BincodeDeserializeTest([]byte{2})
+	if err == nil || !strings.Contains(err.Error(), "limit") {{ panic("was expecting a limit error") }}
+}}
+"#,
+        quote_bytes(&input),
+        quote_bytes(&reference),
+        quote_bytes(&too_large),
+    )
+    .unwrap();
+
+    let status = Command::new("go")
+        .current_dir(dir.path())
+        .arg("mod")
+        .arg("init")
+        .arg("testing")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let runtime_mod_path = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("../../../serde-generate/runtime/golang");
+    let status = Command::new("go")
+        .current_dir(dir.path())
+        .arg("mod")
+        .arg("edit")
+        .arg("-replace")
+        .arg(format!(
+            "github.com/novifinancial/serde-reflection/serde-generate/runtime/golang={}",
+            runtime_mod_path.to_str().unwrap()
+        ))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("go")
+        .current_dir(dir.path())
+        .arg("run")
+        .arg("-mod=mod")
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
 fn quote_bytes(bytes: &[u8]) -> String {
     format!(
         "{{{}}}",
@@ -149,13 +370,14 @@ fn quote_bytes(bytes: &[u8]) -> String {
 }
 
 fn test_golang_runtime_on_supported_types(runtime: Runtime) {
-    let registry = test_utils::get_registry().unwrap();
+    let registry = runtime.get_registry().unwrap();
     let dir = tempdir().unwrap();
     let source_path = dir.path().join("test.go");
     let mut source = File::create(&source_path).unwrap();
 
     let config = CodeGeneratorConfig::new("main".to_string())
         .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options())
         .with_external_definitions(
             vec![("github.com/google/go-cmp/cmp".to_string(), vec![])]
                 .into_iter()
@@ -258,3 +480,103 @@ func main() {{
         .unwrap();
     assert!(status.success());
 }
+
+#[test]
+fn test_golang_json_runtime_on_supported_types() {
+    let registry = json_data::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.go");
+    let mut source = File::create(&source_path).unwrap();
+
+    let config = CodeGeneratorConfig::new("main".to_string()).with_encodings(vec![Encoding::Json]);
+    let generator = golang::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+
+    // JSON strings are valid Go literals.
+    let quote = |input: &String| serde_json::to_string(input).unwrap();
+    let positive_inputs: Vec<_> = json_data::get_positive_samples()
+        .iter()
+        .map(quote)
+        .collect();
+    let alternate_inputs: Vec<_> = json_data::get_alternate_samples()
+        .iter()
+        .map(|(input, output)| format!("{{{}, {}}}", quote(input), quote(output)))
+        .collect();
+    let negative_inputs: Vec<_> = json_data::get_negative_samples()
+        .iter()
+        .map(quote)
+        .collect();
+
+    writeln!(
+        source,
+        r#"
+func main() {{
+	positive_inputs := []string{{{0}}}
+	alternate_inputs := [][]string{{{1}}}
+	negative_inputs := []string{{{2}}}
+
+	for _, input := range(positive_inputs) {{
+		value, err := JsonDeserializeSerdeData([]byte(input))
+		if err != nil {{ panic(fmt.Sprintf("failed to deserialize input %s: %v", input, err)) }}
+		output, err := value.JsonSerialize()
+		if err != nil {{ panic(fmt.Sprintf("failed to serialize: %v", err)) }}
+		if string(output) != input {{ panic(fmt.Sprintf("input != output:\n  %s\n  %s", input, output)) }}
+	}}
+
+	for _, inputs := range(alternate_inputs) {{
+		value, err := JsonDeserializeSerdeData([]byte(inputs[0]))
+		if err != nil {{ panic(fmt.Sprintf("failed to deserialize input %s: %v", inputs[0], err)) }}
+		output, err := value.JsonSerialize()
+		if err != nil {{ panic(fmt.Sprintf("failed to serialize: %v", err)) }}
+		if string(output) != inputs[1] {{ panic(fmt.Sprintf("unexpected output:\n  %s\n  %s", inputs[1], output)) }}
+	}}
+
+	for _, input := range(negative_inputs) {{
+		if _, err := JsonDeserializeSerdeData([]byte(input)); err == nil {{
+			panic(fmt.Sprintf("Input should fail to deserialize: %s", input))
+		}}
+	}}
+}}
+"#,
+        positive_inputs.join(", "),
+        alternate_inputs.join(", "),
+        negative_inputs.join(", "),
+    )
+    .unwrap();
+
+    let status = Command::new("go")
+        .current_dir(dir.path())
+        .arg("mod")
+        .arg("init")
+        .arg("testing")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let runtime_mod_path = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("../../../serde-generate/runtime/golang");
+    let status = Command::new("go")
+        .current_dir(dir.path())
+        .arg("mod")
+        .arg("edit")
+        .arg("-replace")
+        .arg(format!(
+            "github.com/novifinancial/serde-reflection/serde-generate/runtime/golang={}",
+            runtime_mod_path.to_str().unwrap()
+        ))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("go")
+        .current_dir(dir.path())
+        .arg("run")
+        .arg("-mod=mod")
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,320 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils;
+use serde::Deserialize;
+use serde_generate::{
+    haskell, BincodeOptions, Case, CodeGeneratorConfig, Encoding, NamingConventions,
+    SourceGenerator, SourceInstaller, TrailingBytes,
+};
+use serde_reflection::{Tracer, TracerConfig};
+use std::{collections::BTreeMap, process::Command};
+use tempfile::{tempdir, TempDir};
+
+/// Generate `Testing.hs` then type-check it together with the runtime.
+fn test_that_haskell_code_compiles_with_config(
+    config: &CodeGeneratorConfig,
+) -> (TempDir, std::path::PathBuf) {
+    let registry = test_utils::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let installer = haskell::Installer::new(dir.path().to_path_buf());
+    installer.install_module(config, &registry).unwrap();
+
+    let path = dir.path().join("Testing.hs");
+    let status = Command::new("ghc")
+        .arg("-Wall")
+        .arg("-fno-code")
+        .arg("-iruntime/haskell")
+        .arg(format!("-i{}", dir.path().display()))
+        .arg("-outputdir")
+        .arg(dir.path())
+        .arg(&path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    (dir, path)
+}
+
+#[test]
+fn test_that_haskell_code_compiles() {
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    test_that_haskell_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_haskell_code_compiles_without_serialization() {
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_serialization(false);
+    let (_dir, path) = test_that_haskell_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(!content.contains("import qualified Serde\n"));
+    assert!(!content.contains("instance Serde.Serialize"));
+}
+
+#[test]
+fn test_that_haskell_code_compiles_with_bcs() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bcs]);
+    test_that_haskell_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_haskell_code_compiles_with_bincode() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bincode]);
+    test_that_haskell_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_haskell_code_compiles_with_bincode_options() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bincode])
+        .with_bincode_options(
+            BincodeOptions::standard()
+                .with_limit(Some(100))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let (_dir, path) = test_that_haskell_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains("import qualified Bincode\n"));
+    assert!(content.contains("Bincode.varintEncoding = Prelude.True,"));
+    assert!(content.contains("Bincode.limit = Prelude.Just 100,"));
+    assert!(content.contains("bincodeDeserialize = Bincode.deserializeWith bincodeOptions"));
+}
+
+#[test]
+fn test_that_haskell_code_compiles_with_comments() {
+    let comments = vec![
+        (
+            vec!["testing".to_string(), "SerdeData".to_string()],
+            "Some\ncomments".to_string(),
+        ),
+        (
+            vec![
+                "testing".to_string(),
+                "SerdeData".to_string(),
+                "UnitVariant".to_string(),
+            ],
+            "A variant".to_string(),
+        ),
+        (
+            vec!["testing".to_string(), "Struct".to_string(), "x".to_string()],
+            "A field".to_string(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comments(comments);
+
+    let (_dir, path) = test_that_haskell_code_compiles_with_config(&config);
+
+    // Comments were correctly generated.
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains(
+        r#"
+-- | Some
+-- comments
+data SerdeData
+"#
+    ));
+    assert!(content.contains(
+        r#"
+  | -- | A variant
+    SerdeData_UnitVariant
+"#
+    ));
+    assert!(content.contains(
+        r#"
+data Struct = Struct
+  { -- | A field
+    x :: Word.Word32
+  , y :: Word.Word64
+  }
+"#
+    ));
+}
+
+#[test]
+fn test_that_haskell_code_compiles_with_custom_code() {
+    let custom_code = vec![(
+        vec!["testing".to_string(), "SerdeData".to_string()],
+        "customFunction :: SerdeData -> Prelude.Int\ncustomFunction _ = 42".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_custom_code(custom_code);
+
+    let (_dir, path) = test_that_haskell_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains("customFunction _ = 42"));
+}
+
+#[test]
+fn test_that_haskell_code_compiles_with_naming_conventions() {
+    let naming = NamingConventions::default()
+        .with_fields(Case::LowerCamel)
+        .with_variants(Case::ScreamingSnake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+
+    let (_dir, path) = test_that_haskell_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains("  = SerdeData_PRIMITIVE_TYPES PrimitiveTypes\n"));
+    assert!(content.contains("  { fString :: Text.Text\n"));
+}
+
+#[test]
+fn test_haskell_code_with_external_definitions() {
+    let registry = test_utils::get_registry().unwrap();
+
+    // (wrongly) Declare Tree as external.
+    let mut definitions = BTreeMap::new();
+    definitions.insert("pkg.foo".to_string(), vec!["Tree".to_string()]);
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_external_definitions(definitions);
+    let generator = haskell::CodeGenerator::new(&config);
+    let files = generator.generate(&registry).unwrap();
+
+    let content = files.get("Testing.hs").unwrap();
+    assert!(content.contains("import qualified Pkg.Foo\n"));
+    assert!(content.contains("[Pkg.Foo.Tree]"));
+}
+
+#[test]
+fn test_haskell_code_escapes_keywords_in_field_names() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Foo {
+        data: u8,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let files = haskell::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("Testing.hs").unwrap();
+    assert!(content.contains("  { data_ :: Word.Word8\n"));
+    assert!(content.contains("  serialize (Foo data_') = "));
+}
+
+#[test]
+fn test_haskell_code_rejects_validation() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let configs = [
+        CodeGeneratorConfig::new("testing".to_string()).with_validation(true),
+        CodeGeneratorConfig::new("testing".to_string()).with_validate_on_serialize(true),
+    ];
+    for config in &configs {
+        let error = haskell::CodeGenerator::new(config)
+            .generate(&registry)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Haskell does not support validation");
+    }
+}
+
+#[test]
+fn test_haskell_code_rejects_reserved_words() {
+    #[derive(Deserialize)]
+    #[allow(dead_code, non_snake_case)]
+    struct Foo {
+        Data: u8,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let naming = NamingConventions::default().with_fields(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = haskell::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Haskell name `data` of Foo.Data is a reserved word"
+    );
+}
+
+#[test]
+fn test_haskell_code_rejects_lowercase_type_names() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let naming = NamingConventions::default().with_types(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = haskell::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Haskell does not support names of types that do not start with an uppercase letter (in choice)"
+    );
+}
+
+#[test]
+fn test_haskell_code_rejects_clashing_constructors() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    enum Foo {
+        Bar,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code, non_camel_case_types)]
+    struct Foo_Bar;
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    tracer.trace_simple_type::<Foo_Bar>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let error = haskell::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Haskell does not support constructors with the same name (in Foo_Bar)"
+    );
+}
+
+#[test]
+fn test_haskell_code_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comparisons(true);
+    let files = haskell::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("Testing.hs").unwrap();
+    assert!(content.starts_with("{-# LANGUAGE DeriveGeneric #-}\n"));
+    assert!(content.contains("import qualified Data.Hashable as Hashable\n"));
+    assert!(content.contains("import Comparisons ()\n"));
+    assert!(content.contains("deriving (Prelude.Eq, Prelude.Ord, Prelude.Show, Generics.Generic)"));
+    assert!(content.contains("instance Hashable.Hashable OrderedData\n"));
+    assert!(content.contains("instance Hashable.Hashable Label\n"));
+}
+
+#[test]
+fn test_haskell_installer_rejects_unsupported_runtimes() {
+    let dir = tempdir().unwrap();
+    let installer = haskell::Installer::new(dir.path().to_path_buf());
+    installer.install_serde_runtime().unwrap();
+    installer.install_bcs_runtime().unwrap();
+    assert!(dir.path().join("Serde.hs").exists());
+    assert!(dir.path().join("Comparisons.hs").exists());
+    assert!(dir.path().join("Bcs.hs").exists());
+
+    let error = installer.install_postcard_runtime().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Haskell does not support the postcard encoding"
+    );
+}
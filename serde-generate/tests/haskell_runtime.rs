@@ -0,0 +1,405 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils;
+use crate::test_utils::{Choice, Runtime, Test};
+use serde_generate::{
+    haskell, BincodeOptions, CodeGeneratorConfig, SourceInstaller, TrailingBytes,
+};
+use std::{fs::File, io::Write, path::Path, process::Command};
+use tempfile::tempdir;
+
+/// Compile `Main.hs` with the runtime and the generated code, then run it.
+fn compile_and_run_haskell_main(dir: &Path) {
+    let status = Command::new("ghc")
+        .arg("-iruntime/haskell")
+        .arg(format!("-i{}", dir.display()))
+        .arg("-outputdir")
+        .arg(dir)
+        .arg("-o")
+        .arg(dir.join("main"))
+        .arg(dir.join("Main.hs"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new(dir.join("main")).status().unwrap();
+    assert!(status.success());
+}
+
+/// The Haskell module of the runtime of the given encoding.
+fn runtime_module(runtime: Runtime) -> &'static str {
+    match runtime {
+        Runtime::Bcs => "Bcs",
+        Runtime::Bincode | Runtime::BincodeVarintBigEndian => "Bincode",
+        _ => panic!("unsupported runtime"),
+    }
+}
+
+/// The Haskell functions serializing and deserializing values in the given encoding.
+fn runtime_functions(runtime: Runtime) -> (String, String) {
+    match runtime {
+        // Non-default options are provided by the generated module.
+        Runtime::BincodeVarintBigEndian => (
+            "bincodeSerialize".to_string(),
+            "bincodeDeserialize".to_string(),
+        ),
+        _ => (
+            format!("{}.serialize", runtime_module(runtime)),
+            format!("{}.deserialize", runtime_module(runtime)),
+        ),
+    }
+}
+
+fn quote_bytes(bytes: &[u8]) -> String {
+    format!(
+        "ByteString.pack [{}]",
+        bytes
+            .iter()
+            .map(|x| format!("{}", x))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+#[test]
+fn test_haskell_bcs_runtime_on_simple_data() {
+    test_haskell_runtime_on_simple_data(Runtime::Bcs);
+}
+
+#[test]
+fn test_haskell_bincode_runtime_on_simple_data() {
+    test_haskell_runtime_on_simple_data(Runtime::Bincode);
+}
+
+#[test]
+fn test_haskell_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_haskell_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+fn test_haskell_runtime_on_simple_data(runtime: Runtime) {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let installer = haskell::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    let reference = runtime.serialize(&Test {
+        a: vec![4, 6],
+        b: (-3, 5),
+        c: Choice::C { x: 7 },
+    });
+
+    let mut source = File::create(dir.path().join("Main.hs")).unwrap();
+    writeln!(
+        source,
+        r#"module Main (main) where
+
+import qualified {0}
+import Control.Monad (unless)
+import qualified Data.ByteString as ByteString
+import Data.Either (isLeft)
+import qualified Serde
+import Testing
+
+main :: IO ()
+main = do
+  let input = {1}
+      expected = Test [4, 6] (-3, 5) (Choice_C 7)
+  case {3} input of
+    Left error' -> fail (show error')
+    Right value -> do
+      unless (value == expected) (fail "unexpected value")
+      unless ({2} value == Right input) (fail "unexpected output")
+
+  let input2 = ByteString.snoc input 1
+  unless (isLeft ({3} input2 :: Either Serde.DeserializationError Test)) $
+    fail "trailing bytes should be rejected"
+"#,
+        runtime_module(runtime),
+        quote_bytes(&reference),
+        runtime_functions(runtime).0,
+        runtime_functions(runtime).1,
+    )
+    .unwrap();
+
+    compile_and_run_haskell_main(dir.path());
+}
+
+#[test]
+fn test_haskell_bcs_runtime_on_open_enum() {
+    let registry = test_utils::get_registry_with_open_enum();
+    let dir = tempdir().unwrap();
+
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Runtime::Bcs.into()]);
+    let installer = haskell::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    // The second event has a tag unknown to the generated code.
+    let reference = test_utils::get_open_enum_log(Runtime::Bcs);
+
+    let mut source = File::create(dir.path().join("Main.hs")).unwrap();
+    writeln!(
+        source,
+        r#"module Main (main) where
+
+import qualified Bcs
+import Control.Monad (unless)
+import qualified Data.ByteString as ByteString
+import qualified Data.Map.Strict as Map
+import qualified Data.Text as Text
+import qualified Serde
+import Testing
+
+main :: IO ()
+main = do
+  let input = {0}
+      decodeClick = Bcs.deserialize :: ByteString.ByteString -> Either Serde.DeserializationError Click
+      handlers = registerEventHandler (Text.pack "Click") decodeClick Map.empty
+  unless (eventTags == [Text.pack "Click"]) (fail "unexpected tags")
+  case Bcs.deserialize input of
+    Left error' -> fail (show error')
+    Right (Log [first, second]) -> do
+      case handleEvent handlers first of
+        Just (Right click) -> unless (click == Click 7) (fail "unexpected click")
+        _ -> fail "failed to handle the first event"
+      case handleEvent handlers second of
+        Nothing -> pure ()
+        Just _ -> fail "failed to skip the second event"
+      unless (Bcs.serialize (Log [first, second]) == Right input) (fail "unexpected output")
+    Right _ -> fail "unexpected events"
+"#,
+        quote_bytes(&reference),
+    )
+    .unwrap();
+
+    compile_and_run_haskell_main(dir.path());
+}
+
+#[test]
+fn test_haskell_bcs_runtime_on_supported_types() {
+    test_haskell_runtime_on_supported_types(Runtime::Bcs);
+}
+
+#[test]
+fn test_haskell_bincode_runtime_on_supported_types() {
+    test_haskell_runtime_on_supported_types(Runtime::Bincode);
+}
+
+#[test]
+fn test_haskell_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_haskell_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+fn test_haskell_runtime_on_supported_types(runtime: Runtime) {
+    let registry = runtime.get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let installer = haskell::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    let positive_encodings: Vec<_> = runtime
+        .get_positive_samples_quick()
+        .iter()
+        .map(|bytes| quote_bytes(bytes))
+        .collect();
+
+    let negative_encodings: Vec<_> = runtime
+        .get_negative_samples()
+        .iter()
+        .map(|bytes| quote_bytes(bytes))
+        .collect();
+
+    let mut source = File::create(dir.path().join("Main.hs")).unwrap();
+    writeln!(
+        source,
+        r#"module Main (main) where
+
+import qualified {0}
+import Control.Monad (forM_, unless)
+import Data.Bits (xor)
+import qualified Data.ByteString as ByteString
+import qualified Serde
+import Testing
+
+positiveInputs :: [ByteString.ByteString]
+positiveInputs =
+  [ {1}
+  ]
+
+negativeInputs :: [ByteString.ByteString]
+negativeInputs =
+  [ {2}
+  ]
+
+deserialize :: ByteString.ByteString -> Either Serde.DeserializationError SerdeData
+deserialize = {4}
+
+main :: IO ()
+main = do
+  forM_ positiveInputs $ \input ->
+    case deserialize input of
+      Left error' -> fail ("Failed to deserialize " ++ show (ByteString.unpack input) ++ ": " ++ show error')
+      Right value -> do
+        unless ({3} value == Right input) $
+          fail ("Failed to re-serialize " ++ show (ByteString.unpack input))
+
+        -- Test simple mutations of the input.
+        forM_ [0 .. ByteString.length input - 1] $ \i -> do
+          let byte = ByteString.index input i `xor` 0x80
+              input2 = ByteString.take i input <> ByteString.cons byte (ByteString.drop (i + 1) input)
+          case deserialize input2 of
+            Right value2
+              | value2 == value ->
+                  fail ("Mutation of " ++ show (ByteString.unpack input) ++ " at " ++ show i ++ " gave the same value")
+            _ -> pure ()
+
+  forM_ negativeInputs $ \input ->
+    case deserialize input of
+      Right _ -> fail ("Input should fail to deserialize: " ++ show (ByteString.unpack input))
+      Left _ -> pure ()
+"#,
+        runtime_module(runtime),
+        positive_encodings.join("\n  , "),
+        negative_encodings.join("\n  , "),
+        runtime_functions(runtime).0,
+        runtime_functions(runtime).1,
+    )
+    .unwrap();
+
+    compile_and_run_haskell_main(dir.path());
+}
+
+#[test]
+fn test_haskell_runtime_with_bincode_limit_and_trailing_bytes() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let installer = haskell::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    let mut source = File::create(dir.path().join("Main.hs")).unwrap();
+    writeln!(
+        source,
+        r#"module Main (main) where
+
+import Control.Monad (unless)
+import qualified Data.ByteString as ByteString
+import Data.List (isInfixOf)
+import qualified Serde
+import Testing
+
+main :: IO ()
+main = do
+  let input = {0}
+  case bincodeDeserialize (ByteString.snoc input 0) of
+    Left error' -> fail (show error')
+    Right value -> unless (bincodeSerialize (value :: Test) == Right input) (fail "unexpected output")
+
+  case bincodeDeserialize ({1}) :: Either Serde.DeserializationError Test of
+    Left (Serde.DeserializationError message) ->
+      unless ("limit" `isInfixOf` message) (fail ("unexpected error: " ++ message))
+    Right _ -> fail "the size limit should be enforced"
+"#,
+        quote_bytes(&reference),
+        quote_bytes(&too_large),
+    )
+    .unwrap();
+
+    compile_and_run_haskell_main(dir.path());
+}
+
+#[test]
+fn test_haskell_bcs_runtime_autotest() {
+    let dir = tempdir().unwrap();
+    let status = Command::new("ghc")
+        .arg("-iruntime/haskell")
+        .arg("-outputdir")
+        .arg(dir.path())
+        .arg("-o")
+        .arg(dir.path().join("test_bcs"))
+        .arg("runtime/haskell/TestBcs.hs")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new(dir.path().join("test_bcs")).status().unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_haskell_runtime_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bcs.into()])
+        .with_comparisons(true);
+    let installer = haskell::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    // Values are sorted in the order of Rust.
+    let encodings = test_utils::get_ordered_values()
+        .iter()
+        .map(|value| quote_bytes(&Runtime::Bcs.serialize(value)))
+        .collect::<Vec<_>>()
+        .join(",\n    ");
+
+    let mut source = File::create(dir.path().join("Main.hs")).unwrap();
+    writeln!(
+        source,
+        r#"module Main (main) where
+
+import qualified Bcs
+import Control.Monad (forM_, unless)
+import qualified Data.ByteString as ByteString
+import Data.Hashable (hash)
+import qualified Data.List as List
+import qualified Data.Set as Set
+import Testing
+
+main :: IO ()
+main = do
+  let inputs =
+        [ {0}
+        ]
+  values <- mapM (either (fail . show) pure . Bcs.deserialize) inputs :: IO [OrderedData]
+  forM_ (zip [0 :: Int ..] values) $ \(i, x) -> do
+    forM_ (zip [0 :: Int ..] values) $ \(j, y) -> do
+      unless (compare x y == compare i j) (fail ("unexpected order: " ++ show (i, j)))
+      unless ((x == y) == (i == j)) (fail ("unexpected equality: " ++ show (i, j)))
+    copy <- either (fail . show) pure (Bcs.deserialize (inputs !! i))
+    unless (hash x == hash (copy :: OrderedData)) (fail ("unexpected hash: " ++ show i))
+  unless (List.sort (reverse values) == values) (fail "unexpected sort")
+  unless (Set.size (Set.fromList values) == length values) (fail "unexpected set")
+"#,
+        encodings
+    )
+    .unwrap();
+
+    compile_and_run_haskell_main(dir.path());
+}
@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use serde_generate::{java, CodeGeneratorConfig, Encoding};
+use serde::Deserialize;
+use serde_generate::{
+    java, Case, CodeGeneratorConfig, Encoding, ModuleMap, NamingConventions, SourceGenerator,
+};
+use serde_reflection::{Registry, Tracer, TracerConfig};
 use std::{collections::BTreeMap, process::Command};
 use tempfile::{tempdir, TempDir};
 
@@ -10,11 +14,18 @@ fn test_that_java_code_compiles_with_config(
     config: &CodeGeneratorConfig,
 ) -> (TempDir, std::path::PathBuf) {
     let registry = test_utils::get_registry().unwrap();
+    test_that_java_code_compiles_with_config_and_registry(config, &registry)
+}
+
+fn test_that_java_code_compiles_with_config_and_registry(
+    config: &CodeGeneratorConfig,
+    registry: &Registry,
+) -> (TempDir, std::path::PathBuf) {
     let dir = tempdir().unwrap();
 
     let generator = java::CodeGenerator::new(config);
     generator
-        .write_source_files(dir.path().to_path_buf(), &registry)
+        .write_source_files(dir.path().to_path_buf(), registry)
         .unwrap();
 
     let paths = std::iter::empty()
@@ -62,6 +73,41 @@ fn test_that_java_code_compiles_with_bincode() {
     test_that_java_code_compiles_with_config(&config);
 }
 
+#[test]
+fn test_that_java_code_compiles_with_validation() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_validate_on_serialize(true);
+    test_that_java_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_java_validation_can_be_disabled_again() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_validate_on_serialize(true)
+        .with_validate_on_serialize(false);
+    let (_dir, path) = test_that_java_code_compiles_with_config(&config);
+    let content = std::fs::read_to_string(path.join("SerdeData.java")).unwrap();
+    assert!(!content.contains("validate()"));
+}
+
+#[test]
+fn test_that_java_code_with_open_enums_compiles() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_comparisons(true)
+        .with_validation(true);
+    let (_dir, path) = test_that_java_code_compiles_with_config_and_registry(
+        &config,
+        &test_utils::get_registry_with_open_enum(),
+    );
+    // Open enums are decoded as a struct with the tag and the content as bytes.
+    let content = std::fs::read_to_string(path.join("Event.java")).unwrap();
+    assert!(content.contains("public final String tag;"));
+    assert!(content.contains("public final com.novi.serde.Bytes content;"));
+}
+
 #[test]
 fn test_that_java_code_compiles_with_comments() {
     let comments = vec![(
@@ -123,3 +169,166 @@ fn test_that_java_code_compiles_with_custom_code() {
     let content = std::fs::read_to_string(path.join("SerdeData.java")).unwrap();
     assert!(content.contains("me()"));
 }
+
+#[test]
+fn test_java_source_files_in_memory() {
+    let registry = test_utils::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("test.types".to_string());
+    let generator = java::CodeGenerator::new(&config);
+    let files = generator.generate(&registry).unwrap();
+    assert_eq!(files.len(), registry.len() + 1);
+    assert!(files
+        .get("test/types/TraitHelpers.java")
+        .unwrap()
+        .starts_with("package test.types;"));
+
+    let dir = tempdir().unwrap();
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+    for (path, content) in files.iter() {
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(path)).unwrap(),
+            content
+        );
+    }
+}
+
+#[test]
+fn test_java_code_rejects_c_style_enums() {
+    let registry = test_utils::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_c_style_enums(true);
+    let error = java::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Java does not support c-style enums");
+}
+
+#[test]
+fn test_that_java_code_compiles_with_naming_conventions() {
+    let comments = vec![(
+        vec![
+            "testing".to_string(),
+            "SerdeData".to_string(),
+            "PrimitiveTypes".to_string(),
+        ],
+        "Some\ncomments".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    let naming = NamingConventions::default()
+        .with_fields(Case::LowerCamel)
+        .with_variants(Case::ScreamingSnake);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_comments(comments)
+        .with_naming_conventions(naming);
+
+    let (_dir, path) = test_that_java_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path.join("SerdeData.java")).unwrap();
+    assert!(content.contains("public static final class PRIMITIVE_TYPES extends SerdeData {"));
+    // Comments follow renamed definitions.
+    assert!(content.contains("/**\n     * Some\n     * comments\n     */"));
+    let content = std::fs::read_to_string(path.join("OtherTypes.java")).unwrap();
+    assert!(content.contains("public final String fString;"));
+}
+
+#[test]
+fn test_java_code_rejects_name_conflicts() {
+    #[derive(Deserialize)]
+    #[allow(dead_code, non_snake_case)]
+    struct Foo {
+        foo_bar: u8,
+        fooBar: u8,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let naming = NamingConventions::default().with_fields(Case::LowerCamel);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = java::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Java name `fooBar` of Foo.fooBar clashes with Foo.foo_bar"
+    );
+
+    let naming = NamingConventions::default().with_fields(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = java::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Java name `foo_bar` of Foo.fooBar clashes with Foo.foo_bar"
+    );
+}
+
+#[test]
+fn test_java_code_rejects_reserved_words() {
+    #[derive(Deserialize)]
+    #[allow(dead_code, non_snake_case)]
+    struct Foo {
+        Class: u8,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let naming = NamingConventions::default().with_fields(Case::LowerCamel);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = java::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Java name `class` of Foo.Class is a reserved word"
+    );
+
+    let naming = naming.with_files(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = java::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Java does not support file naming conventions"
+    );
+}
+
+#[test]
+fn test_that_java_code_compiles_with_modules() {
+    let registry = test_utils::get_registry_with_modules();
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs, Encoding::Bincode]);
+    let modules = ModuleMap::from_qualified_names(&registry, ".");
+    let files = modules
+        .generate(&java::CAPABILITIES, &config, &registry, |config| {
+            Box::new(java::CodeGenerator::new(config))
+        })
+        .unwrap();
+    let dir = tempdir().unwrap();
+    files.write_to(dir.path()).unwrap();
+
+    let content = std::fs::read_to_string(dir.path().join("a/Foo.java")).unwrap();
+    assert!(content.contains("public final java.util.Optional<b.Bar> bar;"));
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/novi/bcs").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/novi/bincode").unwrap())
+        .map(|e| e.unwrap().path())
+        .chain(files.iter().map(|(path, _)| dir.path().join(path)));
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -1,8 +1,12 @@
 // Copyright (c) Facebook, Inc. and its affiliates
 // SPDX-License-Identifier: MIT OR Apache-2.0
 use crate::test_utils;
-use crate::test_utils::{Choice, Runtime, Test};
-use serde_generate::{java, CodeGeneratorConfig};
+use crate::test_utils::{json_data, Choice, Event, Runtime, Test, Timestamp};
+use maplit::btreemap;
+use serde_generate::{
+    java, BincodeOptions, Case, CodeGeneratorConfig, Encoding, NamingConventions, NativeType,
+    TrailingBytes,
+};
 use std::{fs::File, io::Write, process::Command};
 use tempfile::tempdir;
 
@@ -16,12 +20,33 @@ fn test_java_bincode_runtime_on_simple_data() {
     test_java_runtime_on_simple_data(Runtime::Bincode);
 }
 
+#[test]
+fn test_java_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_java_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_java_postcard_runtime_on_simple_data() {
+    test_java_runtime_on_simple_data(Runtime::Postcard);
+}
+
+#[test]
+fn test_java_borsh_runtime_on_simple_data() {
+    test_java_runtime_on_simple_data(Runtime::Borsh);
+}
+
+#[test]
+fn test_java_scale_runtime_on_simple_data() {
+    test_java_runtime_on_simple_data(Runtime::Scale);
+}
+
 fn test_java_runtime_on_simple_data(runtime: Runtime) {
     let registry = test_utils::get_simple_registry().unwrap();
     let dir = tempdir().unwrap();
 
-    let config =
-        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
     let generator = java::CodeGenerator::new(&config);
     generator
         .write_source_files(dir.path().to_path_buf(), &registry)
@@ -116,6 +141,196 @@ public class Main {{
     assert!(status.success());
 }
 
+#[test]
+fn test_java_runtime_with_bincode_limit_and_trailing_bytes() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let generator = java::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    let to_java_bytes = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .map(|x| format!("{}", *x as i8))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut source = File::create(dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import com.novi.serde.DeserializationError;
+import testing.Test;
+
+public class Main {{
+    public static void main(String[] args) throws java.lang.Exception {{
+        byte[] input = new byte[] {{{0}, 0}};
+        Test value = Test.bincodeDeserialize(input);
+        assert java.util.Arrays.equals(value.bincodeSerialize(), new byte[] {{{0}}});
+
+        byte[] too_large = new byte[] {{{1}}};
+        try {{
+            Test.bincodeDeserialize(too_large);
+        }} catch (DeserializationError e) {{
+            assert e.getMessage().contains("limit");
+            return;
+        }}
+        assert false;
+    }}
+}}
+"#,
+        to_java_bytes(&reference),
+        to_java_bytes(&too_large),
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/novi/bincode").unwrap())
+        .chain(std::fs::read_dir(dir.path().join("testing")).unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_java_runtime_with_naming_conventions() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let naming = NamingConventions::default()
+        .with_fields(Case::ScreamingSnake)
+        .with_variants(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_naming_conventions(naming);
+    let generator = java::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    let reference = Runtime::Bcs.serialize(&Test {
+        a: vec![4, 6],
+        b: (-3, 5),
+        c: Choice::C { x: 7 },
+    });
+
+    let mut source = File::create(dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import java.util.List;
+import java.util.Arrays;
+import com.novi.serde.Unsigned;
+import com.novi.serde.Tuple2;
+import testing.Choice;
+import testing.Test;
+
+public class Main {{
+    public static void main(String[] args) throws java.lang.Exception {{
+        byte[] input = new byte[] {{{0}}};
+
+        Test value = Test.bcsDeserialize(input);
+
+        List<@Unsigned Integer> a = Arrays.asList(4, 6);
+        assert value.A.equals(a);
+        assert ((Choice.c) value.C).X == 7;
+
+        Tuple2<Long, @Unsigned Long> b = new Tuple2<>(Long.valueOf(-3), Long.valueOf(5));
+        Test value2 = new Test(a, b, new Choice.c(Byte.valueOf((byte) 7)));
+        assert java.util.Arrays.equals(input, value2.bcsSerialize());
+    }}
+}}
+"#,
+        reference
+            .iter()
+            .map(|x| format!("{}", *x as i8))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/novi/bcs").unwrap())
+        .chain(std::fs::read_dir(dir.path().join("testing")).unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
 #[test]
 fn test_java_bcs_runtime_on_supported_types() {
     test_java_runtime_on_supported_types(Runtime::Bcs);
@@ -126,6 +341,26 @@ fn test_java_bincode_runtime_on_supported_types() {
     test_java_runtime_on_supported_types(Runtime::Bincode);
 }
 
+#[test]
+fn test_java_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_java_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_java_postcard_runtime_on_supported_types() {
+    test_java_runtime_on_supported_types(Runtime::Postcard);
+}
+
+#[test]
+fn test_java_borsh_runtime_on_supported_types() {
+    test_java_runtime_on_supported_types(Runtime::Borsh);
+}
+
+#[test]
+fn test_java_scale_runtime_on_supported_types() {
+    test_java_runtime_on_supported_types(Runtime::Scale);
+}
+
 fn quote_bytes(bytes: &[u8]) -> String {
     format!(
         "{{{}}}",
@@ -138,11 +373,12 @@ fn quote_bytes(bytes: &[u8]) -> String {
 }
 
 fn test_java_runtime_on_supported_types(runtime: Runtime) {
-    let registry = test_utils::get_registry().unwrap();
+    let registry = runtime.get_registry().unwrap();
     let dir = tempdir().unwrap();
 
-    let config =
-        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
     let generator = java::CodeGenerator::new(&config);
     generator
         .write_source_files(dir.path().to_path_buf(), &registry)
@@ -281,3 +517,416 @@ fn test_java_bcs_runtime_autotest() {
         .unwrap();
     assert!(status.success());
 }
+
+#[test]
+fn test_java_runtime_with_native_types() {
+    let registry = test_utils::get_registry_with_native_types().unwrap();
+    let dir = tempdir().unwrap();
+
+    let native_types = btreemap! {
+        "Timestamp".to_string() => NativeType::new(
+            "java.time.Instant".to_string(),
+            "codecs.Codecs.serializeInstant".to_string(),
+            "codecs.Codecs.deserializeInstant".to_string(),
+        ),
+    };
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_native_types(native_types);
+    let generator = java::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+    assert!(!dir.path().join("testing/Timestamp.java").exists());
+
+    std::fs::create_dir(dir.path().join("codecs")).unwrap();
+    let mut source = File::create(dir.path().join("codecs/Codecs.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+package codecs;
+
+import com.novi.serde.DeserializationError;
+import com.novi.serde.Deserializer;
+import com.novi.serde.SerializationError;
+import com.novi.serde.Serializer;
+
+public final class Codecs {{
+    public static void serializeInstant(java.time.Instant value, Serializer serializer) throws SerializationError {{
+        serializer.serialize_u64(value.getEpochSecond());
+    }}
+
+    public static java.time.Instant deserializeInstant(Deserializer deserializer) throws DeserializationError {{
+        return java.time.Instant.ofEpochSecond(deserializer.deserialize_u64());
+    }}
+}}
+"#
+    )
+    .unwrap();
+
+    let reference = Runtime::Bcs.serialize(&Event {
+        name: "start".to_string(),
+        at: Timestamp(1_600_000_000),
+        history: vec![None, Some(Timestamp(42))],
+    });
+
+    let mut source = File::create(dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import java.time.Instant;
+import testing.Event;
+
+public class Main {{
+    public static void main(String[] args) throws java.lang.Exception {{
+        byte[] input = new byte[] {{{0}}};
+
+        Event value = Event.bcsDeserialize(input);
+        assert value.at.equals(Instant.ofEpochSecond(1600000000));
+        assert value.history.get(1).get().equals(Instant.ofEpochSecond(42));
+        assert java.util.Arrays.equals(input, value.bcsSerialize());
+    }}
+}}
+"#,
+        reference
+            .iter()
+            .map(|x| format!("{}", *x as i8))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/novi/bcs").unwrap())
+        .chain(std::fs::read_dir(dir.path().join("codecs")).unwrap())
+        .chain(std::fs::read_dir(dir.path().join("testing")).unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_java_runtime_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_comparisons(true);
+    let generator = java::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    // Values are sorted in the order of Rust.
+    let encodings = test_utils::get_ordered_values()
+        .iter()
+        .map(|value| quote_bytes(&Runtime::Bcs.serialize(value)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut source = File::create(dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import java.util.HashSet;
+import java.util.TreeSet;
+import testing.OrderedData;
+
+public class Main {{
+    public static void main(String[] args) throws java.lang.Exception {{
+        byte[][] inputs = new byte[][] {{{0}}};
+        OrderedData[] values = new OrderedData[inputs.length];
+        for (int i = 0; i < inputs.length; i++) {{
+            values[i] = OrderedData.bcsDeserialize(inputs[i]);
+        }}
+        for (int i = 0; i < values.length; i++) {{
+            for (int j = 0; j < values.length; j++) {{
+                int expected = Integer.compare(i, j);
+                assert Integer.signum(values[i].compareTo(values[j])) == expected : i + " vs " + j;
+                assert values[i].equals(values[j]) == (i == j);
+            }}
+            OrderedData copy = OrderedData.bcsDeserialize(inputs[i]);
+            assert values[i].hashCode() == copy.hashCode();
+        }}
+        assert new TreeSet<>(java.util.Arrays.asList(values)).size() == values.length;
+        assert new HashSet<>(java.util.Arrays.asList(values)).size() == values.length;
+    }}
+}}
+"#,
+        encodings
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/novi/bcs").unwrap())
+        .chain(std::fs::read_dir(dir.path().join("testing")).unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_java_runtime_with_validation() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_validate_on_serialize(true);
+    let generator = java::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    let mut source = File::create(dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import java.util.Arrays;
+import com.novi.serde.Tuple2;
+import testing.Label;
+import testing.OrderedData;
+
+public class Main {{
+    static OrderedData pair(Short... array) {{
+        return new OrderedData.Pair(new Tuple2<>(new Label("x"), Arrays.asList(array)));
+    }}
+
+    public static void main(String[] args) throws java.lang.Exception {{
+        OrderedData value = pair((short) 1, (short) 2);
+        value.validate();
+        assert value.bcsSerialize().length > 0;
+
+        OrderedData invalid = pair((short) 1);
+        try {{
+            invalid.validate();
+            assert false;
+        }} catch (IllegalArgumentException e) {{
+            // All good
+        }}
+        try {{
+            invalid.bcsSerialize();
+            assert false;
+        }} catch (IllegalArgumentException e) {{
+            // All good
+        }}
+    }}
+}}
+"#
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/novi/bcs").unwrap())
+        .chain(std::fs::read_dir(dir.path().join("testing")).unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn quote_string(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            ' '..='~' if c != '"' && c != '\\' => result.push(c),
+            '\0'..='\x7f' => result.push_str(&format!("\\{:03o}", c as u32)),
+            _ => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    result.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[test]
+fn test_java_json_runtime_on_supported_types() {
+    let registry = json_data::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Json]);
+    let generator = java::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    let positive_inputs: Vec<_> = json_data::get_positive_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect();
+    let alternate_inputs: Vec<_> = json_data::get_alternate_samples()
+        .iter()
+        .map(|(input, output)| format!("{{{}, {}}}", quote_string(input), quote_string(output)))
+        .collect();
+    let negative_inputs: Vec<_> = json_data::get_negative_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect();
+
+    let mut source = File::create(dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import com.novi.serde.DeserializationError;
+import testing.SerdeData;
+
+public class Main {{
+    static final String[] positive_inputs = new String[] {{{0}}};
+    static final String[][] alternate_inputs = new String[][] {{{1}}};
+    static final String[] negative_inputs = new String[] {{{2}}};
+
+    public static void main(String[] args) throws java.lang.Exception {{
+        for (String input : positive_inputs) {{
+            SerdeData value = SerdeData.jsonDeserialize(input);
+            String output = value.jsonSerialize();
+            if (!input.equals(output)) {{
+                throw new Exception("Unexpected output: " + output);
+            }}
+
+            // Test self-equality for the Serde value.
+            assert value.equals(SerdeData.jsonDeserialize(input));
+        }}
+
+        for (String[] inputs : alternate_inputs) {{
+            String output = SerdeData.jsonDeserialize(inputs[0]).jsonSerialize();
+            if (!inputs[1].equals(output)) {{
+                throw new Exception("Unexpected output: " + output);
+            }}
+        }}
+
+        for (String input : negative_inputs) {{
+            try {{
+                SerdeData.jsonDeserialize(input);
+                throw new Exception("Input should fail to deserialize: " + input);
+            }} catch (DeserializationError e) {{
+                // All good
+            }}
+        }}
+    }}
+}}
+"#,
+        positive_inputs.join(", "),
+        alternate_inputs.join(", "),
+        negative_inputs.join(", "),
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/novi/json").unwrap())
+        .chain(std::fs::read_dir(dir.path().join("testing")).unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
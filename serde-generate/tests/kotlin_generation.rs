@@ -0,0 +1,283 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils;
+use serde::Deserialize;
+use serde_generate::{
+    kotlin, BincodeOptions, Case, CodeGeneratorConfig, Encoding, NamingConventions,
+    SourceGenerator, SourceInstaller, TrailingBytes,
+};
+use serde_reflection::{Tracer, TracerConfig};
+use std::{collections::BTreeMap, process::Command};
+use tempfile::{tempdir, TempDir};
+
+fn test_that_kotlin_code_compiles_with_config(
+    config: &CodeGeneratorConfig,
+) -> (TempDir, std::path::PathBuf) {
+    let registry = test_utils::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let generator = kotlin::CodeGenerator::new(config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/kotlin/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/kotlin/com/novi/bincode").unwrap())
+        .chain(std::fs::read_dir("runtime/kotlin/com/novi/bcs").unwrap())
+        .chain(std::fs::read_dir(dir.path().join("testing")).unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("kotlinc")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let path = dir.path().join("testing");
+    (dir, path)
+}
+
+#[test]
+fn test_that_kotlin_code_compiles() {
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    test_that_kotlin_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_kotlin_code_compiles_without_serialization() {
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_serialization(false);
+    test_that_kotlin_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_kotlin_code_compiles_with_bcs() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bcs]);
+    test_that_kotlin_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_kotlin_code_compiles_with_bincode() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bincode]);
+    test_that_kotlin_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_kotlin_code_compiles_with_bincode_options() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bincode])
+        .with_bincode_options(
+            BincodeOptions::standard()
+                .with_limit(Some(100))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let (_dir, path) = test_that_kotlin_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path.join("SerdeData.kt")).unwrap();
+    assert!(content.contains("com.novi.bincode.BincodeSerializer(true, false)"));
+    assert!(content.contains("com.novi.bincode.BincodeDeserializer(input, true, false, 100L)"));
+    assert!(!content.contains("Some input bytes were not read"));
+}
+
+#[test]
+fn test_that_kotlin_code_compiles_with_validation() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_validate_on_serialize(true);
+    test_that_kotlin_code_compiles_with_config(&config);
+}
+
+#[test]
+fn test_that_kotlin_code_compiles_with_c_style_enums() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_c_style_enums(true);
+    let (_dir, path) = test_that_kotlin_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path.join("CStyleEnum.kt")).unwrap();
+    assert!(content.contains("enum class CStyleEnum {"));
+}
+
+#[test]
+fn test_that_kotlin_code_compiles_with_comments() {
+    let comments = vec![(
+        vec!["testing".to_string(), "SerdeData".to_string()],
+        "Some\ncomments".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comments(comments);
+
+    let (_dir, path) = test_that_kotlin_code_compiles_with_config(&config);
+
+    // Comment was correctly generated.
+    let content = std::fs::read_to_string(path.join("SerdeData.kt")).unwrap();
+    assert!(content.contains(
+        r#"
+/**
+ * Some
+ * comments
+ */
+"#
+    ));
+}
+
+#[test]
+fn test_kotlin_code_with_external_definitions() {
+    let registry = test_utils::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    // (wrongly) Declare TraitHelpers as external.
+    let mut definitions = BTreeMap::new();
+    definitions.insert("foo".to_string(), vec!["TraitHelpers".to_string()]);
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_external_definitions(definitions);
+    let generator = kotlin::CodeGenerator::new(&config);
+
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    // References were updated.
+    let content = std::fs::read_to_string(dir.path().join("testing/SerdeData.kt")).unwrap();
+    assert!(content.contains("foo.TraitHelpers."));
+}
+
+#[test]
+fn test_kotlin_source_files_in_memory() {
+    let registry = test_utils::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("test.types".to_string());
+    let generator = kotlin::CodeGenerator::new(&config);
+    let files = generator.generate(&registry).unwrap();
+    assert_eq!(files.len(), registry.len() + 1);
+    assert!(files
+        .get("test/types/TraitHelpers.kt")
+        .unwrap()
+        .starts_with("package test.types\n"));
+
+    // Idiomatic types are used instead of the boxes of the Java runtime.
+    let content = files.get("test/types/PrimitiveTypes.kt").unwrap();
+    assert!(content.contains("data class PrimitiveTypes("));
+    assert!(content.contains("val f_u64: ULong,"));
+    assert!(content.contains("val f_f32: Float?,"));
+    let content = files.get("test/types/OtherTypes.kt").unwrap();
+    assert!(content.contains("val f_tuple: Pair<UByte, UShort>,"));
+    // The registry defines a type `List`.
+    assert!(content.contains("val f_seq: kotlin.collections.List<Struct>,"));
+    let content = files.get("test/types/SerdeData.kt").unwrap();
+    assert!(content.contains("sealed class SerdeData {"));
+    assert!(content.contains("data object UnitVariant : SerdeData() {"));
+    // Variant classes shadow the types of the registry with the same name.
+    assert!(content.contains("val value: test.types.PrimitiveTypes,"));
+
+    let dir = tempdir().unwrap();
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+    for (path, content) in files.iter() {
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(path)).unwrap(),
+            content
+        );
+    }
+}
+
+#[test]
+fn test_kotlin_source_files_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comparisons(true);
+    let files = kotlin::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("testing/OrderedData.kt").unwrap();
+    assert!(content.contains("sealed class OrderedData : Comparable<OrderedData> {"));
+    assert!(content
+        .contains("if (other !is Flag) return variantIndex().compareTo(other.variantIndex())"));
+    let content = files.get("testing/Label.kt").unwrap();
+    assert!(content.contains(") : Comparable<Label> {"));
+    assert!(content
+        .contains("result = com.novi.serde.Comparisons.compareStrings(this.value, other.value)"));
+    let content = files.get("testing/TraitHelpers.kt").unwrap();
+    assert!(content.contains(
+        "fun compare_map_str_to_u16(a: Map<String, UShort>, b: Map<String, UShort>): Int {"
+    ));
+}
+
+#[test]
+fn test_kotlin_code_rejects_nested_options() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Foo {
+        bar: Option<Option<u8>>,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let error = kotlin::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Kotlin does not support nested options (in Foo)"
+    );
+}
+
+#[test]
+fn test_kotlin_installer_rejects_unsupported_runtimes() {
+    let dir = tempdir().unwrap();
+    let installer = kotlin::Installer::new(dir.path().to_path_buf());
+    installer.install_bcs_runtime().unwrap();
+    assert!(dir.path().join("com/novi/bcs/BcsSerializer.kt").exists());
+
+    let error = installer.install_postcard_runtime().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Kotlin does not support the postcard encoding"
+    );
+}
+
+#[test]
+fn test_that_kotlin_code_compiles_with_naming_conventions() {
+    let naming = NamingConventions::default()
+        .with_fields(Case::LowerCamel)
+        .with_variants(Case::ScreamingSnake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+
+    let (_dir, path) = test_that_kotlin_code_compiles_with_config(&config);
+
+    let content = std::fs::read_to_string(path.join("SerdeData.kt")).unwrap();
+    assert!(content.contains("data class PRIMITIVE_TYPES("));
+    let content = std::fs::read_to_string(path.join("OtherTypes.kt")).unwrap();
+    assert!(content.contains("val fString: String,"));
+}
+
+#[test]
+fn test_kotlin_code_rejects_reserved_words() {
+    #[derive(Deserialize)]
+    #[allow(dead_code, non_snake_case)]
+    struct Foo {
+        Val: u8,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let naming = NamingConventions::default().with_fields(Case::LowerCamel);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = kotlin::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Kotlin name `val` of Foo.Val is a reserved word"
+    );
+}
@@ -0,0 +1,352 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+use crate::test_utils;
+use crate::test_utils::{Choice, Runtime, Test};
+use serde_generate::{kotlin, BincodeOptions, CodeGeneratorConfig, TrailingBytes};
+use std::{fs::File, io::Write, path::Path, process::Command};
+use tempfile::tempdir;
+
+/// Compile the generated code, the runtime of the encoding and `Main.kt`, then run `MainKt`.
+fn compile_and_run_kotlin_main(dir: &Path, runtime: Runtime) {
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/kotlin/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/kotlin/com/novi/".to_string() + runtime.name()).unwrap())
+        .chain(std::fs::read_dir(dir.join("testing")).unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("kotlinc")
+        .arg("-d")
+        .arg(dir)
+        .args(paths)
+        .arg(dir.join("Main.kt"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("kotlin")
+        .arg("-cp")
+        .arg(dir)
+        .arg("MainKt")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_kotlin_bcs_runtime_on_simple_data() {
+    test_kotlin_runtime_on_simple_data(Runtime::Bcs);
+}
+
+#[test]
+fn test_kotlin_bincode_runtime_on_simple_data() {
+    test_kotlin_runtime_on_simple_data(Runtime::Bincode);
+}
+
+#[test]
+fn test_kotlin_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_kotlin_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+fn test_kotlin_runtime_on_simple_data(runtime: Runtime) {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let generator = kotlin::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    let reference = runtime.serialize(&Test {
+        a: vec![4, 6],
+        b: (-3, 5),
+        c: Choice::C { x: 7 },
+    });
+
+    let mut source = File::create(dir.path().join("Main.kt")).unwrap();
+    writeln!(
+        source,
+        r#"
+import com.novi.serde.DeserializationError
+import testing.Choice
+import testing.Test
+
+fun main() {{
+    val input = byteArrayOf({0})
+
+    val value = Test.{1}Deserialize(input)
+
+    val value2 = Test(listOf(4u, 6u), Pair(-3L, 5uL), Choice.C(7u))
+    check(value == value2)
+
+    val output = value2.{1}Serialize()
+    check(input.contentEquals(output))
+
+    val input2 = byteArrayOf({0}, 1)
+    try {{
+        Test.{1}Deserialize(input2)
+    }} catch (e: DeserializationError) {{
+        return
+    }}
+    check(false)
+}}
+"#,
+        reference
+            .iter()
+            .map(|x| format!("{}", *x as i8))
+            .collect::<Vec<_>>()
+            .join(", "),
+        runtime.name(),
+    )
+    .unwrap();
+
+    compile_and_run_kotlin_main(dir.path(), runtime);
+}
+
+#[test]
+fn test_kotlin_bcs_runtime_on_supported_types() {
+    test_kotlin_runtime_on_supported_types(Runtime::Bcs);
+}
+
+#[test]
+fn test_kotlin_bincode_runtime_on_supported_types() {
+    test_kotlin_runtime_on_supported_types(Runtime::Bincode);
+}
+
+#[test]
+fn test_kotlin_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_kotlin_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+fn quote_bytes(bytes: &[u8]) -> String {
+    format!(
+        "byteArrayOf({})",
+        bytes
+            .iter()
+            .map(|x| format!("{}", *x as i8))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn test_kotlin_runtime_on_supported_types(runtime: Runtime) {
+    let registry = runtime.get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let generator = kotlin::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    let positive_encodings: Vec<_> = runtime
+        .get_positive_samples_quick()
+        .iter()
+        .map(|bytes| quote_bytes(bytes))
+        .collect();
+
+    let negative_encodings: Vec<_> = runtime
+        .get_negative_samples()
+        .iter()
+        .map(|bytes| quote_bytes(bytes))
+        .collect();
+
+    let mut source = File::create(dir.path().join("Main.kt")).unwrap();
+    writeln!(
+        source,
+        r#"
+import com.novi.serde.DeserializationError
+import testing.SerdeData
+
+val positiveInputs = listOf({0})
+val negativeInputs = listOf({1})
+
+fun main() {{
+    for (input in positiveInputs) {{
+        val value = SerdeData.{2}Deserialize(input)
+        val output = value.{2}Serialize()
+        check(input.contentEquals(output))
+
+        // Test self-equality for the Serde value.
+        val value2 = SerdeData.{2}Deserialize(input)
+        check(value == value2)
+
+        // Test simple mutations of the input.
+        for (i in input.indices) {{
+            val input2 = input.copyOf()
+            input2[i] = (input2[i].toInt() xor 0x80).toByte()
+            try {{
+                val value3 = SerdeData.{2}Deserialize(input2)
+                check(value3 != value)
+            }} catch (e: DeserializationError) {{
+                // All good
+            }}
+        }}
+    }}
+
+    for (input in negativeInputs) {{
+        try {{
+            SerdeData.{2}Deserialize(input)
+        }} catch (e: DeserializationError) {{
+            continue
+        }}
+        throw Exception("Input should fail to deserialize: " + input.map {{ it.toUByte() }})
+    }}
+}}
+"#,
+        positive_encodings.join(", "),
+        negative_encodings.join(", "),
+        runtime.name(),
+    )
+    .unwrap();
+
+    compile_and_run_kotlin_main(dir.path(), runtime);
+}
+
+#[test]
+fn test_kotlin_bcs_runtime_autotest() {
+    let dir = tempdir().unwrap();
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/kotlin/com/novi/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/kotlin/com/novi/bcs").unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("kotlinc")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("kotlin")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("com.novi.bcs.BcsTestKt")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_kotlin_runtime_with_bincode_limit_and_trailing_bytes() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let generator = kotlin::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    let mut source = File::create(dir.path().join("Main.kt")).unwrap();
+    writeln!(
+        source,
+        r#"
+import com.novi.serde.DeserializationError
+import testing.Test
+
+fun main() {{
+    val input = byteArrayOf({0}, 0)
+    val value = Test.bincodeDeserialize(input)
+    check(value.bincodeSerialize().contentEquals({1}))
+
+    try {{
+        Test.bincodeDeserialize({2})
+    }} catch (e: DeserializationError) {{
+        check(e.message!!.contains("limit"))
+        return
+    }}
+    check(false)
+}}
+"#,
+        reference
+            .iter()
+            .map(|x| format!("{}", *x as i8))
+            .collect::<Vec<_>>()
+            .join(", "),
+        quote_bytes(&reference),
+        quote_bytes(&too_large),
+    )
+    .unwrap();
+
+    compile_and_run_kotlin_main(dir.path(), Runtime::Bincode);
+}
+
+#[test]
+fn test_kotlin_runtime_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bcs.into()])
+        .with_comparisons(true);
+    let generator = kotlin::CodeGenerator::new(&config);
+    generator
+        .write_source_files(dir.path().to_path_buf(), &registry)
+        .unwrap();
+
+    // Values are sorted in the order of Rust.
+    let encodings = test_utils::get_ordered_values()
+        .iter()
+        .map(|value| {
+            let bytes = Runtime::Bcs.serialize(value);
+            format!(
+                "byteArrayOf({})",
+                bytes
+                    .iter()
+                    .map(|x| format!("{}", *x as i8))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n        ");
+
+    let mut source = File::create(dir.path().join("Main.kt")).unwrap();
+    writeln!(
+        source,
+        r#"
+import testing.OrderedData
+
+fun main() {{
+    val inputs = listOf(
+        {0}
+    )
+    val values = inputs.map {{ OrderedData.bcsDeserialize(it) }}
+    for ((i, x) in values.withIndex()) {{
+        for ((j, y) in values.withIndex()) {{
+            check(Integer.signum(x.compareTo(y)) == i.compareTo(j)) {{ "$i vs $j" }}
+            check((x == y) == (i == j))
+        }}
+        check(x.hashCode() == OrderedData.bcsDeserialize(inputs[i]).hashCode())
+    }}
+    check(values.reversed().sorted() == values)
+    check(values.toSortedSet().size == values.size)
+    check(values.toHashSet().size == values.size)
+}}
+"#,
+        encodings
+    )
+    .unwrap();
+
+    compile_and_run_kotlin_main(dir.path(), Runtime::Bcs);
+}
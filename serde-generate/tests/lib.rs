@@ -1,4 +1,8 @@
 mod analyzer;
+#[cfg(feature = "c")]
+mod c_generation;
+#[cfg(feature = "c")]
+mod c_runtime;
 #[cfg(feature = "cpp")]
 mod cpp_generation;
 #[cfg(feature = "cpp")]
@@ -11,14 +15,27 @@ mod csharp_runtime;
 mod dart_generation;
 #[cfg(feature = "dart")]
 mod dart_runtime;
+#[cfg(feature = "elixir")]
+mod elixir_generation;
+#[cfg(feature = "elixir")]
+mod elixir_runtime;
 #[cfg(feature = "golang")]
 mod golang_generation;
 #[cfg(feature = "golang")]
 mod golang_runtime;
+#[cfg(feature = "haskell")]
+mod haskell_generation;
+#[cfg(feature = "haskell")]
+mod haskell_runtime;
 #[cfg(feature = "java")]
 mod java_generation;
 #[cfg(feature = "java")]
 mod java_runtime;
+#[cfg(feature = "kotlin")]
+mod kotlin_generation;
+#[cfg(feature = "kotlin")]
+mod kotlin_runtime;
+mod modules;
 #[cfg(feature = "ocaml")]
 mod ocaml_generation;
 #[cfg(feature = "ocaml")]
@@ -27,6 +44,10 @@ mod ocaml_runtime;
 mod python_generation;
 #[cfg(feature = "python3")]
 mod python_runtime;
+#[cfg(feature = "ruby")]
+mod ruby_generation;
+#[cfg(feature = "ruby")]
+mod ruby_runtime;
 #[cfg(feature = "rust")]
 mod rust_generation;
 #[cfg(feature = "rust")]
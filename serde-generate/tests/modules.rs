@@ -0,0 +1,109 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils::{self, struct_format, type_name};
+use maplit::{btreemap, btreeset};
+use serde_generate::{Case, CodeGeneratorConfig, ModuleMap, NamingConventions, NativeType};
+use serde_reflection::Format;
+
+#[test]
+fn test_split_registry_by_qualified_names() {
+    let registry = test_utils::get_registry_with_modules();
+    let config = CodeGeneratorConfig::new("main".to_string());
+    let modules = ModuleMap::from_qualified_names(&registry, ".");
+    assert_eq!(modules.module("a::Foo"), Some("a"));
+    assert_eq!(modules.module("Qux"), None);
+
+    let split = modules.split(&config, &registry).unwrap();
+    assert_eq!(
+        split.keys().cloned().collect::<Vec<_>>(),
+        vec!["a", "b", "c", "main"]
+    );
+
+    let (config_a, registry_a) = &split["a"];
+    assert_eq!(config_a.module_name, "a");
+    assert_eq!(
+        config_a.external_definitions,
+        btreemap! { "b".to_string() => vec!["Bar".to_string()] }
+    );
+    assert_eq!(
+        registry_a["Foo"],
+        struct_format(&[("bar", Format::Option(Box::new(type_name("Bar"))))])
+    );
+
+    let (config_c, registry_c) = &split["c"];
+    assert_eq!(
+        config_c.external_definitions,
+        btreemap! {
+            "a".to_string() => vec!["Foo".to_string()],
+            "main".to_string() => vec!["Qux".to_string()],
+        }
+    );
+    assert_eq!(
+        config_c.generated_modules,
+        btreeset! { "a".to_string(), "main".to_string() }
+    );
+    assert_eq!(
+        registry_c["Baz_Foo"],
+        struct_format(&[("value", type_name("Foo")), ("qux", type_name("Qux"))])
+    );
+
+    let (config_main, registry_main) = &split["main"];
+    assert!(config_main.external_definitions.is_empty());
+    assert_eq!(registry_main.keys().collect::<Vec<_>>(), vec!["Qux"]);
+}
+
+#[test]
+fn test_split_registry_with_explicit_modules() {
+    let registry = test_utils::get_registry_with_modules();
+    let naming = NamingConventions::default().with_types(Case::ScreamingSnake);
+    let config = CodeGeneratorConfig::new("main".to_string()).with_naming_conventions(naming);
+    let mut modules = ModuleMap::new();
+    modules.insert("Qux".to_string(), "other".to_string());
+
+    let split = modules.split(&config, &registry).unwrap();
+    assert_eq!(
+        split.keys().cloned().collect::<Vec<_>>(),
+        vec!["main", "other"]
+    );
+    let (config_main, registry_main) = &split["main"];
+    // References to other modules follow the naming convention for types.
+    assert_eq!(
+        config_main.external_definitions,
+        btreemap! { "other".to_string() => vec!["QUX".to_string()] }
+    );
+    assert_eq!(
+        registry_main["Baz_Foo"],
+        struct_format(&[("value", type_name("Foo")), ("qux", type_name("QUX"))])
+    );
+}
+
+#[test]
+fn test_split_registry_without_native_types() {
+    let registry = test_utils::get_registry_with_modules();
+    let native_types = btreemap! {
+        "Qux".to_string() => NativeType::new(
+            "Instant".to_string(),
+            "serializeInstant".to_string(),
+            "deserializeInstant".to_string(),
+        ),
+    };
+    let config = CodeGeneratorConfig::new("main".to_string()).with_native_types(native_types);
+    let modules = ModuleMap::from_qualified_names(&registry, ".");
+
+    let split = modules.split(&config, &registry).unwrap();
+    assert_eq!(
+        split.keys().cloned().collect::<Vec<_>>(),
+        vec!["a", "b", "c"]
+    );
+    let (config_c, registry_c) = &split["c"];
+    // Native types are not imported from other modules.
+    assert_eq!(
+        config_c.external_definitions,
+        btreemap! { "a".to_string() => vec!["Foo".to_string()] }
+    );
+    assert_eq!(
+        registry_c["Baz_Foo"],
+        struct_format(&[("value", type_name("Foo")), ("qux", type_name("Qux"))])
+    );
+}
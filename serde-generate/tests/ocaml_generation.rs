@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use serde_generate::{ocaml, CodeGeneratorConfig, Encoding, SourceInstaller};
+use serde_generate::{
+    ocaml, BincodeOptions, CodeGeneratorConfig, Encoding, SourceInstaller, TrailingBytes,
+};
 use std::{fs::File, io::Write, process::Command};
 use tempfile::{tempdir, TempDir};
 
@@ -37,6 +39,19 @@ fn test_that_ocaml_code_compiles_with_config(
             installer.install_bincode_runtime().unwrap();
             "\n(libraries bincode_runtime)"
         }
+        Some(Encoding::Postcard) => {
+            installer.install_postcard_runtime().unwrap();
+            "\n(libraries postcard_runtime)"
+        }
+        Some(Encoding::Borsh) => {
+            installer.install_borsh_runtime().unwrap();
+            "\n(libraries borsh_runtime)"
+        }
+        Some(Encoding::Scale) => {
+            installer.install_scale_runtime().unwrap();
+            "\n(libraries scale_runtime)"
+        }
+        Some(Encoding::Json) => unreachable!("JSON is not supported by the OCaml code generator"),
         None => {
             installer.install_serde_runtime().unwrap();
             ""
@@ -160,3 +175,61 @@ fn test_that_ocaml_code_compiles_with_custom_code() {
     let content = std::fs::read_to_string(source_path).unwrap();
     assert!(content.contains("serde_data_to_string"));
 }
+
+#[test]
+fn test_ocaml_code_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comparisons(true);
+    let mut source = Vec::new();
+    ocaml::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+
+    let content = String::from_utf8(source).unwrap();
+    assert!(content.contains(
+        "and compare_ordered_data (a : ordered_data) (b : ordered_data) : int =\n  let index = function"
+    ));
+    assert!(content.contains("| _ -> Stdlib.compare (index a) (index b)"));
+    assert!(content
+        .contains("(Serde.Comparisons.compare_map Stdlib.compare Stdint.Uint16.compare) a b"));
+    assert!(content.contains("let equal_label (a : label) (b : label) : bool"));
+    assert!(content.contains("and hash_ordered_data (v : ordered_data) : int ="));
+}
+
+#[test]
+fn test_ocaml_code_with_validation() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_validation(true);
+    let mut source = Vec::new();
+    ocaml::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+
+    let content = String::from_utf8(source).unwrap();
+    assert!(content.contains("let rec validate_label (v : label) : unit =\n  ignore v"));
+    assert!(content.contains(
+        "| OrderedData_Pair v -> (fun (x0, x1) -> validate_label x0; (Serde.Validation.validate_array 2 ignore) x1) v"
+    ));
+}
+
+#[test]
+fn test_ocaml_code_with_bincode_options() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bincode])
+        .with_bincode_options(
+            BincodeOptions::standard()
+                .with_limit(Some(100))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    ocaml::CAPABILITIES.check(&registry, &config).unwrap();
+    let mut source = Vec::new();
+    ocaml::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+
+    let content = String::from_utf8(source).unwrap();
+    assert!(content.contains(
+        "let () = Serde.Bincode_options.(varint_encoding := true; big_endian := false; limit := Some 100; allow_trailing_bytes := true)"
+    ));
+}
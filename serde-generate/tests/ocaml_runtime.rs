@@ -28,6 +28,26 @@ fn test_ocaml_bincode_runtime_on_simple_data() {
     test_ocaml_runtime_on_simple_data(Runtime::Bincode);
 }
 
+#[test]
+fn test_ocaml_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_ocaml_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_ocaml_postcard_runtime_on_simple_data() {
+    test_ocaml_runtime_on_simple_data(Runtime::Postcard);
+}
+
+#[test]
+fn test_ocaml_borsh_runtime_on_simple_data() {
+    test_ocaml_runtime_on_simple_data(Runtime::Borsh);
+}
+
+#[test]
+fn test_ocaml_scale_runtime_on_simple_data() {
+    test_ocaml_runtime_on_simple_data(Runtime::Scale);
+}
+
 fn test_ocaml_runtime_on_simple_data(runtime: Runtime) {
     let registry = test_utils::get_simple_registry().unwrap();
     let dir0 = tempdir().unwrap();
@@ -38,14 +58,27 @@ fn test_ocaml_runtime_on_simple_data(runtime: Runtime) {
             installer.install_bcs_runtime().unwrap();
             "bcs"
         }
-        Runtime::Bincode => {
+        Runtime::Bincode | Runtime::BincodeVarintBigEndian => {
             installer.install_bincode_runtime().unwrap();
             "bincode"
         }
+        Runtime::Postcard => {
+            installer.install_postcard_runtime().unwrap();
+            "postcard"
+        }
+        Runtime::Borsh => {
+            installer.install_borsh_runtime().unwrap();
+            "borsh"
+        }
+        Runtime::Scale => {
+            installer.install_scale_runtime().unwrap();
+            "scale"
+        }
     };
 
-    let config =
-        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
 
     let dir_path = dir.join(config.module_name());
     std::fs::create_dir_all(&dir_path).unwrap();
@@ -133,6 +166,90 @@ let () =
     assert!(status.success());
 }
 
+#[test]
+fn test_ocaml_bcs_runtime_on_open_enum() {
+    let registry = test_utils::get_registry_with_open_enum();
+    let dir0 = tempdir().unwrap();
+    let dir = dir0.path();
+    let installer = ocaml::Installer::new(dir.to_path_buf());
+    installer.install_bcs_runtime().unwrap();
+
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Runtime::Bcs.into()]);
+
+    let dir_path = dir.join(config.module_name());
+    std::fs::create_dir_all(&dir_path).unwrap();
+
+    let dune_project_source_path = dir.join("dune-project");
+    let mut dune_project_file = std::fs::File::create(dune_project_source_path).unwrap();
+    writeln!(dune_project_file, "(lang dune 3.0)").unwrap();
+
+    let dune_source_path = dir_path.join("dune");
+    let mut dune_file = std::fs::File::create(dune_source_path).unwrap();
+    writeln!(
+        dune_file,
+        r#"
+(env (_ (flags (:standard -w -30-42))))
+
+(library
+ (name testing)
+ (modules testing)
+ (preprocess (pps ppx))
+ (libraries bcs_runtime))
+
+(executable
+ (name main)
+ (modules main)
+ (libraries serde testing))
+"#
+    )
+    .unwrap();
+
+    let lib_path = dir_path.join("testing.ml");
+    let mut lib = File::create(lib_path).unwrap();
+    let generator = ocaml::CodeGenerator::new(&config);
+    generator.output(&mut lib, &registry).unwrap();
+
+    // The second event has a tag unknown to the generated code.
+    let reference = test_utils::get_open_enum_log(Runtime::Bcs);
+    let exe_path = dir_path.join("main.ml");
+    let mut exe = File::create(exe_path).unwrap();
+    writeln!(
+        exe,
+        r#"
+open Serde
+open Stdint
+
+let () =
+  let input = Bytes.of_string {0} in
+  let log = Deserialize.apply Testing.log_de input in
+  assert (Testing.event_tags = ["Click"]);
+  let clicks = ref [] in
+  Testing.register_event_handler "Click" (fun content ->
+    clicks := Deserialize.apply Testing.click_de content :: !clicks);
+  match log.Testing.events with
+  | [first; second] ->
+    assert (Testing.handle_event first);
+    assert (!clicks = [{{ Testing.x = Uint32.of_int 7 }}]);
+    assert (not (Testing.handle_event second));
+    let output = Serialize.apply Testing.log_ser log in
+    assert (input = output)
+  | _ -> assert false
+"#,
+        quote_bytes(&reference)
+    )
+    .unwrap();
+
+    let status = Command::new("dune")
+        .arg("exec")
+        .arg("testing/main.exe")
+        .arg("--root")
+        .arg(dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
 #[test]
 fn test_ocaml_bcs_runtime_on_supported_types() {
     test_ocaml_runtime_on_supported_types(Runtime::Bcs);
@@ -143,8 +260,28 @@ fn test_ocaml_bincode_runtime_on_supported_types() {
     test_ocaml_runtime_on_supported_types(Runtime::Bincode);
 }
 
+#[test]
+fn test_ocaml_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_ocaml_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_ocaml_postcard_runtime_on_supported_types() {
+    test_ocaml_runtime_on_supported_types(Runtime::Postcard);
+}
+
+#[test]
+fn test_ocaml_borsh_runtime_on_supported_types() {
+    test_ocaml_runtime_on_supported_types(Runtime::Borsh);
+}
+
+#[test]
+fn test_ocaml_scale_runtime_on_supported_types() {
+    test_ocaml_runtime_on_supported_types(Runtime::Scale);
+}
+
 fn test_ocaml_runtime_on_supported_types(runtime: Runtime) {
-    let registry = test_utils::get_registry().unwrap();
+    let registry = runtime.get_registry().unwrap();
     let dir0 = tempdir().unwrap();
     let dir = dir0.path();
     let installer = ocaml::Installer::new(dir.to_path_buf());
@@ -153,14 +290,27 @@ fn test_ocaml_runtime_on_supported_types(runtime: Runtime) {
             installer.install_bcs_runtime().unwrap();
             "bcs"
         }
-        Runtime::Bincode => {
+        Runtime::Bincode | Runtime::BincodeVarintBigEndian => {
             installer.install_bincode_runtime().unwrap();
             "bincode"
         }
+        Runtime::Postcard => {
+            installer.install_postcard_runtime().unwrap();
+            "postcard"
+        }
+        Runtime::Borsh => {
+            installer.install_borsh_runtime().unwrap();
+            "borsh"
+        }
+        Runtime::Scale => {
+            installer.install_scale_runtime().unwrap();
+            "scale"
+        }
     };
 
-    let config =
-        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
 
     let dir_path = dir.join(config.module_name());
     std::fs::create_dir_all(&dir_path).unwrap();
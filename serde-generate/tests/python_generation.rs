@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use serde_generate::{python3, CodeGeneratorConfig, Encoding, SourceInstaller};
+use serde_generate::{python3, CodeGeneratorConfig, Encoding, ModuleMap, SourceInstaller};
 use std::{collections::BTreeMap, fs::File, process::Command};
 use tempfile::{tempdir, TempDir};
 
@@ -159,6 +159,10 @@ fn test_that_installed_python_code_passes_pyre_check() {
     installer.install_serde_runtime().unwrap();
     installer.install_bincode_runtime().unwrap();
     installer.install_bcs_runtime().unwrap();
+    installer.install_postcard_runtime().unwrap();
+    installer.install_borsh_runtime().unwrap();
+    installer.install_scale_runtime().unwrap();
+    installer.install_json_runtime().unwrap();
 
     // Copy test files manually to type-check them as well.
     // This should go away when python runtimes are properly packaged.
@@ -176,6 +180,34 @@ fn test_that_installed_python_code_passes_pyre_check() {
         .status()
         .unwrap();
     assert!(status.success());
+    let status = Command::new("cp")
+        .arg("-r")
+        .arg("runtime/python/postcard/test_postcard.py")
+        .arg(dir.path().join("src/postcard"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let status = Command::new("cp")
+        .arg("-r")
+        .arg("runtime/python/borsh/test_borsh.py")
+        .arg(dir.path().join("src/borsh"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let status = Command::new("cp")
+        .arg("-r")
+        .arg("runtime/python/scale/test_scale.py")
+        .arg(dir.path().join("src/scale"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let status = Command::new("cp")
+        .arg("-r")
+        .arg("runtime/python/serde_json/test_serde_json.py")
+        .arg(dir.path().join("src/serde_json"))
+        .status()
+        .unwrap();
+    assert!(status.success());
 
     let site_packages = Command::new("python3")
         .arg("-c")
@@ -219,3 +251,435 @@ fn test_python_autotest() {
         .unwrap();
     assert!(status.success());
 }
+
+#[test]
+fn test_python_code_rejects_tagged_enums() {
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Tagged".to_string(),
+        serde_reflection::ContainerFormat::TaggedEnum {
+            representation: serde_reflection::EnumRepresentation::Untagged,
+            variants: BTreeMap::new(),
+        },
+    );
+    // Rejected even when JSON is the only encoding.
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Json]);
+    let mut source = Vec::new();
+    let error = python3::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap_err();
+    assert!(matches!(
+        &error,
+        serde_generate::Error::Unsupported { language: "Python 3", path: Some(path), .. } if path == "Tagged"
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Python 3 does not support tagged or untagged enum representations (in Tagged)"
+    );
+}
+
+#[test]
+fn test_python_code_rejects_flattened_structs() {
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Flattened".to_string(),
+        serde_reflection::ContainerFormat::FlattenedStruct(Vec::new()),
+    );
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let mut source = Vec::new();
+    let error = python3::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Python 3 does not support flattened structs (in Flattened)"
+    );
+}
+
+#[test]
+fn test_python_code_rejects_registries_incompatible_with_borsh() {
+    use serde_reflection::{ContainerFormat, Format, Named, VariantFormat};
+
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Borsh]);
+    let generate = |registry: &serde_reflection::Registry| {
+        let mut source = Vec::new();
+        python3::CodeGenerator::new(&config)
+            .output(&mut source, registry)
+            .map_err(|error| error.to_string())
+    };
+
+    assert!(generate(&test_utils::borsh_data::get_registry().unwrap()).is_ok());
+    assert_eq!(
+        generate(&test_utils::get_registry().unwrap()).unwrap_err(),
+        "Python 3 does not support characters in the borsh encoding (in PrimitiveTypes)"
+    );
+
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Letter".to_string(),
+        ContainerFormat::NewTypeStruct(Box::new(Format::Char)),
+    );
+    assert_eq!(
+        generate(&registry).unwrap_err(),
+        "Python 3 does not support characters in the borsh encoding (in Letter)"
+    );
+
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Large".to_string(),
+        ContainerFormat::Enum(
+            (0..300)
+                .map(|index| {
+                    let variant = Named {
+                        name: format!("V{}", index),
+                        value: VariantFormat::Unit,
+                    };
+                    (index, variant)
+                })
+                .collect(),
+        ),
+    );
+    assert_eq!(
+        generate(&registry).unwrap_err(),
+        "Python 3 does not support more than 256 variants in the borsh encoding (in Large)"
+    );
+
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Key".to_string(),
+        ContainerFormat::TupleStruct(vec![Format::I64, Format::Option(Box::new(Format::Str))]),
+    );
+    registry.insert(
+        "Map".to_string(),
+        ContainerFormat::NewTypeStruct(Box::new(Format::Map {
+            key: Box::new(Format::TypeName("Key".to_string())),
+            value: Box::new(Format::U64),
+        })),
+    );
+    assert!(generate(&registry).is_ok());
+    registry.insert(
+        "Key".to_string(),
+        ContainerFormat::TupleStruct(vec![Format::U8, Format::Seq(Box::new(Format::U16))]),
+    );
+    assert_eq!(
+        generate(&registry).unwrap_err(),
+        "Python 3 does not support map keys containing floats, bytes, sequences or maps in the \
+         borsh encoding (in Map)"
+    );
+}
+
+#[test]
+fn test_python_code_rejects_registries_incompatible_with_scale() {
+    use serde_reflection::{ContainerFormat, Format, Named, VariantFormat};
+
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Scale]);
+    let generate = |registry: &serde_reflection::Registry| {
+        let mut source = Vec::new();
+        python3::CodeGenerator::new(&config)
+            .output(&mut source, registry)
+            .map_err(|error| error.to_string())
+    };
+
+    assert!(generate(&test_utils::scale_data::get_registry().unwrap()).is_ok());
+    assert_eq!(
+        generate(&test_utils::get_registry().unwrap()).unwrap_err(),
+        "Python 3 does not support characters in the scale encoding (in PrimitiveTypes)"
+    );
+
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Large".to_string(),
+        ContainerFormat::Enum(
+            (0..300)
+                .map(|index| {
+                    let variant = Named {
+                        name: format!("V{}", index),
+                        value: VariantFormat::Unit,
+                    };
+                    (index, variant)
+                })
+                .collect(),
+        ),
+    );
+    assert_eq!(
+        generate(&registry).unwrap_err(),
+        "Python 3 does not support more than 256 variants in the scale encoding (in Large)"
+    );
+
+    // Unlike Borsh, any map keys are supported.
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Map".to_string(),
+        ContainerFormat::NewTypeStruct(Box::new(Format::Map {
+            key: Box::new(Format::Str),
+            value: Box::new(Format::U64),
+        })),
+    );
+    assert!(generate(&registry).is_ok());
+}
+
+#[test]
+fn test_python_code_with_rust_type_names() {
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "my_crate::a::Foo<alloc::string::String>".to_string(),
+        serde_reflection::ContainerFormat::NewTypeStruct(Box::new(serde_reflection::Format::Str)),
+    );
+    registry.insert(
+        "my_crate::b::Foo<alloc::string::String>".to_string(),
+        serde_reflection::ContainerFormat::NewTypeStruct(Box::new(serde_reflection::Format::U8)),
+    );
+    registry.insert(
+        "Bar".to_string(),
+        serde_reflection::ContainerFormat::NewTypeStruct(Box::new(
+            serde_reflection::Format::TypeName(
+                "my_crate::a::Foo<alloc::string::String>".to_string(),
+            ),
+        )),
+    );
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let mut source = Vec::new();
+    python3::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+    let source = String::from_utf8(source).unwrap();
+    assert!(source.contains("class a_Foo_String:"));
+    assert!(source.contains("class b_Foo_String:"));
+    assert!(source.contains("value: \"a_Foo_String\""));
+    assert!(!source.contains("my_crate"));
+}
+
+#[test]
+fn test_python_code_with_rust_type_names_in_config() {
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "my_crate::Foo<u8>".to_string(),
+        serde_reflection::ContainerFormat::NewTypeStruct(Box::new(
+            serde_reflection::Format::TypeName("other::Bar<u64>".to_string()),
+        )),
+    );
+    let mut definitions = BTreeMap::new();
+    definitions.insert("pkg.other".to_string(), vec!["other::Bar<u64>".to_string()]);
+    let comments = vec![(
+        vec!["testing".to_string(), "my_crate::Foo<u8>".to_string()],
+        "Some comments".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    let custom_code = vec![(
+        vec!["testing".to_string(), "my_crate::Foo<u8>".to_string()],
+        "def custom(self): pass".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_external_definitions(definitions)
+        .with_comments(comments)
+        .with_custom_code(custom_code);
+    let mut source = Vec::new();
+    python3::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+    let source = String::from_utf8(source).unwrap();
+    assert!(source.contains("class Foo_u8:"));
+    assert!(source.contains("value: other.Bar_u64"));
+    assert!(source.contains("\"\"\"Some comments"));
+    assert!(source.contains("def custom(self): pass"));
+    assert!(!source.contains("my_crate"));
+}
+
+#[test]
+fn test_python_code_rejects_module_cycles() {
+    let registry = test_utils::get_registry_with_modules();
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let modules = ModuleMap::from_qualified_names(&registry, ".");
+    let error = modules
+        .generate(&python3::CAPABILITIES, &config, &registry, |config| {
+            Box::new(python3::CodeGenerator::new(config))
+        })
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Python 3 does not support cyclic dependencies between modules (in a)"
+    );
+
+    // Without the cycle, modules import each other.
+    let mut modules = ModuleMap::new();
+    modules.insert("Qux".to_string(), "other".to_string());
+    let files = modules
+        .generate(&python3::CAPABILITIES, &config, &registry, |config| {
+            Box::new(python3::CodeGenerator::new(config))
+        })
+        .unwrap();
+    let content = files.get("testing/__init__.py").unwrap();
+    assert!(content.contains("import other"));
+    assert!(content.contains("qux: other.Qux"));
+    assert!(!files
+        .get("other/__init__.py")
+        .unwrap()
+        .contains("import testing"));
+}
+
+#[test]
+fn test_python_code_rejects_imported_names_clashing_after_renaming() {
+    use serde_generate::{Case, NamingConventions};
+    use serde_reflection::{ContainerFormat, Format, Registry};
+
+    let mut registry = Registry::new();
+    registry.insert(
+        "a::FooBar".to_string(),
+        ContainerFormat::NewTypeStruct(Box::new(Format::U8)),
+    );
+    registry.insert(
+        "b::foo_bar".to_string(),
+        ContainerFormat::NewTypeStruct(Box::new(Format::TypeName("a::FooBar".to_string()))),
+    );
+    let naming = NamingConventions::default().with_types(Case::UpperCamel);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let modules = ModuleMap::from_qualified_names(&registry, ".");
+    let error = modules
+        .generate(&python3::CAPABILITIES, &config, &registry, |config| {
+            Box::new(python3::CodeGenerator::new(config))
+        })
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Python 3 name `FooBar` of a.FooBar clashes with b.foo_bar when imported"
+    );
+}
+
+#[test]
+fn test_python_code_rejects_registries_incompatible_with_json() {
+    use serde_reflection::{ContainerFormat, Format, Named, VariantFormat};
+
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Json]);
+    let generate = |config: &CodeGeneratorConfig, registry: &serde_reflection::Registry| {
+        let mut source = Vec::new();
+        python3::CodeGenerator::new(config)
+            .output(&mut source, registry)
+            .map_err(|error| error.to_string())
+    };
+
+    assert!(generate(&config, &test_utils::json_data::get_registry().unwrap()).is_ok());
+    assert_eq!(
+        generate(&config, &test_utils::get_registry().unwrap()).unwrap_err(),
+        "Python 3 does not support map keys other than strings and numbers in the json \
+         encoding (in SerdeData)"
+    );
+
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Map".to_string(),
+        ContainerFormat::NewTypeStruct(Box::new(Format::Map {
+            key: Box::new(Format::Tuple(vec![Format::U8, Format::U8])),
+            value: Box::new(Format::U64),
+        })),
+    );
+    assert_eq!(
+        generate(&config, &registry).unwrap_err(),
+        "Python 3 does not support map keys other than strings and numbers in the json \
+         encoding (in Map)"
+    );
+
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Choice".to_string(),
+        ContainerFormat::Enum(
+            vec![(
+                0,
+                Named {
+                    name: "A".to_string(),
+                    value: VariantFormat::NewType(Box::new(Format::U8)),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        ),
+    );
+    registry.insert(
+        "Map".to_string(),
+        ContainerFormat::NewTypeStruct(Box::new(Format::Map {
+            key: Box::new(Format::TypeName("Choice".to_string())),
+            value: Box::new(Format::U64),
+        })),
+    );
+    assert_eq!(
+        generate(&config, &registry).unwrap_err(),
+        "Python 3 does not support map keys other than strings and numbers in the json \
+         encoding (in Map)"
+    );
+
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Event".to_string(),
+        ContainerFormat::OpenEnum(vec![("A".to_string(), Format::U8)].into_iter().collect()),
+    );
+    assert_eq!(
+        generate(&config, &registry).unwrap_err(),
+        "Python 3 does not support open enums in the json encoding (in Event)"
+    );
+
+    let config = config.with_naming_conventions(
+        serde_generate::NamingConventions::default().with_fields(serde_generate::Case::LowerCamel),
+    );
+    assert_eq!(
+        generate(&config, &test_utils::json_data::get_registry().unwrap()).unwrap_err(),
+        "Python 3 does not support naming conventions for fields and variants in the json encoding"
+    );
+}
+
+#[test]
+fn test_python_code_with_dual_registries() {
+    use serde_reflection::{ContainerFormat, Dual, Format};
+
+    // Addresses are written as hex strings in human-readable encodings.
+    let registry = |format: Format| {
+        let mut registry = serde_reflection::Registry::new();
+        registry.insert(
+            "Address".to_string(),
+            ContainerFormat::NewTypeStruct(Box::new(format)),
+        );
+        registry
+    };
+    let registries = Dual {
+        human_readable: registry(Format::Str),
+        binary: registry(Format::Bytes),
+    };
+    let generate = |encodings: Vec<Encoding>| {
+        let config = CodeGeneratorConfig::new("testing".to_string()).with_encodings(encodings);
+        config
+            .generate_dual(&registries, |config| {
+                Box::new(python3::CodeGenerator::new(config))
+            })
+            .unwrap()
+    };
+
+    let files = generate(vec![Encoding::Json]);
+    assert_eq!(files.len(), 1);
+    assert!(files
+        .get("testing/__init__.py")
+        .unwrap()
+        .contains("    value: str\n"));
+    let files = generate(vec![Encoding::Bcs]);
+    assert_eq!(files.len(), 1);
+    assert!(files
+        .get("testing/__init__.py")
+        .unwrap()
+        .contains("    value: bytes\n"));
+
+    // The JSON encoding goes to a separate module.
+    let files = generate(vec![Encoding::Bcs, Encoding::Json]);
+    assert_eq!(files.len(), 2);
+    let content = files.get("testing/__init__.py").unwrap();
+    assert!(content.contains("    value: bytes\n"));
+    assert!(content.contains("def bcs_serialize("));
+    assert!(!content.contains("def json_serialize("));
+    let content = files.get("testing_json/__init__.py").unwrap();
+    assert!(content.contains("    value: str\n"));
+    assert!(content.contains("def json_serialize("));
+    assert!(!content.contains("def bcs_serialize("));
+}
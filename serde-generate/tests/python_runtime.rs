@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use crate::test_utils::{Choice, Runtime, Test};
-use serde_generate::{python3, CodeGeneratorConfig};
+use crate::test_utils::{json_data, Choice, Runtime, Test};
+use serde_generate::{python3, BincodeOptions, CodeGeneratorConfig, Encoding, TrailingBytes};
 use std::{fs::File, io::Write, process::Command};
 use tempfile::tempdir;
 
@@ -17,14 +17,35 @@ fn test_python_bincode_runtime_on_simple_data() {
     test_python_runtime_on_simple_data(Runtime::Bincode);
 }
 
+#[test]
+fn test_python_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_python_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_python_postcard_runtime_on_simple_data() {
+    test_python_runtime_on_simple_data(Runtime::Postcard);
+}
+
+#[test]
+fn test_python_borsh_runtime_on_simple_data() {
+    test_python_runtime_on_simple_data(Runtime::Borsh);
+}
+
+#[test]
+fn test_python_scale_runtime_on_simple_data() {
+    test_python_runtime_on_simple_data(Runtime::Scale);
+}
+
 fn test_python_runtime_on_simple_data(runtime: Runtime) {
     let registry = test_utils::get_simple_registry().unwrap();
     let dir = tempdir().unwrap();
     let source_path = dir.path().join("test.py");
     let mut source = File::create(&source_path).unwrap();
 
-    let config =
-        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
     let generator = python3::CodeGenerator::new(&config);
     generator.output(&mut source, &registry).unwrap();
 
@@ -82,14 +103,35 @@ fn test_python_bincode_runtime_on_supported_types() {
     test_python_runtime_on_supported_types(Runtime::Bincode);
 }
 
+#[test]
+fn test_python_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_python_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_python_postcard_runtime_on_supported_types() {
+    test_python_runtime_on_supported_types(Runtime::Postcard);
+}
+
+#[test]
+fn test_python_borsh_runtime_on_supported_types() {
+    test_python_runtime_on_supported_types(Runtime::Borsh);
+}
+
+#[test]
+fn test_python_scale_runtime_on_supported_types() {
+    test_python_runtime_on_supported_types(Runtime::Scale);
+}
+
 fn test_python_runtime_on_supported_types(runtime: Runtime) {
-    let registry = test_utils::get_registry().unwrap();
+    let registry = runtime.get_registry().unwrap();
     let dir = tempdir().unwrap();
     let source_path = dir.path().join("test.py");
     let mut source = File::create(&source_path).unwrap();
 
-    let config =
-        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
     let generator = python3::CodeGenerator::new(&config);
     generator.output(&mut source, &registry).unwrap();
 
@@ -153,3 +195,271 @@ for encoding in negative_encodings:
         .unwrap();
     assert!(status.success());
 }
+
+#[test]
+fn test_python_bcs_runtime_on_open_enum() {
+    let registry = test_utils::get_registry_with_open_enum();
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.py");
+    let mut source = File::create(&source_path).unwrap();
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Runtime::Bcs.into()]);
+    let generator = python3::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+
+    // The second event has a tag unknown to the generated code.
+    let reference = test_utils::get_open_enum_log(Runtime::Bcs);
+    writeln!(
+        source,
+        r#"
+input = bytes({:?})
+log = Log.bcs_deserialize(input)
+assert len(log.events) == 2
+assert log.events[0].bcs_decode() == Click(7)
+assert log.events[1].bcs_decode() is None
+assert Event.bcs_encode("Click", Click(7)) == log.events[0]
+assert log.bcs_serialize() == input
+"#,
+        reference,
+    )
+    .unwrap();
+
+    let python_path = std::env::var("PYTHONPATH").unwrap_or_default() + ":runtime/python";
+    let status = Command::new("python3")
+        .arg(source_path)
+        .env("PYTHONPATH", python_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_python_runtime_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.py");
+    let mut source = File::create(&source_path).unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bcs.into()])
+        .with_comparisons(true);
+    let generator = python3::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+
+    // Values are sorted in the order of Rust.
+    let encodings = test_utils::get_ordered_values()
+        .iter()
+        .map(|value| Runtime::Bcs.serialize(value))
+        .collect::<Vec<_>>();
+    writeln!(
+        source,
+        r#"
+values = [OrderedData.bcs_deserialize(bytes(a)) for a in {0:?}]
+for i, x in enumerate(values):
+    for j, y in enumerate(values):
+        assert (x < y) == (i < j), (i, j)
+        assert (x == y) == (i == j), (i, j)
+    assert hash(x) == hash(OrderedData.bcs_deserialize(x.bcs_serialize()))
+assert sorted(reversed(values)) == values
+assert len(set(values)) == len(values)
+"#,
+        encodings
+    )
+    .unwrap();
+
+    let python_path = std::env::var("PYTHONPATH").unwrap_or_default() + ":runtime/python";
+    let status = Command::new("python3")
+        .arg(source_path)
+        .env("PYTHONPATH", python_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_python_runtime_with_validation() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.py");
+    let mut source = File::create(&source_path).unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bcs.into()])
+        .with_validate_on_serialize(true);
+    let generator = python3::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+
+    writeln!(
+        source,
+        r#"
+valid = OrderedData__Pair(value=(Label(value="a"), (st.uint16(1), st.uint16(2))))
+valid.validate()
+assert OrderedData.bcs_deserialize(valid.bcs_serialize()) == valid
+
+for invalid in [
+    OrderedData__Pair(value=(Label(value="a"), (st.uint16(1),))),
+    OrderedData__Pair(value=(Label(value="a"), (st.uint16(1), st.uint16(2), st.uint16(3)))),
+    OrderedData__Pair(value=(Label(value="a"),)),
+    OrderedData__Pair(value=("a", (st.uint16(1), st.uint16(2)))),
+]:
+    for f in [invalid.validate, invalid.bcs_serialize]:
+        try:
+            f()
+            assert False
+        except st.SerializationError:
+            pass
+"#
+    )
+    .unwrap();
+
+    let python_path = std::env::var("PYTHONPATH").unwrap_or_default() + ":runtime/python";
+    let status = Command::new("python3")
+        .arg(source_path)
+        .env("PYTHONPATH", python_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_python_runtime_with_bincode_limit_and_trailing_bytes() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.py");
+    let mut source = File::create(&source_path).unwrap();
+
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let generator = python3::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+
+    writeln!(
+        source,
+        r#"
+input = bytes({:?})
+value = Test([4, 6], (3, 5), Choice__C(7))
+assert value.bincode_serialize() == input
+assert Test.bincode_deserialize(input + bytes([0])) == value
+
+try:
+    Test([4, 6, 8], (3, 5), Choice__C(7)).bincode_serialize()
+    assert False
+except st.SerializationError:
+    pass
+
+try:
+    Test.bincode_deserialize(bytes({:?}))
+    assert False
+except st.DeserializationError as e:
+    assert "limit" in str(e)
+"#,
+        reference, too_large,
+    )
+    .unwrap();
+
+    let python_path = std::env::var("PYTHONPATH").unwrap_or_default() + ":runtime/python";
+    let status = Command::new("python3")
+        .arg(source_path)
+        .env("PYTHONPATH", python_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn quote_string(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            ' '..='~' if c != '"' && c != '\\' => result.push(c),
+            _ => result.push_str(&format!("\\U{:08x}", c as u32)),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[test]
+fn test_python_json_runtime_on_supported_types() {
+    let registry = json_data::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.py");
+    let mut source = File::create(&source_path).unwrap();
+
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Json]);
+    let generator = python3::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+
+    let positive_inputs: Vec<_> = json_data::get_positive_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect();
+    let alternate_inputs: Vec<_> = json_data::get_alternate_samples()
+        .iter()
+        .map(|(input, output)| format!("({}, {})", quote_string(input), quote_string(output)))
+        .collect();
+    let negative_inputs: Vec<_> = json_data::get_negative_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect();
+
+    writeln!(
+        source,
+        r#"
+import serde_types as st
+import sys
+
+# Required to avoid RecursionError's in python.
+sys.setrecursionlimit(serde_json.MAX_CONTAINER_DEPTH * 20)
+
+positive_inputs = [{0}]
+alternate_inputs = [{1}]
+negative_inputs = [{2}]
+
+for input in positive_inputs:
+    value = SerdeData.json_deserialize(input)
+    assert value.json_serialize() == input, value.json_serialize()
+
+    # Test self-equality for the Serde value.
+    assert value == SerdeData.json_deserialize(input)
+
+for input, output in alternate_inputs:
+    assert SerdeData.json_deserialize(input).json_serialize() == output, input
+
+for input in negative_inputs:
+    try:
+        SerdeData.json_deserialize(input)
+        print('Input was wrongfully accepted:\n', input)
+        sys.exit(1)
+    except st.DeserializationError:
+        pass
+"#,
+        positive_inputs.join(", "),
+        alternate_inputs.join(", "),
+        negative_inputs.join(", "),
+    )
+    .unwrap();
+
+    let python_path = std::env::var("PYTHONPATH").unwrap_or_default() + ":runtime/python";
+    let status = Command::new("python3")
+        .arg(source_path)
+        .env("PYTHONPATH", python_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
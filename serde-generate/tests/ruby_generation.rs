@@ -0,0 +1,289 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils;
+use serde::Deserialize;
+use serde_generate::{
+    ruby, BincodeOptions, Case, CodeGeneratorConfig, Encoding, NamingConventions, SourceGenerator,
+    SourceInstaller, TrailingBytes,
+};
+use serde_reflection::{Tracer, TracerConfig};
+use std::{collections::BTreeMap, process::Command};
+use tempfile::{tempdir, TempDir};
+
+/// Generate `testing.rb` then load it, which defines all classes and modules.
+fn test_that_ruby_code_loads_with_config(
+    config: &CodeGeneratorConfig,
+) -> (TempDir, std::path::PathBuf) {
+    let registry = test_utils::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let installer = ruby::Installer::new(dir.path().to_path_buf());
+    installer.install_module(config, &registry).unwrap();
+
+    let status = Command::new("ruby")
+        .arg("-w")
+        .arg("-I")
+        .arg("runtime/ruby")
+        .arg("-I")
+        .arg(dir.path())
+        .arg("-e")
+        .arg("require 'testing'")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let path = dir.path().join("testing.rb");
+    (dir, path)
+}
+
+#[test]
+fn test_that_ruby_code_loads() {
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    test_that_ruby_code_loads_with_config(&config);
+}
+
+#[test]
+fn test_that_ruby_code_loads_without_serialization() {
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_serialization(false);
+    let (_dir, path) = test_that_ruby_code_loads_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(!content.contains("require 'serde'"));
+    assert!(!content.contains("def serialize("));
+}
+
+#[test]
+fn test_that_ruby_code_loads_with_bcs() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bcs]);
+    test_that_ruby_code_loads_with_config(&config);
+}
+
+#[test]
+fn test_that_ruby_code_loads_with_bincode() {
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Bincode]);
+    test_that_ruby_code_loads_with_config(&config);
+}
+
+#[test]
+fn test_that_ruby_code_loads_with_bincode_options() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bincode])
+        .with_bincode_options(
+            BincodeOptions::standard()
+                .with_limit(Some(100))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let (_dir, path) = test_that_ruby_code_loads_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains("::Bincode::Serializer.new(varint_encoding: true, big_endian: false)"));
+    assert!(content.contains(
+        "::Bincode::Deserializer.new(input, varint_encoding: true, big_endian: false, limit: 100)"
+    ));
+    assert!(!content.contains("Some input bytes were not read"));
+}
+
+#[test]
+fn test_that_ruby_code_loads_with_validation() {
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_validate_on_serialize(true);
+    let (_dir, path) = test_that_ruby_code_loads_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains("def validate_array3_u32_array(value)"));
+}
+
+#[test]
+fn test_that_ruby_code_loads_with_comments() {
+    let comments = vec![
+        (
+            vec!["testing".to_string(), "SerdeData".to_string()],
+            "Some\ncomments".to_string(),
+        ),
+        (
+            vec!["testing".to_string(), "Struct".to_string(), "x".to_string()],
+            "A field".to_string(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comments(comments);
+
+    let (_dir, path) = test_that_ruby_code_loads_with_config(&config);
+
+    // Comments were correctly generated.
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains(
+        r#"
+  # Some
+  # comments
+  module SerdeData
+"#
+    ));
+    assert!(content.contains(
+        r#"
+  class Struct < ::Data.define(
+    # A field
+    :x,
+    :y,
+  )
+"#
+    ));
+}
+
+#[test]
+fn test_that_ruby_code_loads_with_custom_code() {
+    let custom_code = vec![(
+        vec!["testing".to_string(), "SerdeData".to_string()],
+        "def custom_method\n  42\nend".to_string(),
+    )]
+    .into_iter()
+    .collect();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_custom_code(custom_code);
+
+    let (_dir, path) = test_that_ruby_code_loads_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains("def custom_method"));
+}
+
+#[test]
+fn test_that_ruby_code_loads_with_naming_conventions() {
+    let naming = NamingConventions::default()
+        .with_fields(Case::LowerCamel)
+        .with_variants(Case::ScreamingSnake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+
+    let (_dir, path) = test_that_ruby_code_loads_with_config(&config);
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.contains("class PRIMITIVE_TYPES < ::Data.define(:value)"));
+    assert!(content.contains("class OtherTypes < ::Data.define(:fString, "));
+}
+
+#[test]
+fn test_ruby_code_with_external_definitions() {
+    let registry = test_utils::get_registry().unwrap();
+
+    // (wrongly) Declare Tree as external.
+    let mut definitions = BTreeMap::new();
+    definitions.insert("pkg.foo".to_string(), vec!["Tree".to_string()]);
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_external_definitions(definitions);
+    let generator = ruby::CodeGenerator::new(&config);
+    let files = generator.generate(&registry).unwrap();
+
+    let content = files.get("testing.rb").unwrap();
+    assert!(content.contains("require 'pkg/foo'"));
+    assert!(content.contains("::Pkg::Foo::Tree.deserialize(deserializer)"));
+}
+
+#[test]
+fn test_ruby_code_qualifies_names_shadowed_by_variants() {
+    let registry = test_utils::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let files = ruby::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    // The variant `SerdeData::SimpleList` shadows the struct `SimpleList`.
+    let content = files.get("testing.rb").unwrap();
+    assert!(content.contains("  module SerdeData\n"));
+    assert!(content.contains("value: ::Testing::SimpleList.deserialize(deserializer),"));
+    assert!(content.contains("value: List.deserialize(deserializer),"));
+}
+
+#[test]
+fn test_ruby_code_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comparisons(true);
+    let files = ruby::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap();
+
+    let content = files.get("testing.rb").unwrap();
+    assert!(content.contains("  module OrderedData\n    include ::Comparable\n"));
+    assert!(content
+        .contains("return VARIANT_INDEX <=> other.class::VARIANT_INDEX unless other.is_a?(Flag)"));
+    assert!(content.contains("(self.value <=> other.value).nonzero? ||"));
+    assert!(content.contains("def compare_map_str_to_u16(a, b)"));
+}
+
+#[test]
+fn test_ruby_code_rejects_nested_options() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Foo {
+        bar: Option<Option<u8>>,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let error = ruby::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Ruby does not support options of options or of unit values (in Foo)"
+    );
+}
+
+#[test]
+fn test_ruby_code_rejects_lowercase_type_names() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let naming = NamingConventions::default().with_types(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = ruby::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Ruby does not support names of types and variants that do not start with an uppercase letter (in choice)"
+    );
+}
+
+#[test]
+fn test_ruby_code_rejects_reserved_words() {
+    #[derive(Deserialize)]
+    #[allow(dead_code, non_snake_case)]
+    struct Foo {
+        End: u8,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Foo>().unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let naming = NamingConventions::default().with_fields(Case::Snake);
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_naming_conventions(naming);
+    let error = ruby::CodeGenerator::new(&config)
+        .generate(&registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Ruby name `end` of Foo.End is a reserved word"
+    );
+}
+
+#[test]
+fn test_ruby_installer_rejects_unsupported_runtimes() {
+    let dir = tempdir().unwrap();
+    let installer = ruby::Installer::new(dir.path().to_path_buf());
+    installer.install_serde_runtime().unwrap();
+    installer.install_bcs_runtime().unwrap();
+    assert!(dir.path().join("serde.rb").exists());
+    assert!(dir.path().join("bcs.rb").exists());
+
+    let error = installer.install_postcard_runtime().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Ruby does not support the postcard encoding"
+    );
+}
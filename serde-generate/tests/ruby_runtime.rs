@@ -0,0 +1,297 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::test_utils;
+use crate::test_utils::{Choice, Runtime, Test};
+use serde_generate::{ruby, BincodeOptions, CodeGeneratorConfig, SourceInstaller, TrailingBytes};
+use std::{fs::File, io::Write, path::Path, process::Command};
+use tempfile::tempdir;
+
+/// Run `main.rb` with the runtime and the generated code in the load path.
+fn run_ruby_main(dir: &Path) {
+    let status = Command::new("ruby")
+        .arg("-w")
+        .arg("-I")
+        .arg("runtime/ruby")
+        .arg("-I")
+        .arg(dir)
+        .arg(dir.join("main.rb"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+fn quote_bytes(bytes: &[u8]) -> String {
+    format!(
+        "[{}].pack('C*')",
+        bytes
+            .iter()
+            .map(|x| format!("{}", x))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+#[test]
+fn test_ruby_bcs_runtime_on_simple_data() {
+    test_ruby_runtime_on_simple_data(Runtime::Bcs);
+}
+
+#[test]
+fn test_ruby_bincode_runtime_on_simple_data() {
+    test_ruby_runtime_on_simple_data(Runtime::Bincode);
+}
+
+#[test]
+fn test_ruby_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_ruby_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+fn test_ruby_runtime_on_simple_data(runtime: Runtime) {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let installer = ruby::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    let reference = runtime.serialize(&Test {
+        a: vec![4, 6],
+        b: (-3, 5),
+        c: Choice::C { x: 7 },
+    });
+
+    let mut source = File::create(dir.path().join("main.rb")).unwrap();
+    writeln!(
+        source,
+        r#"# frozen_string_literal: true
+
+require 'testing'
+
+input = {0}
+
+value = Testing::Test.{1}_deserialize(input)
+
+value2 = Testing::Test.new(a: [4, 6], b: [-3, 5], c: Testing::Choice::C.new(x: 7))
+raise 'unexpected value' unless value == value2
+raise 'unexpected variant' unless value.c.is_a?(Testing::Choice)
+
+output = value2.{1}_serialize
+raise 'unexpected output' unless output == input
+
+input2 = input + [1].pack('C')
+begin
+  Testing::Test.{1}_deserialize(input2)
+  raise 'trailing bytes should be rejected'
+rescue Serde::DeserializationError
+  # All good
+end
+"#,
+        quote_bytes(&reference),
+        runtime.name(),
+    )
+    .unwrap();
+
+    run_ruby_main(dir.path());
+}
+
+#[test]
+fn test_ruby_bcs_runtime_on_supported_types() {
+    test_ruby_runtime_on_supported_types(Runtime::Bcs);
+}
+
+#[test]
+fn test_ruby_bincode_runtime_on_supported_types() {
+    test_ruby_runtime_on_supported_types(Runtime::Bincode);
+}
+
+#[test]
+fn test_ruby_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_ruby_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+fn test_ruby_runtime_on_supported_types(runtime: Runtime) {
+    let registry = runtime.get_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let installer = ruby::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    let positive_encodings: Vec<_> = runtime
+        .get_positive_samples_quick()
+        .iter()
+        .map(|bytes| quote_bytes(bytes))
+        .collect();
+
+    let negative_encodings: Vec<_> = runtime
+        .get_negative_samples()
+        .iter()
+        .map(|bytes| quote_bytes(bytes))
+        .collect();
+
+    let mut source = File::create(dir.path().join("main.rb")).unwrap();
+    writeln!(
+        source,
+        r#"# frozen_string_literal: true
+
+require 'testing'
+
+positive_inputs = [
+  {0}
+]
+negative_inputs = [
+  {1}
+]
+
+positive_inputs.each do |input|
+  value = Testing::SerdeData.{2}_deserialize(input)
+  output = value.{2}_serialize
+  raise "Failed to re-serialize #{{input.bytes}}" unless output == input
+
+  # Test self-equality for the Serde value.
+  value2 = Testing::SerdeData.{2}_deserialize(input)
+  raise "Unexpected value for #{{input.bytes}}" unless value == value2
+
+  # Test simple mutations of the input.
+  input.bytesize.times do |i|
+    input2 = input.dup
+    input2.setbyte(i, input2.getbyte(i) ^ 0x80)
+    begin
+      value3 = Testing::SerdeData.{2}_deserialize(input2)
+      raise "Mutation of #{{input.bytes}} at #{{i}} gave the same value" if value3 == value
+    rescue Serde::DeserializationError
+      # All good
+    end
+  end
+end
+
+negative_inputs.each do |input|
+  begin
+    Testing::SerdeData.{2}_deserialize(input)
+  rescue Serde::DeserializationError
+    next
+  end
+  raise "Input should fail to deserialize: #{{input.bytes}}"
+end
+"#,
+        positive_encodings.join(",\n  "),
+        negative_encodings.join(",\n  "),
+        runtime.name(),
+    )
+    .unwrap();
+
+    run_ruby_main(dir.path());
+}
+
+#[test]
+fn test_ruby_runtime_with_bincode_limit_and_trailing_bytes() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let installer = ruby::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    let mut source = File::create(dir.path().join("main.rb")).unwrap();
+    writeln!(
+        source,
+        r#"# frozen_string_literal: true
+
+require 'testing'
+
+input = {0}
+value = Testing::Test.bincode_deserialize(input + [0].pack('C'))
+raise 'unexpected output' unless value.bincode_serialize == input
+
+begin
+  Testing::Test.bincode_deserialize({1})
+  raise 'the size limit should be enforced'
+rescue Serde::DeserializationError => e
+  raise 'unexpected error' unless e.message.include?('limit')
+end
+"#,
+        quote_bytes(&reference),
+        quote_bytes(&too_large),
+    )
+    .unwrap();
+
+    run_ruby_main(dir.path());
+}
+
+#[test]
+fn test_ruby_runtime_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Runtime::Bcs.into()])
+        .with_comparisons(true);
+    let installer = ruby::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+
+    // Values are sorted in the order of Rust.
+    let inputs = test_utils::get_ordered_values()
+        .iter()
+        .map(|value| quote_bytes(&Runtime::Bcs.serialize(value)))
+        .collect::<Vec<_>>()
+        .join(",\n  ");
+
+    let mut source = File::create(dir.path().join("main.rb")).unwrap();
+    writeln!(
+        source,
+        r#"# frozen_string_literal: true
+
+require 'testing'
+
+inputs = [
+  {0}
+]
+values = inputs.map {{ |input| Testing::OrderedData.bcs_deserialize(input) }}
+values.each_with_index do |x, i|
+  values.each_with_index do |y, j|
+    raise "unexpected comparison: #{{i}} vs #{{j}}" unless (x <=> y) == (i <=> j)
+    raise "unexpected equality: #{{i}} vs #{{j}}" unless (x == y) == (i == j)
+    raise "unexpected eql?: #{{i}} vs #{{j}}" unless x.eql?(y) == (i == j)
+  end
+  raise 'unexpected hash' unless x.hash == Testing::OrderedData.bcs_deserialize(inputs[i]).hash
+end
+raise 'unexpected sort' unless values.reverse.sort == values
+raise 'unexpected uniq' unless values.uniq.size == values.size
+"#,
+        inputs
+    )
+    .unwrap();
+
+    run_ruby_main(dir.path());
+}
+
+#[test]
+fn test_ruby_bcs_runtime_autotest() {
+    let status = Command::new("ruby")
+        .arg("-w")
+        .arg("runtime/ruby/test_bcs.rb")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
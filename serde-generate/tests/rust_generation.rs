@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use serde_generate::{rust, CodeGeneratorConfig};
+use serde_generate::{rust, Case, CodeGeneratorConfig, Encoding, NamingConventions, NativeType};
 use std::{fs::File, io::Write, process::Command};
 use tempfile::{tempdir, TempDir};
 
@@ -167,3 +167,176 @@ fn test_that_rust_code_compiles_with_custom_code() {
     let content = std::fs::read_to_string(source_path).unwrap();
     assert!(content.contains("// custom code\n"));
 }
+
+#[test]
+fn test_rust_code_for_tagged_enum() {
+    #[derive(serde::Serialize)]
+    #[serde(tag = "type")]
+    enum Event {
+        Start { time: u64 },
+        Stop,
+    }
+
+    let mut tracer = serde_reflection::Tracer::new(
+        serde_reflection::TracerConfig::default().is_human_readable(true),
+    );
+    let mut samples = serde_reflection::Samples::new();
+    tracer
+        .trace_tagged_enum(
+            &mut samples,
+            "Event",
+            serde_reflection::EnumRepresentation::Internal { tag: "type".into() },
+            &[("Start", Event::Start { time: 0 }), ("Stop", Event::Stop)],
+        )
+        .unwrap();
+    let registry = tracer.registry().unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let mut source = Vec::new();
+    rust::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+    let content = String::from_utf8(source).unwrap();
+    assert!(content.contains("Deserialize)]\n#[serde(tag = \"type\")]\npub enum Event {"));
+    assert!(content.contains("    Start {\n        time: u64,\n    },\n    Stop,\n"));
+}
+
+#[test]
+fn test_rust_capabilities() {
+    let registry = test_utils::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_c_style_enums(true)
+        .with_encodings(vec![Encoding::Bincode, Encoding::Bcs]);
+    rust::CAPABILITIES.check(&registry, &config).unwrap();
+}
+
+#[test]
+fn test_rust_code_rejects_naming_conventions() {
+    let registry = test_utils::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_naming_conventions(NamingConventions::new(Case::LowerCamel));
+    let mut source = Vec::new();
+    let error = rust::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Rust does not support naming conventions"
+    );
+}
+
+#[test]
+fn test_that_rust_code_with_open_enums_runs() {
+    let mut registry = serde_reflection::Registry::new();
+    registry.insert(
+        "Event".to_string(),
+        serde_reflection::ContainerFormat::OpenEnum(
+            vec![
+                ("A".to_string(), serde_reflection::Format::U8),
+                ("B".to_string(), serde_reflection::Format::Str),
+            ]
+            .into_iter()
+            .collect(),
+        ),
+    );
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.rs");
+    let mut source = File::create(&source_path).unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_serialization(false);
+    rust::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+    writeln!(
+        &mut source,
+        r#"
+fn main() {{
+    assert_eq!(Event::TAGS, &["A", "B"]);
+    let mut handlers = EventHandlers::new();
+    handlers.register("A", |content| content[0]);
+    let event = |tag: &str| Event {{
+        tag: tag.to_string(),
+        content: vec![7],
+    }};
+    assert_eq!(handlers.handle(&event("A")), Some(7));
+    assert_eq!(handlers.handle(&event("C")), None);
+}}
+"#
+    )
+    .unwrap();
+
+    let status = Command::new("rustc")
+        .current_dir(dir.path())
+        .arg("--edition")
+        .arg("2018")
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let status = Command::new(dir.path().join("test")).status().unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_rust_code_rejects_native_types() {
+    let registry = test_utils::get_registry_with_native_types().unwrap();
+    let native_types = maplit::btreemap! {
+        "Timestamp".to_string() => NativeType::new(
+            "std::time::SystemTime".to_string(),
+            "serialize_system_time".to_string(),
+            "deserialize_system_time".to_string(),
+        ),
+    };
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_native_types(native_types);
+    let mut source = Vec::new();
+    let error = rust::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Rust does not support native types");
+}
+
+#[test]
+fn test_that_rust_code_compiles_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.rs");
+    let mut source = File::create(&source_path).unwrap();
+
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_serialization(false)
+        .with_comparisons(true);
+    let generator = rust::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+    writeln!(
+        source,
+        "pub fn check(x: &OrderedData) -> std::collections::BTreeSet<&OrderedData> {{ Some(x).into_iter().collect() }}"
+    )
+    .unwrap();
+
+    let status = Command::new("rustc")
+        .current_dir(dir.path())
+        .arg("--crate-type")
+        .arg("lib")
+        .arg("--edition")
+        .arg("2018")
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let content = std::fs::read_to_string(source_path).unwrap();
+    assert!(content.contains("#[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Hash, Ord)]"));
+}
+
+#[test]
+fn test_rust_code_rejects_comparisons_of_floats() {
+    let registry = test_utils::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comparisons(true);
+    let mut source = Vec::new();
+    let error = rust::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Rust does not support comparisons of floating point numbers (in PrimitiveTypes)"
+    );
+}
@@ -17,6 +17,16 @@ fn test_rust_bincode_runtime() {
     test_rust_runtime(Runtime::Bincode);
 }
 
+#[test]
+fn test_rust_bincode_varint_big_endian_runtime() {
+    test_rust_runtime(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_rust_postcard_runtime() {
+    test_rust_runtime(Runtime::Postcard);
+}
+
 // Full test using cargo. This may take a while.
 fn test_rust_runtime(runtime: Runtime) {
     let registry = test_utils::get_registry().unwrap();
@@ -59,7 +69,7 @@ serde_bytes = "0.11"
         r#"
 fn main() {{
     for encoding in vec![{}] {{
-        let value = {}::<SerdeData>(&encoding).unwrap();
+        let value: SerdeData = {}(&encoding).unwrap();
         let s = {}(&value).unwrap();
         assert_eq!(s, encoding);
     }}
@@ -7,7 +7,7 @@ use serde::{
     de::DeserializeOwned,
     {Deserialize, Serialize},
 };
-use serde_generate::{solidity, CodeGeneratorConfig};
+use serde_generate::{solidity, CodeGeneratorConfig, Encoding};
 use serde_reflection::Samples;
 use serde_reflection::{Registry, Tracer, TracerConfig};
 use std::path::Path;
@@ -150,3 +150,100 @@ fn test_solidity_compilation() {
 
     get_bytecode(path, "test.sol", "test").unwrap();
 }
+
+#[test]
+fn test_solidity_compilation_with_comparisons() {
+    let name = "test".to_string();
+    let config = CodeGeneratorConfig::new(name).with_comparisons(true);
+    let registry = get_solidity_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let path = dir.path();
+    let test_path = path.join("test.sol");
+    {
+        let mut test_file = File::create(&test_path).unwrap();
+        let generator = solidity::CodeGenerator::new(&config);
+        generator.output(&mut test_file, &registry).unwrap();
+    }
+
+    get_bytecode(path, "test.sol", "test").unwrap();
+}
+
+#[test]
+fn test_solidity_code_with_comparisons() {
+    let registry = get_solidity_registry().unwrap();
+    let config = CodeGeneratorConfig::new("test".to_string());
+    let mut content = Vec::new();
+    solidity::CodeGenerator::new(&config)
+        .output(&mut content, &registry)
+        .unwrap();
+    let content = String::from_utf8(content).unwrap();
+    assert!(!content.contains("function compare_"));
+
+    let config = config.with_comparisons(true);
+    let mut content = Vec::new();
+    solidity::CodeGenerator::new(&config)
+        .output(&mut content, &registry)
+        .unwrap();
+    let content = String::from_utf8(content).unwrap();
+    for name in ["SerdeData", "PrimitiveTypes", "seq_Struct", "opt_bytes1"] {
+        assert!(content.contains(&format!("function compare_{name}(")));
+        assert!(content.contains(&format!("function equal_{name}(")));
+        assert!(content.contains(&format!("function hash_{name}(")));
+    }
+    // Entries of maps are compared in the order of their keys.
+    assert!(content.contains("function next_seq_key_values_"));
+}
+
+#[test]
+fn test_solidity_code_rejects_floats() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Point>().unwrap();
+    let registry = tracer.registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let error = solidity::CodeGenerator::new(&config)
+        .output(&mut Vec::new(), &registry)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Solidity does not support floating point numbers (in Point.x)"
+    );
+}
+
+#[test]
+fn test_solidity_capabilities() {
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    enum List {
+        Empty,
+        Node(u64, Box<List>),
+    }
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<List>().unwrap();
+    let registry = tracer.registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string());
+    let error = solidity::CAPABILITIES
+        .check(&registry, &config)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Solidity does not support recursive types (in List)"
+    );
+
+    let registry = Registry::new();
+    let config = config.with_encodings(vec![Encoding::Bincode]);
+    let error = solidity::CAPABILITIES
+        .check(&registry, &config)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Solidity does not support the bincode encoding"
+    );
+}
@@ -1,4 +1,5 @@
 use crate::solidity_generation::{get_bytecode, get_registry_from_type};
+use crate::test_utils::{self, OrderedData};
 use alloy_sol_types::sol;
 use alloy_sol_types::SolCall as _;
 use revm::db::InMemoryDB;
@@ -577,3 +578,83 @@ contract ExampleCode {{
     test_contract(bytecode.clone(), fct_args);
     Ok(())
 }
+
+#[test]
+fn test_comparisons() -> anyhow::Result<()> {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let path = dir.path();
+
+    // The library code
+    let test_library_path = path.join("Library.sol");
+    {
+        let mut test_library_file = File::create(&test_library_path)?;
+        let name = "Library".to_string();
+        let config = CodeGeneratorConfig::new(name).with_comparisons(true);
+        let generator = solidity::CodeGenerator::new(&config);
+        generator.output(&mut test_library_file, &registry).unwrap();
+    }
+
+    // Values are sorted in the order of Rust. Solidity only deserializes ASCII strings.
+    let inputs = test_utils::get_ordered_values()
+        .into_iter()
+        .filter(|value| !matches!(value, OrderedData::Text { name, .. } if !name.is_ascii()))
+        .map(|value| bcs::to_bytes(&value).unwrap())
+        .collect::<Vec<_>>();
+    let len = inputs.len();
+    let inputs = inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| format!("      inputs[{i}] = hex\"{}\";", hex::encode(input)))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // The test code
+    let test_code_path = path.join("test_code.sol");
+    {
+        let mut test_code_file = File::create(&test_code_path)?;
+
+        writeln!(
+            test_code_file,
+            r#"/// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.0;
+
+import "./Library.sol";
+
+contract ExampleCode {{
+
+    function test_comparisons() external {{
+      bytes[] memory inputs = new bytes[]({len});
+{inputs}
+      Library.OrderedData[] memory values = new Library.OrderedData[]({len});
+      for (uint256 i=0; i<{len}; i++) {{
+        values[i] = Library.bcs_deserialize_OrderedData(inputs[i]);
+      }}
+      for (uint256 i=0; i<{len}; i++) {{
+        Library.OrderedData memory value = Library.bcs_deserialize_OrderedData(inputs[i]);
+        require(Library.hash_OrderedData(value) == Library.hash_OrderedData(values[i]));
+        for (uint256 j=0; j<{len}; j++) {{
+          int8 expected = i < j ? int8(-1) : (i > j ? int8(1) : int8(0));
+          require(Library.compare_OrderedData(values[i], values[j]) == expected);
+          require(Library.equal_OrderedData(values[i], values[j]) == (i == j));
+        }}
+      }}
+    }}
+
+}}
+"#
+        )?;
+    }
+
+    // Compiling the code and reading it.
+    let bytecode = get_bytecode(path, "test_code.sol", "ExampleCode")?;
+
+    // Building the input to the smart contract
+    sol! {
+        function test_comparisons();
+    }
+    let fct_args = test_comparisonsCall {}.abi_encode().into();
+
+    test_contract(bytecode, fct_args);
+    Ok(())
+}
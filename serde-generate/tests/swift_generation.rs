@@ -171,6 +171,7 @@ fn test_swift_code_with_external_definitions() {
     // References were updated.
     let content = std::fs::read_to_string(source_path).unwrap();
     assert!(content.contains("foo.Tree"));
+    assert!(content.contains("import Serde\nimport foo\n"));
 }
 
 #[test]
@@ -205,3 +206,54 @@ fn test_that_swift_code_follow_case_convention() {
     assert!(content.contains(r#"@Indirect public var fBool: Bool"#));
     assert!(!content.contains(r#"@Indirect public var f_bool: Bool"#));
 }
+
+#[test]
+fn test_that_swift_code_compiles_with_comparisons() {
+    let config = CodeGeneratorConfig::new("Testing".to_string()).with_comparisons(true);
+
+    let (_dir, source_path) = test_that_swift_code_compiles_with_config_and_registry(
+        &config,
+        &test_utils::get_ordered_registry().unwrap(),
+    );
+    let content = std::fs::read_to_string(source_path).unwrap();
+    assert!(content.contains("indirect public enum OrderedData: Hashable, Comparable {"));
+    assert!(content.contains("case let (.text(a0, a1), .text(b0, b1)):"));
+    assert!(content.contains("return lhs.variantIndex < rhs.variantIndex"));
+}
+
+#[test]
+fn test_that_swift_code_compiles_with_validation() {
+    let config = CodeGeneratorConfig::new("Testing".to_string())
+        .with_encodings(vec![Encoding::Bcs])
+        .with_validate_on_serialize(true);
+
+    let (_dir, source_path) = test_that_swift_code_compiles_with_config_and_registry(
+        &config,
+        &test_utils::get_ordered_registry().unwrap(),
+    );
+    let content = std::fs::read_to_string(source_path).unwrap();
+    assert!(content.contains("case .pair(let x):"));
+    assert!(content.contains("if value.count != 2 {"));
+    assert!(content.contains("try self.validate()"));
+}
+
+#[test]
+fn test_that_swift_code_compiles_with_json() {
+    let config = CodeGeneratorConfig::new("Testing".to_string())
+        .with_encodings(vec![Encoding::Bcs, Encoding::Json]);
+    let registry = test_utils::json_data::get_registry().unwrap();
+    let mut source = Vec::new();
+    swift::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+    let content = String::from_utf8(source).unwrap();
+    assert!(
+        content.contains("public static func jsonDeserialize(input: String) throws -> SerdeData {")
+    );
+    assert!(content.contains(
+        "json.put(key: \"f_stringmap\", value: try to_json_map_str_to_u32(value: self.fStringmap))"
+    ));
+    assert!(content.contains("json.sortKeys(by: JsonObject.numberOrder)"));
+
+    test_that_swift_code_compiles_with_config_and_registry(&config, &registry);
+}
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use crate::test_utils::{Choice, Runtime, Test};
-use serde_generate::{swift, CodeGeneratorConfig, SourceInstaller};
+use crate::test_utils::{json_data, Choice, Runtime, Test};
+use serde_generate::{
+    swift, BincodeOptions, CodeGeneratorConfig, Encoding, SourceInstaller, TrailingBytes,
+};
 use std::{fs::File, io::Write, process::Command, sync::Mutex};
 
 lazy_static::lazy_static! {
@@ -37,14 +39,35 @@ fn test_swift_bincode_runtime_on_simple_data() {
     test_swift_runtime_on_simple_data(Runtime::Bincode);
 }
 
+#[test]
+fn test_swift_bincode_varint_big_endian_runtime_on_simple_data() {
+    test_swift_runtime_on_simple_data(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_swift_postcard_runtime_on_simple_data() {
+    test_swift_runtime_on_simple_data(Runtime::Postcard);
+}
+
+#[test]
+fn test_swift_borsh_runtime_on_simple_data() {
+    test_swift_runtime_on_simple_data(Runtime::Borsh);
+}
+
+#[test]
+fn test_swift_scale_runtime_on_simple_data() {
+    test_swift_runtime_on_simple_data(Runtime::Scale);
+}
+
 fn test_swift_runtime_on_simple_data(runtime: Runtime) {
     // To see the source, uncomment this and replace `dir.path()` by `my_path` below.
     // let my_path = std::path::Path::new("../test");
     // std::fs::remove_dir_all(my_path).unwrap_or(());
     // std::fs::create_dir_all(my_path).unwrap();
     let dir = tempfile::tempdir().unwrap();
-    let config =
-        CodeGeneratorConfig::new("Testing".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("Testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
     let registry = test_utils::get_simple_registry().unwrap();
     let installer = swift::Installer::new(dir.path().to_path_buf());
     installer.install_module(&config, &registry).unwrap();
@@ -138,6 +161,101 @@ let package = Package(
     }
 }
 
+#[test]
+fn test_swift_runtime_with_bincode_limit_and_trailing_bytes() {
+    let dir = tempfile::tempdir().unwrap();
+    let reference = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let too_large = Runtime::Bincode.serialize(&Test {
+        a: vec![4, 6, 8],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    });
+    let config = CodeGeneratorConfig::new("Testing".to_string())
+        .with_encodings(vec![Runtime::Bincode.into()])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_limit(Some(reference.len() as u64))
+                .with_trailing_bytes(TrailingBytes::Allow),
+        );
+    let registry = test_utils::get_simple_registry().unwrap();
+    let installer = swift::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+    installer.install_serde_runtime().unwrap(); // also installs bcs and bincode
+
+    let to_swift_bytes = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .map(|x| format!("{}", x))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    std::fs::create_dir_all(dir.path().join("Sources/main")).unwrap();
+    let main_path = dir.path().join("Sources/main/main.swift");
+    let mut main = File::create(main_path).unwrap();
+    writeln!(
+        main,
+        r#"
+import Serde
+import Testing
+
+let value = try Test.bincodeDeserialize(input: [{0}, 0])
+let output = try value.bincodeSerialize()
+assert(output == [{0}], "unexpected output")
+
+do {{
+    let _ = try Test.bincodeDeserialize(input: [{1}])
+    assertionFailure("Was expecting an error")
+}}
+catch DeserializationError.invalidInput(let issue) {{
+    assert(issue.contains("limit"), "Was expecting a limit error")
+}}
+"#,
+        to_swift_bytes(&reference),
+        to_swift_bytes(&too_large),
+    )
+    .unwrap();
+
+    let mut file = File::create(dir.path().join("Package.swift")).unwrap();
+    write!(
+        file,
+        r#"// swift-tools-version:5.3
+
+import PackageDescription
+
+let package = Package(
+    name: "Testing",
+    targets: [
+        .target(
+            name: "Serde",
+            dependencies: []),
+        .target(
+            name: "Testing",
+            dependencies: ["Serde"]),
+        .target(
+            name: "main",
+            dependencies: ["Serde", "Testing"]
+        ),
+    ]
+)
+"#
+    )
+    .unwrap();
+
+    {
+        let _lock = MUTEX.lock().unwrap();
+        let status = Command::new("swift")
+            .current_dir(dir.path())
+            .arg("run")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+}
+
 #[test]
 fn test_swift_bcs_runtime_on_supported_types() {
     test_swift_runtime_on_supported_types(Runtime::Bcs);
@@ -148,6 +266,26 @@ fn test_swift_bincode_runtime_on_supported_types() {
     test_swift_runtime_on_supported_types(Runtime::Bincode);
 }
 
+#[test]
+fn test_swift_bincode_varint_big_endian_runtime_on_supported_types() {
+    test_swift_runtime_on_supported_types(Runtime::BincodeVarintBigEndian);
+}
+
+#[test]
+fn test_swift_postcard_runtime_on_supported_types() {
+    test_swift_runtime_on_supported_types(Runtime::Postcard);
+}
+
+#[test]
+fn test_swift_borsh_runtime_on_supported_types() {
+    test_swift_runtime_on_supported_types(Runtime::Borsh);
+}
+
+#[test]
+fn test_swift_scale_runtime_on_supported_types() {
+    test_swift_runtime_on_supported_types(Runtime::Scale);
+}
+
 fn quote_bytes(bytes: &[u8]) -> String {
     format!(
         "[{}]",
@@ -165,9 +303,10 @@ fn test_swift_runtime_on_supported_types(runtime: Runtime) {
     // std::fs::remove_dir_all(my_path).unwrap_or(());
     // std::fs::create_dir_all(my_path).unwrap();
     let dir = tempfile::tempdir().unwrap();
-    let config =
-        CodeGeneratorConfig::new("Testing".to_string()).with_encodings(vec![runtime.into()]);
-    let registry = test_utils::get_registry().unwrap();
+    let config = CodeGeneratorConfig::new("Testing".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
+    let registry = runtime.get_registry().unwrap();
     let installer = swift::Installer::new(dir.path().to_path_buf());
     installer.install_module(&config, &registry).unwrap();
     installer.install_serde_runtime().unwrap(); // also installs bcs and bincode
@@ -268,3 +407,129 @@ let package = Package(
         assert!(status.success());
     }
 }
+
+/// Quote a string as a Swift string literal.
+fn quote_string(input: &str) -> String {
+    let mut result = String::from("\"");
+    for c in input.chars() {
+        match c {
+            '"' | '\\' => {
+                result.push('\\');
+                result.push(c);
+            }
+            ' '..='~' => result.push(c),
+            _ => result += &format!("\\u{{{:x}}}", c as u32),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[test]
+fn test_swift_json_runtime_on_supported_types() {
+    let dir = tempfile::tempdir().unwrap();
+    let config =
+        CodeGeneratorConfig::new("Testing".to_string()).with_encodings(vec![Encoding::Json]);
+    let registry = json_data::get_registry().unwrap();
+    let installer = swift::Installer::new(dir.path().to_path_buf());
+    installer.install_module(&config, &registry).unwrap();
+    installer.install_serde_runtime().unwrap();
+    installer.install_json_runtime().unwrap();
+
+    std::fs::create_dir_all(dir.path().join("Sources/main")).unwrap();
+    let main_path = dir.path().join("Sources/main/main.swift");
+    let mut main = File::create(main_path).unwrap();
+
+    let positive_inputs = json_data::get_positive_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect::<Vec<_>>()
+        .join(",\n    ");
+    let alternate_inputs = json_data::get_alternate_samples()
+        .iter()
+        .map(|(input, output)| format!("({}, {})", quote_string(input), quote_string(output)))
+        .collect::<Vec<_>>()
+        .join(",\n    ");
+    let negative_inputs = json_data::get_negative_samples()
+        .iter()
+        .map(|input| quote_string(input))
+        .collect::<Vec<_>>()
+        .join(",\n    ");
+
+    writeln!(
+        main,
+        r#"
+import Serde
+import Testing
+
+let positive_inputs : [String] = [
+    {0}
+]
+let alternate_inputs : [(String, String)] = [
+    {1}
+]
+let negative_inputs : [String] = [
+    {2}
+]
+
+for input in positive_inputs {{
+    let value = try SerdeData.jsonDeserialize(input: input)
+    let output = try value.jsonSerialize()
+    assert(Array(input.utf8) == Array(output.utf8), "input != output:\n  \(input)\n  \(output)")
+
+    // Test self-equality for the Serde value.
+    let value2 = try SerdeData.jsonDeserialize(input: input)
+    assert(value == value2, "Deserialized value should test equal to itself: \(input)")
+}}
+
+for (input, expected) in alternate_inputs {{
+    let output = try SerdeData.jsonDeserialize(input: input).jsonSerialize()
+    assert(Array(expected.utf8) == Array(output.utf8), "expected != output:\n  \(expected)\n  \(output)")
+}}
+
+for input in negative_inputs {{
+    if let _ = try? SerdeData.jsonDeserialize(input: input) {{
+        assertionFailure("Input should fail to deserialize: \(input)")
+    }}
+}}
+"#,
+        positive_inputs, alternate_inputs, negative_inputs,
+    )
+    .unwrap();
+
+    let mut file = File::create(dir.path().join("Package.swift")).unwrap();
+    write!(
+        file,
+        r#"// swift-tools-version:5.3
+
+import PackageDescription
+
+let package = Package(
+    name: "Testing",
+    targets: [
+        .target(
+            name: "Serde",
+            dependencies: []),
+        .target(
+            name: "Testing",
+            dependencies: ["Serde"]),
+        .target(
+            name: "main",
+            dependencies: ["Serde", "Testing"]
+        ),
+    ]
+)
+"#
+    )
+    .unwrap();
+
+    {
+        let _lock = MUTEX.lock().unwrap();
+        let status = Command::new("swift")
+            .current_dir(dir.path())
+            .arg("run")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+}
@@ -1,13 +1,22 @@
 // Copyright (c) Facebook, Inc. and its affiliates
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use bincode::Options;
 use maplit::btreemap;
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
-use serde_generate::Encoding;
-use serde_reflection::{Registry, Result, Samples, Tracer, TracerConfig};
+use serde_generate::{BincodeOptions, Encoding, Endianness, IntEncoding};
+use serde_reflection::{
+    ContainerFormat, Format, Named, Registry, Result, Samples, Tracer, TracerConfig,
+};
 use std::collections::BTreeMap;
 
+pub mod borsh_data;
+mod borsh_format;
+pub mod json_data;
+pub mod scale_data;
+mod scale_format;
+
 // Simple data formats used to create and test values in each language.
 #[derive(Serialize, Deserialize)]
 pub struct Test {
@@ -139,6 +148,175 @@ pub fn get_registry() -> Result<Registry> {
     tracer.registry()
 }
 
+/// The format of a struct with the given fields.
+pub fn struct_format(fields: &[(&str, Format)]) -> ContainerFormat {
+    ContainerFormat::Struct(
+        fields
+            .iter()
+            .map(|(name, value)| Named {
+                name: name.to_string(),
+                value: value.clone(),
+            })
+            .collect(),
+    )
+}
+
+/// A reference to the container `name`.
+pub fn type_name(name: &str) -> Format {
+    Format::TypeName(name.to_string())
+}
+
+/// A registry with qualified container names, for code generation in several modules.
+/// Modules `a` and `b` refer to each other, and `c` refers to `a`.
+pub fn get_registry_with_modules() -> Registry {
+    let mut registry = Registry::new();
+    registry.insert(
+        "a::Foo".to_string(),
+        struct_format(&[("bar", Format::Option(Box::new(type_name("b::Bar"))))]),
+    );
+    registry.insert(
+        "b::Bar".to_string(),
+        struct_format(&[("foo", Format::Seq(Box::new(type_name("a::Foo"))))]),
+    );
+    registry.insert(
+        "c::Baz<a::Foo>".to_string(),
+        struct_format(&[("value", type_name("a::Foo")), ("qux", type_name("Qux"))]),
+    );
+    registry.insert(
+        "Qux".to_string(),
+        ContainerFormat::NewTypeStruct(Box::new(Format::U64)),
+    );
+    registry
+}
+
+// Variant of the open enum `Event` below.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Click {
+    pub x: u32,
+}
+
+/// A registry with a log of events, where `Event` is an open enum whose only known variant
+/// has the tag `Click`.
+pub fn get_registry_with_open_enum() -> Registry {
+    let mut registry = Registry::new();
+    registry.insert("Click".to_string(), struct_format(&[("x", Format::U32)]));
+    registry.insert(
+        "Event".to_string(),
+        ContainerFormat::OpenEnum(
+            vec![("Click".to_string(), type_name("Click"))]
+                .into_iter()
+                .collect(),
+        ),
+    );
+    registry.insert(
+        "Log".to_string(),
+        struct_format(&[("events", Format::Seq(Box::new(type_name("Event"))))]),
+    );
+    registry
+}
+
+/// The encoding of a log with an event `Click { x: 7 }` and an event with the tag `Scroll`,
+/// unknown to the generated code.
+pub fn get_open_enum_log(runtime: Runtime) -> Vec<u8> {
+    let events = vec![
+        ("Click", ByteBuf::from(runtime.serialize(&Click { x: 7 }))),
+        ("Scroll", ByteBuf::from(vec![1, 2, 3])),
+    ];
+    runtime.serialize(&events)
+}
+
+// Data formats without floating point numbers, used to test generated comparisons.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OrderedData {
+    Empty,
+    Flag(bool),
+    Numbers(u8, i16, u32, i64, u64),
+    Text { name: String, bytes: ByteBuf },
+    List(Vec<Option<u32>>),
+    Entries(BTreeMap<String, u16>),
+    Pair((Label, [u16; 2])),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Label(pub String);
+
+pub fn get_ordered_registry() -> Result<Registry> {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let samples = Samples::new();
+    tracer.trace_type::<OrderedData>(&samples)?;
+    tracer.registry()
+}
+
+/// Sample values in increasing order, with edge cases for each format.
+pub fn get_ordered_values() -> Vec<OrderedData> {
+    let text = |name: &str, bytes: &[u8]| OrderedData::Text {
+        name: name.to_string(),
+        bytes: ByteBuf::from(bytes.to_vec()),
+    };
+    let entries = |entries: &[(&str, u16)]| {
+        OrderedData::Entries(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), *value))
+                .collect(),
+        )
+    };
+    let pair = |label: &str, array| OrderedData::Pair((Label(label.to_string()), array));
+    let mut values = vec![
+        OrderedData::Empty,
+        OrderedData::Flag(false),
+        OrderedData::Flag(true),
+        OrderedData::Numbers(0, -1, 0, 0, 0),
+        OrderedData::Numbers(0, 1, 0, 0, 0),
+        OrderedData::Numbers(200, -5, 0, 0, 0),
+        OrderedData::Numbers(200, -5, 3_000_000_000, -1, 0),
+        OrderedData::Numbers(200, -5, 3_000_000_000, -1, 1 << 63),
+        text("", &[]),
+        text("", &[1]),
+        text("", &[1, 200]),
+        text("", &[2]),
+        text("a", &[]),
+        text("b", &[]),
+        text("\u{e000}", &[]),
+        text("\u{1f600}", &[]),
+        OrderedData::List(vec![]),
+        OrderedData::List(vec![None]),
+        OrderedData::List(vec![None, Some(1)]),
+        OrderedData::List(vec![Some(0)]),
+        OrderedData::List(vec![Some(4_000_000_000)]),
+        entries(&[]),
+        entries(&[("a", 2)]),
+        entries(&[("a", 2), ("b", 1)]),
+        entries(&[("a", 3)]),
+        entries(&[("aa", 1), ("b", 0)]),
+        entries(&[("b", 0)]),
+        pair("", [0, 0]),
+        pair("", [0, 40000]),
+        pair("", [1, 0]),
+        pair("x", [0, 0]),
+    ];
+    values.sort();
+    values
+}
+
+// Data formats where `Timestamp` is meant to be replaced by a native type.
+#[derive(Serialize, Deserialize)]
+pub struct Event {
+    pub name: String,
+    pub at: Timestamp,
+    pub history: Vec<Option<Timestamp>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Timestamp(pub u64);
+
+pub fn get_registry_with_native_types() -> Result<Registry> {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let samples = Samples::new();
+    tracer.trace_type::<Event>(&samples)?;
+    tracer.registry()
+}
+
 /// Manually generate sample values.
 /// Avoid maps with more than one element when `has_canonical_maps` is false so that
 /// we can test re-serialization.
@@ -339,18 +517,34 @@ fn get_sample_value_with_long_sequence(length: usize) -> SerdeData {
     SerdeData::UnitVector(vec![(); length])
 }
 
+fn bincode_varint_big_endian() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_varint_encoding()
+        .with_big_endian()
+}
+
 /// Structure used to factorize code in runtime tests.
 #[derive(Copy, Clone)]
 pub enum Runtime {
     Bcs,
     Bincode,
+    /// Bincode with the options `with_varint_encoding` and `with_big_endian`.
+    BincodeVarintBigEndian,
+    Postcard,
+    /// Borsh, tested against a reference implementation on top of Serde.
+    Borsh,
+    /// SCALE, tested against a reference implementation on top of Serde.
+    Scale,
 }
 
 impl std::convert::From<Runtime> for Encoding {
     fn from(runtime: Runtime) -> Self {
         match runtime {
             Runtime::Bcs => Encoding::Bcs,
-            Runtime::Bincode => Encoding::Bincode,
+            Runtime::Bincode | Runtime::BincodeVarintBigEndian => Encoding::Bincode,
+            Runtime::Postcard => Encoding::Postcard,
+            Runtime::Borsh => Encoding::Borsh,
+            Runtime::Scale => Encoding::Scale,
         }
     }
 }
@@ -360,10 +554,22 @@ impl Runtime {
         <Self as std::convert::Into<Encoding>>::into(self).name()
     }
 
+    pub fn bincode_options(self) -> BincodeOptions {
+        match self {
+            Self::BincodeVarintBigEndian => BincodeOptions::default()
+                .with_int_encoding(IntEncoding::Varint)
+                .with_endianness(Endianness::Big),
+            _ => BincodeOptions::default(),
+        }
+    }
+
     pub fn rust_package(self) -> &'static str {
         match self {
             Self::Bcs => "bcs = \"0.1.1\"",
-            Self::Bincode => "bincode = \"1.3\"",
+            Self::Bincode | Self::BincodeVarintBigEndian => "bincode = \"1.3\"",
+            Self::Postcard => "postcard = { version = \"1.0\", features = [\"use-std\"] }",
+            Self::Borsh => unreachable!("Borsh is not supported by the Rust code generator"),
+            Self::Scale => unreachable!("SCALE is not supported by the Rust code generator"),
         }
     }
 
@@ -374,6 +580,10 @@ impl Runtime {
         match self {
             Self::Bcs => bcs::to_bytes(value).unwrap(),
             Self::Bincode => bincode::serialize(value).unwrap(),
+            Self::BincodeVarintBigEndian => bincode_varint_big_endian().serialize(value).unwrap(),
+            Self::Postcard => postcard::to_stdvec(value).unwrap(),
+            Self::Borsh => borsh_format::to_bytes(value).unwrap(),
+            Self::Scale => scale_format::to_bytes(value).unwrap(),
         }
     }
 
@@ -384,6 +594,10 @@ impl Runtime {
         match self {
             Self::Bcs => bcs::from_bytes(bytes).ok(),
             Self::Bincode => bincode::deserialize(bytes).ok(),
+            Self::BincodeVarintBigEndian => bincode_varint_big_endian().deserialize(bytes).ok(),
+            Self::Postcard => postcard::from_bytes(bytes).ok(),
+            Self::Borsh => borsh_format::from_bytes(bytes).ok(),
+            Self::Scale => scale_format::from_bytes(bytes).ok(),
         }
     }
 
@@ -397,9 +611,9 @@ impl Runtime {
         let s = self.serialize(value);
         results.push((s.clone(), true));
 
-        if let Runtime::Bincode = self {
-            // Unfortunately, the current Rust implementation of bincode does not take fuzzing of
-            // `Vec<()>` values well at all.
+        if let Runtime::Bincode | Runtime::BincodeVarintBigEndian | Runtime::Postcard = self {
+            // Unfortunately, the current Rust implementations of bincode and postcard do not take
+            // fuzzing of `Vec<()>` values well at all.
             return results;
         }
 
@@ -436,6 +650,12 @@ impl Runtime {
         match self {
             Self::Bcs => "bcs::to_bytes",
             Self::Bincode => "bincode::serialize",
+            Self::BincodeVarintBigEndian => {
+                "(|value| { use bincode::Options; bincode::DefaultOptions::new().with_varint_encoding().with_big_endian().serialize(value) })"
+            }
+            Self::Postcard => "postcard::to_stdvec",
+            Self::Borsh => unreachable!("Borsh is not supported by the Rust code generator"),
+            Self::Scale => unreachable!("SCALE is not supported by the Rust code generator"),
         }
     }
 
@@ -443,15 +663,21 @@ impl Runtime {
         match self {
             Self::Bcs => "bcs::from_bytes",
             Self::Bincode => "bincode::deserialize",
+            Self::BincodeVarintBigEndian => {
+                "(|bytes: &[u8]| { use bincode::Options; bincode::DefaultOptions::new().with_varint_encoding().with_big_endian().deserialize(bytes) })"
+            }
+            Self::Postcard => "postcard::from_bytes",
+            Self::Borsh => unreachable!("Borsh is not supported by the Rust code generator"),
+            Self::Scale => unreachable!("SCALE is not supported by the Rust code generator"),
         }
     }
 
     /// Whether the encoding enforces ordering of map keys.
-    /// Note that both encodings are canonical on other data structures.
+    /// Note that bincode and BCS are canonical on other data structures.
     pub fn has_canonical_maps(self) -> bool {
         match self {
-            Self::Bcs => true,
-            Self::Bincode => false,
+            Self::Bcs | Self::Borsh => true,
+            Self::Bincode | Self::BincodeVarintBigEndian | Self::Postcard | Self::Scale => false,
         }
     }
 
@@ -459,32 +685,78 @@ impl Runtime {
     pub fn has_floats(self) -> bool {
         match self {
             Self::Bcs => false,
-            Self::Bincode => true,
+            Self::Bincode
+            | Self::BincodeVarintBigEndian
+            | Self::Postcard
+            | Self::Borsh
+            | Self::Scale => true,
         }
     }
 
     pub fn maximum_length(self) -> Option<usize> {
         match self {
             Self::Bcs => Some(bcs::MAX_SEQUENCE_LENGTH),
-            Self::Bincode => None,
+            Self::Bincode
+            | Self::BincodeVarintBigEndian
+            | Self::Postcard
+            | Self::Borsh
+            | Self::Scale => None,
         }
     }
 
     pub fn maximum_container_depth(self) -> Option<usize> {
         match self {
             Self::Bcs => Some(bcs::MAX_CONTAINER_DEPTH),
-            Self::Bincode => None,
+            Self::Bincode
+            | Self::BincodeVarintBigEndian
+            | Self::Postcard
+            | Self::Borsh
+            | Self::Scale => None,
+        }
+    }
+
+    /// The registry of the test data structures supported by the encoding.
+    pub fn get_registry(self) -> Result<Registry> {
+        match self {
+            Self::Borsh => borsh_data::get_registry(),
+            Self::Scale => scale_data::get_registry(),
+            _ => get_registry(),
+        }
+    }
+
+    /// Serialize the sample values supported by the encoding, with noise.
+    fn get_samples_with_noise(self) -> Vec<(Vec<u8>, bool)> {
+        match self {
+            Self::Borsh => borsh_data::get_sample_values()
+                .iter()
+                .flat_map(|value| self.serialize_with_noise_and_deserialize(value))
+                .collect(),
+            Self::Scale => scale_data::get_sample_values()
+                .iter()
+                .flat_map(|value| self.serialize_with_noise_and_deserialize(value))
+                .collect(),
+            _ => get_sample_values(self.has_canonical_maps(), self.has_floats())
+                .iter()
+                .flat_map(|value| self.serialize_with_noise_and_deserialize(value))
+                .collect(),
+        }
+    }
+
+    /// Whether the bytes deserialize into the test data structures supported by the encoding.
+    #[cfg(test)]
+    fn is_valid_sample(self, bytes: &[u8]) -> bool {
+        match self {
+            Self::Borsh => self.deserialize::<borsh_data::SerdeData>(bytes).is_some(),
+            Self::Scale => self.deserialize::<scale_data::SerdeData>(bytes).is_some(),
+            _ => self.deserialize::<SerdeData>(bytes).is_some(),
         }
     }
 
     pub fn get_positive_samples_quick(self) -> Vec<Vec<u8>> {
-        let values = get_sample_values(self.has_canonical_maps(), self.has_floats());
         let mut positive_samples = Vec::new();
-        for value in values {
-            for (sample, result) in self.serialize_with_noise_and_deserialize(&value) {
-                if result {
-                    positive_samples.push(sample);
-                }
+        for (sample, result) in self.get_samples_with_noise() {
+            if result {
+                positive_samples.push(sample);
             }
         }
         if let Some(depth) = self.maximum_container_depth() {
@@ -509,13 +781,10 @@ impl Runtime {
     }
 
     pub fn get_negative_samples(self) -> Vec<Vec<u8>> {
-        let values = get_sample_values(self.has_canonical_maps(), self.has_floats());
         let mut negative_samples = Vec::new();
-        for value in values {
-            for (sample, result) in self.serialize_with_noise_and_deserialize(&value) {
-                if !result {
-                    negative_samples.push(sample);
-                }
+        for (sample, result) in self.get_samples_with_noise() {
+            if !result {
+                negative_samples.push(sample);
             }
         }
         if let Some(length) = self.maximum_length() {
@@ -534,6 +803,41 @@ impl Runtime {
             negative_samples.push(vec![0x09, 0xff, 0xff, 0xff, 0xff, 0x10]);
             negative_samples.push(vec![0x09, 0xff, 0xff, 0xff, 0xff, 0x08]);
         }
+        if let Self::BincodeVarintBigEndian = self {
+            // Invalid tag, and tags of integers too large for a variant index.
+            negative_samples.push(vec![0xff]);
+            negative_samples.push(vec![0xfe, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+            negative_samples.push(vec![0xfd, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        }
+        if let Self::Postcard = self {
+            // Variant indices that overflow a `u32`.
+            negative_samples.push(vec![0x80, 0x80, 0x80, 0x80, 0x10]);
+            negative_samples.push(vec![0x80, 0x80, 0x80, 0x80, 0x80, 0x00]);
+        }
+        if let Self::Borsh = self {
+            // Unknown variant index, invalid tag for an option, and NaN.
+            negative_samples.push(vec![0x0e]);
+            negative_samples.push(vec![0x09, 0x02]);
+            negative_samples.push(
+                [
+                    vec![0x00],
+                    vec![0x00; 63],
+                    vec![0x01, 0x00, 0x00, 0xc0, 0x7f],
+                    vec![0x00],
+                ]
+                .concat(),
+            );
+        }
+        if let Self::Scale = self {
+            // Unknown variant index, invalid tag for an option, and non-canonical or
+            // oversized compact lengths.
+            negative_samples.push(vec![0x0e]);
+            negative_samples.push(vec![0x09, 0x02]);
+            negative_samples.push(vec![0x03, 0x01, 0x00]);
+            negative_samples.push(vec![0x03, 0x02, 0x00, 0x00, 0x00]);
+            negative_samples.push(vec![0x03, 0x03, 0x00, 0x00, 0x00, 0x00]);
+            negative_samples.push(vec![0x03, 0x07, 0x00, 0x00, 0x00, 0x00, 0x01]);
+        }
         negative_samples
     }
 
@@ -589,7 +893,17 @@ impl Runtime {
         let f0 = self.serialize(&SerdeData::UnitVector(Vec::new()));
         let mut result = f0[..f0.len() - e.len()].to_vec();
         match self {
-            Runtime::Bincode => result.append(&mut self.serialize(&(length as u64))),
+            // Varint encoding of the length for postcard.
+            Runtime::Bincode | Runtime::BincodeVarintBigEndian | Runtime::Postcard => {
+                result.append(&mut self.serialize(&(length as u64)))
+            }
+            // Fixed-size encoding of the length for Borsh.
+            Runtime::Borsh => result.append(&mut self.serialize(&(length as u32))),
+            // Compact encoding of the length for SCALE.
+            Runtime::Scale => {
+                use parity_scale_codec::Encode;
+                result.append(&mut parity_scale_codec::Compact(length as u32).encode())
+            }
             Runtime::Bcs => {
                 // ULEB-128 encoding of the length.
                 let mut value = length;
@@ -828,6 +1142,7 @@ UnitStruct: UNITSTRUCT
 #[test]
 fn test_bincode_get_sample_with_long_sequence() {
     test_get_sample_with_long_sequence(Runtime::Bincode);
+    test_get_sample_with_long_sequence(Runtime::BincodeVarintBigEndian);
 }
 
 #[test]
@@ -835,6 +1150,21 @@ fn test_bcs_get_sample_with_long_sequence() {
     test_get_sample_with_long_sequence(Runtime::Bcs);
 }
 
+#[test]
+fn test_postcard_get_sample_with_long_sequence() {
+    test_get_sample_with_long_sequence(Runtime::Postcard);
+}
+
+#[test]
+fn test_borsh_get_sample_with_long_sequence() {
+    test_get_sample_with_long_sequence(Runtime::Borsh);
+}
+
+#[test]
+fn test_scale_get_sample_with_long_sequence() {
+    test_get_sample_with_long_sequence(Runtime::Scale);
+}
+
 // Make sure the direct computation of the serialization of these test values
 // agrees with the usual serialization.
 #[cfg(test)]
@@ -862,6 +1192,8 @@ fn test_get_sample_with_long_sequence(runtime: Runtime) {
 fn test_bincode_samples_with_container_depth() {
     test_get_sample_with_container_depth(Runtime::Bincode);
     test_get_alternate_sample_with_container_depth(Runtime::Bincode);
+    test_get_sample_with_container_depth(Runtime::BincodeVarintBigEndian);
+    test_get_alternate_sample_with_container_depth(Runtime::BincodeVarintBigEndian);
 }
 
 #[test]
@@ -870,6 +1202,24 @@ fn test_bcs_samples_with_container_depth() {
     test_get_alternate_sample_with_container_depth(Runtime::Bcs);
 }
 
+#[test]
+fn test_postcard_samples_with_container_depth() {
+    test_get_sample_with_container_depth(Runtime::Postcard);
+    test_get_alternate_sample_with_container_depth(Runtime::Postcard);
+}
+
+#[test]
+fn test_borsh_samples_with_container_depth() {
+    test_get_sample_with_container_depth(Runtime::Borsh);
+    test_get_alternate_sample_with_container_depth(Runtime::Borsh);
+}
+
+#[test]
+fn test_scale_samples_with_container_depth() {
+    test_get_sample_with_container_depth(Runtime::Scale);
+    test_get_alternate_sample_with_container_depth(Runtime::Scale);
+}
+
 // Make sure the direct computation of the serialization of these test values
 // agrees with the usual serialization.
 #[cfg(test)]
@@ -925,6 +1275,25 @@ fn test_get_alternate_sample_with_container_depth(runtime: Runtime) {
 #[test]
 fn test_bincode_get_positive_samples() {
     assert_eq!(test_get_positive_samples(Runtime::Bincode), 18);
+    assert_eq!(
+        test_get_positive_samples(Runtime::BincodeVarintBigEndian),
+        18
+    );
+}
+
+#[test]
+fn test_postcard_get_positive_samples() {
+    assert_eq!(test_get_positive_samples(Runtime::Postcard), 18);
+}
+
+#[test]
+fn test_borsh_get_positive_samples() {
+    assert_eq!(test_get_positive_samples(Runtime::Borsh), 63);
+}
+
+#[test]
+fn test_scale_get_positive_samples() {
+    assert_eq!(test_get_positive_samples(Runtime::Scale), 70);
 }
 
 #[test]
@@ -941,7 +1310,7 @@ fn test_get_positive_samples(runtime: Runtime) -> usize {
     let samples = runtime.get_positive_samples();
     let length = samples.len();
     for sample in samples {
-        assert!(runtime.deserialize::<SerdeData>(&sample).is_some());
+        assert!(runtime.is_valid_sample(&sample));
     }
     length
 }
@@ -949,6 +1318,25 @@ fn test_get_positive_samples(runtime: Runtime) -> usize {
 #[test]
 fn test_bincode_get_negative_samples() {
     assert_eq!(test_get_negative_samples(Runtime::Bincode), 0);
+    assert_eq!(
+        test_get_negative_samples(Runtime::BincodeVarintBigEndian),
+        3
+    );
+}
+
+#[test]
+fn test_postcard_get_negative_samples() {
+    assert_eq!(test_get_negative_samples(Runtime::Postcard), 2);
+}
+
+#[test]
+fn test_borsh_get_negative_samples() {
+    assert_eq!(test_get_negative_samples(Runtime::Borsh), 58);
+}
+
+#[test]
+fn test_scale_get_negative_samples() {
+    assert_eq!(test_get_negative_samples(Runtime::Scale), 55);
 }
 
 #[test]
@@ -965,7 +1353,7 @@ fn test_get_negative_samples(runtime: Runtime) -> usize {
     let samples = runtime.get_negative_samples();
     let length = samples.len();
     for sample in samples {
-        assert!(runtime.deserialize::<SerdeData>(&sample).is_none());
+        assert!(!runtime.is_valid_sample(&sample));
     }
     length
 }
@@ -0,0 +1,251 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Variant of the test data formats that is compatible with Borsh: no characters, and no map
+//! keys containing floats, bytes, sequences or maps. The types also derive the traits of the
+//! `borsh` crate so that the reference implementation in `borsh_format` can be checked against
+//! it.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use maplit::btreemap;
+use serde::{Deserialize, Serialize};
+use serde_reflection::{Registry, Result, Samples, Tracer, TracerConfig};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub enum SerdeData {
+    PrimitiveTypes(PrimitiveTypes),
+    OtherTypes(OtherTypes),
+    UnitVariant,
+    NewTypeVariant(String),
+    TupleVariant(u32, u64),
+    StructVariant {
+        f0: UnitStruct,
+        f1: NewTypeStruct,
+        f2: TupleStruct,
+        f3: Struct,
+    },
+    ListWithMutualRecursion(List<Box<SerdeData>>),
+    TreeWithMutualRecursion(Tree<Box<SerdeData>>),
+    TupleArray([u32; 3]),
+    SimpleList(SimpleList),
+    CStyleEnum(CStyleEnum),
+    ComplexMap(BTreeMap<([i16; 2], Option<String>), ()>),
+    EmptyTupleVariant(),
+    EmptyStructVariant {},
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct PrimitiveTypes {
+    f_bool: bool,
+    f_u8: u8,
+    f_u16: u16,
+    f_u32: u32,
+    f_u64: u64,
+    f_u128: u128,
+    f_i8: i8,
+    f_i16: i16,
+    f_i32: i32,
+    f_i64: i64,
+    f_i128: i128,
+    f_f32: Option<f32>,
+    f_f64: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct OtherTypes {
+    f_string: String,
+    #[serde(with = "serde_bytes")]
+    f_bytes: Vec<u8>,
+    f_option: Option<Struct>,
+    f_unit: (),
+    f_seq: Vec<Struct>,
+    f_opt_seq: Option<Vec<i32>>,
+    f_tuple: (u8, u16),
+    f_stringmap: BTreeMap<String, u32>,
+    f_intset: BTreeMap<u64, ()>,
+    f_nested_seq: Vec<Vec<Struct>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct UnitStruct;
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct NewTypeStruct(u64);
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct TupleStruct(u32, u64);
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct Struct {
+    x: u32,
+    y: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub enum List<T> {
+    Empty,
+    Node(
+        T,
+        #[borsh(bound(deserialize = "T: BorshDeserialize + Clone"))] Box<List<T>>,
+    ),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct Tree<T> {
+    value: T,
+    children: Vec<Tree<T>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct SimpleList(Option<Box<SimpleList>>);
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub enum CStyleEnum {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+/// The registry corresponding to the test data structures above.
+pub fn get_registry() -> Result<Registry> {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let samples = Samples::new();
+    tracer.trace_type::<SerdeData>(&samples)?;
+    tracer.trace_type::<List<SerdeData>>(&samples)?;
+    tracer.trace_type::<CStyleEnum>(&samples)?;
+    tracer.registry()
+}
+
+/// Manually generate sample values.
+/// Map keys are chosen so that their Rust ordering differs from the ordering of their encodings.
+pub fn get_sample_values() -> Vec<SerdeData> {
+    let v0 = SerdeData::PrimitiveTypes(PrimitiveTypes {
+        f_bool: false,
+        f_u8: 6,
+        f_u16: 5,
+        f_u32: 4,
+        f_u64: 3,
+        f_u128: 2,
+        f_i8: 1,
+        f_i16: 0,
+        f_i32: -1,
+        f_i64: -2,
+        f_i128: -3,
+        f_f32: Some(0.4),
+        f_f64: Some(35.21),
+    });
+
+    let v1 = SerdeData::PrimitiveTypes(PrimitiveTypes {
+        f_bool: true,
+        f_u8: u8::MAX,
+        f_u16: u16::MAX,
+        f_u32: u32::MAX,
+        f_u64: u64::MAX,
+        f_u128: u128::MAX,
+        f_i8: i8::MIN,
+        f_i16: i16::MIN,
+        f_i32: i32::MIN,
+        f_i64: i64::MIN,
+        f_i128: i128::MIN,
+        f_f32: Some(-4111.0),
+        f_f64: Some(-0.0021),
+    });
+
+    let v2 = SerdeData::OtherTypes(OtherTypes {
+        f_string: "test".to_string(),
+        f_bytes: b"bytes".to_vec(),
+        f_option: Some(Struct { x: 2, y: 3 }),
+        f_unit: (),
+        f_seq: vec![Struct { x: 1, y: 3 }],
+        f_opt_seq: Some(vec![1]),
+        f_tuple: (4, 5),
+        f_stringmap: btreemap! {"".to_string() => 0, "ab".to_string() => 1, "b".to_string() => 2},
+        f_intset: btreemap! {1 => (), 256 => (), 1 << 40 => ()},
+        f_nested_seq: vec![
+            vec![Struct { x: 4, y: 5 }, Struct { x: 6, y: 7 }],
+            vec![Struct { x: 8, y: 9 }],
+        ],
+    });
+
+    let v2bis = SerdeData::OtherTypes(OtherTypes {
+        f_string: "".to_string(),
+        f_bytes: vec![1u8; 129],
+        f_option: None,
+        f_unit: (),
+        f_seq: Vec::new(),
+        f_opt_seq: None,
+        f_tuple: (4, 5),
+        f_stringmap: btreemap! {"a\0".to_string() => 0, "a".to_string() => 1},
+        f_intset: BTreeMap::new(),
+        f_nested_seq: vec![],
+    });
+
+    let v3 = SerdeData::UnitVariant;
+
+    let v4 =
+        SerdeData::NewTypeVariant("test.\u{10348}.\u{00a2}\u{0939}\u{20ac}\u{d55c}..".to_string());
+
+    let v5 = SerdeData::TupleVariant(3, 6);
+
+    let v6 = SerdeData::StructVariant {
+        f0: UnitStruct,
+        f1: NewTypeStruct(1),
+        f2: TupleStruct(2, 3),
+        f3: Struct { x: 4, y: 5 },
+    };
+
+    let v7 = SerdeData::ListWithMutualRecursion(List::Node(
+        Box::new(SerdeData::UnitVariant),
+        Box::new(List::Empty),
+    ));
+
+    let v8 = SerdeData::TreeWithMutualRecursion(Tree {
+        value: Box::new(SerdeData::TupleVariant(0, 1)),
+        children: vec![Tree {
+            value: Box::new(SerdeData::CStyleEnum(CStyleEnum::A)),
+            children: vec![],
+        }],
+    });
+
+    let v9 = SerdeData::TupleArray([0, 2, 3]);
+
+    let v10 = SerdeData::SimpleList(SimpleList(Some(Box::new(SimpleList(None)))));
+
+    let v11 = SerdeData::CStyleEnum(CStyleEnum::E);
+
+    let v12 = SerdeData::ComplexMap(btreemap! {
+        ([-1, 2], None) => (),
+        ([-1, 2], Some(String::new())) => (),
+        ([0, -300], Some("a".to_string())) => (),
+        ([256, 1], None) => (),
+    });
+
+    let v13 = SerdeData::EmptyTupleVariant();
+    let v14 = SerdeData::EmptyStructVariant {};
+
+    vec![
+        v0, v1, v2, v2bis, v3, v4, v5, v6, v7, v8, v9, v10, v11, v12, v13, v14,
+    ]
+}
+
+// Make sure that the reference implementation agrees with the `borsh` crate, including on the
+// noisy samples used to test runtimes.
+#[test]
+fn test_borsh_format_agrees_with_borsh_crate() {
+    use super::Runtime;
+
+    for value in get_sample_values() {
+        let bytes = Runtime::Borsh.serialize(&value);
+        assert_eq!(bytes, borsh::to_vec(&value).unwrap());
+        for (sample, is_valid) in Runtime::Borsh.serialize_with_noise_and_deserialize(&value) {
+            let decoded = SerdeData::try_from_slice(&sample).ok();
+            assert_eq!(decoded.is_some(), is_valid);
+            if let Some(decoded) = decoded {
+                assert_eq!(Some(decoded), Runtime::Borsh.deserialize(&sample));
+            }
+        }
+    }
+}
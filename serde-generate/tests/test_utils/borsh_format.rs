@@ -0,0 +1,836 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Reference implementation of the Borsh format on top of Serde, used to test the Borsh
+//! runtimes. Unlike the `borsh` crate, this works with the Serde-based test data.
+//!
+//! Lengths are encoded as `u32` values and variant indices as `u8` values. Characters and NaN
+//! floats are rejected. Map keys must be serialized in increasing order, as in Rust. This is
+//! checked against the `borsh` crate in `borsh_data`.
+
+use serde::{de, ser, Deserialize, Serialize};
+use std::fmt;
+
+/// Maximum length in practice for sequences (e.g. in Java).
+pub const MAX_LENGTH: usize = (1 << 31) - 1;
+
+#[derive(Debug)]
+pub struct Error(String);
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+fn error<T>(msg: &str) -> Result<T> {
+    Err(Error(msg.to_string()))
+}
+
+pub fn to_bytes<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    let mut serializer = Serializer { output: Vec::new() };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+pub fn from_bytes<'de, T>(input: &'de [u8]) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    let mut deserializer = Deserializer {
+        input,
+        offset: 0,
+        sort_keys: Vec::new(),
+    };
+    let value = T::deserialize(&mut deserializer)?;
+    if deserializer.offset != input.len() {
+        return error("trailing bytes");
+    }
+    Ok(value)
+}
+
+struct Serializer {
+    output: Vec<u8>,
+}
+
+impl Serializer {
+    fn serialize_len(&mut self, len: usize) -> Result<()> {
+        if len > MAX_LENGTH {
+            return error("length is too large");
+        }
+        self.output.extend_from_slice(&(len as u32).to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_variant_index(&mut self, index: u32) -> Result<()> {
+        match u8::try_from(index) {
+            Ok(index) => {
+                self.output.push(index);
+                Ok(())
+            }
+            Err(_) => error("variant index is too large"),
+        }
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.output.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.output.push(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        if v.is_nan() {
+            return error("NaN values are not supported");
+        }
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        if v.is_nan() {
+            return error("NaN values are not supported");
+        }
+        self.output.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<()> {
+        error("characters are not supported")
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.serialize_len(v.len())?;
+        self.output.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.output.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.output.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        self.serialize_variant_index(variant_index)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_variant_index(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        match len {
+            Some(len) => {
+                self.serialize_len(len)?;
+                Ok(self)
+            }
+            None => error("sequences must have a known length"),
+        }
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.serialize_variant_index(variant_index)?;
+        Ok(self)
+    }
+
+    // Map entries are expected in increasing order of keys (e.g. from a `BTreeMap`).
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.serialize_seq(len)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.serialize_variant_index(variant_index)?;
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<'a> ser::SerializeSeq for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTuple for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeMap for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStruct for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct Deserializer<'de> {
+    input: &'de [u8],
+    offset: usize,
+    /// Order-preserving encodings of the integers, strings and bytes read so far, together with
+    /// their start and end offsets in the input.
+    sort_keys: Vec<(usize, usize, Vec<u8>)>,
+}
+
+/// Escape zero bytes and add a terminator so that byte strings compare as in Rust, prefixes
+/// first, even when followed by other data.
+fn escape(bytes: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(bytes.len() + 2);
+    for byte in bytes {
+        result.push(*byte);
+        if *byte == 0 {
+            result.push(0xff);
+        }
+    }
+    result.extend_from_slice(&[0, 0]);
+    result
+}
+
+impl<'de> Deserializer<'de> {
+    fn read(&mut self, len: usize) -> Result<&'de [u8]> {
+        if self.input.len() - self.offset < len {
+            return error("unexpected end of input");
+        }
+        let bytes = &self.input[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.read(N)?.try_into().unwrap())
+    }
+
+    fn record_sort_key(&mut self, start: usize, key: Vec<u8>) {
+        while matches!(self.sort_keys.last(), Some((previous, _, _)) if *previous >= start) {
+            self.sort_keys.pop();
+        }
+        self.sort_keys.push((start, self.offset, key));
+    }
+
+    /// Unsigned integers compare as in Rust in big-endian order.
+    fn read_unsigned<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let start = self.offset;
+        let bytes = self.read_array::<N>()?;
+        self.record_sort_key(start, bytes.iter().rev().copied().collect());
+        Ok(bytes)
+    }
+
+    /// Signed integers also need their sign bit flipped.
+    fn read_signed<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let start = self.offset;
+        let bytes = self.read_array::<N>()?;
+        let mut key: Vec<u8> = bytes.iter().rev().copied().collect();
+        key[0] ^= 0x80;
+        self.record_sort_key(start, key);
+        Ok(bytes)
+    }
+
+    fn read_bytes(&mut self) -> Result<&'de [u8]> {
+        let start = self.offset;
+        let len = self.read_len()?;
+        let bytes = self.read(len)?;
+        self.record_sort_key(start, escape(bytes));
+        Ok(bytes)
+    }
+
+    /// The input between the given offsets, where integers, strings and bytes are replaced by
+    /// their order-preserving encodings. Comparing the results orders map keys as in Rust.
+    fn sort_key(&self, start: usize, end: usize) -> Vec<u8> {
+        let mut result = Vec::new();
+        let mut position = start;
+        let first = self
+            .sort_keys
+            .partition_point(|(offset, _, _)| *offset < start);
+        for (offset, next_position, key) in &self.sort_keys[first..] {
+            if *offset >= end {
+                break;
+            }
+            result.extend_from_slice(&self.input[position..*offset]);
+            result.extend_from_slice(key);
+            position = *next_position;
+        }
+        result.extend_from_slice(&self.input[position..end]);
+        result
+    }
+
+    fn read_tag(&mut self) -> Result<bool> {
+        match self.read_array::<1>()? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => error("invalid tag"),
+        }
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        let len = u32::from_le_bytes(self.read_array()?) as usize;
+        if len > MAX_LENGTH {
+            return error("length is too large");
+        }
+        Ok(len)
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        error("Borsh is not a self-describing format")
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_bool(self.read_tag()?)
+    }
+
+    fn deserialize_i8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i8(i8::from_le_bytes(self.read_signed()?))
+    }
+
+    fn deserialize_i16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i16(i16::from_le_bytes(self.read_signed()?))
+    }
+
+    fn deserialize_i32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i32(i32::from_le_bytes(self.read_signed()?))
+    }
+
+    fn deserialize_i64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i64(i64::from_le_bytes(self.read_signed()?))
+    }
+
+    fn deserialize_i128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_i128(i128::from_le_bytes(self.read_signed()?))
+    }
+
+    fn deserialize_u8<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u8(u8::from_le_bytes(self.read_array()?))
+    }
+
+    fn deserialize_u16<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u16(u16::from_le_bytes(self.read_unsigned()?))
+    }
+
+    fn deserialize_u32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u32(u32::from_le_bytes(self.read_unsigned()?))
+    }
+
+    fn deserialize_u64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u64(u64::from_le_bytes(self.read_unsigned()?))
+    }
+
+    fn deserialize_u128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_u128(u128::from_le_bytes(self.read_unsigned()?))
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = f32::from_le_bytes(self.read_array()?);
+        if value.is_nan() {
+            return error("NaN values are not supported");
+        }
+        visitor.visit_f32(value)
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let value = f64::from_le_bytes(self.read_array()?);
+        if value.is_nan() {
+            return error("NaN values are not supported");
+        }
+        visitor.visit_f64(value)
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        error("characters are not supported")
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match std::str::from_utf8(self.read_bytes()?) {
+            Ok(value) => visitor.visit_borrowed_str(value),
+            Err(_) => error("invalid UTF-8 string"),
+        }
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_bytes(self.read_bytes()?)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.read_tag()? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let remaining = self.read_len()?;
+        visitor.visit_seq(Compound {
+            de: self,
+            remaining,
+            previous_key: None,
+        })
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Compound {
+            de: self,
+            remaining: len,
+            previous_key: None,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let remaining = self.read_len()?;
+        visitor.visit_map(Compound {
+            de: self,
+            remaining,
+            previous_key: None,
+        })
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        error("identifiers are not supported")
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        error("Borsh is not a self-describing format")
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct Compound<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+    previous_key: Option<Vec<u8>>,
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for Compound<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'a, 'de> de::MapAccess<'de> for Compound<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        let start = self.de.offset;
+        let key = seed.deserialize(&mut *self.de)?;
+        let sort_key = self.de.sort_key(start, self.de.offset);
+        if let Some(previous_key) = &self.previous_key {
+            if *previous_key >= sort_key {
+                return error("map keys must be serialized in increasing order");
+            }
+        }
+        self.previous_key = Some(sort_key);
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for &'a mut Deserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let index = u32::from(self.read_array::<1>()?[0]);
+        let value = seed.deserialize(de::IntoDeserializer::<Error>::into_deserializer(index))?;
+        Ok((value, self))
+    }
+}
+
+impl<'a, 'de> de::VariantAccess<'de> for &'a mut Deserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
+
+#[test]
+fn test_borsh_format() {
+    use std::collections::BTreeMap;
+
+    assert_eq!(
+        to_bytes(&(1u16, -1i32)).unwrap(),
+        [1, 0, 255, 255, 255, 255]
+    );
+    assert_eq!(to_bytes(&1u128).unwrap(), [&[1u8][..], &[0; 15]].concat());
+    assert_eq!(to_bytes(&1.0f32).unwrap(), [0x00, 0x00, 0x80, 0x3f]);
+    assert_eq!(to_bytes("ab").unwrap(), [2, 0, 0, 0, b'a', b'b']);
+    assert_eq!(to_bytes(&vec![1u8, 2]).unwrap(), [2, 0, 0, 0, 1, 2]);
+    assert_eq!(to_bytes(&[1u8, 2]).unwrap(), [1, 2]);
+    assert_eq!(to_bytes(&(Some(true), None::<u8>)).unwrap(), [1, 1, 0]);
+    assert_eq!(to_bytes(&super::CStyleEnum::E).unwrap(), [4]);
+    assert!(to_bytes(&'a').is_err());
+    assert!(to_bytes(&f64::NAN).is_err());
+
+    assert_eq!(
+        from_bytes::<BTreeMap<u8, ()>>(&[2, 0, 0, 0, 1, 2]).unwrap(),
+        maplit::btreemap! {1 => (), 2 => ()}
+    );
+    assert!(from_bytes::<BTreeMap<u8, ()>>(&[2, 0, 0, 0, 2, 1]).is_err());
+    assert!(from_bytes::<BTreeMap<u8, ()>>(&[2, 0, 0, 0, 1, 1]).is_err());
+    assert_eq!(
+        from_bytes::<BTreeMap<i16, ()>>(&[2, 0, 0, 0, 0, 0x80, 0, 1]).unwrap(),
+        maplit::btreemap! {i16::MIN => (), 256 => ()}
+    );
+    assert!(from_bytes::<BTreeMap<i16, ()>>(&[2, 0, 0, 0, 0, 1, 0, 0x80]).is_err());
+    assert_eq!(
+        to_bytes(&maplit::btreemap! {"ab" => (), "b" => ()}).unwrap(),
+        [2, 0, 0, 0, 2, 0, 0, 0, b'a', b'b', 1, 0, 0, 0, b'b']
+    );
+    assert!(from_bytes::<BTreeMap<String, ()>>(&[
+        2, 0, 0, 0, 1, 0, 0, 0, b'b', 2, 0, 0, 0, b'a', b'b'
+    ])
+    .is_err());
+    assert!(from_bytes::<Vec<()>>(&[0, 0, 0, 0x80]).is_err());
+    assert!(from_bytes::<bool>(&[2]).is_err());
+    assert!(from_bytes::<bool>(&[1, 0]).is_err());
+    assert!(from_bytes::<f32>(&[0x00, 0x00, 0xc0, 0x7f]).is_err());
+    assert!(from_bytes::<super::CStyleEnum>(&[5]).is_err());
+}
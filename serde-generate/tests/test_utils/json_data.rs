@@ -0,0 +1,446 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Variant of the test data formats that is compatible with the JSON encoding: maps only use
+//! keys that `serde_json` writes as strings. Samples are produced and checked with `serde_json`.
+
+use super::{List, NewTypeStruct, SimpleList, Struct, Tree, TupleStruct, UnitStruct};
+use maplit::btreemap;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use serde_reflection::{Dual, DualTracer, Registry, Result, TracerConfig};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub enum SerdeData {
+    PrimitiveTypes(PrimitiveTypes),
+    OtherTypes(OtherTypes),
+    UnitVariant,
+    NewTypeVariant(String),
+    TupleVariant(u32, u64),
+    StructVariant {
+        f0: UnitStruct,
+        f1: NewTypeStruct,
+        f2: TupleStruct,
+        f3: Struct,
+    },
+    ListWithMutualRecursion(List<Box<SerdeData>>),
+    TreeWithMutualRecursion(Tree<Box<SerdeData>>),
+    TupleArray([u32; 3]),
+    UnitVector(Vec<()>),
+    SimpleList(SimpleList),
+    Floats(Floats),
+    KeyedMaps(KeyedMaps),
+    EmptyTupleVariant(),
+    EmptyStructVariant {},
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PrimitiveTypes {
+    f_bool: bool,
+    f_u8: u8,
+    f_u16: u16,
+    f_u32: u32,
+    f_u64: u64,
+    f_u128: u128,
+    f_i8: i8,
+    f_i16: i16,
+    f_i32: i32,
+    f_i64: i64,
+    f_i128: i128,
+    f_f32: Option<f32>,
+    f_f64: Option<f64>,
+    f_char: Option<char>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct OtherTypes {
+    f_string: String,
+    f_bytes: ByteBuf,
+    f_option: Option<Struct>,
+    f_unit: (),
+    f_seq: Vec<Struct>,
+    f_opt_seq: Option<Vec<i32>>,
+    f_tuple: (u8, u16),
+    f_nested_seq: Vec<Vec<Struct>>,
+}
+
+/// Floating-point numbers around the thresholds where `serde_json` switches to exponents.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Floats {
+    f_f32s: Vec<f32>,
+    f_f64s: Vec<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct KeyedMaps {
+    f_stringmap: BTreeMap<String, u32>,
+    f_intset: BTreeMap<u64, ()>,
+    f_i8map: BTreeMap<i8, bool>,
+    f_i128map: BTreeMap<i128, u8>,
+    f_u128map: BTreeMap<u128, u8>,
+    f_boolmap: BTreeMap<bool, String>,
+    f_charmap: BTreeMap<char, u8>,
+    f_keymap: BTreeMap<Key, Option<u8>>,
+    f_colormap: BTreeMap<Color, u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key(i32);
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+/// The registries of both modes of Serde corresponding to the test data structures above.
+pub fn get_registries() -> Result<Dual<Registry>> {
+    let mut tracer = DualTracer::new(TracerConfig::default());
+    let samples = Dual::default();
+    tracer.trace_type::<SerdeData>(&samples)?;
+    tracer.trace_type::<List<SerdeData>>(&samples)?;
+    tracer.trace_type::<Color>(&samples)?;
+    tracer.registries()
+}
+
+/// The registry of the human-readable mode of Serde, used by `serde_json`.
+pub fn get_registry() -> Result<Registry> {
+    Ok(get_registries()?.human_readable)
+}
+
+/// Manually generate sample values.
+pub fn get_sample_values() -> Vec<SerdeData> {
+    let v0 = SerdeData::PrimitiveTypes(PrimitiveTypes {
+        f_bool: false,
+        f_u8: 6,
+        f_u16: 5,
+        f_u32: 4,
+        f_u64: 3,
+        f_u128: 2,
+        f_i8: 1,
+        f_i16: 0,
+        f_i32: -1,
+        f_i64: -2,
+        f_i128: -3,
+        f_f32: Some(0.4),
+        f_f64: Some(35.21),
+        f_char: Some('x'),
+    });
+
+    let v1 = SerdeData::PrimitiveTypes(PrimitiveTypes {
+        f_bool: true,
+        f_u8: u8::MAX,
+        f_u16: u16::MAX,
+        f_u32: u32::MAX,
+        f_u64: u64::MAX,
+        f_u128: u128::MAX,
+        f_i8: i8::MIN,
+        f_i16: i16::MIN,
+        f_i32: i32::MIN,
+        f_i64: i64::MIN,
+        f_i128: i128::MIN,
+        f_f32: None,
+        f_f64: Some(-0.0021),
+        f_char: Some('\u{e9}'),
+    });
+
+    let v2 = SerdeData::OtherTypes(OtherTypes {
+        f_string: "test \"quoted\" \\ / \u{8}\u{c}\n\r\t \u{1}\u{1f}\u{7f} \u{2028}".to_string(),
+        f_bytes: ByteBuf::from(b"bytes".to_vec()),
+        f_option: Some(Struct { x: 2, y: 3 }),
+        f_unit: (),
+        f_seq: vec![Struct { x: 1, y: 3 }],
+        f_opt_seq: Some(vec![1, -1]),
+        f_tuple: (4, 5),
+        f_nested_seq: vec![
+            vec![Struct { x: 4, y: 5 }, Struct { x: 6, y: 7 }],
+            vec![Struct { x: 8, y: 9 }],
+        ],
+    });
+
+    let v2bis = SerdeData::OtherTypes(OtherTypes {
+        f_string: "".to_string(),
+        f_bytes: ByteBuf::from(vec![255u8; 3]),
+        f_option: None,
+        f_unit: (),
+        f_seq: Vec::new(),
+        f_opt_seq: None,
+        f_tuple: (4, 5),
+        f_nested_seq: vec![vec![]],
+    });
+
+    let v3 = SerdeData::UnitVariant;
+
+    let v4 =
+        SerdeData::NewTypeVariant("test.\u{10348}.\u{00a2}\u{0939}\u{20ac}\u{d55c}..".to_string());
+
+    let v5 = SerdeData::TupleVariant(3, 6);
+
+    let v6 = SerdeData::StructVariant {
+        f0: UnitStruct,
+        f1: NewTypeStruct(1),
+        f2: TupleStruct(2, 3),
+        f3: Struct { x: 4, y: 5 },
+    };
+
+    let v7 = SerdeData::ListWithMutualRecursion(List::Node(
+        Box::new(SerdeData::UnitVariant),
+        Box::new(List::Empty),
+    ));
+
+    let v8 = SerdeData::TreeWithMutualRecursion(Tree {
+        value: Box::new(SerdeData::TupleVariant(0, 1)),
+        children: vec![Tree {
+            value: Box::new(SerdeData::EmptyTupleVariant()),
+            children: vec![],
+        }],
+    });
+
+    let v9 = SerdeData::TupleArray([0, 2, 3]);
+
+    let v10 = SerdeData::UnitVector(vec![(), ()]);
+
+    let v11 = SerdeData::SimpleList(SimpleList(Some(Box::new(SimpleList(None)))));
+
+    let v12 = SerdeData::Floats(Floats {
+        f_f32s: vec![
+            0.0,
+            -0.0,
+            1.0,
+            0.1,
+            -4111.0,
+            16777216.0,
+            1e12,
+            1e13,
+            1.5e-6,
+            1e-7,
+            f32::MAX,
+            f32::MIN_POSITIVE,
+            1e-45,
+        ],
+        f_f64s: vec![
+            0.0,
+            -0.0,
+            1.0,
+            0.1,
+            1.0 / 3.0,
+            123456789.125,
+            1e15,
+            1e16,
+            1e17,
+            1e-5,
+            1.5e-7,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+        ],
+    });
+
+    let v13 = SerdeData::KeyedMaps(KeyedMaps {
+        f_stringmap: btreemap! {"".to_string() => 1, "a\"b".to_string() => 2, "\u{e9}".to_string() => 3},
+        f_intset: btreemap! {0 => (), u64::MAX => ()},
+        f_i8map: btreemap! {-128 => true, 0 => false},
+        f_i128map: btreemap! {i128::MIN => 0, i128::MAX => 1},
+        f_u128map: btreemap! {u128::MAX => 2},
+        f_boolmap: btreemap! {false => "no".to_string(), true => "yes".to_string()},
+        f_charmap: btreemap! {'\n' => 0, 'a' => 1},
+        f_keymap: btreemap! {Key(-1) => None, Key(7) => Some(1)},
+        f_colormap: btreemap! {Color::Red => 0, Color::Blue => 2},
+    });
+
+    let v13bis = SerdeData::KeyedMaps(KeyedMaps {
+        f_stringmap: BTreeMap::new(),
+        f_intset: BTreeMap::new(),
+        f_i8map: BTreeMap::new(),
+        f_i128map: BTreeMap::new(),
+        f_u128map: BTreeMap::new(),
+        f_boolmap: BTreeMap::new(),
+        f_charmap: BTreeMap::new(),
+        f_keymap: BTreeMap::new(),
+        f_colormap: BTreeMap::new(),
+    });
+
+    let v14 = SerdeData::EmptyTupleVariant();
+    let v15 = SerdeData::EmptyStructVariant {};
+
+    vec![
+        v0, v1, v2, v2bis, v3, v4, v5, v6, v7, v8, v9, v10, v11, v12, v13, v13bis, v14, v15,
+    ]
+}
+
+/// A list value nested in `2 * length + 1` JSON arrays and objects.
+fn get_sample_value_with_nested_lists(length: usize) -> SerdeData {
+    let mut list = List::Empty;
+    for _ in 0..length {
+        list = List::Node(Box::new(SerdeData::UnitVariant), Box::new(list));
+    }
+    SerdeData::ListWithMutualRecursion(list)
+}
+
+/// The canonical encodings of the sample values, as written by `serde_json`, including one
+/// value using the maximal nesting of JSON containers.
+pub fn get_positive_samples() -> Vec<String> {
+    let mut samples: Vec<_> = get_sample_values()
+        .iter()
+        .map(|value| serde_json::to_string(value).unwrap())
+        .collect();
+    samples.push(serde_json::to_string(&get_sample_value_with_nested_lists(63)).unwrap());
+    samples
+}
+
+/// Inputs accepted by `serde_json` paired with the canonical encoding of the value that they
+/// stand for.
+pub fn get_alternate_samples() -> Vec<(String, String)> {
+    [
+        (" \"UnitVariant\" \n", "\"UnitVariant\""),
+        ("{\"UnitVariant\":null}", "\"UnitVariant\""),
+        ("{ \"TupleVariant\" : [ 3 , 6 ] }", "{\"TupleVariant\":[3,6]}"),
+        ("{\"NewTypeVariant\":\"\\u0041\\/\\ud83d\\ude00\"}", "{\"NewTypeVariant\":\"A/\u{1f600}\"}"),
+        (
+            "{\"StructVariant\":{\"f3\":{\"y\":5,\"x\":4,\"z\":[{}]},\"f2\":[2,3],\"f1\":1,\"f0\":null}}",
+            "{\"StructVariant\":{\"f0\":null,\"f1\":1,\"f2\":[2,3],\"f3\":{\"x\":4,\"y\":5}}}",
+        ),
+        (
+            "{\"OtherTypes\":{\"f_string\":\"\",\"f_bytes\":[],\"f_unit\":null,\"f_seq\":[],\"f_tuple\":[1,2],\"f_nested_seq\":[]}}",
+            "{\"OtherTypes\":{\"f_string\":\"\",\"f_bytes\":[],\"f_option\":null,\"f_unit\":null,\"f_seq\":[],\"f_opt_seq\":null,\"f_tuple\":[1,2],\"f_nested_seq\":[]}}",
+        ),
+        (
+            "{\"Floats\":{\"f_f32s\":[1,-0,1e2,0.10000000149011612],\"f_f64s\":[2E-1,1.00000000000000000001]}}",
+            "{\"Floats\":{\"f_f32s\":[1.0,-0.0,100.0,0.1],\"f_f64s\":[0.2,1.0]}}",
+        ),
+        (
+            // Like in Rust, large f32 values become infinite, which are written as `null`.
+            "{\"Floats\":{\"f_f32s\":[1e39,-1e39],\"f_f64s\":[]}}",
+            "{\"Floats\":{\"f_f32s\":[null,null],\"f_f64s\":[]}}",
+        ),
+        (
+            "{\"KeyedMaps\":{\"f_stringmap\":{\"a\":1,\"a\":2},\"f_intset\":{},\"f_i8map\":{},\"f_i128map\":{\"-0\":0},\"f_u128map\":{},\"f_boolmap\":{},\"f_charmap\":{},\"f_keymap\":{},\"f_colormap\":{}}}",
+            "{\"KeyedMaps\":{\"f_stringmap\":{\"a\":2},\"f_intset\":{},\"f_i8map\":{},\"f_i128map\":{\"0\":0},\"f_u128map\":{},\"f_boolmap\":{},\"f_charmap\":{},\"f_keymap\":{},\"f_colormap\":{}}}",
+        ),
+    ]
+    .iter()
+    .map(|(input, output)| (input.to_string(), output.to_string()))
+    .collect()
+}
+
+/// Inputs rejected by `serde_json`.
+pub fn get_negative_samples() -> Vec<String> {
+    let mut samples: Vec<_> = [
+        "",
+        "\"UnitVariant\" x",
+        "\"Unknown\"",
+        "{\"Unknown\":null}",
+        "\"NewTypeVariant\"",
+        "{\"UnitVariant\":1}",
+        "{\"UnitVariant\":null,\"TupleArray\":[1,2,3]}",
+        "{\"NewTypeVariant\":\"\\ud800\"}",
+        "{\"NewTypeVariant\":\"\\x\"}",
+        "{\"NewTypeVariant\":\"\t\"}",
+        "{\"NewTypeVariant\":\"a}",
+        "{\"NewTypeVariant\":null}",
+        "{\"TupleVariant\":[1]}",
+        "{\"TupleVariant\":[1,2,3]}",
+        "{\"TupleVariant\":[1,-1]}",
+        "{\"TupleVariant\":[1,-0]}",
+        "{\"TupleVariant\":[1.0,2]}",
+        "{\"TupleVariant\":[1,18446744073709551616]}",
+        "{\"TupleVariant\":[01,2]}",
+        "{\"TupleVariant\":[1,2,]}",
+        "{\"TupleArray\":[1,2]}",
+        "{\"UnitVector\":[null,0]}",
+        "{\"EmptyTupleVariant\":[1]}",
+        "{\"StructVariant\":{\"f0\":null,\"f1\":1,\"f2\":[2,3],\"f3\":{\"x\":4}}}",
+        "{\"StructVariant\":{\"f0\":null,\"f1\":1,\"f2\":[2,3],\"f3\":{\"x\":4,\"x\":4,\"y\":5}}}",
+        "{\"StructVariant\":{\"f0\":{},\"f1\":1,\"f2\":[2,3],\"f3\":{\"x\":4,\"y\":5}}}",
+        "{\"StructVariant\":{\"f0\":null,\"f1\":[1],\"f2\":[2,3],\"f3\":{\"x\":4,\"y\":5}}}",
+        "{\"Floats\":{\"f_f32s\":[],\"f_f64s\":[1e309]}}",
+        "{\"Floats\":{\"f_f32s\":[],\"f_f64s\":[null]}}",
+        "{\"Floats\":{\"f_f32s\":[],\"f_f64s\":[.5]}}",
+        "{\"Floats\":{\"f_f32s\":[],\"f_f64s\":[NaN]}}",
+    ]
+    .iter()
+    .map(|input| input.to_string())
+    .collect();
+
+    // Invalid map keys.
+    for (field, key) in [
+        ("f_intset", "\"-1\""),
+        ("f_intset", "\"01\""),
+        ("f_intset", "\" 1\""),
+        ("f_intset", "\"1.0\""),
+        ("f_i8map", "\"128\""),
+        ("f_i128map", "\"0x1\""),
+        ("f_u128map", "\"-0\""),
+        ("f_boolmap", "\"True\""),
+        ("f_charmap", "\"ab\""),
+        ("f_keymap", "\"k\""),
+        ("f_colormap", "\"Black\""),
+    ] {
+        let maps = [
+            "f_stringmap",
+            "f_intset",
+            "f_i8map",
+            "f_i128map",
+            "f_u128map",
+            "f_boolmap",
+            "f_charmap",
+            "f_keymap",
+            "f_colormap",
+        ]
+        .iter()
+        .map(|name| {
+            if *name == field {
+                format!("\"{}\":{{{}:0}}", name, key)
+            } else {
+                format!("\"{}\":{{}}", name)
+            }
+        })
+        .collect::<Vec<_>>();
+        samples.push(format!("{{\"KeyedMaps\":{{{}}}}}", maps.join(",")));
+    }
+
+    // Too many nested JSON containers.
+    samples.push(serde_json::to_string(&get_sample_value_with_nested_lists(64)).unwrap());
+    samples
+}
+
+#[test]
+fn test_json_samples_agree_with_serde_json() {
+    for sample in get_positive_samples() {
+        let value = serde_json::from_str::<SerdeData>(&sample).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), sample);
+    }
+    for (input, output) in get_alternate_samples() {
+        let value = serde_json::from_str::<SerdeData>(&input).unwrap();
+        assert_eq!(serde_json::to_string(&value).unwrap(), output);
+    }
+    for sample in get_negative_samples() {
+        assert!(
+            serde_json::from_str::<SerdeData>(&sample).is_err(),
+            "{}",
+            sample
+        );
+    }
+}
+
+#[test]
+fn test_json_code_is_generated_from_the_human_readable_registry() {
+    use serde_generate::{CodeGeneratorConfig, Encoding};
+
+    let registries = get_registries().unwrap();
+    let config =
+        CodeGeneratorConfig::new("testing".to_string()).with_encodings(vec![Encoding::Json]);
+    let modes = config.split_modes(&registries);
+    assert_eq!(modes.len(), 1);
+    assert_eq!(modes[0].1, &get_registry().unwrap());
+
+    // The test data serialize in the same way in both modes, hence a single module.
+    assert!(registries.differences().is_empty());
+    let config = config.with_encodings(vec![Encoding::Bcs, Encoding::Json]);
+    let modes = config.split_modes(&registries);
+    assert_eq!(modes.len(), 1);
+    assert_eq!(modes[0].0.encodings, config.encodings);
+    assert_eq!(modes[0].1, &registries.binary);
+}
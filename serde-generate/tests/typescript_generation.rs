@@ -3,7 +3,10 @@
 
 use crate::test_utils;
 use regex::Regex;
-use serde_generate::{typescript, CodeGeneratorConfig, Encoding, SourceInstaller};
+use serde_generate::{
+    typescript, BincodeOptions, CodeGeneratorConfig, Encoding, Endianness, IntEncoding,
+    SourceInstaller,
+};
 use std::{
     collections::BTreeMap,
     fs::File,
@@ -123,3 +126,64 @@ fn test_typescript_code_compiles_with_external_definitions() {
 
     test_typescript_code_compiles_with_config(dir.path(), &config);
 }
+
+#[test]
+fn test_typescript_code_with_comparisons() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_comparisons(true);
+    let mut source = Vec::new();
+    typescript::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+
+    let content = String::from_utf8(source).unwrap();
+    assert!(content.contains("abstract compareTo(other: OrderedData): number;"));
+    assert!(content.contains(
+        "if (!(other instanceof OrderedDataVariantText)) return compareNumbers(3, other.variantIndex());"
+    ));
+    assert!(content.contains("static hashMapStrToU16(value: Map<str,uint16>): number {"));
+}
+
+#[test]
+fn test_typescript_code_with_validation() {
+    let registry = test_utils::get_ordered_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string()).with_validate_on_serialize(true);
+    let mut source = Vec::new();
+    typescript::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+
+    let content = String::from_utf8(source).unwrap();
+    assert!(content.contains("abstract validate(): void;"));
+    assert!(content.contains("Helpers.validateTuple2LabelArray2U16Array(this.value);"));
+    assert!(content.contains(
+        "static serializeArray2U16Array(value: ListTuple<[uint16]>, serializer: Serializer): void {\n    if (value.length !== 2) {"
+    ));
+}
+
+#[test]
+fn test_typescript_code_with_bincode_options() {
+    let registry = test_utils::get_simple_registry().unwrap();
+    let config = CodeGeneratorConfig::new("testing".to_string())
+        .with_encodings(vec![Encoding::Bincode])
+        .with_bincode_options(
+            BincodeOptions::default()
+                .with_int_encoding(IntEncoding::Varint)
+                .with_endianness(Endianness::Big)
+                .with_limit(Some(1000)),
+        );
+    let mut source = Vec::new();
+    typescript::CodeGenerator::new(&config)
+        .output(&mut source, &registry)
+        .unwrap();
+
+    let content = String::from_utf8(source).unwrap();
+    assert!(content
+        .contains("import { BincodeSerializer, BincodeDeserializer } from '../bincode/mod.ts';"));
+    assert!(content.contains("const serializer = new BincodeSerializer(true, true);"));
+    assert!(content.contains("if (bytes.length > 1000) {"));
+    assert!(
+        content.contains("const deserializer = new BincodeDeserializer(input, true, true, 1000);")
+    );
+    assert!(content.contains("if (deserializer.offset < input.length) {"));
+}
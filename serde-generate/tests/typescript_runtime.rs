@@ -2,14 +2,22 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use crate::test_utils;
-use crate::test_utils::{Choice, Runtime, Test};
-use heck::CamelCase;
-use serde_generate::{typescript, CodeGeneratorConfig, SourceInstaller};
+use crate::test_utils::{json_data, Choice, Runtime, Test};
+use serde_generate::{typescript, CodeGeneratorConfig, Encoding, SourceInstaller};
 use std::{fs::File, io::Write, process::Command};
 use tempfile::tempdir;
 
 #[test]
 fn test_typescript_runtime_bcs_serialization() {
+    test_typescript_runtime_serialization(Runtime::Bcs);
+}
+
+#[test]
+fn test_typescript_runtime_bincode_varint_big_endian_serialization() {
+    test_typescript_runtime_serialization(Runtime::BincodeVarintBigEndian);
+}
+
+fn test_typescript_runtime_serialization(runtime: Runtime) {
     let registry = test_utils::get_simple_registry().unwrap();
     let dir = tempdir().unwrap();
     let dir_path = dir.path();
@@ -18,12 +26,14 @@ fn test_typescript_runtime_bcs_serialization() {
     let installer = typescript::Installer::new(dir_path.to_path_buf());
     installer.install_serde_runtime().unwrap();
     installer.install_bcs_runtime().unwrap();
+    installer.install_bincode_runtime().unwrap();
 
     let source_path = dir_path.join("tests/test.ts");
     let mut source = File::create(&source_path).unwrap();
 
-    let runtime = Runtime::Bcs;
-    let config = CodeGeneratorConfig::new("main".to_string()).with_encodings(vec![runtime.into()]);
+    let config = CodeGeneratorConfig::new("main".to_string())
+        .with_encodings(vec![runtime.into()])
+        .with_bincode_options(runtime.bincode_options());
     let generator = typescript::CodeGenerator::new(&config);
     generator.output(&mut source, &registry).unwrap();
 
@@ -39,8 +49,7 @@ fn test_typescript_runtime_bcs_serialization() {
 import {{ assertEquals }} from "https://deno.land/std@0.110.0/testing/asserts.ts";
 Deno.test("{1} serialization matches deserialization", () => {{
 	const expectedBytes = new Uint8Array([{0}]);
-  const {1}Deserializer: {2}Deserializer = new {2}Deserializer(expectedBytes);
-  const deserializedInstance: Test = Test.deserialize({1}Deserializer);
+  const deserializedInstance: Test = Test.{1}Deserialize(expectedBytes);
 
   const expectedInstance: Test = new Test(
 		[4, 6],
@@ -50,9 +59,7 @@ Deno.test("{1} serialization matches deserialization", () => {{
 
   assertEquals(deserializedInstance, expectedInstance, "Object instances should match");
 
-  const {1}Serializer = new {2}Serializer();
-	expectedInstance.serialize({1}Serializer);
-  const serializedBytes = {1}Serializer.getBytes();
+  const serializedBytes = expectedInstance.{1}Serialize();
 
   assertEquals(serializedBytes, expectedBytes, "{1} bytes should match");
 }});
@@ -63,7 +70,70 @@ Deno.test("{1} serialization matches deserialization", () => {{
             .collect::<Vec<_>>()
             .join(", "),
         runtime.name().to_lowercase(),
-        runtime.name().to_camel_case(),
+    )
+    .unwrap();
+
+    let status = Command::new("deno")
+        .current_dir(dir_path)
+        .arg("test")
+        .arg(&source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn test_typescript_runtime_json_serialization() {
+    let registry = json_data::get_registry().unwrap();
+    let dir = tempdir().unwrap();
+    let dir_path = dir.path();
+    std::fs::create_dir_all(dir_path.join("tests")).unwrap();
+
+    let installer = typescript::Installer::new(dir_path.to_path_buf());
+    installer.install_serde_runtime().unwrap();
+    installer.install_bcs_runtime().unwrap();
+    installer.install_json_runtime().unwrap();
+
+    let source_path = dir_path.join("tests/test.ts");
+    let mut source = File::create(&source_path).unwrap();
+
+    let config = CodeGeneratorConfig::new("main".to_string()).with_encodings(vec![Encoding::Json]);
+    let generator = typescript::CodeGenerator::new(&config);
+    generator.output(&mut source, &registry).unwrap();
+
+    // JSON strings are valid TypeScript literals.
+    let alternate_inputs: Vec<_> = json_data::get_alternate_samples()
+        .into_iter()
+        .map(|(input, output)| vec![input, output])
+        .collect();
+
+    writeln!(
+        source,
+        r#"
+import {{ assertEquals, assertThrows }} from "https://deno.land/std@0.110.0/testing/asserts.ts";
+Deno.test("json serialization matches serde_json", () => {{
+  const positiveInputs: string[] = {0};
+  const alternateInputs: string[][] = {1};
+  const negativeInputs: string[] = {2};
+
+  for (const input of positiveInputs) {{
+    const value = SerdeData.jsonDeserialize(input);
+    assertEquals(value.jsonSerialize(), input, "JSON output should match");
+    assertEquals(SerdeData.jsonDeserialize(input), value, "Object instances should match");
+  }}
+
+  for (const [input, output] of alternateInputs) {{
+    assertEquals(SerdeData.jsonDeserialize(input).jsonSerialize(), output, "JSON output should match");
+  }}
+
+  for (const input of negativeInputs) {{
+    assertThrows(() => SerdeData.jsonDeserialize(input));
+  }}
+}});
+"#,
+        serde_json::to_string(&json_data::get_positive_samples()).unwrap(),
+        serde_json::to_string(&alternate_inputs).unwrap(),
+        serde_json::to_string(&json_data::get_negative_samples()).unwrap(),
     )
     .unwrap();
 
@@ -0,0 +1,23 @@
+# {{crate}}
+
+[![serde-name-derive on crates.io](https://img.shields.io/crates/v/serde-name-derive)](https://crates.io/crates/serde-name-derive)
+[![Documentation (latest release)](https://docs.rs/serde-name-derive/badge.svg)](https://docs.rs/serde-name-derive/)
+[![License](https://img.shields.io/badge/license-Apache-green.svg)](../LICENSE-APACHE)
+[![License](https://img.shields.io/badge/license-MIT-green.svg)](../LICENSE-MIT)
+
+{{readme}}
+
+## Contributing
+
+See the [CONTRIBUTING](../CONTRIBUTING.md) file for how to help out.
+
+## License
+
+This project is available under the terms of either the [Apache 2.0 license](../LICENSE-APACHE) or the [MIT license](../LICENSE-MIT).
+
+<!--
+README.md is generated from README.tpl by cargo readme. To regenerate:
+
+cargo install cargo-readme
+cargo readme > README.md
+-->
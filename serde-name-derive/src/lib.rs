@@ -0,0 +1,333 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! This crate provides the attribute `#[serde_name::name]` to override the Serde name of
+//! a container with the adapters of `serde-name`. It is re-exported by `serde-name` under
+//! the feature `derive`.
+//!
+//! The attribute must be placed above `#[derive(Serialize, Deserialize)]`. It turns the
+//! derived code into a remote implementation and generates the `Serialize` and
+//! `Deserialize` implementations that wrap it with `SerializeNameAdapter` and
+//! `DeserializeNameAdapter`. This is mostly useful for generic containers, so that each
+//! instantiation gets a distinct name in a registry.
+//!
+//! ```rust
+//! # use serde::{Deserialize, Serialize};
+//! # use serde_name::trace_name;
+//! #[serde_name::name(short)]
+//! #[derive(Serialize, Deserialize)]
+//! struct Foo<T> {
+//!     data: T,
+//! }
+//!
+//! #[serde_name::name(template = "Pair<{A}, {B}>")]
+//! #[derive(Serialize, Deserialize)]
+//! struct Pair<A, B>(A, B);
+//!
+//! assert_eq!(trace_name::<Foo<u64>>(), Some("Foo_u64"));
+//! assert_eq!(trace_name::<Pair<u8, Vec<u8>>>(), Some("Pair<u8, Vec_u8>"));
+//! ```
+//!
+//! The following naming options are available:
+//! * `#[serde_name::name]` or `#[serde_name::name(type_name)]` uses the full name returned
+//!   by `std::any::type_name`, e.g. `my_crate::Foo<u64>`.
+//! * `#[serde_name::name(short)]` uses the name of the container followed by the short
+//!   names of its type and const parameters, separated by `_`, e.g. `Foo_u64`.
+//! * `#[serde_name::name(template = "...")]` replaces each placeholder `{T}` by the short
+//!   name of the parameter `T`. Braces may be escaped as `{{` and `}}`.
+//!
+//! Short names are computed by `serde_name::short_type_name` and interned by
+//! `serde_name::intern_name` so that they may be used as static strings.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, DeriveInput, GenericParam, Ident, LitStr, WherePredicate,
+};
+
+/// Override the Serde name of a container. See the crate documentation for the options.
+#[proc_macro_attribute]
+pub fn name(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut naming = Naming::TypeName;
+    let parser = syn::meta::parser(|meta| naming.parse(meta));
+    parse_macro_input!(attr with parser);
+    let input = parse_macro_input!(item as DeriveInput);
+    expand(naming, input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How to compute the Serde name of the container.
+enum Naming {
+    TypeName,
+    Short,
+    Template(LitStr),
+}
+
+impl Naming {
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("type_name") {
+            *self = Naming::TypeName;
+        } else if meta.path.is_ident("short") {
+            *self = Naming::Short;
+        } else if meta.path.is_ident("template") {
+            *self = Naming::Template(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected `type_name`, `short`, or `template = \"...\"`"));
+        }
+        Ok(())
+    }
+}
+
+/// A piece of the name of the container.
+enum Part {
+    Literal(String),
+    Parameter(Ident),
+}
+
+fn expand(naming: Naming, mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let (serialize, deserialize) = derived_traits(&input)?;
+    if !serialize && !deserialize {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "expected `#[derive(Serialize)]` or `#[derive(Deserialize)]` below this attribute",
+        ));
+    }
+    if has_remote_attribute(&input)? {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`#[serde(remote = \"...\")]` is already used by this attribute",
+        ));
+    }
+
+    let ident = input.ident.clone();
+    let remote = LitStr::new(&ident.to_string(), ident.span());
+    input.attrs.push(parse_quote!(#[serde(remote = #remote)]));
+
+    let name = name_expr(&naming, &input)?;
+    let mut output = quote!(#input);
+
+    if serialize {
+        let mut generics = input.generics.clone();
+        let predicates = type_parameters(&input)
+            .map(|param| -> WherePredicate { parse_quote!(#param: ::serde::Serialize) })
+            .collect::<Vec<_>>();
+        generics.make_where_clause().predicates.extend(predicates);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        output.extend(quote! {
+            impl #impl_generics ::serde::Serialize for #ident #ty_generics #where_clause {
+                fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    #ident::serialize(
+                        self,
+                        ::serde_name::SerializeNameAdapter::new(serializer, #name),
+                    )
+                }
+            }
+        });
+    }
+
+    if deserialize {
+        let mut generics = input.generics.clone();
+        generics.params.insert(0, parse_quote!('de));
+        let predicates = type_parameters(&input)
+            .map(|param| -> WherePredicate { parse_quote!(#param: ::serde::Deserialize<'de>) })
+            .collect::<Vec<_>>();
+        generics.make_where_clause().predicates.extend(predicates);
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let (_, ty_generics, _) = input.generics.split_for_impl();
+        output.extend(quote! {
+            impl #impl_generics ::serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    #ident::deserialize(::serde_name::DeserializeNameAdapter::new(
+                        deserializer,
+                        #name,
+                    ))
+                }
+            }
+        });
+    }
+
+    Ok(output)
+}
+
+/// Whether the container derives `Serialize` and `Deserialize`, respectively.
+fn derived_traits(input: &DeriveInput) -> syn::Result<(bool, bool)> {
+    let mut serialize = false;
+    let mut deserialize = false;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("derive") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if let Some(segment) = meta.path.segments.last() {
+                if segment.ident == "Serialize" {
+                    serialize = true;
+                } else if segment.ident == "Deserialize" {
+                    deserialize = true;
+                }
+            }
+            Ok(())
+        })?;
+    }
+    Ok((serialize, deserialize))
+}
+
+fn has_remote_attribute(input: &DeriveInput) -> syn::Result<bool> {
+    let mut found = false;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("remote") {
+                found = true;
+            }
+            // Consume the value of the attribute, if any.
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(found)
+}
+
+fn type_parameters(input: &DeriveInput) -> impl Iterator<Item = &Ident> {
+    input
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(&param.ident),
+            _ => None,
+        })
+}
+
+/// The expression computing the `&'static str` name of the container.
+fn name_expr(naming: &Naming, input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let parts = match naming {
+        Naming::TypeName => return Ok(quote!(::std::any::type_name::<Self>())),
+        Naming::Short => {
+            let mut parts = vec![Part::Literal(input.ident.to_string())];
+            for param in &input.generics.params {
+                let ident = match param {
+                    GenericParam::Type(param) => &param.ident,
+                    GenericParam::Const(param) => &param.ident,
+                    GenericParam::Lifetime(_) => continue,
+                };
+                parts.push(Part::Literal("_".to_string()));
+                parts.push(Part::Parameter(ident.clone()));
+            }
+            parts
+        }
+        Naming::Template(template) => parse_template(template, input)?,
+    };
+
+    let mut literal = String::new();
+    let mut statements = Vec::new();
+    for part in parts {
+        match part {
+            Part::Literal(value) => literal.push_str(&value),
+            Part::Parameter(ident) => {
+                if !literal.is_empty() {
+                    statements.push(quote!(name.push_str(#literal);));
+                    literal.clear();
+                }
+                statements.push(parameter_name(&ident, input));
+            }
+        }
+    }
+    if statements.is_empty() {
+        // No parameters: the name is a constant.
+        return Ok(quote!(#literal));
+    }
+    if !literal.is_empty() {
+        statements.push(quote!(name.push_str(#literal);));
+    }
+    Ok(quote! {
+        ::serde_name::intern_name({
+            let mut name = ::std::string::String::new();
+            #(#statements)*
+            name
+        })
+    })
+}
+
+/// The statement appending the short name of a type or const parameter.
+fn parameter_name(ident: &Ident, input: &DeriveInput) -> TokenStream2 {
+    let is_const = input
+        .generics
+        .const_params()
+        .any(|param| &param.ident == ident);
+    if is_const {
+        quote!(name.push_str(&#ident.to_string());)
+    } else {
+        quote!(name.push_str(&::serde_name::short_type_name(::std::any::type_name::<#ident>()));)
+    }
+}
+
+fn parse_template(template: &LitStr, input: &DeriveInput) -> syn::Result<Vec<Part>> {
+    let value = template.value();
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut param = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => param.push(c),
+                        None => {
+                            return Err(syn::Error::new(template.span(), "unclosed placeholder"))
+                        }
+                    }
+                }
+                let param = param.trim();
+                let ident = input
+                    .generics
+                    .params
+                    .iter()
+                    .find_map(|p| match p {
+                        GenericParam::Type(p) if p.ident == param => Some(p.ident.clone()),
+                        GenericParam::Const(p) if p.ident == param => Some(p.ident.clone()),
+                        _ => None,
+                    })
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            template.span(),
+                            format!("unknown type or const parameter `{}`", param),
+                        )
+                    })?;
+                parts.push(Part::Literal(std::mem::take(&mut literal)));
+                parts.push(Part::Parameter(ident));
+            }
+            '}' => return Err(syn::Error::new(template.span(), "unmatched `}`")),
+            c => literal.push(c),
+        }
+    }
+    parts.push(Part::Literal(literal));
+    Ok(parts)
+}
@@ -0,0 +1,72 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use serde_name::trace_name;
+use serde_reflection::{ContainerFormat, Format, Named, Samples, Tracer, TracerConfig};
+
+#[serde_name::name]
+#[derive(Serialize, Deserialize)]
+struct Full<T> {
+    data: T,
+}
+
+#[serde_name::name(short)]
+#[derive(Serialize, Deserialize)]
+struct Short<T, const N: usize>(Vec<T>);
+
+#[serde_name::name(short)]
+#[derive(Serialize, Deserialize)]
+enum Choice<A, B> {
+    Left(A),
+    Right(B),
+}
+
+#[serde_name::name(template = "Map{{{K} -> {V}}}")]
+#[derive(Serialize)]
+struct Map<K, V> {
+    entries: Vec<(K, V)>,
+}
+
+#[test]
+fn test_names() {
+    assert!(trace_name::<Full<u64>>().unwrap().ends_with("::Full<u64>"));
+    assert_eq!(trace_name::<Short<String, 2>>(), Some("Short_String_2"));
+    assert_eq!(
+        trace_name::<Choice<Vec<u8>, Option<bool>>>(),
+        Some("Choice_Vec_u8_Option_bool")
+    );
+    // Interned names are allocated once.
+    assert!(std::ptr::eq(
+        trace_name::<Short<u8, 1>>().unwrap(),
+        trace_name::<Short<u8, 1>>().unwrap()
+    ));
+}
+
+#[test]
+fn test_tracing() {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let samples = Samples::new();
+    tracer
+        .trace_type::<Choice<u8, Short<u16, 3>>>(&samples)
+        .unwrap();
+    let mut samples = Samples::new();
+    let map = Map {
+        entries: vec![(1u8, true)],
+    };
+    tracer.trace_value(&mut samples, &map).unwrap();
+    let registry = tracer.registry().unwrap();
+
+    assert_eq!(
+        registry.get("Short_u16_3").unwrap(),
+        &ContainerFormat::NewTypeStruct(Box::new(Format::Seq(Box::new(Format::U16))))
+    );
+    assert!(registry.contains_key("Choice_u8_Short_u16_3"));
+    assert_eq!(
+        registry.get("Map{u8 -> bool}").unwrap(),
+        &ContainerFormat::Struct(vec![Named {
+            name: "entries".into(),
+            value: Format::Seq(Box::new(Format::Tuple(vec![Format::U8, Format::Bool])))
+        }])
+    );
+}
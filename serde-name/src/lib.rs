@@ -82,11 +82,43 @@
 //! ident.normalize().unwrap();
 //! assert!(matches!(ident, Format::TypeName(s) if s.ends_with("Foo<u64>")));
 //! ```
+//!
+//! With the `derive` feature, the attribute `#[serde_name::name]` generates the two
+//! implementations above. It also accepts `#[serde_name::name(short)]` for names such as
+//! `Foo_u64` and `#[serde_name::name(template = "Foo<{T}>")]` for custom names. See the
+//! crate `serde-name-derive` for details.
+//!
+//! # Parsing type names
+//!
+//! Names returned by `std::any::type_name` are not valid identifiers in most languages.
+//! `TypeName::parse` turns them into a tree of paths, identifiers, and generic arguments.
+//! Renderers such as `UnderscoreRenderer` and `PascalCaseRenderer` then produce
+//! identifiers, and `unique_identifiers` avoids collisions between names.
+//!
+//! ```rust
+//! # use serde_name::{unique_identifiers, PascalCaseRenderer, TypeName};
+//! let name = TypeName::parse("my_crate::a::Foo<alloc::string::String>").unwrap();
+//! assert_eq!(name.to_identifier(&PascalCaseRenderer, 0), "FooString");
+//!
+//! let names = ["my_crate::a::Foo<u8>", "my_crate::b::Foo<u8>"];
+//! let identifiers = unique_identifiers(names, &PascalCaseRenderer);
+//! assert_eq!(identifiers["my_crate::a::Foo<u8>"], "AFooU8");
+//! ```
 
 mod de_adapter;
+mod naming;
 mod ser_adapter;
 mod trace;
+mod type_name;
 
 pub use de_adapter::DeserializeNameAdapter;
+pub use naming::intern_name;
 pub use ser_adapter::SerializeNameAdapter;
 pub use trace::trace_name;
+pub use type_name::{
+    short_type_name, unique_identifiers, PascalCaseRenderer, TypeName, TypeNameError,
+    TypeNameRenderer, UnderscoreRenderer,
+};
+
+#[cfg(feature = "derive")]
+pub use serde_name_derive::name;
@@ -0,0 +1,24 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use once_cell::sync::Lazy;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+// Created lazily because `Mutex::new` and `BTreeSet::new` are not `const` before Rust 1.63
+// and 1.66 respectively.
+static NAMES: Lazy<Mutex<BTreeSet<&'static str>>> = Lazy::new(|| Mutex::new(BTreeSet::new()));
+
+/// Return a `&'static str` with the same content as the given name. Each distinct name
+/// is allocated only once for the lifetime of the program.
+pub fn intern_name(name: String) -> &'static str {
+    let mut names = NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    match names.get(name.as_str()) {
+        Some(interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(name.into_boxed_str());
+            names.insert(interned);
+            interned
+        }
+    }
+}
@@ -0,0 +1,498 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use thiserror::Error;
+
+/// A Rust type name, as returned by `std::any::type_name`, parsed into a tree.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum TypeName {
+    /// A path such as `alloc::vec::Vec<u8>`.
+    Path {
+        /// The module path, e.g. `["alloc", "vec"]`.
+        path: Vec<String>,
+        /// The last segment of the path, e.g. `Vec`.
+        ident: String,
+        /// The generic arguments of the last segment, e.g. `[u8]`.
+        args: Vec<TypeName>,
+    },
+    /// A tuple `(A, B)`. The unit type is the empty tuple.
+    Tuple(Vec<TypeName>),
+    /// An array `[T; N]`.
+    Array {
+        content: Box<TypeName>,
+        size: String,
+    },
+    /// A slice `[T]`.
+    Slice(Box<TypeName>),
+    /// A reference `&T` or `&mut T`.
+    Reference {
+        mutable: bool,
+        content: Box<TypeName>,
+    },
+    /// A raw pointer `*const T` or `*mut T`.
+    Pointer {
+        mutable: bool,
+        content: Box<TypeName>,
+    },
+    /// A trait object `dyn A + B`.
+    Dyn(Vec<TypeName>),
+    /// A const generic argument, e.g. `32`.
+    Const(String),
+}
+
+/// Error returned when a type name cannot be parsed.
+#[derive(Clone, Debug, Error, PartialEq)]
+#[error("Failed to parse type name {name:?} at position {position}")]
+pub struct TypeNameError {
+    pub name: String,
+    pub position: usize,
+}
+
+/// How to combine the words of a type name into an identifier, e.g. `["Foo", "u64"]`.
+pub trait TypeNameRenderer {
+    fn join(&self, words: &[String]) -> String;
+}
+
+/// Join words with underscores, e.g. `Foo_u64`. This is the default renderer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnderscoreRenderer;
+
+/// Concatenate capitalized words, e.g. `FooU64`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PascalCaseRenderer;
+
+impl TypeNameRenderer for UnderscoreRenderer {
+    fn join(&self, words: &[String]) -> String {
+        words.join("_")
+    }
+}
+
+impl TypeNameRenderer for PascalCaseRenderer {
+    fn join(&self, words: &[String]) -> String {
+        let mut result = String::new();
+        for word in words {
+            let mut chars = word.chars();
+            if let Some(c) = chars.next() {
+                result.extend(c.to_uppercase());
+                result.extend(chars);
+            }
+        }
+        result
+    }
+}
+
+impl TypeName {
+    /// Parse the output of `std::any::type_name`.
+    ///
+    /// ```rust
+    /// # use serde_name::TypeName;
+    /// let name = TypeName::parse("my_crate::Foo<alloc::string::String>").unwrap();
+    /// assert!(matches!(&name, TypeName::Path { ident, args, .. } if ident == "Foo" && args.len() == 1));
+    /// assert_eq!(name.to_string(), "my_crate::Foo<alloc::string::String>");
+    /// ```
+    pub fn parse(name: &str) -> Result<Self, TypeNameError> {
+        let mut parser = Parser {
+            input: name,
+            position: 0,
+        };
+        let result = parser.parse_type()?;
+        parser.skip_spaces();
+        if parser.position != name.len() {
+            return Err(parser.error());
+        }
+        Ok(result)
+    }
+
+    /// The words making up the short name of the type: module paths and punctuation are
+    /// removed, except for the last `depth` modules of the outermost path.
+    pub fn words(&self, depth: usize) -> Vec<String> {
+        let mut words = Vec::new();
+        self.push_words(depth, &mut words);
+        words
+    }
+
+    /// Render the type as an identifier made of ASCII alphanumeric characters and `_`, not
+    /// starting with a digit.
+    ///
+    /// ```rust
+    /// # use serde_name::{PascalCaseRenderer, TypeName, UnderscoreRenderer};
+    /// let name = TypeName::parse("my_crate::a::Foo<alloc::vec::Vec<u8>>").unwrap();
+    /// assert_eq!(name.to_identifier(&UnderscoreRenderer, 0), "Foo_Vec_u8");
+    /// assert_eq!(name.to_identifier(&UnderscoreRenderer, 1), "a_Foo_Vec_u8");
+    /// assert_eq!(name.to_identifier(&PascalCaseRenderer, 0), "FooVecU8");
+    /// ```
+    pub fn to_identifier(&self, renderer: &dyn TypeNameRenderer, depth: usize) -> String {
+        to_identifier(&renderer.join(&self.words(depth)))
+    }
+
+    /// The number of modules that may qualify the outermost path.
+    pub fn max_depth(&self) -> usize {
+        match self {
+            TypeName::Path { path, .. } => path.len(),
+            _ => 0,
+        }
+    }
+
+    fn push_words(&self, depth: usize, words: &mut Vec<String>) {
+        match self {
+            TypeName::Path { path, ident, args } => {
+                let start = path.len().saturating_sub(depth);
+                words.extend(path[start..].iter().cloned());
+                words.push(ident.clone());
+                for arg in args {
+                    arg.push_words(0, words);
+                }
+            }
+            TypeName::Tuple(elements) => {
+                if elements.is_empty() {
+                    words.push("Unit".to_string());
+                }
+                for element in elements {
+                    element.push_words(0, words);
+                }
+            }
+            TypeName::Array { content, size } => {
+                content.push_words(0, words);
+                words.push(size.clone());
+            }
+            TypeName::Slice(content)
+            | TypeName::Reference { content, .. }
+            | TypeName::Pointer { content, .. } => content.push_words(0, words),
+            TypeName::Dyn(bounds) => {
+                for bound in bounds {
+                    bound.push_words(0, words);
+                }
+            }
+            TypeName::Const(value) => words.push(value.clone()),
+        }
+    }
+}
+
+impl fmt::Display for TypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn list(f: &mut fmt::Formatter<'_>, items: &[TypeName], sep: &str) -> fmt::Result {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    write!(f, "{}", sep)?;
+                }
+                write!(f, "{}", item)?;
+            }
+            Ok(())
+        }
+
+        match self {
+            TypeName::Path { path, ident, args } => {
+                for module in path {
+                    write!(f, "{}::", module)?;
+                }
+                write!(f, "{}", ident)?;
+                if !args.is_empty() {
+                    write!(f, "<")?;
+                    list(f, args, ", ")?;
+                    write!(f, ">")?;
+                }
+                Ok(())
+            }
+            TypeName::Tuple(elements) => {
+                write!(f, "(")?;
+                list(f, elements, ", ")?;
+                if elements.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            TypeName::Array { content, size } => write!(f, "[{}; {}]", content, size),
+            TypeName::Slice(content) => write!(f, "[{}]", content),
+            TypeName::Reference { mutable, content } => {
+                write!(f, "&{}{}", if *mutable { "mut " } else { "" }, content)
+            }
+            TypeName::Pointer { mutable, content } => {
+                write!(f, "*{} {}", if *mutable { "mut" } else { "const" }, content)
+            }
+            TypeName::Dyn(bounds) => {
+                write!(f, "dyn ")?;
+                list(f, bounds, " + ")
+            }
+            TypeName::Const(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self) -> TypeNameError {
+        TypeNameError {
+            name: self.input.to_string(),
+            position: self.position,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn skip_spaces(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_spaces();
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), TypeNameError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// Consume a keyword followed by a space, e.g. `dyn `.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.skip_spaces();
+        let rest = self.rest();
+        if rest.starts_with(keyword) && rest[keyword.len()..].starts_with(' ') {
+            self.position += keyword.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn word(&mut self) -> Result<String, TypeNameError> {
+        self.skip_spaces();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error());
+        }
+        self.position += len;
+        Ok(rest[..len].to_string())
+    }
+
+    fn parse_list(&mut self, end: &str) -> Result<Vec<TypeName>, TypeNameError> {
+        let mut items = Vec::new();
+        while !self.eat(end) {
+            if self.eat("'") {
+                // Lifetimes are ignored.
+                self.word()?;
+            } else {
+                items.push(self.parse_type()?);
+            }
+            if !self.eat(",") {
+                self.expect(end)?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn parse_type(&mut self) -> Result<TypeName, TypeNameError> {
+        if self.eat("&") {
+            if self.eat("'") {
+                self.word()?;
+            }
+            let mutable = self.eat_keyword("mut");
+            let content = Box::new(self.parse_type()?);
+            return Ok(TypeName::Reference { mutable, content });
+        }
+        if self.eat("*") {
+            let mutable = if self.eat_keyword("mut") {
+                true
+            } else if self.eat_keyword("const") {
+                false
+            } else {
+                return Err(self.error());
+            };
+            let content = Box::new(self.parse_type()?);
+            return Ok(TypeName::Pointer { mutable, content });
+        }
+        if self.eat("(") {
+            return Ok(TypeName::Tuple(self.parse_list(")")?));
+        }
+        if self.eat("[") {
+            let content = Box::new(self.parse_type()?);
+            if self.eat(";") {
+                let size = self.word()?;
+                self.expect("]")?;
+                return Ok(TypeName::Array { content, size });
+            }
+            self.expect("]")?;
+            return Ok(TypeName::Slice(content));
+        }
+        if self.eat_keyword("dyn") {
+            let mut bounds = vec![self.parse_type()?];
+            while self.eat("+") {
+                bounds.push(self.parse_type()?);
+            }
+            return Ok(TypeName::Dyn(bounds));
+        }
+        let word = self.word()?;
+        if word.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+            return Ok(TypeName::Const(word));
+        }
+        let mut path = Vec::new();
+        let mut ident = word;
+        while self.rest().starts_with("::") && !self.rest().starts_with("::<") {
+            self.position += 2;
+            path.push(std::mem::replace(&mut ident, self.word()?));
+        }
+        // Turbofish is not expected but harmless.
+        self.eat("::");
+        let args = if self.eat("<") {
+            self.parse_list(">")?
+        } else {
+            Vec::new()
+        };
+        Ok(TypeName::Path { path, ident, args })
+    }
+}
+
+/// Keep ASCII alphanumeric characters and `_`, and avoid a leading digit.
+fn to_identifier(name: &str) -> String {
+    let mut result: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if result.is_empty() || result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+    result
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The words of a type name, falling back to splitting the name on punctuation when the
+/// name cannot be parsed.
+fn words(name: &str, parsed: Option<&TypeName>, depth: usize) -> Vec<String> {
+    match parsed {
+        Some(parsed) => parsed.words(depth),
+        None => {
+            let mut words = Vec::new();
+            let mut rest = name;
+            while !rest.is_empty() {
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                let (word, tail) = rest.split_at(len);
+                // Words followed by `::` are module paths.
+                if !word.is_empty() && !tail.starts_with("::") {
+                    words.push(word.to_string());
+                }
+                rest = if word.is_empty() { &tail[1..] } else { tail };
+                rest = rest.strip_prefix("::").unwrap_or(rest);
+            }
+            words
+        }
+    }
+}
+
+/// Shorten a type name as returned by `std::any::type_name` into an identifier-like
+/// string: module paths are removed and the remaining words are joined with `_`.
+///
+/// ```rust
+/// # use serde_name::short_type_name;
+/// assert_eq!(short_type_name("alloc::vec::Vec<u8>"), "Vec_u8");
+/// assert_eq!(short_type_name("(u32, [alloc::string::String; 2])"), "u32_String_2");
+/// ```
+pub fn short_type_name(type_name: &str) -> String {
+    let parsed = TypeName::parse(type_name).ok();
+    UnderscoreRenderer.join(&words(type_name, parsed.as_ref(), 0))
+}
+
+/// Map each of the given names to a distinct identifier. Names that are already
+/// identifiers are kept unchanged. Other names are parsed and rendered, using the
+/// module path of the type and then a numeric suffix to avoid collisions.
+///
+/// ```rust
+/// # use serde_name::{unique_identifiers, UnderscoreRenderer};
+/// let names = ["a::Foo<u8>", "b::Foo<u8>", "Bar<u64>", "Bar_u64"];
+/// let identifiers = unique_identifiers(names, &UnderscoreRenderer);
+/// assert_eq!(identifiers["a::Foo<u8>"], "a_Foo_u8");
+/// assert_eq!(identifiers["b::Foo<u8>"], "b_Foo_u8");
+/// assert_eq!(identifiers["Bar<u64>"], "Bar_u64_2");
+/// assert_eq!(identifiers["Bar_u64"], "Bar_u64");
+/// ```
+pub fn unique_identifiers<'a, I>(
+    names: I,
+    renderer: &dyn TypeNameRenderer,
+) -> BTreeMap<String, String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut result = BTreeMap::new();
+    let mut taken = BTreeSet::new();
+    // Names to be rendered, with their parsed form and the current qualification depth.
+    let mut pending = Vec::new();
+    for name in names {
+        if is_identifier(name) {
+            result.insert(name.to_string(), name.to_string());
+            taken.insert(name.to_string());
+        } else {
+            pending.push((name, TypeName::parse(name).ok(), 0));
+        }
+    }
+
+    while !pending.is_empty() {
+        let mut candidates = BTreeMap::<String, Vec<usize>>::new();
+        for (index, (name, parsed, depth)) in pending.iter().enumerate() {
+            let candidate = to_identifier(&renderer.join(&words(name, parsed.as_ref(), *depth)));
+            candidates.entry(candidate).or_default().push(index);
+        }
+        let mut done = BTreeSet::new();
+        for (candidate, indices) in candidates {
+            if indices.len() == 1 && !taken.contains(&candidate) {
+                done.insert(indices[0]);
+                result.insert(pending[indices[0]].0.to_string(), candidate.clone());
+                taken.insert(candidate);
+                continue;
+            }
+            let mut progress = false;
+            for &index in &indices {
+                let (_, parsed, depth) = &mut pending[index];
+                if *depth < parsed.as_ref().map_or(0, TypeName::max_depth) {
+                    *depth += 1;
+                    progress = true;
+                }
+            }
+            if !progress {
+                // Fully qualified names still collide: use numeric suffixes.
+                for index in indices {
+                    let mut suffix = 2;
+                    while taken.contains(&format!("{}_{}", candidate, suffix)) {
+                        suffix += 1;
+                    }
+                    let identifier = format!("{}_{}", candidate, suffix);
+                    done.insert(index);
+                    result.insert(pending[index].0.to_string(), identifier.clone());
+                    taken.insert(identifier);
+                }
+            }
+        }
+        let mut index = 0;
+        pending.retain(|_| {
+            index += 1;
+            !done.contains(&(index - 1))
+        });
+    }
+    result
+}
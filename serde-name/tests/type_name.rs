@@ -0,0 +1,88 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use serde_name::{
+    short_type_name, unique_identifiers, PascalCaseRenderer, TypeName, UnderscoreRenderer,
+};
+use std::any::type_name;
+use std::collections::BTreeMap;
+
+mod a {
+    pub struct Foo<T>(pub T);
+}
+
+mod b {
+    pub struct Foo<T>(pub T);
+}
+
+#[test]
+fn test_parse_round_trip() {
+    let names = [
+        type_name::<a::Foo<String>>(),
+        type_name::<Vec<(u8, Option<bool>)>>(),
+        type_name::<BTreeMap<[u16; 4], &str>>(),
+        type_name::<Box<dyn std::any::Any + Send>>(),
+        type_name::<*const [u8]>(),
+        type_name::<()>(),
+        type_name::<(u8,)>(),
+    ];
+    for name in names {
+        let parsed = TypeName::parse(name).unwrap();
+        assert_eq!(parsed.to_string(), name);
+    }
+}
+
+#[test]
+fn test_parse_tree() {
+    assert_eq!(
+        TypeName::parse("x::Foo<[u8; 2], ()>").unwrap(),
+        TypeName::Path {
+            path: vec!["x".into()],
+            ident: "Foo".into(),
+            args: vec![
+                TypeName::Array {
+                    content: Box::new(TypeName::Path {
+                        path: vec![],
+                        ident: "u8".into(),
+                        args: vec![],
+                    }),
+                    size: "2".into(),
+                },
+                TypeName::Tuple(vec![]),
+            ],
+        }
+    );
+    assert!(TypeName::parse("Foo<u8").is_err());
+    assert!(TypeName::parse("main::{{closure}}").is_err());
+}
+
+#[test]
+fn test_identifiers() {
+    let name = TypeName::parse(type_name::<a::Foo<Option<String>>>()).unwrap();
+    assert_eq!(
+        name.to_identifier(&UnderscoreRenderer, 0),
+        "Foo_Option_String"
+    );
+    assert_eq!(
+        name.to_identifier(&PascalCaseRenderer, 1),
+        "AFooOptionString"
+    );
+    assert_eq!(short_type_name(type_name::<&[u8]>()), "u8");
+    // Unparsable names are split on punctuation.
+    assert_eq!(short_type_name("main::{{closure}}"), "closure");
+}
+
+#[test]
+fn test_unique_identifiers() {
+    let names = [
+        type_name::<a::Foo<u8>>(),
+        type_name::<b::Foo<u8>>(),
+        type_name::<a::Foo<u16>>(),
+        "Foo_u16",
+    ];
+    let identifiers = unique_identifiers(names, &UnderscoreRenderer);
+    assert_eq!(identifiers[names[0]], "a_Foo_u8");
+    assert_eq!(identifiers[names[1]], "b_Foo_u8");
+    assert_eq!(identifiers[names[2]], "a_Foo_u16");
+    assert_eq!(identifiers[names[3]], "Foo_u16");
+}
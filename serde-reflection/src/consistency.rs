@@ -0,0 +1,449 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Detection of asymmetries between the serialization and the deserialization of a type.
+
+use crate::{
+    diff::{
+        compare_containers, compare_formats, known, DifferenceKind, FormatDifference,
+        MissingVariants,
+    },
+    error::{Error, Result},
+    format::*,
+    trace::{Registry, Samples, Tracer},
+    value::Value,
+};
+use serde::{de::DeserializeOwned, de::IntoDeserializer, Serialize};
+use std::fmt;
+
+/// A disagreement between the implementations of `Serialize` and `Deserialize` of a type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Asymmetry {
+    /// Location of the problem, e.g. `Foo.bar[]` or `Choice::A.0`.
+    /// The empty path denotes the top-level type.
+    pub path: String,
+    /// Description of the problem.
+    pub kind: AsymmetryKind,
+}
+
+/// The different kinds of asymmetries reported by `Tracer::check_consistency`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AsymmetryKind {
+    /// A container or a variant was produced by serialization but is unknown to deserialization.
+    SerializationOnly,
+    /// The two tracing methods disagree on the format of an anonymous value.
+    FormatMismatch {
+        serialized: Format,
+        deserialized: Format,
+    },
+    /// The two tracing methods disagree on the format of a container.
+    ContainerMismatch {
+        serialized: ContainerFormat,
+        deserialized: ContainerFormat,
+    },
+    /// The two tracing methods disagree on the name or the format of a variant.
+    VariantMismatch {
+        serialized: Named<VariantFormat>,
+        deserialized: Named<VariantFormat>,
+    },
+    /// Tracing deserialization failed.
+    DeserializationFailure(Error),
+    /// A recorded sample does not conform to the format obtained by tracing deserialization.
+    InvalidSample { expected: String, value: Value },
+    /// A recorded sample could not be deserialized back into a Rust value.
+    RoundTripFailure(Error),
+    /// A recorded sample was deserialized then serialized again into a different value.
+    RoundTripMismatch { before: Value, after: Value },
+}
+
+impl fmt::Display for Asymmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "<root>"
+        } else {
+            &self.path
+        };
+        use AsymmetryKind::*;
+        match &self.kind {
+            SerializationOnly => write!(f, "{}: only seen while tracing serialization", path),
+            FormatMismatch {
+                serialized,
+                deserialized,
+            } => write!(
+                f,
+                "{}: serialized as {:?} but deserialized as {:?}",
+                path, serialized, deserialized
+            ),
+            ContainerMismatch {
+                serialized,
+                deserialized,
+            } => write!(
+                f,
+                "{}: serialized as {:?} but deserialized as {:?}",
+                path, serialized, deserialized
+            ),
+            VariantMismatch {
+                serialized,
+                deserialized,
+            } => write!(
+                f,
+                "{}: serialized as {:?} but deserialized as {:?}",
+                path, serialized, deserialized
+            ),
+            DeserializationFailure(error) => {
+                write!(f, "{}: failed to trace deserialization: {}", path, error)
+            }
+            InvalidSample { expected, value } => {
+                write!(
+                    f,
+                    "{}: expected {} but sample is {:?}",
+                    path, expected, value
+                )
+            }
+            RoundTripFailure(error) => {
+                write!(f, "{}: failed to deserialize sample: {}", path, error)
+            }
+            RoundTripMismatch { before, after } => write!(
+                f,
+                "{}: sample {:?} was serialized again as {:?}",
+                path, before, after
+            ),
+        }
+    }
+}
+
+impl From<FormatDifference> for Asymmetry {
+    fn from(difference: FormatDifference) -> Self {
+        use DifferenceKind::*;
+        let kind = match difference.kind {
+            LeftOnly => AsymmetryKind::SerializationOnly,
+            RightOnly => unreachable!("containers only known to deserialization are not reported"),
+            FormatMismatch { left, right } => AsymmetryKind::FormatMismatch {
+                serialized: left,
+                deserialized: right,
+            },
+            ContainerMismatch { left, right } => AsymmetryKind::ContainerMismatch {
+                serialized: left,
+                deserialized: right,
+            },
+            VariantMismatch { left, right } => AsymmetryKind::VariantMismatch {
+                serialized: left,
+                deserialized: right,
+            },
+        };
+        Self {
+            path: difference.path,
+            kind,
+        }
+    }
+}
+
+impl Tracer {
+    /// Trace the type `T` both by serializing the given `values` and by tracing
+    /// deserialization, then report the asymmetries found between the two.
+    /// * The two formats are compared container by container.
+    /// * Every recorded sample is checked against the deserialization format, then
+    ///   deserialized and serialized again.
+    ///
+    /// Tracing uses fresh tracers with the same configuration as `self`, so the registry of
+    /// `self` is left untouched. Samples recorded from `values` are added to `samples`.
+    ///
+    /// Note that enums nested in `T` are only partially explored by deserialization. Variants
+    /// seen during serialization are only reported as missing for enums that were fully traced.
+    pub fn check_consistency<T>(
+        &self,
+        samples: &mut Samples,
+        values: &[T],
+    ) -> Result<Vec<Asymmetry>>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let mut asymmetries = Vec::new();
+
+        let mut ser_tracer = Tracer::new(self.config.clone());
+        let mut ser_format = Format::unknown();
+        let mut recorded = Vec::new();
+        for value in values {
+            let (format, sample) = ser_tracer.trace_value(samples, value)?;
+            ser_format.unify(format)?;
+            recorded.push(sample);
+        }
+        ser_format.reduce();
+        let ser_registry = ser_tracer.registry_unchecked();
+
+        let mut de_tracer = Tracer::new(self.config.clone());
+        // Containers with a recorded sample are not explored while tracing deserialization.
+        // As in a regular tracing session, their formats come from serialization.
+        for name in samples.values.keys() {
+            if let Some(format) = ser_registry.get(*name) {
+                de_tracer.registry.insert(name.to_string(), format.clone());
+            }
+        }
+        let de_format = match de_tracer.trace_type::<T>(samples) {
+            Ok((format, _)) => Some(format),
+            Err(error) => {
+                asymmetries.push(Asymmetry {
+                    path: String::new(),
+                    kind: AsymmetryKind::DeserializationFailure(error),
+                });
+                None
+            }
+        };
+        let incomplete_enums = std::mem::take(&mut de_tracer.incomplete_enums);
+        let de_registry = de_tracer.registry_unchecked();
+
+        let mut differences = Vec::new();
+        if let Some(de_format) = &de_format {
+            compare_formats(&mut differences, String::new(), &ser_format, de_format);
+        }
+        for (name, ser_container) in &ser_registry {
+            match de_registry.get(name) {
+                Some(de_container) => compare_containers(
+                    &mut differences,
+                    name,
+                    ser_container,
+                    de_container,
+                    MissingVariants {
+                        left_only: !incomplete_enums.contains_key(name),
+                        right_only: false,
+                    },
+                ),
+                None => differences.push(FormatDifference {
+                    path: name.clone(),
+                    kind: DifferenceKind::LeftOnly,
+                }),
+            }
+        }
+        asymmetries.extend(differences.into_iter().map(Asymmetry::from));
+
+        if let Some(de_format) = &de_format {
+            for (index, sample) in recorded.iter().enumerate() {
+                let path = format!("[{}]", index);
+                let mut sample_asymmetries = Vec::new();
+                check_sample(
+                    &mut sample_asymmetries,
+                    &de_registry,
+                    path.clone(),
+                    de_format,
+                    sample,
+                );
+                if !sample_asymmetries.is_empty() {
+                    asymmetries.extend(sample_asymmetries);
+                    continue;
+                }
+                match T::deserialize(sample.into_deserializer()) {
+                    Ok(value) => {
+                        let mut tracer = Tracer::new(self.config.clone());
+                        let (_, after) = tracer.trace_value(&mut Samples::new(), &value)?;
+                        if &after != sample {
+                            asymmetries.push(Asymmetry {
+                                path,
+                                kind: AsymmetryKind::RoundTripMismatch {
+                                    before: sample.clone(),
+                                    after,
+                                },
+                            });
+                        }
+                    }
+                    Err(error) => asymmetries.push(Asymmetry {
+                        path,
+                        kind: AsymmetryKind::RoundTripFailure(error),
+                    }),
+                }
+            }
+        }
+        Ok(asymmetries)
+    }
+}
+
+/// Verify that a recorded sample conforms to the given format, in the way a
+/// format-driven decoder would read it.
+fn check_sample(
+    asymmetries: &mut Vec<Asymmetry>,
+    registry: &Registry,
+    path: String,
+    format: &Format,
+    value: &Value,
+) {
+    let format = match known(format) {
+        Some(format) => format,
+        None => return,
+    };
+    use Format::*;
+    match (&format, value) {
+        (Unit, Value::Unit)
+        | (Bool, Value::Bool(_))
+        | (I8, Value::I8(_))
+        | (I16, Value::I16(_))
+        | (I32, Value::I32(_))
+        | (I64, Value::I64(_))
+        | (I128, Value::I128(_))
+        | (U8, Value::U8(_))
+        | (U16, Value::U16(_))
+        | (U32, Value::U32(_))
+        | (U64, Value::U64(_))
+        | (U128, Value::U128(_))
+        | (F32, Value::F32(_))
+        | (F64, Value::F64(_))
+        | (Char, Value::Char(_))
+        | (Str, Value::Str(_))
+        | (Bytes, Value::Bytes(_))
+        | (Option(_), Value::Option(None)) => (),
+
+        (Option(format), Value::Option(Some(value))) => {
+            check_sample(asymmetries, registry, format!("{}?", path), format, value)
+        }
+        (Seq(format), Value::Seq(values)) => {
+            for (i, value) in values.iter().enumerate() {
+                check_sample(
+                    asymmetries,
+                    registry,
+                    format!("{}[{}]", path, i),
+                    format,
+                    value,
+                );
+            }
+        }
+        (
+            Map {
+                key,
+                value: content,
+            },
+            Value::Seq(values),
+        ) if values.len() % 2 == 0 => {
+            for (i, entry) in values.chunks(2).enumerate() {
+                let path = format!("{}[{}]", path, i);
+                check_sample(
+                    asymmetries,
+                    registry,
+                    format!("{}.key", path),
+                    key,
+                    &entry[0],
+                );
+                check_sample(
+                    asymmetries,
+                    registry,
+                    format!("{}.value", path),
+                    content,
+                    &entry[1],
+                );
+            }
+        }
+        (Tuple(formats), Value::Seq(values)) if formats.len() == values.len() => {
+            for (i, (format, value)) in formats.iter().zip(values).enumerate() {
+                check_sample(
+                    asymmetries,
+                    registry,
+                    format!("{}.{}", path, i),
+                    format,
+                    value,
+                );
+            }
+        }
+        (TupleArray { content, size }, Value::Seq(values)) if *size == values.len() => {
+            for (i, value) in values.iter().enumerate() {
+                check_sample(
+                    asymmetries,
+                    registry,
+                    format!("{}[{}]", path, i),
+                    content,
+                    value,
+                );
+            }
+        }
+        (TypeName(name), value) => match registry.get(name) {
+            Some(container) => {
+                check_container_sample(asymmetries, registry, path, name, container, value)
+            }
+            None => asymmetries.push(invalid_sample(path, format!("container {}", name), value)),
+        },
+        (format, value) => asymmetries.push(invalid_sample(path, format!("{:?}", format), value)),
+    }
+}
+
+fn check_container_sample(
+    asymmetries: &mut Vec<Asymmetry>,
+    registry: &Registry,
+    path: String,
+    name: &str,
+    container: &ContainerFormat,
+    value: &Value,
+) {
+    use ContainerFormat::*;
+    match (container, value) {
+        (UnitStruct, Value::Unit) => (),
+        (NewTypeStruct(format), value) => check_sample(asymmetries, registry, path, format, value),
+        (TupleStruct(formats), Value::Seq(values)) if formats.len() == values.len() => {
+            for (i, (format, value)) in formats.iter().zip(values).enumerate() {
+                check_sample(
+                    asymmetries,
+                    registry,
+                    format!("{}.{}", path, i),
+                    format,
+                    value,
+                );
+            }
+        }
+        (Struct(fields), Value::Seq(values)) | (FlattenedStruct(fields), Value::Seq(values))
+            if fields.len() == values.len() =>
+        {
+            for (field, value) in fields.iter().zip(values) {
+                let path = format!("{}.{}", path, field.name);
+                check_sample(asymmetries, registry, path, &field.value, value);
+            }
+        }
+        (Enum(variants), Value::Variant(index, value))
+        | (TaggedEnum { variants, .. }, Value::Variant(index, value))
+            if variants.contains_key(index) =>
+        {
+            let variant = &variants[index];
+            let path = format!("{}::{}", path, variant.name);
+            check_variant_sample(asymmetries, registry, path, &variant.value, value);
+        }
+        (OpenEnum(_), Value::Seq(values))
+            if matches!(values.as_slice(), [Value::Str(_), Value::Bytes(_)]) => {}
+        _ => asymmetries.push(invalid_sample(path, format!("container {}", name), value)),
+    }
+}
+
+fn check_variant_sample(
+    asymmetries: &mut Vec<Asymmetry>,
+    registry: &Registry,
+    path: String,
+    variant: &VariantFormat,
+    value: &Value,
+) {
+    use VariantFormat::*;
+    match (variant, value) {
+        (Variable(_), _) | (Unit, Value::Unit) => (),
+        (NewType(format), value) => check_sample(asymmetries, registry, path, format, value),
+        (Tuple(formats), Value::Seq(values)) if formats.len() == values.len() => {
+            for (i, (format, value)) in formats.iter().zip(values).enumerate() {
+                check_sample(
+                    asymmetries,
+                    registry,
+                    format!("{}.{}", path, i),
+                    format,
+                    value,
+                );
+            }
+        }
+        (Struct(fields), Value::Seq(values)) if fields.len() == values.len() => {
+            for (field, value) in fields.iter().zip(values) {
+                let path = format!("{}.{}", path, field.name);
+                check_sample(asymmetries, registry, path, &field.value, value);
+            }
+        }
+        _ => asymmetries.push(invalid_sample(path, format!("{:?}", variant), value)),
+    }
+}
+
+fn invalid_sample(path: String, expected: String, value: &Value) -> Asymmetry {
+    Asymmetry {
+        path,
+        kind: AsymmetryKind::InvalidSample {
+            expected,
+            value: value.clone(),
+        },
+    }
+}
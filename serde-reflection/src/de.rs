@@ -235,6 +235,15 @@ impl<'de, 'a> de::Deserializer<'de> for Deserializer<'de, 'a> {
         V: Visitor<'de>,
     {
         self.format.unify(Format::TypeName(name.into()))?;
+        if self.tracer.is_open_enum(name) {
+            // The content of open enums is not visible to the tracer.
+            if let Some(sample) = self.samples.value(name) {
+                return visitor.visit_newtype_struct(sample.into_deserializer());
+            }
+            let mut format = Format::Tuple(vec![Format::Str, Format::Bytes]);
+            let inner = Deserializer::new(self.tracer, self.samples, &mut format);
+            return visitor.visit_newtype_struct(inner);
+        }
         if self.tracer.config.record_samples_for_newtype_structs {
             // If a value was recorded during serialization, use it.
             if let Some((format, sample)) = self.tracer.get_sample(self.samples, name) {
@@ -0,0 +1,343 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Structural comparison of formats.
+
+use crate::{format::*, trace::Registry};
+use std::{collections::BTreeMap, fmt};
+
+/// A difference between two formats, e.g. between two registries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatDifference {
+    /// Location of the difference, e.g. `Foo.bar[]` or `Choice::A.0`.
+    pub path: String,
+    /// Description of the difference.
+    pub kind: DifferenceKind,
+}
+
+/// The different kinds of differences between two formats.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DifferenceKind {
+    /// A container or a variant only exists on the left-hand side.
+    LeftOnly,
+    /// A container or a variant only exists on the right-hand side.
+    RightOnly,
+    /// The two sides disagree on the format of an anonymous value.
+    FormatMismatch { left: Format, right: Format },
+    /// The two sides disagree on the format of a container.
+    ContainerMismatch {
+        left: ContainerFormat,
+        right: ContainerFormat,
+    },
+    /// The two sides disagree on the name or the format of a variant.
+    VariantMismatch {
+        left: Named<VariantFormat>,
+        right: Named<VariantFormat>,
+    },
+}
+
+impl fmt::Display for FormatDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DifferenceKind::*;
+        match &self.kind {
+            LeftOnly => write!(f, "{}: only on the left-hand side", self.path),
+            RightOnly => write!(f, "{}: only on the right-hand side", self.path),
+            FormatMismatch { left, right } => write!(f, "{}: {:?} vs {:?}", self.path, left, right),
+            ContainerMismatch { left, right } => {
+                write!(f, "{}: {:?} vs {:?}", self.path, left, right)
+            }
+            VariantMismatch { left, right } => {
+                write!(f, "{}: {:?} vs {:?}", self.path, left, right)
+            }
+        }
+    }
+}
+
+/// Which variants to report when they are missing from one side of a comparison.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MissingVariants {
+    pub(crate) left_only: bool,
+    pub(crate) right_only: bool,
+}
+
+/// Compare two registries container by container.
+/// * Containers and variants present on one side only are reported as such.
+/// * Otherwise, differences are reported at the deepest path where the formats disagree.
+pub fn diff_registries(left: &Registry, right: &Registry) -> Vec<FormatDifference> {
+    let mut differences = Vec::new();
+    let missing_variants = MissingVariants {
+        left_only: true,
+        right_only: true,
+    };
+    for (name, left_format) in left {
+        match right.get(name) {
+            Some(right_format) => compare_containers(
+                &mut differences,
+                name,
+                left_format,
+                right_format,
+                missing_variants,
+            ),
+            None => differences.push(FormatDifference {
+                path: name.clone(),
+                kind: DifferenceKind::LeftOnly,
+            }),
+        }
+    }
+    for name in right.keys() {
+        if !left.contains_key(name) {
+            differences.push(FormatDifference {
+                path: name.clone(),
+                kind: DifferenceKind::RightOnly,
+            });
+        }
+    }
+    differences
+}
+
+/// Return the content of a format, looking through known variables.
+/// Returns `None` for variables with an unknown value.
+pub(crate) fn known(format: &Format) -> Option<Format> {
+    match format {
+        Format::Variable(variable) => variable.borrow().as_ref().and_then(known),
+        _ => Some(format.clone()),
+    }
+}
+
+pub(crate) fn compare_formats(
+    differences: &mut Vec<FormatDifference>,
+    path: String,
+    left: &Format,
+    right: &Format,
+) {
+    let (left, right) = match (known(left), known(right)) {
+        (Some(x), Some(y)) => (x, y),
+        // Nothing to compare.
+        _ => return,
+    };
+    use Format::*;
+    match (&left, &right) {
+        (Option(format1), Option(format2)) => {
+            compare_formats(differences, format!("{}?", path), format1, format2)
+        }
+        (Seq(format1), Seq(format2)) => {
+            compare_formats(differences, format!("{}[]", path), format1, format2)
+        }
+        (
+            Map {
+                key: key1,
+                value: value1,
+            },
+            Map {
+                key: key2,
+                value: value2,
+            },
+        ) => {
+            compare_formats(differences, format!("{}.key", path), key1, key2);
+            compare_formats(differences, format!("{}.value", path), value1, value2);
+        }
+        (Tuple(formats1), Tuple(formats2)) if formats1.len() == formats2.len() => {
+            for (i, (format1, format2)) in formats1.iter().zip(formats2).enumerate() {
+                compare_formats(differences, format!("{}.{}", path, i), format1, format2);
+            }
+        }
+        (
+            TupleArray {
+                content: content1,
+                size: size1,
+            },
+            TupleArray {
+                content: content2,
+                size: size2,
+            },
+        ) if size1 == size2 => {
+            compare_formats(differences, format!("{}[]", path), content1, content2)
+        }
+        (format1, format2) if format1 == format2 => (),
+        _ => differences.push(FormatDifference {
+            path,
+            kind: DifferenceKind::FormatMismatch { left, right },
+        }),
+    }
+}
+
+fn compare_named_formats(
+    differences: &mut Vec<FormatDifference>,
+    path: &str,
+    left: &[Named<Format>],
+    right: &[Named<Format>],
+) -> bool {
+    if left.len() != right.len() || left.iter().zip(right).any(|(x, y)| x.name != y.name) {
+        return false;
+    }
+    for (x, y) in left.iter().zip(right) {
+        compare_formats(
+            differences,
+            format!("{}.{}", path, x.name),
+            &x.value,
+            &y.value,
+        );
+    }
+    true
+}
+
+fn compare_unnamed_formats(
+    differences: &mut Vec<FormatDifference>,
+    path: &str,
+    left: &[Format],
+    right: &[Format],
+) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    for (i, (x, y)) in left.iter().zip(right).enumerate() {
+        compare_formats(differences, format!("{}.{}", path, i), x, y);
+    }
+    true
+}
+
+pub(crate) fn compare_containers(
+    differences: &mut Vec<FormatDifference>,
+    name: &str,
+    left: &ContainerFormat,
+    right: &ContainerFormat,
+    missing_variants: MissingVariants,
+) {
+    use ContainerFormat::*;
+    let is_comparable = match (left, right) {
+        (UnitStruct, UnitStruct) => true,
+        (NewTypeStruct(format1), NewTypeStruct(format2)) => {
+            compare_formats(differences, name.to_string(), format1, format2);
+            true
+        }
+        (TupleStruct(formats1), TupleStruct(formats2)) => {
+            compare_unnamed_formats(differences, name, formats1, formats2)
+        }
+        (Struct(fields1), Struct(fields2))
+        | (FlattenedStruct(fields1), FlattenedStruct(fields2)) => {
+            compare_named_formats(differences, name, fields1, fields2)
+        }
+        (Enum(variants1), Enum(variants2)) => {
+            compare_variants(differences, name, variants1, variants2, missing_variants);
+            true
+        }
+        (
+            TaggedEnum {
+                representation: representation1,
+                variants: variants1,
+            },
+            TaggedEnum {
+                representation: representation2,
+                variants: variants2,
+            },
+        ) if representation1 == representation2 => {
+            compare_variants(differences, name, variants1, variants2, missing_variants);
+            true
+        }
+        (OpenEnum(formats1), OpenEnum(formats2)) => {
+            compare_open_variants(differences, name, formats1, formats2, missing_variants);
+            true
+        }
+        _ => false,
+    };
+    if !is_comparable {
+        differences.push(FormatDifference {
+            path: name.to_string(),
+            kind: DifferenceKind::ContainerMismatch {
+                left: left.clone(),
+                right: right.clone(),
+            },
+        });
+    }
+}
+
+fn compare_variants(
+    differences: &mut Vec<FormatDifference>,
+    name: &str,
+    left: &BTreeMap<u32, Named<VariantFormat>>,
+    right: &BTreeMap<u32, Named<VariantFormat>>,
+    missing_variants: MissingVariants,
+) {
+    for (index, variant1) in left {
+        let path = format!("{}::{}", name, variant1.name);
+        let variant2 = match right.get(index) {
+            Some(variant) => variant,
+            None => {
+                if missing_variants.left_only {
+                    differences.push(FormatDifference {
+                        path,
+                        kind: DifferenceKind::LeftOnly,
+                    });
+                }
+                continue;
+            }
+        };
+        use VariantFormat::*;
+        let is_comparable = variant1.name == variant2.name
+            && match (&variant1.value, &variant2.value) {
+                (Unit, Unit) => true,
+                (NewType(format1), NewType(format2)) => {
+                    compare_formats(differences, path.clone(), format1, format2);
+                    true
+                }
+                (Tuple(formats1), Tuple(formats2)) => {
+                    compare_unnamed_formats(differences, &path, formats1, formats2)
+                }
+                (Struct(fields1), Struct(fields2)) => {
+                    compare_named_formats(differences, &path, fields1, fields2)
+                }
+                // Variables should have been removed by normalization.
+                (Variable(_), _) | (_, Variable(_)) => true,
+                _ => false,
+            };
+        if !is_comparable {
+            differences.push(FormatDifference {
+                path,
+                kind: DifferenceKind::VariantMismatch {
+                    left: variant1.clone(),
+                    right: variant2.clone(),
+                },
+            });
+        }
+    }
+    if missing_variants.right_only {
+        for (index, variant2) in right {
+            if !left.contains_key(index) {
+                differences.push(FormatDifference {
+                    path: format!("{}::{}", name, variant2.name),
+                    kind: DifferenceKind::RightOnly,
+                });
+            }
+        }
+    }
+}
+
+fn compare_open_variants(
+    differences: &mut Vec<FormatDifference>,
+    name: &str,
+    left: &BTreeMap<String, Format>,
+    right: &BTreeMap<String, Format>,
+    missing_variants: MissingVariants,
+) {
+    for (tag, format1) in left {
+        let path = format!("{}::{}", name, tag);
+        match right.get(tag) {
+            Some(format2) => compare_formats(differences, path, format1, format2),
+            None if missing_variants.left_only => differences.push(FormatDifference {
+                path,
+                kind: DifferenceKind::LeftOnly,
+            }),
+            None => (),
+        }
+    }
+    if missing_variants.right_only {
+        for tag in right.keys() {
+            if !left.contains_key(tag) {
+                differences.push(FormatDifference {
+                    path: format!("{}::{}", name, tag),
+                    kind: DifferenceKind::RightOnly,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tracing of the human-readable and the binary modes of (de)serialization at once.
+
+use crate::{
+    diff::{diff_registries, FormatDifference},
+    error::Result,
+    format::Format,
+    trace::{Registry, Samples, Tracer, TracerConfig},
+    value::Value,
+};
+use serde::{Deserialize, Serialize};
+
+/// A pair of objects, one for each mode of (de)serialization.
+/// See `Serializer::is_human_readable` in Serde.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Dual<T> {
+    /// The object for human-readable encodings (e.g. JSON).
+    pub human_readable: T,
+    /// The object for binary encodings (e.g. Bincode, BCS).
+    pub binary: T,
+}
+
+impl<T> Dual<T> {
+    /// Select the object for the given mode.
+    pub fn get(&self, is_human_readable: bool) -> &T {
+        if is_human_readable {
+            &self.human_readable
+        } else {
+            &self.binary
+        }
+    }
+
+    /// Same as `get` but mutable.
+    pub fn get_mut(&mut self, is_human_readable: bool) -> &mut T {
+        if is_human_readable {
+            &mut self.human_readable
+        } else {
+            &mut self.binary
+        }
+    }
+}
+
+impl Dual<Registry> {
+    /// Compare the registry of the human-readable mode (left-hand side) with the
+    /// registry of the binary mode (right-hand side).
+    pub fn differences(&self) -> Vec<FormatDifference> {
+        diff_registries(&self.human_readable, &self.binary)
+    }
+}
+
+/// Structure to drive the tracing of both modes of Serde (de)serialization.
+/// Every call is forwarded to two `Tracer`s that only differ by the option `is_human_readable`.
+#[derive(Debug)]
+pub struct DualTracer {
+    tracers: Dual<Tracer>,
+}
+
+impl DualTracer {
+    /// Start tracing. The option `is_human_readable` of `config` is ignored.
+    pub fn new(config: TracerConfig) -> Self {
+        Self {
+            tracers: Dual {
+                human_readable: Tracer::new(config.clone().is_human_readable(true)),
+                binary: Tracer::new(config.is_human_readable(false)),
+            },
+        }
+    }
+
+    /// Trace the serialization of a particular value in both modes.
+    /// See `Tracer::trace_value`.
+    pub fn trace_value<T>(
+        &mut self,
+        samples: &mut Dual<Samples>,
+        value: &T,
+    ) -> Result<Dual<(Format, Value)>>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(Dual {
+            human_readable: self
+                .tracers
+                .human_readable
+                .trace_value(&mut samples.human_readable, value)?,
+            binary: self
+                .tracers
+                .binary
+                .trace_value(&mut samples.binary, value)?,
+        })
+    }
+
+    /// Trace the deserialization of a type in both modes.
+    /// See `Tracer::trace_type`.
+    pub fn trace_type<'de, T>(
+        &mut self,
+        samples: &'de Dual<Samples>,
+    ) -> Result<Dual<(Format, Vec<T>)>>
+    where
+        T: Deserialize<'de>,
+    {
+        Ok(Dual {
+            human_readable: self
+                .tracers
+                .human_readable
+                .trace_type(&samples.human_readable)?,
+            binary: self.tracers.binary.trace_type(&samples.binary)?,
+        })
+    }
+
+    /// Trace the deserialization of a type that needs no samples in both modes.
+    /// See `Tracer::trace_simple_type`.
+    pub fn trace_simple_type<'de, T>(&mut self) -> Result<Dual<(Format, Vec<T>)>>
+    where
+        T: Deserialize<'de>,
+    {
+        Ok(Dual {
+            human_readable: self.tracers.human_readable.trace_simple_type()?,
+            binary: self.tracers.binary.trace_simple_type()?,
+        })
+    }
+
+    /// Access the tracer of the given mode, e.g. to trace a type with a seed.
+    pub fn tracer_mut(&mut self, is_human_readable: bool) -> &mut Tracer {
+        self.tracers.get_mut(is_human_readable)
+    }
+
+    /// Finish tracing and recover the registries of both modes.
+    /// See `Tracer::registry`.
+    pub fn registries(self) -> Result<Dual<Registry>> {
+        Ok(Dual {
+            human_readable: self.tracers.human_readable.registry()?,
+            binary: self.tracers.binary.registry()?,
+        })
+    }
+}
@@ -65,7 +65,9 @@ are generally not supported. This includes: `#[serde(flatten)]`, `#[serde(tag =
 `#[serde(tag = "t", content = "c")]`, and `#[serde(untagged)]`.
 
 To fix this, avoid unsupported Serde attributes or use custom (de)serialize implementations with different
-behaviors depending on the Serde callback `(De)Serializer::is_human_readable()`.
+behaviors depending on the Serde callback `(De)Serializer::is_human_readable()`. In human-readable mode,
+enums using `tag`, `content`, or `untagged` may be traced with `Tracer::trace_tagged_enum` instead, and
+structs using `flatten` with `Tracer::trace_flattened_struct`.
 "#.to_string()
             }
             DeserializationError(_) => {
@@ -0,0 +1,103 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tracing of structs with flattened fields in human-readable mode.
+
+use crate::{
+    error::{Error, Result},
+    format::*,
+    ser::{Context, Serializer},
+    trace::{Samples, Tracer},
+    value::Value,
+};
+use serde::Serialize;
+
+impl Tracer {
+    /// Trace the serialization of a struct `name` containing fields marked with
+    /// `#[serde(flatten)]`, using `value` as a sample.
+    /// * Serde serializes such structs as maps without a name, where the fields of flattened
+    ///   containers appear in place. All the fields must be present in `value`.
+    /// * The resulting container format is a `ContainerFormat::FlattenedStruct`.
+    ///
+    /// Deserializing such structs requires `deserialize_any`, which is not supported. However,
+    /// once traced with this method, flattened structs are recognized by their field names
+    /// when they occur in values passed to `trace_value`.
+    ///
+    /// This is only supported in human-readable mode (see `TracerConfig::is_human_readable`).
+    pub fn trace_flattened_struct<T>(
+        &mut self,
+        samples: &mut Samples,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(Format, Value)>
+    where
+        T: ?Sized + Serialize,
+    {
+        if !self.config.is_human_readable {
+            return Err(Error::NotSupported(
+                "tracing flattened structs outside of human-readable mode",
+            ));
+        }
+        let serializer = Serializer::with_context(self, samples, Context::FlattenedStruct(name));
+        let (format, value) = value.serialize(serializer)?;
+        match self.registry.get(name) {
+            Some(ContainerFormat::FlattenedStruct(_))
+                if format == Format::TypeName(name.into()) =>
+            {
+                Ok((format, value))
+            }
+            _ => Err(Error::Custom(format!(
+                "Flattened struct {} was not serialized as a map",
+                name
+            ))),
+        }
+    }
+
+    /// Look for a flattened struct with the given keys as field names.
+    pub(crate) fn find_flattened_struct(&self, keys: &[(Format, Value)]) -> Option<String> {
+        if !self.config.is_human_readable {
+            return None;
+        }
+        self.registry
+            .iter()
+            .find(|(_, format)| match format {
+                ContainerFormat::FlattenedStruct(fields) => fields.len() == keys.len()
+                    && fields.iter().zip(keys).all(
+                        |(field, (_, key))| matches!(key, Value::Str(key) if *key == field.name),
+                    ),
+                _ => false,
+            })
+            .map(|(name, _)| name.clone())
+    }
+
+    /// Interpret the entries of a map as the fields of the flattened struct `name`.
+    pub(crate) fn record_flattened_struct(
+        &mut self,
+        name: &str,
+        keys: Vec<(Format, Value)>,
+        values: Vec<(Format, Value)>,
+    ) -> Result<(Format, Value)> {
+        let mut fields = Vec::new();
+        let mut field_values = Vec::new();
+        for ((_, key), (format, value)) in keys.into_iter().zip(values) {
+            let field_name = match key {
+                Value::Str(field_name) => field_name,
+                _ => {
+                    return Err(Error::Custom(format!(
+                        "Flattened struct {} was serialized with keys that are not strings",
+                        name
+                    )))
+                }
+            };
+            fields.push(Named {
+                name: field_name,
+                value: format,
+            });
+            field_values.push(value);
+        }
+        self.registry
+            .entry(name.to_string())
+            .unify(ContainerFormat::FlattenedStruct(fields))?;
+        Ok((Format::TypeName(name.into()), Value::Seq(field_values)))
+    }
+}
@@ -91,6 +91,38 @@ pub enum ContainerFormat {
     /// An enum, that is, an enumeration of variants.
     /// Each variant has a unique name and index within the enum.
     Enum(BTreeMap<u32, Named<VariantFormat>>),
+    /// An enum with a non-default Serde representation, e.g. `#[serde(tag = "type")]`.
+    /// Such enums are only meaningful for self-describing (human-readable) encodings.
+    #[serde(rename_all = "UPPERCASE")]
+    TaggedEnum {
+        representation: EnumRepresentation,
+        variants: BTreeMap<u32, Named<VariantFormat>>,
+    },
+    /// A struct serialized as a map of its fields because some of them are flattened, e.g.
+    /// with `#[serde(flatten)]`. The fields of flattened containers are listed in place.
+    /// Such structs are only meaningful for self-describing (human-readable) encodings.
+    FlattenedStruct(Vec<Named<Format>>),
+    /// An extensible enum, e.g. a trait object serialized through a registry of
+    /// implementations. Each known implementation is listed under its tag with the
+    /// format of its content.
+    /// Values are encoded as a pair `(tag, content)` where the content is itself encoded
+    /// as bytes, so that decoders can skip unknown tags.
+    OpenEnum(BTreeMap<String, Format>),
+}
+
+/// The representation of an enum in self-describing encodings.
+/// See <https://serde.rs/enum-representations.html>.
+#[derive(Serialize, Deserialize, Debug, Eq, Clone, PartialEq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum EnumRepresentation {
+    /// Internally tagged, e.g. `#[serde(tag = "type")]`.
+    #[serde(rename_all = "UPPERCASE")]
+    Internal { tag: String },
+    /// Adjacently tagged, e.g. `#[serde(tag = "t", content = "c")]`.
+    #[serde(rename_all = "UPPERCASE")]
+    Adjacent { tag: String, content: String },
+    /// Untagged, i.e. `#[serde(untagged)]`.
+    Untagged,
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
@@ -413,16 +445,21 @@ impl FormatHolder for ContainerFormat {
                     format.visit(f)?;
                 }
             }
-            Self::Struct(named_formats) => {
+            Self::Struct(named_formats) | Self::FlattenedStruct(named_formats) => {
                 for format in named_formats {
                     format.visit(f)?;
                 }
             }
-            Self::Enum(variants) => {
+            Self::Enum(variants) | Self::TaggedEnum { variants, .. } => {
                 for variant in variants {
                     variant.1.visit(f)?;
                 }
             }
+            Self::OpenEnum(formats) => {
+                for format in formats.values() {
+                    format.visit(f)?;
+                }
+            }
         }
         Ok(())
     }
@@ -436,16 +473,21 @@ impl FormatHolder for ContainerFormat {
                     format.visit_mut(f)?;
                 }
             }
-            Self::Struct(named_formats) => {
+            Self::Struct(named_formats) | Self::FlattenedStruct(named_formats) => {
                 for format in named_formats {
                     format.visit_mut(f)?;
                 }
             }
-            Self::Enum(variants) => {
+            Self::Enum(variants) | Self::TaggedEnum { variants, .. } => {
                 for variant in variants {
                     variant.1.visit_mut(f)?;
                 }
             }
+            Self::OpenEnum(formats) => {
+                for format in formats.values_mut() {
+                    format.visit_mut(f)?;
+                }
+            }
         }
         Ok(())
     }
@@ -467,6 +509,7 @@ impl FormatHolder for ContainerFormat {
             }
 
             (Self::Struct(named_formats1), Self::Struct(named_formats2))
+            | (Self::FlattenedStruct(named_formats1), Self::FlattenedStruct(named_formats2))
                 if named_formats1.len() == named_formats2.len() =>
             {
                 for (format1, format2) in named_formats1.iter_mut().zip(named_formats2.into_iter())
@@ -476,14 +519,30 @@ impl FormatHolder for ContainerFormat {
             }
 
             (Self::Enum(variants1), Self::Enum(variants2)) => {
-                for (index2, variant2) in variants2.into_iter() {
-                    match variants1.entry(index2) {
+                unify_variants(variants1, variants2)?;
+            }
+
+            (
+                Self::TaggedEnum {
+                    representation: representation1,
+                    variants: variants1,
+                },
+                Self::TaggedEnum {
+                    representation: representation2,
+                    variants: variants2,
+                },
+            ) if *representation1 == representation2 => {
+                unify_variants(variants1, variants2)?;
+            }
+
+            (Self::OpenEnum(formats1), Self::OpenEnum(formats2)) => {
+                for (tag, format2) in formats2.into_iter() {
+                    match formats1.entry(tag) {
                         Entry::Vacant(e) => {
-                            // Note that we do not check for name collisions.
-                            e.insert(variant2);
+                            e.insert(format2);
                         }
                         Entry::Occupied(mut e) => {
-                            e.get_mut().unify(variant2)?;
+                            e.get_mut().unify(format2)?;
                         }
                     }
                 }
@@ -687,6 +746,24 @@ impl FormatHolder for Format {
     }
 }
 
+fn unify_variants(
+    variants1: &mut BTreeMap<u32, Named<VariantFormat>>,
+    variants2: BTreeMap<u32, Named<VariantFormat>>,
+) -> Result<()> {
+    for (index2, variant2) in variants2.into_iter() {
+        match variants1.entry(index2) {
+            Entry::Vacant(e) => {
+                // Note that we do not check for name collisions.
+                e.insert(variant2);
+            }
+            Entry::Occupied(mut e) => {
+                e.get_mut().unify(variant2)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Helper trait to update formats in maps.
 pub(crate) trait ContainerFormatEntry {
     fn unify(self, format: ContainerFormat) -> Result<()>;
@@ -92,15 +92,39 @@
 //!   recursion-free. (For instance, `enum List { None, Some(Box<List>)}`.) Note that each
 //!   enum must be traced separately with `trace_type` to discover all the variants.
 //!
+//! * Types whose implementations depend on `is_human_readable()` (e.g. addresses written as
+//!   hex strings in JSON). A `DualTracer` traces both modes at once and returns one registry per
+//!   mode. Use `registries.differences()` to list where the two modes disagree.
+//!
+//! * Enums using `#[serde(tag = ..)]`, `#[serde(tag = .., content = ..)]`, or `#[serde(untagged)]`,
+//!   in human-readable mode only. Call `tracer.trace_tagged_enum` with one sample per variant. The
+//!   resulting `ContainerFormat::TaggedEnum` is only supported by the Rust code generator, which
+//!   derives the matching Serde attributes. Other code generators reject it, including for the
+//!   `json` encoding.
+//!
+//! * Structs with fields marked `#[serde(flatten)]`, in human-readable mode only. Call
+//!   `tracer.trace_flattened_struct` with a sample value. The resulting
+//!   `ContainerFormat::FlattenedStruct` lists the fields of flattened containers in place.
+//!
+//! * Open enums, i.e. trait objects serialized through a registry of implementations (as in
+//!   `typetag`). Register each implementation under its tag with `tracer.trace_open_enum_variant`.
+//!   Values are encoded as a tag followed by the content as bytes, so that generated code may
+//!   skip unknown variants. The code generated for Python, Rust, Go, OCaml and Haskell lists the
+//!   known tags and lets clients register a handler per tag: values with other tags are skipped.
+//!   Python classes also decode the known variants. Other generators decode open enums as a struct
+//!   with the tag and the content as bytes.
+//!
 //! ## Unsupported idioms
 //!
 //! * Containers sharing the same base name (e.g. `Foo`) but from different modules. (Work
 //!   around: use `#[serde(rename = ..)]`)
 //!
 //! * Generic types instantiated multiple times in the same tracing session. (Work around:
-//!   use the crate [`serde-name`](https://crates.io/crates/serde-name) and its adapters `SerializeNameAdapter` and `DeserializeNameAdapter`.)
+//!   use the crate [`serde-name`](https://crates.io/crates/serde-name) and its adapters `SerializeNameAdapter` and `DeserializeNameAdapter`.
+//!   The code generators of `serde-generate` turn names such as `my_crate::Foo<u64>` into identifiers.)
 //!
-//! * Attributes that are not compatible with binary formats (e.g. `#[serde(flatten)]`, `#[serde(tag = ..)]`)
+//! * Attributes that are not compatible with binary formats (e.g. `#[serde(skip_serializing_if = ..)]`),
+//!   except for the representations mentioned above.
 //!
 //! * Tracing type aliases. (E.g. `type Pair = (u32, u64)` will not create an entry "Pair".)
 //!
@@ -131,6 +155,10 @@
 //! The error type used in this crate provides a method `error.explanation()` to help with
 //! troubleshooting during format tracing.
 //!
+//! When custom implementations of `Serialize` and `Deserialize` are suspected to disagree,
+//! `tracer.check_consistency(&mut samples, &values)` traces a type both ways and returns the
+//! asymmetries found, each with the path of the offending container, field, or variant.
+//!
 //! # Detailed Example
 //!
 //! In the following, more complete example, we extract the Serde formats of two containers
@@ -354,14 +382,25 @@
 //! other than the parameter `T` of the main call `trace_type<T>`. As a consequence, each enum type must be
 //! traced separately.
 
+mod consistency;
 mod de;
+mod diff;
+mod dual;
 mod error;
+mod flatten;
 mod format;
+mod open;
 mod ser;
+mod tagged;
 mod trace;
 mod value;
 
+pub use consistency::{Asymmetry, AsymmetryKind};
+pub use diff::{diff_registries, DifferenceKind, FormatDifference};
+pub use dual::{Dual, DualTracer};
 pub use error::{Error, Result};
-pub use format::{ContainerFormat, Format, FormatHolder, Named, Variable, VariantFormat};
+pub use format::{
+    ContainerFormat, EnumRepresentation, Format, FormatHolder, Named, Variable, VariantFormat,
+};
 pub use trace::{Registry, Samples, Tracer, TracerConfig};
 pub use value::Value;
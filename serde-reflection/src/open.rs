@@ -0,0 +1,86 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tracing of open enums, i.e. trait objects serialized through a registry of implementations.
+
+use crate::{
+    error::{Error, Result},
+    format::*,
+    trace::{Samples, Tracer},
+    value::Value,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+impl Tracer {
+    /// Register the implementation of the open enum `name` with the given `tag`, using
+    /// `value` as a sample of this implementation. Returns the format of the content.
+    ///
+    /// Open enums are meant for trait objects (e.g. `Box<dyn Event>`) serialized through a
+    /// registry of concrete implementations, in the style of `typetag`. To be traced, a
+    /// trait object must be serialized as a newtype struct named after the open enum and
+    /// holding a pair `(tag, content)`, where the content is the concrete value encoded
+    /// as bytes (e.g. using `serde_bytes`). Every tag must be registered before tracing
+    /// values that contain the open enum.
+    pub fn trace_open_enum_variant<T>(
+        &mut self,
+        samples: &mut Samples,
+        name: &'static str,
+        tag: &str,
+        value: &T,
+    ) -> Result<Format>
+    where
+        T: ?Sized + Serialize,
+    {
+        let (format, _) = self.trace_value(samples, value)?;
+        let mut formats = BTreeMap::new();
+        formats.insert(tag.to_string(), format.clone());
+        self.registry
+            .entry(name.to_string())
+            .unify(ContainerFormat::OpenEnum(formats))?;
+        Ok(format)
+    }
+
+    pub(crate) fn is_open_enum(&self, name: &str) -> bool {
+        matches!(self.registry.get(name), Some(ContainerFormat::OpenEnum(_)))
+    }
+
+    /// Check a value serialized under the name of an open enum.
+    pub(crate) fn record_open_enum_value(
+        &mut self,
+        samples: &mut Samples,
+        name: &'static str,
+        format: Format,
+        value: Value,
+    ) -> Result<(Format, Value)> {
+        let tag = match (&format, &value) {
+            (Format::Tuple(formats), Value::Seq(values))
+                if formats == &[Format::Str, Format::Bytes] =>
+            {
+                match &values[0] {
+                    Value::Str(tag) => tag,
+                    _ => unreachable!("values match their formats"),
+                }
+            }
+            _ => {
+                return Err(Error::Custom(format!(
+                    "Values of open enum {} should be serialized as a pair (tag, content bytes)",
+                    name
+                )))
+            }
+        };
+        match self.registry.get(name) {
+            Some(ContainerFormat::OpenEnum(formats)) if formats.contains_key(tag) => (),
+            _ => {
+                return Err(Error::Custom(format!(
+                    "Tag {} of open enum {} was not registered",
+                    tag, name
+                )))
+            }
+        }
+        if self.config.record_samples_for_newtype_structs {
+            samples.values.insert(name, value.clone());
+        }
+        Ok((Format::TypeName(name.into()), value))
+    }
+}
@@ -15,11 +15,41 @@ use serde::{ser, Serialize};
 pub(crate) struct Serializer<'a> {
     tracer: &'a mut Tracer,
     samples: &'a mut Samples,
+    context: Option<Context>,
+}
+
+/// What is known about the value being serialized, beyond the Serde callbacks.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Context {
+    /// The content of the newtype variant `variant` of the internally tagged enum `name`. If
+    /// the content is a struct, Serde inserts the tag as its first field.
+    InternalTag {
+        name: &'static str,
+        variant: &'static str,
+    },
+    /// A struct serialized as a map because of flattened fields.
+    FlattenedStruct(&'static str),
 }
 
 impl<'a> Serializer<'a> {
     pub(crate) fn new(tracer: &'a mut Tracer, samples: &'a mut Samples) -> Self {
-        Self { tracer, samples }
+        Self {
+            tracer,
+            samples,
+            context: None,
+        }
+    }
+
+    pub(crate) fn with_context(
+        tracer: &'a mut Tracer,
+        samples: &'a mut Samples,
+        context: Context,
+    ) -> Self {
+        Self {
+            tracer,
+            samples,
+            context: Some(context),
+        }
     }
 }
 
@@ -148,6 +178,11 @@ impl<'a> ser::Serializer for Serializer<'a> {
         T: ?Sized + Serialize,
     {
         let (format, value) = content.serialize(Serializer::new(self.tracer, self.samples))?;
+        if self.tracer.is_open_enum(name) {
+            return self
+                .tracer
+                .record_open_enum_value(self.samples, name, format, value);
+        }
         self.tracer.record_container(
             self.samples,
             name,
@@ -229,20 +264,32 @@ impl<'a> ser::Serializer for Serializer<'a> {
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        let struct_name = match self.context {
+            Some(Context::FlattenedStruct(name)) => Some(name),
+            _ => None,
+        };
         Ok(MapSerializer {
             tracer: self.tracer,
             samples: self.samples,
-            key_format: Format::unknown(),
-            value_format: Format::unknown(),
+            struct_name,
+            keys: Vec::new(),
             values: Vec::new(),
         })
     }
 
     fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        let internal_tag = match self.context {
+            Some(Context::InternalTag {
+                name: enum_name,
+                variant,
+            }) if !self.tracer.is_tagged_enum(name) => Some((enum_name, variant)),
+            _ => None,
+        };
         Ok(StructSerializer {
             tracer: self.tracer,
             samples: self.samples,
             name,
+            internal_tag,
             fields: Vec::new(),
             values: Vec::new(),
         })
@@ -404,9 +451,10 @@ pub struct MapSerializer<'a> {
     tracer: &'a mut Tracer,
     samples: &'a mut Samples,
 
-    key_format: Format,
-    value_format: Format,
-    values: Vec<Value>,
+    /// The struct being traced by `Tracer::trace_flattened_struct`, if any.
+    struct_name: Option<&'static str>,
+    keys: Vec<(Format, Value)>,
+    values: Vec<(Format, Value)>,
 }
 
 impl<'a> ser::SerializeMap for MapSerializer<'a> {
@@ -417,9 +465,8 @@ impl<'a> ser::SerializeMap for MapSerializer<'a> {
     where
         T: ?Sized + Serialize,
     {
-        let (format, value) = key.serialize(Serializer::new(self.tracer, self.samples))?;
-        self.key_format.unify(format)?;
-        self.values.push(value);
+        let key = key.serialize(Serializer::new(self.tracer, self.samples))?;
+        self.keys.push(key);
         Ok(())
     }
 
@@ -427,19 +474,35 @@ impl<'a> ser::SerializeMap for MapSerializer<'a> {
     where
         T: ?Sized + Serialize,
     {
-        let (format, value) = content.serialize(Serializer::new(self.tracer, self.samples))?;
-        self.value_format.unify(format)?;
+        let value = content.serialize(Serializer::new(self.tracer, self.samples))?;
         self.values.push(value);
         Ok(())
     }
 
     fn end(self) -> Result<(Format, Value)> {
+        let struct_name = match self.struct_name {
+            Some(name) => Some(name.to_string()),
+            None => self.tracer.find_flattened_struct(&self.keys),
+        };
+        if let Some(name) = struct_name {
+            return self
+                .tracer
+                .record_flattened_struct(&name, self.keys, self.values);
+        }
+        let mut key_format = Format::unknown();
+        let mut value_format = Format::unknown();
+        let mut values = Vec::new();
+        for ((format1, value1), (format2, value2)) in self.keys.into_iter().zip(self.values) {
+            key_format.unify(format1)?;
+            values.push(value1);
+            value_format.unify(format2)?;
+            values.push(value2);
+        }
         let format = Format::Map {
-            key: Box::new(self.key_format),
-            value: Box::new(self.value_format),
+            key: Box::new(key_format),
+            value: Box::new(value_format),
         };
-        let value = Value::Seq(self.values);
-        Ok((format, value))
+        Ok((format, Value::Seq(values)))
     }
 }
 
@@ -448,6 +511,9 @@ pub struct StructSerializer<'a> {
     samples: &'a mut Samples,
 
     name: &'static str,
+    /// The enum and the variant names, if the struct is the content of a newtype variant of
+    /// an internally tagged enum.
+    internal_tag: Option<(&'static str, &'static str)>,
     fields: Vec<Named<Format>>,
     values: Vec<Value>,
 }
@@ -469,7 +535,32 @@ impl<'a> ser::SerializeStruct for StructSerializer<'a> {
         Ok(())
     }
 
-    fn end(self) -> Result<(Format, Value)> {
+    fn end(mut self) -> Result<(Format, Value)> {
+        if self.tracer.is_tagged_enum(self.name) {
+            return self
+                .tracer
+                .record_tagged_struct(self.name, self.fields, self.values);
+        }
+        let internal_tag = match self.internal_tag {
+            Some((name, variant)) => self
+                .tracer
+                .internal_tag(name)
+                .map(|tag| (tag, variant.to_string())),
+            None => self
+                .tracer
+                .find_internal_tag(self.name, &self.fields, &self.values),
+        };
+        if let Some((tag, variant)) = internal_tag {
+            // Record the content without the tag, as if it was serialized on its own.
+            if self.fields.first().map(|field| &field.name) != Some(&tag) {
+                return Err(Error::Custom(format!(
+                    "Missing tag `{}` in content of internally tagged variant {}",
+                    tag, variant
+                )));
+            }
+            self.fields.remove(0);
+            self.values.remove(0);
+        }
         let format = ContainerFormat::Struct(self.fields);
         let value = Value::Seq(self.values);
         self.tracer.record_container(
@@ -0,0 +1,328 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tracing of enums with non-default Serde representations in human-readable mode.
+
+use crate::{
+    error::{Error, Result},
+    format::*,
+    ser::{Context, Serializer},
+    trace::{Samples, Tracer},
+    value::Value,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+impl Tracer {
+    /// Trace the serialization of an enum `name` with the given non-default
+    /// representation, e.g. `#[serde(tag = "type")]` or `#[serde(untagged)]`.
+    /// * `variants` must contain one sample value for each variant, listed in
+    ///   declaration order together with the (serialized) name of the variant.
+    /// * Variant indices are the positions in `variants`.
+    /// * The resulting container format is a `ContainerFormat::TaggedEnum`.
+    ///
+    /// Deserializing such enums requires `deserialize_any`, which is not supported. However,
+    /// once traced with this method, internally and adjacently tagged enums are recognized
+    /// when they occur in values passed to `trace_value`.
+    ///
+    /// This is only supported in human-readable mode (see `TracerConfig::is_human_readable`).
+    pub fn trace_tagged_enum<T>(
+        &mut self,
+        samples: &mut Samples,
+        name: &'static str,
+        representation: EnumRepresentation,
+        variants: &[(&'static str, T)],
+    ) -> Result<Format>
+    where
+        T: Serialize,
+    {
+        if !self.config.is_human_readable {
+            return Err(Error::NotSupported(
+                "tracing tagged enums outside of human-readable mode",
+            ));
+        }
+        let declared = variants
+            .iter()
+            .enumerate()
+            .map(|(index, (variant_name, _))| {
+                let variant = Named {
+                    name: variant_name.to_string(),
+                    value: VariantFormat::unknown(),
+                };
+                (index as u32, variant)
+            })
+            .collect();
+        self.registry
+            .entry(name.to_string())
+            .unify(ContainerFormat::TaggedEnum {
+                representation: representation.clone(),
+                variants: declared,
+            })?;
+
+        for (index, (variant_name, value)) in variants.iter().enumerate() {
+            let index = index as u32;
+            self.current_variant = Some((name, index));
+            let result = match &representation {
+                EnumRepresentation::Internal { .. } => {
+                    let context = Context::InternalTag {
+                        name,
+                        variant: variant_name,
+                    };
+                    value.serialize(Serializer::with_context(self, samples, context))
+                }
+                _ => value.serialize(Serializer::new(self, samples)),
+            };
+            self.current_variant = None;
+            let (format, _) = result?;
+            if format == Format::TypeName(name.into()) {
+                // Already recorded by `record_tagged_struct`.
+                continue;
+            }
+            let variant = match &representation {
+                EnumRepresentation::Internal { tag } => {
+                    self.internal_variant(tag, variant_name, format)?
+                }
+                EnumRepresentation::Untagged => self.variant_from_payload(variant_name, format),
+                EnumRepresentation::Adjacent { .. } => {
+                    return Err(Error::Custom(format!(
+                        "Adjacently tagged variant {}::{} was not serialized as a struct",
+                        name, variant_name
+                    )));
+                }
+            };
+            self.record_tagged_variant(name, index, variant_name, variant)?;
+        }
+        Ok(Format::TypeName(name.into()))
+    }
+
+    pub(crate) fn is_tagged_enum(&self, name: &str) -> bool {
+        matches!(
+            self.registry.get(name),
+            Some(ContainerFormat::TaggedEnum { .. })
+        )
+    }
+
+    pub(crate) fn is_adjacently_tagged_enum(&self, name: &str) -> bool {
+        matches!(
+            self.registry.get(name),
+            Some(ContainerFormat::TaggedEnum {
+                representation: EnumRepresentation::Adjacent { .. },
+                ..
+            })
+        )
+    }
+
+    /// Interpret a struct serialized under the name of a tagged enum.
+    pub(crate) fn record_tagged_struct(
+        &mut self,
+        name: &'static str,
+        mut fields: Vec<Named<Format>>,
+        mut values: Vec<Value>,
+    ) -> Result<(Format, Value)> {
+        let (representation, variants) = match self.registry.get(name) {
+            Some(ContainerFormat::TaggedEnum {
+                representation,
+                variants,
+            }) => (representation.clone(), variants),
+            _ => unreachable!("tagged enums are declared first"),
+        };
+        let (index, variant_name) = match &representation {
+            EnumRepresentation::Internal { tag } | EnumRepresentation::Adjacent { tag, .. } => {
+                let variant_name = match (fields.first(), values.first()) {
+                    (Some(field), Some(Value::Str(variant_name))) if field.name == *tag => {
+                        variant_name.clone()
+                    }
+                    _ => {
+                        return Err(Error::Custom(format!(
+                            "Missing tag `{}` in value of tagged enum {}",
+                            tag, name
+                        )))
+                    }
+                };
+                fields.remove(0);
+                values.remove(0);
+                match find_variant(variants, &variant_name) {
+                    Some(index) => (index, variant_name),
+                    None => {
+                        return Err(Error::Custom(format!(
+                            "Unknown variant {}::{}",
+                            name, variant_name
+                        )))
+                    }
+                }
+            }
+            EnumRepresentation::Untagged => {
+                match self.current_variant {
+                    Some((enum_name, index)) if enum_name == name => {
+                        (index, variants[&index].name.clone())
+                    }
+                    // Look for a struct variant with the same fields.
+                    _ => match find_struct_variant(variants, &fields) {
+                        Some(index) => (index, variants[&index].name.clone()),
+                        None => {
+                            return Err(Error::Custom(format!(
+                                "Cannot determine the variant of untagged enum {}",
+                                name
+                            )))
+                        }
+                    },
+                }
+            }
+        };
+        let (variant, value) = match &representation {
+            EnumRepresentation::Adjacent { content, .. } => match (fields.pop(), values.pop()) {
+                (None, None) => (VariantFormat::Unit, Value::Unit),
+                (Some(field), Some(value)) if field.name == *content && fields.is_empty() => {
+                    (self.variant_from_payload(&variant_name, field.value), value)
+                }
+                _ => {
+                    return Err(Error::Custom(format!(
+                        "Unexpected fields in value of adjacently tagged enum {}",
+                        name
+                    )))
+                }
+            },
+            _ if fields.is_empty() => (VariantFormat::Unit, Value::Unit),
+            _ => (VariantFormat::Struct(fields), Value::Seq(values)),
+        };
+        self.record_tagged_variant(name, index, &variant_name, variant)?;
+        Ok((
+            Format::TypeName(name.into()),
+            Value::Variant(index, Box::new(value)),
+        ))
+    }
+
+    fn record_tagged_variant(
+        &mut self,
+        name: &str,
+        index: u32,
+        variant_name: &str,
+        variant: VariantFormat,
+    ) -> Result<()> {
+        let representation = match self.registry.get(name) {
+            Some(ContainerFormat::TaggedEnum { representation, .. }) => representation.clone(),
+            _ => unreachable!("tagged enums are declared first"),
+        };
+        let mut variants = BTreeMap::new();
+        variants.insert(
+            index,
+            Named {
+                name: variant_name.into(),
+                value: variant,
+            },
+        );
+        self.registry
+            .entry(name.to_string())
+            .unify(ContainerFormat::TaggedEnum {
+                representation,
+                variants,
+            })
+    }
+
+    /// Compute the format of a variant from the serialized content of the variant.
+    fn variant_from_payload(&mut self, variant_name: &str, format: Format) -> VariantFormat {
+        match format {
+            Format::Unit => VariantFormat::Unit,
+            Format::Tuple(formats) => VariantFormat::Tuple(formats),
+            // Struct variants of adjacently tagged enums are serialized as structs named
+            // after the variant.
+            Format::TypeName(struct_name) if struct_name == variant_name => {
+                match self.registry.get(&struct_name) {
+                    Some(ContainerFormat::Struct(_)) => match self.registry.remove(&struct_name) {
+                        Some(ContainerFormat::Struct(fields)) => VariantFormat::Struct(fields),
+                        _ => unreachable!(),
+                    },
+                    _ => VariantFormat::NewType(Box::new(Format::TypeName(struct_name))),
+                }
+            }
+            format => VariantFormat::NewType(Box::new(format)),
+        }
+    }
+
+    /// Newtype variants of internally tagged enums insert the tag in the content of the variant,
+    /// which must be a struct. The tag was removed from the format of the struct during
+    /// serialization (see `find_internal_tag`).
+    fn internal_variant(
+        &self,
+        tag: &str,
+        variant_name: &str,
+        format: Format,
+    ) -> Result<VariantFormat> {
+        if let Format::TypeName(struct_name) = &format {
+            if let Some(ContainerFormat::Struct(_)) = self.registry.get(struct_name) {
+                return Ok(VariantFormat::NewType(Box::new(format)));
+            }
+        }
+        Err(Error::Custom(format!(
+            "Missing tag `{}` in content of internally tagged variant {}",
+            tag, variant_name
+        )))
+    }
+
+    /// The tag of the internally tagged enum `name`, if any.
+    pub(crate) fn internal_tag(&self, name: &str) -> Option<String> {
+        match self.registry.get(name) {
+            Some(ContainerFormat::TaggedEnum {
+                representation: EnumRepresentation::Internal { tag },
+                ..
+            }) => Some(tag.clone()),
+            _ => None,
+        }
+    }
+
+    /// Recognize the struct `struct_name` serialized as the content of a known newtype variant
+    /// of an internally tagged enum. Returns the tag and the name of the variant.
+    pub(crate) fn find_internal_tag(
+        &self,
+        struct_name: &str,
+        fields: &[Named<Format>],
+        values: &[Value],
+    ) -> Option<(String, String)> {
+        let (field, variant_name) = match (fields.first(), values.first()) {
+            (Some(field), Some(Value::Str(variant_name))) => (field, variant_name),
+            _ => return None,
+        };
+        let content = Format::TypeName(struct_name.to_string());
+        self.registry.values().find_map(|format| match format {
+            ContainerFormat::TaggedEnum {
+                representation: EnumRepresentation::Internal { tag },
+                variants,
+            } if *tag == field.name => variants
+                .values()
+                .any(|variant| {
+                    let mut value = variant.value.clone();
+                    value.reduce();
+                    variant.name == *variant_name
+                        && matches!(&value, VariantFormat::NewType(format) if **format == content)
+                })
+                .then(|| (tag.clone(), variant_name.clone())),
+            _ => None,
+        })
+    }
+}
+
+fn find_variant(variants: &BTreeMap<u32, Named<VariantFormat>>, name: &str) -> Option<u32> {
+    variants
+        .iter()
+        .find(|(_, variant)| variant.name == name)
+        .map(|(index, _)| *index)
+}
+
+fn find_struct_variant(
+    variants: &BTreeMap<u32, Named<VariantFormat>>,
+    fields: &[Named<Format>],
+) -> Option<u32> {
+    variants
+        .iter()
+        .find(|(_, variant)| match &variant.value {
+            VariantFormat::Struct(variant_fields) => {
+                variant_fields.len() == fields.len()
+                    && variant_fields
+                        .iter()
+                        .zip(fields)
+                        .all(|(x, y)| x.name == y.name)
+            }
+            _ => false,
+        })
+        .map(|(index, _)| *index)
+}
@@ -34,6 +34,9 @@ pub struct Tracer {
 
     /// Discriminant associated with each variant of each enum.
     pub(crate) discriminants: BTreeMap<(TypeId, VariantId<'static>), Discriminant>,
+
+    /// Tagged enum and variant index being traced by `trace_tagged_enum`, if any.
+    pub(crate) current_variant: Option<(&'static str, u32)>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -70,7 +73,7 @@ impl Samples {
 }
 
 /// Configuration object to create a tracer.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TracerConfig {
     pub(crate) is_human_readable: bool,
     pub(crate) record_samples_for_newtype_structs: bool,
@@ -190,6 +193,7 @@ impl Tracer {
             registry: BTreeMap::new(),
             incomplete_enums: BTreeMap::new(),
             discriminants: BTreeMap::new(),
+            current_variant: None,
         }
     }
 
@@ -363,6 +367,10 @@ impl Tracer {
         variant: VariantFormat,
         variant_value: Value,
     ) -> Result<(Format, Value)> {
+        if self.is_adjacently_tagged_enum(name) {
+            // This is the tag field of an adjacently tagged enum.
+            return Ok((Format::Str, Value::Str(variant_name.into())));
+        }
         let mut variants = BTreeMap::new();
         variants.insert(
             variant_index,
@@ -381,15 +389,9 @@ impl Tracer {
         samples: &'de Samples,
         name: &'static str,
     ) -> Option<(&'a ContainerFormat, &'de Value)> {
-        match samples.value(name) {
-            Some(value) => {
-                let format = self
-                    .registry
-                    .get(name)
-                    .expect("recorded containers should have a format already");
-                Some((format, value))
-            }
-            None => None,
-        }
+        let value = samples.value(name)?;
+        // Samples recorded by another tracer are ignored unless the container is known.
+        let format = self.registry.get(name)?;
+        Some((format, value))
     }
 }
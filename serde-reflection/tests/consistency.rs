@@ -0,0 +1,125 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
+use serde_reflection::{AsymmetryKind, Format, Samples, Tracer, TracerConfig, Value};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct Name(String);
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+enum Person {
+    NickName(Name),
+    FullName { first: Name, last: Name },
+}
+
+#[test]
+fn test_consistent_derived_types() {
+    let tracer = Tracer::new(TracerConfig::default());
+    let mut samples = Samples::new();
+    let values = vec![
+        Person::NickName(Name("Bob".into())),
+        Person::FullName {
+            first: Name("Alice".into()),
+            last: Name("Smith".into()),
+        },
+    ];
+    let asymmetries = tracer.check_consistency(&mut samples, &values).unwrap();
+    assert_eq!(asymmetries, Vec::new());
+    assert!(samples.value("Name").is_some());
+}
+
+#[derive(Deserialize, PartialEq, Eq, Debug, Clone)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+// Custom serializer that (mistakenly) widens the second field.
+impl Serialize for Point {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut s = serializer.serialize_struct("Point", 2)?;
+        s.serialize_field("x", &self.x)?;
+        s.serialize_field("y", &(self.y as u64))?;
+        s.end()
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct Line {
+    points: Vec<Point>,
+}
+
+#[test]
+fn test_asymmetric_custom_serializer() {
+    let tracer = Tracer::new(TracerConfig::default());
+    let mut samples = Samples::new();
+    let values = vec![Line {
+        points: vec![Point { x: 1, y: 2 }],
+    }];
+    let asymmetries = tracer.check_consistency(&mut samples, &values).unwrap();
+
+    assert_eq!(asymmetries.len(), 2);
+    assert_eq!(asymmetries[0].path, "Point.y");
+    assert_eq!(
+        asymmetries[0].kind,
+        AsymmetryKind::FormatMismatch {
+            serialized: Format::U64,
+            deserialized: Format::U32,
+        }
+    );
+    assert_eq!(
+        asymmetries[0].to_string(),
+        "Point.y: serialized as U64 but deserialized as U32"
+    );
+    assert_eq!(asymmetries[1].path, "[0].points[0].y");
+    assert_eq!(
+        asymmetries[1].kind,
+        AsymmetryKind::InvalidSample {
+            expected: "U32".into(),
+            value: Value::U64(2),
+        }
+    );
+}
+
+#[derive(Serialize, PartialEq, Eq, Debug, Clone)]
+enum Shape {
+    Circle(u32),
+    Square(u32),
+}
+
+// Custom deserializer that does not know about the variant `Square`.
+impl<'de> Deserialize<'de> for Shape {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Shape")]
+        enum Internal {
+            Circle(u32),
+        }
+        let Internal::Circle(x) = Internal::deserialize(deserializer)?;
+        Ok(Shape::Circle(x))
+    }
+}
+
+#[test]
+fn test_missing_variant() {
+    let tracer = Tracer::new(TracerConfig::default());
+    let mut samples = Samples::new();
+    let values = vec![Shape::Square(3)];
+    let asymmetries = tracer.check_consistency(&mut samples, &values).unwrap();
+
+    assert_eq!(asymmetries.len(), 2);
+    assert_eq!(asymmetries[0].path, "Shape::Square");
+    assert_eq!(asymmetries[0].kind, AsymmetryKind::SerializationOnly);
+    assert_eq!(asymmetries[1].path, "[0]");
+    assert!(matches!(
+        asymmetries[1].kind,
+        AsymmetryKind::InvalidSample { .. }
+    ));
+}
@@ -0,0 +1,139 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use serde_reflection::{
+    diff_registries, ContainerFormat, DifferenceKind, Dual, DualTracer, Format, FormatDifference,
+    Samples, TracerConfig,
+};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Address([u8; 4]);
+
+impl Serialize for Address {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            let hex = format!(
+                "{:02x}{:02x}{:02x}{:02x}",
+                self.0[0], self.0[1], self.0[2], self.0[3]
+            );
+            serializer.serialize_newtype_struct("Address", &hex)
+        } else {
+            serializer.serialize_newtype_struct("Address", &self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            #[derive(Deserialize)]
+            #[serde(rename = "Address")]
+            struct Value(String);
+            let value = Value::deserialize(deserializer)?;
+            let bytes = (0..value.0.len())
+                .step_by(2)
+                .filter_map(|i| u8::from_str_radix(value.0.get(i..i + 2)?, 16).ok())
+                .collect::<Vec<_>>();
+            let array = <[u8; 4]>::try_from(bytes)
+                .map_err(|_| serde::de::Error::custom("invalid address"))?;
+            Ok(Address(array))
+        } else {
+            #[derive(Deserialize)]
+            #[serde(rename = "Address")]
+            struct Value([u8; 4]);
+            Ok(Address(Value::deserialize(deserializer)?.0))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+struct Account {
+    address: Address,
+    balance: u64,
+}
+
+#[test]
+fn test_dual_tracing() {
+    let mut tracer = DualTracer::new(TracerConfig::default());
+    let mut samples = Dual::<Samples>::default();
+    let address = Address([0, 1, 2, 255]);
+    tracer.trace_value(&mut samples, &address).unwrap();
+    let values = tracer.trace_type::<Account>(&samples).unwrap();
+    assert_eq!(values.human_readable.1[0].address, address);
+    assert_eq!(values.binary.1[0].address, address);
+
+    let registries = tracer.registries().unwrap();
+    assert_eq!(
+        registries.human_readable.get("Address").unwrap(),
+        &ContainerFormat::NewTypeStruct(Box::new(Format::Str))
+    );
+    assert_eq!(
+        registries.get(false).get("Address").unwrap(),
+        &ContainerFormat::NewTypeStruct(Box::new(Format::TupleArray {
+            content: Box::new(Format::U8),
+            size: 4
+        }))
+    );
+    assert_eq!(
+        registries.human_readable.get("Account"),
+        registries.binary.get("Account")
+    );
+
+    let differences = registries.differences();
+    assert_eq!(
+        differences,
+        vec![FormatDifference {
+            path: "Address".into(),
+            kind: DifferenceKind::FormatMismatch {
+                left: Format::Str,
+                right: Format::TupleArray {
+                    content: Box::new(Format::U8),
+                    size: 4
+                },
+            },
+        }]
+    );
+}
+
+#[test]
+fn test_registry_diff() {
+    #[derive(Serialize, Deserialize)]
+    enum Choice {
+        A(u32),
+        B { x: Option<u8> },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Choice")]
+    enum OtherChoice {
+        A(u64),
+        C,
+    }
+
+    let mut tracer = DualTracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<Choice>().unwrap();
+    let left = tracer.registries().unwrap().binary;
+    let mut tracer = DualTracer::new(TracerConfig::default());
+    tracer.trace_simple_type::<OtherChoice>().unwrap();
+    let right = tracer.registries().unwrap().binary;
+
+    let differences = diff_registries(&left, &right);
+    assert_eq!(differences.len(), 2);
+    assert_eq!(
+        differences[0].to_string(),
+        "Choice::A: U32 vs U64".to_string()
+    );
+    assert_eq!(differences[1].path, "Choice::B");
+    assert!(matches!(
+        differences[1].kind,
+        DifferenceKind::VariantMismatch { .. }
+    ));
+    assert!(diff_registries(&left, &left).is_empty());
+}
@@ -0,0 +1,94 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use serde::Serialize;
+use serde_reflection::{ContainerFormat, Error, Format, Named, Samples, Tracer, TracerConfig};
+
+#[derive(Serialize)]
+struct Point {
+    x: u8,
+    y: String,
+}
+
+#[derive(Serialize)]
+struct Labeled {
+    label: String,
+    #[serde(flatten)]
+    point: Point,
+}
+
+#[derive(Serialize)]
+struct Wrapper {
+    content: Vec<Labeled>,
+}
+
+fn human_readable_tracer() -> Tracer {
+    Tracer::new(TracerConfig::default().is_human_readable(true))
+}
+
+fn sample() -> Labeled {
+    Labeled {
+        label: "a".into(),
+        point: Point {
+            x: 1,
+            y: "b".into(),
+        },
+    }
+}
+
+#[test]
+fn test_flattened_struct() {
+    let mut tracer = human_readable_tracer();
+    let mut samples = Samples::new();
+    let (format, _) = tracer
+        .trace_flattened_struct(&mut samples, "Labeled", &sample())
+        .unwrap();
+    assert_eq!(format, Format::TypeName("Labeled".into()));
+    // Values containing the struct are now recognized.
+    let wrapper = Wrapper {
+        content: vec![sample()],
+    };
+    tracer.trace_value(&mut samples, &wrapper).unwrap();
+
+    let registry = tracer.registry().unwrap();
+    assert_eq!(
+        registry.get("Labeled").unwrap(),
+        &ContainerFormat::FlattenedStruct(vec![
+            Named {
+                name: "label".into(),
+                value: Format::Str
+            },
+            Named {
+                name: "x".into(),
+                value: Format::U8
+            },
+            Named {
+                name: "y".into(),
+                value: Format::Str
+            },
+        ])
+    );
+    // The flattened struct is not a container of its own.
+    assert!(!registry.contains_key("Point"));
+
+    let yaml = serde_yaml::to_string(&registry).unwrap();
+    assert!(yaml.contains("FLATTENEDSTRUCT"));
+    let registry2 = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(registry, registry2);
+}
+
+#[test]
+fn test_flattened_struct_is_not_a_map() {
+    let mut tracer = human_readable_tracer();
+    let mut samples = Samples::new();
+    let result = tracer.trace_flattened_struct(&mut samples, "Point", &sample().point);
+    assert!(matches!(result, Err(Error::Custom(_))));
+}
+
+#[test]
+fn test_flattened_struct_requires_human_readable_mode() {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let mut samples = Samples::new();
+    let result = tracer.trace_flattened_struct(&mut samples, "Labeled", &sample());
+    assert!(matches!(result, Err(Error::NotSupported(_))));
+}
@@ -0,0 +1,141 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use serde_bytes::{ByteBuf, Bytes};
+use serde_reflection::{ContainerFormat, Error, Format, Named, Samples, Tracer, TracerConfig};
+use std::collections::BTreeMap;
+
+// A trait object serialized through a registry of implementations.
+trait Event {
+    fn tag(&self) -> &'static str;
+    fn encode(&self) -> Vec<u8>;
+}
+
+#[derive(Serialize, Deserialize)]
+struct Click {
+    x: u32,
+    y: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeyPress {
+    key: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Scroll(i64);
+
+macro_rules! impl_event {
+    ($name:ident) => {
+        impl Event for $name {
+            fn tag(&self) -> &'static str {
+                stringify!($name)
+            }
+
+            fn encode(&self) -> Vec<u8> {
+                bincode::serialize(self).unwrap()
+            }
+        }
+    };
+}
+
+impl_event!(Click);
+impl_event!(KeyPress);
+impl_event!(Scroll);
+
+impl Serialize for Box<dyn Event> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let content = self.encode();
+        serializer.serialize_newtype_struct("Event", &(self.tag(), Bytes::new(&content)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Box<dyn Event> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Event")]
+        struct Raw((String, ByteBuf));
+
+        let Raw((tag, content)) = Raw::deserialize(deserializer)?;
+        let event: Box<dyn Event> = match tag.as_str() {
+            "Click" => Box::new(bincode::deserialize::<Click>(&content).unwrap()),
+            "KeyPress" => Box::new(bincode::deserialize::<KeyPress>(&content).unwrap()),
+            _ => return Err(serde::de::Error::custom("unknown event")),
+        };
+        Ok(event)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Log {
+    events: Vec<Box<dyn Event>>,
+}
+
+#[test]
+fn test_open_enum() {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let mut samples = Samples::new();
+    tracer
+        .trace_open_enum_variant(&mut samples, "Event", "Click", &Click { x: 1, y: 2 })
+        .unwrap();
+    tracer
+        .trace_open_enum_variant(
+            &mut samples,
+            "Event",
+            "KeyPress",
+            &KeyPress { key: "a".into() },
+        )
+        .unwrap();
+
+    let log = Log {
+        events: vec![Box::new(Click { x: 3, y: 4 })],
+    };
+    tracer.trace_value(&mut samples, &log).unwrap();
+    tracer.trace_type::<Log>(&samples).unwrap();
+    // Deserialization tracing relies on the sample recorded above.
+    let (_, events) = tracer.trace_type::<Box<dyn Event>>(&samples).unwrap();
+    assert_eq!(events[0].tag(), "Click");
+
+    let registry = tracer.registry().unwrap();
+    let mut formats = BTreeMap::new();
+    formats.insert("Click".to_string(), Format::TypeName("Click".into()));
+    formats.insert("KeyPress".to_string(), Format::TypeName("KeyPress".into()));
+    assert_eq!(
+        registry.get("Event").unwrap(),
+        &ContainerFormat::OpenEnum(formats)
+    );
+    assert_eq!(
+        registry.get("Log").unwrap(),
+        &ContainerFormat::Struct(vec![Named {
+            name: "events".into(),
+            value: Format::Seq(Box::new(Format::TypeName("Event".into())))
+        }])
+    );
+
+    let yaml = serde_yaml::to_string(&registry).unwrap();
+    assert!(yaml.contains("OPENENUM"));
+    let registry2 = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(registry, registry2);
+}
+
+#[test]
+fn test_open_enum_with_unregistered_tag() {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let mut samples = Samples::new();
+    tracer
+        .trace_open_enum_variant(&mut samples, "Event", "Click", &Click { x: 1, y: 2 })
+        .unwrap();
+
+    let log = Log {
+        events: vec![Box::new(Scroll(-1))],
+    };
+    let result = tracer.trace_value(&mut samples, &log);
+    assert!(matches!(result, Err(Error::Custom(_))));
+}
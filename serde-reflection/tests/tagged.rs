@@ -0,0 +1,279 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use serde::Serialize;
+use serde_reflection::{
+    ContainerFormat, EnumRepresentation, Error, Format, Named, Samples, Tracer, TracerConfig,
+    VariantFormat,
+};
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Circle {
+    radius: u32,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum Internal {
+    A,
+    B(Circle),
+    C { x: u8, y: String },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    A,
+    B(u64),
+    C(u8, bool),
+    D { x: u8 },
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Untagged {
+    A,
+    B(u64),
+    C(u8, bool),
+    D { x: u8 },
+}
+
+#[derive(Serialize)]
+struct Wrapper {
+    content: Vec<Internal>,
+}
+
+fn human_readable_tracer() -> Tracer {
+    Tracer::new(TracerConfig::default().is_human_readable(true))
+}
+
+fn variants(list: Vec<(&str, VariantFormat)>) -> BTreeMap<u32, Named<VariantFormat>> {
+    list.into_iter()
+        .enumerate()
+        .map(|(index, (name, value))| {
+            let named = Named {
+                name: name.into(),
+                value,
+            };
+            (index as u32, named)
+        })
+        .collect()
+}
+
+#[test]
+fn test_internally_tagged_enum() {
+    let mut tracer = human_readable_tracer();
+    let mut samples = Samples::new();
+    tracer
+        .trace_tagged_enum(
+            &mut samples,
+            "Internal",
+            EnumRepresentation::Internal { tag: "type".into() },
+            &[
+                ("A", Internal::A),
+                ("B", Internal::B(Circle { radius: 1 })),
+                (
+                    "C",
+                    Internal::C {
+                        x: 1,
+                        y: "a".into(),
+                    },
+                ),
+            ],
+        )
+        .unwrap();
+    // Values containing the enum are now recognized.
+    let wrapper = Wrapper {
+        content: vec![Internal::A],
+    };
+    tracer.trace_value(&mut samples, &wrapper).unwrap();
+
+    let registry = tracer.registry().unwrap();
+    assert_eq!(
+        registry.get("Internal").unwrap(),
+        &ContainerFormat::TaggedEnum {
+            representation: EnumRepresentation::Internal { tag: "type".into() },
+            variants: variants(vec![
+                ("A", VariantFormat::Unit),
+                (
+                    "B",
+                    VariantFormat::NewType(Box::new(Format::TypeName("Circle".into())))
+                ),
+                (
+                    "C",
+                    VariantFormat::Struct(vec![
+                        Named {
+                            name: "x".into(),
+                            value: Format::U8
+                        },
+                        Named {
+                            name: "y".into(),
+                            value: Format::Str
+                        },
+                    ])
+                ),
+            ]),
+        }
+    );
+    assert_eq!(
+        registry.get("Circle").unwrap(),
+        &ContainerFormat::Struct(vec![Named {
+            name: "radius".into(),
+            value: Format::U32
+        }])
+    );
+
+    let yaml = serde_yaml::to_string(&registry).unwrap();
+    assert!(yaml.contains("TAGGEDENUM"));
+    let registry2 = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(registry, registry2);
+}
+
+#[test]
+fn test_internally_tagged_enum_keeps_content_structs() {
+    let mut tracer = human_readable_tracer();
+    let mut samples = Samples::new();
+    let circle = Circle { radius: 1 };
+    tracer.trace_value(&mut samples, &circle).unwrap();
+    // Tracing the enum twice does not alter the struct `Circle`.
+    for _ in 0..2 {
+        tracer
+            .trace_tagged_enum(
+                &mut samples,
+                "Internal",
+                EnumRepresentation::Internal { tag: "type".into() },
+                &[
+                    ("A", Internal::A),
+                    ("B", Internal::B(Circle { radius: 1 })),
+                    (
+                        "C",
+                        Internal::C {
+                            x: 1,
+                            y: "a".into(),
+                        },
+                    ),
+                ],
+            )
+            .unwrap();
+    }
+    // The tag is also recognized in the content of variants within other values.
+    let wrapper = Wrapper {
+        content: vec![Internal::B(Circle { radius: 2 })],
+    };
+    tracer.trace_value(&mut samples, &wrapper).unwrap();
+    tracer.trace_value(&mut samples, &circle).unwrap();
+
+    let registry = tracer.registry().unwrap();
+    assert_eq!(
+        registry.get("Circle").unwrap(),
+        &ContainerFormat::Struct(vec![Named {
+            name: "radius".into(),
+            value: Format::U32
+        }])
+    );
+    match registry.get("Internal").unwrap() {
+        ContainerFormat::TaggedEnum { variants, .. } => assert_eq!(
+            variants[&1].value,
+            VariantFormat::NewType(Box::new(Format::TypeName("Circle".into())))
+        ),
+        _ => panic!("Internal should be a tagged enum"),
+    }
+}
+
+#[test]
+fn test_adjacently_tagged_enum() {
+    let mut tracer = human_readable_tracer();
+    let mut samples = Samples::new();
+    let representation = EnumRepresentation::Adjacent {
+        tag: "t".into(),
+        content: "c".into(),
+    };
+    tracer
+        .trace_tagged_enum(
+            &mut samples,
+            "Adjacent",
+            representation.clone(),
+            &[
+                ("A", Adjacent::A),
+                ("B", Adjacent::B(1)),
+                ("C", Adjacent::C(1, true)),
+                ("D", Adjacent::D { x: 1 }),
+            ],
+        )
+        .unwrap();
+
+    let registry = tracer.registry().unwrap();
+    assert_eq!(
+        registry.get("Adjacent").unwrap(),
+        &ContainerFormat::TaggedEnum {
+            representation,
+            variants: variants(vec![
+                ("A", VariantFormat::Unit),
+                ("B", VariantFormat::NewType(Box::new(Format::U64))),
+                ("C", VariantFormat::Tuple(vec![Format::U8, Format::Bool])),
+                (
+                    "D",
+                    VariantFormat::Struct(vec![Named {
+                        name: "x".into(),
+                        value: Format::U8
+                    }])
+                ),
+            ]),
+        }
+    );
+    // The struct of variant `D` is not a container of its own.
+    assert_eq!(registry.len(), 1);
+}
+
+#[test]
+fn test_untagged_enum() {
+    let mut tracer = human_readable_tracer();
+    let mut samples = Samples::new();
+    tracer
+        .trace_tagged_enum(
+            &mut samples,
+            "Untagged",
+            EnumRepresentation::Untagged,
+            &[
+                ("A", Untagged::A),
+                ("B", Untagged::B(1)),
+                ("C", Untagged::C(1, true)),
+                ("D", Untagged::D { x: 1 }),
+            ],
+        )
+        .unwrap();
+
+    let registry = tracer.registry().unwrap();
+    assert_eq!(
+        registry.get("Untagged").unwrap(),
+        &ContainerFormat::TaggedEnum {
+            representation: EnumRepresentation::Untagged,
+            variants: variants(vec![
+                ("A", VariantFormat::Unit),
+                ("B", VariantFormat::NewType(Box::new(Format::U64))),
+                ("C", VariantFormat::Tuple(vec![Format::U8, Format::Bool])),
+                (
+                    "D",
+                    VariantFormat::Struct(vec![Named {
+                        name: "x".into(),
+                        value: Format::U8
+                    }])
+                ),
+            ]),
+        }
+    );
+}
+
+#[test]
+fn test_tagged_enum_requires_human_readable_mode() {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let mut samples = Samples::new();
+    let result = tracer.trace_tagged_enum(
+        &mut samples,
+        "Untagged",
+        EnumRepresentation::Untagged,
+        &[("A", Untagged::A)],
+    );
+    assert!(matches!(result, Err(Error::NotSupported(_))));
+}